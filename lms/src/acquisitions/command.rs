@@ -0,0 +1,7 @@
+pub mod allocate_budget_cmd;
+pub mod find_purchases_cmd;
+pub mod get_budget_cmd;
+pub mod get_purchase_cmd;
+pub mod order_purchase_cmd;
+pub mod receive_purchase_cmd;
+pub mod request_purchase_cmd;
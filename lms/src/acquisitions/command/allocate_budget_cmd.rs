@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::acquisitions::domain::AcquisitionService;
+use crate::acquisitions::dto::BudgetDto;
+use crate::core::command::{Command, CommandError};
+
+pub(crate) struct AllocateBudgetCommand {
+    acquisition_service: Box<dyn AcquisitionService>,
+}
+
+impl AllocateBudgetCommand {
+    pub(crate) fn new(acquisition_service: Box<dyn AcquisitionService>) -> Self {
+        Self {
+            acquisition_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AllocateBudgetCommandRequest {
+    allocated_by: String,
+    amount: i64,
+}
+
+impl AllocateBudgetCommandRequest {
+    pub fn new(allocated_by: &str, amount: i64) -> Self {
+        Self {
+            allocated_by: allocated_by.to_string(),
+            amount,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AllocateBudgetCommandResponse {
+    pub budget: BudgetDto,
+}
+
+impl AllocateBudgetCommandResponse {
+    pub fn new(budget: BudgetDto) -> Self {
+        Self {
+            budget,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<AllocateBudgetCommandRequest, AllocateBudgetCommandResponse> for AllocateBudgetCommand {
+    async fn execute(&self, req: AllocateBudgetCommandRequest) -> Result<AllocateBudgetCommandResponse, CommandError> {
+        self.acquisition_service.allocate_budget(req.allocated_by.as_str(), req.amount)
+            .await.map_err(CommandError::from).map(AllocateBudgetCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::acquisitions::command::allocate_budget_cmd::{AllocateBudgetCommand, AllocateBudgetCommandRequest};
+    use crate::acquisitions::factory::create_acquisition_service;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_allocate_cmd(store: RepositoryStore) -> AllocateBudgetCommand {
+        let svc = create_acquisition_service(&Configuration::new("allocate_test"), store).await;
+        AllocateBudgetCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_allocate_budget() {
+        let store = test_store(module_path!(), "test_should_run_allocate_budget");
+        let allocate_cmd = build_allocate_cmd(store).await;
+        let mut admin = PartyEntity::new(PartyKind::Patron, "allocate_budget@example.com");
+        admin.group_roles = vec![Role::Admin.to_string()];
+        let _ = create_party_repository(store).await.create(&admin).await.expect("should create party");
+
+        let res = allocate_cmd.execute(AllocateBudgetCommandRequest::new(admin.party_id.as_str(), 500)).await.expect("should allocate budget");
+        assert_eq!("allocate_test", res.budget.branch_id.as_str());
+        assert!(res.budget.allocated >= 500);
+    }
+}
@@ -0,0 +1,107 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::acquisitions::domain::AcquisitionQueryService;
+use crate::acquisitions::dto::PurchaseRequestDto;
+use crate::core::command::{Command, CommandError};
+use crate::core::library::PurchaseStatus;
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+
+pub(crate) struct FindPurchasesCommand {
+    acquisition_service: Box<dyn AcquisitionQueryService>,
+}
+
+impl FindPurchasesCommand {
+    pub(crate) fn new(acquisition_service: Box<dyn AcquisitionQueryService>) -> Self {
+        Self {
+            acquisition_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindPurchasesCommandRequest {
+    pub(crate) status: Option<String>,
+    pub(crate) page: Option<String>,
+    pub(crate) page_size: Option<usize>,
+}
+
+impl FindPurchasesCommandRequest {
+    pub fn new(status: PurchaseStatus) -> Self {
+        Self {
+            status: Some(status.to_string()),
+            page: None,
+            page_size: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindPurchasesCommandResponse {
+    pub purchases: Vec<PurchaseRequestDto>,
+    pub next_page: Option<String>,
+}
+
+impl FindPurchasesCommandResponse {
+    pub fn new(purchases: Vec<PurchaseRequestDto>, next_page: Option<String>) -> Self {
+        Self {
+            purchases,
+            next_page,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<FindPurchasesCommandRequest, FindPurchasesCommandResponse> for FindPurchasesCommand {
+    async fn execute(&self, req: FindPurchasesCommandRequest) -> Result<FindPurchasesCommandResponse, CommandError> {
+        let status = req.status.map(PurchaseStatus::from).unwrap_or(PurchaseStatus::Requested);
+        self.acquisition_service.find_purchases_by_status(status, req.page.as_deref(),
+                                                          req.page_size.unwrap_or(DEFAULT_PAGE_SIZE))
+            .await.map_err(CommandError::from).map(|res| FindPurchasesCommandResponse::new(res.records, res.next_page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::acquisitions::command::find_purchases_cmd::{FindPurchasesCommand, FindPurchasesCommandRequest};
+    use crate::acquisitions::domain::AcquisitionService;
+    use crate::acquisitions::dto::PurchaseRequestDto;
+    use crate::acquisitions::factory::{create_acquisition_service, create_acquisition_query_service};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, PurchaseStatus, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn AcquisitionService> {
+        create_acquisition_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_find_cmd(store: RepositoryStore) -> FindPurchasesCommand {
+        let svc = create_acquisition_query_service(&Configuration::new("test"), store).await;
+        FindPurchasesCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_find_purchases() {
+        let store = test_store(module_path!(), "test_should_run_find_purchases");
+        let svc = build_svc(store).await;
+        let find_cmd = build_find_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "find_purchases@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let vendor = PartyEntity::new(PartyKind::Organization, "vendor@example.com");
+        let party_repo = create_party_repository(store).await;
+        let _ = party_repo.create(&librarian).await.expect("should create party");
+        let _ = party_repo.create(&vendor).await.expect("should create vendor");
+        let purchase = svc.request_purchase(&PurchaseRequestDto::new(
+            librarian.party_id.as_str(), "isbn", "title", 1, vendor.party_id.as_str())).await.expect("should request purchase");
+        let _ = svc.order(purchase.purchase_id.as_str()).await.expect("should order purchase");
+
+        let res = find_cmd.execute(FindPurchasesCommandRequest::new(PurchaseStatus::Ordered)).await.expect("should find purchases");
+        assert!(res.purchases.iter().any(|p| p.purchase_id == purchase.purchase_id));
+        assert!(res.purchases.iter().all(|p| p.purchase_status == PurchaseStatus::Ordered));
+    }
+}
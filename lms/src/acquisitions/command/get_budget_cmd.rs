@@ -0,0 +1,72 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::acquisitions::domain::AcquisitionQueryService;
+use crate::acquisitions::dto::BudgetDto;
+use crate::core::command::{Command, CommandError};
+
+pub(crate) struct GetBudgetCommand {
+    acquisition_service: Box<dyn AcquisitionQueryService>,
+}
+
+impl GetBudgetCommand {
+    pub(crate) fn new(acquisition_service: Box<dyn AcquisitionQueryService>) -> Self {
+        Self {
+            acquisition_service,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct GetBudgetCommandRequest {}
+
+impl GetBudgetCommandRequest {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetBudgetCommandResponse {
+    pub budget: BudgetDto,
+}
+
+impl GetBudgetCommandResponse {
+    pub fn new(budget: BudgetDto) -> Self {
+        Self {
+            budget,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<GetBudgetCommandRequest, GetBudgetCommandResponse> for GetBudgetCommand {
+    async fn execute(&self, _req: GetBudgetCommandRequest) -> Result<GetBudgetCommandResponse, CommandError> {
+        self.acquisition_service.budget_report()
+            .await.map_err(CommandError::from).map(GetBudgetCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::acquisitions::command::get_budget_cmd::{GetBudgetCommand, GetBudgetCommandRequest};
+    use crate::acquisitions::factory::create_acquisition_query_service;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_get_cmd(store: RepositoryStore) -> GetBudgetCommand {
+        let svc = create_acquisition_query_service(&Configuration::new("report_test"), store).await;
+        GetBudgetCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_get_budget() {
+        let store = test_store(module_path!(), "test_should_run_get_budget");
+        let get_cmd = build_get_cmd(store).await;
+        let res = get_cmd.execute(GetBudgetCommandRequest::new()).await.expect("should report budget");
+        assert_eq!("report_test", res.budget.branch_id.as_str());
+        assert_eq!(res.budget.allocated, res.budget.available + res.budget.committed + res.budget.spent);
+    }
+}
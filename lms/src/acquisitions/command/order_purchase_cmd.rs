@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::acquisitions::domain::AcquisitionService;
+use crate::acquisitions::dto::PurchaseRequestDto;
+use crate::core::command::{Command, CommandError};
+
+pub(crate) struct OrderPurchaseCommand {
+    acquisition_service: Box<dyn AcquisitionService>,
+}
+
+impl OrderPurchaseCommand {
+    pub(crate) fn new(acquisition_service: Box<dyn AcquisitionService>) -> Self {
+        Self {
+            acquisition_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct OrderPurchaseCommandRequest {
+    pub(crate) purchase_id: String,
+}
+
+impl OrderPurchaseCommandRequest {
+    pub fn new(purchase_id: &str) -> Self {
+        Self {
+            purchase_id: purchase_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct OrderPurchaseCommandResponse {
+    pub purchase: PurchaseRequestDto,
+}
+
+impl OrderPurchaseCommandResponse {
+    pub fn new(purchase: PurchaseRequestDto) -> Self {
+        Self {
+            purchase,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<OrderPurchaseCommandRequest, OrderPurchaseCommandResponse> for OrderPurchaseCommand {
+    async fn execute(&self, req: OrderPurchaseCommandRequest) -> Result<OrderPurchaseCommandResponse, CommandError> {
+        self.acquisition_service.order(req.purchase_id.as_str())
+            .await.map_err(CommandError::from).map(OrderPurchaseCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::acquisitions::command::order_purchase_cmd::{OrderPurchaseCommand, OrderPurchaseCommandRequest};
+    use crate::acquisitions::domain::AcquisitionService;
+    use crate::acquisitions::dto::PurchaseRequestDto;
+    use crate::acquisitions::factory::create_acquisition_service;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, PurchaseStatus, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn AcquisitionService> {
+        create_acquisition_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> OrderPurchaseCommand {
+        let svc = create_acquisition_service(&Configuration::new("test"), store).await;
+        OrderPurchaseCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_order_purchase() {
+        let store = test_store(module_path!(), "test_should_run_order_purchase");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "order_purchase@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let vendor = PartyEntity::new(PartyKind::Organization, "vendor@example.com");
+        let party_repo = create_party_repository(store).await;
+        let _ = party_repo.create(&librarian).await.expect("should create party");
+        let _ = party_repo.create(&vendor).await.expect("should create vendor");
+        let purchase = svc.request_purchase(&PurchaseRequestDto::new(
+            librarian.party_id.as_str(), "isbn", "title", 1, vendor.party_id.as_str())).await.expect("should request purchase");
+
+        let res = sut_cmd.execute(OrderPurchaseCommandRequest::new(purchase.purchase_id.as_str())).await.expect("should order purchase");
+        assert_eq!(PurchaseStatus::Ordered, res.purchase.purchase_status);
+        assert!(res.purchase.ordered_at.is_some());
+    }
+}
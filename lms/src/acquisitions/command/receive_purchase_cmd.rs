@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::acquisitions::domain::AcquisitionService;
+use crate::acquisitions::dto::PurchaseRequestDto;
+use crate::core::command::{Command, CommandError};
+
+pub(crate) struct ReceivePurchaseCommand {
+    acquisition_service: Box<dyn AcquisitionService>,
+}
+
+impl ReceivePurchaseCommand {
+    pub(crate) fn new(acquisition_service: Box<dyn AcquisitionService>) -> Self {
+        Self {
+            acquisition_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ReceivePurchaseCommandRequest {
+    pub(crate) purchase_id: String,
+}
+
+impl ReceivePurchaseCommandRequest {
+    pub fn new(purchase_id: &str) -> Self {
+        Self {
+            purchase_id: purchase_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ReceivePurchaseCommandResponse {
+    pub purchase: PurchaseRequestDto,
+}
+
+impl ReceivePurchaseCommandResponse {
+    pub fn new(purchase: PurchaseRequestDto) -> Self {
+        Self {
+            purchase,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<ReceivePurchaseCommandRequest, ReceivePurchaseCommandResponse> for ReceivePurchaseCommand {
+    async fn execute(&self, req: ReceivePurchaseCommandRequest) -> Result<ReceivePurchaseCommandResponse, CommandError> {
+        self.acquisition_service.receive(req.purchase_id.as_str())
+            .await.map_err(CommandError::from).map(ReceivePurchaseCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::acquisitions::command::receive_purchase_cmd::{ReceivePurchaseCommand, ReceivePurchaseCommandRequest};
+    use crate::acquisitions::domain::AcquisitionService;
+    use crate::acquisitions::dto::PurchaseRequestDto;
+    use crate::acquisitions::factory::create_acquisition_service;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, PurchaseStatus, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn AcquisitionService> {
+        create_acquisition_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> ReceivePurchaseCommand {
+        let svc = create_acquisition_service(&Configuration::new("test"), store).await;
+        ReceivePurchaseCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_receive_purchase() {
+        let store = test_store(module_path!(), "test_should_run_receive_purchase");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "receive_purchase@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let vendor = PartyEntity::new(PartyKind::Organization, "vendor@example.com");
+        let party_repo = create_party_repository(store).await;
+        let _ = party_repo.create(&librarian).await.expect("should create party");
+        let _ = party_repo.create(&vendor).await.expect("should create vendor");
+        let purchase = svc.request_purchase(&PurchaseRequestDto::new(
+            librarian.party_id.as_str(), "isbn", "title", 1, vendor.party_id.as_str())).await.expect("should request purchase");
+
+        let _ = svc.order(purchase.purchase_id.as_str()).await.expect("should order purchase");
+        let res = sut_cmd.execute(ReceivePurchaseCommandRequest::new(purchase.purchase_id.as_str())).await.expect("should receive purchase");
+        assert_eq!(PurchaseStatus::Cataloged, res.purchase.purchase_status);
+        assert_eq!(1, res.purchase.book_ids.len());
+    }
+}
@@ -0,0 +1,101 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::acquisitions::domain::AcquisitionService;
+use crate::acquisitions::dto::PurchaseRequestDto;
+use crate::core::command::{Command, CommandError};
+
+pub(crate) struct RequestPurchaseCommand {
+    acquisition_service: Box<dyn AcquisitionService>,
+}
+
+impl RequestPurchaseCommand {
+    pub(crate) fn new(acquisition_service: Box<dyn AcquisitionService>) -> Self {
+        Self {
+            acquisition_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RequestPurchaseCommandRequest {
+    requested_by: String,
+    isbn: String,
+    title: String,
+    quantity: i64,
+    vendor_id: String,
+    #[serde(default)]
+    unit_price: i64,
+}
+
+impl RequestPurchaseCommandRequest {
+    pub fn new(requested_by: &str, isbn: &str, title: &str, quantity: i64, vendor_id: &str) -> Self {
+        Self {
+            requested_by: requested_by.to_string(),
+            isbn: isbn.to_string(),
+            title: title.to_string(),
+            quantity,
+            vendor_id: vendor_id.to_string(),
+            unit_price: 0,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RequestPurchaseCommandResponse {
+    pub purchase: PurchaseRequestDto,
+}
+
+impl RequestPurchaseCommandResponse {
+    pub fn new(purchase: PurchaseRequestDto) -> Self {
+        Self {
+            purchase,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<RequestPurchaseCommandRequest, RequestPurchaseCommandResponse> for RequestPurchaseCommand {
+    async fn execute(&self, req: RequestPurchaseCommandRequest) -> Result<RequestPurchaseCommandResponse, CommandError> {
+        let mut purchase = PurchaseRequestDto::new(req.requested_by.as_str(), req.isbn.as_str(), req.title.as_str(),
+                                                   req.quantity, req.vendor_id.as_str());
+        purchase.unit_price = req.unit_price;
+        self.acquisition_service.request_purchase(&purchase)
+            .await.map_err(CommandError::from).map(RequestPurchaseCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::acquisitions::command::request_purchase_cmd::{RequestPurchaseCommand, RequestPurchaseCommandRequest};
+    use crate::acquisitions::factory::create_acquisition_service;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, PurchaseStatus, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_request_cmd(store: RepositoryStore) -> RequestPurchaseCommand {
+        let svc = create_acquisition_service(&Configuration::new("test"), store).await;
+        RequestPurchaseCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_request_purchase() {
+        let store = test_store(module_path!(), "test_should_run_request_purchase");
+        let request_cmd = build_request_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "request_purchase@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let vendor = PartyEntity::new(PartyKind::Organization, "vendor@example.com");
+        let party_repo = create_party_repository(store).await;
+        let _ = party_repo.create(&librarian).await.expect("should create party");
+        let _ = party_repo.create(&vendor).await.expect("should create vendor");
+
+        let res = request_cmd.execute(RequestPurchaseCommandRequest::new(
+            librarian.party_id.as_str(), "isbn", "title", 3, vendor.party_id.as_str())).await.expect("should request purchase");
+        assert_eq!(PurchaseStatus::Requested, res.purchase.purchase_status);
+        assert_eq!(3, res.purchase.quantity);
+    }
+}
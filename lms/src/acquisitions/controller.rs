@@ -0,0 +1,103 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use serde_json::{Value};
+use crate::acquisitions::command::allocate_budget_cmd::{AllocateBudgetCommand, AllocateBudgetCommandRequest, AllocateBudgetCommandResponse};
+use crate::acquisitions::command::find_purchases_cmd::{FindPurchasesCommand, FindPurchasesCommandRequest, FindPurchasesCommandResponse};
+use crate::acquisitions::command::get_budget_cmd::{GetBudgetCommand, GetBudgetCommandRequest, GetBudgetCommandResponse};
+use crate::acquisitions::command::get_purchase_cmd::{GetPurchaseCommand, GetPurchaseCommandRequest, GetPurchaseCommandResponse};
+use crate::acquisitions::command::order_purchase_cmd::{OrderPurchaseCommand, OrderPurchaseCommandRequest, OrderPurchaseCommandResponse};
+use crate::acquisitions::command::receive_purchase_cmd::{ReceivePurchaseCommand, ReceivePurchaseCommandRequest, ReceivePurchaseCommandResponse};
+use crate::acquisitions::command::request_purchase_cmd::{RequestPurchaseCommand, RequestPurchaseCommandRequest, RequestPurchaseCommandResponse};
+use crate::acquisitions::domain::{AcquisitionQueryService, AcquisitionService};
+use crate::acquisitions::factory;
+use crate::core::controller::{AppState, command_bus, json_to_server_error, with_common_layers, ServerError};
+use crate::utils::ddb::{build_db_client, create_key_table, create_table};
+
+async fn build_service(state: AppState) -> Box<dyn AcquisitionService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_table(&client, state.store.table_name("purchases").as_str(), "purchase_id", "purchase_status", "vendor_id").await;
+    let _ = create_key_table(&client, state.store.table_name("budgets").as_str(), "branch_id").await;
+    factory::create_acquisition_service(&state.config, state.store).await
+}
+
+async fn build_query_service(state: AppState) -> Box<dyn AcquisitionQueryService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_table(&client, state.store.table_name("purchases").as_str(), "purchase_id", "purchase_status", "vendor_id").await;
+    let _ = create_key_table(&client, state.store.table_name("budgets").as_str(), "branch_id").await;
+    factory::create_acquisition_query_service(&state.config, state.store).await
+}
+
+pub(crate) async fn request_purchase(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<Json<RequestPurchaseCommandResponse>, ServerError> {
+    let req: RequestPurchaseCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res = command_bus().register(RequestPurchaseCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn find_purchase_by_id(
+    State(state): State<AppState>,
+    Path(purchase_id): Path<String>) -> Result<Json<GetPurchaseCommandResponse>, ServerError> {
+    let req = GetPurchaseCommandRequest { purchase_id };
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(GetPurchaseCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn find_purchases(
+    State(state): State<AppState>,
+    Query(req): Query<FindPurchasesCommandRequest>) -> Result<Json<FindPurchasesCommandResponse>, ServerError> {
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(FindPurchasesCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn order_purchase(
+    State(state): State<AppState>,
+    Path(purchase_id): Path<String>) -> Result<Json<OrderPurchaseCommandResponse>, ServerError> {
+    let req = OrderPurchaseCommandRequest { purchase_id };
+    let svc = build_service(state).await;
+    let res = command_bus().register(OrderPurchaseCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// receiving a purchase adds its copies to the catalog
+pub(crate) async fn receive_purchase(
+    State(state): State<AppState>,
+    Path(purchase_id): Path<String>) -> Result<Json<ReceivePurchaseCommandResponse>, ServerError> {
+    let req = ReceivePurchaseCommandRequest { purchase_id };
+    let svc = build_service(state).await;
+    let res = command_bus().register(ReceivePurchaseCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn allocate_budget(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<Json<AllocateBudgetCommandResponse>, ServerError> {
+    let req: AllocateBudgetCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res = command_bus().register(AllocateBudgetCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn get_budget(
+    State(state): State<AppState>) -> Result<Json<GetBudgetCommandResponse>, ServerError> {
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(GetBudgetCommand::new(svc)).dispatch(GetBudgetCommandRequest::new()).await?;
+    Ok(Json(res))
+}
+
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/acquisitions", post(request_purchase).get(find_purchases))
+        .route("/acquisitions/budget", get(get_budget).post(allocate_budget))
+        .route("/acquisitions/:id", get(find_purchase_by_id))
+        .route("/acquisitions/:id/order", post(order_purchase))
+        .route("/acquisitions/:id/receive", post(receive_purchase));
+    with_common_layers(router, state)
+}
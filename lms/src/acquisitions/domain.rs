@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+use crate::acquisitions::dto::{BudgetDto, PurchaseRequestDto};
+use crate::core::library::{LibraryResult, PaginatedResult, PurchaseStatus};
+
+pub mod model;
+pub mod query;
+pub mod service;
+
+// read side of acquisitions, queries never change purchases or budgets
+#[async_trait]
+pub(crate) trait AcquisitionQueryService: Sync + Send {
+    async fn find_purchase_by_id(&self, purchase_id: &str) -> LibraryResult<PurchaseRequestDto>;
+    async fn find_purchases_by_status(&self, status: PurchaseStatus,
+                                      page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<PurchaseRequestDto>>;
+    async fn budget_report(&self) -> LibraryResult<BudgetDto>;
+}
+
+#[async_trait]
+pub(crate) trait AcquisitionService: AcquisitionQueryService {
+    async fn request_purchase(&self, purchase: &PurchaseRequestDto) -> LibraryResult<PurchaseRequestDto>;
+    // ordering a purchase commits its cost against the budget of the branch
+    async fn order(&self, purchase_id: &str) -> LibraryResult<PurchaseRequestDto>;
+    // receiving a purchase adds copies of the title to the catalog
+    async fn receive(&self, purchase_id: &str) -> LibraryResult<PurchaseRequestDto>;
+    async fn allocate_budget(&self, allocated_by: &str, amount: i64) -> LibraryResult<BudgetDto>;
+}
@@ -0,0 +1,115 @@
+use chrono::{NaiveDateTime, Utc};
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+use crate::core::domain::Identifiable;
+use crate::core::library::PurchaseStatus;
+use crate::utils::date::serializer;
+
+// PurchaseRequestEntity abstracts request of a librarian to acquire copies of a title from a vendor
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct PurchaseRequestEntity {
+    pub purchase_id: String,
+    pub version: i64,
+    pub branch_id: String,
+    pub requested_by: String,
+    pub vendor_id: String,
+    pub isbn: String,
+    pub title: String,
+    pub quantity: i64,
+    // price of each copy in cents
+    #[serde(default)]
+    pub unit_price: i64,
+    pub purchase_status: PurchaseStatus,
+    // catalog entries of copies that were created when the purchase was received
+    #[serde(default)]
+    pub book_ids: Vec<String>,
+    pub ordered_at: Option<NaiveDateTime>,
+    pub received_at: Option<NaiveDateTime>,
+    pub cataloged_at: Option<NaiveDateTime>,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl PurchaseRequestEntity {
+    pub fn new(isbn: &str, title: &str, quantity: i64, vendor_id: &str) -> Self {
+        Self {
+            purchase_id: Uuid::new_v4().to_string(),
+            version: 0,
+            branch_id: Uuid::new_v4().to_string(),
+            requested_by: Uuid::new_v4().to_string(),
+            vendor_id: vendor_id.to_string(),
+            isbn: isbn.to_string(),
+            title: title.to_string(),
+            quantity,
+            unit_price: 0,
+            purchase_status: PurchaseStatus::Requested,
+            book_ids: vec![],
+            ordered_at: None,
+            received_at: None,
+            cataloged_at: None,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+
+    // cost that is committed against the budget when the purchase is ordered
+    pub fn total_cost(&self) -> i64 {
+        self.quantity * self.unit_price
+    }
+}
+
+// BudgetEntity tracks acquisition funds of a branch, available funds are reduced when purchases
+// are ordered and committed funds are moved to spent when purchases are received.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct BudgetEntity {
+    pub branch_id: String,
+    pub allocated: i64,
+    pub available: i64,
+    pub committed: i64,
+    pub spent: i64,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl BudgetEntity {
+    pub fn new(branch_id: &str) -> Self {
+        Self {
+            branch_id: branch_id.to_string(),
+            allocated: 0,
+            available: 0,
+            committed: 0,
+            spent: 0,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl Identifiable for PurchaseRequestEntity {
+    fn id(&self) -> String {
+        self.purchase_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::acquisitions::domain::model::PurchaseRequestEntity;
+    use crate::core::library::PurchaseStatus;
+
+    #[tokio::test]
+    async fn test_should_build_purchase_request() {
+        let purchase = PurchaseRequestEntity::new("isbn", "title", 2, "vendor1");
+        assert_eq!("isbn", purchase.isbn.as_str());
+        assert_eq!(2, purchase.quantity);
+        assert_eq!(PurchaseStatus::Requested, purchase.purchase_status);
+        assert_eq!(0, purchase.total_cost());
+    }
+}
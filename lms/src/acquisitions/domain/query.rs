@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+
+use crate::acquisitions::domain::AcquisitionQueryService;
+use crate::acquisitions::domain::model::BudgetEntity;
+use crate::acquisitions::dto::{BudgetDto, PurchaseRequestDto};
+use crate::acquisitions::repository::{BudgetRepository, PurchaseRepository};
+use crate::core::domain::Configuration;
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult, PurchaseStatus};
+
+pub(crate) struct AcquisitionQueryServiceImpl {
+    branch_id: String,
+    purchase_repository: Box<dyn PurchaseRepository>,
+    budget_repository: Box<dyn BudgetRepository>,
+}
+
+impl AcquisitionQueryServiceImpl {
+    pub(crate) fn new(config: &Configuration, purchase_repository: Box<dyn PurchaseRepository>,
+                      budget_repository: Box<dyn BudgetRepository>) -> Self {
+        Self {
+            branch_id: config.branch_id.to_string(),
+            purchase_repository,
+            budget_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl AcquisitionQueryService for AcquisitionQueryServiceImpl {
+    async fn find_purchase_by_id(&self, purchase_id: &str) -> LibraryResult<PurchaseRequestDto> {
+        self.purchase_repository.get(purchase_id).await.map(|p| PurchaseRequestDto::from(&p))
+    }
+
+    async fn find_purchases_by_status(&self, status: PurchaseStatus,
+                                      page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<PurchaseRequestDto>> {
+        let res = self.purchase_repository.find_by_status(status, page, page_size).await?;
+        let records = res.records.iter().map(PurchaseRequestDto::from).collect();
+        Ok(PaginatedResult::new(page, page_size, res.next_page, records))
+    }
+
+    async fn budget_report(&self) -> LibraryResult<BudgetDto> {
+        match self.budget_repository.get(self.branch_id.as_str()).await {
+            Ok(budget) => Ok(BudgetDto::from(&budget)),
+            Err(LibraryError::NotFound { .. }) => Ok(BudgetDto::from(&BudgetEntity::new(self.branch_id.as_str()))),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::acquisitions::domain::AcquisitionQueryService;
+    use crate::acquisitions::factory;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn AcquisitionQueryService> {
+        factory::create_acquisition_query_service(&Configuration::new("query_test"), store).await
+    }
+
+    #[tokio::test]
+    async fn test_should_report_empty_budget() {
+        let store = test_store(module_path!(), "test_should_report_empty_budget");
+        let query_svc = sut_svc(store).await;
+        let budget = query_svc.budget_report().await.expect("should report budget");
+        assert_eq!(0, budget.committed);
+        assert!(query_svc.find_purchase_by_id("unknown_purchase").await.is_err());
+    }
+}
@@ -0,0 +1,361 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::acquisitions::domain::{AcquisitionQueryService, AcquisitionService};
+use crate::acquisitions::domain::model::{BudgetEntity, PurchaseRequestEntity};
+use crate::acquisitions::dto::{BudgetDto, PurchaseRequestDto};
+use crate::acquisitions::repository::{BudgetRepository, PurchaseRepository};
+use crate::books::dto::BookDto;
+use crate::catalog::domain::CatalogService;
+use crate::core::domain::Configuration;
+use crate::core::events::DomainEvent;
+use crate::core::ids::PatronId;
+use crate::core::library::{BookStatus, LibraryError, LibraryResult, PaginatedResult, PurchaseStatus};
+use crate::gateway::events::EventPublisher;
+use crate::patrons::domain::PatronService;
+use crate::patrons::Patron;
+use crate::vendors::domain::VendorService;
+
+pub(crate) struct AcquisitionServiceImpl {
+    branch_id: String,
+    purchase_repository: Box<dyn PurchaseRepository>,
+    budget_repository: Box<dyn BudgetRepository>,
+    patron_service: Box<dyn PatronService>,
+    catalog_service: Box<dyn CatalogService>,
+    vendor_service: Box<dyn VendorService>,
+    events_publisher: Box<dyn EventPublisher>,
+    query_service: Box<dyn AcquisitionQueryService>,
+}
+
+impl AcquisitionServiceImpl {
+    pub(crate) fn new(config: &Configuration, purchase_repository: Box<dyn PurchaseRepository>,
+                      budget_repository: Box<dyn BudgetRepository>,
+                      patron_service: Box<dyn PatronService>, catalog_service: Box<dyn CatalogService>,
+                      vendor_service: Box<dyn VendorService>, events_publisher: Box<dyn EventPublisher>,
+                      query_service: Box<dyn AcquisitionQueryService>) -> Self {
+        Self {
+            branch_id: config.branch_id.to_string(),
+            purchase_repository,
+            budget_repository,
+            patron_service,
+            catalog_service,
+            vendor_service,
+            events_publisher,
+            query_service,
+        }
+    }
+
+    // purchases can only be placed with active vendors
+    async fn validate_vendor(&self, vendor_id: &str) -> LibraryResult<()> {
+        let vendor = self.vendor_service.find_vendor_by_id(vendor_id).await?;
+        if !vendor.active {
+            return Err(LibraryError::validation(format!("vendor {} is not active",
+                                                        vendor_id).as_str(), Some("400".to_string())));
+        }
+        Ok(())
+    }
+
+    // moves purchase to next status and publishes event of the transition
+    async fn transition(&self, purchase: &mut PurchaseRequestEntity, next: PurchaseStatus) -> LibraryResult<()> {
+        if !purchase.purchase_status.can_transition_to(next) {
+            return Err(LibraryError::validation(format!("purchase {} cannot be moved from {} to {}",
+                                                        purchase.purchase_id, purchase.purchase_status, next).as_str(), Some("400".to_string())));
+        }
+        let now = Utc::now().naive_utc();
+        purchase.purchase_status = next;
+        match next {
+            PurchaseStatus::Ordered => purchase.ordered_at = Some(now),
+            PurchaseStatus::Received => purchase.received_at = Some(now),
+            PurchaseStatus::Cataloged => purchase.cataloged_at = Some(now),
+            PurchaseStatus::Requested => {}
+        }
+        self.purchase_repository.update(purchase).await?;
+        purchase.version += 1;
+        let dto = PurchaseRequestDto::from(&*purchase);
+        let _ = self.events_publisher.publish(&DomainEvent::updated(
+            format!("purchase_{}", next.to_string().to_lowercase()).as_str(), "acquisitions",
+            dto.purchase_id.as_str(), &HashMap::new(), &dto)?).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AcquisitionService for AcquisitionServiceImpl {
+    async fn request_purchase(&self, purchase: &PurchaseRequestDto) -> LibraryResult<PurchaseRequestDto> {
+        if purchase.quantity <= 0 {
+            return Err(LibraryError::validation("quantity must be positive", Some("400".to_string())));
+        }
+        if purchase.unit_price < 0 {
+            return Err(LibraryError::validation("unit price cannot be negative", Some("400".to_string())));
+        }
+        if purchase.isbn.is_empty() || purchase.title.is_empty() || purchase.vendor_id.is_empty() {
+            return Err(LibraryError::validation("isbn, title and vendor are required", Some("400".to_string())));
+        }
+        self.validate_vendor(purchase.vendor_id.as_str()).await?;
+        let requester = self.patron_service.find_patron_by_id(&PatronId::new(purchase.requested_by.as_str())).await?;
+        if !requester.is_librarian() && !requester.is_admin() {
+            return Err(LibraryError::validation(format!("patron {} cannot request purchases",
+                                                        purchase.requested_by).as_str(), Some("400".to_string())));
+        }
+        let mut entity = PurchaseRequestEntity::from(purchase);
+        entity.branch_id = self.branch_id.to_string();
+        entity.purchase_status = PurchaseStatus::Requested;
+        entity.book_ids = vec![];
+        self.purchase_repository.create(&entity).await?;
+        let purchase = PurchaseRequestDto::from(&entity);
+        let _ = self.events_publisher.publish(&DomainEvent::added(
+            "purchase_requested", "acquisitions", purchase.purchase_id.as_str(), &HashMap::new(), &purchase)?).await?;
+        Ok(purchase)
+    }
+
+    async fn order(&self, purchase_id: &str) -> LibraryResult<PurchaseRequestDto> {
+        let mut purchase = self.purchase_repository.get(purchase_id).await?;
+        self.validate_vendor(purchase.vendor_id.as_str()).await?;
+        if !purchase.purchase_status.can_transition_to(PurchaseStatus::Ordered) {
+            return Err(LibraryError::validation(format!("purchase {} is already {}",
+                                                        purchase_id, purchase.purchase_status).as_str(), Some("400".to_string())));
+        }
+        // orders exceeding remaining budget are rejected
+        let cost = purchase.total_cost();
+        if cost > 0 {
+            let _ = self.budget_repository.commit(purchase.branch_id.as_str(), cost).await?;
+        }
+        if let Err(err) = self.transition(&mut purchase, PurchaseStatus::Ordered).await {
+            if cost > 0 {
+                let _ = self.budget_repository.release(purchase.branch_id.as_str(), cost).await?;
+            }
+            return Err(err);
+        }
+        Ok(PurchaseRequestDto::from(&purchase))
+    }
+
+    async fn receive(&self, purchase_id: &str) -> LibraryResult<PurchaseRequestDto> {
+        let mut purchase = self.purchase_repository.get(purchase_id).await?;
+        self.transition(&mut purchase, PurchaseStatus::Received).await?;
+        let cost = purchase.total_cost();
+        if cost > 0 {
+            let _ = self.budget_repository.spend(purchase.branch_id.as_str(), cost).await?;
+        }
+        for _i in 0..purchase.quantity {
+            let book = BookDto::builder().isbn(purchase.isbn.as_str()).title(purchase.title.as_str())
+                .book_status(BookStatus::Available).build()?;
+            let book = self.catalog_service.add_book(&book).await?;
+            purchase.book_ids.push(book.book_id.into());
+        }
+        self.transition(&mut purchase, PurchaseStatus::Cataloged).await?;
+        Ok(PurchaseRequestDto::from(&purchase))
+    }
+
+    async fn allocate_budget(&self, allocated_by: &str, amount: i64) -> LibraryResult<BudgetDto> {
+        let allocator = self.patron_service.find_patron_by_id(&PatronId::new(allocated_by)).await?;
+        if !allocator.is_admin() {
+            return Err(LibraryError::validation(format!("patron {} cannot allocate budget",
+                                                        allocated_by).as_str(), Some("400".to_string())));
+        }
+        let budget = BudgetDto::from(&self.budget_repository.allocate(self.branch_id.as_str(), amount).await?);
+        let _ = self.events_publisher.publish(&DomainEvent::updated(
+            "budget_allocated", "acquisitions", budget.branch_id.as_str(), &HashMap::new(), &budget)?).await?;
+        Ok(budget)
+    }
+}
+
+#[async_trait]
+impl AcquisitionQueryService for AcquisitionServiceImpl {
+    async fn find_purchase_by_id(&self, purchase_id: &str) -> LibraryResult<PurchaseRequestDto> {
+        self.query_service.find_purchase_by_id(purchase_id).await
+    }
+
+    async fn find_purchases_by_status(&self, status: PurchaseStatus,
+                                      page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<PurchaseRequestDto>> {
+        self.query_service.find_purchases_by_status(status, page, page_size).await
+    }
+
+    async fn budget_report(&self) -> LibraryResult<BudgetDto> {
+        self.query_service.budget_report().await
+    }
+}
+
+impl From<&BudgetEntity> for BudgetDto {
+    fn from(other: &BudgetEntity) -> BudgetDto {
+        BudgetDto {
+            branch_id: other.branch_id.to_string(),
+            allocated: other.allocated,
+            available: other.available,
+            committed: other.committed,
+            spent: other.spent,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+impl From<&PurchaseRequestDto> for PurchaseRequestEntity {
+    fn from(other: &PurchaseRequestDto) -> PurchaseRequestEntity {
+        PurchaseRequestEntity {
+            purchase_id: other.purchase_id.to_string(),
+            version: other.version,
+            branch_id: other.branch_id.to_string(),
+            requested_by: other.requested_by.to_string(),
+            vendor_id: other.vendor_id.to_string(),
+            isbn: other.isbn.to_string(),
+            title: other.title.to_string(),
+            quantity: other.quantity,
+            unit_price: other.unit_price,
+            purchase_status: other.purchase_status,
+            book_ids: other.book_ids.clone(),
+            ordered_at: other.ordered_at,
+            received_at: other.received_at,
+            cataloged_at: other.cataloged_at,
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+impl From<&PurchaseRequestEntity> for PurchaseRequestDto {
+    fn from(other: &PurchaseRequestEntity) -> PurchaseRequestDto {
+        PurchaseRequestDto {
+            purchase_id: other.purchase_id.to_string(),
+            version: other.version,
+            branch_id: other.branch_id.to_string(),
+            requested_by: other.requested_by.to_string(),
+            vendor_id: other.vendor_id.to_string(),
+            isbn: other.isbn.to_string(),
+            title: other.title.to_string(),
+            quantity: other.quantity,
+            unit_price: other.unit_price,
+            purchase_status: other.purchase_status,
+            book_ids: other.book_ids.clone(),
+            ordered_at: other.ordered_at,
+            received_at: other.received_at,
+            cataloged_at: other.cataloged_at,
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::acquisitions::domain::AcquisitionService;
+    use crate::acquisitions::dto::PurchaseRequestDto;
+    use crate::acquisitions::factory;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, PurchaseStatus, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::parties::repository::PartyRepository;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn AcquisitionService> {
+        factory::create_acquisition_service(&Configuration::new("test"), store).await
+    }
+
+    async fn budget_svc(store: RepositoryStore) -> Box<dyn AcquisitionService> {
+        factory::create_acquisition_service(&Configuration::new("budget_test"), store).await
+    }
+
+    async fn party_repo(store: RepositoryStore) -> Box<dyn PartyRepository> {
+        create_party_repository(store).await
+    }
+
+    async fn add_party(store: RepositoryStore, email: &str, role: Role) -> PartyEntity {
+        let mut party = PartyEntity::new(PartyKind::Patron, email);
+        party.group_roles = vec![role.to_string()];
+        let _ = party_repo(store).await.create(&party).await.expect("should create party");
+        party
+    }
+
+    async fn add_vendor(store: RepositoryStore, email: &str, active: bool) -> PartyEntity {
+        let mut vendor = PartyEntity::new(PartyKind::Organization, email);
+        vendor.organization_name = "Books Inc".to_string();
+        vendor.active = active;
+        let _ = party_repo(store).await.create(&vendor).await.expect("should create vendor");
+        vendor
+    }
+
+    #[tokio::test]
+    async fn test_should_request_order_receive_purchase() {
+        let store = test_store(module_path!(), "test_should_request_order_receive_purchase");
+        let acquisition_svc = sut_svc(store).await;
+        let librarian = add_party(store, "librarian@example.com", Role::Librarian).await;
+        let vendor = add_vendor(store, "vendor@example.com", true).await;
+
+        let purchase = PurchaseRequestDto::new(librarian.party_id.as_str(), "isbn_purchase", "new title", 2, vendor.party_id.as_str());
+        let purchase = acquisition_svc.request_purchase(&purchase).await.expect("should request purchase");
+        assert_eq!(PurchaseStatus::Requested, purchase.purchase_status);
+        // purchase must be ordered before it can be received
+        assert!(acquisition_svc.receive(purchase.purchase_id.as_str()).await.is_err());
+
+        let ordered = acquisition_svc.order(purchase.purchase_id.as_str()).await.expect("should order");
+        assert_eq!(PurchaseStatus::Ordered, ordered.purchase_status);
+        let cataloged = acquisition_svc.receive(purchase.purchase_id.as_str()).await.expect("should receive");
+        assert_eq!(PurchaseStatus::Cataloged, cataloged.purchase_status);
+        assert_eq!(2, cataloged.book_ids.len());
+        assert!(cataloged.received_at.is_some());
+
+        let loaded = acquisition_svc.find_purchase_by_id(purchase.purchase_id.as_str()).await.expect("should find purchase");
+        assert_eq!(cataloged.book_ids, loaded.book_ids);
+    }
+
+    #[tokio::test]
+    async fn test_should_not_request_purchase_by_regular_patron() {
+        let store = test_store(module_path!(), "test_should_not_request_purchase_by_regular_patron");
+        let acquisition_svc = sut_svc(store).await;
+        let patron = add_party(store, "regular@example.com", Role::Regular).await;
+        let vendor = add_vendor(store, "vendor@example.com", true).await;
+
+        let purchase = PurchaseRequestDto::new(patron.party_id.as_str(), "isbn", "title", 1, vendor.party_id.as_str());
+        assert!(acquisition_svc.request_purchase(&purchase).await.is_err());
+        let purchase = PurchaseRequestDto::new(patron.party_id.as_str(), "isbn", "title", 0, vendor.party_id.as_str());
+        assert!(acquisition_svc.request_purchase(&purchase).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_not_request_purchase_from_inactive_vendor() {
+        let store = test_store(module_path!(), "test_should_not_request_purchase_from_inactive_vendor");
+        let acquisition_svc = sut_svc(store).await;
+        let librarian = add_party(store, "vendor_librarian@example.com", Role::Librarian).await;
+        let vendor = add_vendor(store, "inactive_vendor@example.com", false).await;
+
+        let purchase = PurchaseRequestDto::new(librarian.party_id.as_str(), "isbn", "title", 1, vendor.party_id.as_str());
+        assert!(acquisition_svc.request_purchase(&purchase).await.is_err());
+        // patrons cannot be used as vendors
+        let purchase = PurchaseRequestDto::new(librarian.party_id.as_str(), "isbn", "title", 1, librarian.party_id.as_str());
+        assert!(acquisition_svc.request_purchase(&purchase).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_enforce_budget_on_order() {
+        let store = test_store(module_path!(), "test_should_enforce_budget_on_order");
+        let acquisition_svc = budget_svc(store).await;
+        let admin = add_party(store, "budget_admin@example.com", Role::Admin).await;
+        let librarian = add_party(store, "budget_librarian@example.com", Role::Librarian).await;
+        let vendor = add_vendor(store, "budget_vendor@example.com", true).await;
+        // only admins can allocate budget
+        assert!(acquisition_svc.allocate_budget(librarian.party_id.as_str(), 1000).await.is_err());
+        let before = acquisition_svc.budget_report().await.expect("should report budget");
+        let budget = acquisition_svc.allocate_budget(admin.party_id.as_str(), 1000).await.expect("should allocate");
+        assert_eq!(before.available + 1000, budget.available);
+
+        let mut purchase = PurchaseRequestDto::new(librarian.party_id.as_str(), "isbn_budget", "budget title", 2, vendor.party_id.as_str());
+        purchase.unit_price = budget.available / 2;
+        let purchase = acquisition_svc.request_purchase(&purchase).await.expect("should request purchase");
+        let _ = acquisition_svc.order(purchase.purchase_id.as_str()).await.expect("should order");
+        let report = acquisition_svc.budget_report().await.expect("should report budget");
+        assert_eq!(budget.committed + purchase.quantity * purchase.unit_price, report.committed);
+
+        let mut expensive = PurchaseRequestDto::new(librarian.party_id.as_str(), "isbn_budget", "budget title", 1, vendor.party_id.as_str());
+        expensive.unit_price = report.available + 1;
+        let expensive = acquisition_svc.request_purchase(&expensive).await.expect("should request purchase");
+        assert!(acquisition_svc.order(expensive.purchase_id.as_str()).await.is_err());
+
+        let _ = acquisition_svc.receive(purchase.purchase_id.as_str()).await.expect("should receive");
+        let received = acquisition_svc.budget_report().await.expect("should report budget");
+        assert_eq!(report.committed - purchase.quantity * purchase.unit_price, received.committed);
+        assert_eq!(report.spent + purchase.quantity * purchase.unit_price, received.spent);
+    }
+}
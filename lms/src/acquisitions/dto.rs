@@ -0,0 +1,90 @@
+use chrono::{NaiveDateTime, Utc};
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+use crate::core::domain::Identifiable;
+use crate::core::library::PurchaseStatus;
+use crate::utils::date::serializer;
+
+// PurchaseRequestDto abstracts data transfer object for acquisition of new titles
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct PurchaseRequestDto {
+    pub purchase_id: String,
+    pub version: i64,
+    pub branch_id: String,
+    pub requested_by: String,
+    pub vendor_id: String,
+    pub isbn: String,
+    pub title: String,
+    pub quantity: i64,
+    // price of each copy in cents
+    #[serde(default)]
+    pub unit_price: i64,
+    pub purchase_status: PurchaseStatus,
+    pub book_ids: Vec<String>,
+    pub ordered_at: Option<NaiveDateTime>,
+    pub received_at: Option<NaiveDateTime>,
+    pub cataloged_at: Option<NaiveDateTime>,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl PurchaseRequestDto {
+    pub fn new(requested_by: &str, isbn: &str, title: &str, quantity: i64, vendor_id: &str) -> Self {
+        Self {
+            purchase_id: Uuid::new_v4().to_string(),
+            version: 0,
+            branch_id: Uuid::new_v4().to_string(),
+            requested_by: requested_by.to_string(),
+            vendor_id: vendor_id.to_string(),
+            isbn: isbn.to_string(),
+            title: title.to_string(),
+            quantity,
+            unit_price: 0,
+            purchase_status: PurchaseStatus::Requested,
+            book_ids: vec![],
+            ordered_at: None,
+            received_at: None,
+            cataloged_at: None,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+// BudgetDto reports acquisition funds of a branch
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct BudgetDto {
+    pub branch_id: String,
+    pub allocated: i64,
+    pub available: i64,
+    pub committed: i64,
+    pub spent: i64,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl Identifiable for PurchaseRequestDto {
+    fn id(&self) -> String {
+        self.purchase_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::acquisitions::dto::PurchaseRequestDto;
+    use crate::core::library::PurchaseStatus;
+
+    #[tokio::test]
+    async fn test_should_build_purchase_request() {
+        let purchase = PurchaseRequestDto::new("librarian1", "isbn", "title", 3, "vendor1");
+        assert_eq!("librarian1", purchase.requested_by.as_str());
+        assert_eq!(3, purchase.quantity);
+        assert_eq!(PurchaseStatus::Requested, purchase.purchase_status);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::acquisitions::domain::{AcquisitionQueryService, AcquisitionService};
+use crate::acquisitions::domain::query::AcquisitionQueryServiceImpl;
+use crate::acquisitions::domain::service::AcquisitionServiceImpl;
+use crate::acquisitions::factory;
+use crate::acquisitions::repository::{BudgetRepository, PurchaseRepository};
+use crate::acquisitions::repository::ddb_budget_repository::DDBBudgetRepository;
+use crate::acquisitions::repository::ddb_purchase_repository::DDBPurchaseRepository;
+use crate::catalog::factory::create_catalog_service;
+use crate::core::domain::Configuration;
+use crate::core::repository::RepositoryStore;
+use crate::gateway::factory::create_publisher;
+use crate::patrons::factory::create_patron_service;
+use crate::utils::ddb::{build_db_client, create_key_table, create_table};
+use crate::vendors::factory::create_vendor_service;
+
+pub(crate) async fn create_purchase_repository(store: RepositoryStore) -> Box<dyn PurchaseRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBPurchaseRepository::new(client, "purchases", "purchases_ndx"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("purchases");
+            let _ = create_table(&client, table_name.as_str(), "purchase_id", "purchase_status", "vendor_id").await;
+            Box::new(DDBPurchaseRepository::new(client, table_name.as_str(), store.table_name("purchases_ndx").as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_budget_repository(store: RepositoryStore) -> Box<dyn BudgetRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBBudgetRepository::new(client, "budgets"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("budgets");
+            let _ = create_key_table(&client, table_name.as_str(), "branch_id").await;
+            Box::new(DDBBudgetRepository::new(client, table_name.as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_acquisition_query_service(config: &Configuration, store: RepositoryStore) -> Box<dyn AcquisitionQueryService> {
+    let purchase_repo = factory::create_purchase_repository(store).await;
+    let budget_repo = factory::create_budget_repository(store).await;
+    Box::new(AcquisitionQueryServiceImpl::new(config, purchase_repo, budget_repo))
+}
+
+pub(crate) async fn create_acquisition_service(config: &Configuration, store: RepositoryStore) -> Box<dyn AcquisitionService> {
+    let purchase_repo = factory::create_purchase_repository(store).await;
+    let budget_repo = factory::create_budget_repository(store).await;
+    let patron_svc = create_patron_service(config, store).await;
+    let catalog_svc = create_catalog_service(config, store).await;
+    let vendor_svc = create_vendor_service(config, store).await;
+    let publisher = create_publisher(store.gateway_publisher()).await;
+    let query_svc = create_acquisition_query_service(config, store).await;
+    Box::new(AcquisitionServiceImpl::new(config, purchase_repo, budget_repo, patron_svc, catalog_svc, vendor_svc, publisher, query_svc))
+}
@@ -0,0 +1,25 @@
+pub mod ddb_budget_repository;
+pub mod ddb_purchase_repository;
+
+use async_trait::async_trait;
+use crate::acquisitions::domain::model::{BudgetEntity, PurchaseRequestEntity};
+use crate::core::library::{LibraryResult, PaginatedResult, PurchaseStatus};
+use crate::core::repository::Repository;
+
+
+#[async_trait]
+pub(crate) trait PurchaseRepository: Repository<PurchaseRequestEntity> {
+    async fn find_by_status(&self, status: PurchaseStatus,
+                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<PurchaseRequestEntity>>;
+}
+
+// BudgetRepository updates budget amounts atomically so that concurrent orders cannot exceed available funds
+#[async_trait]
+pub(crate) trait BudgetRepository: Sync + Send {
+    async fn get(&self, branch_id: &str) -> LibraryResult<BudgetEntity>;
+    // negative amount reduces allocation as long as funds are still available
+    async fn allocate(&self, branch_id: &str, amount: i64) -> LibraryResult<BudgetEntity>;
+    async fn commit(&self, branch_id: &str, amount: i64) -> LibraryResult<BudgetEntity>;
+    async fn release(&self, branch_id: &str, amount: i64) -> LibraryResult<BudgetEntity>;
+    async fn spend(&self, branch_id: &str, amount: i64) -> LibraryResult<BudgetEntity>;
+}
@@ -0,0 +1,169 @@
+use std::cmp;
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::{AttributeValue, ReturnValue};
+use chrono::Utc;
+
+use crate::acquisitions::domain::model::BudgetEntity;
+use crate::acquisitions::repository::BudgetRepository;
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::utils::ddb::{is_conditional_check_failed, parse_date_attribute, parse_number_attribute, parse_string_attribute, string_date};
+
+#[derive(Debug)]
+pub(crate) struct DDBBudgetRepository {
+    client: Client,
+    table_name: String,
+}
+
+impl DDBBudgetRepository {
+    pub(crate) fn new(client: Client, table_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+        }
+    }
+
+    async fn update_budget(&self, branch_id: &str, update_expr: &str, condition_expr: &str,
+                           values: HashMap<String, AttributeValue>, conflict: &str) -> LibraryResult<BudgetEntity> {
+        let now = Utc::now().naive_utc();
+        let table_name: &str = self.table_name.as_ref();
+        let mut values = values;
+        values.insert(":updated_at".to_string(), string_date(now));
+        self.client
+            .update_item()
+            .table_name(table_name)
+            .key("branch_id", AttributeValue::S(branch_id.to_string()))
+            .update_expression(format!("SET updated_at = :updated_at, created_at = if_not_exists(created_at, :updated_at) {}", update_expr))
+            .condition_expression(condition_expr)
+            .set_expression_attribute_values(Some(values))
+            .return_values(ReturnValue::AllNew)
+            .send()
+            .await.map_err(|err| {
+            if is_conditional_check_failed(&err) {
+                LibraryError::validation(format!("{} for {}", conflict, branch_id).as_str(), Some("400".to_string()))
+            } else {
+                LibraryError::from(err)
+            }
+        }).map(|res| {
+            res.attributes().map(BudgetEntity::from).unwrap_or_else(|| BudgetEntity::new(branch_id))
+        })
+    }
+}
+
+#[async_trait]
+impl BudgetRepository for DDBBudgetRepository {
+    async fn get(&self, branch_id: &str) -> LibraryResult<BudgetEntity> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .query()
+            .table_name(table_name)
+            .limit(1)
+            .consistent_read(true)
+            .key_condition_expression("branch_id = :branch_id")
+            .expression_attribute_values(":branch_id", AttributeValue::S(branch_id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).and_then(|req| {
+            if let Some(map) = req.items.as_ref().and_then(|items| items.first()) {
+                return Ok(BudgetEntity::from(map));
+            }
+            Err(LibraryError::not_found(format!("budget not found for {}", branch_id).as_str()))
+        })
+    }
+
+    async fn allocate(&self, branch_id: &str, amount: i64) -> LibraryResult<BudgetEntity> {
+        self.update_budget(branch_id, "ADD allocated :amount, available :amount",
+                           "attribute_not_exists(branch_id) OR available >= :min",
+                           HashMap::from([
+                               (":amount".to_string(), AttributeValue::N(amount.to_string())),
+                               (":min".to_string(), AttributeValue::N(cmp::max(0, -amount).to_string())),
+                           ]), "allocation cannot be reduced below committed and spent funds").await
+    }
+
+    async fn commit(&self, branch_id: &str, amount: i64) -> LibraryResult<BudgetEntity> {
+        self.update_budget(branch_id, "ADD available :negative, committed :amount",
+                           "attribute_exists(branch_id) AND available >= :amount",
+                           HashMap::from([
+                               (":amount".to_string(), AttributeValue::N(amount.to_string())),
+                               (":negative".to_string(), AttributeValue::N((-amount).to_string())),
+                           ]), "insufficient budget").await
+    }
+
+    async fn release(&self, branch_id: &str, amount: i64) -> LibraryResult<BudgetEntity> {
+        self.update_budget(branch_id, "ADD available :amount, committed :negative",
+                           "attribute_exists(branch_id) AND committed >= :amount",
+                           HashMap::from([
+                               (":amount".to_string(), AttributeValue::N(amount.to_string())),
+                               (":negative".to_string(), AttributeValue::N((-amount).to_string())),
+                           ]), "budget is not committed").await
+    }
+
+    async fn spend(&self, branch_id: &str, amount: i64) -> LibraryResult<BudgetEntity> {
+        self.update_budget(branch_id, "ADD spent :amount, committed :negative",
+                           "attribute_exists(branch_id) AND committed >= :amount",
+                           HashMap::from([
+                               (":amount".to_string(), AttributeValue::N(amount.to_string())),
+                               (":negative".to_string(), AttributeValue::N((-amount).to_string())),
+                           ]), "budget is not committed").await
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for BudgetEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        BudgetEntity {
+            branch_id: parse_string_attribute("branch_id", map).unwrap_or_else(|| String::from("")),
+            allocated: parse_number_attribute("allocated", map),
+            available: parse_number_attribute("available", map),
+            committed: parse_number_attribute("committed", map),
+            spent: parse_number_attribute("spent", map),
+            created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+
+    use crate::acquisitions::repository::BudgetRepository;
+    use crate::acquisitions::repository::ddb_budget_repository::DDBBudgetRepository;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::ddb::{build_db_client, create_key_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_key_table(&client, store.table_name("budgets").as_str(), "branch_id").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_allocate_commit_spend_budget() {
+        let store = test_store(module_path!(), "test_should_allocate_commit_spend_budget");
+        let repo = DDBBudgetRepository::new(build_client(store).await, store.table_name("budgets").as_str());
+        assert!(repo.get("budget_branch").await.is_err());
+        assert!(repo.commit("budget_branch", 100).await.is_err());
+
+        let budget = repo.allocate("budget_branch", 1000).await.expect("should allocate budget");
+        assert_eq!(1000, budget.allocated);
+        assert_eq!(1000, budget.available);
+
+        let budget = repo.commit("budget_branch", 600).await.expect("should commit budget");
+        assert_eq!(400, budget.available);
+        assert_eq!(600, budget.committed);
+        // orders exceeding available funds are rejected
+        assert!(repo.commit("budget_branch", 500).await.is_err());
+        // committed funds cannot be reduced from allocation
+        assert!(repo.allocate("budget_branch", -500).await.is_err());
+
+        let budget = repo.spend("budget_branch", 600).await.expect("should spend budget");
+        assert_eq!(0, budget.committed);
+        assert_eq!(600, budget.spent);
+        assert!(repo.release("budget_branch", 100).await.is_err());
+
+        let loaded = repo.get("budget_branch").await.expect("should get budget");
+        assert_eq!(budget, loaded);
+    }
+}
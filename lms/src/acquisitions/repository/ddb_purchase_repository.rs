@@ -0,0 +1,242 @@
+use std::cmp;
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::acquisitions::domain::model::PurchaseRequestEntity;
+use crate::acquisitions::repository::PurchaseRepository;
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult, PurchaseStatus};
+use crate::core::repository::Repository;
+use crate::utils::ddb::{add_filter_expr, from_ddb, opt_string_date, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, parse_string_set_attribute, string_date, to_ddb_page};
+
+#[derive(Debug)]
+pub struct DDBPurchaseRepository {
+    client: Client,
+    table_name: String,
+    index_name: String,
+}
+
+impl DDBPurchaseRepository {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl Repository<PurchaseRequestEntity> for DDBPurchaseRepository {
+    async fn create(&self, entity: &PurchaseRequestEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(purchase_id)")
+            .set_item(Some(parse_item(val)?))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn update(&self, entity: &PurchaseRequestEntity) -> LibraryResult<usize> {
+        let now = Utc::now().naive_utc();
+        let table_name: &str = self.table_name.as_ref();
+        let book_ids = entity.book_ids.iter().map(|id| AttributeValue::S(id.to_string())).collect();
+
+        self.client
+            .update_item()
+            .table_name(table_name)
+            .key("purchase_id", AttributeValue::S(entity.purchase_id.clone()))
+            .update_expression("SET version = :version, vendor_id = :vendor_id, quantity = :quantity, unit_price = :unit_price, purchase_status = :purchase_status, book_ids = :book_ids, ordered_at = :ordered_at, received_at = :received_at, cataloged_at = :cataloged_at, updated_at = :updated_at")
+            .expression_attribute_values(":old_version", AttributeValue::N(entity.version.to_string()))
+            .expression_attribute_values(":version", AttributeValue::N((entity.version + 1).to_string()))
+            .expression_attribute_values(":vendor_id", AttributeValue::S(entity.vendor_id.to_string()))
+            .expression_attribute_values(":quantity", AttributeValue::N(entity.quantity.to_string()))
+            .expression_attribute_values(":unit_price", AttributeValue::N(entity.unit_price.to_string()))
+            .expression_attribute_values(":purchase_status", AttributeValue::S(entity.purchase_status.to_string()))
+            .expression_attribute_values(":book_ids", AttributeValue::L(book_ids))
+            .expression_attribute_values(":ordered_at", opt_string_date(entity.ordered_at))
+            .expression_attribute_values(":received_at", opt_string_date(entity.received_at))
+            .expression_attribute_values(":cataloged_at", opt_string_date(entity.cataloged_at))
+            .expression_attribute_values(":updated_at", string_date(now))
+            .condition_expression("attribute_exists(version) AND version = :old_version")
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn get(&self, id: &str) -> LibraryResult<PurchaseRequestEntity> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .query()
+            .table_name(table_name)
+            .limit(2)
+            .consistent_read(true)
+            .key_condition_expression(
+                "purchase_id = :purchase_id",
+            )
+            .expression_attribute_values(
+                ":purchase_id",
+                AttributeValue::S(id.to_string()),
+            )
+            .send()
+            .await.map_err(LibraryError::from).and_then(|req| {
+            if let Some(items) = req.items {
+                if items.len() > 1 {
+                    return Err(LibraryError::database(format!("too many purchases for {}", id).as_str(), None, false));
+                } else if !items.is_empty() {
+                    if let Some(map) = items.first() {
+                        return Ok(PurchaseRequestEntity::from(map));
+                    }
+                }
+                Err(LibraryError::not_found(format!("purchase not found for {}", id).as_str()))
+            } else {
+                Err(LibraryError::not_found(format!("purchase not found for {}", id).as_str()))
+            }
+        })
+    }
+
+    async fn delete(&self, id: &str) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client.delete_item()
+            .table_name(table_name)
+            .key("purchase_id", AttributeValue::S(id.to_string()))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    // Note you cannot use certain reserved words per https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/ReservedWords.html
+    async fn query(&self, predicate: &HashMap<String, String>,
+                   page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<PurchaseRequestEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let exclusive_start_key = to_ddb_page(page, predicate);
+        let mut request = self.client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .limit(cmp::min(page_size, 500) as i32)
+            .consistent_read(false)
+            .set_exclusive_start_key(exclusive_start_key)
+            .expression_attribute_values(":purchase_status", AttributeValue::S(
+                predicate.get("purchase_status").unwrap_or(&PurchaseStatus::Requested.to_string()).to_string()
+            ));
+        // handle GSI keys first
+        let mut key_cond = String::new();
+        key_cond.push_str("purchase_status = :purchase_status");
+
+        if let Some(vendor_id) = predicate.get("vendor_id") {
+            key_cond.push_str(" AND vendor_id = :vendor_id");
+            request = request.expression_attribute_values(":vendor_id", AttributeValue::S(vendor_id.to_string()));
+        }
+        request = request.key_condition_expression(key_cond);
+        let mut filter_expr = String::new();
+        // then handle other filters
+        for (k, v) in predicate {
+            if k != "purchase_status" && k != "vendor_id" {
+                let ks = add_filter_expr(k.as_str(), &mut filter_expr);
+                request = request.expression_attribute_values(format!(":{}", ks).as_str(), AttributeValue::S(v.to_string()));
+            }
+        }
+        if !filter_expr.is_empty() {
+            request = request.filter_expression(filter_expr);
+        }
+        request
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(PurchaseRequestEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+}
+
+#[async_trait]
+impl PurchaseRepository for DDBPurchaseRepository {
+    async fn find_by_status(&self, status: PurchaseStatus,
+                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<PurchaseRequestEntity>> {
+        let predicate = HashMap::from([
+            ("purchase_status".to_string(), status.to_string()),
+        ]);
+        self.query(&predicate, page, page_size).await
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for PurchaseRequestEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        PurchaseRequestEntity {
+            purchase_id: parse_string_attribute("purchase_id", map).unwrap_or_else(|| String::from("")),
+            version: parse_number_attribute("version", map),
+            branch_id: parse_string_attribute("branch_id", map).unwrap_or_else(|| String::from("")),
+            requested_by: parse_string_attribute("requested_by", map).unwrap_or_else(|| String::from("")),
+            vendor_id: parse_string_attribute("vendor_id", map).unwrap_or_else(|| String::from("")),
+            isbn: parse_string_attribute("isbn", map).unwrap_or_else(|| String::from("")),
+            title: parse_string_attribute("title", map).unwrap_or_else(|| String::from("")),
+            quantity: parse_number_attribute("quantity", map),
+            unit_price: parse_number_attribute("unit_price", map),
+            purchase_status: PurchaseStatus::from(parse_string_attribute("purchase_status", map).unwrap_or_else(|| PurchaseStatus::Requested.to_string())),
+            book_ids: parse_string_set_attribute("book_ids", map),
+            ordered_at: parse_date_attribute("ordered_at", map),
+            received_at: parse_date_attribute("received_at", map),
+            cataloged_at: parse_date_attribute("cataloged_at", map),
+            created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+
+    use crate::acquisitions::domain::model::PurchaseRequestEntity;
+    use crate::acquisitions::repository::ddb_purchase_repository::DDBPurchaseRepository;
+    use crate::acquisitions::repository::PurchaseRepository;
+    use crate::core::library::PurchaseStatus;
+    use crate::core::repository::{Repository, RepositoryStore};
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("purchases").as_str(), "purchase_id", "purchase_status", "vendor_id").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_create_get_purchase() {
+        let store = test_store(module_path!(), "test_should_create_get_purchase");
+        let purchase_repo = DDBPurchaseRepository::new(build_client(store).await, store.table_name("purchases").as_str(), store.table_name("purchases_ndx").as_str());
+        let purchase = PurchaseRequestEntity::new("isbn", "title", 2, "vendor1");
+        let size = purchase_repo.create(&purchase).await.expect("should create purchase");
+        assert_eq!(1, size);
+
+        let loaded = purchase_repo.get(purchase.purchase_id.as_str()).await.expect("should return purchase");
+        assert_eq!(purchase.purchase_id, loaded.purchase_id);
+        assert_eq!(2, loaded.quantity);
+    }
+
+    #[tokio::test]
+    async fn test_should_create_update_find_purchase() {
+        let store = test_store(module_path!(), "test_should_create_update_find_purchase");
+        let purchase_repo = DDBPurchaseRepository::new(build_client(store).await, store.table_name("purchases").as_str(), store.table_name("purchases_ndx").as_str());
+        let mut purchase = PurchaseRequestEntity::new("isbn", "title", 2, "vendor2");
+        let _ = purchase_repo.create(&purchase).await.expect("should create purchase");
+
+        purchase.purchase_status = PurchaseStatus::Cataloged;
+        purchase.book_ids = vec!["book1".to_string(), "book2".to_string()];
+        let size = purchase_repo.update(&purchase).await.expect("should update purchase");
+        assert_eq!(1, size);
+
+        let loaded = purchase_repo.get(purchase.purchase_id.as_str()).await.expect("should return purchase");
+        assert_eq!(PurchaseStatus::Cataloged, loaded.purchase_status);
+        assert_eq!(purchase.book_ids, loaded.book_ids);
+
+        let res = purchase_repo.find_by_status(PurchaseStatus::Cataloged, None, 100).await.expect("should find purchases");
+        assert!(res.records.iter().any(|p| p.purchase_id == purchase.purchase_id));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod anonymize;
+pub mod demo;
+pub mod dev;
+pub mod jobs;
+pub mod tables;
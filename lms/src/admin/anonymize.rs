@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::{AttributeValue, PutRequest, WriteRequest};
+use serde_json::Value;
+use tracing::log::info;
+use crate::admin::tables::TABLES;
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::core::repository::RepositoryStore;
+use crate::utils::ddb::{build_db_client, parse_json_attribute, ScanGuard};
+use crate::utils::signing::hmac_sha256;
+
+const MAX_BATCH_WRITE_ITEMS: usize = 25;
+const MAX_BATCH_WRITE_ATTEMPTS: usize = 3;
+
+// tables that are not copied because they hold secrets, uploaded documents or bookkeeping of the source environment
+const SKIPPED_TABLES: [&str; 7] = ["credentials", "api_keys", "party_documents", "processed_events",
+    "projection_tables", "party_counter_changes", "notification_deliveries"];
+
+// attributes with personal data, they are stored under the same names by all repositories
+const FIRST_NAME_ATTRIBUTES: [&str; 1] = ["first_name"];
+const LAST_NAME_ATTRIBUTES: [&str; 1] = ["last_name"];
+const EMAIL_ATTRIBUTES: [&str; 2] = ["email", "normalized_email"];
+const PHONE_ATTRIBUTES: [&str; 3] = ["home_phone", "cell_phone", "work_phone"];
+const ADDRESS_ATTRIBUTES: [&str; 1] = ["address"];
+// free text that staff or notifications may have written personal data into
+const FREE_TEXT_ATTRIBUTES: [&str; 3] = ["message", "reason", "status_reason"];
+
+const FIRST_NAMES: [&str; 16] = ["Alex", "Bailey", "Casey", "Dana", "Emery", "Finley", "Gray", "Harper",
+    "Indigo", "Jordan", "Kai", "Logan", "Morgan", "Noel", "Parker", "Quinn"];
+const LAST_NAMES: [&str; 16] = ["Adler", "Brooks", "Carter", "Dalton", "Ellis", "Foster", "Garcia", "Hayes",
+    "Irving", "Jensen", "Keller", "Lopez", "Mercer", "Nolan", "Ortiz", "Porter"];
+const STREETS: [&str; 8] = ["Maple", "Oak", "Cedar", "Pine", "Elm", "Birch", "Willow", "Spruce"];
+const CITIES: [&str; 8] = ["Springfield", "Riverton", "Fairview", "Lakeside", "Greenville", "Milton", "Ashford",
+    "Brookfield"];
+
+// Anonymizer replaces personal data with fake data derived from a keyed hash of the original value. The same value
+// gets the same fake in every item and table, e.g. the keys of party_emails still match the emails of parties, and
+// the salt keeps fakes from being matched against hashes of known emails.
+pub(crate) struct Anonymizer {
+    salt: String,
+}
+
+impl Anonymizer {
+    pub(crate) fn new(salt: &str) -> Self {
+        Self {
+            salt: salt.to_string(),
+        }
+    }
+
+    fn hash(&self, kind: &str, value: &str) -> u64 {
+        let digest = hmac_sha256(self.salt.as_str(), format!("{}:{}", kind, value).as_str());
+        digest.iter().take(8).fold(0u64, |n, b| (n << 8) | *b as u64)
+    }
+
+    fn pick(&self, kind: &str, value: &str, choices: &[&str]) -> String {
+        choices[(self.hash(kind, value) % choices.len() as u64) as usize].to_string()
+    }
+
+    pub(crate) fn first_name(&self, value: &str) -> String {
+        self.pick("first_name", value.trim(), &FIRST_NAMES)
+    }
+
+    pub(crate) fn last_name(&self, value: &str) -> String {
+        self.pick("last_name", value.trim(), &LAST_NAMES)
+    }
+
+    // emails are hashed after normalizing so that an email and its normalized form get the same fake
+    pub(crate) fn email(&self, value: &str) -> String {
+        format!("user.{:012x}@example.com", self.hash("email", value.trim().to_lowercase().as_str()) >> 16)
+    }
+
+    // numbers of the 555 exchange are reserved for fiction
+    pub(crate) fn phone(&self, value: &str) -> String {
+        let n = self.hash("phone", value.trim());
+        format!("555-{:03}-{:04}", n % 1000, (n / 1000) % 10000)
+    }
+
+    // street, city and zip code are replaced, state and country are kept for realistic reports, coordinates are
+    // removed because they locate the original address
+    pub(crate) fn address(&self, json: &str) -> LibraryResult<String> {
+        let mut address: Value = serde_json::from_str(json)?;
+        if let Value::Object(fields) = &mut address {
+            let key = fields.get("street_address").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+            let n = self.hash("address", key.as_str());
+            fields.insert("street_address".to_string(),
+                          Value::String(format!("{} {} St", 1 + n % 999, self.pick("street", key.as_str(), &STREETS))));
+            fields.insert("city".to_string(), Value::String(self.pick("city", key.as_str(), &CITIES)));
+            fields.insert("zip_code".to_string(), Value::String(format!("{:05}", (n >> 16) % 100000)));
+            fields.insert("latitude".to_string(), Value::Null);
+            fields.insert("longitude".to_string(), Value::Null);
+        }
+        Ok(serde_json::to_string(&address)?)
+    }
+
+    // returns the item with personal data replaced, empty values are kept so that e.g. parties without email stay so
+    pub(crate) fn anonymize_item(&self, item: &HashMap<String, AttributeValue>) -> LibraryResult<HashMap<String, AttributeValue>> {
+        let mut anonymized = item.clone();
+        for (name, value) in item {
+            if let AttributeValue::S(text) = value {
+                if text.is_empty() {
+                    continue;
+                }
+            }
+            let name = name.as_str();
+            let fake = if ADDRESS_ATTRIBUTES.contains(&name) {
+                match parse_json_attribute(name, item) {
+                    Some(json) if json != "{}" => self.address(json.as_str())?,
+                    _ => continue,
+                }
+            } else if let AttributeValue::S(text) = value {
+                if FIRST_NAME_ATTRIBUTES.contains(&name) {
+                    self.first_name(text)
+                } else if LAST_NAME_ATTRIBUTES.contains(&name) {
+                    self.last_name(text)
+                } else if EMAIL_ATTRIBUTES.contains(&name) {
+                    self.email(text)
+                } else if PHONE_ATTRIBUTES.contains(&name) {
+                    self.phone(text)
+                } else if FREE_TEXT_ATTRIBUTES.contains(&name) {
+                    "redacted".to_string()
+                } else {
+                    continue;
+                }
+            } else {
+                continue;
+            };
+            anonymized.insert(name.to_string(), AttributeValue::S(fake));
+        }
+        Ok(anonymized)
+    }
+}
+
+// CopySummary counts the items copied from a table of the source into the table of the target
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopySummary {
+    pub table: String,
+    pub copied: usize,
+}
+
+// copies items of all tables from the source store into the target store with personal data anonymized, tables with
+// secrets or bookkeeping of the source are skipped and the tables of the target must exist
+pub async fn copy_anonymized(source: RepositoryStore, target: RepositoryStore, salt: &str) -> LibraryResult<Vec<CopySummary>> {
+    if source == target {
+        return Err(LibraryError::validation("anonymized data must be copied into another store", None));
+    }
+    if salt.trim().is_empty() {
+        return Err(LibraryError::validation("salt of anonymized data is required", None));
+    }
+    let anonymizer = Anonymizer::new(salt);
+    let source_client = build_db_client(source).await;
+    let target_client = build_db_client(target).await;
+    let mut summaries = vec![];
+    for spec in TABLES.iter().filter(|spec| !SKIPPED_TABLES.contains(&spec.name)) {
+        let copied = copy_table(&source_client, &target_client, source.table_name(spec.name).as_str(),
+                                target.table_name(spec.name).as_str(), &anonymizer, &ScanGuard::new(source)).await?;
+        info!("copied {} anonymized items of {}", copied, spec.name);
+        summaries.push(CopySummary { table: spec.name.to_string(), copied });
+    }
+    Ok(summaries)
+}
+
+async fn copy_table(source: &Client, target: &Client, source_table: &str, target_table: &str,
+                    anonymizer: &Anonymizer, scan_guard: &ScanGuard) -> LibraryResult<usize> {
+    let mut copied = 0;
+    let mut last_key: Option<HashMap<String, AttributeValue>> = None;
+    loop {
+        scan_guard.check(source_table)?;
+        let res = source
+            .scan()
+            .table_name(source_table)
+            .set_exclusive_start_key(last_key)
+            .send()
+            .await?;
+        let mut items = vec![];
+        for item in res.items().unwrap_or_default() {
+            items.push(anonymizer.anonymize_item(item)?);
+        }
+        write_items(target, target_table, items.as_slice()).await?;
+        copied += items.len();
+        last_key = res.last_evaluated_key().cloned();
+        if last_key.is_none() {
+            break;
+        }
+    }
+    Ok(copied)
+}
+
+async fn write_items(client: &Client, table_name: &str, items: &[HashMap<String, AttributeValue>]) -> LibraryResult<()> {
+    for chunk in items.chunks(MAX_BATCH_WRITE_ITEMS) {
+        let mut requests: Vec<WriteRequest> = chunk.iter().map(|item| {
+            WriteRequest::builder().put_request(PutRequest::builder().set_item(Some(item.clone())).build()).build()
+        }).collect();
+        // throttled writes are returned as unprocessed items and retried
+        for _attempt in 0..MAX_BATCH_WRITE_ATTEMPTS {
+            let res = client
+                .batch_write_item()
+                .set_request_items(Some(HashMap::from([(table_name.to_string(), requests)])))
+                .send()
+                .await?;
+            requests = res.unprocessed_items()
+                .and_then(|items| items.get(table_name))
+                .cloned()
+                .unwrap_or_default();
+            if requests.is_empty() {
+                break;
+            }
+        }
+        if !requests.is_empty() {
+            return Err(LibraryError::unavailable(format!("failed to copy {} items into {}",
+                                                         requests.len(), table_name).as_str(), None, true));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::admin::anonymize::{copy_table, Anonymizer};
+    use crate::core::library::PartyKind;
+    use crate::core::repository::Repository;
+    use crate::parties::domain::model::{AddressEntity, PartyEntity};
+    use crate::parties::repository::ddb_party_repository::{DDBPartyRepository, COUNTER_CHANGES_TABLE, EMAILS_TABLE};
+    use crate::utils::ddb::{build_db_client, create_table, ScanGuard};
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_anonymize_deterministically() {
+        let anonymizer = Anonymizer::new("salt");
+        assert_eq!(anonymizer.email("Jane@Example.org "), anonymizer.email("jane@example.org"));
+        assert_ne!(anonymizer.email("jane@example.org"), anonymizer.email("john@example.org"));
+        assert_ne!(anonymizer.email("jane@example.org"), Anonymizer::new("other").email("jane@example.org"));
+        assert!(anonymizer.email("jane@example.org").ends_with("@example.com"));
+        assert_eq!(anonymizer.first_name("Jane"), anonymizer.first_name("Jane"));
+        assert!(anonymizer.phone("+1 206 555 1234").starts_with("555-"));
+        let address = anonymizer.address(r#"{"street_address": "1 Main", "city": "Seattle", "zip_code": "98101",
+            "state": "WA", "country": "US", "latitude": 47.6}"#).expect("should anonymize address");
+        assert!(!address.contains("Main") && !address.contains("Seattle") && !address.contains("47.6"));
+        assert!(address.contains("WA"));
+    }
+
+    #[tokio::test]
+    async fn test_should_copy_anonymized_parties() {
+        let store = test_store(module_path!(), "test_should_copy_anonymized_parties");
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("anonymize_source").as_str(), "party_id", "kind", "normalized_email").await;
+        let _ = create_table(&client, store.table_name("anonymize_target").as_str(), "party_id", "kind", "normalized_email").await;
+        let source = DDBPartyRepository::new(client.clone(), store.table_name("anonymize_source").as_str(), store.table_name("anonymize_source_ndx").as_str(),
+            store.table_name(EMAILS_TABLE).as_str(), store.table_name(COUNTER_CHANGES_TABLE).as_str());
+        let target = DDBPartyRepository::new(client.clone(), store.table_name("anonymize_target").as_str(), store.table_name("anonymize_target_ndx").as_str(),
+            store.table_name(EMAILS_TABLE).as_str(), store.table_name(COUNTER_CHANGES_TABLE).as_str());
+        let mut party = PartyEntity::new(PartyKind::Patron, "Jane.Doe@example.org");
+        party.first_name = "Jane".to_string();
+        party.last_name = "Doe".to_string();
+        party.cell_phone = Some("+1 206 555 1234".to_string());
+        party.address = Some(AddressEntity {
+            street_address: "1 Main".to_string(),
+            city: "Seattle".to_string(),
+            zip_code: "98101".to_string(),
+            state: "WA".to_string(),
+            ..Default::default()
+        });
+        let _ = source.create(&party).await.expect("should create party");
+
+        let copied = copy_table(&client, &client, "anonymize_source", "anonymize_target",
+                                &Anonymizer::new("salt"), &ScanGuard::default()).await.expect("should copy");
+        assert_eq!(1, copied);
+        let copy = target.get(party.party_id.as_str()).await.expect("should copy party");
+        assert_eq!(Anonymizer::new("salt").email(party.email.as_str()), copy.email);
+        assert_eq!(copy.email, copy.normalized_email);
+        assert_ne!("Jane", copy.first_name);
+        assert_ne!(party.cell_phone, copy.cell_phone);
+        let address = copy.address.expect("should keep address");
+        assert_ne!("Seattle", address.city);
+        assert_eq!("WA", address.state);
+        assert_eq!(party.num_holds, copy.num_holds);
+    }
+}
@@ -0,0 +1,444 @@
+use std::collections::BTreeMap;
+#[cfg(feature = "dev")]
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use lambda_http::Error;
+use lms::demo::run_demo;
+use lms::jobs::{allow_unbounded_scans, check_invariants, check_ledger, compact_events, compensate_stuck_sagas, copy_anonymized, email_shelf_list, export_schemas, export_shelf_list, publish_overdue_checkouts, purge_deleted_patrons, purge_expired_documents, queue_compact_events, queue_rebuild_projection, rebuild_projection, replay_events, send_due_soon_digests};
+use lms::dev::{create_dev_tables, Configuration};
+#[cfg(feature = "dev")]
+use lms::dev::{merged_router, seed_data, DynamoDBLocal};
+use lms::tables::{bootstrap_tables, describe_tables, AutoScaling, BootstrapOptions, TableBilling};
+use lms::{setup_tracing, RepositoryStore};
+#[cfg(feature = "dev")]
+use lms::AppState;
+#[cfg(feature = "dev")]
+use testcontainers::clients::Cli as Docker;
+
+// administration tasks that are run by operators or deployment pipelines instead of lambda functions
+#[derive(Parser)]
+#[command(name = "admin", about = "Administration tasks of the library management system")]
+struct Cli {
+    /// Runs against DynamoDB Local instead of the account of the environment
+    #[arg(long, global = true)]
+    local: bool,
+    /// Allows jobs to scan whole tables in the account, scans of production repositories are limited otherwise
+    #[arg(long, global = true)]
+    allow_scans: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Creates missing tables of all bounded contexts
+    CreateTables(TableArgs),
+    /// Describes settings of all tables and reports drift from the desired settings
+    Describe(TableArgs),
+    /// Starts DynamoDB Local, creates and seeds tables and serves the routes of all contexts
+    #[cfg(feature = "dev")]
+    Dev(DevArgs),
+    /// Runs a scripted scenario from adding a branch to returning a late book and prints its steps and events
+    Demo(DemoArgs),
+    /// Sends each patron one digest of their checkouts that are due soon, meant to be scheduled daily
+    Digest(DigestArgs),
+    /// Publishes book_overdue for overdue checkouts so that overdue counters of patrons are updated, meant to be
+    /// scheduled daily
+    Overdue(BranchArgs),
+    /// Purges verification documents of parties whose retention ended, meant to be scheduled daily
+    PurgeDocuments(BranchArgs),
+    /// Purges removed patrons whose retention ended along with their emails and reading history, meant to be
+    /// scheduled daily
+    PurgePatrons(BranchArgs),
+    /// Checks invariants of stored holds and checkouts and fails when any of them is broken
+    Invariants(BranchArgs),
+    /// Verifies that every journal entry of the ledger balances and that the fines of the branch reconcile with its
+    /// fine accounts, fails on any difference
+    Ledger(BranchArgs),
+    /// Rejects interlibrary loan requests that were not handled within ill_stuck_days and alerts loans stuck waiting
+    /// on the lending library, meant to be scheduled daily
+    Sagas(BranchArgs),
+    /// Replays events of the events table to the subscribers of the event triggers, old events are upcast to the
+    /// current schema of their payload
+    Replay(ReplayArgs),
+    /// Moves events older than the given days from the events table into the event archive of EVENTS_BUCKET, replays,
+    /// rebuilds and event history read both
+    CompactEvents(CompactArgs),
+    /// Rebuilds a projection into a new table from the events table and switches the projection over to it, an
+    /// interrupted rebuild resumes from its checkpoint when it is run again
+    RebuildProjection(RebuildArgs),
+    /// Prints physical copies within a call number range as CSV in shelf order for shelf-reading
+    ShelfList(ShelfListArgs),
+    /// Writes JSON schemas of the event envelope and of event payloads for code generation of consumers
+    Schemas(SchemasArgs),
+    /// Copies the tables of the account into DynamoDB Local with names, emails, phones and addresses replaced by
+    /// deterministic fake data
+    Anonymize(AnonymizeArgs),
+}
+
+#[cfg(feature = "dev")]
+#[derive(Args)]
+struct DevArgs {
+    /// Port of the local server
+    #[arg(long, default_value_t = 3000)]
+    port: u16,
+    /// Branch of the configuration
+    #[arg(long, default_value = "dev")]
+    branch: String,
+    /// Starts with empty tables instead of sample books and patrons
+    #[arg(long)]
+    skip_seed: bool,
+}
+
+#[derive(Args)]
+struct DemoArgs {
+    /// Branch of the configuration, the scenario registers the branch when it does not exist
+    #[arg(long, default_value = "demo")]
+    branch: String,
+}
+
+#[derive(Args)]
+struct DigestArgs {
+    /// Branch of the configuration
+    #[arg(long, default_value = "dev")]
+    branch: String,
+    /// Checkouts due within this many days are listed, defaults to due_soon_digest_days of the configuration
+    #[arg(long)]
+    within_days: Option<i64>,
+}
+
+#[derive(Args)]
+struct BranchArgs {
+    /// Branch of the configuration
+    #[arg(long, default_value = "dev")]
+    branch: String,
+}
+
+#[derive(Args)]
+struct ReplayArgs {
+    /// Replays only events with this name
+    #[arg(long)]
+    name: Option<String>,
+}
+
+#[derive(Args)]
+struct CompactArgs {
+    /// Events published more than this many days ago are archived
+    #[arg(long, default_value_t = 90)]
+    older_than_days: i64,
+    /// Queues the compaction for the task worker instead of running it
+    #[arg(long)]
+    background: bool,
+}
+
+#[derive(Args)]
+struct RebuildArgs {
+    /// Name of the projection such as co_checkouts or reading_history
+    #[arg(long)]
+    name: String,
+    /// Leaves the active table in place, a later run catches up with new events and switches over
+    #[arg(long)]
+    skip_switch: bool,
+    /// Queues the rebuild for the task worker instead of running it
+    #[arg(long)]
+    background: bool,
+}
+
+#[derive(Args)]
+struct ShelfListArgs {
+    /// Branch of the configuration
+    #[arg(long, default_value = "dev")]
+    branch: String,
+    /// Collection prefix of the call numbers such as REF, books without collection are listed when it is omitted
+    #[arg(long, default_value = "")]
+    collection: String,
+    /// First dewey class of the range
+    #[arg(long)]
+    from: String,
+    /// Last dewey class of the range, inclusive
+    #[arg(long)]
+    to: String,
+    /// Emails the list as CSV attachment to this address instead of printing it
+    #[arg(long)]
+    email: Option<String>,
+}
+
+#[derive(Args)]
+struct SchemasArgs {
+    /// Directory of the schema files, one file per type named after the type
+    #[arg(long, default_value = "schemas")]
+    out: PathBuf,
+}
+
+#[derive(Args)]
+struct AnonymizeArgs {
+    /// Secret of the fake data, the same salt gives the same fake data for the same person on every copy
+    #[arg(long, env = "LMS_ANONYMIZE_SALT")]
+    salt: String,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum BillingModeArg {
+    OnDemand,
+    Provisioned,
+}
+
+// desired settings of the tables, describe compares existing tables against the same options as create-tables
+#[derive(Args)]
+struct TableArgs {
+    /// Capacity mode of created tables
+    #[arg(long, value_enum, env = "LMS_BILLING_MODE", default_value = "on-demand")]
+    billing_mode: BillingModeArg,
+    /// Read capacity units of provisioned tables and indexes
+    #[arg(long, env = "LMS_READ_CAPACITY", default_value_t = 10)]
+    read_capacity: i64,
+    /// Write capacity units of provisioned tables and indexes
+    #[arg(long, env = "LMS_WRITE_CAPACITY", default_value_t = 10)]
+    write_capacity: i64,
+    /// Minimum capacity units of autoscaling, autoscaling is enabled when both min and max are set
+    #[arg(long, requires = "autoscale_max")]
+    autoscale_min: Option<i32>,
+    /// Maximum capacity units of autoscaling
+    #[arg(long, requires = "autoscale_min")]
+    autoscale_max: Option<i32>,
+    /// Target utilization percentage of autoscaling
+    #[arg(long, default_value_t = 70.0)]
+    autoscale_target: f64,
+    /// Enables point-in-time recovery of created tables
+    #[arg(long, env = "LMS_POINT_IN_TIME_RECOVERY")]
+    point_in_time_recovery: bool,
+    /// Protects created tables from deletion
+    #[arg(long, env = "LMS_DELETION_PROTECTION")]
+    deletion_protection: bool,
+    /// Tag of created tables as key=value, can be repeated
+    #[arg(long = "tag", value_parser = parse_tag)]
+    tags: Vec<(String, String)>,
+}
+
+fn parse_tag(tag: &str) -> Result<(String, String), String> {
+    match tag.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => Ok((key.trim().to_string(), value.trim().to_string())),
+        _ => Err(format!("tag {} must be formatted as key=value", tag)),
+    }
+}
+
+impl TableArgs {
+    fn options(&self) -> BootstrapOptions {
+        let billing = match self.billing_mode {
+            BillingModeArg::OnDemand => TableBilling::OnDemand,
+            BillingModeArg::Provisioned => TableBilling::Provisioned {
+                read_capacity: self.read_capacity,
+                write_capacity: self.write_capacity,
+            },
+        };
+        let autoscaling = match (self.autoscale_min, self.autoscale_max) {
+            (Some(min_capacity), Some(max_capacity)) => Some(AutoScaling {
+                min_capacity,
+                max_capacity,
+                target_utilization: self.autoscale_target,
+            }),
+            _ => None,
+        };
+        BootstrapOptions {
+            billing,
+            autoscaling,
+            point_in_time_recovery: self.point_in_time_recovery,
+            deletion_protection: self.deletion_protection,
+            tags: self.tags.iter().cloned().collect::<BTreeMap<String, String>>(),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    setup_tracing();
+
+    let cli = Cli::parse();
+    let store = if cli.local {
+        RepositoryStore::LocalDynamoDB
+    } else {
+        RepositoryStore::DynamoDB
+    };
+    if cli.allow_scans {
+        allow_unbounded_scans();
+    }
+    match cli.command {
+        Command::CreateTables(args) => {
+            let created = bootstrap_tables(store, &args.options()).await.map_err(|err| err.to_string())?;
+            println!("created {} tables {:?}", created.len(), created);
+        }
+        Command::Describe(args) => {
+            let reports = describe_tables(store, &args.options()).await.map_err(|err| err.to_string())?;
+            for report in &reports {
+                match &report.description {
+                    Some(description) => println!("{}: {}, point_in_time_recovery={}, deletion_protection={}, tags={:?}",
+                                                  description.name, description.billing, description.point_in_time_recovery,
+                                                  description.deletion_protection, description.tags),
+                    None => println!("{}: missing", report.name),
+                }
+                for drift in &report.drifts {
+                    println!("  drift {}", drift);
+                }
+            }
+            // fails so that deployment pipelines can detect tables that were changed outside the bootstrap
+            let drifted = reports.iter().filter(|report| !report.drifts.is_empty()).count();
+            if drifted > 0 {
+                return Err(format!("{} of {} tables drifted from desired settings", drifted, reports.len()).into());
+            }
+        }
+        #[cfg(feature = "dev")]
+        Command::Dev(args) => run_dev(args).await?,
+        Command::Digest(args) => {
+            let summary = send_due_soon_digests(&Configuration::new(args.branch.as_str()), store, args.within_days)
+                .await.map_err(|err| err.to_string())?;
+            println!("sent {} digests listing {} items, skipped {} patrons notified earlier today",
+                     summary.sent, summary.items, summary.skipped);
+        }
+        Command::Overdue(args) => {
+            let overdue = publish_overdue_checkouts(&Configuration::new(args.branch.as_str()), store)
+                .await.map_err(|err| err.to_string())?;
+            println!("published {} overdue checkouts", overdue);
+        }
+        Command::PurgeDocuments(args) => {
+            let purged = purge_expired_documents(&Configuration::new(args.branch.as_str()), store)
+                .await.map_err(|err| err.to_string())?;
+            println!("purged {} expired documents", purged);
+        }
+        Command::PurgePatrons(args) => {
+            let purged = purge_deleted_patrons(&Configuration::new(args.branch.as_str()), store)
+                .await.map_err(|err| err.to_string())?;
+            println!("purged {} removed patrons", purged);
+        }
+        Command::Sagas(args) => {
+            let stuck = compensate_stuck_sagas(&Configuration::new(args.branch.as_str()), store)
+                .await.map_err(|err| err.to_string())?;
+            for saga in &stuck {
+                println!("{} {} stuck in {} since {}: {}", saga.workflow, saga.workflow_id, saga.state, saga.stuck_since,
+                         saga.compensation.as_deref().unwrap_or("alerted"));
+            }
+        }
+        Command::Invariants(args) => {
+            let violations = check_invariants(&Configuration::new(args.branch.as_str()), store)
+                .await.map_err(|err| err.to_string())?;
+            for violation in &violations {
+                println!("{} {}: {}", violation.aggregate, violation.aggregate_id, violation.rule);
+            }
+            if !violations.is_empty() {
+                return Err(format!("found {} invariant violations", violations.len()).into());
+            }
+        }
+        Command::Ledger(args) => {
+            let check = check_ledger(&Configuration::new(args.branch.as_str()), store)
+                .await.map_err(|err| err.to_string())?;
+            println!("checked {} entries with {} debits and {} credits", check.entries, check.debits, check.credits);
+            for entry_id in &check.unbalanced {
+                println!("unbalanced entry {}", entry_id);
+            }
+            for discrepancy in &check.discrepancies {
+                println!("{}", discrepancy);
+            }
+            if !check.is_consistent() {
+                return Err(format!("ledger is inconsistent with {} unbalanced entries and {} discrepancies",
+                                   check.unbalanced.len(), check.discrepancies.len()).into());
+            }
+        }
+        Command::Replay(args) => {
+            let replayed = replay_events(store, args.name.as_deref()).await.map_err(|err| err.to_string())?;
+            println!("replayed {} events", replayed);
+        }
+        Command::CompactEvents(args) if args.background => {
+            let task_id = queue_compact_events(store, args.older_than_days).await.map_err(|err| err.to_string())?;
+            println!("queued compaction as task {}", task_id);
+        }
+        Command::CompactEvents(args) => {
+            let summary = compact_events(store, args.older_than_days).await.map_err(|err| err.to_string())?;
+            println!("archived {} events into {} objects", summary.archived, summary.objects);
+        }
+        Command::RebuildProjection(args) if args.background => {
+            let task_id = queue_rebuild_projection(store, args.name.as_str(), !args.skip_switch).await
+                .map_err(|err| err.to_string())?;
+            println!("queued rebuild of {} as task {}", args.name, task_id);
+        }
+        Command::RebuildProjection(args) => {
+            let res = rebuild_projection(store, args.name.as_str(), !args.skip_switch, &|progress| {
+                println!("{}: replayed {} events into {}, {} pending, {} failed", progress.projection,
+                         progress.processed, progress.table, progress.pending, progress.failed);
+            }).await.map_err(|err| err.to_string())?;
+            if res.switched {
+                println!("{} switched over to {}", res.projection, res.table);
+            }
+        }
+        Command::ShelfList(args) => {
+            let csv = export_shelf_list(&Configuration::new(args.branch.as_str()), store, args.collection.as_str(),
+                                        args.from.as_str(), args.to.as_str()).await.map_err(|err| err.to_string())?;
+            match &args.email {
+                Some(recipient) => {
+                    let message_id = email_shelf_list(recipient, args.collection.as_str(), args.from.as_str(),
+                                                      args.to.as_str(), csv.as_str()).await.map_err(|err| err.to_string())?;
+                    println!("emailed shelf list to {} as {}", recipient, message_id);
+                }
+                None => print!("{}", csv),
+            }
+        }
+        Command::Schemas(args) => {
+            let written = export_schemas(args.out.as_path()).map_err(|err| err.to_string())?;
+            for path in &written {
+                println!("wrote {}", path);
+            }
+        }
+        Command::Anonymize(args) => {
+            let _ = create_dev_tables().await.map_err(|err| err.to_string())?;
+            let summaries = copy_anonymized(store, RepositoryStore::LocalDynamoDB, args.salt.as_str())
+                .await.map_err(|err| err.to_string())?;
+            for summary in &summaries {
+                println!("{}: copied {} items", summary.table, summary.copied);
+            }
+        }
+        Command::Demo(args) => {
+            if cli.local {
+                let _ = create_dev_tables().await.map_err(|err| err.to_string())?;
+            }
+            let report = run_demo(&Configuration::new(args.branch.as_str()), store).await.map_err(|err| err.to_string())?;
+            println!("demo {}", report.correlation_id);
+            for step in &report.steps {
+                println!("  {}: {}", step.name, step.detail);
+            }
+            if cli.local {
+                println!("published {} events", report.events.len());
+                for event in &report.events {
+                    println!("  {}", event);
+                }
+            } else {
+                println!("events were published to SNS with correlation id {}", report.correlation_id);
+            }
+        }
+    }
+    Ok(())
+}
+
+// the container of DynamoDB Local lives as long as the server, stopping the server with ctrl-c removes it
+#[cfg(feature = "dev")]
+async fn run_dev(args: DevArgs) -> Result<(), Error> {
+    let docker = Docker::default();
+    let ddb = DynamoDBLocal::start(&docker);
+    println!("started DynamoDB Local at {}", ddb.endpoint);
+
+    let created = create_dev_tables().await.map_err(|err| err.to_string())?;
+    println!("created {} tables", created.len());
+    if !args.skip_seed {
+        let summary = seed_data(&Configuration::new(args.branch.as_str())).await.map_err(|err| err.to_string())?;
+        println!("seeded {} books and {} patrons", summary.books, summary.patrons);
+    }
+
+    let app = merged_router(AppState::new(args.branch.as_str(), RepositoryStore::LocalDynamoDB));
+    let addr = SocketAddr::from(([127, 0, 0, 1], args.port));
+    println!("serving all contexts at http://{}", addr);
+    axum::Server::bind(&addr)
+        .serve(app.into_make_service())
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::{Duration, NaiveTime, Utc, Weekday};
+use uuid::Uuid;
+use crate::books::dto::BookDto;
+use crate::branches::dto::{BranchDto, ClosureDto, OpeningHoursDto};
+use crate::branches::factory::create_branch_service;
+use crate::catalog::factory::create_catalog_service;
+use crate::checkout::dto::CheckoutDto;
+use crate::checkout::factory::{create_checkout_repository, create_checkout_service};
+use crate::core::context::RequestContext;
+use crate::core::domain::Configuration;
+use crate::core::ids::{BookId, PatronId};
+use crate::core::library::{LibraryError, LibraryResult, Role};
+use crate::core::repository::RepositoryStore;
+use crate::hold::factory::create_hold_service;
+use crate::notifications::factory::create_notification_service;
+use crate::patrons::dto::PatronDto;
+use crate::patrons::factory::create_patron_service;
+use crate::utils::ddb::{build_db_client, create_table, describe_table, parse_string_attribute};
+
+// the checkout of the scenario is made late by moving its due date this many days into the past
+const DAYS_LATE: i64 = 3;
+
+// DemoStep describes the outcome of a single step of the demo scenario
+#[derive(Debug, Clone, PartialEq)]
+pub struct DemoStep {
+    pub name: String,
+    pub detail: String,
+}
+
+impl DemoStep {
+    fn new(name: &str, detail: String) -> Self {
+        Self {
+            name: name.to_string(),
+            detail,
+        }
+    }
+}
+
+// DemoReport lists the steps of the demo scenario along with the domain events they published, events are only
+// listed for the local store because the DynamoDB store publishes them to SNS
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DemoReport {
+    pub correlation_id: String,
+    pub steps: Vec<DemoStep>,
+    pub events: Vec<String>,
+}
+
+// runs a scripted scenario across the bounded contexts against the given store, all events of the scenario share
+// a correlation id so that they can be told apart from other traffic of the store
+pub async fn run_demo(config: &Configuration, store: RepositoryStore) -> LibraryResult<DemoReport> {
+    let correlation_id = format!("demo-{}", Uuid::new_v4());
+    if store != RepositoryStore::DynamoDB {
+        let client = build_db_client(store).await;
+        let table_name = store.table_name("events");
+        if describe_table(&client, table_name.as_str()).await.is_err() {
+            create_table(&client, table_name.as_str(), "event_id", "group", "key").await?;
+        }
+    }
+    let ctx = RequestContext::anonymous(config.branch_id.as_str(), Some(correlation_id.as_str()), None);
+    let steps = ctx.scope(run_steps(config, store)).await?;
+    let events = match store {
+        RepositoryStore::DynamoDB => vec![],
+        _ => {
+            find_events(store, correlation_id.as_str()).await?
+        }
+    };
+    Ok(DemoReport {
+        correlation_id,
+        steps,
+        events,
+    })
+}
+
+async fn run_steps(config: &Configuration, store: RepositoryStore) -> LibraryResult<Vec<DemoStep>> {
+    let branch_svc = create_branch_service(config, store).await;
+    let patron_svc = create_patron_service(config, store).await;
+    let catalog_svc = create_catalog_service(config, store).await;
+    let hold_svc = create_hold_service(config, store).await;
+    let checkout_svc = create_checkout_service(config, store).await;
+    let checkout_repo = create_checkout_repository(store).await;
+    let notification_svc = create_notification_service(store).await;
+    // emails are unique per run so that the scenario can be repeated against the same tables
+    let run_id = Uuid::new_v4().simple().to_string()[..8].to_string();
+    let mut steps = vec![];
+
+    // checkouts and check-ins use the branch of the configuration so the branch is registered only once
+    let branch = match branch_svc.find_branch_by_id(config.branch_id.as_str()).await {
+        Ok(branch) => branch,
+        Err(LibraryError::NotFound { .. }) => {
+            let mut branch = BranchDto::new("Central Library", format!("central+{}@library.cc", run_id).as_str());
+            branch.branch_id = config.branch_id.to_string();
+            branch.street_address = "1000 4th Ave".to_string();
+            branch.city = "Seattle".to_string();
+            branch.zip_code = "98104".to_string();
+            branch.state = "WA".to_string();
+            branch.country = "US".to_string();
+            branch_svc.add_branch(&branch).await?
+        }
+        Err(err) => return Err(err),
+    };
+    let opens_at = NaiveTime::from_hms_opt(9, 0, 0).unwrap_or_default();
+    let closes_at = NaiveTime::from_hms_opt(18, 0, 0).unwrap_or_default();
+    let opening_hours: Vec<OpeningHoursDto> = [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri,
+        Weekday::Sat, Weekday::Sun].into_iter().map(|weekday| OpeningHoursDto::new(weekday, opens_at, closes_at)).collect();
+    let closures = vec![ClosureDto::new(Utc::now().naive_utc().date() + Duration::days(30), "Staff training")];
+    let branch = branch_svc.update_calendar(branch.branch_id.as_str(), &opening_hours, &closures).await?;
+    steps.push(DemoStep::new("add branch", format!("{} {} open daily 9-18 with {} closure",
+                                                   branch.name, branch.branch_id, branch.closures.len())));
+
+    let mut librarian = PatronDto::builder().email(format!("librarian+{}@library.cc", run_id).as_str())
+        .first_name("Melvil").last_name("Dewey").build()?;
+    librarian.group_roles = vec![Role::Librarian];
+    patron_svc.add_patron(&librarian).await?;
+    steps.push(DemoStep::new("add librarian", format!("{} {}", librarian.email, librarian.patron_id)));
+
+    let mut patrons = vec![];
+    for (first_name, last_name) in [("Ada", "Lovelace"), ("Alan", "Turing")] {
+        let email = format!("{}+{}@example.com", first_name.to_lowercase(), run_id);
+        let patron = PatronDto::builder().email(email.as_str()).first_name(first_name).last_name(last_name).build()?;
+        patron_svc.add_patron(&patron).await?;
+        steps.push(DemoStep::new("add patron", format!("{} {}", patron.email, patron.patron_id)));
+        patrons.push(patron);
+    }
+
+    let mut books = vec![];
+    for (isbn, title) in [("978-0321125217", "Domain-Driven Design"), ("978-0132350884", "Clean Code")] {
+        let book = catalog_svc.add_book(&BookDto::builder().isbn(isbn).title(title).collection("demo").build()?).await?;
+        steps.push(DemoStep::new("add book", format!("{} {}", book.title, book.book_id)));
+        books.push(book);
+    }
+
+    let hold = hold_svc.hold(&PatronId::new(patrons[0].patron_id.as_str()), &BookId::new(books[0].book_id.as_str()),
+                             Some(branch.branch_id.as_str())).await?;
+    steps.push(DemoStep::new("place hold", format!("{} holds {} with status {}",
+                                                   patrons[0].email, books[0].title, hold.hold_status)));
+
+    let checkout = checkout_svc.checkout(&PatronId::new(patrons[1].patron_id.as_str()), &BookId::new(books[1].book_id.as_str())).await?;
+    steps.push(DemoStep::new("check out", format!("{} borrows {} due at {}",
+                                                  patrons[1].email, books[1].title, checkout.due_at)));
+
+    // there is no clock to advance so the checkout is moved into the past instead
+    let mut late = checkout_repo.get(checkout.checkout_id.as_str()).await?;
+    let loan = late.due_at - late.checkout_at;
+    late.due_at = Utc::now().naive_utc() - Duration::days(DAYS_LATE);
+    late.checkout_at = late.due_at - loan;
+    let _ = checkout_repo.update(&late).await?;
+    let predicate = HashMap::from([("patron_id".to_string(), patrons[1].patron_id.to_string())]);
+    let overdue = checkout_svc.query_overdue(&predicate, None, 10).await?;
+    steps.push(DemoStep::new("query overdue", format!("{} overdue checkouts of {}",
+                                                      overdue.records.len(), patrons[1].email)));
+
+    let days = CheckoutDto::from(&late).open_days_overdue(Some(&branch), Utc::now().naive_utc().date());
+    steps.push(DemoStep::new("assess overdue", format!("{} is {} open days overdue at {}",
+                                                       books[1].title, days, branch.name)));
+
+    let notification = notification_svc.notify(patrons[1].patron_id.as_str(), "Overdue book",
+                                               format!("{} was due {} open days ago", books[1].title, days).as_str()).await?;
+    steps.push(DemoStep::new("notify patron", format!("{} notified with {}",
+                                                      patrons[1].email, notification.notification_id)));
+
+    let check_in = checkout_svc.check_in(&BookId::new(books[1].book_id.as_str()), Some(branch.branch_id.as_str()),
+                                         librarian.patron_id.as_str()).await?;
+    steps.push(DemoStep::new("return late", format!("{} checked in by {}, {}",
+                                                    books[1].title, librarian.email, check_in.instructions)));
+    Ok(steps)
+}
+
+// events of the local publisher are scanned because the events table has no index on their metadata
+async fn find_events(store: RepositoryStore, correlation_id: &str) -> LibraryResult<Vec<String>> {
+    let client = build_db_client(store).await;
+    let table_name = store.table_name("events");
+    let mut events = vec![];
+    let mut last_key: Option<HashMap<String, AttributeValue>> = None;
+    loop {
+        let res = client
+            .scan()
+            .table_name(table_name.as_str())
+            .filter_expression("#metadata.correlation_id = :correlation_id")
+            .expression_attribute_names("#metadata", "metadata")
+            .expression_attribute_values(":correlation_id", AttributeValue::S(correlation_id.to_string()))
+            .set_exclusive_start_key(last_key)
+            .send()
+            .await?;
+        for item in res.items().unwrap_or_default() {
+            let attribute = |name: &str| parse_string_attribute(name, item).unwrap_or_default();
+            events.push((attribute("created_at"), format!("{} {}/{}", attribute("name"), attribute("group"), attribute("key"))));
+        }
+        last_key = res.last_evaluated_key().cloned();
+        if last_key.is_none() {
+            break;
+        }
+    }
+    events.sort();
+    Ok(events.into_iter().map(|(_, event)| event).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::admin::demo::run_demo;
+    use crate::core::domain::Configuration;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_run_demo_scenario() {
+        let store = test_store(module_path!(), "test_should_run_demo_scenario");
+        let report = run_demo(&Configuration::new("test"), store).await.expect("should run demo");
+        let names: Vec<&str> = report.steps.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(vec!["add branch", "add librarian", "add patron", "add patron", "add book", "add book", "place hold",
+                        "check out", "query overdue", "assess overdue", "notify patron", "return late"], names);
+        assert!(report.steps[8].detail.starts_with("1 overdue"));
+        assert!(report.steps[9].detail.contains("3 open days"));
+        assert!(!report.events.is_empty());
+
+        // the scenario can be repeated against the same tables
+        let again = run_demo(&Configuration::new("test"), store).await.expect("should run demo again");
+        assert_eq!(report.steps.len(), again.steps.len());
+        assert_ne!(report.correlation_id, again.correlation_id);
+    }
+}
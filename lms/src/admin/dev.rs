@@ -0,0 +1,141 @@
+use axum::Router;
+#[cfg(any(test, feature = "dev"))]
+use testcontainers::{clients::Cli, core::WaitFor, images::generic::GenericImage, Container};
+use crate::admin::tables::{bootstrap_tables, BootstrapOptions};
+use crate::books::dto::BookDto;
+use crate::catalog::factory::create_catalog_service;
+use crate::core::controller::AppState;
+use crate::core::domain::Configuration;
+use crate::core::library::{BookFormat, LibraryResult};
+use crate::core::repository::RepositoryStore;
+use crate::patrons::dto::PatronDto;
+use crate::patrons::factory::create_patron_service;
+use crate::utils::ddb::{build_db_client, create_table, describe_table, TableBilling};
+#[cfg(any(test, feature = "dev"))]
+use crate::utils::ddb::LOCAL_ENDPOINT_ENV;
+
+#[cfg(any(test, feature = "dev"))]
+const DYNAMODB_LOCAL_PORT: u16 = 8000;
+
+// DynamoDBLocal runs DynamoDB Local in a container and points clients of the LocalDynamoDB store to it,
+// the container is stopped and removed when it is dropped
+#[cfg(any(test, feature = "dev"))]
+pub struct DynamoDBLocal<'d> {
+    container: Container<'d, GenericImage>,
+    pub endpoint: String,
+}
+
+#[cfg(any(test, feature = "dev"))]
+impl<'d> DynamoDBLocal<'d> {
+    pub fn start(docker: &'d Cli) -> Self {
+        let image = GenericImage::new("amazon/dynamodb-local", "latest")
+            .with_exposed_port(DYNAMODB_LOCAL_PORT)
+            .with_wait_for(WaitFor::message_on_stdout("Initializing DynamoDB Local"));
+        let container = docker.run(image);
+        let endpoint = format!("http://127.0.0.1:{}", container.get_host_port_ipv4(DYNAMODB_LOCAL_PORT));
+        std::env::set_var(LOCAL_ENDPOINT_ENV, endpoint.as_str());
+        Self {
+            container,
+            endpoint,
+        }
+    }
+
+    // id of the container for removing containers that are not dropped such as the one shared by tests
+    #[cfg(test)]
+    pub(crate) fn container_id(&self) -> &str {
+        self.container.id()
+    }
+}
+
+// creates tables of all bounded contexts along with the events table of the local publisher
+pub async fn create_dev_tables() -> LibraryResult<Vec<String>> {
+    let options = BootstrapOptions { billing: TableBilling::OnDemand, ..Default::default() };
+    let mut created = bootstrap_tables(RepositoryStore::LocalDynamoDB, &options).await?;
+    let client = build_db_client(RepositoryStore::LocalDynamoDB).await;
+    if describe_table(&client, "events").await.is_err() {
+        create_table(&client, "events", "event_id", "group", "key").await?;
+        created.push("events".to_string());
+    }
+    Ok(created)
+}
+
+// SeedSummary counts records added by the seed of the dev environment
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SeedSummary {
+    pub books: usize,
+    pub patrons: usize,
+}
+
+const SEED_BOOKS: &[(&str, &str, BookFormat)] = &[
+    ("978-0132350884", "Clean Code", BookFormat::Physical),
+    ("978-0201633610", "Design Patterns", BookFormat::Physical),
+    ("978-0321125217", "Domain-Driven Design", BookFormat::Physical),
+    ("978-1449373320", "Designing Data-Intensive Applications", BookFormat::EBook),
+    ("978-1718503106", "The Rust Programming Language", BookFormat::Audiobook),
+];
+
+const SEED_PATRONS: &[(&str, &str, &str)] = &[
+    ("ada@example.com", "Ada", "Lovelace"),
+    ("alan@example.com", "Alan", "Turing"),
+    ("grace@example.com", "Grace", "Hopper"),
+];
+
+// adds sample books and patrons so that the local API can be tried without preparing data first
+pub async fn seed_data(config: &Configuration) -> LibraryResult<SeedSummary> {
+    let catalog_svc = create_catalog_service(config, RepositoryStore::LocalDynamoDB).await;
+    let patron_svc = create_patron_service(config, RepositoryStore::LocalDynamoDB).await;
+    let mut summary = SeedSummary::default();
+    for (isbn, title, format) in SEED_BOOKS {
+        let mut book = BookDto::builder().isbn(isbn).title(title).book_format(*format).collection("dev");
+        if format.is_digital() {
+            book = book.license_count(2);
+        }
+        let _ = catalog_svc.add_book(&book.build()?).await?;
+        summary.books += 1;
+    }
+    for (email, first_name, last_name) in SEED_PATRONS {
+        let patron = PatronDto::builder().email(email).first_name(first_name).last_name(last_name).build()?;
+        patron_svc.add_patron(&patron).await?;
+        summary.patrons += 1;
+    }
+    Ok(summary)
+}
+
+// routes of all bounded contexts served by a single local server instead of separate lambda functions
+pub fn merged_router(state: AppState) -> Router {
+    Router::new()
+        .merge(crate::acquisitions::controller::router(state.clone()))
+        .merge(crate::audit::controller::router(state.clone()))
+        .merge(crate::branches::controller::router(state.clone()))
+        .merge(crate::catalog::controller::router(state.clone()))
+        .merge(crate::checkout::controller::router(state.clone()))
+        .merge(crate::credentials::controller::router(state.clone()))
+        .merge(crate::documents::controller::router(state.clone()))
+        .merge(crate::fines::controller::router(state.clone()))
+        .merge(crate::gateway::controller::router())
+        .merge(crate::hold::controller::router(state.clone()))
+        .merge(crate::ill::controller::router(state.clone()))
+        .merge(crate::inventory::controller::router(state.clone()))
+        .merge(crate::ncip::controller::router(state.clone()))
+        .merge(crate::notifications::controller::router(state.clone()))
+        .merge(crate::patrons::controller::router(state.clone()))
+        .merge(crate::programs::controller::router(state.clone()))
+        .merge(crate::reserves::controller::router(state.clone()))
+        .merge(crate::resources::controller::router(state.clone()))
+        .merge(crate::serials::controller::router(state.clone()))
+        .merge(crate::vendors::controller::router(state))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::admin::dev::merged_router;
+    use crate::core::controller::AppState;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_merge_routes_without_conflicts() {
+        let store = test_store(module_path!(), "test_should_merge_routes_without_conflicts");
+        // merging panics when two contexts register the same route
+        let _ = merged_router(AppState::new("test", store));
+    }
+}
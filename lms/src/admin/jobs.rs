@@ -0,0 +1,222 @@
+use std::path::Path;
+use chrono::{Duration, NaiveDateTime, Utc};
+use crate::catalog::factory::create_catalog_query_service;
+use crate::catalog::shelf_list;
+use crate::checkout::factory::create_checkout_service;
+use crate::core::domain::Configuration;
+use crate::core::events::schemas::message_schemas;
+use crate::core::invariants::InvariantViolation;
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::core::repository::RepositoryStore;
+use crate::core::tasks::{Task, TaskPayload};
+use crate::core::tasks::factory::create_task_queue;
+use crate::documents::factory::create_document_service;
+use crate::fines::domain::{CASH_ACCOUNT, RECEIVABLE_ACCOUNT, REVENUE_ACCOUNT, WAIVED_ACCOUNT};
+use crate::fines::factory::create_fine_query_service;
+use crate::gateway::ddb::compaction::{self, CompactionSummary};
+use crate::gateway::ddb::replay;
+use crate::gateway::factory::{create_email_sender, create_event_archive, create_event_log, create_replay_registry};
+use crate::gateway::ses::Email;
+use crate::hold::factory::create_hold_service;
+use crate::ill::dto::StuckWorkflowDto;
+use crate::ill::factory::create_ill_service;
+use crate::ledger::dto::LedgerCheckDto;
+use crate::ledger::factory::create_ledger_query_service;
+use crate::patrons::factory::create_patron_service;
+use crate::projector::rebuild::{self, RebuildProgress};
+use crate::utils::ddb::{build_db_client, ScanGuard};
+
+const JOB_PAGE_SIZE: usize = 100;
+
+// DigestSummary counts the due-soon digests of a run, digests already sent earlier on the same day are skipped
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DigestSummary {
+    pub patrons: usize,
+    pub items: usize,
+    pub sent: usize,
+    pub skipped: usize,
+}
+
+// sends the due-soon digests of all patrons, scheduled daily so the window defaults to due_soon_digest_days of the
+// configuration
+pub async fn send_due_soon_digests(config: &Configuration, store: RepositoryStore,
+                                   within_days: Option<i64>) -> LibraryResult<DigestSummary> {
+    let checkout_svc = create_checkout_service(config, store).await;
+    let digests = checkout_svc.send_due_soon_digests(
+        within_days.unwrap_or(config.due_soon_digest_days), JOB_PAGE_SIZE).await?;
+    let mut summary = DigestSummary::default();
+    for digest in &digests {
+        summary.patrons += 1;
+        summary.items += digest.items.len();
+        if digest.notification_id.is_some() {
+            summary.sent += 1;
+        } else {
+            summary.skipped += 1;
+        }
+    }
+    Ok(summary)
+}
+
+// publishes book_overdue for overdue checkouts so that consumers such as the patron counters catch up, returns the
+// number of overdue checkouts
+pub async fn publish_overdue_checkouts(config: &Configuration, store: RepositoryStore) -> LibraryResult<usize> {
+    let checkout_svc = create_checkout_service(config, store).await;
+    checkout_svc.publish_overdue(JOB_PAGE_SIZE).await.map(|overdue| overdue.len())
+}
+
+// removes verification documents whose retention ended along with their files, returns the number of purged
+// documents
+pub async fn purge_expired_documents(config: &Configuration, store: RepositoryStore) -> LibraryResult<usize> {
+    let document_svc = create_document_service(config, store).await;
+    document_svc.purge_expired(JOB_PAGE_SIZE).await
+}
+
+// purges patrons that were removed before their retention ended, returns the number of purged patrons
+pub async fn purge_deleted_patrons(config: &Configuration, store: RepositoryStore) -> LibraryResult<usize> {
+    let patron_svc = create_patron_service(config, store).await;
+    patron_svc.purge_deleted(JOB_PAGE_SIZE).await
+}
+
+// rejects interlibrary loan requests that no librarian handled within the timeout of the configuration and alerts
+// loans stuck waiting on the lending library, returns all stuck loans with their compensation
+pub async fn compensate_stuck_sagas(config: &Configuration, store: RepositoryStore) -> LibraryResult<Vec<StuckWorkflowDto>> {
+    let ill_svc = create_ill_service(config, store).await;
+    let stuck_before = Utc::now().naive_utc() - Duration::days(config.ill_stuck_days);
+    ill_svc.compensate_stuck(stuck_before, JOB_PAGE_SIZE).await
+}
+
+// scans holds and checkouts for broken invariants, debug builds assert them on every mutation whereas production
+// relies on this job to detect aggregates that were corrupted by a defect or written outside the services
+pub async fn check_invariants(config: &Configuration, store: RepositoryStore) -> LibraryResult<Vec<InvariantViolation>> {
+    let hold_svc = create_hold_service(config, store).await;
+    let checkout_svc = create_checkout_service(config, store).await;
+    let mut violations = hold_svc.find_invariant_violations(JOB_PAGE_SIZE).await?;
+    violations.extend(checkout_svc.find_invariant_violations(JOB_PAGE_SIZE).await?);
+    Ok(violations)
+}
+
+// verifies that every journal entry balances and reconciles the fines of the branch with the balances of its fine
+// accounts, fines assessed before the ledger existed show up as discrepancies until they are posted by hand
+pub async fn check_ledger(config: &Configuration, store: RepositoryStore) -> LibraryResult<LedgerCheckDto> {
+    let ledger_svc = create_ledger_query_service(config, store).await;
+    let mut check = ledger_svc.check_integrity(JOB_PAGE_SIZE).await?;
+    let totals = create_fine_query_service(config, store).await.branch_totals(config.branch_id.as_str(), JOB_PAGE_SIZE).await?;
+    let balances = ledger_svc.branch_balances(totals.branch_id.as_str(), NaiveDateTime::default(),
+                                              Utc::now().naive_utc()).await?;
+    let balance = |account: &str| balances.iter().find(|b| b.account == account).map(|b| b.balance).unwrap_or_default();
+    for (name, recorded, account, posted) in [
+        ("assessed", totals.assessed, REVENUE_ACCOUNT, -balance(REVENUE_ACCOUNT)),
+        ("waived", totals.waived, WAIVED_ACCOUNT, balance(WAIVED_ACCOUNT)),
+        ("collected", totals.paid - totals.refunded, CASH_ACCOUNT, balance(CASH_ACCOUNT)),
+        ("outstanding", totals.outstanding(), RECEIVABLE_ACCOUNT, balance(RECEIVABLE_ACCOUNT)),
+    ] {
+        if recorded != posted {
+            check.discrepancies.push(format!("fines of {} have {} {} but {} has {}",
+                                             totals.branch_id, recorded, name, account, posted));
+        }
+    }
+    Ok(check)
+}
+
+// replays published events of the events table and the event archive, optionally only those with the name, to the
+// subscribers of the event triggers, e.g. to rebuild projections after a defect, returns the number of replayed events
+pub async fn replay_events(store: RepositoryStore, name: Option<&str>) -> LibraryResult<usize> {
+    let registry = create_replay_registry(store).await;
+    replay::replay_events(create_event_log(store).await.as_ref(), &registry, name).await
+}
+
+// moves events published more than the days ago from the events table into the event archive, replays, rebuilds and
+// the history of entities keep reading them from there
+pub async fn compact_events(store: RepositoryStore, older_than_days: i64) -> LibraryResult<CompactionSummary> {
+    let client = build_db_client(store).await;
+    let before = Utc::now().naive_utc() - Duration::days(older_than_days);
+    compaction::compact_events(&client, store.table_name("events").as_str(), &create_event_archive().await,
+                               &ScanGuard::new(store), before).await
+}
+
+// queues the compaction of events for the task worker instead of running it in the admin process, returns the task id
+pub async fn queue_compact_events(store: RepositoryStore, older_than_days: i64) -> LibraryResult<String> {
+    queue_task(store, TaskPayload::ArchiveEvents { older_than_days }).await
+}
+
+// exports physical copies of the collection within the dewey range as CSV in shelf order for shelf-reading
+pub async fn export_shelf_list(config: &Configuration, store: RepositoryStore, collection: &str,
+                               from_dewey: &str, to_dewey: &str) -> LibraryResult<String> {
+    let catalog_svc = create_catalog_query_service(config, store).await;
+    shelf_list::export_shelf_list(catalog_svc.as_ref(), collection, from_dewey, to_dewey).await
+}
+
+// emails an exported shelf list as CSV attachment to the staff that reads the shelves, returns the message id
+pub async fn email_shelf_list(recipient: &str, collection: &str, from_dewey: &str, to_dewey: &str,
+                              csv: &str) -> LibraryResult<String> {
+    let range = format!("{}-{}", from_dewey, to_dewey);
+    let subject = if collection.is_empty() {
+        format!("Shelf list {}", range)
+    } else {
+        format!("Shelf list {} {}", collection, range)
+    };
+    let email = Email::new(recipient, subject.as_str(), "The shelf list is attached as CSV in shelf order.")
+        .with_attachment("shelf_list.csv", "text/csv", csv.as_bytes().to_vec());
+    create_email_sender().await.send(&email).await
+}
+
+// rebuilds the projection into a new table from the events table, an interrupted rebuild resumes from its checkpoint,
+// and makes the new table the active table of the projection when switch_over is set
+pub async fn rebuild_projection(store: RepositoryStore, projection: &str, switch_over: bool,
+                                progress: &(dyn Fn(&RebuildProgress) + Sync)) -> LibraryResult<RebuildProgress> {
+    rebuild::rebuild_projection(store, projection, switch_over, progress).await
+}
+
+// queues the rebuild of the projection for the task worker, a worker that is stopped midway resumes the rebuild from
+// its checkpoint when the task is retried, returns the task id
+pub async fn queue_rebuild_projection(store: RepositoryStore, projection: &str, switch_over: bool) -> LibraryResult<String> {
+    queue_task(store, TaskPayload::RebuildProjection { projection: projection.to_string(), switch_over }).await
+}
+
+async fn queue_task(store: RepositoryStore, payload: TaskPayload) -> LibraryResult<String> {
+    let task = Task::new(payload);
+    create_task_queue(store.task_queue()).await.enqueue(&task, Duration::zero()).await?;
+    Ok(task.task_id)
+}
+
+// writes the json schemas of the event envelope and of published payloads as <name>.json into the directory so that
+// downstream teams can generate consumers against them, returns the paths of the written files. Builds with the
+// examples feature also write the sample payloads of command requests and responses into examples/<name>.json
+pub fn export_schemas(out_dir: &Path) -> LibraryResult<Vec<String>> {
+    let mut written = vec![];
+    for (name, schema) in message_schemas() {
+        written.push(write_json(out_dir, name, &serde_json::to_value(&schema)?)?);
+    }
+    #[cfg(feature = "examples")]
+    for (name, examples) in crate::core::command::examples::command_examples() {
+        written.push(write_json(out_dir.join("examples").as_path(), name, &serde_json::Value::Array(examples))?);
+    }
+    Ok(written)
+}
+
+fn write_json(dir: &Path, name: &str, value: &serde_json::Value) -> LibraryResult<String> {
+    std::fs::create_dir_all(dir).map_err(|err| LibraryError::runtime(
+        format!("failed to create {}: {}", dir.display(), err).as_str(), None))?;
+    let path = dir.join(format!("{}.json", name));
+    let json = serde_json::to_string_pretty(value)?;
+    std::fs::write(&path, json + "\n").map_err(|err| LibraryError::runtime(
+        format!("failed to write {}: {}", path.display(), err).as_str(), None))?;
+    Ok(path.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::admin::jobs::send_due_soon_digests;
+    use crate::core::domain::Configuration;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_skip_digests_sent_on_same_day() {
+        let store = test_store(module_path!(), "test_should_skip_digests_sent_on_same_day");
+        let config = Configuration::new("test");
+        let _ = send_due_soon_digests(&config, store, Some(3)).await.expect("should send digests");
+        let again = send_due_soon_digests(&config, store, Some(3)).await.expect("should send digests");
+        assert_eq!(0, again.sent);
+        assert_eq!(again.patrons, again.skipped);
+    }
+}
@@ -0,0 +1,631 @@
+use aws_sdk_applicationautoscaling::types::{MetricType, PolicyType, PredefinedMetricSpecification, ScalableDimension,
+                                            ServiceNamespace, TargetTrackingScalingPolicyConfiguration};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::types::{BillingMode, IndexStatus, KeySchemaElement, KeyType, PointInTimeRecoverySpecification,
+                              PointInTimeRecoveryStatus};
+use tracing::log::info;
+use crate::books::repository::ddb_book_repository::{ACQUISITION_INDEX, AUTHOR_INDEX, SHELF_INDEX};
+use crate::checkout::repository::ddb_checkout_repository::BRANCH_INDEX as CHECKOUT_BRANCH_INDEX;
+use crate::hold::repository::ddb_hold_repository::BRANCH_INDEX as HOLD_BRANCH_INDEX;
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::core::repository::RepositoryStore;
+use crate::utils::ddb::{build_db_client, create_index, create_table_with_settings, describe_table, enable_time_to_live,
+                        TableBilling, TableSettings};
+
+// TableSpec defines the key schema of a table read and written by the DynamoDB repositories
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TableSpec {
+    pub name: &'static str,
+    pub pk: &'static str,
+    // partition and sort keys of the <table>_ndx index
+    pub gsi: Option<(&'static str, &'static str)>,
+    // further indexes that are added to the table after it was created
+    pub indexes: &'static [IndexSpec],
+    // attribute with the epoch seconds after which DynamoDB deletes an item
+    pub ttl: Option<&'static str>,
+}
+
+impl TableSpec {
+    const fn new(name: &'static str, pk: &'static str, gsi: Option<(&'static str, &'static str)>) -> Self {
+        Self { name, pk, gsi, indexes: &[], ttl: None }
+    }
+
+    const fn with_indexes(self, indexes: &'static [IndexSpec]) -> Self {
+        Self { indexes, ..self }
+    }
+
+    const fn with_ttl(self, attribute: &'static str) -> Self {
+        Self { ttl: Some(attribute), ..self }
+    }
+
+    // compares the key schema and indexes of an existing table with the spec, names of the indexes are derived from
+    // the qualified table name the same way the bootstrap names them
+    pub fn schema_mismatches(&self, actual: &TableSchema) -> Vec<TableDrift> {
+        let table = actual.name.as_str();
+        let mut mismatches = vec![];
+        let key = KeySchema::new(self.pk, None);
+        if actual.key != key {
+            mismatches.push(TableDrift::new(table, "key schema", key.to_string(), actual.key.to_string()));
+        }
+        let mut indexes = vec![];
+        if let Some((pk, sk)) = self.gsi {
+            indexes.push((format!("{}_ndx", table), KeySchema::new(pk, Some(sk))));
+        }
+        for index in self.indexes {
+            indexes.push((format!("{}_{}", table, index.suffix), KeySchema::new(index.pk, Some(index.sk))));
+        }
+        for (name, key) in indexes {
+            match actual.indexes.get(name.as_str()) {
+                None => mismatches.push(TableDrift::new(table, format!("index {}", name).as_str(),
+                                                        key.to_string(), "missing".to_string())),
+                Some(index) if index.key != key => mismatches.push(
+                    TableDrift::new(table, format!("index {}", name).as_str(), key.to_string(), index.key.to_string())),
+                // queries of an index fail while it is backfilled after being added to an existing table
+                Some(index) if index.status != IndexStatus::Active.as_str() => mismatches.push(
+                    TableDrift::new(table, format!("index {} status", name).as_str(),
+                                    IndexStatus::Active.as_str().to_string(), index.status.clone())),
+                Some(_) => {}
+            }
+        }
+        mismatches
+    }
+}
+
+// IndexSpec defines a global secondary index named <table>_<suffix>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IndexSpec {
+    pub suffix: &'static str,
+    pub pk: &'static str,
+    pub sk: &'static str,
+}
+
+// tables of all bounded contexts, the events table is left out because events are published to SNS in production
+pub const TABLES: &[TableSpec] = &[
+    TableSpec::new("books", "book_id", Some(("book_status", "isbn")))
+        .with_indexes(&[IndexSpec { suffix: AUTHOR_INDEX, pk: "author_id", sk: "created_at" },
+                        IndexSpec { suffix: SHELF_INDEX, pk: "book_format", sk: "call_number" },
+                        IndexSpec { suffix: ACQUISITION_INDEX, pk: "book_format", sk: "created_at" }]),
+    TableSpec::new("tags", "tag_name", None),
+    TableSpec::new("co_checkouts", "pair_id", Some(("book_id", "related_book_id"))),
+    TableSpec::new("book_popularity", "score_id", Some(("score_window", "score_key"))),
+    TableSpec::new("parties", "party_id", Some(("kind", "normalized_email"))),
+    TableSpec::new("party_emails", "email", None),
+    TableSpec::new("party_counter_changes", "change_key", None),
+    TableSpec::new("reading_history", "history_id", Some(("patron_id", "returned_at"))),
+    TableSpec::new("projection_tables", "projection", None),
+    TableSpec::new("processed_events", "event_key", None).with_ttl("expires_at"),
+    TableSpec::new("notifications", "notification_id", Some(("party_id", "created_at"))),
+    TableSpec::new("notification_deliveries", "delivery_key", None),
+    TableSpec::new("checkout", "checkout_id", Some(("checkout_status", "patron_id")))
+        .with_indexes(&[IndexSpec { suffix: CHECKOUT_BRANCH_INDEX, pk: "branch_id", sk: "checkout_id" }]),
+    TableSpec::new("hold", "hold_id", Some(("hold_status", "patron_id")))
+        .with_indexes(&[IndexSpec { suffix: HOLD_BRANCH_INDEX, pk: "branch_id", sk: "hold_id" }]),
+    TableSpec::new("active_holds", "patron_book", None),
+    TableSpec::new("purchases", "purchase_id", Some(("purchase_status", "vendor_id"))),
+    TableSpec::new("budgets", "branch_id", None),
+    TableSpec::new("serials", "serial_id", None),
+    TableSpec::new("serial_issues", "issue_id", Some(("serial_id", "expected_at"))),
+    TableSpec::new("reserve_lists", "list_name", None),
+    TableSpec::new("reserve_items", "book_id", Some(("list_name", "added_at"))),
+    TableSpec::new("ill_requests", "ill_id", Some(("ill_status", "patron_id"))),
+    TableSpec::new("resources", "resource_id", Some(("resource_kind", "name"))),
+    TableSpec::new("resource_bookings", "booking_id", Some(("resource_id", "starts_at"))),
+    TableSpec::new("programs", "program_id", Some(("branch_id", "starts_at"))),
+    TableSpec::new("program_registrations", "registration_id", Some(("program_id", "created_at"))),
+    TableSpec::new("audit_log", "audit_id", Some(("audit_type", "created_at"))),
+    TableSpec::new("inventory_sessions", "session_id", None),
+    TableSpec::new("inventory_scans", "scan_id", Some(("session_id", "scan_result"))),
+    TableSpec::new("credentials", "party_id", None),
+    TableSpec::new("api_keys", "key_id", None),
+    TableSpec::new("party_documents", "document_id", Some(("party_id", "created_at"))),
+    TableSpec::new("journal", "entry_id", Some(("branch_id", "posted_at"))),
+    TableSpec::new("fines", "fine_id", Some(("patron_id", "assessed_at"))),
+    TableSpec::new("fine_payments", "payment_id", Some(("patron_id", "created_at"))),
+    TableSpec::new("fine_adjustments", "adjustment_id", Some(("patron_id", "created_at"))),
+];
+
+// AutoScaling registers read and write capacity of provisioned tables and their indexes with target tracking
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AutoScaling {
+    pub min_capacity: i32,
+    pub max_capacity: i32,
+    // percentage of consumed to provisioned capacity that scaling keeps the table at
+    pub target_utilization: f64,
+}
+
+// BootstrapOptions defines desired settings of tables created by the admin bootstrap
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BootstrapOptions {
+    pub billing: TableBilling,
+    pub autoscaling: Option<AutoScaling>,
+    // continuous backups that allow restoring a table to any second of the last 35 days
+    pub point_in_time_recovery: bool,
+    pub deletion_protection: bool,
+    pub tags: BTreeMap<String, String>,
+}
+
+impl BootstrapOptions {
+    pub fn validate(&self) -> LibraryResult<()> {
+        if let TableBilling::Provisioned { read_capacity, write_capacity } = self.billing {
+            if read_capacity < 1 || write_capacity < 1 {
+                return Err(LibraryError::validation("provisioned capacity must be at least 1", None));
+            }
+        }
+        if let Some(scaling) = self.autoscaling {
+            if self.billing == TableBilling::OnDemand {
+                return Err(LibraryError::validation("autoscaling requires provisioned billing", None));
+            }
+            if scaling.min_capacity < 1 || scaling.max_capacity < scaling.min_capacity {
+                return Err(LibraryError::validation("autoscaling capacity must be between 1 and max capacity", None));
+            }
+            if !(20.0..=90.0).contains(&scaling.target_utilization) {
+                return Err(LibraryError::validation("autoscaling target utilization must be between 20 and 90", None));
+            }
+        }
+        if self.tags.keys().any(|key| key.is_empty() || key.starts_with("aws:")) {
+            return Err(LibraryError::validation("tag keys cannot be empty or use the aws: prefix", None));
+        }
+        Ok(())
+    }
+
+    // compares an existing table with the desired settings, capacity is only compared when it is not managed
+    // by autoscaling and tags that were added outside the bootstrap are ignored
+    pub fn drifts(&self, actual: &TableDescription) -> Vec<TableDrift> {
+        let mut drifts = vec![];
+        let billing_drifted = match (self.billing, actual.billing) {
+            (TableBilling::Provisioned { .. }, TableBilling::Provisioned { .. }) if self.autoscaling.is_some() => false,
+            (desired, actual) => desired != actual,
+        };
+        if billing_drifted {
+            drifts.push(TableDrift::new(actual.name.as_str(), "billing", self.billing.to_string(), actual.billing.to_string()));
+        }
+        if self.point_in_time_recovery != actual.point_in_time_recovery {
+            drifts.push(TableDrift::new(actual.name.as_str(), "point_in_time_recovery",
+                                        self.point_in_time_recovery.to_string(), actual.point_in_time_recovery.to_string()));
+        }
+        if self.deletion_protection != actual.deletion_protection {
+            drifts.push(TableDrift::new(actual.name.as_str(), "deletion_protection",
+                                        self.deletion_protection.to_string(), actual.deletion_protection.to_string()));
+        }
+        for (key, value) in &self.tags {
+            let actual_value = actual.tags.get(key).cloned().unwrap_or_else(|| "<missing>".to_string());
+            if &actual_value != value {
+                drifts.push(TableDrift::new(actual.name.as_str(), format!("tag {}", key).as_str(), value.to_string(), actual_value));
+            }
+        }
+        drifts
+    }
+
+    fn table_settings(&self) -> TableSettings {
+        TableSettings {
+            billing: self.billing,
+            deletion_protection: self.deletion_protection,
+            tags: self.tags.clone(),
+        }
+    }
+}
+
+// TableDescription holds actual settings of an existing table
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableDescription {
+    pub name: String,
+    pub billing: TableBilling,
+    pub point_in_time_recovery: bool,
+    pub deletion_protection: bool,
+    pub tags: BTreeMap<String, String>,
+}
+
+// TableDrift is a setting of a table whose actual value differs from the desired value
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableDrift {
+    pub table: String,
+    pub setting: String,
+    pub desired: String,
+    pub actual: String,
+}
+
+impl TableDrift {
+    fn new(table: &str, setting: &str, desired: String, actual: String) -> Self {
+        Self {
+            table: table.to_string(),
+            setting: setting.to_string(),
+            desired,
+            actual,
+        }
+    }
+}
+
+impl Display for TableDrift {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}: {} is {}, expected {}", self.table, self.setting, self.actual, self.desired)
+    }
+}
+
+// KeySchema holds the partition key and optional sort key of a table or an index
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct KeySchema {
+    pub pk: String,
+    pub sk: Option<String>,
+}
+
+impl KeySchema {
+    pub fn new(pk: &str, sk: Option<&str>) -> Self {
+        Self {
+            pk: pk.to_string(),
+            sk: sk.map(str::to_string),
+        }
+    }
+
+    fn from_elements(elements: &[KeySchemaElement]) -> Self {
+        let attribute = |key_type: KeyType| elements.iter()
+            .find(|element| element.key_type() == Some(&key_type))
+            .and_then(|element| element.attribute_name())
+            .map(str::to_string);
+        Self {
+            pk: attribute(KeyType::Hash).unwrap_or_default(),
+            sk: attribute(KeyType::Range),
+        }
+    }
+}
+
+impl Display for KeySchema {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match &self.sk {
+            Some(sk) => write!(f, "{}/{}", self.pk, sk),
+            None => write!(f, "{}", self.pk),
+        }
+    }
+}
+
+// IndexSchema holds the key schema and status of a global secondary index of an existing table
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexSchema {
+    pub key: KeySchema,
+    pub status: String,
+}
+
+// TableSchema holds the key schema of an existing table and its global secondary indexes by name
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableSchema {
+    pub name: String,
+    pub key: KeySchema,
+    pub indexes: BTreeMap<String, IndexSchema>,
+}
+
+// TableReport describes a table of the bootstrap along with its drifts, missing tables have no description
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableReport {
+    pub name: String,
+    pub description: Option<TableDescription>,
+    pub drifts: Vec<TableDrift>,
+}
+
+// creates tables that do not exist yet and returns names of the created tables, existing tables are left
+// unchanged except for missing indexes so that the bootstrap can be rerun after adding a bounded context or an index
+pub async fn bootstrap_tables(store: RepositoryStore, options: &BootstrapOptions) -> LibraryResult<Vec<String>> {
+    options.validate()?;
+    let client = build_db_client(store).await;
+    let settings = options.table_settings();
+    let mut created = vec![];
+    for spec in TABLES {
+        let table_name = store.table_name(spec.name);
+        let table_name = table_name.as_str();
+        if describe_table(&client, table_name).await.is_ok() {
+            info!("table {} already exists", table_name);
+            create_indexes(&client, table_name, spec, options).await?;
+            enable_ttl(&client, table_name, spec).await?;
+            continue;
+        }
+        create_table_with_settings(&client, table_name, spec.pk, spec.gsi, &settings).await?;
+        create_indexes(&client, table_name, spec, options).await?;
+        enable_ttl(&client, table_name, spec).await?;
+        if options.point_in_time_recovery {
+            enable_point_in_time_recovery(&client, table_name).await?;
+        }
+        if let Some(scaling) = options.autoscaling {
+            register_autoscaling(table_name, spec, &scaling).await?;
+        }
+        info!("created table {}", table_name);
+        created.push(spec.name.to_string());
+    }
+    Ok(created)
+}
+
+async fn create_indexes(client: &Client, table_name: &str, spec: &TableSpec, options: &BootstrapOptions) -> LibraryResult<()> {
+    for index in spec.indexes {
+        create_index(client, table_name, index.suffix, index.pk, index.sk, options.billing).await?;
+    }
+    Ok(())
+}
+
+async fn enable_ttl(client: &Client, table_name: &str, spec: &TableSpec) -> LibraryResult<()> {
+    match spec.ttl {
+        Some(attribute) => enable_time_to_live(client, table_name, attribute).await,
+        None => Ok(()),
+    }
+}
+
+// describes actual settings of all tables and reports their drift from the desired settings
+pub async fn describe_tables(store: RepositoryStore, options: &BootstrapOptions) -> LibraryResult<Vec<TableReport>> {
+    options.validate()?;
+    let client = build_db_client(store).await;
+    let mut reports = vec![];
+    for spec in TABLES {
+        let table_name = store.table_name(spec.name);
+        let report = match describe_table_settings(&client, table_name.as_str()).await? {
+            Some(description) => {
+                let mut drifts = options.drifts(&description);
+                if let Some(schema) = describe_table_schema(&client, table_name.as_str()).await? {
+                    drifts.extend(spec.schema_mismatches(&schema));
+                }
+                TableReport {
+                    name: spec.name.to_string(),
+                    drifts,
+                    description: Some(description),
+                }
+            }
+            None => TableReport {
+                name: spec.name.to_string(),
+                description: None,
+                drifts: vec![TableDrift::new(table_name.as_str(), "table", "present".to_string(), "missing".to_string())],
+            },
+        };
+        reports.push(report);
+    }
+    Ok(reports)
+}
+
+// checks the key schema and indexes of all tables when a function starts so that a table or index that was not
+// bootstrapped fails its cold start with reason schema_mismatch, naming each missing table and index, rather than
+// failing queries of the index with a validation error of DynamoDB at runtime
+pub async fn check_tables(store: RepositoryStore) -> LibraryResult<()> {
+    let client = build_db_client(store).await;
+    let mut mismatches = vec![];
+    for spec in TABLES {
+        let table_name = store.table_name(spec.name);
+        match describe_table_schema(&client, table_name.as_str()).await? {
+            Some(schema) => mismatches.extend(spec.schema_mismatches(&schema)),
+            None => mismatches.push(TableDrift::new(table_name.as_str(), "table",
+                                                    "present".to_string(), "missing".to_string())),
+        }
+    }
+    if mismatches.is_empty() {
+        return Ok(());
+    }
+    let details = mismatches.iter().map(|mismatch| mismatch.to_string()).collect::<Vec<String>>().join("; ");
+    Err(LibraryError::runtime(format!("tables do not match their specs: {}", details).as_str(),
+                              Some("schema_mismatch".to_string())))
+}
+
+async fn describe_table_schema(client: &Client, table_name: &str) -> LibraryResult<Option<TableSchema>> {
+    let out = match client.describe_table().table_name(table_name).send().await {
+        Ok(out) => out,
+        Err(SdkError::ServiceError(ctx)) if ctx.err().is_resource_not_found_exception() => return Ok(None),
+        Err(err) => return Err(LibraryError::database_or_unavailable(
+            format!("failed to describe {} table due to {}", table_name, err).as_str(), None, true)),
+    };
+    let table = out.table()
+        .ok_or_else(|| LibraryError::runtime(format!("failed to describe {} table", table_name).as_str(), None))?;
+    let mut indexes = BTreeMap::new();
+    for index in table.global_secondary_indexes().unwrap_or_default() {
+        indexes.insert(index.index_name().unwrap_or_default().to_string(), IndexSchema {
+            key: KeySchema::from_elements(index.key_schema().unwrap_or_default()),
+            status: index.index_status().map(|status| status.as_str().to_string()).unwrap_or_default(),
+        });
+    }
+    Ok(Some(TableSchema {
+        name: table_name.to_string(),
+        key: KeySchema::from_elements(table.key_schema().unwrap_or_default()),
+        indexes,
+    }))
+}
+
+async fn describe_table_settings(client: &Client, table_name: &str) -> LibraryResult<Option<TableDescription>> {
+    let out = match client.describe_table().table_name(table_name).send().await {
+        Ok(out) => out,
+        Err(SdkError::ServiceError(ctx)) if ctx.err().is_resource_not_found_exception() => return Ok(None),
+        Err(err) => return Err(LibraryError::database_or_unavailable(
+            format!("failed to describe {} table due to {}", table_name, err).as_str(), None, true)),
+    };
+    let table = out.table()
+        .ok_or_else(|| LibraryError::runtime(format!("failed to describe {} table", table_name).as_str(), None))?;
+    let billing = match table.billing_mode_summary().and_then(|summary| summary.billing_mode()) {
+        Some(BillingMode::PayPerRequest) => TableBilling::OnDemand,
+        _ => TableBilling::Provisioned {
+            read_capacity: table.provisioned_throughput().and_then(|t| t.read_capacity_units()).unwrap_or_default(),
+            write_capacity: table.provisioned_throughput().and_then(|t| t.write_capacity_units()).unwrap_or_default(),
+        },
+    };
+
+    let backups = client.describe_continuous_backups().table_name(table_name).send().await
+        .map_err(|err| LibraryError::database_or_unavailable(
+            format!("failed to describe backups of {} table due to {}", table_name, err).as_str(), None, true))?;
+    let point_in_time_recovery = backups.continuous_backups_description()
+        .and_then(|backups| backups.point_in_time_recovery_description())
+        .and_then(|pitr| pitr.point_in_time_recovery_status()) == Some(&PointInTimeRecoveryStatus::Enabled);
+
+    let mut tags = BTreeMap::new();
+    if let Some(arn) = table.table_arn() {
+        let out = client.list_tags_of_resource().resource_arn(arn).send().await
+            .map_err(|err| LibraryError::database_or_unavailable(
+                format!("failed to list tags of {} table due to {}", table_name, err).as_str(), None, true))?;
+        for tag in out.tags().unwrap_or_default() {
+            tags.insert(tag.key().unwrap_or_default().to_string(), tag.value().unwrap_or_default().to_string());
+        }
+    }
+
+    Ok(Some(TableDescription {
+        name: table_name.to_string(),
+        billing,
+        point_in_time_recovery,
+        deletion_protection: table.deletion_protection_enabled().unwrap_or_default(),
+        tags,
+    }))
+}
+
+async fn enable_point_in_time_recovery(client: &Client, table_name: &str) -> LibraryResult<()> {
+    client.update_continuous_backups()
+        .table_name(table_name)
+        .point_in_time_recovery_specification(
+            PointInTimeRecoverySpecification::builder().point_in_time_recovery_enabled(true).build())
+        .send().await
+        .map_err(|err| LibraryError::database_or_unavailable(
+            format!("failed to enable point in time recovery of {} table due to {}", table_name, err).as_str(), None, false))?;
+    Ok(())
+}
+
+async fn register_autoscaling(table_name: &str, spec: &TableSpec, scaling: &AutoScaling) -> LibraryResult<()> {
+    let config = aws_config::load_from_env().await;
+    let client = aws_sdk_applicationautoscaling::Client::new(&config);
+    let mut targets = vec![
+        (format!("table/{}", table_name), ScalableDimension::DynamoDbTableReadCapacityUnits, MetricType::DynamoDbReadCapacityUtilization),
+        (format!("table/{}", table_name), ScalableDimension::DynamoDbTableWriteCapacityUnits, MetricType::DynamoDbWriteCapacityUtilization),
+    ];
+    let mut index_names = spec.indexes.iter().map(|index| format!("{}_{}", table_name, index.suffix)).collect::<Vec<String>>();
+    if spec.gsi.is_some() {
+        index_names.insert(0, format!("{}_ndx", table_name));
+    }
+    for index_name in index_names {
+        let index = format!("table/{}/index/{}", table_name, index_name);
+        targets.push((index.clone(), ScalableDimension::DynamoDbIndexReadCapacityUnits, MetricType::DynamoDbReadCapacityUtilization));
+        targets.push((index, ScalableDimension::DynamoDbIndexWriteCapacityUnits, MetricType::DynamoDbWriteCapacityUtilization));
+    }
+    for (resource_id, dimension, metric) in targets {
+        client.register_scalable_target()
+            .service_namespace(ServiceNamespace::Dynamodb)
+            .resource_id(resource_id.as_str())
+            .scalable_dimension(dimension.clone())
+            .min_capacity(scaling.min_capacity)
+            .max_capacity(scaling.max_capacity)
+            .send().await
+            .map_err(|err| LibraryError::unavailable(
+                format!("failed to register scalable target {} due to {}", resource_id, err).as_str(), None, false))?;
+        client.put_scaling_policy()
+            .policy_name(format!("{}-{}", resource_id.replace('/', "-"), metric.as_str()))
+            .service_namespace(ServiceNamespace::Dynamodb)
+            .resource_id(resource_id.as_str())
+            .scalable_dimension(dimension)
+            .policy_type(PolicyType::TargetTrackingScaling)
+            .target_tracking_scaling_policy_configuration(
+                TargetTrackingScalingPolicyConfiguration::builder()
+                    .target_value(scaling.target_utilization)
+                    .predefined_metric_specification(
+                        PredefinedMetricSpecification::builder().predefined_metric_type(metric).build())
+                    .build())
+            .send().await
+            .map_err(|err| LibraryError::unavailable(
+                format!("failed to put scaling policy of {} due to {}", resource_id, err).as_str(), None, false))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use crate::admin::tables::{AutoScaling, BootstrapOptions, IndexSchema, KeySchema, TableDescription, TableSchema,
+                               TABLES};
+    use crate::utils::ddb::TableBilling;
+
+    #[tokio::test]
+    async fn test_should_validate_options() {
+        assert!(BootstrapOptions::default().validate().is_ok());
+        let scaling = AutoScaling { min_capacity: 5, max_capacity: 100, target_utilization: 70.0 };
+        assert!(BootstrapOptions { billing: TableBilling::OnDemand, autoscaling: Some(scaling), ..Default::default() }
+            .validate().is_err());
+        assert!(BootstrapOptions { autoscaling: Some(scaling), ..Default::default() }.validate().is_ok());
+        let inverted = AutoScaling { min_capacity: 10, max_capacity: 5, target_utilization: 70.0 };
+        assert!(BootstrapOptions { autoscaling: Some(inverted), ..Default::default() }.validate().is_err());
+        let zero = TableBilling::Provisioned { read_capacity: 0, write_capacity: 10 };
+        assert!(BootstrapOptions { billing: zero, ..Default::default() }.validate().is_err());
+        let tags = BTreeMap::from([("aws:owner".to_string(), "lms".to_string())]);
+        assert!(BootstrapOptions { tags, ..Default::default() }.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_report_drifts() {
+        let options = BootstrapOptions {
+            billing: TableBilling::OnDemand,
+            point_in_time_recovery: true,
+            deletion_protection: true,
+            tags: BTreeMap::from([("env".to_string(), "prod".to_string()), ("team".to_string(), "lms".to_string())]),
+            ..Default::default()
+        };
+        let actual = TableDescription {
+            name: "books".to_string(),
+            billing: TableBilling::OnDemand,
+            point_in_time_recovery: false,
+            deletion_protection: true,
+            tags: BTreeMap::from([("env".to_string(), "dev".to_string()), ("owner".to_string(), "ops".to_string())]),
+        };
+        let drifts = options.drifts(&actual);
+        let settings = drifts.iter().map(|drift| drift.setting.as_str()).collect::<Vec<&str>>();
+        assert_eq!(vec!["point_in_time_recovery", "tag env", "tag team"], settings);
+        assert_eq!("books: tag env is dev, expected prod", drifts[1].to_string());
+
+        let in_sync = TableDescription { point_in_time_recovery: true, tags: options.tags.clone(), ..actual };
+        assert!(options.drifts(&in_sync).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_should_ignore_capacity_managed_by_autoscaling() {
+        let scaling = AutoScaling { min_capacity: 5, max_capacity: 100, target_utilization: 70.0 };
+        let options = BootstrapOptions { autoscaling: Some(scaling), ..Default::default() };
+        let actual = TableDescription {
+            name: "hold".to_string(),
+            billing: TableBilling::Provisioned { read_capacity: 45, write_capacity: 12 },
+            point_in_time_recovery: false,
+            deletion_protection: false,
+            tags: BTreeMap::new(),
+        };
+        assert!(options.drifts(&actual).is_empty());
+        let unscaled = BootstrapOptions::default();
+        assert_eq!("billing", unscaled.drifts(&actual)[0].setting.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_should_report_schema_mismatches() {
+        let spec = TABLES.iter().find(|spec| spec.name == "books").expect("should define books");
+        let index = |pk: &str, sk: &str, status: &str| IndexSchema {
+            key: KeySchema::new(pk, Some(sk)),
+            status: status.to_string(),
+        };
+        let actual = TableSchema {
+            name: "books".to_string(),
+            key: KeySchema::new("book_id", None),
+            indexes: BTreeMap::from([
+                ("books_ndx".to_string(), index("book_status", "isbn", "ACTIVE")),
+                ("books_author_ndx".to_string(), index("author_id", "created_at", "ACTIVE")),
+                ("books_shelf_ndx".to_string(), index("book_format", "call_number", "ACTIVE")),
+                ("books_acquisition_ndx".to_string(), index("book_format", "created_at", "ACTIVE")),
+            ]),
+        };
+        assert!(spec.schema_mismatches(&actual).is_empty());
+
+        let drifted = TableSchema {
+            key: KeySchema::new("isbn", None),
+            indexes: BTreeMap::from([
+                ("books_ndx".to_string(), index("book_status", "title", "ACTIVE")),
+                ("books_author_ndx".to_string(), index("author_id", "created_at", "CREATING")),
+            ]),
+            ..actual
+        };
+        let mismatches = spec.schema_mismatches(&drifted).iter().map(|mismatch| mismatch.to_string()).collect::<Vec<String>>();
+        assert_eq!(vec!["books: key schema is isbn, expected book_id",
+                        "books: index books_ndx is book_status/title, expected book_status/isbn",
+                        "books: index books_author_ndx status is CREATING, expected ACTIVE",
+                        "books: index books_shelf_ndx is missing, expected book_format/call_number",
+                        "books: index books_acquisition_ndx is missing, expected book_format/created_at"], mismatches);
+    }
+
+    #[tokio::test]
+    async fn test_should_define_unique_tables() {
+        let mut names = TABLES.iter().map(|spec| spec.name).collect::<Vec<&str>>();
+        names.sort();
+        names.dedup();
+        assert_eq!(TABLES.len(), names.len());
+    }
+}
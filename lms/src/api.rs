@@ -0,0 +1,48 @@
+// embedding API for applications that call catalog, hold and checkout services in process instead of over HTTP,
+// services are built against the chosen store the same way the lambda handlers build them
+pub use crate::core::domain::{Configuration, Identifiable};
+pub use crate::core::ids::{BookId, HoldId, PatronId};
+pub use crate::core::library::{BatchFailure, BatchResult, BatchStatus, BookFormat, BookStatus, CheckoutStatus, HoldStatus,
+                               ItemRouting, LibraryError, LibraryResult, PaginatedResult};
+pub use crate::core::repository::{ReadConsistency, RepositoryStore};
+
+// DTOs accepted and returned by the services
+pub use crate::audit::dto::StaffOverrideDto;
+pub use crate::books::dto::{BookDto, BookDtoBuilder, RelatedBookDto, TagCountDto};
+pub use crate::checkout::dto::{CheckInDto, CheckoutDto};
+pub use crate::hold::dto::HoldDto;
+
+// read and write sides of each service
+pub use crate::catalog::domain::{CatalogQueryService, CatalogService};
+pub use crate::checkout::domain::{CheckoutQueryService, CheckoutService};
+pub use crate::hold::domain::{HoldQueryService, HoldService};
+
+// constructors of the services, write services also publish domain events of the chosen store
+pub use crate::catalog::factory::{create_catalog_query_service, create_catalog_service};
+pub use crate::checkout::factory::{create_checkout_query_service, create_checkout_service};
+pub use crate::hold::factory::{create_hold_query_service, create_hold_service};
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{create_catalog_query_service, create_catalog_service, BookDto, BookStatus, Configuration, ReadConsistency};
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_embed_catalog_service() {
+        let store = test_store(module_path!(), "test_should_embed_catalog_service");
+        let config = Configuration::new("test");
+        let catalog_svc = create_catalog_service(&config, store).await;
+        let book = BookDto::builder().isbn("isbn").title("embedded book").build().expect("should build book");
+        let _ = catalog_svc.add_book(&book).await.expect("should add book");
+
+        let query_svc = create_catalog_query_service(&config, store).await;
+        let loaded = query_svc.find_book_by_id(&book.book_id).await.expect("should return book");
+        assert_eq!("embedded book", loaded.title.as_str());
+        assert_eq!(BookStatus::Available, loaded.book_status);
+
+        // applications read their own writes through the isbn index
+        let consistency = ReadConsistency::expecting(Some(book.book_id.as_str()));
+        let found = query_svc.find_book_by_isbn("isbn", &consistency).await.expect("should find books");
+        assert!(found.iter().any(|found| found.book_id == book.book_id));
+    }
+}
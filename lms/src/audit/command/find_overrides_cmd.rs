@@ -0,0 +1,105 @@
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::audit::domain::AuditQueryService;
+use crate::audit::dto::AuditDto;
+use crate::core::command::{Command, CommandError};
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+const DEFAULT_REPORT_DAYS: i64 = 30;
+
+pub(crate) struct FindOverridesCommand {
+    audit_service: Box<dyn AuditQueryService>,
+}
+
+impl FindOverridesCommand {
+    pub(crate) fn new(audit_service: Box<dyn AuditQueryService>) -> Self {
+        Self {
+            audit_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindOverridesCommandRequest {
+    pub(crate) from: Option<NaiveDateTime>,
+    pub(crate) to: Option<NaiveDateTime>,
+    pub(crate) page: Option<String>,
+    pub(crate) page_size: Option<usize>,
+}
+
+impl FindOverridesCommandRequest {
+    pub fn new(from: NaiveDateTime, to: NaiveDateTime) -> Self {
+        Self {
+            from: Some(from),
+            to: Some(to),
+            page: None,
+            page_size: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindOverridesCommandResponse {
+    pub overrides: Vec<AuditDto>,
+    pub next_page: Option<String>,
+}
+
+impl FindOverridesCommandResponse {
+    pub fn new(overrides: Vec<AuditDto>, next_page: Option<String>) -> Self {
+        Self {
+            overrides,
+            next_page,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<FindOverridesCommandRequest, FindOverridesCommandResponse> for FindOverridesCommand {
+    async fn execute(&self, req: FindOverridesCommandRequest) -> Result<FindOverridesCommandResponse, CommandError> {
+        // report defaults to overrides of last month
+        let to = req.to.unwrap_or_else(|| Utc::now().naive_utc());
+        let from = req.from.unwrap_or(to - Duration::days(DEFAULT_REPORT_DAYS));
+        self.audit_service.find_overrides(from, to, req.page.as_deref(),
+                                          req.page_size.unwrap_or(DEFAULT_PAGE_SIZE))
+            .await.map_err(CommandError::from).map(|res| FindOverridesCommandResponse::new(res.records, res.next_page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::audit::command::find_overrides_cmd::{FindOverridesCommand, FindOverridesCommandRequest};
+    use crate::audit::domain::AuditService;
+    use crate::audit::dto::StaffOverrideDto;
+    use crate::audit::factory::{create_audit_service, create_audit_query_service};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::OverrideRule;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn AuditService> {
+        create_audit_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> FindOverridesCommand {
+        let svc = create_audit_query_service(&Configuration::new("test"), store).await;
+        FindOverridesCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_find_overrides() {
+        let store = test_store(module_path!(), "test_should_run_find_overrides");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let audit = svc.record_override(&StaffOverrideDto::new("cmd_staff", "exam week"), OverrideRule::RestrictedBook,
+                                        "cmd_patron", "cmd_checkout").await.expect("should record override");
+
+        let now = Utc::now().naive_utc();
+        let res = sut_cmd.execute(FindOverridesCommandRequest::new(now - Duration::hours(1), now + Duration::hours(1)))
+            .await.expect("should find overrides");
+        assert!(res.overrides.iter().any(|a| a.audit_id == audit.audit_id));
+    }
+}
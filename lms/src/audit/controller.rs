@@ -0,0 +1,31 @@
+use axum::{
+    extract::{Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use crate::audit::command::find_overrides_cmd::{FindOverridesCommand, FindOverridesCommandRequest, FindOverridesCommandResponse};
+use crate::audit::domain::AuditQueryService;
+use crate::audit::factory;
+use crate::core::controller::{AppState, command_bus, with_common_layers, ServerError};
+use crate::utils::ddb::{build_db_client, create_table};
+
+async fn build_query_service(state: AppState) -> Box<dyn AuditQueryService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_table(&client, state.store.table_name("audit_log").as_str(), "audit_id", "audit_type", "created_at").await;
+    factory::create_audit_query_service(&state.config, state.store).await
+}
+
+pub(crate) async fn find_overrides(
+    State(state): State<AppState>,
+    Query(req): Query<FindOverridesCommandRequest>) -> Result<Json<FindOverridesCommandResponse>, ServerError> {
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(FindOverridesCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/audit/overrides", get(find_overrides));
+    with_common_layers(router, state)
+}
@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use crate::audit::dto::{AuditDto, StaffOverrideDto};
+use crate::core::library::{LibraryResult, OverrideRule, PaginatedResult};
+
+pub mod model;
+pub mod query;
+pub mod service;
+
+// read side of the audit log
+#[async_trait]
+pub(crate) trait AuditQueryService: Sync + Send {
+    // returns overrides recorded within the time window with most recent first
+    async fn find_overrides(&self, from: NaiveDateTime, to: NaiveDateTime,
+                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<AuditDto>>;
+}
+
+#[async_trait]
+pub(crate) trait AuditService: AuditQueryService {
+    // verifies that the override is given by a librarian with a reason
+    async fn authorize_override(&self, staff_override: &StaffOverrideDto) -> LibraryResult<()>;
+    async fn record_override(&self, staff_override: &StaffOverrideDto, rule: OverrideRule,
+                             patron_id: &str, subject_id: &str) -> LibraryResult<AuditDto>;
+    // records administrative action of a staff member or API client such as issuing API keys
+    async fn record_action(&self, audit_type: &str, actor_id: &str, subject_id: &str, details: &str) -> LibraryResult<AuditDto>;
+}
@@ -0,0 +1,44 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::core::context::RequestContext;
+use crate::utils::date::serializer;
+
+// AuditEntity records an action of a staff member such as overriding a policy rejection for a patron
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct AuditEntity {
+    pub audit_id: String,
+    pub audit_type: String,
+    pub staff_id: String,
+    pub patron_id: String,
+    // id of hold or checkout that was affected by the action
+    pub subject_id: String,
+    pub details: String,
+    pub reason: String,
+    // authenticated caller and correlation id of the request that caused the action
+    #[serde(default)]
+    pub principal: String,
+    #[serde(default)]
+    pub correlation_id: String,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+}
+
+impl AuditEntity {
+    pub fn new(audit_type: &str, staff_id: &str, patron_id: &str, subject_id: &str,
+               details: &str, reason: &str) -> Self {
+        let ctx = RequestContext::current();
+        Self {
+            audit_id: Uuid::new_v4().to_string(),
+            audit_type: audit_type.to_string(),
+            staff_id: staff_id.to_string(),
+            patron_id: patron_id.to_string(),
+            subject_id: subject_id.to_string(),
+            details: details.to_string(),
+            reason: reason.to_string(),
+            principal: ctx.as_ref().map(|c| c.principal()).unwrap_or_default(),
+            correlation_id: ctx.map(|c| c.correlation_id).unwrap_or_default(),
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+use crate::audit::domain::AuditQueryService;
+use crate::audit::domain::service::STAFF_OVERRIDE;
+use crate::audit::dto::AuditDto;
+use crate::audit::repository::AuditRepository;
+use crate::core::library::{LibraryResult, PaginatedResult};
+
+pub(crate) struct AuditQueryServiceImpl {
+    audit_repository: Box<dyn AuditRepository>,
+}
+
+impl AuditQueryServiceImpl {
+    pub(crate) fn new(audit_repository: Box<dyn AuditRepository>) -> Self {
+        Self {
+            audit_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl AuditQueryService for AuditQueryServiceImpl {
+    async fn find_overrides(&self, from: NaiveDateTime, to: NaiveDateTime,
+                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<AuditDto>> {
+        let res = self.audit_repository.find_by_type(STAFF_OVERRIDE, from, to, page, page_size).await?;
+        let records = res.records.iter().map(AuditDto::from).collect();
+        Ok(PaginatedResult::new(page, page_size, res.next_page, records))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::audit::domain::AuditQueryService;
+    use crate::audit::factory;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn AuditQueryService> {
+        factory::create_audit_query_service(&Configuration::new("test"), store).await
+    }
+
+    #[tokio::test]
+    async fn test_should_not_find_overrides_in_future() {
+        let store = test_store(module_path!(), "test_should_not_find_overrides_in_future");
+        let query_svc = sut_svc(store).await;
+        let from = Utc::now().naive_utc() + Duration::days(365);
+        let res = query_svc.find_overrides(from, from + Duration::hours(1), None, 100)
+            .await.expect("should find overrides");
+        assert_eq!(0, res.records.len());
+    }
+}
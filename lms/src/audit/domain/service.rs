@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+use crate::audit::domain::{AuditQueryService, AuditService};
+use crate::audit::domain::model::AuditEntity;
+use crate::audit::dto::{AuditDto, StaffOverrideDto};
+use crate::audit::repository::AuditRepository;
+use crate::core::events::DomainEvent;
+use crate::core::ids::PatronId;
+use crate::core::library::{LibraryError, LibraryResult, OverrideRule, PaginatedResult};
+use crate::gateway::events::EventPublisher;
+use crate::patrons::domain::PatronService;
+use crate::patrons::Patron;
+
+pub(crate) const STAFF_OVERRIDE: &str = "staff_override";
+
+pub(crate) struct AuditServiceImpl {
+    audit_repository: Box<dyn AuditRepository>,
+    patron_service: Box<dyn PatronService>,
+    events_publisher: Box<dyn EventPublisher>,
+    query_service: Box<dyn AuditQueryService>,
+}
+
+impl AuditServiceImpl {
+    pub(crate) fn new(audit_repository: Box<dyn AuditRepository>,
+                      patron_service: Box<dyn PatronService>,
+                      events_publisher: Box<dyn EventPublisher>,
+                      query_service: Box<dyn AuditQueryService>) -> Self {
+        Self {
+            audit_repository,
+            patron_service,
+            events_publisher,
+            query_service,
+        }
+    }
+}
+
+#[async_trait]
+impl AuditService for AuditServiceImpl {
+    async fn authorize_override(&self, staff_override: &StaffOverrideDto) -> LibraryResult<()> {
+        if staff_override.reason.trim().is_empty() {
+            return Err(LibraryError::validation("override reason is required", Some("400".to_string())));
+        }
+        let staff = self.patron_service.find_patron_by_id(&PatronId::new(staff_override.staff_id.as_str())).await?;
+        if !staff.is_librarian() && !staff.is_admin() {
+            return Err(LibraryError::not_granted(format!("{} cannot override policies",
+                                                         staff_override.staff_id).as_str(), Some("403".to_string())));
+        }
+        Ok(())
+    }
+
+    async fn record_override(&self, staff_override: &StaffOverrideDto, rule: OverrideRule,
+                             patron_id: &str, subject_id: &str) -> LibraryResult<AuditDto> {
+        let audit = AuditEntity::new(STAFF_OVERRIDE, staff_override.staff_id.as_str(), patron_id, subject_id,
+                                     rule.to_string().as_str(), staff_override.reason.as_str());
+        self.audit_repository.create(&audit).await?;
+        let dto = AuditDto::from(&audit);
+        let _ = self.events_publisher.publish(&DomainEvent::added(
+            STAFF_OVERRIDE, "audit", dto.audit_id.as_str(), &HashMap::new(), &dto)?).await?;
+        Ok(dto)
+    }
+
+    async fn record_action(&self, audit_type: &str, actor_id: &str, subject_id: &str, details: &str) -> LibraryResult<AuditDto> {
+        let audit = AuditEntity::new(audit_type, actor_id, "", subject_id, details, "");
+        self.audit_repository.create(&audit).await?;
+        let dto = AuditDto::from(&audit);
+        let _ = self.events_publisher.publish(&DomainEvent::added(
+            audit_type, "audit", dto.audit_id.as_str(), &HashMap::new(), &dto)?).await?;
+        Ok(dto)
+    }
+}
+
+#[async_trait]
+impl AuditQueryService for AuditServiceImpl {
+    async fn find_overrides(&self, from: NaiveDateTime, to: NaiveDateTime,
+                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<AuditDto>> {
+        self.query_service.find_overrides(from, to, page, page_size).await
+    }
+}
+
+impl From<&AuditEntity> for AuditDto {
+    fn from(other: &AuditEntity) -> AuditDto {
+        AuditDto {
+            audit_id: other.audit_id.to_string(),
+            audit_type: other.audit_type.to_string(),
+            staff_id: other.staff_id.to_string(),
+            patron_id: other.patron_id.to_string(),
+            subject_id: other.subject_id.to_string(),
+            details: other.details.to_string(),
+            reason: other.reason.to_string(),
+            principal: other.principal.to_string(),
+            correlation_id: other.correlation_id.to_string(),
+            created_at: other.created_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::audit::domain::AuditService;
+    use crate::audit::dto::StaffOverrideDto;
+    use crate::audit::factory;
+    use crate::core::context::RequestContext;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{OverrideRule, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn AuditService> {
+        factory::create_audit_service(&Configuration::new("test"), store).await
+    }
+
+    #[tokio::test]
+    async fn test_should_authorize_and_record_override() {
+        let store = test_store(module_path!(), "test_should_authorize_and_record_override");
+        let audit_svc = sut_svc(store).await;
+        let patron = PartyEntity::new(PartyKind::Patron, "override_patron@example.com");
+        let mut librarian = PartyEntity::new(PartyKind::Employee, "override_librarian@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        for party in [&patron, &librarian] {
+            let _ = create_party_repository(store).await.create(party).await.expect("should create party");
+        }
+
+        assert!(audit_svc.authorize_override(&StaffOverrideDto::new(patron.party_id.as_str(), "friend")).await.is_err());
+        assert!(audit_svc.authorize_override(&StaffOverrideDto::new(librarian.party_id.as_str(), " ")).await.is_err());
+        let staff_override = StaffOverrideDto::new(librarian.party_id.as_str(), "visiting scholar");
+        audit_svc.authorize_override(&staff_override).await.expect("should authorize override");
+
+        let audit = audit_svc.record_override(&staff_override, OverrideRule::MaxHolds, patron.party_id.as_str(), "hold")
+            .await.expect("should record override");
+        assert_eq!("MaxHolds", audit.details.as_str());
+        let now = Utc::now().naive_utc();
+        let res = audit_svc.find_overrides(now - Duration::hours(1), now + Duration::hours(1), None, 100)
+            .await.expect("should find overrides");
+        assert!(res.records.iter().any(|a| a.audit_id == audit.audit_id));
+    }
+
+    #[tokio::test]
+    async fn test_should_record_action() {
+        let store = test_store(module_path!(), "test_should_record_action");
+        let audit_svc = sut_svc(store).await;
+        let audit = audit_svc.record_action("api_key_revoked", "api_key:key1", "key1", "discovery-layer")
+            .await.expect("should record action");
+        assert_eq!("api_key_revoked", audit.audit_type.as_str());
+        assert_eq!("api_key:key1", audit.staff_id.as_str());
+        assert_eq!("", audit.correlation_id.as_str());
+
+        let ctx = RequestContext::anonymous("test", Some("audit-corr"), None).authenticated("admin1", &[], "", "key2");
+        let audit = ctx.scope(audit_svc.record_action("api_key_revoked", "admin1", "key2", "discovery-layer"))
+            .await.expect("should record action");
+        assert_eq!("api_key:key2", audit.principal.as_str());
+        assert_eq!("audit-corr", audit.correlation_id.as_str());
+        // actions are not overrides
+        let now = Utc::now().naive_utc();
+        let res = audit_svc.find_overrides(now - Duration::hours(1), now + Duration::hours(1), None, 100)
+            .await.expect("should find overrides");
+        assert!(!res.records.iter().any(|a| a.audit_id == audit.audit_id));
+    }
+}
@@ -0,0 +1,38 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use crate::utils::date::{serializer, Rfc3339};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct AuditDto {
+    pub audit_id: String,
+    pub audit_type: String,
+    pub staff_id: String,
+    pub patron_id: String,
+    pub subject_id: String,
+    pub details: String,
+    pub reason: String,
+    #[serde(default)]
+    pub principal: String,
+    #[serde(default)]
+    pub correlation_id: String,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub created_at: NaiveDateTime,
+}
+
+// StaffOverrideDto is supplied by librarians to bypass policy rejections such as max holds
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct StaffOverrideDto {
+    pub staff_id: String,
+    pub reason: String,
+}
+
+impl StaffOverrideDto {
+    pub fn new(staff_id: &str, reason: &str) -> Self {
+        Self {
+            staff_id: staff_id.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
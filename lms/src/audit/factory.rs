@@ -0,0 +1,39 @@
+use crate::audit::domain::{AuditQueryService, AuditService};
+use crate::audit::domain::query::AuditQueryServiceImpl;
+use crate::audit::domain::service::AuditServiceImpl;
+use crate::audit::factory;
+use crate::audit::repository::AuditRepository;
+use crate::audit::repository::ddb_audit_repository::DDBAuditRepository;
+use crate::core::domain::Configuration;
+use crate::core::repository::RepositoryStore;
+use crate::gateway::factory::create_publisher;
+use crate::patrons::factory::create_patron_service;
+use crate::utils::ddb::{build_db_client, create_table};
+
+pub(crate) async fn create_audit_repository(store: RepositoryStore) -> Box<dyn AuditRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBAuditRepository::new(client, "audit_log", "audit_log_ndx"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("audit_log");
+            let _ = create_table(&client, table_name.as_str(), "audit_id", "audit_type", "created_at").await;
+            Box::new(DDBAuditRepository::new(client, table_name.as_str(), store.table_name("audit_log_ndx").as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_audit_query_service(_config: &Configuration, store: RepositoryStore) -> Box<dyn AuditQueryService> {
+    let audit_repo = factory::create_audit_repository(store).await;
+    Box::new(AuditQueryServiceImpl::new(audit_repo))
+}
+
+pub(crate) async fn create_audit_service(config: &Configuration, store: RepositoryStore) -> Box<dyn AuditService> {
+    let audit_repo = factory::create_audit_repository(store).await;
+    let patron_svc = create_patron_service(config, store).await;
+    let publisher = create_publisher(store.gateway_publisher()).await;
+    let query_svc = create_audit_query_service(config, store).await;
+    Box::new(AuditServiceImpl::new(audit_repo, patron_svc, publisher, query_svc))
+}
@@ -0,0 +1,13 @@
+pub mod ddb_audit_repository;
+
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use crate::audit::domain::model::AuditEntity;
+use crate::core::library::{LibraryResult, PaginatedResult};
+
+#[async_trait]
+pub(crate) trait AuditRepository: Sync + Send {
+    async fn create(&self, entity: &AuditEntity) -> LibraryResult<usize>;
+    async fn find_by_type(&self, audit_type: &str, from: NaiveDateTime, to: NaiveDateTime,
+                          page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<AuditEntity>>;
+}
@@ -0,0 +1,125 @@
+use std::cmp;
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::{NaiveDateTime, Utc};
+
+use crate::audit::domain::model::AuditEntity;
+use crate::audit::repository::AuditRepository;
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult};
+use crate::utils::ddb::{from_ddb, parse_date_attribute, parse_item, parse_string_attribute, string_date, to_ddb_page};
+
+#[derive(Debug)]
+pub(crate) struct DDBAuditRepository {
+    client: Client,
+    table_name: String,
+    index_name: String,
+}
+
+impl DDBAuditRepository {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl AuditRepository for DDBAuditRepository {
+    async fn create(&self, entity: &AuditEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        let mut item = parse_item(val)?;
+        item.insert("created_at".to_string(), string_date(entity.created_at));
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(audit_id)")
+            .set_item(Some(item))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn find_by_type(&self, audit_type: &str, from: NaiveDateTime, to: NaiveDateTime,
+                          page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<AuditEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let predicate = HashMap::from([("audit_type".to_string(), audit_type.to_string())]);
+        self.client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .limit(cmp::min(page_size, 500) as i32)
+            .consistent_read(false)
+            .scan_index_forward(false)
+            .set_exclusive_start_key(to_ddb_page(page, &predicate))
+            .key_condition_expression("audit_type = :audit_type AND created_at BETWEEN :from AND :to")
+            .expression_attribute_values(":audit_type", AttributeValue::S(audit_type.to_string()))
+            .expression_attribute_values(":from", string_date(from))
+            .expression_attribute_values(":to", string_date(to))
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(AuditEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for AuditEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        AuditEntity {
+            audit_id: parse_string_attribute("audit_id", map).unwrap_or_else(|| String::from("")),
+            audit_type: parse_string_attribute("audit_type", map).unwrap_or_else(|| String::from("")),
+            staff_id: parse_string_attribute("staff_id", map).unwrap_or_else(|| String::from("")),
+            patron_id: parse_string_attribute("patron_id", map).unwrap_or_else(|| String::from("")),
+            subject_id: parse_string_attribute("subject_id", map).unwrap_or_else(|| String::from("")),
+            details: parse_string_attribute("details", map).unwrap_or_else(|| String::from("")),
+            reason: parse_string_attribute("reason", map).unwrap_or_else(|| String::from("")),
+            principal: parse_string_attribute("principal", map).unwrap_or_else(|| String::from("")),
+            correlation_id: parse_string_attribute("correlation_id", map).unwrap_or_else(|| String::from("")),
+            created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+    use chrono::{Duration, Utc};
+
+    use crate::audit::domain::model::AuditEntity;
+    use crate::audit::repository::AuditRepository;
+    use crate::audit::repository::ddb_audit_repository::DDBAuditRepository;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("audit_log").as_str(), "audit_id", "audit_type", "created_at").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_create_find_audit_records() {
+        let store = test_store(module_path!(), "test_should_create_find_audit_records");
+        let repo = DDBAuditRepository::new(build_client(store).await, store.table_name("audit_log").as_str(), store.table_name("audit_log_ndx").as_str());
+        let audit = AuditEntity::new("repo_override", "staff", "patron", "hold", "MaxHolds", "visiting scholar");
+        assert_eq!(1, repo.create(&audit).await.expect("should create audit"));
+        assert!(repo.create(&audit).await.is_err());
+
+        let now = Utc::now().naive_utc();
+        let res = repo.find_by_type("repo_override", now - Duration::hours(1), now + Duration::hours(1), None, 10)
+            .await.expect("should find audit records");
+        assert_eq!(1, res.records.len());
+        assert_eq!(audit, res.records[0]);
+        let res = repo.find_by_type("repo_override", now - Duration::days(2), now - Duration::days(1), None, 10)
+            .await.expect("should find audit records");
+        assert_eq!(0, res.records.len());
+    }
+}
@@ -1,9 +1,13 @@
 use crate::core::domain::Identifiable;
-use crate::core::library::BookStatus;
+use crate::core::library::{BookFormat, BookStatus};
 
 pub mod model;
 
 pub(crate) trait Book: Identifiable {
     fn is_restricted(&self) -> bool;
     fn status(&self) -> BookStatus;
+    fn format(&self) -> BookFormat;
+    fn is_digital(&self) -> bool {
+        self.format().is_digital()
+    }
 }
@@ -3,7 +3,7 @@ use rand::Rng;
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use crate::core::domain::Identifiable;
-use crate::core::library::BookStatus;
+use crate::core::library::{BookFormat, BookStatus, LanguageCode};
 use crate::utils::date::serializer;
 
 // BookEntity abstracts physical book in library management system and there can be
@@ -15,11 +15,34 @@ pub(crate) struct BookEntity {
     pub version: i64,
     pub author_id: String,
     pub publisher_id: String,
-    pub language: String,
+    #[serde(default)]
+    pub language: LanguageCode,
     pub isbn: String,
     pub title: String,
     pub book_status: BookStatus,
     pub restricted: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub book_format: BookFormat,
+    // concurrent licenses of digital items, available_licenses is decremented on checkout
+    #[serde(default)]
+    pub license_count: i64,
+    #[serde(default)]
+    pub available_licenses: i64,
+    // shelving metadata, call number is built from dewey decimal id, collection and title
+    #[serde(default)]
+    pub collection: String,
+    #[serde(default)]
+    pub shelf_location: String,
+    #[serde(default)]
+    pub call_number: String,
+    // home branch of the copy, copies of floating collections are moved to the branch they are returned at
+    #[serde(default)]
+    pub branch_id: String,
+    // key of the cover image in the cover store, empty until a cover is uploaded
+    #[serde(default)]
+    pub cover_key: String,
     #[serde(with = "serializer")]
     pub published_at: NaiveDateTime,
     #[serde(with = "serializer")]
@@ -47,11 +70,20 @@ impl BookEntity {
             book_id: Uuid::new_v4().to_string(),
             author_id: Uuid::new_v4().to_string(), // random for testing purpose
             publisher_id: Uuid::new_v4().to_string(), // random for testing purpose
-            language: "en".to_string(), // random for testing purpose
+            language: LanguageCode::default(), // random for testing purpose
             isbn: isbn.to_string(),
             title: title.to_string(),
             book_status: status,
             restricted: false,
+            tags: vec![],
+            book_format: BookFormat::Physical,
+            license_count: 0,
+            available_licenses: 0,
+            collection: String::new(),
+            shelf_location: String::new(),
+            call_number: String::new(),
+            branch_id: String::new(),
+            cover_key: String::new(),
             published_at: Utc::now().naive_utc(), // for testing purpose
             created_at: Utc::now().naive_utc(),
             updated_at: Utc::now().naive_utc(),
@@ -69,10 +101,29 @@ impl Identifiable for BookEntity {
     }
 }
 
+// TagCountEntity keeps number of books that are tagged with a genre or tag
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct TagCountEntity {
+    pub tag_name: String,
+    pub usage_count: i64,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl TagCountEntity {
+    pub fn new(tag_name: &str, usage_count: i64) -> Self {
+        Self {
+            tag_name: tag_name.to_string(),
+            usage_count,
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
-    use crate::books::domain::model::BookEntity;
+    use crate::books::domain::model::{BookEntity, TagCountEntity};
     use crate::core::library::BookStatus;
 
     #[tokio::test]
@@ -81,5 +132,13 @@ mod tests {
         assert_eq!("isbn", book.isbn.as_str());
         assert_eq!("title", book.title.as_str());
         assert_eq!("en", book.language.as_str());
+        assert!(book.tags.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_should_build_tag_count() {
+        let tag = TagCountEntity::new("fiction", 3);
+        assert_eq!("fiction", tag.tag_name.as_str());
+        assert_eq!(3, tag.usage_count);
     }
 }
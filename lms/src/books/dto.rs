@@ -1,49 +1,464 @@
+use std::collections::HashMap;
 use chrono::{NaiveDateTime, Utc};
-use rand::Rng;
-use uuid::Uuid;
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use crate::books::domain::Book;
 use crate::core::domain::Identifiable;
-use crate::core::library::BookStatus;
-use crate::utils::date::serializer;
+use crate::core::ids::BookId;
+use crate::core::library::{BatchResult, BookFormat, BookStatus, LanguageCode, LibraryError, LibraryResult};
+use crate::utils::date::{serializer, Rfc3339};
 
 // BookDto is a data transfer object for Catalog service
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub(crate) struct BookDto {
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct BookDto {
     pub dewey_decimal_id: String,
-    pub book_id: String,
+    pub book_id: BookId,
     pub version: i64,
     pub author_id: String,
     pub publisher_id: String,
-    pub language: String,
+    // iso 639-1 code such as en
+    #[serde(default)]
+    #[schemars(with = "String")]
+    pub language: LanguageCode,
     pub isbn: String,
     pub title: String,
     pub book_status: BookStatus,
     pub restricted: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub book_format: BookFormat,
+    // concurrent licenses of digital items, available_licenses is decremented on checkout
+    #[serde(default)]
+    pub license_count: i64,
+    #[serde(default)]
+    pub available_licenses: i64,
+    // shelving metadata, call number is built from dewey decimal id, collection and title
+    #[serde(default)]
+    pub collection: String,
+    #[serde(default)]
+    pub shelf_location: String,
+    #[serde(default)]
+    pub call_number: String,
+    // home branch of the copy, copies of floating collections are moved to the branch they are returned at
+    #[serde(default)]
+    pub branch_id: String,
+    // key of the cover image in the cover store, empty until a cover is uploaded
+    #[serde(default)]
+    pub cover_key: String,
     #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
     pub published_at: NaiveDateTime,
     #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
     pub created_at: NaiveDateTime,
     #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
     pub updated_at: NaiveDateTime,
 }
 
 impl BookDto {
+    pub fn builder() -> BookDtoBuilder {
+        BookDtoBuilder::default()
+    }
+
+    // canonical book of command examples with fixed id and timestamps
+    #[cfg(feature = "examples")]
+    pub(crate) fn example() -> BookDto {
+        use crate::core::command::examples::{example_time, EXAMPLE_BOOK_ID, EXAMPLE_ISBN};
+        let mut book = BookDto::builder().book_id(EXAMPLE_BOOK_ID).isbn(EXAMPLE_ISBN).title("Dune")
+            .dewey_decimal_id("813.54").author_id("frank-herbert").publisher_id("ace-books").language("en")
+            .tags(&["science-fiction".to_string()]).collection("FIC").shelf_location("A3")
+            .build().expect("should build example book");
+        book.call_number = "FIC 813.54 DUN".to_string();
+        book.published_at = example_time();
+        book.created_at = example_time();
+        book.updated_at = example_time();
+        book
+    }
+
+    // test fixture with random dewey, author and publisher, production code uses the builder
+    #[cfg(test)]
     pub fn new(isbn: &str, title: &str, status: BookStatus) -> BookDto {
-        BookDto {
-            dewey_decimal_id: format!("{}", rand::thread_rng().gen_range(0..1000)),
+        use rand::Rng;
+        BookDto::builder().isbn(isbn).title(title).book_status(status)
+            .dewey_decimal_id(format!("{}", rand::thread_rng().gen_range(0..1000)).as_str())
+            .author_id(uuid::Uuid::new_v4().to_string().as_str())
+            .publisher_id(uuid::Uuid::new_v4().to_string().as_str())
+            .build().expect("should build book")
+    }
+}
+
+// BookDtoBuilder builds a book from the required isbn and title, other fields are optional and default to
+// empty values instead of made up metadata
+#[derive(Debug, Clone, Default)]
+pub struct BookDtoBuilder {
+    book_id: Option<String>,
+    isbn: String,
+    title: String,
+    dewey_decimal_id: String,
+    author_id: String,
+    publisher_id: String,
+    language: Option<String>,
+    book_status: Option<BookStatus>,
+    restricted: bool,
+    tags: Vec<String>,
+    book_format: BookFormat,
+    license_count: i64,
+    collection: String,
+    shelf_location: String,
+}
+
+impl BookDtoBuilder {
+    // id of an existing book, a new id is generated otherwise
+    pub fn book_id(mut self, book_id: &str) -> Self {
+        self.book_id = Some(book_id.to_string());
+        self
+    }
+
+    pub fn isbn(mut self, isbn: &str) -> Self {
+        self.isbn = isbn.trim().to_string();
+        self
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = title.trim().to_string();
+        self
+    }
+
+    pub fn dewey_decimal_id(mut self, dewey_decimal_id: &str) -> Self {
+        self.dewey_decimal_id = dewey_decimal_id.trim().to_string();
+        self
+    }
+
+    pub fn author_id(mut self, author_id: &str) -> Self {
+        self.author_id = author_id.to_string();
+        self
+    }
+
+    pub fn publisher_id(mut self, publisher_id: &str) -> Self {
+        self.publisher_id = publisher_id.to_string();
+        self
+    }
+
+    pub fn language(mut self, language: &str) -> Self {
+        self.language = Some(language.to_string());
+        self
+    }
+
+    pub fn book_status(mut self, book_status: BookStatus) -> Self {
+        self.book_status = Some(book_status);
+        self
+    }
+
+    pub fn restricted(mut self, restricted: bool) -> Self {
+        self.restricted = restricted;
+        self
+    }
+
+    pub fn tags(mut self, tags: &[String]) -> Self {
+        self.tags = tags.to_vec();
+        self
+    }
+
+    pub fn book_format(mut self, book_format: BookFormat) -> Self {
+        self.book_format = book_format;
+        self
+    }
+
+    pub fn license_count(mut self, license_count: i64) -> Self {
+        self.license_count = license_count;
+        self
+    }
+
+    pub fn collection(mut self, collection: &str) -> Self {
+        self.collection = collection.to_string();
+        self
+    }
+
+    pub fn shelf_location(mut self, shelf_location: &str) -> Self {
+        self.shelf_location = shelf_location.to_string();
+        self
+    }
+
+    pub fn build(self) -> LibraryResult<BookDto> {
+        if self.isbn.is_empty() {
+            return Err(LibraryError::validation("isbn is required", Some("400".to_string())));
+        }
+        if self.title.is_empty() {
+            return Err(LibraryError::validation("title is required", Some("400".to_string())));
+        }
+        if self.license_count < 0 {
+            return Err(LibraryError::validation("license count cannot be negative", Some("400".to_string())));
+        }
+        let language = match self.language {
+            Some(language) => LanguageCode::parse(language.as_str())?,
+            None => LanguageCode::default(),
+        };
+        let now = Utc::now().naive_utc();
+        Ok(BookDto {
+            dewey_decimal_id: self.dewey_decimal_id,
+            book_id: self.book_id.map(BookId::from).unwrap_or_else(BookId::generate),
             version: 0,
-            book_id: Uuid::new_v4().to_string(),
-            author_id: Uuid::new_v4().to_string(), // random for testing purpose
-            publisher_id: Uuid::new_v4().to_string(), // random for testing purpose
-            language: "en".to_string(), // random for testing purpose
+            author_id: self.author_id,
+            publisher_id: self.publisher_id,
+            language,
+            isbn: self.isbn,
+            title: self.title,
+            book_status: self.book_status.unwrap_or(BookStatus::Available),
+            restricted: self.restricted,
+            tags: self.tags,
+            book_format: self.book_format,
+            license_count: self.license_count,
+            available_licenses: 0,
+            collection: self.collection,
+            shelf_location: self.shelf_location,
+            call_number: String::new(),
+            branch_id: String::new(),
+            cover_key: String::new(),
+            // publication date is not captured when cataloging yet
+            published_at: now,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+}
+
+// BookFilter narrows catalog listings down to books of a language and format
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BookFilter {
+    pub language: Option<LanguageCode>,
+    pub book_format: Option<BookFormat>,
+}
+
+impl BookFilter {
+    // parses query parameters of listings, blank parameters do not filter
+    pub(crate) fn from_params(language: Option<&str>, book_format: Option<&str>) -> LibraryResult<BookFilter> {
+        Ok(BookFilter {
+            language: language.filter(|l| !l.trim().is_empty()).map(LanguageCode::parse).transpose()?,
+            book_format: book_format.filter(|f| !f.trim().is_empty()).map(BookFormat::parse).transpose()?,
+        })
+    }
+
+    // attributes and values that books of the listing must be equal to
+    pub(crate) fn predicate(&self) -> HashMap<String, String> {
+        let mut predicate = HashMap::new();
+        if let Some(language) = &self.language {
+            predicate.insert("language".to_string(), language.to_string());
+        }
+        if let Some(book_format) = &self.book_format {
+            predicate.insert("book_format".to_string(), book_format.to_string());
+        }
+        predicate
+    }
+}
+
+// BookSortField lists attributes that catalog listings can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum BookSortField {
+    Title,
+    PublishedAt,
+    CreatedAt,
+}
+
+// BookSort orders a page of catalog listings such as sort=title or sort=-published_at for descending order, pages
+// are read in index order so the sort applies within each page
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct BookSort {
+    pub field: BookSortField,
+    pub descending: bool,
+}
+
+impl BookSort {
+    // listings without a sort parameter are left in index order
+    pub(crate) fn from_param(sort: Option<&str>) -> LibraryResult<Option<BookSort>> {
+        sort.filter(|s| !s.trim().is_empty()).map(BookSort::parse).transpose()
+    }
+
+    pub(crate) fn parse(sort: &str) -> LibraryResult<BookSort> {
+        let sort = sort.trim();
+        let (name, descending) = match sort.strip_prefix('-') {
+            Some(name) => (name, true),
+            None => (sort, false),
+        };
+        let field = match name {
+            "title" => BookSortField::Title,
+            "published_at" => BookSortField::PublishedAt,
+            "created_at" => BookSortField::CreatedAt,
+            _ => return Err(LibraryError::validation(
+                format!("cannot sort books by {}, use title, published_at or created_at", name).as_str(),
+                Some("400".to_string()))),
+        };
+        Ok(BookSort { field, descending })
+    }
+
+    // ties are broken by book id so that equal titles or dates keep a stable order
+    pub(crate) fn sort(&self, books: &mut [BookDto]) {
+        books.sort_by(|a, b| {
+            let ordering = match self.field {
+                BookSortField::Title => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+                BookSortField::PublishedAt => a.published_at.cmp(&b.published_at),
+                BookSortField::CreatedAt => a.created_at.cmp(&b.created_at),
+            };
+            let ordering = if self.descending { ordering.reverse() } else { ordering };
+            ordering.then_with(|| a.book_id.cmp(&b.book_id))
+        });
+    }
+}
+
+// TagCountDto is a data transfer object for tag usage of Catalog service
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagCountDto {
+    pub tag: String,
+    pub usage_count: i64,
+}
+
+impl TagCountDto {
+    pub fn new(tag: &str, usage_count: i64) -> TagCountDto {
+        TagCountDto {
+            tag: tag.to_string(),
+            usage_count,
+        }
+    }
+}
+
+// RelatedBookDto is a book recommended for another book along with the reasons of the relation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelatedBookDto {
+    pub book: BookDto,
+    pub score: i64,
+    pub same_author: bool,
+    pub shared_tags: Vec<String>,
+    pub co_checkout_count: i64,
+}
+
+impl RelatedBookDto {
+    pub fn new(book: BookDto) -> RelatedBookDto {
+        RelatedBookDto {
+            book,
+            score: 0,
+            same_author: false,
+            shared_tags: vec![],
+            co_checkout_count: 0,
+        }
+    }
+}
+
+// TrendingBookDto is a book with its decay-weighted score of recent checkouts and holds in a window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrendingBookDto {
+    pub book: BookDto,
+    pub score: f64,
+}
+
+impl TrendingBookDto {
+    pub fn new(book: BookDto, score: f64) -> TrendingBookDto {
+        TrendingBookDto {
+            book,
+            score,
+        }
+    }
+}
+
+// DuplicateBooksDto groups catalog records whose isbn is the same once normalized, the oldest record comes first
+// and is kept when the group is merged
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicateBooksDto {
+    pub isbn: String,
+    pub books: Vec<BookDto>,
+}
+
+impl DuplicateBooksDto {
+    pub fn new(isbn: &str, books: Vec<BookDto>) -> DuplicateBooksDto {
+        DuplicateBooksDto {
             isbn: isbn.to_string(),
-            title: title.to_string(),
-            book_status: status,
-            restricted: false,
-            published_at: Utc::now().naive_utc(), // for testing purpose
-            created_at: Utc::now().naive_utc(),
-            updated_at: Utc::now().naive_utc(),
+            books,
+        }
+    }
+}
+
+// ImportedBookDto reports a book added by a MARC import along with the number of its record in the import and the
+// control number (001) of the record in the legacy system
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportedBookDto {
+    pub record: usize,
+    pub control_number: String,
+    pub book_id: String,
+    pub isbn: String,
+    pub title: String,
+}
+
+impl ImportedBookDto {
+    pub fn new(record: usize, control_number: &str, book: &BookDto) -> ImportedBookDto {
+        ImportedBookDto {
+            record,
+            control_number: control_number.to_string(),
+            book_id: book.book_id.to_string(),
+            isbn: book.isbn.to_string(),
+            title: book.title.to_string(),
+        }
+    }
+}
+
+// UnmappedFieldDto counts the records of a MARC import with data of the field that was not carried over to the books
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnmappedFieldDto {
+    pub tag: String,
+    pub records: usize,
+}
+
+impl UnmappedFieldDto {
+    pub fn new(tag: &str, records: usize) -> UnmappedFieldDto {
+        UnmappedFieldDto {
+            tag: tag.to_string(),
+            records,
+        }
+    }
+}
+
+// MarcImportDto is the outcome of each record of a MARC import with the report of unmapped fields ordered by tag
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarcImportDto {
+    #[serde(flatten)]
+    pub result: BatchResult<ImportedBookDto>,
+    pub unmapped_fields: Vec<UnmappedFieldDto>,
+}
+
+impl MarcImportDto {
+    pub fn new(result: BatchResult<ImportedBookDto>, unmapped_fields: Vec<UnmappedFieldDto>) -> MarcImportDto {
+        MarcImportDto {
+            result,
+            unmapped_fields,
+        }
+    }
+}
+
+// FederatedSearchDto lists books of the union catalog that match a search as candidates for cataloging, a degraded
+// search has no candidates and tells why the union catalog could not be searched
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FederatedSearchDto {
+    pub query: String,
+    pub candidates: Vec<BookDto>,
+    pub degraded: bool,
+    pub reason: Option<String>,
+}
+
+impl FederatedSearchDto {
+    pub fn new(query: &str, candidates: Vec<BookDto>) -> FederatedSearchDto {
+        FederatedSearchDto {
+            query: query.to_string(),
+            candidates,
+            degraded: false,
+            reason: None,
+        }
+    }
+
+    pub fn degraded(query: &str, reason: &str) -> FederatedSearchDto {
+        FederatedSearchDto {
+            query: query.to_string(),
+            candidates: vec![],
+            degraded: true,
+            reason: Some(reason.to_string()),
         }
     }
 }
@@ -66,12 +481,17 @@ impl Book for BookDto {
     fn status(&self) -> BookStatus {
         self.book_status
     }
+
+    fn format(&self) -> BookFormat {
+        self.book_format
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::books::dto::BookDto;
-    use crate::core::library::BookStatus;
+    use chrono::Duration;
+    use crate::books::dto::{BookDto, BookFilter, BookSort, BookSortField};
+    use crate::core::library::{BookFormat, BookStatus, LanguageCode};
 
     #[tokio::test]
     async fn test_should_build_books() {
@@ -80,4 +500,52 @@ mod tests {
         assert_eq!("title", book.title.as_str());
         assert_eq!("en", book.language.as_str());
     }
+
+    #[tokio::test]
+    async fn test_should_build_book_without_placeholders() {
+        let book = BookDto::builder().isbn("isbn").title("title").author_id("author1")
+            .book_format(BookFormat::EBook).license_count(2).build().expect("should build book");
+        assert_eq!("author1", book.author_id.as_str());
+        assert_eq!(BookStatus::Available, book.book_status);
+        assert_eq!(BookFormat::EBook, book.book_format);
+        assert!(book.dewey_decimal_id.is_empty());
+        assert!(book.publisher_id.is_empty());
+        assert!(!book.book_id.as_str().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_should_not_build_book_without_required_fields() {
+        assert!(BookDto::builder().title("title").build().is_err());
+        assert!(BookDto::builder().isbn("isbn").title(" ").build().is_err());
+        assert!(BookDto::builder().isbn("isbn").title("title").license_count(-1).build().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_sort_books() {
+        let mut books = vec![BookDto::new("isbn", "beta", BookStatus::Available),
+                             BookDto::new("isbn", "Alpha", BookStatus::Available),
+                             BookDto::new("isbn", "gamma", BookStatus::Available)];
+        books[0].published_at -= Duration::days(10);
+        books[2].published_at -= Duration::days(5);
+        BookSort::parse("title").expect("should parse").sort(&mut books);
+        assert_eq!(vec!["Alpha", "beta", "gamma"], books.iter().map(|b| b.title.as_str()).collect::<Vec<&str>>());
+        BookSort::parse("-published_at").expect("should parse").sort(&mut books);
+        assert_eq!(vec!["Alpha", "gamma", "beta"], books.iter().map(|b| b.title.as_str()).collect::<Vec<&str>>());
+        assert_eq!(BookSort { field: BookSortField::CreatedAt, descending: false },
+                   BookSort::parse("created_at").expect("should parse"));
+        assert!(BookSort::parse("isbn").is_err());
+        assert_eq!(None, BookSort::from_param(Some(" ")).expect("should ignore blank sort"));
+    }
+
+    #[tokio::test]
+    async fn test_should_parse_book_filter() {
+        let filter = BookFilter::from_params(Some("FR"), Some("ebook")).expect("should parse filter");
+        assert_eq!(Some(LanguageCode::parse("fr").expect("should parse code")), filter.language);
+        assert_eq!(Some(BookFormat::EBook), filter.book_format);
+        assert_eq!(Some(&"EBook".to_string()), filter.predicate().get("book_format"));
+        assert!(BookFilter::from_params(Some(""), None).expect("should ignore blank").predicate().is_empty());
+        assert!(BookFilter::from_params(Some("french"), None).is_err());
+        assert!(BookFilter::from_params(None, Some("vinyl")).is_err());
+        assert!(BookDto::builder().isbn("isbn").title("title").language("xx").build().is_err());
+    }
 }
@@ -1,18 +1,49 @@
-use crate::books::repository::BookRepository;
-use crate::books::repository::ddb_book_repository::DDBBookRepository;
+use crate::books::repository::{BookRepository, TagRepository};
+use crate::books::repository::ddb_book_repository::{DDBBookRepository, ACQUISITION_INDEX, AUTHOR_INDEX, SHELF_INDEX};
+use crate::books::repository::ddb_tag_repository::DDBTagRepository;
 use crate::core::repository::RepositoryStore;
-use crate::utils::ddb::{build_db_client, create_table};
+use crate::utils::ddb::{build_region_client, create_index, create_key_table, create_table, ScanGuard, TableBilling};
+use crate::utils::region::ClientRole;
 
+// writes and consistent gets use the primary region while eventual reads are routed per call site with QueryOptions,
+// which lets them fail over to the secondary region while the primary region is unhealthy
 pub(crate) async fn create_book_repository(store: RepositoryStore) -> Box<dyn BookRepository> {
     match store {
         RepositoryStore::DynamoDB => {
-            let client = build_db_client(store).await;
-            Box::new(DDBBookRepository::new(client, "books", "books_ndx"))
+            let client = build_region_client(store, ClientRole::Write).await;
+            let read_client = build_region_client(store, ClientRole::Read).await;
+            Box::new(DDBBookRepository::new(client, "books", "books_ndx")
+                .with_read_client(read_client)
+                .with_scan_guard(ScanGuard::new(store)))
         }
-        RepositoryStore::LocalDynamoDB => {
-            let client = build_db_client(store).await;
-            let _ = create_table(&client, "books", "book_id", "book_status", "isbn").await;
-            Box::new(DDBBookRepository::new(client, "books", "books_ndx"))
+        _ => {
+            let client = build_region_client(store, ClientRole::Write).await;
+            let table_name = store.table_name("books");
+            let _ = create_table(&client, table_name.as_str(), "book_id", "book_status", "isbn").await;
+            let _ = create_index(&client, table_name.as_str(), AUTHOR_INDEX, "author_id", "created_at", TableBilling::default()).await;
+            let _ = create_index(&client, table_name.as_str(), SHELF_INDEX, "book_format", "call_number", TableBilling::default()).await;
+            let _ = create_index(&client, table_name.as_str(), ACQUISITION_INDEX, "book_format", "created_at", TableBilling::default()).await;
+            Box::new(DDBBookRepository::new(client, table_name.as_str(), store.table_name("books_ndx").as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_tag_repository(store: RepositoryStore) -> Box<dyn TagRepository> {
+    create_tag_repository_for(store, ClientRole::Write).await
+}
+
+// repositories of query services read from the secondary region while the primary region is unhealthy
+pub(crate) async fn create_tag_repository_for(store: RepositoryStore, role: ClientRole) -> Box<dyn TagRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_region_client(store, role).await;
+            Box::new(DDBTagRepository::new(client, "tags").with_scan_guard(ScanGuard::new(store)))
+        }
+        _ => {
+            let client = build_region_client(store, role).await;
+            let table_name = store.table_name("tags");
+            let _ = create_key_table(&client, table_name.as_str(), "tag_name").await;
+            Box::new(DDBTagRepository::new(client, table_name.as_str()))
         }
     }
 }
@@ -1,14 +1,68 @@
 pub mod ddb_book_repository;
+pub mod ddb_tag_repository;
 
+use std::collections::HashMap;
 use async_trait::async_trait;
-use crate::books::domain::model::BookEntity;
-use crate::core::library::{LibraryResult, PaginatedResult};
+use chrono::NaiveDateTime;
+use crate::books::domain::model::{BookEntity, TagCountEntity};
+use crate::core::library::{BookFormat, LibraryResult, PaginatedResult};
 use crate::core::repository::Repository;
 
 
 #[async_trait]
 pub(crate) trait BookRepository: Repository<BookEntity> {
-    async fn find_by_author_id(&self, author_id: &str,
+    // predicate holds attributes such as language or book_format that found books must be equal to
+    async fn find_by_author_id(&self, author_id: &str, predicate: &HashMap<String, String>,
+                               page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookEntity>>;
+
+    // updates shelving metadata of the book copy without changing other attributes
+    async fn update_location(&self, book_id: &str, dewey_decimal_id: &str, collection: &str,
+                             shelf_location: &str, call_number: &str) -> LibraryResult<usize>;
+
+    // changes home branch of the copy
+    async fn update_branch(&self, book_id: &str, branch_id: &str) -> LibraryResult<usize>;
+
+    // records the key of the uploaded cover image of the book
+    async fn update_cover(&self, book_id: &str, cover_key: &str) -> LibraryResult<usize>;
+
+    // scans physical copies shelved at the location or all books when location is not given
+    async fn find_by_shelf(&self, shelf_location: Option<&str>,
                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookEntity>>;
+
+    // adds tags to the string set of book and returns the tags that the book did not have before
+    async fn add_tags(&self, book_id: &str, tags: &[String]) -> LibraryResult<Vec<String>>;
+
+    // removes tags from the string set of book and returns the tags that the book had before
+    async fn remove_tags(&self, book_id: &str, tags: &[String]) -> LibraryResult<Vec<String>>;
+
+    // returns physical copies whose call number is between from and to (inclusive) in shelf order
+    async fn find_by_call_number(&self, from: &str, to: &str,
+                                 page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookEntity>>;
+
+    // returns books of the format created since the given time, newest first
+    async fn find_by_created_at(&self, book_format: &BookFormat, since: NaiveDateTime,
+                                page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookEntity>>;
+
+    // returns books with the tag, the page is only short when no books are left
+    async fn find_by_tag(&self, tag: &str, predicate: &HashMap<String, String>,
+                         page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookEntity>>;
+
+    // decrements available licenses of digital book and fails if no license is available
+    async fn acquire_license(&self, book_id: &str) -> LibraryResult<i64>;
+
+    // increments available licenses of digital book up to its license count
+    async fn release_license(&self, book_id: &str) -> LibraryResult<i64>;
+
+    // adds delta to both license count and available licenses when licenses are purchased or expired
+    async fn update_licenses(&self, book_id: &str, delta: i64) -> LibraryResult<i64>;
+}
+
+#[async_trait]
+pub(crate) trait TagRepository: Sync + Send {
+    // adds delta (which may be negative) to usage count of tag and returns new count
+    async fn increment(&self, tag: &str, delta: i64) -> LibraryResult<i64>;
+
+    // returns all tags with their usage counts
+    async fn find_all(&self) -> LibraryResult<Vec<TagCountEntity>>;
 }
 
@@ -3,47 +3,156 @@ use std::collections::HashMap;
 
 use async_trait::async_trait;
 use aws_sdk_dynamodb::Client;
-use aws_sdk_dynamodb::types::AttributeValue;
-use chrono::Utc;
+use aws_sdk_dynamodb::types::{AttributeValue, ConsumedCapacity, ReturnConsumedCapacity, ReturnValue};
+use chrono::{NaiveDateTime, Utc};
 
 use crate::books::domain::model::BookEntity;
 use crate::books::repository::BookRepository;
-use crate::core::library::{BookStatus, LibraryError, LibraryResult, PaginatedResult};
-use crate::core::repository::Repository;
-use crate::utils::ddb::{add_filter_expr, from_ddb, parse_bool_attribute, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, string_date, to_ddb_page};
+use crate::core::library::{BookFormat, BookStatus, LanguageCode, LibraryError, LibraryResult, PaginatedResult};
+use crate::core::repository::{QueryOptions, Repository};
+use crate::utils::ddb::{add_filter_expr, decrement_attribute, from_ddb, increment_attribute, is_conditional_check_failed, CounterUpdate, parse_bool_attribute, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, parse_string_set_attribute, string_date, string_set, to_ddb_page, ScanGuard};
+use crate::utils::metrics::OperationMeter;
+
+// suffix of the index of books by author, books without an author are left out of the index
+pub(crate) const AUTHOR_INDEX: &str = "author_ndx";
+// suffix of the index of books by format sorted by call number, which orders physical copies for shelf-reading
+pub(crate) const SHELF_INDEX: &str = "shelf_ndx";
+// suffix of the index of books by format sorted by creation time, which lists new acquisitions of each format
+pub(crate) const ACQUISITION_INDEX: &str = "acquisition_ndx";
 
 #[derive(Debug)]
 pub struct DDBBookRepository {
     client: Client,
+    // client of eventually consistent reads, which may be served by a replica region
+    read_client: Client,
     table_name: String,
     index_name: String,
+    author_index_name: String,
+    shelf_index_name: String,
+    acquisition_index_name: String,
+    scan_guard: ScanGuard,
 }
 
 impl DDBBookRepository {
     pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
         Self {
+            read_client: client.clone(),
             client,
             table_name: table_name.to_string(),
             index_name: index_name.to_string(),
+            author_index_name: format!("{}_{}", table_name, AUTHOR_INDEX),
+            shelf_index_name: format!("{}_{}", table_name, SHELF_INDEX),
+            acquisition_index_name: format!("{}_{}", table_name, ACQUISITION_INDEX),
+            scan_guard: ScanGuard::default(),
         }
     }
+
+    pub(crate) fn with_scan_guard(mut self, scan_guard: ScanGuard) -> Self {
+        self.scan_guard = scan_guard;
+        self
+    }
+
+    pub(crate) fn with_read_client(mut self, read_client: Client) -> Self {
+        self.read_client = read_client;
+        self
+    }
+
+    // consistent reads are only served by the table that takes the writes
+    fn client_for(&self, options: &QueryOptions) -> &Client {
+        if options.consistent {
+            &self.client
+        } else {
+            &self.read_client
+        }
+    }
+
     async fn scan(&self, page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookEntity>> {
         let table_name: &str = self.table_name.as_ref();
+        self.scan_guard.check(table_name)?;
         let exclusive_start_key = to_ddb_page(page, &HashMap::new());
-        self.client
+        let meter = OperationMeter::start(table_name, "scan").page_size(page_size);
+        self.read_client
             .scan()
             .table_name(table_name)
             .consistent_read(false)
             .set_exclusive_start_key(exclusive_start_key)
             .limit(cmp::min(page_size, 500) as i32)
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
             .send()
             .await.map_err(LibraryError::from).map(|req| {
+            meter.finish(req.consumed_capacity());
             let def_items = vec![];
             let items = req.items.as_ref().unwrap_or(&def_items);
             let records = items.iter().map(map_to_book).collect();
             from_ddb(page, page_size, req.last_evaluated_key(), records)
         })
     }
+
+    // ADD and DELETE update set atomically so concurrent tagging does not overwrite each other
+    // each tag is added or deleted by a conditional update that only succeeds when the tag changes the set, so that
+    // of concurrent requests adding or removing the same tag exactly one reports the change and counts it
+    async fn update_tags(&self, action: &str, book_id: &str, tags: &[String]) -> LibraryResult<Vec<String>> {
+        let table_name: &str = self.table_name.as_ref();
+        let condition = if action == "ADD" {
+            "attribute_exists(book_id) AND NOT contains(tags, :tag)"
+        } else {
+            "attribute_exists(book_id) AND contains(tags, :tag)"
+        };
+        let mut changed = vec![];
+        for tag in tags {
+            let res = self.client
+                .update_item()
+                .table_name(table_name)
+                .key("book_id", AttributeValue::S(book_id.to_string()))
+                .update_expression(format!("SET updated_at = :updated_at ADD version :one {} tags :tags", action))
+                .condition_expression(condition)
+                .expression_attribute_values(":one", AttributeValue::N("1".to_string()))
+                .expression_attribute_values(":tags", AttributeValue::Ss(vec![tag.to_string()]))
+                .expression_attribute_values(":tag", AttributeValue::S(tag.to_string()))
+                .expression_attribute_values(":updated_at", string_date(Utc::now().naive_utc()))
+                .send()
+                .await;
+            match res {
+                Ok(_) => changed.push(tag.to_string()),
+                Err(err) if is_conditional_check_failed(&err) => {}
+                Err(err) => return Err(LibraryError::from(err)),
+            }
+        }
+        Ok(changed)
+    }
+
+    // license counters are updated atomically so that concurrent checkouts cannot exceed license count
+    fn license_counter(&self, book_id: &str) -> CounterUpdate {
+        CounterUpdate::new(self.table_name.as_str(), "book_id", book_id, "available_licenses")
+            .set("updated_at", string_date(Utc::now().naive_utc()))
+    }
+
+    // license count and available licenses change together when licenses are added or withdrawn
+    async fn update_license_counter(&self, book_id: &str, update_expr: &str, condition_expr: &str,
+                                    values: HashMap<String, AttributeValue>, conflict: &str) -> LibraryResult<i64> {
+        let now = Utc::now().naive_utc();
+        let table_name: &str = self.table_name.as_ref();
+        let mut values = values;
+        values.insert(":updated_at".to_string(), string_date(now));
+        self.client
+            .update_item()
+            .table_name(table_name)
+            .key("book_id", AttributeValue::S(book_id.to_string()))
+            .update_expression(format!("SET updated_at = :updated_at {}", update_expr))
+            .condition_expression(format!("attribute_exists(book_id) AND {}", condition_expr))
+            .set_expression_attribute_values(Some(values))
+            .return_values(ReturnValue::UpdatedNew)
+            .send()
+            .await.map_err(|err| {
+            if is_conditional_check_failed(&err) {
+                LibraryError::validation(format!("{} for {}", conflict, book_id).as_str(), Some("400".to_string()))
+            } else {
+                LibraryError::from(err)
+            }
+        }).map(|res| {
+            res.attributes().map(|attrs| parse_number_attribute("available_licenses", attrs)).unwrap_or(0)
+        })
+    }
 }
 
 #[async_trait]
@@ -51,11 +160,23 @@ impl Repository<BookEntity> for DDBBookRepository {
     async fn create(&self, entity: &BookEntity) -> LibraryResult<usize> {
         let table_name: &str = self.table_name.as_ref();
         let val = serde_json::to_value(entity)?;
+        let mut item = parse_item(val)?;
+        item.remove("tags");
+        if let Some(tags) = string_set(&entity.tags) {
+            item.insert("tags".to_string(), tags);
+        }
+        // index keys cannot be empty strings
+        if entity.author_id.is_empty() {
+            item.remove("author_id");
+        }
+        if entity.call_number.is_empty() {
+            item.remove("call_number");
+        }
         self.client
             .put_item()
             .table_name(table_name)
             .condition_expression("attribute_not_exists(book_id)")
-            .set_item(Some(parse_item(val)?))
+            .set_item(Some(item))
             .send()
             .await.map(|_| 1).map_err(LibraryError::from)
     }
@@ -64,30 +185,47 @@ impl Repository<BookEntity> for DDBBookRepository {
         let now = Utc::now().naive_utc();
         let table_name: &str = self.table_name.as_ref();
 
-        self.client
+        // books without call number are left out of the shelf index
+        let (update_expr, call_number) = if entity.call_number.is_empty() {
+            ("SET version = :version, title = :title, book_status = :book_status, book_format = :book_format, dewey_decimal_id = :dewey_decimal_id, restricted = :restricted, updated_at = :updated_at REMOVE call_number", None)
+        } else {
+            ("SET version = :version, title = :title, book_status = :book_status, book_format = :book_format, dewey_decimal_id = :dewey_decimal_id, call_number = :call_number, restricted = :restricted, updated_at = :updated_at",
+             Some(AttributeValue::S(entity.call_number.to_string())))
+        };
+        let mut request = self.client
             .update_item()
             .table_name(table_name)
             .key("book_id", AttributeValue::S(entity.book_id.clone()))
-            .update_expression("SET version = :version, title = :title, book_status = :book_status, dewey_decimal_id = :dewey_decimal_id, restricted = :restricted, updated_at = :updated_at")
+            .update_expression(update_expr)
             .expression_attribute_values(":old_version", AttributeValue::N(entity.version.to_string()))
             .expression_attribute_values(":version", AttributeValue::N((entity.version + 1).to_string()))
             .expression_attribute_values(":title", AttributeValue::S(entity.title.to_string()))
             .expression_attribute_values(":book_status", AttributeValue::S(entity.book_status.to_string()))
+            .expression_attribute_values(":book_format", AttributeValue::S(entity.book_format.to_string()))
             .expression_attribute_values(":restricted", AttributeValue::Bool(entity.restricted))
             .expression_attribute_values(":dewey_decimal_id", AttributeValue::S(entity.dewey_decimal_id.to_string()))
             .expression_attribute_values(":updated_at", string_date(now))
-            .condition_expression("attribute_exists(version) AND version = :old_version")
+            .condition_expression("attribute_exists(version) AND version = :old_version");
+        if let Some(call_number) = call_number {
+            request = request.expression_attribute_values(":call_number", call_number);
+        }
+        request
             .send()
             .await.map(|_| 1).map_err(LibraryError::from)
     }
 
     async fn get(&self, id: &str) -> LibraryResult<BookEntity> {
+        self.get_with(id, &QueryOptions::consistent()).await
+    }
+
+    async fn get_with(&self, id: &str, options: &QueryOptions) -> LibraryResult<BookEntity> {
         let table_name: &str = self.table_name.as_ref();
-        self.client
+        let meter = OperationMeter::start(table_name, "get").key("book_id");
+        self.client_for(options)
             .query()
             .table_name(table_name)
             .limit(2)
-            .consistent_read(true)
+            .consistent_read(options.consistent)
             .key_condition_expression(
                 "book_id = :book_id",
             )
@@ -95,8 +233,10 @@ impl Repository<BookEntity> for DDBBookRepository {
                 ":book_id",
                 AttributeValue::S(id.to_string()),
             )
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
             .send()
             .await.map_err(LibraryError::from).and_then(|req| {
+            meter.finish(req.consumed_capacity());
             if let Some(items) = req.items {
                 if items.len() > 1 {
                     return Err(LibraryError::database(format!("too many books for {}", id).as_str(), None, false));
@@ -124,10 +264,16 @@ impl Repository<BookEntity> for DDBBookRepository {
     // Note you cannot use certain reserved words per https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/ReservedWords.html
     async fn query(&self, predicate: &HashMap<String, String>,
                    page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookEntity>> {
+        self.query_with(predicate, page, page_size, &QueryOptions::eventual()).await
+    }
+
+    // secondary indexes do not support consistent reads, so only the client differs between the options
+    async fn query_with(&self, predicate: &HashMap<String, String>,
+                        page: Option<&str>, page_size: usize, options: &QueryOptions) -> LibraryResult<PaginatedResult<BookEntity>> {
         let table_name: &str = self.table_name.as_ref();
         let index_name: &str = self.index_name.as_ref();
         let exclusive_start_key = to_ddb_page(page, predicate);
-        let mut request = self.client
+        let mut request = self.client_for(options)
             .query()
             .table_name(table_name)
             .index_name(index_name)
@@ -145,6 +291,7 @@ impl Repository<BookEntity> for DDBBookRepository {
             key_cond.push_str(" AND isbn = :isbn");
             request = request.expression_attribute_values(":isbn", AttributeValue::S(title.to_string()));
         }
+        let meter = OperationMeter::start(table_name, "query").index(index_name).key(key_cond.as_str()).page_size(page_size);
         request = request.key_condition_expression(key_cond);
         let mut filter_expr = String::new();
         // then handle other filters
@@ -159,8 +306,10 @@ impl Repository<BookEntity> for DDBBookRepository {
         }
 
         request
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
             .send()
             .await.map_err(LibraryError::from).map(|req| {
+            meter.finish(req.consumed_capacity());
             let records = req.items.as_ref().unwrap_or(&vec![]).iter()
                 .map(map_to_book).collect();
             from_ddb(page, page_size, req.last_evaluated_key(), records)
@@ -170,12 +319,263 @@ impl Repository<BookEntity> for DDBBookRepository {
 
 #[async_trait]
 impl BookRepository for DDBBookRepository {
-    async fn find_by_author_id(&self, author_id: &str, page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookEntity>> {
-        let predicate = HashMap::from([
+    async fn find_by_author_id(&self, author_id: &str, predicate: &HashMap<String, String>,
+                               page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.author_index_name.as_ref();
+        let key = HashMap::from([
             ("author_id".to_string(), author_id.to_string()),
         ]);
-        self.query(&predicate, page, page_size).await
+        let exclusive_start_key = to_ddb_page(page, &key);
+        let (filter_expr, names, mut values) = listing_filter(None, predicate);
+        values.insert(":author_id".to_string(), AttributeValue::S(author_id.to_string()));
+        let meter = OperationMeter::start(table_name, "find_by_author_id").index(index_name).key("author_id").page_size(page_size);
+        self.read_client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .limit(cmp::min(page_size, 500) as i32)
+            .consistent_read(false)
+            .set_exclusive_start_key(exclusive_start_key)
+            .key_condition_expression("author_id = :author_id")
+            .set_filter_expression(filter_expr)
+            .set_expression_attribute_names(names)
+            .set_expression_attribute_values(Some(values))
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            meter.finish(req.consumed_capacity());
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(map_to_book).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+
+    async fn update_location(&self, book_id: &str, dewey_decimal_id: &str, collection: &str,
+                             shelf_location: &str, call_number: &str) -> LibraryResult<usize> {
+        let now = Utc::now().naive_utc();
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .update_item()
+            .table_name(table_name)
+            .key("book_id", AttributeValue::S(book_id.to_string()))
+            .update_expression("SET dewey_decimal_id = :dewey_decimal_id, collection = :collection, shelf_location = :shelf_location, call_number = :call_number, updated_at = :updated_at ADD version :one")
+            .expression_attribute_values(":one", AttributeValue::N("1".to_string()))
+            .expression_attribute_values(":dewey_decimal_id", AttributeValue::S(dewey_decimal_id.to_string()))
+            .expression_attribute_values(":collection", AttributeValue::S(collection.to_string()))
+            .expression_attribute_values(":shelf_location", AttributeValue::S(shelf_location.to_string()))
+            .expression_attribute_values(":call_number", AttributeValue::S(call_number.to_string()))
+            .expression_attribute_values(":updated_at", string_date(now))
+            .condition_expression("attribute_exists(book_id)")
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
     }
+
+    async fn update_branch(&self, book_id: &str, branch_id: &str) -> LibraryResult<usize> {
+        let now = Utc::now().naive_utc();
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .update_item()
+            .table_name(table_name)
+            .key("book_id", AttributeValue::S(book_id.to_string()))
+            .update_expression("SET branch_id = :branch_id, updated_at = :updated_at ADD version :one")
+            .expression_attribute_values(":one", AttributeValue::N("1".to_string()))
+            .expression_attribute_values(":branch_id", AttributeValue::S(branch_id.to_string()))
+            .expression_attribute_values(":updated_at", string_date(now))
+            .condition_expression("attribute_exists(book_id)")
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn update_cover(&self, book_id: &str, cover_key: &str) -> LibraryResult<usize> {
+        let now = Utc::now().naive_utc();
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .update_item()
+            .table_name(table_name)
+            .key("book_id", AttributeValue::S(book_id.to_string()))
+            .update_expression("SET cover_key = :cover_key, updated_at = :updated_at ADD version :one")
+            .expression_attribute_values(":one", AttributeValue::N("1".to_string()))
+            .expression_attribute_values(":cover_key", AttributeValue::S(cover_key.to_string()))
+            .expression_attribute_values(":updated_at", string_date(now))
+            .condition_expression("attribute_exists(book_id)")
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn find_by_shelf(&self, shelf_location: Option<&str>,
+                           page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        self.scan_guard.check(table_name)?;
+        let exclusive_start_key = to_ddb_page(page, &HashMap::new());
+        let mut request = self.read_client
+            .scan()
+            .table_name(table_name)
+            .consistent_read(false)
+            .set_exclusive_start_key(exclusive_start_key)
+            .limit(cmp::min(page_size, 500) as i32);
+        if let Some(shelf_location) = shelf_location {
+            request = request.filter_expression("shelf_location = :shelf_location")
+                .expression_attribute_values(":shelf_location", AttributeValue::S(shelf_location.to_string()));
+        }
+        let meter = OperationMeter::start(table_name, "find_by_shelf").page_size(page_size);
+        request
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            meter.finish(req.consumed_capacity());
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(map_to_book).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+
+    async fn find_by_call_number(&self, from: &str, to: &str,
+                                 page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.shelf_index_name.as_ref();
+        let key = HashMap::from([
+            ("book_format".to_string(), BookFormat::Physical.to_string()),
+        ]);
+        let exclusive_start_key = to_ddb_page(page, &key);
+        let meter = OperationMeter::start(table_name, "find_by_call_number").index(index_name)
+            .key("book_format, call_number").page_size(page_size);
+        self.read_client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .limit(cmp::min(page_size, 500) as i32)
+            .consistent_read(false)
+            .set_exclusive_start_key(exclusive_start_key)
+            .key_condition_expression("book_format = :book_format AND call_number BETWEEN :from AND :to")
+            .expression_attribute_values(":book_format", AttributeValue::S(BookFormat::Physical.to_string()))
+            .expression_attribute_values(":from", AttributeValue::S(from.to_string()))
+            .expression_attribute_values(":to", AttributeValue::S(to.to_string()))
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            meter.finish(req.consumed_capacity());
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(map_to_book).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+
+    async fn find_by_created_at(&self, book_format: &BookFormat, since: NaiveDateTime,
+                                page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.acquisition_index_name.as_ref();
+        let key = HashMap::from([
+            ("book_format".to_string(), book_format.to_string()),
+        ]);
+        let exclusive_start_key = to_ddb_page(page, &key);
+        let meter = OperationMeter::start(table_name, "find_by_created_at").index(index_name)
+            .key("book_format, created_at").page_size(page_size);
+        self.read_client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .limit(cmp::min(page_size, 500) as i32)
+            .consistent_read(false)
+            .scan_index_forward(false)
+            .set_exclusive_start_key(exclusive_start_key)
+            .key_condition_expression("book_format = :book_format AND created_at >= :since")
+            .expression_attribute_values(":book_format", AttributeValue::S(book_format.to_string()))
+            .expression_attribute_values(":since", string_date(since))
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            meter.finish(req.consumed_capacity());
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(map_to_book).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+
+    async fn add_tags(&self, book_id: &str, tags: &[String]) -> LibraryResult<Vec<String>> {
+        self.update_tags("ADD", book_id, tags).await
+    }
+
+    async fn remove_tags(&self, book_id: &str, tags: &[String]) -> LibraryResult<Vec<String>> {
+        self.update_tags("DELETE", book_id, tags).await
+    }
+
+    // tags are a string set, which cannot be a key of an index, so books are scanned with a filter and the scan
+    // continues over pages that the filter left empty until the page is full or the table is exhausted
+    async fn find_by_tag(&self, tag: &str, predicate: &HashMap<String, String>,
+                         page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let page_size = cmp::min(page_size, 500);
+        let mut exclusive_start_key = to_ddb_page(page, &HashMap::new());
+        let (filter_expr, names, mut values) = listing_filter(Some("contains(tags, :tag)"), predicate);
+        values.insert(":tag".to_string(), AttributeValue::S(tag.to_string()));
+        let meter = OperationMeter::start(table_name, "find_by_tag").page_size(page_size);
+        let mut records = vec![];
+        let mut consumed = 0.0;
+        loop {
+            // every page of the scan is counted so that a rare tag cannot walk the whole table past the guard
+            self.scan_guard.check(table_name)?;
+            // items read by a scan are limited to the remainder of the page so that it never overflows
+            let res = self.read_client
+                .scan()
+                .table_name(table_name)
+                .consistent_read(false)
+                .set_filter_expression(filter_expr.clone())
+                .set_expression_attribute_names(names.clone())
+                .set_expression_attribute_values(Some(values.clone()))
+                .set_exclusive_start_key(exclusive_start_key)
+                .limit((page_size - records.len()) as i32)
+                .return_consumed_capacity(ReturnConsumedCapacity::Total)
+                .send()
+                .await.map_err(LibraryError::from)?;
+            consumed += res.consumed_capacity().and_then(|c| c.capacity_units()).unwrap_or_default();
+            records.extend(res.items.as_ref().unwrap_or(&vec![]).iter().map(map_to_book));
+            exclusive_start_key = res.last_evaluated_key().cloned();
+            if exclusive_start_key.is_none() || records.len() >= page_size {
+                break;
+            }
+        }
+        meter.finish(Some(&ConsumedCapacity::builder().table_name(table_name).capacity_units(consumed).build()));
+        Ok(from_ddb(page, page_size, exclusive_start_key.as_ref(), records))
+    }
+
+    async fn acquire_license(&self, book_id: &str) -> LibraryResult<i64> {
+        let counter = self.license_counter(book_id).conflict(format!("no license is available for {}", book_id).as_str());
+        decrement_attribute(&self.client, &counter, 1).await
+    }
+
+    async fn release_license(&self, book_id: &str) -> LibraryResult<i64> {
+        let counter = self.license_counter(book_id)
+            .condition("available_licenses < license_count", HashMap::new())
+            .conflict(format!("all licenses are already available for {}", book_id).as_str());
+        increment_attribute(&self.client, &counter, 1).await
+    }
+
+    async fn update_licenses(&self, book_id: &str, delta: i64) -> LibraryResult<i64> {
+        // licenses that are checked out cannot be removed
+        self.update_license_counter(book_id, "ADD license_count :delta, available_licenses :delta", "available_licenses >= :min",
+                                    HashMap::from([
+                                        (":delta".to_string(), AttributeValue::N(delta.to_string())),
+                                        (":min".to_string(), AttributeValue::N(cmp::max(0, -delta).to_string())),
+                                    ]), "checked out licenses cannot be removed").await
+    }
+}
+
+// adds equality filters of listings to the condition, names are aliased because language is a reserved word
+fn listing_filter(condition: Option<&str>, predicate: &HashMap<String, String>)
+                  -> (Option<String>, Option<HashMap<String, String>>, HashMap<String, AttributeValue>) {
+    let mut conditions = condition.map(|c| vec![c.to_string()]).unwrap_or_default();
+    let mut names = HashMap::new();
+    let mut values = HashMap::new();
+    let mut keys = predicate.keys().collect::<Vec<&String>>();
+    keys.sort();
+    for k in keys {
+        conditions.push(format!("#{} = :{}", k, k));
+        names.insert(format!("#{}", k), k.to_string());
+        values.insert(format!(":{}", k), AttributeValue::S(predicate[k].to_string()));
+    }
+    let filter_expr = if conditions.is_empty() { None } else { Some(conditions.join(" AND ")) };
+    (filter_expr, if names.is_empty() { None } else { Some(names) }, values)
 }
 
 fn map_to_book(map: &HashMap<String, AttributeValue>) -> BookEntity {
@@ -185,11 +585,22 @@ fn map_to_book(map: &HashMap<String, AttributeValue>) -> BookEntity {
         book_id: parse_string_attribute("book_id", map).unwrap_or_else(|| String::from("")),
         author_id: parse_string_attribute("author_id", map).unwrap_or_else(|| String::from("")),
         publisher_id: parse_string_attribute("publisher_id", map).unwrap_or_else(|| String::from("")),
-        language: parse_string_attribute("language", map).unwrap_or_else(|| String::from("")),
+        // languages of books cataloged before codes were validated default to en
+        language: parse_string_attribute("language", map)
+            .and_then(|language| LanguageCode::parse(language.as_str()).ok()).unwrap_or_default(),
         isbn: parse_string_attribute("isbn", map).unwrap_or_else(|| String::from("")),
         title: parse_string_attribute("title", map).unwrap_or_else(|| String::from("")),
         book_status: BookStatus::from(parse_string_attribute("book_status", map).unwrap_or_else(|| String::from(""))),
         restricted: parse_bool_attribute("restricted", map),
+        tags: parse_string_set_attribute("tags", map),
+        book_format: BookFormat::from(parse_string_attribute("book_format", map).unwrap_or_else(|| String::from(""))),
+        license_count: parse_number_attribute("license_count", map),
+        available_licenses: parse_number_attribute("available_licenses", map),
+        collection: parse_string_attribute("collection", map).unwrap_or_else(|| String::from("")),
+        shelf_location: parse_string_attribute("shelf_location", map).unwrap_or_else(|| String::from("")),
+        call_number: parse_string_attribute("call_number", map).unwrap_or_else(|| String::from("")),
+        branch_id: parse_string_attribute("branch_id", map).unwrap_or_else(|| String::from("")),
+        cover_key: parse_string_attribute("cover_key", map).unwrap_or_else(|| String::from("")),
         published_at: parse_date_attribute("published_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
         created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
         updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
@@ -199,28 +610,30 @@ fn map_to_book(map: &HashMap<String, AttributeValue>) -> BookEntity {
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
-    use async_once::AsyncOnce;
     use aws_sdk_dynamodb::Client;
-    use lazy_static::lazy_static;
+    use chrono::Duration;
 
     use crate::books::domain::model::BookEntity;
-    use crate::books::repository::ddb_book_repository::DDBBookRepository;
-    use crate::core::library::BookStatus;
-    use crate::core::repository::{Repository, RepositoryStore};
-    use crate::utils::ddb::{build_db_client, create_table, delete_table};
+    use crate::books::repository::ddb_book_repository::{DDBBookRepository, ACQUISITION_INDEX, AUTHOR_INDEX, SHELF_INDEX};
+    use crate::core::library::{BookFormat, BookStatus};
+    use crate::books::repository::BookRepository;
+    use crate::core::repository::{QueryOptions, Repository, RepositoryStore};
+    use crate::utils::ddb::{build_db_client, create_index, create_table, ScanGuard, TableBilling};
+    use crate::utils::testing::test_store;
 
-    lazy_static! {
-        static ref CLIENT: AsyncOnce<Client> = AsyncOnce::new(async {
-                let client = build_db_client(RepositoryStore::LocalDynamoDB).await;
-                let _ = delete_table(&client, "books").await;
-                let _ = create_table(&client, "books", "book_id", "book_status", "isbn").await;
-                client
-            });
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("books").as_str(), "book_id", "book_status", "isbn").await;
+        let _ = create_index(&client, store.table_name("books").as_str(), AUTHOR_INDEX, "author_id", "created_at", TableBilling::default()).await;
+        let _ = create_index(&client, store.table_name("books").as_str(), SHELF_INDEX, "book_format", "call_number", TableBilling::default()).await;
+        let _ = create_index(&client, store.table_name("books").as_str(), ACQUISITION_INDEX, "book_format", "created_at", TableBilling::default()).await;
+        client
     }
 
     #[tokio::test]
     async fn test_should_create_get_books() {
-        let books_repo = DDBBookRepository::new(CLIENT.get().await.clone(), "books", "books_ndx");
+        let store = test_store(module_path!(), "test_should_create_get_books");
+        let books_repo = DDBBookRepository::new(build_client(store).await, store.table_name("books").as_str(), store.table_name("books_ndx").as_str());
         let book = BookEntity::new("isbn", "test book", BookStatus::Available);
         let size = books_repo.create(&book).await.expect("should create book");
         assert_eq!(1, size);
@@ -229,9 +642,28 @@ mod tests {
         assert_eq!(book.book_id, loaded.book_id);
     }
 
+    #[tokio::test]
+    async fn test_should_get_query_books_with_options() {
+        let store = test_store(module_path!(), "test_should_get_query_books_with_options");
+        let client = build_client(store).await;
+        let books_repo = DDBBookRepository::new(client.clone(), store.table_name("books").as_str(), store.table_name("books_ndx").as_str()).with_read_client(client);
+        let book = BookEntity::new("options_isbn", "options book", BookStatus::Available);
+        let _ = books_repo.create(&book).await.expect("should create book");
+
+        let loaded = books_repo.get_with(book.book_id.as_str(), &QueryOptions::consistent()).await.expect("should return book");
+        assert_eq!(book.book_id, loaded.book_id);
+        let loaded = books_repo.get_with(book.book_id.as_str(), &QueryOptions::eventual()).await.expect("should return book");
+        assert_eq!(book.book_id, loaded.book_id);
+
+        let predicate = HashMap::from([("isbn".to_string(), "options_isbn".to_string())]);
+        let res = books_repo.query_with(&predicate, None, 10, &QueryOptions::eventual()).await.expect("should query books");
+        assert!(res.records.iter().any(|b| b.book_id == book.book_id));
+    }
+
     #[tokio::test]
     async fn test_should_create_update_books() {
-        let books_repo = DDBBookRepository::new(CLIENT.get().await.clone(), "books", "books_ndx");
+        let store = test_store(module_path!(), "test_should_create_update_books");
+        let books_repo = DDBBookRepository::new(build_client(store).await, store.table_name("books").as_str(), store.table_name("books_ndx").as_str());
         let mut book = BookEntity::new("isbn", "test book", BookStatus::Available);
         let size = books_repo.create(&book).await.expect("should create book");
         assert_eq!(1, size);
@@ -248,15 +680,40 @@ mod tests {
 
     #[tokio::test]
     async fn test_should_create_scan_books() {
-        let books_repo = DDBBookRepository::new(CLIENT.get().await.clone(), "books", "books_ndx");
+        let store = test_store(module_path!(), "test_should_create_scan_books");
+        let books_repo = DDBBookRepository::new(build_client(store).await, store.table_name("books").as_str(), store.table_name("books_ndx").as_str());
         add_test_books(&books_repo, BookStatus::OnHold).await;
         let res = books_repo.scan(None, 20).await.expect("should return book");
         assert_eq!(20, res.records.len());
     }
 
+    #[tokio::test]
+    async fn test_should_reject_scans_beyond_guard_limit() {
+        let store = test_store(module_path!(), "test_should_reject_scans_beyond_guard_limit");
+        let books_repo = DDBBookRepository::new(build_client(store).await, store.table_name("books").as_str(), store.table_name("books_ndx").as_str())
+            .with_scan_guard(ScanGuard::limited(2));
+        add_test_books(&books_repo, BookStatus::Available).await;
+        let res = books_repo.find_by_shelf(None, None, 10).await.expect("should scan first page");
+        let _ = books_repo.find_by_shelf(None, res.next_page.as_deref(), 10).await.expect("should scan second page");
+        assert!(books_repo.find_by_shelf(None, None, 10).await.is_err());
+        // queries of indexes are not counted
+        assert!(books_repo.find_by_author_id("author_1", &HashMap::new(), None, 10).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_should_count_every_page_of_tag_scans() {
+        let store = test_store(module_path!(), "test_should_count_every_page_of_tag_scans");
+        let books_repo = DDBBookRepository::new(build_client(store).await, store.table_name("books").as_str(), store.table_name("books_ndx").as_str())
+            .with_scan_guard(ScanGuard::limited(2));
+        add_test_books(&books_repo, BookStatus::Available).await;
+        // no book has the tag so the scan keeps reading pages until the guard stops it
+        assert!(books_repo.find_by_tag("rare_tag", &HashMap::new(), None, 5).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_should_create_query_books() {
-        let books_repo = DDBBookRepository::new(CLIENT.get().await.clone(), "books", "books_ndx");
+        let store = test_store(module_path!(), "test_should_create_query_books");
+        let books_repo = DDBBookRepository::new(build_client(store).await, store.table_name("books").as_str(), store.table_name("books_ndx").as_str());
         add_test_books(&books_repo, BookStatus::CheckedOut).await;
         let res = books_repo.query(
             &HashMap::from([("book_status".to_string(), BookStatus::CheckedOut.to_string())]),
@@ -288,7 +745,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_should_create_delete_books() {
-        let books_repo = DDBBookRepository::new(CLIENT.get().await.clone(), "books", "books_ndx");
+        let store = test_store(module_path!(), "test_should_create_delete_books");
+        let books_repo = DDBBookRepository::new(build_client(store).await, store.table_name("books").as_str(), store.table_name("books_ndx").as_str());
         let book = BookEntity::new("isbn", "test book", BookStatus::Available);
         let size = books_repo.create(&book).await.expect("should create book");
         assert_eq!(1, size);
@@ -300,6 +758,158 @@ mod tests {
         assert!(loaded.is_err());
     }
 
+    #[tokio::test]
+    async fn test_should_update_location() {
+        let store = test_store(module_path!(), "test_should_update_location");
+        let books_repo = DDBBookRepository::new(build_client(store).await, store.table_name("books").as_str(), store.table_name("books_ndx").as_str());
+        let book = BookEntity::new("isbn", "shelved book", BookStatus::Available);
+        let _ = books_repo.create(&book).await.expect("should create book");
+        let size = books_repo.update_location(book.book_id.as_str(), "510.2", "REF", "Floor 2, Aisle 5", "REF 510.2 SHE")
+            .await.expect("should update location");
+        assert_eq!(1, size);
+        let loaded = books_repo.get(book.book_id.as_str()).await.expect("should return book");
+        assert_eq!("510.2", loaded.dewey_decimal_id.as_str());
+        assert_eq!("REF", loaded.collection.as_str());
+        assert_eq!("Floor 2, Aisle 5", loaded.shelf_location.as_str());
+        assert_eq!("REF 510.2 SHE", loaded.call_number.as_str());
+        assert_eq!(book.version + 1, loaded.version);
+        assert_eq!(1, books_repo.update_branch(book.book_id.as_str(), "branch2").await.expect("should update branch"));
+        assert_eq!("branch2", books_repo.get(book.book_id.as_str()).await.expect("should return book").branch_id.as_str());
+        assert!(books_repo.update_location("missing", "510.2", "REF", "Floor 2", "REF 510.2").await.is_err());
+        assert_eq!(1, books_repo.update_cover(book.book_id.as_str(), "covers/cover.png").await.expect("should update cover"));
+        assert_eq!("covers/cover.png", books_repo.get(book.book_id.as_str()).await.expect("should return book").cover_key.as_str());
+        assert!(books_repo.update_cover("missing", "covers/cover.png").await.is_err());
+
+        let mut found = vec![];
+        let mut next_page: Option<String> = None;
+        loop {
+            let res = books_repo.find_by_shelf(Some("Floor 2, Aisle 5"), next_page.as_deref(), 50)
+                .await.expect("should find by shelf");
+            found.extend(res.records);
+            next_page = res.next_page;
+            if next_page.is_none() {
+                break;
+            }
+        }
+        assert!(found.iter().any(|b| b.book_id == book.book_id));
+        assert!(found.iter().all(|b| b.shelf_location == "Floor 2, Aisle 5"));
+    }
+
+    #[tokio::test]
+    async fn test_should_find_books_by_call_number() {
+        let store = test_store(module_path!(), "test_should_find_books_by_call_number");
+        let books_repo = DDBBookRepository::new(build_client(store).await, store.table_name("books").as_str(), store.table_name("books_ndx").as_str());
+        let mut ids = vec![];
+        for (call_number, book_format) in [("SHLF 520 AST", BookFormat::Physical), ("SHLF 510 ALG", BookFormat::Physical),
+                                           ("SHLF 515 CAL", BookFormat::EBook), ("SHLF 610 MED", BookFormat::Physical)] {
+            let mut book = BookEntity::new("isbn", "shelf list book", BookStatus::Available);
+            book.call_number = call_number.to_string();
+            book.book_format = book_format;
+            let _ = books_repo.create(&book).await.expect("should create book");
+            ids.push(book.book_id);
+        }
+        let res = books_repo.find_by_call_number("SHLF 500", "SHLF 599~", None, 50).await.expect("should find by call number");
+        assert_eq!(vec![ids[1].to_string(), ids[0].to_string()], res.records.iter()
+            .filter(|b| ids.contains(&b.book_id)).map(|b| b.book_id.to_string()).collect::<Vec<String>>());
+    }
+
+    #[tokio::test]
+    async fn test_should_add_remove_find_tags() {
+        let store = test_store(module_path!(), "test_should_add_remove_find_tags");
+        let books_repo = DDBBookRepository::new(build_client(store).await, store.table_name("books").as_str(), store.table_name("books_ndx").as_str());
+        let mut book = BookEntity::new("isbn", "test book", BookStatus::Available);
+        book.tags = vec!["mystery".to_string()];
+        let _ = books_repo.create(&book).await.expect("should create book");
+
+        let added = books_repo.add_tags(book.book_id.as_str(), &["tagged_fantasy".to_string(), "mystery".to_string()])
+            .await.expect("should add tags");
+        assert_eq!(vec!["tagged_fantasy".to_string()], added);
+        let loaded = books_repo.get(book.book_id.as_str()).await.expect("should return book");
+        assert_eq!(2, loaded.tags.len());
+        assert_eq!(1, loaded.version);
+        // adding the tag again does not change the book
+        let added = books_repo.add_tags(book.book_id.as_str(), &["tagged_fantasy".to_string()]).await.expect("should add tags");
+        assert!(added.is_empty());
+        assert_eq!(1, books_repo.get(book.book_id.as_str()).await.expect("should return book").version);
+
+        let res = books_repo.find_by_tag("tagged_fantasy", &HashMap::new(), None, 500).await.expect("should find by tag");
+        assert!(res.records.iter().any(|b| b.book_id == book.book_id));
+        let french = HashMap::from([("language".to_string(), "fr".to_string())]);
+        let res = books_repo.find_by_tag("tagged_fantasy", &french, None, 500).await.expect("should find by tag");
+        assert!(res.records.iter().all(|b| b.book_id != book.book_id));
+
+        let removed = books_repo.remove_tags(book.book_id.as_str(), &["tagged_fantasy".to_string(), "unknown".to_string()])
+            .await.expect("should remove tags");
+        assert_eq!(vec!["tagged_fantasy".to_string()], removed);
+        let loaded = books_repo.get(book.book_id.as_str()).await.expect("should return book");
+        assert_eq!(vec!["mystery".to_string()], loaded.tags);
+        assert!(books_repo.remove_tags(book.book_id.as_str(), &["tagged_fantasy".to_string()])
+            .await.expect("should remove tags").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_should_acquire_release_licenses() {
+        let store = test_store(module_path!(), "test_should_acquire_release_licenses");
+        let books_repo = DDBBookRepository::new(build_client(store).await, store.table_name("books").as_str(), store.table_name("books_ndx").as_str());
+        let mut book = BookEntity::new("isbn", "test ebook", BookStatus::Available);
+        book.book_format = BookFormat::EBook;
+        book.license_count = 1;
+        book.available_licenses = 1;
+        let _ = books_repo.create(&book).await.expect("should create book");
+
+        assert_eq!(0, books_repo.acquire_license(book.book_id.as_str()).await.expect("should acquire license"));
+        assert!(books_repo.acquire_license(book.book_id.as_str()).await.is_err());
+        assert!(books_repo.update_licenses(book.book_id.as_str(), -1).await.is_err());
+        assert_eq!(1, books_repo.release_license(book.book_id.as_str()).await.expect("should release license"));
+        assert!(books_repo.release_license(book.book_id.as_str()).await.is_err());
+        assert_eq!(3, books_repo.update_licenses(book.book_id.as_str(), 2).await.expect("should add licenses"));
+
+        let loaded = books_repo.get(book.book_id.as_str()).await.expect("should return book");
+        assert_eq!(BookFormat::EBook, loaded.book_format);
+        assert_eq!(3, loaded.license_count);
+        assert_eq!(3, loaded.available_licenses);
+    }
+
+    #[tokio::test]
+    async fn test_should_find_books_by_author() {
+        let store = test_store(module_path!(), "test_should_find_books_by_author");
+        let books_repo = DDBBookRepository::new(build_client(store).await, store.table_name("books").as_str(), store.table_name("books_ndx").as_str());
+        for (i, status) in [BookStatus::Available, BookStatus::CheckedOut, BookStatus::OnHold].into_iter().enumerate() {
+            let mut book = BookEntity::new("isbn", format!("author book {}", i).as_str(), status);
+            book.author_id = "author_1".to_string();
+            let _ = books_repo.create(&book).await.expect("should create book");
+        }
+        // books without author are not indexed
+        let _ = books_repo.create(&BookEntity::new("isbn", "anonymous", BookStatus::Available)).await.expect("should create book");
+
+        let res = books_repo.find_by_author_id("author_1", &HashMap::new(), None, 2).await.expect("should find books");
+        assert_eq!(2, res.records.len());
+        let next = books_repo.find_by_author_id("author_1", &HashMap::new(), res.next_page.as_deref(), 2)
+            .await.expect("should find books");
+        assert_eq!(1, next.records.len());
+        assert!(res.records.iter().chain(next.records.iter()).all(|b| b.author_id == "author_1"));
+        assert!(books_repo.find_by_author_id("author_2", &HashMap::new(), None, 10).await.expect("should find books").records.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_should_find_books_by_created_at() {
+        let store = test_store(module_path!(), "test_should_find_books_by_created_at");
+        let books_repo = DDBBookRepository::new(build_client(store).await, store.table_name("books").as_str(), store.table_name("books_ndx").as_str());
+        let mut older = BookEntity::new("isbn", "acquired last year", BookStatus::Available);
+        older.book_format = BookFormat::Audiobook;
+        older.created_at = older.created_at - Duration::days(365);
+        let _ = books_repo.create(&older).await.expect("should create book");
+        let mut newer = BookEntity::new("isbn", "acquired today", BookStatus::Available);
+        newer.book_format = BookFormat::Audiobook;
+        let _ = books_repo.create(&newer).await.expect("should create book");
+
+        let since = newer.created_at - Duration::days(30);
+        let res = books_repo.find_by_created_at(&BookFormat::Audiobook, since, None, 500).await.expect("should find books");
+        assert!(res.records.iter().any(|b| b.book_id == newer.book_id));
+        assert!(res.records.iter().all(|b| b.book_id != older.book_id && b.created_at >= since));
+        assert!(res.records.windows(2).all(|w| w[0].created_at >= w[1].created_at));
+    }
+
     async fn add_test_books(books_repo: &DDBBookRepository, status: BookStatus) {
         for i in 0..50 {
             let book = BookEntity::new(format!("isbn_{}", i / 10).as_str(),
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::books::domain::model::TagCountEntity;
+use crate::books::repository::TagRepository;
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::utils::ddb::{decrement_attribute, increment_attribute, parse_date_attribute, parse_number_attribute, parse_string_attribute, string_date, CounterUpdate, ScanGuard};
+
+// DDBTagRepository maintains counter table of tags keyed by tag_name
+#[derive(Debug)]
+pub(crate) struct DDBTagRepository {
+    client: Client,
+    table_name: String,
+    scan_guard: ScanGuard,
+}
+
+impl DDBTagRepository {
+    pub(crate) fn new(client: Client, table_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            scan_guard: ScanGuard::default(),
+        }
+    }
+
+    pub(crate) fn with_scan_guard(mut self, scan_guard: ScanGuard) -> Self {
+        self.scan_guard = scan_guard;
+        self
+    }
+}
+
+#[async_trait]
+impl TagRepository for DDBTagRepository {
+    async fn increment(&self, tag: &str, delta: i64) -> LibraryResult<i64> {
+        let counter = CounterUpdate::new(self.table_name.as_str(), "tag_name", tag, "usage_count").upsert()
+            .set("updated_at", string_date(Utc::now().naive_utc()));
+        if delta >= 0 {
+            return increment_attribute(&self.client, &counter, delta).await;
+        }
+        match decrement_attribute(&self.client, &counter, -delta).await {
+            // tags that are no longer used stay at zero
+            Err(LibraryError::Validation { .. }) => Ok(0),
+            res => res,
+        }
+    }
+
+    async fn find_all(&self) -> LibraryResult<Vec<TagCountEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let mut tags = vec![];
+        let mut next_page: Option<HashMap<String, AttributeValue>> = None;
+        loop {
+            self.scan_guard.check(table_name)?;
+            let res = self.client
+                .scan()
+                .table_name(table_name)
+                .consistent_read(false)
+                .set_exclusive_start_key(next_page)
+                .send()
+                .await.map_err(LibraryError::from)?;
+            for item in res.items().unwrap_or_default() {
+                let tag = TagCountEntity::from(item);
+                if tag.usage_count > 0 {
+                    tags.push(tag);
+                }
+            }
+            next_page = res.last_evaluated_key().cloned();
+            if next_page.is_none() {
+                break;
+            }
+        }
+        tags.sort_by(|a, b| b.usage_count.cmp(&a.usage_count).then(a.tag_name.cmp(&b.tag_name)));
+        Ok(tags)
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for TagCountEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        TagCountEntity {
+            tag_name: parse_string_attribute("tag_name", map).unwrap_or_else(|| String::from("")),
+            usage_count: parse_number_attribute("usage_count", map),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+
+    use crate::books::repository::ddb_tag_repository::DDBTagRepository;
+    use crate::books::repository::TagRepository;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::ddb::{build_db_client, create_key_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_key_table(&client, store.table_name("tags").as_str(), "tag_name").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_increment_and_find_tags() {
+        let store = test_store(module_path!(), "test_should_increment_and_find_tags");
+        let tags_repo = DDBTagRepository::new(build_client(store).await, store.table_name("tags").as_str());
+        assert_eq!(1, tags_repo.increment("fiction", 1).await.expect("should increment tag"));
+        assert_eq!(2, tags_repo.increment("fiction", 1).await.expect("should increment tag"));
+        assert_eq!(1, tags_repo.increment("poetry", 1).await.expect("should increment tag"));
+        assert_eq!(0, tags_repo.increment("poetry", -1).await.expect("should decrement tag"));
+        assert_eq!(0, tags_repo.increment("poetry", -1).await.expect("should not decrement tag below zero"));
+
+        let tags = tags_repo.find_all().await.expect("should find tags");
+        assert_eq!(1, tags.len());
+        assert_eq!("fiction", tags[0].tag_name.as_str());
+        assert_eq!(2, tags[0].usage_count);
+    }
+}
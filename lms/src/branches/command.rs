@@ -0,0 +1,4 @@
+pub mod add_branch_cmd;
+pub mod find_branches_cmd;
+pub mod find_nearest_branches_cmd;
+pub mod update_calendar_cmd;
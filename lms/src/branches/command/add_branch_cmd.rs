@@ -0,0 +1,113 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::branches::domain::BranchService;
+use crate::branches::dto::{BranchDto, ClosureDto, OpeningHoursDto};
+use crate::core::command::{Command, CommandError};
+
+pub(crate) struct AddBranchCommand {
+    branch_service: Box<dyn BranchService>,
+}
+
+impl AddBranchCommand {
+    pub(crate) fn new(branch_service: Box<dyn BranchService>) -> Self {
+        Self {
+            branch_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AddBranchCommandRequest {
+    pub name: String,
+    pub email: String,
+    pub work_phone: Option<String>,
+    pub street_address: String,
+    pub city: String,
+    #[serde(default)]
+    pub zip_code: String,
+    #[serde(default)]
+    pub state: String,
+    pub country: String,
+    #[serde(default)]
+    pub opening_hours: Vec<OpeningHoursDto>,
+    #[serde(default)]
+    pub closures: Vec<ClosureDto>,
+}
+
+impl AddBranchCommandRequest {
+    pub fn new(name: &str, email: &str, street_address: &str, city: &str, country: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            email: email.to_string(),
+            work_phone: None,
+            street_address: street_address.to_string(),
+            city: city.to_string(),
+            zip_code: "".to_string(),
+            state: "".to_string(),
+            country: country.to_string(),
+            opening_hours: vec![],
+            closures: vec![],
+        }
+    }
+
+    pub fn build_branch(&self) -> BranchDto {
+        let mut branch = BranchDto::new(self.name.as_str(), self.email.as_str());
+        branch.work_phone = self.work_phone.clone();
+        branch.street_address = self.street_address.to_string();
+        branch.city = self.city.to_string();
+        branch.zip_code = self.zip_code.to_string();
+        branch.state = self.state.to_string();
+        branch.country = self.country.to_string();
+        branch.opening_hours = self.opening_hours.clone();
+        branch.closures = self.closures.clone();
+        branch
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AddBranchCommandResponse {
+    pub branch: BranchDto,
+}
+
+impl AddBranchCommandResponse {
+    pub fn new(branch: BranchDto) -> Self {
+        Self {
+            branch,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<AddBranchCommandRequest, AddBranchCommandResponse> for AddBranchCommand {
+    async fn execute(&self, req: AddBranchCommandRequest) -> Result<AddBranchCommandResponse, CommandError> {
+        let branch = req.build_branch();
+        self.branch_service.add_branch(&branch).await.map_err(CommandError::from).map(AddBranchCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::branches::command::add_branch_cmd::{AddBranchCommand, AddBranchCommandRequest};
+    use crate::branches::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn sut_cmd(store: RepositoryStore) -> AddBranchCommand {
+        let svc = factory::create_branch_service(&Configuration::new("test"), store).await;
+        AddBranchCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_add_branch() {
+        let store = test_store(module_path!(), "test_should_run_add_branch");
+        let cmd = sut_cmd(store).await;
+
+        let res = cmd.execute(AddBranchCommandRequest::new("Central", "add@library.cc", "1000 4th Ave", "Seattle", "us"))
+            .await.expect("should add branch");
+        assert!(res.branch.active);
+        assert_eq!("US", res.branch.country.as_str());
+    }
+}
@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::branches::domain::BranchQueryService;
+use crate::branches::dto::BranchDto;
+use crate::core::command::{Command, CommandError};
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+
+pub(crate) struct FindBranchesCommand {
+    branch_service: Box<dyn BranchQueryService>,
+}
+
+impl FindBranchesCommand {
+    pub(crate) fn new(branch_service: Box<dyn BranchQueryService>) -> Self {
+        Self {
+            branch_service,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct FindBranchesCommandRequest {
+    pub(crate) page: Option<String>,
+    pub(crate) page_size: Option<usize>,
+}
+
+impl FindBranchesCommandRequest {
+    pub fn new() -> Self {
+        Self {
+            page: None,
+            page_size: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindBranchesCommandResponse {
+    pub branches: Vec<BranchDto>,
+    pub next_page: Option<String>,
+}
+
+impl FindBranchesCommandResponse {
+    pub fn new(branches: Vec<BranchDto>, next_page: Option<String>) -> Self {
+        Self {
+            branches,
+            next_page,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<FindBranchesCommandRequest, FindBranchesCommandResponse> for FindBranchesCommand {
+    async fn execute(&self, req: FindBranchesCommandRequest) -> Result<FindBranchesCommandResponse, CommandError> {
+        self.branch_service.find_branches(req.page.as_deref(), req.page_size.unwrap_or(DEFAULT_PAGE_SIZE))
+            .await.map_err(CommandError::from).map(|res| FindBranchesCommandResponse::new(res.records, res.next_page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::branches::command::add_branch_cmd::{AddBranchCommand, AddBranchCommandRequest};
+    use crate::branches::command::find_branches_cmd::{FindBranchesCommand, FindBranchesCommandRequest};
+    use crate::branches::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBranchCommand {
+        let svc = factory::create_branch_service(&Configuration::new("test"), store).await;
+        AddBranchCommand::new(svc)
+    }
+
+    async fn build_find_cmd(store: RepositoryStore) -> FindBranchesCommand {
+        let svc = factory::create_branch_query_service(&Configuration::new("test"), store).await;
+        FindBranchesCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_find_branches() {
+        let store = test_store(module_path!(), "test_should_run_find_branches");
+        let add_cmd = build_add_cmd(store).await;
+        let find_cmd = build_find_cmd(store).await;
+
+        let _ = add_cmd.execute(AddBranchCommandRequest::new("Central", "find@library.cc", "1000 4th Ave", "Seattle", "US"))
+            .await.expect("should add branch");
+        let res = find_cmd.execute(FindBranchesCommandRequest::new()).await.expect("should find branches");
+        assert_eq!(1, res.branches.len());
+    }
+}
@@ -0,0 +1,102 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::branches::domain::BranchQueryService;
+use crate::branches::dto::NearestBranchDto;
+use crate::core::command::{Command, CommandError};
+use crate::core::library::LibraryError;
+
+const DEFAULT_LIMIT: usize = 5;
+
+pub(crate) struct FindNearestBranchesCommand {
+    branch_service: Box<dyn BranchQueryService>,
+}
+
+impl FindNearestBranchesCommand {
+    pub(crate) fn new(branch_service: Box<dyn BranchQueryService>) -> Self {
+        Self {
+            branch_service,
+        }
+    }
+}
+
+// branches are searched near the given coordinates or near the address of the patron
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct FindNearestBranchesCommandRequest {
+    pub(crate) lat: Option<f64>,
+    pub(crate) lon: Option<f64>,
+    pub(crate) patron_id: Option<String>,
+    pub(crate) limit: Option<usize>,
+}
+
+impl FindNearestBranchesCommandRequest {
+    pub fn new(lat: f64, lon: f64) -> Self {
+        Self {
+            lat: Some(lat),
+            lon: Some(lon),
+            patron_id: None,
+            limit: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindNearestBranchesCommandResponse {
+    pub branches: Vec<NearestBranchDto>,
+}
+
+impl FindNearestBranchesCommandResponse {
+    pub fn new(branches: Vec<NearestBranchDto>) -> Self {
+        Self {
+            branches,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<FindNearestBranchesCommandRequest, FindNearestBranchesCommandResponse> for FindNearestBranchesCommand {
+    async fn execute(&self, req: FindNearestBranchesCommandRequest) -> Result<FindNearestBranchesCommandResponse, CommandError> {
+        let limit = req.limit.unwrap_or(DEFAULT_LIMIT);
+        let res = match (req.lat, req.lon, req.patron_id) {
+            (Some(lat), Some(lon), _) => self.branch_service.find_nearest_branches(lat, lon, limit).await,
+            (None, None, Some(patron_id)) => self.branch_service.find_nearest_branches_to_patron(patron_id.as_str(), limit).await,
+            _ => Err(LibraryError::validation("lat and lon or patron_id are required", Some("400".to_string()))),
+        };
+        res.map_err(CommandError::from).map(FindNearestBranchesCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::branches::command::add_branch_cmd::{AddBranchCommand, AddBranchCommandRequest};
+    use crate::branches::command::find_nearest_branches_cmd::{FindNearestBranchesCommand, FindNearestBranchesCommandRequest};
+    use crate::branches::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBranchCommand {
+        let svc = factory::create_branch_service(&Configuration::new("test"), store).await;
+        AddBranchCommand::new(svc)
+    }
+
+    async fn build_nearest_cmd(store: RepositoryStore) -> FindNearestBranchesCommand {
+        let svc = factory::create_branch_query_service(&Configuration::new("test"), store).await;
+        FindNearestBranchesCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_find_nearest_branches() {
+        let store = test_store(module_path!(), "test_should_run_find_nearest_branches");
+        let add_cmd = build_add_cmd(store).await;
+        let nearest_cmd = build_nearest_cmd(store).await;
+
+        let _ = add_cmd.execute(AddBranchCommandRequest::new("Central", "nearest@library.cc", "1000 4th Ave", "Seattle", "US"))
+            .await.expect("should add branch");
+        // branches without coordinates are not returned
+        let res = nearest_cmd.execute(FindNearestBranchesCommandRequest::new(47.6, -122.3)).await.expect("should find branches");
+        assert!(res.branches.is_empty());
+        assert!(nearest_cmd.execute(FindNearestBranchesCommandRequest::default()).await.is_err());
+    }
+}
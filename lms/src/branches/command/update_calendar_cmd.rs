@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::branches::domain::BranchService;
+use crate::branches::dto::{BranchDto, ClosureDto, OpeningHoursDto};
+use crate::core::command::{Command, CommandError};
+
+pub(crate) struct UpdateCalendarCommand {
+    branch_service: Box<dyn BranchService>,
+}
+
+impl UpdateCalendarCommand {
+    pub(crate) fn new(branch_service: Box<dyn BranchService>) -> Self {
+        Self {
+            branch_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct UpdateCalendarCommandRequest {
+    // set from the path of the request
+    #[serde(default)]
+    pub(crate) branch_id: String,
+    #[serde(default)]
+    pub(crate) opening_hours: Vec<OpeningHoursDto>,
+    #[serde(default)]
+    pub(crate) closures: Vec<ClosureDto>,
+}
+
+impl UpdateCalendarCommandRequest {
+    pub fn new(branch_id: &str, opening_hours: Vec<OpeningHoursDto>, closures: Vec<ClosureDto>) -> Self {
+        Self {
+            branch_id: branch_id.to_string(),
+            opening_hours,
+            closures,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct UpdateCalendarCommandResponse {
+    pub branch: BranchDto,
+}
+
+impl UpdateCalendarCommandResponse {
+    pub fn new(branch: BranchDto) -> Self {
+        Self {
+            branch,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<UpdateCalendarCommandRequest, UpdateCalendarCommandResponse> for UpdateCalendarCommand {
+    async fn execute(&self, req: UpdateCalendarCommandRequest) -> Result<UpdateCalendarCommandResponse, CommandError> {
+        self.branch_service.update_calendar(req.branch_id.as_str(), &req.opening_hours, &req.closures)
+            .await.map_err(CommandError::from).map(UpdateCalendarCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use crate::branches::command::add_branch_cmd::{AddBranchCommand, AddBranchCommandRequest};
+    use crate::branches::command::update_calendar_cmd::{UpdateCalendarCommand, UpdateCalendarCommandRequest};
+    use crate::branches::dto::ClosureDto;
+    use crate::branches::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBranchCommand {
+        let svc = factory::create_branch_service(&Configuration::new("test"), store).await;
+        AddBranchCommand::new(svc)
+    }
+
+    async fn build_update_cmd(store: RepositoryStore) -> UpdateCalendarCommand {
+        let svc = factory::create_branch_service(&Configuration::new("test"), store).await;
+        UpdateCalendarCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_update_calendar() {
+        let store = test_store(module_path!(), "test_should_run_update_calendar");
+        let add_cmd = build_add_cmd(store).await;
+        let update_cmd = build_update_cmd(store).await;
+
+        let branch = add_cmd.execute(AddBranchCommandRequest::new("Central", "calendar@library.cc", "1000 4th Ave", "Seattle", "US"))
+            .await.expect("should add branch").branch;
+        let closure = ClosureDto::new(NaiveDate::from_ymd_opt(2024, 1, 1).expect("should build date"), "New Year");
+        let res = update_cmd.execute(UpdateCalendarCommandRequest::new(branch.branch_id.as_str(), vec![], vec![closure.clone()]))
+            .await.expect("should update calendar");
+        assert_eq!(vec![closure], res.branch.closures);
+    }
+}
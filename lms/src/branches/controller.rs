@@ -0,0 +1,71 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::{get, post, put},
+    Router,
+};
+use serde_json::{Value};
+use crate::branches::command::add_branch_cmd::{AddBranchCommand, AddBranchCommandRequest, AddBranchCommandResponse};
+use crate::branches::command::find_branches_cmd::{FindBranchesCommand, FindBranchesCommandRequest, FindBranchesCommandResponse};
+use crate::branches::command::find_nearest_branches_cmd::{FindNearestBranchesCommand, FindNearestBranchesCommandRequest, FindNearestBranchesCommandResponse};
+use crate::branches::command::update_calendar_cmd::{UpdateCalendarCommand, UpdateCalendarCommandRequest, UpdateCalendarCommandResponse};
+use crate::branches::domain::{BranchQueryService, BranchService};
+use crate::branches::factory;
+use crate::core::controller::{AppState, command_bus, json_to_server_error, with_common_layers, ServerError};
+use crate::utils::ddb::{build_db_client, create_table};
+
+async fn build_service(state: AppState) -> Box<dyn BranchService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_table(&client, state.store.table_name("parties").as_str(), "party_id", "kind", "normalized_email").await;
+    factory::create_branch_service(&state.config, state.store).await
+}
+
+async fn build_query_service(state: AppState) -> Box<dyn BranchQueryService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_table(&client, state.store.table_name("parties").as_str(), "party_id", "kind", "normalized_email").await;
+    factory::create_branch_query_service(&state.config, state.store).await
+}
+
+pub(crate) async fn add_branch(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<Json<AddBranchCommandResponse>, ServerError> {
+    let req: AddBranchCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res = command_bus().register(AddBranchCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn update_calendar(
+    State(state): State<AppState>,
+    Path(branch_id): Path<String>,
+    json: Json<Value>) -> Result<Json<UpdateCalendarCommandResponse>, ServerError> {
+    let mut req: UpdateCalendarCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.branch_id = branch_id;
+    let svc = build_service(state).await;
+    let res = command_bus().register(UpdateCalendarCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn find_branches(
+    State(state): State<AppState>,
+    Query(req): Query<FindBranchesCommandRequest>) -> Result<Json<FindBranchesCommandResponse>, ServerError> {
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(FindBranchesCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn find_nearest_branches(
+    State(state): State<AppState>,
+    Query(req): Query<FindNearestBranchesCommandRequest>) -> Result<Json<FindNearestBranchesCommandResponse>, ServerError> {
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(FindNearestBranchesCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/branches", post(add_branch).get(find_branches))
+        .route("/branches/nearest", get(find_nearest_branches))
+        .route("/branches/:id/calendar", put(update_calendar));
+    with_common_layers(router, state)
+}
@@ -0,0 +1,25 @@
+pub mod query;
+pub mod service;
+
+use async_trait::async_trait;
+use crate::branches::dto::{BranchDto, ClosureDto, NearestBranchDto, OpeningHoursDto};
+use crate::core::library::{LibraryResult, PaginatedResult};
+
+// read-only lookups of branches used by queries and by other contexts
+#[async_trait]
+pub(crate) trait BranchQueryService: Sync + Send {
+    async fn find_branch_by_id(&self, id: &str) -> LibraryResult<BranchDto>;
+    async fn find_branches(&self, page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BranchDto>>;
+    // active geocoded branches sorted by their distance from the location
+    async fn find_nearest_branches(&self, latitude: f64, longitude: f64, limit: usize) -> LibraryResult<Vec<NearestBranchDto>>;
+    // branches nearest to the geocoded address of the patron
+    async fn find_nearest_branches_to_patron(&self, patron_id: &str, limit: usize) -> LibraryResult<Vec<NearestBranchDto>>;
+}
+
+#[async_trait]
+pub(crate) trait BranchService: BranchQueryService {
+    async fn add_branch(&self, branch: &BranchDto) -> LibraryResult<BranchDto>;
+    // replaces weekly opening hours and closure dates of the branch
+    async fn update_calendar(&self, branch_id: &str, opening_hours: &[OpeningHoursDto],
+                             closures: &[ClosureDto]) -> LibraryResult<BranchDto>;
+}
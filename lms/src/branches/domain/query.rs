@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use async_trait::async_trait;
+use crate::branches::domain::BranchQueryService;
+use crate::branches::dto::{BranchDto, NearestBranchDto};
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult, PartyKind};
+use crate::parties::repository::PartyRepository;
+
+// mean radius of the earth used by the haversine distance
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+// branches are read in pages of this size when they are sorted by distance
+const BRANCHES_PAGE_SIZE: usize = 100;
+
+pub(crate) struct BranchQueryServiceImpl {
+    party_repository: Box<dyn PartyRepository>,
+}
+
+impl BranchQueryServiceImpl {
+    pub(crate) fn new(party_repository: Box<dyn PartyRepository>) -> Self {
+        Self {
+            party_repository,
+        }
+    }
+
+    async fn find_all_branches(&self) -> LibraryResult<Vec<BranchDto>> {
+        let mut branches = vec![];
+        let mut page: Option<String> = None;
+        loop {
+            let res = self.party_repository.query(
+                &HashMap::from([("kind".to_string(), PartyKind::Branch.to_string())]),
+                page.as_deref(), BRANCHES_PAGE_SIZE).await?;
+            branches.extend(res.records.iter().map(BranchDto::from));
+            match res.next_page {
+                Some(next_page) => page = Some(next_page),
+                None => return Ok(branches),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl BranchQueryService for BranchQueryServiceImpl {
+    async fn find_branch_by_id(&self, id: &str) -> LibraryResult<BranchDto> {
+        let party = self.party_repository.get(id).await?;
+        if party.kind != PartyKind::Branch {
+            return Err(LibraryError::not_found(format!("branch not found for {}", id).as_str()));
+        }
+        Ok(BranchDto::from(&party))
+    }
+
+    async fn find_branches(&self, page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BranchDto>> {
+        let res = self.party_repository.query(
+            &HashMap::from([("kind".to_string(), PartyKind::Branch.to_string())]), page, page_size).await?;
+        let records = res.records.iter().map(BranchDto::from).collect();
+        Ok(PaginatedResult::new(page, page_size, res.next_page, records))
+    }
+
+    async fn find_nearest_branches(&self, latitude: f64, longitude: f64, limit: usize) -> LibraryResult<Vec<NearestBranchDto>> {
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            return Err(LibraryError::validation(
+                format!("invalid location {},{}", latitude, longitude).as_str(), Some("400".to_string())));
+        }
+        let mut nearest: Vec<NearestBranchDto> = self.find_all_branches().await?.into_iter()
+            .filter(|branch| branch.active)
+            .filter_map(|branch| match (branch.latitude, branch.longitude) {
+                (Some(lat), Some(lon)) => Some(NearestBranchDto::new(branch, haversine_km(latitude, longitude, lat, lon))),
+                _ => None,
+            })
+            .collect();
+        nearest.sort_by(|a, b| a.distance_km.total_cmp(&b.distance_km));
+        nearest.truncate(limit);
+        Ok(nearest)
+    }
+
+    async fn find_nearest_branches_to_patron(&self, patron_id: &str, limit: usize) -> LibraryResult<Vec<NearestBranchDto>> {
+        let patron = self.party_repository.get(patron_id).await?;
+        match patron.address.as_ref().and_then(|address| address.latitude.zip(address.longitude)) {
+            Some((latitude, longitude)) => self.find_nearest_branches(latitude, longitude, limit).await,
+            None => Err(LibraryError::validation(
+                format!("address of patron {} is not geocoded", patron_id).as_str(), Some("400".to_string()))),
+        }
+    }
+}
+
+// great-circle distance between two coordinates
+fn haversine_km(from_latitude: f64, from_longitude: f64, to_latitude: f64, to_longitude: f64) -> f64 {
+    let d_latitude = (to_latitude - from_latitude).to_radians();
+    let d_longitude = (to_longitude - from_longitude).to_radians();
+    let a = (d_latitude / 2.0).sin().powi(2) +
+        from_latitude.to_radians().cos() * to_latitude.to_radians().cos() * (d_longitude / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::branches::domain::{BranchQueryService, BranchService};
+    use crate::branches::domain::query::haversine_km;
+    use crate::branches::dto::BranchDto;
+    use crate::branches::factory;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn BranchQueryService> {
+        factory::create_branch_query_service(&Configuration::new("test"), store).await
+    }
+
+    async fn branch_svc(store: RepositoryStore) -> Box<dyn BranchService> {
+        factory::create_branch_service(&Configuration::new("test"), store).await
+    }
+
+    fn new_branch(name: &str, latitude: Option<f64>, longitude: Option<f64>) -> BranchDto {
+        let mut branch = BranchDto::new(name, format!("{}@library.cc", name).as_str());
+        branch.street_address = "100 Main St.".to_string();
+        branch.city = "Seattle".to_string();
+        branch.country = "US".to_string();
+        branch.latitude = latitude;
+        branch.longitude = longitude;
+        branch
+    }
+
+    #[tokio::test]
+    async fn test_should_compute_haversine_distance() {
+        assert_eq!(0.0, haversine_km(47.6, -122.3, 47.6, -122.3));
+        // seattle to portland is about 233 km
+        let distance = haversine_km(47.6062, -122.3321, 45.5152, -122.6784);
+        assert!((distance - 233.0).abs() < 2.0, "unexpected distance {}", distance);
+    }
+
+    #[tokio::test]
+    async fn test_should_find_nearest_branches() {
+        let store = test_store(module_path!(), "test_should_find_nearest_branches");
+        let query_svc = sut_svc(store).await;
+        let branch_svc = branch_svc(store).await;
+        let _ = branch_svc.add_branch(&new_branch("downtown", Some(47.6062), Some(-122.3321))).await.expect("should add branch");
+        let _ = branch_svc.add_branch(&new_branch("portland", Some(45.5152), Some(-122.6784))).await.expect("should add branch");
+        let _ = branch_svc.add_branch(&new_branch("ballard", Some(47.6687), Some(-122.3841))).await.expect("should add branch");
+        let _ = branch_svc.add_branch(&new_branch("unknown", None, None)).await.expect("should add branch");
+
+        let nearest = query_svc.find_nearest_branches(47.6205, -122.3493, 10).await.expect("should find branches");
+        let names: Vec<&str> = nearest.iter().map(|n| n.branch.name.as_str()).collect();
+        assert_eq!(vec!["downtown", "ballard", "portland"], names);
+        assert!(nearest[0].distance_km < nearest[1].distance_km);
+
+        let nearest = query_svc.find_nearest_branches(47.6205, -122.3493, 1).await.expect("should find branches");
+        assert_eq!(1, nearest.len());
+        assert!(query_svc.find_nearest_branches(91.0, 0.0, 1).await.is_err());
+    }
+}
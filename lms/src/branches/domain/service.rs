@@ -0,0 +1,296 @@
+use async_trait::async_trait;
+use crate::branches::domain::{BranchQueryService, BranchService};
+use crate::branches::dto::{BranchDto, ClosureDto, NearestBranchDto, OpeningHoursDto};
+use crate::core::domain::Configuration;
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult, PartyKind};
+use crate::gateway::address::{AddressValidator, PostalAddress};
+use crate::parties::domain::model::{AddressEntity, ClosureEntity, OpeningHoursEntity, PartyEntity};
+use crate::parties::repository::PartyRepository;
+
+pub(crate) struct BranchServiceImpl {
+    party_repository: Box<dyn PartyRepository>,
+    query_service: Box<dyn BranchQueryService>,
+    address_validator: Box<dyn AddressValidator>,
+}
+
+impl BranchServiceImpl {
+    pub(crate) fn new(_config: &Configuration, party_repository: Box<dyn PartyRepository>,
+                      query_service: Box<dyn BranchQueryService>,
+                      address_validator: Box<dyn AddressValidator>) -> Self {
+        BranchServiceImpl {
+            party_repository,
+            query_service,
+            address_validator,
+        }
+    }
+
+    fn validate_hours(opening_hours: &[OpeningHoursDto]) -> LibraryResult<()> {
+        for (i, hours) in opening_hours.iter().enumerate() {
+            if hours.opens_at >= hours.closes_at {
+                return Err(LibraryError::validation(
+                    format!("branch must open before it closes on {}", hours.weekday).as_str(), Some("400".to_string())));
+            }
+            if opening_hours[..i].iter().any(|h| h.weekday == hours.weekday) {
+                return Err(LibraryError::validation(
+                    format!("opening hours of {} are repeated", hours.weekday).as_str(), Some("400".to_string())));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl BranchService for BranchServiceImpl {
+    async fn add_branch(&self, branch: &BranchDto) -> LibraryResult<BranchDto> {
+        if branch.name.is_empty() || branch.email.is_empty() {
+            return Err(LibraryError::validation("branch name and email are required", Some("400".to_string())));
+        }
+        BranchServiceImpl::validate_hours(&branch.opening_hours)?;
+        // branches are located by their address so it is always validated
+        let address = self.address_validator.validate(&PostalAddress {
+            street_address: branch.street_address.to_string(),
+            city: branch.city.to_string(),
+            zip_code: branch.zip_code.to_string(),
+            state: branch.state.to_string(),
+            country: branch.country.to_string(),
+            latitude: branch.latitude,
+            longitude: branch.longitude,
+        }).await?;
+        let mut entity = PartyEntity::from(branch);
+        entity.address = Some(AddressEntity {
+            street_address: address.street_address,
+            city: address.city,
+            zip_code: address.zip_code,
+            state: address.state,
+            country: address.country,
+            latitude: address.latitude,
+            longitude: address.longitude,
+            created_at: branch.created_at,
+            updated_at: branch.updated_at,
+        });
+        self.party_repository.create(&entity).await?;
+        self.find_branch_by_id(branch.branch_id.as_str()).await
+    }
+
+    async fn update_calendar(&self, branch_id: &str, opening_hours: &[OpeningHoursDto],
+                             closures: &[ClosureDto]) -> LibraryResult<BranchDto> {
+        BranchServiceImpl::validate_hours(opening_hours)?;
+        let mut entity = self.party_repository.get(branch_id).await?;
+        if entity.kind != PartyKind::Branch {
+            return Err(LibraryError::not_found(format!("branch not found for {}", branch_id).as_str()));
+        }
+        entity.opening_hours = opening_hours.iter().map(OpeningHoursEntity::from).collect();
+        entity.closures = closures.iter().map(ClosureEntity::from).collect();
+        self.party_repository.update(&entity).await?;
+        self.find_branch_by_id(branch_id).await
+    }
+}
+
+#[async_trait]
+impl BranchQueryService for BranchServiceImpl {
+    async fn find_branch_by_id(&self, id: &str) -> LibraryResult<BranchDto> {
+        self.query_service.find_branch_by_id(id).await
+    }
+
+    async fn find_branches(&self, page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BranchDto>> {
+        self.query_service.find_branches(page, page_size).await
+    }
+
+    async fn find_nearest_branches(&self, latitude: f64, longitude: f64, limit: usize) -> LibraryResult<Vec<NearestBranchDto>> {
+        self.query_service.find_nearest_branches(latitude, longitude, limit).await
+    }
+
+    async fn find_nearest_branches_to_patron(&self, patron_id: &str, limit: usize) -> LibraryResult<Vec<NearestBranchDto>> {
+        self.query_service.find_nearest_branches_to_patron(patron_id, limit).await
+    }
+}
+
+impl From<&PartyEntity> for BranchDto {
+    fn from(other: &PartyEntity) -> Self {
+        let mut branch = Self {
+            branch_id: other.party_id.to_string(),
+            version: other.version,
+            name: other.organization_name.to_string(),
+            email: other.email.to_string(),
+            work_phone: other.work_phone.clone(),
+            street_address: "".to_string(),
+            city: "".to_string(),
+            zip_code: "".to_string(),
+            state: "".to_string(),
+            country: "".to_string(),
+            latitude: None,
+            longitude: None,
+            opening_hours: other.opening_hours.iter().map(OpeningHoursDto::from).collect(),
+            closures: other.closures.iter().map(ClosureDto::from).collect(),
+            active: other.active,
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        };
+        if let Some(address) = &other.address {
+            branch.street_address = address.street_address.to_string();
+            branch.city = address.city.to_string();
+            branch.zip_code = address.zip_code.to_string();
+            branch.state = address.state.to_string();
+            branch.country = address.country.to_string();
+            branch.latitude = address.latitude;
+            branch.longitude = address.longitude;
+        }
+        branch
+    }
+}
+
+impl From<&BranchDto> for PartyEntity {
+    fn from(other: &BranchDto) -> Self {
+        let mut branch = PartyEntity::new(PartyKind::Branch, other.email.as_str());
+        branch.party_id = other.branch_id.to_string();
+        branch.version = other.version;
+        branch.organization_name = other.name.to_string();
+        branch.work_phone = other.work_phone.clone();
+        branch.opening_hours = other.opening_hours.iter().map(OpeningHoursEntity::from).collect();
+        branch.closures = other.closures.iter().map(ClosureEntity::from).collect();
+        branch.active = other.active;
+        branch.created_at = other.created_at;
+        branch.updated_at = other.updated_at;
+        branch
+    }
+}
+
+impl From<&OpeningHoursEntity> for OpeningHoursDto {
+    fn from(other: &OpeningHoursEntity) -> Self {
+        OpeningHoursDto::new(other.weekday, other.opens_at, other.closes_at)
+    }
+}
+
+impl From<&OpeningHoursDto> for OpeningHoursEntity {
+    fn from(other: &OpeningHoursDto) -> Self {
+        Self {
+            weekday: other.weekday,
+            opens_at: other.opens_at,
+            closes_at: other.closes_at,
+        }
+    }
+}
+
+impl From<&ClosureEntity> for ClosureDto {
+    fn from(other: &ClosureEntity) -> Self {
+        ClosureDto::new(other.date, other.reason.as_str())
+    }
+}
+
+impl From<&ClosureDto> for ClosureEntity {
+    fn from(other: &ClosureDto) -> Self {
+        Self {
+            date: other.date,
+            reason: other.reason.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveTime, Weekday};
+    use crate::branches::domain::BranchService;
+    use crate::branches::dto::{BranchDto, ClosureDto, OpeningHoursDto};
+    use crate::branches::factory;
+    use crate::core::domain::Configuration;
+    use crate::core::library::LibraryError;
+    use crate::core::repository::RepositoryStore;
+    use crate::patrons::domain::PatronService;
+    use crate::patrons::dto::PatronDto;
+    use crate::patrons::factory::create_patron_service;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn BranchService> {
+        factory::create_branch_service(&Configuration::new("test"), store).await
+    }
+
+    async fn patron_svc(store: RepositoryStore) -> Box<dyn PatronService> {
+        create_patron_service(&Configuration::new("test"), store).await
+    }
+
+    fn time(hour: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, 0, 0).expect("should build time")
+    }
+
+    #[tokio::test]
+    async fn test_should_add_branch() {
+        let store = test_store(module_path!(), "test_should_add_branch");
+        let branch_svc = sut_svc(store).await;
+
+        let mut branch = BranchDto::new("Central", "central@library.cc");
+        branch.street_address = " 1000  4th Ave ".to_string();
+        branch.city = "Seattle".to_string();
+        branch.state = "wa".to_string();
+        branch.country = "us".to_string();
+        branch.opening_hours = vec![OpeningHoursDto::new(Weekday::Sat, time(10), time(20))];
+        let added = branch_svc.add_branch(&branch).await.expect("should add branch");
+        assert_eq!("1000 4th Ave", added.street_address.as_str());
+        assert_eq!("WA", added.state.as_str());
+        assert_eq!(branch.opening_hours, added.opening_hours);
+
+        // branches require a name and an address
+        assert!(branch_svc.add_branch(&BranchDto::new("", "no_name@library.cc")).await.is_err());
+        let res = branch_svc.add_branch(&BranchDto::new("No Address", "no_address@library.cc")).await;
+        assert!(matches!(res, Err(LibraryError::Validation { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_should_find_nearest_branches_to_patron() {
+        let store = test_store(module_path!(), "test_should_find_nearest_branches_to_patron");
+        let branch_svc = sut_svc(store).await;
+        let patron_svc = patron_svc(store).await;
+
+        let mut branch = BranchDto::new("Central", "central@library.cc");
+        branch.street_address = "1000 4th Ave".to_string();
+        branch.city = "Seattle".to_string();
+        branch.country = "US".to_string();
+        branch.latitude = Some(47.6067);
+        branch.longitude = Some(-122.3325);
+        let _ = branch_svc.add_branch(&branch).await.expect("should add branch");
+
+        let mut patron = PatronDto::new("nearby@example.com");
+        let _ = patron_svc.add_patron(&patron).await.expect("should add patron");
+        let res = branch_svc.find_nearest_branches_to_patron(patron.patron_id.as_str(), 5).await;
+        assert!(matches!(res, Err(LibraryError::Validation { .. })));
+
+        patron.street_address = Some("400 Broad St".to_string());
+        patron.city = Some("Seattle".to_string());
+        patron.zip_code = Some("98109".to_string());
+        patron.state = Some("WA".to_string());
+        patron.country = Some("US".to_string());
+        patron.latitude = Some(47.6205);
+        patron.longitude = Some(-122.3493);
+        let _ = patron_svc.update_patron(&patron).await.expect("should update patron");
+        let nearest = branch_svc.find_nearest_branches_to_patron(patron.patron_id.as_str(), 5).await
+            .expect("should find branches");
+        assert_eq!(1, nearest.len());
+        assert_eq!(branch.branch_id, nearest[0].branch.branch_id);
+        assert!(nearest[0].distance_km < 3.0);
+    }
+
+    #[tokio::test]
+    async fn test_should_update_calendar() {
+        let store = test_store(module_path!(), "test_should_update_calendar");
+        let branch_svc = sut_svc(store).await;
+
+        let mut branch = BranchDto::new("Central", "calendar@library.cc");
+        branch.street_address = "1000 4th Ave".to_string();
+        branch.city = "Seattle".to_string();
+        branch.country = "US".to_string();
+        let _ = branch_svc.add_branch(&branch).await.expect("should add branch");
+
+        let hours = vec![OpeningHoursDto::new(Weekday::Mon, time(9), time(18)),
+                         OpeningHoursDto::new(Weekday::Tue, time(9), time(18))];
+        let closures = vec![ClosureDto::new(NaiveDate::from_ymd_opt(2023, 12, 25).expect("should build date"), "Christmas")];
+        let updated = branch_svc.update_calendar(branch.branch_id.as_str(), &hours, &closures).await
+            .expect("should update calendar");
+        assert_eq!(hours, updated.opening_hours);
+        assert_eq!(closures, updated.closures);
+        assert_eq!("1000 4th Ave", updated.street_address.as_str());
+
+        let invalid = vec![OpeningHoursDto::new(Weekday::Mon, time(18), time(9))];
+        assert!(branch_svc.update_calendar(branch.branch_id.as_str(), &invalid, &[]).await.is_err());
+        let repeated = vec![hours[0].clone(), hours[0].clone()];
+        assert!(branch_svc.update_calendar(branch.branch_id.as_str(), &repeated, &[]).await.is_err());
+    }
+}
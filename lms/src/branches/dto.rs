@@ -0,0 +1,197 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::core::domain::Identifiable;
+
+// due dates are rolled forward by at most a year when a branch has no open day
+const MAX_CLOSED_DAYS: usize = 366;
+
+// Branch abstracts library location where patrons borrow and return books.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct BranchDto {
+    pub branch_id: String,
+    pub version: i64,
+    pub name: String,
+    pub email: String,
+    pub work_phone: Option<String>,
+    pub street_address: String,
+    pub city: String,
+    pub zip_code: String,
+    pub state: String,
+    pub country: String,
+    // coordinates are set when the address validation provider geocodes the address of the branch
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    // branches without opening hours are treated as open every day other than their closures
+    pub opening_hours: Vec<OpeningHoursDto>,
+    pub closures: Vec<ClosureDto>,
+    pub active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl BranchDto {
+    pub(crate) fn new(name: &str, email: &str) -> Self {
+        Self {
+            branch_id: Uuid::new_v4().to_string(),
+            version: 0,
+            name: name.to_string(),
+            email: email.to_string(),
+            work_phone: None,
+            street_address: "".to_string(),
+            city: "".to_string(),
+            zip_code: "".to_string(),
+            state: "".to_string(),
+            country: "".to_string(),
+            latitude: None,
+            longitude: None,
+            opening_hours: vec![],
+            closures: vec![],
+            active: true,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+
+    pub(crate) fn is_open_on(&self, date: NaiveDate) -> bool {
+        if self.closures.iter().any(|c| c.date == date) {
+            return false;
+        }
+        self.opening_hours.is_empty() || self.opening_hours.iter().any(|h| h.weekday == date.weekday())
+    }
+
+    // rolls the time forward by whole days until it falls on a day the branch is open
+    pub(crate) fn next_open_at(&self, at: NaiveDateTime) -> NaiveDateTime {
+        let mut next = at;
+        for _ in 0..MAX_CLOSED_DAYS {
+            if self.is_open_on(next.date()) {
+                return next;
+            }
+            next += Duration::days(1);
+        }
+        at
+    }
+
+    // overdue days after the due date up to and including the given date, days the branch is closed are not counted
+    pub(crate) fn open_days_overdue(&self, due_on: NaiveDate, on: NaiveDate) -> i64 {
+        due_on.iter_days().skip(1).take_while(|date| *date <= on).filter(|date| self.is_open_on(*date)).count() as i64
+    }
+}
+
+impl Identifiable for BranchDto {
+    fn id(&self) -> String {
+        self.branch_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+// OpeningHoursDto defines when a branch opens and closes on a day of the week
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct OpeningHoursDto {
+    pub weekday: Weekday,
+    pub opens_at: NaiveTime,
+    pub closes_at: NaiveTime,
+}
+
+impl OpeningHoursDto {
+    pub(crate) fn new(weekday: Weekday, opens_at: NaiveTime, closes_at: NaiveTime) -> Self {
+        Self {
+            weekday,
+            opens_at,
+            closes_at,
+        }
+    }
+}
+
+// ClosureDto defines a date such as a holiday on which a branch is closed
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct ClosureDto {
+    pub date: NaiveDate,
+    #[serde(default)]
+    pub reason: String,
+}
+
+impl ClosureDto {
+    pub(crate) fn new(date: NaiveDate, reason: &str) -> Self {
+        Self {
+            date,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+// NearestBranchDto is a branch along with its distance from the location of a search
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct NearestBranchDto {
+    #[serde(flatten)]
+    pub branch: BranchDto,
+    pub distance_km: f64,
+}
+
+impl NearestBranchDto {
+    pub(crate) fn new(branch: BranchDto, distance_km: f64) -> Self {
+        Self {
+            branch,
+            distance_km,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveTime, Weekday};
+    use crate::branches::dto::{BranchDto, ClosureDto, NearestBranchDto, OpeningHoursDto};
+
+    #[tokio::test]
+    async fn test_should_build_branch() {
+        let branch = BranchDto::new("Central", "central@library.cc");
+        assert_eq!("Central", branch.name.as_str());
+        assert!(branch.active);
+        assert_eq!(None, branch.latitude);
+
+        let json = serde_json::to_value(NearestBranchDto::new(branch, 1.5)).expect("should serialize");
+        assert_eq!("Central", json["name"].as_str().unwrap_or_default());
+        assert_eq!(1.5, json["distance_km"].as_f64().unwrap_or_default());
+    }
+
+    #[tokio::test]
+    async fn test_should_roll_to_next_open_day() {
+        let mut branch = BranchDto::new("Central", "central@library.cc");
+        let saturday = NaiveDate::from_ymd_opt(2023, 12, 23).expect("should build date");
+        let at = saturday.and_hms_opt(12, 0, 0).expect("should build time");
+        // branches without hours are open every day
+        assert_eq!(at, branch.next_open_at(at));
+
+        let (opens_at, closes_at) = (NaiveTime::from_hms_opt(9, 0, 0).expect("should build time"),
+                                     NaiveTime::from_hms_opt(18, 0, 0).expect("should build time"));
+        branch.opening_hours = [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri].iter()
+            .map(|d| OpeningHoursDto::new(*d, opens_at, closes_at)).collect();
+        branch.closures = vec![ClosureDto::new(NaiveDate::from_ymd_opt(2023, 12, 25).expect("should build date"), "Christmas")];
+        assert!(!branch.is_open_on(saturday));
+        // saturday and sunday are closed and monday is a holiday
+        let next = branch.next_open_at(at);
+        assert_eq!(NaiveDate::from_ymd_opt(2023, 12, 26).expect("should build date"), next.date());
+        assert_eq!(at.time(), next.time());
+    }
+
+    #[tokio::test]
+    async fn test_should_count_open_days_overdue() {
+        let date = |day: u32| NaiveDate::from_ymd_opt(2023, 12, day).expect("should build date");
+        let mut branch = BranchDto::new("Central", "central@library.cc");
+        assert_eq!(0, branch.open_days_overdue(date(22), date(22)));
+        assert_eq!(0, branch.open_days_overdue(date(22), date(20)));
+        assert_eq!(5, branch.open_days_overdue(date(22), date(27)));
+
+        // multi-day closure right after the due date
+        branch.closures = (23..=26).map(|day| ClosureDto::new(date(day), "Holidays")).collect();
+        assert_eq!(0, branch.open_days_overdue(date(22), date(26)));
+        assert_eq!(1, branch.open_days_overdue(date(22), date(27)));
+        // closure on the due date itself does not change the count
+        assert_eq!(1, branch.open_days_overdue(date(23), date(27)));
+        // closure ending on the day of the count
+        assert_eq!(2, branch.open_days_overdue(date(20), date(26)));
+    }
+}
@@ -0,0 +1,19 @@
+use crate::branches::domain::{BranchQueryService, BranchService};
+use crate::branches::domain::query::BranchQueryServiceImpl;
+use crate::branches::domain::service::BranchServiceImpl;
+use crate::core::domain::Configuration;
+use crate::core::repository::RepositoryStore;
+use crate::gateway::factory::create_address_validator;
+use crate::parties::factory;
+
+pub(crate) async fn create_branch_query_service(_config: &Configuration, store: RepositoryStore) -> Box<dyn BranchQueryService> {
+    let party_repo = factory::create_party_repository(store).await;
+    Box::new(BranchQueryServiceImpl::new(party_repo))
+}
+
+pub(crate) async fn create_branch_service(config: &Configuration, store: RepositoryStore) -> Box<dyn BranchService> {
+    let party_repo = factory::create_party_repository(store).await;
+    let query_svc = create_branch_query_service(config, store).await;
+    let address_validator = create_address_validator(config);
+    Box::new(BranchServiceImpl::new(config, party_repo, query_svc, address_validator))
+}
@@ -3,3 +3,7 @@ pub mod domain;
 pub mod dto;
 pub mod factory;
 pub mod controller;
+pub mod shelf_list;
+pub mod marc;
+pub mod oai;
+pub mod feed;
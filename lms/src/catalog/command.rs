@@ -1,4 +1,23 @@
 pub mod add_book_cmd;
 pub mod update_book_cmd;
 pub mod remove_book_cmd;
-pub mod get_book_cmd;
\ No newline at end of file
+pub mod remove_books_cmd;
+pub mod get_book_cmd;
+pub mod add_book_tags_cmd;
+pub mod remove_book_tags_cmd;
+pub mod find_books_by_tag_cmd;
+pub mod find_books_by_isbn_cmd;
+pub mod find_books_by_author_cmd;
+pub mod get_tags_cmd;
+pub mod find_related_books_cmd;
+pub mod update_location_cmd;
+pub mod upload_cover_cmd;
+pub mod get_cover_cmd;
+pub mod export_shelf_list_cmd;
+pub mod find_duplicate_books_cmd;
+pub mod merge_books_cmd;
+pub mod find_trending_books_cmd;
+pub mod import_marc_cmd;
+pub mod federated_search_cmd;
+pub mod harvest_oai_cmd;
+pub mod new_acquisitions_feed_cmd;
@@ -3,7 +3,9 @@ use serde::{Deserialize, Serialize};
 use crate::books::dto::BookDto;
 use crate::catalog::domain::CatalogService;
 use crate::core::command::{Command, CommandError};
-use crate::core::library::BookStatus;
+#[cfg(feature = "examples")]
+use crate::core::command::examples::{CommandExamples, EXAMPLE_ISBN};
+use crate::core::library::{BookFormat, BookStatus, LibraryResult};
 
 pub(crate) struct AddBookCommand {
     catalog_service: Box<dyn CatalogService>,
@@ -17,10 +19,28 @@ impl AddBookCommand {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct AddBookCommandRequest {
     pub(crate) isbn: String,
     pub(crate) title: String,
+    #[serde(default)]
+    pub(crate) author_id: String,
+    #[serde(default)]
+    pub(crate) publisher_id: String,
+    #[serde(default)]
+    pub(crate) language: Option<String>,
+    #[serde(default)]
+    pub(crate) dewey_decimal_id: String,
+    #[serde(default)]
+    pub(crate) tags: Vec<String>,
+    #[serde(default)]
+    pub(crate) book_format: BookFormat,
+    #[serde(default)]
+    pub(crate) license_count: i64,
+    #[serde(default)]
+    pub(crate) collection: String,
+    #[serde(default)]
+    pub(crate) shelf_location: String,
 }
 
 impl AddBookCommandRequest {
@@ -28,15 +48,39 @@ impl AddBookCommandRequest {
         Self {
             isbn: isbn.to_string(),
             title: title.to_string(),
+            author_id: String::new(),
+            publisher_id: String::new(),
+            language: None,
+            dewey_decimal_id: String::new(),
+            tags: vec![],
+            book_format: BookFormat::Physical,
+            license_count: 0,
+            collection: String::new(),
+            shelf_location: String::new(),
         }
     }
-    pub fn build_book(&self) -> BookDto {
-        BookDto::new(self.isbn.as_str(), self.title.as_str(), BookStatus::Available)
+    pub fn build_book(&self) -> LibraryResult<BookDto> {
+        let mut builder = BookDto::builder()
+            .isbn(self.isbn.as_str())
+            .title(self.title.as_str())
+            .author_id(self.author_id.as_str())
+            .publisher_id(self.publisher_id.as_str())
+            .dewey_decimal_id(self.dewey_decimal_id.as_str())
+            .book_status(BookStatus::Available)
+            .tags(&self.tags)
+            .book_format(self.book_format)
+            .license_count(self.license_count)
+            .collection(self.collection.as_str())
+            .shelf_location(self.shelf_location.as_str());
+        if let Some(language) = &self.language {
+            builder = builder.language(language.as_str());
+        }
+        builder.build()
     }
 }
 
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct AddBookCommandResponse {
     pub book: BookDto,
 }
@@ -49,18 +93,41 @@ impl AddBookCommandResponse {
     }
 }
 
+#[cfg(feature = "examples")]
+impl CommandExamples for AddBookCommandRequest {
+    fn examples() -> Vec<Self> {
+        let mut req = AddBookCommandRequest::new(EXAMPLE_ISBN, "Dune");
+        req.author_id = "frank-herbert".to_string();
+        req.publisher_id = "ace-books".to_string();
+        req.language = Some("en".to_string());
+        req.dewey_decimal_id = "813.54".to_string();
+        req.tags = vec!["science-fiction".to_string()];
+        req.collection = "FIC".to_string();
+        req.shelf_location = "A3".to_string();
+        let mut ebook = AddBookCommandRequest::new("9780593099322", "Dune Messiah");
+        ebook.book_format = BookFormat::EBook;
+        ebook.license_count = 5;
+        vec![req, ebook]
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for AddBookCommandResponse {
+    fn examples() -> Vec<Self> {
+        vec![AddBookCommandResponse::new(BookDto::example())]
+    }
+}
+
 #[async_trait]
 impl Command<AddBookCommandRequest, AddBookCommandResponse> for AddBookCommand {
     async fn execute(&self, req: AddBookCommandRequest) -> Result<AddBookCommandResponse, CommandError> {
-        let book = req.build_book();
-        self.catalog_service.add_book(&book).await.map_err(CommandError::from).map(|_| AddBookCommandResponse::new(book))
+        let book = req.build_book().map_err(CommandError::from)?;
+        self.catalog_service.add_book(&book).await.map_err(CommandError::from).map(AddBookCommandResponse::new)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use async_once::AsyncOnce;
-    use lazy_static::lazy_static;
     use crate::books::dto::BookDto;
     use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
     use crate::catalog::factory;
@@ -68,17 +135,17 @@ mod tests {
     use crate::core::library::BookStatus;
     use crate::core::domain::Configuration;
     use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
 
-    lazy_static! {
-        static ref SUT_CMD : AsyncOnce<AddBookCommand> = AsyncOnce::new(async {
-                let svc = factory::create_catalog_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                AddBookCommand::new(svc)
-            });
+    async fn sut_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
     }
 
     #[tokio::test]
     async fn test_should_run_add_book() {
-        let cmd = SUT_CMD.get().await.clone();
+        let store = test_store(module_path!(), "test_should_run_add_book");
+        let cmd = sut_cmd(store).await;
 
         let book = BookDto::new("isbn", "test book", BookStatus::Available);
         let _ = cmd.execute(AddBookCommandRequest::new(book.isbn.as_str(), book.title.as_str()))
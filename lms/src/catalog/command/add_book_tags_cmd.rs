@@ -0,0 +1,107 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::books::dto::BookDto;
+use crate::catalog::domain::CatalogService;
+use crate::core::command::{Command, CommandError};
+#[cfg(feature = "examples")]
+use crate::core::command::examples::{CommandExamples, EXAMPLE_BOOK_ID};
+use crate::core::ids::BookId;
+
+pub(crate) struct AddBookTagsCommand {
+    catalog_service: Box<dyn CatalogService>,
+}
+
+impl AddBookTagsCommand {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogService>) -> Self {
+        Self {
+            catalog_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AddBookTagsCommandRequest {
+    #[serde(default)]
+    pub(crate) book_id: String,
+    pub(crate) tags: Vec<String>,
+}
+
+impl AddBookTagsCommandRequest {
+    pub fn new(book_id: &str, tags: Vec<String>) -> Self {
+        Self {
+            book_id: book_id.to_string(),
+            tags,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AddBookTagsCommandResponse {
+    pub book: BookDto,
+}
+
+impl AddBookTagsCommandResponse {
+    pub fn new(book: BookDto) -> Self {
+        Self {
+            book,
+        }
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for AddBookTagsCommandRequest {
+    fn examples() -> Vec<Self> {
+        vec![AddBookTagsCommandRequest::new(EXAMPLE_BOOK_ID, vec!["classics".to_string()])]
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for AddBookTagsCommandResponse {
+    fn examples() -> Vec<Self> {
+        let mut book = BookDto::example();
+        book.tags.push("classics".to_string());
+        vec![AddBookTagsCommandResponse::new(book)]
+    }
+}
+
+#[async_trait]
+impl Command<AddBookTagsCommandRequest, AddBookTagsCommandResponse> for AddBookTagsCommand {
+    async fn execute(&self, req: AddBookTagsCommandRequest) -> Result<AddBookTagsCommandResponse, CommandError> {
+        self.catalog_service.add_tags(&BookId::new(req.book_id.as_str()), &req.tags)
+            .await.map_err(CommandError::from).map(AddBookTagsCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
+    use crate::catalog::command::add_book_tags_cmd::{AddBookTagsCommand, AddBookTagsCommandRequest};
+    use crate::catalog::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_tags_cmd(store: RepositoryStore) -> AddBookTagsCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookTagsCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_add_book_tags() {
+        let store = test_store(module_path!(), "test_should_run_add_book_tags");
+        let add_cmd = build_add_cmd(store).await;
+        let tags_cmd = build_tags_cmd(store).await;
+
+        let res = add_cmd.execute(AddBookCommandRequest::new("isbn", "test book")).await.expect("should add book");
+        let tagged = tags_cmd.execute(AddBookTagsCommandRequest::new(
+            res.book.book_id.as_str(), vec!["history".to_string()])).await.expect("should add tags");
+        assert_eq!(vec!["history".to_string()], tagged.book.tags);
+    }
+}
@@ -0,0 +1,123 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::catalog::domain::CatalogQueryService;
+use crate::catalog::shelf_list::export_shelf_list;
+use crate::core::command::{Command, CommandError};
+#[cfg(feature = "examples")]
+use crate::core::command::examples::CommandExamples;
+#[cfg(feature = "examples")]
+use crate::books::dto::BookDto;
+#[cfg(feature = "examples")]
+use crate::catalog::shelf_list::to_csv;
+
+pub(crate) struct ExportShelfListCommand {
+    catalog_service: Box<dyn CatalogQueryService>,
+}
+
+impl ExportShelfListCommand {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogQueryService>) -> Self {
+        Self {
+            catalog_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ExportShelfListCommandRequest {
+    // collection prefix of call numbers such as REF, books without collection are listed when it is empty
+    #[serde(default)]
+    pub(crate) collection: String,
+    // dewey classes of the range, both inclusive
+    pub(crate) from: String,
+    pub(crate) to: String,
+}
+
+impl ExportShelfListCommandRequest {
+    pub fn new(collection: &str, from: &str, to: &str) -> Self {
+        Self {
+            collection: collection.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ExportShelfListCommandResponse {
+    pub csv: String,
+}
+
+impl ExportShelfListCommandResponse {
+    pub fn new(csv: String) -> Self {
+        Self {
+            csv,
+        }
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for ExportShelfListCommandRequest {
+    fn examples() -> Vec<Self> {
+        vec![ExportShelfListCommandRequest::new("FIC", "800", "899")]
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for ExportShelfListCommandResponse {
+    fn examples() -> Vec<Self> {
+        vec![ExportShelfListCommandResponse::new(to_csv(&[BookDto::example()]))]
+    }
+}
+
+#[async_trait]
+impl Command<ExportShelfListCommandRequest, ExportShelfListCommandResponse> for ExportShelfListCommand {
+    async fn execute(&self, req: ExportShelfListCommandRequest) -> Result<ExportShelfListCommandResponse, CommandError> {
+        export_shelf_list(self.catalog_service.as_ref(), req.collection.as_str(), req.from.as_str(), req.to.as_str())
+            .await.map_err(CommandError::from).map(ExportShelfListCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
+    use crate::catalog::command::export_shelf_list_cmd::{ExportShelfListCommand, ExportShelfListCommandRequest};
+    use crate::catalog::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_export_cmd(store: RepositoryStore) -> ExportShelfListCommand {
+        let svc = factory::create_catalog_query_service(&Configuration::new("test"), store).await;
+        ExportShelfListCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_export_shelf_list() {
+        let store = test_store(module_path!(), "test_should_run_export_shelf_list");
+        let add_cmd = build_add_cmd(store).await;
+        let export_cmd = build_export_cmd(store).await;
+        for (dewey, title) in [("523.1", "Galaxies"), ("510", "Algebra"), ("610", "Anatomy")] {
+            let mut req = AddBookCommandRequest::new("isbn", title);
+            req.dewey_decimal_id = dewey.to_string();
+            req.collection = "shelfcmd".to_string();
+            let _ = add_cmd.execute(req).await.expect("should add book");
+        }
+
+        let res = export_cmd.execute(ExportShelfListCommandRequest::new("shelfcmd", "500", "599"))
+            .await.expect("should export shelf list");
+        let call_numbers = res.csv.lines().skip(1).map(|line| line.split(',').next().unwrap_or_default())
+            .collect::<Vec<&str>>();
+        assert!(call_numbers.contains(&"SHELFCMD 510 ALG"));
+        assert!(call_numbers.contains(&"SHELFCMD 523.1 GAL"));
+        assert!(!call_numbers.contains(&"SHELFCMD 610 ANA"));
+        assert!(call_numbers.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(export_cmd.execute(ExportShelfListCommandRequest::new("shelfcmd", "599", "500")).await.is_err());
+    }
+}
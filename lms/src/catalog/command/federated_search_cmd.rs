@@ -0,0 +1,92 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::books::dto::FederatedSearchDto;
+use crate::catalog::domain::CatalogQueryService;
+use crate::core::command::{Command, CommandError};
+
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 50;
+
+pub(crate) struct FederatedSearchCommand {
+    catalog_service: Box<dyn CatalogQueryService>,
+}
+
+impl FederatedSearchCommand {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogQueryService>) -> Self {
+        Self {
+            catalog_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FederatedSearchCommandRequest {
+    // isbn or title
+    pub(crate) q: String,
+    pub(crate) limit: Option<usize>,
+}
+
+impl FederatedSearchCommandRequest {
+    pub fn new(q: &str) -> Self {
+        Self {
+            q: q.to_string(),
+            limit: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FederatedSearchCommandResponse {
+    #[serde(flatten)]
+    pub search: FederatedSearchDto,
+}
+
+impl FederatedSearchCommandResponse {
+    pub fn new(search: FederatedSearchDto) -> Self {
+        Self {
+            search,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<FederatedSearchCommandRequest, FederatedSearchCommandResponse> for FederatedSearchCommand {
+    async fn execute(&self, req: FederatedSearchCommandRequest) -> Result<FederatedSearchCommandResponse, CommandError> {
+        let limit = req.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+        self.catalog_service.federated_search(req.q.as_str(), limit)
+            .await.map_err(CommandError::from).map(FederatedSearchCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::command::federated_search_cmd::{FederatedSearchCommand, FederatedSearchCommandRequest};
+    use crate::catalog::factory;
+    use crate::core::command::{Command, CommandError};
+    use crate::core::domain::Configuration;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_run_federated_search() {
+        let store = test_store(module_path!(), "test_should_run_federated_search");
+        let mut config = Configuration::new("test");
+        config.union_catalog_url = None;
+        let sut_cmd = FederatedSearchCommand::new(factory::create_catalog_query_service(&config, store).await);
+
+        // searches degrade without union catalog
+        let res = sut_cmd.execute(FederatedSearchCommandRequest::new("Dune")).await.expect("should search");
+        assert!(res.search.degraded);
+        assert!(res.search.candidates.is_empty());
+        assert_eq!(Some("union catalog is not configured"), res.search.reason.as_deref());
+
+        // searches degrade when the union catalog cannot be reached
+        config.union_catalog_url = Some("http://127.0.0.1:9/sru".to_string());
+        config.union_catalog_timeout_ms = 500;
+        let sut_cmd = FederatedSearchCommand::new(factory::create_catalog_query_service(&config, store).await);
+        let res = sut_cmd.execute(FederatedSearchCommandRequest::new("978-0-441-17271-9")).await.expect("should search");
+        assert!(res.search.degraded);
+
+        let res = sut_cmd.execute(FederatedSearchCommandRequest::new(" ")).await;
+        assert!(matches!(res, Err(CommandError::Validation { .. })));
+    }
+}
@@ -0,0 +1,153 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::books::dto::{BookDto, BookFilter, BookSort};
+use crate::catalog::domain::CatalogQueryService;
+use crate::core::command::{Command, CommandError};
+#[cfg(feature = "examples")]
+use crate::core::command::examples::{CommandExamples};
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+
+pub(crate) struct FindBooksByAuthorCommand {
+    catalog_service: Box<dyn CatalogQueryService>,
+}
+
+impl FindBooksByAuthorCommand {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogQueryService>) -> Self {
+        Self {
+            catalog_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindBooksByAuthorCommandRequest {
+    #[serde(default)]
+    pub(crate) author_id: String,
+    pub(crate) page: Option<String>,
+    pub(crate) page_size: Option<usize>,
+    // title, published_at or created_at with a leading - for descending order
+    pub(crate) sort: Option<String>,
+    // ISO 639-1 code such as en
+    pub(crate) language: Option<String>,
+    // Physical, EBook, Audiobook or Serial
+    pub(crate) book_format: Option<String>,
+}
+
+impl FindBooksByAuthorCommandRequest {
+    pub fn new(author_id: &str) -> Self {
+        Self {
+            author_id: author_id.to_string(),
+            page: None,
+            page_size: None,
+            sort: None,
+            language: None,
+            book_format: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindBooksByAuthorCommandResponse {
+    pub books: Vec<BookDto>,
+    pub next_page: Option<String>,
+}
+
+impl FindBooksByAuthorCommandResponse {
+    pub fn new(books: Vec<BookDto>, next_page: Option<String>) -> Self {
+        Self {
+            books,
+            next_page,
+        }
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for FindBooksByAuthorCommandRequest {
+    fn examples() -> Vec<Self> {
+        let mut req = FindBooksByAuthorCommandRequest::new("frank-herbert");
+        req.page_size = Some(20);
+        req.sort = Some("-published_at".to_string());
+        req.language = Some("en".to_string());
+        vec![req]
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for FindBooksByAuthorCommandResponse {
+    fn examples() -> Vec<Self> {
+        vec![FindBooksByAuthorCommandResponse::new(vec![BookDto::example()], Some("eyJib29rX2lkIjoiMSJ9".to_string()))]
+    }
+}
+
+#[async_trait]
+impl Command<FindBooksByAuthorCommandRequest, FindBooksByAuthorCommandResponse> for FindBooksByAuthorCommand {
+    async fn execute(&self, req: FindBooksByAuthorCommandRequest) -> Result<FindBooksByAuthorCommandResponse, CommandError> {
+        let sort = BookSort::from_param(req.sort.as_deref())?;
+        let filter = BookFilter::from_params(req.language.as_deref(), req.book_format.as_deref())?;
+        let res = self.catalog_service.find_books_by_author(req.author_id.as_str(), &filter, req.page.as_deref(),
+                                                            req.page_size.unwrap_or(DEFAULT_PAGE_SIZE))
+            .await.map_err(CommandError::from)?;
+        let mut books = res.records;
+        if let Some(sort) = sort {
+            sort.sort(&mut books);
+        }
+        Ok(FindBooksByAuthorCommandResponse::new(books, res.next_page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
+    use crate::catalog::command::find_books_by_author_cmd::{FindBooksByAuthorCommand, FindBooksByAuthorCommandRequest};
+    use crate::catalog::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_find_cmd(store: RepositoryStore) -> FindBooksByAuthorCommand {
+        let svc = factory::create_catalog_query_service(&Configuration::new("test"), store).await;
+        FindBooksByAuthorCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_find_books_by_author() {
+        let store = test_store(module_path!(), "test_should_run_find_books_by_author");
+        let add_cmd = build_add_cmd(store).await;
+        let find_cmd = build_find_cmd(store).await;
+
+        let mut req = AddBookCommandRequest::new("isbn", "test book");
+        req.author_id = "cmd_author".to_string();
+        let res = add_cmd.execute(req).await.expect("should add book");
+        let found = find_cmd.execute(FindBooksByAuthorCommandRequest::new("cmd_author")).await.expect("should find books");
+        assert_eq!(vec![res.book.book_id], found.books.iter().map(|b| b.book_id.to_string()).collect::<Vec<String>>());
+        assert_eq!(None, found.next_page);
+    }
+
+    #[tokio::test]
+    async fn test_should_sort_books_by_author() {
+        let store = test_store(module_path!(), "test_should_sort_books_by_author");
+        let add_cmd = build_add_cmd(store).await;
+        let find_cmd = build_find_cmd(store).await;
+        for title in ["b book", "c book", "a book"] {
+            let mut req = AddBookCommandRequest::new("isbn", title);
+            req.author_id = "sorted_author".to_string();
+            let _ = add_cmd.execute(req).await.expect("should add book");
+        }
+
+        let mut req = FindBooksByAuthorCommandRequest::new("sorted_author");
+        req.sort = Some("-title".to_string());
+        let found = find_cmd.execute(req).await.expect("should find books");
+        assert_eq!(vec!["c book", "b book", "a book"], found.books.iter().map(|b| b.title.as_str()).collect::<Vec<&str>>());
+        let mut req = FindBooksByAuthorCommandRequest::new("sorted_author");
+        req.sort = Some("isbn".to_string());
+        assert!(find_cmd.execute(req).await.is_err());
+    }
+}
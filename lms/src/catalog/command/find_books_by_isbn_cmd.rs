@@ -0,0 +1,111 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::books::dto::BookDto;
+use crate::catalog::domain::CatalogQueryService;
+use crate::core::command::{Command, CommandError};
+#[cfg(feature = "examples")]
+use crate::core::command::examples::{CommandExamples, EXAMPLE_BOOK_ID, EXAMPLE_ISBN};
+use crate::core::repository::ReadConsistency;
+
+pub(crate) struct FindBooksByIsbnCommand {
+    catalog_service: Box<dyn CatalogQueryService>,
+}
+
+impl FindBooksByIsbnCommand {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogQueryService>) -> Self {
+        Self {
+            catalog_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindBooksByIsbnCommandRequest {
+    #[serde(default)]
+    pub(crate) isbn: String,
+    // id of a book that was just added, the query waits for it instead of returning stale results
+    #[serde(default)]
+    pub(crate) expected_book_id: Option<String>,
+}
+
+impl FindBooksByIsbnCommandRequest {
+    pub fn new(isbn: &str) -> Self {
+        Self {
+            isbn: isbn.to_string(),
+            expected_book_id: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindBooksByIsbnCommandResponse {
+    pub books: Vec<BookDto>,
+}
+
+impl FindBooksByIsbnCommandResponse {
+    pub fn new(books: Vec<BookDto>) -> Self {
+        Self {
+            books,
+        }
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for FindBooksByIsbnCommandRequest {
+    fn examples() -> Vec<Self> {
+        let mut expecting = FindBooksByIsbnCommandRequest::new(EXAMPLE_ISBN);
+        expecting.expected_book_id = Some(EXAMPLE_BOOK_ID.to_string());
+        vec![FindBooksByIsbnCommandRequest::new(EXAMPLE_ISBN), expecting]
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for FindBooksByIsbnCommandResponse {
+    fn examples() -> Vec<Self> {
+        vec![FindBooksByIsbnCommandResponse::new(vec![BookDto::example()])]
+    }
+}
+
+#[async_trait]
+impl Command<FindBooksByIsbnCommandRequest, FindBooksByIsbnCommandResponse> for FindBooksByIsbnCommand {
+    async fn execute(&self, req: FindBooksByIsbnCommandRequest) -> Result<FindBooksByIsbnCommandResponse, CommandError> {
+        let consistency = ReadConsistency::expecting(req.expected_book_id.as_deref());
+        self.catalog_service.find_book_by_isbn(req.isbn.as_str(), &consistency)
+            .await.map_err(CommandError::from).map(FindBooksByIsbnCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
+    use crate::catalog::command::find_books_by_isbn_cmd::{FindBooksByIsbnCommand, FindBooksByIsbnCommandRequest};
+    use crate::catalog::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_find_cmd(store: RepositoryStore) -> FindBooksByIsbnCommand {
+        let svc = factory::create_catalog_query_service(&Configuration::new("test"), store).await;
+        FindBooksByIsbnCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_find_books_by_isbn() {
+        let store = test_store(module_path!(), "test_should_run_find_books_by_isbn");
+        let add_cmd = build_add_cmd(store).await;
+        let find_cmd = build_find_cmd(store).await;
+
+        let res = add_cmd.execute(AddBookCommandRequest::new("isbn_ryw", "test book")).await.expect("should add book");
+        let mut find_req = FindBooksByIsbnCommandRequest::new("isbn_ryw");
+        find_req.expected_book_id = Some(res.book.book_id.to_string());
+        let found = find_cmd.execute(find_req).await.expect("should find books");
+        assert_eq!(vec![res.book.book_id], found.books.iter().map(|b| b.book_id.to_string()).collect::<Vec<String>>());
+    }
+}
@@ -0,0 +1,161 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::books::dto::{BookDto, BookFilter, BookSort};
+use crate::catalog::domain::CatalogQueryService;
+use crate::core::command::{Command, CommandError};
+#[cfg(feature = "examples")]
+use crate::core::command::examples::{CommandExamples};
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+
+pub(crate) struct FindBooksByTagCommand {
+    catalog_service: Box<dyn CatalogQueryService>,
+}
+
+impl FindBooksByTagCommand {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogQueryService>) -> Self {
+        Self {
+            catalog_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindBooksByTagCommandRequest {
+    pub(crate) tag: String,
+    pub(crate) page: Option<String>,
+    pub(crate) page_size: Option<usize>,
+    // title, published_at or created_at with a leading - for descending order
+    pub(crate) sort: Option<String>,
+    // ISO 639-1 code such as en
+    pub(crate) language: Option<String>,
+    // Physical, EBook, Audiobook or Serial
+    pub(crate) book_format: Option<String>,
+}
+
+impl FindBooksByTagCommandRequest {
+    pub fn new(tag: &str) -> Self {
+        Self {
+            tag: tag.to_string(),
+            page: None,
+            page_size: None,
+            sort: None,
+            language: None,
+            book_format: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindBooksByTagCommandResponse {
+    pub books: Vec<BookDto>,
+    pub next_page: Option<String>,
+}
+
+impl FindBooksByTagCommandResponse {
+    pub fn new(books: Vec<BookDto>, next_page: Option<String>) -> Self {
+        Self {
+            books,
+            next_page,
+        }
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for FindBooksByTagCommandRequest {
+    fn examples() -> Vec<Self> {
+        let mut req = FindBooksByTagCommandRequest::new("science-fiction");
+        req.page_size = Some(20);
+        req.sort = Some("title".to_string());
+        req.book_format = Some("Physical".to_string());
+        vec![req]
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for FindBooksByTagCommandResponse {
+    fn examples() -> Vec<Self> {
+        vec![FindBooksByTagCommandResponse::new(vec![BookDto::example()], None)]
+    }
+}
+
+#[async_trait]
+impl Command<FindBooksByTagCommandRequest, FindBooksByTagCommandResponse> for FindBooksByTagCommand {
+    async fn execute(&self, req: FindBooksByTagCommandRequest) -> Result<FindBooksByTagCommandResponse, CommandError> {
+        let sort = BookSort::from_param(req.sort.as_deref())?;
+        let filter = BookFilter::from_params(req.language.as_deref(), req.book_format.as_deref())?;
+        let res = self.catalog_service.find_books_by_tag(req.tag.as_str(), &filter, req.page.as_deref(),
+                                                         req.page_size.unwrap_or(DEFAULT_PAGE_SIZE))
+            .await.map_err(CommandError::from)?;
+        let mut books = res.records;
+        if let Some(sort) = sort {
+            sort.sort(&mut books);
+        }
+        Ok(FindBooksByTagCommandResponse::new(books, res.next_page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
+    use crate::catalog::command::find_books_by_tag_cmd::{FindBooksByTagCommand, FindBooksByTagCommandRequest};
+    use crate::catalog::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_find_cmd(store: RepositoryStore) -> FindBooksByTagCommand {
+        let svc = factory::create_catalog_query_service(&Configuration::new("test"), store).await;
+        FindBooksByTagCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_find_books_by_tag() {
+        let store = test_store(module_path!(), "test_should_run_find_books_by_tag");
+        let add_cmd = build_add_cmd(store).await;
+        let find_cmd = build_find_cmd(store).await;
+
+        let mut req = AddBookCommandRequest::new("isbn", "test book");
+        req.tags = vec!["cooking".to_string()];
+        let res = add_cmd.execute(req).await.expect("should add book");
+        let mut find_req = FindBooksByTagCommandRequest::new("cooking");
+        find_req.page_size = Some(500);
+        let found = find_cmd.execute(find_req).await.expect("should find books");
+        assert!(found.books.iter().any(|b| b.book_id == res.book.book_id));
+    }
+
+    #[tokio::test]
+    async fn test_should_filter_books_by_language_and_format() {
+        let store = test_store(module_path!(), "test_should_filter_books_by_language_and_format");
+        let add_cmd = build_add_cmd(store).await;
+        let find_cmd = build_find_cmd(store).await;
+
+        let mut req = AddBookCommandRequest::new("isbn", "livre");
+        req.tags = vec!["roman".to_string()];
+        req.language = Some("FR".to_string());
+        let res = add_cmd.execute(req).await.expect("should add book");
+        assert_eq!("fr", res.book.language.as_str());
+
+        let mut find_req = FindBooksByTagCommandRequest::new("roman");
+        find_req.page_size = Some(500);
+        find_req.language = Some("fr".to_string());
+        find_req.book_format = Some("physical".to_string());
+        let found = find_cmd.execute(find_req).await.expect("should find books");
+        assert!(found.books.iter().any(|b| b.book_id == res.book.book_id));
+        let mut find_req = FindBooksByTagCommandRequest::new("roman");
+        find_req.page_size = Some(500);
+        find_req.language = Some("de".to_string());
+        let found = find_cmd.execute(find_req).await.expect("should find books");
+        assert!(found.books.iter().all(|b| b.book_id != res.book.book_id));
+        let mut find_req = FindBooksByTagCommandRequest::new("roman");
+        find_req.language = Some("french".to_string());
+        assert!(find_cmd.execute(find_req).await.is_err());
+    }
+}
@@ -0,0 +1,110 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::books::dto::DuplicateBooksDto;
+use crate::catalog::domain::CatalogQueryService;
+use crate::core::command::{Command, CommandError};
+#[cfg(feature = "examples")]
+use crate::core::command::examples::{CommandExamples, EXAMPLE_ISBN};
+#[cfg(feature = "examples")]
+use crate::books::dto::BookDto;
+#[cfg(feature = "examples")]
+use crate::core::ids::BookId;
+
+const SCAN_PAGE_SIZE: usize = 500;
+
+pub(crate) struct FindDuplicateBooksCommand {
+    catalog_service: Box<dyn CatalogQueryService>,
+}
+
+impl FindDuplicateBooksCommand {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogQueryService>) -> Self {
+        Self {
+            catalog_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindDuplicateBooksCommandRequest {}
+
+impl FindDuplicateBooksCommandRequest {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindDuplicateBooksCommandResponse {
+    pub duplicates: Vec<DuplicateBooksDto>,
+}
+
+impl FindDuplicateBooksCommandResponse {
+    pub fn new(duplicates: Vec<DuplicateBooksDto>) -> Self {
+        Self {
+            duplicates,
+        }
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for FindDuplicateBooksCommandRequest {
+    fn examples() -> Vec<Self> {
+        vec![FindDuplicateBooksCommandRequest::new()]
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for FindDuplicateBooksCommandResponse {
+    fn examples() -> Vec<Self> {
+        let mut duplicate = BookDto::example();
+        duplicate.book_id = BookId::new("5b7e2c1d-8f4a-4e6b-a3c9-1d2e3f4a5b6c");
+        vec![FindDuplicateBooksCommandResponse::new(vec![DuplicateBooksDto::new(EXAMPLE_ISBN, vec![BookDto::example(), duplicate])])]
+    }
+}
+
+#[async_trait]
+impl Command<FindDuplicateBooksCommandRequest, FindDuplicateBooksCommandResponse> for FindDuplicateBooksCommand {
+    async fn execute(&self, _req: FindDuplicateBooksCommandRequest) -> Result<FindDuplicateBooksCommandResponse, CommandError> {
+        self.catalog_service.find_duplicate_books(SCAN_PAGE_SIZE)
+            .await.map_err(CommandError::from).map(FindDuplicateBooksCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
+    use crate::catalog::command::find_duplicate_books_cmd::{FindDuplicateBooksCommand, FindDuplicateBooksCommandRequest};
+    use crate::catalog::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_find_cmd(store: RepositoryStore) -> FindDuplicateBooksCommand {
+        let svc = factory::create_catalog_query_service(&Configuration::new("test"), store).await;
+        FindDuplicateBooksCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_find_duplicate_books() {
+        let store = test_store(module_path!(), "test_should_run_find_duplicate_books");
+        let add_cmd = build_add_cmd(store).await;
+        let find_cmd = build_find_cmd(store).await;
+        let mut ids = vec![];
+        for isbn in ["0-19-852663-6", "9780198526636"] {
+            let res = add_cmd.execute(AddBookCommandRequest::new(isbn, "duplicate title")).await.expect("should add book");
+            ids.push(res.book.book_id);
+        }
+
+        let res = find_cmd.execute(FindDuplicateBooksCommandRequest::new()).await.expect("should find duplicates");
+        let group = res.duplicates.iter().find(|group| group.isbn == "9780198526636").expect("should group isbn");
+        assert!(ids.iter().all(|id| group.books.iter().any(|b| &b.book_id == id)));
+        assert!(group.books.windows(2).all(|pair| pair[0].created_at <= pair[1].created_at));
+    }
+}
@@ -0,0 +1,120 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::books::dto::RelatedBookDto;
+use crate::catalog::domain::CatalogQueryService;
+use crate::core::command::{Command, CommandError};
+#[cfg(feature = "examples")]
+use crate::core::command::examples::{CommandExamples, EXAMPLE_BOOK_ID};
+#[cfg(feature = "examples")]
+use crate::books::dto::BookDto;
+use crate::core::ids::BookId;
+
+const DEFAULT_LIMIT: usize = 10;
+
+pub(crate) struct FindRelatedBooksCommand {
+    catalog_service: Box<dyn CatalogQueryService>,
+}
+
+impl FindRelatedBooksCommand {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogQueryService>) -> Self {
+        Self {
+            catalog_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindRelatedBooksCommandRequest {
+    #[serde(default)]
+    pub(crate) book_id: String,
+    pub(crate) limit: Option<usize>,
+}
+
+impl FindRelatedBooksCommandRequest {
+    pub fn new(book_id: &str) -> Self {
+        Self {
+            book_id: book_id.to_string(),
+            limit: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindRelatedBooksCommandResponse {
+    pub related: Vec<RelatedBookDto>,
+}
+
+impl FindRelatedBooksCommandResponse {
+    pub fn new(related: Vec<RelatedBookDto>) -> Self {
+        Self {
+            related,
+        }
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for FindRelatedBooksCommandRequest {
+    fn examples() -> Vec<Self> {
+        let mut req = FindRelatedBooksCommandRequest::new(EXAMPLE_BOOK_ID);
+        req.limit = Some(10);
+        vec![req]
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for FindRelatedBooksCommandResponse {
+    fn examples() -> Vec<Self> {
+        let mut book = BookDto::example();
+        book.book_id = BookId::new("7c9d1e2f-3a4b-4c5d-8e6f-9a0b1c2d3e4f");
+        book.isbn = "9780593099322".to_string();
+        book.title = "Dune Messiah".to_string();
+        let mut related = RelatedBookDto::new(book);
+        related.score = 5;
+        related.same_author = true;
+        related.shared_tags = vec!["science-fiction".to_string()];
+        related.co_checkout_count = 2;
+        vec![FindRelatedBooksCommandResponse::new(vec![related])]
+    }
+}
+
+#[async_trait]
+impl Command<FindRelatedBooksCommandRequest, FindRelatedBooksCommandResponse> for FindRelatedBooksCommand {
+    async fn execute(&self, req: FindRelatedBooksCommandRequest) -> Result<FindRelatedBooksCommandResponse, CommandError> {
+        self.catalog_service.find_related_books(&BookId::new(req.book_id.as_str()), req.limit.unwrap_or(DEFAULT_LIMIT))
+            .await.map_err(CommandError::from).map(FindRelatedBooksCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
+    use crate::catalog::command::find_related_books_cmd::{FindRelatedBooksCommand, FindRelatedBooksCommandRequest};
+    use crate::catalog::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_related_cmd(store: RepositoryStore) -> FindRelatedBooksCommand {
+        let svc = factory::create_catalog_query_service(&Configuration::new("test"), store).await;
+        FindRelatedBooksCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_find_related_books() {
+        let store = test_store(module_path!(), "test_should_run_find_related_books");
+        let add_cmd = build_add_cmd(store).await;
+        let related_cmd = build_related_cmd(store).await;
+
+        let res = add_cmd.execute(AddBookCommandRequest::new("isbn", "test book")).await.expect("should add book");
+        let related = related_cmd.execute(FindRelatedBooksCommandRequest::new(res.book.book_id.as_str()))
+            .await.expect("should find related books");
+        assert!(related.related.iter().all(|r| r.book.book_id != res.book.book_id));
+    }
+}
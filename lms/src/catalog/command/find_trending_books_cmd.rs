@@ -0,0 +1,137 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::books::dto::TrendingBookDto;
+use crate::catalog::domain::CatalogQueryService;
+use crate::core::command::{Command, CommandError};
+#[cfg(feature = "examples")]
+use crate::core::command::examples::CommandExamples;
+#[cfg(feature = "examples")]
+use crate::books::dto::BookDto;
+
+const DEFAULT_WINDOW: &str = "7d";
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 100;
+
+pub(crate) struct FindTrendingBooksCommand {
+    catalog_service: Box<dyn CatalogQueryService>,
+}
+
+impl FindTrendingBooksCommand {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogQueryService>) -> Self {
+        Self {
+            catalog_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindTrendingBooksCommandRequest {
+    // 1d, 7d or 30d
+    pub(crate) window: Option<String>,
+    pub(crate) limit: Option<usize>,
+}
+
+impl FindTrendingBooksCommandRequest {
+    pub fn new(window: &str) -> Self {
+        Self {
+            window: Some(window.to_string()),
+            limit: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindTrendingBooksCommandResponse {
+    pub window: String,
+    pub trending: Vec<TrendingBookDto>,
+}
+
+impl FindTrendingBooksCommandResponse {
+    pub fn new(window: &str, trending: Vec<TrendingBookDto>) -> Self {
+        Self {
+            window: window.to_string(),
+            trending,
+        }
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for FindTrendingBooksCommandRequest {
+    fn examples() -> Vec<Self> {
+        let mut req = FindTrendingBooksCommandRequest::new("7d");
+        req.limit = Some(10);
+        vec![req]
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for FindTrendingBooksCommandResponse {
+    fn examples() -> Vec<Self> {
+        vec![FindTrendingBooksCommandResponse::new("7d", vec![TrendingBookDto::new(BookDto::example(), 3.5)])]
+    }
+}
+
+#[async_trait]
+impl Command<FindTrendingBooksCommandRequest, FindTrendingBooksCommandResponse> for FindTrendingBooksCommand {
+    async fn execute(&self, req: FindTrendingBooksCommandRequest) -> Result<FindTrendingBooksCommandResponse, CommandError> {
+        let window = req.window.unwrap_or_else(|| DEFAULT_WINDOW.to_string());
+        let limit = req.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+        self.catalog_service.find_trending_books(window.as_str(), limit)
+            .await.map_err(CommandError::from).map(|trending| FindTrendingBooksCommandResponse::new(window.as_str(), trending))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
+    use crate::catalog::command::find_trending_books_cmd::{FindTrendingBooksCommand, FindTrendingBooksCommandRequest};
+    use crate::catalog::factory;
+    use crate::checkout::domain::model::CheckoutEntity;
+    use crate::checkout::dto::CheckoutDto;
+    use crate::core::command::{Command, CommandError};
+    use crate::core::domain::Configuration;
+    use crate::core::events::DomainEvent;
+    use crate::core::repository::RepositoryStore;
+    use crate::projector::domain::popularity::PopularityProjector;
+    use crate::projector::domain::Projector;
+    use crate::projector::factory::create_popularity_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_trending_cmd(store: RepositoryStore) -> FindTrendingBooksCommand {
+        let svc = factory::create_catalog_query_service(&Configuration::new("test"), store).await;
+        FindTrendingBooksCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_find_trending_books() {
+        let store = test_store(module_path!(), "test_should_run_find_trending_books");
+        let res = build_add_cmd(store).await.execute(AddBookCommandRequest::new("isbn", "trending book")).await.expect("should add book");
+        let projector = PopularityProjector::new(create_popularity_repository(store).await);
+        // enough checkouts to rank above books scored by other tests
+        for _ in 0..50 {
+            let checkout = CheckoutDto::from(&CheckoutEntity::new(res.book.book_id.as_str(), "trending_patron"));
+            let event = DomainEvent::added("book_checkout", "checkout", checkout.checkout_id.as_str(),
+                                           &HashMap::new(), &checkout).expect("should build event");
+            projector.project(&event).await.expect("should project");
+        }
+
+        let trending_cmd = build_trending_cmd(store).await;
+        let mut req = FindTrendingBooksCommandRequest::new("1d");
+        req.limit = Some(1);
+        let trending = trending_cmd.execute(req).await.expect("should find trending books");
+        assert_eq!("1d", trending.window.as_str());
+        assert_eq!(1, trending.trending.len());
+        assert_eq!(res.book.book_id, trending.trending[0].book.book_id);
+        assert!(trending.trending[0].score > 49.0);
+
+        let res = trending_cmd.execute(FindTrendingBooksCommandRequest::new("2w")).await;
+        assert!(matches!(res, Err(CommandError::Validation { .. })));
+    }
+}
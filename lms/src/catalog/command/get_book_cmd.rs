@@ -1,22 +1,30 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use crate::books::dto::BookDto;
-use crate::catalog::domain::CatalogService;
+use crate::catalog::domain::CatalogQueryService;
 use crate::core::command::{Command, CommandError};
+#[cfg(feature = "examples")]
+use crate::core::command::examples::{CommandExamples, EXAMPLE_BOOK_ID};
+use crate::core::ids::BookId;
+use crate::core::library::BookFormat;
+use crate::serials::domain::SerialQueryService;
+use crate::serials::dto::HoldingsDto;
 
 pub(crate) struct GetBookCommand {
-    catalog_service: Box<dyn CatalogService>,
+    catalog_service: Box<dyn CatalogQueryService>,
+    serial_service: Box<dyn SerialQueryService>,
 }
 
 impl GetBookCommand {
-    pub(crate) fn new(catalog_service: Box<dyn CatalogService>) -> Self {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogQueryService>, serial_service: Box<dyn SerialQueryService>) -> Self {
         Self {
             catalog_service,
+            serial_service,
         }
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct GetBookCommandRequest {
     pub(crate) book_id: String,
 }
@@ -30,60 +38,103 @@ impl GetBookCommandRequest {
 }
 
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct GetBookCommandResponse {
     book: BookDto,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    holdings: Option<HoldingsDto>,
 }
 
 impl GetBookCommandResponse {
-    pub fn new(book: BookDto) -> Self {
+    pub fn new(book: BookDto, holdings: Option<HoldingsDto>) -> Self {
         Self {
             book,
+            holdings,
         }
     }
 }
 
+#[cfg(feature = "examples")]
+impl CommandExamples for GetBookCommandRequest {
+    fn examples() -> Vec<Self> {
+        vec![GetBookCommandRequest::new(EXAMPLE_BOOK_ID.to_string())]
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for GetBookCommandResponse {
+    fn examples() -> Vec<Self> {
+        vec![GetBookCommandResponse::new(BookDto::example(), None)]
+    }
+}
+
 #[async_trait]
 impl Command<GetBookCommandRequest, GetBookCommandResponse> for GetBookCommand {
     async fn execute(&self, req: GetBookCommandRequest) -> Result<GetBookCommandResponse, CommandError> {
-        self.catalog_service.find_book_by_id(req.book_id.as_str())
-            .await.map_err(CommandError::from).map(GetBookCommandResponse::new)
+        let book = self.catalog_service.find_book_by_id(&BookId::new(req.book_id.as_str())).await.map_err(CommandError::from)?;
+        // serial titles show their issue holdings along with the title record
+        let holdings = if book.book_format == BookFormat::Serial {
+            Some(self.serial_service.holdings(book.book_id.as_str()).await.map_err(CommandError::from)?)
+        } else {
+            None
+        };
+        Ok(GetBookCommandResponse::new(book, holdings))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use async_once::AsyncOnce;
-    use lazy_static::lazy_static;
     use crate::books::dto::BookDto;
     use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
     use crate::catalog::command::get_book_cmd::{GetBookCommand, GetBookCommandRequest};
     use crate::catalog::factory;
     use crate::core::command::Command;
-    use crate::core::library::BookStatus;
+    use crate::core::library::{BookStatus, SerialFrequency};
     use crate::core::domain::Configuration;
     use crate::core::repository::RepositoryStore;
+    use crate::serials::domain::SerialService;
+    use crate::serials::dto::SerialDto;
+    use crate::serials::factory::{create_serial_query_service, create_serial_service};
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_get_cmd(store: RepositoryStore) -> GetBookCommand {
+        let svc = factory::create_catalog_query_service(&Configuration::new("test"), store).await;
+        let serial_svc = create_serial_query_service(&Configuration::new("test"), store).await;
+        GetBookCommand::new(svc, serial_svc)
+    }
 
-    lazy_static! {
-        static ref ADD_CMD : AsyncOnce<AddBookCommand> = AsyncOnce::new(async {
-                let svc = factory::create_catalog_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                AddBookCommand::new(svc)
-            });
-        static ref GET_CMD : AsyncOnce<GetBookCommand> = AsyncOnce::new(async {
-                let svc = factory::create_catalog_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                GetBookCommand::new(svc)
-            });
+    async fn build_serial_svc(store: RepositoryStore) -> Box<dyn SerialService> {
+        create_serial_service(&Configuration::new("test"), store).await
     }
 
     #[tokio::test]
     async fn test_should_run_get_book() {
-        let add_cmd = ADD_CMD.get().await.clone();
-        let get_cmd = GET_CMD.get().await.clone();
+        let store = test_store(module_path!(), "test_should_run_get_book");
+        let add_cmd = build_add_cmd(store).await;
+        let get_cmd = build_get_cmd(store).await;
 
         let book = BookDto::new("isbn", "test book", BookStatus::Available);
         let res = add_cmd.execute(AddBookCommandRequest::new(book.isbn.as_str(), book.title.as_str())).await.expect("should add book");
         let loaded = get_cmd.execute(GetBookCommandRequest::new(res.book.book_id.to_string())).await.expect("should get book");
         assert_eq!(book.isbn, loaded.book.isbn);
         assert_eq!(book.title, loaded.book.title);
+        assert!(loaded.holdings.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_should_run_get_serial_book_with_holdings() {
+        let store = test_store(module_path!(), "test_should_run_get_serial_book_with_holdings");
+        let get_cmd = build_get_cmd(store).await;
+
+        let serial = build_serial_svc(store).await.add_serial(&SerialDto::new("9999-0000", "catalog serial", SerialFrequency::Quarterly))
+            .await.expect("should add serial");
+        let loaded = get_cmd.execute(GetBookCommandRequest::new(serial.serial_id.to_string())).await.expect("should get book");
+        let holdings = loaded.holdings.expect("should have holdings");
+        assert_eq!(serial.scheduled_issues as usize, holdings.expected);
     }
 }
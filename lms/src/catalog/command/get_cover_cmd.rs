@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::catalog::domain::CatalogQueryService;
+use crate::core::command::{Command, CommandError};
+#[cfg(feature = "examples")]
+use crate::core::command::examples::{CommandExamples, EXAMPLE_BOOK_ID};
+use crate::core::ids::BookId;
+
+pub(crate) struct GetCoverCommand {
+    catalog_service: Box<dyn CatalogQueryService>,
+}
+
+impl GetCoverCommand {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogQueryService>) -> Self {
+        Self {
+            catalog_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetCoverCommandRequest {
+    pub(crate) book_id: String,
+}
+
+impl GetCoverCommandRequest {
+    pub fn new(book_id: &str) -> Self {
+        Self {
+            book_id: book_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetCoverCommandResponse {
+    pub url: String,
+}
+
+impl GetCoverCommandResponse {
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+        }
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for GetCoverCommandRequest {
+    fn examples() -> Vec<Self> {
+        vec![GetCoverCommandRequest::new(EXAMPLE_BOOK_ID)]
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for GetCoverCommandResponse {
+    fn examples() -> Vec<Self> {
+        vec![GetCoverCommandResponse::new(format!("https://covers.s3.amazonaws.com/covers/{}.jpg", EXAMPLE_BOOK_ID))]
+    }
+}
+
+#[async_trait]
+impl Command<GetCoverCommandRequest, GetCoverCommandResponse> for GetCoverCommand {
+    async fn execute(&self, req: GetCoverCommandRequest) -> Result<GetCoverCommandResponse, CommandError> {
+        self.catalog_service.find_cover_url(&BookId::new(req.book_id.as_str()))
+            .await.map_err(CommandError::from).map(GetCoverCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
+    use crate::catalog::command::get_cover_cmd::{GetCoverCommand, GetCoverCommandRequest};
+    use crate::catalog::command::upload_cover_cmd::{UploadCoverCommand, UploadCoverCommandRequest};
+    use crate::catalog::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_run_get_cover() {
+        let store = test_store(module_path!(), "test_should_run_get_cover");
+        let config = Configuration::new("test");
+        let add_cmd = AddBookCommand::new(factory::create_catalog_service(&config, store).await);
+        let upload_cmd = UploadCoverCommand::new(factory::create_catalog_service(&config, store).await);
+        let get_cmd = GetCoverCommand::new(factory::create_catalog_query_service(&config, store).await);
+
+        let res = add_cmd.execute(AddBookCommandRequest::new("isbn", "Jacket")).await.expect("should add book");
+        // books without a cover have nothing to redirect to
+        assert!(get_cmd.execute(GetCoverCommandRequest::new(res.book.book_id.as_str())).await.is_err());
+
+        let jpeg = vec![0xFF, 0xD8, 0xFF, 0xE0, 0];
+        let uploaded = upload_cmd.execute(UploadCoverCommandRequest::new(
+            res.book.book_id.as_str(), "image/jpeg", jpeg)).await.expect("should upload cover");
+        let cover = get_cmd.execute(GetCoverCommandRequest::new(res.book.book_id.as_str())).await.expect("should get cover");
+        assert!(cover.url.ends_with(uploaded.book.cover_key.as_str()));
+    }
+}
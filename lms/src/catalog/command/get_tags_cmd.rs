@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::books::dto::TagCountDto;
+use crate::catalog::domain::CatalogQueryService;
+use crate::core::command::{Command, CommandError};
+#[cfg(feature = "examples")]
+use crate::core::command::examples::{CommandExamples};
+
+pub(crate) struct GetTagsCommand {
+    catalog_service: Box<dyn CatalogQueryService>,
+}
+
+impl GetTagsCommand {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogQueryService>) -> Self {
+        Self {
+            catalog_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetTagsCommandRequest {}
+
+impl GetTagsCommandRequest {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetTagsCommandResponse {
+    pub tags: Vec<TagCountDto>,
+}
+
+impl GetTagsCommandResponse {
+    pub fn new(tags: Vec<TagCountDto>) -> Self {
+        Self {
+            tags,
+        }
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for GetTagsCommandRequest {
+    fn examples() -> Vec<Self> {
+        vec![GetTagsCommandRequest::new()]
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for GetTagsCommandResponse {
+    fn examples() -> Vec<Self> {
+        vec![GetTagsCommandResponse::new(vec![TagCountDto::new("science-fiction", 12), TagCountDto::new("classics", 4)])]
+    }
+}
+
+#[async_trait]
+impl Command<GetTagsCommandRequest, GetTagsCommandResponse> for GetTagsCommand {
+    async fn execute(&self, _req: GetTagsCommandRequest) -> Result<GetTagsCommandResponse, CommandError> {
+        self.catalog_service.tag_counts()
+            .await.map_err(CommandError::from).map(GetTagsCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
+    use crate::catalog::command::get_tags_cmd::{GetTagsCommand, GetTagsCommandRequest};
+    use crate::catalog::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_tags_cmd(store: RepositoryStore) -> GetTagsCommand {
+        let svc = factory::create_catalog_query_service(&Configuration::new("test"), store).await;
+        GetTagsCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_get_tags() {
+        let store = test_store(module_path!(), "test_should_run_get_tags");
+        let add_cmd = build_add_cmd(store).await;
+        let tags_cmd = build_tags_cmd(store).await;
+
+        let mut req = AddBookCommandRequest::new("isbn", "test book");
+        req.tags = vec!["gardening".to_string()];
+        let _ = add_cmd.execute(req).await.expect("should add book");
+        let res = tags_cmd.execute(GetTagsCommandRequest::new()).await.expect("should get tags");
+        assert!(res.tags.iter().any(|t| t.tag == "gardening" && t.usage_count > 0));
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use crate::catalog::domain::CatalogQueryService;
+use crate::catalog::oai::{harvest, OaiRepository};
+use crate::core::command::{Command, CommandError};
+
+pub(crate) struct HarvestOaiCommand {
+    catalog_service: Box<dyn CatalogQueryService>,
+    repository: OaiRepository,
+}
+
+impl HarvestOaiCommand {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogQueryService>, repository: OaiRepository) -> Self {
+        Self {
+            catalog_service,
+            repository,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct HarvestOaiCommandRequest {
+    // arguments of the OAI-PMH request such as verb and metadataPrefix, they are validated against the verb
+    pub(crate) arguments: HashMap<String, String>,
+}
+
+impl HarvestOaiCommandRequest {
+    pub fn new(arguments: HashMap<String, String>) -> Self {
+        Self {
+            arguments,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct HarvestOaiCommandResponse {
+    pub xml: String,
+}
+
+impl HarvestOaiCommandResponse {
+    pub fn new(xml: String) -> Self {
+        Self {
+            xml,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<HarvestOaiCommandRequest, HarvestOaiCommandResponse> for HarvestOaiCommand {
+    async fn execute(&self, req: HarvestOaiCommandRequest) -> Result<HarvestOaiCommandResponse, CommandError> {
+        harvest(self.catalog_service.as_ref(), &self.repository, &req.arguments, Utc::now().naive_utc())
+            .await.map_err(CommandError::from).map(HarvestOaiCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::books::dto::BookDto;
+    use crate::catalog::command::harvest_oai_cmd::{HarvestOaiCommand, HarvestOaiCommandRequest};
+    use crate::catalog::factory;
+    use crate::catalog::oai::OaiRepository;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::utils::testing::test_store;
+
+    fn arguments(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+    }
+
+    #[tokio::test]
+    async fn test_should_run_harvest_oai() {
+        let store = test_store(module_path!(), "test_should_run_harvest_oai");
+        let config = Configuration::new("test");
+        let svc = factory::create_catalog_service(&config, store).await;
+        let book = BookDto::builder().isbn("9780441172719").title("Dune").author_id("frank-herbert")
+            .build().expect("should build book");
+        let book = svc.add_book(&book).await.expect("should add book");
+
+        let svc = factory::create_catalog_query_service(&config, store).await;
+        let sut_cmd = HarvestOaiCommand::new(svc, OaiRepository::new(&config));
+        let identifier = format!("oai:lms:{}", book.book_id);
+        let res = sut_cmd.execute(HarvestOaiCommandRequest::new(arguments(&[
+            ("verb", "GetRecord"), ("identifier", identifier.as_str()), ("metadataPrefix", "oai_dc")])))
+            .await.expect("should get record");
+        assert!(res.xml.contains("<dc:title>Dune</dc:title>"));
+        assert!(res.xml.contains(format!("<request identifier=\"{}\" metadataPrefix=\"oai_dc\" verb=\"GetRecord\">", identifier).as_str()));
+
+        let res = sut_cmd.execute(HarvestOaiCommandRequest::new(arguments(&[
+            ("verb", "ListRecords"), ("metadataPrefix", "oai_dc")]))).await.expect("should list records");
+        assert!(res.xml.contains("<ListRecords><record>"));
+
+        let res = sut_cmd.execute(HarvestOaiCommandRequest::new(arguments(&[
+            ("verb", "ListRecords"), ("metadataPrefix", "marc21")]))).await.expect("should answer error");
+        assert!(res.xml.contains("<error code=\"cannotDisseminateFormat\">"));
+        let res = sut_cmd.execute(HarvestOaiCommandRequest::new(arguments(&[
+            ("verb", "ListRecords"), ("resumptionToken", "bad"), ("metadataPrefix", "oai_dc")]))).await.expect("should answer error");
+        assert!(res.xml.contains("<error code=\"badArgument\">"));
+        let res = sut_cmd.execute(HarvestOaiCommandRequest::new(arguments(&[
+            ("verb", "ListRecords"), ("resumptionToken", "bad")]))).await.expect("should answer error");
+        assert!(res.xml.contains("<error code=\"badResumptionToken\">"));
+        let res = sut_cmd.execute(HarvestOaiCommandRequest::new(arguments(&[
+            ("verb", "GetRecord"), ("identifier", "oai:lms:missing"), ("metadataPrefix", "oai_dc")]))).await.expect("should answer error");
+        assert!(res.xml.contains("<error code=\"idDoesNotExist\">"));
+        let res = sut_cmd.execute(HarvestOaiCommandRequest::new(arguments(&[("verb", "Harvest")]))).await.expect("should answer error");
+        assert!(res.xml.contains("<request>http://"));
+        assert!(res.xml.contains("<error code=\"badVerb\">"));
+    }
+}
@@ -0,0 +1,110 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::books::dto::MarcImportDto;
+use crate::catalog::domain::CatalogService;
+use crate::core::command::{Command, CommandError};
+use crate::core::library::BatchStatus;
+
+pub(crate) struct ImportMarcCommand {
+    catalog_service: Box<dyn CatalogService>,
+}
+
+impl ImportMarcCommand {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogService>) -> Self {
+        Self {
+            catalog_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ImportMarcCommandRequest {
+    content: Vec<u8>,
+}
+
+impl ImportMarcCommandRequest {
+    pub fn new(content: &[u8]) -> Self {
+        Self {
+            content: content.to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ImportMarcCommandResponse {
+    #[serde(flatten)]
+    import: MarcImportDto,
+}
+
+impl ImportMarcCommandResponse {
+    pub fn new(import: MarcImportDto) -> Self {
+        Self {
+            import,
+        }
+    }
+
+    pub(crate) fn status(&self) -> BatchStatus {
+        self.import.result.status
+    }
+}
+
+#[async_trait]
+impl Command<ImportMarcCommandRequest, ImportMarcCommandResponse> for ImportMarcCommand {
+    async fn execute(&self, req: ImportMarcCommandRequest) -> Result<ImportMarcCommandResponse, CommandError> {
+        self.catalog_service.import_marc(&req.content)
+            .await.map_err(CommandError::from).map(ImportMarcCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::command::import_marc_cmd::{ImportMarcCommand, ImportMarcCommandRequest};
+    use crate::catalog::factory;
+    use crate::core::command::{Command, CommandError};
+    use crate::core::domain::Configuration;
+    use crate::core::ids::BookId;
+    use crate::core::library::BatchStatus;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_run_import_marc() {
+        let store = test_store(module_path!(), "test_should_run_import_marc");
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        let sut_cmd = ImportMarcCommand::new(svc);
+        let xml = r#"<collection xmlns="http://www.loc.gov/MARC21/slim">
+              <record>
+                <controlfield tag="001">marc_import_1</controlfield>
+                <datafield tag="020" ind1=" " ind2=" "><subfield code="a">9780441172719</subfield></datafield>
+                <datafield tag="082" ind1="0" ind2="4"><subfield code="a">813/.54</subfield></datafield>
+                <datafield tag="245" ind1="1" ind2="0"><subfield code="a">Dune /</subfield></datafield>
+                <datafield tag="650" ind1=" " ind2="0"><subfield code="a">Science fiction.</subfield></datafield>
+                <datafield tag="852" ind1=" " ind2=" "><subfield code="h">813.54 HER</subfield></datafield>
+              </record>
+              <record>
+                <controlfield tag="001">marc_import_2</controlfield>
+                <datafield tag="245" ind1="1" ind2="0"><subfield code="a">Untitled without isbn</subfield></datafield>
+                <datafield tag="852" ind1=" " ind2=" "><subfield code="h">FIC</subfield></datafield>
+              </record>
+            </collection>"#;
+        let res = sut_cmd.execute(ImportMarcCommandRequest::new(xml.as_bytes())).await.expect("should import records");
+        assert_eq!(BatchStatus::PartiallySucceeded, res.status());
+        assert_eq!(1, res.import.result.successes.len());
+        let imported = &res.import.result.successes[0];
+        assert_eq!(1, imported.record);
+        assert_eq!("marc_import_1", imported.control_number.as_str());
+        assert_eq!("Dune", imported.title.as_str());
+        assert_eq!(vec!["2"], res.import.result.failures.iter().map(|f| f.id.as_str()).collect::<Vec<&str>>());
+        assert_eq!(1, res.import.unmapped_fields.len());
+        assert_eq!("852", res.import.unmapped_fields[0].tag.as_str());
+        assert_eq!(2, res.import.unmapped_fields[0].records);
+
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        let book = svc.find_book_by_id(&BookId::new(imported.book_id.as_str())).await.expect("should find imported book");
+        assert_eq!("813.54", book.dewey_decimal_id.as_str());
+        assert_eq!(vec!["science fiction"], book.tags);
+        assert_eq!("813.54 DUN", book.call_number.as_str());
+
+        let res = sut_cmd.execute(ImportMarcCommandRequest::new(b"<collection>")).await;
+        assert!(matches!(res, Err(CommandError::Validation { .. })));
+    }
+}
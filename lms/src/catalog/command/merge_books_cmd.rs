@@ -0,0 +1,175 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::catalog::domain::CatalogService;
+use crate::catalog::domain::service::normalize_isbn;
+use crate::checkout::domain::CheckoutService;
+use crate::core::command::{Command, CommandError};
+#[cfg(feature = "examples")]
+use crate::core::command::examples::{CommandExamples, EXAMPLE_BOOK_ID, EXAMPLE_ISBN};
+use crate::core::ids::BookId;
+use crate::core::library::LibraryError;
+use crate::hold::domain::HoldService;
+
+const SCAN_PAGE_SIZE: usize = 500;
+
+// merges duplicate catalog records of an isbn into the oldest record, holds and checkouts of the duplicates are
+// moved to it before the duplicates are removed
+pub(crate) struct MergeBooksCommand {
+    catalog_service: Box<dyn CatalogService>,
+    hold_service: Box<dyn HoldService>,
+    checkout_service: Box<dyn CheckoutService>,
+}
+
+impl MergeBooksCommand {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogService>, hold_service: Box<dyn HoldService>,
+                      checkout_service: Box<dyn CheckoutService>) -> Self {
+        Self {
+            catalog_service,
+            hold_service,
+            checkout_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct MergeBooksCommandRequest {
+    pub(crate) isbn: String,
+    // reports what would be merged without changing anything
+    #[serde(default)]
+    pub(crate) dry_run: bool,
+}
+
+impl MergeBooksCommandRequest {
+    pub fn new(isbn: &str, dry_run: bool) -> Self {
+        Self {
+            isbn: isbn.to_string(),
+            dry_run,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct MergeBooksCommandResponse {
+    pub isbn: String,
+    pub book_id: String,
+    pub merged_book_ids: Vec<String>,
+    pub holds: usize,
+    pub checkouts: usize,
+    pub dry_run: bool,
+}
+
+impl MergeBooksCommandResponse {
+    pub fn new(isbn: &str, book_id: &str, merged_book_ids: Vec<String>, dry_run: bool) -> Self {
+        Self {
+            isbn: isbn.to_string(),
+            book_id: book_id.to_string(),
+            merged_book_ids,
+            holds: 0,
+            checkouts: 0,
+            dry_run,
+        }
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for MergeBooksCommandRequest {
+    fn examples() -> Vec<Self> {
+        vec![MergeBooksCommandRequest::new(EXAMPLE_ISBN, true)]
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for MergeBooksCommandResponse {
+    fn examples() -> Vec<Self> {
+        let mut res = MergeBooksCommandResponse::new(EXAMPLE_ISBN, EXAMPLE_BOOK_ID,
+                                                     vec!["5b7e2c1d-8f4a-4e6b-a3c9-1d2e3f4a5b6c".to_string()], true);
+        res.holds = 1;
+        res.checkouts = 2;
+        vec![res]
+    }
+}
+
+#[async_trait]
+impl Command<MergeBooksCommandRequest, MergeBooksCommandResponse> for MergeBooksCommand {
+    async fn execute(&self, req: MergeBooksCommandRequest) -> Result<MergeBooksCommandResponse, CommandError> {
+        let isbn = normalize_isbn(req.isbn.as_str());
+        let duplicates = self.catalog_service.find_duplicate_books(SCAN_PAGE_SIZE).await.map_err(CommandError::from)?;
+        let group = duplicates.into_iter().find(|group| group.isbn == isbn).ok_or_else(|| CommandError::from(
+            LibraryError::not_found(format!("isbn {} has no duplicate books", req.isbn).as_str())))?;
+        let kept = group.books[0].book_id.clone();
+        let merged = group.books[1..].iter().map(|b| b.book_id.clone()).collect::<Vec<BookId>>();
+        let mut res = MergeBooksCommandResponse::new(isbn.as_str(), kept.as_str(),
+                                                     merged.iter().map(BookId::to_string).collect(), req.dry_run);
+
+        // counting first rejects conflicting checkouts before anything is moved
+        let passes: &[bool] = if req.dry_run { &[true] } else { &[true, false] };
+        for &dry_run in passes {
+            res.holds = 0;
+            res.checkouts = 0;
+            for id in &merged {
+                res.holds += self.hold_service.reassign_book(id, &kept, dry_run)
+                    .await.map_err(CommandError::from)?;
+                res.checkouts += self.checkout_service.reassign_book(id, &kept, dry_run)
+                    .await.map_err(CommandError::from)?;
+            }
+        }
+        if !req.dry_run {
+            let _ = self.catalog_service.merge_books(&kept, &merged).await.map_err(CommandError::from)?;
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
+    use crate::catalog::command::merge_books_cmd::{MergeBooksCommand, MergeBooksCommandRequest};
+    use crate::catalog::factory;
+    use crate::checkout::factory::create_checkout_service;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::hold::factory::create_hold_service;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_merge_cmd(store: RepositoryStore) -> MergeBooksCommand {
+        let config = Configuration::new("test");
+        let svc = factory::create_catalog_service(&config, store).await;
+        let hold_svc = create_hold_service(&config, store).await;
+        let checkout_svc = create_checkout_service(&config, store).await;
+        MergeBooksCommand::new(svc, hold_svc, checkout_svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_merge_books() {
+        let store = test_store(module_path!(), "test_should_run_merge_books");
+        let add_cmd = build_add_cmd(store).await;
+        let merge_cmd = build_merge_cmd(store).await;
+        let isbn = format!("979{:010}", rand::thread_rng().gen_range(0..10_000_000_000u64));
+        let mut req = AddBookCommandRequest::new(isbn.as_str(), "merged title");
+        req.tags = vec!["first".to_string()];
+        let first = add_cmd.execute(req).await.expect("should add book").book;
+        let mut req = AddBookCommandRequest::new(format!("{}-{}", &isbn[..3], &isbn[3..]).as_str(), "merged title");
+        req.tags = vec!["second".to_string()];
+        let second = add_cmd.execute(req).await.expect("should add book").book;
+
+        let res = merge_cmd.execute(MergeBooksCommandRequest::new(isbn.as_str(), true)).await.expect("should plan merge");
+        assert_eq!(first.book_id, res.book_id);
+        assert_eq!(vec![second.book_id.to_string()], res.merged_book_ids);
+        let catalog_svc = factory::create_catalog_query_service(&Configuration::new("test"), store).await;
+        assert!(catalog_svc.find_book_by_id(&second.book_id).await.is_ok());
+
+        let _ = merge_cmd.execute(MergeBooksCommandRequest::new(isbn.as_str(), false)).await.expect("should merge");
+        assert!(catalog_svc.find_book_by_id(&second.book_id).await.is_err());
+        let merged = catalog_svc.find_book_by_id(&first.book_id).await.expect("should find kept book");
+        assert!(merged.tags.contains(&"first".to_string()) && merged.tags.contains(&"second".to_string()));
+        assert!(merge_cmd.execute(MergeBooksCommandRequest::new(isbn.as_str(), false)).await.is_err());
+    }
+}
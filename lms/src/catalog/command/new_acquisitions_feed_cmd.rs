@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use crate::catalog::domain::CatalogQueryService;
+use crate::catalog::feed::{new_acquisitions_feed, AcquisitionFeed};
+use crate::core::command::{Command, CommandError};
+
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 100;
+
+pub(crate) struct NewAcquisitionsFeedCommand {
+    catalog_service: Box<dyn CatalogQueryService>,
+}
+
+impl NewAcquisitionsFeedCommand {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogQueryService>) -> Self {
+        Self {
+            catalog_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct NewAcquisitionsFeedCommandRequest {
+    // home branch of the books, books of all branches are in the feed when it is not given
+    pub(crate) branch: Option<String>,
+    // tag of the books such as science fiction
+    pub(crate) genre: Option<String>,
+    pub(crate) limit: Option<usize>,
+}
+
+impl NewAcquisitionsFeedCommandRequest {
+    pub fn new(branch: Option<&str>, genre: Option<&str>) -> Self {
+        Self {
+            branch: branch.map(str::to_string),
+            genre: genre.map(str::to_string),
+            limit: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct NewAcquisitionsFeedCommandResponse {
+    pub xml: String,
+    pub etag: String,
+    pub last_modified: NaiveDateTime,
+}
+
+impl NewAcquisitionsFeedCommandResponse {
+    pub fn new(feed: AcquisitionFeed) -> Self {
+        Self {
+            xml: feed.xml,
+            etag: feed.etag,
+            last_modified: feed.last_modified,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<NewAcquisitionsFeedCommandRequest, NewAcquisitionsFeedCommandResponse> for NewAcquisitionsFeedCommand {
+    async fn execute(&self, req: NewAcquisitionsFeedCommandRequest) -> Result<NewAcquisitionsFeedCommandResponse, CommandError> {
+        let limit = req.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+        let branch = req.branch.as_deref().map(str::trim).filter(|branch| !branch.is_empty());
+        let genre = req.genre.as_deref().map(str::trim).filter(|genre| !genre.is_empty());
+        new_acquisitions_feed(self.catalog_service.as_ref(), branch, genre, limit)
+            .await.map_err(CommandError::from).map(NewAcquisitionsFeedCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::books::dto::BookDto;
+    use crate::catalog::command::new_acquisitions_feed_cmd::{NewAcquisitionsFeedCommand, NewAcquisitionsFeedCommandRequest};
+    use crate::catalog::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::BookStatus;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_run_new_acquisitions_feed() {
+        let store = test_store(module_path!(), "test_should_run_new_acquisitions_feed");
+        let config = Configuration::new("test");
+        let mut book = BookDto::new("feed_isbn", "feed acquisition", BookStatus::Available);
+        book.tags = vec!["feed genre".to_string()];
+        let book = factory::create_catalog_service(&config, store).await
+            .add_book(&book).await.expect("should add book");
+
+        let sut_cmd = NewAcquisitionsFeedCommand::new(factory::create_catalog_query_service(&config, store).await);
+        let res = sut_cmd.execute(NewAcquisitionsFeedCommandRequest::new(None, Some("feed genre")))
+            .await.expect("should build feed");
+        assert!(res.xml.contains(format!("<id>urn:lms:book:{}</id>", book.book_id).as_str()));
+        assert!(res.last_modified >= book.created_at);
+        let again = sut_cmd.execute(NewAcquisitionsFeedCommandRequest::new(None, Some("feed genre")))
+            .await.expect("should build feed");
+        assert_eq!(res.etag, again.etag);
+    }
+}
@@ -2,6 +2,9 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use crate::catalog::domain::CatalogService;
 use crate::core::command::{Command, CommandError};
+#[cfg(feature = "examples")]
+use crate::core::command::examples::{CommandExamples, EXAMPLE_BOOK_ID};
+use crate::core::ids::BookId;
 
 pub(crate) struct RemoveBookCommand {
     catalog_service: Box<dyn CatalogService>,
@@ -15,7 +18,7 @@ impl RemoveBookCommand {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct RemoveBookCommandRequest {
     pub(crate) book_id: String,
 }
@@ -29,7 +32,7 @@ impl RemoveBookCommandRequest {
 }
 
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct RemoveBookCommandResponse {}
 
 impl RemoveBookCommandResponse {
@@ -38,18 +41,30 @@ impl RemoveBookCommandResponse {
     }
 }
 
+#[cfg(feature = "examples")]
+impl CommandExamples for RemoveBookCommandRequest {
+    fn examples() -> Vec<Self> {
+        vec![RemoveBookCommandRequest::new(EXAMPLE_BOOK_ID.to_string())]
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for RemoveBookCommandResponse {
+    fn examples() -> Vec<Self> {
+        vec![RemoveBookCommandResponse::new()]
+    }
+}
+
 #[async_trait]
 impl Command<RemoveBookCommandRequest, RemoveBookCommandResponse> for RemoveBookCommand {
     async fn execute(&self, req: RemoveBookCommandRequest) -> Result<RemoveBookCommandResponse, CommandError> {
-        self.catalog_service.remove_book(req.book_id.as_str()).await
+        self.catalog_service.remove_book(&BookId::new(req.book_id.as_str())).await
             .map_err(CommandError::from).map(|_|RemoveBookCommandResponse::new())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use async_once::AsyncOnce;
-    use lazy_static::lazy_static;
     use crate::books::dto::BookDto;
     use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
     use crate::catalog::command::remove_book_cmd::{RemoveBookCommand, RemoveBookCommandRequest};
@@ -58,27 +73,28 @@ mod tests {
     use crate::core::library::BookStatus;
     use crate::core::domain::Configuration;
     use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
 
-    lazy_static! {
-        static ref ADD_CMD : AsyncOnce<AddBookCommand> = AsyncOnce::new(async {
-                let svc = factory::create_catalog_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                AddBookCommand::new(svc)
-            });
-        static ref REMOVE_CMD : AsyncOnce<RemoveBookCommand> = AsyncOnce::new(async {
-                let svc = factory::create_catalog_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                RemoveBookCommand::new(svc)
-            });
+    async fn build_remove_cmd(store: RepositoryStore) -> RemoveBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        RemoveBookCommand::new(svc)
     }
 
     #[tokio::test]
     async fn test_should_run_remove_book() {
-        let add_cmd = ADD_CMD.get().await.clone();
-        let remove_cmd = REMOVE_CMD.get().await.clone();
+        let store = test_store(module_path!(), "test_should_run_remove_book");
+        let add_cmd = build_add_cmd(store).await;
+        let remove_cmd = build_remove_cmd(store).await;
 
         let book = BookDto::new("isbn", "test book", BookStatus::Available);
         let _ = add_cmd.execute(AddBookCommandRequest::new(book.isbn.as_str(), book.title.as_str()))
             .await.expect("should add book");
-        let _ = remove_cmd.execute(RemoveBookCommandRequest::new(book.book_id)).await.expect("should remove book");
+        let _ = remove_cmd.execute(RemoveBookCommandRequest::new(book.book_id.into())).await.expect("should remove book");
     }
 
 }
@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::books::dto::BookDto;
+use crate::catalog::domain::CatalogService;
+use crate::core::command::{Command, CommandError};
+#[cfg(feature = "examples")]
+use crate::core::command::examples::{CommandExamples, EXAMPLE_BOOK_ID};
+use crate::core::ids::BookId;
+
+pub(crate) struct RemoveBookTagsCommand {
+    catalog_service: Box<dyn CatalogService>,
+}
+
+impl RemoveBookTagsCommand {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogService>) -> Self {
+        Self {
+            catalog_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RemoveBookTagsCommandRequest {
+    pub(crate) book_id: String,
+    pub(crate) tags: Vec<String>,
+}
+
+impl RemoveBookTagsCommandRequest {
+    pub fn new(book_id: &str, tags: Vec<String>) -> Self {
+        Self {
+            book_id: book_id.to_string(),
+            tags,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RemoveBookTagsCommandResponse {
+    pub book: BookDto,
+}
+
+impl RemoveBookTagsCommandResponse {
+    pub fn new(book: BookDto) -> Self {
+        Self {
+            book,
+        }
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for RemoveBookTagsCommandRequest {
+    fn examples() -> Vec<Self> {
+        vec![RemoveBookTagsCommandRequest::new(EXAMPLE_BOOK_ID, vec!["science-fiction".to_string()])]
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for RemoveBookTagsCommandResponse {
+    fn examples() -> Vec<Self> {
+        let mut book = BookDto::example();
+        book.tags.clear();
+        vec![RemoveBookTagsCommandResponse::new(book)]
+    }
+}
+
+#[async_trait]
+impl Command<RemoveBookTagsCommandRequest, RemoveBookTagsCommandResponse> for RemoveBookTagsCommand {
+    async fn execute(&self, req: RemoveBookTagsCommandRequest) -> Result<RemoveBookTagsCommandResponse, CommandError> {
+        self.catalog_service.remove_tags(&BookId::new(req.book_id.as_str()), &req.tags)
+            .await.map_err(CommandError::from).map(RemoveBookTagsCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
+    use crate::catalog::command::remove_book_tags_cmd::{RemoveBookTagsCommand, RemoveBookTagsCommandRequest};
+    use crate::catalog::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_untag_cmd(store: RepositoryStore) -> RemoveBookTagsCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        RemoveBookTagsCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_remove_book_tags() {
+        let store = test_store(module_path!(), "test_should_run_remove_book_tags");
+        let add_cmd = build_add_cmd(store).await;
+        let untag_cmd = build_untag_cmd(store).await;
+
+        let mut req = AddBookCommandRequest::new("isbn", "test book");
+        req.tags = vec!["biography".to_string(), "travel".to_string()];
+        let res = add_cmd.execute(req).await.expect("should add book");
+        let untagged = untag_cmd.execute(RemoveBookTagsCommandRequest::new(
+            res.book.book_id.as_str(), vec!["travel".to_string()])).await.expect("should remove tags");
+        assert_eq!(vec!["biography".to_string()], untagged.book.tags);
+    }
+}
@@ -0,0 +1,120 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::catalog::domain::CatalogService;
+use crate::core::command::{Command, CommandError};
+use crate::core::ids::BookId;
+#[cfg(feature = "examples")]
+use crate::core::command::examples::{CommandExamples, EXAMPLE_BOOK_ID};
+use crate::core::library::{BatchResult, BatchStatus};
+#[cfg(feature = "examples")]
+use crate::core::library::LibraryError;
+
+pub(crate) struct RemoveBooksCommand {
+    catalog_service: Box<dyn CatalogService>,
+}
+
+impl RemoveBooksCommand {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogService>) -> Self {
+        Self {
+            catalog_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RemoveBooksCommandRequest {
+    book_ids: Vec<String>,
+}
+
+impl RemoveBooksCommandRequest {
+    pub fn new(book_ids: Vec<String>) -> Self {
+        Self {
+            book_ids,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RemoveBooksCommandResponse {
+    // successes are the ids of removed books
+    #[serde(flatten)]
+    result: BatchResult<String>,
+}
+
+impl RemoveBooksCommandResponse {
+    pub fn new(result: BatchResult<String>) -> Self {
+        Self {
+            result,
+        }
+    }
+
+    pub(crate) fn status(&self) -> BatchStatus {
+        self.result.status
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for RemoveBooksCommandRequest {
+    fn examples() -> Vec<Self> {
+        vec![RemoveBooksCommandRequest::new(vec![EXAMPLE_BOOK_ID.to_string(), "unknown-book".to_string()])]
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for RemoveBooksCommandResponse {
+    fn examples() -> Vec<Self> {
+        let mut result = BatchResult::new();
+        result.succeeded(EXAMPLE_BOOK_ID.to_string());
+        result.failed("unknown-book", &LibraryError::not_found("book unknown-book is not found"));
+        vec![RemoveBooksCommandResponse::new(result)]
+    }
+}
+
+#[async_trait]
+impl Command<RemoveBooksCommandRequest, RemoveBooksCommandResponse> for RemoveBooksCommand {
+    async fn execute(&self, req: RemoveBooksCommandRequest) -> Result<RemoveBooksCommandResponse, CommandError> {
+        let book_ids = req.book_ids.iter().map(|id| BookId::new(id)).collect::<Vec<_>>();
+        self.catalog_service.remove_books(&book_ids).await
+            .map_err(CommandError::from).map(RemoveBooksCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
+    use crate::catalog::command::remove_books_cmd::{RemoveBooksCommand, RemoveBooksCommandRequest};
+    use crate::catalog::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::BatchStatus;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_remove_cmd(store: RepositoryStore) -> RemoveBooksCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        RemoveBooksCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_remove_books() {
+        let store = test_store(module_path!(), "test_should_run_remove_books");
+        let add_cmd = build_add_cmd(store).await;
+        let remove_cmd = build_remove_cmd(store).await;
+
+        let mut book_ids = vec![];
+        for isbn in ["isbn1", "isbn2"] {
+            let res = add_cmd.execute(AddBookCommandRequest::new(isbn, "test book"))
+                .await.expect("should add book");
+            book_ids.push(res.book.book_id.to_string());
+        }
+        let res = remove_cmd.execute(RemoveBooksCommandRequest::new(book_ids.clone())).await.expect("should remove books");
+        assert_eq!(BatchStatus::Succeeded, res.status());
+        assert_eq!(book_ids, res.result.successes);
+    }
+}
@@ -1,12 +1,11 @@
 use async_trait::async_trait;
-use chrono::Utc;
-use rand::Rng;
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
 use crate::books::dto::BookDto;
 use crate::catalog::domain::CatalogService;
 use crate::core::command::{Command, CommandError};
-use crate::core::library::BookStatus;
+#[cfg(feature = "examples")]
+use crate::core::command::examples::{CommandExamples, EXAMPLE_BOOK_ID, EXAMPLE_ISBN};
+use crate::core::library::{BookFormat, BookStatus, LibraryResult};
 
 pub(crate) struct UpdateBookCommand {
     catalog_service: Box<dyn CatalogService>,
@@ -20,13 +19,17 @@ impl UpdateBookCommand {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct UpdateBookCommandRequest {
     pub book_id: String,
     pub isbn: String,
     pub title: String,
     pub book_status: BookStatus,
     pub restricted: bool,
+    #[serde(default)]
+    pub book_format: BookFormat,
+    #[serde(default)]
+    pub license_count: i64,
 }
 
 impl UpdateBookCommandRequest {
@@ -37,24 +40,20 @@ impl UpdateBookCommandRequest {
             title: title.to_string(),
             book_status: status,
             restricted: false,
+            book_format: BookFormat::Physical,
+            license_count: 0,
         }
     }
-    pub fn build_book(&self) -> BookDto {
-        BookDto {
-            dewey_decimal_id: format!("{}", rand::thread_rng().gen_range(0..1000)),
-            version: 0,
-            book_id: self.book_id.to_string(),
-            author_id: Uuid::new_v4().to_string(), // random for testing purpose
-            publisher_id: Uuid::new_v4().to_string(), // random for testing purpose
-            language: "en".to_string(), // random for testing purpose
-            isbn: self.isbn.to_string(),
-            title: self.title.to_string(),
-            book_status: self.book_status,
-            restricted: self.restricted,
-            published_at: Utc::now().naive_utc(), // for testing purpose
-            created_at: Utc::now().naive_utc(),
-            updated_at: Utc::now().naive_utc(),
-        }
+    pub fn build_book(&self) -> LibraryResult<BookDto> {
+        BookDto::builder()
+            .book_id(self.book_id.as_str())
+            .isbn(self.isbn.as_str())
+            .title(self.title.as_str())
+            .book_status(self.book_status)
+            .restricted(self.restricted)
+            .book_format(self.book_format)
+            .license_count(self.license_count)
+            .build()
     }
 }
 
@@ -72,18 +71,35 @@ impl UpdateBookCommandResponse {
     }
 }
 
+#[cfg(feature = "examples")]
+impl CommandExamples for UpdateBookCommandRequest {
+    fn examples() -> Vec<Self> {
+        let mut req = UpdateBookCommandRequest::new(EXAMPLE_BOOK_ID, EXAMPLE_ISBN, "Dune", BookStatus::Available);
+        req.restricted = true;
+        vec![req]
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for UpdateBookCommandResponse {
+    fn examples() -> Vec<Self> {
+        let mut book = BookDto::example();
+        book.restricted = true;
+        book.version = 1;
+        vec![UpdateBookCommandResponse::new(book)]
+    }
+}
+
 #[async_trait]
 impl Command<UpdateBookCommandRequest, UpdateBookCommandResponse> for UpdateBookCommand {
     async fn execute(&self, req: UpdateBookCommandRequest) -> Result<UpdateBookCommandResponse, CommandError> {
-        let book = req.build_book();
+        let book = req.build_book().map_err(CommandError::from)?;
         self.catalog_service.update_book(&book).await.map_err(CommandError::from).map(|_| UpdateBookCommandResponse::new(book))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use async_once::AsyncOnce;
-    use lazy_static::lazy_static;
     use crate::books::dto::BookDto;
     use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
     use crate::catalog::command::update_book_cmd::{UpdateBookCommand, UpdateBookCommandRequest};
@@ -92,22 +108,23 @@ mod tests {
     use crate::core::library::BookStatus;
     use crate::core::domain::Configuration;
     use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
 
-    lazy_static! {
-        static ref ADD_CMD : AsyncOnce<AddBookCommand> = AsyncOnce::new(async {
-                let svc = factory::create_catalog_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                AddBookCommand::new(svc)
-            });
-        static ref UPDATE_CMD : AsyncOnce<UpdateBookCommand> = AsyncOnce::new(async {
-                let svc = factory::create_catalog_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                UpdateBookCommand::new(svc)
-            });
+    async fn build_update_cmd(store: RepositoryStore) -> UpdateBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        UpdateBookCommand::new(svc)
     }
 
     #[tokio::test]
     async fn test_should_run_update_book() {
-        let add_cmd = ADD_CMD.get().await.clone();
-        let update_cmd = UPDATE_CMD.get().await.clone();
+        let store = test_store(module_path!(), "test_should_run_update_book");
+        let add_cmd = build_add_cmd(store).await;
+        let update_cmd = build_update_cmd(store).await;
 
         let book = BookDto::new("isbn", "test book", BookStatus::Available);
         let _ = add_cmd.execute(AddBookCommandRequest::new(book.isbn.as_str(), book.title.as_str()))
@@ -0,0 +1,117 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::books::dto::BookDto;
+use crate::catalog::domain::CatalogService;
+use crate::core::command::{Command, CommandError};
+#[cfg(feature = "examples")]
+use crate::core::command::examples::{CommandExamples, EXAMPLE_BOOK_ID};
+use crate::core::ids::BookId;
+
+pub(crate) struct UpdateLocationCommand {
+    catalog_service: Box<dyn CatalogService>,
+}
+
+impl UpdateLocationCommand {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogService>) -> Self {
+        Self {
+            catalog_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct UpdateLocationCommandRequest {
+    #[serde(default)]
+    pub(crate) book_id: String,
+    #[serde(default)]
+    pub(crate) dewey_decimal_id: Option<String>,
+    #[serde(default)]
+    pub(crate) collection: String,
+    pub(crate) shelf_location: String,
+}
+
+impl UpdateLocationCommandRequest {
+    pub fn new(book_id: &str, dewey_decimal_id: Option<&str>, collection: &str, shelf_location: &str) -> Self {
+        Self {
+            book_id: book_id.to_string(),
+            dewey_decimal_id: dewey_decimal_id.map(|d| d.to_string()),
+            collection: collection.to_string(),
+            shelf_location: shelf_location.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct UpdateLocationCommandResponse {
+    pub book: BookDto,
+}
+
+impl UpdateLocationCommandResponse {
+    pub fn new(book: BookDto) -> Self {
+        Self {
+            book,
+        }
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for UpdateLocationCommandRequest {
+    fn examples() -> Vec<Self> {
+        vec![UpdateLocationCommandRequest::new(EXAMPLE_BOOK_ID, Some("813.54"), "REF", "B1")]
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for UpdateLocationCommandResponse {
+    fn examples() -> Vec<Self> {
+        let mut book = BookDto::example();
+        book.collection = "REF".to_string();
+        book.shelf_location = "B1".to_string();
+        book.call_number = "REF 813.54 DUN".to_string();
+        vec![UpdateLocationCommandResponse::new(book)]
+    }
+}
+
+#[async_trait]
+impl Command<UpdateLocationCommandRequest, UpdateLocationCommandResponse> for UpdateLocationCommand {
+    async fn execute(&self, req: UpdateLocationCommandRequest) -> Result<UpdateLocationCommandResponse, CommandError> {
+        self.catalog_service.update_location(&BookId::new(req.book_id.as_str()), req.dewey_decimal_id.as_deref(),
+                                             req.collection.as_str(), req.shelf_location.as_str())
+            .await.map_err(CommandError::from).map(UpdateLocationCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
+    use crate::catalog::command::update_location_cmd::{UpdateLocationCommand, UpdateLocationCommandRequest};
+    use crate::catalog::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_location_cmd(store: RepositoryStore) -> UpdateLocationCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        UpdateLocationCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_update_location() {
+        let store = test_store(module_path!(), "test_should_run_update_location");
+        let add_cmd = build_add_cmd(store).await;
+        let location_cmd = build_location_cmd(store).await;
+
+        let res = add_cmd.execute(AddBookCommandRequest::new("isbn", "Atlas")).await.expect("should add book");
+        let updated = location_cmd.execute(UpdateLocationCommandRequest::new(
+            res.book.book_id.as_str(), Some("912"), "ref", "Map Room")).await.expect("should update location");
+        assert_eq!("Map Room", updated.book.shelf_location.as_str());
+        assert_eq!("REF 912 ATL", updated.book.call_number.as_str());
+    }
+}
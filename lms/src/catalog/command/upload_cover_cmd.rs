@@ -0,0 +1,114 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::books::dto::BookDto;
+use crate::catalog::domain::CatalogService;
+use crate::core::command::{Command, CommandError};
+#[cfg(feature = "examples")]
+use crate::core::command::examples::{CommandExamples, EXAMPLE_BOOK_ID};
+use crate::core::ids::BookId;
+
+pub(crate) struct UploadCoverCommand {
+    catalog_service: Box<dyn CatalogService>,
+}
+
+impl UploadCoverCommand {
+    pub(crate) fn new(catalog_service: Box<dyn CatalogService>) -> Self {
+        Self {
+            catalog_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct UploadCoverCommandRequest {
+    #[serde(default)]
+    pub(crate) book_id: String,
+    #[serde(default)]
+    pub(crate) content_type: String,
+    #[serde(default)]
+    pub(crate) content: Vec<u8>,
+}
+
+impl UploadCoverCommandRequest {
+    pub fn new(book_id: &str, content_type: &str, content: Vec<u8>) -> Self {
+        Self {
+            book_id: book_id.to_string(),
+            content_type: content_type.to_string(),
+            content,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct UploadCoverCommandResponse {
+    pub book: BookDto,
+}
+
+impl UploadCoverCommandResponse {
+    pub fn new(book: BookDto) -> Self {
+        Self {
+            book,
+        }
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for UploadCoverCommandRequest {
+    fn examples() -> Vec<Self> {
+        vec![UploadCoverCommandRequest::new(EXAMPLE_BOOK_ID, "image/jpeg", vec![0xFF, 0xD8, 0xFF, 0xE0])]
+    }
+}
+
+#[cfg(feature = "examples")]
+impl CommandExamples for UploadCoverCommandResponse {
+    fn examples() -> Vec<Self> {
+        let mut book = BookDto::example();
+        book.cover_key = format!("covers/{}.jpg", EXAMPLE_BOOK_ID);
+        vec![UploadCoverCommandResponse::new(book)]
+    }
+}
+
+#[async_trait]
+impl Command<UploadCoverCommandRequest, UploadCoverCommandResponse> for UploadCoverCommand {
+    async fn execute(&self, req: UploadCoverCommandRequest) -> Result<UploadCoverCommandResponse, CommandError> {
+        self.catalog_service.upload_cover(&BookId::new(req.book_id.as_str()), req.content_type.as_str(), req.content)
+            .await.map_err(CommandError::from).map(UploadCoverCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
+    use crate::catalog::command::upload_cover_cmd::{UploadCoverCommand, UploadCoverCommandRequest};
+    use crate::catalog::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_upload_cmd(store: RepositoryStore) -> UploadCoverCommand {
+        let svc = factory::create_catalog_service(&Configuration::new("test"), store).await;
+        UploadCoverCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_upload_cover() {
+        let store = test_store(module_path!(), "test_should_run_upload_cover");
+        let add_cmd = build_add_cmd(store).await;
+        let upload_cmd = build_upload_cmd(store).await;
+
+        let res = add_cmd.execute(AddBookCommandRequest::new("isbn", "Covered")).await.expect("should add book");
+        let png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0];
+        let uploaded = upload_cmd.execute(UploadCoverCommandRequest::new(
+            res.book.book_id.as_str(), "image/png", png)).await.expect("should upload cover");
+        assert!(uploaded.book.cover_key.ends_with(".png"));
+        assert!(upload_cmd.execute(UploadCoverCommandRequest::new(
+            res.book.book_id.as_str(), "image/gif", b"GIF89a".to_vec())).await.is_err());
+    }
+}
@@ -1,29 +1,88 @@
+use std::collections::HashMap;
 use axum::{
-    extract::{Path, State},
+    body::Bytes,
+    http::{header, HeaderMap, StatusCode},
+    extract::{Path, Query, State},
+    middleware,
     response::Json,
+    routing::{delete, get, post, put},
+    Router,
 };
+use chrono::DateTime;
 use serde_json::{Value};
+use crate::books::repository::ddb_book_repository::{ACQUISITION_INDEX, AUTHOR_INDEX, SHELF_INDEX};
 use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest, AddBookCommandResponse};
+use crate::catalog::command::add_book_tags_cmd::{AddBookTagsCommand, AddBookTagsCommandRequest, AddBookTagsCommandResponse};
+use crate::catalog::command::export_shelf_list_cmd::{ExportShelfListCommand, ExportShelfListCommandRequest, ExportShelfListCommandResponse};
+use crate::catalog::command::federated_search_cmd::{FederatedSearchCommand, FederatedSearchCommandRequest, FederatedSearchCommandResponse};
+use crate::catalog::command::find_duplicate_books_cmd::{FindDuplicateBooksCommand, FindDuplicateBooksCommandRequest, FindDuplicateBooksCommandResponse};
+use crate::catalog::command::find_related_books_cmd::{FindRelatedBooksCommand, FindRelatedBooksCommandRequest, FindRelatedBooksCommandResponse};
+use crate::catalog::command::find_books_by_author_cmd::{FindBooksByAuthorCommand, FindBooksByAuthorCommandRequest, FindBooksByAuthorCommandResponse};
+use crate::catalog::command::find_books_by_isbn_cmd::{FindBooksByIsbnCommand, FindBooksByIsbnCommandRequest, FindBooksByIsbnCommandResponse};
+use crate::catalog::command::find_books_by_tag_cmd::{FindBooksByTagCommand, FindBooksByTagCommandRequest, FindBooksByTagCommandResponse};
+use crate::catalog::command::find_trending_books_cmd::{FindTrendingBooksCommand, FindTrendingBooksCommandRequest, FindTrendingBooksCommandResponse};
+use crate::catalog::command::get_cover_cmd::{GetCoverCommand, GetCoverCommandRequest, GetCoverCommandResponse};
+use crate::catalog::command::new_acquisitions_feed_cmd::{NewAcquisitionsFeedCommand, NewAcquisitionsFeedCommandRequest, NewAcquisitionsFeedCommandResponse};
+use crate::catalog::command::harvest_oai_cmd::{HarvestOaiCommand, HarvestOaiCommandRequest, HarvestOaiCommandResponse};
 use crate::catalog::command::get_book_cmd::{GetBookCommand, GetBookCommandRequest, GetBookCommandResponse};
+use crate::catalog::command::import_marc_cmd::{ImportMarcCommand, ImportMarcCommandRequest, ImportMarcCommandResponse};
+use crate::catalog::command::get_tags_cmd::{GetTagsCommand, GetTagsCommandRequest, GetTagsCommandResponse};
+use crate::catalog::command::merge_books_cmd::{MergeBooksCommand, MergeBooksCommandRequest, MergeBooksCommandResponse};
 use crate::catalog::command::remove_book_cmd::{RemoveBookCommand, RemoveBookCommandRequest, RemoveBookCommandResponse};
-use crate::catalog::domain::CatalogService;
+use crate::catalog::command::remove_books_cmd::{RemoveBooksCommand, RemoveBooksCommandRequest, RemoveBooksCommandResponse};
+use crate::catalog::command::remove_book_tags_cmd::{RemoveBookTagsCommand, RemoveBookTagsCommandRequest, RemoveBookTagsCommandResponse};
+use crate::catalog::command::update_location_cmd::{UpdateLocationCommand, UpdateLocationCommandRequest, UpdateLocationCommandResponse};
+use crate::catalog::command::upload_cover_cmd::{UploadCoverCommand, UploadCoverCommandRequest, UploadCoverCommandResponse};
+use crate::catalog::domain::{CatalogQueryService, CatalogService};
 use crate::catalog::factory;
-use crate::core::command::Command;
-use crate::core::controller::{AppState, json_to_server_error, ServerError};
-use crate::utils::ddb::{build_db_client, create_table};
+use crate::catalog::oai::OaiRepository;
+use crate::checkout::factory::create_checkout_service;
+use crate::core::controller::{AppState, batch_status_code, body_limit, command_bus, json_to_server_error, with_common_layers, ServerError};
+use crate::credentials::controller::authenticate;
+use crate::gateway::command::find_events_cmd::FindEventsCommandResponse;
+use crate::gateway::controller::find_entity_events;
+use crate::hold::factory::create_hold_service;
+use crate::serials::domain::SerialQueryService;
+use crate::serials::factory::create_serial_query_service;
+use crate::utils::ddb::{build_db_client, create_index, create_key_table, create_table, TableBilling};
 
 async fn build_service(state: AppState) -> Box<dyn CatalogService> {
     let client = build_db_client(state.store).await;
-    let _ = create_table(&client, "books", "book_id", "book_status", "isbn").await;
+    let _ = create_table(&client, state.store.table_name("books").as_str(), "book_id", "book_status", "isbn").await;
+    let _ = create_index(&client, state.store.table_name("books").as_str(), AUTHOR_INDEX, "author_id", "created_at", TableBilling::default()).await;
+    let _ = create_index(&client, state.store.table_name("books").as_str(), SHELF_INDEX, "book_format", "call_number", TableBilling::default()).await;
+    let _ = create_index(&client, state.store.table_name("books").as_str(), ACQUISITION_INDEX, "book_format", "created_at", TableBilling::default()).await;
+    let _ = create_key_table(&client, state.store.table_name("tags").as_str(), "tag_name").await;
+    let _ = create_table(&client, state.store.table_name("co_checkouts").as_str(), "pair_id", "book_id", "related_book_id").await;
+    let _ = create_table(&client, state.store.table_name("book_popularity").as_str(), "score_id", "score_window", "score_key").await;
     factory::create_catalog_service(&state.config, state.store).await
 }
 
+async fn build_query_service(state: AppState) -> Box<dyn CatalogQueryService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_table(&client, state.store.table_name("books").as_str(), "book_id", "book_status", "isbn").await;
+    let _ = create_index(&client, state.store.table_name("books").as_str(), AUTHOR_INDEX, "author_id", "created_at", TableBilling::default()).await;
+    let _ = create_index(&client, state.store.table_name("books").as_str(), SHELF_INDEX, "book_format", "call_number", TableBilling::default()).await;
+    let _ = create_index(&client, state.store.table_name("books").as_str(), ACQUISITION_INDEX, "book_format", "created_at", TableBilling::default()).await;
+    let _ = create_key_table(&client, state.store.table_name("tags").as_str(), "tag_name").await;
+    let _ = create_table(&client, state.store.table_name("co_checkouts").as_str(), "pair_id", "book_id", "related_book_id").await;
+    let _ = create_table(&client, state.store.table_name("book_popularity").as_str(), "score_id", "score_window", "score_key").await;
+    factory::create_catalog_query_service(&state.config, state.store).await
+}
+
+async fn build_serial_query_service(state: AppState) -> Box<dyn SerialQueryService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_key_table(&client, state.store.table_name("serials").as_str(), "serial_id").await;
+    let _ = create_table(&client, state.store.table_name("serial_issues").as_str(), "issue_id", "serial_id", "expected_at").await;
+    create_serial_query_service(&state.config, state.store).await
+}
+
 pub(crate) async fn add_book(
     State(state): State<AppState>,
     json: Json<Value>) -> Result<Json<AddBookCommandResponse>, ServerError> {
     let req: AddBookCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
     let svc = build_service(state).await;
-    let res = AddBookCommand::new(svc).execute(req).await?;
+    let res = command_bus().register(AddBookCommand::new(svc)).dispatch(req).await?;
     Ok(Json(res))
 }
 
@@ -31,8 +90,9 @@ pub(crate) async fn find_book_by_id(
     State(state): State<AppState>,
     Path(book_id): Path<String>) -> Result<Json<GetBookCommandResponse>, ServerError> {
     let req = GetBookCommandRequest { book_id };
-    let svc = build_service(state).await;
-    let res = GetBookCommand::new(svc).execute(req).await?;
+    let serial_svc = build_serial_query_service(state.clone()).await;
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(GetBookCommand::new(svc, serial_svc)).dispatch(req).await?;
     Ok(Json(res))
 }
 
@@ -41,6 +101,242 @@ pub(crate) async fn remove_book(
     Path(book_id): Path<String>) -> Result<Json<RemoveBookCommandResponse>, ServerError> {
     let req = RemoveBookCommandRequest { book_id };
     let svc = build_service(state).await;
-    let res = RemoveBookCommand::new(svc).execute(req).await?;
+    let res = command_bus().register(RemoveBookCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn remove_books(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<(StatusCode, Json<RemoveBooksCommandResponse>), ServerError> {
+    let req: RemoveBooksCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res: RemoveBooksCommandResponse = command_bus().register(RemoveBooksCommand::new(svc)).dispatch(req).await?;
+    Ok((batch_status_code(res.status()), Json(res)))
+}
+
+// records exported from the legacy system as MARC21 or MARCXML are added as books, the response reports the outcome of
+// each record and the fields that were not carried over
+pub(crate) async fn import_marc(
+    State(state): State<AppState>,
+    body: Bytes) -> Result<(StatusCode, Json<ImportMarcCommandResponse>), ServerError> {
+    let req = ImportMarcCommandRequest::new(&body);
+    let svc = build_service(state).await;
+    let res: ImportMarcCommandResponse = command_bus().register(ImportMarcCommand::new(svc)).dispatch(req).await?;
+    Ok((batch_status_code(res.status()), Json(res)))
+}
+
+pub(crate) async fn add_book_tags(
+    State(state): State<AppState>,
+    Path(book_id): Path<String>,
+    json: Json<Value>) -> Result<Json<AddBookTagsCommandResponse>, ServerError> {
+    let mut req: AddBookTagsCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.book_id = book_id;
+    let svc = build_service(state).await;
+    let res = command_bus().register(AddBookTagsCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn update_location(
+    State(state): State<AppState>,
+    Path(book_id): Path<String>,
+    json: Json<Value>) -> Result<Json<UpdateLocationCommandResponse>, ServerError> {
+    let mut req: UpdateLocationCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.book_id = book_id;
+    let svc = build_service(state).await;
+    let res = command_bus().register(UpdateLocationCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn upload_cover(
+    State(state): State<AppState>,
+    Path(book_id): Path<String>,
+    headers: HeaderMap,
+    body: Bytes) -> Result<Json<UploadCoverCommandResponse>, ServerError> {
+    let content_type = headers.get(header::CONTENT_TYPE).and_then(|v| v.to_str().ok()).unwrap_or_default();
+    let req = UploadCoverCommandRequest::new(book_id.as_str(), content_type, body.to_vec());
+    let svc = build_service(state).await;
+    let res = command_bus().register(UploadCoverCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// redirects to a short-lived link of the cover image so that clients can fetch it straight from storage
+pub(crate) async fn get_cover(
+    State(state): State<AppState>,
+    Path(book_id): Path<String>) -> Result<(StatusCode, [(header::HeaderName, String); 1], Json<GetCoverCommandResponse>), ServerError> {
+    let req = GetCoverCommandRequest { book_id };
+    let svc = build_query_service(state).await;
+    let res: GetCoverCommandResponse = command_bus().register(GetCoverCommand::new(svc)).dispatch(req).await?;
+    Ok((StatusCode::TEMPORARY_REDIRECT, [(header::LOCATION, res.url.clone())], Json(res)))
+}
+
+pub(crate) async fn remove_book_tag(
+    State(state): State<AppState>,
+    Path((book_id, tag)): Path<(String, String)>) -> Result<Json<RemoveBookTagsCommandResponse>, ServerError> {
+    let req = RemoveBookTagsCommandRequest { book_id, tags: vec![tag] };
+    let svc = build_service(state).await;
+    let res = command_bus().register(RemoveBookTagsCommand::new(svc)).dispatch(req).await?;
     Ok(Json(res))
 }
+
+pub(crate) async fn find_books_by_tag(
+    State(state): State<AppState>,
+    Query(req): Query<FindBooksByTagCommandRequest>) -> Result<Json<FindBooksByTagCommandResponse>, ServerError> {
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(FindBooksByTagCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn find_books_by_isbn(
+    State(state): State<AppState>,
+    Path(isbn): Path<String>,
+    Query(mut req): Query<FindBooksByIsbnCommandRequest>) -> Result<Json<FindBooksByIsbnCommandResponse>, ServerError> {
+    req.isbn = isbn;
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(FindBooksByIsbnCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn find_books_by_author(
+    State(state): State<AppState>,
+    Path(author_id): Path<String>,
+    Query(mut req): Query<FindBooksByAuthorCommandRequest>) -> Result<Json<FindBooksByAuthorCommandResponse>, ServerError> {
+    req.author_id = author_id;
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(FindBooksByAuthorCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn get_tags(
+    State(state): State<AppState>) -> Result<Json<GetTagsCommandResponse>, ServerError> {
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(GetTagsCommand::new(svc)).dispatch(GetTagsCommandRequest::new()).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn find_related_books(
+    State(state): State<AppState>,
+    Path(book_id): Path<String>,
+    Query(mut req): Query<FindRelatedBooksCommandRequest>) -> Result<Json<FindRelatedBooksCommandResponse>, ServerError> {
+    req.book_id = book_id;
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(FindRelatedBooksCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// titles of the union catalog matching an isbn or title, e.g. /catalog/federated-search?q=dune
+pub(crate) async fn federated_search(
+    State(state): State<AppState>,
+    Query(req): Query<FederatedSearchCommandRequest>) -> Result<Json<FederatedSearchCommandResponse>, ServerError> {
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(FederatedSearchCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// most popular books of a window by recent checkouts and holds, e.g. /catalog/trending?window=7d
+pub(crate) async fn find_trending_books(
+    State(state): State<AppState>,
+    Query(req): Query<FindTrendingBooksCommandRequest>) -> Result<Json<FindTrendingBooksCommandResponse>, ServerError> {
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(FindTrendingBooksCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn find_duplicate_books(
+    State(state): State<AppState>) -> Result<Json<FindDuplicateBooksCommandResponse>, ServerError> {
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(FindDuplicateBooksCommand::new(svc)).dispatch(FindDuplicateBooksCommandRequest::new()).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn merge_books(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<Json<MergeBooksCommandResponse>, ServerError> {
+    let req: MergeBooksCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let hold_svc = create_hold_service(&state.config, state.store).await;
+    let checkout_svc = create_checkout_service(&state.config, state.store).await;
+    let svc = build_service(state).await;
+    let res = command_bus().register(MergeBooksCommand::new(svc, hold_svc, checkout_svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// shelf list of a call number range as CSV, ordered for shelf-reading
+pub(crate) async fn export_shelf_list(
+    State(state): State<AppState>,
+    Query(req): Query<ExportShelfListCommandRequest>) -> Result<([(header::HeaderName, &'static str); 1], String), ServerError> {
+    let svc = build_query_service(state).await;
+    let res: ExportShelfListCommandResponse = command_bus().register(ExportShelfListCommand::new(svc)).dispatch(req).await?;
+    Ok(([(header::CONTENT_TYPE, "text/csv; charset=utf-8")], res.csv))
+}
+
+// Atom feed of new acquisitions, e.g. /catalog/feed/new?branch=main&genre=mystery, feed readers revalidate with
+// If-None-Match or If-Modified-Since and get 304 while the feed is unchanged
+pub(crate) async fn new_acquisitions_feed(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(req): Query<NewAcquisitionsFeedCommandRequest>) -> Result<(StatusCode, [(header::HeaderName, String); 4], String), ServerError> {
+    let cache_control = format!("public, max-age={}", state.config.feed_cache_seconds);
+    let svc = build_query_service(state).await;
+    let res: NewAcquisitionsFeedCommandResponse = command_bus().register(NewAcquisitionsFeedCommand::new(svc)).dispatch(req).await?;
+    let last_modified = res.last_modified.format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+    let not_modified = match headers.get(header::IF_NONE_MATCH).and_then(|v| v.to_str().ok()) {
+        Some(etags) => etags.split(',').any(|etag| etag.trim() == "*" || etag.trim().trim_start_matches("W/") == res.etag),
+        None => headers.get(header::IF_MODIFIED_SINCE).and_then(|v| v.to_str().ok())
+            .and_then(|since| DateTime::parse_from_rfc2822(since).ok())
+            .map_or(false, |since| res.last_modified.and_utc().timestamp() <= since.timestamp()),
+    };
+    let headers = [
+        (header::CONTENT_TYPE, "application/atom+xml; charset=utf-8".to_string()),
+        (header::CACHE_CONTROL, cache_control),
+        (header::ETAG, res.etag),
+        (header::LAST_MODIFIED, last_modified),
+    ];
+    if not_modified {
+        return Ok((StatusCode::NOT_MODIFIED, headers, String::new()));
+    }
+    Ok((StatusCode::OK, headers, res.xml))
+}
+
+// OAI-PMH requests of metadata harvesters, e.g. /catalog/oai?verb=ListRecords&metadataPrefix=oai_dc
+pub(crate) async fn harvest_oai(
+    State(state): State<AppState>,
+    Query(arguments): Query<HashMap<String, String>>) -> Result<([(header::HeaderName, &'static str); 1], String), ServerError> {
+    let repository = OaiRepository::new(&state.config);
+    let svc = build_query_service(state).await;
+    let res: HarvestOaiCommandResponse = command_bus().register(HarvestOaiCommand::new(svc, repository))
+        .dispatch(HarvestOaiCommandRequest::new(arguments)).await?;
+    Ok(([(header::CONTENT_TYPE, "text/xml; charset=utf-8")], res.xml))
+}
+
+// events published for the book, served to librarians and admins for support investigations
+pub(crate) async fn find_book_events(
+    State(state): State<AppState>,
+    Path(id): Path<String>) -> Result<Json<FindEventsCommandResponse>, ServerError> {
+    find_entity_events(state, &["books"], id.as_str()).await
+}
+
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/catalog/shelf_list", get(export_shelf_list))
+        .route("/catalog/duplicates", get(find_duplicate_books))
+        .route("/catalog/duplicates/merge", post(merge_books))
+        .route("/catalog/import", post(import_marc).layer(body_limit(state.config.max_bulk_body_bytes)))
+        .route("/catalog/:id/events", get(find_book_events))
+        .route_layer(middleware::from_fn_with_state(state.clone(), authenticate))
+        .route("/catalog", post(add_book).get(find_books_by_tag))
+        .route("/catalog/batch/delete", post(remove_books).layer(body_limit(state.config.max_bulk_body_bytes)))
+        .route("/catalog/isbn/:isbn", get(find_books_by_isbn))
+        .route("/catalog/author/:id", get(find_books_by_author))
+        .route("/catalog/trending", get(find_trending_books))
+        .route("/catalog/federated-search", get(federated_search))
+        .route("/catalog/oai", get(harvest_oai))
+        .route("/catalog/feed/new", get(new_acquisitions_feed))
+        .route("/catalog/:id",
+               get(find_book_by_id).delete(remove_book))
+        .route("/catalog/:id/cover", put(upload_cover).layer(body_limit(state.config.max_cover_bytes)).get(get_cover))
+        .route("/catalog/:id/location", put(update_location))
+        .route("/catalog/:id/related", get(find_related_books))
+        .route("/catalog/:id/tags", post(add_book_tags))
+        .route("/catalog/:id/tags/:tag", delete(remove_book_tag))
+        .route("/tags", get(get_tags));
+    with_common_layers(router, state)
+}
@@ -1,15 +1,70 @@
+pub mod query;
 pub mod service;
 
 use async_trait::async_trait;
-use crate::books::dto::BookDto;
-use crate::core::library::LibraryResult;
+use crate::books::dto::{BookDto, BookFilter, DuplicateBooksDto, FederatedSearchDto, MarcImportDto, RelatedBookDto, TagCountDto, TrendingBookDto};
+use crate::core::ids::BookId;
+use crate::core::library::{BatchResult, LibraryResult, PaginatedResult};
+use crate::core::repository::ReadConsistency;
 
+// read side of the catalog, other contexts that only look up books should depend on it instead of CatalogService
 #[async_trait]
-pub(crate) trait CatalogService: Sync + Send {
+pub trait CatalogQueryService: Sync + Send {
+    async fn find_book_by_id(&self, id: &BookId) -> LibraryResult<BookDto>;
+    // the isbn index lags behind writes, a just-added book is awaited and then read by id when the consistency expects it
+    async fn find_book_by_isbn(&self, isbn: &str, consistency: &ReadConsistency) -> LibraryResult<Vec<BookDto>>;
+    // returns books shelved at the location (scanned pages may be partially filled) or all books without location
+    async fn find_books_by_shelf(&self, shelf_location: Option<&str>,
+                                 page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookDto>>;
+    // returns physical copies of the collection from one dewey class through another in shelf-reading order
+    async fn find_books_by_call_number(&self, collection: &str, from_dewey: &str, to_dewey: &str,
+                                       page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookDto>>;
+    async fn find_books_by_tag(&self, tag: &str, filter: &BookFilter,
+                               page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookDto>>;
+    // returns books of the author in the order they were added to the catalog
+    async fn find_books_by_author(&self, author_id: &str, filter: &BookFilter,
+                                  page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookDto>>;
+    async fn tag_counts(&self) -> LibraryResult<Vec<TagCountDto>>;
+    // scans the catalog for records sharing the normalized isbn, e.g. the same title entered with and without hyphens
+    async fn find_duplicate_books(&self, page_size: usize) -> LibraryResult<Vec<DuplicateBooksDto>>;
+    async fn find_related_books(&self, id: &BookId, limit: usize) -> LibraryResult<Vec<RelatedBookDto>>;
+    // returns the most popular books of the window (1d, 7d or 30d) by decay-weighted checkouts and holds
+    async fn find_trending_books(&self, window: &str, limit: usize) -> LibraryResult<Vec<TrendingBookDto>>;
+    // returns a URL of the cover image of the book that expires after a while
+    async fn find_cover_url(&self, id: &BookId) -> LibraryResult<String>;
+    // searches the union catalog by isbn or title for books to catalog, the search is degraded instead of failing
+    // when the union catalog is unavailable or does not respond in time
+    async fn federated_search(&self, q: &str, limit: usize) -> LibraryResult<FederatedSearchDto>;
+
+    // books added within the acquisition window newest first, optionally only of a branch and of a genre tag
+    async fn find_new_acquisitions(&self, branch_id: Option<&str>, genre: Option<&str>, limit: usize) -> LibraryResult<Vec<BookDto>>;
+}
+
+#[async_trait]
+pub trait CatalogService: CatalogQueryService {
     async fn add_book(&self, book: &BookDto) -> LibraryResult<BookDto>;
-    async fn remove_book(&self, id: &str) -> LibraryResult<()>;
+    async fn remove_book(&self, id: &BookId) -> LibraryResult<()>;
+    // removes each book on its own, books that cannot be removed are reported as failures of the batch
+    async fn remove_books(&self, ids: &[BookId]) -> LibraryResult<BatchResult<String>>;
+    // adds a book for each record of a MARC21 or MARCXML export of the legacy system, records that cannot be mapped
+    // or added are reported as failures and fields that were not carried over are counted in the report
+    async fn import_marc(&self, content: &[u8]) -> LibraryResult<MarcImportDto>;
     async fn update_book(&self, book: &BookDto) -> LibraryResult<BookDto>;
-    async fn find_book_by_id(&self, id: &str) -> LibraryResult<BookDto>;
-    async fn find_book_by_isbn(&self, isbn: &str) -> LibraryResult<Vec<BookDto>>;
+    // updates shelving metadata and rebuilds call number so that staff and patrons can find physical copies
+    async fn update_location(&self, id: &BookId, dewey_decimal_id: Option<&str>, collection: &str,
+                             shelf_location: &str) -> LibraryResult<BookDto>;
+    // changes home branch of the copy
+    async fn update_branch(&self, id: &BookId, branch_id: &str) -> LibraryResult<BookDto>;
+    async fn add_tags(&self, id: &BookId, tags: &[String]) -> LibraryResult<BookDto>;
+    async fn remove_tags(&self, id: &BookId, tags: &[String]) -> LibraryResult<BookDto>;
+    // stores a JPEG, PNG or WebP cover image of the book and replaces the previous cover
+    async fn upload_cover(&self, id: &BookId, content_type: &str, content: Vec<u8>) -> LibraryResult<BookDto>;
+    // folds duplicate records into the kept record, tags and licenses are added to it and the duplicates are removed,
+    // holds and checkouts of the duplicates must be moved to the kept record beforehand
+    async fn merge_books(&self, kept_id: &BookId, merged_ids: &[BookId]) -> LibraryResult<BookDto>;
+    // atomically takes a concurrent license of digital book and returns remaining licenses
+    async fn acquire_license(&self, id: &BookId) -> LibraryResult<i64>;
+    // returns a license of digital book and returns available licenses
+    async fn release_license(&self, id: &BookId) -> LibraryResult<i64>;
 }
 
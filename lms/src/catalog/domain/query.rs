@@ -0,0 +1,356 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::log::warn;
+use crate::books::domain::model::BookEntity;
+use crate::books::dto::{BookDto, BookFilter, DuplicateBooksDto, FederatedSearchDto, RelatedBookDto, TagCountDto, TrendingBookDto};
+use crate::books::repository::{BookRepository, TagRepository};
+use crate::catalog::domain::CatalogQueryService;
+use crate::catalog::domain::service::{call_number_range, normalize_isbn, normalize_tags};
+use crate::core::domain::Configuration;
+use crate::core::ids::BookId;
+use crate::core::library::{BookFormat, LibraryError, LibraryResult, PaginatedResult};
+use crate::core::repository::{query_with_consistency, QueryOptions, ReadConsistency};
+use crate::gateway::objects::ObjectStore;
+use crate::gateway::sru::{UnionCatalog, UnionCatalogQuery};
+use crate::projector::domain::model::PopularityEntity;
+use crate::projector::repository::{CoCheckoutRepository, PopularityRepository};
+
+// weights for ranking related books
+const AUTHOR_SCORE: i64 = 3;
+const TAG_SCORE: i64 = 2;
+const CO_CHECKOUT_SCORE: i64 = 1;
+const MAX_CANDIDATES: usize = 100;
+const MAX_REMOVED_TRENDING: usize = 10;
+// books of each format read for new acquisitions before books of other branches and genres are skipped
+const ACQUISITION_PAGE_SIZE: usize = 100;
+const MAX_ACQUISITION_PAGES: usize = 5;
+
+pub(crate) struct CatalogQueryServiceImpl {
+    book_repository: Box<dyn BookRepository>,
+    tag_repository: Box<dyn TagRepository>,
+    co_checkout_repository: Box<dyn CoCheckoutRepository>,
+    popularity_repository: Box<dyn PopularityRepository>,
+    cover_store: Box<dyn ObjectStore>,
+    cover_url_expiry: Duration,
+    union_catalog: Box<dyn UnionCatalog>,
+    union_catalog_timeout: Duration,
+    new_acquisition_days: i64,
+}
+
+impl CatalogQueryServiceImpl {
+    pub(crate) fn new(config: &Configuration,
+                      book_repository: Box<dyn BookRepository>,
+                      tag_repository: Box<dyn TagRepository>,
+                      co_checkout_repository: Box<dyn CoCheckoutRepository>,
+                      popularity_repository: Box<dyn PopularityRepository>,
+                      cover_store: Box<dyn ObjectStore>,
+                      union_catalog: Box<dyn UnionCatalog>) -> Self {
+        Self {
+            book_repository,
+            tag_repository,
+            co_checkout_repository,
+            popularity_repository,
+            cover_store,
+            cover_url_expiry: Duration::from_secs(config.cover_url_seconds),
+            union_catalog,
+            union_catalog_timeout: Duration::from_millis(config.union_catalog_timeout_ms),
+            new_acquisition_days: config.new_acquisition_days,
+        }
+    }
+
+    async fn query_by_isbn(&self, isbn: &str) -> LibraryResult<Vec<BookEntity>> {
+        let res = self.book_repository.query_with(
+            &HashMap::from([("isbn".to_string(), isbn.to_string())]), None, 100, &QueryOptions::eventual()).await?;
+        Ok(res.records)
+    }
+}
+
+#[async_trait]
+impl CatalogQueryService for CatalogQueryServiceImpl {
+    async fn find_book_by_id(&self, id: &BookId) -> LibraryResult<BookDto> {
+        self.book_repository.get(id.as_str()).await.map(|b| BookDto::from(&b))
+    }
+
+    async fn find_book_by_isbn(&self, isbn: &str, consistency: &ReadConsistency) -> LibraryResult<Vec<BookDto>> {
+        let records = query_with_consistency(consistency, |b: &BookEntity, id| b.book_id == id,
+                                             || self.query_by_isbn(isbn)).await?;
+        let mut books: Vec<BookDto> = records.iter().map(BookDto::from).collect();
+        if let Some(expected_id) = consistency.expected_id() {
+            if !books.iter().any(|b| b.book_id == expected_id) {
+                // the index has not caught up with the write so the book is read from the table
+                match self.book_repository.get(expected_id).await {
+                    Ok(book) if book.isbn == isbn => books.push(BookDto::from(&book)),
+                    Ok(_) | Err(LibraryError::NotFound { .. }) => {}
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+        Ok(books)
+    }
+
+    async fn find_books_by_shelf(&self, shelf_location: Option<&str>,
+                                 page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookDto>> {
+        let res = self.book_repository.find_by_shelf(shelf_location.map(|s| s.trim()), page, page_size).await?;
+        let records = res.records.iter().map(BookDto::from).collect();
+        Ok(PaginatedResult::new(page, page_size, res.next_page, records))
+    }
+
+    async fn find_books_by_tag(&self, tag: &str, filter: &BookFilter,
+                               page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookDto>> {
+        let tag = normalize_tags(&[tag.to_string()])?.remove(0);
+        let res = self.book_repository.find_by_tag(tag.as_str(), &filter.predicate(), page, page_size).await?;
+        let records = res.records.iter().map(BookDto::from).collect();
+        Ok(PaginatedResult::new(page, page_size, res.next_page, records))
+    }
+
+    async fn find_books_by_call_number(&self, collection: &str, from_dewey: &str, to_dewey: &str,
+                                       page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookDto>> {
+        let (from, to) = call_number_range(collection, from_dewey, to_dewey)?;
+        let res = self.book_repository.find_by_call_number(from.as_str(), to.as_str(), page, page_size).await?;
+        let records = res.records.iter().map(BookDto::from).collect();
+        Ok(PaginatedResult::new(page, page_size, res.next_page, records))
+    }
+
+    async fn find_books_by_author(&self, author_id: &str, filter: &BookFilter,
+                                  page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookDto>> {
+        let res = self.book_repository.find_by_author_id(author_id.trim(), &filter.predicate(), page, page_size).await?;
+        let records = res.records.iter().map(BookDto::from).collect();
+        Ok(PaginatedResult::new(page, page_size, res.next_page, records))
+    }
+
+    async fn tag_counts(&self) -> LibraryResult<Vec<TagCountDto>> {
+        let tags = self.tag_repository.find_all().await?;
+        Ok(tags.iter().map(|t| TagCountDto::new(t.tag_name.as_str(), t.usage_count)).collect())
+    }
+
+    async fn find_duplicate_books(&self, page_size: usize) -> LibraryResult<Vec<DuplicateBooksDto>> {
+        let mut by_isbn: BTreeMap<String, Vec<BookDto>> = BTreeMap::new();
+        let mut next_page: Option<String> = None;
+        loop {
+            let res = self.book_repository.find_by_shelf(None, next_page.as_deref(), page_size).await?;
+            for book in &res.records {
+                let isbn = normalize_isbn(book.isbn.as_str());
+                if !isbn.is_empty() {
+                    by_isbn.entry(isbn).or_default().push(BookDto::from(book));
+                }
+            }
+            next_page = res.next_page;
+            if next_page.is_none() {
+                break;
+            }
+        }
+        Ok(by_isbn.into_iter().filter(|(_, books)| books.len() > 1).map(|(isbn, mut books)| {
+            books.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.book_id.cmp(&b.book_id)));
+            DuplicateBooksDto::new(isbn.as_str(), books)
+        }).collect())
+    }
+
+    async fn find_related_books(&self, id: &BookId, limit: usize) -> LibraryResult<Vec<RelatedBookDto>> {
+        // recommendations tolerate replication lag so they may be served by a read replica
+        let book = self.book_repository.get_with(id.as_str(), &QueryOptions::eventual()).await?;
+        let mut related: HashMap<String, RelatedBookDto> = HashMap::new();
+
+        // books without an author would otherwise be related to each other
+        let same_author = if book.author_id.is_empty() {
+            vec![]
+        } else {
+            self.book_repository.find_by_author_id(book.author_id.as_str(), &HashMap::new(), None, MAX_CANDIDATES).await?.records
+        };
+        for other in same_author.iter().filter(|b| b.book_id != book.book_id) {
+            let entry = related.entry(other.book_id.to_string())
+                .or_insert_with(|| RelatedBookDto::new(BookDto::from(other)));
+            entry.same_author = true;
+            entry.score += AUTHOR_SCORE;
+        }
+
+        for tag in &book.tags {
+            // tag search filters scanned pages so it may take a few pages to find candidates
+            let mut next_page: Option<String> = None;
+            let mut found = 0;
+            loop {
+                let tagged = self.book_repository.find_by_tag(
+                    tag.as_str(), &HashMap::new(), next_page.as_deref(), MAX_CANDIDATES).await?;
+                for other in tagged.records.iter().filter(|b| b.book_id != book.book_id) {
+                    let entry = related.entry(other.book_id.to_string())
+                        .or_insert_with(|| RelatedBookDto::new(BookDto::from(other)));
+                    entry.shared_tags.push(tag.to_string());
+                    entry.score += TAG_SCORE;
+                    found += 1;
+                }
+                next_page = tagged.next_page;
+                if next_page.is_none() || found >= MAX_CANDIDATES {
+                    break;
+                }
+            }
+        }
+
+        let co_checkouts = self.co_checkout_repository.find_related(id.as_str(), MAX_CANDIDATES).await?;
+        for co_checkout in co_checkouts {
+            if !related.contains_key(co_checkout.related_book_id.as_str()) {
+                // books that were removed from the catalog are not recommended
+                match self.book_repository.get_with(co_checkout.related_book_id.as_str(), &QueryOptions::eventual()).await {
+                    Ok(other) => {
+                        related.insert(other.book_id.to_string(), RelatedBookDto::new(BookDto::from(&other)));
+                    }
+                    Err(LibraryError::NotFound { .. }) => continue,
+                    Err(err) => return Err(err),
+                }
+            }
+            if let Some(entry) = related.get_mut(co_checkout.related_book_id.as_str()) {
+                entry.co_checkout_count = co_checkout.checkout_count;
+                entry.score += CO_CHECKOUT_SCORE * co_checkout.checkout_count;
+            }
+        }
+
+        let mut related: Vec<RelatedBookDto> = related.into_values().collect();
+        related.sort_by(|a, b| b.score.cmp(&a.score).then(a.book.title.cmp(&b.book.title)));
+        related.truncate(limit);
+        Ok(related)
+    }
+
+    async fn find_trending_books(&self, window: &str, limit: usize) -> LibraryResult<Vec<TrendingBookDto>> {
+        if PopularityEntity::half_life_days(window).is_none() {
+            return Err(LibraryError::validation(format!("unknown trending window {}", window).as_str(),
+                                                Some("400".to_string())));
+        }
+        // scores of removed books are left in the index so a few more scores are read than requested
+        let scores = self.popularity_repository.find_top(window, limit + MAX_REMOVED_TRENDING).await?;
+        let now = Utc::now().naive_utc();
+        let mut trending = vec![];
+        for score in scores {
+            match self.book_repository.get_with(score.book_id.as_str(), &QueryOptions::eventual()).await {
+                Ok(book) => trending.push(TrendingBookDto::new(BookDto::from(&book), score.score_at(now))),
+                Err(LibraryError::NotFound { .. }) => continue,
+                Err(err) => return Err(err),
+            }
+            if trending.len() >= limit {
+                break;
+            }
+        }
+        Ok(trending)
+    }
+
+    async fn find_cover_url(&self, id: &BookId) -> LibraryResult<String> {
+        let book = self.book_repository.get(id.as_str()).await?;
+        if book.cover_key.is_empty() {
+            return Err(LibraryError::not_found(format!("book {} has no cover", id).as_str()));
+        }
+        self.cover_store.url(book.cover_key.as_str(), self.cover_url_expiry).await
+    }
+
+    async fn federated_search(&self, q: &str, limit: usize) -> LibraryResult<FederatedSearchDto> {
+        let query = UnionCatalogQuery::parse(q)?;
+        match tokio::time::timeout(self.union_catalog_timeout, self.union_catalog.search(&query, limit)).await {
+            Ok(Ok(mut candidates)) => {
+                candidates.truncate(limit);
+                Ok(FederatedSearchDto::new(q, candidates))
+            }
+            Ok(Err(err)) => {
+                warn!("federated search of {} is degraded due to {}", q, err);
+                Ok(FederatedSearchDto::degraded(q, err.message()))
+            }
+            Err(_) => {
+                warn!("federated search of {} timed out", q);
+                Ok(FederatedSearchDto::degraded(q, format!("union catalog did not respond within {}ms",
+                                                           self.union_catalog_timeout.as_millis()).as_str()))
+            }
+        }
+    }
+
+    // the index of new acquisitions is partitioned by format so the newest books of every format are merged
+    async fn find_new_acquisitions(&self, branch_id: Option<&str>, genre: Option<&str>, limit: usize) -> LibraryResult<Vec<BookDto>> {
+        let genre = match genre {
+            Some(genre) => Some(normalize_tags(&[genre.to_string()])?.remove(0)),
+            None => None,
+        };
+        let since = Utc::now().naive_utc() - chrono::Duration::days(self.new_acquisition_days);
+        let mut books = vec![];
+        for book_format in [BookFormat::Physical, BookFormat::EBook, BookFormat::Audiobook, BookFormat::Serial] {
+            let mut found = 0;
+            let mut page = None;
+            for _ in 0..MAX_ACQUISITION_PAGES {
+                let res = self.book_repository.find_by_created_at(&book_format, since, page.as_deref(), ACQUISITION_PAGE_SIZE).await?;
+                for book in res.records.iter().filter(|b| branch_id.map_or(true, |id| b.branch_id == id) &&
+                    genre.as_ref().map_or(true, |tag| b.tags.contains(tag))).take(limit - found) {
+                    books.push(BookDto::from(book));
+                    found += 1;
+                }
+                page = res.next_page;
+                if found >= limit || page.is_none() {
+                    break;
+                }
+            }
+        }
+        books.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        books.truncate(limit);
+        Ok(books)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::books::dto::{BookDto, BookFilter};
+    use crate::catalog::domain::{CatalogQueryService, CatalogService};
+    use crate::catalog::factory;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{BookFormat, BookStatus};
+    use crate::core::repository::{ReadConsistency, RepositoryStore};
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn CatalogQueryService> {
+        factory::create_catalog_query_service(&Configuration::new("test"), store).await
+    }
+
+    async fn catalog_svc(store: RepositoryStore) -> Box<dyn CatalogService> {
+        factory::create_catalog_service(&Configuration::new("test"), store).await
+    }
+
+    #[tokio::test]
+    async fn test_should_find_books_added_by_command_side() {
+        let store = test_store(module_path!(), "test_should_find_books_added_by_command_side");
+        let query_svc = sut_svc(store).await;
+        let mut book = BookDto::new("query_isbn", "query title", BookStatus::Available);
+        book.tags = vec!["querying".to_string()];
+        let book = catalog_svc(store).await.add_book(&book).await.expect("should add book");
+
+        let loaded = query_svc.find_book_by_id(&book.book_id).await.expect("should find book");
+        assert_eq!(book.title, loaded.title);
+        let res = query_svc.find_book_by_isbn("query_isbn", &ReadConsistency::Eventual).await.expect("should find by isbn");
+        assert!(res.iter().any(|b| b.book_id == book.book_id));
+        let res = query_svc.find_book_by_isbn("query_isbn", &ReadConsistency::expecting(Some(book.book_id.as_str())))
+            .await.expect("should find by isbn");
+        assert_eq!(1, res.iter().filter(|b| b.book_id == book.book_id).count());
+        // an expected book with another isbn is not added to the results
+        let res = query_svc.find_book_by_isbn("other_isbn", &ReadConsistency::expecting(Some(book.book_id.as_str())))
+            .await.expect("should find by isbn");
+        assert!(res.is_empty());
+        let res = query_svc.find_books_by_tag("Querying", &BookFilter::default(), None, 100).await.expect("should find by tag");
+        assert!(res.records.iter().any(|b| b.book_id == book.book_id));
+        let res = query_svc.find_books_by_author(book.author_id.as_str(), &BookFilter::default(), None, 100).await.expect("should find by author");
+        assert_eq!(vec![book.book_id.to_string()], res.records.iter().map(|b| b.book_id.to_string()).collect::<Vec<String>>());
+        let ebooks = BookFilter { book_format: Some(BookFormat::EBook), ..Default::default() };
+        let res = query_svc.find_books_by_author(book.author_id.as_str(), &ebooks, None, 100).await.expect("should find by author");
+        assert!(res.records.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_should_find_new_acquisitions_of_branch_and_genre() {
+        let store = test_store(module_path!(), "test_should_find_new_acquisitions_of_branch_and_genre");
+        let query_svc = sut_svc(store).await;
+        let mut book = BookDto::new("acquisition_isbn", "new acquisition", BookStatus::Available);
+        book.tags = vec!["cozy mystery".to_string()];
+        book.book_format = BookFormat::EBook;
+        book.license_count = 2;
+        let book = catalog_svc(store).await.add_book(&book).await.expect("should add book");
+        let book = catalog_svc(store).await.update_branch(&book.book_id, "acquisition_branch").await.expect("should update branch");
+
+        let res = query_svc.find_new_acquisitions(Some("acquisition_branch"), Some("Cozy Mystery"), 10).await.expect("should find new acquisitions");
+        assert_eq!(vec![book.book_id.to_string()], res.iter().map(|b| b.book_id.to_string()).collect::<Vec<String>>());
+        let res = query_svc.find_new_acquisitions(Some("acquisition_branch"), Some("horror"), 10).await.expect("should find new acquisitions");
+        assert!(res.is_empty());
+        let res = query_svc.find_new_acquisitions(None, None, 500).await.expect("should find new acquisitions");
+        assert!(res.windows(2).all(|w| w[0].created_at >= w[1].created_at));
+    }
+}
@@ -1,61 +1,418 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use async_trait::async_trait;
+use tracing::log::warn;
+use uuid::Uuid;
 use crate::books::domain::model::BookEntity;
-use crate::books::dto::BookDto;
-use crate::books::repository::BookRepository;
-use crate::catalog::domain::CatalogService;
+use crate::books::dto::{BookDto, BookFilter, DuplicateBooksDto, FederatedSearchDto, ImportedBookDto, MarcImportDto, RelatedBookDto, TagCountDto, TrendingBookDto, UnmappedFieldDto};
+use crate::books::repository::{BookRepository, TagRepository};
+use crate::catalog::domain::{CatalogQueryService, CatalogService};
+use crate::catalog::marc::{map_record, parse_marc};
 use crate::core::domain::Configuration;
 use crate::core::events::DomainEvent;
-use crate::core::library::LibraryResult;
+use crate::core::ids::BookId;
+use crate::core::library::{validate_batch, BatchResult, LibraryError, LibraryResult, PaginatedResult};
+use crate::core::repository::ReadConsistency;
+use crate::gateway::objects::ObjectStore;
 use crate::gateway::events::EventPublisher;
 
 pub(crate) struct CatalogServiceImpl {
+    max_cover_bytes: usize,
     book_repository: Box<dyn BookRepository>,
+    tag_repository: Box<dyn TagRepository>,
+    cover_store: Box<dyn ObjectStore>,
     events_publisher: Box<dyn EventPublisher>,
+    query_service: Box<dyn CatalogQueryService>,
 }
 
 impl CatalogServiceImpl {
-    pub(crate) fn new(_config: &Configuration, book_repository: Box<dyn BookRepository>,
-                      events_publisher: Box<dyn EventPublisher>) -> Self {
+    pub(crate) fn new(config: &Configuration, book_repository: Box<dyn BookRepository>,
+                      tag_repository: Box<dyn TagRepository>,
+                      cover_store: Box<dyn ObjectStore>,
+                      events_publisher: Box<dyn EventPublisher>,
+                      query_service: Box<dyn CatalogQueryService>) -> Self {
         Self {
+            max_cover_bytes: config.max_cover_bytes,
             book_repository,
+            tag_repository,
+            cover_store,
             events_publisher,
+            query_service,
         }
     }
+
+    async fn update_tag_counts(&self, tags: &[String], delta: i64) -> LibraryResult<()> {
+        for tag in tags {
+            let _ = self.tag_repository.increment(tag, delta).await?;
+        }
+        Ok(())
+    }
+}
+
+// digital items require concurrent licenses whereas physical items do not use license counters
+pub(crate) fn validate_licenses(book: &BookDto) -> LibraryResult<()> {
+    if book.book_format.is_digital() && book.license_count <= 0 {
+        return Err(LibraryError::validation(format!("digital book {} requires license count",
+                                                    book.book_id).as_str(), Some("400".to_string())));
+    }
+    if !book.book_format.is_digital() && book.license_count != 0 {
+        return Err(LibraryError::validation(format!("physical book {} cannot have licenses",
+                                                    book.book_id).as_str(), Some("400".to_string())));
+    }
+    Ok(())
+}
+
+// tags are case-insensitive so they are stored in lower case without duplicates
+pub(crate) fn normalize_tags(tags: &[String]) -> LibraryResult<Vec<String>> {
+    let mut normalized: Vec<String> = vec![];
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() {
+            return Err(LibraryError::validation("tag cannot be empty", Some("400".to_string())));
+        }
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    Ok(normalized)
+}
+
+// dewey decimal classes range from 000 to 999 with optional decimal subdivisions
+pub(crate) fn validate_dewey(dewey_decimal_id: &str) -> LibraryResult<()> {
+    match dewey_decimal_id.trim().parse::<f64>() {
+        Ok(class) if (0.0..1000.0).contains(&class) => Ok(()),
+        _ => Err(LibraryError::validation(format!("invalid dewey decimal id {}",
+                                                  dewey_decimal_id).as_str(), Some("400".to_string()))),
+    }
+}
+
+// covers must be JPEG, PNG or WebP images whose content matches the declared type, the file extension of the
+// stored image is returned
+pub(crate) fn validate_cover(content_type: &str, content: &[u8], max_bytes: usize) -> LibraryResult<&'static str> {
+    if content.is_empty() {
+        return Err(LibraryError::validation("cover image is empty", Some("400".to_string())));
+    }
+    if content.len() > max_bytes {
+        return Err(LibraryError::validation(format!("cover image of {} bytes exceeds {} bytes",
+                                                    content.len(), max_bytes).as_str(), Some("400".to_string())));
+    }
+    let content_type = content_type.split(';').next().unwrap_or_default().trim().to_lowercase();
+    let (extension, matches) = match content_type.as_str() {
+        "image/jpeg" => ("jpg", content.starts_with(&[0xFF, 0xD8, 0xFF])),
+        "image/png" => ("png", content.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A])),
+        "image/webp" => ("webp", content.len() >= 12 && content.starts_with(b"RIFF") && &content[8..12] == b"WEBP"),
+        _ => return Err(LibraryError::validation(
+            format!("cover content type {} is not supported, use image/jpeg, image/png or image/webp",
+                    content_type).as_str(), Some("400".to_string()))),
+    };
+    if !matches {
+        return Err(LibraryError::validation(format!("cover image is not a valid {}", content_type).as_str(),
+                                            Some("400".to_string())));
+    }
+    Ok(extension)
+}
+
+// call number consists of collection prefix, dewey class padded to three digits and first letters of title
+pub(crate) fn build_call_number(dewey_decimal_id: &str, collection: &str, title: &str) -> String {
+    let dewey = pad_dewey(dewey_decimal_id);
+    let cutter: String = title.chars().filter(|c| c.is_alphanumeric()).take(3).collect::<String>().to_uppercase();
+    [collection.trim().to_uppercase(), dewey, cutter].into_iter()
+        .filter(|part| !part.is_empty()).collect::<Vec<String>>().join(" ")
+}
+
+fn pad_dewey(dewey_decimal_id: &str) -> String {
+    let dewey = dewey_decimal_id.trim();
+    match dewey.split_once('.') {
+        Some((class, subdivision)) => format!("{:0>3}.{}", class, subdivision),
+        None => format!("{:0>3}", dewey),
+    }
+}
+
+// bounds of call numbers shelved in the collection from one dewey class through another, the upper bound is
+// suffixed with ~ that sorts after subdivisions and cutters so that the whole upper class is included
+pub(crate) fn call_number_range(collection: &str, from_dewey: &str, to_dewey: &str) -> LibraryResult<(String, String)> {
+    validate_dewey(from_dewey)?;
+    validate_dewey(to_dewey)?;
+    if from_dewey.trim().parse::<f64>().unwrap_or_default() > to_dewey.trim().parse::<f64>().unwrap_or_default() {
+        return Err(LibraryError::validation(format!("dewey range {} to {} is reversed", from_dewey, to_dewey).as_str(),
+                                            Some("400".to_string())));
+    }
+    let prefix = match collection.trim() {
+        "" => String::new(),
+        collection => format!("{} ", collection.to_uppercase()),
+    };
+    Ok((format!("{}{}", prefix, pad_dewey(from_dewey)), format!("{}{}~", prefix, pad_dewey(to_dewey))))
+}
+
+// isbn without hyphens and spaces, 10 digit isbns are converted to 13 digits so both forms of a title match
+pub(crate) fn normalize_isbn(isbn: &str) -> String {
+    let isbn: String = isbn.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_uppercase();
+    let digits: Vec<u32> = isbn.chars().take(9).filter_map(|c| c.to_digit(10)).collect();
+    if isbn.len() != 10 || digits.len() != 9 || !isbn.ends_with(|c: char| c.is_ascii_digit() || c == 'X') {
+        return isbn;
+    }
+    let mut isbn13: Vec<u32> = vec![9, 7, 8];
+    isbn13.extend(digits);
+    let sum: u32 = isbn13.iter().enumerate().map(|(i, d)| if i % 2 == 0 { *d } else { d * 3 }).sum();
+    isbn13.push((10 - sum % 10) % 10);
+    isbn13.iter().map(|d| d.to_string()).collect()
 }
 
 #[async_trait]
 impl CatalogService for CatalogServiceImpl {
     async fn add_book(&self, book: &BookDto) -> LibraryResult<BookDto> {
-        let _ = self.book_repository.create(&BookEntity::from(book)).await.map(|_| ())?;
+        let mut book = book.clone();
+        book.tags = normalize_tags(&book.tags)?;
+        validate_licenses(&book)?;
+        book.available_licenses = book.license_count;
+        book.collection = book.collection.trim().to_uppercase();
+        book.call_number = build_call_number(book.dewey_decimal_id.as_str(), book.collection.as_str(), book.title.as_str());
+        let _ = self.book_repository.create(&BookEntity::from(&book)).await.map(|_| ())?;
+        self.update_tag_counts(&book.tags, 1).await?;
         let _ = self.events_publisher.publish(&DomainEvent::added(
-            "books", "books", book.book_id.as_str(), &HashMap::new(), book)?).await?;
-        Ok(book.clone())
+            "books", "books", book.book_id.as_str(), &HashMap::new(), &book)?).await?;
+        Ok(book)
     }
 
-    async fn remove_book(&self, id: &str) -> LibraryResult<()> {
-        let res = self.book_repository.delete(id).await.map(|_| ())?;
+    async fn remove_book(&self, id: &BookId) -> LibraryResult<()> {
+        let existing = self.book_repository.get(id.as_str()).await?;
+        let res = self.book_repository.delete(id.as_str()).await.map(|_| ())?;
+        self.update_tag_counts(&existing.tags, -1).await?;
         let data = id.to_string();
         let _ = self.events_publisher.publish(&DomainEvent::deleted(
-            "books", "books", id, &HashMap::new(), &data)?).await?;
+            "books", "books", id.as_str(), &HashMap::new(), &data)?).await?;
         Ok(res)
     }
 
+    async fn remove_books(&self, ids: &[BookId]) -> LibraryResult<BatchResult<String>> {
+        validate_batch(ids.len())?;
+        let mut res = BatchResult::new();
+        for (i, id) in ids.iter().enumerate() {
+            if ids[..i].contains(id) {
+                res.failed(id.as_str(), &LibraryError::validation(
+                    format!("book {} is repeated in the batch", id).as_str(), Some("400".to_string())));
+                continue;
+            }
+            match self.remove_book(id).await {
+                Ok(_) => res.succeeded(id.to_string()),
+                Err(err) => res.failed(id.as_str(), &err),
+            }
+        }
+        Ok(res)
+    }
+
+    async fn import_marc(&self, content: &[u8]) -> LibraryResult<MarcImportDto> {
+        let records = parse_marc(content)?;
+        let mut res = BatchResult::new();
+        let mut unmapped: BTreeMap<String, usize> = BTreeMap::new();
+        // every record is a copy so records sharing an isbn are all added
+        for (i, record) in records.iter().enumerate() {
+            let mapping = map_record(record);
+            for tag in mapping.unmapped {
+                *unmapped.entry(tag).or_default() += 1;
+            }
+            let number = i + 1;
+            match mapping.book {
+                Ok(book) => match self.add_book(&book).await {
+                    Ok(book) => res.succeeded(ImportedBookDto::new(number, mapping.control_number.as_str(), &book)),
+                    Err(err) => res.failed(number.to_string().as_str(), &err),
+                },
+                Err(err) => res.failed(number.to_string().as_str(), &err),
+            }
+        }
+        let unmapped = unmapped.into_iter()
+            .map(|(tag, records)| UnmappedFieldDto::new(tag.as_str(), records)).collect();
+        Ok(MarcImportDto::new(res, unmapped))
+    }
+
     async fn update_book(&self, book: &BookDto) -> LibraryResult<BookDto> {
-        let _ = self.book_repository.update(&BookEntity::from(book)).await.map(|_| ())?;
+        validate_licenses(book)?;
+        let existing = self.book_repository.get(book.book_id.as_str()).await?;
+        // shelving metadata is only changed by update_location
+        let mut book = book.clone();
+        book.dewey_decimal_id = existing.dewey_decimal_id.to_string();
+        book.collection = existing.collection.to_string();
+        book.shelf_location = existing.shelf_location.to_string();
+        book.branch_id = existing.branch_id.to_string();
+        book.call_number = build_call_number(book.dewey_decimal_id.as_str(), book.collection.as_str(), book.title.as_str());
+        let _ = self.book_repository.update(&BookEntity::from(&book)).await.map(|_| ())?;
+        let delta = book.license_count - existing.license_count;
+        if delta != 0 {
+            let _ = self.book_repository.update_licenses(book.book_id.as_str(), delta).await?;
+        }
+        let _ = self.events_publisher.publish(&DomainEvent::updated(
+            "books", "books", book.book_id.as_str(), &HashMap::new(), &book)?).await?;
+        Ok(book)
+    }
+
+    async fn update_location(&self, id: &BookId, dewey_decimal_id: Option<&str>, collection: &str,
+                             shelf_location: &str) -> LibraryResult<BookDto> {
+        let existing = self.book_repository.get(id.as_str()).await?;
+        let dewey_decimal_id = dewey_decimal_id.unwrap_or(existing.dewey_decimal_id.as_str()).trim();
+        validate_dewey(dewey_decimal_id)?;
+        if shelf_location.trim().is_empty() {
+            return Err(LibraryError::validation("shelf location cannot be empty", Some("400".to_string())));
+        }
+        let collection = collection.trim().to_uppercase();
+        let call_number = build_call_number(dewey_decimal_id, collection.as_str(), existing.title.as_str());
+        let _ = self.book_repository.update_location(id.as_str(), dewey_decimal_id, collection.as_str(),
+                                                     shelf_location.trim(), call_number.as_str()).await?;
+        let book = self.find_book_by_id(id).await?;
         let _ = self.events_publisher.publish(&DomainEvent::updated(
-            "books", "books", book.book_id.as_str(), &HashMap::new(), book)?).await?;
-        Ok(book.clone())
+            "books", "books", book.book_id.as_str(), &HashMap::new(), &book)?).await?;
+        Ok(book)
     }
 
-    async fn find_book_by_id(&self, id: &str) -> LibraryResult<BookDto> {
-        self.book_repository.get(id).await.map(|b| BookDto::from(&b))
+    async fn update_branch(&self, id: &BookId, branch_id: &str) -> LibraryResult<BookDto> {
+        let _ = self.book_repository.update_branch(id.as_str(), branch_id).await?;
+        let book = self.find_book_by_id(id).await?;
+        let _ = self.events_publisher.publish(&DomainEvent::updated(
+            "books", "books", book.book_id.as_str(), &HashMap::new(), &book)?).await?;
+        Ok(book)
     }
 
-    async fn find_book_by_isbn(&self, isbn: &str) -> LibraryResult<Vec<BookDto>> {
-        let res = self.book_repository.query(
-            &HashMap::from([("isbn".to_string(), isbn.to_string())]), None, 100).await?;
-        Ok(res.records.iter().map(BookDto::from).collect())
+    async fn add_tags(&self, id: &BookId, tags: &[String]) -> LibraryResult<BookDto> {
+        let tags = normalize_tags(tags)?;
+        let _ = self.book_repository.get(id.as_str()).await?;
+        // only tags that this request added to the book are counted so that concurrent requests count a tag once
+        let added = self.book_repository.add_tags(id.as_str(), &tags).await?;
+        self.update_tag_counts(&added, 1).await?;
+        let book = self.find_book_by_id(id).await?;
+        let _ = self.events_publisher.publish(&DomainEvent::updated(
+            "books", "books", book.book_id.as_str(), &HashMap::new(), &book)?).await?;
+        Ok(book)
+    }
+
+    async fn remove_tags(&self, id: &BookId, tags: &[String]) -> LibraryResult<BookDto> {
+        let tags = normalize_tags(tags)?;
+        let _ = self.book_repository.get(id.as_str()).await?;
+        let removed = self.book_repository.remove_tags(id.as_str(), &tags).await?;
+        self.update_tag_counts(&removed, -1).await?;
+        let book = self.find_book_by_id(id).await?;
+        let _ = self.events_publisher.publish(&DomainEvent::updated(
+            "books", "books", book.book_id.as_str(), &HashMap::new(), &book)?).await?;
+        Ok(book)
+    }
+
+    async fn upload_cover(&self, id: &BookId, content_type: &str, content: Vec<u8>) -> LibraryResult<BookDto> {
+        let existing = self.book_repository.get(id.as_str()).await?;
+        let extension = validate_cover(content_type, &content, self.max_cover_bytes)?;
+        // every upload gets a key of its own so that cached images of the previous cover are not served
+        let cover_key = format!("covers/{}/{}.{}", id, Uuid::new_v4(), extension);
+        self.cover_store.put(cover_key.as_str(), content_type, content).await?;
+        if let Err(err) = self.book_repository.update_cover(id.as_str(), cover_key.as_str()).await {
+            let _ = self.cover_store.delete(cover_key.as_str()).await;
+            return Err(err);
+        }
+        if !existing.cover_key.is_empty() {
+            if let Err(err) = self.cover_store.delete(existing.cover_key.as_str()).await {
+                warn!("failed to delete previous cover {} of {} due to {}", existing.cover_key, id, err);
+            }
+        }
+        let book = self.find_book_by_id(id).await?;
+        let _ = self.events_publisher.publish(&DomainEvent::updated(
+            "books", "books", book.book_id.as_str(), &HashMap::new(), &book)?).await?;
+        Ok(book)
+    }
+
+    async fn merge_books(&self, kept_id: &BookId, merged_ids: &[BookId]) -> LibraryResult<BookDto> {
+        let kept = self.book_repository.get(kept_id.as_str()).await?;
+        let mut duplicates = vec![];
+        for id in merged_ids.iter().filter(|id| *id != kept_id) {
+            let duplicate = self.book_repository.get(id.as_str()).await?;
+            if normalize_isbn(duplicate.isbn.as_str()) != normalize_isbn(kept.isbn.as_str()) {
+                return Err(LibraryError::validation(format!("book {} has another isbn than {}", id, kept_id).as_str(),
+                                                    Some("400".to_string())));
+            }
+            duplicates.push(duplicate);
+        }
+        for duplicate in &duplicates {
+            let _ = self.add_tags(kept_id, &duplicate.tags).await?;
+            if kept.book_format.is_digital() && duplicate.license_count > 0 {
+                let _ = self.book_repository.update_licenses(kept_id.as_str(), duplicate.license_count).await?;
+                // licenses taken by checkouts that were moved to the kept record stay taken
+                for _ in 0..(duplicate.license_count - duplicate.available_licenses) {
+                    let _ = self.book_repository.acquire_license(kept_id.as_str()).await?;
+                }
+            }
+            self.remove_book(&BookId::new(duplicate.book_id.as_str())).await?;
+        }
+        let book = self.find_book_by_id(kept_id).await?;
+        let merged_book_ids = duplicates.iter().map(|b| b.book_id.to_string()).collect::<Vec<String>>().join(",");
+        let _ = self.events_publisher.publish(&DomainEvent::updated(
+            "books_merged", "books", kept_id.as_str(), &HashMap::from([("merged_book_ids".to_string(), merged_book_ids)]),
+            &book)?).await?;
+        Ok(book)
+    }
+
+    async fn acquire_license(&self, id: &BookId) -> LibraryResult<i64> {
+        let book = self.book_repository.get(id.as_str()).await?;
+        if !book.book_format.is_digital() {
+            return Err(LibraryError::validation(format!("book {} is not digital", id).as_str(), Some("400".to_string())));
+        }
+        self.book_repository.acquire_license(id.as_str()).await
+    }
+
+    async fn release_license(&self, id: &BookId) -> LibraryResult<i64> {
+        self.book_repository.release_license(id.as_str()).await
+    }
+}
+
+#[async_trait]
+impl CatalogQueryService for CatalogServiceImpl {
+    async fn find_book_by_id(&self, id: &BookId) -> LibraryResult<BookDto> {
+        self.query_service.find_book_by_id(id).await
+    }
+
+    async fn find_book_by_isbn(&self, isbn: &str, consistency: &ReadConsistency) -> LibraryResult<Vec<BookDto>> {
+        self.query_service.find_book_by_isbn(isbn, consistency).await
+    }
+
+    async fn find_books_by_shelf(&self, shelf_location: Option<&str>,
+                                 page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookDto>> {
+        self.query_service.find_books_by_shelf(shelf_location, page, page_size).await
+    }
+
+    async fn find_books_by_call_number(&self, collection: &str, from_dewey: &str, to_dewey: &str,
+                                       page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookDto>> {
+        self.query_service.find_books_by_call_number(collection, from_dewey, to_dewey, page, page_size).await
+    }
+
+    async fn find_books_by_tag(&self, tag: &str, filter: &BookFilter,
+                               page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookDto>> {
+        self.query_service.find_books_by_tag(tag, filter, page, page_size).await
+    }
+
+    async fn find_books_by_author(&self, author_id: &str, filter: &BookFilter,
+                                  page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookDto>> {
+        self.query_service.find_books_by_author(author_id, filter, page, page_size).await
+    }
+
+    async fn tag_counts(&self) -> LibraryResult<Vec<TagCountDto>> {
+        self.query_service.tag_counts().await
+    }
+
+    async fn find_duplicate_books(&self, page_size: usize) -> LibraryResult<Vec<DuplicateBooksDto>> {
+        self.query_service.find_duplicate_books(page_size).await
+    }
+
+    async fn find_related_books(&self, id: &BookId, limit: usize) -> LibraryResult<Vec<RelatedBookDto>> {
+        self.query_service.find_related_books(id, limit).await
+    }
+
+    async fn find_trending_books(&self, window: &str, limit: usize) -> LibraryResult<Vec<TrendingBookDto>> {
+        self.query_service.find_trending_books(window, limit).await
+    }
+
+    async fn find_cover_url(&self, id: &BookId) -> LibraryResult<String> {
+        self.query_service.find_cover_url(id).await
+    }
+
+    async fn federated_search(&self, q: &str, limit: usize) -> LibraryResult<FederatedSearchDto> {
+        self.query_service.federated_search(q, limit).await
+    }
+
+    async fn find_new_acquisitions(&self, branch_id: Option<&str>, genre: Option<&str>, limit: usize) -> LibraryResult<Vec<BookDto>> {
+        self.query_service.find_new_acquisitions(branch_id, genre, limit).await
     }
 }
 
@@ -64,14 +421,23 @@ impl From<&BookEntity> for BookDto {
         Self {
             dewey_decimal_id: other.dewey_decimal_id.to_string(),
             version: other.version,
-            book_id: other.book_id.to_string(),
+            book_id: BookId::new(other.book_id.as_str()),
             author_id: other.author_id.to_string(),
             publisher_id: other.publisher_id.to_string(),
-            language: other.language.to_string(),
+            language: other.language.clone(),
             isbn: other.isbn.to_string(),
             title: other.title.to_string(),
             book_status: other.book_status,
             restricted: other.restricted,
+            tags: other.tags.clone(),
+            book_format: other.book_format,
+            license_count: other.license_count,
+            available_licenses: other.available_licenses,
+            collection: other.collection.to_string(),
+            shelf_location: other.shelf_location.to_string(),
+            call_number: other.call_number.to_string(),
+            branch_id: other.branch_id.to_string(),
+            cover_key: other.cover_key.to_string(),
             published_at: other.published_at,
             created_at: other.created_at,
             updated_at: other.updated_at,
@@ -87,11 +453,20 @@ impl From<&BookDto> for BookEntity {
             book_id: other.book_id.to_string(),
             author_id: other.author_id.to_string(),
             publisher_id: other.publisher_id.to_string(),
-            language: other.language.to_string(),
+            language: other.language.clone(),
             isbn: other.isbn.to_string(),
             title: other.title.to_string(),
             book_status: other.book_status,
             restricted: other.restricted,
+            tags: other.tags.clone(),
+            book_format: other.book_format,
+            license_count: other.license_count,
+            available_licenses: other.available_licenses,
+            collection: other.collection.to_string(),
+            shelf_location: other.shelf_location.to_string(),
+            call_number: other.call_number.to_string(),
+            branch_id: other.branch_id.to_string(),
+            cover_key: other.cover_key.to_string(),
             published_at: other.published_at,
             created_at: other.created_at,
             updated_at: other.updated_at,
@@ -102,38 +477,36 @@ impl From<&BookDto> for BookEntity {
 
 #[cfg(test)]
 mod tests {
-    use async_once::AsyncOnce;
-    use lazy_static::lazy_static;
-    use crate::books::dto::BookDto;
+    use crate::books::dto::{BookDto, BookFilter};
     use crate::catalog::domain::CatalogService;
+    use crate::catalog::domain::service::{build_call_number, call_number_range, normalize_isbn, normalize_tags, validate_cover, validate_dewey};
     use crate::catalog::factory;
-    use crate::core::library::BookStatus;
+    use crate::core::library::{BatchStatus, BookFormat, BookStatus};
     use crate::core::domain::Configuration;
-    use crate::core::repository::RepositoryStore;
-    use crate::utils::ddb::{build_db_client, delete_table};
+    use crate::core::ids::BookId;
+    use crate::core::repository::{ReadConsistency, RepositoryStore};
+    use crate::utils::testing::test_store;
 
-    lazy_static! {
-        static ref SUT_SVC: AsyncOnce<Box<dyn CatalogService>> = AsyncOnce::new(async {
-                let client = build_db_client(RepositoryStore::LocalDynamoDB).await;
-                let _ = delete_table(&client, "books").await;
-                factory::create_catalog_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await
-            });
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn CatalogService> {
+        factory::create_catalog_service(&Configuration::new("test"), store).await
     }
 
     #[tokio::test]
     async fn test_should_add_book() {
-        let catalog_svc = SUT_SVC.get().await.clone();
+        let store = test_store(module_path!(), "test_should_add_book");
+        let catalog_svc = sut_svc(store).await;
 
         let book = BookDto::new("isbn", "test book", BookStatus::Available);
         let _ = catalog_svc.add_book(&book).await.expect("should add book");
 
-        let loaded = catalog_svc.find_book_by_id(book.book_id.as_str()).await.expect("should return book");
+        let loaded = catalog_svc.find_book_by_id(&book.book_id).await.expect("should return book");
         assert_eq!(book.book_id, loaded.book_id);
     }
 
     #[tokio::test]
     async fn test_should_update_book() {
-        let catalog_svc = SUT_SVC.get().await.clone();
+        let store = test_store(module_path!(), "test_should_update_book");
+        let catalog_svc = sut_svc(store).await;
 
         let mut book = BookDto::new("isbn", "test book", BookStatus::Available);
         let _ = catalog_svc.add_book(&book).await.expect("should add book");
@@ -142,32 +515,236 @@ mod tests {
         book.book_status = BookStatus::CheckedOut;
         let _ = catalog_svc.update_book(&book).await.expect("should update book");
 
-        let loaded = catalog_svc.find_book_by_id(book.book_id.as_str()).await.expect("should return book");
+        let loaded = catalog_svc.find_book_by_id(&book.book_id).await.expect("should return book");
         assert_eq!(book.title, loaded.title);
         assert_eq!(BookStatus::CheckedOut, book.book_status);
     }
 
 
+    #[tokio::test]
+    async fn test_should_acquire_and_release_licenses() {
+        let store = test_store(module_path!(), "test_should_acquire_and_release_licenses");
+        let catalog_svc = sut_svc(store).await;
+
+        let mut book = BookDto::new("isbn_ebook", "test ebook", BookStatus::Available);
+        book.book_format = BookFormat::EBook;
+        assert!(catalog_svc.add_book(&book).await.is_err());
+        book.license_count = 2;
+        let book = catalog_svc.add_book(&book).await.expect("should add ebook");
+        assert_eq!(2, book.available_licenses);
+
+        assert_eq!(1, catalog_svc.acquire_license(&book.book_id).await.expect("should acquire license"));
+        assert_eq!(0, catalog_svc.acquire_license(&book.book_id).await.expect("should acquire license"));
+        assert!(catalog_svc.acquire_license(&book.book_id).await.is_err());
+        assert_eq!(1, catalog_svc.release_license(&book.book_id).await.expect("should release license"));
+
+        let physical = BookDto::new("isbn_physical", "test book", BookStatus::Available);
+        let _ = catalog_svc.add_book(&physical).await.expect("should add book");
+        assert!(catalog_svc.acquire_license(&physical.book_id).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_should_find_by_isbn() {
-        let catalog_svc = SUT_SVC.get().await.clone();
+        let store = test_store(module_path!(), "test_should_find_by_isbn");
+        let catalog_svc = sut_svc(store).await;
 
         let book = BookDto::new("isbn981", "test book", BookStatus::Available);
         let _ = catalog_svc.add_book(&book).await.expect("should add book");
-        let res = catalog_svc.find_book_by_isbn(book.isbn.as_str()).await.expect("should return book");
+        let res = catalog_svc.find_book_by_isbn(book.isbn.as_str(), &ReadConsistency::Eventual).await.expect("should return book");
         assert_eq!(1, res.len());
     }
 
+    #[tokio::test]
+    async fn test_should_add_and_remove_tags() {
+        let store = test_store(module_path!(), "test_should_add_and_remove_tags");
+        let catalog_svc = sut_svc(store).await;
+
+        let mut book = BookDto::new("isbn777", "test book", BookStatus::Available);
+        book.tags = vec!["Science Fiction".to_string()];
+        let book = catalog_svc.add_book(&book).await.expect("should add book");
+        assert_eq!(vec!["science fiction".to_string()], book.tags);
+
+        let tagged = catalog_svc.add_tags(&book.book_id,
+                                          &["space".to_string(), "SPACE".to_string()]).await.expect("should add tags");
+        assert_eq!(2, tagged.tags.len());
+        let res = catalog_svc.find_books_by_tag("Space", &BookFilter::default(), None, 500).await.expect("should find by tag");
+        assert!(res.records.iter().any(|b| b.book_id == book.book_id));
+        let counts = catalog_svc.tag_counts().await.expect("should return tag counts");
+        assert!(counts.iter().any(|t| t.tag == "space" && t.usage_count == 1));
+
+        let untagged = catalog_svc.remove_tags(&book.book_id, &["space".to_string()]).await.expect("should remove tags");
+        assert_eq!(vec!["science fiction".to_string()], untagged.tags);
+        let counts = catalog_svc.tag_counts().await.expect("should return tag counts");
+        assert!(!counts.iter().any(|t| t.tag == "space"));
+    }
+
+    #[tokio::test]
+    async fn test_should_count_concurrently_added_tags_once() {
+        let store = test_store(module_path!(), "test_should_count_concurrently_added_tags_once");
+        let catalog_svc = sut_svc(store).await;
+        let book = catalog_svc.add_book(&BookDto::new("isbn778", "test book", BookStatus::Available))
+            .await.expect("should add book");
+        let tags = ["concurrent_tag".to_string()];
+        let (first, second) = tokio::join!(catalog_svc.add_tags(&book.book_id, &tags),
+                                           catalog_svc.add_tags(&book.book_id, &tags));
+        let _ = first.expect("should add tags");
+        let _ = second.expect("should add tags");
+        let counts = catalog_svc.tag_counts().await.expect("should return tag counts");
+        assert!(counts.iter().any(|t| t.tag == "concurrent_tag" && t.usage_count == 1));
+
+        let (first, second) = tokio::join!(catalog_svc.remove_tags(&book.book_id, &tags),
+                                           catalog_svc.remove_tags(&book.book_id, &tags));
+        let _ = first.expect("should remove tags");
+        let _ = second.expect("should remove tags");
+        let counts = catalog_svc.tag_counts().await.expect("should return tag counts");
+        assert!(!counts.iter().any(|t| t.tag == "concurrent_tag"));
+    }
+
+    #[tokio::test]
+    async fn test_should_find_related_books() {
+        let store = test_store(module_path!(), "test_should_find_related_books");
+        let catalog_svc = sut_svc(store).await;
+
+        let mut book = BookDto::new("isbn555", "test book", BookStatus::Available);
+        book.tags = vec!["related_tag".to_string()];
+        let book = catalog_svc.add_book(&book).await.expect("should add book");
+        let mut same_author = BookDto::new("isbn556", "same author", BookStatus::Available);
+        same_author.author_id = book.author_id.to_string();
+        let same_author = catalog_svc.add_book(&same_author).await.expect("should add book");
+        let mut same_tag = BookDto::new("isbn557", "same tag", BookStatus::Available);
+        same_tag.tags = vec!["related_tag".to_string()];
+        let same_tag = catalog_svc.add_book(&same_tag).await.expect("should add book");
+        let _ = catalog_svc.add_book(&BookDto::new("isbn558", "unrelated", BookStatus::Available)).await.expect("should add book");
+
+        let related = catalog_svc.find_related_books(&book.book_id, 10).await.expect("should find related");
+        assert_eq!(2, related.len());
+        assert_eq!(same_author.book_id, related[0].book.book_id);
+        assert!(related[0].same_author);
+        assert_eq!(same_tag.book_id, related[1].book.book_id);
+        assert_eq!(vec!["related_tag".to_string()], related[1].shared_tags);
+    }
+
+    #[tokio::test]
+    async fn test_should_normalize_tags() {
+        let tags = normalize_tags(&[" Fiction ".to_string(), "fiction".to_string(), "Poetry".to_string()]).expect("should normalize");
+        assert_eq!(vec!["fiction".to_string(), "poetry".to_string()], tags);
+        assert!(normalize_tags(&["  ".to_string()]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_build_call_number() {
+        assert_eq!("REF 042.5 HIS", build_call_number("42.5", "ref", "History of Maps"));
+        assert_eq!("510 ALG", build_call_number("510", "", "Algebra"));
+        assert!(validate_dewey("510.23").is_ok());
+        assert!(validate_dewey("1000").is_err());
+        assert!(validate_dewey("abc").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_normalize_isbn() {
+        assert_eq!("9780306406157", normalize_isbn("978-0-306-40615-7"));
+        assert_eq!("9780306406157", normalize_isbn("0-306-40615-2"));
+        assert_eq!("9780080442907", normalize_isbn("0 08 044290 x"));
+        assert_eq!("ISBN", normalize_isbn("isbn"));
+    }
+
+    #[tokio::test]
+    async fn test_should_build_call_number_range() {
+        assert_eq!(("REF 042".to_string(), "REF 099~".to_string()), call_number_range("ref", "42", "99").expect("should build range"));
+        assert_eq!(("510".to_string(), "510.5~".to_string()), call_number_range("", "510", "510.5").expect("should build range"));
+        assert!(call_number_range("", "600", "500").is_err());
+        assert!(call_number_range("", "500", "1000").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_update_location() {
+        let store = test_store(module_path!(), "test_should_update_location");
+        let catalog_svc = sut_svc(store).await;
+
+        let mut book = BookDto::new("isbn_shelf", "Shelved Book", BookStatus::Available);
+        book.dewey_decimal_id = "510".to_string();
+        let book = catalog_svc.add_book(&book).await.expect("should add book");
+        assert_eq!("510 SHE", book.call_number.as_str());
+
+        assert!(catalog_svc.update_location(&book.book_id, Some("abc"), "ref", "Floor 2").await.is_err());
+        assert!(catalog_svc.update_location(&book.book_id, None, "ref", " ").await.is_err());
+        let _ = catalog_svc.update_location(&book.book_id, Some("510.2"), "ref", "Floor 2, Aisle 5")
+            .await.expect("should update location");
+
+        // location is included in search results
+        let res = catalog_svc.find_book_by_isbn("isbn_shelf", &ReadConsistency::Eventual).await.expect("should return book");
+        let loaded = res.iter().find(|b| b.book_id == book.book_id).expect("should find book");
+        assert_eq!("REF", loaded.collection.as_str());
+        assert_eq!("Floor 2, Aisle 5", loaded.shelf_location.as_str());
+        assert_eq!("REF 510.2 SHE", loaded.call_number.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_should_validate_covers() {
+        let png = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0];
+        assert_eq!("png", validate_cover("image/png", &png, 100).expect("should accept png"));
+        assert_eq!("jpg", validate_cover("Image/JPEG; charset=binary", &[0xFF, 0xD8, 0xFF, 0xE0], 100).expect("should accept jpeg"));
+        assert_eq!("webp", validate_cover("image/webp", b"RIFF\0\0\0\0WEBPVP8 ", 100).expect("should accept webp"));
+        assert!(validate_cover("image/gif", b"GIF89a", 100).is_err());
+        assert!(validate_cover("image/jpeg", &png, 100).is_err());
+        assert!(validate_cover("image/png", &png, 5).is_err());
+        assert!(validate_cover("image/png", &[], 100).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_upload_cover() {
+        let store = test_store(module_path!(), "test_should_upload_cover");
+        let catalog_svc = sut_svc(store).await;
+        let book = catalog_svc.add_book(&BookDto::new("isbn_cover", "covered book", BookStatus::Available))
+            .await.expect("should add book");
+        assert!(catalog_svc.find_cover_url(&book.book_id).await.is_err());
+
+        let png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 1, 2, 3];
+        let first = catalog_svc.upload_cover(&book.book_id, "image/png", png.clone()).await.expect("should upload cover");
+        assert!(first.cover_key.starts_with(format!("covers/{}/", book.book_id).as_str()));
+        let url = catalog_svc.find_cover_url(&book.book_id).await.expect("should return cover url");
+        assert!(url.ends_with(first.cover_key.as_str()));
+
+        // a new cover replaces the previous image
+        let second = catalog_svc.upload_cover(&book.book_id, "image/png", png).await.expect("should upload cover");
+        assert_ne!(first.cover_key, second.cover_key);
+        assert!(catalog_svc.find_cover_url(&book.book_id).await.expect("should return cover url")
+            .ends_with(second.cover_key.as_str()));
+        assert!(catalog_svc.upload_cover(&book.book_id, "text/plain", b"cover".to_vec()).await.is_err());
+        assert!(catalog_svc.upload_cover(&BookId::new("missing"), "image/png", vec![0x89]).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_should_remove_book() {
-        let catalog_svc = SUT_SVC.get().await.clone();
+        let store = test_store(module_path!(), "test_should_remove_book");
+        let catalog_svc = sut_svc(store).await;
 
         let book = BookDto::new("isbn123", "test book", BookStatus::Available);
         let _ = catalog_svc.add_book(&book).await.expect("should add book");
 
-        let _ = catalog_svc.remove_book(book.book_id.as_str()).await.expect("should remove book");
+        let _ = catalog_svc.remove_book(&book.book_id).await.expect("should remove book");
 
-        let loaded = catalog_svc.find_book_by_id(book.book_id.as_str()).await;
+        let loaded = catalog_svc.find_book_by_id(&book.book_id).await;
         assert!(loaded.is_err());
     }
+
+    #[tokio::test]
+    async fn test_should_remove_books() {
+        let store = test_store(module_path!(), "test_should_remove_books");
+        let catalog_svc = sut_svc(store).await;
+
+        let book = BookDto::new("isbn123", "test book", BookStatus::Available);
+        let _ = catalog_svc.add_book(&book).await.expect("should add book");
+
+        let ids = vec![book.book_id.clone(), BookId::new("missing")];
+        let res = catalog_svc.remove_books(&ids).await.expect("should remove books");
+        assert_eq!(BatchStatus::PartiallySucceeded, res.status);
+        assert_eq!(vec![book.book_id.to_string()], res.successes);
+        assert_eq!("missing", res.failures[0].id);
+        assert!(catalog_svc.find_book_by_id(&book.book_id).await.is_err());
+
+        let res = catalog_svc.remove_books(&ids[..1]).await.expect("should remove books");
+        assert_eq!(BatchStatus::Failed, res.status);
+        assert!(catalog_svc.remove_books(&[]).await.is_err());
+    }
 }
@@ -1,12 +1,29 @@
 use crate::books::factory;
-use crate::catalog::domain::CatalogService;
+use crate::catalog::domain::{CatalogQueryService, CatalogService};
+use crate::catalog::domain::query::CatalogQueryServiceImpl;
 use crate::catalog::domain::service::CatalogServiceImpl;
 use crate::core::domain::Configuration;
 use crate::core::repository::RepositoryStore;
-use crate::gateway::factory::create_publisher;
+use crate::gateway::factory::{create_cover_store, create_publisher, create_union_catalog};
+use crate::projector::factory::{create_co_checkout_repository, create_popularity_repository};
+use crate::utils::region::ClientRole;
 
-pub(crate) async fn create_catalog_service(config: &Configuration, store: RepositoryStore) -> Box<dyn CatalogService> {
+pub async fn create_catalog_query_service(config: &Configuration, store: RepositoryStore) -> Box<dyn CatalogQueryService> {
     let book_repo = factory::create_book_repository(store).await;
+    let tag_repo = factory::create_tag_repository_for(store, ClientRole::Read).await;
+    let co_checkout_repo = create_co_checkout_repository(store).await;
+    let popularity_repo = create_popularity_repository(store).await;
+    let cover_store = create_cover_store(config).await;
+    let union_catalog = create_union_catalog(config);
+    Box::new(CatalogQueryServiceImpl::new(config, book_repo, tag_repo, co_checkout_repo, popularity_repo, cover_store,
+                                          union_catalog))
+}
+
+pub async fn create_catalog_service(config: &Configuration, store: RepositoryStore) -> Box<dyn CatalogService> {
+    let book_repo = factory::create_book_repository(store).await;
+    let tag_repo = factory::create_tag_repository(store).await;
+    let cover_store = create_cover_store(config).await;
     let publisher = create_publisher(store.gateway_publisher()).await;
-    Box::new(CatalogServiceImpl::new(config, book_repo, publisher))
+    let query_svc = create_catalog_query_service(config, store).await;
+    Box::new(CatalogServiceImpl::new(config, book_repo, tag_repo, cover_store, publisher, query_svc))
 }
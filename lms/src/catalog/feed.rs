@@ -0,0 +1,117 @@
+use chrono::NaiveDateTime;
+use quick_xml::escape::escape;
+use sha2::{Digest, Sha256};
+use crate::books::dto::BookDto;
+use crate::catalog::domain::CatalogQueryService;
+use crate::core::library::LibraryResult;
+
+const FEED_PATH: &str = "/catalog/feed/new";
+const FEED_ID: &str = "urn:lms:feed:new";
+const ATOM_DATE_FMT: &str = "%Y-%m-%dT%H:%M:%SZ";
+
+// AcquisitionFeed is the Atom document of new acquisitions along with the validators of HTTP caching
+#[derive(Debug, Clone)]
+pub(crate) struct AcquisitionFeed {
+    pub xml: String,
+    pub etag: String,
+    pub last_modified: NaiveDateTime,
+}
+
+// builds the feed of the newest books, feeds of a branch or a genre are separate feeds with their own id
+pub(crate) async fn new_acquisitions_feed(catalog_service: &dyn CatalogQueryService, branch_id: Option<&str>,
+                                          genre: Option<&str>, limit: usize) -> LibraryResult<AcquisitionFeed> {
+    let books = catalog_service.find_new_acquisitions(branch_id, genre, limit).await?;
+    Ok(to_atom(branch_id, genre, &books))
+}
+
+// the feed is updated when a book is added or changed, an empty feed keeps the epoch so that it stays cacheable
+pub(crate) fn to_atom(branch_id: Option<&str>, genre: Option<&str>, books: &[BookDto]) -> AcquisitionFeed {
+    let last_modified = books.iter().map(|b| b.updated_at.max(b.created_at)).max().unwrap_or_default();
+    let mut params = vec![];
+    let mut title = String::from("New acquisitions");
+    if let Some(branch_id) = branch_id {
+        params.push(("branch", branch_id));
+        title.push_str(format!(" at {}", branch_id).as_str());
+    }
+    if let Some(genre) = genre {
+        params.push(("genre", genre));
+        title.push_str(format!(" in {}", genre).as_str());
+    }
+    let query = params.iter().map(|(name, value)| format!("{}={}", name, percent_encode(value)))
+        .collect::<Vec<String>>().join("&");
+    let (id, href) = if query.is_empty() {
+        (FEED_ID.to_string(), FEED_PATH.to_string())
+    } else {
+        (format!("{}?{}", FEED_ID, query), format!("{}?{}", FEED_PATH, query))
+    };
+
+    let mut xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?><feed xmlns=\"http://www.w3.org/2005/Atom\">\
+        <id>{}</id><title>{}</title><updated>{}</updated><author><name>Library Management System</name></author>\
+        <link rel=\"self\" type=\"application/atom+xml\" href=\"{}\"/>",
+                          escape(id.as_str()), escape(title.as_str()), last_modified.format(ATOM_DATE_FMT), escape(href.as_str()));
+    for book in books {
+        xml.push_str(entry(book).as_str());
+    }
+    xml.push_str("</feed>");
+
+    let mut digest = Sha256::new();
+    digest.update(id.as_bytes());
+    for book in books {
+        digest.update(format!("|{}:{}", book.book_id, book.version).as_bytes());
+    }
+    let etag = format!("\"{}\"", digest.finalize().iter().take(16).map(|b| format!("{:02x}", b)).collect::<String>());
+    AcquisitionFeed { xml, etag, last_modified }
+}
+
+// entries link to the book resource of the catalog, tags are the categories of the entry
+fn entry(book: &BookDto) -> String {
+    let mut summary = format!("ISBN {}, {}", book.isbn, book.book_format);
+    if !book.call_number.is_empty() {
+        summary.push_str(format!(", call number {}", book.call_number).as_str());
+    }
+    let author = if book.author_id.is_empty() {
+        String::new()
+    } else {
+        format!("<author><name>{}</name></author>", escape(book.author_id.as_str()))
+    };
+    let categories = book.tags.iter().map(|tag| format!("<category term=\"{}\"/>", escape(tag.as_str()))).collect::<String>();
+    format!("<entry><id>urn:lms:book:{}</id><title>{}</title><updated>{}</updated><published>{}</published>{}\
+        <link rel=\"alternate\" type=\"application/json\" href=\"/catalog/{}\"/>{}<summary>{}</summary></entry>",
+            escape(book.book_id.as_str()), escape(book.title.as_str()), book.updated_at.format(ATOM_DATE_FMT),
+            book.created_at.format(ATOM_DATE_FMT), author, escape(book.book_id.as_str()), categories, escape(summary.as_str()))
+}
+
+fn percent_encode(value: &str) -> String {
+    value.bytes().map(|b| match b {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+        _ => format!("%{:02X}", b),
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::books::dto::BookDto;
+    use crate::catalog::feed::to_atom;
+    use crate::core::library::BookStatus;
+
+    #[tokio::test]
+    async fn test_should_build_atom_feed() {
+        let mut book = BookDto::new("9780441172719", "Dune & Sons", BookStatus::Available);
+        book.author_id = "frank-herbert".to_string();
+        book.tags = vec!["science fiction".to_string()];
+        let feed = to_atom(Some("branch1"), Some("science fiction"), &[book.clone()]);
+        assert!(feed.xml.contains("<id>urn:lms:feed:new?branch=branch1&amp;genre=science%20fiction</id>"));
+        assert!(feed.xml.contains("<title>New acquisitions at branch1 in science fiction</title>"));
+        assert!(feed.xml.contains("<title>Dune &amp; Sons</title>"));
+        assert!(feed.xml.contains("<category term=\"science fiction\"/>"));
+        assert!(feed.xml.contains(format!("href=\"/catalog/{}\"", book.book_id).as_str()));
+        assert_eq!(book.updated_at.max(book.created_at), feed.last_modified);
+
+        // the validator changes with the books of the feed and with the feed itself
+        assert_eq!(feed.etag, to_atom(Some("branch1"), Some("science fiction"), &[book.clone()]).etag);
+        assert_ne!(feed.etag, to_atom(None, None, &[book.clone()]).etag);
+        book.version += 1;
+        assert_ne!(feed.etag, to_atom(Some("branch1"), Some("science fiction"), &[book]).etag);
+        assert_eq!(0, to_atom(None, None, &[]).last_modified.and_utc().timestamp());
+    }
+}
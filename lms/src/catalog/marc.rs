@@ -0,0 +1,410 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use crate::books::dto::BookDto;
+use crate::catalog::domain::service::validate_dewey;
+use crate::core::library::{LibraryError, LibraryResult};
+
+// imports are limited so that mapping and adding all records fits into a single request
+pub(crate) const MAX_IMPORT_RECORDS: usize = 1000;
+
+const LEADER_LEN: usize = 24;
+const DIRECTORY_ENTRY_LEN: usize = 12;
+const FIELD_TERMINATOR: u8 = 0x1E;
+const SUBFIELD_DELIMITER: u8 = 0x1F;
+
+// tags of fields that are mapped to the book, fields with other tags are reported as unmapped
+const MAPPED_TAGS: [&str; 10] = ["001", "008", "020", "041", "082", "100", "245", "260", "264", "650"];
+
+// trailing ISBD punctuation that separates the parts of a field in the source records
+const ISBD_PUNCTUATION: [char; 7] = [' ', '/', ':', ';', ',', '=', '.'];
+
+// MARC 21 language codes (ISO 639-2/B) of the languages in the collections of the legacy system with their ISO
+// 639-1 codes, the ISO 639-2/T variants are accepted as well
+const MARC_LANGUAGES: [(&str, &str); 48] = [
+    ("ara", "ar"), ("ben", "bn"), ("chi", "zh"), ("ces", "cs"), ("cym", "cy"), ("cze", "cs"), ("dan", "da"),
+    ("deu", "de"), ("dut", "nl"), ("ell", "el"), ("eng", "en"), ("fas", "fa"), ("fin", "fi"), ("fra", "fr"),
+    ("fre", "fr"), ("ger", "de"), ("gle", "ga"), ("gre", "el"), ("heb", "he"), ("hin", "hi"), ("hun", "hu"),
+    ("ice", "is"), ("ind", "id"), ("isl", "is"), ("ita", "it"), ("jpn", "ja"), ("kor", "ko"), ("lat", "la"),
+    ("may", "ms"), ("msa", "ms"), ("nld", "nl"), ("nor", "no"), ("per", "fa"), ("pol", "pl"), ("por", "pt"),
+    ("ron", "ro"), ("rum", "ro"), ("rus", "ru"), ("spa", "es"), ("swa", "sw"), ("swe", "sv"), ("tgl", "tl"),
+    ("tha", "th"), ("tur", "tr"), ("ukr", "uk"), ("urd", "ur"), ("vie", "vi"), ("wel", "cy"),
+];
+
+// MarcField is a control field (001-009) with its value or a data field with its subfields, indicators are not
+// used by the mapping so they are not kept
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MarcField {
+    pub tag: String,
+    pub value: String,
+    pub subfields: Vec<(char, String)>,
+}
+
+impl MarcField {
+    fn new(tag: &str) -> Self {
+        Self {
+            tag: tag.to_string(),
+            value: String::new(),
+            subfields: vec![],
+        }
+    }
+}
+
+// MarcRecord is a bibliographic record of an ISO 2709 (MARC21) or MARCXML export of the legacy system
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct MarcRecord {
+    pub leader: String,
+    pub fields: Vec<MarcField>,
+}
+
+impl MarcRecord {
+    fn control(&self, tag: &str) -> Option<&str> {
+        self.fields.iter().find(|field| field.tag == tag).map(|field| field.value.as_str())
+    }
+
+    fn subfields<'a>(&'a self, tag: &'a str, code: char) -> impl Iterator<Item=&'a str> + 'a {
+        self.fields.iter().filter(move |field| field.tag == tag)
+            .flat_map(move |field| field.subfields.iter().filter(move |(c, _)| *c == code))
+            .map(|(_, value)| value.trim()).filter(|value| !value.is_empty())
+    }
+
+    fn subfield<'a>(&'a self, tag: &'a str, code: char) -> Option<&'a str> {
+        self.subfields(tag, code).next()
+    }
+}
+
+// MarcMapping is the book mapped from a record with the tags of fields whose data did not make it into the book,
+// i.e. fields the catalog has no place for and mapped fields with values that could not be used
+#[derive(Debug)]
+pub(crate) struct MarcMapping {
+    pub control_number: String,
+    pub book: LibraryResult<BookDto>,
+    pub unmapped: Vec<String>,
+}
+
+// reads records of a MARCXML collection or of ISO 2709 exchange records, content starting with < is read as XML
+pub(crate) fn parse_marc(content: &[u8]) -> LibraryResult<Vec<MarcRecord>> {
+    let content = content.strip_prefix("\u{feff}".as_bytes()).unwrap_or(content);
+    let start = content.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(content.len());
+    let content = &content[start..];
+    let records = if content.starts_with(b"<") {
+        let xml = std::str::from_utf8(content).map_err(|_| LibraryError::validation(
+            "MARCXML import is not UTF-8 encoded", Some("400".to_string())))?;
+        parse_marcxml(xml)?
+    } else {
+        parse_iso2709(content)?
+    };
+    if records.is_empty() || records.len() > MAX_IMPORT_RECORDS {
+        return Err(LibraryError::validation(format!("MARC import must have between 1 and {} records but had {}",
+                                                    MAX_IMPORT_RECORDS, records.len()).as_str(), Some("400".to_string())));
+    }
+    Ok(records)
+}
+
+// maps title (245 $a $b), isbn (020 $a), author (100 $a), publisher (264 or 260 $b), language (041 $a or 008/35-37),
+// dewey class (082 $a) and subjects (650 $a) as tags, authors and publishers are referenced by slugs of their names
+// such as frank-herbert because the legacy records carry names instead of ids
+pub(crate) fn map_record(record: &MarcRecord) -> MarcMapping {
+    let mut unmapped: Vec<String> = vec![];
+    for field in &record.fields {
+        if !MAPPED_TAGS.contains(&field.tag.as_str()) && !unmapped.contains(&field.tag) {
+            unmapped.push(field.tag.to_string());
+        }
+    }
+    let book = to_book(record, &mut unmapped);
+    unmapped.sort();
+    unmapped.dedup();
+    MarcMapping {
+        control_number: record.control("001").unwrap_or_default().trim().to_string(),
+        book,
+        unmapped,
+    }
+}
+
+fn to_book(record: &MarcRecord, unmapped: &mut Vec<String>) -> LibraryResult<BookDto> {
+    // records in MARC-8 are read as UTF-8 so their diacritics end up as replacement characters
+    if record.fields.iter().any(|field| field.value.contains('\u{fffd}') ||
+        field.subfields.iter().any(|(_, value)| value.contains('\u{fffd}'))) {
+        return Err(LibraryError::validation("record is not UTF-8 encoded, MARC-8 records must be converted first",
+                                            Some("400".to_string())));
+    }
+    let isbn = record.subfield("020", 'a')
+        .and_then(|isbn| isbn.split_whitespace().next()).unwrap_or_default();
+    let title = ['a', 'b'].iter().filter_map(|code| record.subfield("245", *code))
+        .map(trim_isbd).filter(|part| !part.is_empty()).collect::<Vec<&str>>().join(": ");
+    let mut builder = BookDto::builder()
+        .isbn(isbn)
+        .title(title.as_str())
+        .author_id(record.subfield("100", 'a').map(author_slug).unwrap_or_default().as_str())
+        .publisher_id(record.subfield("264", 'b').or_else(|| record.subfield("260", 'b'))
+            .map(|publisher| slug(trim_isbd(publisher))).unwrap_or_default().as_str())
+        .tags(&record.subfields("650", 'a').map(|subject| trim_isbd(subject).to_lowercase())
+            .filter(|subject| !subject.is_empty()).collect::<Vec<String>>());
+
+    let language = record.subfield("041", 'a').map(|code| ("041", code))
+        .or_else(|| record.control("008").and_then(|value| value.get(35..38)).map(|code| ("008", code)));
+    if let Some((tag, code)) = language {
+        match to_language(code) {
+            Some(language) => builder = builder.language(language),
+            // undetermined, multiple and no linguistic content keep the default language
+            None if matches!(code.trim(), "" | "und" | "mul" | "zxx" | "|||") => {}
+            None => unmapped.push(tag.to_string()),
+        }
+    }
+    if let Some(dewey) = record.subfield("082", 'a') {
+        // prime marks of the segmented classification are dropped, e.g. 813/.54 is class 813.54
+        let dewey = dewey.chars().filter(|c| *c != '/' && *c != '\'').collect::<String>();
+        if validate_dewey(dewey.as_str()).is_ok() {
+            builder = builder.dewey_decimal_id(dewey.as_str());
+        } else {
+            unmapped.push("082".to_string());
+        }
+    }
+    builder.build()
+}
+
+fn to_language(code: &str) -> Option<&'static str> {
+    let code = code.trim().to_lowercase();
+    MARC_LANGUAGES.iter().find(|(marc, _)| *marc == code).map(|(_, iso)| *iso)
+}
+
+fn trim_isbd(value: &str) -> &str {
+    value.trim().trim_end_matches(|c| ISBD_PUNCTUATION.contains(&c)).trim()
+}
+
+// personal names are entered inverted, e.g. Herbert, Frank, 1920-1986 is the author frank-herbert
+fn author_slug(name: &str) -> String {
+    let name = trim_isbd(name);
+    match name.split_once(',') {
+        Some((last, rest)) => {
+            let first = rest.split(',').next().unwrap_or_default();
+            slug(format!("{} {}", first, last).as_str())
+        }
+        None => slug(name),
+    }
+}
+
+fn slug(name: &str) -> String {
+    name.to_lowercase().split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty()).collect::<Vec<&str>>().join("-")
+}
+
+// ISO 2709 records start with a leader holding the record length and the base address of data, followed by a
+// directory of 12 byte entries (tag, field length and field offset) and the fields
+fn parse_iso2709(content: &[u8]) -> LibraryResult<Vec<MarcRecord>> {
+    let mut records = vec![];
+    let mut rest = content;
+    loop {
+        // some systems put line breaks between records
+        let start = rest.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(rest.len());
+        rest = &rest[start..];
+        if rest.is_empty() {
+            break;
+        }
+        let number = records.len() + 1;
+        let invalid = |reason: &str| LibraryError::validation(
+            format!("MARC record {} is invalid: {}", number, reason).as_str(), Some("400".to_string()));
+        if rest.len() < LEADER_LEN {
+            return Err(invalid("record is truncated"));
+        }
+        let length = parse_number(&rest[0..5]).ok_or_else(|| invalid("record length is not a number"))?;
+        let base = parse_number(&rest[12..17]).ok_or_else(|| invalid("base address is not a number"))?;
+        if length > rest.len() || base <= LEADER_LEN || base > length {
+            return Err(invalid("record length or base address is out of bounds"));
+        }
+        let (record, remaining) = rest.split_at(length);
+        rest = remaining;
+
+        // the directory ends with a field terminator right before the base address
+        let directory = &record[LEADER_LEN..base - 1];
+        if directory.len() % DIRECTORY_ENTRY_LEN != 0 {
+            return Err(invalid("directory is malformed"));
+        }
+        let mut fields = vec![];
+        for entry in directory.chunks(DIRECTORY_ENTRY_LEN) {
+            let tag = String::from_utf8_lossy(&entry[0..3]).to_string();
+            let data = parse_number(&entry[3..7]).zip(parse_number(&entry[7..12]))
+                .and_then(|(len, offset)| record.get(base + offset..base + offset + len))
+                .ok_or_else(|| invalid(format!("field {} is out of bounds", tag).as_str()))?;
+            let data = data.strip_suffix(&[FIELD_TERMINATOR]).unwrap_or(data);
+            fields.push(parse_iso2709_field(tag.as_str(), data));
+        }
+        records.push(MarcRecord {
+            leader: String::from_utf8_lossy(&record[..LEADER_LEN]).to_string(),
+            fields,
+        });
+    }
+    Ok(records)
+}
+
+fn parse_iso2709_field(tag: &str, data: &[u8]) -> MarcField {
+    let mut field = MarcField::new(tag);
+    if tag.starts_with("00") {
+        field.value = String::from_utf8_lossy(data).to_string();
+        return field;
+    }
+    // the indicators come before the first subfield delimiter
+    field.subfields = data.split(|b| *b == SUBFIELD_DELIMITER).skip(1)
+        .filter(|subfield| !subfield.is_empty())
+        .map(|subfield| (subfield[0] as char, String::from_utf8_lossy(&subfield[1..]).to_string()))
+        .collect();
+    field
+}
+
+fn parse_number(digits: &[u8]) -> Option<usize> {
+    std::str::from_utf8(digits).ok().and_then(|digits| digits.parse::<usize>().ok())
+}
+
+// MARCXML elements are matched by local name so that both default and prefixed (marc:) namespaces are read
+pub(crate) fn parse_marcxml(xml: &str) -> LibraryResult<Vec<MarcRecord>> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+    let mut records = vec![];
+    let mut record: Option<MarcRecord> = None;
+    let mut text = String::new();
+    let mut code: Option<char> = None;
+    loop {
+        match reader.read_event().map_err(xml_error)? {
+            Event::Start(e) => {
+                text.clear();
+                match e.local_name().as_ref() {
+                    b"record" => record = Some(MarcRecord::default()),
+                    b"controlfield" | b"datafield" => if let Some(record) = record.as_mut() {
+                        record.fields.push(MarcField::new(attribute(&e, "tag")?.as_str()));
+                    },
+                    b"subfield" => code = attribute(&e, "code")?.chars().next(),
+                    _ => {}
+                }
+            }
+            Event::Text(e) => text.push_str(e.unescape().map_err(xml_error)?.as_ref()),
+            Event::CData(e) => text.push_str(String::from_utf8_lossy(e.into_inner().as_ref()).as_ref()),
+            Event::End(e) => {
+                let value = std::mem::take(&mut text);
+                let name = e.local_name();
+                if name.as_ref() == b"record" {
+                    records.extend(record.take());
+                } else if let Some(record) = record.as_mut() {
+                    match name.as_ref() {
+                        b"leader" => record.leader = value,
+                        b"controlfield" => if let Some(field) = record.fields.last_mut() {
+                            field.value = value;
+                        },
+                        b"subfield" => if let (Some(field), Some(code)) = (record.fields.last_mut(), code.take()) {
+                            field.subfields.push((code, value));
+                        },
+                        _ => {}
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(records)
+}
+
+fn attribute(e: &BytesStart, name: &str) -> LibraryResult<String> {
+    e.try_get_attribute(name).map_err(xml_error)?
+        .map(|attr| attr.unescape_value().map(|value| value.to_string())).transpose().map_err(xml_error)?
+        .ok_or_else(|| LibraryError::validation(
+            format!("MARCXML element {} has no {} attribute", String::from_utf8_lossy(e.local_name().as_ref()), name).as_str(),
+            Some("400".to_string())))
+}
+
+fn xml_error<E: std::fmt::Display>(err: E) -> LibraryError {
+    LibraryError::validation(format!("MARCXML import is malformed: {}", err).as_str(), Some("400".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::marc::{map_record, parse_marc};
+    use crate::core::library::LibraryError;
+
+    // assembles an ISO 2709 record from control fields and data fields written with $ as subfield delimiter
+    fn iso2709(fields: &[(&str, &str)]) -> Vec<u8> {
+        let mut directory = vec![];
+        let mut data = vec![];
+        for (tag, value) in fields {
+            let field = format!("{}\x1e", value.replace('$', "\x1f"));
+            directory.extend(format!("{}{:04}{:05}", tag, field.len(), data.len()).into_bytes());
+            data.extend(field.into_bytes());
+        }
+        directory.push(0x1e);
+        data.push(0x1d);
+        let base = 24 + directory.len();
+        let mut record = format!("{:05}nam a22{:05}   4500", base + data.len(), base).into_bytes();
+        record.extend(directory);
+        record.extend(data);
+        record
+    }
+
+    #[tokio::test]
+    async fn test_should_map_iso2709_records() {
+        let mut content = iso2709(&[
+            ("001", "ocm00012345"),
+            ("008", "850101s1965    pau           000 1 fre d"),
+            ("020", "  $a0441172717 (pbk.) :$cUSD 7.99"),
+            ("082", "04$a813/.54$222"),
+            ("100", "1 $aHerbert, Frank,$d1920-1986."),
+            ("245", "10$aDune /$cFrank Herbert."),
+            ("260", "  $aPhiladelphia :$bChilton Books,$c1965."),
+            ("500", "  $aFirst edition."),
+            ("650", " 0$aDesert ecology$vFiction.$aSand dunes."),
+        ]);
+        content.push(b'\n');
+        content.extend(iso2709(&[("245", "10$aNo isbn"), ("082", "04$aFIC"), ("041", "0 $axxx")]));
+
+        let records = parse_marc(&content).expect("should parse records");
+        assert_eq!(2, records.len());
+        let mapping = map_record(&records[0]);
+        assert_eq!("ocm00012345", mapping.control_number.as_str());
+        assert_eq!(vec!["500"], mapping.unmapped);
+        let book = mapping.book.expect("should map book");
+        assert_eq!("0441172717", book.isbn.as_str());
+        assert_eq!("Dune", book.title.as_str());
+        assert_eq!("frank-herbert", book.author_id.as_str());
+        assert_eq!("chilton-books", book.publisher_id.as_str());
+        assert_eq!("fr", book.language.as_str());
+        assert_eq!("813.54", book.dewey_decimal_id.as_str());
+        assert_eq!(vec!["desert ecology", "sand dunes"], book.tags);
+
+        let mapping = map_record(&records[1]);
+        assert!(matches!(mapping.book, Err(LibraryError::Validation { .. })));
+        assert_eq!(vec!["041", "082"], mapping.unmapped);
+
+        // truncated records are rejected
+        assert!(parse_marc(&content[..content.len() - 10]).is_err());
+        assert!(parse_marc(b"").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_map_marcxml_records() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <marc:collection xmlns:marc="http://www.loc.gov/MARC21/slim">
+              <marc:record>
+                <marc:leader>00000nam a2200000 a 4500</marc:leader>
+                <marc:controlfield tag="001">42</marc:controlfield>
+                <marc:datafield tag="020" ind1=" " ind2=" "><marc:subfield code="a">978-0-306-40615-7</marc:subfield></marc:datafield>
+                <marc:datafield tag="041" ind1="0" ind2=" "><marc:subfield code="a">ger</marc:subfield></marc:datafield>
+                <marc:datafield tag="245" ind1="1" ind2="0">
+                  <marc:subfield code="a">Nature &amp; science :</marc:subfield>
+                  <marc:subfield code="b">an essay /</marc:subfield>
+                </marc:datafield>
+                <marc:datafield tag="264" ind1=" " ind2="1"><marc:subfield code="b">O'Reilly Media,</marc:subfield></marc:datafield>
+                <marc:datafield tag="856" ind1="4" ind2="0"><marc:subfield code="u">http://example.com</marc:subfield></marc:datafield>
+              </marc:record>
+            </marc:collection>"#;
+        let records = parse_marc(xml.as_bytes()).expect("should parse records");
+        assert_eq!(1, records.len());
+        let mapping = map_record(&records[0]);
+        assert_eq!("42", mapping.control_number.as_str());
+        assert_eq!(vec!["856"], mapping.unmapped);
+        let book = mapping.book.expect("should map book");
+        assert_eq!("978-0-306-40615-7", book.isbn.as_str());
+        assert_eq!("Nature & science: an essay", book.title.as_str());
+        assert_eq!("o-reilly-media", book.publisher_id.as_str());
+        assert_eq!("de", book.language.as_str());
+
+        assert!(parse_marc(b"<collection><record></collection>").is_err());
+        assert!(parse_marc(b"<collection></collection>").is_err());
+    }
+}
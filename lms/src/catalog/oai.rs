@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{NaiveDate, NaiveDateTime};
+use quick_xml::escape::escape;
+use serde::{Deserialize, Serialize};
+use crate::books::dto::BookDto;
+use crate::catalog::domain::CatalogQueryService;
+use crate::core::domain::Configuration;
+use crate::core::ids::BookId;
+use crate::core::library::{LibraryError, LibraryResult};
+
+pub(crate) const OAI_PAGE_SIZE: usize = 100;
+// pages read by a single list request when records are filtered by datestamp, the list is resumed after them
+const MAX_PAGES_PER_REQUEST: usize = 10;
+const REPOSITORY_NAME: &str = "Library Management System";
+const METADATA_PREFIX: &str = "oai_dc";
+const IDENTIFIER_PREFIX: &str = "oai:lms:";
+const DATESTAMP_FMT: &str = "%Y-%m-%dT%H:%M:%SZ";
+const DATE_FMT: &str = "%Y-%m-%d";
+
+// OaiRepository describes the repository in responses, the base URL is the public URL of the OAI-PMH endpoint
+#[derive(Debug, Clone)]
+pub(crate) struct OaiRepository {
+    pub base_url: String,
+    pub admin_email: String,
+}
+
+impl OaiRepository {
+    pub(crate) fn new(config: &Configuration) -> Self {
+        Self {
+            base_url: config.oai_base_url.to_string(),
+            admin_email: config.oai_admin_email.to_string(),
+        }
+    }
+}
+
+// OaiFailure is a protocol error that is answered with an OAI-PMH error or a failure of the catalog
+enum OaiFailure {
+    Protocol { code: &'static str, message: String },
+    Library(LibraryError),
+}
+
+impl From<LibraryError> for OaiFailure {
+    fn from(err: LibraryError) -> Self {
+        OaiFailure::Library(err)
+    }
+}
+
+fn protocol(code: &'static str, message: &str) -> OaiFailure {
+    OaiFailure::Protocol { code, message: message.to_string() }
+}
+
+// resumption tokens carry the pagination cursor of the catalog along with the datestamp range of the list because
+// resumed requests have no other arguments
+#[derive(Debug, Serialize, Deserialize)]
+struct ResumptionToken {
+    cursor: String,
+    from: Option<String>,
+    until: Option<String>,
+}
+
+impl ResumptionToken {
+    fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    fn decode(token: &str) -> Result<Self, OaiFailure> {
+        URL_SAFE_NO_PAD.decode(token.trim()).ok()
+            .and_then(|json| serde_json::from_slice::<ResumptionToken>(&json).ok())
+            .ok_or_else(|| protocol("badResumptionToken", "resumption token is invalid"))
+    }
+}
+
+// answers an OAI-PMH 2.0 request with the arguments of the query string, books are disseminated as unqualified
+// Dublin Core, protocol errors are part of the response and only failures of the catalog are returned as errors
+pub(crate) async fn harvest(catalog_service: &dyn CatalogQueryService, repository: &OaiRepository,
+                            args: &HashMap<String, String>, now: NaiveDateTime) -> LibraryResult<String> {
+    let verb = args.get("verb").map(String::as_str).unwrap_or_default();
+    let res = match verb {
+        "Identify" => identify(repository, args),
+        "ListMetadataFormats" => list_metadata_formats(catalog_service, args).await,
+        "ListSets" => check_arguments(args, &[], &["resumptionToken"])
+            .and(Err(protocol("noSetHierarchy", "the repository does not support sets"))),
+        "GetRecord" => get_record(catalog_service, args).await,
+        "ListRecords" => list(catalog_service, args, false).await,
+        "ListIdentifiers" => list(catalog_service, args, true).await,
+        _ => Err(protocol("badVerb", format!("{} is not a verb of OAI-PMH", verb).as_str())),
+    };
+    let (request, body) = match res {
+        Ok(body) => (request_element(repository, Some(args)), element(verb, body)),
+        Err(OaiFailure::Protocol { code, message }) => {
+            // arguments are only echoed when they are valid
+            let args = if code == "badVerb" || code == "badArgument" { None } else { Some(args) };
+            (request_element(repository, args), format!("<error code=\"{}\">{}</error>", code, escape(message.as_str())))
+        }
+        Err(OaiFailure::Library(err)) => return Err(err),
+    };
+    Ok(format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+        <OAI-PMH xmlns=\"http://www.openarchives.org/OAI/2.0/\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+        xsi:schemaLocation=\"http://www.openarchives.org/OAI/2.0/ http://www.openarchives.org/OAI/2.0/OAI-PMH.xsd\">\
+        <responseDate>{}</responseDate>{}{}</OAI-PMH>", now.format(DATESTAMP_FMT), request, body))
+}
+
+fn identify(repository: &OaiRepository, args: &HashMap<String, String>) -> Result<String, OaiFailure> {
+    check_arguments(args, &[], &[])?;
+    // deleted books are removed from the catalog without trace so deletions are not reported
+    Ok(format!("{}{}{}{}{}{}{}",
+               leaf("repositoryName", REPOSITORY_NAME),
+               leaf("baseURL", repository.base_url.as_str()),
+               leaf("protocolVersion", "2.0"),
+               leaf("adminEmail", repository.admin_email.as_str()),
+               leaf("earliestDatestamp", "1970-01-01T00:00:00Z"),
+               leaf("deletedRecord", "no"),
+               leaf("granularity", "YYYY-MM-DDThh:mm:ssZ")))
+}
+
+async fn list_metadata_formats(catalog_service: &dyn CatalogQueryService,
+                               args: &HashMap<String, String>) -> Result<String, OaiFailure> {
+    check_arguments(args, &[], &["identifier"])?;
+    if let Some(identifier) = args.get("identifier") {
+        let _ = find_book(catalog_service, identifier).await?;
+    }
+    Ok(element("metadataFormat", format!("{}{}{}",
+                                         leaf("metadataPrefix", METADATA_PREFIX),
+                                         leaf("schema", "http://www.openarchives.org/OAI/2.0/oai_dc.xsd"),
+                                         leaf("metadataNamespace", "http://www.openarchives.org/OAI/2.0/oai_dc/"))))
+}
+
+async fn get_record(catalog_service: &dyn CatalogQueryService,
+                    args: &HashMap<String, String>) -> Result<String, OaiFailure> {
+    check_arguments(args, &["identifier", "metadataPrefix"], &[])?;
+    check_metadata_prefix(args)?;
+    let book = find_book(catalog_service, args.get("identifier").map(String::as_str).unwrap_or_default()).await?;
+    Ok(record(&book))
+}
+
+// lists books a page of the catalog at a time, every response of an incomplete list has a resumption token and the
+// last response of a resumed list has an empty one
+async fn list(catalog_service: &dyn CatalogQueryService, args: &HashMap<String, String>,
+              headers_only: bool) -> Result<String, OaiFailure> {
+    let (resumed, from, until) = match args.get("resumptionToken") {
+        Some(token) => {
+            check_arguments(args, &["resumptionToken"], &[])?;
+            let token = ResumptionToken::decode(token)?;
+            (Some(token.cursor), token.from, token.until)
+        }
+        None => {
+            check_arguments(args, &["metadataPrefix"], &["from", "until", "set"])?;
+            check_metadata_prefix(args)?;
+            if args.contains_key("set") {
+                return Err(protocol("noSetHierarchy", "the repository does not support sets"));
+            }
+            (None, args.get("from").cloned(), args.get("until").cloned())
+        }
+    };
+    let from_at = from.as_deref().map(|from| parse_datestamp(from, false)).transpose()?;
+    let until_at = until.as_deref().map(|until| parse_datestamp(until, true)).transpose()?;
+    if let (Some(from_str), Some(until_str)) = (&from, &until) {
+        if from_str.len() != until_str.len() || from_at > until_at {
+            return Err(protocol("badArgument", "from and until must have the same granularity and be in order"));
+        }
+    }
+
+    let mut books = vec![];
+    let mut cursor = resumed.clone();
+    for _ in 0..MAX_PAGES_PER_REQUEST {
+        let page = catalog_service.find_books_by_shelf(None, cursor.as_deref(), OAI_PAGE_SIZE).await?;
+        books.extend(page.records.into_iter().filter(|book|
+            from_at.map_or(true, |from| book.updated_at >= from) && until_at.map_or(true, |until| book.updated_at <= until)));
+        cursor = page.next_page;
+        if !books.is_empty() || cursor.is_none() {
+            break;
+        }
+    }
+    if books.is_empty() && cursor.is_none() && resumed.is_none() {
+        return Err(protocol("noRecordsMatch", "no books match the arguments"));
+    }
+    let mut body = books.iter().map(|book| if headers_only { header(book) } else { record(book) }).collect::<String>();
+    match cursor {
+        Some(cursor) => body.push_str(leaf("resumptionToken", ResumptionToken { cursor, from, until }.encode().as_str()).as_str()),
+        None if resumed.is_some() => body.push_str("<resumptionToken/>"),
+        None => {}
+    }
+    Ok(body)
+}
+
+async fn find_book(catalog_service: &dyn CatalogQueryService, identifier: &str) -> Result<BookDto, OaiFailure> {
+    let unknown = || protocol("idDoesNotExist", format!("{} is not an identifier of the repository", identifier).as_str());
+    let book_id = identifier.strip_prefix(IDENTIFIER_PREFIX).filter(|id| !id.is_empty()).ok_or_else(unknown)?;
+    match catalog_service.find_book_by_id(&BookId::new(book_id)).await {
+        Ok(book) => Ok(book),
+        Err(LibraryError::NotFound { .. }) => Err(unknown()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+// arguments other than the verb must be required or optional arguments of the verb
+fn check_arguments(args: &HashMap<String, String>, required: &[&str], optional: &[&str]) -> Result<(), OaiFailure> {
+    if let Some(illegal) = args.keys().find(|key| key.as_str() != "verb" &&
+        !required.contains(&key.as_str()) && !optional.contains(&key.as_str())) {
+        return Err(protocol("badArgument", format!("{} is not an argument of the verb", illegal).as_str()));
+    }
+    if let Some(missing) = required.iter().find(|key| args.get(**key).map_or(true, |value| value.trim().is_empty())) {
+        return Err(protocol("badArgument", format!("{} is required", missing).as_str()));
+    }
+    Ok(())
+}
+
+fn check_metadata_prefix(args: &HashMap<String, String>) -> Result<(), OaiFailure> {
+    match args.get("metadataPrefix").map(String::as_str) {
+        Some(METADATA_PREFIX) => Ok(()),
+        prefix => Err(protocol("cannotDisseminateFormat",
+                               format!("{} is not supported, use {}", prefix.unwrap_or_default(), METADATA_PREFIX).as_str())),
+    }
+}
+
+// datestamps are days or seconds in UTC, a day bound covers the whole day
+fn parse_datestamp(value: &str, until: bool) -> Result<NaiveDateTime, OaiFailure> {
+    let invalid = || protocol("badArgument", format!("{} is not a datestamp", value).as_str());
+    if value.len() == 10 {
+        let date = NaiveDate::parse_from_str(value, DATE_FMT).map_err(|_| invalid())?;
+        let at = if until { date.and_hms_opt(23, 59, 59) } else { date.and_hms_opt(0, 0, 0) };
+        return at.ok_or_else(invalid);
+    }
+    NaiveDateTime::parse_from_str(value, DATESTAMP_FMT).map_err(|_| invalid())
+}
+
+fn request_element(repository: &OaiRepository, args: Option<&HashMap<String, String>>) -> String {
+    let mut attributes = args.map(|args| args.iter().collect::<Vec<(&String, &String)>>()).unwrap_or_default();
+    attributes.sort();
+    let attributes = attributes.iter()
+        .map(|(key, value)| format!(" {}=\"{}\"", escape(key.as_str()), escape(value.as_str()))).collect::<String>();
+    format!("<request{}>{}</request>", attributes, escape(repository.base_url.as_str()))
+}
+
+fn header(book: &BookDto) -> String {
+    element("header", format!("{}{}",
+                              leaf("identifier", format!("{}{}", IDENTIFIER_PREFIX, book.book_id).as_str()),
+                              leaf("datestamp", book.updated_at.format(DATESTAMP_FMT).to_string().as_str())))
+}
+
+// authors and publishers are the ids the catalog references them by
+fn record(book: &BookDto) -> String {
+    let mut dc = leaf("dc:title", book.title.as_str());
+    for (name, value) in [("dc:creator", book.author_id.as_str()), ("dc:publisher", book.publisher_id.as_str())] {
+        if !value.is_empty() {
+            dc.push_str(leaf(name, value).as_str());
+        }
+    }
+    for tag in &book.tags {
+        dc.push_str(leaf("dc:subject", tag.as_str()).as_str());
+    }
+    if !book.dewey_decimal_id.is_empty() {
+        dc.push_str(leaf("dc:subject", format!("DDC {}", book.dewey_decimal_id).as_str()).as_str());
+    }
+    dc.push_str(leaf("dc:type", "Text").as_str());
+    dc.push_str(leaf("dc:format", book.book_format.to_string().as_str()).as_str());
+    dc.push_str(leaf("dc:language", book.language.as_str()).as_str());
+    dc.push_str(leaf("dc:identifier", format!("urn:isbn:{}", book.isbn).as_str()).as_str());
+    element("record", format!(
+        "{}<metadata><oai_dc:dc xmlns:oai_dc=\"http://www.openarchives.org/OAI/2.0/oai_dc/\" \
+        xmlns:dc=\"http://purl.org/dc/elements/1.1/\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+        xsi:schemaLocation=\"http://www.openarchives.org/OAI/2.0/oai_dc/ http://www.openarchives.org/OAI/2.0/oai_dc.xsd\">\
+        {}</oai_dc:dc></metadata>", header(book), dc))
+}
+
+fn element(name: &str, body: String) -> String {
+    format!("<{0}>{1}</{0}>", name, body)
+}
+
+fn leaf(name: &str, value: &str) -> String {
+    format!("<{0}>{1}</{0}>", name, escape(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use crate::books::dto::BookDto;
+    use crate::catalog::oai::{parse_datestamp, record, ResumptionToken};
+
+    #[tokio::test]
+    async fn test_should_write_dublin_core_record() {
+        let book = BookDto::builder().isbn("9780441172719").title("Dune & Sons").author_id("frank-herbert")
+            .dewey_decimal_id("813.54").tags(&["science fiction".to_string()]).build().expect("should build book");
+        let xml = record(&book);
+        assert!(xml.contains(format!("<identifier>oai:lms:{}</identifier>", book.book_id).as_str()));
+        assert!(xml.contains("<dc:title>Dune &amp; Sons</dc:title><dc:creator>frank-herbert</dc:creator>\
+            <dc:subject>science fiction</dc:subject><dc:subject>DDC 813.54</dc:subject>"));
+        assert!(xml.contains("<dc:language>en</dc:language><dc:identifier>urn:isbn:9780441172719</dc:identifier>"));
+        assert!(!xml.contains("dc:publisher"));
+    }
+
+    #[tokio::test]
+    async fn test_should_parse_datestamps_and_tokens() {
+        let day = NaiveDate::from_ymd_opt(2024, 3, 1).expect("should be a date");
+        assert_eq!(day.and_hms_opt(0, 0, 0), parse_datestamp("2024-03-01", false).ok());
+        assert_eq!(day.and_hms_opt(23, 59, 59), parse_datestamp("2024-03-01", true).ok());
+        assert_eq!(day.and_hms_opt(8, 30, 0), parse_datestamp("2024-03-01T08:30:00Z", false).ok());
+        assert!(parse_datestamp("2024-03-01T08:30", false).is_err());
+
+        let token = ResumptionToken { cursor: "cursor".to_string(), from: Some("2024-03-01".to_string()), until: None };
+        let decoded = ResumptionToken::decode(token.encode().as_str()).ok().expect("should decode token");
+        assert_eq!("cursor", decoded.cursor.as_str());
+        assert_eq!(Some("2024-03-01".to_string()), decoded.from);
+        assert!(ResumptionToken::decode("not a token").is_err());
+    }
+}
@@ -0,0 +1,65 @@
+use crate::books::dto::BookDto;
+use crate::catalog::domain::CatalogQueryService;
+use crate::core::library::LibraryResult;
+
+const SHELF_LIST_PAGE_SIZE: usize = 200;
+const SHELF_LIST_HEADER: &str = "call_number,title,isbn,book_id,book_status,branch_id,shelf_location";
+
+// reads all pages of the call number range so that staff can walk the shelves with a single list
+pub(crate) async fn export_shelf_list(catalog_service: &dyn CatalogQueryService, collection: &str,
+                                      from_dewey: &str, to_dewey: &str) -> LibraryResult<String> {
+    let mut books = vec![];
+    let mut next_page: Option<String> = None;
+    loop {
+        let res = catalog_service.find_books_by_call_number(collection, from_dewey, to_dewey,
+                                                            next_page.as_deref(), SHELF_LIST_PAGE_SIZE).await?;
+        books.extend(res.records);
+        next_page = res.next_page;
+        if next_page.is_none() {
+            break;
+        }
+    }
+    Ok(to_csv(&books))
+}
+
+// one row per copy with the header line, fields with commas, quotes or line breaks are quoted
+pub(crate) fn to_csv(books: &[BookDto]) -> String {
+    let mut csv = String::from(SHELF_LIST_HEADER);
+    csv.push('\n');
+    for book in books {
+        let row = [book.call_number.as_str(), book.title.as_str(), book.isbn.as_str(), book.book_id.as_str(),
+            book.book_status.to_string().as_str(), book.branch_id.as_str(), book.shelf_location.as_str()]
+            .iter().map(|field| escape(field)).collect::<Vec<String>>().join(",");
+        csv.push_str(row.as_str());
+        csv.push('\n');
+    }
+    csv
+}
+
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::books::dto::BookDto;
+    use crate::catalog::shelf_list::to_csv;
+    use crate::core::library::BookStatus;
+
+    #[tokio::test]
+    async fn test_should_write_shelf_list_csv() {
+        let mut book = BookDto::new("isbn1", "Maps, \"Old\" and New", BookStatus::Available);
+        book.call_number = "REF 042 MAP".to_string();
+        book.shelf_location = "Floor 2".to_string();
+        let csv = to_csv(&[book.clone()]);
+        let lines = csv.lines().collect::<Vec<&str>>();
+        assert_eq!("call_number,title,isbn,book_id,book_status,branch_id,shelf_location", lines[0]);
+        assert_eq!(format!("REF 042 MAP,\"Maps, \"\"Old\"\" and New\",isbn1,{},Available,,Floor 2", book.book_id),
+                   lines[1]);
+        assert_eq!(2, lines.len());
+    }
+}
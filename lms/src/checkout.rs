@@ -1,6 +1,7 @@
 pub mod domain;
 pub mod command;
 pub mod dto;
+pub mod printer;
 pub mod factory;
 pub mod repository;
 pub mod controller;
@@ -1,2 +1,8 @@
+pub mod check_in_cmd;
 pub mod checkout_book_cmd;
+pub mod checkout_books_cmd;
+pub mod find_recent_checkouts_cmd;
+pub mod fulfill_hold_cmd;
+pub mod get_receipt_cmd;
 pub mod return_book_cmd;
+pub mod return_expired_cmd;
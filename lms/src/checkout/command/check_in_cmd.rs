@@ -0,0 +1,102 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::checkout::domain::CheckoutService;
+use crate::checkout::dto::CheckInDto;
+use crate::core::command::{Command, CommandError};
+use crate::core::ids::BookId;
+
+pub(crate) struct CheckInCommand {
+    checkout_service: Box<dyn CheckoutService>,
+}
+
+impl CheckInCommand {
+    pub(crate) fn new(checkout_service: Box<dyn CheckoutService>) -> Self {
+        Self {
+            checkout_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CheckInCommandRequest {
+    book_id: String,
+    #[serde(default)]
+    branch_id: Option<String>,
+    checked_in_by: String,
+}
+
+impl CheckInCommandRequest {
+    pub fn new(book_id: &str, branch_id: Option<&str>, checked_in_by: &str) -> Self {
+        Self {
+            book_id: book_id.to_string(),
+            branch_id: branch_id.map(|b| b.to_string()),
+            checked_in_by: checked_in_by.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CheckInCommandResponse {
+    check_in: CheckInDto,
+}
+
+impl CheckInCommandResponse {
+    pub fn new(check_in: CheckInDto) -> Self {
+        Self {
+            check_in,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<CheckInCommandRequest, CheckInCommandResponse> for CheckInCommand {
+    async fn execute(&self, req: CheckInCommandRequest) -> Result<CheckInCommandResponse, CommandError> {
+        self.checkout_service.check_in(&BookId::new(req.book_id.as_str()), req.branch_id.as_deref(), req.checked_in_by.as_str())
+            .await.map_err(CommandError::from).map(CheckInCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::books::domain::model::BookEntity;
+    use crate::books::factory::create_book_repository;
+    use crate::checkout::command::check_in_cmd::{CheckInCommand, CheckInCommandRequest};
+    use crate::checkout::factory::create_checkout_service;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::ids::{BookId, PatronId};
+    use crate::core::library::{BookStatus, ItemRouting, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn sut_cmd(store: RepositoryStore) -> CheckInCommand {
+        let svc = create_checkout_service(&Configuration::new("test"), store).await;
+        CheckInCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_check_in() {
+        let store = test_store(module_path!(), "test_should_run_check_in");
+        let cmd = sut_cmd(store).await;
+        let svc = create_checkout_service(&Configuration::new("test"), store).await;
+
+        let patron = PartyEntity::new(PartyKind::Patron, "check_in_cmd_patron@example.com");
+        let mut librarian = PartyEntity::new(PartyKind::Employee, "check_in_cmd_librarian@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let party_repo = create_party_repository(store).await;
+        for party in [&patron, &librarian] {
+            let _ = party_repo.create(party).await.expect("should create party");
+        }
+        let book = BookEntity::new("isbn", "check in title", BookStatus::Available);
+        let _ = create_book_repository(store).await.create(&book).await.expect("should create book");
+        let _ = svc.checkout(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.expect("should checkout");
+
+        let res = cmd.execute(CheckInCommandRequest::new(book.book_id.as_str(), Some("remote"), librarian.party_id.as_str()))
+            .await.expect("should check in");
+        assert_eq!(ItemRouting::Transfer, res.check_in.routing);
+        assert_eq!("test", res.check_in.destination_branch_id.as_str());
+        assert_eq!(book.book_id, res.check_in.checkout.book_id);
+    }
+}
@@ -1,8 +1,10 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use crate::audit::dto::StaffOverrideDto;
 use crate::checkout::domain::CheckoutService;
 use crate::checkout::dto::CheckoutDto;
 use crate::core::command::{Command, CommandError};
+use crate::core::ids::{BookId, PatronId};
 
 pub(crate) struct CheckoutBookCommand {
     checkout_service: Box<dyn CheckoutService>,
@@ -16,10 +18,13 @@ impl CheckoutBookCommand {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct CheckoutBookCommandRequest {
     patron_id: String,
     book_id: String,
+    // librarians can supply an override to bypass policy rejections
+    #[serde(default)]
+    staff_override: Option<StaffOverrideDto>,
 }
 
 impl CheckoutBookCommandRequest {
@@ -27,12 +32,13 @@ impl CheckoutBookCommandRequest {
         Self {
             patron_id,
             book_id,
+            staff_override: None,
         }
     }
 }
 
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct CheckoutBookCommandResponse {
     checkout: CheckoutDto,
 }
@@ -48,15 +54,18 @@ impl CheckoutBookCommandResponse {
 #[async_trait]
 impl Command<CheckoutBookCommandRequest, CheckoutBookCommandResponse> for CheckoutBookCommand {
     async fn execute(&self, req: CheckoutBookCommandRequest) -> Result<CheckoutBookCommandResponse, CommandError> {
-        self.checkout_service.checkout(req.patron_id.as_str(), req.book_id.as_str())
-            .await.map_err(CommandError::from).map(CheckoutBookCommandResponse::new)
+        if let Some(staff_override) = req.staff_override {
+            self.checkout_service.checkout_with_override(&PatronId::new(req.patron_id.as_str()), &BookId::new(req.book_id.as_str()), &staff_override)
+                .await.map_err(CommandError::from).map(CheckoutBookCommandResponse::new)
+        } else {
+            self.checkout_service.checkout(&PatronId::new(req.patron_id.as_str()), &BookId::new(req.book_id.as_str()))
+                .await.map_err(CommandError::from).map(CheckoutBookCommandResponse::new)
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use async_once::AsyncOnce;
-    use lazy_static::lazy_static;
     use crate::books::dto::BookDto;
     use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
     use crate::catalog::factory::create_catalog_service;
@@ -69,27 +78,29 @@ mod tests {
     use crate::patrons::command::add_patron_cmd::{AddPatronCommand, AddPatronCommandRequest};
     use crate::patrons::dto::PatronDto;
     use crate::patrons::factory::create_patron_service;
+    use crate::utils::testing::test_store;
+
+    async fn build_book_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_patron_cmd(store: RepositoryStore) -> AddPatronCommand {
+        let svc = create_patron_service(&Configuration::new("test"), store).await;
+        AddPatronCommand::new(svc)
+    }
 
-    lazy_static! {
-        static ref BOOK_CMD : AsyncOnce<AddBookCommand> = AsyncOnce::new(async {
-                let svc = create_catalog_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                AddBookCommand::new(svc)
-            });
-        static ref PATRON_CMD : AsyncOnce<AddPatronCommand> = AsyncOnce::new(async {
-                let svc = create_patron_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                AddPatronCommand::new(svc)
-            });
-        static ref CHECKOUT_CMD : AsyncOnce<CheckoutBookCommand> = AsyncOnce::new(async {
-                let svc = create_checkout_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                CheckoutBookCommand::new(svc)
-            });
+    async fn build_checkout_cmd(store: RepositoryStore) -> CheckoutBookCommand {
+        let svc = create_checkout_service(&Configuration::new("test"), store).await;
+        CheckoutBookCommand::new(svc)
     }
 
     #[tokio::test]
     async fn test_should_run_checkout_book() {
-        let patron_cmd: &AddPatronCommand = PATRON_CMD.get().await.clone();
-        let book_cmd: &AddBookCommand = BOOK_CMD.get().await.clone();
-        let checkout_cmd: &CheckoutBookCommand = CHECKOUT_CMD.get().await.clone();
+        let store = test_store(module_path!(), "test_should_run_checkout_book");
+        let patron_cmd = build_patron_cmd(store).await;
+        let book_cmd = build_book_cmd(store).await;
+        let checkout_cmd = build_checkout_cmd(store).await;
 
         let patron = PatronDto::new("email");
         let _ = patron_cmd.execute(AddPatronCommandRequest::new(patron.email.as_str())).await.expect("should add patron");
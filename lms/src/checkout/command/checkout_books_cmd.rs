@@ -0,0 +1,119 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::audit::dto::StaffOverrideDto;
+use crate::checkout::domain::CheckoutService;
+use crate::checkout::dto::CheckoutDto;
+use crate::core::command::{Command, CommandError};
+use crate::core::ids::{BookId, PatronId};
+use crate::core::library::{BatchResult, BatchStatus};
+
+pub(crate) struct CheckoutBooksCommand {
+    checkout_service: Box<dyn CheckoutService>,
+}
+
+impl CheckoutBooksCommand {
+    pub(crate) fn new(checkout_service: Box<dyn CheckoutService>) -> Self {
+        Self {
+            checkout_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CheckoutBooksCommandRequest {
+    patron_id: String,
+    book_ids: Vec<String>,
+    // librarians can supply an override to bypass policy rejections of all books
+    #[serde(default)]
+    staff_override: Option<StaffOverrideDto>,
+}
+
+impl CheckoutBooksCommandRequest {
+    pub fn new(patron_id: String, book_ids: Vec<String>) -> Self {
+        Self {
+            patron_id,
+            book_ids,
+            staff_override: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CheckoutBooksCommandResponse {
+    #[serde(flatten)]
+    result: BatchResult<CheckoutDto>,
+}
+
+impl CheckoutBooksCommandResponse {
+    pub fn new(result: BatchResult<CheckoutDto>) -> Self {
+        Self {
+            result,
+        }
+    }
+
+    pub(crate) fn status(&self) -> BatchStatus {
+        self.result.status
+    }
+}
+
+#[async_trait]
+impl Command<CheckoutBooksCommandRequest, CheckoutBooksCommandResponse> for CheckoutBooksCommand {
+    async fn execute(&self, req: CheckoutBooksCommandRequest) -> Result<CheckoutBooksCommandResponse, CommandError> {
+        let book_ids = req.book_ids.iter().map(|id| BookId::new(id)).collect::<Vec<_>>();
+        self.checkout_service.checkout_all(&PatronId::new(req.patron_id.as_str()), &book_ids, req.staff_override.as_ref())
+            .await.map_err(CommandError::from).map(CheckoutBooksCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::books::dto::BookDto;
+    use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
+    use crate::catalog::factory::create_catalog_service;
+    use crate::checkout::command::checkout_books_cmd::{CheckoutBooksCommand, CheckoutBooksCommandRequest};
+    use crate::checkout::factory::create_checkout_service;
+    use crate::core::command::Command;
+    use crate::core::library::{BatchStatus, BookStatus};
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::patrons::command::add_patron_cmd::{AddPatronCommand, AddPatronCommandRequest};
+    use crate::patrons::factory::create_patron_service;
+    use crate::utils::testing::test_store;
+
+    async fn build_book_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_patron_cmd(store: RepositoryStore) -> AddPatronCommand {
+        let svc = create_patron_service(&Configuration::new("test"), store).await;
+        AddPatronCommand::new(svc)
+    }
+
+    async fn build_checkout_cmd(store: RepositoryStore) -> CheckoutBooksCommand {
+        let svc = create_checkout_service(&Configuration::new("test"), store).await;
+        CheckoutBooksCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_checkout_books() {
+        let store = test_store(module_path!(), "test_should_run_checkout_books");
+        let patron_cmd = build_patron_cmd(store).await;
+        let book_cmd = build_book_cmd(store).await;
+        let checkout_cmd = build_checkout_cmd(store).await;
+
+        let patron = patron_cmd.execute(AddPatronCommandRequest::new("email")).await.expect("should add patron").patron;
+        let mut book_ids = vec![];
+        for isbn in ["isbn1", "isbn2"] {
+            let book = BookDto::new(isbn, "test book", BookStatus::Available);
+            let res = book_cmd.execute(AddBookCommandRequest::new(book.isbn.as_str(), book.title.as_str()))
+                .await.expect("should add book");
+            book_ids.push(res.book.book_id.to_string());
+        }
+        let res = checkout_cmd.execute(CheckoutBooksCommandRequest::new(
+            patron.patron_id.to_string(), book_ids.clone())).await.expect("should checkout books");
+        assert_eq!(BatchStatus::Succeeded, res.status());
+        assert_eq!(book_ids, res.result.successes.iter().map(|c| c.book_id.to_string()).collect::<Vec<String>>());
+    }
+}
@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::checkout::domain::CheckoutQueryService;
+use crate::checkout::dto::CheckoutDto;
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+// recent checkouts are those of the last day unless the request starts earlier
+const DEFAULT_RECENT_HOURS: i64 = 24;
+
+pub(crate) struct FindRecentCheckoutsCommand {
+    checkout_service: Box<dyn CheckoutQueryService>,
+}
+
+impl FindRecentCheckoutsCommand {
+    pub(crate) fn new(checkout_service: Box<dyn CheckoutQueryService>) -> Self {
+        Self {
+            checkout_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindRecentCheckoutsCommandRequest {
+    // defaults to the branch of the configuration
+    #[serde(default)]
+    pub(crate) branch_id: String,
+    pub(crate) since: Option<DateTime<Utc>>,
+    pub(crate) until: Option<DateTime<Utc>>,
+    pub(crate) page: Option<String>,
+    pub(crate) page_size: Option<usize>,
+}
+
+impl FindRecentCheckoutsCommandRequest {
+    pub fn new(branch_id: &str) -> Self {
+        Self {
+            branch_id: branch_id.to_string(),
+            since: None,
+            until: None,
+            page: None,
+            page_size: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindRecentCheckoutsCommandResponse {
+    pub checkouts: Vec<CheckoutDto>,
+    pub next_page: Option<String>,
+}
+
+impl FindRecentCheckoutsCommandResponse {
+    pub fn new(checkouts: Vec<CheckoutDto>, next_page: Option<String>) -> Self {
+        Self {
+            checkouts,
+            next_page,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<FindRecentCheckoutsCommandRequest, FindRecentCheckoutsCommandResponse> for FindRecentCheckoutsCommand {
+    async fn execute(&self, req: FindRecentCheckoutsCommandRequest) -> Result<FindRecentCheckoutsCommandResponse, CommandError> {
+        let until = req.until.unwrap_or_else(Utc::now).naive_utc();
+        let since = req.since.map(|since| since.naive_utc()).unwrap_or(until - Duration::hours(DEFAULT_RECENT_HOURS));
+        self.checkout_service.find_recent(req.branch_id.as_str(), since, until, req.page.as_deref(),
+                                          req.page_size.unwrap_or(DEFAULT_PAGE_SIZE))
+            .await.map_err(CommandError::from).map(|res| FindRecentCheckoutsCommandResponse::new(res.records, res.next_page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::checkout::command::find_recent_checkouts_cmd::{FindRecentCheckoutsCommand, FindRecentCheckoutsCommandRequest};
+    use crate::checkout::domain::model::CheckoutEntity;
+    use crate::checkout::factory::{create_checkout_query_service, create_checkout_repository};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::ids::branch_scoped_id;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_run_find_recent_checkouts() {
+        let store = test_store(module_path!(), "test_should_run_find_recent_checkouts");
+        let config = Configuration::new("test");
+        let sut_cmd = FindRecentCheckoutsCommand::new(create_checkout_query_service(&config, store).await);
+        let repo = create_checkout_repository(store).await;
+        let mut checkouts = vec![];
+        for book_id in ["recent_book1", "recent_book2"] {
+            let mut checkout = CheckoutEntity::new(book_id, "recent_patron");
+            checkout.branch_id = "recent_checkouts_branch".to_string();
+            checkout.checkout_id = branch_scoped_id("recent_checkouts_branch");
+            let _ = repo.create(&checkout).await.expect("should create checkout");
+            checkouts.push(checkout.checkout_id);
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        // legacy checkouts of the branch with UUIDs are not in range
+        let mut legacy = CheckoutEntity::new("recent_book3", "recent_patron");
+        legacy.branch_id = "recent_checkouts_branch".to_string();
+        let _ = repo.create(&legacy).await.expect("should create checkout");
+
+        let res = sut_cmd.execute(FindRecentCheckoutsCommandRequest::new("recent_checkouts_branch"))
+            .await.expect("should find checkouts");
+        checkouts.reverse();
+        assert_eq!(checkouts, res.checkouts.iter().map(|c| c.checkout_id.clone()).collect::<Vec<String>>());
+
+        let mut req = FindRecentCheckoutsCommandRequest::new("recent_checkouts_branch");
+        req.since = Some(Utc::now() + Duration::minutes(1));
+        req.until = Some(Utc::now() + Duration::minutes(2));
+        let res = sut_cmd.execute(req).await.expect("should find checkouts");
+        assert!(res.checkouts.is_empty());
+    }
+}
@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::checkout::domain::CheckoutService;
+use crate::checkout::dto::FulfilledHoldDto;
+use crate::core::command::{Command, CommandError};
+
+pub(crate) struct FulfillHoldCommand {
+    checkout_service: Box<dyn CheckoutService>,
+}
+
+impl FulfillHoldCommand {
+    pub(crate) fn new(checkout_service: Box<dyn CheckoutService>) -> Self {
+        Self {
+            checkout_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FulfillHoldCommandRequest {
+    pub(crate) hold_id: String,
+}
+
+impl FulfillHoldCommandRequest {
+    pub fn new(hold_id: &str) -> Self {
+        Self {
+            hold_id: hold_id.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FulfillHoldCommandResponse {
+    pub fulfilled: FulfilledHoldDto,
+}
+
+impl FulfillHoldCommandResponse {
+    pub fn new(fulfilled: FulfilledHoldDto) -> Self {
+        Self {
+            fulfilled,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<FulfillHoldCommandRequest, FulfillHoldCommandResponse> for FulfillHoldCommand {
+    async fn execute(&self, req: FulfillHoldCommandRequest) -> Result<FulfillHoldCommandResponse, CommandError> {
+        self.checkout_service.fulfill_hold(req.hold_id.as_str())
+            .await.map_err(CommandError::from).map(FulfillHoldCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::books::domain::model::BookEntity;
+    use crate::books::factory::create_book_repository;
+    use crate::checkout::command::fulfill_hold_cmd::{FulfillHoldCommand, FulfillHoldCommandRequest};
+    use crate::checkout::factory::create_checkout_service;
+    use crate::core::command::{Command, CommandError};
+    use crate::core::domain::Configuration;
+    use crate::core::ids::{BookId, PatronId};
+    use crate::core::library::{BookStatus, CheckoutStatus, HoldStatus, PartyKind};
+    use crate::hold::factory::create_hold_service;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_run_fulfill_hold() {
+        let store = test_store(module_path!(), "test_should_run_fulfill_hold");
+        let config = Configuration::new("fulfill_branch");
+        let hold_svc = create_hold_service(&config, store).await;
+        let sut_cmd = FulfillHoldCommand::new(create_checkout_service(&config, store).await);
+        let patron = PartyEntity::new(PartyKind::Patron, "fulfill_cmd@example.com");
+        let _ = create_party_repository(store).await.create(&patron).await.expect("should create patron");
+        let book = BookEntity::new("isbn", "fulfill title", BookStatus::Available);
+        let _ = create_book_repository(store).await.create(&book).await.expect("should create book");
+        let hold = hold_svc.hold(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str()), None)
+            .await.expect("should hold");
+
+        // holds that are not ready for pickup cannot be fulfilled
+        let res = sut_cmd.execute(FulfillHoldCommandRequest::new(hold.hold_id.as_str())).await;
+        assert!(matches!(res, Err(CommandError::Validation { .. })));
+
+        let _ = hold_svc.ready_for_pickup(&hold.hold_id).await.expect("should be ready");
+        let res = sut_cmd.execute(FulfillHoldCommandRequest::new(hold.hold_id.as_str())).await.expect("should fulfill hold");
+        assert_eq!(HoldStatus::CheckedOut, res.fulfilled.hold.hold_status);
+        assert!(res.fulfilled.hold.checked_out_at.is_some());
+        assert_eq!(CheckoutStatus::CheckedOut, res.fulfilled.checkout.checkout_status);
+        assert_eq!(patron.party_id, res.fulfilled.checkout.patron_id);
+        assert_eq!(book.book_id, res.fulfilled.checkout.book_id);
+        assert!(res.fulfilled.checkout.due_at > res.fulfilled.checkout.checkout_at);
+
+        let res = sut_cmd.execute(FulfillHoldCommandRequest::new(hold.hold_id.as_str())).await;
+        assert!(matches!(res, Err(CommandError::Validation { .. })));
+    }
+}
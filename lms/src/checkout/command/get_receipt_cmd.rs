@@ -0,0 +1,114 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::checkout::domain::CheckoutService;
+use crate::checkout::dto::ReceiptDto;
+use crate::checkout::printer::ReceiptFormat;
+use crate::core::command::{Command, CommandError};
+
+pub(crate) struct GetReceiptCommand {
+    checkout_service: Box<dyn CheckoutService>,
+}
+
+impl GetReceiptCommand {
+    pub(crate) fn new(checkout_service: Box<dyn CheckoutService>) -> Self {
+        Self {
+            checkout_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetReceiptCommandRequest {
+    #[serde(default)]
+    pub(crate) checkout_id: String,
+    // the receipt is also emailed to the patron through the notifications
+    #[serde(default)]
+    pub(crate) email: bool,
+    // json, text or escpos, overrides the Accept header
+    #[serde(default)]
+    pub(crate) format: Option<String>,
+    #[serde(skip)]
+    pub(crate) accept: Option<String>,
+}
+
+impl GetReceiptCommandRequest {
+    pub fn new(checkout_id: &str, email: bool) -> Self {
+        Self {
+            checkout_id: checkout_id.to_string(),
+            email,
+            format: None,
+            accept: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetReceiptCommandResponse {
+    pub receipt: ReceiptDto,
+    #[serde(skip)]
+    pub format: ReceiptFormat,
+}
+
+impl GetReceiptCommandResponse {
+    pub fn new(receipt: ReceiptDto, format: ReceiptFormat) -> Self {
+        Self {
+            receipt,
+            format,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<GetReceiptCommandRequest, GetReceiptCommandResponse> for GetReceiptCommand {
+    async fn execute(&self, req: GetReceiptCommandRequest) -> Result<GetReceiptCommandResponse, CommandError> {
+        // the format is checked before the receipt is emailed
+        let format = ReceiptFormat::negotiate(req.format.as_deref(), req.accept.as_deref()).map_err(CommandError::from)?;
+        self.checkout_service.receipt(req.checkout_id.as_str(), req.email)
+            .await.map_err(CommandError::from).map(|receipt| GetReceiptCommandResponse::new(receipt, format))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::books::domain::model::BookEntity;
+    use crate::books::factory::create_book_repository;
+    use crate::checkout::command::get_receipt_cmd::{GetReceiptCommand, GetReceiptCommandRequest};
+    use crate::checkout::factory::create_checkout_service;
+    use crate::checkout::printer::ReceiptFormat;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::ids::{BookId, PatronId};
+    use crate::core::library::{BookStatus, PartyKind};
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_run_get_receipt() {
+        let store = test_store(module_path!(), "test_should_run_get_receipt");
+        let svc = create_checkout_service(&Configuration::new("test"), store).await;
+        let patron = PartyEntity::new(PartyKind::Patron, "receipt_patron@example.com");
+        let _ = create_party_repository(store).await.create(&patron).await.expect("should create patron");
+        let book = BookEntity::new("isbn", "Receipt Book", BookStatus::Available);
+        let _ = create_book_repository(store).await.create(&book).await.expect("should create book");
+        let checkout = svc.checkout(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.expect("should checkout");
+
+        let receipt_cmd = GetReceiptCommand::new(svc);
+        let res = receipt_cmd.execute(GetReceiptCommandRequest::new(checkout.checkout_id.as_str(), false))
+            .await.expect("should get receipt");
+        assert_eq!("Receipt Book", res.receipt.title.as_str());
+        assert!(res.receipt.html.contains("Receipt Book"));
+        assert_eq!(None, res.receipt.notification_id);
+        assert_eq!(ReceiptFormat::Json, res.format);
+
+        let mut req = GetReceiptCommandRequest::new(checkout.checkout_id.as_str(), false);
+        req.accept = Some("text/plain".to_string());
+        let res = receipt_cmd.execute(req).await.expect("should get receipt");
+        assert_eq!(ReceiptFormat::Text, res.format);
+
+        let mut req = GetReceiptCommandRequest::new(checkout.checkout_id.as_str(), true);
+        req.format = Some("pdf".to_string());
+        assert!(receipt_cmd.execute(req).await.is_err());
+    }
+}
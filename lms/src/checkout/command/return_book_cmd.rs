@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use crate::checkout::domain::CheckoutService;
 use crate::checkout::dto::CheckoutDto;
 use crate::core::command::{Command, CommandError};
+use crate::core::ids::{BookId, PatronId};
 
 pub(crate) struct ReturnBookCommand {
     checkout_service: Box<dyn CheckoutService>,
@@ -16,7 +17,7 @@ impl ReturnBookCommand {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct ReturnBookCommandRequest {
     patron_id: String,
     book_id: String,
@@ -32,7 +33,7 @@ impl ReturnBookCommandRequest {
 }
 
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct ReturnBookCommandResponse {
     checkout: CheckoutDto,
 }
@@ -48,15 +49,13 @@ impl ReturnBookCommandResponse {
 #[async_trait]
 impl Command<ReturnBookCommandRequest, ReturnBookCommandResponse> for ReturnBookCommand {
     async fn execute(&self, req: ReturnBookCommandRequest) -> Result<ReturnBookCommandResponse, CommandError> {
-        self.checkout_service.returned(req.patron_id.as_str(), req.book_id.as_str())
+        self.checkout_service.returned(&PatronId::new(req.patron_id.as_str()), &BookId::new(req.book_id.as_str()))
             .await.map_err(CommandError::from).map(ReturnBookCommandResponse::new)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use async_once::AsyncOnce;
-    use lazy_static::lazy_static;
     use crate::books::dto::BookDto;
     use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
     use crate::catalog::factory::create_catalog_service;
@@ -70,32 +69,35 @@ mod tests {
     use crate::patrons::command::add_patron_cmd::{AddPatronCommand, AddPatronCommandRequest};
     use crate::patrons::dto::PatronDto;
     use crate::patrons::factory::create_patron_service;
+    use crate::utils::testing::test_store;
 
-    lazy_static! {
-        static ref BOOK_CMD : AsyncOnce<AddBookCommand> = AsyncOnce::new(async {
-                let svc = create_catalog_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                AddBookCommand::new(svc)
-            });
-        static ref PATRON_CMD : AsyncOnce<AddPatronCommand> = AsyncOnce::new(async {
-                let svc = create_patron_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                AddPatronCommand::new(svc)
-            });
-        static ref CHECKOUT_CMD : AsyncOnce<CheckoutBookCommand> = AsyncOnce::new(async {
-                let svc = create_checkout_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                CheckoutBookCommand::new(svc)
-            });
-        static ref RETURN_CMD : AsyncOnce<ReturnBookCommand> = AsyncOnce::new(async {
-                let svc = create_checkout_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                ReturnBookCommand::new(svc)
-            });
+    async fn build_book_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_patron_cmd(store: RepositoryStore) -> AddPatronCommand {
+        let svc = create_patron_service(&Configuration::new("test"), store).await;
+        AddPatronCommand::new(svc)
+    }
+
+    async fn build_checkout_cmd(store: RepositoryStore) -> CheckoutBookCommand {
+        let svc = create_checkout_service(&Configuration::new("test"), store).await;
+        CheckoutBookCommand::new(svc)
+    }
+
+    async fn build_return_cmd(store: RepositoryStore) -> ReturnBookCommand {
+        let svc = create_checkout_service(&Configuration::new("test"), store).await;
+        ReturnBookCommand::new(svc)
     }
 
     #[tokio::test]
     async fn test_should_run_checkout_book() {
-        let patron_cmd: &AddPatronCommand = PATRON_CMD.get().await.clone();
-        let book_cmd: &AddBookCommand = BOOK_CMD.get().await.clone();
-        let checkout_cmd: &CheckoutBookCommand = CHECKOUT_CMD.get().await.clone();
-        let return_cmd: &ReturnBookCommand = RETURN_CMD.get().await.clone();
+        let store = test_store(module_path!(), "test_should_run_checkout_book");
+        let patron_cmd = build_patron_cmd(store).await;
+        let book_cmd = build_book_cmd(store).await;
+        let checkout_cmd = build_checkout_cmd(store).await;
+        let return_cmd = build_return_cmd(store).await;
 
         let patron = PatronDto::new("email");
         let _ = patron_cmd.execute(AddPatronCommandRequest::new(patron.email.as_str())).await.expect("should add patron");
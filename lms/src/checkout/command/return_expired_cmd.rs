@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::checkout::domain::CheckoutService;
+use crate::checkout::dto::CheckoutDto;
+use crate::core::command::{Command, CommandError};
+
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+pub(crate) struct ReturnExpiredCommand {
+    checkout_service: Box<dyn CheckoutService>,
+}
+
+impl ReturnExpiredCommand {
+    pub(crate) fn new(checkout_service: Box<dyn CheckoutService>) -> Self {
+        Self {
+            checkout_service,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ReturnExpiredCommandRequest {
+    page_size: Option<usize>,
+}
+
+impl ReturnExpiredCommandRequest {
+    pub fn new() -> Self {
+        Self {
+            page_size: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ReturnExpiredCommandResponse {
+    returned: Vec<CheckoutDto>,
+}
+
+impl ReturnExpiredCommandResponse {
+    pub fn new(returned: Vec<CheckoutDto>) -> Self {
+        Self {
+            returned,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<ReturnExpiredCommandRequest, ReturnExpiredCommandResponse> for ReturnExpiredCommand {
+    async fn execute(&self, req: ReturnExpiredCommandRequest) -> Result<ReturnExpiredCommandResponse, CommandError> {
+        self.checkout_service.return_expired_digital(req.page_size.unwrap_or(DEFAULT_PAGE_SIZE))
+            .await.map_err(CommandError::from).map(ReturnExpiredCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checkout::command::return_expired_cmd::{ReturnExpiredCommand, ReturnExpiredCommandRequest};
+    use crate::checkout::factory::create_checkout_service;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_expire_cmd(store: RepositoryStore) -> ReturnExpiredCommand {
+        let svc = create_checkout_service(&Configuration::new("test"), store).await;
+        ReturnExpiredCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_return_expired() {
+        let store = test_store(module_path!(), "test_should_run_return_expired");
+        let expire_cmd = build_expire_cmd(store).await;
+        let res = expire_cmd.execute(ReturnExpiredCommandRequest::new()).await.expect("should return expired");
+        assert!(res.returned.iter().all(|c| c.book_format.is_digital()));
+    }
+}
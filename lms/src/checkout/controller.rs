@@ -1,19 +1,31 @@
 use axum::{
-    extract::State,
-    response::Json,
+    http::{header, HeaderMap, StatusCode},
+    extract::{Path, Query, State},
+    middleware,
+    response::{IntoResponse, Json, Response},
+    routing::{get, post},
+    Router,
 };
 use serde_json::{Value};
+use crate::checkout::command::check_in_cmd::{CheckInCommand, CheckInCommandRequest, CheckInCommandResponse};
 use crate::checkout::command::checkout_book_cmd::{CheckoutBookCommand, CheckoutBookCommandRequest, CheckoutBookCommandResponse};
+use crate::checkout::command::checkout_books_cmd::{CheckoutBooksCommand, CheckoutBooksCommandRequest, CheckoutBooksCommandResponse};
+use crate::checkout::command::find_recent_checkouts_cmd::{FindRecentCheckoutsCommand, FindRecentCheckoutsCommandRequest, FindRecentCheckoutsCommandResponse};
+use crate::checkout::command::get_receipt_cmd::{GetReceiptCommand, GetReceiptCommandRequest, GetReceiptCommandResponse};
 use crate::checkout::command::return_book_cmd::{ReturnBookCommand, ReturnBookCommandRequest, ReturnBookCommandResponse};
+use crate::checkout::command::return_expired_cmd::{ReturnExpiredCommand, ReturnExpiredCommandRequest, ReturnExpiredCommandResponse};
 use crate::checkout::domain::CheckoutService;
 use crate::checkout::factory;
-use crate::core::command::Command;
-use crate::core::controller::{AppState, json_to_server_error, ServerError};
+use crate::checkout::printer::{to_escpos, to_text, ReceiptFormat, ESCPOS_CONTENT_TYPE, RECEIPT_COLUMNS};
+use crate::core::controller::{AppState, batch_status_code, body_limit, command_bus, json_to_server_error, with_common_layers, ServerError};
+use crate::credentials::controller::authenticate;
+use crate::gateway::command::find_events_cmd::FindEventsCommandResponse;
+use crate::gateway::controller::find_entity_events;
 use crate::utils::ddb::{build_db_client, create_table};
 
 async fn build_service(state: AppState) -> Box<dyn CheckoutService> {
     let client = build_db_client(state.store).await;
-    let _ = create_table(&client, "checkout", "checkout_id", "checkout_status", "patron_id").await;
+    let _ = create_table(&client, state.store.table_name("checkout").as_str(), "checkout_id", "checkout_status", "patron_id").await;
     factory::create_checkout_service(&state.config, state.store).await
 }
 
@@ -22,15 +34,96 @@ pub(crate) async fn checkout_book(
     json: Json<Value>) -> Result<Json<CheckoutBookCommandResponse>, ServerError> {
     let req: CheckoutBookCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
     let svc = build_service(state).await;
-    let res = CheckoutBookCommand::new(svc).execute(req).await?;
+    let res = command_bus().register(CheckoutBookCommand::new(svc)).dispatch(req).await?;
     Ok(Json(res))
 }
 
+// patrons check out a stack of books at once, the response reports the outcome of each book
+pub(crate) async fn checkout_books(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<(StatusCode, Json<CheckoutBooksCommandResponse>), ServerError> {
+    let req: CheckoutBooksCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res: CheckoutBooksCommandResponse = command_bus().register(CheckoutBooksCommand::new(svc)).dispatch(req).await?;
+    Ok((batch_status_code(res.status()), Json(res)))
+}
+
 pub(crate) async fn return_book(
     State(state): State<AppState>,
     json: Json<Value>) -> Result<Json<ReturnBookCommandResponse>, ServerError> {
     let req: ReturnBookCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
     let svc = build_service(state).await;
-    let res = ReturnBookCommand::new(svc).execute(req).await?;
+    let res = command_bus().register(ReturnBookCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// librarians check in returned copies by book id and receive routing instructions
+pub(crate) async fn check_in(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<Json<CheckInCommandResponse>, ServerError> {
+    let req: CheckInCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res = command_bus().register(CheckInCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// invoked periodically (e.g. by a scheduled rule) to auto-return expired digital checkouts
+pub(crate) async fn return_expired(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<Json<ReturnExpiredCommandResponse>, ServerError> {
+    let req: ReturnExpiredCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res = command_bus().register(ReturnExpiredCommand::new(svc)).dispatch(req).await?;
     Ok(Json(res))
 }
+
+// printable receipt of a checkout, `?email=true` also sends it to the patron, receipt printers at the desk ask for
+// `?format=escpos` (or `Accept: application/vnd.escpos`) and get the raw byte stream to send to the printer
+pub(crate) async fn get_receipt(
+    State(state): State<AppState>,
+    Path(checkout_id): Path<String>,
+    headers: HeaderMap,
+    Query(mut req): Query<GetReceiptCommandRequest>) -> Result<Response, ServerError> {
+    req.checkout_id = checkout_id;
+    req.accept = headers.get(header::ACCEPT).and_then(|v| v.to_str().ok()).map(str::to_string);
+    let svc = build_service(state).await;
+    let res: GetReceiptCommandResponse = command_bus().register(GetReceiptCommand::new(svc)).dispatch(req).await?;
+    Ok(match res.format {
+        ReceiptFormat::Json => Json(res).into_response(),
+        ReceiptFormat::Text => ([(header::CONTENT_TYPE, "text/plain; charset=utf-8")], to_text(&res.receipt, RECEIPT_COLUMNS)).into_response(),
+        ReceiptFormat::EscPos => ([(header::CONTENT_TYPE, ESCPOS_CONTENT_TYPE)], to_escpos(&res.receipt, RECEIPT_COLUMNS)).into_response(),
+    })
+}
+
+// checkouts made at a branch, most recent first, the branch defaults to the branch of the configuration
+pub(crate) async fn find_recent_checkouts(
+    State(state): State<AppState>,
+    Query(mut req): Query<FindRecentCheckoutsCommandRequest>) -> Result<Json<FindRecentCheckoutsCommandResponse>, ServerError> {
+    if req.branch_id.is_empty() {
+        req.branch_id = state.config.branch_id.to_string();
+    }
+    let svc = factory::create_checkout_query_service(&state.config, state.store).await;
+    let res = command_bus().register(FindRecentCheckoutsCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// events of the checkout until it is returned, including overdue notices
+pub(crate) async fn find_checkout_events(
+    State(state): State<AppState>,
+    Path(id): Path<String>) -> Result<Json<FindEventsCommandResponse>, ServerError> {
+    find_entity_events(state, &["checkout"], id.as_str()).await
+}
+
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/checkout/:id/events", get(find_checkout_events))
+        .route("/checkout/recent", get(find_recent_checkouts))
+        .route_layer(middleware::from_fn_with_state(state.clone(), authenticate))
+        .route("/checkout", post(checkout_book))
+        .route("/checkout/batch", post(checkout_books).layer(body_limit(state.config.max_bulk_body_bytes)))
+        .route("/checkout/return", post(return_book))
+        .route("/checkout/checkin", post(check_in))
+        .route("/checkout/expire", post(return_expired))
+        .route("/checkout/:id/receipt", get(get_receipt));
+    with_common_layers(router, state)
+}
@@ -1,15 +1,62 @@
 use async_trait::async_trait;
 use std::collections::HashMap;
-use crate::checkout::dto::CheckoutDto;
-use crate::core::library::{LibraryResult, PaginatedResult};
+use chrono::NaiveDateTime;
+use crate::audit::dto::StaffOverrideDto;
+use crate::checkout::dto::{CheckInDto, CheckoutDto, DueSoonDigestDto, FulfilledHoldDto, ReceiptDto};
+use crate::core::ids::{BookId, PatronId};
+use crate::core::invariants::InvariantViolation;
+use crate::core::library::{BatchResult, LibraryResult, PaginatedResult};
 
+pub mod digest;
 pub mod model;
+pub mod query;
+pub mod policy;
 pub mod service;
 
+// read side of checkouts
 #[async_trait]
-pub(crate) trait CheckoutService: Sync + Send {
-    async fn checkout(&self, patron_id: &str, book_id: &str) -> LibraryResult<CheckoutDto>;
-    async fn returned(&self, patron_id: &str, book_id: &str) -> LibraryResult<CheckoutDto>;
+pub trait CheckoutQueryService: Sync + Send {
     async fn query_overdue(&self, predicate: &HashMap<String, String>,
                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<CheckoutDto>>;
+    // returns checkouts of the book that are not returned yet, digital books have one per license in use
+    async fn find_active_by_book(&self, book_id: &BookId) -> LibraryResult<Vec<CheckoutDto>>;
+    // returns checkouts of the patron that are not returned yet
+    async fn find_active_by_patron(&self, patron_id: &PatronId) -> LibraryResult<Vec<CheckoutDto>>;
+    // returns checkouts made at the branch within the time range, most recent first, checkouts made before their ids
+    // were scoped by branch are not returned
+    async fn find_recent(&self, branch_id: &str, since: NaiveDateTime, until: NaiveDateTime,
+                         page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<CheckoutDto>>;
+}
+
+#[async_trait]
+pub trait CheckoutService: CheckoutQueryService {
+    async fn checkout(&self, patron_id: &PatronId, book_id: &BookId) -> LibraryResult<CheckoutDto>;
+    // librarians can override suspended accounts and restricted books, overrides are recorded in the audit log
+    async fn checkout_with_override(&self, patron_id: &PatronId, book_id: &BookId,
+                                    staff_override: &StaffOverrideDto) -> LibraryResult<CheckoutDto>;
+    // checks out a stack of books for the patron, checkouts of the books passing the policies are saved in a single
+    // transaction and the outcome of each book is reported separately
+    async fn checkout_all(&self, patron_id: &PatronId, book_ids: &[BookId],
+                          staff_override: Option<&StaffOverrideDto>) -> LibraryResult<BatchResult<CheckoutDto>>;
+    // converts the hold that is ready for pickup at the branch into a checkout of its patron with the due date of the
+    // loan policies, the checkout is removed again when the hold cannot be marked as checked out
+    async fn fulfill_hold(&self, hold_id: &str) -> LibraryResult<FulfilledHoldDto>;
+    async fn returned(&self, patron_id: &PatronId, book_id: &BookId) -> LibraryResult<CheckoutDto>;
+    // librarians check in returned copies by book id, the response tells where the copy goes next
+    async fn check_in(&self, book_id: &BookId, branch_id: Option<&str>, checked_in_by: &str) -> LibraryResult<CheckInDto>;
+    // digital checkouts are returned automatically once they are due without a physical return
+    async fn return_expired_digital(&self, page_size: usize) -> LibraryResult<Vec<CheckoutDto>>;
+    // receipt of the checkout listing the item, due date and branch, it is emailed to the patron when requested
+    async fn receipt(&self, checkout_id: &str, email: bool) -> LibraryResult<ReceiptDto>;
+    // publishes book_overdue for every checkout that is overdue, run daily by the admin binary so checkouts that are
+    // still overdue are published again and consumers count each checkout once
+    async fn publish_overdue(&self, page_size: usize) -> LibraryResult<Vec<CheckoutDto>>;
+    // sends each patron a single digest of their checkouts that are due within the given days, run daily by the
+    // admin binary and digests that were already sent on the same day are skipped
+    async fn send_due_soon_digests(&self, within_days: i64, page_size: usize) -> LibraryResult<Vec<DueSoonDigestDto>>;
+    // points checkouts that are not returned yet from a duplicate catalog record to the record it is merged into, a
+    // physical copy can only be checked out once so both records cannot have open checkouts, dry run only counts them
+    async fn reassign_book(&self, from: &BookId, to: &BookId, dry_run: bool) -> LibraryResult<usize>;
+    // scans all checkouts for broken invariants, run by the admin binary because release builds do not assert them
+    async fn find_invariant_violations(&self, page_size: usize) -> LibraryResult<Vec<InvariantViolation>>;
 }
@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+use chrono::NaiveDate;
+use crate::checkout::dto::{CheckoutDto, DueSoonDigestDto, DueSoonItemDto};
+
+// DueSoonDigestBuilder groups checkouts that are due soon by patron so that each patron receives one notification
+// listing all of their items instead of one notification per item, digests are keyed by patron and day so that the
+// digest of a patron is sent at most once a day
+#[derive(Debug, Clone)]
+pub(crate) struct DueSoonDigestBuilder {
+    digest_on: NaiveDate,
+    items: BTreeMap<String, Vec<DueSoonItemDto>>,
+}
+
+impl DueSoonDigestBuilder {
+    pub(crate) fn new(digest_on: NaiveDate) -> Self {
+        Self {
+            digest_on,
+            items: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn add(&mut self, checkout: &CheckoutDto, title: &str) {
+        self.items.entry(checkout.patron_id.to_string()).or_default().push(DueSoonItemDto {
+            checkout_id: checkout.checkout_id.to_string(),
+            book_id: checkout.book_id.to_string(),
+            title: title.to_string(),
+            due_at: checkout.due_at,
+        });
+    }
+
+    pub(crate) fn build(self) -> Vec<DueSoonDigestDto> {
+        let digest_on = self.digest_on;
+        self.items.into_iter().map(|(patron_id, mut items)| {
+            items.sort_by(|a, b| a.due_at.cmp(&b.due_at).then_with(|| a.title.cmp(&b.title)));
+            let subject = if items.len() == 1 {
+                "1 item is due soon".to_string()
+            } else {
+                format!("{} items are due soon", items.len())
+            };
+            let message = items.iter().map(|item| format!("- {} due {}", item.title, item.due_at.format("%Y-%m-%d")))
+                .collect::<Vec<String>>().join("\n");
+            DueSoonDigestDto {
+                delivery_key: format!("due_soon#{}#{}", patron_id, digest_on),
+                patron_id,
+                subject,
+                message,
+                items,
+                notification_id: None,
+            }
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::checkout::domain::digest::DueSoonDigestBuilder;
+    use crate::checkout::dto::CheckoutDto;
+
+    #[tokio::test]
+    async fn test_should_group_due_soon_items_by_patron() {
+        let today = Utc::now().naive_utc().date();
+        let mut first = CheckoutDto::new("book1", "patron1");
+        first.due_at = Utc::now().naive_utc() + Duration::days(2);
+        let mut second = CheckoutDto::new("book2", "patron1");
+        second.due_at = Utc::now().naive_utc() + Duration::days(1);
+        let other = CheckoutDto::new("book3", "patron2");
+
+        let mut builder = DueSoonDigestBuilder::new(today);
+        builder.add(&first, "First");
+        builder.add(&second, "Second");
+        builder.add(&other, "Other");
+        let digests = builder.build();
+        assert_eq!(2, digests.len());
+        assert_eq!("patron1", digests[0].patron_id.as_str());
+        assert_eq!("2 items are due soon", digests[0].subject.as_str());
+        assert_eq!(vec!["Second", "First"], digests[0].items.iter().map(|i| i.title.as_str()).collect::<Vec<&str>>());
+        assert!(digests[0].message.starts_with("- Second due "));
+        assert_eq!(format!("due_soon#patron1#{}", today), digests[0].delivery_key);
+        assert_eq!("1 item is due soon", digests[1].subject.as_str());
+    }
+}
@@ -1,7 +1,9 @@
 use chrono::{Duration, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use crate::core::library::CheckoutStatus;
+use crate::core::ids::branch_scoped_id;
+use crate::core::invariants::Invariants;
+use crate::core::library::{BookFormat, CheckoutStatus};
 use crate::utils::date::serializer;
 
 // CheckoutEntity abstracts the book that is checked out or borrowed.
@@ -13,6 +15,8 @@ pub(crate) struct CheckoutEntity {
     pub book_id: String,
     pub patron_id: String,
     pub checkout_status: CheckoutStatus,
+    #[serde(default)]
+    pub book_format: BookFormat,
     #[serde(with = "serializer")]
     pub checkout_at: NaiveDateTime,
     #[serde(with = "serializer")]
@@ -26,13 +30,15 @@ pub(crate) struct CheckoutEntity {
 
 impl CheckoutEntity {
     pub fn new(book_id: &str, patron_id: &str) -> Self {
+        let branch_id = Uuid::new_v4().to_string();
         Self {
-            checkout_id: Uuid::new_v4().to_string(),
+            checkout_id: branch_scoped_id(branch_id.as_str()),
             version: 0,
-            branch_id: Uuid::new_v4().to_string(),
+            branch_id,
             book_id: book_id.to_string(),
             patron_id: patron_id.to_string(),
             checkout_status: CheckoutStatus::CheckedOut,
+            book_format: BookFormat::Physical,
             checkout_at: Utc::now().naive_utc(),
             due_at: Utc::now().naive_utc() + Duration::days(15),
             returned_at: None,
@@ -42,9 +48,32 @@ impl CheckoutEntity {
     }
 }
 
+impl Invariants for CheckoutEntity {
+    fn aggregate(&self) -> &'static str {
+        "checkout"
+    }
+
+    fn aggregate_id(&self) -> String {
+        self.checkout_id.to_string()
+    }
+
+    fn violated_rules(&self) -> Vec<&'static str> {
+        let mut rules = vec![];
+        if self.due_at <= self.checkout_at {
+            rules.push("checkout must be due after it was checked out");
+        }
+        if self.returned_at.map(|returned_at| returned_at < self.checkout_at).unwrap_or(false) {
+            rules.push("checkout cannot be returned before it was checked out");
+        }
+        rules
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use chrono::Duration;
     use crate::checkout::domain::model::CheckoutEntity;
+    use crate::core::invariants::Invariants;
     use crate::core::library::CheckoutStatus;
 
     #[tokio::test]
@@ -54,4 +83,14 @@ mod tests {
         assert_eq!("patron1", checkout.patron_id.as_str());
         assert_eq!(CheckoutStatus::CheckedOut, checkout.checkout_status);
     }
+
+    #[tokio::test]
+    async fn test_should_check_checkout_invariants() {
+        let mut checkout = CheckoutEntity::new("book1", "patron1");
+        assert!(checkout.violated_rules().is_empty());
+        checkout.due_at = checkout.checkout_at - Duration::days(1);
+        checkout.returned_at = Some(checkout.checkout_at - Duration::hours(1));
+        assert_eq!(vec!["checkout must be due after it was checked out", "checkout cannot be returned before it was checked out"],
+                   checkout.violated_rules());
+    }
 }
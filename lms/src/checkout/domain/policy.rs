@@ -0,0 +1,75 @@
+use chrono::{Duration, NaiveDateTime};
+use crate::branches::dto::BranchDto;
+use crate::core::domain::Configuration;
+use crate::core::library::BookFormat;
+use crate::reserves::dto::ReserveListDto;
+
+// DueDatePolicy determines due date of a checkout, loan rules of reserve lists take precedence over the format of the book
+// and due dates falling on a day the branch is closed are rolled to its next open day
+#[derive(Debug, Clone)]
+pub(crate) struct DueDatePolicy {
+    book_loan_days: i64,
+    digital_loan_days: i64,
+}
+
+impl DueDatePolicy {
+    pub(crate) fn new(config: &Configuration) -> Self {
+        Self {
+            book_loan_days: config.book_loan_days,
+            digital_loan_days: config.digital_loan_days,
+        }
+    }
+
+    pub(crate) fn due_at(&self, checkout_at: NaiveDateTime, format: BookFormat,
+                         reserve: Option<&ReserveListDto>, branch: Option<&BranchDto>) -> NaiveDateTime {
+        let due_at = if let Some(reserve) = reserve {
+            checkout_at + Duration::hours(reserve.loan_hours)
+        } else if format.is_digital() {
+            checkout_at + Duration::days(self.digital_loan_days)
+        } else {
+            checkout_at + Duration::days(self.book_loan_days)
+        };
+        match branch {
+            Some(branch) => branch.next_open_at(due_at),
+            None => due_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate, Utc};
+    use crate::branches::dto::{BranchDto, ClosureDto};
+    use crate::checkout::domain::policy::DueDatePolicy;
+    use crate::core::domain::Configuration;
+    use crate::core::library::BookFormat;
+    use crate::reserves::dto::ReserveListDto;
+
+    #[tokio::test]
+    async fn test_should_compute_due_date() {
+        let config = Configuration::new("test");
+        let policy = DueDatePolicy::new(&config);
+        let now = Utc::now().naive_utc();
+        assert_eq!(now + Duration::days(config.book_loan_days), policy.due_at(now, BookFormat::Physical, None, None));
+        assert_eq!(now + Duration::days(config.digital_loan_days), policy.due_at(now, BookFormat::EBook, None, None));
+        let reserve = ReserveListDto::new("CS101", 2, "librarian1");
+        assert_eq!(now + Duration::hours(2), policy.due_at(now, BookFormat::Physical, Some(&reserve), None));
+        assert_eq!(now + Duration::hours(2), policy.due_at(now, BookFormat::EBook, Some(&reserve), None));
+    }
+
+    #[tokio::test]
+    async fn test_should_roll_due_date_past_closures() {
+        let config = Configuration::new("test");
+        let policy = DueDatePolicy::new(&config);
+        let checkout_at = NaiveDate::from_ymd_opt(2023, 12, 1).expect("should build date")
+            .and_hms_opt(10, 0, 0).expect("should build time");
+        let due_at = checkout_at + Duration::days(config.book_loan_days);
+
+        let mut branch = BranchDto::new("Central", "central@library.cc");
+        assert_eq!(due_at, policy.due_at(checkout_at, BookFormat::Physical, None, Some(&branch)));
+        branch.closures = vec![ClosureDto::new(due_at.date(), "Holiday"),
+                               ClosureDto::new(due_at.date().succ_opt().expect("should have next day"), "Holiday")];
+        let rolled = policy.due_at(checkout_at, BookFormat::Physical, None, Some(&branch));
+        assert_eq!(due_at + Duration::days(2), rolled);
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use crate::branches::domain::BranchQueryService;
+use crate::branches::dto::BranchDto;
+use crate::checkout::domain::CheckoutQueryService;
+use crate::checkout::dto::CheckoutDto;
+use crate::checkout::repository::CheckoutRepository;
+use crate::core::ids::{BookId, PatronId};
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult};
+
+pub(crate) struct CheckoutQueryServiceImpl {
+    checkout_repository: Box<dyn CheckoutRepository>,
+    branch_service: Box<dyn BranchQueryService>,
+}
+
+impl CheckoutQueryServiceImpl {
+    pub(crate) fn new(checkout_repository: Box<dyn CheckoutRepository>,
+                      branch_service: Box<dyn BranchQueryService>) -> Self {
+        Self {
+            checkout_repository,
+            branch_service,
+        }
+    }
+
+    // checkouts of unregistered branches are overdue on every day after their due date
+    async fn find_branch(&self, branches: &mut HashMap<String, Option<BranchDto>>,
+                         branch_id: &str) -> LibraryResult<Option<BranchDto>> {
+        if let Some(branch) = branches.get(branch_id) {
+            return Ok(branch.clone());
+        }
+        let branch = match self.branch_service.find_branch_by_id(branch_id).await {
+            Ok(branch) => Some(branch),
+            Err(LibraryError::NotFound { .. }) => None,
+            Err(err) => return Err(err),
+        };
+        branches.insert(branch_id.to_string(), branch.clone());
+        Ok(branch)
+    }
+}
+
+#[async_trait]
+impl CheckoutQueryService for CheckoutQueryServiceImpl {
+    async fn query_overdue(&self, predicate: &HashMap<String, String>,
+                           page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<CheckoutDto>> {
+        let today = Utc::now().naive_utc().date();
+        let mut branches = HashMap::new();
+        let mut records = vec![];
+        let mut next_page = page.map(|page| page.to_string());
+        // checkouts are filtered before they are paginated, so pages of the repository are read until the page is full
+        // and each read asks only for the missing checkouts so that the cursor never skips checkouts that were read
+        loop {
+            let res = self.checkout_repository.query_overdue(
+                predicate, next_page.as_deref(), page_size.saturating_sub(records.len()).max(1)).await?;
+            for checkout in res.records.iter().map(CheckoutDto::from) {
+                // checkouts whose branch has been closed on every day since their due date are not overdue yet
+                if checkout.due_at.date() < today {
+                    let branch = self.find_branch(&mut branches, checkout.branch_id.as_str()).await?;
+                    if checkout.open_days_overdue(branch.as_ref(), today) == 0 {
+                        continue;
+                    }
+                }
+                records.push(checkout);
+            }
+            next_page = res.next_page;
+            if next_page.is_none() || records.len() >= page_size {
+                break;
+            }
+        }
+        Ok(PaginatedResult::new(page, page_size, next_page, records))
+    }
+
+    async fn find_active_by_book(&self, book_id: &BookId) -> LibraryResult<Vec<CheckoutDto>> {
+        let checkouts = self.checkout_repository.find_all_active_by_book(book_id.as_str()).await?;
+        Ok(checkouts.iter().map(CheckoutDto::from).collect())
+    }
+
+    async fn find_active_by_patron(&self, patron_id: &PatronId) -> LibraryResult<Vec<CheckoutDto>> {
+        let predicate = HashMap::from([("patron_id".to_string(), patron_id.to_string())]);
+        let mut checkouts = vec![];
+        let mut page: Option<String> = None;
+        loop {
+            let res = self.checkout_repository.query(&predicate, page.as_deref(), 100).await?;
+            checkouts.extend(res.records.iter().map(CheckoutDto::from));
+            page = res.next_page;
+            if page.is_none() {
+                break;
+            }
+        }
+        Ok(checkouts)
+    }
+
+    async fn find_recent(&self, branch_id: &str, since: NaiveDateTime, until: NaiveDateTime,
+                         page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<CheckoutDto>> {
+        if since > until {
+            return Err(LibraryError::validation("recent checkouts must start before they end", None));
+        }
+        let res = self.checkout_repository.find_recent(branch_id, since, until, page, page_size).await?;
+        let records = res.records.iter().map(CheckoutDto::from).collect();
+        Ok(PaginatedResult::new(page, page_size, res.next_page, records))
+    }
+}
@@ -1,39 +1,110 @@
 use std::collections::HashMap;
-use chrono::Utc;
+use chrono::{Duration, NaiveDateTime, Utc};
 use async_trait::async_trait;
+use tracing::log::warn;
+use crate::audit::domain::AuditService;
+use crate::audit::dto::StaffOverrideDto;
 use crate::books::domain::Book;
+use crate::branches::domain::BranchQueryService;
+use crate::branches::dto::BranchDto;
 use crate::catalog::domain::CatalogService;
-use crate::checkout::domain::CheckoutService;
+use crate::checkout::domain::{CheckoutQueryService, CheckoutService};
+use crate::checkout::domain::digest::DueSoonDigestBuilder;
 use crate::checkout::domain::model::CheckoutEntity;
-use crate::checkout::dto::CheckoutDto;
+use crate::checkout::domain::policy::DueDatePolicy;
+use crate::books::dto::BookDto;
+use crate::checkout::dto::{CheckInDto, CheckoutDto, DueSoonDigestDto, FulfilledHoldDto, ReceiptDto};
 use crate::checkout::repository::CheckoutRepository;
 use crate::core::domain::{Configuration, Identifiable};
 use crate::core::events::DomainEvent;
-use crate::core::library::{BookStatus, CheckoutStatus, LibraryError, LibraryResult, PaginatedResult};
+use crate::core::ids::{BookId, HoldId, PatronId};
+use crate::core::invariants::{find_violations, InvariantViolation};
+use crate::core::library::{validate_batch, BatchResult, BookFormat, BookStatus, CheckoutStatus, ItemRouting, LibraryError, LibraryResult, OverrideRule, PaginatedResult, PushTopic};
+use crate::core::repository::ReadConsistency;
+use crate::fines::domain::FineService;
+use crate::fines::dto::FineDto;
 use crate::gateway::events::EventPublisher;
+use crate::hold::domain::HoldService;
+use crate::hold::domain::service::check_pickup;
+use crate::notifications::domain::NotificationService;
 use crate::patrons::domain::PatronService;
+use crate::patrons::dto::PatronDto;
 use crate::patrons::Patron;
+use crate::reserves::domain::ReserveService;
 
 pub(crate) struct CheckoutServiceImpl {
     branch_id: String,
+    due_date_policy: DueDatePolicy,
+    floating_collections: HashMap<String, usize>,
+    overdue_fine_per_day: i64,
     checkout_repository: Box<dyn CheckoutRepository>,
     patron_service: Box<dyn PatronService>,
     catalog_service: Box<dyn CatalogService>,
+    reserve_service: Box<dyn ReserveService>,
+    audit_service: Box<dyn AuditService>,
+    hold_service: Box<dyn HoldService>,
+    branch_service: Box<dyn BranchQueryService>,
+    notification_service: Box<dyn NotificationService>,
+    fine_service: Box<dyn FineService>,
     events_publisher: Box<dyn EventPublisher>,
+    query_service: Box<dyn CheckoutQueryService>,
 }
 
 impl CheckoutServiceImpl {
     pub(crate) fn new(config: &Configuration, checkout_repository: Box<dyn CheckoutRepository>,
                       patron_service: Box<dyn PatronService>, catalog_service: Box<dyn CatalogService>,
-                      events_publisher: Box<dyn EventPublisher>) -> Self {
+                      reserve_service: Box<dyn ReserveService>, audit_service: Box<dyn AuditService>,
+                      hold_service: Box<dyn HoldService>, branch_service: Box<dyn BranchQueryService>,
+                      notification_service: Box<dyn NotificationService>, fine_service: Box<dyn FineService>,
+                      events_publisher: Box<dyn EventPublisher>, query_service: Box<dyn CheckoutQueryService>) -> Self {
         Self {
             branch_id: config.branch_id.to_string(),
+            due_date_policy: DueDatePolicy::new(config),
+            floating_collections: config.floating_collections.clone(),
+            overdue_fine_per_day: config.overdue_fine_per_day,
             checkout_repository,
             patron_service,
             catalog_service,
+            reserve_service,
+            audit_service,
+            hold_service,
+            branch_service,
+            notification_service,
+            fine_service,
             events_publisher,
+            query_service,
         }
     }
+
+    // calendar of the branch that books are checked out at, due dates are not rolled for unregistered branches
+    async fn find_branch(&self) -> LibraryResult<Option<BranchDto>> {
+        match self.branch_service.find_branch_by_id(self.branch_id.as_str()).await {
+            Ok(branch) => Ok(Some(branch)),
+            Err(LibraryError::NotFound { .. }) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    // fine of a book returned late, only the days its branch was open after the due date are charged
+    async fn accrue_overdue_fine(&self, checkout: &CheckoutDto, assessed_by: &str) -> LibraryResult<Option<FineDto>> {
+        let returned_on = checkout.returned_at.unwrap_or_else(|| Utc::now().naive_utc()).date();
+        if self.overdue_fine_per_day <= 0 || checkout.book_format.is_digital() || returned_on <= checkout.due_at.date() {
+            return Ok(None);
+        }
+        let branch = match self.branch_service.find_branch_by_id(checkout.branch_id.as_str()).await {
+            Ok(branch) => Some(branch),
+            Err(LibraryError::NotFound { .. }) => None,
+            Err(err) => return Err(err),
+        };
+        let days = checkout.open_days_overdue(branch.as_ref(), returned_on);
+        if days == 0 {
+            return Ok(None);
+        }
+        let mut fine = FineDto::new(assessed_by, checkout.patron_id.as_str(), "overdue", days * self.overdue_fine_per_day);
+        fine.checkout_id = checkout.checkout_id.to_string();
+        Ok(Some(self.fine_service.assess(&fine).await?))
+    }
+
     async fn find_first(&self, patron_id: &str, book_id: &str) -> LibraryResult<CheckoutEntity> {
         let res = self.checkout_repository.query(
             &HashMap::from([("patron_id".to_string(), patron_id.to_string()),
@@ -46,46 +117,387 @@ impl CheckoutServiceImpl {
                                                 book_id, patron_id).as_str()))
         }
     }
-}
 
-#[async_trait]
-impl CheckoutService for CheckoutServiceImpl {
-    async fn checkout(&self, patron_id: &str, book_id: &str) -> LibraryResult<CheckoutDto> {
-        let patron = self.patron_service.find_patron_by_id(patron_id).await?;
-        let book = self.catalog_service.find_book_by_id(book_id).await?;
+    // copies of floating collections stay at the return branch while it has fewer available copies of the title
+    // than the configured minimum
+    async fn floats_at(&self, book: &BookDto, branch_id: &str) -> LibraryResult<bool> {
+        if let Some(min_copies) = self.floating_collections.get(book.collection.as_str()) {
+            let copies = self.catalog_service.find_book_by_isbn(book.isbn.as_str(), &ReadConsistency::Eventual).await?.iter()
+                .filter(|b| b.branch_id == branch_id).count();
+            return Ok(copies < *min_copies);
+        }
+        Ok(false)
+    }
+
+    // marks checkout as returned and gives back the license of digital books
+    async fn complete_return(&self, existing: &mut CheckoutEntity) -> LibraryResult<CheckoutDto> {
+        existing.checkout_status = CheckoutStatus::Returned;
+        existing.returned_at = Some(Utc::now().naive_utc());
+        self.checkout_repository.update(existing).await?;
+        if existing.book_format.is_digital() {
+            let _ = self.catalog_service.release_license(&BookId::new(existing.book_id.as_str())).await?;
+        }
+        let checkout = CheckoutDto::from(&*existing);
+        let _ = self.events_publisher.publish(&DomainEvent::deleted(
+            "book_returned", "checkout", checkout.checkout_id.as_str(), &HashMap::new(), &checkout.clone())?).await?;
+        Ok(checkout)
+    }
+
+    // staff override allows librarians to checkout books to suspended patrons or restricted books to regular patrons
+    async fn checkout_book(&self, patron_id: &str, book_id: &str,
+                           staff_override: Option<&StaffOverrideDto>) -> LibraryResult<CheckoutDto> {
+        if let Some(staff_override) = staff_override {
+            self.audit_service.authorize_override(staff_override).await?;
+        }
+        let mut overridden = vec![];
+        let patron = self.find_patron(patron_id, staff_override, &mut overridden).await?;
+        let checkout = self.prepare_checkout(&patron, book_id, staff_override, &mut overridden).await?;
+        if let Err(err) = self.checkout_repository.create(&CheckoutEntity::from(&checkout)).await {
+            self.release_digital(&checkout).await?;
+            return Err(err);
+        }
+        self.record_overrides(staff_override, &overridden, &checkout).await?;
+        let _ = self.events_publisher.publish(&DomainEvent::added(
+            "book_checkout", "checkout", checkout.checkout_id.as_str(), &HashMap::new(), &checkout.clone())?).await?;
+        Ok(checkout)
+    }
+
+    // suspended patrons can only checkout with a staff override
+    async fn find_patron(&self, patron_id: &str, staff_override: Option<&StaffOverrideDto>,
+                         overridden: &mut Vec<OverrideRule>) -> LibraryResult<PatronDto> {
+        match self.patron_service.find_patron_in_good_standing(&PatronId::new(patron_id)).await {
+            Ok(patron) => Ok(patron),
+            Err(LibraryError::NotGranted { .. }) if staff_override.is_some() => {
+                overridden.push(OverrideRule::SuspendedAccount);
+                self.patron_service.find_patron_by_id(&PatronId::new(patron_id)).await
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    // validates the book against the policies and acquires the license of digital books, the returned checkout
+    // is not saved yet
+    async fn prepare_checkout(&self, patron: &PatronDto, book_id: &str, staff_override: Option<&StaffOverrideDto>,
+                              overridden: &mut Vec<OverrideRule>) -> LibraryResult<CheckoutDto> {
+        let book = self.catalog_service.find_book_by_id(&BookId::new(book_id)).await?;
         if book.status() != BookStatus::Available {
             return Err(LibraryError::validation(format!("book is not available {}",
                                                         book.id()).as_str(), Some("400".to_string())));
         }
         if book.is_restricted() && patron.is_regular() {
-            return Err(LibraryError::validation(format!("patron {} cannot hold restricted books {}",
-                                                        patron.id(), book.id()).as_str(), Some("400".to_string())));
+            if staff_override.is_none() {
+                return Err(LibraryError::validation(format!("patron {} cannot hold restricted books {}",
+                                                            patron.id(), book.id()).as_str(), Some("400".to_string())));
+            }
+            overridden.push(OverrideRule::RestrictedBook);
+        }
+        let reserve = self.reserve_service.find_rules_for_book(book_id).await?;
+        let mut checkout = CheckoutDto::from_patron_book(self.branch_id.as_str(), patron, &book);
+        let branch = self.find_branch().await?;
+        checkout.due_at = self.due_date_policy.due_at(checkout.checkout_at, book.format(), reserve.as_ref(), branch.as_ref());
+        if book.is_digital() {
+            let _ = self.catalog_service.acquire_license(&BookId::new(book_id)).await?;
+        }
+        Ok(checkout)
+    }
+
+    // gives back the license acquired for a checkout that could not be saved
+    async fn release_digital(&self, checkout: &CheckoutDto) -> LibraryResult<()> {
+        if checkout.book_format.is_digital() {
+            let _ = self.catalog_service.release_license(&checkout.book_id).await?;
+        }
+        Ok(())
+    }
+
+    async fn record_overrides(&self, staff_override: Option<&StaffOverrideDto>, overridden: &[OverrideRule],
+                              checkout: &CheckoutDto) -> LibraryResult<()> {
+        if let Some(staff_override) = staff_override {
+            for rule in overridden {
+                let _ = self.audit_service.record_override(staff_override, *rule, checkout.patron_id.as_str(),
+                                                           checkout.checkout_id.as_str()).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CheckoutService for CheckoutServiceImpl {
+    async fn checkout(&self, patron_id: &PatronId, book_id: &BookId) -> LibraryResult<CheckoutDto> {
+        self.checkout_book(patron_id.as_str(), book_id.as_str(), None).await
+    }
+
+    async fn checkout_with_override(&self, patron_id: &PatronId, book_id: &BookId,
+                                    staff_override: &StaffOverrideDto) -> LibraryResult<CheckoutDto> {
+        self.checkout_book(patron_id.as_str(), book_id.as_str(), Some(staff_override)).await
+    }
+
+    async fn checkout_all(&self, patron_id: &PatronId, book_ids: &[BookId],
+                          staff_override: Option<&StaffOverrideDto>) -> LibraryResult<BatchResult<CheckoutDto>> {
+        validate_batch(book_ids.len())?;
+        if let Some(staff_override) = staff_override {
+            self.audit_service.authorize_override(staff_override).await?;
+        }
+        let mut patron_overridden = vec![];
+        let patron = self.find_patron(patron_id.as_str(), staff_override, &mut patron_overridden).await?;
+        let mut res = BatchResult::new();
+        let mut prepared = vec![];
+        for (i, book_id) in book_ids.iter().enumerate() {
+            if book_ids[..i].contains(book_id) {
+                res.failed(book_id.as_str(), &LibraryError::validation(
+                    format!("book {} is repeated in the batch", book_id).as_str(), Some("400".to_string())));
+                continue;
+            }
+            let mut overridden = patron_overridden.clone();
+            match self.prepare_checkout(&patron, book_id.as_str(), staff_override, &mut overridden).await {
+                Ok(checkout) => prepared.push((checkout, overridden)),
+                Err(err) => res.failed(book_id.as_str(), &err),
+            }
+        }
+        if !prepared.is_empty() {
+            let entities = prepared.iter().map(|(checkout, _)| CheckoutEntity::from(checkout)).collect::<Vec<CheckoutEntity>>();
+            if let Err(err) = self.checkout_repository.create_all(&entities).await {
+                for (checkout, _) in prepared {
+                    self.release_digital(&checkout).await?;
+                    res.failed(checkout.book_id.as_str(), &err);
+                }
+            } else {
+                let mut events = vec![];
+                for (checkout, overridden) in prepared {
+                    self.record_overrides(staff_override, &overridden, &checkout).await?;
+                    events.push(DomainEvent::added(
+                        "book_checkout", "checkout", checkout.checkout_id.as_str(), &HashMap::new(), &checkout.clone())?);
+                    res.succeeded(checkout);
+                }
+                self.events_publisher.publish_all(&events).await?;
+            }
         }
-        let checkout = CheckoutDto::from_patron_book(self.branch_id.as_str(), &patron, &book);
-        self.checkout_repository.create(&CheckoutEntity::from(&checkout)).await?;
+        Ok(res)
+    }
+
+    async fn fulfill_hold(&self, hold_id: &str) -> LibraryResult<FulfilledHoldDto> {
+        let hold = self.hold_service.find_hold(&HoldId::new(hold_id)).await?;
+        check_pickup(&hold, self.branch_id.as_str())?;
+        let mut overridden = vec![];
+        let patron = self.find_patron(hold.patron_id.as_str(), None, &mut overridden).await?;
+        let checkout = self.prepare_checkout(&patron, hold.book_id.as_str(), None, &mut overridden).await?;
+        if let Err(err) = self.checkout_repository.create(&CheckoutEntity::from(&checkout)).await {
+            self.release_digital(&checkout).await?;
+            return Err(err);
+        }
+        // the checkout and the hold live in separate tables, the checkout is compensated when the hold was changed
+        // since it was read, e.g. it was fulfilled at another desk or canceled by the patron
+        let hold = match self.hold_service.fulfill(&hold.hold_id, checkout.checkout_id.as_str()).await {
+            Ok(hold) => hold,
+            Err(err) => {
+                let _ = self.checkout_repository.delete(checkout.checkout_id.as_str()).await?;
+                self.release_digital(&checkout).await?;
+                return Err(err);
+            }
+        };
         let _ = self.events_publisher.publish(&DomainEvent::added(
             "book_checkout", "checkout", checkout.checkout_id.as_str(), &HashMap::new(), &checkout.clone())?).await?;
-        Ok(checkout)
+        Ok(FulfilledHoldDto::new(hold, checkout))
     }
 
-    async fn returned(&self, patron_id: &str, book_id: &str) -> LibraryResult<CheckoutDto> {
+    async fn returned(&self, patron_id: &PatronId, book_id: &BookId) -> LibraryResult<CheckoutDto> {
         let _ = self.patron_service.find_patron_by_id(patron_id).await?;
         let _ = self.catalog_service.find_book_by_id(book_id).await?;
-        let mut existing = self.find_first(patron_id, book_id).await?;
-        existing.checkout_status = CheckoutStatus::Returned;
-        existing.returned_at = Some(Utc::now().naive_utc());
-        self.checkout_repository.update(&existing).await?;
-        let checkout = CheckoutDto::from(&existing);
-        let _ = self.events_publisher.publish(&DomainEvent::deleted(
-            "book_returned", "checkout", checkout.checkout_id.as_str(), &HashMap::new(), &checkout.clone())?).await?;
-        Ok(checkout)
+        let mut existing = self.find_first(patron_id.as_str(), book_id.as_str()).await?;
+        self.complete_return(&mut existing).await
+    }
+
+    async fn check_in(&self, book_id: &BookId, branch_id: Option<&str>, checked_in_by: &str) -> LibraryResult<CheckInDto> {
+        let staff = self.patron_service.find_patron_by_id(&PatronId::new(checked_in_by)).await?;
+        if !staff.is_librarian() && !staff.is_admin() {
+            return Err(LibraryError::not_granted(format!("patron {} cannot check in books",
+                                                         checked_in_by).as_str(), Some("403".to_string())));
+        }
+        let mut existing = self.checkout_repository.find_active_by_book(book_id.as_str()).await?.ok_or_else(|| {
+            LibraryError::not_found(format!("active checkout for book {} not found", book_id).as_str())
+        })?;
+        let checkout = self.complete_return(&mut existing).await?;
+        // the book is already returned, so a fine that cannot be assessed is left to staff rather than failing the check-in
+        if let Err(err) = self.accrue_overdue_fine(&checkout, checked_in_by).await {
+            warn!("failed to assess overdue fine of checkout {} due to {}", checkout.checkout_id, err);
+        }
+        let branch_id = branch_id.unwrap_or(self.branch_id.as_str());
+        let check_in = match self.hold_service.find_next_hold(book_id).await? {
+            Some(hold) if hold.pickup_branch_id == branch_id => {
+                let hold = self.hold_service.ready_for_pickup(&hold.hold_id).await?;
+                CheckInDto::new(checkout, ItemRouting::FillHold, branch_id, Some(hold.clone()),
+                                format!("place on hold shelf for patron {}", hold.patron_id).as_str())
+            }
+            Some(hold) => {
+                let destination = hold.pickup_branch_id.to_string();
+                CheckInDto::new(checkout, ItemRouting::Transfer, destination.as_str(), Some(hold),
+                                format!("transfer to branch {} to fill hold", destination).as_str())
+            }
+            None => {
+                let book = self.catalog_service.find_book_by_id(book_id).await?;
+                let home_branch_id = if book.branch_id.is_empty() { checkout.branch_id.to_string() } else { book.branch_id.to_string() };
+                if home_branch_id == branch_id {
+                    CheckInDto::new(checkout, ItemRouting::Reshelve, branch_id, None, "reshelve")
+                } else if self.floats_at(&book, branch_id).await? {
+                    let _ = self.catalog_service.update_branch(book_id, branch_id).await?;
+                    CheckInDto::new(checkout, ItemRouting::Reshelve, branch_id, None,
+                                    format!("reshelve, floating collection {} stays at this branch", book.collection).as_str())
+                } else {
+                    CheckInDto::new(checkout, ItemRouting::Transfer, home_branch_id.as_str(), None,
+                                    format!("transfer to home branch {}", home_branch_id).as_str())
+                }
+            }
+        };
+        let _ = self.events_publisher.publish(&DomainEvent::updated(
+            "book_checked_in", "checkout", check_in.checkout.checkout_id.as_str(), &HashMap::new(), &check_in.clone())?).await?;
+        Ok(check_in)
+    }
+
+    async fn return_expired_digital(&self, page_size: usize) -> LibraryResult<Vec<CheckoutDto>> {
+        let mut returned = vec![];
+        for format in [BookFormat::EBook, BookFormat::Audiobook] {
+            let predicate = HashMap::from([("book_format".to_string(), format.to_string())]);
+            let mut next_page: Option<String> = None;
+            loop {
+                let res = self.checkout_repository.query_overdue(&predicate, next_page.as_deref(), page_size).await?;
+                for mut existing in res.records {
+                    returned.push(self.complete_return(&mut existing).await?);
+                }
+                next_page = res.next_page;
+                if next_page.is_none() {
+                    break;
+                }
+            }
+        }
+        Ok(returned)
+    }
+
+    async fn receipt(&self, checkout_id: &str, email: bool) -> LibraryResult<ReceiptDto> {
+        let checkout = CheckoutDto::from(&self.checkout_repository.get(checkout_id).await?);
+        let book = self.catalog_service.find_book_by_id(&checkout.book_id).await?;
+        // receipts of unregistered branches show the branch id in place of its name
+        let branch_name = match self.branch_service.find_branch_by_id(checkout.branch_id.as_str()).await {
+            Ok(branch) => branch.name,
+            Err(LibraryError::NotFound { .. }) => checkout.branch_id.to_string(),
+            Err(err) => return Err(err),
+        };
+        let mut receipt = ReceiptDto::new(&checkout, &book, branch_name.as_str());
+        if email {
+            let notification = self.notification_service.notify(
+                checkout.patron_id.as_str(), format!("Checkout receipt for {}", book.title).as_str(), receipt.text.as_str()).await?;
+            receipt.notification_id = Some(notification.notification_id);
+        }
+        Ok(receipt)
+    }
+
+    async fn publish_overdue(&self, page_size: usize) -> LibraryResult<Vec<CheckoutDto>> {
+        let mut overdue = vec![];
+        let mut next_page: Option<String> = None;
+        loop {
+            let res = self.query_service.query_overdue(&HashMap::new(), next_page.as_deref(), page_size).await?;
+            let events = res.records.iter().map(|checkout| DomainEvent::updated(
+                "book_overdue", "checkout", checkout.checkout_id.as_str(), &HashMap::new(), checkout))
+                .collect::<Result<Vec<DomainEvent>, _>>()?;
+            self.events_publisher.publish_all(&events).await?;
+            overdue.extend(res.records);
+            next_page = res.next_page;
+            if next_page.is_none() {
+                break;
+            }
+        }
+        Ok(overdue)
+    }
+
+    async fn send_due_soon_digests(&self, within_days: i64, page_size: usize) -> LibraryResult<Vec<DueSoonDigestDto>> {
+        let now = Utc::now().naive_utc();
+        let mut builder = DueSoonDigestBuilder::new(now.date());
+        let mut titles: HashMap<String, String> = HashMap::new();
+        let mut next_page: Option<String> = None;
+        loop {
+            let res = self.checkout_repository.query_due_by(now + Duration::days(within_days), next_page.as_deref(), page_size).await?;
+            // overdue checkouts are left to the overdue notices
+            for checkout in res.records.iter().map(CheckoutDto::from).filter(|c| c.due_at > now) {
+                if !titles.contains_key(checkout.book_id.as_str()) {
+                    let title = match self.catalog_service.find_book_by_id(&checkout.book_id).await {
+                        Ok(book) => book.title,
+                        Err(LibraryError::NotFound { .. }) => checkout.book_id.to_string(),
+                        Err(err) => return Err(err),
+                    };
+                    titles.insert(checkout.book_id.to_string(), title);
+                }
+                builder.add(&checkout, titles[checkout.book_id.as_str()].as_str());
+            }
+            next_page = res.next_page;
+            if next_page.is_none() {
+                break;
+            }
+        }
+        let mut digests = builder.build();
+        for digest in digests.iter_mut() {
+            digest.notification_id = self.notification_service.notify_once(
+                digest.delivery_key.as_str(), Some(PushTopic::DueSoon), digest.patron_id.as_str(), digest.subject.as_str(), digest.message.as_str())
+                .await?.map(|notification| notification.notification_id);
+        }
+        Ok(digests)
     }
 
+    async fn reassign_book(&self, from: &BookId, to: &BookId, dry_run: bool) -> LibraryResult<usize> {
+        let moved = self.checkout_repository.find_all_active_by_book(from.as_str()).await?;
+        if moved.is_empty() {
+            return Ok(0);
+        }
+        let book = self.catalog_service.find_book_by_id(to).await?;
+        if !book.book_format.is_digital() && self.checkout_repository.find_active_by_book(to.as_str()).await?.is_some() {
+            return Err(LibraryError::validation(format!("books {} and {} are both checked out", from, to).as_str(),
+                                                Some("409".to_string())));
+        }
+        if dry_run {
+            return Ok(moved.len());
+        }
+        let count = moved.len();
+        for mut checkout in moved {
+            checkout.book_id = to.to_string();
+            self.checkout_repository.reassign_book(&checkout).await?;
+            let dto = CheckoutDto::from(&checkout);
+            let _ = self.events_publisher.publish(&DomainEvent::updated(
+                "book_checkout_reassigned", "checkout", dto.checkout_id.as_str(), &HashMap::new(), &dto.clone())?).await?;
+        }
+        Ok(count)
+    }
+
+    async fn find_invariant_violations(&self, page_size: usize) -> LibraryResult<Vec<InvariantViolation>> {
+        let mut violations = vec![];
+        let mut next_page: Option<String> = None;
+        loop {
+            let res = self.checkout_repository.scan(next_page.as_deref(), page_size).await?;
+            violations.extend(find_violations(&res.records));
+            next_page = res.next_page;
+            if next_page.is_none() {
+                break;
+            }
+        }
+        Ok(violations)
+    }
+}
+
+#[async_trait]
+impl CheckoutQueryService for CheckoutServiceImpl {
     async fn query_overdue(&self, predicate: &HashMap<String, String>,
                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<CheckoutDto>> {
-        let res = self.checkout_repository.query_overdue(predicate, page, page_size).await?;
-        let records = res.records.iter().map(CheckoutDto::from).collect();
-        Ok(PaginatedResult::new(page, page_size, res.next_page, records))
+        self.query_service.query_overdue(predicate, page, page_size).await
+    }
+
+    async fn find_active_by_book(&self, book_id: &BookId) -> LibraryResult<Vec<CheckoutDto>> {
+        self.query_service.find_active_by_book(book_id).await
+    }
+
+    async fn find_recent(&self, branch_id: &str, since: NaiveDateTime, until: NaiveDateTime,
+                         page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<CheckoutDto>> {
+        self.query_service.find_recent(branch_id, since, until, page, page_size).await
+    }
+
+    async fn find_active_by_patron(&self, patron_id: &PatronId) -> LibraryResult<Vec<CheckoutDto>> {
+        self.query_service.find_active_by_patron(patron_id).await
     }
 }
 
@@ -95,9 +507,10 @@ impl From<&CheckoutEntity> for CheckoutDto {
             checkout_id: other.checkout_id.to_string(),
             version: other.version,
             branch_id: other.branch_id.to_string(),
-            book_id: other.book_id.to_string(),
-            patron_id: other.patron_id.to_string(),
+            book_id: BookId::new(other.book_id.as_str()),
+            patron_id: PatronId::new(other.patron_id.as_str()),
             checkout_status: other.checkout_status,
+            book_format: other.book_format,
             checkout_at: other.checkout_at,
             due_at: other.due_at,
             returned_at: other.returned_at,
@@ -117,6 +530,7 @@ impl From<&CheckoutDto> for CheckoutEntity {
             book_id: other.book_id.to_string(),
             patron_id: other.patron_id.to_string(),
             checkout_status: other.checkout_status,
+            book_format: other.book_format,
             checkout_at: other.checkout_at,
             due_at: other.due_at,
             returned_at: other.returned_at,
@@ -129,69 +543,522 @@ impl From<&CheckoutDto> for CheckoutEntity {
 
 #[cfg(test)]
 mod tests {
-    use async_once::AsyncOnce;
     use std::collections::HashMap;
-    use lazy_static::lazy_static;
     use aws_sdk_dynamodb::Client;
+    use chrono::{Duration, Utc};
+    use crate::audit::dto::StaffOverrideDto;
+    use crate::audit::factory::create_audit_service;
     use crate::books::domain::model::BookEntity;
     use crate::books::repository::BookRepository;
     use crate::books::factory::create_book_repository;
     use crate::checkout::domain::CheckoutService;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+use crate::checkout::domain::model::CheckoutEntity;
     use crate::checkout::factory;
+    use crate::checkout::factory::create_checkout_repository;
     use crate::core::domain::Configuration;
-    use crate::core::library::{BookStatus, PartyKind};
-    use crate::core::repository::RepositoryStore;
-    use crate::parties::domain::model::PartyEntity;
+    use crate::core::ids::{BookId, PatronId};
+    use crate::core::library::{AccountStatus, BatchStatus, BookFormat, BookStatus, HoldStatus, ItemRouting, PartyKind, Role};
+    use crate::fines::factory::create_fine_query_service;
+    use crate::hold::domain::model::HoldEntity;
+    use crate::hold::factory::create_hold_repository;
+    use crate::parties::domain::model::{ClosureEntity, PartyEntity};
     use crate::parties::factory::create_party_repository;
     use crate::parties::repository::PartyRepository;
-    use crate::utils::ddb::{build_db_client, create_table, delete_table};
-
-    lazy_static! {
-        static ref CLIENT: AsyncOnce<Client> = AsyncOnce::new(async {
-                build_db_client(RepositoryStore::LocalDynamoDB).await
-            });
-        static ref SUT_SVC: AsyncOnce<Box<dyn CheckoutService>> = AsyncOnce::new(async {
-                let _ = delete_table(&CLIENT.get().await.clone(), "checkout").await;
-                let _ = create_table(&CLIENT.get().await.clone(), "checkout", "checkout_id", "checkout_status", "patron_id").await;
-                factory::create_checkout_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await
-            });
-        static ref BOOK_REPO: AsyncOnce<Box<dyn BookRepository>> = AsyncOnce::new(async {
-                let _ = delete_table(&CLIENT.get().await.clone(), "books").await;
-                let _ = create_table(&CLIENT.get().await.clone(), "books", "book_id", "book_status", "isbn").await;
-                create_book_repository(RepositoryStore::LocalDynamoDB).await
-            });
-        static ref PARTY_REPO: AsyncOnce<Box<dyn PartyRepository>> = AsyncOnce::new(async {
-                let _ = delete_table(&CLIENT.get().await.clone(), "parties").await;
-                let _ = create_table(&CLIENT.get().await.clone(), "parties", "party_id", "kind", "email").await;
-                create_party_repository(RepositoryStore::LocalDynamoDB).await
-            });
+    use crate::reserves::domain::model::{ReserveItemEntity, ReserveListEntity};
+    use crate::reserves::factory::{create_reserve_item_repository, create_reserve_list_repository};
+    use crate::utils::ddb::{build_db_client, create_table};
+
+    async fn client(store: RepositoryStore) -> Client {
+        build_db_client(store).await
+    }
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn CheckoutService> {
+        let _ = create_table(&client(store).await, store.table_name("checkout").as_str(), "checkout_id", "checkout_status", "patron_id").await;
+        factory::create_checkout_service(&Configuration::new("test"), store).await
+    }
+
+    async fn book_repo(store: RepositoryStore) -> Box<dyn BookRepository> {
+        let _ = create_table(&client(store).await, store.table_name("books").as_str(), "book_id", "book_status", "isbn").await;
+        create_book_repository(store).await
+    }
+
+    async fn party_repo(store: RepositoryStore) -> Box<dyn PartyRepository> {
+        let _ = create_table(&client(store).await, store.table_name("parties").as_str(), "party_id", "kind", "normalized_email").await;
+        create_party_repository(store).await
     }
 
     #[tokio::test]
     async fn test_should_checkout_and_returned() {
-        let checkout_svc = SUT_SVC.get().await.clone();
+        let store = test_store(module_path!(), "test_should_checkout_and_returned");
+        let checkout_svc = sut_svc(store).await;
 
         let patron = &PartyEntity::new(PartyKind::Patron, "email");
-        let _ = PARTY_REPO.get().await.create(&patron).await.expect("should get patron");
+        let _ = party_repo(store).await.create(&patron).await.expect("should get patron");
         let book = BookEntity::new("isbn", "title", BookStatus::Available);
-        let _ = BOOK_REPO.get().await.create(&book).await.expect("should get book");
-        let res = checkout_svc.returned(patron.party_id.as_str(), book.book_id.as_str()).await;
+        let _ = book_repo(store).await.create(&book).await.expect("should get book");
+        let res = checkout_svc.returned(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str())).await;
         assert!(res.is_err());
-        let checkout = checkout_svc.checkout(patron.party_id.as_str(), book.book_id.as_str()).await.expect("should checkout");
+        let checkout = checkout_svc.checkout(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.expect("should checkout");
         assert_eq!(patron.party_id, checkout.patron_id);
         assert_eq!(book.book_id, checkout.book_id);
-        let returned = checkout_svc.returned(patron.party_id.as_str(), book.book_id.as_str()).await.expect("should returned");
+        let returned = checkout_svc.returned(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.expect("should returned");
         assert_eq!(patron.party_id, returned.patron_id);
         assert_eq!(book.book_id, returned.book_id);
     }
 
+    #[tokio::test]
+    async fn test_should_reassign_checkout_of_merged_book() {
+        let store = test_store(module_path!(), "test_should_reassign_checkout_of_merged_book");
+        let checkout_svc = sut_svc(store).await;
+
+        let patron = PartyEntity::new(PartyKind::Patron, "merge_checkout@example.com");
+        let _ = party_repo(store).await.create(&patron).await.expect("should create patron");
+        let kept = BookEntity::new("isbn", "merged title", BookStatus::Available);
+        let duplicate = BookEntity::new("isbn", "merged title", BookStatus::Available);
+        for book in [&kept, &duplicate] {
+            let _ = book_repo(store).await.create(book).await.expect("should create book");
+        }
+        let checkout = checkout_svc.checkout(&PatronId::new(patron.party_id.as_str()), &BookId::new(duplicate.book_id.as_str())).await.expect("should checkout");
+
+        assert_eq!(1, checkout_svc.reassign_book(&BookId::new(duplicate.book_id.as_str()), &BookId::new(kept.book_id.as_str()), true).await.expect("should count"));
+        assert_eq!(1, checkout_svc.reassign_book(&BookId::new(duplicate.book_id.as_str()), &BookId::new(kept.book_id.as_str()), false).await.expect("should reassign"));
+        let loaded = create_checkout_repository(store).await
+            .get(checkout.checkout_id.as_str()).await.expect("should get checkout");
+        assert_eq!(kept.book_id, loaded.book_id);
+        // the kept copy is checked out now so another open checkout cannot be moved onto it
+        let other = BookEntity::new("isbn", "merged title", BookStatus::Available);
+        let _ = book_repo(store).await.create(&other).await.expect("should create book");
+        let _ = checkout_svc.checkout(&PatronId::new(patron.party_id.as_str()), &BookId::new(other.book_id.as_str())).await.expect("should checkout");
+        assert!(checkout_svc.reassign_book(&BookId::new(other.book_id.as_str()), &BookId::new(kept.book_id.as_str()), true).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_not_be_due_when_branch_is_closed() {
+        let store = test_store(module_path!(), "test_should_not_be_due_when_branch_is_closed");
+        let checkout_svc = sut_svc(store).await;
+        let config = Configuration::new("test");
+
+        // the branch of the configuration is closed on the usual due date and the day after it
+        let due_date = (Utc::now().naive_utc() + Duration::days(config.book_loan_days)).date();
+        let mut branch = PartyEntity::new(PartyKind::Branch, "closed_branch@library.cc");
+        branch.party_id = config.branch_id.to_string();
+        branch.closures = vec![ClosureEntity { date: due_date, reason: "Holiday".to_string() },
+                               ClosureEntity { date: due_date + Duration::days(1), reason: "Holiday".to_string() }];
+        let _ = party_repo(store).await.create(&branch).await.expect("should create branch");
+
+        let patron = PartyEntity::new(PartyKind::Patron, "closed_branch_patron@example.com");
+        let _ = party_repo(store).await.create(&patron).await.expect("should create patron");
+        let book = BookEntity::new("isbn", "title", BookStatus::Available);
+        let _ = book_repo(store).await.create(&book).await.expect("should create book");
+        let checkout = checkout_svc.checkout(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.expect("should checkout");
+        assert_eq!(due_date + Duration::days(2), checkout.due_at.date());
+    }
+
+    #[tokio::test]
+    async fn test_should_checkout_all_with_item_results() {
+        let store = test_store(module_path!(), "test_should_checkout_all_with_item_results");
+        let checkout_svc = sut_svc(store).await;
+
+        let patron = &PartyEntity::new(PartyKind::Patron, "email");
+        let _ = party_repo(store).await.create(&patron).await.expect("should get patron");
+        let first = BookEntity::new("isbn1", "title1", BookStatus::Available);
+        let _ = book_repo(store).await.create(&first).await.expect("should get book");
+        let second = BookEntity::new("isbn2", "title2", BookStatus::Available);
+        let _ = book_repo(store).await.create(&second).await.expect("should get book");
+        let book_ids = vec![BookId::new(first.book_id.as_str()), BookId::new("missing"), BookId::new(second.book_id.as_str()), BookId::new(first.book_id.as_str())];
+        let res = checkout_svc.checkout_all(&PatronId::new(patron.party_id.as_str()), &book_ids, None).await.expect("should checkout all");
+        assert_eq!(BatchStatus::PartiallySucceeded, res.status);
+        assert_eq!(vec![first.book_id.to_string(), second.book_id.to_string()],
+                   res.successes.iter().map(|c| c.book_id.to_string()).collect::<Vec<String>>());
+        assert_eq!(vec!["missing".to_string(), first.book_id.to_string()],
+                   res.failures.iter().map(|f| f.id.to_string()).collect::<Vec<String>>());
+        assert_eq!("validation", res.failures[1].code);
+        let _ = checkout_svc.returned(&PatronId::new(patron.party_id.as_str()), &BookId::new(second.book_id.as_str())).await.expect("should returned");
+
+        assert!(checkout_svc.checkout_all(&PatronId::new(patron.party_id.as_str()), &[], None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_checkout_digital_within_licenses() {
+        let store = test_store(module_path!(), "test_should_checkout_digital_within_licenses");
+        let checkout_svc = sut_svc(store).await;
+
+        let first = &PartyEntity::new(PartyKind::Patron, "digital1@example.com");
+        let second = &PartyEntity::new(PartyKind::Patron, "digital2@example.com");
+        for patron in [first, second] {
+            let _ = party_repo(store).await.create(patron).await.expect("should create patron");
+        }
+        let mut book = BookEntity::new("isbn", "ebook", BookStatus::Available);
+        book.book_format = BookFormat::EBook;
+        book.license_count = 1;
+        book.available_licenses = 1;
+        let _ = book_repo(store).await.create(&book).await.expect("should create book");
+
+        let checkout = checkout_svc.checkout(&PatronId::new(first.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.expect("should checkout");
+        assert_eq!(BookFormat::EBook, checkout.book_format);
+        assert!(checkout_svc.checkout(&PatronId::new(second.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.is_err());
+        // licenses are not expired yet
+        let expired = checkout_svc.return_expired_digital(50).await.expect("should return expired");
+        assert!(expired.iter().all(|c| c.checkout_id != checkout.checkout_id));
+
+        let _ = checkout_svc.returned(&PatronId::new(first.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.expect("should return");
+        let _ = checkout_svc.checkout(&PatronId::new(second.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.expect("should checkout");
+    }
+
+    #[tokio::test]
+    async fn test_should_checkout_reserve_with_short_loan() {
+        let store = test_store(module_path!(), "test_should_checkout_reserve_with_short_loan");
+        let checkout_svc = sut_svc(store).await;
+
+        let patron = &PartyEntity::new(PartyKind::Patron, "reserve_patron@example.com");
+        let _ = party_repo(store).await.create(patron).await.expect("should create patron");
+        let book = BookEntity::new("isbn", "course text", BookStatus::Available);
+        let _ = book_repo(store).await.create(&book).await.expect("should create book");
+        let list = ReserveListEntity::new(format!("checkout_{}", book.book_id).as_str(), 2);
+        let _ = create_reserve_list_repository(store).await.create(&list).await.expect("should create list");
+        let _ = create_reserve_item_repository(store).await
+            .create(&ReserveItemEntity::new(list.list_name.as_str(), book.book_id.as_str(), "librarian")).await.expect("should reserve book");
+
+        let checkout = checkout_svc.checkout(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.expect("should checkout");
+        assert_eq!(checkout.checkout_at + Duration::hours(2), checkout.due_at);
+    }
+
+    #[tokio::test]
+    async fn test_should_not_checkout_for_suspended_patron() {
+        let store = test_store(module_path!(), "test_should_not_checkout_for_suspended_patron");
+        let checkout_svc = sut_svc(store).await;
+
+        let mut patron = PartyEntity::new(PartyKind::Patron, "suspended_checkout@example.com");
+        patron.account_status = AccountStatus::Suspended;
+        let _ = party_repo(store).await.create(&patron).await.expect("should create patron");
+        let book = BookEntity::new("isbn", "title", BookStatus::Available);
+        let _ = book_repo(store).await.create(&book).await.expect("should create book");
+        assert!(checkout_svc.checkout(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_checkout_with_staff_override() {
+        let store = test_store(module_path!(), "test_should_checkout_with_staff_override");
+        let checkout_svc = sut_svc(store).await;
+
+        let mut patron = PartyEntity::new(PartyKind::Patron, "override_checkout@example.com");
+        patron.account_status = AccountStatus::Suspended;
+        let mut librarian = PartyEntity::new(PartyKind::Employee, "override_checkout_librarian@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        for party in [&patron, &librarian] {
+            let _ = party_repo(store).await.create(party).await.expect("should create party");
+        }
+        let mut book = BookEntity::new("isbn", "rare title", BookStatus::Available);
+        book.restricted = true;
+        let _ = book_repo(store).await.create(&book).await.expect("should create book");
+
+        // only librarians can override
+        let not_staff = StaffOverrideDto::new(patron.party_id.as_str(), "please");
+        assert!(checkout_svc.checkout_with_override(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str()), &not_staff).await.is_err());
+        let staff_override = StaffOverrideDto::new(librarian.party_id.as_str(), "research project");
+        let checkout = checkout_svc.checkout_with_override(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str()), &staff_override)
+            .await.expect("should checkout with override");
+
+        let now = Utc::now().naive_utc();
+        let res = create_audit_service(&Configuration::new("test"), store).await
+            .find_overrides(now - Duration::hours(1), now + Duration::hours(1), None, 500).await.expect("should find overrides");
+        let overrides: Vec<_> = res.records.iter().filter(|a| a.subject_id == checkout.checkout_id).collect();
+        assert_eq!(2, overrides.len());
+        assert!(overrides.iter().all(|a| a.staff_id == librarian.party_id));
+    }
+
+    #[tokio::test]
+    async fn test_should_check_in_and_route_books() {
+        let store = test_store(module_path!(), "test_should_check_in_and_route_books");
+        let checkout_svc = sut_svc(store).await;
+
+        let patron = PartyEntity::new(PartyKind::Patron, "check_in_patron@example.com");
+        let waiting = PartyEntity::new(PartyKind::Patron, "check_in_waiting@example.com");
+        let mut librarian = PartyEntity::new(PartyKind::Employee, "check_in_librarian@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        for party in [&patron, &waiting, &librarian] {
+            let _ = party_repo(store).await.create(party).await.expect("should create party");
+        }
+        let reshelved = BookEntity::new("isbn", "reshelved", BookStatus::Available);
+        let filled = BookEntity::new("isbn", "filled", BookStatus::Available);
+        let transferred = BookEntity::new("isbn", "transferred", BookStatus::Available);
+        for book in [&reshelved, &filled, &transferred] {
+            let _ = book_repo(store).await.create(book).await.expect("should create book");
+            let _ = checkout_svc.checkout(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.expect("should checkout");
+        }
+        let hold_repo = create_hold_repository(store).await;
+        let mut local_hold = HoldEntity::new(&BookId::new(filled.book_id.as_str()), &PatronId::new(waiting.party_id.as_str()));
+        local_hold.pickup_branch_id = "test".to_string();
+        let mut remote_hold = HoldEntity::new(&BookId::new(transferred.book_id.as_str()), &PatronId::new(waiting.party_id.as_str()));
+        remote_hold.pickup_branch_id = "remote".to_string();
+        for hold in [&local_hold, &remote_hold] {
+            let _ = hold_repo.create(hold).await.expect("should create hold");
+        }
+
+        // only librarians can check in books
+        assert!(checkout_svc.check_in(&BookId::new(reshelved.book_id.as_str()), None, patron.party_id.as_str()).await.is_err());
+
+        let check_in = checkout_svc.check_in(&BookId::new(reshelved.book_id.as_str()), None, librarian.party_id.as_str())
+            .await.expect("should check in");
+        assert_eq!(ItemRouting::Reshelve, check_in.routing);
+        assert!(check_in.checkout.returned_at.is_some());
+        // returned book has no active checkout left
+        assert!(checkout_svc.check_in(&BookId::new(reshelved.book_id.as_str()), None, librarian.party_id.as_str()).await.is_err());
+
+        let check_in = checkout_svc.check_in(&BookId::new(filled.book_id.as_str()), None, librarian.party_id.as_str())
+            .await.expect("should check in");
+        assert_eq!(ItemRouting::FillHold, check_in.routing);
+        assert_eq!(HoldStatus::ReadyForPickup, check_in.hold.expect("should have hold").hold_status);
+
+        let check_in = checkout_svc.check_in(&BookId::new(transferred.book_id.as_str()), None, librarian.party_id.as_str())
+            .await.expect("should check in");
+        assert_eq!(ItemRouting::Transfer, check_in.routing);
+        assert_eq!("remote", check_in.destination_branch_id.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_should_keep_floating_copies_at_return_branch() {
+        let store = test_store(module_path!(), "test_should_keep_floating_copies_at_return_branch");
+        let mut config = Configuration::new("test");
+        config.floating_collections.insert("FLOAT".to_string(), 1);
+        let checkout_svc = factory::create_checkout_service(&config, store).await;
+
+        let patron = PartyEntity::new(PartyKind::Patron, "floating_patron@example.com");
+        let mut librarian = PartyEntity::new(PartyKind::Employee, "floating_librarian@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        for party in [&patron, &librarian] {
+            let _ = party_repo(store).await.create(party).await.expect("should create party");
+        }
+        let mut copies = vec![];
+        for _ in 0..2 {
+            let mut book = BookEntity::new("isbn_floating", "floating title", BookStatus::Available);
+            book.collection = "FLOAT".to_string();
+            book.branch_id = "floating_home".to_string();
+            let _ = book_repo(store).await.create(&book).await.expect("should create book");
+            let _ = checkout_svc.checkout(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.expect("should checkout");
+            copies.push(book);
+        }
+
+        // first copy stays as return branch has no copies of the title
+        let check_in = checkout_svc.check_in(&BookId::new(copies[0].book_id.as_str()), None, librarian.party_id.as_str())
+            .await.expect("should check in");
+        assert_eq!(ItemRouting::Reshelve, check_in.routing);
+        assert_eq!("test", book_repo(store).await.get(copies[0].book_id.as_str()).await.expect("should get book").branch_id.as_str());
+
+        // second copy goes home as return branch has enough copies
+        let check_in = checkout_svc.check_in(&BookId::new(copies[1].book_id.as_str()), None, librarian.party_id.as_str())
+            .await.expect("should check in");
+        assert_eq!(ItemRouting::Transfer, check_in.routing);
+        assert_eq!("floating_home", check_in.destination_branch_id.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_should_email_receipt() {
+        let store = test_store(module_path!(), "test_should_email_receipt");
+        let checkout_svc = sut_svc(store).await;
+        let patron = PartyEntity::new(PartyKind::Patron, "receipt@example.com");
+        let _ = party_repo(store).await.create(&patron).await.expect("should create patron");
+        let book = BookEntity::new("isbn_receipt", "Receipt Title", BookStatus::Available);
+        let _ = book_repo(store).await.create(&book).await.expect("should create book");
+        let checkout = checkout_svc.checkout(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.expect("should checkout");
+
+        let receipt = checkout_svc.receipt(checkout.checkout_id.as_str(), true).await.expect("should email receipt");
+        assert_eq!(checkout.due_at, receipt.due_at);
+        assert_eq!("isbn_receipt", receipt.isbn.as_str());
+        assert!(receipt.text.contains("Item: Receipt Title"));
+        assert!(receipt.notification_id.is_some());
+        assert!(checkout_svc.receipt("missing", false).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_send_due_soon_digest_once() {
+        let store = test_store(module_path!(), "test_should_send_due_soon_digest_once");
+        let checkout_svc = sut_svc(store).await;
+        let patron = PartyEntity::new(PartyKind::Patron, "due_soon@example.com");
+        let _ = party_repo(store).await.create(&patron).await.expect("should create patron");
+        let checkout_repo = create_checkout_repository(store).await;
+        for (title, days) in [("Due Soon One", 1), ("Due Soon Two", 2), ("Due Later", 20)] {
+            let book = BookEntity::new("isbn_due_soon", title, BookStatus::Available);
+            let _ = book_repo(store).await.create(&book).await.expect("should create book");
+            let checkout = checkout_svc.checkout(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.expect("should checkout");
+            let mut entity = checkout_repo.get(checkout.checkout_id.as_str()).await.expect("should get checkout");
+            entity.due_at = Utc::now().naive_utc() + Duration::days(days);
+            let _ = checkout_repo.update(&entity).await.expect("should update checkout");
+        }
+
+        let digests = checkout_svc.send_due_soon_digests(3, 100).await.expect("should send digests");
+        let digest = digests.iter().find(|d| d.patron_id == patron.party_id).expect("should find digest");
+        assert_eq!(vec!["Due Soon One", "Due Soon Two"], digest.items.iter().map(|i| i.title.as_str()).collect::<Vec<&str>>());
+        assert!(digest.notification_id.is_some());
+
+        // the digest is not sent again on the same day
+        let digests = checkout_svc.send_due_soon_digests(3, 100).await.expect("should send digests");
+        let digest = digests.iter().find(|d| d.patron_id == patron.party_id).expect("should find digest");
+        assert_eq!(None, digest.notification_id);
+    }
+
+    #[tokio::test]
+    async fn test_should_count_overdue_checkouts_of_patron() {
+        let store = test_store(module_path!(), "test_should_count_overdue_checkouts_of_patron");
+        let checkout_svc = sut_svc(store).await;
+        let patron = PartyEntity::new(PartyKind::Patron, "overdue_counter@example.com");
+        let _ = party_repo(store).await.create(&patron).await.expect("should create patron");
+        let book = BookEntity::new("isbn_overdue_counter", "title", BookStatus::Available);
+        let _ = book_repo(store).await.create(&book).await.expect("should create book");
+        let checkout = checkout_svc.checkout(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.expect("should checkout");
+        let checkout_repo = create_checkout_repository(store).await;
+        let mut entity = checkout_repo.get(checkout.checkout_id.as_str()).await.expect("should get checkout");
+        entity.checkout_at = Utc::now().naive_utc() - Duration::days(20);
+        entity.due_at = Utc::now().naive_utc() - Duration::days(2);
+        let _ = checkout_repo.update(&entity).await.expect("should update checkout");
+
+        // overdue checkouts are published by every run but counted once
+        for _ in 0..2 {
+            let overdue = checkout_svc.publish_overdue(50).await.expect("should publish overdue");
+            assert!(overdue.iter().any(|c| c.checkout_id == checkout.checkout_id));
+        }
+        assert_eq!(1, party_repo(store).await.get(patron.party_id.as_str()).await.expect("should get patron").num_overdue);
+
+        let _ = checkout_svc.returned(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.expect("should return");
+        assert_eq!(0, party_repo(store).await.get(patron.party_id.as_str()).await.expect("should get patron").num_overdue);
+    }
 
     #[tokio::test]
     async fn test_should_query_overdue() {
-        let checkout_svc = SUT_SVC.get().await.clone();
+        let store = test_store(module_path!(), "test_should_query_overdue");
+        let checkout_svc = sut_svc(store).await;
 
         let res = checkout_svc.query_overdue(
             &HashMap::new(), None, 50).await.expect("should query");
         assert_eq!(0, res.records.len());
     }
+
+    #[tokio::test]
+    async fn test_should_not_be_overdue_during_closures() {
+        let store = test_store(module_path!(), "test_should_not_be_overdue_during_closures");
+        let checkout_svc = sut_svc(store).await;
+        let checkout_repo = create_checkout_repository(store).await;
+
+        // the branch has been closed for the last three days including today
+        let now = Utc::now().naive_utc();
+        let mut branch = PartyEntity::new(PartyKind::Branch, "holiday_branch@library.cc");
+        branch.closures = (0..3).map(|days| ClosureEntity {
+            date: (now - Duration::days(days)).date(),
+            reason: "Holidays".to_string(),
+        }).collect();
+        let _ = party_repo(store).await.create(&branch).await.expect("should create branch");
+
+        let mut checkouts = vec![];
+        for due_days_ago in [3, 4] {
+            let mut checkout = CheckoutEntity::new("book", "patron");
+            checkout.branch_id = branch.party_id.to_string();
+            checkout.checkout_at = now - Duration::days(20);
+            checkout.due_at = now - Duration::days(due_days_ago);
+            let _ = checkout_repo.create(&checkout).await.expect("should create checkout");
+            checkouts.push(checkout);
+        }
+        // checkout of an unregistered branch is overdue regardless of closures
+        let mut unregistered = CheckoutEntity::new("book", "patron");
+        unregistered.checkout_at = now - Duration::days(20);
+        unregistered.due_at = now - Duration::days(1);
+        let _ = checkout_repo.create(&unregistered).await.expect("should create checkout");
+
+        let res = checkout_svc.query_overdue(&HashMap::new(), None, 50).await.expect("should query");
+        let mut overdue: Vec<String> = res.records.iter().map(|c| c.checkout_id.to_string()).collect();
+        overdue.sort();
+        let mut expected = vec![checkouts[1].checkout_id.to_string(), unregistered.checkout_id.to_string()];
+        expected.sort();
+        // the checkout due right before the closure is skipped and the one due a day earlier is overdue
+        assert_eq!(expected, overdue);
+    }
+
+    #[tokio::test]
+    async fn test_should_fill_pages_of_overdue_checkouts() {
+        let store = test_store(module_path!(), "test_should_fill_pages_of_overdue_checkouts");
+        let checkout_svc = sut_svc(store).await;
+        let checkout_repo = create_checkout_repository(store).await;
+
+        let now = Utc::now().naive_utc();
+        let mut branch = PartyEntity::new(PartyKind::Branch, "paged_holiday_branch@library.cc");
+        branch.closures = (0..3).map(|days| ClosureEntity {
+            date: (now - Duration::days(days)).date(),
+            reason: "Holidays".to_string(),
+        }).collect();
+        let _ = party_repo(store).await.create(&branch).await.expect("should create branch");
+
+        // checkouts due during the closure are not overdue yet and are mixed with overdue ones
+        let mut expected = vec![];
+        for (i, due_days_ago) in [1, 4, 2, 5, 1, 6].into_iter().enumerate() {
+            let mut checkout = CheckoutEntity::new(format!("book{}", i).as_str(), "patron");
+            checkout.branch_id = branch.party_id.to_string();
+            checkout.checkout_at = now - Duration::days(20);
+            checkout.due_at = now - Duration::days(due_days_ago);
+            let _ = checkout_repo.create(&checkout).await.expect("should create checkout");
+            if due_days_ago > 3 {
+                expected.push(checkout.checkout_id.to_string());
+            }
+        }
+
+        // pages are filtered before they are cut, so only the last page can be short
+        let mut overdue = vec![];
+        let mut next_page: Option<String> = None;
+        loop {
+            let res = checkout_svc.query_overdue(&HashMap::new(), next_page.as_deref(), 2).await.expect("should query");
+            if res.next_page.is_some() {
+                assert_eq!(2, res.records.len());
+            }
+            overdue.extend(res.records.iter().map(|c| c.checkout_id.to_string()));
+            next_page = res.next_page;
+            if next_page.is_none() {
+                break;
+            }
+        }
+        overdue.sort();
+        expected.sort();
+        assert_eq!(expected, overdue);
+    }
+
+    #[tokio::test]
+    async fn test_should_fine_open_days_overdue_on_check_in() {
+        let store = test_store(module_path!(), "test_should_fine_open_days_overdue_on_check_in");
+        let checkout_svc = sut_svc(store).await;
+        let checkout_repo = create_checkout_repository(store).await;
+        let fine_svc = create_fine_query_service(&Configuration::new("test"), store).await;
+
+        // the branch of the checkouts has been closed for the last two days
+        let now = Utc::now().naive_utc();
+        let mut branch = PartyEntity::new(PartyKind::Branch, "fine_holiday_branch@library.cc");
+        branch.closures = (0..2).map(|days| ClosureEntity {
+            date: (now - Duration::days(days)).date(),
+            reason: "Holidays".to_string(),
+        }).collect();
+        let patron = PartyEntity::new(PartyKind::Patron, "fine_check_in_patron@example.com");
+        let mut librarian = PartyEntity::new(PartyKind::Employee, "fine_check_in_librarian@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        for party in [&branch, &patron, &librarian] {
+            let _ = party_repo(store).await.create(party).await.expect("should create party");
+        }
+
+        let late = BookEntity::new("isbn", "late", BookStatus::Available);
+        let closed = BookEntity::new("isbn", "closed", BookStatus::Available);
+        for (book, due_days_ago) in [(&late, 5), (&closed, 2)] {
+            let _ = book_repo(store).await.create(book).await.expect("should create book");
+            let checkout = checkout_svc.checkout(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str()))
+                .await.expect("should checkout");
+            let mut existing = checkout_repo.get(checkout.checkout_id.as_str()).await.expect("should get checkout");
+            existing.branch_id = branch.party_id.to_string();
+            existing.due_at = now - Duration::days(due_days_ago);
+            let _ = checkout_repo.update(&existing).await.expect("should update checkout");
+            let _ = checkout_svc.check_in(&BookId::new(book.book_id.as_str()), None, librarian.party_id.as_str())
+                .await.expect("should check in");
+        }
+
+        // the book returned during the closure is not fined and the late one is fined for three open days
+        let fines = fine_svc.find_fines_by_patron(patron.party_id.as_str()).await.expect("should find fines");
+        assert_eq!(1, fines.len());
+        assert_eq!(3 * Configuration::new("test").overdue_fine_per_day, fines[0].amount);
+        assert_eq!("overdue", fines[0].reason.as_str());
+    }
 }
@@ -1,42 +1,54 @@
-use chrono::{Duration, NaiveDateTime, Utc};
+use chrono::{Duration, NaiveDate, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use uuid::Uuid;
 use crate::books::domain::Book;
-use crate::core::library::CheckoutStatus;
+use crate::books::dto::BookDto;
+use crate::branches::dto::BranchDto;
+use crate::core::library::{BookFormat, CheckoutStatus, ItemRouting};
 use crate::core::domain::Identifiable;
+use crate::core::ids::{branch_scoped_id, BookId, PatronId};
+use crate::hold::dto::HoldDto;
 use crate::patrons::Patron;
-use crate::utils::date::{serializer};
+use crate::utils::date::{serializer, Rfc3339};
 
 
 // CheckoutDto abstracts the book that is checked out or borrowed.
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
-pub(crate) struct CheckoutDto {
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CheckoutDto {
     pub checkout_id: String,
     pub version: i64,
     pub branch_id: String,
-    pub book_id: String,
-    pub patron_id: String,
+    pub book_id: BookId,
+    pub patron_id: PatronId,
     pub checkout_status: CheckoutStatus,
+    pub book_format: BookFormat,
     #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
     pub checkout_at: NaiveDateTime,
     #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
     pub due_at: NaiveDateTime,
     pub returned_at: Option<NaiveDateTime>,
     #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
     pub created_at: NaiveDateTime,
     #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
     pub updated_at: NaiveDateTime,
 }
 
 impl CheckoutDto {
     pub fn new(book_id: &str, patron_id: &str) -> Self {
+        let branch_id = Uuid::new_v4().to_string();
         Self {
-            checkout_id: Uuid::new_v4().to_string(),
+            checkout_id: branch_scoped_id(branch_id.as_str()),
             version: 0,
-            branch_id: Uuid::new_v4().to_string(),
-            book_id: book_id.to_string(),
-            patron_id: patron_id.to_string(),
+            branch_id,
+            book_id: BookId::new(book_id),
+            patron_id: PatronId::new(patron_id),
             checkout_status: CheckoutStatus::CheckedOut,
+            book_format: BookFormat::Physical,
             checkout_at: Utc::now().naive_utc(),
             due_at: Utc::now().naive_utc() + Duration::days(15),
             returned_at: None,
@@ -45,14 +57,15 @@ impl CheckoutDto {
         }
     }
 
-    pub fn from_patron_book(branch_id: &str, patron: &dyn Patron, book: &dyn Book) -> Self {
+    pub(crate) fn from_patron_book(branch_id: &str, patron: &dyn Patron, book: &dyn Book) -> Self {
         CheckoutDto {
-            checkout_id: Uuid::new_v4().to_string(),
+            checkout_id: branch_scoped_id(branch_id),
             version: 0,
             branch_id: branch_id.to_string(),
-            book_id: book.id(),
-            patron_id: patron.id(),
+            book_id: BookId::from(book.id()),
+            patron_id: PatronId::from(patron.id()),
             checkout_status: CheckoutStatus::CheckedOut,
+            book_format: book.format(),
             checkout_at: Utc::now().naive_utc(),
             due_at: Utc::now().naive_utc() + Duration::days(15),
             returned_at: None,
@@ -60,6 +73,16 @@ impl CheckoutDto {
             updated_at: Utc::now().naive_utc(),
         }
     }
+
+    // days the checkout is overdue on the given date, only the days its branch is open are counted and checkouts of
+    // unregistered branches are overdue on every day after their due date
+    pub(crate) fn open_days_overdue(&self, branch: Option<&BranchDto>, on: NaiveDate) -> i64 {
+        let due_on = self.due_at.date();
+        match branch {
+            Some(branch) => branch.open_days_overdue(due_on, on),
+            None => (on - due_on).num_days().max(0),
+        }
+    }
 }
 
 impl Identifiable for CheckoutDto {
@@ -72,10 +95,136 @@ impl Identifiable for CheckoutDto {
     }
 }
 
+// CheckInDto returns the completed checkout with routing instructions for the checked-in item
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CheckInDto {
+    pub checkout: CheckoutDto,
+    pub routing: ItemRouting,
+    pub destination_branch_id: String,
+    pub hold: Option<HoldDto>,
+    pub instructions: String,
+}
+
+impl CheckInDto {
+    pub fn new(checkout: CheckoutDto, routing: ItemRouting, destination_branch_id: &str,
+               hold: Option<HoldDto>, instructions: &str) -> Self {
+        Self {
+            checkout,
+            routing,
+            destination_branch_id: destination_branch_id.to_string(),
+            hold,
+            instructions: instructions.to_string(),
+        }
+    }
+}
+
+// FulfilledHoldDto returns the hold that was picked up at the desk along with the checkout it was converted to
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FulfilledHoldDto {
+    pub hold: HoldDto,
+    pub checkout: CheckoutDto,
+}
+
+impl FulfilledHoldDto {
+    pub fn new(hold: HoldDto, checkout: CheckoutDto) -> Self {
+        Self {
+            hold,
+            checkout,
+        }
+    }
+}
+
+// ReceiptDto is the printable receipt of a checkout, the same details are rendered as plain text for emails and
+// as html for printing at the desk, returned checkouts are printed as check-in receipts
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ReceiptDto {
+    pub checkout_id: String,
+    pub patron_id: String,
+    pub book_id: String,
+    pub title: String,
+    pub isbn: String,
+    pub branch_id: String,
+    pub branch_name: String,
+    #[serde(with = "serializer")]
+    pub checkout_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub due_at: NaiveDateTime,
+    #[serde(default)]
+    pub returned_at: Option<NaiveDateTime>,
+    pub text: String,
+    pub html: String,
+    pub notification_id: Option<String>,
+}
+
+impl ReceiptDto {
+    pub(crate) fn new(checkout: &CheckoutDto, book: &BookDto, branch_name: &str) -> Self {
+        let mut lines = vec![
+            ("Branch", branch_name.to_string()),
+            ("Item", book.title.to_string()),
+            ("ISBN", book.isbn.to_string()),
+            ("Checked out", checkout.checkout_at.format("%Y-%m-%d %H:%M").to_string()),
+        ];
+        match checkout.returned_at {
+            Some(returned_at) => lines.push(("Returned", returned_at.format("%Y-%m-%d %H:%M").to_string())),
+            None => lines.push(("Due", checkout.due_at.format("%Y-%m-%d").to_string())),
+        }
+        lines.push(("Receipt", checkout.checkout_id.to_string()));
+        let heading = if checkout.returned_at.is_some() { "Check-in receipt" } else { "Checkout receipt" };
+        let text = lines.iter().map(|(label, value)| format!("{}: {}", label, value)).collect::<Vec<String>>().join("\n");
+        let rows = lines.iter().map(|(label, value)| format!("<tr><th>{}</th><td>{}</td></tr>", label, escape_html(value)))
+            .collect::<Vec<String>>().join("");
+        let html = format!("<html><body><h1>{}</h1><table>{}</table></body></html>", heading, rows);
+        Self {
+            checkout_id: checkout.checkout_id.to_string(),
+            patron_id: checkout.patron_id.to_string(),
+            book_id: checkout.book_id.to_string(),
+            title: book.title.to_string(),
+            isbn: book.isbn.to_string(),
+            branch_id: checkout.branch_id.to_string(),
+            branch_name: branch_name.to_string(),
+            checkout_at: checkout.checkout_at,
+            due_at: checkout.due_at,
+            returned_at: checkout.returned_at,
+            text,
+            html,
+            notification_id: None,
+        }
+    }
+}
+
+// DueSoonItemDto is a checkout listed in the due-soon digest of a patron
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct DueSoonItemDto {
+    pub checkout_id: String,
+    pub book_id: String,
+    pub title: String,
+    #[serde(with = "serializer")]
+    pub due_at: NaiveDateTime,
+}
+
+// DueSoonDigestDto groups the checkouts of a patron that are due soon into a single notification, the notification
+// id is empty when the digest was sent by an earlier run of the same day
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct DueSoonDigestDto {
+    pub patron_id: String,
+    pub delivery_key: String,
+    pub subject: String,
+    pub message: String,
+    pub items: Vec<DueSoonItemDto>,
+    pub notification_id: Option<String>,
+}
+
+// titles and branch names are entered by staff so they are escaped before they are rendered
+fn escape_html(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
 
 #[cfg(test)]
 mod tests {
-    use crate::checkout::dto::CheckoutDto;
+    use chrono::NaiveDate;
+    use crate::books::dto::BookDto;
+    use crate::branches::dto::{BranchDto, ClosureDto};
+    use crate::checkout::dto::{CheckoutDto, ReceiptDto};
     use crate::core::library::CheckoutStatus;
 
     #[tokio::test]
@@ -85,4 +234,38 @@ mod tests {
         assert_eq!("patron1", checkout.patron_id.as_str());
         assert_eq!(CheckoutStatus::CheckedOut, checkout.checkout_status);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_should_count_open_days_overdue_of_checkout() {
+        let date = |day: u32| NaiveDate::from_ymd_opt(2023, 12, day).expect("should build date");
+        let mut checkout = CheckoutDto::new("book1", "patron1");
+        checkout.due_at = date(22).and_hms_opt(18, 0, 0).expect("should build time");
+        let mut branch = BranchDto::new("Central", "central@library.cc");
+        branch.closures = (23..=26).map(|day| ClosureDto::new(date(day), "Holidays")).collect();
+
+        // days the branch is closed are not counted while unregistered branches count every day
+        assert_eq!(1, checkout.open_days_overdue(Some(&branch), date(27)));
+        assert_eq!(5, checkout.open_days_overdue(None, date(27)));
+        assert_eq!(0, checkout.open_days_overdue(None, date(20)));
+    }
+
+    #[tokio::test]
+    async fn test_should_render_receipt() {
+        let checkout = CheckoutDto::new("book1", "patron1");
+        let book = BookDto::builder().isbn("isbn1").title("Rust <&> DDD").build().expect("should build book");
+        let receipt = ReceiptDto::new(&checkout, &book, "Central");
+        assert!(receipt.text.contains("Item: Rust <&> DDD"));
+        assert!(receipt.text.contains(format!("Due: {}", checkout.due_at.format("%Y-%m-%d")).as_str()));
+        assert!(receipt.html.contains("<td>Rust &lt;&amp;&gt; DDD</td>"));
+        assert!(receipt.html.contains("<td>Central</td>"));
+        assert!(receipt.html.contains("<h1>Checkout receipt</h1>"));
+        assert_eq!(None, receipt.notification_id);
+
+        let mut returned = checkout.clone();
+        returned.returned_at = Some(checkout.due_at);
+        let receipt = ReceiptDto::new(&returned, &book, "Central");
+        assert!(receipt.html.contains("<h1>Check-in receipt</h1>"));
+        assert!(receipt.text.contains(format!("Returned: {}", checkout.due_at.format("%Y-%m-%d %H:%M")).as_str()));
+        assert!(!receipt.text.contains("Due: "));
+    }
+}
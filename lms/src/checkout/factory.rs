@@ -1,34 +1,63 @@
+use crate::audit::factory::create_audit_service;
+use crate::branches::factory::create_branch_query_service;
 use crate::catalog::factory::create_catalog_service;
-use crate::checkout::domain::CheckoutService;
+use crate::checkout::domain::{CheckoutQueryService, CheckoutService};
+use crate::checkout::domain::query::CheckoutQueryServiceImpl;
 use crate::checkout::domain::service::CheckoutServiceImpl;
 use crate::checkout::factory;
 use crate::checkout::repository::CheckoutRepository;
-use crate::checkout::repository::ddb_checkout_repository::DDBCheckoutRepository;
+use crate::checkout::repository::ddb_checkout_repository::{DDBCheckoutRepository, BRANCH_INDEX};
 use crate::core::domain::Configuration;
 use crate::core::repository::RepositoryStore;
-use crate::gateway::factory::create_publisher;
+use crate::fines::factory::create_fine_service;
+use crate::hold::factory::create_hold_service;
+use crate::notifications::factory::create_notification_service;
 use crate::patrons::factory::create_patron_service;
-use crate::utils::ddb::{build_db_client, create_table};
+use crate::projector::factory::create_projecting_publisher;
+use crate::reserves::factory::create_reserve_service;
+use crate::utils::ddb::{build_region_client, create_index, create_table, ScanGuard, TableBilling};
+use crate::utils::region::ClientRole;
 
 pub(crate) async fn create_checkout_repository(store: RepositoryStore) -> Box<dyn CheckoutRepository> {
+    create_checkout_repository_for(store, ClientRole::Write).await
+}
+
+pub(crate) async fn create_checkout_repository_for(store: RepositoryStore, role: ClientRole) -> Box<dyn CheckoutRepository> {
     match store {
         RepositoryStore::DynamoDB => {
-            let client = build_db_client(store).await;
-            Box::new(DDBCheckoutRepository::new(client, "checkout", "checkout_ndx"))
+            let client = build_region_client(store, role).await;
+            Box::new(DDBCheckoutRepository::new(client, "checkout", "checkout_ndx")
+                .with_scan_guard(ScanGuard::new(store)))
         }
-        RepositoryStore::LocalDynamoDB => {
-            let client = build_db_client(store).await;
-            let _ = create_table(&client, "checkout", "checkout_id", "checkout_status", "patron_id").await;
-            Box::new(DDBCheckoutRepository::new(client, "checkout", "checkout_ndx"))
+        _ => {
+            let client = build_region_client(store, role).await;
+            let table_name = store.table_name("checkout");
+            let _ = create_table(&client, table_name.as_str(), "checkout_id", "checkout_status", "patron_id").await;
+            let _ = create_index(&client, table_name.as_str(), BRANCH_INDEX, "branch_id", "checkout_id", TableBilling::default()).await;
+            Box::new(DDBCheckoutRepository::new(client, table_name.as_str(), store.table_name("checkout_ndx").as_str()))
         }
     }
 }
 
-pub(crate) async fn create_checkout_service(config: &Configuration, store: RepositoryStore) -> Box<dyn CheckoutService> {
+pub async fn create_checkout_query_service(config: &Configuration, store: RepositoryStore) -> Box<dyn CheckoutQueryService> {
+    let checkout_repo = factory::create_checkout_repository_for(store, ClientRole::Read).await;
+    let branch_svc = create_branch_query_service(config, store).await;
+    Box::new(CheckoutQueryServiceImpl::new(checkout_repo, branch_svc))
+}
+
+pub async fn create_checkout_service(config: &Configuration, store: RepositoryStore) -> Box<dyn CheckoutService> {
     let checkout_repo = factory::create_checkout_repository(store).await;
     let catalog_svc = create_catalog_service(config, store).await;
     let patron_svc = create_patron_service(config, store).await;
-    let publisher = create_publisher(store.gateway_publisher()).await;
+    let reserve_svc = create_reserve_service(config, store).await;
+    let audit_svc = create_audit_service(config, store).await;
+    let hold_svc = create_hold_service(config, store).await;
+    let branch_svc = create_branch_query_service(config, store).await;
+    let notification_svc = create_notification_service(store).await;
+    let fine_svc = create_fine_service(config, store).await;
+    let publisher = create_projecting_publisher(store).await;
+    let query_svc = create_checkout_query_service(config, store).await;
     Box::new(CheckoutServiceImpl::new(config, checkout_repo,
-                                      patron_svc, catalog_svc, publisher))
+                                      patron_svc, catalog_svc, reserve_svc, audit_svc, hold_svc, branch_svc,
+                                      notification_svc, fine_svc, publisher, query_svc))
 }
@@ -0,0 +1,212 @@
+use crate::checkout::dto::ReceiptDto;
+use crate::core::library::{LibraryError, LibraryResult};
+
+// characters per line of Font A on 80mm rolls, narrower rolls wrap the lines themselves
+pub(crate) const RECEIPT_COLUMNS: usize = 42;
+
+pub(crate) const ESCPOS_CONTENT_TYPE: &str = "application/vnd.escpos";
+
+const ESC: u8 = 0x1b;
+const GS: u8 = 0x1d;
+const LF: u8 = b'\n';
+
+// labels take the width of the longest label "Returned:" and a space, so values of checkout and check-in receipts
+// start in the same column and wrapped values are indented by the same width
+const LABEL_WIDTH: usize = 10;
+
+// ReceiptFormat is the rendering of a receipt sent to desk clients, printers without a driver take ESC/POS
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) enum ReceiptFormat {
+    #[default]
+    Json,
+    Text,
+    EscPos,
+}
+
+impl ReceiptFormat {
+    // the `format` parameter takes precedence over the Accept header so that desk hardware that cannot set headers
+    // still gets its format, unknown parameters are rejected while unknown media types fall back to json
+    pub(crate) fn negotiate(format: Option<&str>, accept: Option<&str>) -> LibraryResult<ReceiptFormat> {
+        if let Some(format) = format {
+            return match format.trim().to_lowercase().as_str() {
+                "json" => Ok(ReceiptFormat::Json),
+                "text" | "txt" => Ok(ReceiptFormat::Text),
+                "escpos" => Ok(ReceiptFormat::EscPos),
+                other => Err(LibraryError::validation(
+                    format!("unsupported receipt format {}, expected json, text or escpos", other).as_str(), Some("400".to_string()))),
+            };
+        }
+        let media_types = accept.unwrap_or_default().split(',')
+            .map(|media_type| media_type.split(';').next().unwrap_or_default().trim().to_lowercase())
+            .collect::<Vec<String>>();
+        for media_type in media_types {
+            match media_type.as_str() {
+                ESCPOS_CONTENT_TYPE | "application/octet-stream" => return Ok(ReceiptFormat::EscPos),
+                "text/plain" => return Ok(ReceiptFormat::Text),
+                "application/json" => return Ok(ReceiptFormat::Json),
+                _ => {}
+            }
+        }
+        Ok(ReceiptFormat::Json)
+    }
+}
+
+fn heading(receipt: &ReceiptDto) -> &'static str {
+    if receipt.returned_at.is_some() { "CHECK-IN RECEIPT" } else { "CHECKOUT RECEIPT" }
+}
+
+// label and value lines of the receipt, values longer than the line are wrapped under the value
+fn body_lines(receipt: &ReceiptDto, columns: usize) -> Vec<String> {
+    let mut rows = vec![
+        ("Item", receipt.title.to_string()),
+        ("ISBN", receipt.isbn.to_string()),
+        ("Out", receipt.checkout_at.format("%Y-%m-%d %H:%M").to_string()),
+    ];
+    match receipt.returned_at {
+        Some(returned_at) => rows.push(("Returned", returned_at.format("%Y-%m-%d %H:%M").to_string())),
+        None => rows.push(("Due", receipt.due_at.format("%Y-%m-%d").to_string())),
+    }
+    rows.push(("Receipt", receipt.checkout_id.to_string()));
+    let mut lines = vec![];
+    for (label, value) in rows {
+        for (i, part) in wrap(value.as_str(), columns.saturating_sub(LABEL_WIDTH).max(1)).into_iter().enumerate() {
+            let label = if i == 0 { format!("{}:", label) } else { String::new() };
+            lines.push(format!("{:<width$}{}", label, part, width = LABEL_WIDTH));
+        }
+    }
+    lines
+}
+
+// plain text of a receipt for line printers and printers behind a generic text driver
+pub(crate) fn to_text(receipt: &ReceiptDto, columns: usize) -> String {
+    let rule = "-".repeat(columns);
+    let mut lines = vec![center(heading(receipt), columns), center(clean(receipt.branch_name.as_str()).as_str(), columns), rule.to_string()];
+    lines.extend(body_lines(receipt, columns));
+    lines.push(rule);
+    lines.iter().map(|line| format!("{}\n", line.trim_end())).collect()
+}
+
+// ESC/POS commands of thermal receipt printers: the heading is printed bold at double size, the body in Font A and the
+// paper is fed past the cutter before a partial cut, text is sent in code page 1252
+pub(crate) fn to_escpos(receipt: &ReceiptDto, columns: usize) -> Vec<u8> {
+    let mut out = vec![ESC, b'@', ESC, b't', 16];
+    out.extend([ESC, b'a', 1, ESC, b'E', 1, GS, b'!', 0x11]);
+    out.extend(encode(heading(receipt)));
+    out.extend([LF, GS, b'!', 0, ESC, b'E', 0]);
+    out.extend(encode(clean(receipt.branch_name.as_str()).as_str()));
+    out.extend([LF, ESC, b'a', 0]);
+    out.extend(encode("-".repeat(columns).as_str()));
+    out.push(LF);
+    for line in body_lines(receipt, columns) {
+        out.extend(encode(line.trim_end()));
+        out.push(LF);
+    }
+    out.extend(encode("-".repeat(columns).as_str()));
+    out.extend([LF, ESC, b'd', 4, GS, b'V', 66, 0]);
+    out
+}
+
+// titles and branch names come from staff and imported records, control characters are dropped so that they cannot
+// smuggle printer commands into the stream
+fn clean(value: &str) -> String {
+    value.chars().map(|c| if c.is_control() { ' ' } else { c }).collect::<String>().split_whitespace()
+        .collect::<Vec<&str>>().join(" ")
+}
+
+fn wrap(value: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::new();
+    for word in clean(value).split(' ') {
+        let mut word = word.to_string();
+        while word.chars().count() > width {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            let head = word.chars().take(width).collect::<String>();
+            word = word.chars().skip(width).collect();
+            lines.push(head);
+        }
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word.as_str());
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+fn center(value: &str, columns: usize) -> String {
+    let len = value.chars().count();
+    if len >= columns {
+        return value.to_string();
+    }
+    format!("{}{}", " ".repeat((columns - len) / 2), value)
+}
+
+// code page 1252 matches Latin-1 above 0xA0, characters the printer cannot print become '?'
+fn encode(value: &str) -> Vec<u8> {
+    value.chars().map(|c| match c {
+        ' '..='~' => c as u8,
+        '\u{a0}'..='\u{ff}' => c as u32 as u8,
+        '€' => 0x80,
+        _ => b'?',
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::books::dto::BookDto;
+    use crate::checkout::dto::{CheckoutDto, ReceiptDto};
+    use crate::checkout::printer::{to_escpos, to_text, ReceiptFormat, RECEIPT_COLUMNS};
+
+    fn receipt(title: &str) -> ReceiptDto {
+        let checkout = CheckoutDto::new("book1", "patron1");
+        let book = BookDto::builder().isbn("isbn1").title(title).build().expect("should build book");
+        ReceiptDto::new(&checkout, &book, "Central")
+    }
+
+    #[tokio::test]
+    async fn test_should_negotiate_receipt_format() {
+        assert_eq!(ReceiptFormat::Json, ReceiptFormat::negotiate(None, None).expect("should negotiate"));
+        assert_eq!(ReceiptFormat::EscPos, ReceiptFormat::negotiate(Some("ESCPOS"), Some("application/json")).expect("should negotiate"));
+        assert_eq!(ReceiptFormat::Text, ReceiptFormat::negotiate(None, Some("text/html, text/plain;q=0.8")).expect("should negotiate"));
+        assert_eq!(ReceiptFormat::EscPos, ReceiptFormat::negotiate(None, Some("application/vnd.escpos")).expect("should negotiate"));
+        assert_eq!(ReceiptFormat::Json, ReceiptFormat::negotiate(None, Some("*/*")).expect("should negotiate"));
+        assert!(ReceiptFormat::negotiate(Some("pdf"), None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_render_text_receipt() {
+        let receipt = receipt("A Very Long Title Of A Book That Does Not Fit On One Line Of The Roll");
+        let text = to_text(&receipt, RECEIPT_COLUMNS);
+        assert!(text.contains("CHECKOUT RECEIPT"));
+        assert!(text.contains("Central"));
+        assert!(text.contains(format!("Due:      {}", receipt.due_at.format("%Y-%m-%d")).as_str()));
+        assert!(text.lines().all(|line| line.chars().count() <= RECEIPT_COLUMNS));
+        assert!(text.lines().any(|line| line.starts_with("          ") && line.contains("Line")));
+
+        // values of check-in receipts start in the same column
+        let mut returned = receipt.clone();
+        returned.returned_at = Some(receipt.due_at);
+        let text = to_text(&returned, RECEIPT_COLUMNS);
+        assert!(text.contains(format!("Returned: {}", receipt.due_at.format("%Y-%m-%d %H:%M")).as_str()));
+        assert!(text.lines().any(|line| line.starts_with("Receipt:  ")));
+    }
+
+    #[tokio::test]
+    async fn test_should_render_escpos_receipt() {
+        let receipt = receipt("Caf\u{e9} \u{1b}@ \u{1d}V\u{0} Stories \u{4e66}");
+        let bytes = to_escpos(&receipt, RECEIPT_COLUMNS);
+        assert!(bytes.starts_with(&[0x1b, b'@', 0x1b, b't', 16]));
+        assert!(bytes.ends_with(&[0x1b, b'd', 4, 0x1d, b'V', 66, 0]));
+        // control characters of the title are dropped and the rest is encoded in code page 1252
+        let title = b"Item:     Caf\xe9 @ V Stories ?\n";
+        assert!(bytes.windows(title.len()).any(|w| w == title));
+        assert_eq!(1, bytes.windows(2).filter(|w| *w == [0x1b, b'@']).count());
+    }
+}
@@ -2,6 +2,7 @@ pub mod ddb_checkout_repository;
 
 use async_trait::async_trait;
 use std::collections::HashMap;
+use chrono::NaiveDateTime;
 use crate::checkout::domain::model::CheckoutEntity;
 use crate::core::library::{LibraryResult, PaginatedResult};
 use crate::core::repository::Repository;
@@ -11,4 +12,20 @@ use crate::core::repository::Repository;
 pub(crate) trait CheckoutRepository : Repository<CheckoutEntity> {
     async fn query_overdue(&self, predicate: &HashMap::<String, String>,
                    page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<CheckoutEntity>>;
+    // returns checkouts that are not returned yet and are due by the given time, including overdue checkouts
+    async fn query_due_by(&self, due_by: NaiveDateTime,
+                          page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<CheckoutEntity>>;
+    // returns the checkout of the book that has not been returned yet
+    async fn find_active_by_book(&self, book_id: &str) -> LibraryResult<Option<CheckoutEntity>>;
+    // returns all checkouts of the book that have not been returned yet, digital books are checked out by many patrons
+    async fn find_all_active_by_book(&self, book_id: &str) -> LibraryResult<Vec<CheckoutEntity>>;
+    // points the checkout to the book of the entity, used when duplicate catalog records are merged
+    async fn reassign_book(&self, entity: &CheckoutEntity) -> LibraryResult<usize>;
+    // creates checkouts of a batch together, none of them is created when one of them fails
+    async fn create_all(&self, entities: &[CheckoutEntity]) -> LibraryResult<usize>;
+    // returns checkouts made at the branch within the time range, most recent first
+    async fn find_recent(&self, branch_id: &str, since: NaiveDateTime, until: NaiveDateTime,
+                         page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<CheckoutEntity>>;
+    // reads checkouts of all statuses page by page, used by the invariants job
+    async fn scan(&self, page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<CheckoutEntity>>;
 }
@@ -3,20 +3,28 @@ use std::collections::HashMap;
 
 use async_trait::async_trait;
 use aws_sdk_dynamodb::Client;
-use aws_sdk_dynamodb::types::AttributeValue;
-use chrono::Utc;
+use aws_sdk_dynamodb::types::{AttributeValue, ReturnConsumedCapacity};
+use chrono::{NaiveDateTime, Utc};
 
 use crate::checkout::domain::model::CheckoutEntity;
 use crate::checkout::repository::CheckoutRepository;
-use crate::core::library::{CheckoutStatus, LibraryError, LibraryResult, PaginatedResult};
+use crate::core::ids::branch_scoped_range;
+use crate::core::invariants::assert_invariants;
+use crate::core::library::{BookFormat, CheckoutStatus, LibraryError, LibraryResult, PaginatedResult};
 use crate::core::repository::Repository;
-use crate::utils::ddb::{add_filter_expr, from_ddb, opt_string_date, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, string_date, to_ddb_page};
+use crate::utils::ddb::{add_filter_expr, from_ddb, opt_string_date, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, string_date, to_ddb_page, transact_put_items, ScanGuard};
+use crate::utils::metrics::OperationMeter;
+
+// suffix of the index of checkouts by branch sorted by their branch scoped ids
+pub(crate) const BRANCH_INDEX: &str = "branch_ndx";
 
 #[derive(Debug)]
 pub(crate) struct DDBCheckoutRepository {
     client: Client,
     table_name: String,
     index_name: String,
+    branch_index_name: String,
+    scan_guard: ScanGuard,
 }
 
 impl DDBCheckoutRepository {
@@ -25,13 +33,21 @@ impl DDBCheckoutRepository {
             client,
             table_name: table_name.to_string(),
             index_name: index_name.to_string(),
+            branch_index_name: format!("{}_{}", table_name, BRANCH_INDEX),
+            scan_guard: ScanGuard::default(),
         }
     }
+
+    pub(crate) fn with_scan_guard(mut self, scan_guard: ScanGuard) -> Self {
+        self.scan_guard = scan_guard;
+        self
+    }
 }
 
 #[async_trait]
 impl Repository<CheckoutEntity> for DDBCheckoutRepository {
     async fn create(&self, entity: &CheckoutEntity) -> LibraryResult<usize> {
+        assert_invariants(entity);
         let table_name: &str = self.table_name.as_ref();
         let val = serde_json::to_value(entity)?;
         self.client
@@ -44,6 +60,7 @@ impl Repository<CheckoutEntity> for DDBCheckoutRepository {
     }
 
     async fn update(&self, entity: &CheckoutEntity) -> LibraryResult<usize> {
+        assert_invariants(entity);
         let now = Utc::now().naive_utc();
         let table_name: &str = self.table_name.as_ref();
 
@@ -51,10 +68,11 @@ impl Repository<CheckoutEntity> for DDBCheckoutRepository {
             .update_item()
             .table_name(table_name)
             .key("checkout_id", AttributeValue::S(entity.checkout_id.clone()))
-            .update_expression("SET version = :version, checkout_status = :checkout_status, due_at = :due_at, returned_at = :returned_at, updated_at = :updated_at")
+            .update_expression("SET version = :version, checkout_status = :checkout_status, checkout_at = :checkout_at, due_at = :due_at, returned_at = :returned_at, updated_at = :updated_at")
             .expression_attribute_values(":old_version", AttributeValue::N(entity.version.to_string()))
             .expression_attribute_values(":version", AttributeValue::N((entity.version + 1).to_string()))
             .expression_attribute_values(":checkout_status", AttributeValue::S(entity.checkout_status.to_string()))
+            .expression_attribute_values(":checkout_at", string_date(entity.checkout_at))
             .expression_attribute_values(":due_at", string_date(entity.due_at))
             .expression_attribute_values(":returned_at", opt_string_date(entity.returned_at))
             .expression_attribute_values(":updated_at", string_date(now))
@@ -65,6 +83,7 @@ impl Repository<CheckoutEntity> for DDBCheckoutRepository {
 
     async fn get(&self, id: &str) -> LibraryResult<CheckoutEntity> {
         let table_name: &str = self.table_name.as_ref();
+        let meter = OperationMeter::start(table_name, "get").key("checkout_id");
         self.client
             .query()
             .table_name(table_name)
@@ -77,8 +96,10 @@ impl Repository<CheckoutEntity> for DDBCheckoutRepository {
                 ":checkout_id",
                 AttributeValue::S(id.to_string()),
             )
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
             .send()
             .await.map_err(LibraryError::from).and_then(|req| {
+            meter.finish(req.consumed_capacity());
             if let Some(items) = req.items {
                 if items.len() > 1 {
                     return Err(LibraryError::database(format!("too many checkout for {}", id).as_str(), None, false));
@@ -127,6 +148,7 @@ impl Repository<CheckoutEntity> for DDBCheckoutRepository {
             key_cond.push_str(" AND patron_id = :patron_id");
             request = request.expression_attribute_values(":patron_id", AttributeValue::S(patron_id.to_string()));
         }
+        let meter = OperationMeter::start(table_name, "query").index(index_name).key(key_cond.as_str()).page_size(page_size);
         request = request.key_condition_expression(key_cond);
         let mut filter_expr = String::new();
         // then handle other filters
@@ -140,8 +162,10 @@ impl Repository<CheckoutEntity> for DDBCheckoutRepository {
             request = request.filter_expression(filter_expr);
         }
         request
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
             .send()
             .await.map_err(LibraryError::from).map(|req| {
+            meter.finish(req.consumed_capacity());
             let records = req.items.as_ref().unwrap_or(&vec![]).iter()
                 .map(CheckoutEntity::from).collect();
             from_ddb(page, page_size, req.last_evaluated_key(), records)
@@ -151,6 +175,15 @@ impl Repository<CheckoutEntity> for DDBCheckoutRepository {
 
 #[async_trait]
 impl CheckoutRepository for DDBCheckoutRepository {
+    async fn create_all(&self, entities: &[CheckoutEntity]) -> LibraryResult<usize> {
+        let mut items = vec![];
+        for entity in entities {
+            assert_invariants(entity);
+            items.push(serde_json::to_value(entity)?);
+        }
+        transact_put_items(&self.client, self.table_name.as_str(), "checkout_id", items).await
+    }
+
     async fn query_overdue(&self,
                            predicate: &HashMap<String, String>,
                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<CheckoutEntity>> {
@@ -164,6 +197,134 @@ impl CheckoutRepository for DDBCheckoutRepository {
         }
         self.query(&new_predicate, page, page_size).await
     }
+
+    async fn query_due_by(&self, due_by: NaiveDateTime,
+                          page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<CheckoutEntity>> {
+        let predicate = HashMap::from([
+            ("checkout_status".to_string(), CheckoutStatus::CheckedOut.to_string()),
+            ("due_at:<=".to_string(), string_date(due_by).as_s().unwrap_or(&"0".to_string()).to_string()),
+        ]);
+        self.query(&predicate, page, page_size).await
+    }
+
+    async fn find_active_by_book(&self, book_id: &str) -> LibraryResult<Option<CheckoutEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let mut last_key: Option<HashMap<String, AttributeValue>> = None;
+        loop {
+            let res = self.client
+                .query()
+                .table_name(table_name)
+                .index_name(index_name)
+                .consistent_read(false)
+                .set_exclusive_start_key(last_key)
+                .key_condition_expression("checkout_status = :checkout_status")
+                .filter_expression("book_id = :book_id")
+                .expression_attribute_values(":checkout_status", AttributeValue::S(CheckoutStatus::CheckedOut.to_string()))
+                .expression_attribute_values(":book_id", AttributeValue::S(book_id.to_string()))
+                .send()
+                .await.map_err(LibraryError::from)?;
+            if let Some(item) = res.items.as_ref().and_then(|items| items.first()) {
+                return Ok(Some(CheckoutEntity::from(item)));
+            }
+            last_key = res.last_evaluated_key().cloned();
+            if last_key.is_none() {
+                return Ok(None);
+            }
+        }
+    }
+
+    async fn find_all_active_by_book(&self, book_id: &str) -> LibraryResult<Vec<CheckoutEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let mut checkouts = vec![];
+        let mut last_key: Option<HashMap<String, AttributeValue>> = None;
+        loop {
+            let res = self.client
+                .query()
+                .table_name(table_name)
+                .index_name(index_name)
+                .consistent_read(false)
+                .set_exclusive_start_key(last_key)
+                .key_condition_expression("checkout_status = :checkout_status")
+                .filter_expression("book_id = :book_id")
+                .expression_attribute_values(":checkout_status", AttributeValue::S(CheckoutStatus::CheckedOut.to_string()))
+                .expression_attribute_values(":book_id", AttributeValue::S(book_id.to_string()))
+                .send()
+                .await.map_err(LibraryError::from)?;
+            checkouts.extend(res.items.as_ref().unwrap_or(&vec![]).iter().map(CheckoutEntity::from));
+            last_key = res.last_evaluated_key().cloned();
+            if last_key.is_none() {
+                return Ok(checkouts);
+            }
+        }
+    }
+
+    async fn reassign_book(&self, entity: &CheckoutEntity) -> LibraryResult<usize> {
+        assert_invariants(entity);
+        let now = Utc::now().naive_utc();
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .update_item()
+            .table_name(table_name)
+            .key("checkout_id", AttributeValue::S(entity.checkout_id.clone()))
+            .update_expression("SET version = :version, book_id = :book_id, updated_at = :updated_at")
+            .expression_attribute_values(":old_version", AttributeValue::N(entity.version.to_string()))
+            .expression_attribute_values(":version", AttributeValue::N((entity.version + 1).to_string()))
+            .expression_attribute_values(":book_id", AttributeValue::S(entity.book_id.to_string()))
+            .expression_attribute_values(":updated_at", string_date(now))
+            .condition_expression("attribute_exists(version) AND version = :old_version")
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn find_recent(&self, branch_id: &str, since: NaiveDateTime, until: NaiveDateTime,
+                         page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<CheckoutEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.branch_index_name.as_ref();
+        let key = HashMap::from([
+            ("branch_id".to_string(), branch_id.to_string()),
+        ]);
+        let (lower, upper) = branch_scoped_range(branch_id, since, until);
+        let meter = OperationMeter::start(table_name, "find_recent").index(index_name).key("branch_id").page_size(page_size);
+        self.client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .limit(cmp::min(page_size, 500) as i32)
+            .consistent_read(false)
+            .scan_index_forward(false)
+            .set_exclusive_start_key(to_ddb_page(page, &key))
+            .key_condition_expression("branch_id = :branch_id AND checkout_id BETWEEN :lower AND :upper")
+            .expression_attribute_values(":branch_id", AttributeValue::S(branch_id.to_string()))
+            .expression_attribute_values(":lower", AttributeValue::S(lower))
+            .expression_attribute_values(":upper", AttributeValue::S(upper))
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            meter.finish(req.consumed_capacity());
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(CheckoutEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+
+    async fn scan(&self, page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<CheckoutEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        self.scan_guard.check(table_name)?;
+        self.client
+            .scan()
+            .table_name(table_name)
+            .consistent_read(false)
+            .set_exclusive_start_key(to_ddb_page(page, &HashMap::new()))
+            .limit(cmp::min(page_size, 500) as i32)
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(CheckoutEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
 }
 
 impl From<&HashMap<String, AttributeValue>> for CheckoutEntity {
@@ -175,6 +336,7 @@ impl From<&HashMap<String, AttributeValue>> for CheckoutEntity {
             book_id: parse_string_attribute("book_id", map).unwrap_or_else(|| String::from("")),
             patron_id: parse_string_attribute("patron_id", map).unwrap_or_else(|| String::from("")),
             checkout_status: CheckoutStatus::from(parse_string_attribute("checkout_status", map).unwrap_or_else(|| CheckoutStatus::CheckedOut.to_string())),
+            book_format: BookFormat::from(parse_string_attribute("book_format", map).unwrap_or_else(|| BookFormat::Physical.to_string())),
             checkout_at: Default::default(),
             due_at: parse_date_attribute("due_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
             returned_at: parse_date_attribute("returned_at", map),
@@ -187,31 +349,29 @@ impl From<&HashMap<String, AttributeValue>> for CheckoutEntity {
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
-    use async_once::AsyncOnce;
     use aws_sdk_dynamodb::Client;
     use chrono::NaiveDateTime;
-    use lazy_static::lazy_static;
 
     use crate::checkout::domain::model::CheckoutEntity;
+    use crate::checkout::repository::CheckoutRepository;
     use crate::checkout::repository::ddb_checkout_repository::DDBCheckoutRepository;
+    use crate::core::ids::BookId;
     use crate::core::library::CheckoutStatus;
     use crate::core::repository::{Repository, RepositoryStore};
-    use crate::utils::ddb::{build_db_client, create_table, delete_table};
+    use crate::utils::ddb::{build_db_client, create_table};
     use crate::utils::date::DATE_FMT;
+    use crate::utils::testing::test_store;
 
-    lazy_static! {
-        static ref CLIENT: AsyncOnce<Client> = AsyncOnce::new(async {
-                let client = build_db_client(RepositoryStore::LocalDynamoDB).await;
-                let _ = delete_table(&client, "checkout").await;
-                let _ = create_table(&client, "checkout", "checkout_id", "checkout_status", "patron_id").await;
-                client
-            });
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("checkout").as_str(), "checkout_id", "checkout_status", "patron_id").await;
+        client
     }
 
     #[tokio::test]
     async fn test_should_create_get_checkout() {
-        let checkout_repo = DDBCheckoutRepository::new(
-            CLIENT.get().await.clone(), "checkout", "checkout_ndx");
+        let store = test_store(module_path!(), "test_should_create_get_checkout");
+        let checkout_repo = DDBCheckoutRepository::new(build_client(store).await, store.table_name("checkout").as_str(), store.table_name("checkout_ndx").as_str());
         let checkout = CheckoutEntity::new("book1", "patron1");
         let size = checkout_repo.create(&checkout).await.expect("should create checkout");
         assert_eq!(1, size);
@@ -220,11 +380,28 @@ mod tests {
         assert_eq!(checkout.checkout_id, loaded.checkout_id);
     }
 
+    #[tokio::test]
+    async fn test_should_create_all_checkouts_together() {
+        let store = test_store(module_path!(), "test_should_create_all_checkouts_together");
+        let checkout_repo = DDBCheckoutRepository::new(build_client(store).await, store.table_name("checkout").as_str(), store.table_name("checkout_ndx").as_str());
+        let first = CheckoutEntity::new("book1", "patron1");
+        let second = CheckoutEntity::new("book2", "patron1");
+        let size = checkout_repo.create_all(&[first.clone(), second.clone()]).await.expect("should create checkouts");
+        assert_eq!(2, size);
+        let _ = checkout_repo.get(second.checkout_id.as_str()).await.expect("should return checkout");
+
+        // existing checkout fails the transaction so the new checkout is not created either
+        let third = CheckoutEntity::new("book3", "patron1");
+        assert!(checkout_repo.create_all(&[third.clone(), first]).await.is_err());
+        assert!(checkout_repo.get(third.checkout_id.as_str()).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_should_create_update_checkout() {
-        let checkout_repo = DDBCheckoutRepository::new(
-            CLIENT.get().await.clone(), "checkout", "checkout_ndx");
+        let store = test_store(module_path!(), "test_should_create_update_checkout");
+        let checkout_repo = DDBCheckoutRepository::new(build_client(store).await, store.table_name("checkout").as_str(), store.table_name("checkout_ndx").as_str());
         let mut checkout = CheckoutEntity::new("book2", "patron2");
+        checkout.checkout_at = NaiveDateTime::parse_from_str("2023-04-01T10:10:10.0", DATE_FMT).unwrap();
         let size = checkout_repo.create(&checkout).await.expect("should create checkout");
         assert_eq!(1, size);
 
@@ -240,8 +417,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_should_create_query_checkout() {
-        let checkout_repo = DDBCheckoutRepository::new(
-            CLIENT.get().await.clone(), "checkout", "checkout_ndx");
+        let store = test_store(module_path!(), "test_should_create_query_checkout");
+        let checkout_repo = DDBCheckoutRepository::new(build_client(store).await, store.table_name("checkout").as_str(), store.table_name("checkout_ndx").as_str());
         add_test_checkout(&checkout_repo, CheckoutStatus::Returned).await;
         let mut next_page = None;
         let mut total = 0;
@@ -272,8 +449,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_should_create_delete_checkout() {
-        let checkout_repo = DDBCheckoutRepository::new(
-            CLIENT.get().await.clone(), "checkout", "checkout_ndx");
+        let store = test_store(module_path!(), "test_should_create_delete_checkout");
+        let checkout_repo = DDBCheckoutRepository::new(build_client(store).await, store.table_name("checkout").as_str(), store.table_name("checkout_ndx").as_str());
         let checkout = CheckoutEntity::new("book1", "patron1");
         let size = checkout_repo.create(&checkout).await.expect("should create checkout");
         assert_eq!(1, size);
@@ -285,10 +462,45 @@ mod tests {
         assert!(loaded.is_err());
     }
 
+    #[tokio::test]
+    async fn test_should_find_active_checkout_by_book() {
+        let store = test_store(module_path!(), "test_should_find_active_checkout_by_book");
+        let checkout_repo = DDBCheckoutRepository::new(build_client(store).await, store.table_name("checkout").as_str(), store.table_name("checkout_ndx").as_str());
+        let mut returned = CheckoutEntity::new("active_book", "patron1");
+        returned.checkout_status = CheckoutStatus::Returned;
+        let _ = checkout_repo.create(&returned).await.expect("should create checkout");
+        assert!(checkout_repo.find_active_by_book("active_book").await.expect("should query checkout").is_none());
+
+        let active = CheckoutEntity::new("active_book", "patron2");
+        let _ = checkout_repo.create(&active).await.expect("should create checkout");
+        let loaded = checkout_repo.find_active_by_book("active_book").await
+            .expect("should query checkout").expect("should find checkout");
+        assert_eq!(active.checkout_id, loaded.checkout_id);
+    }
+
+    #[tokio::test]
+    async fn test_should_reassign_active_checkouts_of_book() {
+        let store = test_store(module_path!(), "test_should_reassign_active_checkouts_of_book");
+        let checkout_repo = DDBCheckoutRepository::new(build_client(store).await, store.table_name("checkout").as_str(), store.table_name("checkout_ndx").as_str());
+        let (from, to) = (BookId::generate(), BookId::generate());
+        for patron_id in ["patron1", "patron2"] {
+            let _ = checkout_repo.create(&CheckoutEntity::new(from.as_str(), patron_id)).await.expect("should create checkout");
+        }
+        let active = checkout_repo.find_all_active_by_book(from.as_str()).await.expect("should query checkouts");
+        assert_eq!(2, active.len());
+        for checkout in &active {
+            let moved = CheckoutEntity { book_id: to.to_string(), ..checkout.clone() };
+            assert_eq!(1, checkout_repo.reassign_book(&moved).await.expect("should reassign checkout"));
+        }
+        assert!(checkout_repo.find_all_active_by_book(from.as_str()).await.expect("should query checkouts").is_empty());
+        assert_eq!(2, checkout_repo.find_all_active_by_book(to.as_str()).await.expect("should query checkouts").len());
+    }
+
     async fn add_test_checkout(checkout_repo: &DDBCheckoutRepository, status: CheckoutStatus) {
         for i in 0..50 {
             let mut checkout = CheckoutEntity::new("book1", "patron1");
             checkout.checkout_status = status;
+            checkout.checkout_at = NaiveDateTime::parse_from_str("2023-04-01T11:11:11", DATE_FMT).unwrap();
             checkout.due_at = NaiveDateTime::parse_from_str("2023-04-11T11:11:11", DATE_FMT).unwrap();
             if i % 2 == 0 {
                 checkout.returned_at = Some(NaiveDateTime::parse_from_str("2023-07-17T17:17:17", DATE_FMT).unwrap());
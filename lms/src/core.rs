@@ -1,6 +1,10 @@
 pub mod domain;
 pub mod command;
+pub mod context;
 pub mod events;
+pub mod ids;
+pub mod invariants;
 pub mod library;
 pub mod repository;
+pub mod tasks;
 pub mod controller;
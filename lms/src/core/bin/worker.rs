@@ -0,0 +1,32 @@
+use std::time::Duration;
+use lambda_http::Error;
+use tracing::log::warn;
+use lms::tasks::create_task_worker;
+use lms::{setup_tracing, RepositoryStore};
+
+const DEV_MODE: bool = true;
+const BATCH_SIZE: usize = 10;
+// pause between polls of an idle or failing queue, SQS receives already wait for messages
+const IDLE_POLL: Duration = Duration::from_secs(1);
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    setup_tracing();
+
+    let store = if DEV_MODE {
+        RepositoryStore::LocalDynamoDB
+    } else {
+        RepositoryStore::DynamoDB
+    };
+    let worker = create_task_worker(store).await;
+    loop {
+        match worker.run_once(BATCH_SIZE).await {
+            Ok(0) => tokio::time::sleep(IDLE_POLL).await,
+            Ok(_) => {}
+            Err(err) => {
+                warn!("failed to process tasks due to {}", err);
+                tokio::time::sleep(IDLE_POLL).await;
+            }
+        }
+    }
+}
@@ -1,6 +1,21 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::core::context::RequestContext;
 use crate::core::library::LibraryError;
 
+pub mod middleware;
+#[cfg(feature = "examples")]
+pub mod examples;
+
 #[derive(Debug)]
 pub enum CommandError {
     Access {
@@ -41,6 +56,183 @@ pub trait Command<Request, Response> {
     async fn execute(&self, req: Request) -> Result<Response, CommandError>;
 }
 
+// CommandEnvelope describes a dispatched command to the middleware, the payload is the serialized request
+#[derive(Debug, Clone)]
+pub(crate) struct CommandEnvelope {
+    pub name: String,
+    pub context: Option<RequestContext>,
+    pub payload: Value,
+}
+
+impl CommandEnvelope {
+    pub fn new(name: &str, context: Option<RequestContext>, payload: Value) -> Self {
+        Self {
+            name: name.to_string(),
+            context,
+            payload,
+        }
+    }
+
+    // identity of the caller or empty for anonymous and background commands
+    pub fn principal(&self) -> String {
+        self.context.as_ref().map(|ctx| ctx.principal()).unwrap_or_default()
+    }
+
+    pub fn correlation_id(&self) -> String {
+        self.context.as_ref().map(|ctx| ctx.correlation_id.to_string()).unwrap_or_default()
+    }
+
+    pub fn has_idempotency_key(&self) -> bool {
+        self.context.as_ref().map(|ctx| !ctx.idempotency_key.is_empty()).unwrap_or(false)
+    }
+}
+
+// CommandMiddleware runs around the handler of every dispatched command, an error from before rejects
+// the command and after is invoked with the outcome for each middleware whose before succeeded
+#[async_trait]
+pub(crate) trait CommandMiddleware: Sync + Send {
+    async fn before(&self, _envelope: &CommandEnvelope) -> Result<(), CommandError> {
+        Ok(())
+    }
+
+    // response stored for the command that is returned instead of running the handler again
+    async fn replay(&self, _envelope: &CommandEnvelope) -> Option<Value> {
+        None
+    }
+
+    // invoked with the serialized response of commands sent with an idempotency key that succeeded
+    async fn completed(&self, _envelope: &CommandEnvelope, _response: &Value) {}
+
+    async fn after(&self, _envelope: &CommandEnvelope, _failure: Option<&CommandError>, _elapsed: Duration) {}
+}
+
+// CommandBus dispatches requests to the handler registered for the type of request and response through the
+// middleware pipeline in the order the middleware was added
+#[derive(Default)]
+pub(crate) struct CommandBus {
+    handlers: HashMap<(TypeId, TypeId), Box<dyn Any + Send + Sync>>,
+    middleware: Vec<Arc<dyn CommandMiddleware>>,
+}
+
+impl CommandBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // bus with the pipeline of middleware that is built once and shared by the buses of all requests
+    pub fn with_pipeline(middleware: &[Arc<dyn CommandMiddleware>]) -> Self {
+        Self {
+            handlers: HashMap::new(),
+            middleware: middleware.to_vec(),
+        }
+    }
+
+    pub fn with_middleware(mut self, middleware: Arc<dyn CommandMiddleware>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    // registers the handler for its request and response type, replacing any handler registered before, the returned
+    // handle dispatches with the response type of the handler
+    pub fn register<Request, Response, C>(mut self, handler: C) -> RegisteredCommand<Request, Response>
+        where C: Command<Request, Response> + Send + Sync + 'static, Request: 'static, Response: 'static {
+        let handler: Box<dyn Command<Request, Response> + Send + Sync> = Box::new(handler);
+        self.handlers.insert((TypeId::of::<Request>(), TypeId::of::<Response>()), Box::new(handler));
+        RegisteredCommand { bus: self, types: PhantomData }
+    }
+
+    pub async fn dispatch<Request, Response>(&self, req: Request) -> Result<Response, CommandError>
+        where Request: Serialize + Send + 'static, Response: Serialize + DeserializeOwned + Send + 'static {
+        let name = command_name::<Request>();
+        let handler = self.handlers.get(&(TypeId::of::<Request>(), TypeId::of::<Response>()))
+            .and_then(|handler| handler.downcast_ref::<Box<dyn Command<Request, Response> + Send + Sync>>())
+            .ok_or_else(|| CommandError::Other {
+                message: format!("no handler is registered for {}", name),
+                reason_code: Some("500".to_string()),
+            })?;
+        let payload = serde_json::to_value(&req).map_err(|err| CommandError::Serialization { message: err.to_string() })?;
+        let envelope = CommandEnvelope::new(name.as_str(), RequestContext::current(), payload);
+
+        let started = Instant::now();
+        let mut passed = 0;
+        let mut rejection = None;
+        let mut replayed = None;
+        for middleware in &self.middleware {
+            if let Err(err) = middleware.before(&envelope).await {
+                rejection = Some(err);
+                break;
+            }
+            passed += 1;
+            if let Some(response) = middleware.replay(&envelope).await {
+                replayed = Some(response);
+                break;
+            }
+        }
+        let res = match (rejection, replayed) {
+            (Some(err), _) => Err(err),
+            (None, Some(response)) => serde_json::from_value(response)
+                .map_err(|err| CommandError::Serialization { message: err.to_string() }),
+            (None, None) => {
+                let res = handler.execute(req).await;
+                // responses are only kept for commands that clients may retry with the same idempotency key
+                if let (Ok(response), true) = (&res, envelope.has_idempotency_key()) {
+                    if let Ok(response) = serde_json::to_value(response) {
+                        for middleware in self.middleware[..passed].iter().rev() {
+                            middleware.completed(&envelope, &response).await;
+                        }
+                    }
+                }
+                res
+            }
+        };
+        let elapsed = started.elapsed();
+        let failure = res.as_ref().err();
+        for middleware in self.middleware[..passed].iter().rev() {
+            middleware.after(&envelope, failure, elapsed).await;
+        }
+        res
+    }
+}
+
+// RegisteredCommand is the bus returned by register, it dispatches requests of the registered handler so that the
+// response type is known to callers
+pub(crate) struct RegisteredCommand<Request, Response> {
+    bus: CommandBus,
+    types: PhantomData<fn(Request) -> Response>,
+}
+
+impl<Request, Response> RegisteredCommand<Request, Response> {
+    pub async fn dispatch(&self, req: Request) -> Result<Response, CommandError>
+        where Request: Serialize + Send + 'static, Response: Serialize + DeserializeOwned + Send + 'static {
+        self.bus.dispatch(req).await
+    }
+
+    // the bus with the registered handler for dispatching with a response type of the caller's choosing
+    #[cfg(test)]
+    pub fn into_bus(self) -> CommandBus {
+        self.bus
+    }
+}
+
+// name of the command from its request type, e.g. CheckoutBookCommandRequest is named checkout_book
+pub(crate) fn command_name<Request>() -> String {
+    let type_name = std::any::type_name::<Request>();
+    let type_name = type_name.rsplit("::").next().unwrap_or(type_name);
+    let type_name = type_name.strip_suffix("CommandRequest").unwrap_or(type_name);
+    let mut name = String::new();
+    for (i, c) in type_name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                name.push('_');
+            }
+            name.extend(c.to_lowercase());
+        } else {
+            name.push(c);
+        }
+    }
+    name
+}
+
 impl From<LibraryError> for CommandError {
     fn from(other: LibraryError) -> Self {
         match other {
@@ -69,7 +261,7 @@ impl From<LibraryError> for CommandError {
                 CommandError::Serialization { message }
             }
             LibraryError::Runtime { message, reason_code } => {
-                CommandError::Runtime { message, reason_code, retryable: true }
+                CommandError::Runtime { message, reason_code, retryable: false }
             }
         }
     }
@@ -77,7 +269,132 @@ impl From<LibraryError> for CommandError {
 
 #[cfg(test)]
 mod tests {
-    use crate::core::command::CommandError;
+    use std::sync::{Arc, Mutex};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use async_trait::async_trait;
+    use serde::Serialize;
+    use crate::core::command::{Command, CommandBus, CommandEnvelope, CommandError, CommandMiddleware, command_name};
+    use crate::core::command::middleware::IdempotencyMiddleware;
+    use crate::core::context::RequestContext;
+
+    #[derive(Debug, Serialize)]
+    struct EchoCommandRequest {
+        message: String,
+    }
+
+    struct EchoCommand {}
+
+    #[async_trait]
+    impl Command<EchoCommandRequest, String> for EchoCommand {
+        async fn execute(&self, req: EchoCommandRequest) -> Result<String, CommandError> {
+            if req.message.is_empty() {
+                return Err(CommandError::Validation { message: "empty message".to_string(), reason_code: None });
+            }
+            Ok(req.message)
+        }
+    }
+
+    // records the calls of the pipeline and rejects messages starting with reject
+    struct RecordingMiddleware {
+        label: String,
+        calls: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl CommandMiddleware for RecordingMiddleware {
+        async fn before(&self, envelope: &CommandEnvelope) -> Result<(), CommandError> {
+            self.calls.lock().unwrap().push(format!("before {} {}", self.label, envelope.name));
+            if envelope.payload["message"].as_str().unwrap_or_default().starts_with("reject") {
+                return Err(CommandError::Access { message: "rejected".to_string(), reason_code: None });
+            }
+            Ok(())
+        }
+
+        async fn after(&self, _envelope: &CommandEnvelope, failure: Option<&CommandError>, _elapsed: Duration) {
+            self.calls.lock().unwrap().push(format!("after {} {}", self.label, failure.is_none()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_should_dispatch_through_middleware() {
+        let calls = Arc::new(Mutex::new(vec![]));
+        let bus = CommandBus::new()
+            .with_middleware(Arc::new(RecordingMiddleware { label: "outer".to_string(), calls: calls.clone() }))
+            .with_middleware(Arc::new(RecordingMiddleware { label: "inner".to_string(), calls: calls.clone() }))
+            .register(EchoCommand {});
+
+        let res = bus.dispatch(EchoCommandRequest { message: "hello".to_string() }).await.expect("should dispatch");
+        assert_eq!("hello", res.as_str());
+        assert_eq!(vec!["before outer echo", "before inner echo", "after inner true", "after outer true"],
+                   calls.lock().unwrap().clone());
+
+        calls.lock().unwrap().clear();
+        let res = bus.dispatch(EchoCommandRequest { message: "".to_string() }).await;
+        assert!(matches!(res, Err(CommandError::Validation { .. })));
+        assert_eq!(vec!["before outer echo", "before inner echo", "after inner false", "after outer false"],
+                   calls.lock().unwrap().clone());
+    }
+
+    #[tokio::test]
+    async fn test_should_stop_pipeline_on_rejection() {
+        let calls = Arc::new(Mutex::new(vec![]));
+        let bus = CommandBus::new()
+            .with_middleware(Arc::new(RecordingMiddleware { label: "outer".to_string(), calls: calls.clone() }))
+            .register(EchoCommand {});
+        let res = bus.dispatch(EchoCommandRequest { message: "reject me".to_string() }).await;
+        assert!(matches!(res, Err(CommandError::Access { .. })));
+        assert_eq!(vec!["before outer echo"], calls.lock().unwrap().clone());
+    }
+
+    #[tokio::test]
+    async fn test_should_reject_unregistered_command() {
+        let res: Result<String, CommandError> = CommandBus::new().dispatch(EchoCommandRequest { message: "hello".to_string() }).await;
+        assert!(matches!(res, Err(CommandError::Other { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_should_reject_command_with_other_response() {
+        let bus = CommandBus::new().register(EchoCommand {}).into_bus();
+        let res: Result<String, CommandError> = bus.dispatch(EchoCommandRequest { message: "hello".to_string() }).await;
+        assert_eq!("hello", res.expect("should dispatch").as_str());
+        let res: Result<usize, CommandError> = bus.dispatch(EchoCommandRequest { message: "hello".to_string() }).await;
+        assert!(matches!(res, Err(CommandError::Other { .. })));
+    }
+
+    // numbers the messages it echoes so that replayed responses can be told from new ones
+    struct CountingCommand {
+        count: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Command<EchoCommandRequest, String> for CountingCommand {
+        async fn execute(&self, req: EchoCommandRequest) -> Result<String, CommandError> {
+            let count = self.count.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(format!("{} {}", req.message, count))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_should_replay_response_of_idempotent_command() {
+        let bus = CommandBus::new()
+            .with_middleware(Arc::new(IdempotencyMiddleware::new()))
+            .register(CountingCommand { count: AtomicUsize::new(0) });
+        let mut ctx = RequestContext::anonymous("branch1", None, None);
+        ctx.idempotency_key = "key1".to_string();
+        let first = ctx.clone().scope(bus.dispatch(EchoCommandRequest { message: "hello".to_string() })).await;
+        let retry = ctx.scope(bus.dispatch(EchoCommandRequest { message: "hello".to_string() })).await;
+        assert_eq!("hello 1", first.expect("should dispatch").as_str());
+        assert_eq!("hello 1", retry.expect("should replay").as_str());
+        let res = bus.dispatch(EchoCommandRequest { message: "hello".to_string() }).await;
+        assert_eq!("hello 2", res.expect("should dispatch").as_str());
+    }
+
+    #[tokio::test]
+    async fn test_should_name_command_from_request() {
+        assert_eq!("echo", command_name::<EchoCommandRequest>().as_str());
+        assert_eq!("string", command_name::<String>().as_str());
+    }
 
     #[tokio::test]
     async fn test_should_build_command_error() {
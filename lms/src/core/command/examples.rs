@@ -0,0 +1,137 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use crate::catalog::command::add_book_cmd::{AddBookCommandRequest, AddBookCommandResponse};
+use crate::catalog::command::add_book_tags_cmd::{AddBookTagsCommandRequest, AddBookTagsCommandResponse};
+use crate::catalog::command::export_shelf_list_cmd::{ExportShelfListCommandRequest, ExportShelfListCommandResponse};
+use crate::catalog::command::find_books_by_author_cmd::{FindBooksByAuthorCommandRequest, FindBooksByAuthorCommandResponse};
+use crate::catalog::command::find_books_by_isbn_cmd::{FindBooksByIsbnCommandRequest, FindBooksByIsbnCommandResponse};
+use crate::catalog::command::find_books_by_tag_cmd::{FindBooksByTagCommandRequest, FindBooksByTagCommandResponse};
+use crate::catalog::command::find_duplicate_books_cmd::{FindDuplicateBooksCommandRequest, FindDuplicateBooksCommandResponse};
+use crate::catalog::command::find_related_books_cmd::{FindRelatedBooksCommandRequest, FindRelatedBooksCommandResponse};
+use crate::catalog::command::find_trending_books_cmd::{FindTrendingBooksCommandRequest, FindTrendingBooksCommandResponse};
+use crate::catalog::command::get_book_cmd::{GetBookCommandRequest, GetBookCommandResponse};
+use crate::catalog::command::get_cover_cmd::{GetCoverCommandRequest, GetCoverCommandResponse};
+use crate::catalog::command::get_tags_cmd::{GetTagsCommandRequest, GetTagsCommandResponse};
+use crate::catalog::command::merge_books_cmd::{MergeBooksCommandRequest, MergeBooksCommandResponse};
+use crate::catalog::command::remove_book_cmd::{RemoveBookCommandRequest, RemoveBookCommandResponse};
+use crate::catalog::command::remove_book_tags_cmd::{RemoveBookTagsCommandRequest, RemoveBookTagsCommandResponse};
+use crate::catalog::command::remove_books_cmd::{RemoveBooksCommandRequest, RemoveBooksCommandResponse};
+use crate::catalog::command::update_book_cmd::{UpdateBookCommandRequest, UpdateBookCommandResponse};
+use crate::catalog::command::update_location_cmd::{UpdateLocationCommandRequest, UpdateLocationCommandResponse};
+use crate::catalog::command::upload_cover_cmd::{UploadCoverCommandRequest, UploadCoverCommandResponse};
+
+// ids shared by examples so that the request and the response of a command describe the same book
+pub(crate) const EXAMPLE_BOOK_ID: &str = "0e3ad4b6-3e2f-4b8e-9d6a-5f1c2b7a9e10";
+pub(crate) const EXAMPLE_ISBN: &str = "9780441013593";
+
+// CommandExamples provides canonical sample payloads of a command request or response, examples must be
+// deterministic because they are compared against golden files and published as documentation
+pub(crate) trait CommandExamples: Serialize + Sized {
+    fn examples() -> Vec<Self>;
+}
+
+// timestamps of examples are fixed instead of the current time
+pub(crate) fn example_time() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2023, 5, 1).and_then(|date| date.and_hms_opt(10, 0, 0)).expect("should build example time")
+}
+
+// returns the examples of all command requests and responses keyed by type name, examples of requests are parsed
+// back so that they show what the command receives after defaults of missing fields are applied
+pub(crate) fn command_examples() -> Vec<(&'static str, Vec<Value>)> {
+    vec![
+        request::<AddBookCommandRequest>("AddBookCommandRequest"),
+        response::<AddBookCommandResponse>("AddBookCommandResponse"),
+        request::<AddBookTagsCommandRequest>("AddBookTagsCommandRequest"),
+        response::<AddBookTagsCommandResponse>("AddBookTagsCommandResponse"),
+        request::<ExportShelfListCommandRequest>("ExportShelfListCommandRequest"),
+        response::<ExportShelfListCommandResponse>("ExportShelfListCommandResponse"),
+        request::<FindBooksByAuthorCommandRequest>("FindBooksByAuthorCommandRequest"),
+        response::<FindBooksByAuthorCommandResponse>("FindBooksByAuthorCommandResponse"),
+        request::<FindBooksByIsbnCommandRequest>("FindBooksByIsbnCommandRequest"),
+        response::<FindBooksByIsbnCommandResponse>("FindBooksByIsbnCommandResponse"),
+        request::<FindBooksByTagCommandRequest>("FindBooksByTagCommandRequest"),
+        response::<FindBooksByTagCommandResponse>("FindBooksByTagCommandResponse"),
+        request::<FindDuplicateBooksCommandRequest>("FindDuplicateBooksCommandRequest"),
+        response::<FindDuplicateBooksCommandResponse>("FindDuplicateBooksCommandResponse"),
+        request::<FindRelatedBooksCommandRequest>("FindRelatedBooksCommandRequest"),
+        response::<FindRelatedBooksCommandResponse>("FindRelatedBooksCommandResponse"),
+        request::<FindTrendingBooksCommandRequest>("FindTrendingBooksCommandRequest"),
+        response::<FindTrendingBooksCommandResponse>("FindTrendingBooksCommandResponse"),
+        request::<GetBookCommandRequest>("GetBookCommandRequest"),
+        response::<GetBookCommandResponse>("GetBookCommandResponse"),
+        request::<GetCoverCommandRequest>("GetCoverCommandRequest"),
+        response::<GetCoverCommandResponse>("GetCoverCommandResponse"),
+        request::<GetTagsCommandRequest>("GetTagsCommandRequest"),
+        response::<GetTagsCommandResponse>("GetTagsCommandResponse"),
+        request::<MergeBooksCommandRequest>("MergeBooksCommandRequest"),
+        response::<MergeBooksCommandResponse>("MergeBooksCommandResponse"),
+        request::<RemoveBookCommandRequest>("RemoveBookCommandRequest"),
+        response::<RemoveBookCommandResponse>("RemoveBookCommandResponse"),
+        request::<RemoveBookTagsCommandRequest>("RemoveBookTagsCommandRequest"),
+        response::<RemoveBookTagsCommandResponse>("RemoveBookTagsCommandResponse"),
+        request::<RemoveBooksCommandRequest>("RemoveBooksCommandRequest"),
+        response::<RemoveBooksCommandResponse>("RemoveBooksCommandResponse"),
+        request::<UpdateBookCommandRequest>("UpdateBookCommandRequest"),
+        response::<UpdateBookCommandResponse>("UpdateBookCommandResponse"),
+        request::<UpdateLocationCommandRequest>("UpdateLocationCommandRequest"),
+        response::<UpdateLocationCommandResponse>("UpdateLocationCommandResponse"),
+        request::<UploadCoverCommandRequest>("UploadCoverCommandRequest"),
+        response::<UploadCoverCommandResponse>("UploadCoverCommandResponse"),
+    ]
+}
+
+fn request<T: CommandExamples + DeserializeOwned>(name: &'static str) -> (&'static str, Vec<Value>) {
+    let examples = T::examples().iter().map(|example| {
+        let value = serde_json::to_value(example).expect("should serialize example");
+        let parsed: T = serde_json::from_value(value).expect("should parse example");
+        serde_json::to_value(parsed).expect("should serialize example")
+    }).collect();
+    (name, examples)
+}
+
+fn response<T: CommandExamples>(name: &'static str) -> (&'static str, Vec<Value>) {
+    (name, T::examples().iter().map(|example| serde_json::to_value(example).expect("should serialize example")).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use serde_json::Value;
+    use crate::catalog::command::add_book_cmd::AddBookCommandRequest;
+    use crate::core::command::examples::{command_examples, CommandExamples};
+
+    // golden files are recorded when they are missing or when UPDATE_GOLDEN=true, changes of the serialized
+    // examples fail otherwise so that breaking changes of the api are reviewed along with the updated files
+    fn golden_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("golden").join("commands")
+    }
+
+    #[tokio::test]
+    async fn test_should_keep_serialized_examples_stable() {
+        let update = std::env::var("UPDATE_GOLDEN").map(|update| update == "true").unwrap_or(false);
+        let dir = golden_dir();
+        std::fs::create_dir_all(&dir).expect("should create golden dir");
+        for (name, examples) in command_examples() {
+            assert!(!examples.is_empty(), "{} has no examples", name);
+            let actual = Value::Array(examples);
+            let path = dir.join(format!("{}.json", name));
+            if update || !path.exists() {
+                let json = serde_json::to_string_pretty(&actual).expect("should serialize examples");
+                std::fs::write(&path, json + "\n").expect("should write golden file");
+                continue;
+            }
+            let golden: Value = serde_json::from_str(std::fs::read_to_string(&path).expect("should read golden file").as_str())
+                .expect("should parse golden file");
+            assert_eq!(golden, actual, "serialized examples of {} differ from {}", name, path.display());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_should_build_deterministic_examples() {
+        let first = serde_json::to_value(AddBookCommandRequest::examples()).expect("should serialize");
+        let second = serde_json::to_value(AddBookCommandRequest::examples()).expect("should serialize");
+        assert_eq!(first, second);
+    }
+}
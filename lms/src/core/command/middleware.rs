@@ -0,0 +1,361 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tracing::log::{info, warn};
+
+use crate::core::command::{CommandEnvelope, CommandError, CommandMiddleware};
+use crate::core::library::Role;
+
+// number of seconds an idempotency key is remembered after the command succeeded
+const IDEMPOTENCY_KEY_TTL_SECS: i64 = 24 * 3600;
+
+// LoggingMiddleware logs the outcome of each command with its caller, payloads are not logged because
+// they may contain passwords and tokens
+pub(crate) struct LoggingMiddleware {}
+
+impl LoggingMiddleware {
+    pub(crate) fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl CommandMiddleware for LoggingMiddleware {
+    async fn after(&self, envelope: &CommandEnvelope, failure: Option<&CommandError>, elapsed: Duration) {
+        match failure {
+            None => info!("command {} by '{}' completed in {}ms [correlation_id={}]",
+                envelope.name, envelope.principal(), elapsed.as_millis(), envelope.correlation_id()),
+            Some(err) => warn!("command {} by '{}' failed in {}ms due to {:?} [correlation_id={}]",
+                envelope.name, envelope.principal(), elapsed.as_millis(), err, envelope.correlation_id()),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub(crate) struct CommandStats {
+    pub invocations: u64,
+    pub failures: u64,
+    pub total_millis: u128,
+}
+
+// MetricsMiddleware counts invocations, failures and latency of each command in memory of the lambda instance and
+// logs the running totals of a command after each invocation so that metric filters of the logs can chart them
+pub(crate) struct MetricsMiddleware {
+    stats: Mutex<HashMap<String, CommandStats>>,
+}
+
+impl MetricsMiddleware {
+    pub(crate) fn new() -> Self {
+        Self {
+            stats: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn stats(&self, name: &str) -> CommandStats {
+        let stats = self.stats.lock().unwrap_or_else(|err| err.into_inner());
+        stats.get(name).copied().unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl CommandMiddleware for MetricsMiddleware {
+    async fn after(&self, envelope: &CommandEnvelope, failure: Option<&CommandError>, elapsed: Duration) {
+        {
+            let mut stats = self.stats.lock().unwrap_or_else(|err| err.into_inner());
+            let entry = stats.entry(envelope.name.to_string()).or_default();
+            entry.invocations += 1;
+            if failure.is_some() {
+                entry.failures += 1;
+            }
+            entry.total_millis += elapsed.as_millis();
+        }
+        let stats = self.stats(envelope.name.as_str());
+        info!("command metrics {} invocations={} failures={} total_millis={}",
+            envelope.name, stats.invocations, stats.failures, stats.total_millis);
+    }
+}
+
+// AuthorizationMiddleware rejects restricted commands unless the caller has one of the roles of the command,
+// commands restricted without roles only require an authenticated caller
+pub(crate) struct AuthorizationMiddleware {
+    rules: HashMap<String, Vec<Role>>,
+}
+
+impl AuthorizationMiddleware {
+    pub(crate) fn new() -> Self {
+        Self {
+            rules: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn restrict(mut self, name: &str, roles: &[Role]) -> Self {
+        self.rules.insert(name.to_string(), roles.to_vec());
+        self
+    }
+}
+
+#[async_trait]
+impl CommandMiddleware for AuthorizationMiddleware {
+    async fn before(&self, envelope: &CommandEnvelope) -> Result<(), CommandError> {
+        let roles = match self.rules.get(envelope.name.as_str()) {
+            Some(roles) => roles,
+            None => return Ok(()),
+        };
+        let ctx = envelope.context.as_ref().filter(|ctx| ctx.is_authenticated())
+            .ok_or_else(|| CommandError::Access {
+                message: format!("command {} requires an authenticated caller", envelope.name),
+                reason_code: Some("401".to_string()),
+            })?;
+        if !roles.is_empty() && !roles.iter().any(|role| ctx.has_role(role.clone())) {
+            return Err(CommandError::Access {
+                message: format!("{} is not allowed to run command {}", ctx.principal(), envelope.name),
+                reason_code: Some("403".to_string()),
+            });
+        }
+        Ok(())
+    }
+}
+
+// ValidationMiddleware rejects oversized or control characters in string fields of any command and blank
+// values of the fields that are required by a command
+pub(crate) struct ValidationMiddleware {
+    max_field_length: usize,
+    required: HashMap<String, Vec<String>>,
+}
+
+impl ValidationMiddleware {
+    pub(crate) fn new(max_field_length: usize) -> Self {
+        Self {
+            max_field_length,
+            required: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn require(mut self, name: &str, fields: &[&str]) -> Self {
+        self.required.insert(name.to_string(), fields.iter().map(|f| f.to_string()).collect());
+        self
+    }
+
+    fn validate_value(&self, field: &str, value: &Value) -> Result<(), CommandError> {
+        match value {
+            Value::String(s) => {
+                if s.chars().count() > self.max_field_length {
+                    return Err(CommandError::Validation {
+                        message: format!("{} exceeds {} characters", field, self.max_field_length),
+                        reason_code: Some("400".to_string()),
+                    });
+                }
+                if s.chars().any(|c| c.is_control() && c != '\n' && c != '\r' && c != '\t') {
+                    return Err(CommandError::Validation {
+                        message: format!("{} contains control characters", field),
+                        reason_code: Some("400".to_string()),
+                    });
+                }
+                Ok(())
+            }
+            Value::Array(values) => {
+                values.iter().try_for_each(|v| self.validate_value(field, v))
+            }
+            Value::Object(map) => {
+                map.iter().try_for_each(|(k, v)| self.validate_value(k, v))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandMiddleware for ValidationMiddleware {
+    async fn before(&self, envelope: &CommandEnvelope) -> Result<(), CommandError> {
+        self.validate_value(envelope.name.as_str(), &envelope.payload)?;
+        for field in self.required.get(envelope.name.as_str()).into_iter().flatten() {
+            let blank = match &envelope.payload[field.as_str()] {
+                Value::Null => true,
+                Value::String(s) => s.trim().is_empty(),
+                _ => false,
+            };
+            if blank {
+                return Err(CommandError::Validation {
+                    message: format!("{} is required for {}", field, envelope.name),
+                    reason_code: Some("400".to_string()),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+// IdempotencyMiddleware runs a command at most once for an Idempotency-Key of the caller, the key is reserved
+// before the command runs and released if the command fails so that the client can retry it. The response of
+// the command is stored with the key and replayed to retries. Keys are kept in memory of the lambda instance.
+pub(crate) struct IdempotencyMiddleware {
+    // key -> (fingerprint of the payload, epoch seconds when the key expires, response once the command succeeded)
+    keys: Mutex<HashMap<String, (String, i64, Option<Value>)>>,
+}
+
+impl IdempotencyMiddleware {
+    pub(crate) fn new() -> Self {
+        Self {
+            keys: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn key(envelope: &CommandEnvelope) -> Option<String> {
+        envelope.context.as_ref()
+            .filter(|ctx| !ctx.idempotency_key.is_empty())
+            .map(|ctx| format!("{}:{}:{}", ctx.principal(), envelope.name, ctx.idempotency_key))
+    }
+
+    fn fingerprint(payload: &Value) -> String {
+        Sha256::digest(payload.to_string().as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+#[async_trait]
+impl CommandMiddleware for IdempotencyMiddleware {
+    async fn before(&self, envelope: &CommandEnvelope) -> Result<(), CommandError> {
+        let key = match Self::key(envelope) {
+            Some(key) => key,
+            None => return Ok(()),
+        };
+        let fingerprint = Self::fingerprint(&envelope.payload);
+        let now = Utc::now().timestamp();
+        let mut keys = self.keys.lock().unwrap_or_else(|err| err.into_inner());
+        keys.retain(|_, (_, expires_at, _)| *expires_at > now);
+        if let Some((existing, _, response)) = keys.get(key.as_str()) {
+            if *existing != fingerprint {
+                return Err(CommandError::Validation {
+                    message: format!("idempotency key of {} was used with another request", envelope.name),
+                    reason_code: Some("422".to_string()),
+                });
+            }
+            // a completed command is replayed, a retry racing the first request has nothing to replay yet
+            if response.is_none() {
+                return Err(CommandError::DuplicateKey {
+                    message: format!("{} with the idempotency key is still being processed", envelope.name),
+                });
+            }
+            return Ok(());
+        }
+        keys.insert(key, (fingerprint, now + IDEMPOTENCY_KEY_TTL_SECS, None));
+        Ok(())
+    }
+
+    async fn replay(&self, envelope: &CommandEnvelope) -> Option<Value> {
+        let key = Self::key(envelope)?;
+        let keys = self.keys.lock().unwrap_or_else(|err| err.into_inner());
+        keys.get(key.as_str()).and_then(|(_, _, response)| response.clone())
+    }
+
+    async fn completed(&self, envelope: &CommandEnvelope, response: &Value) {
+        if let Some(key) = Self::key(envelope) {
+            let mut keys = self.keys.lock().unwrap_or_else(|err| err.into_inner());
+            if let Some((_, _, stored)) = keys.get_mut(key.as_str()) {
+                *stored = Some(response.clone());
+            }
+        }
+    }
+
+    async fn after(&self, envelope: &CommandEnvelope, failure: Option<&CommandError>, _elapsed: Duration) {
+        if failure.is_none() {
+            return;
+        }
+        if let Some(key) = Self::key(envelope) {
+            let mut keys = self.keys.lock().unwrap_or_else(|err| err.into_inner());
+            keys.remove(key.as_str());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use serde_json::json;
+    use crate::core::command::{CommandEnvelope, CommandError, CommandMiddleware};
+    use crate::core::command::middleware::{AuthorizationMiddleware, IdempotencyMiddleware, MetricsMiddleware, ValidationMiddleware};
+    use crate::core::context::RequestContext;
+    use crate::core::library::Role;
+
+    fn context(roles: &[Role], idempotency_key: &str) -> RequestContext {
+        let roles: Vec<String> = roles.iter().map(|r| r.to_string()).collect();
+        let mut ctx = RequestContext::anonymous("branch1", None, None)
+            .authenticated("party1", &roles, "", "");
+        ctx.idempotency_key = idempotency_key.to_string();
+        ctx
+    }
+
+    #[tokio::test]
+    async fn test_should_authorize_restricted_commands() {
+        let middleware = AuthorizationMiddleware::new()
+            .restrict("create_api_key", &[Role::Admin])
+            .restrict("change_password", &[]);
+        let anonymous = RequestContext::anonymous("branch1", None, None);
+        assert!(middleware.before(&CommandEnvelope::new("get_book", Some(anonymous.clone()), json!({}))).await.is_ok());
+        assert!(middleware.before(&CommandEnvelope::new("change_password", Some(anonymous), json!({}))).await.is_err());
+        assert!(middleware.before(&CommandEnvelope::new("change_password", None, json!({}))).await.is_err());
+        assert!(middleware.before(&CommandEnvelope::new("change_password", Some(context(&[Role::Regular], "")), json!({}))).await.is_ok());
+        assert!(middleware.before(&CommandEnvelope::new("create_api_key", Some(context(&[Role::Librarian], "")), json!({}))).await.is_err());
+        assert!(middleware.before(&CommandEnvelope::new("create_api_key", Some(context(&[Role::Admin], "")), json!({}))).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_should_validate_payload() {
+        let middleware = ValidationMiddleware::new(10).require("checkout_book", &["book_id", "patron_id"]);
+        let ok = json!({"book_id": "book1", "patron_id": "patron1", "tags": ["a", "b"]});
+        assert!(middleware.before(&CommandEnvelope::new("checkout_book", None, ok)).await.is_ok());
+        let blank = json!({"book_id": "book1", "patron_id": "  "});
+        assert!(middleware.before(&CommandEnvelope::new("checkout_book", None, blank)).await.is_err());
+        let missing = json!({"book_id": "book1"});
+        assert!(middleware.before(&CommandEnvelope::new("checkout_book", None, missing)).await.is_err());
+        let long = json!({"tags": ["a very long tag"]});
+        assert!(middleware.before(&CommandEnvelope::new("add_book", None, long)).await.is_err());
+        let control = json!({"title": "a\u{0}b"});
+        assert!(middleware.before(&CommandEnvelope::new("add_book", None, control)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_run_command_once_per_idempotency_key() {
+        let middleware = IdempotencyMiddleware::new();
+        let envelope = CommandEnvelope::new("checkout_book", Some(context(&[], "key1")), json!({"book_id": "book1"}));
+        assert!(middleware.before(&envelope).await.is_ok());
+        assert_eq!(None, middleware.replay(&envelope).await);
+        // a retry while the command is running is rejected as there is no response to replay yet
+        assert!(matches!(middleware.before(&envelope).await, Err(CommandError::DuplicateKey { .. })));
+        middleware.completed(&envelope, &json!({"checkout_id": "checkout1"})).await;
+        middleware.after(&envelope, None, Duration::from_millis(1)).await;
+        assert!(middleware.before(&envelope).await.is_ok());
+        assert_eq!(Some(json!({"checkout_id": "checkout1"})), middleware.replay(&envelope).await);
+
+        let other = CommandEnvelope::new("checkout_book", Some(context(&[], "key1")), json!({"book_id": "book2"}));
+        assert!(matches!(middleware.before(&other).await, Err(CommandError::Validation { .. })));
+
+        // failed commands can be retried with the same key
+        let failing = CommandEnvelope::new("return_book", Some(context(&[], "key2")), json!({"book_id": "book1"}));
+        assert!(middleware.before(&failing).await.is_ok());
+        middleware.after(&failing, Some(&CommandError::NotFound { message: "test".to_string() }), Duration::from_millis(1)).await;
+        assert!(middleware.before(&failing).await.is_ok());
+
+        // commands without key are not deduplicated
+        let without_key = CommandEnvelope::new("checkout_book", Some(context(&[], "")), json!({"book_id": "book1"}));
+        assert!(middleware.before(&without_key).await.is_ok());
+        assert!(middleware.before(&without_key).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_should_record_metrics() {
+        let middleware = MetricsMiddleware::new();
+        let envelope = CommandEnvelope::new("checkout_book", None, json!({}));
+        middleware.after(&envelope, None, Duration::from_millis(5)).await;
+        middleware.after(&envelope, Some(&CommandError::NotFound { message: "test".to_string() }), Duration::from_millis(7)).await;
+        let stats = middleware.stats("checkout_book");
+        assert_eq!(2, stats.invocations);
+        assert_eq!(1, stats.failures);
+        assert_eq!(12, stats.total_millis);
+        assert_eq!(0, middleware.stats("return_book").invocations);
+    }
+}
@@ -0,0 +1,136 @@
+use std::future::Future;
+
+use axum::http::HeaderMap;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::library::Role;
+
+const DEFAULT_LOCALE: &str = "en";
+
+tokio::task_local! {
+    static REQUEST_CONTEXT: RequestContext;
+}
+
+// RequestContext carries the caller of a request down the stack, it is built by middleware and is
+// available to services of the request task through RequestContext::current
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct RequestContext {
+    // empty for anonymous requests
+    pub actor_id: String,
+    pub roles: Vec<String>,
+    pub branch_id: String,
+    pub correlation_id: String,
+    pub locale: String,
+    // set when the actor is authenticated with an API key
+    pub api_key_id: String,
+    // Idempotency-Key header of the request, empty when the client did not send one
+    pub idempotency_key: String,
+}
+
+impl RequestContext {
+    pub fn anonymous(branch_id: &str, correlation_id: Option<&str>, locale: Option<&str>) -> Self {
+        Self {
+            actor_id: "".to_string(),
+            roles: vec![],
+            branch_id: branch_id.to_string(),
+            correlation_id: correlation_id.filter(|id| !id.trim().is_empty())
+                .map(|id| id.trim().to_string())
+                .unwrap_or_else(|| Uuid::new_v4().to_string()),
+            locale: locale.filter(|l| !l.trim().is_empty())
+                .map(|l| l.trim().to_string())
+                .unwrap_or_else(|| DEFAULT_LOCALE.to_string()),
+            api_key_id: "".to_string(),
+            idempotency_key: "".to_string(),
+        }
+    }
+
+    // uses X-Correlation-Id, Idempotency-Key and the first language of Accept-Language headers
+    pub fn from_headers(branch_id: &str, headers: &HeaderMap) -> Self {
+        let correlation_id = headers.get("x-correlation-id").and_then(|h| h.to_str().ok());
+        let locale = headers.get("accept-language").and_then(|h| h.to_str().ok())
+            .and_then(|h| h.split([',', ';']).next());
+        let mut ctx = Self::anonymous(branch_id, correlation_id, locale);
+        ctx.idempotency_key = headers.get("idempotency-key").and_then(|h| h.to_str().ok())
+            .map(|h| h.trim().to_string()).unwrap_or_default();
+        ctx
+    }
+
+    pub fn authenticated(&self, actor_id: &str, roles: &[String], branch_id: &str, api_key_id: &str) -> Self {
+        let mut ctx = self.clone();
+        ctx.actor_id = actor_id.to_string();
+        ctx.roles = roles.to_vec();
+        if !branch_id.is_empty() {
+            ctx.branch_id = branch_id.to_string();
+        }
+        ctx.api_key_id = api_key_id.to_string();
+        ctx
+    }
+
+    // returns identity of the caller for audit logs, API keys are distinguished from their parties
+    pub fn principal(&self) -> String {
+        if self.api_key_id.is_empty() {
+            self.actor_id.to_string()
+        } else {
+            format!("api_key:{}", self.api_key_id)
+        }
+    }
+
+    pub fn is_authenticated(&self) -> bool {
+        !self.actor_id.is_empty()
+    }
+
+    pub fn has_role(&self, role: Role) -> bool {
+        let role = role.to_string();
+        self.roles.iter().any(|r| *r == role)
+    }
+
+    // returns context of the current request task if any
+    pub fn current() -> Option<RequestContext> {
+        REQUEST_CONTEXT.try_with(|ctx| ctx.clone()).ok()
+    }
+
+    // runs the future with this context as the current context
+    pub async fn scope<F: Future>(self, f: F) -> F::Output {
+        REQUEST_CONTEXT.scope(self, f).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{HeaderMap, HeaderValue};
+    use crate::core::context::RequestContext;
+    use crate::core::library::Role;
+
+    #[tokio::test]
+    async fn test_should_build_context_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-correlation-id", HeaderValue::from_static("corr-1"));
+        headers.insert("accept-language", HeaderValue::from_static("fr-CA,fr;q=0.9,en;q=0.8"));
+        headers.insert("idempotency-key", HeaderValue::from_static("key-1"));
+        let ctx = RequestContext::from_headers("branch1", &headers);
+        assert_eq!("corr-1", ctx.correlation_id.as_str());
+        assert_eq!("fr-CA", ctx.locale.as_str());
+        assert_eq!("key-1", ctx.idempotency_key.as_str());
+        assert!(!ctx.is_authenticated());
+
+        let ctx = RequestContext::from_headers("branch1", &HeaderMap::new());
+        assert!(!ctx.correlation_id.is_empty());
+        assert_eq!("en", ctx.locale.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_should_scope_current_context() {
+        assert_eq!(None, RequestContext::current());
+        let ctx = RequestContext::anonymous("branch1", Some("corr-2"), None)
+            .authenticated("librarian1", &[Role::Librarian.to_string()], "", "");
+        let current = ctx.clone().scope(async { RequestContext::current() }).await.expect("should have context");
+        assert_eq!(ctx, current);
+        assert!(current.is_authenticated());
+        assert!(current.has_role(Role::Librarian));
+        assert!(!current.has_role(Role::Admin));
+        assert_eq!("branch1", current.branch_id.as_str());
+        assert_eq!("librarian1", current.principal().as_str());
+        assert_eq!(None, RequestContext::current());
+    }
+}
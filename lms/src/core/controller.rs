@@ -1,11 +1,39 @@
-use axum::http::StatusCode;
+use std::io::Read;
+use std::sync::Arc;
+use axum::body::{Body, HttpBody};
+use axum::extract::{DefaultBodyLimit, State};
+use axum::http::{header, HeaderMap, HeaderValue, Request, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use flate2::read::{GzDecoder, ZlibDecoder};
+use lazy_static::lazy_static;
+use tower_http::compression::CompressionLayer;
 use serde::{Deserialize, Serialize};
-use crate::core::command::CommandError;
+use crate::core::command::{CommandBus, CommandError, CommandMiddleware};
+use crate::core::command::middleware::{AuthorizationMiddleware, IdempotencyMiddleware, LoggingMiddleware, MetricsMiddleware, ValidationMiddleware};
+use crate::core::context::RequestContext;
 use crate::core::domain::Configuration;
+use crate::core::library::{BatchStatus, Role};
 use crate::core::repository::RepositoryStore;
 
+// maximum number of characters accepted in a string field of a command request
+const MAX_FIELD_LENGTH: usize = 8192;
+// seconds clients wait before retrying a request that failed with a retryable error, throttled requests of the
+// database back off longer than requests that hit a transient failure
+const RETRY_AFTER_SECONDS: i64 = 1;
+const THROTTLED_RETRY_AFTER_SECONDS: i64 = 5;
+
+lazy_static! {
+    // metrics and idempotency keys are shared by the buses of all requests of the lambda instance
+    pub(crate) static ref COMMAND_METRICS: Arc<MetricsMiddleware> = Arc::new(MetricsMiddleware::new());
+    static ref IDEMPOTENCY: Arc<IdempotencyMiddleware> = Arc::new(IdempotencyMiddleware::new());
+    // the pipeline is built once for the lambda instance instead of for every request
+    static ref COMMAND_MIDDLEWARE: Vec<Arc<dyn CommandMiddleware>> = command_middleware();
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub(crate) struct AppState {
+pub struct AppState {
     pub(crate) config: Configuration,
     pub(crate) store: RepositoryStore,
 }
@@ -19,40 +47,295 @@ impl AppState {
     }
 }
 
-pub(crate) type ServerError = (StatusCode, String);
+// ServerError is the error response of handlers with the message as body, retryable errors tell clients when to
+// retry with Retry-After and rate limited responses carry the budget headers of the principal
+#[derive(Debug, Clone)]
+pub(crate) struct ServerError {
+    pub(crate) status: StatusCode,
+    pub(crate) message: String,
+    pub(crate) headers: HeaderMap,
+}
+
+impl ServerError {
+    pub(crate) fn new(status: StatusCode, message: &str) -> Self {
+        Self {
+            status,
+            message: message.to_string(),
+            headers: HeaderMap::new(),
+        }
+    }
+
+    pub(crate) fn with_retry_after(mut self, seconds: i64) -> Self {
+        if let Ok(value) = HeaderValue::from_str(seconds.max(0).to_string().as_str()) {
+            self.headers.insert(header::RETRY_AFTER, value);
+        }
+        self
+    }
+
+    pub(crate) fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+}
+
+impl From<(StatusCode, String)> for ServerError {
+    fn from((status, message): (StatusCode, String)) -> Self {
+        ServerError::new(status, message.as_str())
+    }
+}
+
+impl IntoResponse for ServerError {
+    fn into_response(self) -> Response {
+        (self.status, self.headers, self.message).into_response()
+    }
+}
+
+// remaining request budget of a principal within the current rate limit window, added to every authenticated
+// response so that clients can slow down before they are rejected
+pub(crate) fn rate_limit_headers(limit: i64, remaining: i64, reset_seconds: i64) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    for (name, value) in [("x-ratelimit-limit", limit), ("x-ratelimit-remaining", remaining.max(0)),
+                          ("x-ratelimit-reset", reset_seconds)] {
+        if let Ok(value) = HeaderValue::from_str(value.to_string().as_str()) {
+            headers.insert(name, value);
+        }
+    }
+    headers
+}
+
+// request_context runs the request within an anonymous context with correlation id and locale of the request,
+// the auth middleware replaces it with the authenticated actor
+pub(crate) async fn request_context<B>(
+    State(state): State<AppState>,
+    mut req: Request<B>,
+    next: Next<B>) -> Response {
+    let ctx = RequestContext::from_headers(state.config.branch_id.as_str(), req.headers());
+    let correlation_id = ctx.correlation_id.to_string();
+    req.extensions_mut().insert(ctx.clone());
+    let mut res = ctx.scope(next.run(req)).await;
+    if let Ok(value) = HeaderValue::from_str(correlation_id.as_str()) {
+        res.headers_mut().insert("x-correlation-id", value);
+    }
+    res
+}
+
+// with_common_layers adds the layers that all context routers share and binds the state, the layers run from the
+// bottom up: responses are compressed for clients that accept gzip or deflate, the request context is set up and
+// compressed request bodies are inflated before the body limit of the route applies to them
+pub(crate) fn with_common_layers(router: Router<AppState>, state: AppState) -> Router {
+    router
+        .layer(middleware::from_fn_with_state(state.clone(), decompress_request))
+        .layer(DefaultBodyLimit::max(state.config.max_body_bytes))
+        .layer(middleware::from_fn_with_state(state.clone(), request_context))
+        .layer(CompressionLayer::new())
+        .with_state(state)
+}
+
+// run_lambda serves a router built by with_common_layers as a Lambda function, the layers of the router work on hyper
+// bodies so the body of each Lambda request is copied into one before it is routed
+pub async fn run_lambda(router: Router) -> Result<(), lambda_http::Error> {
+    use lambda_http::tower::ServiceExt;
+    let app = router.map_request(|req: lambda_http::Request| req.map(|body| Body::from(body.to_vec())));
+    lambda_http::run(app).await
+}
+
+// body limit of routes that accept more than max_body_bytes such as batch endpoints and uploads
+pub(crate) fn body_limit(max_bytes: usize) -> DefaultBodyLimit {
+    DefaultBodyLimit::max(max_bytes)
+}
+
+// decompress_request inflates request bodies sent with Content-Encoding gzip or deflate, the inflated body is capped
+// at max_bulk_body_bytes, the largest limit of any route, so that a small payload cannot expand without bound
+pub(crate) async fn decompress_request(
+    State(state): State<AppState>,
+    req: Request<Body>,
+    next: Next<Body>) -> Result<Response, ServerError> {
+    let encoding = req.headers().get(header::CONTENT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_lowercase())
+        .filter(|value| !value.is_empty() && value != "identity");
+    let encoding = match encoding {
+        Some(encoding) => encoding,
+        None => return Ok(next.run(req).await),
+    };
+    let max_bytes = state.config.max_bulk_body_bytes;
+    let (mut parts, mut body) = req.into_parts();
+    let mut compressed = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|err| ServerError::new(StatusCode::BAD_REQUEST, err.to_string().as_str()))?;
+        if compressed.len() + chunk.len() > max_bytes {
+            return Err(ServerError::new(StatusCode::PAYLOAD_TOO_LARGE,
+                                        format!("request body exceeds {} bytes", max_bytes).as_str()));
+        }
+        compressed.extend_from_slice(&chunk);
+    }
+    let inflated = decompress(encoding.as_str(), compressed.as_slice(), max_bytes)?;
+    parts.headers.remove(header::CONTENT_ENCODING);
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Ok(next.run(Request::from_parts(parts, Body::from(inflated))).await)
+}
+
+// inflates a gzip or deflate (zlib) body up to max_bytes
+pub(crate) fn decompress(encoding: &str, compressed: &[u8], max_bytes: usize) -> Result<Vec<u8>, ServerError> {
+    let reader: Box<dyn Read + '_> = match encoding {
+        "gzip" | "x-gzip" => Box::new(GzDecoder::new(compressed)),
+        "deflate" => Box::new(ZlibDecoder::new(compressed)),
+        _ => return Err(ServerError::new(StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                                         format!("unsupported content encoding {}", encoding).as_str())),
+    };
+    let mut inflated = Vec::new();
+    reader.take(max_bytes as u64 + 1).read_to_end(&mut inflated)
+        .map_err(|err| ServerError::new(StatusCode::BAD_REQUEST, format!("invalid {} body: {}", encoding, err).as_str()))?;
+    if inflated.len() > max_bytes {
+        return Err(ServerError::new(StatusCode::PAYLOAD_TOO_LARGE,
+                                    format!("decompressed request body exceeds {} bytes", max_bytes).as_str()));
+    }
+    Ok(inflated)
+}
+
+// command_middleware builds the pipeline that controllers dispatch commands through, logging and metrics come first
+// so that they observe commands rejected by the other middleware
+fn command_middleware() -> Vec<Arc<dyn CommandMiddleware>> {
+    let authorization = AuthorizationMiddleware::new()
+        .restrict("change_password", &[])
+        .restrict("create_api_key", &[Role::Admin])
+        .restrict("rotate_api_key", &[Role::Admin])
+        .restrict("revoke_api_key", &[Role::Admin])
+        .restrict("find_events", &[Role::Librarian, Role::Admin])
+        .restrict("export_shelf_list", &[Role::Librarian, Role::Admin])
+        .restrict("find_duplicate_books", &[Role::Librarian, Role::Admin])
+        .restrict("merge_books", &[Role::Librarian, Role::Admin]);
+    vec![
+        Arc::new(LoggingMiddleware::new()),
+        COMMAND_METRICS.clone(),
+        Arc::new(authorization),
+        Arc::new(ValidationMiddleware::new(MAX_FIELD_LENGTH)),
+        IDEMPOTENCY.clone(),
+    ]
+}
+
+// command_bus returns a bus with the shared pipeline for registering the handler of a request, handlers are bound to
+// the services of the request
+pub(crate) fn command_bus() -> CommandBus {
+    CommandBus::with_pipeline(COMMAND_MIDDLEWARE.as_slice())
+}
 
 pub fn json_to_server_error(err: serde_json::Error) -> ServerError {
-    (StatusCode::BAD_REQUEST, format!("{}", err))
+    ServerError::new(StatusCode::BAD_REQUEST, format!("{}", err).as_str())
+}
+
+// batch endpoints respond with 207 multi-status when only some of the items failed
+pub(crate) fn batch_status_code(status: BatchStatus) -> StatusCode {
+    StatusCode::from_u16(status.http_status()).unwrap_or(StatusCode::OK)
+}
+
+// database throttling is reported with the 400 status of the service and retried like rate limited requests
+fn is_throttled(reason_code: &Option<String>) -> bool {
+    reason_code.as_deref().map(|code| code.starts_with("400") || code.starts_with("429")).unwrap_or(false)
 }
 
 impl From<CommandError> for ServerError {
     fn from(err: CommandError) -> Self {
-        match err {
-            CommandError::Access { .. } => {
-                (StatusCode::BAD_REQUEST, format!("{:?}", err))
-            }
-            CommandError::Database { .. } => {
-                (StatusCode::INTERNAL_SERVER_ERROR, format!("{:?}", err))
-            }
-            CommandError::DuplicateKey { .. } => {
-                (StatusCode::CONFLICT, format!("{:?}", err))
-            }
-            CommandError::NotFound { .. } => {
-                (StatusCode::NOT_FOUND, format!("{:?}", err))
-            }
-            CommandError::Runtime { .. } => {
-                (StatusCode::INTERNAL_SERVER_ERROR, format!("{:?}", err))
-            }
-            CommandError::Serialization { .. } => {
-                (StatusCode::BAD_REQUEST, format!("{:?}", err))
-            }
-            CommandError::Validation { .. } => {
-                (StatusCode::BAD_REQUEST, format!("{:?}", err))
-            }
-            CommandError::Other { .. } => {
-                (StatusCode::INTERNAL_SERVER_ERROR, format!("{:?}", err))
+        let status = match &err {
+            CommandError::Access { .. } => StatusCode::BAD_REQUEST,
+            CommandError::Database { retryable: true, reason_code, .. } |
+            CommandError::Runtime { retryable: true, reason_code, .. } if is_throttled(reason_code) => {
+                StatusCode::TOO_MANY_REQUESTS
             }
+            CommandError::Database { retryable: true, .. } => StatusCode::SERVICE_UNAVAILABLE,
+            CommandError::Runtime { retryable: true, .. } => StatusCode::SERVICE_UNAVAILABLE,
+            CommandError::Database { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+            CommandError::DuplicateKey { .. } => StatusCode::CONFLICT,
+            CommandError::NotFound { .. } => StatusCode::NOT_FOUND,
+            CommandError::Runtime { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+            CommandError::Serialization { .. } => StatusCode::BAD_REQUEST,
+            CommandError::Validation { .. } => StatusCode::BAD_REQUEST,
+            CommandError::Other { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        let res = ServerError::new(status, format!("{:?}", err).as_str());
+        match status {
+            StatusCode::TOO_MANY_REQUESTS => res.with_retry_after(THROTTLED_RETRY_AFTER_SECONDS),
+            StatusCode::SERVICE_UNAVAILABLE => res.with_retry_after(RETRY_AFTER_SECONDS),
+            _ => res,
         }
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use axum::http::{header, StatusCode};
+    use axum::response::IntoResponse;
+    use flate2::Compression;
+    use flate2::write::{GzEncoder, ZlibEncoder};
+    use crate::core::command::CommandError;
+    use crate::core::controller::{decompress, rate_limit_headers, ServerError};
+    use crate::core::library::LibraryError;
+
+    fn status_and_retry_after(err: CommandError) -> (StatusCode, Option<String>) {
+        let res = ServerError::from(err).into_response();
+        let retry_after = res.headers().get(header::RETRY_AFTER).and_then(|value| value.to_str().ok()).map(str::to_string);
+        (res.status(), retry_after)
+    }
+
+    #[tokio::test]
+    async fn test_should_map_each_error_to_response() {
+        let message = "test".to_string();
+        let cases = [
+            (CommandError::Access { message: message.clone(), reason_code: None }, StatusCode::BAD_REQUEST, None),
+            (CommandError::Database { message: message.clone(), reason_code: None, retryable: false },
+             StatusCode::INTERNAL_SERVER_ERROR, None),
+            (CommandError::Database { message: message.clone(), reason_code: Some("TimeoutError".to_string()), retryable: true },
+             StatusCode::SERVICE_UNAVAILABLE, Some("1")),
+            (CommandError::Database { message: message.clone(), reason_code: Some("400 Bad Request".to_string()), retryable: true },
+             StatusCode::TOO_MANY_REQUESTS, Some("5")),
+            (CommandError::DuplicateKey { message: message.clone() }, StatusCode::CONFLICT, None),
+            (CommandError::NotFound { message: message.clone() }, StatusCode::NOT_FOUND, None),
+            (CommandError::Runtime { message: message.clone(), reason_code: None, retryable: false },
+             StatusCode::INTERNAL_SERVER_ERROR, None),
+            (CommandError::Runtime { message: message.clone(), reason_code: Some("scan_limit".to_string()), retryable: true },
+             StatusCode::SERVICE_UNAVAILABLE, Some("1")),
+            (CommandError::Serialization { message: message.clone() }, StatusCode::BAD_REQUEST, None),
+            (CommandError::Validation { message: message.clone(), reason_code: None }, StatusCode::BAD_REQUEST, None),
+            (CommandError::Other { message: message.clone(), reason_code: None }, StatusCode::INTERNAL_SERVER_ERROR, None),
+        ];
+        for (err, status, retry_after) in cases {
+            assert_eq!((status, retry_after.map(str::to_string)), status_and_retry_after(err));
+        }
+        // only library errors that are retryable are retried by clients
+        let unavailable = LibraryError::unavailable("busy", None, true);
+        assert!(unavailable.retryable());
+        assert_eq!(StatusCode::SERVICE_UNAVAILABLE, status_and_retry_after(CommandError::from(unavailable)).0);
+        let runtime = LibraryError::runtime("failed", None);
+        assert!(!runtime.retryable());
+        assert_eq!((StatusCode::INTERNAL_SERVER_ERROR, None), status_and_retry_after(CommandError::from(runtime)));
+    }
+
+    #[tokio::test]
+    async fn test_should_add_rate_limit_headers() {
+        let res = ServerError::new(StatusCode::TOO_MANY_REQUESTS, "limited").with_retry_after(30)
+            .with_headers(rate_limit_headers(10, 0, 30)).into_response();
+        assert_eq!("10", res.headers()["x-ratelimit-limit"]);
+        assert_eq!("0", res.headers()["x-ratelimit-remaining"]);
+        assert_eq!("30", res.headers()["x-ratelimit-reset"]);
+        assert_eq!("30", res.headers()[header::RETRY_AFTER]);
+    }
+
+    #[tokio::test]
+    async fn test_should_decompress_request_bodies() {
+        let body = br#"{"book_ids": ["1", "2", "3"]}"#.repeat(100);
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(&body).expect("should compress");
+        let gzip = gzip.finish().expect("should compress");
+        let mut deflate = ZlibEncoder::new(Vec::new(), Compression::default());
+        deflate.write_all(&body).expect("should compress");
+        let deflate = deflate.finish().expect("should compress");
+
+        assert_eq!(body, decompress("gzip", &gzip, body.len()).expect("should inflate gzip"));
+        assert_eq!(body, decompress("deflate", &deflate, body.len()).expect("should inflate deflate"));
+        // bodies that inflate beyond the limit are rejected
+        assert_eq!(StatusCode::PAYLOAD_TOO_LARGE, decompress("gzip", &gzip, body.len() - 1).expect_err("should reject").status);
+        assert_eq!(StatusCode::UNSUPPORTED_MEDIA_TYPE, decompress("br", &gzip, body.len()).expect_err("should reject").status);
+        assert_eq!(StatusCode::BAD_REQUEST, decompress("gzip", &body, body.len()).expect_err("should reject").status);
+    }
+}
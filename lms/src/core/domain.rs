@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 
 // Identifiable defines common traits that can be shared by persistent objects
@@ -9,11 +10,99 @@ pub trait Identifiable : Sync + Send {
 
 // Configuration abstracts config options for library system
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
-pub(crate) struct Configuration {
+pub struct Configuration {
     pub branch_id: String,
     pub max_holds: i64,
     pub book_loan_days: i64,
     pub bool_hold_days: i64,
+    // number of days after which digital checkouts are returned automatically
+    pub digital_loan_days: i64,
+    // patrons with more overdue items are suspended automatically
+    pub max_overdue: i64,
+    // number of days a hold is kept at the pickup branch before it is canceled
+    pub hold_pickup_days: i64,
+    // number of times patrons can extend the expiry of a hold, librarians are not limited
+    pub max_hold_extensions: i64,
+    // number of days an extension pushes out the expiry of a hold
+    pub hold_extension_days: i64,
+    // patrons get a single digest of their checkouts that are due within this many days
+    pub due_soon_digest_days: i64,
+    // floating collections with minimum copies of a title, returned copies stay at the return branch
+    // when it has fewer available copies than the minimum
+    pub floating_collections: HashMap<String, usize>,
+    // secret for signing email verification tokens of self-registered patrons
+    pub verification_secret: String,
+    // secret for signing tokens of calendar feeds of patrons, rotating it revokes all subscriptions
+    pub calendar_secret: String,
+    // number of hours a verification token remains valid
+    pub verification_token_hours: i64,
+    // secret for signing access tokens issued on login
+    pub jwt_secret: String,
+    // number of hours an access token remains valid
+    pub jwt_expiry_hours: i64,
+    // number of hours a password reset token remains valid
+    pub password_reset_hours: i64,
+    // default number of requests per minute accepted from a user or API key
+    pub rate_limit_per_minute: i64,
+    // emails that differ only in a plus tag such as jane+library@example.com belong to the same patron
+    pub strip_email_plus_tags: bool,
+    // endpoint of the address validation and geocoding provider, addresses are only normalized locally without it
+    pub address_validation_url: Option<String>,
+    // SRU endpoint of the union catalog searched for titles that are not in the catalog, federated search reports
+    // the union catalog as unavailable without it
+    pub union_catalog_url: Option<String>,
+    // number of milliseconds federated search waits for the union catalog before it returns without its results
+    pub union_catalog_timeout_ms: u64,
+    // API of the payment provider, payments are only simulated without it
+    pub payment_gateway_url: Option<String>,
+    // secret key of the payment provider account
+    pub payment_api_key: String,
+    // secret shared with the payment provider for signing webhooks
+    pub payment_webhook_secret: String,
+    // number of seconds a signed webhook is accepted after it was sent, older webhooks are treated as replays
+    pub payment_webhook_tolerance_seconds: i64,
+    // ISO 4217 code of the currency that fines are charged and paid in, amounts are kept in its smallest unit
+    pub fine_currency: String,
+    // largest waiver or refund of a fine in the smallest unit of the currency that librarians can make, larger
+    // adjustments need an admin
+    pub fine_adjustment_approval_threshold: i64,
+    // fine per day a book is returned late in the smallest unit of the currency, days the branch of the checkout is
+    // closed are not charged and zero disables overdue fines
+    pub overdue_fine_per_day: i64,
+    // number of days books stay in the feed of new acquisitions after they are added
+    pub new_acquisition_days: i64,
+    // number of seconds feed readers and caches may keep a feed before requesting it again
+    pub feed_cache_seconds: u64,
+    // public URL of the OAI-PMH endpoint that harvesters are given as the base URL of the repository
+    pub oai_base_url: String,
+    // contact of the repository administrator reported to harvesters
+    pub oai_admin_email: String,
+    // S3 bucket of book cover images, covers are kept in a local directory without it
+    pub covers_bucket: Option<String>,
+    // largest cover image accepted by uploads
+    pub max_cover_bytes: usize,
+    // number of seconds a pre-signed cover URL remains valid
+    pub cover_url_seconds: u64,
+    // S3 bucket of verification documents of parties, documents are kept in a local directory without it
+    pub documents_bucket: Option<String>,
+    // number of days verification documents are retained before they are purged
+    pub document_retention_days: i64,
+    // number of seconds a pre-signed upload or download URL of a document remains valid
+    pub document_url_seconds: u64,
+    // largest request body accepted by routes without a limit of their own, after decompression
+    pub max_body_bytes: usize,
+    // largest request body of batch endpoints, which is also the cap of decompressed request bodies
+    pub max_bulk_body_bytes: usize,
+    // number of days removed patrons can be restored before their records are purged
+    pub patron_retention_days: i64,
+    // number of days an interlibrary loan can wait on the lending library or the patron in an intermediate state
+    // before the watchdog reports it as stuck
+    pub ill_stuck_days: i64,
+    // number of holds a patron let expire without pickup, less the holds picked up since, at which their max holds
+    // are reduced, zero disables the reduction
+    pub no_show_threshold: i64,
+    // max holds of patrons at the no-show threshold, zero requires librarian approval for each of their holds
+    pub no_show_max_holds: i64,
 }
 
 impl Configuration {
@@ -23,6 +112,47 @@ impl Configuration {
             max_holds: 4,
             book_loan_days: 15,
             bool_hold_days: 10,
+            digital_loan_days: 14,
+            max_overdue: 5,
+            hold_pickup_days: 7,
+            max_hold_extensions: 2,
+            hold_extension_days: 7,
+            due_soon_digest_days: 3,
+            floating_collections: HashMap::new(),
+            verification_secret: std::env::var("VERIFICATION_SECRET").unwrap_or_else(|_| "dev-verification-secret".to_string()),
+            calendar_secret: std::env::var("CALENDAR_SECRET").unwrap_or_else(|_| "dev-calendar-secret".to_string()),
+            verification_token_hours: 48,
+            jwt_secret: std::env::var("JWT_SECRET").unwrap_or_else(|_| "dev-jwt-secret".to_string()),
+            jwt_expiry_hours: 12,
+            password_reset_hours: 2,
+            rate_limit_per_minute: 600,
+            strip_email_plus_tags: false,
+            address_validation_url: std::env::var("ADDRESS_VALIDATION_URL").ok(),
+            union_catalog_url: std::env::var("UNION_CATALOG_URL").ok(),
+            union_catalog_timeout_ms: 3000,
+            payment_gateway_url: std::env::var("PAYMENT_GATEWAY_URL").ok(),
+            payment_api_key: std::env::var("PAYMENT_API_KEY").unwrap_or_default(),
+            payment_webhook_secret: std::env::var("PAYMENT_WEBHOOK_SECRET").unwrap_or_else(|_| "dev-payment-webhook-secret".to_string()),
+            payment_webhook_tolerance_seconds: 300,
+            fine_currency: "usd".to_string(),
+            fine_adjustment_approval_threshold: 2500,
+            overdue_fine_per_day: 25,
+            new_acquisition_days: 30,
+            feed_cache_seconds: 900,
+            oai_base_url: std::env::var("OAI_BASE_URL").unwrap_or_else(|_| "http://localhost:9000/catalog/oai".to_string()),
+            oai_admin_email: std::env::var("OAI_ADMIN_EMAIL").unwrap_or_else(|_| "librarian@example.com".to_string()),
+            covers_bucket: std::env::var("COVERS_BUCKET").ok(),
+            max_cover_bytes: 2 * 1024 * 1024,
+            cover_url_seconds: 900,
+            documents_bucket: std::env::var("DOCUMENTS_BUCKET").ok(),
+            document_retention_days: 365,
+            document_url_seconds: 300,
+            patron_retention_days: 30,
+            ill_stuck_days: 14,
+            no_show_threshold: 3,
+            no_show_max_holds: 1,
+            max_body_bytes: 1024 * 1024,
+            max_bulk_body_bytes: 10 * 1024 * 1024,
         }
     }
 }
@@ -37,5 +167,43 @@ mod tests {
         assert_eq!(4, config.max_holds);
         assert_eq!(15, config.book_loan_days);
         assert_eq!(10, config.bool_hold_days);
+        assert_eq!(14, config.digital_loan_days);
+        assert_eq!(5, config.max_overdue);
+        assert_eq!(7, config.hold_pickup_days);
+        assert_eq!(2, config.max_hold_extensions);
+        assert_eq!(7, config.hold_extension_days);
+        assert_eq!(3, config.due_soon_digest_days);
+        assert!(config.floating_collections.is_empty());
+        assert!(!config.verification_secret.is_empty());
+        assert!(!config.calendar_secret.is_empty());
+        assert_eq!(48, config.verification_token_hours);
+        assert!(!config.jwt_secret.is_empty());
+        assert_eq!(12, config.jwt_expiry_hours);
+        assert_eq!(2, config.password_reset_hours);
+        assert!(!config.strip_email_plus_tags);
+        assert_eq!(std::env::var("ADDRESS_VALIDATION_URL").ok(), config.address_validation_url);
+        assert_eq!(std::env::var("UNION_CATALOG_URL").ok(), config.union_catalog_url);
+        assert_eq!(3000, config.union_catalog_timeout_ms);
+        assert_eq!(std::env::var("PAYMENT_GATEWAY_URL").ok(), config.payment_gateway_url);
+        assert!(!config.payment_webhook_secret.is_empty());
+        assert_eq!(300, config.payment_webhook_tolerance_seconds);
+        assert_eq!("usd", config.fine_currency.as_str());
+        assert_eq!(2500, config.fine_adjustment_approval_threshold);
+        assert_eq!(25, config.overdue_fine_per_day);
+        assert_eq!(30, config.new_acquisition_days);
+        assert_eq!(900, config.feed_cache_seconds);
+        assert!(!config.oai_base_url.is_empty());
+        assert!(!config.oai_admin_email.is_empty());
+        assert_eq!(2 * 1024 * 1024, config.max_cover_bytes);
+        assert_eq!(900, config.cover_url_seconds);
+        assert_eq!(365, config.document_retention_days);
+        assert_eq!(300, config.document_url_seconds);
+        assert_eq!(30, config.patron_retention_days);
+        assert_eq!(14, config.ill_stuck_days);
+        assert_eq!(3, config.no_show_threshold);
+        assert_eq!(1, config.no_show_max_holds);
+        assert_eq!(1024 * 1024, config.max_body_bytes);
+        assert_eq!(10 * 1024 * 1024, config.max_bulk_body_bytes);
+        assert_eq!(600, config.rate_limit_per_minute);
     }
 }
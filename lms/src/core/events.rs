@@ -1,11 +1,17 @@
 use std::collections::HashMap;
 use chrono::{NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use uuid::Uuid;
-use crate::utils::date::{serializer};
+use crate::core::context::RequestContext;
+use crate::core::events::upcasters::{INITIAL_SCHEMA_VERSION, UPCASTERS};
+use crate::utils::date::{serializer, Rfc3339};
+
+pub mod schemas;
+pub mod upcasters;
 
 // DomainEventType defines type of event for domain changes
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum DomainEventType {
     Added,
     Updated,
@@ -13,7 +19,7 @@ pub enum DomainEventType {
 }
 
 // DomainEvent abstracts domain event for data changes
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
 pub(crate) struct DomainEvent {
     pub event_id: String,
     pub name: String,
@@ -22,10 +28,18 @@ pub(crate) struct DomainEvent {
     pub kind: DomainEventType,
     pub metadata: HashMap<String, String>,
     pub json_data: String,
+    // version of the payload schema, events published before payloads were versioned have none
+    #[serde(default = "initial_schema_version")]
+    pub schema_version: u32,
     #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
     pub created_at: NaiveDateTime,
 }
 
+fn initial_schema_version() -> u32 {
+    INITIAL_SCHEMA_VERSION
+}
+
 impl DomainEvent {
     pub fn added<T: Serialize>(name: &str, group: &str, key: &str, metadata: &HashMap<String, String>, data: &T) -> serde_json::Result<Self> {
         let json = serde_json::to_string(&data)?;
@@ -43,14 +57,23 @@ impl DomainEvent {
     }
 
     fn build(name: &str, group: &str, key: &str, kind: DomainEventType, metadata: &HashMap<String, String>, json: String) -> DomainEvent {
+        let mut metadata = metadata.clone();
+        // events raised within a request are correlated with the request and its actor
+        if let Some(ctx) = RequestContext::current() {
+            metadata.entry("correlation_id".to_string()).or_insert(ctx.correlation_id.clone());
+            if ctx.is_authenticated() {
+                metadata.entry("actor_id".to_string()).or_insert(ctx.actor_id);
+            }
+        }
         DomainEvent {
             event_id: Uuid::new_v4().to_string(),
             name: name.to_string(),
             group: group.to_string(),
             key: key.to_string(),
             kind,
-            metadata: metadata.clone(),
+            metadata,
             json_data: json,
+            schema_version: UPCASTERS.current_version(name),
             created_at: Utc::now().naive_utc(),
         }
     }
@@ -59,7 +82,23 @@ impl DomainEvent {
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
+    use crate::core::context::RequestContext;
     use crate::core::events::{DomainEvent, DomainEventType};
+    use crate::core::events::upcasters::INITIAL_SCHEMA_VERSION;
+
+    #[tokio::test]
+    async fn test_should_add_request_context_to_metadata() {
+        let data = HashMap::from([("a", 1)]);
+        let event = DomainEvent::added("name", "group", "key", &HashMap::new(), &data).expect("build event");
+        assert!(event.metadata.is_empty());
+
+        let ctx = RequestContext::anonymous("branch", Some("corr-3"), None).authenticated("actor1", &[], "", "");
+        let event = ctx.scope(async {
+            DomainEvent::added("name", "group", "key", &HashMap::new(), &data)
+        }).await.expect("build event");
+        assert_eq!(Some(&"corr-3".to_string()), event.metadata.get("correlation_id"));
+        assert_eq!(Some(&"actor1".to_string()), event.metadata.get("actor_id"));
+    }
 
     #[tokio::test]
     async fn test_should_build_added() {
@@ -70,6 +109,16 @@ mod tests {
         assert_eq!(DomainEventType::Added, event.kind);
     }
 
+    #[tokio::test]
+    async fn test_should_read_unversioned_events() {
+        let data = HashMap::from([("a", 1)]);
+        let event = DomainEvent::added("name", "group", "key", &HashMap::new(), &data).expect("build event");
+        let mut json = serde_json::to_value(&event).expect("should serialize");
+        json.as_object_mut().expect("should be object").remove("schema_version");
+        let read: DomainEvent = serde_json::from_value(json).expect("should deserialize");
+        assert_eq!(INITIAL_SCHEMA_VERSION, read.schema_version);
+    }
+
     #[tokio::test]
     async fn test_should_build_updated() {
         let data = HashMap::from([("a", 1), ("b", 2)]);
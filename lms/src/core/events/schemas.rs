@@ -0,0 +1,71 @@
+use schemars::schema::RootSchema;
+use schemars::{schema_for, JsonSchema};
+use serde_json::Value;
+use crate::audit::dto::AuditDto;
+use crate::books::dto::BookDto;
+use crate::checkout::dto::{CheckInDto, CheckoutDto};
+use crate::core::events::DomainEvent;
+use crate::hold::dto::HoldDto;
+use crate::ill::dto::IllRequestDto;
+use crate::inventory::dto::InventorySessionDto;
+use crate::notifications::dto::NotificationDto;
+use crate::programs::dto::{ProgramDto, RegistrationDto};
+use crate::reserves::dto::{ReserveItemDto, ReserveListDto};
+use crate::resources::dto::{BookingDto, ResourceDto};
+
+// extension of payload schemas that lists the groups of the events carrying the payload in json_data
+pub(crate) const EVENT_GROUPS_EXTENSION: &str = "x-event-groups";
+
+// returns the json schema of the event envelope followed by the schemas of all payloads that are published,
+// schemas are named after their type so that consumers can generate types with the same names
+pub(crate) fn message_schemas() -> Vec<(&'static str, RootSchema)> {
+    vec![
+        ("DomainEvent", schema_for!(DomainEvent)),
+        payload::<BookDto>("BookDto", &["books"]),
+        payload::<CheckoutDto>("CheckoutDto", &["checkout"]),
+        payload::<CheckInDto>("CheckInDto", &["checkout"]),
+        payload::<HoldDto>("HoldDto", &["book_hold", "book_hold_cancel", "book_hold_checkout"]),
+        payload::<IllRequestDto>("IllRequestDto", &["ill"]),
+        payload::<ProgramDto>("ProgramDto", &["programs"]),
+        payload::<RegistrationDto>("RegistrationDto", &["programs"]),
+        payload::<ReserveListDto>("ReserveListDto", &["reserves"]),
+        payload::<ReserveItemDto>("ReserveItemDto", &["reserves"]),
+        payload::<AuditDto>("AuditDto", &["audit"]),
+        payload::<NotificationDto>("NotificationDto", &["notifications"]),
+        payload::<InventorySessionDto>("InventorySessionDto", &["inventory"]),
+        payload::<ResourceDto>("ResourceDto", &["resources"]),
+        payload::<BookingDto>("BookingDto", &["resources"]),
+    ]
+}
+
+fn payload<T: JsonSchema>(name: &'static str, groups: &[&str]) -> (&'static str, RootSchema) {
+    let mut schema = schema_for!(T);
+    schema.schema.extensions.insert(EVENT_GROUPS_EXTENSION.to_string(),
+                                    Value::Array(groups.iter().map(|group| Value::String(group.to_string())).collect()));
+    (name, schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+    use crate::books::dto::BookDto;
+    use crate::core::events::schemas::{message_schemas, EVENT_GROUPS_EXTENSION};
+    use crate::core::library::BookStatus;
+
+    #[tokio::test]
+    async fn test_should_describe_published_payloads() {
+        let schemas = message_schemas();
+        assert_eq!("DomainEvent", schemas[0].0);
+        assert!(schemas[1..].iter().all(|(_, schema)| schema.schema.extensions.contains_key(EVENT_GROUPS_EXTENSION)));
+
+        let (_, book_schema) = schemas.iter().find(|(name, _)| *name == "BookDto").expect("should describe books");
+        let book_schema = serde_json::to_value(book_schema).expect("should serialize schema");
+        let book = serde_json::to_value(BookDto::new("isbn", "title", BookStatus::Available)).expect("should serialize book");
+        let properties = book_schema["properties"].as_object().expect("should have properties");
+        for field in book.as_object().expect("should be object").keys() {
+            assert!(properties.contains_key(field), "{} is not described", field);
+        }
+        assert_eq!(Value::String("date-time".to_string()), properties["created_at"]["format"]);
+        assert_eq!(Value::String("books".to_string()), book_schema[EVENT_GROUPS_EXTENSION][0]);
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use lazy_static::lazy_static;
+use serde_json::Value;
+use crate::core::events::DomainEvent;
+use crate::core::library::{BookFormat, LibraryResult};
+
+// schema version of events that were published before payloads were versioned
+pub(crate) const INITIAL_SCHEMA_VERSION: u32 = 1;
+
+// Upcaster transforms a payload of one schema version into the shape of the next version
+pub(crate) type Upcaster = fn(Value) -> Value;
+
+// UpcasterRegistry keeps upcasters by event name and the schema version they read so that consumers only
+// deal with the current shape of payloads when old events are replayed or delivered again
+pub(crate) struct UpcasterRegistry {
+    upcasters: HashMap<(String, u32), Upcaster>,
+}
+
+impl UpcasterRegistry {
+    pub(crate) fn new() -> Self {
+        Self {
+            upcasters: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn register(mut self, name: &str, from_version: u32, upcaster: Upcaster) -> Self {
+        self.upcasters.insert((name.to_string(), from_version), upcaster);
+        self
+    }
+
+    // events are published with the version that follows the last registered upcaster of their name
+    pub(crate) fn current_version(&self, name: &str) -> u32 {
+        let mut version = INITIAL_SCHEMA_VERSION;
+        while self.upcasters.contains_key(&(name.to_string(), version)) {
+            version += 1;
+        }
+        version
+    }
+
+    // applies upcasters one version at a time until the payload has the current shape
+    pub(crate) fn upcast(&self, event: &DomainEvent) -> LibraryResult<DomainEvent> {
+        let mut upcasted = event.clone();
+        let mut data: Option<Value> = None;
+        while let Some(upcaster) = self.upcasters.get(&(event.name.to_string(), upcasted.schema_version)) {
+            let current = match data.take() {
+                Some(current) => current,
+                None => serde_json::from_str(event.json_data.as_str())?,
+            };
+            data = Some(upcaster(current));
+            upcasted.schema_version += 1;
+        }
+        if let Some(data) = data {
+            upcasted.json_data = serde_json::to_string(&data)?;
+        }
+        Ok(upcasted)
+    }
+}
+
+lazy_static! {
+    // register an upcaster whenever a payload changes in a way that consumers cannot read its old events
+    pub(crate) static ref UPCASTERS: UpcasterRegistry = UpcasterRegistry::new()
+        .register("book_checkout", 1, add_book_format)
+        .register("book_returned", 1, add_book_format)
+        .register("book_overdue", 1, add_book_format)
+        .register("book_checked_in", 1, add_checked_in_book_format);
+}
+
+// checkouts published before digital formats were added are physical books
+fn add_book_format(mut checkout: Value) -> Value {
+    if let Some(fields) = checkout.as_object_mut() {
+        fields.entry("book_format").or_insert_with(|| Value::String(BookFormat::Physical.to_string()));
+    }
+    checkout
+}
+
+fn add_checked_in_book_format(mut check_in: Value) -> Value {
+    if let Some(checkout) = check_in.get_mut("checkout") {
+        *checkout = add_book_format(checkout.take());
+    }
+    check_in
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use serde_json::{json, Value};
+    use crate::checkout::dto::CheckoutDto;
+    use crate::core::events::DomainEvent;
+    use crate::core::events::upcasters::{UpcasterRegistry, INITIAL_SCHEMA_VERSION, UPCASTERS};
+    use crate::core::library::BookFormat;
+
+    fn rename_title(mut data: Value) -> Value {
+        if let Some(fields) = data.as_object_mut() {
+            if let Some(title) = fields.remove("name") {
+                fields.insert("title".to_string(), title);
+            }
+        }
+        data
+    }
+
+    fn add_subtitle(mut data: Value) -> Value {
+        if let Some(fields) = data.as_object_mut() {
+            fields.insert("subtitle".to_string(), Value::String("".to_string()));
+        }
+        data
+    }
+
+    #[tokio::test]
+    async fn test_should_upcast_through_versions() {
+        let registry = UpcasterRegistry::new()
+            .register("book_added", 1, rename_title)
+            .register("book_added", 2, add_subtitle);
+        assert_eq!(3, registry.current_version("book_added"));
+        assert_eq!(INITIAL_SCHEMA_VERSION, registry.current_version("book_removed"));
+
+        let mut event = DomainEvent::added("book_added", "books", "key", &HashMap::new(), &json!({"name": "Dune"}))
+            .expect("build event");
+        event.schema_version = INITIAL_SCHEMA_VERSION;
+        let upcasted = registry.upcast(&event).expect("should upcast");
+        assert_eq!(3, upcasted.schema_version);
+        assert_eq!(json!({"title": "Dune", "subtitle": ""}), serde_json::from_str::<Value>(upcasted.json_data.as_str()).unwrap());
+
+        // current events are left alone
+        assert_eq!(upcasted, registry.upcast(&upcasted).expect("should upcast"));
+    }
+
+    #[tokio::test]
+    async fn test_should_upcast_checkouts_without_book_format() {
+        let mut checkout = serde_json::to_value(CheckoutDto::new("book1", "patron1")).expect("should serialize");
+        checkout.as_object_mut().expect("should be object").remove("book_format");
+        let mut event = DomainEvent::added("book_checkout", "checkout", "key", &HashMap::new(), &checkout)
+            .expect("build event");
+        event.schema_version = INITIAL_SCHEMA_VERSION;
+        assert!(serde_json::from_str::<Value>(event.json_data.as_str()).unwrap().get("book_format").is_none());
+
+        let upcasted = UPCASTERS.upcast(&event).expect("should upcast");
+        let dto: CheckoutDto = serde_json::from_str(upcasted.json_data.as_str()).expect("should read checkout");
+        assert_eq!(BookFormat::Physical, dto.book_format);
+    }
+}
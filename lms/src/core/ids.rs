@@ -0,0 +1,151 @@
+use std::fmt;
+use chrono::NaiveDateTime;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use ulid::Ulid;
+use uuid::Uuid;
+
+// defines a newtype over the string id of an aggregate so that ids of different aggregates cannot be swapped,
+// ids are serialized as plain strings so stored items and json payloads are unchanged
+macro_rules! typed_id {
+    ($name:ident) => {
+        #[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn new(id: &str) -> Self {
+                Self(id.to_string())
+            }
+
+            pub fn generate() -> Self {
+                Self(Uuid::new_v4().to_string())
+            }
+
+            pub fn as_str(&self) -> &str {
+                self.0.as_str()
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                self.0.as_str()
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(id: &str) -> Self {
+                Self::new(id)
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(id: String) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<String> for $name {
+            fn eq(&self, other: &String) -> bool {
+                &self.0 == other
+            }
+        }
+
+        impl PartialEq<$name> for String {
+            fn eq(&self, other: &$name) -> bool {
+                self == &other.0
+            }
+        }
+    };
+}
+
+typed_id!(BookId);
+typed_id!(PatronId);
+typed_id!(HoldId);
+
+impl HoldId {
+    pub fn generate_in(branch_id: &str) -> Self {
+        Self(branch_scoped_id(branch_id))
+    }
+}
+
+// ids of holds and checkouts are the branch followed by a ULID, ids of a branch sort by the millisecond they were
+// generated in so that the branch index can read activity of a time range with a range condition on the id, ids
+// generated before were UUIDs that sort at random
+pub(crate) fn branch_scoped_id(branch_id: &str) -> String {
+    format!("{}_{}", branch_id, Ulid::new())
+}
+
+// returns the lowest and highest id that can be generated for the branch within the time range
+pub(crate) fn branch_scoped_range(branch_id: &str, since: NaiveDateTime, until: NaiveDateTime) -> (String, String) {
+    let millis = |time: NaiveDateTime| time.and_utc().timestamp_millis().max(0) as u64;
+    (format!("{}_{}", branch_id, Ulid::from_parts(millis(since), 0)),
+     format!("{}_{}", branch_id, Ulid::from_parts(millis(until), u128::MAX)))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::ids::{branch_scoped_id, branch_scoped_range, BookId, HoldId, PatronId};
+
+    #[tokio::test]
+    async fn test_should_serialize_as_string() {
+        let book_id = BookId::new("book1");
+        assert_eq!("\"book1\"", serde_json::to_string(&book_id).expect("should serialize"));
+        let patron_id: PatronId = serde_json::from_str("\"patron1\"").expect("should deserialize");
+        assert_eq!("patron1", patron_id.to_string());
+        assert_eq!(patron_id, "patron1");
+    }
+
+    #[tokio::test]
+    async fn test_should_generate_unique_ids() {
+        let first = HoldId::generate();
+        let second = HoldId::generate();
+        assert_ne!(first, second);
+        assert_eq!(36, first.as_str().len());
+    }
+
+    #[tokio::test]
+    async fn test_should_generate_branch_scoped_ids_in_time_order() {
+        let since = Utc::now().naive_utc() - Duration::seconds(1);
+        let first = HoldId::generate_in("main");
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let second = branch_scoped_id("main");
+        assert!(first.as_str().starts_with("main_"));
+        assert_eq!("main_".len() + 26, first.as_str().len());
+        assert!(first.as_str() < second.as_str());
+
+        let (lower, upper) = branch_scoped_range("main", since, Utc::now().naive_utc() + Duration::seconds(1));
+        assert!(lower.as_str() < first.as_str() && second.as_str() < upper.as_str());
+        let (lower, _) = branch_scoped_range("main", Utc::now().naive_utc() + Duration::seconds(1),
+                                             Utc::now().naive_utc() + Duration::seconds(2));
+        assert!(second.as_str() < lower.as_str());
+        // ids of other branches are out of range
+        let (lower, upper) = branch_scoped_range("east", since, Utc::now().naive_utc() + Duration::seconds(1));
+        assert!(!(lower.as_str() < first.as_str() && first.as_str() < upper.as_str()));
+    }
+}
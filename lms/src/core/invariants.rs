@@ -0,0 +1,92 @@
+use std::fmt::Debug;
+use serde::{Deserialize, Serialize};
+
+// Invariants are rules of an aggregate that hold after every mutation regardless of the service that changed it
+pub(crate) trait Invariants: Debug {
+    // names the aggregate in reports of violations
+    fn aggregate(&self) -> &'static str;
+
+    fn aggregate_id(&self) -> String;
+
+    // returns the rules broken by the current state, empty when the aggregate is consistent
+    fn violated_rules(&self) -> Vec<&'static str>;
+}
+
+// InvariantViolation reports a rule broken by a stored aggregate
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct InvariantViolation {
+    pub aggregate: String,
+    pub aggregate_id: String,
+    pub rule: String,
+}
+
+impl InvariantViolation {
+    pub fn new(aggregate: &str, aggregate_id: &str, rule: &str) -> Self {
+        Self {
+            aggregate: aggregate.to_string(),
+            aggregate_id: aggregate_id.to_string(),
+            rule: rule.to_string(),
+        }
+    }
+}
+
+// repositories assert invariants before saving an aggregate, debug and test builds panic on a broken rule so that
+// the offending mutation is caught where it happens, release builds skip the check and rely on the invariants job
+pub(crate) fn assert_invariants<T: Invariants>(aggregate: &T) {
+    if cfg!(debug_assertions) {
+        let rules = aggregate.violated_rules();
+        assert!(rules.is_empty(), "{} {} violates {:?}: {:?}",
+                aggregate.aggregate(), aggregate.aggregate_id(), rules, aggregate);
+    }
+}
+
+// reports broken rules of stored aggregates, used by the invariants job in every build
+pub(crate) fn find_violations<T: Invariants>(aggregates: &[T]) -> Vec<InvariantViolation> {
+    aggregates.iter().flat_map(|aggregate| {
+        let id = aggregate.aggregate_id();
+        aggregate.violated_rules().into_iter()
+            .map(move |rule| InvariantViolation::new(aggregate.aggregate(), id.as_str(), rule))
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::invariants::{assert_invariants, find_violations, Invariants, InvariantViolation};
+
+    #[derive(Debug)]
+    struct Range {
+        from: i64,
+        to: i64,
+    }
+
+    impl Invariants for Range {
+        fn aggregate(&self) -> &'static str {
+            "range"
+        }
+
+        fn aggregate_id(&self) -> String {
+            format!("{}..{}", self.from, self.to)
+        }
+
+        fn violated_rules(&self) -> Vec<&'static str> {
+            let mut rules = vec![];
+            if self.from > self.to {
+                rules.push("from must not be after to");
+            }
+            rules
+        }
+    }
+
+    #[tokio::test]
+    async fn test_should_find_violations() {
+        let violations = find_violations(&[Range { from: 1, to: 2 }, Range { from: 3, to: 2 }]);
+        assert_eq!(vec![InvariantViolation::new("range", "3..2", "from must not be after to")], violations);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "violates")]
+    async fn test_should_assert_invariants() {
+        assert_invariants(&Range { from: 1, to: 2 });
+        assert_invariants(&Range { from: 3, to: 2 });
+    }
+}
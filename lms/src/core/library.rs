@@ -1,6 +1,8 @@
 use std::fmt;
 use std::fmt::{Display, Formatter};
+use chrono::{Duration, Months, NaiveDateTime};
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 
 #[derive(Debug)]
 pub enum LibraryError {
@@ -115,6 +117,35 @@ impl LibraryError {
             LibraryError::Runtime { .. } => { false }
         }
     }
+
+    // stable code of the error kind that clients can match on, e.g. in failures of batch results
+    pub fn code(&self) -> &'static str {
+        match self {
+            LibraryError::Database { .. } => { "database" }
+            LibraryError::AccessDenied { .. } => { "access_denied" }
+            LibraryError::NotGranted { .. } => { "not_granted" }
+            LibraryError::DuplicateKey { .. } => { "duplicate_key" }
+            LibraryError::NotFound { .. } => { "not_found" }
+            LibraryError::CurrentlyUnavailable { .. } => { "currently_unavailable" }
+            LibraryError::Validation { .. } => { "validation" }
+            LibraryError::Serialization { .. } => { "serialization" }
+            LibraryError::Runtime { .. } => { "runtime" }
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            LibraryError::Database { message, .. } => { message }
+            LibraryError::AccessDenied { message, .. } => { message }
+            LibraryError::NotGranted { message, .. } => { message }
+            LibraryError::DuplicateKey { message } => { message }
+            LibraryError::NotFound { message } => { message }
+            LibraryError::CurrentlyUnavailable { message, .. } => { message }
+            LibraryError::Validation { message, .. } => { message }
+            LibraryError::Serialization { message } => { message }
+            LibraryError::Runtime { message, .. } => { message }
+        }
+    }
 }
 
 impl From<std::io::Error> for LibraryError {
@@ -202,9 +233,101 @@ impl<T> PaginatedResult<T> {
     }
 }
 
+// upper bound of items of batch operations, it stays within the items of a single ddb transaction
+pub(crate) const MAX_BATCH_ITEMS: usize = 25;
 
+// It defines the overall outcome of a batch operation, mirroring http 200, 207 multi-status and 422
 #[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
-pub(crate) enum BookStatus {
+pub enum BatchStatus {
+    Succeeded,
+    PartiallySucceeded,
+    Failed,
+}
+
+impl BatchStatus {
+    pub fn http_status(&self) -> u16 {
+        match self {
+            BatchStatus::Succeeded => { 200 }
+            BatchStatus::PartiallySucceeded => { 207 }
+            BatchStatus::Failed => { 422 }
+        }
+    }
+}
+
+// It defines a failed item of a batch operation with the code and message of its error
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchFailure {
+    // id of the requested item such as the book id
+    pub id: String,
+    pub code: String,
+    pub message: String,
+}
+
+impl BatchFailure {
+    pub(crate) fn new(id: &str, err: &LibraryError) -> Self {
+        BatchFailure {
+            id: id.to_string(),
+            code: err.code().to_string(),
+            message: err.message().to_string(),
+        }
+    }
+}
+
+// It defines the result of a batch operation such as bulk import, batch checkout/hold and batch delete,
+// items are processed independently so that successes are kept along with the failures of other items
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchResult<T> {
+    pub status: BatchStatus,
+    pub successes: Vec<T>,
+    pub failures: Vec<BatchFailure>,
+}
+
+impl<T> BatchResult<T> {
+    pub(crate) fn new() -> Self {
+        BatchResult {
+            status: BatchStatus::Succeeded,
+            successes: vec![],
+            failures: vec![],
+        }
+    }
+
+    pub(crate) fn succeeded(&mut self, record: T) {
+        self.successes.push(record);
+        self.update_status();
+    }
+
+    pub(crate) fn failed(&mut self, id: &str, err: &LibraryError) {
+        self.failures.push(BatchFailure::new(id, err));
+        self.update_status();
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.status == BatchStatus::Succeeded
+    }
+
+    fn update_status(&mut self) {
+        self.status = if self.failures.is_empty() {
+            BatchStatus::Succeeded
+        } else if self.successes.is_empty() {
+            BatchStatus::Failed
+        } else {
+            BatchStatus::PartiallySucceeded
+        };
+    }
+}
+
+// rejects empty batches and batches exceeding MAX_BATCH_ITEMS
+pub(crate) fn validate_batch(size: usize) -> LibraryResult<()> {
+    if size == 0 || size > MAX_BATCH_ITEMS {
+        return Err(LibraryError::validation(format!("batch must have between 1 and {} items but had {}",
+                                                    MAX_BATCH_ITEMS, size).as_str(), Some("400".to_string())));
+    }
+    Ok(())
+}
+
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub enum BookStatus {
     Available,
     CheckedOut,
     OnHold,
@@ -236,6 +359,122 @@ impl Display for BookStatus {
     }
 }
 
+// BookFormat defines physical and digital formats of a library item
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, JsonSchema, Default)]
+pub enum BookFormat {
+    #[default]
+    Physical,
+    EBook,
+    Audiobook,
+    // title record of a magazine or journal whose issues are tracked by serials
+    Serial,
+}
+
+impl BookFormat {
+    // digital items are lent against concurrent licenses instead of physical copies
+    pub fn is_digital(&self) -> bool {
+        matches!(self, BookFormat::EBook | BookFormat::Audiobook)
+    }
+}
+
+impl From<String> for BookFormat {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "EBook" => BookFormat::EBook,
+            "Audiobook" => BookFormat::Audiobook,
+            "Serial" => BookFormat::Serial,
+            _ => BookFormat::Physical,
+        }
+    }
+}
+
+impl BookFormat {
+    // parses formats given by clients such as ebook or EBook, unlike From<String> unknown formats are rejected
+    pub fn parse(format: &str) -> LibraryResult<BookFormat> {
+        match format.trim().to_lowercase().as_str() {
+            "physical" => Ok(BookFormat::Physical),
+            "ebook" => Ok(BookFormat::EBook),
+            "audiobook" => Ok(BookFormat::Audiobook),
+            "serial" => Ok(BookFormat::Serial),
+            _ => Err(LibraryError::validation(
+                format!("{} is not a book format, use Physical, EBook, Audiobook or Serial", format).as_str(),
+                Some("400".to_string()))),
+        }
+    }
+}
+
+impl Display for BookFormat {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            BookFormat::Physical => write!(f, "Physical"),
+            BookFormat::EBook => write!(f, "EBook"),
+            BookFormat::Audiobook => write!(f, "Audiobook"),
+            BookFormat::Serial => write!(f, "Serial"),
+        }
+    }
+}
+
+// two-letter ISO 639-1 codes in alphabetical order
+const ISO_639_1_CODES: [&str; 183] = [
+    "aa", "ab", "ae", "af", "ak", "am", "an", "ar", "as", "av", "ay", "az", "ba", "be", "bg", "bi", "bm", "bn", "bo",
+    "br", "bs", "ca", "ce", "ch", "co", "cr", "cs", "cu", "cv", "cy", "da", "de", "dv", "dz", "ee", "el", "en", "eo",
+    "es", "et", "eu", "fa", "ff", "fi", "fj", "fo", "fr", "fy", "ga", "gd", "gl", "gn", "gu", "gv", "ha", "he", "hi",
+    "ho", "hr", "ht", "hu", "hy", "hz", "ia", "id", "ie", "ig", "ii", "ik", "io", "is", "it", "iu", "ja", "jv", "ka",
+    "kg", "ki", "kj", "kk", "kl", "km", "kn", "ko", "kr", "ks", "ku", "kv", "kw", "ky", "la", "lb", "lg", "li", "ln",
+    "lo", "lt", "lu", "lv", "mg", "mh", "mi", "mk", "ml", "mn", "mr", "ms", "mt", "my", "na", "nb", "nd", "ne", "ng",
+    "nl", "nn", "no", "nr", "nv", "ny", "oc", "oj", "om", "or", "os", "pa", "pi", "pl", "ps", "pt", "qu", "rm", "rn",
+    "ro", "ru", "rw", "sa", "sc", "sd", "se", "sg", "si", "sk", "sl", "sm", "sn", "so", "sq", "sr", "ss", "st", "su",
+    "sv", "sw", "ta", "te", "tg", "th", "ti", "tk", "tl", "tn", "to", "tr", "ts", "tt", "tw", "ty", "ug", "uk", "ur",
+    "uz", "ve", "vi", "vo", "wa", "wo", "xh", "yi", "yo", "za", "zh", "zu",
+];
+
+// LanguageCode is the ISO 639-1 code of the language of a book such as en or fr, codes are kept in lower case
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct LanguageCode(String);
+
+impl LanguageCode {
+    pub fn parse(code: &str) -> LibraryResult<LanguageCode> {
+        let normalized = code.trim().to_lowercase();
+        if ISO_639_1_CODES.binary_search(&normalized.as_str()).is_err() {
+            return Err(LibraryError::validation(
+                format!("{} is not an ISO 639-1 language code such as en or fr", code).as_str(),
+                Some("400".to_string())));
+        }
+        Ok(LanguageCode(normalized))
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl Default for LanguageCode {
+    fn default() -> Self {
+        LanguageCode("en".to_string())
+    }
+}
+
+impl TryFrom<String> for LanguageCode {
+    type Error = LibraryError;
+
+    fn try_from(code: String) -> Result<Self, Self::Error> {
+        LanguageCode::parse(code.as_str())
+    }
+}
+
+impl From<LanguageCode> for String {
+    fn from(code: LanguageCode) -> Self {
+        code.0
+    }
+}
+
+impl Display for LanguageCode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub(crate) enum Role {
     Admin,
@@ -270,8 +509,8 @@ impl Display for Role {
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
-pub(crate) enum CheckoutStatus {
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub enum CheckoutStatus {
     CheckedOut,
     Returned,
 }
@@ -296,10 +535,11 @@ impl Display for CheckoutStatus {
 }
 
 
-#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
-pub(crate) enum HoldStatus {
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub enum HoldStatus {
     OnHold,
     Waiting,
+    ReadyForPickup,
     CheckedOut,
     Canceled,
 }
@@ -309,6 +549,7 @@ impl From<String> for HoldStatus {
         match s.as_str() {
             "OnHold" => HoldStatus::OnHold,
             "Waiting" => HoldStatus::Waiting,
+            "ReadyForPickup" => HoldStatus::ReadyForPickup,
             "CheckedOut" => HoldStatus::CheckedOut,
             "Canceled" => HoldStatus::Canceled,
             _ => HoldStatus::OnHold,
@@ -316,17 +557,607 @@ impl From<String> for HoldStatus {
     }
 }
 
+impl HoldStatus {
+    // holds that still reserve the book for the patron, a patron has at most one of them per book
+    pub(crate) fn is_active(&self) -> bool {
+        matches!(self, HoldStatus::OnHold | HoldStatus::Waiting | HoldStatus::ReadyForPickup)
+    }
+}
+
 impl Display for HoldStatus {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
             HoldStatus::OnHold => write!(f, "OnHold"),
             HoldStatus::Waiting => write!(f, "Waiting"),
+            HoldStatus::ReadyForPickup => write!(f, "ReadyForPickup"),
             HoldStatus::CheckedOut => write!(f, "CheckedOut"),
             HoldStatus::Canceled => write!(f, "Canceled"),
         }
     }
 }
 
+// PurchaseStatus defines workflow of acquiring new titles
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum PurchaseStatus {
+    Requested,
+    Ordered,
+    Received,
+    Cataloged,
+}
+
+impl PurchaseStatus {
+    // purchase requests can only move forward one step at a time
+    pub fn can_transition_to(&self, next: PurchaseStatus) -> bool {
+        matches!((self, next),
+            (PurchaseStatus::Requested, PurchaseStatus::Ordered) |
+            (PurchaseStatus::Ordered, PurchaseStatus::Received) |
+            (PurchaseStatus::Received, PurchaseStatus::Cataloged))
+    }
+}
+
+impl From<String> for PurchaseStatus {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Requested" => PurchaseStatus::Requested,
+            "Ordered" => PurchaseStatus::Ordered,
+            "Received" => PurchaseStatus::Received,
+            "Cataloged" => PurchaseStatus::Cataloged,
+            _ => PurchaseStatus::Requested,
+        }
+    }
+}
+
+impl Display for PurchaseStatus {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            PurchaseStatus::Requested => write!(f, "Requested"),
+            PurchaseStatus::Ordered => write!(f, "Ordered"),
+            PurchaseStatus::Received => write!(f, "Received"),
+            PurchaseStatus::Cataloged => write!(f, "Cataloged"),
+        }
+    }
+}
+
+// FineStatus defines state of a fine charged to a patron, a fine is paid once payments cover what was not waived and
+// waived when waivers cover it without payments
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum FineStatus {
+    Outstanding,
+    Paid,
+    Waived,
+}
+
+impl From<String> for FineStatus {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Outstanding" => FineStatus::Outstanding,
+            "Paid" => FineStatus::Paid,
+            "Waived" => FineStatus::Waived,
+            _ => FineStatus::Outstanding,
+        }
+    }
+}
+
+impl Display for FineStatus {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            FineStatus::Outstanding => write!(f, "Outstanding"),
+            FineStatus::Paid => write!(f, "Paid"),
+            FineStatus::Waived => write!(f, "Waived"),
+        }
+    }
+}
+
+// AdjustmentKind defines how a librarian corrects a fine, a waiver reduces what is owed and a refund returns money that
+// was paid beyond it
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum AdjustmentKind {
+    Waive,
+    Refund,
+}
+
+impl From<String> for AdjustmentKind {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Refund" => AdjustmentKind::Refund,
+            _ => AdjustmentKind::Waive,
+        }
+    }
+}
+
+impl Display for AdjustmentKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            AdjustmentKind::Waive => write!(f, "Waive"),
+            AdjustmentKind::Refund => write!(f, "Refund"),
+        }
+    }
+}
+
+// AdjustmentReason is the reason code recorded with each adjustment of a fine so that adjustments can be reported
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum AdjustmentReason {
+    Hardship,
+    StaffError,
+    ItemReturned,
+    Duplicate,
+    Overcharge,
+    Other,
+}
+
+impl From<String> for AdjustmentReason {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Hardship" => AdjustmentReason::Hardship,
+            "StaffError" => AdjustmentReason::StaffError,
+            "ItemReturned" => AdjustmentReason::ItemReturned,
+            "Duplicate" => AdjustmentReason::Duplicate,
+            "Overcharge" => AdjustmentReason::Overcharge,
+            _ => AdjustmentReason::Other,
+        }
+    }
+}
+
+impl Display for AdjustmentReason {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            AdjustmentReason::Hardship => write!(f, "Hardship"),
+            AdjustmentReason::StaffError => write!(f, "StaffError"),
+            AdjustmentReason::ItemReturned => write!(f, "ItemReturned"),
+            AdjustmentReason::Duplicate => write!(f, "Duplicate"),
+            AdjustmentReason::Overcharge => write!(f, "Overcharge"),
+            AdjustmentReason::Other => write!(f, "Other"),
+        }
+    }
+}
+
+// StatementEntryKind defines the lines of a statement of a patron, fines and refunds add to what the patron owes
+// while payments and waivers reduce it
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum StatementEntryKind {
+    Fine,
+    Payment,
+    Waiver,
+    Refund,
+}
+
+impl Display for StatementEntryKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            StatementEntryKind::Fine => write!(f, "Fine"),
+            StatementEntryKind::Payment => write!(f, "Payment"),
+            StatementEntryKind::Waiver => write!(f, "Waiver"),
+            StatementEntryKind::Refund => write!(f, "Refund"),
+        }
+    }
+}
+
+// SerialFrequency defines publication schedule of serial issues
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum SerialFrequency {
+    Weekly,
+    Biweekly,
+    Monthly,
+    Quarterly,
+    Annual,
+}
+
+impl SerialFrequency {
+    // expected publication date of nth issue counted from the first issue, months are added to
+    // the first issue so that issues at the end of month do not drift
+    pub fn expected_at(&self, first_issue_at: NaiveDateTime, issue_index: u32) -> NaiveDateTime {
+        let months = match self {
+            SerialFrequency::Weekly => return first_issue_at + Duration::days(7 * issue_index as i64),
+            SerialFrequency::Biweekly => return first_issue_at + Duration::days(14 * issue_index as i64),
+            SerialFrequency::Monthly => issue_index,
+            SerialFrequency::Quarterly => 3 * issue_index,
+            SerialFrequency::Annual => 12 * issue_index,
+        };
+        first_issue_at.checked_add_months(Months::new(months)).unwrap_or(first_issue_at)
+    }
+}
+
+impl From<String> for SerialFrequency {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Weekly" => SerialFrequency::Weekly,
+            "Biweekly" => SerialFrequency::Biweekly,
+            "Monthly" => SerialFrequency::Monthly,
+            "Quarterly" => SerialFrequency::Quarterly,
+            "Annual" => SerialFrequency::Annual,
+            _ => SerialFrequency::Monthly,
+        }
+    }
+}
+
+impl Display for SerialFrequency {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SerialFrequency::Weekly => write!(f, "Weekly"),
+            SerialFrequency::Biweekly => write!(f, "Biweekly"),
+            SerialFrequency::Monthly => write!(f, "Monthly"),
+            SerialFrequency::Quarterly => write!(f, "Quarterly"),
+            SerialFrequency::Annual => write!(f, "Annual"),
+        }
+    }
+}
+
+// IssueStatus defines check-in status of a serial issue
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum IssueStatus {
+    Expected,
+    Received,
+    // missing issues are claimed from the publisher
+    Claimed,
+}
+
+impl From<String> for IssueStatus {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Expected" => IssueStatus::Expected,
+            "Received" => IssueStatus::Received,
+            "Claimed" => IssueStatus::Claimed,
+            _ => IssueStatus::Expected,
+        }
+    }
+}
+
+impl Display for IssueStatus {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            IssueStatus::Expected => write!(f, "Expected"),
+            IssueStatus::Received => write!(f, "Received"),
+            IssueStatus::Claimed => write!(f, "Claimed"),
+        }
+    }
+}
+
+// IllStatus defines workflow of interlibrary loan requests for titles that are not in the catalog
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub(crate) enum IllStatus {
+    Requested,
+    Approved,
+    Rejected,
+    // book arrived from the lending library and is loaned to the patron
+    Received,
+    Returned,
+    // book was shipped back and delivered to the lending library
+    Completed,
+}
+
+impl IllStatus {
+    pub fn can_transition_to(&self, next: IllStatus) -> bool {
+        matches!((self, next),
+            (IllStatus::Requested, IllStatus::Approved) |
+            (IllStatus::Requested, IllStatus::Rejected) |
+            (IllStatus::Approved, IllStatus::Received) |
+            (IllStatus::Received, IllStatus::Returned) |
+            (IllStatus::Returned, IllStatus::Completed))
+    }
+}
+
+impl From<String> for IllStatus {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Requested" => IllStatus::Requested,
+            "Approved" => IllStatus::Approved,
+            "Rejected" => IllStatus::Rejected,
+            "Received" => IllStatus::Received,
+            "Returned" => IllStatus::Returned,
+            "Completed" => IllStatus::Completed,
+            _ => IllStatus::Requested,
+        }
+    }
+}
+
+impl Display for IllStatus {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            IllStatus::Requested => write!(f, "Requested"),
+            IllStatus::Approved => write!(f, "Approved"),
+            IllStatus::Rejected => write!(f, "Rejected"),
+            IllStatus::Received => write!(f, "Received"),
+            IllStatus::Returned => write!(f, "Returned"),
+            IllStatus::Completed => write!(f, "Completed"),
+        }
+    }
+}
+
+// ShippingStatus defines status of shipping an interlibrary loan back to the lending library
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub(crate) enum ShippingStatus {
+    NotShipped,
+    InTransit,
+    Delivered,
+}
+
+impl From<String> for ShippingStatus {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "NotShipped" => ShippingStatus::NotShipped,
+            "InTransit" => ShippingStatus::InTransit,
+            "Delivered" => ShippingStatus::Delivered,
+            _ => ShippingStatus::NotShipped,
+        }
+    }
+}
+
+impl Display for ShippingStatus {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ShippingStatus::NotShipped => write!(f, "NotShipped"),
+            ShippingStatus::InTransit => write!(f, "InTransit"),
+            ShippingStatus::Delivered => write!(f, "Delivered"),
+        }
+    }
+}
+
+// ResourceKind defines lendable resources other than books that can be booked for a time slot
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub(crate) enum ResourceKind {
+    Room,
+    Equipment,
+}
+
+impl From<String> for ResourceKind {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Room" => ResourceKind::Room,
+            "Equipment" => ResourceKind::Equipment,
+            _ => ResourceKind::Room,
+        }
+    }
+}
+
+impl Display for ResourceKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ResourceKind::Room => write!(f, "Room"),
+            ResourceKind::Equipment => write!(f, "Equipment"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub(crate) enum BookingStatus {
+    Reserved,
+    Canceled,
+}
+
+impl From<String> for BookingStatus {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Reserved" => BookingStatus::Reserved,
+            "Canceled" => BookingStatus::Canceled,
+            _ => BookingStatus::Reserved,
+        }
+    }
+}
+
+impl Display for BookingStatus {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            BookingStatus::Reserved => write!(f, "Reserved"),
+            BookingStatus::Canceled => write!(f, "Canceled"),
+        }
+    }
+}
+
+// RegistrationStatus defines status of patron registration for a library program
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub(crate) enum RegistrationStatus {
+    Registered,
+    Waitlisted,
+    Canceled,
+}
+
+impl From<String> for RegistrationStatus {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Registered" => RegistrationStatus::Registered,
+            "Waitlisted" => RegistrationStatus::Waitlisted,
+            "Canceled" => RegistrationStatus::Canceled,
+            _ => RegistrationStatus::Waitlisted,
+        }
+    }
+}
+
+impl Display for RegistrationStatus {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            RegistrationStatus::Registered => write!(f, "Registered"),
+            RegistrationStatus::Waitlisted => write!(f, "Waitlisted"),
+            RegistrationStatus::Canceled => write!(f, "Canceled"),
+        }
+    }
+}
+
+// AccountStatus defines standing of a party account, only active accounts can hold or checkout books
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum AccountStatus {
+    Active,
+    // self-registered accounts remain pending until email is verified
+    Pending,
+    Suspended,
+    Expired,
+    Banned,
+    // removed accounts are kept with their personal data until the retention job purges them, so they can be restored
+    Deleted,
+}
+
+impl From<String> for AccountStatus {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Active" => AccountStatus::Active,
+            "Pending" => AccountStatus::Pending,
+            "Suspended" => AccountStatus::Suspended,
+            "Expired" => AccountStatus::Expired,
+            "Banned" => AccountStatus::Banned,
+            "Deleted" => AccountStatus::Deleted,
+            _ => AccountStatus::Active,
+        }
+    }
+}
+
+impl Display for AccountStatus {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            AccountStatus::Active => write!(f, "Active"),
+            AccountStatus::Pending => write!(f, "Pending"),
+            AccountStatus::Suspended => write!(f, "Suspended"),
+            AccountStatus::Expired => write!(f, "Expired"),
+            AccountStatus::Banned => write!(f, "Banned"),
+            AccountStatus::Deleted => write!(f, "Deleted"),
+        }
+    }
+}
+
+// ItemRouting defines where a checked-in item is sent next
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub enum ItemRouting {
+    Reshelve,
+    FillHold,
+    Transfer,
+}
+
+impl From<String> for ItemRouting {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Reshelve" => ItemRouting::Reshelve,
+            "FillHold" => ItemRouting::FillHold,
+            "Transfer" => ItemRouting::Transfer,
+            _ => ItemRouting::Reshelve,
+        }
+    }
+}
+
+impl Display for ItemRouting {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ItemRouting::Reshelve => write!(f, "Reshelve"),
+            ItemRouting::FillHold => write!(f, "FillHold"),
+            ItemRouting::Transfer => write!(f, "Transfer"),
+        }
+    }
+}
+
+// InventoryStatus defines status of a shelf inventory session
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub(crate) enum InventoryStatus {
+    InProgress,
+    Completed,
+}
+
+impl From<String> for InventoryStatus {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "InProgress" => InventoryStatus::InProgress,
+            "Completed" => InventoryStatus::Completed,
+            _ => InventoryStatus::InProgress,
+        }
+    }
+}
+
+impl Display for InventoryStatus {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            InventoryStatus::InProgress => write!(f, "InProgress"),
+            InventoryStatus::Completed => write!(f, "Completed"),
+        }
+    }
+}
+
+// ApiKeyStatus defines status of an API key issued to a machine client
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum ApiKeyStatus {
+    Active,
+    Revoked,
+}
+
+impl From<String> for ApiKeyStatus {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Active" => ApiKeyStatus::Active,
+            "Revoked" => ApiKeyStatus::Revoked,
+            _ => ApiKeyStatus::Revoked,
+        }
+    }
+}
+
+impl Display for ApiKeyStatus {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ApiKeyStatus::Active => write!(f, "Active"),
+            ApiKeyStatus::Revoked => write!(f, "Revoked"),
+        }
+    }
+}
+
+// ScanResult defines reconciliation of a scanned or expected item against the catalog
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum ScanResult {
+    Found,
+    Misplaced,
+    Unexpected,
+    Missing,
+}
+
+impl From<String> for ScanResult {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Found" => ScanResult::Found,
+            "Misplaced" => ScanResult::Misplaced,
+            "Unexpected" => ScanResult::Unexpected,
+            "Missing" => ScanResult::Missing,
+            _ => ScanResult::Unexpected,
+        }
+    }
+}
+
+impl Display for ScanResult {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ScanResult::Found => write!(f, "Found"),
+            ScanResult::Misplaced => write!(f, "Misplaced"),
+            ScanResult::Unexpected => write!(f, "Unexpected"),
+            ScanResult::Missing => write!(f, "Missing"),
+        }
+    }
+}
+
+// OverrideRule defines policy rejections that can be overridden by librarians
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum OverrideRule {
+    MaxHolds,
+    RestrictedBook,
+    SuspendedAccount,
+    // holds of patrons whose max holds are reduced for repeated no-shows
+    NoShows,
+}
+
+impl From<String> for OverrideRule {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "MaxHolds" => OverrideRule::MaxHolds,
+            "RestrictedBook" => OverrideRule::RestrictedBook,
+            "SuspendedAccount" => OverrideRule::SuspendedAccount,
+            "NoShows" => OverrideRule::NoShows,
+            _ => OverrideRule::MaxHolds,
+        }
+    }
+}
+
+impl Display for OverrideRule {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            OverrideRule::MaxHolds => write!(f, "MaxHolds"),
+            OverrideRule::RestrictedBook => write!(f, "RestrictedBook"),
+            OverrideRule::SuspendedAccount => write!(f, "SuspendedAccount"),
+            OverrideRule::NoShows => write!(f, "NoShows"),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub(crate) enum PartyKind {
     Patron,
@@ -358,9 +1189,101 @@ impl Display for PartyKind {
     }
 }
 
+// DocumentKind defines verification documents that are kept for parties
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum DocumentKind {
+    IdCard,
+    Passport,
+    DriverLicense,
+    ProofOfAddress,
+    Other,
+}
+
+impl From<String> for DocumentKind {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "IdCard" => DocumentKind::IdCard,
+            "Passport" => DocumentKind::Passport,
+            "DriverLicense" => DocumentKind::DriverLicense,
+            "ProofOfAddress" => DocumentKind::ProofOfAddress,
+            _ => DocumentKind::Other,
+        }
+    }
+}
+
+impl Display for DocumentKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            DocumentKind::IdCard => write!(f, "IdCard"),
+            DocumentKind::Passport => write!(f, "Passport"),
+            DocumentKind::DriverLicense => write!(f, "DriverLicense"),
+            DocumentKind::ProofOfAddress => write!(f, "ProofOfAddress"),
+            DocumentKind::Other => write!(f, "Other"),
+        }
+    }
+}
+
+// PushTopic defines notifications that are also pushed to connected clients of the party
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum PushTopic {
+    HoldReady,
+    DueSoon,
+}
+
+impl TryFrom<String> for PushTopic {
+    type Error = LibraryError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match s.as_str() {
+            "HoldReady" => Ok(PushTopic::HoldReady),
+            "DueSoon" => Ok(PushTopic::DueSoon),
+            _ => Err(LibraryError::validation(format!("unknown push topic {}", s).as_str(), Some("400".to_string()))),
+        }
+    }
+}
+
+impl Display for PushTopic {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            PushTopic::HoldReady => write!(f, "HoldReady"),
+            PushTopic::DueSoon => write!(f, "DueSoon"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::core::library::{BookStatus, LibraryError};
+    use chrono::NaiveDateTime;
+    use crate::core::library::{validate_batch, AccountStatus, AdjustmentReason, ApiKeyStatus, BatchResult, BatchStatus, BookFormat, BookingStatus, BookStatus, DocumentKind, IllStatus, InventoryStatus, IssueStatus, ItemRouting, LanguageCode, LibraryError, OverrideRule, PurchaseStatus, PushTopic, RegistrationStatus, ResourceKind, ScanResult, SerialFrequency, ShippingStatus, MAX_BATCH_ITEMS};
+    use crate::utils::date::DATE_FMT;
+
+    #[tokio::test]
+    async fn test_should_validate_batch_size() {
+        assert!(validate_batch(0).is_err());
+        assert!(validate_batch(1).is_ok());
+        assert!(validate_batch(MAX_BATCH_ITEMS).is_ok());
+        assert!(validate_batch(MAX_BATCH_ITEMS + 1).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_build_batch_result() {
+        let mut res = BatchResult::new();
+        res.succeeded(1);
+        assert!(res.is_success());
+        assert_eq!(200, res.status.http_status());
+        res.failed("book2", &LibraryError::not_found("book2 not found"));
+        assert_eq!(BatchStatus::PartiallySucceeded, res.status);
+        assert_eq!(207, res.status.http_status());
+        assert_eq!(vec![1], res.successes);
+        assert_eq!("book2", res.failures[0].id);
+        assert_eq!("not_found", res.failures[0].code);
+        assert_eq!("book2 not found", res.failures[0].message);
+
+        let mut failed: BatchResult<i32> = BatchResult::new();
+        failed.failed("book3", &LibraryError::validation("invalid", None));
+        assert_eq!(BatchStatus::Failed, failed.status);
+        assert_eq!(422, failed.status.http_status());
+    }
 
     #[tokio::test]
     async fn test_should_create_database_error() {
@@ -445,4 +1368,135 @@ mod tests {
             assert_eq!(status, str_status);
         }
     }
+
+    #[tokio::test]
+    async fn test_should_format_book_format() {
+        let formats = vec![
+            BookFormat::Physical,
+            BookFormat::EBook,
+            BookFormat::Audiobook,
+            BookFormat::Serial,
+        ];
+        for format in formats {
+            let str = format.to_string();
+            let str_format = BookFormat::from(str);
+            assert_eq!(format, str_format);
+        }
+        assert_eq!(BookFormat::EBook, BookFormat::parse(" ebook").expect("should parse format"));
+        assert!(BookFormat::parse("Digital").is_err());
+        assert!(!BookFormat::Physical.is_digital());
+        assert!(BookFormat::EBook.is_digital());
+        assert!(!BookFormat::Serial.is_digital());
+    }
+
+    #[tokio::test]
+    async fn test_should_parse_language_codes() {
+        assert_eq!("fr", LanguageCode::parse(" FR").expect("should parse code").as_str());
+        assert_eq!("en", LanguageCode::default().to_string());
+        assert!(LanguageCode::parse("english").is_err());
+        assert!(LanguageCode::parse("xx").is_err());
+        assert!(serde_json::from_str::<LanguageCode>("\"zz\"").is_err());
+        assert_eq!("\"de\"", serde_json::to_string(&LanguageCode::parse("de").expect("should parse code")).expect("should serialize"));
+    }
+
+    #[tokio::test]
+    async fn test_should_transition_purchase_status() {
+        assert!(PurchaseStatus::Requested.can_transition_to(PurchaseStatus::Ordered));
+        assert!(PurchaseStatus::Ordered.can_transition_to(PurchaseStatus::Received));
+        assert!(PurchaseStatus::Received.can_transition_to(PurchaseStatus::Cataloged));
+        assert!(!PurchaseStatus::Requested.can_transition_to(PurchaseStatus::Received));
+        assert!(!PurchaseStatus::Cataloged.can_transition_to(PurchaseStatus::Requested));
+        assert_eq!(PurchaseStatus::Ordered, PurchaseStatus::from(PurchaseStatus::Ordered.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_should_parse_adjustment_reason() {
+        assert_eq!(AdjustmentReason::StaffError, AdjustmentReason::from(AdjustmentReason::StaffError.to_string()));
+        assert_eq!(AdjustmentReason::Other, AdjustmentReason::from("unknown".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_should_schedule_serial_issues() {
+        let first_issue_at = NaiveDateTime::parse_from_str("2023-01-31T10:00:00", DATE_FMT).unwrap();
+        assert_eq!(first_issue_at, SerialFrequency::Monthly.expected_at(first_issue_at, 0));
+        assert_eq!(NaiveDateTime::parse_from_str("2023-02-07T10:00:00", DATE_FMT).unwrap(), SerialFrequency::Weekly.expected_at(first_issue_at, 1));
+        assert_eq!(NaiveDateTime::parse_from_str("2023-02-28T10:00:00", DATE_FMT).unwrap(), SerialFrequency::Monthly.expected_at(first_issue_at, 1));
+        assert_eq!(NaiveDateTime::parse_from_str("2023-03-31T10:00:00", DATE_FMT).unwrap(), SerialFrequency::Monthly.expected_at(first_issue_at, 2));
+        assert_eq!(NaiveDateTime::parse_from_str("2024-01-31T10:00:00", DATE_FMT).unwrap(), SerialFrequency::Annual.expected_at(first_issue_at, 1));
+        assert_eq!(SerialFrequency::Quarterly, SerialFrequency::from(SerialFrequency::Quarterly.to_string()));
+        assert_eq!(IssueStatus::Claimed, IssueStatus::from(IssueStatus::Claimed.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_should_transition_ill_status() {
+        assert!(IllStatus::Requested.can_transition_to(IllStatus::Approved));
+        assert!(IllStatus::Requested.can_transition_to(IllStatus::Rejected));
+        assert!(IllStatus::Approved.can_transition_to(IllStatus::Received));
+        assert!(IllStatus::Returned.can_transition_to(IllStatus::Completed));
+        assert!(!IllStatus::Rejected.can_transition_to(IllStatus::Approved));
+        assert!(!IllStatus::Requested.can_transition_to(IllStatus::Received));
+        assert_eq!(IllStatus::Returned, IllStatus::from(IllStatus::Returned.to_string()));
+        assert_eq!(ShippingStatus::InTransit, ShippingStatus::from(ShippingStatus::InTransit.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_should_parse_resource_kind_and_booking_status() {
+        assert_eq!(ResourceKind::Equipment, ResourceKind::from(ResourceKind::Equipment.to_string()));
+        assert_eq!(ResourceKind::Room, ResourceKind::from("Room".to_string()));
+        assert_eq!(BookingStatus::Canceled, BookingStatus::from(BookingStatus::Canceled.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_should_parse_registration_status() {
+        assert_eq!(RegistrationStatus::Registered, RegistrationStatus::from(RegistrationStatus::Registered.to_string()));
+        assert_eq!(RegistrationStatus::Canceled, RegistrationStatus::from("Canceled".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_should_parse_account_status() {
+        assert_eq!(AccountStatus::Suspended, AccountStatus::from(AccountStatus::Suspended.to_string()));
+        assert_eq!(AccountStatus::Banned, AccountStatus::from("Banned".to_string()));
+        assert_eq!(AccountStatus::Pending, AccountStatus::from("Pending".to_string()));
+        assert_eq!(AccountStatus::Deleted, AccountStatus::from(AccountStatus::Deleted.to_string()));
+        // parties without status are active
+        assert_eq!(AccountStatus::Active, AccountStatus::from("".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_should_parse_inventory_status() {
+        assert_eq!(InventoryStatus::Completed, InventoryStatus::from(InventoryStatus::Completed.to_string()));
+        assert_eq!(ScanResult::Missing, ScanResult::from(ScanResult::Missing.to_string()));
+        assert_eq!(ScanResult::Misplaced, ScanResult::from("Misplaced".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_should_parse_api_key_status() {
+        assert_eq!(ApiKeyStatus::Active, ApiKeyStatus::from(ApiKeyStatus::Active.to_string()));
+        // unknown status should not grant access
+        assert_eq!(ApiKeyStatus::Revoked, ApiKeyStatus::from("".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_should_parse_item_routing() {
+        assert_eq!(ItemRouting::FillHold, ItemRouting::from(ItemRouting::FillHold.to_string()));
+        assert_eq!(ItemRouting::Transfer, ItemRouting::from("Transfer".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_should_parse_override_rule() {
+        assert_eq!(OverrideRule::RestrictedBook, OverrideRule::from(OverrideRule::RestrictedBook.to_string()));
+        assert_eq!(OverrideRule::SuspendedAccount, OverrideRule::from("SuspendedAccount".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_should_parse_document_kind() {
+        assert_eq!(DocumentKind::DriverLicense, DocumentKind::from(DocumentKind::DriverLicense.to_string()));
+        assert_eq!(DocumentKind::Other, DocumentKind::from("Selfie".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_should_parse_push_topic() {
+        assert_eq!(PushTopic::DueSoon, PushTopic::try_from(PushTopic::DueSoon.to_string()).expect("should parse topic"));
+        assert!(PushTopic::try_from("Overdue".to_string()).is_err());
+    }
 }
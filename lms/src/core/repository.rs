@@ -1,8 +1,13 @@
 use async_trait::async_trait;
 use core::option::Option;
 use std::collections::HashMap;
+use std::future::Future;
+use std::time::Duration;
 use serde::{Deserialize, Serialize};
+#[cfg(any(test, feature = "dev"))]
+use crate::core::library::LibraryError;
 use crate::core::library::{LibraryResult, PaginatedResult};
+use crate::core::tasks::TaskQueueVia;
 use crate::gateway::GatewayPublisherVia;
 
 #[async_trait]
@@ -22,19 +27,197 @@ pub trait Repository<Entity>: Sync + Send {
     // find by tenant_id
     async fn query(&self, predicate: &HashMap::<String, String>,
                    page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<Entity>>;
+
+    // get an entity with the read options of the call site, repositories without read replicas ignore the options
+    async fn get_with(&self, id: &str, _options: &QueryOptions) -> LibraryResult<Entity> {
+        self.get(id).await
+    }
+
+    // query entities with the read options of the call site
+    async fn query_with(&self, predicate: &HashMap::<String, String>,
+                        page: Option<&str>, page_size: usize, _options: &QueryOptions) -> LibraryResult<PaginatedResult<Entity>> {
+        self.query(predicate, page, page_size).await
+    }
+}
+
+// QueryOptions of a read, consistent reads are served by the table in the primary region while eventual reads may be
+// served by a read replica such as the global table in the secondary region
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QueryOptions {
+    pub consistent: bool,
+}
+
+impl QueryOptions {
+    // for reads that must observe the latest write, e.g. before a conditional update
+    pub fn consistent() -> Self {
+        Self { consistent: true }
+    }
+
+    // for listings and lookups that tolerate replication lag
+    pub fn eventual() -> Self {
+        Self { consistent: false }
+    }
+}
+
+// queries of secondary indexes are retried with doubling delays while a just-written entity is missing from them
+const READ_YOUR_WRITES_RETRIES: u32 = 3;
+const READ_YOUR_WRITES_BACKOFF: Duration = Duration::from_millis(50);
+
+// ReadConsistency of queries served by secondary indexes, which are only eventually consistent with their table
+#[derive(Debug, PartialEq, Clone, Default)]
+pub enum ReadConsistency {
+    #[default]
+    Eventual,
+    // the entity with the id was just written and is expected in the results of the query
+    ReadYourWrites(String),
+}
+
+impl ReadConsistency {
+    pub fn expecting(id: Option<&str>) -> Self {
+        match id.filter(|id| !id.is_empty()) {
+            Some(id) => ReadConsistency::ReadYourWrites(id.to_string()),
+            None => ReadConsistency::Eventual,
+        }
+    }
+
+    pub fn expected_id(&self) -> Option<&str> {
+        match self {
+            ReadConsistency::Eventual => None,
+            ReadConsistency::ReadYourWrites(id) => Some(id.as_str()),
+        }
+    }
+}
+
+// runs the query and, when an entity is expected, repeats it with bounded backoff until the entity shows up, the
+// last results are returned if the index does not catch up so that callers can fall back to a consistent read
+pub(crate) async fn query_with_consistency<T, Q, Fut>(consistency: &ReadConsistency,
+                                                      is_expected: impl Fn(&T, &str) -> bool,
+                                                      query: Q) -> LibraryResult<Vec<T>>
+    where Q: Fn() -> Fut, Fut: Future<Output=LibraryResult<Vec<T>>> {
+    let mut records = query().await?;
+    let expected_id = match consistency.expected_id() {
+        Some(id) => id,
+        None => return Ok(records),
+    };
+    let mut delay = READ_YOUR_WRITES_BACKOFF;
+    for _ in 0..READ_YOUR_WRITES_RETRIES {
+        if records.iter().any(|r| is_expected(r, expected_id)) {
+            break;
+        }
+        tokio::time::sleep(delay).await;
+        delay *= 2;
+        records = query().await?;
+    }
+    Ok(records)
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Copy)]
-pub(crate) enum RepositoryStore {
+pub enum RepositoryStore {
     DynamoDB,
     LocalDynamoDB,
+    // DynamoDB Local with the tables named <prefix>_<table> so that stores with separate prefixes such as the stores of
+    // tests share an endpoint without seeing records of each other, the prefix is not serialized with the state
+    #[cfg(any(test, feature = "dev"))]
+    #[serde(skip)]
+    PrefixedLocalDynamoDB(TablePrefix),
+}
+
+// longest prefix of table names, DynamoDB allows 255 characters for the prefix and the name of the table together
+#[cfg(any(test, feature = "dev"))]
+const MAX_TABLE_PREFIX: usize = 192;
+
+// TablePrefix is the prefix of the table names of a PrefixedLocalDynamoDB store, it is kept inline so that stores stay
+// Copy without leaking the prefixes of stores that are created for every test
+#[cfg(any(test, feature = "dev"))]
+#[derive(PartialEq, Clone, Copy)]
+pub struct TablePrefix {
+    len: usize,
+    bytes: [u8; MAX_TABLE_PREFIX],
+}
+
+#[cfg(any(test, feature = "dev"))]
+impl TablePrefix {
+    pub fn new(prefix: &str) -> LibraryResult<Self> {
+        if prefix.is_empty() || prefix.len() > MAX_TABLE_PREFIX {
+            return Err(LibraryError::validation(
+                format!("table prefix {} must have 1 to {} characters", prefix, MAX_TABLE_PREFIX).as_str(), None));
+        }
+        let mut bytes = [0; MAX_TABLE_PREFIX];
+        bytes[..prefix.len()].copy_from_slice(prefix.as_bytes());
+        Ok(Self { len: prefix.len(), bytes })
+    }
+
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+}
+
+#[cfg(any(test, feature = "dev"))]
+impl std::fmt::Debug for TablePrefix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TablePrefix").field(&self.as_str()).finish()
+    }
 }
 
 impl RepositoryStore {
-    pub fn gateway_publisher(&self) -> GatewayPublisherVia  {
+    pub(crate) fn gateway_publisher(&self) -> GatewayPublisherVia  {
         match self {
             RepositoryStore::DynamoDB => {GatewayPublisherVia::Sns},
-            RepositoryStore::LocalDynamoDB => {GatewayPublisherVia::LocalDynamoDB},
+            _ => {GatewayPublisherVia::LocalDynamoDB(*self)},
+        }
+    }
+
+    pub(crate) fn task_queue(&self) -> TaskQueueVia {
+        match self {
+            RepositoryStore::DynamoDB => {TaskQueueVia::Sqs},
+            _ => {TaskQueueVia::Memory},
         }
     }
-}
\ No newline at end of file
+
+    // name of the table in the store, repositories are given the names of their tables and indexes by factories
+    pub(crate) fn table_name(&self, name: &str) -> String {
+        match self {
+            RepositoryStore::DynamoDB | RepositoryStore::LocalDynamoDB => name.to_string(),
+            #[cfg(any(test, feature = "dev"))]
+            RepositoryStore::PrefixedLocalDynamoDB(prefix) => format!("{}_{}", prefix.as_str(), name),
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use crate::core::repository::{query_with_consistency, ReadConsistency, RepositoryStore, TablePrefix};
+
+    #[tokio::test]
+    async fn test_should_retry_query_until_expected_entity_is_found() {
+        // the index catches up with the write on the third query
+        let calls = AtomicUsize::new(0);
+        let query = || async {
+            let call = calls.fetch_add(1, Ordering::SeqCst);
+            Ok(if call < 2 { vec!["other".to_string()] } else { vec!["other".to_string(), "written".to_string()] })
+        };
+        let res = query_with_consistency(&ReadConsistency::expecting(Some("written")), |r: &String, id| r == id, query)
+            .await.expect("should query");
+        assert_eq!(2, res.len());
+        assert_eq!(3, calls.load(Ordering::SeqCst));
+
+        // eventual reads and entities that never show up do not retry forever
+        calls.store(0, Ordering::SeqCst);
+        let _ = query_with_consistency(&ReadConsistency::expecting(None), |r: &String, id| r == id, query).await;
+        assert_eq!(1, calls.load(Ordering::SeqCst));
+        calls.store(0, Ordering::SeqCst);
+        let res = query_with_consistency(&ReadConsistency::expecting(Some("missing")), |r: &String, id| r == id, query)
+            .await.expect("should query");
+        assert!(!res.contains(&"missing".to_string()));
+        assert_eq!(4, calls.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_should_prefix_table_names() {
+        assert_eq!("books", RepositoryStore::DynamoDB.table_name("books"));
+        assert_eq!("books", RepositoryStore::LocalDynamoDB.table_name("books"));
+        assert!(TablePrefix::new("").is_err());
+        assert!(TablePrefix::new("p".repeat(200).as_str()).is_err());
+        assert_eq!("run1_books_ndx", RepositoryStore::PrefixedLocalDynamoDB(TablePrefix::new("run1").expect("should build prefix")).table_name("books_ndx"));
+    }
+}
@@ -0,0 +1,169 @@
+use std::cmp::min;
+
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::events::DomainEvent;
+use crate::core::library::LibraryResult;
+
+pub mod factory;
+pub mod memory;
+pub mod sqs;
+pub mod worker;
+
+// TaskPayload defines the deferred work that can be queued, the variant is stored as task_type
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(tag = "task_type", rename_all = "snake_case")]
+pub(crate) enum TaskPayload {
+    SendNotification {
+        party_id: String,
+        subject: String,
+        message: String,
+    },
+    ProjectEvent {
+        projector: String,
+        event: DomainEvent,
+    },
+    RebuildProjection {
+        projection: String,
+        switch_over: bool,
+    },
+    ArchiveEvents {
+        older_than_days: i64,
+    },
+}
+
+impl TaskPayload {
+    pub fn task_type(&self) -> &'static str {
+        match self {
+            TaskPayload::SendNotification { .. } => "send_notification",
+            TaskPayload::ProjectEvent { .. } => "project_event",
+            TaskPayload::RebuildProjection { .. } => "rebuild_projection",
+            TaskPayload::ArchiveEvents { .. } => "archive_events",
+        }
+    }
+}
+
+// Task wraps the payload with the bookkeeping of the queue
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Task {
+    pub task_id: String,
+    pub payload: TaskPayload,
+    // number of failed attempts so far
+    pub attempts: i64,
+    pub created_at: NaiveDateTime,
+    // handle of the received message that is needed to acknowledge it
+    #[serde(skip)]
+    pub receipt: Option<String>,
+}
+
+impl Task {
+    pub fn new(payload: TaskPayload) -> Self {
+        Self {
+            task_id: Uuid::new_v4().to_string(),
+            payload,
+            attempts: 0,
+            created_at: Utc::now().naive_utc(),
+            receipt: None,
+        }
+    }
+}
+
+// RetryPolicy backs off exponentially between attempts of a failing task
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) struct RetryPolicy {
+    pub max_attempts: i64,
+    pub base_delay_secs: i64,
+    pub max_delay_secs: i64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        // SQS does not delay messages for more than 15 minutes
+        Self {
+            max_attempts: 5,
+            base_delay_secs: 30,
+            max_delay_secs: 900,
+        }
+    }
+}
+
+impl RetryPolicy {
+    // returns delay before the next attempt after given failed attempts or None when the task is exhausted
+    pub fn next_delay(&self, attempts: i64) -> Option<Duration> {
+        if attempts >= self.max_attempts {
+            return None;
+        }
+        let exponent = min(attempts.max(1) - 1, 20) as u32;
+        Some(Duration::seconds(min(self.base_delay_secs.saturating_mul(2_i64.pow(exponent)), self.max_delay_secs)))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum TaskQueueVia {
+    Sqs,
+    Memory,
+}
+
+#[async_trait]
+pub(crate) trait TaskQueue: Sync + Send {
+    // queues the task to become available after the delay
+    async fn enqueue(&self, task: &Task, delay: Duration) -> LibraryResult<()>;
+
+    // receives up to max tasks that are available, received tasks are hidden until acknowledged
+    async fn receive(&self, max: usize) -> LibraryResult<Vec<Task>>;
+
+    // removes the received task from the queue
+    async fn ack(&self, task: &Task) -> LibraryResult<()>;
+
+    // moves the received task that exhausted its retries to the dead-letter queue
+    async fn dead_letter(&self, task: &Task, reason: &str) -> LibraryResult<()>;
+}
+
+// TaskHandler runs the deferred work of tasks
+#[async_trait]
+pub(crate) trait TaskHandler: Sync + Send {
+    // returns true if handler runs the task
+    fn handles(&self, task: &Task) -> bool;
+
+    async fn handle(&self, task: &Task) -> LibraryResult<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use crate::core::tasks::{RetryPolicy, Task, TaskPayload};
+
+    #[tokio::test]
+    async fn test_should_serialize_task_with_type() {
+        let task = Task::new(TaskPayload::SendNotification {
+            party_id: "party1".to_string(),
+            subject: "subject".to_string(),
+            message: "message".to_string(),
+        });
+        let json = serde_json::to_value(&task).expect("should serialize");
+        assert_eq!("send_notification", json["payload"]["task_type"].as_str().unwrap_or_default());
+        assert_eq!("send_notification", task.payload.task_type());
+        let loaded: Task = serde_json::from_value(json).expect("should deserialize");
+        assert_eq!(task, loaded);
+
+        for payload in [TaskPayload::RebuildProjection { projection: "co_checkouts".to_string(), switch_over: true },
+                        TaskPayload::ArchiveEvents { older_than_days: 90 }] {
+            let json = serde_json::to_value(&payload).expect("should serialize");
+            assert_eq!(payload.task_type(), json["task_type"].as_str().unwrap_or_default());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_should_back_off_retries() {
+        let policy = RetryPolicy::default();
+        assert_eq!(Some(Duration::seconds(30)), policy.next_delay(1));
+        assert_eq!(Some(Duration::seconds(60)), policy.next_delay(2));
+        assert_eq!(Some(Duration::seconds(240)), policy.next_delay(4));
+        assert_eq!(None, policy.next_delay(5));
+        let policy = RetryPolicy { max_attempts: 20, base_delay_secs: 30, max_delay_secs: 900 };
+        assert_eq!(Some(Duration::seconds(900)), policy.next_delay(10));
+    }
+}
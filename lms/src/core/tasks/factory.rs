@@ -0,0 +1,44 @@
+use lazy_static::lazy_static;
+
+use crate::core::repository::RepositoryStore;
+use crate::core::tasks::memory::MemoryTaskQueue;
+use crate::core::tasks::sqs::SqsTaskQueue;
+use crate::core::tasks::worker::TaskWorker;
+use crate::core::tasks::{RetryPolicy, TaskHandler, TaskQueue, TaskQueueVia};
+use crate::gateway::factory::create_archive_events_task_handler;
+use crate::notifications::factory::create_notification_task_handler;
+use crate::projector::factory::{create_projection_task_handler, create_rebuild_projection_task_handler};
+use crate::utils::ddb::build_sqs_client;
+
+lazy_static! {
+    // services and the worker of a dev process share the in-memory queue
+    static ref MEMORY_QUEUE: MemoryTaskQueue = MemoryTaskQueue::new();
+}
+
+pub(crate) async fn create_task_queue(via: TaskQueueVia) -> Box<dyn TaskQueue> {
+    match via {
+        TaskQueueVia::Sqs => {
+            let client = build_sqs_client().await;
+            let queue_url = std::env::var("TASK_QUEUE_URL").unwrap_or_default();
+            let dead_letter_url = std::env::var("TASK_DEAD_LETTER_QUEUE_URL").unwrap_or_default();
+            Box::new(SqsTaskQueue::new(client, queue_url.as_str(), dead_letter_url.as_str()))
+        }
+        TaskQueueVia::Memory => {
+            Box::new(MEMORY_QUEUE.clone())
+        }
+    }
+}
+
+pub(crate) async fn create_task_handlers(store: RepositoryStore) -> Vec<Box<dyn TaskHandler>> {
+    vec![
+        create_notification_task_handler(store).await,
+        create_projection_task_handler(store).await,
+        create_rebuild_projection_task_handler(store).await,
+        create_archive_events_task_handler(store).await,
+    ]
+}
+
+pub async fn create_task_worker(store: RepositoryStore) -> TaskWorker {
+    TaskWorker::new(create_task_queue(store.task_queue()).await,
+                    create_task_handlers(store).await, RetryPolicy::default())
+}
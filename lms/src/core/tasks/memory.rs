@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime, Utc};
+use uuid::Uuid;
+
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::core::tasks::{Task, TaskQueue};
+
+// number of seconds a received task stays hidden before it is delivered again
+const VISIBILITY_TIMEOUT_SECS: i64 = 300;
+
+#[derive(Debug, Default)]
+struct QueueState {
+    // serialized tasks with the time they become available
+    pending: Vec<(NaiveDateTime, String)>,
+    // receipt -> (time the task is delivered again, serialized task)
+    in_flight: HashMap<String, (NaiveDateTime, String)>,
+    dead_letters: Vec<(String, String)>,
+}
+
+// MemoryTaskQueue keeps tasks in memory of the process for development and tests, clones share the same queue.
+// Tasks are stored serialized so that payloads go through the same encoding as on SQS.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MemoryTaskQueue {
+    state: Arc<Mutex<QueueState>>,
+}
+
+impl MemoryTaskQueue {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    // returns dead-lettered tasks with the reason of their last failure
+    pub(crate) fn dead_letters(&self) -> Vec<(Task, String)> {
+        let state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        state.dead_letters.iter()
+            .filter_map(|(json, reason)| serde_json::from_str(json).ok().map(|task| (task, reason.to_string())))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl TaskQueue for MemoryTaskQueue {
+    async fn enqueue(&self, task: &Task, delay: Duration) -> LibraryResult<()> {
+        let json = serde_json::to_string(task)?;
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        state.pending.push((Utc::now().naive_utc() + delay, json));
+        Ok(())
+    }
+
+    async fn receive(&self, max: usize) -> LibraryResult<Vec<Task>> {
+        let now = Utc::now().naive_utc();
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        // tasks that were not acknowledged within the visibility timeout are delivered again
+        let expired: Vec<String> = state.in_flight.iter()
+            .filter(|(_, (visible_at, _))| *visible_at <= now)
+            .map(|(receipt, _)| receipt.to_string())
+            .collect();
+        for receipt in expired {
+            if let Some((visible_at, json)) = state.in_flight.remove(receipt.as_str()) {
+                state.pending.push((visible_at, json));
+            }
+        }
+
+        let mut tasks = vec![];
+        let mut i = 0;
+        while i < state.pending.len() && tasks.len() < max {
+            if state.pending[i].0 > now {
+                i += 1;
+                continue;
+            }
+            let (_, json) = state.pending.remove(i);
+            let mut task: Task = serde_json::from_str(json.as_str())?;
+            let receipt = Uuid::new_v4().to_string();
+            task.receipt = Some(receipt.to_string());
+            state.in_flight.insert(receipt, (now + Duration::seconds(VISIBILITY_TIMEOUT_SECS), json));
+            tasks.push(task);
+        }
+        Ok(tasks)
+    }
+
+    async fn ack(&self, task: &Task) -> LibraryResult<()> {
+        let receipt = task.receipt.as_deref()
+            .ok_or_else(|| LibraryError::validation("task was not received from the queue", None))?;
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        state.in_flight.remove(receipt);
+        Ok(())
+    }
+
+    async fn dead_letter(&self, task: &Task, reason: &str) -> LibraryResult<()> {
+        let json = serde_json::to_string(task)?;
+        self.ack(task).await?;
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        state.dead_letters.push((json, reason.to_string()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use crate::core::tasks::memory::MemoryTaskQueue;
+    use crate::core::tasks::{Task, TaskPayload, TaskQueue};
+
+    fn notification(subject: &str) -> Task {
+        Task::new(TaskPayload::SendNotification {
+            party_id: "party1".to_string(),
+            subject: subject.to_string(),
+            message: "message".to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_should_enqueue_receive_and_ack() {
+        let queue = MemoryTaskQueue::new();
+        queue.enqueue(&notification("now"), Duration::zero()).await.expect("should enqueue");
+        queue.enqueue(&notification("later"), Duration::minutes(5)).await.expect("should enqueue");
+
+        let tasks = queue.receive(10).await.expect("should receive");
+        assert_eq!(1, tasks.len());
+        assert!(tasks[0].receipt.is_some());
+        // received tasks are hidden until acknowledged
+        assert_eq!(0, queue.receive(10).await.expect("should receive").len());
+        queue.ack(&tasks[0]).await.expect("should ack");
+        assert!(queue.ack(&notification("unreceived")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_dead_letter() {
+        let queue = MemoryTaskQueue::new();
+        queue.enqueue(&notification("failing"), Duration::zero()).await.expect("should enqueue");
+        let tasks = queue.receive(1).await.expect("should receive");
+        queue.dead_letter(&tasks[0], "boom").await.expect("should dead letter");
+        let dead = queue.dead_letters();
+        assert_eq!(1, dead.len());
+        assert_eq!("boom", dead[0].1.as_str());
+        assert_eq!(0, queue.receive(10).await.expect("should receive").len());
+    }
+}
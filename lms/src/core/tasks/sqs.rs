@@ -0,0 +1,107 @@
+use async_trait::async_trait;
+use aws_sdk_sqs::Client;
+use aws_sdk_sqs::error::SdkError;
+use aws_sdk_sqs::operation::delete_message::DeleteMessageError;
+use aws_sdk_sqs::operation::receive_message::ReceiveMessageError;
+use aws_sdk_sqs::operation::send_message::SendMessageError;
+use chrono::Duration;
+use tracing::log::warn;
+
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::core::tasks::{Task, TaskQueue};
+
+// SQS does not accept longer delays or batches
+const MAX_DELAY_SECS: i64 = 900;
+const MAX_BATCH: usize = 10;
+// long polling avoids empty receives while the queue is idle
+const WAIT_TIME_SECS: i32 = 20;
+
+#[derive(Debug)]
+pub(crate) struct SqsTaskQueue {
+    client: Client,
+    queue_url: String,
+    dead_letter_url: String,
+}
+
+impl SqsTaskQueue {
+    pub(crate) fn new(client: Client, queue_url: &str, dead_letter_url: &str) -> Self {
+        Self {
+            client,
+            queue_url: queue_url.to_string(),
+            dead_letter_url: dead_letter_url.to_string(),
+        }
+    }
+
+    async fn send(&self, queue_url: &str, task: &Task, delay: Duration) -> LibraryResult<()> {
+        let json = serde_json::to_string(task)?;
+        let delay = delay.num_seconds().clamp(0, MAX_DELAY_SECS) as i32;
+        self.client.send_message().queue_url(queue_url).message_body(json).delay_seconds(delay).send().await?;
+        Ok(())
+    }
+
+    async fn delete(&self, receipt: &str) -> LibraryResult<()> {
+        self.client.delete_message().queue_url(self.queue_url.as_str()).receipt_handle(receipt).send().await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TaskQueue for SqsTaskQueue {
+    async fn enqueue(&self, task: &Task, delay: Duration) -> LibraryResult<()> {
+        self.send(self.queue_url.as_str(), task, delay).await
+    }
+
+    async fn receive(&self, max: usize) -> LibraryResult<Vec<Task>> {
+        let resp = self.client.receive_message()
+            .queue_url(self.queue_url.as_str())
+            .max_number_of_messages(max.clamp(1, MAX_BATCH) as i32)
+            .wait_time_seconds(WAIT_TIME_SECS)
+            .send().await?;
+        let mut tasks = vec![];
+        for message in resp.messages().unwrap_or_default() {
+            let receipt = message.receipt_handle().unwrap_or_default();
+            match serde_json::from_str::<Task>(message.body().unwrap_or_default()) {
+                Ok(mut task) => {
+                    task.receipt = Some(receipt.to_string());
+                    tasks.push(task);
+                }
+                Err(err) => {
+                    // malformed messages would otherwise be delivered forever
+                    warn!("dropping malformed task message {:?} due to {}", message.message_id(), err);
+                    self.delete(receipt).await?;
+                }
+            }
+        }
+        Ok(tasks)
+    }
+
+    async fn ack(&self, task: &Task) -> LibraryResult<()> {
+        let receipt = task.receipt.as_deref()
+            .ok_or_else(|| LibraryError::validation("task was not received from the queue", None))?;
+        self.delete(receipt).await
+    }
+
+    async fn dead_letter(&self, task: &Task, reason: &str) -> LibraryResult<()> {
+        warn!("dead-lettering task {} of type {} due to {}", task.task_id, task.payload.task_type(), reason);
+        self.send(self.dead_letter_url.as_str(), task, Duration::zero()).await?;
+        self.ack(task).await
+    }
+}
+
+impl From<SdkError<SendMessageError>> for LibraryError {
+    fn from(err: SdkError<SendMessageError>) -> Self {
+        LibraryError::runtime(format!("{:?}", err).as_str(), None)
+    }
+}
+
+impl From<SdkError<ReceiveMessageError>> for LibraryError {
+    fn from(err: SdkError<ReceiveMessageError>) -> Self {
+        LibraryError::runtime(format!("{:?}", err).as_str(), None)
+    }
+}
+
+impl From<SdkError<DeleteMessageError>> for LibraryError {
+    fn from(err: SdkError<DeleteMessageError>) -> Self {
+        LibraryError::runtime(format!("{:?}", err).as_str(), None)
+    }
+}
@@ -0,0 +1,157 @@
+use tracing::log::warn;
+
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::core::tasks::{RetryPolicy, Task, TaskHandler, TaskQueue};
+
+// TaskWorker receives tasks from the queue and runs them with the handler of the task, failed tasks are queued
+// again with a delay of the retry policy until they are exhausted and moved to the dead-letter queue
+pub struct TaskWorker {
+    queue: Box<dyn TaskQueue>,
+    handlers: Vec<Box<dyn TaskHandler>>,
+    retry_policy: RetryPolicy,
+}
+
+impl TaskWorker {
+    pub(crate) fn new(queue: Box<dyn TaskQueue>, handlers: Vec<Box<dyn TaskHandler>>, retry_policy: RetryPolicy) -> Self {
+        Self {
+            queue,
+            handlers,
+            retry_policy,
+        }
+    }
+
+    // processes a batch of available tasks and returns the number of received tasks
+    pub async fn run_once(&self, batch_size: usize) -> LibraryResult<usize> {
+        let tasks = self.queue.receive(batch_size).await?;
+        for task in &tasks {
+            match self.handle(task).await {
+                Ok(_) => self.queue.ack(task).await?,
+                Err(err) => self.retry(task, err).await?,
+            }
+        }
+        Ok(tasks.len())
+    }
+
+    async fn handle(&self, task: &Task) -> LibraryResult<()> {
+        let handler = self.handlers.iter().find(|h| h.handles(task))
+            .ok_or_else(|| LibraryError::validation(
+                format!("no handler for task of type {}", task.payload.task_type()).as_str(), None))?;
+        handler.handle(task).await
+    }
+
+    async fn retry(&self, task: &Task, err: LibraryError) -> LibraryResult<()> {
+        let attempts = task.attempts + 1;
+        match self.retry_policy.next_delay(attempts) {
+            Some(delay) if !is_permanent(&err) => {
+                warn!("task {} of type {} failed on attempt {} due to {}, retrying in {}s",
+                    task.task_id, task.payload.task_type(), attempts, err, delay.num_seconds());
+                let retried = Task {
+                    task_id: task.task_id.to_string(),
+                    payload: task.payload.clone(),
+                    attempts,
+                    created_at: task.created_at,
+                    receipt: None,
+                };
+                self.queue.enqueue(&retried, delay).await?;
+                self.queue.ack(task).await
+            }
+            _ => {
+                self.queue.dead_letter(task, format!("{}", err).as_str()).await
+            }
+        }
+    }
+}
+
+// errors caused by the task itself fail again on retry, unlike failures of the database or other services
+fn is_permanent(err: &LibraryError) -> bool {
+    matches!(err, LibraryError::AccessDenied { .. } | LibraryError::NotGranted { .. } | LibraryError::DuplicateKey { .. } |
+        LibraryError::NotFound { .. } | LibraryError::Validation { .. } | LibraryError::Serialization { .. })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use async_trait::async_trait;
+    use chrono::Duration;
+    use crate::core::events::DomainEvent;
+    use crate::core::library::{LibraryError, LibraryResult};
+    use crate::core::tasks::memory::MemoryTaskQueue;
+    use crate::core::tasks::worker::TaskWorker;
+    use crate::core::tasks::{RetryPolicy, Task, TaskHandler, TaskPayload, TaskQueue};
+
+    // fails notifications with subject fail and counts the attempts
+    struct FlakyHandler {
+        attempts: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl TaskHandler for FlakyHandler {
+        fn handles(&self, task: &Task) -> bool {
+            matches!(task.payload, TaskPayload::SendNotification { .. })
+        }
+
+        async fn handle(&self, task: &Task) -> LibraryResult<()> {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            match &task.payload {
+                TaskPayload::SendNotification { subject, .. } if subject == "fail" => {
+                    Err(LibraryError::runtime("notification gateway is down", None))
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+
+    fn notification(subject: &str) -> Task {
+        Task::new(TaskPayload::SendNotification {
+            party_id: "party1".to_string(),
+            subject: subject.to_string(),
+            message: "message".to_string(),
+        })
+    }
+
+    fn build_worker(queue: &MemoryTaskQueue, attempts: Arc<AtomicUsize>) -> TaskWorker {
+        let policy = RetryPolicy { max_attempts: 3, base_delay_secs: 0, max_delay_secs: 0 };
+        TaskWorker::new(Box::new(queue.clone()), vec![Box::new(FlakyHandler { attempts })], policy)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_tasks() {
+        let queue = MemoryTaskQueue::new();
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let worker = build_worker(&queue, attempts.clone());
+        queue.enqueue(&notification("hello"), Duration::zero()).await.expect("should enqueue");
+        assert_eq!(1, worker.run_once(10).await.expect("should run"));
+        assert_eq!(1, attempts.load(Ordering::SeqCst));
+        assert_eq!(0, worker.run_once(10).await.expect("should run"));
+        assert!(queue.dead_letters().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_should_retry_and_dead_letter_failing_tasks() {
+        let queue = MemoryTaskQueue::new();
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let worker = build_worker(&queue, attempts.clone());
+        queue.enqueue(&notification("fail"), Duration::zero()).await.expect("should enqueue");
+        for _ in 0..3 {
+            assert_eq!(1, worker.run_once(10).await.expect("should run"));
+        }
+        assert_eq!(0, worker.run_once(10).await.expect("should run"));
+        assert_eq!(3, attempts.load(Ordering::SeqCst));
+        let dead = queue.dead_letters();
+        assert_eq!(1, dead.len());
+        assert_eq!(2, dead[0].0.attempts);
+    }
+
+    #[tokio::test]
+    async fn test_should_dead_letter_tasks_without_handler() {
+        let queue = MemoryTaskQueue::new();
+        let worker = build_worker(&queue, Arc::new(AtomicUsize::new(0)));
+        let event = DomainEvent::added("counted", "group", "key", &HashMap::new(), &HashMap::from([("a", 1)])).expect("build event");
+        queue.enqueue(&Task::new(TaskPayload::ProjectEvent { projector: "unknown".to_string(), event }), Duration::zero())
+            .await.expect("should enqueue");
+        assert_eq!(1, worker.run_once(10).await.expect("should run"));
+        assert_eq!(1, queue.dead_letters().len());
+    }
+}
@@ -0,0 +1,24 @@
+use lambda_http::Error;
+use lms::{check_tables, check_topics, routes, run_lambda, setup_tracing, AppState, RepositoryStore};
+
+const DEV_MODE: bool = true;
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    setup_tracing();
+
+    let state = if DEV_MODE {
+        std::env::set_var("AWS_LAMBDA_FUNCTION_NAME", "_");
+        std::env::set_var("AWS_LAMBDA_FUNCTION_MEMORY_SIZE", "4096"); // 200MB
+        std::env::set_var("AWS_LAMBDA_FUNCTION_VERSION", "1");
+        std::env::set_var("AWS_LAMBDA_RUNTIME_API", "http://[::]:9000/.rt");
+        AppState::new("dev", RepositoryStore::LocalDynamoDB)
+    } else {
+        check_topics(RepositoryStore::DynamoDB).await.map_err(|err| err.to_string())?;
+        check_tables(RepositoryStore::DynamoDB).await.map_err(|err| err.to_string())?;
+        AppState::new("prod", RepositoryStore::DynamoDB)
+    };
+
+    // routes added before the layer require a bearer token
+    run_lambda(routes::credentials(state)).await
+}
@@ -0,0 +1,7 @@
+pub mod change_password_cmd;
+pub mod create_api_key_cmd;
+pub mod login_cmd;
+pub mod request_password_reset_cmd;
+pub mod reset_password_cmd;
+pub mod revoke_api_key_cmd;
+pub mod rotate_api_key_cmd;
@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::credentials::domain::CredentialService;
+
+pub(crate) struct ChangePasswordCommand {
+    credential_service: Box<dyn CredentialService>,
+}
+
+impl ChangePasswordCommand {
+    pub(crate) fn new(credential_service: Box<dyn CredentialService>) -> Self {
+        Self {
+            credential_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ChangePasswordCommandRequest {
+    // set from claims of the access token
+    #[serde(default)]
+    pub party_id: String,
+    pub current_password: String,
+    pub new_password: String,
+}
+
+impl ChangePasswordCommandRequest {
+    pub fn new(party_id: &str, current_password: &str, new_password: &str) -> Self {
+        Self {
+            party_id: party_id.to_string(),
+            current_password: current_password.to_string(),
+            new_password: new_password.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ChangePasswordCommandResponse {}
+
+impl ChangePasswordCommandResponse {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl Command<ChangePasswordCommandRequest, ChangePasswordCommandResponse> for ChangePasswordCommand {
+    async fn execute(&self, req: ChangePasswordCommandRequest) -> Result<ChangePasswordCommandResponse, CommandError> {
+        self.credential_service.change_password(req.party_id.as_str(), req.current_password.as_str(), req.new_password.as_str())
+            .await.map_err(CommandError::from).map(|_| ChangePasswordCommandResponse::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::credentials::command::change_password_cmd::{ChangePasswordCommand, ChangePasswordCommandRequest};
+    use crate::credentials::factory;
+    use crate::utils::testing::test_store;
+
+    async fn sut_cmd(store: RepositoryStore) -> ChangePasswordCommand {
+        let svc = factory::create_credential_service(&Configuration::new("test"), store).await;
+        ChangePasswordCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_change_password() {
+        let store = test_store(module_path!(), "test_should_run_change_password");
+        let cmd = sut_cmd(store).await;
+
+        // parties without credentials cannot change password
+        assert!(cmd.execute(ChangePasswordCommandRequest::new("change_password_cmd_party", "password1", "password2")).await.is_err());
+    }
+}
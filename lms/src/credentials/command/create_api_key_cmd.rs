@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::credentials::domain::ApiKeyService;
+use crate::credentials::dto::ApiKeyDto;
+
+pub(crate) struct CreateApiKeyCommand {
+    api_key_service: Box<dyn ApiKeyService>,
+}
+
+impl CreateApiKeyCommand {
+    pub(crate) fn new(api_key_service: Box<dyn ApiKeyService>) -> Self {
+        Self {
+            api_key_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CreateApiKeyCommandRequest {
+    // set from claims of the authenticated admin
+    #[serde(default)]
+    pub created_by: String,
+    #[serde(default)]
+    pub party_id: String,
+    #[serde(default)]
+    pub integration_name: String,
+    #[serde(default)]
+    pub roles: Vec<String>,
+    #[serde(default)]
+    pub rate_limit_per_minute: i64,
+}
+
+impl CreateApiKeyCommandRequest {
+    pub fn new(created_by: &str, party_id: &str, integration_name: &str) -> Self {
+        Self {
+            created_by: created_by.to_string(),
+            party_id: party_id.to_string(),
+            integration_name: integration_name.to_string(),
+            roles: vec![],
+            rate_limit_per_minute: 0,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CreateApiKeyCommandResponse {
+    pub api_key: ApiKeyDto,
+}
+
+impl CreateApiKeyCommandResponse {
+    pub fn new(api_key: ApiKeyDto) -> Self {
+        Self {
+            api_key,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<CreateApiKeyCommandRequest, CreateApiKeyCommandResponse> for CreateApiKeyCommand {
+    async fn execute(&self, req: CreateApiKeyCommandRequest) -> Result<CreateApiKeyCommandResponse, CommandError> {
+        self.api_key_service.create_api_key(req.created_by.as_str(), req.party_id.as_str(), req.integration_name.as_str(),
+                                            &req.roles, req.rate_limit_per_minute)
+            .await.map_err(CommandError::from).map(CreateApiKeyCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::credentials::command::create_api_key_cmd::{CreateApiKeyCommand, CreateApiKeyCommandRequest};
+    use crate::credentials::factory;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn sut_cmd(store: RepositoryStore) -> CreateApiKeyCommand {
+        let svc = factory::create_api_key_service(&Configuration::new("test"), store).await;
+        CreateApiKeyCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_create_api_key() {
+        let store = test_store(module_path!(), "test_should_run_create_api_key");
+        let cmd = sut_cmd(store).await;
+        let mut admin = PartyEntity::new(PartyKind::Employee, "create_api_key_cmd@example.com");
+        admin.group_roles = vec![Role::Admin.to_string()];
+        let _ = create_party_repository(store).await.create(&admin).await.expect("should create party");
+
+        let res = cmd.execute(CreateApiKeyCommandRequest::new(admin.party_id.as_str(), "", "self-checkout"))
+            .await.expect("should create api key");
+        assert_eq!("self-checkout", res.api_key.integration_name.as_str());
+        assert!(res.api_key.key.is_some());
+    }
+}
@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::credentials::domain::CredentialService;
+use crate::credentials::dto::TokenDto;
+
+pub(crate) struct LoginCommand {
+    credential_service: Box<dyn CredentialService>,
+}
+
+impl LoginCommand {
+    pub(crate) fn new(credential_service: Box<dyn CredentialService>) -> Self {
+        Self {
+            credential_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct LoginCommandRequest {
+    pub email: String,
+    pub password: String,
+}
+
+impl LoginCommandRequest {
+    pub fn new(email: &str, password: &str) -> Self {
+        Self {
+            email: email.to_string(),
+            password: password.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct LoginCommandResponse {
+    pub token: TokenDto,
+}
+
+impl LoginCommandResponse {
+    pub fn new(token: TokenDto) -> Self {
+        Self {
+            token,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<LoginCommandRequest, LoginCommandResponse> for LoginCommand {
+    async fn execute(&self, req: LoginCommandRequest) -> Result<LoginCommandResponse, CommandError> {
+        self.credential_service.login(req.email.as_str(), req.password.as_str())
+            .await.map_err(CommandError::from).map(LoginCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::credentials::command::login_cmd::{LoginCommand, LoginCommandRequest};
+    use crate::credentials::factory;
+    use crate::utils::testing::test_store;
+
+    async fn sut_cmd(store: RepositoryStore) -> LoginCommand {
+        let svc = factory::create_credential_service(&Configuration::new("test"), store).await;
+        LoginCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_login() {
+        let store = test_store(module_path!(), "test_should_run_login");
+        let cmd = sut_cmd(store).await;
+
+        assert!(cmd.execute(LoginCommandRequest::new("login_cmd_unknown@example.com", "password1")).await.is_err());
+    }
+}
@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::credentials::domain::CredentialService;
+
+pub(crate) struct RequestPasswordResetCommand {
+    credential_service: Box<dyn CredentialService>,
+}
+
+impl RequestPasswordResetCommand {
+    pub(crate) fn new(credential_service: Box<dyn CredentialService>) -> Self {
+        Self {
+            credential_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RequestPasswordResetCommandRequest {
+    pub email: String,
+}
+
+impl RequestPasswordResetCommandRequest {
+    pub fn new(email: &str) -> Self {
+        Self {
+            email: email.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RequestPasswordResetCommandResponse {}
+
+impl RequestPasswordResetCommandResponse {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl Command<RequestPasswordResetCommandRequest, RequestPasswordResetCommandResponse> for RequestPasswordResetCommand {
+    async fn execute(&self, req: RequestPasswordResetCommandRequest) -> Result<RequestPasswordResetCommandResponse, CommandError> {
+        self.credential_service.request_password_reset(req.email.as_str())
+            .await.map_err(CommandError::from).map(|_| RequestPasswordResetCommandResponse::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::PartyKind;
+    use crate::core::repository::RepositoryStore;
+    use crate::credentials::command::request_password_reset_cmd::{RequestPasswordResetCommand, RequestPasswordResetCommandRequest};
+    use crate::credentials::factory;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn sut_cmd(store: RepositoryStore) -> RequestPasswordResetCommand {
+        let svc = factory::create_credential_service(&Configuration::new("test"), store).await;
+        RequestPasswordResetCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_request_password_reset() {
+        let store = test_store(module_path!(), "test_should_run_request_password_reset");
+        let cmd = sut_cmd(store).await;
+        let patron = PartyEntity::new(PartyKind::Patron, "request_reset_cmd@example.com");
+        let _ = create_party_repository(store).await.create(&patron).await.expect("should create party");
+
+        let _ = cmd.execute(RequestPasswordResetCommandRequest::new(patron.email.as_str())).await.expect("should request reset");
+        // requesting again replaces the earlier token
+        let _ = cmd.execute(RequestPasswordResetCommandRequest::new(patron.email.as_str())).await.expect("should request reset");
+    }
+}
@@ -0,0 +1,72 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::credentials::domain::CredentialService;
+
+pub(crate) struct ResetPasswordCommand {
+    credential_service: Box<dyn CredentialService>,
+}
+
+impl ResetPasswordCommand {
+    pub(crate) fn new(credential_service: Box<dyn CredentialService>) -> Self {
+        Self {
+            credential_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ResetPasswordCommandRequest {
+    pub token: String,
+    pub new_password: String,
+}
+
+impl ResetPasswordCommandRequest {
+    pub fn new(token: &str, new_password: &str) -> Self {
+        Self {
+            token: token.to_string(),
+            new_password: new_password.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ResetPasswordCommandResponse {}
+
+impl ResetPasswordCommandResponse {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl Command<ResetPasswordCommandRequest, ResetPasswordCommandResponse> for ResetPasswordCommand {
+    async fn execute(&self, req: ResetPasswordCommandRequest) -> Result<ResetPasswordCommandResponse, CommandError> {
+        self.credential_service.reset_password(req.token.as_str(), req.new_password.as_str())
+            .await.map_err(CommandError::from).map(|_| ResetPasswordCommandResponse::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::credentials::command::reset_password_cmd::{ResetPasswordCommand, ResetPasswordCommandRequest};
+    use crate::credentials::factory;
+    use crate::utils::testing::test_store;
+
+    async fn sut_cmd(store: RepositoryStore) -> ResetPasswordCommand {
+        let svc = factory::create_credential_service(&Configuration::new("test"), store).await;
+        ResetPasswordCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_reset_password() {
+        let store = test_store(module_path!(), "test_should_run_reset_password");
+        let cmd = sut_cmd(store).await;
+
+        assert!(cmd.execute(ResetPasswordCommandRequest::new("reset_cmd_party.token", "password1")).await.is_err());
+    }
+}
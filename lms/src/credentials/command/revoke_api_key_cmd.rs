@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::credentials::domain::ApiKeyService;
+use crate::credentials::dto::ApiKeyDto;
+
+pub(crate) struct RevokeApiKeyCommand {
+    api_key_service: Box<dyn ApiKeyService>,
+}
+
+impl RevokeApiKeyCommand {
+    pub(crate) fn new(api_key_service: Box<dyn ApiKeyService>) -> Self {
+        Self {
+            api_key_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RevokeApiKeyCommandRequest {
+    #[serde(default)]
+    pub key_id: String,
+    // set from claims of the authenticated admin
+    #[serde(default)]
+    pub revoked_by: String,
+}
+
+impl RevokeApiKeyCommandRequest {
+    pub fn new(key_id: &str, revoked_by: &str) -> Self {
+        Self {
+            key_id: key_id.to_string(),
+            revoked_by: revoked_by.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RevokeApiKeyCommandResponse {
+    pub api_key: ApiKeyDto,
+}
+
+impl RevokeApiKeyCommandResponse {
+    pub fn new(api_key: ApiKeyDto) -> Self {
+        Self {
+            api_key,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<RevokeApiKeyCommandRequest, RevokeApiKeyCommandResponse> for RevokeApiKeyCommand {
+    async fn execute(&self, req: RevokeApiKeyCommandRequest) -> Result<RevokeApiKeyCommandResponse, CommandError> {
+        self.api_key_service.revoke_api_key(req.key_id.as_str(), req.revoked_by.as_str())
+            .await.map_err(CommandError::from).map(RevokeApiKeyCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{ApiKeyStatus, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::credentials::command::revoke_api_key_cmd::{RevokeApiKeyCommand, RevokeApiKeyCommandRequest};
+    use crate::credentials::domain::ApiKeyService;
+    use crate::credentials::factory;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn ApiKeyService> {
+        factory::create_api_key_service(&Configuration::new("test"), store).await
+    }
+
+    async fn sut_cmd(store: RepositoryStore) -> RevokeApiKeyCommand {
+        let svc = factory::create_api_key_service(&Configuration::new("test"), store).await;
+        RevokeApiKeyCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_revoke_api_key() {
+        let store = test_store(module_path!(), "test_should_run_revoke_api_key");
+        let cmd = sut_cmd(store).await;
+        let mut admin = PartyEntity::new(PartyKind::Employee, "revoke_api_key_cmd@example.com");
+        admin.group_roles = vec![Role::Admin.to_string()];
+        let _ = create_party_repository(store).await.create(&admin).await.expect("should create party");
+        let created = build_svc(store).await.create_api_key(admin.party_id.as_str(), "", "revoke-integration", &[], 0)
+            .await.expect("should create api key");
+
+        let res = cmd.execute(RevokeApiKeyCommandRequest::new(created.key_id.as_str(), admin.party_id.as_str()))
+            .await.expect("should revoke api key");
+        assert_eq!(ApiKeyStatus::Revoked, res.api_key.key_status);
+    }
+}
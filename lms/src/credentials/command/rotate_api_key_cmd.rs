@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::credentials::domain::ApiKeyService;
+use crate::credentials::dto::ApiKeyDto;
+
+pub(crate) struct RotateApiKeyCommand {
+    api_key_service: Box<dyn ApiKeyService>,
+}
+
+impl RotateApiKeyCommand {
+    pub(crate) fn new(api_key_service: Box<dyn ApiKeyService>) -> Self {
+        Self {
+            api_key_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RotateApiKeyCommandRequest {
+    #[serde(default)]
+    pub key_id: String,
+    // set from claims of the authenticated admin
+    #[serde(default)]
+    pub rotated_by: String,
+}
+
+impl RotateApiKeyCommandRequest {
+    pub fn new(key_id: &str, rotated_by: &str) -> Self {
+        Self {
+            key_id: key_id.to_string(),
+            rotated_by: rotated_by.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RotateApiKeyCommandResponse {
+    pub api_key: ApiKeyDto,
+}
+
+impl RotateApiKeyCommandResponse {
+    pub fn new(api_key: ApiKeyDto) -> Self {
+        Self {
+            api_key,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<RotateApiKeyCommandRequest, RotateApiKeyCommandResponse> for RotateApiKeyCommand {
+    async fn execute(&self, req: RotateApiKeyCommandRequest) -> Result<RotateApiKeyCommandResponse, CommandError> {
+        self.api_key_service.rotate_api_key(req.key_id.as_str(), req.rotated_by.as_str())
+            .await.map_err(CommandError::from).map(RotateApiKeyCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{ApiKeyStatus, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::credentials::command::rotate_api_key_cmd::{RotateApiKeyCommand, RotateApiKeyCommandRequest};
+    use crate::credentials::domain::ApiKeyService;
+    use crate::credentials::factory;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn ApiKeyService> {
+        factory::create_api_key_service(&Configuration::new("test"), store).await
+    }
+
+    async fn sut_cmd(store: RepositoryStore) -> RotateApiKeyCommand {
+        let svc = factory::create_api_key_service(&Configuration::new("test"), store).await;
+        RotateApiKeyCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_rotate_api_key() {
+        let store = test_store(module_path!(), "test_should_run_rotate_api_key");
+        let cmd = sut_cmd(store).await;
+        let mut admin = PartyEntity::new(PartyKind::Employee, "rotate_api_key_cmd@example.com");
+        admin.group_roles = vec![Role::Admin.to_string()];
+        let _ = create_party_repository(store).await.create(&admin).await.expect("should create party");
+        let created = build_svc(store).await.create_api_key(admin.party_id.as_str(), "", "rotate-integration", &[], 0)
+            .await.expect("should create api key");
+
+        let res = cmd.execute(RotateApiKeyCommandRequest::new(created.key_id.as_str(), admin.party_id.as_str()))
+            .await.expect("should rotate api key");
+        assert_eq!(ApiKeyStatus::Active, res.api_key.key_status);
+        assert_ne!(created.key, res.api_key.key);
+    }
+}
@@ -0,0 +1,167 @@
+use axum::{
+    extract::{Extension, Path, State},
+    http::{header::AUTHORIZATION, Request, StatusCode},
+    middleware::{self, Next},
+    response::{Json, Response},
+    routing::{delete, post, put},
+    Router,
+};
+use chrono::Utc;
+use lazy_static::lazy_static;
+use serde_json::{Value};
+use crate::core::context::RequestContext;
+use crate::core::controller::{AppState, command_bus, json_to_server_error, rate_limit_headers, with_common_layers, ServerError};
+use crate::credentials::command::change_password_cmd::{ChangePasswordCommand, ChangePasswordCommandRequest, ChangePasswordCommandResponse};
+use crate::credentials::command::create_api_key_cmd::{CreateApiKeyCommand, CreateApiKeyCommandRequest, CreateApiKeyCommandResponse};
+use crate::credentials::command::login_cmd::{LoginCommand, LoginCommandRequest, LoginCommandResponse};
+use crate::credentials::command::request_password_reset_cmd::{RequestPasswordResetCommand, RequestPasswordResetCommandRequest, RequestPasswordResetCommandResponse};
+use crate::credentials::command::reset_password_cmd::{ResetPasswordCommand, ResetPasswordCommandRequest, ResetPasswordCommandResponse};
+use crate::credentials::command::revoke_api_key_cmd::{RevokeApiKeyCommand, RevokeApiKeyCommandRequest, RevokeApiKeyCommandResponse};
+use crate::credentials::command::rotate_api_key_cmd::{RotateApiKeyCommand, RotateApiKeyCommandRequest, RotateApiKeyCommandResponse};
+use crate::credentials::domain::{ApiKeyService, CredentialService};
+use crate::credentials::domain::rate_limiter::RateLimiter;
+use crate::credentials::domain::service::decode_token;
+use crate::credentials::dto::ClaimsDto;
+use crate::credentials::factory;
+use crate::utils::ddb::{build_db_client, create_key_table, create_table};
+
+async fn build_service(state: AppState) -> Box<dyn CredentialService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_key_table(&client, state.store.table_name("credentials").as_str(), "party_id").await;
+    let _ = create_table(&client, state.store.table_name("parties").as_str(), "party_id", "kind", "normalized_email").await;
+    let _ = create_table(&client, state.store.table_name("notifications").as_str(), "notification_id", "party_id", "created_at").await;
+    factory::create_credential_service(&state.config, state.store).await
+}
+
+async fn build_api_key_service(state: AppState) -> Box<dyn ApiKeyService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_key_table(&client, state.store.table_name("api_keys").as_str(), "key_id").await;
+    let _ = create_table(&client, state.store.table_name("parties").as_str(), "party_id", "kind", "normalized_email").await;
+    let _ = create_table(&client, state.store.table_name("audit_log").as_str(), "audit_id", "audit_type", "created_at").await;
+    factory::create_api_key_service(&state.config, state.store).await
+}
+
+lazy_static! {
+    static ref RATE_LIMITER: RateLimiter = RateLimiter::new();
+}
+
+// authenticate validates the X-Api-Key header or the bearer token, applies the rate limit of the principal
+// and adds its claims and the authenticated request context to the request for the handlers
+pub(crate) async fn authenticate<B>(
+    State(state): State<AppState>,
+    mut req: Request<B>,
+    next: Next<B>) -> Result<Response, ServerError> {
+    let api_key = req.headers().get("x-api-key")
+        .and_then(|header| header.to_str().ok())
+        .map(|header| header.to_string());
+    let claims = if let Some(api_key) = api_key {
+        build_api_key_service(state.clone()).await.validate_api_key(api_key.as_str()).await
+            .map_err(|err| (StatusCode::UNAUTHORIZED, format!("{:?}", err)))?
+    } else {
+        let token = req.headers().get(AUTHORIZATION)
+            .and_then(|header| header.to_str().ok())
+            .and_then(|header| header.strip_prefix("Bearer "))
+            .ok_or_else(|| ServerError::new(StatusCode::UNAUTHORIZED, "missing bearer token or api key"))?;
+        decode_token(state.config.jwt_secret.as_str(), token)
+            .map_err(|err| (StatusCode::UNAUTHORIZED, format!("{:?}", err)))?
+    };
+    let limit = if claims.rate_limit_per_minute > 0 { claims.rate_limit_per_minute } else { state.config.rate_limit_per_minute };
+    let budget = RATE_LIMITER.consume(claims.principal().as_str(), limit, Utc::now().timestamp());
+    let budget_headers = rate_limit_headers(budget.limit, budget.remaining, budget.reset_seconds);
+    if !budget.allowed {
+        return Err(ServerError::new(StatusCode::TOO_MANY_REQUESTS,
+                                    format!("{} exceeded rate limit of {} requests per minute", claims.principal(), limit).as_str())
+            .with_retry_after(budget.reset_seconds)
+            .with_headers(budget_headers));
+    }
+    let ctx = RequestContext::current()
+        .unwrap_or_else(|| RequestContext::from_headers(state.config.branch_id.as_str(), req.headers()))
+        .authenticated(claims.sub.as_str(), &claims.roles, claims.branch_id.as_str(), claims.api_key_id.as_str());
+    req.extensions_mut().insert(ctx.clone());
+    req.extensions_mut().insert(claims);
+    let mut res = ctx.scope(next.run(req)).await;
+    res.headers_mut().extend(budget_headers);
+    Ok(res)
+}
+
+pub(crate) async fn login(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<Json<LoginCommandResponse>, ServerError> {
+    let req: LoginCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res = command_bus().register(LoginCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn change_password(
+    State(state): State<AppState>,
+    Extension(claims): Extension<ClaimsDto>,
+    json: Json<Value>) -> Result<Json<ChangePasswordCommandResponse>, ServerError> {
+    let mut req: ChangePasswordCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.party_id = claims.sub;
+    let svc = build_service(state).await;
+    let res = command_bus().register(ChangePasswordCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn request_password_reset(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<Json<RequestPasswordResetCommandResponse>, ServerError> {
+    let req: RequestPasswordResetCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res = command_bus().register(RequestPasswordResetCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn reset_password(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<Json<ResetPasswordCommandResponse>, ServerError> {
+    let req: ResetPasswordCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res = command_bus().register(ResetPasswordCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn create_api_key(
+    State(state): State<AppState>,
+    Extension(claims): Extension<ClaimsDto>,
+    json: Json<Value>) -> Result<Json<CreateApiKeyCommandResponse>, ServerError> {
+    let mut req: CreateApiKeyCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.created_by = claims.sub;
+    let svc = build_api_key_service(state).await;
+    let res = command_bus().register(CreateApiKeyCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn rotate_api_key(
+    State(state): State<AppState>,
+    Extension(claims): Extension<ClaimsDto>,
+    Path(key_id): Path<String>) -> Result<Json<RotateApiKeyCommandResponse>, ServerError> {
+    let req = RotateApiKeyCommandRequest::new(key_id.as_str(), claims.sub.as_str());
+    let svc = build_api_key_service(state).await;
+    let res = command_bus().register(RotateApiKeyCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn revoke_api_key(
+    State(state): State<AppState>,
+    Extension(claims): Extension<ClaimsDto>,
+    Path(key_id): Path<String>) -> Result<Json<RevokeApiKeyCommandResponse>, ServerError> {
+    let req = RevokeApiKeyCommandRequest::new(key_id.as_str(), claims.sub.as_str());
+    let svc = build_api_key_service(state).await;
+    let res = command_bus().register(RevokeApiKeyCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/auth/password", put(change_password))
+        .route("/auth/api-keys", post(create_api_key))
+        .route("/auth/api-keys/:id/rotate", post(rotate_api_key))
+        .route("/auth/api-keys/:id", delete(revoke_api_key))
+        .route_layer(middleware::from_fn_with_state(state.clone(), authenticate))
+        .route("/auth/login", post(login))
+        .route("/auth/password-reset", post(request_password_reset))
+        .route("/auth/password-reset/confirm", post(reset_password));
+    with_common_layers(router, state)
+}
@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use crate::core::library::LibraryResult;
+use crate::credentials::dto::{ApiKeyDto, ClaimsDto, TokenDto};
+
+pub mod api_keys;
+pub mod model;
+pub mod rate_limiter;
+pub mod service;
+
+#[async_trait]
+pub(crate) trait CredentialService: Sync + Send {
+    // issues access token with roles and branch claims for a valid email and password
+    async fn login(&self, email: &str, password: &str) -> LibraryResult<TokenDto>;
+    async fn change_password(&self, party_id: &str, current_password: &str, new_password: &str) -> LibraryResult<()>;
+    // emails a single-use reset token, unknown emails are ignored so that accounts cannot be discovered
+    async fn request_password_reset(&self, email: &str) -> LibraryResult<()>;
+    // sets the password with the emailed token, which is also used to set the initial password
+    async fn reset_password(&self, token: &str, new_password: &str) -> LibraryResult<()>;
+}
+
+#[async_trait]
+pub(crate) trait ApiKeyService: Sync + Send {
+    // only admins can issue keys for a party or an integration, the plain key is only returned once
+    async fn create_api_key(&self, created_by: &str, party_id: &str, integration_name: &str,
+                            roles: &[String], rate_limit_per_minute: i64) -> LibraryResult<ApiKeyDto>;
+    // replaces the secret of the key, the previous key stops working immediately
+    async fn rotate_api_key(&self, key_id: &str, rotated_by: &str) -> LibraryResult<ApiKeyDto>;
+    async fn revoke_api_key(&self, key_id: &str, revoked_by: &str) -> LibraryResult<ApiKeyDto>;
+    // returns claims of an active key that are accepted by the auth middleware like JWT claims
+    async fn validate_api_key(&self, key: &str) -> LibraryResult<ClaimsDto>;
+}
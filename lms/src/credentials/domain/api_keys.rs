@@ -0,0 +1,255 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+
+use crate::audit::domain::AuditService;
+use crate::core::domain::Configuration;
+use crate::core::ids::PatronId;
+use crate::core::library::{AccountStatus, ApiKeyStatus, LibraryError, LibraryResult, Role};
+use crate::credentials::domain::ApiKeyService;
+use crate::credentials::domain::model::ApiKeyEntity;
+use crate::credentials::domain::service::digest_token;
+use crate::credentials::dto::{ApiKeyDto, ClaimsDto};
+use crate::credentials::repository::ApiKeyRepository;
+use crate::patrons::domain::PatronService;
+use crate::patrons::Patron;
+
+const API_KEY_PREFIX: &str = "lms_";
+
+pub(crate) struct ApiKeyServiceImpl {
+    branch_id: String,
+    api_key_repository: Box<dyn ApiKeyRepository>,
+    patron_service: Box<dyn PatronService>,
+    audit_service: Box<dyn AuditService>,
+}
+
+impl ApiKeyServiceImpl {
+    pub(crate) fn new(config: &Configuration, api_key_repository: Box<dyn ApiKeyRepository>,
+                      patron_service: Box<dyn PatronService>,
+                      audit_service: Box<dyn AuditService>) -> Self {
+        Self {
+            branch_id: config.branch_id.to_string(),
+            api_key_repository,
+            patron_service,
+            audit_service,
+        }
+    }
+
+    async fn check_admin(&self, id: &str) -> LibraryResult<()> {
+        let admin = self.patron_service.find_patron_by_id(&PatronId::new(id)).await?;
+        if !admin.is_admin() {
+            return Err(LibraryError::not_granted(format!("{} cannot manage api keys", id).as_str(), Some("403".to_string())));
+        }
+        Ok(())
+    }
+
+    // returns key of the form lms_{key-id}.{secret} so that the key can be found without scanning hashes
+    fn generate_key(key_id: &str) -> String {
+        let secret: String = rand::thread_rng().sample_iter(&Alphanumeric).take(40).map(char::from).collect();
+        format!("{}{}.{}", API_KEY_PREFIX, key_id, secret)
+    }
+}
+
+#[async_trait]
+impl ApiKeyService for ApiKeyServiceImpl {
+    async fn create_api_key(&self, created_by: &str, party_id: &str, integration_name: &str,
+                            roles: &[String], rate_limit_per_minute: i64) -> LibraryResult<ApiKeyDto> {
+        self.check_admin(created_by).await?;
+        if party_id.is_empty() == integration_name.trim().is_empty() {
+            return Err(LibraryError::validation("api key must be bound to either a party or an integration name",
+                                                Some("400".to_string())));
+        }
+        if rate_limit_per_minute < 0 {
+            return Err(LibraryError::validation("rate limit cannot be negative", Some("400".to_string())));
+        }
+        let mut api_key = ApiKeyEntity::new(party_id, integration_name.trim(), created_by);
+        if party_id.is_empty() {
+            api_key.roles = roles.iter().map(|r| Role::from(r.to_string()).to_string()).collect();
+        } else {
+            let _ = self.patron_service.find_patron_by_id(&PatronId::new(party_id)).await?;
+        }
+        api_key.rate_limit_per_minute = rate_limit_per_minute;
+        let key = Self::generate_key(api_key.key_id.as_str());
+        api_key.key_hash = digest_token(key.as_str());
+        let _ = self.api_key_repository.create(&api_key).await?;
+        let _ = self.audit_service.record_action("api_key_created", created_by, api_key.key_id.as_str(),
+                                                 describe(&api_key).as_str()).await?;
+        let mut dto = ApiKeyDto::from(&api_key);
+        dto.key = Some(key);
+        Ok(dto)
+    }
+
+    async fn rotate_api_key(&self, key_id: &str, rotated_by: &str) -> LibraryResult<ApiKeyDto> {
+        self.check_admin(rotated_by).await?;
+        let mut api_key = self.api_key_repository.get(key_id).await?;
+        if api_key.key_status != ApiKeyStatus::Active {
+            return Err(LibraryError::validation(format!("api key {} is {}", key_id, api_key.key_status).as_str(),
+                                                Some("400".to_string())));
+        }
+        let key = Self::generate_key(key_id);
+        api_key.key_hash = digest_token(key.as_str());
+        api_key.rotated_at = Some(Utc::now().naive_utc());
+        let _ = self.api_key_repository.update(&api_key).await?;
+        let _ = self.audit_service.record_action("api_key_rotated", rotated_by, key_id,
+                                                 describe(&api_key).as_str()).await?;
+        let mut dto = ApiKeyDto::from(&self.api_key_repository.get(key_id).await?);
+        dto.key = Some(key);
+        Ok(dto)
+    }
+
+    async fn revoke_api_key(&self, key_id: &str, revoked_by: &str) -> LibraryResult<ApiKeyDto> {
+        self.check_admin(revoked_by).await?;
+        let mut api_key = self.api_key_repository.get(key_id).await?;
+        if api_key.key_status != ApiKeyStatus::Revoked {
+            api_key.key_status = ApiKeyStatus::Revoked;
+            api_key.revoked_at = Some(Utc::now().naive_utc());
+            let _ = self.api_key_repository.update(&api_key).await?;
+            let _ = self.audit_service.record_action("api_key_revoked", revoked_by, key_id,
+                                                     describe(&api_key).as_str()).await?;
+        }
+        self.api_key_repository.get(key_id).await.map(|k| ApiKeyDto::from(&k))
+    }
+
+    async fn validate_api_key(&self, key: &str) -> LibraryResult<ClaimsDto> {
+        let invalid = || LibraryError::not_granted("invalid api key", Some("401".to_string()));
+        let key_id = key.strip_prefix(API_KEY_PREFIX)
+            .and_then(|rest| rest.split_once('.'))
+            .map(|(key_id, _)| key_id)
+            .ok_or_else(invalid)?;
+        let api_key = match self.api_key_repository.get(key_id).await {
+            Ok(api_key) => api_key,
+            Err(LibraryError::NotFound { .. }) => return Err(invalid()),
+            Err(err) => return Err(err),
+        };
+        if api_key.key_status != ApiKeyStatus::Active || api_key.key_hash != digest_token(key) {
+            return Err(invalid());
+        }
+        let now = Utc::now().timestamp();
+        let mut claims = ClaimsDto {
+            sub: format!("integration:{}", api_key.integration_name),
+            email: "".to_string(),
+            roles: api_key.roles.clone(),
+            branch_id: self.branch_id.to_string(),
+            iat: now,
+            exp: now,
+            api_key_id: api_key.key_id.to_string(),
+            rate_limit_per_minute: api_key.rate_limit_per_minute,
+        };
+        // keys of parties act with the current roles of the party
+        if !api_key.party_id.is_empty() {
+            let party = self.patron_service.find_patron_by_id(&PatronId::new(api_key.party_id.as_str())).await?;
+            if matches!(party.account_status, AccountStatus::Pending | AccountStatus::Banned | AccountStatus::Deleted) {
+                return Err(invalid());
+            }
+            claims.sub = party.patron_id.to_string();
+            claims.email = party.email.to_string();
+            claims.roles = party.group_roles.iter().map(|r| r.to_string()).collect();
+        }
+        Ok(claims)
+    }
+}
+
+fn describe(api_key: &ApiKeyEntity) -> String {
+    if api_key.party_id.is_empty() {
+        format!("integration {}", api_key.integration_name)
+    } else {
+        format!("party {}", api_key.party_id)
+    }
+}
+
+impl From<&ApiKeyEntity> for ApiKeyDto {
+    fn from(other: &ApiKeyEntity) -> Self {
+        Self {
+            key_id: other.key_id.to_string(),
+            version: other.version,
+            party_id: other.party_id.to_string(),
+            integration_name: other.integration_name.to_string(),
+            roles: other.roles.clone(),
+            rate_limit_per_minute: other.rate_limit_per_minute,
+            key_status: other.key_status,
+            created_by: other.created_by.to_string(),
+            rotated_at: other.rotated_at,
+            revoked_at: other.revoked_at,
+            key: None,
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::core::domain::Configuration;
+    use crate::core::library::{ApiKeyStatus, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::credentials::domain::ApiKeyService;
+    use crate::credentials::factory;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn ApiKeyService> {
+        factory::create_api_key_service(&Configuration::new("test"), store).await
+    }
+
+    async fn add_party(store: RepositoryStore, email: &str, role: Role) -> PartyEntity {
+        let mut party = PartyEntity::new(PartyKind::Employee, email);
+        party.group_roles = vec![role.to_string()];
+        let _ = create_party_repository(store).await.create(&party).await.expect("should create party");
+        party
+    }
+
+    #[tokio::test]
+    async fn test_should_create_rotate_revoke_integration_key() {
+        let store = test_store(module_path!(), "test_should_create_rotate_revoke_integration_key");
+        let api_key_svc = sut_svc(store).await;
+        let admin = add_party(store, "api_key_admin@example.com", Role::Admin).await;
+        let librarian = add_party(store, "api_key_librarian@example.com", Role::Librarian).await;
+        let roles = vec![Role::Librarian.to_string()];
+
+        // only admins can issue keys
+        assert!(api_key_svc.create_api_key(librarian.party_id.as_str(), "", "discovery", &roles, 0).await.is_err());
+        // key must be bound to either party or integration
+        assert!(api_key_svc.create_api_key(admin.party_id.as_str(), "", "", &roles, 0).await.is_err());
+        assert!(api_key_svc.create_api_key(admin.party_id.as_str(), librarian.party_id.as_str(), "discovery", &roles, 0).await.is_err());
+
+        let created = api_key_svc.create_api_key(admin.party_id.as_str(), "", "discovery", &roles, 100)
+            .await.expect("should create key");
+        let key = created.key.clone().expect("should return key");
+        let claims = api_key_svc.validate_api_key(key.as_str()).await.expect("should validate key");
+        assert_eq!("integration:discovery", claims.sub.as_str());
+        assert_eq!(roles, claims.roles);
+        assert_eq!(100, claims.rate_limit_per_minute);
+        assert_eq!(format!("api_key:{}", created.key_id), claims.principal());
+        assert!(api_key_svc.validate_api_key(format!("{}x", key).as_str()).await.is_err());
+
+        let rotated = api_key_svc.rotate_api_key(created.key_id.as_str(), admin.party_id.as_str()).await.expect("should rotate key");
+        let new_key = rotated.key.clone().expect("should return key");
+        assert!(rotated.rotated_at.is_some());
+        assert!(api_key_svc.validate_api_key(key.as_str()).await.is_err());
+        let _ = api_key_svc.validate_api_key(new_key.as_str()).await.expect("should validate rotated key");
+
+        let revoked = api_key_svc.revoke_api_key(created.key_id.as_str(), admin.party_id.as_str()).await.expect("should revoke key");
+        assert_eq!(ApiKeyStatus::Revoked, revoked.key_status);
+        assert_eq!(None, revoked.key);
+        assert!(api_key_svc.validate_api_key(new_key.as_str()).await.is_err());
+        assert!(api_key_svc.rotate_api_key(created.key_id.as_str(), admin.party_id.as_str()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_use_roles_of_party_key() {
+        let store = test_store(module_path!(), "test_should_use_roles_of_party_key");
+        let api_key_svc = sut_svc(store).await;
+        let admin = add_party(store, "api_key_party_admin@example.com", Role::Admin).await;
+        let librarian = add_party(store, "api_key_party_librarian@example.com", Role::Librarian).await;
+
+        let created = api_key_svc.create_api_key(admin.party_id.as_str(), librarian.party_id.as_str(), "",
+                                                 &[Role::Admin.to_string()], 0).await.expect("should create key");
+        let claims = api_key_svc.validate_api_key(created.key.unwrap().as_str()).await.expect("should validate key");
+        assert_eq!(librarian.party_id, claims.sub);
+        assert_eq!(vec![Role::Librarian.to_string()], claims.roles);
+        assert!(api_key_svc.validate_api_key("lms_unknown.secret").await.is_err());
+    }
+}
@@ -0,0 +1,96 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::core::domain::Identifiable;
+use crate::core::library::ApiKeyStatus;
+use crate::utils::date::serializer;
+
+// CredentialEntity stores salted argon2 password hash of a party, the password is empty until it is set
+// with a reset token and only the SHA-256 digest of the reset token is kept
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct CredentialEntity {
+    pub party_id: String,
+    pub version: i64,
+    pub password_hash: String,
+    pub reset_token_hash: String,
+    pub reset_expires_at: Option<NaiveDateTime>,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl CredentialEntity {
+    pub fn new(party_id: &str) -> Self {
+        Self {
+            party_id: party_id.to_string(),
+            version: 0,
+            password_hash: "".to_string(),
+            reset_token_hash: "".to_string(),
+            reset_expires_at: None,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl Identifiable for CredentialEntity {
+    fn id(&self) -> String {
+        self.party_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+// ApiKeyEntity binds an API key to a party or an integration, only the SHA-256 digest of the key is stored
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct ApiKeyEntity {
+    pub key_id: String,
+    pub version: i64,
+    pub party_id: String,
+    pub integration_name: String,
+    pub key_hash: String,
+    // roles of integrations, keys of parties use the current roles of the party
+    pub roles: Vec<String>,
+    pub rate_limit_per_minute: i64,
+    pub key_status: ApiKeyStatus,
+    pub created_by: String,
+    pub rotated_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl ApiKeyEntity {
+    pub fn new(party_id: &str, integration_name: &str, created_by: &str) -> Self {
+        Self {
+            key_id: Uuid::new_v4().to_string(),
+            version: 0,
+            party_id: party_id.to_string(),
+            integration_name: integration_name.to_string(),
+            key_hash: "".to_string(),
+            roles: vec![],
+            rate_limit_per_minute: 0,
+            key_status: ApiKeyStatus::Active,
+            created_by: created_by.to_string(),
+            rotated_at: None,
+            revoked_at: None,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl Identifiable for ApiKeyEntity {
+    fn id(&self) -> String {
+        self.key_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+// remaining requests of a principal in the current window and the seconds until the window is reset
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RateLimitBudget {
+    pub(crate) allowed: bool,
+    pub(crate) limit: i64,
+    pub(crate) remaining: i64,
+    pub(crate) reset_seconds: i64,
+}
+
+// RateLimiter counts requests of each principal within fixed one-minute windows, counts are kept in
+// memory of the lambda instance
+pub(crate) struct RateLimiter {
+    windows: Mutex<HashMap<String, (i64, i64)>>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new() -> Self {
+        Self {
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // counts the request of the principal at given epoch seconds, the request is not allowed when the principal
+    // already used its limit within the window
+    pub(crate) fn consume(&self, principal: &str, limit: i64, now: i64) -> RateLimitBudget {
+        let window = now / 60;
+        let reset_seconds = (window + 1) * 60 - now;
+        let mut windows = self.windows.lock().unwrap_or_else(|err| err.into_inner());
+        // drop counts of earlier windows so that idle principals are not kept
+        windows.retain(|_, (w, _)| *w == window);
+        let (_, count) = windows.entry(principal.to_string()).or_insert((window, 0));
+        let allowed = *count < limit;
+        if allowed {
+            *count += 1;
+        }
+        RateLimitBudget { allowed, limit, remaining: (limit - *count).max(0), reset_seconds }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::credentials::domain::rate_limiter::RateLimiter;
+
+    #[tokio::test]
+    async fn test_should_limit_requests_per_principal() {
+        let limiter = RateLimiter::new();
+        let now = 1_700_000_020;
+        let budget = limiter.consume("api_key:key1", 2, now);
+        assert!(budget.allowed);
+        assert_eq!(1, budget.remaining);
+        assert_eq!(20, budget.reset_seconds);
+        assert!(limiter.consume("api_key:key1", 2, now + 1).allowed);
+        let budget = limiter.consume("api_key:key1", 2, now + 2);
+        assert!(!budget.allowed);
+        assert_eq!(0, budget.remaining);
+        assert_eq!(18, budget.reset_seconds);
+        // other principals have their own limit
+        assert!(limiter.consume("patron1", 2, now + 2).allowed);
+        // limit is reset in the next window
+        assert!(limiter.consume("api_key:key1", 2, now + 60).allowed);
+    }
+}
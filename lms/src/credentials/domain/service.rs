@@ -0,0 +1,268 @@
+use std::collections::HashMap;
+
+use argon2::Argon2;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::password_hash::rand_core::OsRng;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+use crate::core::domain::Configuration;
+use crate::core::library::{AccountStatus, LibraryError, LibraryResult, PartyKind};
+use crate::credentials::domain::CredentialService;
+use crate::credentials::domain::model::CredentialEntity;
+use crate::credentials::dto::{ClaimsDto, TokenDto};
+use crate::credentials::repository::CredentialRepository;
+use crate::notifications::domain::NotificationService;
+use crate::parties::domain::model::{normalize_email, PartyEntity};
+use crate::parties::repository::PartyRepository;
+
+const MIN_PASSWORD_LEN: usize = 8;
+
+pub(crate) struct CredentialServiceImpl {
+    branch_id: String,
+    jwt_secret: String,
+    jwt_expiry_hours: i64,
+    password_reset_hours: i64,
+    strip_email_plus_tags: bool,
+    credential_repository: Box<dyn CredentialRepository>,
+    party_repository: Box<dyn PartyRepository>,
+    notification_service: Box<dyn NotificationService>,
+}
+
+impl CredentialServiceImpl {
+    pub(crate) fn new(config: &Configuration, credential_repository: Box<dyn CredentialRepository>,
+                      party_repository: Box<dyn PartyRepository>,
+                      notification_service: Box<dyn NotificationService>) -> Self {
+        Self {
+            branch_id: config.branch_id.to_string(),
+            jwt_secret: config.jwt_secret.to_string(),
+            jwt_expiry_hours: config.jwt_expiry_hours,
+            password_reset_hours: config.password_reset_hours,
+            strip_email_plus_tags: config.strip_email_plus_tags,
+            credential_repository,
+            party_repository,
+            notification_service,
+        }
+    }
+
+    // patrons and employees can login, organizations and branches have no credentials
+    async fn find_party_by_email(&self, email: &str) -> LibraryResult<Option<PartyEntity>> {
+        for kind in [PartyKind::Patron, PartyKind::Employee] {
+            let res = self.party_repository.query(
+                &HashMap::from([("email".to_string(), normalize_email(email, self.strip_email_plus_tags)),
+                    ("kind".to_string(), kind.to_string())]), None, 1).await?;
+            if let Some(party) = res.records.into_iter().next() {
+                return Ok(Some(party));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn find_credential(&self, party_id: &str) -> LibraryResult<Option<CredentialEntity>> {
+        match self.credential_repository.get(party_id).await {
+            Ok(credential) => Ok(Some(credential)),
+            Err(LibraryError::NotFound { .. }) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[async_trait]
+impl CredentialService for CredentialServiceImpl {
+    async fn login(&self, email: &str, password: &str) -> LibraryResult<TokenDto> {
+        let invalid = || LibraryError::not_granted("invalid email or password", Some("401".to_string()));
+        let party = self.find_party_by_email(email).await?.ok_or_else(invalid)?;
+        let credential = self.find_credential(party.party_id.as_str()).await?.ok_or_else(invalid)?;
+        if !verify_password(credential.password_hash.as_str(), password) {
+            return Err(invalid());
+        }
+        if !party.active || matches!(party.account_status, AccountStatus::Pending | AccountStatus::Banned | AccountStatus::Deleted) {
+            return Err(LibraryError::not_granted(format!("account of {} is {}",
+                                                         email, party.account_status).as_str(), Some("403".to_string())));
+        }
+        let now = Utc::now();
+        let expires_at = now + Duration::hours(self.jwt_expiry_hours);
+        let claims = ClaimsDto {
+            sub: party.party_id.to_string(),
+            email: party.email.to_string(),
+            roles: party.group_roles.clone(),
+            branch_id: self.branch_id.to_string(),
+            iat: now.timestamp(),
+            exp: expires_at.timestamp(),
+            // logins with a password are rate limited by the configured default
+            api_key_id: String::new(),
+            rate_limit_per_minute: 0,
+        };
+        let token = jsonwebtoken::encode(&Header::new(Algorithm::HS256), &claims,
+                                         &EncodingKey::from_secret(self.jwt_secret.as_bytes()))
+            .map_err(|err| LibraryError::runtime(format!("failed to sign token {}", err).as_str(), None))?;
+        Ok(TokenDto::new(token.as_str(), expires_at.naive_utc()))
+    }
+
+    async fn change_password(&self, party_id: &str, current_password: &str, new_password: &str) -> LibraryResult<()> {
+        let mut credential = self.find_credential(party_id).await?.ok_or_else(
+            || LibraryError::not_granted("current password is invalid", Some("403".to_string())))?;
+        if !verify_password(credential.password_hash.as_str(), current_password) {
+            return Err(LibraryError::not_granted("current password is invalid", Some("403".to_string())));
+        }
+        credential.password_hash = hash_password(new_password)?;
+        self.credential_repository.update(&credential).await.map(|_| ())
+    }
+
+    async fn request_password_reset(&self, email: &str) -> LibraryResult<()> {
+        let party = match self.find_party_by_email(email).await? {
+            Some(party) => party,
+            None => return Ok(()),
+        };
+        let secret: String = rand::thread_rng().sample_iter(&Alphanumeric).take(32).map(char::from).collect();
+        let token = format!("{}.{}", party.party_id, secret);
+        let expires_at = Utc::now().naive_utc() + Duration::hours(self.password_reset_hours);
+        match self.find_credential(party.party_id.as_str()).await? {
+            Some(mut credential) => {
+                credential.reset_token_hash = digest_token(token.as_str());
+                credential.reset_expires_at = Some(expires_at);
+                let _ = self.credential_repository.update(&credential).await?;
+            }
+            None => {
+                let mut credential = CredentialEntity::new(party.party_id.as_str());
+                credential.reset_token_hash = digest_token(token.as_str());
+                credential.reset_expires_at = Some(expires_at);
+                let _ = self.credential_repository.create(&credential).await?;
+            }
+        }
+        let _ = self.notification_service.notify(
+            party.party_id.as_str(), "Reset your library password",
+            format!("Use reset token {} to set your password before {}.", token, expires_at).as_str()).await?;
+        Ok(())
+    }
+
+    async fn reset_password(&self, token: &str, new_password: &str) -> LibraryResult<()> {
+        let invalid = || LibraryError::not_granted("invalid password reset token", Some("403".to_string()));
+        let party_id = token.split('.').next().ok_or_else(invalid)?;
+        let mut credential = self.find_credential(party_id).await?.ok_or_else(invalid)?;
+        if credential.reset_token_hash.is_empty() || credential.reset_token_hash != digest_token(token) {
+            return Err(invalid());
+        }
+        if credential.reset_expires_at.map(|at| at < Utc::now().naive_utc()).unwrap_or(true) {
+            return Err(LibraryError::validation("password reset token is expired", Some("400".to_string())));
+        }
+        credential.password_hash = hash_password(new_password)?;
+        // reset tokens can only be used once
+        credential.reset_token_hash = "".to_string();
+        credential.reset_expires_at = None;
+        self.credential_repository.update(&credential).await.map(|_| ())
+    }
+}
+
+// validates signature and expiration of the access token and returns its claims
+pub(crate) fn decode_token(secret: &str, token: &str) -> LibraryResult<ClaimsDto> {
+    jsonwebtoken::decode::<ClaimsDto>(token, &DecodingKey::from_secret(secret.as_bytes()),
+                                      &Validation::new(Algorithm::HS256))
+        .map(|data| data.claims)
+        .map_err(|err| LibraryError::not_granted(format!("invalid access token {}", err).as_str(), Some("401".to_string())))
+}
+
+fn hash_password(password: &str) -> LibraryResult<String> {
+    if password.len() < MIN_PASSWORD_LEN {
+        return Err(LibraryError::validation(
+            format!("password must have at least {} characters", MIN_PASSWORD_LEN).as_str(), Some("400".to_string())));
+    }
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default().hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|err| LibraryError::runtime(format!("failed to hash password {}", err).as_str(), None))
+}
+
+fn verify_password(password_hash: &str, password: &str) -> bool {
+    match PasswordHash::new(password_hash) {
+        Ok(hash) => Argon2::default().verify_password(password.as_bytes(), &hash).is_ok(),
+        Err(_) => false,
+    }
+}
+
+pub(crate) fn digest_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::core::domain::Configuration;
+    use crate::core::library::{AccountStatus, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::credentials::domain::CredentialService;
+    use crate::credentials::domain::service::decode_token;
+    use crate::credentials::factory;
+    use crate::notifications::domain::NotificationService;
+    use crate::notifications::factory::create_notification_service;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn CredentialService> {
+        factory::create_credential_service(&Configuration::new("test"), store).await
+    }
+
+    async fn notification_svc(store: RepositoryStore) -> Box<dyn NotificationService> {
+        create_notification_service(store).await
+    }
+
+    async fn emailed_reset_token(store: RepositoryStore, party_id: &str) -> String {
+        let res = notification_svc(store).await.find_notifications(party_id, None, 10).await.expect("should find notifications");
+        let notification = res.records.first().expect("should send reset email");
+        notification.message.split_whitespace().nth(3).expect("should include token").to_string()
+    }
+
+    #[tokio::test]
+    async fn test_should_reset_password_and_login() {
+        let store = test_store(module_path!(), "test_should_reset_password_and_login");
+        let credential_svc = sut_svc(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Employee, "credential_librarian@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create party");
+
+        // unknown emails are ignored
+        credential_svc.request_password_reset("credential_unknown@example.com").await.expect("should ignore unknown email");
+        assert!(credential_svc.login(librarian.email.as_str(), "password1").await.is_err());
+
+        credential_svc.request_password_reset(librarian.email.as_str()).await.expect("should request reset");
+        let token = emailed_reset_token(store, librarian.party_id.as_str()).await;
+        assert!(credential_svc.reset_password(format!("{}x", token).as_str(), "password1").await.is_err());
+        assert!(credential_svc.reset_password(token.as_str(), "short").await.is_err());
+        credential_svc.reset_password(token.as_str(), "password1").await.expect("should reset password");
+        // token cannot be reused
+        assert!(credential_svc.reset_password(token.as_str(), "password2").await.is_err());
+
+        assert!(credential_svc.login(librarian.email.as_str(), "password2").await.is_err());
+        let token = credential_svc.login(librarian.email.as_str(), "password1").await.expect("should login");
+        assert_eq!("Bearer", token.token_type.as_str());
+        let claims = decode_token(Configuration::new("test").jwt_secret.as_str(), token.access_token.as_str()).expect("should decode token");
+        assert_eq!(librarian.party_id, claims.sub);
+        assert_eq!(vec![Role::Librarian.to_string()], claims.roles);
+        assert_eq!("test", claims.branch_id.as_str());
+        assert!(decode_token("wrong secret", token.access_token.as_str()).is_err());
+
+        assert!(credential_svc.change_password(librarian.party_id.as_str(), "password2", "password3").await.is_err());
+        credential_svc.change_password(librarian.party_id.as_str(), "password1", "password3").await.expect("should change password");
+        assert!(credential_svc.login(librarian.email.as_str(), "password1").await.is_err());
+        let _ = credential_svc.login(librarian.email.as_str(), "password3").await.expect("should login");
+    }
+
+    #[tokio::test]
+    async fn test_should_not_login_pending_account() {
+        let store = test_store(module_path!(), "test_should_not_login_pending_account");
+        let credential_svc = sut_svc(store).await;
+        let mut patron = PartyEntity::new(PartyKind::Patron, "credential_pending@example.com");
+        patron.account_status = AccountStatus::Pending;
+        let _ = create_party_repository(store).await.create(&patron).await.expect("should create party");
+
+        credential_svc.request_password_reset(patron.email.as_str()).await.expect("should request reset");
+        let token = emailed_reset_token(store, patron.party_id.as_str()).await;
+        credential_svc.reset_password(token.as_str(), "password1").await.expect("should reset password");
+        assert!(credential_svc.login(patron.email.as_str(), "password1").await.is_err());
+    }
+}
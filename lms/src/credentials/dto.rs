@@ -0,0 +1,73 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use crate::core::library::ApiKeyStatus;
+use crate::utils::date::serializer;
+
+// TokenDto is the bearer token issued on login
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct TokenDto {
+    pub access_token: String,
+    pub token_type: String,
+    #[serde(with = "serializer")]
+    pub expires_at: NaiveDateTime,
+}
+
+impl TokenDto {
+    pub fn new(access_token: &str, expires_at: NaiveDateTime) -> Self {
+        Self {
+            access_token: access_token.to_string(),
+            token_type: "Bearer".to_string(),
+            expires_at,
+        }
+    }
+}
+
+// ClaimsDto defines JWT claims of an authenticated party, the auth middleware adds them to requests
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct ClaimsDto {
+    pub sub: String,
+    pub email: String,
+    pub roles: Vec<String>,
+    pub branch_id: String,
+    pub iat: i64,
+    pub exp: i64,
+    // set when the request is authenticated with an API key instead of JWT
+    #[serde(default)]
+    pub api_key_id: String,
+    // overrides default rate limit of the API key, zero uses the configured limit
+    #[serde(default)]
+    pub rate_limit_per_minute: i64,
+}
+
+impl ClaimsDto {
+    // returns identity used for audit logs and rate limiting
+    pub fn principal(&self) -> String {
+        if self.api_key_id.is_empty() {
+            self.sub.to_string()
+        } else {
+            format!("api_key:{}", self.api_key_id)
+        }
+    }
+}
+
+// ApiKeyDto describes an API key of a party or integration, the plain key is only returned when it is
+// created or rotated
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct ApiKeyDto {
+    pub key_id: String,
+    pub version: i64,
+    pub party_id: String,
+    pub integration_name: String,
+    pub roles: Vec<String>,
+    pub rate_limit_per_minute: i64,
+    pub key_status: ApiKeyStatus,
+    pub created_by: String,
+    pub rotated_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub key: Option<String>,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
@@ -0,0 +1,58 @@
+use crate::audit::factory::create_audit_service;
+use crate::core::domain::Configuration;
+use crate::core::repository::RepositoryStore;
+use crate::credentials::domain::{ApiKeyService, CredentialService};
+use crate::credentials::domain::api_keys::ApiKeyServiceImpl;
+use crate::credentials::domain::service::CredentialServiceImpl;
+use crate::credentials::factory;
+use crate::credentials::repository::{ApiKeyRepository, CredentialRepository};
+use crate::credentials::repository::ddb_api_key_repository::DDBApiKeyRepository;
+use crate::credentials::repository::ddb_credential_repository::DDBCredentialRepository;
+use crate::notifications::factory::create_notification_service;
+use crate::parties::factory::create_party_repository;
+use crate::patrons::factory::create_patron_service;
+use crate::utils::ddb::{build_db_client, create_key_table};
+
+pub(crate) async fn create_credential_repository(store: RepositoryStore) -> Box<dyn CredentialRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBCredentialRepository::new(client, "credentials"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("credentials");
+            let _ = create_key_table(&client, table_name.as_str(), "party_id").await;
+            Box::new(DDBCredentialRepository::new(client, table_name.as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_credential_service(config: &Configuration, store: RepositoryStore) -> Box<dyn CredentialService> {
+    let credential_repo = factory::create_credential_repository(store).await;
+    let party_repo = create_party_repository(store).await;
+    let notification_svc = create_notification_service(store).await;
+    Box::new(CredentialServiceImpl::new(config, credential_repo, party_repo, notification_svc))
+}
+
+pub(crate) async fn create_api_key_repository(store: RepositoryStore) -> Box<dyn ApiKeyRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBApiKeyRepository::new(client, "api_keys"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("api_keys");
+            let _ = create_key_table(&client, table_name.as_str(), "key_id").await;
+            Box::new(DDBApiKeyRepository::new(client, table_name.as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_api_key_service(config: &Configuration, store: RepositoryStore) -> Box<dyn ApiKeyService> {
+    let api_key_repo = factory::create_api_key_repository(store).await;
+    let patron_svc = create_patron_service(config, store).await;
+    let audit_svc = create_audit_service(config, store).await;
+    Box::new(ApiKeyServiceImpl::new(config, api_key_repo, patron_svc, audit_svc))
+}
@@ -0,0 +1,20 @@
+pub mod ddb_api_key_repository;
+pub mod ddb_credential_repository;
+
+use async_trait::async_trait;
+use crate::core::library::LibraryResult;
+use crate::credentials::domain::model::{ApiKeyEntity, CredentialEntity};
+
+#[async_trait]
+pub(crate) trait CredentialRepository: Sync + Send {
+    async fn create(&self, entity: &CredentialEntity) -> LibraryResult<usize>;
+    async fn update(&self, entity: &CredentialEntity) -> LibraryResult<usize>;
+    async fn get(&self, party_id: &str) -> LibraryResult<CredentialEntity>;
+}
+
+#[async_trait]
+pub(crate) trait ApiKeyRepository: Sync + Send {
+    async fn create(&self, entity: &ApiKeyEntity) -> LibraryResult<usize>;
+    async fn update(&self, entity: &ApiKeyEntity) -> LibraryResult<usize>;
+    async fn get(&self, key_id: &str) -> LibraryResult<ApiKeyEntity>;
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::core::library::{ApiKeyStatus, LibraryError, LibraryResult};
+use crate::credentials::domain::model::ApiKeyEntity;
+use crate::credentials::repository::ApiKeyRepository;
+use crate::utils::ddb::{opt_string_date, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, parse_string_set_attribute, string_date};
+
+#[derive(Debug)]
+pub(crate) struct DDBApiKeyRepository {
+    client: Client,
+    table_name: String,
+}
+
+impl DDBApiKeyRepository {
+    pub(crate) fn new(client: Client, table_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepository for DDBApiKeyRepository {
+    async fn create(&self, entity: &ApiKeyEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        let mut item = parse_item(val)?;
+        item.insert("rotated_at".to_string(), opt_string_date(entity.rotated_at));
+        item.insert("revoked_at".to_string(), opt_string_date(entity.revoked_at));
+        item.insert("created_at".to_string(), string_date(entity.created_at));
+        item.insert("updated_at".to_string(), string_date(entity.updated_at));
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(key_id)")
+            .set_item(Some(item))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn update(&self, entity: &ApiKeyEntity) -> LibraryResult<usize> {
+        let now = Utc::now().naive_utc();
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .update_item()
+            .table_name(table_name)
+            .key("key_id", AttributeValue::S(entity.key_id.clone()))
+            .update_expression("SET version = :version, key_hash = :key_hash, key_status = :key_status, rate_limit_per_minute = :rate_limit_per_minute, rotated_at = :rotated_at, revoked_at = :revoked_at, updated_at = :updated_at")
+            .expression_attribute_values(":old_version", AttributeValue::N(entity.version.to_string()))
+            .expression_attribute_values(":version", AttributeValue::N((entity.version + 1).to_string()))
+            .expression_attribute_values(":key_hash", AttributeValue::S(entity.key_hash.to_string()))
+            .expression_attribute_values(":key_status", AttributeValue::S(entity.key_status.to_string()))
+            .expression_attribute_values(":rate_limit_per_minute", AttributeValue::N(entity.rate_limit_per_minute.to_string()))
+            .expression_attribute_values(":rotated_at", opt_string_date(entity.rotated_at))
+            .expression_attribute_values(":revoked_at", opt_string_date(entity.revoked_at))
+            .expression_attribute_values(":updated_at", string_date(now))
+            .condition_expression("attribute_exists(version) AND version = :old_version")
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn get(&self, key_id: &str) -> LibraryResult<ApiKeyEntity> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .query()
+            .table_name(table_name)
+            .limit(1)
+            .consistent_read(true)
+            .key_condition_expression("key_id = :key_id")
+            .expression_attribute_values(":key_id", AttributeValue::S(key_id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).and_then(|req| {
+            if let Some(map) = req.items.as_ref().and_then(|items| items.first()) {
+                return Ok(ApiKeyEntity::from(map));
+            }
+            Err(LibraryError::not_found(format!("api key not found for {}", key_id).as_str()))
+        })
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for ApiKeyEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        ApiKeyEntity {
+            key_id: parse_string_attribute("key_id", map).unwrap_or_else(|| String::from("")),
+            version: parse_number_attribute("version", map),
+            party_id: parse_string_attribute("party_id", map).unwrap_or_else(|| String::from("")),
+            integration_name: parse_string_attribute("integration_name", map).unwrap_or_else(|| String::from("")),
+            key_hash: parse_string_attribute("key_hash", map).unwrap_or_else(|| String::from("")),
+            roles: parse_string_set_attribute("roles", map),
+            rate_limit_per_minute: parse_number_attribute("rate_limit_per_minute", map),
+            key_status: ApiKeyStatus::from(parse_string_attribute("key_status", map).unwrap_or_else(|| String::from(""))),
+            created_by: parse_string_attribute("created_by", map).unwrap_or_else(|| String::from("")),
+            rotated_at: parse_date_attribute("rotated_at", map),
+            revoked_at: parse_date_attribute("revoked_at", map),
+            created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+    use chrono::Utc;
+
+    use crate::core::library::{ApiKeyStatus, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::credentials::domain::model::ApiKeyEntity;
+    use crate::credentials::repository::ddb_api_key_repository::DDBApiKeyRepository;
+    use crate::credentials::repository::ApiKeyRepository;
+    use crate::utils::ddb::{build_db_client, create_key_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_key_table(&client, store.table_name("api_keys").as_str(), "key_id").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_create_update_get_api_key() {
+        let store = test_store(module_path!(), "test_should_create_update_get_api_key");
+        let repo = DDBApiKeyRepository::new(build_client(store).await, store.table_name("api_keys").as_str());
+        let mut api_key = ApiKeyEntity::new("", "discovery-layer", "admin");
+        api_key.key_hash = "digest".to_string();
+        api_key.roles = vec![Role::Librarian.to_string()];
+        assert_eq!(1, repo.create(&api_key).await.expect("should create api key"));
+
+        let mut loaded = repo.get(api_key.key_id.as_str()).await.expect("should get api key");
+        assert_eq!("discovery-layer", loaded.integration_name.as_str());
+        assert_eq!(vec![Role::Librarian.to_string()], loaded.roles);
+        assert_eq!(ApiKeyStatus::Active, loaded.key_status);
+        loaded.key_status = ApiKeyStatus::Revoked;
+        loaded.revoked_at = Some(Utc::now().naive_utc());
+        assert_eq!(1, repo.update(&loaded).await.expect("should update api key"));
+        assert!(repo.update(&loaded).await.is_err());
+
+        let loaded = repo.get(api_key.key_id.as_str()).await.expect("should get api key");
+        assert_eq!(ApiKeyStatus::Revoked, loaded.key_status);
+        assert!(loaded.revoked_at.is_some());
+        assert_eq!("digest", loaded.key_hash.as_str());
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::credentials::domain::model::CredentialEntity;
+use crate::credentials::repository::CredentialRepository;
+use crate::utils::ddb::{opt_string_date, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, string_date};
+
+#[derive(Debug)]
+pub(crate) struct DDBCredentialRepository {
+    client: Client,
+    table_name: String,
+}
+
+impl DDBCredentialRepository {
+    pub(crate) fn new(client: Client, table_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl CredentialRepository for DDBCredentialRepository {
+    async fn create(&self, entity: &CredentialEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        let mut item = parse_item(val)?;
+        item.insert("reset_expires_at".to_string(), opt_string_date(entity.reset_expires_at));
+        item.insert("created_at".to_string(), string_date(entity.created_at));
+        item.insert("updated_at".to_string(), string_date(entity.updated_at));
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(party_id)")
+            .set_item(Some(item))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn update(&self, entity: &CredentialEntity) -> LibraryResult<usize> {
+        let now = Utc::now().naive_utc();
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .update_item()
+            .table_name(table_name)
+            .key("party_id", AttributeValue::S(entity.party_id.clone()))
+            .update_expression("SET version = :version, password_hash = :password_hash, reset_token_hash = :reset_token_hash, reset_expires_at = :reset_expires_at, updated_at = :updated_at")
+            .expression_attribute_values(":old_version", AttributeValue::N(entity.version.to_string()))
+            .expression_attribute_values(":version", AttributeValue::N((entity.version + 1).to_string()))
+            .expression_attribute_values(":password_hash", AttributeValue::S(entity.password_hash.to_string()))
+            .expression_attribute_values(":reset_token_hash", AttributeValue::S(entity.reset_token_hash.to_string()))
+            .expression_attribute_values(":reset_expires_at", opt_string_date(entity.reset_expires_at))
+            .expression_attribute_values(":updated_at", string_date(now))
+            .condition_expression("attribute_exists(version) AND version = :old_version")
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn get(&self, party_id: &str) -> LibraryResult<CredentialEntity> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .query()
+            .table_name(table_name)
+            .limit(1)
+            .consistent_read(true)
+            .key_condition_expression("party_id = :party_id")
+            .expression_attribute_values(":party_id", AttributeValue::S(party_id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).and_then(|req| {
+            if let Some(map) = req.items.as_ref().and_then(|items| items.first()) {
+                return Ok(CredentialEntity::from(map));
+            }
+            Err(LibraryError::not_found(format!("credential not found for {}", party_id).as_str()))
+        })
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for CredentialEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        CredentialEntity {
+            party_id: parse_string_attribute("party_id", map).unwrap_or_else(|| String::from("")),
+            version: parse_number_attribute("version", map),
+            password_hash: parse_string_attribute("password_hash", map).unwrap_or_else(|| String::from("")),
+            reset_token_hash: parse_string_attribute("reset_token_hash", map).unwrap_or_else(|| String::from("")),
+            reset_expires_at: parse_date_attribute("reset_expires_at", map),
+            created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+    use chrono::Utc;
+
+    use crate::core::repository::RepositoryStore;
+    use crate::credentials::domain::model::CredentialEntity;
+    use crate::credentials::repository::ddb_credential_repository::DDBCredentialRepository;
+    use crate::credentials::repository::CredentialRepository;
+    use crate::utils::ddb::{build_db_client, create_key_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_key_table(&client, store.table_name("credentials").as_str(), "party_id").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_create_update_get_credential() {
+        let store = test_store(module_path!(), "test_should_create_update_get_credential");
+        let repo = DDBCredentialRepository::new(build_client(store).await, store.table_name("credentials").as_str());
+        let credential = CredentialEntity::new("credential_party");
+        assert_eq!(1, repo.create(&credential).await.expect("should create credential"));
+        assert!(repo.create(&credential).await.is_err());
+
+        let mut loaded = repo.get("credential_party").await.expect("should get credential");
+        assert_eq!("", loaded.password_hash.as_str());
+        assert_eq!(None, loaded.reset_expires_at);
+        loaded.password_hash = "hash".to_string();
+        loaded.reset_token_hash = "digest".to_string();
+        loaded.reset_expires_at = Some(Utc::now().naive_utc());
+        assert_eq!(1, repo.update(&loaded).await.expect("should update credential"));
+        // stale version should be rejected
+        assert!(repo.update(&loaded).await.is_err());
+
+        let loaded = repo.get("credential_party").await.expect("should get credential");
+        assert_eq!(1, loaded.version);
+        assert_eq!("hash", loaded.password_hash.as_str());
+        assert_eq!("digest", loaded.reset_token_hash.as_str());
+        assert!(loaded.reset_expires_at.is_some());
+        assert!(repo.get("unknown_party").await.is_err());
+    }
+}
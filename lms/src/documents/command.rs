@@ -0,0 +1,4 @@
+pub mod request_upload_cmd;
+pub mod find_documents_cmd;
+pub mod get_document_cmd;
+pub mod remove_document_cmd;
@@ -0,0 +1,92 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::documents::domain::DocumentService;
+use crate::documents::dto::DocumentDto;
+
+pub(crate) struct FindDocumentsCommand {
+    document_service: Box<dyn DocumentService>,
+}
+
+impl FindDocumentsCommand {
+    pub(crate) fn new(document_service: Box<dyn DocumentService>) -> Self {
+        Self {
+            document_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindDocumentsCommandRequest {
+    // set from claims of the authenticated librarian
+    #[serde(default)]
+    pub requested_by: String,
+    pub party_id: String,
+}
+
+impl FindDocumentsCommandRequest {
+    pub fn new(requested_by: &str, party_id: &str) -> Self {
+        Self {
+            requested_by: requested_by.to_string(),
+            party_id: party_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindDocumentsCommandResponse {
+    pub documents: Vec<DocumentDto>,
+}
+
+impl FindDocumentsCommandResponse {
+    pub fn new(documents: Vec<DocumentDto>) -> Self {
+        Self {
+            documents,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<FindDocumentsCommandRequest, FindDocumentsCommandResponse> for FindDocumentsCommand {
+    async fn execute(&self, req: FindDocumentsCommandRequest) -> Result<FindDocumentsCommandResponse, CommandError> {
+        self.document_service.find_documents(req.requested_by.as_str(), req.party_id.as_str())
+            .await.map_err(CommandError::from).map(FindDocumentsCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{DocumentKind, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::documents::command::find_documents_cmd::{FindDocumentsCommand, FindDocumentsCommandRequest};
+    use crate::documents::domain::DocumentService;
+    use crate::documents::factory;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn DocumentService> {
+        factory::create_document_service(&Configuration::new("test"), store).await
+    }
+
+    #[tokio::test]
+    async fn test_should_run_find_documents() {
+        let store = test_store(module_path!(), "test_should_run_find_documents");
+        let cmd = FindDocumentsCommand::new(build_svc(store).await);
+        let mut librarian = PartyEntity::new(PartyKind::Employee, "find_documents_cmd@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create party");
+        let document = build_svc(store).await.request_upload(librarian.party_id.as_str(), librarian.party_id.as_str(),
+                                                        DocumentKind::Passport, "passport.pdf", "application/pdf")
+            .await.expect("should request upload");
+
+        let res = cmd.execute(FindDocumentsCommandRequest::new(librarian.party_id.as_str(), librarian.party_id.as_str()))
+            .await.expect("should find documents");
+        assert_eq!(1, res.documents.len());
+        assert_eq!(document.document_id, res.documents[0].document_id);
+        assert!(res.documents[0].upload_url.is_none());
+    }
+}
@@ -0,0 +1,90 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::documents::domain::DocumentService;
+use crate::documents::dto::DocumentDto;
+
+pub(crate) struct GetDocumentCommand {
+    document_service: Box<dyn DocumentService>,
+}
+
+impl GetDocumentCommand {
+    pub(crate) fn new(document_service: Box<dyn DocumentService>) -> Self {
+        Self {
+            document_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetDocumentCommandRequest {
+    pub requested_by: String,
+    pub document_id: String,
+}
+
+impl GetDocumentCommandRequest {
+    pub fn new(requested_by: &str, document_id: &str) -> Self {
+        Self {
+            requested_by: requested_by.to_string(),
+            document_id: document_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetDocumentCommandResponse {
+    pub document: DocumentDto,
+}
+
+impl GetDocumentCommandResponse {
+    pub fn new(document: DocumentDto) -> Self {
+        Self {
+            document,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<GetDocumentCommandRequest, GetDocumentCommandResponse> for GetDocumentCommand {
+    async fn execute(&self, req: GetDocumentCommandRequest) -> Result<GetDocumentCommandResponse, CommandError> {
+        self.document_service.get_document(req.requested_by.as_str(), req.document_id.as_str())
+            .await.map_err(CommandError::from).map(GetDocumentCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{DocumentKind, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::documents::command::get_document_cmd::{GetDocumentCommand, GetDocumentCommandRequest};
+    use crate::documents::domain::DocumentService;
+    use crate::documents::factory;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn DocumentService> {
+        factory::create_document_service(&Configuration::new("test"), store).await
+    }
+
+    #[tokio::test]
+    async fn test_should_run_get_document() {
+        let store = test_store(module_path!(), "test_should_run_get_document");
+        let cmd = GetDocumentCommand::new(build_svc(store).await);
+        let mut admin = PartyEntity::new(PartyKind::Employee, "get_document_cmd@example.com");
+        admin.group_roles = vec![Role::Admin.to_string()];
+        let _ = create_party_repository(store).await.create(&admin).await.expect("should create party");
+        let document = build_svc(store).await.request_upload(admin.party_id.as_str(), admin.party_id.as_str(),
+                                                        DocumentKind::IdCard, "id.png", "image/png")
+            .await.expect("should request upload");
+        std::fs::write(document.upload_url.unwrap().trim_start_matches("file://"), [0x89, b'P', b'N', b'G'])
+            .expect("should upload document");
+
+        let res = cmd.execute(GetDocumentCommandRequest::new(admin.party_id.as_str(), document.document_id.as_str()))
+            .await.expect("should get document");
+        assert!(res.document.download_url.is_some());
+    }
+}
@@ -0,0 +1,84 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::documents::domain::DocumentService;
+
+pub(crate) struct RemoveDocumentCommand {
+    document_service: Box<dyn DocumentService>,
+}
+
+impl RemoveDocumentCommand {
+    pub(crate) fn new(document_service: Box<dyn DocumentService>) -> Self {
+        Self {
+            document_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RemoveDocumentCommandRequest {
+    pub removed_by: String,
+    pub document_id: String,
+}
+
+impl RemoveDocumentCommandRequest {
+    pub fn new(removed_by: &str, document_id: &str) -> Self {
+        Self {
+            removed_by: removed_by.to_string(),
+            document_id: document_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RemoveDocumentCommandResponse {}
+
+impl RemoveDocumentCommandResponse {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl Command<RemoveDocumentCommandRequest, RemoveDocumentCommandResponse> for RemoveDocumentCommand {
+    async fn execute(&self, req: RemoveDocumentCommandRequest) -> Result<RemoveDocumentCommandResponse, CommandError> {
+        self.document_service.remove_document(req.removed_by.as_str(), req.document_id.as_str())
+            .await.map_err(CommandError::from).map(|_| RemoveDocumentCommandResponse::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{DocumentKind, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::documents::command::remove_document_cmd::{RemoveDocumentCommand, RemoveDocumentCommandRequest};
+    use crate::documents::domain::DocumentService;
+    use crate::documents::factory;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn DocumentService> {
+        factory::create_document_service(&Configuration::new("test"), store).await
+    }
+
+    #[tokio::test]
+    async fn test_should_run_remove_document() {
+        let store = test_store(module_path!(), "test_should_run_remove_document");
+        let cmd = RemoveDocumentCommand::new(build_svc(store).await);
+        let mut librarian = PartyEntity::new(PartyKind::Employee, "remove_document_cmd@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create party");
+        let document = build_svc(store).await.request_upload(librarian.party_id.as_str(), librarian.party_id.as_str(),
+                                                        DocumentKind::ProofOfAddress, "bill.pdf", "application/pdf")
+            .await.expect("should request upload");
+
+        let _ = cmd.execute(RemoveDocumentCommandRequest::new(librarian.party_id.as_str(), document.document_id.as_str()))
+            .await.expect("should remove document");
+        assert!(cmd.execute(RemoveDocumentCommandRequest::new(librarian.party_id.as_str(), document.document_id.as_str()))
+            .await.is_err());
+    }
+}
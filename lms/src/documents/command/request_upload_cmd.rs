@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::core::library::DocumentKind;
+use crate::documents::domain::DocumentService;
+use crate::documents::dto::DocumentDto;
+
+pub(crate) struct RequestUploadCommand {
+    document_service: Box<dyn DocumentService>,
+}
+
+impl RequestUploadCommand {
+    pub(crate) fn new(document_service: Box<dyn DocumentService>) -> Self {
+        Self {
+            document_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RequestUploadCommandRequest {
+    // set from claims of the authenticated librarian
+    #[serde(default)]
+    pub requested_by: String,
+    pub party_id: String,
+    pub document_kind: DocumentKind,
+    pub file_name: String,
+    pub content_type: String,
+}
+
+impl RequestUploadCommandRequest {
+    pub fn new(requested_by: &str, party_id: &str, document_kind: DocumentKind, file_name: &str, content_type: &str) -> Self {
+        Self {
+            requested_by: requested_by.to_string(),
+            party_id: party_id.to_string(),
+            document_kind,
+            file_name: file_name.to_string(),
+            content_type: content_type.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RequestUploadCommandResponse {
+    pub document: DocumentDto,
+}
+
+impl RequestUploadCommandResponse {
+    pub fn new(document: DocumentDto) -> Self {
+        Self {
+            document,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<RequestUploadCommandRequest, RequestUploadCommandResponse> for RequestUploadCommand {
+    async fn execute(&self, req: RequestUploadCommandRequest) -> Result<RequestUploadCommandResponse, CommandError> {
+        self.document_service.request_upload(req.requested_by.as_str(), req.party_id.as_str(), req.document_kind,
+                                             req.file_name.as_str(), req.content_type.as_str())
+            .await.map_err(CommandError::from).map(RequestUploadCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{DocumentKind, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::documents::command::request_upload_cmd::{RequestUploadCommand, RequestUploadCommandRequest};
+    use crate::documents::factory;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn sut_cmd(store: RepositoryStore) -> RequestUploadCommand {
+        let svc = factory::create_document_service(&Configuration::new("test"), store).await;
+        RequestUploadCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_request_upload() {
+        let store = test_store(module_path!(), "test_should_run_request_upload");
+        let cmd = sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Employee, "request_upload_cmd@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create party");
+
+        let res = cmd.execute(RequestUploadCommandRequest::new(librarian.party_id.as_str(), librarian.party_id.as_str(),
+                                                               DocumentKind::DriverLicense, "license.jpg", "image/jpeg"))
+            .await.expect("should request upload");
+        assert!(res.document.upload_url.is_some());
+        assert_eq!(DocumentKind::DriverLicense, res.document.document_kind);
+    }
+}
@@ -0,0 +1,77 @@
+use axum::{
+    extract::{Extension, Path, State},
+    middleware,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use serde_json::{Value};
+use crate::core::controller::{AppState, command_bus, json_to_server_error, with_common_layers, ServerError};
+use crate::credentials::controller::authenticate;
+use crate::credentials::dto::ClaimsDto;
+use crate::documents::command::find_documents_cmd::{FindDocumentsCommand, FindDocumentsCommandRequest, FindDocumentsCommandResponse};
+use crate::documents::command::get_document_cmd::{GetDocumentCommand, GetDocumentCommandRequest, GetDocumentCommandResponse};
+use crate::documents::command::remove_document_cmd::{RemoveDocumentCommand, RemoveDocumentCommandRequest, RemoveDocumentCommandResponse};
+use crate::documents::command::request_upload_cmd::{RequestUploadCommand, RequestUploadCommandRequest, RequestUploadCommandResponse};
+use crate::documents::domain::DocumentService;
+use crate::documents::factory;
+use crate::utils::ddb::{build_db_client, create_table};
+
+async fn build_service(state: AppState) -> Box<dyn DocumentService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_table(&client, state.store.table_name("party_documents").as_str(), "document_id", "party_id", "created_at").await;
+    let _ = create_table(&client, state.store.table_name("parties").as_str(), "party_id", "kind", "normalized_email").await;
+    let _ = create_table(&client, state.store.table_name("audit_log").as_str(), "audit_id", "audit_type", "created_at").await;
+    factory::create_document_service(&state.config, state.store).await
+}
+
+pub(crate) async fn request_upload(
+    State(state): State<AppState>,
+    Extension(claims): Extension<ClaimsDto>,
+    json: Json<Value>) -> Result<Json<RequestUploadCommandResponse>, ServerError> {
+    let mut req: RequestUploadCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.requested_by = claims.sub;
+    let svc = build_service(state).await;
+    let res = command_bus().register(RequestUploadCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn find_documents(
+    State(state): State<AppState>,
+    Extension(claims): Extension<ClaimsDto>,
+    Path(party_id): Path<String>) -> Result<Json<FindDocumentsCommandResponse>, ServerError> {
+    let req = FindDocumentsCommandRequest::new(claims.sub.as_str(), party_id.as_str());
+    let svc = build_service(state).await;
+    let res = command_bus().register(FindDocumentsCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn get_document(
+    State(state): State<AppState>,
+    Extension(claims): Extension<ClaimsDto>,
+    Path(document_id): Path<String>) -> Result<Json<GetDocumentCommandResponse>, ServerError> {
+    let req = GetDocumentCommandRequest::new(claims.sub.as_str(), document_id.as_str());
+    let svc = build_service(state).await;
+    let res = command_bus().register(GetDocumentCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn remove_document(
+    State(state): State<AppState>,
+    Extension(claims): Extension<ClaimsDto>,
+    Path(document_id): Path<String>) -> Result<Json<RemoveDocumentCommandResponse>, ServerError> {
+    let req = RemoveDocumentCommandRequest::new(claims.sub.as_str(), document_id.as_str());
+    let svc = build_service(state).await;
+    let res = command_bus().register(RemoveDocumentCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// all routes require an authenticated librarian or admin, the service checks roles of the caller
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/documents", post(request_upload))
+        .route("/documents/party/:id", get(find_documents))
+        .route("/documents/:id", get(get_document).delete(remove_document))
+        .route_layer(middleware::from_fn_with_state(state.clone(), authenticate));
+    with_common_layers(router, state)
+}
@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+use crate::core::library::{DocumentKind, LibraryResult};
+use crate::documents::dto::DocumentDto;
+
+pub mod model;
+pub mod service;
+
+#[async_trait]
+pub(crate) trait DocumentService: Sync + Send {
+    // only librarians and admins can handle documents of parties and each access is recorded in the audit log,
+    // the returned document carries a pre-signed URL that the file is uploaded to
+    async fn request_upload(&self, requested_by: &str, party_id: &str, document_kind: DocumentKind,
+                            file_name: &str, content_type: &str) -> LibraryResult<DocumentDto>;
+    async fn find_documents(&self, requested_by: &str, party_id: &str) -> LibraryResult<Vec<DocumentDto>>;
+    // returns the document with a pre-signed URL to download the file
+    async fn get_document(&self, requested_by: &str, document_id: &str) -> LibraryResult<DocumentDto>;
+    async fn remove_document(&self, removed_by: &str, document_id: &str) -> LibraryResult<()>;
+    // removes files and metadata of documents whose retention ended and returns number of purged documents
+    async fn purge_expired(&self, page_size: usize) -> LibraryResult<usize>;
+}
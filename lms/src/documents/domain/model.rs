@@ -0,0 +1,57 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::core::domain::Identifiable;
+use crate::core::library::DocumentKind;
+use crate::utils::date::serializer;
+
+// DocumentEntity records metadata of a verification document of a party, the content is kept in the object store
+// under object_key and both are purged after retain_until
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct DocumentEntity {
+    pub document_id: String,
+    pub version: i64,
+    pub party_id: String,
+    pub document_kind: DocumentKind,
+    pub file_name: String,
+    pub content_type: String,
+    pub object_key: String,
+    pub uploaded_by: String,
+    #[serde(with = "serializer")]
+    pub retain_until: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl DocumentEntity {
+    pub fn new(party_id: &str, document_kind: DocumentKind, file_name: &str, content_type: &str,
+               uploaded_by: &str, retention_days: i64) -> Self {
+        let document_id = Uuid::new_v4().to_string();
+        let now = Utc::now().naive_utc();
+        Self {
+            object_key: format!("documents/{}/{}", party_id, document_id),
+            document_id,
+            version: 0,
+            party_id: party_id.to_string(),
+            document_kind,
+            file_name: file_name.to_string(),
+            content_type: content_type.to_string(),
+            uploaded_by: uploaded_by.to_string(),
+            retain_until: now + Duration::days(retention_days),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+impl Identifiable for DocumentEntity {
+    fn id(&self) -> String {
+        self.document_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
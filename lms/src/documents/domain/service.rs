@@ -0,0 +1,235 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::audit::domain::AuditService;
+use crate::core::domain::Configuration;
+use crate::core::ids::PatronId;
+use crate::core::library::{DocumentKind, LibraryError, LibraryResult};
+use crate::documents::domain::DocumentService;
+use crate::documents::domain::model::DocumentEntity;
+use crate::documents::dto::DocumentDto;
+use crate::documents::repository::DocumentRepository;
+use crate::gateway::objects::ObjectStore;
+use crate::patrons::domain::PatronService;
+use crate::patrons::Patron;
+
+const DOCUMENT_CONTENT_TYPES: [&str; 3] = ["application/pdf", "image/jpeg", "image/png"];
+// actor of audit records of documents that are purged by the retention job
+const RETENTION_ACTOR: &str = "retention_policy";
+
+pub(crate) struct DocumentServiceImpl {
+    retention_days: i64,
+    url_expiry: Duration,
+    document_repository: Box<dyn DocumentRepository>,
+    document_store: Box<dyn ObjectStore>,
+    patron_service: Box<dyn PatronService>,
+    audit_service: Box<dyn AuditService>,
+}
+
+impl DocumentServiceImpl {
+    pub(crate) fn new(config: &Configuration, document_repository: Box<dyn DocumentRepository>,
+                      document_store: Box<dyn ObjectStore>,
+                      patron_service: Box<dyn PatronService>,
+                      audit_service: Box<dyn AuditService>) -> Self {
+        Self {
+            retention_days: config.document_retention_days,
+            url_expiry: Duration::from_secs(config.document_url_seconds),
+            document_repository,
+            document_store,
+            patron_service,
+            audit_service,
+        }
+    }
+
+    async fn check_staff(&self, id: &str) -> LibraryResult<()> {
+        let staff = self.patron_service.find_patron_by_id(&PatronId::new(id)).await?;
+        if !staff.is_librarian() && !staff.is_admin() {
+            return Err(LibraryError::not_granted(format!("{} cannot access party documents", id).as_str(), Some("403".to_string())));
+        }
+        Ok(())
+    }
+
+    async fn purge(&self, document: &DocumentEntity, audit_type: &str, actor_id: &str) -> LibraryResult<()> {
+        // the object is removed first so that a failure leaves the metadata for the next attempt
+        self.document_store.delete(document.object_key.as_str()).await?;
+        let _ = self.document_repository.delete(document.document_id.as_str()).await?;
+        let _ = self.audit_service.record_action(audit_type, actor_id, document.document_id.as_str(),
+                                                 describe(document).as_str()).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DocumentService for DocumentServiceImpl {
+    async fn request_upload(&self, requested_by: &str, party_id: &str, document_kind: DocumentKind,
+                            file_name: &str, content_type: &str) -> LibraryResult<DocumentDto> {
+        self.check_staff(requested_by).await?;
+        let content_type = content_type.trim().to_lowercase();
+        if !DOCUMENT_CONTENT_TYPES.contains(&content_type.as_str()) {
+            return Err(LibraryError::validation(format!("document content type {} is not supported, use {}",
+                                                        content_type, DOCUMENT_CONTENT_TYPES.join(", ")).as_str(),
+                                                Some("400".to_string())));
+        }
+        if file_name.trim().is_empty() {
+            return Err(LibraryError::validation("document file name is required", Some("400".to_string())));
+        }
+        let _ = self.patron_service.find_patron_by_id(&PatronId::new(party_id)).await?;
+        let document = DocumentEntity::new(party_id, document_kind, file_name.trim(), content_type.as_str(),
+                                           requested_by, self.retention_days);
+        let upload_url = self.document_store.upload_url(document.object_key.as_str(), content_type.as_str(),
+                                                        self.url_expiry).await?;
+        let _ = self.document_repository.create(&document).await?;
+        let _ = self.audit_service.record_action("document_upload_requested", requested_by,
+                                                 document.document_id.as_str(), describe(&document).as_str()).await?;
+        let mut dto = DocumentDto::from(&document);
+        dto.upload_url = Some(upload_url);
+        Ok(dto)
+    }
+
+    async fn find_documents(&self, requested_by: &str, party_id: &str) -> LibraryResult<Vec<DocumentDto>> {
+        self.check_staff(requested_by).await?;
+        self.document_repository.find_by_party(party_id).await
+            .map(|documents| documents.iter().map(DocumentDto::from).collect())
+    }
+
+    async fn get_document(&self, requested_by: &str, document_id: &str) -> LibraryResult<DocumentDto> {
+        self.check_staff(requested_by).await?;
+        let document = self.document_repository.get(document_id).await?;
+        let download_url = self.document_store.url(document.object_key.as_str(), self.url_expiry).await?;
+        let _ = self.audit_service.record_action("document_viewed", requested_by, document_id,
+                                                 describe(&document).as_str()).await?;
+        let mut dto = DocumentDto::from(&document);
+        dto.download_url = Some(download_url);
+        Ok(dto)
+    }
+
+    async fn remove_document(&self, removed_by: &str, document_id: &str) -> LibraryResult<()> {
+        self.check_staff(removed_by).await?;
+        let document = self.document_repository.get(document_id).await?;
+        self.purge(&document, "document_removed", removed_by).await
+    }
+
+    async fn purge_expired(&self, page_size: usize) -> LibraryResult<usize> {
+        let now = Utc::now().naive_utc();
+        let mut purged = 0;
+        let mut page: Option<String> = None;
+        loop {
+            let res = self.document_repository.find_expired(now, page.as_deref(), page_size).await?;
+            for document in &res.records {
+                self.purge(document, "document_purged", RETENTION_ACTOR).await?;
+                purged += 1;
+            }
+            page = res.next_page;
+            if page.is_none() {
+                break;
+            }
+        }
+        Ok(purged)
+    }
+}
+
+fn describe(document: &DocumentEntity) -> String {
+    format!("{} {} of party {}", document.document_kind, document.file_name, document.party_id)
+}
+
+impl From<&DocumentEntity> for DocumentDto {
+    fn from(other: &DocumentEntity) -> Self {
+        Self {
+            document_id: other.document_id.to_string(),
+            version: other.version,
+            party_id: other.party_id.to_string(),
+            document_kind: other.document_kind,
+            file_name: other.file_name.to_string(),
+            content_type: other.content_type.to_string(),
+            uploaded_by: other.uploaded_by.to_string(),
+            retain_until: other.retain_until,
+            upload_url: None,
+            download_url: None,
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::core::domain::Configuration;
+    use crate::core::library::{DocumentKind, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::documents::domain::DocumentService;
+    use crate::documents::domain::model::DocumentEntity;
+    use crate::documents::factory;
+    use crate::gateway::factory::create_document_store;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn DocumentService> {
+        factory::create_document_service(&Configuration::new("test"), store).await
+    }
+
+    async fn add_party(store: RepositoryStore, email: &str, kind: PartyKind, role: Role) -> PartyEntity {
+        let mut party = PartyEntity::new(kind, email);
+        party.group_roles = vec![role.to_string()];
+        let _ = create_party_repository(store).await.create(&party).await.expect("should create party");
+        party
+    }
+
+    #[tokio::test]
+    async fn test_should_upload_get_remove_documents() {
+        let store = test_store(module_path!(), "test_should_upload_get_remove_documents");
+        let document_svc = sut_svc(store).await;
+        let librarian = add_party(store, "documents_librarian@example.com", PartyKind::Employee, Role::Librarian).await;
+        let patron = add_party(store, "documents_patron@example.com", PartyKind::Patron, Role::Regular).await;
+
+        // patrons cannot handle documents and only pdf and images are accepted
+        assert!(document_svc.request_upload(patron.party_id.as_str(), patron.party_id.as_str(), DocumentKind::IdCard,
+                                            "id.pdf", "application/pdf").await.is_err());
+        assert!(document_svc.request_upload(librarian.party_id.as_str(), patron.party_id.as_str(), DocumentKind::IdCard,
+                                            "id.txt", "text/plain").await.is_err());
+
+        let document = document_svc.request_upload(librarian.party_id.as_str(), patron.party_id.as_str(),
+                                                   DocumentKind::IdCard, "id.pdf", "application/pdf")
+            .await.expect("should request upload");
+        let upload_url = document.upload_url.clone().expect("should return upload url");
+        assert!(document.retain_until > Utc::now().naive_utc() + Duration::days(364));
+        // local upload urls are paths of the document store
+        std::fs::write(upload_url.trim_start_matches("file://"), b"%PDF-1.7").expect("should upload document");
+
+        let loaded = document_svc.get_document(librarian.party_id.as_str(), document.document_id.as_str())
+            .await.expect("should get document");
+        assert!(loaded.download_url.is_some());
+        assert!(document_svc.get_document(patron.party_id.as_str(), document.document_id.as_str()).await.is_err());
+        let documents = document_svc.find_documents(librarian.party_id.as_str(), patron.party_id.as_str())
+            .await.expect("should find documents");
+        assert_eq!(vec![document.document_id.to_string()], documents.iter().map(|d| d.document_id.to_string()).collect::<Vec<String>>());
+
+        document_svc.remove_document(librarian.party_id.as_str(), document.document_id.as_str()).await.expect("should remove document");
+        assert!(document_svc.get_document(librarian.party_id.as_str(), document.document_id.as_str()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_purge_expired_documents() {
+        let store = test_store(module_path!(), "test_should_purge_expired_documents");
+        let document_svc = sut_svc(store).await;
+        let document_repo = factory::create_document_repository(store).await;
+        let document_store = create_document_store(&Configuration::new("test")).await;
+        let librarian = add_party(store, "documents_purge_librarian@example.com", PartyKind::Employee, Role::Librarian).await;
+        let patron = add_party(store, "documents_purge_patron@example.com", PartyKind::Patron, Role::Regular).await;
+
+        let mut expired = DocumentEntity::new(patron.party_id.as_str(), DocumentKind::ProofOfAddress, "bill.png",
+                                              "image/png", librarian.party_id.as_str(), 30);
+        expired.retain_until = Utc::now().naive_utc() - Duration::days(1);
+        document_store.put(expired.object_key.as_str(), "image/png", vec![1, 2, 3]).await.expect("should put document");
+        let _ = document_repo.create(&expired).await.expect("should create document");
+
+        assert!(document_svc.purge_expired(100).await.expect("should purge documents") >= 1);
+        assert!(document_repo.get(expired.document_id.as_str()).await.is_err());
+        assert!(document_svc.find_documents(librarian.party_id.as_str(), patron.party_id.as_str())
+            .await.expect("should find documents").is_empty());
+    }
+}
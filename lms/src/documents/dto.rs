@@ -0,0 +1,27 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use crate::core::library::DocumentKind;
+use crate::utils::date::serializer;
+
+// DocumentDto describes a verification document of a party, pre-signed URLs are only set by the operation that
+// needs them and expire shortly
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct DocumentDto {
+    pub document_id: String,
+    pub version: i64,
+    pub party_id: String,
+    pub document_kind: DocumentKind,
+    pub file_name: String,
+    pub content_type: String,
+    pub uploaded_by: String,
+    #[serde(with = "serializer")]
+    pub retain_until: NaiveDateTime,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upload_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
@@ -0,0 +1,35 @@
+use crate::audit::factory::create_audit_service;
+use crate::core::domain::Configuration;
+use crate::core::repository::RepositoryStore;
+use crate::documents::domain::DocumentService;
+use crate::documents::domain::service::DocumentServiceImpl;
+use crate::documents::factory;
+use crate::documents::repository::DocumentRepository;
+use crate::documents::repository::ddb_document_repository::DDBDocumentRepository;
+use crate::gateway::factory::create_document_store;
+use crate::patrons::factory::create_patron_service;
+use crate::utils::ddb::{build_db_client, create_table, ScanGuard};
+
+pub(crate) async fn create_document_repository(store: RepositoryStore) -> Box<dyn DocumentRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBDocumentRepository::new(client, "party_documents", "party_documents_ndx")
+                .with_scan_guard(ScanGuard::new(store)))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("party_documents");
+            let _ = create_table(&client, table_name.as_str(), "document_id", "party_id", "created_at").await;
+            Box::new(DDBDocumentRepository::new(client, table_name.as_str(), store.table_name("party_documents_ndx").as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_document_service(config: &Configuration, store: RepositoryStore) -> Box<dyn DocumentService> {
+    let document_repo = factory::create_document_repository(store).await;
+    let document_store = create_document_store(config).await;
+    let patron_svc = create_patron_service(config, store).await;
+    let audit_svc = create_audit_service(config, store).await;
+    Box::new(DocumentServiceImpl::new(config, document_repo, document_store, patron_svc, audit_svc))
+}
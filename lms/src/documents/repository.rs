@@ -0,0 +1,18 @@
+pub mod ddb_document_repository;
+
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use crate::core::library::{LibraryResult, PaginatedResult};
+use crate::documents::domain::model::DocumentEntity;
+
+#[async_trait]
+pub(crate) trait DocumentRepository: Sync + Send {
+    async fn create(&self, entity: &DocumentEntity) -> LibraryResult<usize>;
+    async fn get(&self, document_id: &str) -> LibraryResult<DocumentEntity>;
+    async fn delete(&self, document_id: &str) -> LibraryResult<usize>;
+    // returns documents of the party with most recent first
+    async fn find_by_party(&self, party_id: &str) -> LibraryResult<Vec<DocumentEntity>>;
+    // returns documents whose retention ended before the given time
+    async fn find_expired(&self, before: NaiveDateTime,
+                          page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<DocumentEntity>>;
+}
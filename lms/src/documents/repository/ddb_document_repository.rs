@@ -0,0 +1,205 @@
+use std::cmp;
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::{NaiveDateTime, Utc};
+
+use crate::core::library::{DocumentKind, LibraryError, LibraryResult, PaginatedResult};
+use crate::documents::domain::model::DocumentEntity;
+use crate::documents::repository::DocumentRepository;
+use crate::utils::ddb::{from_ddb, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, string_date, to_ddb_page, ScanGuard};
+
+#[derive(Debug)]
+pub(crate) struct DDBDocumentRepository {
+    client: Client,
+    table_name: String,
+    index_name: String,
+    scan_guard: ScanGuard,
+}
+
+impl DDBDocumentRepository {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+            scan_guard: ScanGuard::default(),
+        }
+    }
+
+    pub(crate) fn with_scan_guard(mut self, scan_guard: ScanGuard) -> Self {
+        self.scan_guard = scan_guard;
+        self
+    }
+}
+
+#[async_trait]
+impl DocumentRepository for DDBDocumentRepository {
+    async fn create(&self, entity: &DocumentEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        let mut item = parse_item(val)?;
+        item.insert("retain_until".to_string(), string_date(entity.retain_until));
+        item.insert("created_at".to_string(), string_date(entity.created_at));
+        item.insert("updated_at".to_string(), string_date(entity.updated_at));
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(document_id)")
+            .set_item(Some(item))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn get(&self, document_id: &str) -> LibraryResult<DocumentEntity> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .query()
+            .table_name(table_name)
+            .limit(1)
+            .consistent_read(true)
+            .key_condition_expression("document_id = :document_id")
+            .expression_attribute_values(":document_id", AttributeValue::S(document_id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).and_then(|req| {
+            if let Some(map) = req.items.as_ref().and_then(|items| items.first()) {
+                return Ok(DocumentEntity::from(map));
+            }
+            Err(LibraryError::not_found(format!("document not found for {}", document_id).as_str()))
+        })
+    }
+
+    async fn delete(&self, document_id: &str) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client.delete_item()
+            .table_name(table_name)
+            .key("document_id", AttributeValue::S(document_id.to_string()))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn find_by_party(&self, party_id: &str) -> LibraryResult<Vec<DocumentEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let mut documents = vec![];
+        let mut last_key: Option<HashMap<String, AttributeValue>> = None;
+        loop {
+            let res = self.client
+                .query()
+                .table_name(table_name)
+                .index_name(index_name)
+                .consistent_read(false)
+                .scan_index_forward(false)
+                .set_exclusive_start_key(last_key)
+                .key_condition_expression("party_id = :party_id")
+                .expression_attribute_values(":party_id", AttributeValue::S(party_id.to_string()))
+                .send()
+                .await.map_err(LibraryError::from)?;
+            documents.extend(res.items.as_ref().unwrap_or(&vec![]).iter().map(DocumentEntity::from));
+            last_key = res.last_evaluated_key().cloned();
+            if last_key.is_none() {
+                break;
+            }
+        }
+        Ok(documents)
+    }
+
+    // retention purges run daily so a filtered scan is used instead of an index of retention dates
+    async fn find_expired(&self, before: NaiveDateTime,
+                          page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<DocumentEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        self.scan_guard.check(table_name)?;
+        self.client
+            .scan()
+            .table_name(table_name)
+            .consistent_read(false)
+            .filter_expression("retain_until < :before")
+            .expression_attribute_values(":before", string_date(before))
+            .set_exclusive_start_key(to_ddb_page(page, &HashMap::new()))
+            .limit(cmp::min(page_size, 500) as i32)
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(DocumentEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for DocumentEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        DocumentEntity {
+            document_id: parse_string_attribute("document_id", map).unwrap_or_else(|| String::from("")),
+            version: parse_number_attribute("version", map),
+            party_id: parse_string_attribute("party_id", map).unwrap_or_else(|| String::from("")),
+            document_kind: DocumentKind::from(parse_string_attribute("document_kind", map).unwrap_or_else(|| String::from(""))),
+            file_name: parse_string_attribute("file_name", map).unwrap_or_else(|| String::from("")),
+            content_type: parse_string_attribute("content_type", map).unwrap_or_else(|| String::from("")),
+            object_key: parse_string_attribute("object_key", map).unwrap_or_else(|| String::from("")),
+            uploaded_by: parse_string_attribute("uploaded_by", map).unwrap_or_else(|| String::from("")),
+            retain_until: parse_date_attribute("retain_until", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+    use chrono::{Duration, Utc};
+    use uuid::Uuid;
+
+    use crate::core::library::DocumentKind;
+    use crate::core::repository::RepositoryStore;
+    use crate::documents::domain::model::DocumentEntity;
+    use crate::documents::repository::DocumentRepository;
+    use crate::documents::repository::ddb_document_repository::DDBDocumentRepository;
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("party_documents").as_str(), "document_id", "party_id", "created_at").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_create_find_delete_documents() {
+        let store = test_store(module_path!(), "test_should_create_find_delete_documents");
+        let repo = DDBDocumentRepository::new(build_client(store).await, store.table_name("party_documents").as_str(), store.table_name("party_documents_ndx").as_str());
+        let party_id = Uuid::new_v4().to_string();
+        let document = DocumentEntity::new(party_id.as_str(), DocumentKind::Passport, "passport.pdf",
+                                           "application/pdf", "librarian", 30);
+        assert_eq!(1, repo.create(&document).await.expect("should create document"));
+        assert!(repo.create(&document).await.is_err());
+
+        let loaded = repo.get(document.document_id.as_str()).await.expect("should get document");
+        assert_eq!(document.object_key, loaded.object_key);
+        assert_eq!(DocumentKind::Passport, loaded.document_kind);
+        assert_eq!(1, repo.find_by_party(party_id.as_str()).await.expect("should find documents").len());
+
+        let mut expired = DocumentEntity::new(party_id.as_str(), DocumentKind::IdCard, "id.png",
+                                              "image/png", "librarian", 30);
+        expired.retain_until = Utc::now().naive_utc() - Duration::days(1);
+        assert_eq!(1, repo.create(&expired).await.expect("should create document"));
+        let mut found = vec![];
+        let mut page: Option<String> = None;
+        loop {
+            let res = repo.find_expired(Utc::now().naive_utc(), page.as_deref(), 100).await.expect("should find expired");
+            found.extend(res.records.into_iter().map(|d| d.document_id));
+            page = res.next_page;
+            if page.is_none() {
+                break;
+            }
+        }
+        assert!(found.contains(&expired.document_id));
+        assert!(!found.contains(&document.document_id));
+
+        assert_eq!(1, repo.delete(document.document_id.as_str()).await.expect("should delete document"));
+        assert!(repo.get(document.document_id.as_str()).await.is_err());
+        assert_eq!(1, repo.find_by_party(party_id.as_str()).await.expect("should find documents").len());
+    }
+}
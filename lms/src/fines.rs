@@ -0,0 +1,7 @@
+pub mod domain;
+pub mod command;
+pub mod dto;
+pub mod factory;
+pub mod repository;
+pub mod statement;
+pub mod controller;
@@ -0,0 +1,8 @@
+pub mod adjust_fine_cmd;
+pub mod assess_fine_cmd;
+pub mod confirm_payment_cmd;
+pub mod find_adjustments_cmd;
+pub mod find_fines_cmd;
+pub mod get_fine_cmd;
+pub mod get_statement_cmd;
+pub mod pay_fine_cmd;
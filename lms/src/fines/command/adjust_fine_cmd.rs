@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::core::library::{AdjustmentKind, AdjustmentReason};
+use crate::fines::domain::FineService;
+use crate::fines::dto::FineAdjustmentDto;
+
+pub(crate) struct AdjustFineCommand {
+    fine_service: Box<dyn FineService>,
+}
+
+impl AdjustFineCommand {
+    pub(crate) fn new(fine_service: Box<dyn FineService>) -> Self {
+        Self {
+            fine_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AdjustFineCommandRequest {
+    #[serde(default)]
+    pub(crate) adjusted_by: String,
+    #[serde(default)]
+    pub(crate) fine_id: String,
+    adjustment_kind: AdjustmentKind,
+    reason: AdjustmentReason,
+    amount: i64,
+    #[serde(default)]
+    note: String,
+}
+
+impl AdjustFineCommandRequest {
+    pub fn new(adjusted_by: &str, fine_id: &str, adjustment_kind: AdjustmentKind, reason: AdjustmentReason, amount: i64) -> Self {
+        Self {
+            adjusted_by: adjusted_by.to_string(),
+            fine_id: fine_id.to_string(),
+            adjustment_kind,
+            reason,
+            amount,
+            note: String::new(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AdjustFineCommandResponse {
+    pub adjustment: FineAdjustmentDto,
+}
+
+impl AdjustFineCommandResponse {
+    pub fn new(adjustment: FineAdjustmentDto) -> Self {
+        Self {
+            adjustment,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<AdjustFineCommandRequest, AdjustFineCommandResponse> for AdjustFineCommand {
+    async fn execute(&self, req: AdjustFineCommandRequest) -> Result<AdjustFineCommandResponse, CommandError> {
+        let mut adjustment = FineAdjustmentDto::new(req.adjusted_by.as_str(), req.fine_id.as_str(),
+                                                    req.adjustment_kind, req.reason, req.amount);
+        adjustment.note = req.note;
+        self.fine_service.adjust(&adjustment)
+            .await.map_err(CommandError::from).map(AdjustFineCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{AdjustmentKind, AdjustmentReason, PartyKind, Role};
+    use crate::fines::command::adjust_fine_cmd::{AdjustFineCommand, AdjustFineCommandRequest};
+    use crate::fines::dto::FineDto;
+    use crate::fines::factory::create_fine_service;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_run_adjust_fine() {
+        let store = test_store(module_path!(), "test_should_run_adjust_fine");
+        let svc = create_fine_service(&Configuration::new("test"), store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "adjust_fine@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create party");
+        let fine = svc.assess(&FineDto::new(librarian.party_id.as_str(), "patron1", "overdue", 300))
+            .await.expect("should assess fine");
+
+        let sut_cmd = AdjustFineCommand::new(svc);
+        let res = sut_cmd.execute(AdjustFineCommandRequest::new(
+            librarian.party_id.as_str(), fine.fine_id.as_str(), AdjustmentKind::Waive, AdjustmentReason::Hardship, 100))
+            .await.expect("should adjust fine");
+        assert_eq!(200, res.adjustment.balance_after);
+        assert_eq!(AdjustmentReason::Hardship, res.adjustment.reason);
+    }
+}
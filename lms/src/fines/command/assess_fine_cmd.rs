@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::fines::domain::FineService;
+use crate::fines::dto::FineDto;
+
+pub(crate) struct AssessFineCommand {
+    fine_service: Box<dyn FineService>,
+}
+
+impl AssessFineCommand {
+    pub(crate) fn new(fine_service: Box<dyn FineService>) -> Self {
+        Self {
+            fine_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AssessFineCommandRequest {
+    #[serde(default)]
+    pub(crate) assessed_by: String,
+    patron_id: String,
+    reason: String,
+    amount: i64,
+    #[serde(default)]
+    checkout_id: String,
+}
+
+impl AssessFineCommandRequest {
+    pub fn new(assessed_by: &str, patron_id: &str, reason: &str, amount: i64) -> Self {
+        Self {
+            assessed_by: assessed_by.to_string(),
+            patron_id: patron_id.to_string(),
+            reason: reason.to_string(),
+            amount,
+            checkout_id: String::new(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AssessFineCommandResponse {
+    pub fine: FineDto,
+}
+
+impl AssessFineCommandResponse {
+    pub fn new(fine: FineDto) -> Self {
+        Self {
+            fine,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<AssessFineCommandRequest, AssessFineCommandResponse> for AssessFineCommand {
+    async fn execute(&self, req: AssessFineCommandRequest) -> Result<AssessFineCommandResponse, CommandError> {
+        let mut fine = FineDto::new(req.assessed_by.as_str(), req.patron_id.as_str(), req.reason.as_str(), req.amount);
+        fine.checkout_id = req.checkout_id;
+        self.fine_service.assess(&fine)
+            .await.map_err(CommandError::from).map(AssessFineCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{FineStatus, PartyKind, Role};
+    use crate::fines::command::assess_fine_cmd::{AssessFineCommand, AssessFineCommandRequest};
+    use crate::fines::factory::create_fine_service;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_run_assess_fine() {
+        let store = test_store(module_path!(), "test_should_run_assess_fine");
+        let sut_cmd = AssessFineCommand::new(create_fine_service(&Configuration::new("test"), store).await);
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "assess_fine@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let patron = PartyEntity::new(PartyKind::Patron, "assess_fine_patron@example.com");
+        let party_repo = create_party_repository(store).await;
+        let _ = party_repo.create(&librarian).await.expect("should create party");
+        let _ = party_repo.create(&patron).await.expect("should create party");
+
+        let res = sut_cmd.execute(AssessFineCommandRequest::new(
+            librarian.party_id.as_str(), patron.party_id.as_str(), "damaged cover", 500)).await.expect("should assess fine");
+        assert_eq!(FineStatus::Outstanding, res.fine.fine_status);
+        assert_eq!(500, res.fine.balance());
+    }
+}
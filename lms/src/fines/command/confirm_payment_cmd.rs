@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::fines::domain::FineService;
+use crate::fines::dto::FinePaymentDto;
+
+pub(crate) struct ConfirmPaymentCommand {
+    fine_service: Box<dyn FineService>,
+}
+
+impl ConfirmPaymentCommand {
+    pub(crate) fn new(fine_service: Box<dyn FineService>) -> Self {
+        Self {
+            fine_service,
+        }
+    }
+}
+
+// payload is the raw body of the webhook because the signature covers its exact bytes
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ConfirmPaymentCommandRequest {
+    pub(crate) payload: String,
+    pub(crate) signature: String,
+    pub(crate) received_at: i64,
+}
+
+impl ConfirmPaymentCommandRequest {
+    pub fn new(payload: &str, signature: &str, received_at: i64) -> Self {
+        Self {
+            payload: payload.to_string(),
+            signature: signature.to_string(),
+            received_at,
+        }
+    }
+}
+
+
+// events other than changes of payments are acknowledged without a payment
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ConfirmPaymentCommandResponse {
+    pub payment: Option<FinePaymentDto>,
+}
+
+impl ConfirmPaymentCommandResponse {
+    pub fn new(payment: Option<FinePaymentDto>) -> Self {
+        Self {
+            payment,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<ConfirmPaymentCommandRequest, ConfirmPaymentCommandResponse> for ConfirmPaymentCommand {
+    async fn execute(&self, req: ConfirmPaymentCommandRequest) -> Result<ConfirmPaymentCommandResponse, CommandError> {
+        self.fine_service.confirm_payment(req.payload.as_str(), req.signature.as_str(), req.received_at)
+            .await.map_err(CommandError::from).map(ConfirmPaymentCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::fines::command::confirm_payment_cmd::{ConfirmPaymentCommand, ConfirmPaymentCommandRequest};
+    use crate::fines::factory::create_fine_service;
+    use crate::gateway::payments::sign_webhook;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_acknowledge_other_events() {
+        let store = test_store(module_path!(), "test_should_acknowledge_other_events");
+        let config = Configuration::new("test");
+        let sut_cmd = ConfirmPaymentCommand::new(create_fine_service(&config, store).await);
+        let payload = json!({"id": "evt_1", "type": "customer.created", "data": {"object": {"id": "cus_1"}}}).to_string();
+        let now = Utc::now().timestamp();
+        let signature = sign_webhook(config.payment_webhook_secret.as_str(), payload.as_str(), now);
+        let res = sut_cmd.execute(ConfirmPaymentCommandRequest::new(payload.as_str(), signature.as_str(), now))
+            .await.expect("should acknowledge event");
+        assert!(res.payment.is_none());
+        assert!(sut_cmd.execute(ConfirmPaymentCommandRequest::new(payload.as_str(), "t=1,v1=00", now)).await.is_err());
+    }
+}
@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::fines::domain::FineService;
+use crate::fines::dto::FineAdjustmentDto;
+
+pub(crate) struct FindAdjustmentsCommand {
+    fine_service: Box<dyn FineService>,
+}
+
+impl FindAdjustmentsCommand {
+    pub(crate) fn new(fine_service: Box<dyn FineService>) -> Self {
+        Self {
+            fine_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindAdjustmentsCommandRequest {
+    pub(crate) requested_by: String,
+    pub(crate) fine_id: String,
+}
+
+impl FindAdjustmentsCommandRequest {
+    pub fn new(requested_by: &str, fine_id: &str) -> Self {
+        Self {
+            requested_by: requested_by.to_string(),
+            fine_id: fine_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindAdjustmentsCommandResponse {
+    pub adjustments: Vec<FineAdjustmentDto>,
+}
+
+impl FindAdjustmentsCommandResponse {
+    pub fn new(adjustments: Vec<FineAdjustmentDto>) -> Self {
+        Self {
+            adjustments,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<FindAdjustmentsCommandRequest, FindAdjustmentsCommandResponse> for FindAdjustmentsCommand {
+    async fn execute(&self, req: FindAdjustmentsCommandRequest) -> Result<FindAdjustmentsCommandResponse, CommandError> {
+        self.fine_service.find_adjustments(req.requested_by.as_str(), req.fine_id.as_str())
+            .await.map_err(CommandError::from).map(FindAdjustmentsCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::fines::command::find_adjustments_cmd::{FindAdjustmentsCommand, FindAdjustmentsCommandRequest};
+    use crate::fines::factory::create_fine_service;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_not_find_adjustments_of_unknown_fine() {
+        let store = test_store(module_path!(), "test_should_not_find_adjustments_of_unknown_fine");
+        let sut_cmd = FindAdjustmentsCommand::new(create_fine_service(&Configuration::new("test"), store).await);
+        assert!(sut_cmd.execute(FindAdjustmentsCommandRequest::new("patron1", "unknown_fine")).await.is_err());
+    }
+}
@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::fines::domain::FineService;
+use crate::fines::dto::FineDto;
+
+pub(crate) struct FindFinesCommand {
+    fine_service: Box<dyn FineService>,
+}
+
+impl FindFinesCommand {
+    pub(crate) fn new(fine_service: Box<dyn FineService>) -> Self {
+        Self {
+            fine_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindFinesCommandRequest {
+    #[serde(default)]
+    pub(crate) requested_by: String,
+    pub(crate) patron_id: String,
+}
+
+impl FindFinesCommandRequest {
+    pub fn new(requested_by: &str, patron_id: &str) -> Self {
+        Self {
+            requested_by: requested_by.to_string(),
+            patron_id: patron_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindFinesCommandResponse {
+    pub fines: Vec<FineDto>,
+    // sum of the balances of the fines that are still outstanding
+    pub outstanding: i64,
+}
+
+impl FindFinesCommandResponse {
+    pub fn new(fines: Vec<FineDto>) -> Self {
+        let outstanding = fines.iter().map(|f| f.balance().max(0)).sum();
+        Self {
+            fines,
+            outstanding,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<FindFinesCommandRequest, FindFinesCommandResponse> for FindFinesCommand {
+    async fn execute(&self, req: FindFinesCommandRequest) -> Result<FindFinesCommandResponse, CommandError> {
+        self.fine_service.find_patron_fines(req.requested_by.as_str(), req.patron_id.as_str())
+            .await.map_err(CommandError::from).map(FindFinesCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::fines::command::find_fines_cmd::{FindFinesCommand, FindFinesCommandRequest};
+    use crate::fines::factory::create_fine_service;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_run_find_own_fines() {
+        let store = test_store(module_path!(), "test_should_run_find_own_fines");
+        let sut_cmd = FindFinesCommand::new(create_fine_service(&Configuration::new("test"), store).await);
+        let res = sut_cmd.execute(FindFinesCommandRequest::new("patron_without_fines", "patron_without_fines"))
+            .await.expect("should find fines");
+        assert!(res.fines.is_empty());
+        assert_eq!(0, res.outstanding);
+    }
+}
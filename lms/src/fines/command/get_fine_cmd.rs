@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::fines::domain::FineService;
+use crate::fines::dto::FineDto;
+
+pub(crate) struct GetFineCommand {
+    fine_service: Box<dyn FineService>,
+}
+
+impl GetFineCommand {
+    pub(crate) fn new(fine_service: Box<dyn FineService>) -> Self {
+        Self {
+            fine_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetFineCommandRequest {
+    pub(crate) requested_by: String,
+    pub(crate) fine_id: String,
+}
+
+impl GetFineCommandRequest {
+    pub fn new(requested_by: &str, fine_id: &str) -> Self {
+        Self {
+            requested_by: requested_by.to_string(),
+            fine_id: fine_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetFineCommandResponse {
+    pub fine: FineDto,
+}
+
+impl GetFineCommandResponse {
+    pub fn new(fine: FineDto) -> Self {
+        Self {
+            fine,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<GetFineCommandRequest, GetFineCommandResponse> for GetFineCommand {
+    async fn execute(&self, req: GetFineCommandRequest) -> Result<GetFineCommandResponse, CommandError> {
+        self.fine_service.get_fine(req.requested_by.as_str(), req.fine_id.as_str())
+            .await.map_err(CommandError::from).map(GetFineCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::fines::command::get_fine_cmd::{GetFineCommand, GetFineCommandRequest};
+    use crate::fines::factory::create_fine_service;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_not_get_unknown_fine() {
+        let store = test_store(module_path!(), "test_should_not_get_unknown_fine");
+        let sut_cmd = GetFineCommand::new(create_fine_service(&Configuration::new("test"), store).await);
+        assert!(sut_cmd.execute(GetFineCommandRequest::new("patron1", "unknown_fine")).await.is_err());
+    }
+}
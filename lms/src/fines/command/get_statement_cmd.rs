@@ -0,0 +1,101 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::fines::domain::FineService;
+use crate::fines::dto::PatronStatementDto;
+use crate::fines::statement::StatementFormat;
+
+pub(crate) struct GetStatementCommand {
+    fine_service: Box<dyn FineService>,
+}
+
+impl GetStatementCommand {
+    pub(crate) fn new(fine_service: Box<dyn FineService>) -> Self {
+        Self {
+            fine_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetStatementCommandRequest {
+    #[serde(default)]
+    pub(crate) requested_by: String,
+    #[serde(default)]
+    pub(crate) patron_id: String,
+    // first and last day of the statement in YYYY-MM-DD
+    #[serde(default)]
+    pub(crate) from: Option<String>,
+    #[serde(default)]
+    pub(crate) to: Option<String>,
+    // json or html, overrides the Accept header
+    #[serde(default)]
+    pub(crate) format: Option<String>,
+    #[serde(skip)]
+    pub(crate) accept: Option<String>,
+}
+
+impl GetStatementCommandRequest {
+    pub fn new(requested_by: &str, patron_id: &str) -> Self {
+        Self {
+            requested_by: requested_by.to_string(),
+            patron_id: patron_id.to_string(),
+            from: None,
+            to: None,
+            format: None,
+            accept: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetStatementCommandResponse {
+    pub statement: PatronStatementDto,
+    #[serde(skip)]
+    pub format: StatementFormat,
+}
+
+impl GetStatementCommandResponse {
+    pub fn new(statement: PatronStatementDto, format: StatementFormat) -> Self {
+        Self {
+            statement,
+            format,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<GetStatementCommandRequest, GetStatementCommandResponse> for GetStatementCommand {
+    async fn execute(&self, req: GetStatementCommandRequest) -> Result<GetStatementCommandResponse, CommandError> {
+        let format = StatementFormat::negotiate(req.format.as_deref(), req.accept.as_deref()).map_err(CommandError::from)?;
+        self.fine_service.statement(req.requested_by.as_str(), req.patron_id.as_str(), req.from.as_deref(), req.to.as_deref())
+            .await.map_err(CommandError::from).map(|statement| GetStatementCommandResponse::new(statement, format))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::fines::command::get_statement_cmd::{GetStatementCommand, GetStatementCommandRequest};
+    use crate::fines::factory::create_fine_service;
+    use crate::fines::statement::StatementFormat;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_run_get_own_statement() {
+        let store = test_store(module_path!(), "test_should_run_get_own_statement");
+        let sut_cmd = GetStatementCommand::new(create_fine_service(&Configuration::new("test"), store).await);
+        let mut req = GetStatementCommandRequest::new("patron1", "patron1");
+        req.accept = Some("text/html".to_string());
+        let res = sut_cmd.execute(req).await.expect("should get statement");
+        assert_eq!(StatementFormat::Html, res.format);
+        assert!(res.statement.lines.is_empty());
+        assert_eq!(0, res.statement.closing_balance);
+
+        let mut req = GetStatementCommandRequest::new("patron1", "patron1");
+        req.from = Some("yesterday".to_string());
+        assert!(sut_cmd.execute(req).await.is_err());
+    }
+}
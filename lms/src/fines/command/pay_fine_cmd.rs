@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::fines::domain::FineService;
+use crate::fines::dto::FinePaymentDto;
+
+pub(crate) struct PayFineCommand {
+    fine_service: Box<dyn FineService>,
+}
+
+impl PayFineCommand {
+    pub(crate) fn new(fine_service: Box<dyn FineService>) -> Self {
+        Self {
+            fine_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct PayFineCommandRequest {
+    pub(crate) paid_by: String,
+    pub(crate) fine_id: String,
+    pub(crate) idempotency_key: String,
+}
+
+impl PayFineCommandRequest {
+    pub fn new(paid_by: &str, fine_id: &str, idempotency_key: &str) -> Self {
+        Self {
+            paid_by: paid_by.to_string(),
+            fine_id: fine_id.to_string(),
+            idempotency_key: idempotency_key.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct PayFineCommandResponse {
+    pub payment: FinePaymentDto,
+}
+
+impl PayFineCommandResponse {
+    pub fn new(payment: FinePaymentDto) -> Self {
+        Self {
+            payment,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<PayFineCommandRequest, PayFineCommandResponse> for PayFineCommand {
+    async fn execute(&self, req: PayFineCommandRequest) -> Result<PayFineCommandResponse, CommandError> {
+        self.fine_service.pay(req.paid_by.as_str(), req.fine_id.as_str(), req.idempotency_key.as_str())
+            .await.map_err(CommandError::from).map(PayFineCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::fines::command::pay_fine_cmd::{PayFineCommand, PayFineCommandRequest};
+    use crate::fines::factory::create_fine_service;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_require_idempotency_key() {
+        let store = test_store(module_path!(), "test_should_require_idempotency_key");
+        let sut_cmd = PayFineCommand::new(create_fine_service(&Configuration::new("test"), store).await);
+        assert!(sut_cmd.execute(PayFineCommandRequest::new("patron1", "fine1", "")).await.is_err());
+        assert!(sut_cmd.execute(PayFineCommandRequest::new("patron1", "unknown_fine", "key1")).await.is_err());
+    }
+}
@@ -0,0 +1,145 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::{header, HeaderMap},
+    middleware,
+    response::{IntoResponse, Json, Response},
+    routing::{get, post},
+    Router,
+};
+use chrono::Utc;
+use serde_json::{Value};
+use crate::core::controller::{AppState, command_bus, json_to_server_error, with_common_layers, ServerError};
+use crate::credentials::controller::authenticate;
+use crate::credentials::dto::ClaimsDto;
+use crate::fines::command::adjust_fine_cmd::{AdjustFineCommand, AdjustFineCommandRequest, AdjustFineCommandResponse};
+use crate::fines::command::assess_fine_cmd::{AssessFineCommand, AssessFineCommandRequest, AssessFineCommandResponse};
+use crate::fines::command::confirm_payment_cmd::{ConfirmPaymentCommand, ConfirmPaymentCommandRequest, ConfirmPaymentCommandResponse};
+use crate::fines::command::find_adjustments_cmd::{FindAdjustmentsCommand, FindAdjustmentsCommandRequest, FindAdjustmentsCommandResponse};
+use crate::fines::command::find_fines_cmd::{FindFinesCommand, FindFinesCommandRequest, FindFinesCommandResponse};
+use crate::fines::command::get_fine_cmd::{GetFineCommand, GetFineCommandRequest, GetFineCommandResponse};
+use crate::fines::command::get_statement_cmd::{GetStatementCommand, GetStatementCommandRequest, GetStatementCommandResponse};
+use crate::fines::command::pay_fine_cmd::{PayFineCommand, PayFineCommandRequest, PayFineCommandResponse};
+use crate::fines::domain::FineService;
+use crate::fines::factory;
+use crate::fines::statement::{to_html, StatementFormat};
+use crate::utils::ddb::{build_db_client, create_table};
+
+async fn build_service(state: AppState) -> Box<dyn FineService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_table(&client, state.store.table_name("fines").as_str(), "fine_id", "patron_id", "assessed_at").await;
+    let _ = create_table(&client, state.store.table_name("fine_payments").as_str(), "payment_id", "patron_id", "created_at").await;
+    let _ = create_table(&client, state.store.table_name("fine_adjustments").as_str(), "adjustment_id", "patron_id", "created_at").await;
+    let _ = create_table(&client, state.store.table_name("journal").as_str(), "entry_id", "branch_id", "posted_at").await;
+    factory::create_fine_service(&state.config, state.store).await
+}
+
+pub(crate) async fn assess_fine(
+    State(state): State<AppState>,
+    Extension(claims): Extension<ClaimsDto>,
+    json: Json<Value>) -> Result<Json<AssessFineCommandResponse>, ServerError> {
+    let mut req: AssessFineCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.assessed_by = claims.sub;
+    let svc = build_service(state).await;
+    let res = command_bus().register(AssessFineCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn find_fines(
+    State(state): State<AppState>,
+    Extension(claims): Extension<ClaimsDto>,
+    Query(mut req): Query<FindFinesCommandRequest>) -> Result<Json<FindFinesCommandResponse>, ServerError> {
+    req.requested_by = claims.sub;
+    let svc = build_service(state).await;
+    let res = command_bus().register(FindFinesCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn get_fine(
+    State(state): State<AppState>,
+    Extension(claims): Extension<ClaimsDto>,
+    Path(fine_id): Path<String>) -> Result<Json<GetFineCommandResponse>, ServerError> {
+    let req = GetFineCommandRequest::new(claims.sub.as_str(), fine_id.as_str());
+    let svc = build_service(state).await;
+    let res = command_bus().register(GetFineCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// clients send an Idempotency-Key header that stays the same when the request is retried, so that a payment is only
+// started once however often the request reaches the server
+pub(crate) async fn pay_fine(
+    State(state): State<AppState>,
+    Extension(claims): Extension<ClaimsDto>,
+    Path(fine_id): Path<String>,
+    headers: HeaderMap) -> Result<Json<PayFineCommandResponse>, ServerError> {
+    let idempotency_key = headers.get("idempotency-key").and_then(|v| v.to_str().ok()).unwrap_or_default();
+    let req = PayFineCommandRequest::new(claims.sub.as_str(), fine_id.as_str(), idempotency_key);
+    let svc = build_service(state).await;
+    let res = command_bus().register(PayFineCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn adjust_fine(
+    State(state): State<AppState>,
+    Extension(claims): Extension<ClaimsDto>,
+    Path(fine_id): Path<String>,
+    json: Json<Value>) -> Result<Json<AdjustFineCommandResponse>, ServerError> {
+    let mut req: AdjustFineCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.adjusted_by = claims.sub;
+    req.fine_id = fine_id;
+    let svc = build_service(state).await;
+    let res = command_bus().register(AdjustFineCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn find_adjustments(
+    State(state): State<AppState>,
+    Extension(claims): Extension<ClaimsDto>,
+    Path(fine_id): Path<String>) -> Result<Json<FindAdjustmentsCommandResponse>, ServerError> {
+    let req = FindAdjustmentsCommandRequest::new(claims.sub.as_str(), fine_id.as_str());
+    let svc = build_service(state).await;
+    let res = command_bus().register(FindAdjustmentsCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// statement of a patron for `?from=YYYY-MM-DD&to=YYYY-MM-DD`, browsers and PDF renderers that ask for text/html (or
+// `?format=html`) get a printable page
+pub(crate) async fn get_statement(
+    State(state): State<AppState>,
+    Extension(claims): Extension<ClaimsDto>,
+    Path(patron_id): Path<String>,
+    headers: HeaderMap,
+    Query(mut req): Query<GetStatementCommandRequest>) -> Result<Response, ServerError> {
+    req.requested_by = claims.sub;
+    req.patron_id = patron_id;
+    req.accept = headers.get(header::ACCEPT).and_then(|v| v.to_str().ok()).map(str::to_string);
+    let svc = build_service(state).await;
+    let res: GetStatementCommandResponse = command_bus().register(GetStatementCommand::new(svc)).dispatch(req).await?;
+    Ok(match res.format {
+        StatementFormat::Json => Json(res).into_response(),
+        StatementFormat::Html => ([(header::CONTENT_TYPE, "text/html; charset=utf-8")], to_html(&res.statement)).into_response(),
+    })
+}
+
+// webhooks of the payment provider are authenticated by their signature instead of a token
+pub(crate) async fn payment_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: String) -> Result<Json<ConfirmPaymentCommandResponse>, ServerError> {
+    let signature = headers.get("stripe-signature").and_then(|v| v.to_str().ok()).unwrap_or_default();
+    let req = ConfirmPaymentCommandRequest::new(body.as_str(), signature, Utc::now().timestamp());
+    let svc = build_service(state).await;
+    let res = command_bus().register(ConfirmPaymentCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/fines", post(assess_fine).get(find_fines))
+        .route("/fines/:id", get(get_fine))
+        .route("/fines/:id/payments", post(pay_fine))
+        .route("/fines/:id/adjustments", post(adjust_fine).get(find_adjustments))
+        .route("/patrons/:id/statement", get(get_statement))
+        .route_layer(middleware::from_fn_with_state(state.clone(), authenticate))
+        .route("/fines/payments/webhook", post(payment_webhook));
+    with_common_layers(router, state)
+}
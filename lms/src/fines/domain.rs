@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+use crate::core::library::LibraryResult;
+use crate::fines::dto::{FineAdjustmentDto, FineDto, FinePaymentDto, FineTotalsDto, PatronStatementDto};
+
+pub mod model;
+pub mod query;
+pub mod service;
+
+// accounts of the ledger that fines are posted to, the receivable account sums the balances of the fines of a branch
+// and the waived account reduces the revenue of fines by what was waived
+pub(crate) const RECEIVABLE_ACCOUNT: &str = "receivable:fines";
+pub(crate) const REVENUE_ACCOUNT: &str = "revenue:fines";
+pub(crate) const WAIVED_ACCOUNT: &str = "revenue:fines_waived";
+pub(crate) const CASH_ACCOUNT: &str = "cash:fines";
+
+// read side of fines, queries never change fines or payments
+#[async_trait]
+pub(crate) trait FineQueryService: Sync + Send {
+    async fn find_fine_by_id(&self, fine_id: &str) -> LibraryResult<FineDto>;
+    // fines of the patron, most recently assessed first
+    async fn find_fines_by_patron(&self, patron_id: &str) -> LibraryResult<Vec<FineDto>>;
+    // waivers and refunds of the fine, oldest first
+    async fn find_adjustments_by_fine(&self, fine_id: &str) -> LibraryResult<Vec<FineAdjustmentDto>>;
+    // payments of the patron in any status, most recent first
+    async fn find_payments_by_patron(&self, patron_id: &str) -> LibraryResult<Vec<FinePaymentDto>>;
+    // waivers and refunds of fines of the patron, oldest first
+    async fn find_adjustments_by_patron(&self, patron_id: &str) -> LibraryResult<Vec<FineAdjustmentDto>>;
+    // walks all fines to sum those of the branch, only meant for jobs such as reconciling the ledger
+    async fn branch_totals(&self, branch_id: &str, page_size: usize) -> LibraryResult<FineTotalsDto>;
+}
+
+#[async_trait]
+pub(crate) trait FineService: FineQueryService {
+    // only librarians and admins can assess fines
+    async fn assess(&self, fine: &FineDto) -> LibraryResult<FineDto>;
+    // fines are visible to the patron they were charged to and to librarians and admins
+    async fn get_fine(&self, requested_by: &str, fine_id: &str) -> LibraryResult<FineDto>;
+    async fn find_patron_fines(&self, requested_by: &str, patron_id: &str) -> LibraryResult<Vec<FineDto>>;
+    // starts paying the balance of the fine, requests with the same idempotency key return the same payment so
+    // that the patron is never charged twice for a retried request
+    async fn pay(&self, paid_by: &str, fine_id: &str, idempotency_key: &str) -> LibraryResult<FinePaymentDto>;
+    // applies a signed webhook of the payment provider, the fine is only changed when the provider confirms that the
+    // payment succeeded and each payment is applied once however often the webhook is delivered
+    async fn confirm_payment(&self, payload: &str, signature: &str, now: i64) -> LibraryResult<Option<FinePaymentDto>>;
+    // waives part of the balance or refunds an overcharge, librarians can adjust a fine up to the approval threshold of
+    // the configuration in total and adjustments beyond it need an admin
+    async fn adjust(&self, adjustment: &FineAdjustmentDto) -> LibraryResult<FineAdjustmentDto>;
+    async fn find_adjustments(&self, requested_by: &str, fine_id: &str) -> LibraryResult<Vec<FineAdjustmentDto>>;
+    // itemized fines, payments, waivers and refunds of the patron between two days in YYYY-MM-DD, visible to the
+    // patron and to librarians and admins
+    async fn statement(&self, requested_by: &str, patron_id: &str, from: Option<&str>, to: Option<&str>) -> LibraryResult<PatronStatementDto>;
+}
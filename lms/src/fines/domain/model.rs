@@ -0,0 +1,264 @@
+use chrono::{NaiveDateTime, Utc};
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::core::domain::Identifiable;
+use crate::core::library::{AdjustmentKind, AdjustmentReason, FineStatus, LibraryError, LibraryResult};
+use crate::gateway::payments::PaymentStatus;
+use crate::utils::date::serializer;
+
+// FineEntity abstracts a fine charged to a patron, payments are added to the paid amount once the payment provider
+// confirms them and adjustments of librarians to the waived or refunded amount, the fine is settled when they cover
+// its amount
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct FineEntity {
+    pub fine_id: String,
+    pub version: i64,
+    pub branch_id: String,
+    pub patron_id: String,
+    pub checkout_id: String,
+    pub reason: String,
+    pub amount: i64,
+    pub paid: i64,
+    #[serde(default)]
+    pub waived: i64,
+    #[serde(default)]
+    pub refunded: i64,
+    pub fine_status: FineStatus,
+    #[serde(default)]
+    pub payment_ids: Vec<String>,
+    #[serde(default)]
+    pub adjustment_ids: Vec<String>,
+    pub assessed_by: String,
+    #[serde(with = "serializer")]
+    pub assessed_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl FineEntity {
+    pub fn new(patron_id: &str, reason: &str, amount: i64) -> Self {
+        Self {
+            fine_id: Uuid::new_v4().to_string(),
+            version: 0,
+            branch_id: Uuid::new_v4().to_string(),
+            patron_id: patron_id.to_string(),
+            checkout_id: String::new(),
+            reason: reason.to_string(),
+            amount,
+            paid: 0,
+            waived: 0,
+            refunded: 0,
+            fine_status: FineStatus::Outstanding,
+            payment_ids: vec![],
+            adjustment_ids: vec![],
+            assessed_by: Uuid::new_v4().to_string(),
+            assessed_at: Utc::now().naive_utc(),
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+
+    // amount that is still owed, negative when payments exceeded what was not waived
+    pub fn balance(&self) -> i64 {
+        self.amount - self.waived - self.paid + self.refunded
+    }
+
+    fn settle(&mut self) {
+        self.fine_status = if self.balance() > 0 {
+            FineStatus::Outstanding
+        } else if self.paid > self.refunded {
+            FineStatus::Paid
+        } else {
+            FineStatus::Waived
+        };
+    }
+
+    // adds a confirmed payment to the fine, returns false when the payment was already applied so that redelivered
+    // confirmations do not count the payment twice
+    pub fn apply_payment(&mut self, payment_id: &str, amount: i64) -> bool {
+        if self.payment_ids.iter().any(|id| id == payment_id) {
+            return false;
+        }
+        self.payment_ids.push(payment_id.to_string());
+        self.paid += amount;
+        self.settle();
+        true
+    }
+
+    // waivers are limited to the balance and refunds to what was paid beyond it, returns false when the adjustment
+    // was already applied
+    pub fn apply_adjustment(&mut self, adjustment_id: &str, kind: AdjustmentKind, amount: i64) -> LibraryResult<bool> {
+        if self.adjustment_ids.iter().any(|id| id == adjustment_id) {
+            return Ok(false);
+        }
+        let limit = match kind {
+            AdjustmentKind::Waive => self.balance().max(0),
+            AdjustmentKind::Refund => (-self.balance()).max(0),
+        };
+        if amount <= 0 || amount > limit {
+            return Err(LibraryError::validation(format!("{} of fine {} must be between 1 and {}",
+                                                        kind, self.fine_id, limit).as_str(), Some("400".to_string())));
+        }
+        match kind {
+            AdjustmentKind::Waive => self.waived += amount,
+            AdjustmentKind::Refund => self.refunded += amount,
+        }
+        self.adjustment_ids.push(adjustment_id.to_string());
+        self.settle();
+        Ok(true)
+    }
+}
+
+impl Identifiable for FineEntity {
+    fn id(&self) -> String {
+        self.fine_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+// FinePaymentEntity is a payment of a fine through the payment provider, its id is derived from the fine and the
+// idempotency key of the client so that a retried request finds the payment instead of charging again
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct FinePaymentEntity {
+    pub payment_id: String,
+    pub version: i64,
+    pub fine_id: String,
+    pub patron_id: String,
+    pub idempotency_key: String,
+    pub amount: i64,
+    pub currency: String,
+    pub intent_id: String,
+    pub client_secret: String,
+    pub payment_status: PaymentStatus,
+    pub event_id: String,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl FinePaymentEntity {
+    pub fn new(fine_id: &str, patron_id: &str, idempotency_key: &str, amount: i64, currency: &str) -> Self {
+        Self {
+            payment_id: FinePaymentEntity::payment_id(fine_id, idempotency_key),
+            version: 0,
+            fine_id: fine_id.to_string(),
+            patron_id: patron_id.to_string(),
+            idempotency_key: idempotency_key.to_string(),
+            amount,
+            currency: currency.to_lowercase(),
+            intent_id: String::new(),
+            client_secret: String::new(),
+            payment_status: PaymentStatus::RequiresPayment,
+            event_id: String::new(),
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+
+    // keys are chosen by clients so they are hashed with the fine rather than used as ids directly
+    pub fn payment_id(fine_id: &str, idempotency_key: &str) -> String {
+        let digest = Sha256::digest(format!("{}:{}", fine_id, idempotency_key).as_bytes());
+        format!("pay_{}", digest.iter().take(16).map(|b| format!("{:02x}", b)).collect::<String>())
+    }
+}
+
+impl Identifiable for FinePaymentEntity {
+    fn id(&self) -> String {
+        self.payment_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+// FineAdjustmentEntity records a waiver or refund of a fine by a librarian, adjustments are never changed so that
+// they form the history of the fine
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct FineAdjustmentEntity {
+    pub adjustment_id: String,
+    pub fine_id: String,
+    pub patron_id: String,
+    pub branch_id: String,
+    pub adjustment_kind: AdjustmentKind,
+    pub reason: AdjustmentReason,
+    pub note: String,
+    pub amount: i64,
+    // balance of the fine after the adjustment
+    pub balance_after: i64,
+    pub adjusted_by: String,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+}
+
+impl FineAdjustmentEntity {
+    pub fn new(fine: &FineEntity, adjustment_kind: AdjustmentKind, reason: AdjustmentReason, amount: i64, adjusted_by: &str) -> Self {
+        Self {
+            adjustment_id: Uuid::new_v4().to_string(),
+            fine_id: fine.fine_id.to_string(),
+            patron_id: fine.patron_id.to_string(),
+            branch_id: fine.branch_id.to_string(),
+            adjustment_kind,
+            reason,
+            note: String::new(),
+            amount,
+            balance_after: fine.balance(),
+            adjusted_by: adjusted_by.to_string(),
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::library::{AdjustmentKind, FineStatus};
+    use crate::fines::domain::model::{FineEntity, FinePaymentEntity};
+
+    #[tokio::test]
+    async fn test_should_apply_payment_once() {
+        let mut fine = FineEntity::new("patron1", "overdue", 250);
+        assert!(fine.apply_payment("pay_1", 100));
+        assert!(!fine.apply_payment("pay_1", 100));
+        assert_eq!(150, fine.balance());
+        assert_eq!(FineStatus::Outstanding, fine.fine_status);
+        assert!(fine.apply_payment("pay_2", 150));
+        assert_eq!(FineStatus::Paid, fine.fine_status);
+    }
+
+    #[tokio::test]
+    async fn test_should_derive_payment_id_from_key() {
+        let payment = FinePaymentEntity::new("fine1", "patron1", "key1", 250, "USD");
+        assert_eq!(payment.payment_id, FinePaymentEntity::payment_id("fine1", "key1"));
+        assert_ne!(payment.payment_id, FinePaymentEntity::payment_id("fine2", "key1"));
+        assert_eq!("usd", payment.currency.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_should_limit_adjustments() {
+        let mut fine = FineEntity::new("patron1", "overdue", 250);
+        assert!(fine.apply_adjustment("adj_1", AdjustmentKind::Refund, 10).is_err());
+        assert!(fine.apply_adjustment("adj_1", AdjustmentKind::Waive, 300).is_err());
+        assert!(fine.apply_adjustment("adj_1", AdjustmentKind::Waive, 100).expect("should waive"));
+        assert!(!fine.apply_adjustment("adj_1", AdjustmentKind::Waive, 100).expect("should ignore repeated waiver"));
+        assert_eq!(150, fine.balance());
+
+        // the patron paid the whole fine before the waiver so the overcharge is refunded
+        assert!(fine.apply_payment("pay_1", 250));
+        assert_eq!(FineStatus::Paid, fine.fine_status);
+        assert!(fine.apply_adjustment("adj_2", AdjustmentKind::Refund, 101).is_err());
+        assert!(fine.apply_adjustment("adj_2", AdjustmentKind::Refund, 100).expect("should refund"));
+        assert_eq!(0, fine.balance());
+        assert_eq!(FineStatus::Paid, fine.fine_status);
+
+        let mut waived = FineEntity::new("patron1", "overdue", 250);
+        assert!(waived.apply_adjustment("adj_3", AdjustmentKind::Waive, 250).expect("should waive"));
+        assert_eq!(FineStatus::Waived, waived.fine_status);
+    }
+}
@@ -0,0 +1,90 @@
+use async_trait::async_trait;
+
+use crate::core::library::LibraryResult;
+use crate::fines::domain::FineQueryService;
+use crate::fines::dto::{FineAdjustmentDto, FineDto, FinePaymentDto, FineTotalsDto};
+use crate::fines::repository::{FineAdjustmentRepository, FinePaymentRepository, FineRepository};
+
+pub(crate) struct FineQueryServiceImpl {
+    fine_repository: Box<dyn FineRepository>,
+    payment_repository: Box<dyn FinePaymentRepository>,
+    adjustment_repository: Box<dyn FineAdjustmentRepository>,
+}
+
+impl FineQueryServiceImpl {
+    pub(crate) fn new(fine_repository: Box<dyn FineRepository>, payment_repository: Box<dyn FinePaymentRepository>,
+                      adjustment_repository: Box<dyn FineAdjustmentRepository>) -> Self {
+        Self {
+            fine_repository,
+            payment_repository,
+            adjustment_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl FineQueryService for FineQueryServiceImpl {
+    async fn find_fine_by_id(&self, fine_id: &str) -> LibraryResult<FineDto> {
+        self.fine_repository.get(fine_id).await.map(|f| FineDto::from(&f))
+    }
+
+    async fn find_fines_by_patron(&self, patron_id: &str) -> LibraryResult<Vec<FineDto>> {
+        let fines = self.fine_repository.find_by_patron(patron_id).await?;
+        Ok(fines.iter().map(FineDto::from).collect())
+    }
+
+    // adjustments are indexed by patron, patrons have few fines so the adjustments of the fine are filtered from them
+    async fn find_adjustments_by_fine(&self, fine_id: &str) -> LibraryResult<Vec<FineAdjustmentDto>> {
+        let fine = self.fine_repository.get(fine_id).await?;
+        let mut adjustments = self.adjustment_repository.find_by_patron(fine.patron_id.as_str()).await?
+            .iter().filter(|a| a.fine_id == fine_id).map(FineAdjustmentDto::from).collect::<Vec<FineAdjustmentDto>>();
+        adjustments.sort_by_key(|a| a.created_at);
+        Ok(adjustments)
+    }
+
+    async fn find_payments_by_patron(&self, patron_id: &str) -> LibraryResult<Vec<FinePaymentDto>> {
+        let payments = self.payment_repository.find_by_patron(patron_id).await?;
+        Ok(payments.iter().map(FinePaymentDto::from).collect())
+    }
+
+    async fn find_adjustments_by_patron(&self, patron_id: &str) -> LibraryResult<Vec<FineAdjustmentDto>> {
+        let adjustments = self.adjustment_repository.find_by_patron(patron_id).await?;
+        Ok(adjustments.iter().map(FineAdjustmentDto::from).collect())
+    }
+
+    async fn branch_totals(&self, branch_id: &str, page_size: usize) -> LibraryResult<FineTotalsDto> {
+        let mut totals = FineTotalsDto { branch_id: branch_id.to_string(), ..FineTotalsDto::default() };
+        let mut next_page: Option<String> = None;
+        loop {
+            let res = self.fine_repository.scan(next_page.as_deref(), page_size).await?;
+            for fine in res.records.iter().filter(|f| f.branch_id == branch_id) {
+                totals.fines += 1;
+                totals.assessed += fine.amount;
+                totals.paid += fine.paid;
+                totals.waived += fine.waived;
+                totals.refunded += fine.refunded;
+            }
+            next_page = res.next_page;
+            if next_page.is_none() {
+                break;
+            }
+        }
+        Ok(totals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::domain::Configuration;
+    use crate::fines::factory;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_not_find_unknown_fine() {
+        let store = test_store(module_path!(), "test_should_not_find_unknown_fine");
+        let query_svc = factory::create_fine_query_service(&Configuration::new("query_test"), store).await;
+        assert!(query_svc.find_fine_by_id("unknown_fine").await.is_err());
+        assert!(query_svc.find_fines_by_patron("unknown_patron").await.expect("should find fines").is_empty());
+        assert!(query_svc.find_payments_by_patron("unknown_patron").await.expect("should find payments").is_empty());
+    }
+}
@@ -0,0 +1,565 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::core::events::DomainEvent;
+use crate::core::domain::Configuration;
+use crate::core::ids::{branch_scoped_id, PatronId};
+use crate::core::library::{AdjustmentKind, AdjustmentReason, FineStatus, LibraryError, LibraryResult};
+use crate::fines::domain::{FineQueryService, FineService, CASH_ACCOUNT, RECEIVABLE_ACCOUNT, REVENUE_ACCOUNT, WAIVED_ACCOUNT};
+use crate::fines::domain::model::{FineAdjustmentEntity, FineEntity, FinePaymentEntity};
+use crate::fines::dto::{FineAdjustmentDto, FineDto, FinePaymentDto, FineTotalsDto, PatronStatementDto};
+use crate::fines::repository::{FineAdjustmentRepository, FinePaymentRepository, FineRepository};
+use crate::fines::statement::{build_statement, statement_period};
+use crate::gateway::events::EventPublisher;
+use crate::gateway::payments::{PaymentGateway, PaymentStatus};
+use crate::ledger::domain::LedgerService;
+use crate::ledger::dto::JournalEntryDto;
+use crate::patrons::domain::PatronService;
+use crate::patrons::Patron;
+
+// longest idempotency key accepted from clients, which matches the limit of the payment provider
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+pub(crate) struct FineServiceImpl {
+    branch_id: String,
+    currency: String,
+    approval_threshold: i64,
+    fine_repository: Box<dyn FineRepository>,
+    payment_repository: Box<dyn FinePaymentRepository>,
+    adjustment_repository: Box<dyn FineAdjustmentRepository>,
+    patron_service: Box<dyn PatronService>,
+    payment_gateway: Box<dyn PaymentGateway>,
+    ledger_service: Box<dyn LedgerService>,
+    events_publisher: Box<dyn EventPublisher>,
+    query_service: Box<dyn FineQueryService>,
+}
+
+impl FineServiceImpl {
+    pub(crate) fn new(config: &Configuration, fine_repository: Box<dyn FineRepository>,
+                      payment_repository: Box<dyn FinePaymentRepository>,
+                      adjustment_repository: Box<dyn FineAdjustmentRepository>, patron_service: Box<dyn PatronService>,
+                      payment_gateway: Box<dyn PaymentGateway>, ledger_service: Box<dyn LedgerService>,
+                      events_publisher: Box<dyn EventPublisher>, query_service: Box<dyn FineQueryService>) -> Self {
+        Self {
+            branch_id: config.branch_id.to_string(),
+            currency: config.fine_currency.to_string(),
+            approval_threshold: config.fine_adjustment_approval_threshold,
+            fine_repository,
+            payment_repository,
+            adjustment_repository,
+            patron_service,
+            payment_gateway,
+            ledger_service,
+            events_publisher,
+            query_service,
+        }
+    }
+
+    async fn check_staff(&self, id: &str) -> LibraryResult<()> {
+        let staff = self.patron_service.find_patron_by_id(&PatronId::new(id)).await?;
+        if !staff.is_librarian() && !staff.is_admin() {
+            return Err(LibraryError::not_granted(format!("{} cannot manage fines", id).as_str(), Some("403".to_string())));
+        }
+        Ok(())
+    }
+
+    // patrons can only see and pay their own fines
+    async fn check_access(&self, requested_by: &str, patron_id: &str) -> LibraryResult<()> {
+        if requested_by == patron_id {
+            return Ok(());
+        }
+        let staff = self.patron_service.find_patron_by_id(&PatronId::new(requested_by)).await?;
+        if !staff.is_librarian() && !staff.is_admin() {
+            return Err(LibraryError::not_granted(format!("{} cannot access fines of {}",
+                                                         requested_by, patron_id).as_str(), Some("403".to_string())));
+        }
+        Ok(())
+    }
+
+    // returns the payment of the idempotency key, the payment is created for the balance of the fine when the key
+    // was not used before and a concurrent request with the same key reads the payment that won the race
+    async fn find_or_create_payment(&self, fine: &FineEntity, idempotency_key: &str) -> LibraryResult<FinePaymentEntity> {
+        let payment_id = FinePaymentEntity::payment_id(fine.fine_id.as_str(), idempotency_key);
+        match self.payment_repository.get(payment_id.as_str()).await {
+            Ok(payment) => return Ok(payment),
+            Err(LibraryError::NotFound { .. }) => {}
+            Err(err) => return Err(err),
+        }
+        if fine.fine_status != FineStatus::Outstanding || fine.balance() <= 0 {
+            return Err(LibraryError::validation(format!("fine {} has no outstanding balance",
+                                                        fine.fine_id).as_str(), Some("400".to_string())));
+        }
+        let payment = FinePaymentEntity::new(fine.fine_id.as_str(), fine.patron_id.as_str(), idempotency_key,
+                                             fine.balance(), self.currency.as_str());
+        // a concurrent request with the same key created the payment first, other errors are not retried as reads
+        match self.payment_repository.create(&payment).await {
+            Ok(_) => Ok(payment),
+            Err(LibraryError::DuplicateKey { .. }) => self.payment_repository.get(payment_id.as_str()).await,
+            Err(err) => Err(err),
+        }
+    }
+
+    // posts the entry of a change of a fine to the ledger, entries are keyed by the change so that a change that is
+    // retried after its entry was posted is not posted twice
+    async fn post(&self, entry: JournalEntryDto) -> LibraryResult<()> {
+        match self.ledger_service.post(&entry).await {
+            Ok(_) | Err(LibraryError::DuplicateKey { .. }) => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    // adds a succeeded payment to its fine, the ids of applied payments on the fine keep the paid amount exact when a
+    // confirmation is delivered again after the fine was updated. The payment is posted before the fine is updated so
+    // that a confirmation that failed in between posts it once when it is delivered again
+    async fn apply_payment(&self, payment: &FinePaymentEntity) -> LibraryResult<()> {
+        let mut fine = self.fine_repository.get(payment.fine_id.as_str()).await?;
+        if !fine.apply_payment(payment.payment_id.as_str(), payment.amount) {
+            return Ok(());
+        }
+        self.post(JournalEntryDto::new(fine.branch_id.as_str(), fine.fine_id.as_str(), "fine paid")
+            .with_entry_id(payment.payment_id.as_str())
+            .transfer(RECEIVABLE_ACCOUNT, CASH_ACCOUNT, payment.amount)).await?;
+        self.fine_repository.update(&fine).await?;
+        fine.version += 1;
+        let dto = FineDto::from(&fine);
+        let name = if fine.fine_status == FineStatus::Paid { "fine_paid" } else { "fine_partially_paid" };
+        self.events_publisher.publish(&DomainEvent::updated(
+            name, "fines", dto.fine_id.as_str(), &HashMap::new(), &dto)?).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl FineService for FineServiceImpl {
+    async fn assess(&self, fine: &FineDto) -> LibraryResult<FineDto> {
+        if fine.amount <= 0 {
+            return Err(LibraryError::validation("fine amount must be positive", Some("400".to_string())));
+        }
+        if fine.patron_id.is_empty() || fine.reason.is_empty() {
+            return Err(LibraryError::validation("patron and reason are required", Some("400".to_string())));
+        }
+        self.check_staff(fine.assessed_by.as_str()).await?;
+        let _ = self.patron_service.find_patron_by_id(&PatronId::new(fine.patron_id.as_str())).await?;
+        let now = Utc::now().naive_utc();
+        let mut entity = FineEntity::from(fine);
+        entity.fine_id = branch_scoped_id(self.branch_id.as_str());
+        entity.version = 0;
+        entity.branch_id = self.branch_id.to_string();
+        entity.paid = 0;
+        entity.waived = 0;
+        entity.refunded = 0;
+        entity.fine_status = FineStatus::Outstanding;
+        entity.payment_ids = vec![];
+        entity.adjustment_ids = vec![];
+        entity.assessed_at = now;
+        entity.created_at = now;
+        entity.updated_at = now;
+        self.fine_repository.create(&entity).await?;
+        if let Err(err) = self.post(JournalEntryDto::new(entity.branch_id.as_str(), entity.fine_id.as_str(), "fine assessed")
+            .with_entry_id(entity.fine_id.as_str())
+            .transfer(REVENUE_ACCOUNT, RECEIVABLE_ACCOUNT, entity.amount)).await {
+            let _ = self.fine_repository.delete(entity.fine_id.as_str()).await;
+            return Err(err);
+        }
+        let fine = FineDto::from(&entity);
+        self.events_publisher.publish(&DomainEvent::added(
+            "fine_assessed", "fines", fine.fine_id.as_str(), &HashMap::new(), &fine)?).await?;
+        Ok(fine)
+    }
+
+    async fn get_fine(&self, requested_by: &str, fine_id: &str) -> LibraryResult<FineDto> {
+        let fine = self.query_service.find_fine_by_id(fine_id).await?;
+        self.check_access(requested_by, fine.patron_id.as_str()).await?;
+        Ok(fine)
+    }
+
+    async fn find_patron_fines(&self, requested_by: &str, patron_id: &str) -> LibraryResult<Vec<FineDto>> {
+        self.check_access(requested_by, patron_id).await?;
+        self.query_service.find_fines_by_patron(patron_id).await
+    }
+
+    async fn pay(&self, paid_by: &str, fine_id: &str, idempotency_key: &str) -> LibraryResult<FinePaymentDto> {
+        if idempotency_key.trim().is_empty() || idempotency_key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+            return Err(LibraryError::validation(format!("idempotency key must have 1 to {} characters",
+                                                        MAX_IDEMPOTENCY_KEY_LEN).as_str(), Some("400".to_string())));
+        }
+        let fine = self.fine_repository.get(fine_id).await?;
+        self.check_access(paid_by, fine.patron_id.as_str()).await?;
+        let mut payment = self.find_or_create_payment(&fine, idempotency_key).await?;
+        // an intent that failed to be created is created again on retry, the provider returns the same intent for
+        // the payment id
+        if payment.intent_id.is_empty() {
+            let intent = self.payment_gateway.create_intent(payment.payment_id.as_str(), payment.amount,
+                                                            payment.currency.as_str()).await?;
+            payment.intent_id = intent.intent_id;
+            payment.client_secret = intent.client_secret;
+            self.payment_repository.update(&payment).await?;
+            payment.version += 1;
+        }
+        Ok(FinePaymentDto::from(&payment))
+    }
+
+    async fn confirm_payment(&self, payload: &str, signature: &str, now: i64) -> LibraryResult<Option<FinePaymentDto>> {
+        let Some(event) = self.payment_gateway.parse_webhook(payload, signature, now)? else {
+            return Ok(None);
+        };
+        let mut payment = self.payment_repository.get(event.intent.reference.as_str()).await?;
+        let mismatch = (!payment.intent_id.is_empty() && payment.intent_id != event.intent.intent_id) ||
+            payment.amount != event.intent.amount_cents || !payment.currency.eq_ignore_ascii_case(event.intent.currency.as_str());
+        if mismatch {
+            return Err(LibraryError::validation(format!("payment {} does not match intent {}",
+                                                        payment.payment_id, event.intent.intent_id).as_str(), Some("409".to_string())));
+        }
+        let next = event.intent.status;
+        if payment.payment_status.transition(next)? {
+            payment.payment_status = next;
+            payment.intent_id = event.intent.intent_id.to_string();
+            payment.event_id = event.event_id.to_string();
+            self.payment_repository.update(&payment).await?;
+            payment.version += 1;
+            let dto = FinePaymentDto::from(&payment);
+            self.events_publisher.publish(&DomainEvent::updated(
+                format!("fine_payment_{}", next.to_string().to_lowercase()).as_str(), "fines",
+                dto.payment_id.as_str(), &HashMap::new(), &dto)?).await?;
+        }
+        // also applied when the status was recorded before so that a failed update of the fine is completed by the
+        // next delivery of the webhook
+        if payment.payment_status == PaymentStatus::Succeeded {
+            self.apply_payment(&payment).await?;
+        }
+        Ok(Some(FinePaymentDto::from(&payment)))
+    }
+
+    async fn adjust(&self, adjustment: &FineAdjustmentDto) -> LibraryResult<FineAdjustmentDto> {
+        let staff = self.patron_service.find_patron_by_id(&PatronId::new(adjustment.adjusted_by.as_str())).await?;
+        if !staff.is_librarian() && !staff.is_admin() {
+            return Err(LibraryError::not_granted(format!("{} cannot adjust fines",
+                                                         adjustment.adjusted_by).as_str(), Some("403".to_string())));
+        }
+        if adjustment.reason == AdjustmentReason::Other && adjustment.note.trim().is_empty() {
+            return Err(LibraryError::validation("adjustments for other reasons need a note", Some("400".to_string())));
+        }
+        let mut fine = self.fine_repository.get(adjustment.fine_id.as_str()).await?;
+        // the threshold applies to all adjustments of the fine together so that splitting a waiver does not avoid it,
+        // the version of the fine keeps concurrent adjustments from both passing the check
+        if fine.waived + fine.refunded + adjustment.amount > self.approval_threshold && !staff.is_admin() {
+            return Err(LibraryError::not_granted(format!("adjustments of fine {} above {} in total need an admin",
+                                                         fine.fine_id, self.approval_threshold).as_str(), Some("403".to_string())));
+        }
+        let mut entity = FineAdjustmentEntity::new(&fine, adjustment.adjustment_kind, adjustment.reason,
+                                                   adjustment.amount, adjustment.adjusted_by.as_str());
+        entity.note = adjustment.note.to_string();
+        fine.apply_adjustment(entity.adjustment_id.as_str(), entity.adjustment_kind, entity.amount)?;
+        entity.balance_after = fine.balance();
+        // the adjustment is recorded and posted before the fine is changed, and removed and reversed again when the fine
+        // changed concurrently, so that the history and the ledger never miss an adjustment of the fine
+        let (from_account, to_account, description) = match entity.adjustment_kind {
+            AdjustmentKind::Waive => (RECEIVABLE_ACCOUNT, WAIVED_ACCOUNT, "fine waived"),
+            AdjustmentKind::Refund => (CASH_ACCOUNT, RECEIVABLE_ACCOUNT, "fine refunded"),
+        };
+        self.adjustment_repository.create(&entity).await?;
+        if let Err(err) = self.post(JournalEntryDto::new(fine.branch_id.as_str(), fine.fine_id.as_str(), description)
+            .with_entry_id(entity.adjustment_id.as_str())
+            .transfer(from_account, to_account, entity.amount)).await {
+            let _ = self.adjustment_repository.delete(entity.adjustment_id.as_str()).await;
+            return Err(err);
+        }
+        if let Err(err) = self.fine_repository.update(&fine).await {
+            let _ = self.adjustment_repository.delete(entity.adjustment_id.as_str()).await;
+            self.post(JournalEntryDto::new(fine.branch_id.as_str(), fine.fine_id.as_str(), "fine adjustment failed")
+                .with_entry_id(format!("{}_reversed", entity.adjustment_id).as_str())
+                .transfer(to_account, from_account, entity.amount)).await?;
+            return Err(err);
+        }
+        let dto = FineAdjustmentDto::from(&entity);
+        let name = match dto.adjustment_kind {
+            AdjustmentKind::Waive => "fine_waived",
+            AdjustmentKind::Refund => "fine_refunded",
+        };
+        self.events_publisher.publish(&DomainEvent::added(
+            name, "fines", dto.fine_id.as_str(), &HashMap::new(), &dto)?).await?;
+        Ok(dto)
+    }
+
+    async fn find_adjustments(&self, requested_by: &str, fine_id: &str) -> LibraryResult<Vec<FineAdjustmentDto>> {
+        let fine = self.query_service.find_fine_by_id(fine_id).await?;
+        self.check_access(requested_by, fine.patron_id.as_str()).await?;
+        self.query_service.find_adjustments_by_fine(fine_id).await
+    }
+
+    async fn statement(&self, requested_by: &str, patron_id: &str, from: Option<&str>, to: Option<&str>) -> LibraryResult<PatronStatementDto> {
+        let (from, to) = statement_period(from, to, Utc::now().date_naive())?;
+        self.check_access(requested_by, patron_id).await?;
+        let fines = self.query_service.find_fines_by_patron(patron_id).await?;
+        let payments = self.query_service.find_payments_by_patron(patron_id).await?;
+        let adjustments = self.query_service.find_adjustments_by_patron(patron_id).await?;
+        Ok(build_statement(patron_id, self.currency.as_str(), from, to, &fines, &payments, &adjustments))
+    }
+}
+
+#[async_trait]
+impl FineQueryService for FineServiceImpl {
+    async fn find_fine_by_id(&self, fine_id: &str) -> LibraryResult<FineDto> {
+        self.query_service.find_fine_by_id(fine_id).await
+    }
+
+    async fn find_fines_by_patron(&self, patron_id: &str) -> LibraryResult<Vec<FineDto>> {
+        self.query_service.find_fines_by_patron(patron_id).await
+    }
+
+    async fn find_adjustments_by_fine(&self, fine_id: &str) -> LibraryResult<Vec<FineAdjustmentDto>> {
+        self.query_service.find_adjustments_by_fine(fine_id).await
+    }
+
+    async fn find_payments_by_patron(&self, patron_id: &str) -> LibraryResult<Vec<FinePaymentDto>> {
+        self.query_service.find_payments_by_patron(patron_id).await
+    }
+
+    async fn find_adjustments_by_patron(&self, patron_id: &str) -> LibraryResult<Vec<FineAdjustmentDto>> {
+        self.query_service.find_adjustments_by_patron(patron_id).await
+    }
+
+    async fn branch_totals(&self, branch_id: &str, page_size: usize) -> LibraryResult<FineTotalsDto> {
+        self.query_service.branch_totals(branch_id, page_size).await
+    }
+}
+
+impl From<&FineDto> for FineEntity {
+    fn from(other: &FineDto) -> FineEntity {
+        FineEntity {
+            fine_id: other.fine_id.to_string(),
+            version: other.version,
+            branch_id: other.branch_id.to_string(),
+            patron_id: other.patron_id.to_string(),
+            checkout_id: other.checkout_id.to_string(),
+            reason: other.reason.to_string(),
+            amount: other.amount,
+            paid: other.paid,
+            waived: other.waived,
+            refunded: other.refunded,
+            fine_status: other.fine_status,
+            payment_ids: other.payment_ids.clone(),
+            adjustment_ids: other.adjustment_ids.clone(),
+            assessed_by: other.assessed_by.to_string(),
+            assessed_at: other.assessed_at,
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+impl From<&FineEntity> for FineDto {
+    fn from(other: &FineEntity) -> FineDto {
+        FineDto {
+            fine_id: other.fine_id.to_string(),
+            version: other.version,
+            branch_id: other.branch_id.to_string(),
+            patron_id: other.patron_id.to_string(),
+            checkout_id: other.checkout_id.to_string(),
+            reason: other.reason.to_string(),
+            amount: other.amount,
+            paid: other.paid,
+            waived: other.waived,
+            refunded: other.refunded,
+            fine_status: other.fine_status,
+            payment_ids: other.payment_ids.clone(),
+            adjustment_ids: other.adjustment_ids.clone(),
+            assessed_by: other.assessed_by.to_string(),
+            assessed_at: other.assessed_at,
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+impl From<&FineAdjustmentEntity> for FineAdjustmentDto {
+    fn from(other: &FineAdjustmentEntity) -> FineAdjustmentDto {
+        FineAdjustmentDto {
+            adjustment_id: other.adjustment_id.to_string(),
+            fine_id: other.fine_id.to_string(),
+            patron_id: other.patron_id.to_string(),
+            branch_id: other.branch_id.to_string(),
+            adjustment_kind: other.adjustment_kind,
+            reason: other.reason,
+            note: other.note.to_string(),
+            amount: other.amount,
+            balance_after: other.balance_after,
+            adjusted_by: other.adjusted_by.to_string(),
+            created_at: other.created_at,
+        }
+    }
+}
+
+impl From<&FinePaymentEntity> for FinePaymentDto {
+    fn from(other: &FinePaymentEntity) -> FinePaymentDto {
+        FinePaymentDto {
+            payment_id: other.payment_id.to_string(),
+            version: other.version,
+            fine_id: other.fine_id.to_string(),
+            patron_id: other.patron_id.to_string(),
+            idempotency_key: other.idempotency_key.to_string(),
+            amount: other.amount,
+            currency: other.currency.to_string(),
+            intent_id: other.intent_id.to_string(),
+            client_secret: other.client_secret.to_string(),
+            payment_status: other.payment_status,
+            event_id: other.event_id.to_string(),
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+
+    use crate::core::domain::Configuration;
+    use crate::core::library::{AdjustmentKind, AdjustmentReason, FineStatus, PartyKind, Role, StatementEntryKind};
+    use crate::core::repository::RepositoryStore;
+    use crate::fines::domain::FineService;
+    use crate::fines::dto::{FineAdjustmentDto, FineDto, FinePaymentDto};
+    use crate::fines::factory;
+    use crate::gateway::payments::{sign_webhook, PaymentStatus};
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn FineService> {
+        factory::create_fine_service(&Configuration::new("fines_test"), store).await
+    }
+
+    async fn add_party(store: RepositoryStore, email: &str, role: Role) -> PartyEntity {
+        let mut party = PartyEntity::new(PartyKind::Patron, email);
+        party.group_roles = vec![role.to_string()];
+        let _ = create_party_repository(store).await.create(&party).await.expect("should create party");
+        party
+    }
+
+    // webhook of the provider for the intent of the payment signed with the secret of the configuration
+    fn webhook(event_id: &str, event_type: &str, payment: &FinePaymentDto) -> (String, String, i64) {
+        let payload = json!({
+            "id": event_id,
+            "type": event_type,
+            "data": {"object": {"id": payment.intent_id, "status": "succeeded", "amount": payment.amount,
+                                "currency": payment.currency, "metadata": {"reference": payment.payment_id}}},
+        }).to_string();
+        let now = Utc::now().timestamp();
+        let signature = sign_webhook(Configuration::new("fines_test").payment_webhook_secret.as_str(), payload.as_str(), now);
+        (payload, signature, now)
+    }
+
+    #[tokio::test]
+    async fn test_should_pay_fine_once_on_confirmed_payment() {
+        let store = test_store(module_path!(), "test_should_pay_fine_once_on_confirmed_payment");
+        let fine_svc = sut_svc(store).await;
+        let librarian = add_party(store, "fines_librarian@example.com", Role::Librarian).await;
+        let patron = add_party(store, "fines_patron@example.com", Role::Regular).await;
+        let other = add_party(store, "fines_other@example.com", Role::Regular).await;
+        // patrons cannot assess fines
+        assert!(fine_svc.assess(&FineDto::new(patron.party_id.as_str(), patron.party_id.as_str(), "overdue", 250)).await.is_err());
+        let fine = fine_svc.assess(&FineDto::new(librarian.party_id.as_str(), patron.party_id.as_str(), "overdue", 250))
+            .await.expect("should assess fine");
+        assert_eq!(FineStatus::Outstanding, fine.fine_status);
+
+        assert!(fine_svc.pay(other.party_id.as_str(), fine.fine_id.as_str(), "key1").await.is_err());
+        let payment = fine_svc.pay(patron.party_id.as_str(), fine.fine_id.as_str(), "key1").await.expect("should pay");
+        assert_eq!(250, payment.amount);
+        assert!(!payment.intent_id.is_empty());
+        // a retry with the same key returns the same payment
+        assert_eq!(payment, fine_svc.pay(patron.party_id.as_str(), fine.fine_id.as_str(), "key1").await.expect("should pay"));
+
+        // failures and pending payments leave the fine outstanding
+        let (payload, signature, now) = webhook("evt_failed", "payment_intent.payment_failed", &payment);
+        let failed = fine_svc.confirm_payment(payload.as_str(), signature.as_str(), now).await.expect("should confirm")
+            .expect("should change payment");
+        assert_eq!(PaymentStatus::Failed, failed.payment_status);
+        assert_eq!(0, fine_svc.find_fine_by_id(fine.fine_id.as_str()).await.expect("should find fine").paid);
+
+        let (payload, signature, now) = webhook("evt_succeeded", "payment_intent.succeeded", &payment);
+        for _ in 0..2 {
+            let confirmed = fine_svc.confirm_payment(payload.as_str(), signature.as_str(), now).await.expect("should confirm")
+                .expect("should change payment");
+            assert_eq!(PaymentStatus::Succeeded, confirmed.payment_status);
+        }
+        let paid = fine_svc.get_fine(patron.party_id.as_str(), fine.fine_id.as_str()).await.expect("should get fine");
+        assert_eq!(250, paid.paid);
+        assert_eq!(FineStatus::Paid, paid.fine_status);
+        assert_eq!(vec![payment.payment_id.to_string()], paid.payment_ids);
+        // paid fines cannot be paid with a new key
+        assert!(fine_svc.pay(patron.party_id.as_str(), fine.fine_id.as_str(), "key2").await.is_err());
+        assert!(fine_svc.confirm_payment(payload.as_str(), "t=1,v1=00", now).await.is_err());
+
+        // the failed attempt is left out of the statement and the succeeded payment is listed once
+        assert!(fine_svc.statement(other.party_id.as_str(), patron.party_id.as_str(), None, None).await.is_err());
+        let statement = fine_svc.statement(librarian.party_id.as_str(), patron.party_id.as_str(), None, None)
+            .await.expect("should build statement");
+        assert_eq!(vec![StatementEntryKind::Fine, StatementEntryKind::Payment],
+                   statement.lines.iter().map(|l| l.entry_kind).collect::<Vec<StatementEntryKind>>());
+        assert_eq!(250, statement.assessed);
+        assert_eq!(250, statement.paid);
+        assert_eq!(0, statement.closing_balance);
+    }
+
+    #[tokio::test]
+    async fn test_should_require_admin_for_large_adjustments() {
+        let store = test_store(module_path!(), "test_should_require_admin_for_large_adjustments");
+        let fine_svc = sut_svc(store).await;
+        let threshold = Configuration::new("fines_test").fine_adjustment_approval_threshold;
+        let admin = add_party(store, "adjust_admin@example.com", Role::Admin).await;
+        let librarian = add_party(store, "adjust_librarian@example.com", Role::Librarian).await;
+        let patron = add_party(store, "adjust_patron@example.com", Role::Regular).await;
+        let fine = fine_svc.assess(&FineDto::new(librarian.party_id.as_str(), patron.party_id.as_str(), "lost item", threshold * 3))
+            .await.expect("should assess fine");
+
+        let waiver = |adjusted_by: &str, amount: i64, reason: AdjustmentReason|
+            FineAdjustmentDto::new(adjusted_by, fine.fine_id.as_str(), AdjustmentKind::Waive, reason, amount);
+        assert!(fine_svc.adjust(&waiver(patron.party_id.as_str(), 100, AdjustmentReason::Hardship)).await.is_err());
+        assert!(fine_svc.adjust(&waiver(librarian.party_id.as_str(), threshold + 1, AdjustmentReason::Hardship)).await.is_err());
+        assert!(fine_svc.adjust(&waiver(librarian.party_id.as_str(), 100, AdjustmentReason::Other)).await.is_err());
+        let small = fine_svc.adjust(&waiver(librarian.party_id.as_str(), threshold, AdjustmentReason::StaffError))
+            .await.expect("should waive");
+        assert_eq!(threshold * 2, small.balance_after);
+        let large = fine_svc.adjust(&waiver(admin.party_id.as_str(), threshold * 2, AdjustmentReason::ItemReturned))
+            .await.expect("should waive");
+        assert_eq!(0, large.balance_after);
+        // nothing is left to waive and nothing was paid that could be refunded
+        assert!(fine_svc.adjust(&waiver(admin.party_id.as_str(), 1, AdjustmentReason::Hardship)).await.is_err());
+        assert!(fine_svc.adjust(&FineAdjustmentDto::new(admin.party_id.as_str(), fine.fine_id.as_str(), AdjustmentKind::Refund,
+                                                        AdjustmentReason::Overcharge, 1)).await.is_err());
+
+        let waived = fine_svc.get_fine(patron.party_id.as_str(), fine.fine_id.as_str()).await.expect("should get fine");
+        assert_eq!(FineStatus::Waived, waived.fine_status);
+        assert_eq!(threshold * 3, waived.waived);
+        let history = fine_svc.find_adjustments(patron.party_id.as_str(), fine.fine_id.as_str()).await.expect("should find adjustments");
+        assert_eq!(vec![small.adjustment_id, large.adjustment_id], history.into_iter().map(|a| a.adjustment_id).collect::<Vec<String>>());
+    }
+
+    #[tokio::test]
+    async fn test_should_require_admin_for_split_waivers() {
+        let store = test_store(module_path!(), "test_should_require_admin_for_split_waivers");
+        let fine_svc = sut_svc(store).await;
+        let threshold = Configuration::new("fines_test").fine_adjustment_approval_threshold;
+        let admin = add_party(store, "split_admin@example.com", Role::Admin).await;
+        let librarian = add_party(store, "split_librarian@example.com", Role::Librarian).await;
+        let patron = add_party(store, "split_patron@example.com", Role::Regular).await;
+        let fine = fine_svc.assess(&FineDto::new(librarian.party_id.as_str(), patron.party_id.as_str(), "lost item", threshold * 3))
+            .await.expect("should assess fine");
+
+        let waiver = |adjusted_by: &str, amount: i64|
+            FineAdjustmentDto::new(adjusted_by, fine.fine_id.as_str(), AdjustmentKind::Waive, AdjustmentReason::Hardship, amount);
+        // waivers below the threshold add up and the one that crosses it needs an admin
+        let first = fine_svc.adjust(&waiver(librarian.party_id.as_str(), threshold - 1)).await.expect("should waive");
+        assert_eq!(threshold * 2 + 1, first.balance_after);
+        let _ = fine_svc.adjust(&waiver(librarian.party_id.as_str(), 1)).await.expect("should waive");
+        assert!(fine_svc.adjust(&waiver(librarian.party_id.as_str(), 1)).await.is_err());
+        let rest = fine_svc.adjust(&waiver(admin.party_id.as_str(), threshold * 2)).await.expect("should waive");
+        assert_eq!(0, rest.balance_after);
+
+        let waived = fine_svc.get_fine(patron.party_id.as_str(), fine.fine_id.as_str()).await.expect("should get fine");
+        assert_eq!(threshold * 3, waived.waived);
+    }
+}
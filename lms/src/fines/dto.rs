@@ -0,0 +1,199 @@
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+use crate::core::domain::Identifiable;
+use crate::core::library::{AdjustmentKind, AdjustmentReason, FineStatus, StatementEntryKind};
+use crate::gateway::payments::PaymentStatus;
+use crate::utils::date::serializer;
+
+// FineDto abstracts data transfer object for a fine charged to a patron, amounts are in the smallest unit of the
+// currency of the configuration
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct FineDto {
+    pub fine_id: String,
+    pub version: i64,
+    pub branch_id: String,
+    pub patron_id: String,
+    // checkout that the fine was charged for, empty for fines such as damages that are not tied to a checkout
+    pub checkout_id: String,
+    pub reason: String,
+    pub amount: i64,
+    pub paid: i64,
+    pub waived: i64,
+    pub refunded: i64,
+    pub fine_status: FineStatus,
+    // payments that were applied to the fine, a confirmed payment is only applied once
+    pub payment_ids: Vec<String>,
+    pub adjustment_ids: Vec<String>,
+    pub assessed_by: String,
+    #[serde(with = "serializer")]
+    pub assessed_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl FineDto {
+    pub fn new(assessed_by: &str, patron_id: &str, reason: &str, amount: i64) -> Self {
+        Self {
+            fine_id: Uuid::new_v4().to_string(),
+            version: 0,
+            branch_id: Uuid::new_v4().to_string(),
+            patron_id: patron_id.to_string(),
+            checkout_id: String::new(),
+            reason: reason.to_string(),
+            amount,
+            paid: 0,
+            waived: 0,
+            refunded: 0,
+            fine_status: FineStatus::Outstanding,
+            payment_ids: vec![],
+            adjustment_ids: vec![],
+            assessed_by: assessed_by.to_string(),
+            assessed_at: Utc::now().naive_utc(),
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+
+    // amount that is still owed, negative when payments exceeded what was not waived
+    pub fn balance(&self) -> i64 {
+        self.amount - self.waived - self.paid + self.refunded
+    }
+}
+
+impl Identifiable for FineDto {
+    fn id(&self) -> String {
+        self.fine_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+// FinePaymentDto is an attempt of a patron to pay a fine, the client completes the payment with the client secret
+// and retries with the same idempotency key get the same payment
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct FinePaymentDto {
+    pub payment_id: String,
+    pub version: i64,
+    pub fine_id: String,
+    pub patron_id: String,
+    pub idempotency_key: String,
+    pub amount: i64,
+    pub currency: String,
+    pub intent_id: String,
+    pub client_secret: String,
+    pub payment_status: PaymentStatus,
+    // last webhook event of the provider that changed the payment
+    pub event_id: String,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+// FineAdjustmentDto is a waiver or refund of a fine in the history of the fine
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct FineAdjustmentDto {
+    pub adjustment_id: String,
+    pub fine_id: String,
+    pub patron_id: String,
+    pub branch_id: String,
+    pub adjustment_kind: AdjustmentKind,
+    pub reason: AdjustmentReason,
+    pub note: String,
+    pub amount: i64,
+    pub balance_after: i64,
+    pub adjusted_by: String,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+}
+
+impl FineAdjustmentDto {
+    pub fn new(adjusted_by: &str, fine_id: &str, adjustment_kind: AdjustmentKind, reason: AdjustmentReason, amount: i64) -> Self {
+        Self {
+            adjustment_id: Uuid::new_v4().to_string(),
+            fine_id: fine_id.to_string(),
+            patron_id: String::new(),
+            branch_id: String::new(),
+            adjustment_kind,
+            reason,
+            note: String::new(),
+            amount,
+            balance_after: 0,
+            adjusted_by: adjusted_by.to_string(),
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+// FineTotalsDto sums the fines of a branch, the fine accounts of the ledger of the branch reconcile with the totals
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct FineTotalsDto {
+    pub branch_id: String,
+    pub fines: usize,
+    pub assessed: i64,
+    pub paid: i64,
+    pub waived: i64,
+    pub refunded: i64,
+}
+
+impl FineTotalsDto {
+    pub fn outstanding(&self) -> i64 {
+        self.assessed - self.waived - self.paid + self.refunded
+    }
+}
+
+// StatementLineDto is a fine, payment, waiver or refund on the statement of a patron, the amount is what it added to
+// the balance of the patron so payments and waivers are negative
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct StatementLineDto {
+    pub fine_id: String,
+    pub entry_kind: StatementEntryKind,
+    pub description: String,
+    pub amount: i64,
+    // balance of the patron after the line
+    pub balance: i64,
+    #[serde(with = "serializer")]
+    pub occurred_at: NaiveDateTime,
+}
+
+// PatronStatementDto itemizes what a patron owed between two days, both days included, the closing balance is the
+// opening balance plus the fines and refunds less the payments and waivers of the period
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct PatronStatementDto {
+    pub patron_id: String,
+    pub currency: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub opening_balance: i64,
+    pub assessed: i64,
+    pub paid: i64,
+    pub waived: i64,
+    pub refunded: i64,
+    pub closing_balance: i64,
+    pub lines: Vec<StatementLineDto>,
+    #[serde(with = "serializer")]
+    pub generated_at: NaiveDateTime,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::library::FineStatus;
+    use crate::fines::dto::FineDto;
+
+    #[tokio::test]
+    async fn test_should_build_fine() {
+        let mut fine = FineDto::new("librarian1", "patron1", "overdue", 250);
+        assert_eq!(FineStatus::Outstanding, fine.fine_status);
+        assert_eq!(250, fine.balance());
+        fine.paid = 300;
+        assert_eq!(-50, fine.balance());
+        fine.refunded = 50;
+        fine.waived = 100;
+        assert_eq!(-100, fine.balance());
+    }
+}
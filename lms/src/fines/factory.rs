@@ -0,0 +1,78 @@
+use crate::core::domain::Configuration;
+use crate::core::repository::RepositoryStore;
+use crate::fines::domain::{FineQueryService, FineService};
+use crate::fines::domain::query::FineQueryServiceImpl;
+use crate::fines::domain::service::FineServiceImpl;
+use crate::fines::factory;
+use crate::fines::repository::{FineAdjustmentRepository, FinePaymentRepository, FineRepository};
+use crate::fines::repository::ddb_fine_adjustment_repository::DDBFineAdjustmentRepository;
+use crate::fines::repository::ddb_fine_payment_repository::DDBFinePaymentRepository;
+use crate::fines::repository::ddb_fine_repository::DDBFineRepository;
+use crate::gateway::factory::{create_payment_gateway, create_publisher};
+use crate::ledger::factory::create_ledger_service;
+use crate::patrons::factory::create_patron_service;
+use crate::utils::ddb::{build_db_client, create_table, ScanGuard};
+
+pub(crate) async fn create_fine_repository(store: RepositoryStore) -> Box<dyn FineRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBFineRepository::new(client, "fines", "fines_ndx").with_scan_guard(ScanGuard::new(store)))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("fines");
+            let _ = create_table(&client, table_name.as_str(), "fine_id", "patron_id", "assessed_at").await;
+            Box::new(DDBFineRepository::new(client, table_name.as_str(), store.table_name("fines_ndx").as_str()).with_scan_guard(ScanGuard::new(store)))
+        }
+    }
+}
+
+pub(crate) async fn create_fine_payment_repository(store: RepositoryStore) -> Box<dyn FinePaymentRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBFinePaymentRepository::new(client, "fine_payments", "fine_payments_ndx"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("fine_payments");
+            let _ = create_table(&client, table_name.as_str(), "payment_id", "patron_id", "created_at").await;
+            Box::new(DDBFinePaymentRepository::new(client, table_name.as_str(), store.table_name("fine_payments_ndx").as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_fine_adjustment_repository(store: RepositoryStore) -> Box<dyn FineAdjustmentRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBFineAdjustmentRepository::new(client, "fine_adjustments", "fine_adjustments_ndx"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("fine_adjustments");
+            let _ = create_table(&client, table_name.as_str(), "adjustment_id", "patron_id", "created_at").await;
+            Box::new(DDBFineAdjustmentRepository::new(client, table_name.as_str(), store.table_name("fine_adjustments_ndx").as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_fine_query_service(_config: &Configuration, store: RepositoryStore) -> Box<dyn FineQueryService> {
+    let fine_repo = factory::create_fine_repository(store).await;
+    let payment_repo = factory::create_fine_payment_repository(store).await;
+    let adjustment_repo = factory::create_fine_adjustment_repository(store).await;
+    Box::new(FineQueryServiceImpl::new(fine_repo, payment_repo, adjustment_repo))
+}
+
+pub(crate) async fn create_fine_service(config: &Configuration, store: RepositoryStore) -> Box<dyn FineService> {
+    let fine_repo = factory::create_fine_repository(store).await;
+    let payment_repo = factory::create_fine_payment_repository(store).await;
+    let adjustment_repo = factory::create_fine_adjustment_repository(store).await;
+    let patron_svc = create_patron_service(config, store).await;
+    let ledger_svc = create_ledger_service(config, store).await;
+    let publisher = create_publisher(store.gateway_publisher()).await;
+    let query_svc = create_fine_query_service(config, store).await;
+    Box::new(FineServiceImpl::new(config, fine_repo, payment_repo, adjustment_repo, patron_svc,
+                                  create_payment_gateway(config), ledger_svc, publisher, query_svc))
+}
@@ -0,0 +1,32 @@
+pub mod ddb_fine_repository;
+pub mod ddb_fine_payment_repository;
+pub mod ddb_fine_adjustment_repository;
+
+use async_trait::async_trait;
+use crate::core::library::{LibraryResult, PaginatedResult};
+use crate::core::repository::Repository;
+use crate::fines::domain::model::{FineAdjustmentEntity, FineEntity, FinePaymentEntity};
+
+#[async_trait]
+pub(crate) trait FineRepository: Repository<FineEntity> {
+    // fines of the patron, most recently assessed first
+    async fn find_by_patron(&self, patron_id: &str) -> LibraryResult<Vec<FineEntity>>;
+    // all fines of all branches, only meant for jobs such as reconciling the ledger
+    async fn scan(&self, page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<FineEntity>>;
+}
+
+#[async_trait]
+pub(crate) trait FinePaymentRepository: Repository<FinePaymentEntity> {
+    // payments of the patron, most recent first
+    async fn find_by_patron(&self, patron_id: &str) -> LibraryResult<Vec<FinePaymentEntity>>;
+}
+
+// FineAdjustmentRepository keeps the history of adjustments of fines, adjustments are added and never changed
+#[async_trait]
+pub(crate) trait FineAdjustmentRepository: Sync + Send {
+    async fn create(&self, adjustment: &FineAdjustmentEntity) -> LibraryResult<usize>;
+    // removes an adjustment that could not be applied to its fine
+    async fn delete(&self, adjustment_id: &str) -> LibraryResult<usize>;
+    // adjustments of fines of the patron, oldest first
+    async fn find_by_patron(&self, patron_id: &str) -> LibraryResult<Vec<FineAdjustmentEntity>>;
+}
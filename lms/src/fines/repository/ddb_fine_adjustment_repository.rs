@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::core::library::{AdjustmentKind, AdjustmentReason, LibraryError, LibraryResult};
+use crate::fines::domain::model::FineAdjustmentEntity;
+use crate::fines::repository::FineAdjustmentRepository;
+use crate::utils::ddb::{parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute};
+
+#[derive(Debug)]
+pub(crate) struct DDBFineAdjustmentRepository {
+    client: Client,
+    table_name: String,
+    index_name: String,
+}
+
+impl DDBFineAdjustmentRepository {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl FineAdjustmentRepository for DDBFineAdjustmentRepository {
+    async fn create(&self, adjustment: &FineAdjustmentEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(adjustment)?;
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(adjustment_id)")
+            .set_item(Some(parse_item(val)?))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn delete(&self, adjustment_id: &str) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client.delete_item()
+            .table_name(table_name)
+            .key("adjustment_id", AttributeValue::S(adjustment_id.to_string()))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn find_by_patron(&self, patron_id: &str) -> LibraryResult<Vec<FineAdjustmentEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let mut adjustments = vec![];
+        let mut last_key: Option<HashMap<String, AttributeValue>> = None;
+        loop {
+            let res = self.client
+                .query()
+                .table_name(table_name)
+                .index_name(index_name)
+                .consistent_read(false)
+                .set_exclusive_start_key(last_key)
+                .key_condition_expression("patron_id = :patron_id")
+                .expression_attribute_values(":patron_id", AttributeValue::S(patron_id.to_string()))
+                .send()
+                .await.map_err(LibraryError::from)?;
+            adjustments.extend(res.items.as_ref().unwrap_or(&vec![]).iter().map(FineAdjustmentEntity::from));
+            last_key = res.last_evaluated_key().cloned();
+            if last_key.is_none() {
+                break;
+            }
+        }
+        Ok(adjustments)
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for FineAdjustmentEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        FineAdjustmentEntity {
+            adjustment_id: parse_string_attribute("adjustment_id", map).unwrap_or_else(|| String::from("")),
+            fine_id: parse_string_attribute("fine_id", map).unwrap_or_else(|| String::from("")),
+            patron_id: parse_string_attribute("patron_id", map).unwrap_or_else(|| String::from("")),
+            branch_id: parse_string_attribute("branch_id", map).unwrap_or_else(|| String::from("")),
+            adjustment_kind: AdjustmentKind::from(parse_string_attribute("adjustment_kind", map).unwrap_or_else(|| String::from(""))),
+            reason: AdjustmentReason::from(parse_string_attribute("reason", map).unwrap_or_else(|| String::from(""))),
+            note: parse_string_attribute("note", map).unwrap_or_else(|| String::from("")),
+            amount: parse_number_attribute("amount", map),
+            balance_after: parse_number_attribute("balance_after", map),
+            adjusted_by: parse_string_attribute("adjusted_by", map).unwrap_or_else(|| String::from("")),
+            created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+    use uuid::Uuid;
+
+    use crate::core::library::{AdjustmentKind, AdjustmentReason};
+    use crate::core::repository::RepositoryStore;
+    use crate::fines::domain::model::{FineAdjustmentEntity, FineEntity};
+    use crate::fines::repository::ddb_fine_adjustment_repository::DDBFineAdjustmentRepository;
+    use crate::fines::repository::FineAdjustmentRepository;
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("fine_adjustments").as_str(), "adjustment_id", "patron_id", "created_at").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_create_find_delete_adjustments() {
+        let store = test_store(module_path!(), "test_should_create_find_delete_adjustments");
+        let repo = DDBFineAdjustmentRepository::new(build_client(store).await, store.table_name("fine_adjustments").as_str(), store.table_name("fine_adjustments_ndx").as_str());
+        let fine = FineEntity::new(Uuid::new_v4().to_string().as_str(), "overdue", 250);
+        let waiver = FineAdjustmentEntity::new(&fine, AdjustmentKind::Waive, AdjustmentReason::Hardship, 100, "librarian1");
+        assert_eq!(1, repo.create(&waiver).await.expect("should create adjustment"));
+        assert!(repo.create(&waiver).await.is_err());
+
+        let adjustments = repo.find_by_patron(fine.patron_id.as_str()).await.expect("should find adjustments");
+        assert_eq!(vec![waiver.adjustment_id.to_string()], adjustments.into_iter().map(|a| a.adjustment_id).collect::<Vec<String>>());
+        assert_eq!(1, repo.delete(waiver.adjustment_id.as_str()).await.expect("should delete adjustment"));
+        assert!(repo.find_by_patron(fine.patron_id.as_str()).await.expect("should find adjustments").is_empty());
+    }
+}
@@ -0,0 +1,204 @@
+use std::cmp;
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult};
+use crate::core::repository::Repository;
+use crate::fines::domain::model::FinePaymentEntity;
+use crate::fines::repository::FinePaymentRepository;
+use crate::gateway::payments::PaymentStatus;
+use crate::utils::ddb::{from_ddb, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, string_date, to_ddb_page};
+
+#[derive(Debug)]
+pub(crate) struct DDBFinePaymentRepository {
+    client: Client,
+    table_name: String,
+    index_name: String,
+}
+
+impl DDBFinePaymentRepository {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl Repository<FinePaymentEntity> for DDBFinePaymentRepository {
+    // a payment is only created once per idempotency key, a retry fails on the condition and reads the payment
+    async fn create(&self, entity: &FinePaymentEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        let res = self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(payment_id)")
+            .set_item(Some(parse_item(val)?))
+            .send()
+            .await;
+        match res {
+            Ok(_) => Ok(1),
+            Err(SdkError::ServiceError(ctx)) if ctx.err().is_conditional_check_failed_exception() =>
+                Err(LibraryError::duplicate_key(format!("payment {} already exists", entity.payment_id).as_str())),
+            Err(err) => Err(LibraryError::from(err)),
+        }
+    }
+
+    async fn update(&self, entity: &FinePaymentEntity) -> LibraryResult<usize> {
+        let now = Utc::now().naive_utc();
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .update_item()
+            .table_name(table_name)
+            .key("payment_id", AttributeValue::S(entity.payment_id.clone()))
+            .update_expression("SET version = :version, intent_id = :intent_id, client_secret = :client_secret, payment_status = :payment_status, event_id = :event_id, updated_at = :updated_at")
+            .expression_attribute_values(":old_version", AttributeValue::N(entity.version.to_string()))
+            .expression_attribute_values(":version", AttributeValue::N((entity.version + 1).to_string()))
+            .expression_attribute_values(":intent_id", AttributeValue::S(entity.intent_id.to_string()))
+            .expression_attribute_values(":client_secret", AttributeValue::S(entity.client_secret.to_string()))
+            .expression_attribute_values(":payment_status", AttributeValue::S(entity.payment_status.to_string()))
+            .expression_attribute_values(":event_id", AttributeValue::S(entity.event_id.to_string()))
+            .expression_attribute_values(":updated_at", string_date(now))
+            .condition_expression("attribute_exists(version) AND version = :old_version")
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn get(&self, id: &str) -> LibraryResult<FinePaymentEntity> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .query()
+            .table_name(table_name)
+            .limit(1)
+            .consistent_read(true)
+            .key_condition_expression("payment_id = :payment_id")
+            .expression_attribute_values(":payment_id", AttributeValue::S(id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).and_then(|req| {
+            if let Some(map) = req.items.as_ref().and_then(|items| items.first()) {
+                return Ok(FinePaymentEntity::from(map));
+            }
+            Err(LibraryError::not_found(format!("payment not found for {}", id).as_str()))
+        })
+    }
+
+    async fn delete(&self, id: &str) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client.delete_item()
+            .table_name(table_name)
+            .key("payment_id", AttributeValue::S(id.to_string()))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn query(&self, predicate: &HashMap<String, String>,
+                   page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<FinePaymentEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let patron_id = predicate.get("patron_id").ok_or_else(|| LibraryError::validation(
+            "patron_id is required to query payments", Some("400".to_string())))?;
+        self.client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .limit(cmp::min(page_size, 500) as i32)
+            .consistent_read(false)
+            .scan_index_forward(false)
+            .set_exclusive_start_key(to_ddb_page(page, predicate))
+            .key_condition_expression("patron_id = :patron_id")
+            .expression_attribute_values(":patron_id", AttributeValue::S(patron_id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(FinePaymentEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+}
+
+#[async_trait]
+impl FinePaymentRepository for DDBFinePaymentRepository {
+    async fn find_by_patron(&self, patron_id: &str) -> LibraryResult<Vec<FinePaymentEntity>> {
+        let predicate = HashMap::from([("patron_id".to_string(), patron_id.to_string())]);
+        let mut payments = vec![];
+        let mut page: Option<String> = None;
+        loop {
+            let res = self.query(&predicate, page.as_deref(), 500).await?;
+            payments.extend(res.records);
+            page = res.next_page;
+            if page.is_none() {
+                break;
+            }
+        }
+        Ok(payments)
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for FinePaymentEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        FinePaymentEntity {
+            payment_id: parse_string_attribute("payment_id", map).unwrap_or_else(|| String::from("")),
+            version: parse_number_attribute("version", map),
+            fine_id: parse_string_attribute("fine_id", map).unwrap_or_else(|| String::from("")),
+            patron_id: parse_string_attribute("patron_id", map).unwrap_or_else(|| String::from("")),
+            idempotency_key: parse_string_attribute("idempotency_key", map).unwrap_or_else(|| String::from("")),
+            amount: parse_number_attribute("amount", map),
+            currency: parse_string_attribute("currency", map).unwrap_or_else(|| String::from("")),
+            intent_id: parse_string_attribute("intent_id", map).unwrap_or_else(|| String::from("")),
+            client_secret: parse_string_attribute("client_secret", map).unwrap_or_else(|| String::from("")),
+            payment_status: PaymentStatus::from(parse_string_attribute("payment_status", map).unwrap_or_else(|| PaymentStatus::RequiresPayment.to_string())),
+            event_id: parse_string_attribute("event_id", map).unwrap_or_else(|| String::from("")),
+            created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+    use uuid::Uuid;
+
+    use crate::core::repository::{Repository, RepositoryStore};
+    use crate::fines::domain::model::FinePaymentEntity;
+    use crate::fines::repository::ddb_fine_payment_repository::DDBFinePaymentRepository;
+    use crate::fines::repository::FinePaymentRepository;
+    use crate::gateway::payments::PaymentStatus;
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("fine_payments").as_str(), "payment_id", "patron_id", "created_at").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_create_payment_once_per_key() {
+        let store = test_store(module_path!(), "test_should_create_payment_once_per_key");
+        let payment_repo = DDBFinePaymentRepository::new(build_client(store).await, store.table_name("fine_payments").as_str(), store.table_name("fine_payments_ndx").as_str());
+        let patron_id = Uuid::new_v4().to_string();
+        let mut payment = FinePaymentEntity::new("fine1", patron_id.as_str(), "key1", 250, "usd");
+        assert_eq!(1, payment_repo.create(&payment).await.expect("should create payment"));
+        assert!(payment_repo.create(&FinePaymentEntity::new("fine1", patron_id.as_str(), "key1", 250, "usd")).await.is_err());
+
+        payment.intent_id = "pi_1".to_string();
+        payment.payment_status = PaymentStatus::Succeeded;
+        assert_eq!(1, payment_repo.update(&payment).await.expect("should update payment"));
+        assert!(payment_repo.update(&payment).await.is_err());
+
+        let loaded = payment_repo.get(payment.payment_id.as_str()).await.expect("should get payment");
+        assert_eq!(PaymentStatus::Succeeded, loaded.payment_status);
+        assert_eq!("pi_1", loaded.intent_id.as_str());
+        let payments = payment_repo.find_by_patron(patron_id.as_str()).await.expect("should find payments");
+        assert_eq!(vec![payment.payment_id.to_string()], payments.into_iter().map(|p| p.payment_id).collect::<Vec<String>>());
+    }
+}
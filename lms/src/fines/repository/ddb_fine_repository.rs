@@ -0,0 +1,232 @@
+use std::cmp;
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::core::library::{FineStatus, LibraryError, LibraryResult, PaginatedResult};
+use crate::core::repository::Repository;
+use crate::fines::domain::model::FineEntity;
+use crate::fines::repository::FineRepository;
+use crate::utils::ddb::{from_ddb, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, parse_string_set_attribute, string_date, to_ddb_page, ScanGuard};
+
+#[derive(Debug)]
+pub(crate) struct DDBFineRepository {
+    client: Client,
+    table_name: String,
+    index_name: String,
+    scan_guard: ScanGuard,
+}
+
+impl DDBFineRepository {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+            scan_guard: ScanGuard::default(),
+        }
+    }
+
+    pub(crate) fn with_scan_guard(mut self, scan_guard: ScanGuard) -> Self {
+        self.scan_guard = scan_guard;
+        self
+    }
+}
+
+#[async_trait]
+impl Repository<FineEntity> for DDBFineRepository {
+    async fn create(&self, entity: &FineEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(fine_id)")
+            .set_item(Some(parse_item(val)?))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    // amounts are only changed through versioned updates so that concurrent confirmations cannot lose a payment
+    async fn update(&self, entity: &FineEntity) -> LibraryResult<usize> {
+        let now = Utc::now().naive_utc();
+        let table_name: &str = self.table_name.as_ref();
+        let payment_ids = entity.payment_ids.iter().map(|id| AttributeValue::S(id.to_string())).collect();
+        let adjustment_ids = entity.adjustment_ids.iter().map(|id| AttributeValue::S(id.to_string())).collect();
+
+        self.client
+            .update_item()
+            .table_name(table_name)
+            .key("fine_id", AttributeValue::S(entity.fine_id.clone()))
+            .update_expression("SET version = :version, amount = :amount, paid = :paid, waived = :waived, refunded = :refunded, fine_status = :fine_status, payment_ids = :payment_ids, adjustment_ids = :adjustment_ids, updated_at = :updated_at")
+            .expression_attribute_values(":old_version", AttributeValue::N(entity.version.to_string()))
+            .expression_attribute_values(":version", AttributeValue::N((entity.version + 1).to_string()))
+            .expression_attribute_values(":amount", AttributeValue::N(entity.amount.to_string()))
+            .expression_attribute_values(":paid", AttributeValue::N(entity.paid.to_string()))
+            .expression_attribute_values(":waived", AttributeValue::N(entity.waived.to_string()))
+            .expression_attribute_values(":refunded", AttributeValue::N(entity.refunded.to_string()))
+            .expression_attribute_values(":fine_status", AttributeValue::S(entity.fine_status.to_string()))
+            .expression_attribute_values(":payment_ids", AttributeValue::L(payment_ids))
+            .expression_attribute_values(":adjustment_ids", AttributeValue::L(adjustment_ids))
+            .expression_attribute_values(":updated_at", string_date(now))
+            .condition_expression("attribute_exists(version) AND version = :old_version")
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn get(&self, id: &str) -> LibraryResult<FineEntity> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .query()
+            .table_name(table_name)
+            .limit(1)
+            .consistent_read(true)
+            .key_condition_expression("fine_id = :fine_id")
+            .expression_attribute_values(":fine_id", AttributeValue::S(id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).and_then(|req| {
+            if let Some(map) = req.items.as_ref().and_then(|items| items.first()) {
+                return Ok(FineEntity::from(map));
+            }
+            Err(LibraryError::not_found(format!("fine not found for {}", id).as_str()))
+        })
+    }
+
+    async fn delete(&self, id: &str) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client.delete_item()
+            .table_name(table_name)
+            .key("fine_id", AttributeValue::S(id.to_string()))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn query(&self, predicate: &HashMap<String, String>,
+                   page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<FineEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let patron_id = predicate.get("patron_id").ok_or_else(|| LibraryError::validation(
+            "patron_id is required to query fines", Some("400".to_string())))?;
+        self.client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .limit(cmp::min(page_size, 500) as i32)
+            .consistent_read(false)
+            .scan_index_forward(false)
+            .set_exclusive_start_key(to_ddb_page(page, predicate))
+            .key_condition_expression("patron_id = :patron_id")
+            .expression_attribute_values(":patron_id", AttributeValue::S(patron_id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(FineEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+}
+
+#[async_trait]
+impl FineRepository for DDBFineRepository {
+    async fn find_by_patron(&self, patron_id: &str) -> LibraryResult<Vec<FineEntity>> {
+        let predicate = HashMap::from([("patron_id".to_string(), patron_id.to_string())]);
+        let mut fines = vec![];
+        let mut page: Option<String> = None;
+        loop {
+            let res = self.query(&predicate, page.as_deref(), 500).await?;
+            fines.extend(res.records);
+            page = res.next_page;
+            if page.is_none() {
+                break;
+            }
+        }
+        Ok(fines)
+    }
+
+    async fn scan(&self, page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<FineEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        self.scan_guard.check(table_name)?;
+        self.client
+            .scan()
+            .table_name(table_name)
+            .consistent_read(false)
+            .set_exclusive_start_key(to_ddb_page(page, &HashMap::new()))
+            .limit(cmp::min(page_size, 500) as i32)
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(FineEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for FineEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        FineEntity {
+            fine_id: parse_string_attribute("fine_id", map).unwrap_or_else(|| String::from("")),
+            version: parse_number_attribute("version", map),
+            branch_id: parse_string_attribute("branch_id", map).unwrap_or_else(|| String::from("")),
+            patron_id: parse_string_attribute("patron_id", map).unwrap_or_else(|| String::from("")),
+            checkout_id: parse_string_attribute("checkout_id", map).unwrap_or_else(|| String::from("")),
+            reason: parse_string_attribute("reason", map).unwrap_or_else(|| String::from("")),
+            amount: parse_number_attribute("amount", map),
+            paid: parse_number_attribute("paid", map),
+            waived: parse_number_attribute("waived", map),
+            refunded: parse_number_attribute("refunded", map),
+            fine_status: FineStatus::from(parse_string_attribute("fine_status", map).unwrap_or_else(|| FineStatus::Outstanding.to_string())),
+            payment_ids: parse_string_set_attribute("payment_ids", map),
+            adjustment_ids: parse_string_set_attribute("adjustment_ids", map),
+            assessed_by: parse_string_attribute("assessed_by", map).unwrap_or_else(|| String::from("")),
+            assessed_at: parse_date_attribute("assessed_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+    use uuid::Uuid;
+
+    use crate::core::library::FineStatus;
+    use crate::core::repository::{Repository, RepositoryStore};
+    use crate::fines::domain::model::FineEntity;
+    use crate::fines::repository::ddb_fine_repository::DDBFineRepository;
+    use crate::fines::repository::FineRepository;
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("fines").as_str(), "fine_id", "patron_id", "assessed_at").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_create_update_find_fine() {
+        let store = test_store(module_path!(), "test_should_create_update_find_fine");
+        let fine_repo = DDBFineRepository::new(build_client(store).await, store.table_name("fines").as_str(), store.table_name("fines_ndx").as_str());
+        let mut fine = FineEntity::new(Uuid::new_v4().to_string().as_str(), "overdue", 250);
+        assert_eq!(1, fine_repo.create(&fine).await.expect("should create fine"));
+        assert!(fine_repo.create(&fine).await.is_err());
+
+        assert!(fine.apply_payment("pay_1", 250));
+        assert_eq!(1, fine_repo.update(&fine).await.expect("should update fine"));
+        // the stale version is rejected
+        assert!(fine_repo.update(&fine).await.is_err());
+
+        let loaded = fine_repo.get(fine.fine_id.as_str()).await.expect("should get fine");
+        assert_eq!(FineStatus::Paid, loaded.fine_status);
+        assert_eq!(vec!["pay_1".to_string()], loaded.payment_ids);
+        assert_eq!(1, loaded.version);
+        let fines = fine_repo.find_by_patron(fine.patron_id.as_str()).await.expect("should find fines");
+        assert_eq!(vec![fine.fine_id.to_string()], fines.into_iter().map(|f| f.fine_id).collect::<Vec<String>>());
+        let res = fine_repo.scan(None, 500).await.expect("should scan fines");
+        assert!(res.records.iter().any(|f| f.fine_id == fine.fine_id));
+    }
+}
@@ -0,0 +1,264 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Utc};
+use crate::core::library::{AdjustmentKind, LibraryError, LibraryResult, StatementEntryKind};
+use crate::fines::dto::{FineAdjustmentDto, FineDto, FinePaymentDto, PatronStatementDto, StatementLineDto};
+use crate::gateway::payments::PaymentStatus;
+
+const DATE_FMT: &str = "%Y-%m-%d";
+
+// longest period of a statement so that a statement never walks years of history of a patron
+pub(crate) const MAX_STATEMENT_DAYS: i64 = 366;
+
+// StatementFormat is the rendering of a statement, html is laid out for printing or converting to a PDF
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) enum StatementFormat {
+    #[default]
+    Json,
+    Html,
+}
+
+impl StatementFormat {
+    // the `format` parameter takes precedence over the Accept header, unknown parameters are rejected while unknown
+    // media types fall back to json
+    pub(crate) fn negotiate(format: Option<&str>, accept: Option<&str>) -> LibraryResult<StatementFormat> {
+        if let Some(format) = format {
+            return match format.trim().to_lowercase().as_str() {
+                "json" => Ok(StatementFormat::Json),
+                "html" => Ok(StatementFormat::Html),
+                other => Err(LibraryError::validation(
+                    format!("unsupported statement format {}, expected json or html", other).as_str(), Some("400".to_string()))),
+            };
+        }
+        let media_types = accept.unwrap_or_default().split(',')
+            .map(|media_type| media_type.split(';').next().unwrap_or_default().trim().to_lowercase())
+            .collect::<Vec<String>>();
+        for media_type in media_types {
+            match media_type.as_str() {
+                "text/html" => return Ok(StatementFormat::Html),
+                "application/json" => return Ok(StatementFormat::Json),
+                _ => {}
+            }
+        }
+        Ok(StatementFormat::Json)
+    }
+}
+
+// days of the statement from the `from` and `to` parameters in YYYY-MM-DD, the period defaults to the month of `to`
+// up to `to` and `to` defaults to today
+pub(crate) fn statement_period(from: Option<&str>, to: Option<&str>, today: NaiveDate) -> LibraryResult<(NaiveDate, NaiveDate)> {
+    let parse = |name: &str, value: &str| NaiveDate::parse_from_str(value.trim(), DATE_FMT).map_err(|_| LibraryError::validation(
+        format!("{} must be a date in YYYY-MM-DD but was {}", name, value).as_str(), Some("400".to_string())));
+    let to = match to.filter(|to| !to.trim().is_empty()) {
+        Some(to) => parse("to", to)?,
+        None => today,
+    };
+    let from = match from.filter(|from| !from.trim().is_empty()) {
+        Some(from) => parse("from", from)?,
+        None => to.with_day(1).unwrap_or(to),
+    };
+    if from > to {
+        return Err(LibraryError::validation(format!("from {} is after to {}", from, to).as_str(), Some("400".to_string())));
+    }
+    if (to - from).num_days() >= MAX_STATEMENT_DAYS {
+        return Err(LibraryError::validation(
+            format!("statements cover at most {} days", MAX_STATEMENT_DAYS).as_str(), Some("400".to_string())));
+    }
+    Ok((from, to))
+}
+
+// itemizes the fines of a patron with the payments that were applied to them and their waivers and refunds, payments
+// that did not succeed or were never applied to a fine are left out because they did not change what is owed
+pub(crate) fn build_statement(patron_id: &str, currency: &str, from: NaiveDate, to: NaiveDate, fines: &[FineDto],
+                              payments: &[FinePaymentDto], adjustments: &[FineAdjustmentDto]) -> PatronStatementDto {
+    let mut entries = vec![];
+    for fine in fines {
+        entries.push((fine.assessed_at, fine.fine_id.to_string(), StatementEntryKind::Fine, fine.reason.to_string(), fine.amount));
+    }
+    for payment in payments.iter().filter(|p| p.payment_status == PaymentStatus::Succeeded) {
+        if fines.iter().any(|f| f.fine_id == payment.fine_id && f.payment_ids.contains(&payment.payment_id)) {
+            entries.push((payment.updated_at, payment.fine_id.to_string(), StatementEntryKind::Payment,
+                          format!("Payment {}", payment.payment_id), -payment.amount));
+        }
+    }
+    for adjustment in adjustments {
+        let description = if adjustment.note.is_empty() {
+            adjustment.reason.to_string()
+        } else {
+            format!("{}: {}", adjustment.reason, adjustment.note)
+        };
+        let (kind, amount) = match adjustment.adjustment_kind {
+            AdjustmentKind::Waive => (StatementEntryKind::Waiver, -adjustment.amount),
+            AdjustmentKind::Refund => (StatementEntryKind::Refund, adjustment.amount),
+        };
+        entries.push((adjustment.created_at, adjustment.fine_id.to_string(), kind, description, amount));
+    }
+    entries.sort_by_key(|entry| entry.0);
+
+    let start = from.and_hms_opt(0, 0, 0).unwrap_or_default();
+    let end = (to + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap_or_default();
+    let mut statement = PatronStatementDto {
+        patron_id: patron_id.to_string(),
+        currency: currency.to_string(),
+        from,
+        to,
+        opening_balance: 0,
+        assessed: 0,
+        paid: 0,
+        waived: 0,
+        refunded: 0,
+        closing_balance: 0,
+        lines: vec![],
+        generated_at: Utc::now().naive_utc(),
+    };
+    let mut balance = 0;
+    for (occurred_at, fine_id, entry_kind, description, amount) in entries {
+        if occurred_at >= end {
+            break;
+        }
+        balance += amount;
+        if occurred_at < start {
+            statement.opening_balance = balance;
+            continue;
+        }
+        match entry_kind {
+            StatementEntryKind::Fine => statement.assessed += amount,
+            StatementEntryKind::Payment => statement.paid -= amount,
+            StatementEntryKind::Waiver => statement.waived -= amount,
+            StatementEntryKind::Refund => statement.refunded += amount,
+        }
+        statement.lines.push(StatementLineDto { fine_id, entry_kind, description, amount, balance, occurred_at });
+    }
+    statement.closing_balance = balance;
+    statement
+}
+
+// amounts are kept in the smallest unit of the currency and printed with two decimals
+fn money(amount: i64) -> String {
+    let sign = if amount < 0 { "-" } else { "" };
+    format!("{}{}.{:02}", sign, amount.abs() / 100, amount.abs() % 100)
+}
+
+fn date(time: NaiveDateTime) -> String {
+    time.format(DATE_FMT).to_string()
+}
+
+// reasons and notes of fines are entered by staff so they are escaped before they are rendered
+fn escape_html(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// self-contained html of a statement with print styles so that it can be printed or converted to a PDF as it is
+pub(crate) fn to_html(statement: &PatronStatementDto) -> String {
+    let currency = escape_html(statement.currency.to_uppercase().as_str());
+    let rows = statement.lines.iter().map(|line| format!(
+        "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td class=\"amount\">{}</td><td class=\"amount\">{}</td></tr>",
+        date(line.occurred_at), line.entry_kind, escape_html(line.description.as_str()), escape_html(line.fine_id.as_str()),
+        money(line.amount), money(line.balance))).collect::<Vec<String>>().join("");
+    let rows = if rows.is_empty() { "<tr><td colspan=\"6\">No activity in this period</td></tr>".to_string() } else { rows };
+    let totals = [
+        ("Opening balance", statement.opening_balance),
+        ("Fines assessed", statement.assessed),
+        ("Payments", -statement.paid),
+        ("Waivers", -statement.waived),
+        ("Refunds", statement.refunded),
+        ("Closing balance", statement.closing_balance),
+    ].iter().map(|(label, amount)| format!("<tr><th>{}</th><td class=\"amount\">{} {}</td></tr>", label, money(*amount), currency))
+        .collect::<Vec<String>>().join("");
+    format!("<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Statement {} to {}</title>\
+<style>@page {{ size: A4; margin: 20mm; }} body {{ font-family: sans-serif; font-size: 10pt; }} \
+table {{ width: 100%; border-collapse: collapse; margin-bottom: 8mm; }} th, td {{ border-bottom: 1px solid #ccc; padding: 2mm; text-align: left; }} \
+.amount {{ text-align: right; }} tr {{ page-break-inside: avoid; }}</style></head>\
+<body><h1>Statement</h1><p>Patron {}<br>{} to {}</p><table>{}</table>\
+<table><thead><tr><th>Date</th><th>Type</th><th>Description</th><th>Fine</th><th class=\"amount\">Amount</th><th class=\"amount\">Balance</th></tr></thead>\
+<tbody>{}</tbody></table><p>Generated {}</p></body></html>",
+            statement.from, statement.to, escape_html(statement.patron_id.as_str()), statement.from, statement.to, totals, rows,
+            statement.generated_at.format("%Y-%m-%d %H:%M"))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate, NaiveDateTime};
+    use crate::core::library::{AdjustmentKind, AdjustmentReason, StatementEntryKind};
+    use crate::fines::dto::{FineAdjustmentDto, FineDto, FinePaymentDto};
+    use crate::fines::statement::{build_statement, statement_period, to_html, StatementFormat};
+    use crate::gateway::payments::PaymentStatus;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).expect("should be a date")
+    }
+
+    fn at(d: u32) -> NaiveDateTime {
+        day(d).and_hms_opt(12, 0, 0).expect("should be a time")
+    }
+
+    fn payment(fine: &FineDto, amount: i64, status: PaymentStatus, d: u32) -> FinePaymentDto {
+        FinePaymentDto {
+            payment_id: format!("pay_{}_{}", fine.fine_id, d),
+            version: 0,
+            fine_id: fine.fine_id.to_string(),
+            patron_id: fine.patron_id.to_string(),
+            idempotency_key: d.to_string(),
+            amount,
+            currency: "usd".to_string(),
+            intent_id: String::new(),
+            client_secret: String::new(),
+            payment_status: status,
+            event_id: String::new(),
+            created_at: at(d),
+            updated_at: at(d),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_should_negotiate_statement_format() {
+        assert_eq!(StatementFormat::Json, StatementFormat::negotiate(None, None).expect("should negotiate"));
+        assert_eq!(StatementFormat::Html, StatementFormat::negotiate(None, Some("text/html,application/xhtml+xml")).expect("should negotiate"));
+        assert_eq!(StatementFormat::Json, StatementFormat::negotiate(Some("JSON"), Some("text/html")).expect("should negotiate"));
+        assert!(StatementFormat::negotiate(Some("pdf"), None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_parse_statement_period() {
+        assert_eq!((day(1), day(20)), statement_period(None, None, day(20)).expect("should parse"));
+        assert_eq!((day(5), day(10)), statement_period(Some("2026-03-05"), Some("2026-03-10"), day(20)).expect("should parse"));
+        assert!(statement_period(Some("2026-03-10"), Some("2026-03-05"), day(20)).is_err());
+        assert!(statement_period(Some("03/05/2026"), None, day(20)).is_err());
+        assert!(statement_period(Some("2024-01-01"), Some("2026-03-05"), day(20)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_build_statement() {
+        let mut old = FineDto::new("librarian1", "patron1", "overdue", 300);
+        old.assessed_at = at(1);
+        let mut lost = FineDto::new("librarian1", "patron1", "lost <item> & damage", 1000);
+        lost.assessed_at = at(6);
+        let paid = payment(&old, 300, PaymentStatus::Succeeded, 7);
+        old.payment_ids = vec![paid.payment_id.to_string()];
+        let failed = payment(&lost, 1000, PaymentStatus::Failed, 8);
+        let mut waiver = FineAdjustmentDto::new("librarian1", lost.fine_id.as_str(), AdjustmentKind::Waive, AdjustmentReason::Hardship, 400);
+        waiver.created_at = at(9);
+        let mut late = FineDto::new("librarian1", "patron1", "overdue", 50);
+        late.assessed_at = at(25);
+
+        let statement = build_statement("patron1", "usd", day(5), day(20), &[late, lost.clone(), old],
+                                        &[failed, paid], &[waiver]);
+        assert_eq!(300, statement.opening_balance);
+        assert_eq!(1000, statement.assessed);
+        assert_eq!(300, statement.paid);
+        assert_eq!(400, statement.waived);
+        assert_eq!(0, statement.refunded);
+        assert_eq!(600, statement.closing_balance);
+        assert_eq!(vec![StatementEntryKind::Fine, StatementEntryKind::Payment, StatementEntryKind::Waiver],
+                   statement.lines.iter().map(|l| l.entry_kind).collect::<Vec<StatementEntryKind>>());
+        assert_eq!(vec![1300, 1000, 600], statement.lines.iter().map(|l| l.balance).collect::<Vec<i64>>());
+        assert_eq!(lost.fine_id, statement.lines[0].fine_id);
+
+        let html = to_html(&statement);
+        assert!(html.contains("lost &lt;item&gt; &amp; damage"));
+        assert!(html.contains("<th>Closing balance</th><td class=\"amount\">6.00 USD</td>"));
+        assert!(html.contains("-4.00"));
+
+        let empty = build_statement("patron1", "usd", day(1) + Duration::days(20), day(22), &[], &[], &[]);
+        assert_eq!(0, empty.closing_balance);
+        assert!(to_html(&empty).contains("No activity in this period"));
+    }
+}
@@ -1,23 +1,42 @@
+pub mod address;
+pub mod archive;
+pub mod command;
+pub mod controller;
+pub mod dedup;
+pub mod objects;
 pub mod ddb;
 pub mod events;
+pub mod http;
+pub mod lambda;
 pub mod logs;
+pub mod payments;
+pub mod ses;
 pub mod sns;
+pub mod sru;
+pub mod stream;
+pub mod subscribers;
+pub mod tasks;
+pub mod topics;
 pub mod factory;
 
+use crate::core::repository::RepositoryStore;
+
 #[derive(Debug, PartialEq)]
 pub(crate) enum GatewayPublisherVia {
     Sns,
-    LocalDynamoDB,
+    // events are written to the events table of the local store
+    LocalDynamoDB(RepositoryStore),
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::core::repository::RepositoryStore;
     use crate::gateway::GatewayPublisherVia;
 
     #[tokio::test]
     async fn test_should_create_sns_via() {
         let _ = GatewayPublisherVia::Sns;
-        let _ = GatewayPublisherVia::LocalDynamoDB;
+        let _ = GatewayPublisherVia::LocalDynamoDB(RepositoryStore::LocalDynamoDB);
     }
 }
 
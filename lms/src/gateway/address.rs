@@ -0,0 +1,143 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::gateway::http::HttpClient;
+
+// PostalAddress defines components of an address sent to and returned by address validation, coordinates are
+// only known once the address is geocoded
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub(crate) struct PostalAddress {
+    pub street_address: String,
+    pub city: String,
+    pub zip_code: String,
+    pub state: String,
+    pub country: String,
+    #[serde(default)]
+    pub latitude: Option<f64>,
+    #[serde(default)]
+    pub longitude: Option<f64>,
+}
+
+// AddressValidator checks an address and returns its normalized components along with its coordinates when
+// the provider geocodes addresses
+#[async_trait]
+pub(crate) trait AddressValidator: Sync + Send {
+    async fn validate(&self, address: &PostalAddress) -> LibraryResult<PostalAddress>;
+}
+
+// StubAddressValidator is used when no provider is configured, it only normalizes the components locally
+#[derive(Debug, Default)]
+pub(crate) struct StubAddressValidator {}
+
+impl StubAddressValidator {
+    pub(crate) fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl AddressValidator for StubAddressValidator {
+    async fn validate(&self, address: &PostalAddress) -> LibraryResult<PostalAddress> {
+        let normalized = PostalAddress {
+            street_address: collapse_spaces(address.street_address.as_str()),
+            city: collapse_spaces(address.city.as_str()),
+            zip_code: collapse_spaces(address.zip_code.as_str()).to_uppercase(),
+            state: collapse_spaces(address.state.as_str()).to_uppercase(),
+            country: collapse_spaces(address.country.as_str()).to_uppercase(),
+            latitude: address.latitude,
+            longitude: address.longitude,
+        };
+        if normalized.street_address.is_empty() || normalized.city.is_empty() || normalized.country.is_empty() {
+            return Err(LibraryError::validation(
+                "address requires street address, city and country", Some("400".to_string())));
+        }
+        Ok(normalized)
+    }
+}
+
+// HttpAddressValidator posts addresses to an external validation and geocoding provider that responds with the
+// normalized address, addresses rejected by the provider fail with a validation error
+pub(crate) struct HttpAddressValidator {
+    client: HttpClient,
+    url: String,
+}
+
+impl HttpAddressValidator {
+    pub(crate) fn new(client: HttpClient, url: &str) -> Self {
+        Self {
+            client,
+            url: url.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl AddressValidator for HttpAddressValidator {
+    async fn validate(&self, address: &PostalAddress) -> LibraryResult<PostalAddress> {
+        // the provider sees the locally normalized address so that trivial differences do not fail validation
+        let normalized = StubAddressValidator::new().validate(address).await?;
+        self.client.post_json(self.url.as_str(), &normalized).await
+    }
+}
+
+fn collapse_spaces(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use axum::routing::post;
+    use axum::{Json, Router};
+    use crate::gateway::address::{AddressValidator, HttpAddressValidator, PostalAddress, StubAddressValidator};
+    use crate::gateway::http::{HttpClient, HttpClientConfig};
+
+    fn address() -> PostalAddress {
+        PostalAddress {
+            street_address: " 100  Main St. ".to_string(),
+            city: "Seattle".to_string(),
+            zip_code: "98101".to_string(),
+            state: "wa".to_string(),
+            country: "us".to_string(),
+            latitude: None,
+            longitude: None,
+        }
+    }
+
+    // geocodes every address to the same coordinates
+    async fn geocode(Json(mut address): Json<PostalAddress>) -> Json<PostalAddress> {
+        address.latitude = Some(47.6);
+        address.longitude = Some(-122.3);
+        Json(address)
+    }
+
+    #[tokio::test]
+    async fn test_should_normalize_address_with_stub() {
+        let validated = StubAddressValidator::new().validate(&address()).await.expect("should validate address");
+        assert_eq!("100 Main St.", validated.street_address.as_str());
+        assert_eq!("WA", validated.state.as_str());
+        assert_eq!("US", validated.country.as_str());
+        assert_eq!(None, validated.latitude);
+
+        let mut incomplete = address();
+        incomplete.city = " ".to_string();
+        assert!(StubAddressValidator::new().validate(&incomplete).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_geocode_address_with_provider() {
+        let app = Router::new().route("/validate", post(geocode));
+        let listener = TcpListener::bind("127.0.0.1:0").expect("should bind");
+        let url = format!("http://{}/validate", listener.local_addr().expect("should have address"));
+        tokio::spawn(async move {
+            let _ = axum::Server::from_tcp(listener).expect("should serve").serve(app.into_make_service()).await;
+        });
+
+        let client = HttpClient::new(HttpClientConfig::default()).expect("should build client");
+        let validator = HttpAddressValidator::new(client, url.as_str());
+        let validated = validator.validate(&address()).await.expect("should validate address");
+        assert_eq!("100 Main St.", validated.street_address.as_str());
+        assert_eq!(Some(47.6), validated.latitude);
+        assert_eq!(Some(-122.3), validated.longitude);
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::{BTreeMap, HashSet};
+use std::io::{Read, Write};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use crate::core::events::DomainEvent;
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::gateway::events::{EventHistory, EventLog};
+use crate::gateway::objects::ObjectStore;
+
+const ARCHIVE_PREFIX: &str = "events/";
+const ARCHIVE_CONTENT_TYPE: &str = "application/gzip";
+
+// EventArchive is the long-term tier of published events, each group and day of events is one gzipped JSON lines
+// object under events/{group}/{yyyy-mm-dd}.jsonl.gz so that the history of an entity reads only its groups
+pub(crate) struct EventArchive {
+    objects: Box<dyn ObjectStore>,
+}
+
+impl EventArchive {
+    pub(crate) fn new(objects: Box<dyn ObjectStore>) -> Self {
+        Self {
+            objects,
+        }
+    }
+
+    fn object_key(group: &str, day: NaiveDate) -> String {
+        format!("{}{}/{}.jsonl.gz", ARCHIVE_PREFIX, group, day.format("%Y-%m-%d"))
+    }
+
+    // writes the events into the objects of their group and day merged with the events already in them, so that
+    // archiving the same events again leaves the objects unchanged, returns the keys of the written objects
+    pub(crate) async fn archive(&self, events: &[DomainEvent]) -> LibraryResult<Vec<String>> {
+        let mut objects: BTreeMap<String, Vec<DomainEvent>> = BTreeMap::new();
+        for event in events {
+            objects.entry(Self::object_key(event.group.as_str(), event.created_at.date())).or_default().push(event.clone());
+        }
+        let mut written = vec![];
+        for (key, mut events) in objects {
+            match self.read_object(key.as_str()).await {
+                Ok(archived) => events.extend(archived),
+                Err(LibraryError::NotFound { .. }) => {}
+                Err(err) => return Err(err),
+            }
+            self.objects.put(key.as_str(), ARCHIVE_CONTENT_TYPE, encode(&in_published_order(events))?).await?;
+            written.push(key);
+        }
+        Ok(written)
+    }
+
+    async fn read_object(&self, key: &str) -> LibraryResult<Vec<DomainEvent>> {
+        decode(key, self.objects.get(key).await?.as_slice())
+    }
+
+    async fn read_objects(&self, prefix: &str) -> LibraryResult<Vec<DomainEvent>> {
+        let mut events = vec![];
+        for key in self.objects.list(prefix).await? {
+            events.extend(self.read_object(key.as_str()).await?);
+        }
+        Ok(events)
+    }
+}
+
+#[async_trait]
+impl EventLog for EventArchive {
+    async fn find_events(&self, name: Option<&str>) -> Result<Vec<DomainEvent>, LibraryError> {
+        let events = self.read_objects(ARCHIVE_PREFIX).await?.into_iter()
+            .filter(|event| name.map(|name| event.name == name).unwrap_or(true))
+            .collect();
+        Ok(in_published_order(events))
+    }
+}
+
+#[async_trait]
+impl EventHistory for EventArchive {
+    async fn find_events(&self, groups: &[String], key: &str) -> Result<Vec<DomainEvent>, LibraryError> {
+        let mut events = vec![];
+        for group in groups {
+            events.extend(self.read_objects(format!("{}{}/", ARCHIVE_PREFIX, group).as_str()).await?.into_iter()
+                .filter(|event| event.key == key));
+        }
+        Ok(in_published_order(events))
+    }
+}
+
+// TieredEventLog reads the recent tier and the archive as one log. The recent tier is read first because compactions
+// delete events only after archiving them, so an event moved during the read is still found in the archive, and an
+// event found in both tiers is returned once.
+pub(crate) struct TieredEventLog {
+    recent: Box<dyn EventLog>,
+    archive: Box<dyn EventLog>,
+}
+
+impl TieredEventLog {
+    pub(crate) fn new(recent: Box<dyn EventLog>, archive: Box<dyn EventLog>) -> Self {
+        Self {
+            recent,
+            archive,
+        }
+    }
+}
+
+#[async_trait]
+impl EventLog for TieredEventLog {
+    async fn find_events(&self, name: Option<&str>) -> Result<Vec<DomainEvent>, LibraryError> {
+        let mut events = self.recent.find_events(name).await?;
+        events.extend(self.archive.find_events(name).await?);
+        Ok(in_published_order(events))
+    }
+}
+
+// TieredEventHistory reads the history of an entity from the recent tier and the archive like TieredEventLog
+pub(crate) struct TieredEventHistory {
+    recent: Box<dyn EventHistory>,
+    archive: Box<dyn EventHistory>,
+}
+
+impl TieredEventHistory {
+    pub(crate) fn new(recent: Box<dyn EventHistory>, archive: Box<dyn EventHistory>) -> Self {
+        Self {
+            recent,
+            archive,
+        }
+    }
+}
+
+#[async_trait]
+impl EventHistory for TieredEventHistory {
+    async fn find_events(&self, groups: &[String], key: &str) -> Result<Vec<DomainEvent>, LibraryError> {
+        let mut events = self.recent.find_events(groups, key).await?;
+        events.extend(self.archive.find_events(groups, key).await?);
+        Ok(in_published_order(events))
+    }
+}
+
+// drops events seen before and orders the rest as they were published, event ids order events of the same time
+fn in_published_order(events: Vec<DomainEvent>) -> Vec<DomainEvent> {
+    let mut seen = HashSet::new();
+    let mut events = events.into_iter().filter(|event| seen.insert(event.event_id.to_string())).collect::<Vec<_>>();
+    events.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.event_id.cmp(&b.event_id)));
+    events
+}
+
+fn encode(events: &[DomainEvent]) -> LibraryResult<Vec<u8>> {
+    let mut encoder = GzEncoder::new(vec![], Compression::default());
+    for event in events {
+        let line = serde_json::to_string(event)? + "\n";
+        encoder.write_all(line.as_bytes()).map_err(|err| LibraryError::runtime(
+            format!("failed to compress events due to {}", err).as_str(), None))?;
+    }
+    encoder.finish().map_err(|err| LibraryError::runtime(format!("failed to compress events due to {}", err).as_str(), None))
+}
+
+fn decode(key: &str, content: &[u8]) -> LibraryResult<Vec<DomainEvent>> {
+    let mut lines = String::new();
+    GzDecoder::new(content).read_to_string(&mut lines).map_err(|err| LibraryError::runtime(
+        format!("failed to decompress archived events {} due to {}", key, err).as_str(), None))?;
+    let mut events = vec![];
+    for line in lines.lines().filter(|line| !line.trim().is_empty()) {
+        events.push(serde_json::from_str::<DomainEvent>(line)?);
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use async_trait::async_trait;
+    use chrono::NaiveDate;
+    use uuid::Uuid;
+    use crate::core::events::DomainEvent;
+    use crate::core::library::LibraryError;
+    use crate::gateway::archive::{EventArchive, TieredEventHistory, TieredEventLog};
+    use crate::gateway::events::{EventHistory, EventLog};
+    use crate::gateway::objects::{LocalObjectStore, ObjectStore};
+
+    struct RecentEvents {
+        events: Vec<DomainEvent>,
+    }
+
+    #[async_trait]
+    impl EventLog for RecentEvents {
+        async fn find_events(&self, name: Option<&str>) -> Result<Vec<DomainEvent>, LibraryError> {
+            Ok(self.events.iter().filter(|event| name.map(|name| event.name == name).unwrap_or(true)).cloned().collect())
+        }
+    }
+
+    #[async_trait]
+    impl EventHistory for RecentEvents {
+        async fn find_events(&self, groups: &[String], key: &str) -> Result<Vec<DomainEvent>, LibraryError> {
+            Ok(self.events.iter().filter(|event| groups.contains(&event.group) && event.key == key).cloned().collect())
+        }
+    }
+
+    fn event(name: &str, group: &str, key: &str, day: u32) -> DomainEvent {
+        let mut event = DomainEvent::added(name, group, key, &HashMap::new(), &HashMap::from([("a", 1)])).expect("build event");
+        event.created_at = NaiveDate::from_ymd_opt(2020, 1, day).expect("valid date").and_hms_opt(10, 0, 0).expect("valid time");
+        event
+    }
+
+    #[tokio::test]
+    async fn test_should_archive_events_by_group_and_day() {
+        let dir = std::env::temp_dir().join(format!("lms-events-{}", Uuid::new_v4()));
+        let archive = EventArchive::new(Box::new(LocalObjectStore::new(dir.clone())));
+        let placed = event("book_hold", "book_hold", "hold1", 1);
+        let canceled = event("book_hold_cancel", "book_hold_cancel", "hold1", 2);
+        let other = event("book_hold", "book_hold", "hold2", 1);
+        let keys = archive.archive(&[canceled.clone(), placed.clone(), other.clone()]).await.expect("should archive");
+        assert_eq!(vec!["events/book_hold/2020-01-01.jsonl.gz".to_string(), "events/book_hold_cancel/2020-01-02.jsonl.gz".to_string()], keys);
+
+        // archiving again after an interrupted compaction does not duplicate events
+        archive.archive(&[placed.clone()]).await.expect("should archive again");
+        let events = EventLog::find_events(&archive, None).await.expect("should find events");
+        assert_eq!(3, events.len());
+        let holds = EventLog::find_events(&archive, Some("book_hold")).await.expect("should find events");
+        assert_eq!(2, holds.len());
+
+        let history = EventHistory::find_events(&archive, &["book_hold".to_string(), "book_hold_cancel".to_string()], "hold1")
+            .await.expect("should find history");
+        assert_eq!(vec![placed.event_id, canceled.event_id], history.iter().map(|e| e.event_id.to_string()).collect::<Vec<String>>());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_should_read_tiers_as_one_log() {
+        let dir = std::env::temp_dir().join(format!("lms-events-{}", Uuid::new_v4()));
+        let archived = event("book_hold", "book_hold", "hold1", 1);
+        // an event that a compaction archived before it was deleted from the recent tier is in both
+        let moving = event("book_hold", "book_hold", "hold1", 2);
+        let recent = event("book_hold", "book_hold", "hold1", 3);
+        EventArchive::new(Box::new(LocalObjectStore::new(dir.clone()))).archive(&[archived.clone(), moving.clone()])
+            .await.expect("should archive");
+
+        let log = TieredEventLog::new(Box::new(RecentEvents { events: vec![recent.clone(), moving.clone()] }),
+                                      Box::new(EventArchive::new(Box::new(LocalObjectStore::new(dir.clone())))));
+        let events = log.find_events(Some("book_hold")).await.expect("should find events");
+        assert_eq!(vec![archived.event_id.to_string(), moving.event_id.to_string(), recent.event_id.to_string()],
+                   events.iter().map(|e| e.event_id.to_string()).collect::<Vec<String>>());
+
+        let history = TieredEventHistory::new(Box::new(RecentEvents { events: vec![recent.clone(), moving] }),
+                                              Box::new(EventArchive::new(Box::new(LocalObjectStore::new(dir.clone())))));
+        assert_eq!(3, history.find_events(&["book_hold".to_string()], "hold1").await.expect("should find history").len());
+        assert_eq!(2, LocalObjectStore::new(dir.clone()).list("events/book_hold/").await.expect("should list").len());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
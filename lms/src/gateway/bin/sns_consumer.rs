@@ -0,0 +1,26 @@
+use lambda_runtime::{run, service_fn, Error, LambdaEvent};
+use lms::events::{create_subscriber_registry, handle_sns_event, SnsEvent};
+use lms::{setup_tracing, RepositoryStore};
+
+const DEV_MODE: bool = true;
+
+// consumes domain events published to SNS topics that the function is subscribed to
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    setup_tracing();
+
+    let store = if DEV_MODE {
+        std::env::set_var("AWS_LAMBDA_FUNCTION_NAME", "_");
+        std::env::set_var("AWS_LAMBDA_FUNCTION_MEMORY_SIZE", "4096");
+        std::env::set_var("AWS_LAMBDA_FUNCTION_VERSION", "1");
+        std::env::set_var("AWS_LAMBDA_RUNTIME_API", "http://[::]:9000/.rt");
+        RepositoryStore::LocalDynamoDB
+    } else {
+        RepositoryStore::DynamoDB
+    };
+
+    let registry = &create_subscriber_registry(store).await;
+    run(service_fn(move |event: LambdaEvent<SnsEvent>| async move {
+        handle_sns_event(registry, event.payload).await.map_err(|err| Error::from(err.to_string()))
+    })).await
+}
@@ -0,0 +1,26 @@
+use lambda_runtime::{run, service_fn, Error, LambdaEvent};
+use lms::events::{create_subscriber_registry, handle_sqs_event, SqsBatchResponse, SqsEvent};
+use lms::{setup_tracing, RepositoryStore};
+
+const DEV_MODE: bool = true;
+
+// consumes domain events from an SQS queue, failed messages are reported so that only they are delivered again
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    setup_tracing();
+
+    let store = if DEV_MODE {
+        std::env::set_var("AWS_LAMBDA_FUNCTION_NAME", "_");
+        std::env::set_var("AWS_LAMBDA_FUNCTION_MEMORY_SIZE", "4096");
+        std::env::set_var("AWS_LAMBDA_FUNCTION_VERSION", "1");
+        std::env::set_var("AWS_LAMBDA_RUNTIME_API", "http://[::]:9000/.rt");
+        RepositoryStore::LocalDynamoDB
+    } else {
+        RepositoryStore::DynamoDB
+    };
+
+    let registry = &create_subscriber_registry(store).await;
+    run(service_fn(move |event: LambdaEvent<SqsEvent>| async move {
+        Ok::<SqsBatchResponse, Error>(handle_sqs_event(registry, event.payload).await)
+    })).await
+}
@@ -0,0 +1 @@
+pub mod find_events_cmd;
@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::core::events::DomainEvent;
+use crate::gateway::events::EventHistory;
+
+pub(crate) struct FindEventsCommand {
+    event_history: Box<dyn EventHistory>,
+}
+
+impl FindEventsCommand {
+    pub(crate) fn new(event_history: Box<dyn EventHistory>) -> Self {
+        Self {
+            event_history,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindEventsCommandRequest {
+    // groups that the context publishes events of the entity in
+    pub groups: Vec<String>,
+    pub key: String,
+}
+
+impl FindEventsCommandRequest {
+    pub fn new(groups: &[&str], key: &str) -> Self {
+        Self {
+            groups: groups.iter().map(|g| g.to_string()).collect(),
+            key: key.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindEventsCommandResponse {
+    pub events: Vec<DomainEvent>,
+}
+
+impl FindEventsCommandResponse {
+    pub fn new(events: Vec<DomainEvent>) -> Self {
+        Self {
+            events,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<FindEventsCommandRequest, FindEventsCommandResponse> for FindEventsCommand {
+    async fn execute(&self, req: FindEventsCommandRequest) -> Result<FindEventsCommandResponse, CommandError> {
+        self.event_history.find_events(&req.groups, req.key.as_str())
+            .await.map_err(CommandError::from).map(FindEventsCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::core::command::Command;
+    use crate::core::events::DomainEvent;
+    use crate::gateway::command::find_events_cmd::{FindEventsCommand, FindEventsCommandRequest};
+    use crate::gateway::factory::{create_event_history, create_publisher};
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_run_find_events() {
+        let store = test_store(module_path!(), "test_should_run_find_events");
+        let _ = create_table(&build_db_client(store).await, store.table_name("events").as_str(), "event_id", "group", "key").await;
+        let data = HashMap::from([("a", 1)]);
+        let event = DomainEvent::added("books", "books", "find_events_cmd_book", &HashMap::new(), &data).expect("build event");
+        let publisher = create_publisher(store.gateway_publisher()).await;
+        publisher.publish(&event).await.expect("should publish");
+
+        let cmd = FindEventsCommand::new(create_event_history(store).await);
+        let res = cmd.execute(FindEventsCommandRequest::new(&["books"], "find_events_cmd_book"))
+            .await.expect("should find events");
+        assert!(res.events.iter().any(|e| e.event_id == event.event_id));
+    }
+}
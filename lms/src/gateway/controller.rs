@@ -0,0 +1,51 @@
+use std::convert::Infallible;
+use std::time::Duration;
+use axum::{
+    extract::Query,
+    response::{Json, sse::{Event, KeepAlive, Sse}},
+    routing::get,
+    Router,
+};
+use tokio_stream::{Stream, StreamExt};
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use crate::core::controller::{AppState, command_bus, ServerError};
+use crate::gateway::command::find_events_cmd::{FindEventsCommand, FindEventsCommandRequest, FindEventsCommandResponse};
+use crate::gateway::factory::create_event_history;
+use crate::gateway::stream::{subscribe_events, EventStreamFilter};
+use crate::utils::ddb::{build_db_client, create_table};
+
+// streams events published by the services of this process as server-sent events, each event is named after the
+// domain event and carries it as JSON
+pub(crate) async fn stream_events(
+    Query(filter): Query<EventStreamFilter>) -> Sse<impl Stream<Item=Result<Event, Infallible>>> {
+    let stream = BroadcastStream::new(subscribe_events()).filter_map(move |received| match received {
+        Ok(event) if filter.matches(&event) => Event::default()
+            .id(event.event_id.as_str())
+            .event(event.name.as_str())
+            .json_data(&event)
+            .ok().map(Ok),
+        Ok(_) => None,
+        // dashboards that fall behind are told how many events they missed
+        Err(BroadcastStreamRecvError::Lagged(skipped)) => Some(Ok(Event::default()
+            .comment(format!("skipped {} events", skipped)))),
+    });
+    Sse::new(stream).keep_alive(KeepAlive::new().interval(Duration::from_secs(15)))
+}
+
+// serves the event history of an entity for the routes of its context, which know the groups of its events
+pub(crate) async fn find_entity_events(state: AppState, groups: &[&str],
+                                       key: &str) -> Result<Json<FindEventsCommandResponse>, ServerError> {
+    let client = build_db_client(state.store).await;
+    let _ = create_table(&client, state.store.table_name("events").as_str(), "event_id", "group", "key").await;
+    let history = create_event_history(state.store).await;
+    let req = FindEventsCommandRequest::new(groups, key);
+    let res = command_bus().register(FindEventsCommand::new(history)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// the stream only sees events of its own process so it is served by the standalone server rather than lambdas
+pub fn router() -> Router {
+    Router::new()
+        .route("/events/stream", get(stream_events))
+}
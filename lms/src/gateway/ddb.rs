@@ -1,2 +1,5 @@
+pub mod compaction;
+pub mod history;
+pub mod processed_events;
 pub mod publisher;
-
+pub mod replay;
@@ -0,0 +1,81 @@
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::NaiveDateTime;
+use crate::core::library::LibraryResult;
+use crate::gateway::archive::EventArchive;
+use crate::gateway::ddb::replay::find_events;
+use crate::utils::ddb::{ScanGuard};
+
+// CompactionSummary reports the events that a compaction moved from the events table into the archive
+#[derive(Debug, Clone, Default)]
+pub struct CompactionSummary {
+    pub archived: usize,
+    pub objects: usize,
+}
+
+// moves events published before the cutoff from the events table into the archive. Events are deleted only after
+// their objects are written, so an interrupted compaction leaves events in both tiers until the next compaction
+// archives them again, which does not duplicate them.
+pub(crate) async fn compact_events(client: &Client, table_name: &str, archive: &EventArchive, scan_guard: &ScanGuard,
+                                   before: NaiveDateTime) -> LibraryResult<CompactionSummary> {
+    let events = find_events(client, table_name, scan_guard, None).await?.into_iter()
+        .filter(|event| event.created_at < before)
+        .collect::<Vec<_>>();
+    let objects = archive.archive(&events).await?;
+    for event in &events {
+        client.delete_item()
+            .table_name(table_name)
+            .key("event_id", AttributeValue::S(event.event_id.to_string()))
+            .send()
+            .await?;
+    }
+    Ok(CompactionSummary {
+        archived: events.len(),
+        objects: objects.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use chrono::{Duration, NaiveDate};
+    use uuid::Uuid;
+    use crate::core::events::DomainEvent;
+    use crate::gateway::archive::{EventArchive, TieredEventLog};
+    use crate::gateway::ddb::compaction::compact_events;
+    use crate::gateway::ddb::publisher::DDBPublisher;
+    use crate::gateway::ddb::replay::{find_events, DDBEventLog};
+    use crate::gateway::events::{EventLog, EventPublisher};
+    use crate::gateway::objects::LocalObjectStore;
+    use crate::utils::ddb::{build_db_client, create_table, ScanGuard};
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_compact_old_events_into_archive() {
+        let store = test_store(module_path!(), "test_should_compact_old_events_into_archive");
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("events").as_str(), "event_id", "group", "key").await;
+        let dir = std::env::temp_dir().join(format!("lms-events-{}", Uuid::new_v4()));
+        let publisher = DDBPublisher::new(client.clone(), store.table_name("events").as_str(), store.table_name("events_ndx").as_str());
+        let name = format!("compacted_{}", Uuid::new_v4().simple());
+        let published_at = NaiveDate::from_ymd_opt(2001, 1, 1).expect("valid date").and_hms_opt(0, 0, 0).expect("valid time");
+        let mut old = DomainEvent::added(name.as_str(), "book_hold", "hold1", &HashMap::new(), &HashMap::from([("a", 1)])).expect("build event");
+        old.created_at = published_at;
+        let recent = DomainEvent::added(name.as_str(), "book_hold", "hold1", &HashMap::new(), &HashMap::from([("a", 2)])).expect("build event");
+        for event in [&old, &recent] {
+            publisher.publish(event).await.expect("should publish");
+        }
+
+        let archive = EventArchive::new(Box::new(LocalObjectStore::new(dir.clone())));
+        let summary = compact_events(&client, store.table_name("events").as_str(), &archive, &ScanGuard::default(), published_at + Duration::days(1))
+            .await.expect("should compact");
+        assert!(summary.archived >= 1 && summary.objects >= 1);
+        let remaining = find_events(&client, store.table_name("events").as_str(), &ScanGuard::default(), Some(name.as_str())).await.expect("should find events");
+        assert_eq!(vec![recent.event_id.to_string()], remaining.iter().map(|e| e.event_id.to_string()).collect::<Vec<String>>());
+
+        let log = TieredEventLog::new(Box::new(DDBEventLog::new(client.clone(), store.table_name("events").as_str(), ScanGuard::default())), Box::new(archive));
+        let events = log.find_events(Some(name.as_str())).await.expect("should find events");
+        assert_eq!(vec![old.event_id, recent.event_id], events.iter().map(|e| e.event_id.to_string()).collect::<Vec<String>>());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
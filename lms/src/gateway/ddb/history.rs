@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use crate::core::events::DomainEvent;
+use crate::core::library::LibraryError;
+use crate::gateway::events::EventHistory;
+use crate::utils::ddb::{parse_json_item};
+
+#[derive(Debug)]
+pub struct DDBEventHistory {
+    client: Client,
+    table_name: String,
+    index_name: String,
+}
+
+impl DDBEventHistory {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+        }
+    }
+
+    // reads all pages of events of the group and key, group and key are reserved words of DynamoDB
+    async fn find_group_events(&self, group: &str, key: &str) -> Result<Vec<DomainEvent>, LibraryError> {
+        let mut events = vec![];
+        let mut last_key: Option<HashMap<String, AttributeValue>> = None;
+        loop {
+            let res = self.client
+                .query()
+                .table_name(self.table_name.as_str())
+                .index_name(self.index_name.as_str())
+                .consistent_read(false)
+                .set_exclusive_start_key(last_key)
+                .key_condition_expression("#group = :group AND #key = :key")
+                .expression_attribute_names("#group", "group")
+                .expression_attribute_names("#key", "key")
+                .expression_attribute_values(":group", AttributeValue::S(group.to_string()))
+                .expression_attribute_values(":key", AttributeValue::S(key.to_string()))
+                .send()
+                .await?;
+            for item in res.items().unwrap_or_default() {
+                events.push(serde_json::from_value::<DomainEvent>(parse_json_item(item))?);
+            }
+            last_key = res.last_evaluated_key().cloned();
+            if last_key.is_none() {
+                break;
+            }
+        }
+        Ok(events)
+    }
+}
+
+#[async_trait]
+impl EventHistory for DDBEventHistory {
+    async fn find_events(&self, groups: &[String], key: &str) -> Result<Vec<DomainEvent>, LibraryError> {
+        let mut events = vec![];
+        for group in groups {
+            events.extend(self.find_group_events(group.as_str(), key).await?);
+        }
+        events.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use uuid::Uuid;
+    use crate::core::events::DomainEvent;
+    use crate::gateway::ddb::history::DDBEventHistory;
+    use crate::gateway::ddb::publisher::DDBPublisher;
+    use crate::gateway::events::{EventHistory, EventPublisher};
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_find_events_of_key_in_order() {
+        let store = test_store(module_path!(), "test_should_find_events_of_key_in_order");
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("events").as_str(), "event_id", "group", "key").await;
+        let publisher = DDBPublisher::new(client.clone(), store.table_name("events").as_str(), store.table_name("events_ndx").as_str());
+        let key = Uuid::new_v4().to_string();
+        let data = HashMap::from([("a", 1)]);
+        let placed = DomainEvent::added("book_hold", "book_hold", key.as_str(), &HashMap::new(), &data).expect("build event");
+        let canceled = DomainEvent::deleted("book_hold_cancel", "book_hold_cancel", key.as_str(), &HashMap::new(), &data).expect("build event");
+        let other = DomainEvent::added("book_hold", "book_hold", "other", &HashMap::new(), &data).expect("build event");
+        for event in [&canceled, &placed, &other] {
+            publisher.publish(event).await.expect("should publish");
+        }
+
+        let history = DDBEventHistory::new(client, store.table_name("events").as_str(), store.table_name("events_ndx").as_str());
+        let events = history.find_events(&["book_hold".to_string(), "book_hold_cancel".to_string()], key.as_str())
+            .await.expect("should find events");
+        assert_eq!(vec![placed.event_id, canceled.event_id], events.iter().map(|e| e.event_id.to_string()).collect::<Vec<String>>());
+    }
+}
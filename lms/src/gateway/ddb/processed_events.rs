@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::{Duration, Utc};
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::gateway::dedup::ProcessedEventRepository;
+use crate::utils::ddb::{string_date};
+
+// DDBProcessedEventRepository keeps keys of processed events until DynamoDB expires them by expires_at
+#[derive(Debug)]
+pub(crate) struct DDBProcessedEventRepository {
+    client: Client,
+    table_name: String,
+}
+
+impl DDBProcessedEventRepository {
+    pub(crate) fn new(client: Client, table_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl ProcessedEventRepository for DDBProcessedEventRepository {
+    async fn claim(&self, event_key: &str, ttl: Duration) -> LibraryResult<bool> {
+        let now = Utc::now();
+        // expired keys may not be deleted yet so they are overwritten
+        let res = self.client
+            .put_item()
+            .table_name(self.table_name.as_str())
+            .condition_expression("attribute_not_exists(event_key) OR expires_at < :now")
+            .item("event_key", AttributeValue::S(event_key.to_string()))
+            .item("expires_at", AttributeValue::N((now + ttl).timestamp().to_string()))
+            .item("created_at", string_date(now.naive_utc()))
+            .expression_attribute_values(":now", AttributeValue::N(now.timestamp().to_string()))
+            .send()
+            .await;
+        match res {
+            Ok(_) => Ok(true),
+            Err(SdkError::ServiceError(ctx)) if ctx.err().is_conditional_check_failed_exception() => Ok(false),
+            Err(err) => Err(LibraryError::from(err)),
+        }
+    }
+
+    async fn release(&self, event_key: &str) -> LibraryResult<()> {
+        self.client
+            .delete_item()
+            .table_name(self.table_name.as_str())
+            .key("event_key", AttributeValue::S(event_key.to_string()))
+            .send()
+            .await.map(|_| ()).map_err(LibraryError::from)
+    }
+}
@@ -1,9 +1,15 @@
+use std::collections::HashMap;
 use async_trait::async_trait;
 use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::{PutRequest, WriteRequest};
 use crate::core::events::DomainEvent;
 use crate::core::library::LibraryError;
 use crate::gateway::events::EventPublisher;
-use crate::utils::ddb::parse_item;
+use crate::utils::ddb::{parse_item};
+
+// BatchWriteItem accepts at most 25 items per request
+const MAX_BATCH_WRITE_ITEMS: usize = 25;
+const MAX_BATCH_WRITE_ATTEMPTS: usize = 3;
 
 #[derive(Debug)]
 pub struct DDBPublisher {
@@ -41,39 +47,78 @@ impl EventPublisher for DDBPublisher {
             .send()
             .await.map(|_|()).map_err(LibraryError::from)
     }
+
+    // event ids are unique so the events are written without the condition of publish
+    async fn publish_all(&self, events: &[DomainEvent]) -> Result<(), LibraryError> {
+        let table_name: &str = self.table_name.as_ref();
+        for chunk in events.chunks(MAX_BATCH_WRITE_ITEMS) {
+            let mut requests = vec![];
+            for event in chunk {
+                let put = PutRequest::builder().set_item(Some(parse_item(serde_json::to_value(event)?)?)).build();
+                requests.push(WriteRequest::builder().put_request(put).build());
+            }
+            // throttled writes are returned as unprocessed items and retried
+            for _attempt in 0..MAX_BATCH_WRITE_ATTEMPTS {
+                let res = self.client
+                    .batch_write_item()
+                    .set_request_items(Some(HashMap::from([(table_name.to_string(), requests)])))
+                    .send()
+                    .await?;
+                requests = res.unprocessed_items()
+                    .and_then(|items| items.get(table_name))
+                    .cloned()
+                    .unwrap_or_default();
+                if requests.is_empty() {
+                    break;
+                }
+            }
+            if !requests.is_empty() {
+                return Err(LibraryError::unavailable(format!("failed to publish {} events to {}",
+                                                             requests.len(), table_name).as_str(), None, true));
+            }
+        }
+        Ok(())
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
-    use async_once::AsyncOnce;
     use aws_sdk_dynamodb::Client;
-    use lazy_static::lazy_static;
     use crate::core::events::DomainEvent;
-    use crate::core::repository::RepositoryStore;
 
+    use crate::core::repository::RepositoryStore;
     use crate::gateway::ddb::publisher::DDBPublisher;
     use crate::gateway::events::EventPublisher;
-    use crate::utils::ddb::{build_db_client, create_table, delete_table};
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
 
-    lazy_static! {
-        static ref CLIENT: AsyncOnce<Client> = AsyncOnce::new(async {
-                let client = build_db_client(RepositoryStore::LocalDynamoDB).await;
-                let _ = delete_table(&client, "events").await;
-                let _ = create_table(&client, "events", "event_id", "group", "key").await;
-                client
-            });
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("events").as_str(), "event_id", "group", "key").await;
+        client
     }
 
     #[tokio::test]
     async fn test_should_publish_to_ddb() {
+        let store = test_store(module_path!(), "test_should_publish_to_ddb");
         let data = HashMap::from([("a", 1), ("b", 2)]);
         let event = DomainEvent::added("test-name", "group", "key", &HashMap::from([("k".to_string(), "v".to_string())]), &data).expect("build event");
-        let mut publisher = DDBPublisher::new(CLIENT.get().await.clone(), "events", "events_ndx");
+        let mut publisher = DDBPublisher::new(build_client(store).await, store.table_name("events").as_str(), store.table_name("events_ndx").as_str());
         let _arn = publisher.create_topic(event.name.as_str()).await.expect("should create topic");
         let _ = publisher.publish(&event).await.expect("should publish");
         let topics = publisher.get_topics().await.expect("should get topics");
         assert_eq!(0, topics.len());
     }
+
+    #[tokio::test]
+    async fn test_should_publish_all_to_ddb() {
+        let store = test_store(module_path!(), "test_should_publish_all_to_ddb");
+        let events = (0..30).map(|i| DomainEvent::added("test-name", "group", format!("key{}", i).as_str(),
+                                                        &HashMap::new(), &i).expect("build event"))
+            .collect::<Vec<DomainEvent>>();
+        let publisher = DDBPublisher::new(build_client(store).await, store.table_name("events").as_str(), store.table_name("events_ndx").as_str());
+        let _ = publisher.publish_all(&events).await.expect("should publish all");
+    }
 }
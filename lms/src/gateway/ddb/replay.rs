@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use crate::core::events::DomainEvent;
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::gateway::events::EventLog;
+use crate::gateway::subscribers::SubscriberRegistry;
+use crate::utils::ddb::{parse_json_item, ScanGuard};
+
+// delivers events of the log to the subscribers in the order they were published, e.g. to rebuild a projection, the
+// registry upcasts old events like any other delivery and returns the number of replayed events
+pub(crate) async fn replay_events(log: &dyn EventLog, registry: &SubscriberRegistry,
+                                  name: Option<&str>) -> LibraryResult<usize> {
+    let events = log.find_events(name).await?;
+    for event in &events {
+        let _ = registry.dispatch(event).await?;
+    }
+    Ok(events.len())
+}
+
+// reads events of the events table, optionally only those with the name, in the order they were published
+pub(crate) async fn find_events(client: &Client, table_name: &str, scan_guard: &ScanGuard,
+                                name: Option<&str>) -> LibraryResult<Vec<DomainEvent>> {
+    let mut events = vec![];
+    let mut last_key: Option<HashMap<String, AttributeValue>> = None;
+    loop {
+        scan_guard.check(table_name)?;
+        let mut request = client
+            .scan()
+            .table_name(table_name)
+            .set_exclusive_start_key(last_key);
+        if let Some(name) = name {
+            request = request
+                .filter_expression("#name = :name")
+                .expression_attribute_names("#name", "name")
+                .expression_attribute_values(":name", AttributeValue::S(name.to_string()));
+        }
+        let res = request.send().await?;
+        for item in res.items().unwrap_or_default() {
+            events.push(serde_json::from_value::<DomainEvent>(parse_json_item(item))?);
+        }
+        last_key = res.last_evaluated_key().cloned();
+        if last_key.is_none() {
+            break;
+        }
+    }
+    // scans return items in the order of their keys, event ids order events that were published at the same time
+    events.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.event_id.cmp(&b.event_id)));
+    Ok(events)
+}
+
+// DDBEventLog is the recent tier of published events kept in the events table
+pub(crate) struct DDBEventLog {
+    client: Client,
+    table_name: String,
+    scan_guard: ScanGuard,
+}
+
+impl DDBEventLog {
+    pub(crate) fn new(client: Client, table_name: &str, scan_guard: ScanGuard) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            scan_guard,
+        }
+    }
+}
+
+#[async_trait]
+impl EventLog for DDBEventLog {
+    async fn find_events(&self, name: Option<&str>) -> Result<Vec<DomainEvent>, LibraryError> {
+        find_events(&self.client, self.table_name.as_str(), &self.scan_guard, name).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use async_trait::async_trait;
+    use crate::checkout::dto::CheckoutDto;
+    use crate::core::events::DomainEvent;
+    use crate::core::events::upcasters::INITIAL_SCHEMA_VERSION;
+    use crate::core::library::{BookFormat, LibraryResult};
+    use crate::gateway::ddb::publisher::DDBPublisher;
+    use crate::gateway::ddb::replay::{replay_events, DDBEventLog};
+    use crate::gateway::events::EventPublisher;
+    use crate::gateway::subscribers::{EventSubscriber, SubscriberRegistry};
+    use crate::utils::ddb::{build_db_client, create_table, ScanGuard};
+    use crate::utils::testing::test_store;
+
+    struct RecordingSubscriber {
+        key: String,
+        received: Arc<Mutex<Vec<DomainEvent>>>,
+    }
+
+    #[async_trait]
+    impl EventSubscriber for RecordingSubscriber {
+        fn name(&self) -> String {
+            "recording".to_string()
+        }
+
+        fn handles(&self, event: &DomainEvent) -> bool {
+            event.key == self.key
+        }
+
+        async fn handle(&self, event: &DomainEvent) -> LibraryResult<()> {
+            self.received.lock().unwrap().push(event.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_should_replay_old_events_in_current_shape() {
+        let store = test_store(module_path!(), "test_should_replay_old_events_in_current_shape");
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("events").as_str(), "event_id", "group", "key").await;
+        let checkout = CheckoutDto::new("replayed_book", "patron1");
+        let mut payload = serde_json::to_value(&checkout).expect("should serialize");
+        payload.as_object_mut().expect("should be object").remove("book_format");
+        let mut event = DomainEvent::added("book_checkout", "checkout", checkout.checkout_id.as_str(),
+                                           &HashMap::new(), &payload).expect("build event");
+        event.schema_version = INITIAL_SCHEMA_VERSION;
+        DDBPublisher::new(client.clone(), store.table_name("events").as_str(), store.table_name("events_ndx").as_str()).publish(&event).await.expect("should publish");
+
+        let received = Arc::new(Mutex::new(vec![]));
+        let registry = SubscriberRegistry::new().register(Box::new(RecordingSubscriber {
+            key: checkout.checkout_id.to_string(),
+            received: received.clone(),
+        }));
+        let log = DDBEventLog::new(client.clone(), store.table_name("events").as_str(), ScanGuard::default());
+        let replayed = replay_events(&log, &registry, Some("book_checkout")).await.expect("should replay");
+        assert!(replayed >= 1);
+
+        let received = received.lock().unwrap();
+        assert_eq!(1, received.len());
+        let dto: CheckoutDto = serde_json::from_str(received[0].json_data.as_str()).expect("should read checkout");
+        assert_eq!(BookFormat::Physical, dto.book_format);
+    }
+}
@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use chrono::Duration;
+use tracing::log::{info, warn};
+use crate::core::events::DomainEvent;
+use crate::core::library::LibraryResult;
+use crate::gateway::subscribers::EventSubscriber;
+
+// events are remembered for as long as SQS retains messages so that redeliveries of a queue are always detected
+pub(crate) const PROCESSED_EVENT_TTL_DAYS: i64 = 14;
+
+#[async_trait]
+pub(crate) trait ProcessedEventRepository: Sync + Send {
+    // records the key before the event is processed and returns false if it was recorded and did not expire yet
+    async fn claim(&self, event_key: &str, ttl: Duration) -> LibraryResult<bool>;
+
+    // removes the key of an event whose processing failed so that its redelivery is processed
+    async fn release(&self, event_key: &str) -> LibraryResult<()>;
+}
+
+// IdempotentSubscriber skips events that the subscriber it wraps processed before, e.g. when SNS or SQS deliver an
+// event twice. Events are claimed per subscriber so that a redelivery after another subscriber failed only reaches
+// the subscribers that did not process it yet.
+pub(crate) struct IdempotentSubscriber {
+    delegate: Box<dyn EventSubscriber>,
+    processed_events: Box<dyn ProcessedEventRepository>,
+    ttl: Duration,
+}
+
+impl IdempotentSubscriber {
+    pub(crate) fn new(delegate: Box<dyn EventSubscriber>, processed_events: Box<dyn ProcessedEventRepository>) -> Self {
+        Self {
+            delegate,
+            processed_events,
+            ttl: Duration::days(PROCESSED_EVENT_TTL_DAYS),
+        }
+    }
+}
+
+#[async_trait]
+impl EventSubscriber for IdempotentSubscriber {
+    fn name(&self) -> String {
+        self.delegate.name()
+    }
+
+    fn handles(&self, event: &DomainEvent) -> bool {
+        self.delegate.handles(event)
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> LibraryResult<()> {
+        let event_key = format!("{}#{}", self.delegate.name(), event.event_id);
+        if !self.processed_events.claim(event_key.as_str(), self.ttl).await? {
+            info!("skipping event {} that {} processed before", event.event_id, self.delegate.name());
+            return Ok(());
+        }
+        let res = self.delegate.handle(event).await;
+        if res.is_err() {
+            if let Err(err) = self.processed_events.release(event_key.as_str()).await {
+                warn!("failed to release event {} of {} due to {}", event.event_id, self.delegate.name(), err);
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use async_trait::async_trait;
+    use crate::core::events::DomainEvent;
+    use crate::core::library::{LibraryError, LibraryResult};
+    use crate::gateway::dedup::IdempotentSubscriber;
+    use crate::gateway::factory::create_processed_event_repository;
+    use crate::gateway::subscribers::EventSubscriber;
+    use crate::utils::testing::test_store;
+
+    // fails the first delivery of each event
+    struct FlakySubscriber {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl EventSubscriber for FlakySubscriber {
+        fn name(&self) -> String {
+            "flaky".to_string()
+        }
+
+        fn handles(&self, _event: &DomainEvent) -> bool {
+            true
+        }
+
+        async fn handle(&self, _event: &DomainEvent) -> LibraryResult<()> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                return Err(LibraryError::runtime("first delivery fails", None));
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_should_process_redelivered_event_once() {
+        let store = test_store(module_path!(), "test_should_process_redelivered_event_once");
+        let calls = Arc::new(AtomicUsize::new(0));
+        let subscriber = IdempotentSubscriber::new(Box::new(FlakySubscriber { calls: calls.clone() }),
+                                                   create_processed_event_repository(store).await);
+        let event = DomainEvent::added("deduped", "group", "key", &HashMap::new(), &HashMap::from([("a", 1)]))
+            .expect("build event");
+        // failed processing is released so that the redelivery is processed
+        assert!(subscriber.handle(&event).await.is_err());
+        subscriber.handle(&event).await.expect("should process redelivery");
+        subscriber.handle(&event).await.expect("should skip duplicate");
+        assert_eq!(2, calls.load(Ordering::SeqCst));
+    }
+}
@@ -7,5 +7,26 @@ pub(crate) trait EventPublisher: Sync + Send {
     async fn create_topic(&mut self, topic: &str) -> Result<String, LibraryError>;
     async fn get_topics(&mut self) -> Result<Vec<String>, LibraryError>;
     async fn publish(&self, event: &DomainEvent) -> Result<(), LibraryError>;
+    // publishes events of batch operations, publishers override it to send the events in fewer requests
+    async fn publish_all(&self, events: &[DomainEvent]) -> Result<(), LibraryError> {
+        for event in events {
+            self.publish(event).await?;
+        }
+        Ok(())
+    }
 }
 
+
+// EventHistory reads the published events of an entity, e.g. for support investigations
+#[async_trait]
+pub(crate) trait EventHistory: Sync + Send {
+    // returns events that were published with the key in any of the groups, oldest first
+    async fn find_events(&self, groups: &[String], key: &str) -> Result<Vec<DomainEvent>, LibraryError>;
+}
+
+// EventLog reads all published events of a storage tier for replays, rebuilds and audits
+#[async_trait]
+pub(crate) trait EventLog: Sync + Send {
+    // returns events, optionally only those with the name, oldest first
+    async fn find_events(&self, name: Option<&str>) -> Result<Vec<DomainEvent>, LibraryError>;
+}
@@ -1,19 +1,194 @@
+use std::time::Duration;
+use tracing::log::warn;
+use crate::core::domain::Configuration;
+use crate::core::library::LibraryResult;
 use crate::core::repository::RepositoryStore;
+use crate::core::tasks::TaskHandler;
+use crate::gateway::address::{AddressValidator, HttpAddressValidator, StubAddressValidator};
+use crate::gateway::archive::{EventArchive, TieredEventHistory, TieredEventLog};
+use crate::gateway::dedup::{IdempotentSubscriber, ProcessedEventRepository};
+use crate::gateway::ddb::history::DDBEventHistory;
+use crate::gateway::ddb::processed_events::DDBProcessedEventRepository;
 use crate::gateway::ddb::publisher::DDBPublisher;
-use crate::gateway::events::EventPublisher;
+use crate::gateway::ddb::replay::DDBEventLog;
+use crate::gateway::events::{EventHistory, EventLog, EventPublisher};
 use crate::gateway::GatewayPublisherVia;
-use crate::gateway::sns::publisher::SESPublisher;
-use crate::utils::ddb::{build_db_client, build_ses_client};
+use crate::gateway::http::{HttpClient, HttpClientConfig};
+use crate::gateway::objects::{LocalObjectStore, ObjectStore, S3ObjectStore};
+use crate::gateway::payments::{PaymentGateway, StripePaymentGateway, StubPaymentGateway};
+use crate::gateway::ses::{EmailSender, LocalEmailSender, SESEmailSender};
+use crate::gateway::sns::publisher::SNSPublisher;
+use crate::gateway::sru::{SruUnionCatalog, StubUnionCatalog, UnionCatalog};
+use crate::gateway::stream::BroadcastPublisher;
+use crate::gateway::subscribers::SubscriberRegistry;
+use crate::gateway::tasks::ArchiveEventsTaskHandler;
+use crate::gateway::topics::TopicConfig;
+use crate::notifications::factory::create_notification_email_subscriber;
+use crate::projector::factory::create_projectors;
+use crate::utils::ddb::{build_db_client, build_email_client, build_s3_client, build_sns_client, create_key_table,
+                        enable_time_to_live, ScanGuard};
 
+// published events are also broadcast to in-process subscribers such as the event stream of the standalone server
 pub(crate) async fn create_publisher(via: GatewayPublisherVia) -> Box<dyn EventPublisher> {
-    match via {
+    let publisher: Box<dyn EventPublisher> = match via {
         GatewayPublisherVia::Sns => {
-            let client = build_ses_client().await;
-            Box::new(SESPublisher::new(client))
+            let config = TopicConfig::from_env().unwrap_or_else(|err| {
+                warn!("ignoring topic configuration due to {}", err);
+                TopicConfig::default()
+            });
+            Box::new(create_sns_publisher(config).await)
         }
-        GatewayPublisherVia::LocalDynamoDB => {
-            let client = build_db_client(RepositoryStore::LocalDynamoDB).await;
-            Box::new(DDBPublisher::new(client, "events", "events_ndx"))
+        GatewayPublisherVia::LocalDynamoDB(store) => {
+            let client = build_db_client(store).await;
+            Box::new(DDBPublisher::new(client, store.table_name("events").as_str(), store.table_name("events_ndx").as_str()))
         }
+    };
+    Box::new(BroadcastPublisher::new(publisher))
+}
+
+async fn create_sns_publisher(config: TopicConfig) -> SNSPublisher {
+    let client = build_sns_client().await;
+    let fifo_topics = std::env::var("FIFO_TOPICS").map(|fifo| fifo == "true").unwrap_or(false);
+    SNSPublisher::new(client).with_fifo_topics(fifo_topics).with_topic_config(config)
+}
+
+// checks the topic configuration when a function starts so that missing topics fail its cold start with reason
+// missing_topics, events of local stores are written to the events table that needs no topics
+pub async fn check_topics(store: RepositoryStore) -> LibraryResult<Vec<String>> {
+    match store.gateway_publisher() {
+        GatewayPublisherVia::Sns => create_sns_publisher(TopicConfig::from_env()?).await.ensure_topics().await,
+        GatewayPublisherVia::LocalDynamoDB(_) => Ok(vec![]),
+    }
+}
+
+// history is read from the events table that the local publisher writes and from the archive that older events are
+// compacted into, in AWS the table is fed by a subscription of the topic
+pub(crate) async fn create_event_history(store: RepositoryStore) -> Box<dyn EventHistory> {
+    let client = build_db_client(store).await;
+    Box::new(TieredEventHistory::new(Box::new(DDBEventHistory::new(client, store.table_name("events").as_str(),
+                                                                   store.table_name("events_ndx").as_str())),
+                                     Box::new(create_event_archive().await)))
+}
+
+// replays and projection rebuilds read the events table and the archive as one log
+pub(crate) async fn create_event_log(store: RepositoryStore) -> Box<dyn EventLog> {
+    let recent = DDBEventLog::new(build_db_client(store).await, store.table_name("events").as_str(), ScanGuard::new(store));
+    Box::new(TieredEventLog::new(Box::new(recent), Box::new(create_event_archive().await)))
+}
+
+// addresses are validated by the configured provider, the stub is used without a provider so that local and test
+// environments work offline
+pub(crate) fn create_address_validator(config: &Configuration) -> Box<dyn AddressValidator> {
+    if let Some(url) = &config.address_validation_url {
+        match HttpClient::new(HttpClientConfig::default()) {
+            Ok(client) => return Box::new(HttpAddressValidator::new(client, url.as_str())),
+            Err(err) => warn!("falling back to stub address validation due to {}", err),
+        }
+    }
+    Box::new(StubAddressValidator::new())
+}
+
+// federated search waits on the union catalog while the patron or librarian waits on the response so a slow
+// union catalog is not retried
+pub(crate) fn create_union_catalog(config: &Configuration) -> Box<dyn UnionCatalog> {
+    if let Some(url) = &config.union_catalog_url {
+        let client_config = HttpClientConfig {
+            timeout: Duration::from_millis(config.union_catalog_timeout_ms),
+            max_retries: 0,
+            ..HttpClientConfig::default()
+        };
+        match HttpClient::new(client_config) {
+            Ok(client) => return Box::new(SruUnionCatalog::new(client, url.as_str())),
+            Err(err) => warn!("falling back to stub union catalog due to {}", err),
+        }
+    }
+    Box::new(StubUnionCatalog::new())
+}
+
+// payments are simulated without a provider, intents are retried by the client like other outbound calls since the
+// provider discards duplicates by the idempotency key of the reference
+pub(crate) fn create_payment_gateway(config: &Configuration) -> Box<dyn PaymentGateway> {
+    if let Some(url) = &config.payment_gateway_url {
+        match HttpClient::new(HttpClientConfig::default()) {
+            Ok(client) => return Box::new(StripePaymentGateway::new(client, url.as_str(), config.payment_api_key.as_str(),
+                                                                    config.payment_webhook_secret.as_str(),
+                                                                    config.payment_webhook_tolerance_seconds)),
+            Err(err) => warn!("falling back to stub payment gateway due to {}", err),
+        }
+    }
+    Box::new(StubPaymentGateway::new(config.payment_webhook_secret.as_str(), config.payment_webhook_tolerance_seconds))
+}
+
+// objects are stored in the configured bucket, local and test environments keep them in a temporary directory
+async fn create_object_store(bucket: Option<&String>, local_dir: &str) -> Box<dyn ObjectStore> {
+    match bucket {
+        Some(bucket) => Box::new(S3ObjectStore::new(build_s3_client().await, bucket.as_str())),
+        None => Box::new(LocalObjectStore::new(std::env::temp_dir().join(local_dir))),
+    }
+}
+
+pub(crate) async fn create_cover_store(config: &Configuration) -> Box<dyn ObjectStore> {
+    create_object_store(config.covers_bucket.as_ref(), "lms-covers").await
+}
+
+pub(crate) async fn create_document_store(config: &Configuration) -> Box<dyn ObjectStore> {
+    create_object_store(config.documents_bucket.as_ref(), "lms-documents").await
+}
+
+// events compacted out of the events table are kept in EVENTS_BUCKET, the history and replays of every function
+// read them so the bucket is taken from the environment rather than the configuration of a branch
+pub(crate) async fn create_event_archive() -> EventArchive {
+    EventArchive::new(create_object_store(std::env::var("EVENTS_BUCKET").ok().as_ref(), "lms-events").await)
+}
+
+pub(crate) async fn create_archive_events_task_handler(store: RepositoryStore) -> Box<dyn TaskHandler> {
+    Box::new(ArchiveEventsTaskHandler::new(store))
+}
+
+// emails are sent through SES from the verified EMAIL_FROM address, EMAIL_SANDBOX_RECIPIENT receives all emails of
+// test accounts, without EMAIL_FROM emails are written to a temporary directory so that local environments work offline
+pub(crate) async fn create_email_sender() -> Box<dyn EmailSender> {
+    match std::env::var("EMAIL_FROM") {
+        Ok(from) => Box::new(SESEmailSender::new(build_email_client().await, from.as_str())
+            .with_sandbox_recipient(std::env::var("EMAIL_SANDBOX_RECIPIENT").ok())),
+        Err(_) => Box::new(LocalEmailSender::new(std::env::temp_dir().join("lms-emails"), "library@localhost")),
+    }
+}
+
+pub(crate) async fn create_processed_event_repository(store: RepositoryStore) -> Box<dyn ProcessedEventRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBProcessedEventRepository::new(client, "processed_events"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("processed_events");
+            let _ = create_key_table(&client, table_name.as_str(), "event_key").await;
+            let _ = enable_time_to_live(&client, table_name.as_str(), "expires_at").await;
+            Box::new(DDBProcessedEventRepository::new(client, table_name.as_str()))
+        }
+    }
+}
+
+// creates registry of the subscribers that consume events delivered by SNS and SQS triggers, each subscriber skips
+// events that it processed before because triggers deliver events at least once
+pub async fn create_subscriber_registry(store: RepositoryStore) -> SubscriberRegistry {
+    let mut registry = SubscriberRegistry::new();
+    for projector in create_projectors(store).await {
+        registry = registry.register(Box::new(IdempotentSubscriber::new(
+            Box::new(projector), create_processed_event_repository(store).await)));
+    }
+    registry.register(Box::new(IdempotentSubscriber::new(
+        create_notification_email_subscriber().await, create_processed_event_repository(store).await)))
+}
+
+// creates registry of the subscribers that replays deliver events to again, processed events are not skipped and
+// notifications are not emailed again
+pub(crate) async fn create_replay_registry(store: RepositoryStore) -> SubscriberRegistry {
+    let mut registry = SubscriberRegistry::new();
+    for projector in create_projectors(store).await {
+        registry = registry.register(Box::new(projector));
     }
+    registry
 }
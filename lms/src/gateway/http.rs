@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use reqwest::{Client, Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::log::{info, warn};
+use uuid::Uuid;
+
+use crate::core::context::RequestContext;
+use crate::core::library::{LibraryError, LibraryResult};
+
+// HttpClientConfig defines timeouts, retries and circuit breaking of outbound calls
+#[derive(Debug, Clone)]
+pub(crate) struct HttpClientConfig {
+    pub timeout: Duration,
+    pub max_retries: u32,
+    pub backoff_base: Duration,
+    // consecutive failures of a host after which its circuit opens
+    pub failure_threshold: u32,
+    // period an open circuit rejects calls before a trial call is allowed
+    pub open_duration: Duration,
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(5),
+            max_retries: 2,
+            backoff_base: Duration::from_millis(200),
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CircuitState {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    HalfOpen,
+}
+
+// CircuitBreaker tracks failures of each host and rejects calls to hosts that keep failing so that a broken
+// integration does not slow down every request, the state is kept in memory of the lambda instance
+#[derive(Debug, Clone)]
+pub(crate) struct CircuitBreaker {
+    failure_threshold: u32,
+    open_duration: Duration,
+    hosts: Arc<Mutex<HashMap<String, CircuitState>>>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(failure_threshold: u32, open_duration: Duration) -> Self {
+        Self {
+            failure_threshold,
+            open_duration,
+            hosts: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    // returns false while the circuit of the host is open, a single trial call is allowed after the open period
+    pub(crate) fn allow(&self, host: &str, now: Instant) -> bool {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|err| err.into_inner());
+        let state = hosts.entry(host.to_string()).or_insert(CircuitState::Closed { failures: 0 });
+        match *state {
+            CircuitState::Closed { .. } => true,
+            CircuitState::Open { until } if now >= until => {
+                *state = CircuitState::HalfOpen;
+                true
+            }
+            CircuitState::Open { .. } | CircuitState::HalfOpen => false,
+        }
+    }
+
+    pub(crate) fn record_success(&self, host: &str) {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|err| err.into_inner());
+        hosts.insert(host.to_string(), CircuitState::Closed { failures: 0 });
+    }
+
+    pub(crate) fn record_failure(&self, host: &str, now: Instant) {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|err| err.into_inner());
+        let state = hosts.entry(host.to_string()).or_insert(CircuitState::Closed { failures: 0 });
+        *state = match *state {
+            CircuitState::Closed { failures } if failures + 1 < self.failure_threshold => {
+                CircuitState::Closed { failures: failures + 1 }
+            }
+            _ => CircuitState::Open { until: now + self.open_duration },
+        };
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn json<T: DeserializeOwned>(&self) -> LibraryResult<T> {
+        Ok(serde_json::from_str(self.body.as_str())?)
+    }
+}
+
+// HttpClient wraps outbound calls to third-party services with a timeout, retries with backoff for transient
+// failures, circuit breaking per host and propagation of the correlation id and the Lambda trace id
+#[derive(Debug, Clone)]
+pub(crate) struct HttpClient {
+    client: Client,
+    config: HttpClientConfig,
+    breaker: CircuitBreaker,
+}
+
+impl HttpClient {
+    pub(crate) fn new(config: HttpClientConfig) -> LibraryResult<Self> {
+        let client = Client::builder().timeout(config.timeout).build()
+            .map_err(|err| LibraryError::runtime(format!("failed to build http client {}", err).as_str(), None))?;
+        let breaker = CircuitBreaker::new(config.failure_threshold, config.open_duration);
+        Ok(Self {
+            client,
+            config,
+            breaker,
+        })
+    }
+
+    pub(crate) async fn get_json<T: DeserializeOwned>(&self, url: &str) -> LibraryResult<T> {
+        self.send(Method::GET, url, None::<&()>).await?.json()
+    }
+
+    pub(crate) async fn post_json<B: Serialize + Sync, T: DeserializeOwned>(&self, url: &str, body: &B) -> LibraryResult<T> {
+        self.send(Method::POST, url, Some(body)).await?.json()
+    }
+
+    // sends the request and retries transient failures, the same Idempotency-Key is sent on every attempt so that
+    // receivers can discard duplicates of non-idempotent requests
+    pub(crate) async fn send<B: Serialize + Sync>(&self, method: Method, url: &str, body: Option<&B>) -> LibraryResult<HttpResponse> {
+        self.send_with_headers(method, url, body, &[]).await
+    }
+
+    // sends the request with headers such as credentials of the service, an Idempotency-Key among the headers
+    // replaces the generated one so that retries of the caller are discarded as well
+    pub(crate) async fn send_with_headers<B: Serialize + Sync>(&self, method: Method, url: &str, body: Option<&B>,
+                                                              headers: &[(&str, String)]) -> LibraryResult<HttpResponse> {
+        let host = reqwest::Url::parse(url)
+            .map_err(|err| LibraryError::validation(format!("invalid url {} {}", url, err).as_str(), None))?
+            .host_str().unwrap_or_default().to_string();
+        let idempotency_key = headers.iter().find(|(name, _)| name.eq_ignore_ascii_case("idempotency-key"))
+            .map(|(_, value)| value.to_string()).unwrap_or_else(|| Uuid::new_v4().to_string());
+        let headers = headers.iter().filter(|(name, _)| !name.eq_ignore_ascii_case("idempotency-key"))
+            .cloned().collect::<Vec<(&str, String)>>();
+        let mut attempt = 0;
+        loop {
+            if !self.breaker.allow(host.as_str(), Instant::now()) {
+                return Err(LibraryError::unavailable(format!("circuit of {} is open", host).as_str(), None, true));
+            }
+            let started = Instant::now();
+            let res = self.attempt(method.clone(), url, body, idempotency_key.as_str(), headers.as_slice()).await;
+            let transient = match &res {
+                Ok(res) => is_transient_status(res.status),
+                Err(_) => true,
+            };
+            if transient {
+                self.breaker.record_failure(host.as_str(), Instant::now());
+            } else {
+                self.breaker.record_success(host.as_str());
+            }
+            match &res {
+                Ok(res) => info!("{} {} returned {} in {}ms", method, url, res.status, started.elapsed().as_millis()),
+                Err(err) => warn!("{} {} failed in {}ms due to {}", method, url, started.elapsed().as_millis(), err),
+            }
+            if transient && attempt < self.config.max_retries {
+                attempt += 1;
+                tokio::time::sleep(backoff(self.config.backoff_base, attempt)).await;
+                continue;
+            }
+            return res.and_then(to_result);
+        }
+    }
+
+    async fn attempt<B: Serialize + Sync>(&self, method: Method, url: &str, body: Option<&B>,
+                                          idempotency_key: &str, headers: &[(&str, String)]) -> LibraryResult<HttpResponse> {
+        let mut req = self.client.request(method, url).header("idempotency-key", idempotency_key);
+        for (name, value) in headers {
+            req = req.header(*name, value.as_str());
+        }
+        if let Some(ctx) = RequestContext::current() {
+            req = req.header("x-correlation-id", ctx.correlation_id.as_str());
+        }
+        if let Ok(trace_id) = std::env::var("_X_AMZN_TRACE_ID") {
+            req = req.header("x-amzn-trace-id", trace_id);
+        }
+        if let Some(body) = body {
+            req = req.json(body);
+        }
+        let res = req.send().await
+            .map_err(|err| LibraryError::unavailable(format!("request to {} failed {}", url, err).as_str(), None, true))?;
+        let status = res.status().as_u16();
+        let body = res.text().await
+            .map_err(|err| LibraryError::unavailable(format!("response of {} failed {}", url, err).as_str(), None, true))?;
+        Ok(HttpResponse { status, body })
+    }
+}
+
+// throttling and gateway failures are expected to pass
+fn is_transient_status(status: u16) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS.as_u16() || (500..600).contains(&status)
+}
+
+fn backoff(base: Duration, attempt: u32) -> Duration {
+    base * 2_u32.pow(attempt.saturating_sub(1).min(10))
+}
+
+fn to_result(res: HttpResponse) -> LibraryResult<HttpResponse> {
+    match res.status {
+        200..=299 => Ok(res),
+        404 => Err(LibraryError::not_found(res.body.as_str())),
+        400..=499 => Err(LibraryError::validation(res.body.as_str(), Some(res.status.to_string()))),
+        _ => Err(LibraryError::unavailable(res.body.as_str(), Some(res.status.to_string()), is_transient_status(res.status))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
+    use axum::extract::State;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use axum::{Json, Router};
+    use serde_json::{json, Value};
+    use crate::gateway::http::{backoff, CircuitBreaker, HttpClient, HttpClientConfig};
+
+    #[tokio::test]
+    async fn test_should_open_and_close_circuit() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(30));
+        let now = Instant::now();
+        assert!(breaker.allow("partner", now));
+        breaker.record_failure("partner", now);
+        assert!(breaker.allow("partner", now));
+        breaker.record_failure("partner", now);
+        assert!(!breaker.allow("partner", now));
+        assert!(breaker.allow("other", now));
+
+        // a trial call is allowed after the open period and its failure opens the circuit again
+        let later = now + Duration::from_secs(31);
+        assert!(breaker.allow("partner", later));
+        assert!(!breaker.allow("partner", later));
+        breaker.record_failure("partner", later);
+        assert!(!breaker.allow("partner", later + Duration::from_secs(1)));
+
+        let trial = later + Duration::from_secs(31);
+        assert!(breaker.allow("partner", trial));
+        breaker.record_success("partner");
+        assert!(breaker.allow("partner", trial));
+    }
+
+    #[tokio::test]
+    async fn test_should_back_off_exponentially() {
+        let base = Duration::from_millis(100);
+        assert_eq!(Duration::from_millis(100), backoff(base, 1));
+        assert_eq!(Duration::from_millis(400), backoff(base, 3));
+    }
+
+    // fails with 503 until the number of calls reaches the threshold
+    async fn flaky(State((calls, succeed_at)): State<(Arc<AtomicUsize>, usize)>) -> (StatusCode, Json<Value>) {
+        let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+        if call < succeed_at {
+            return (StatusCode::SERVICE_UNAVAILABLE, Json(json!({"error": "unavailable"})));
+        }
+        (StatusCode::OK, Json(json!({"call": call})))
+    }
+
+    async fn serve_flaky(succeed_at: usize) -> (String, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = Router::new().route("/flaky", get(flaky)).with_state((calls.clone(), succeed_at));
+        let listener = TcpListener::bind("127.0.0.1:0").expect("should bind");
+        let url = format!("http://{}/flaky", listener.local_addr().expect("should have address"));
+        tokio::spawn(async move {
+            let _ = axum::Server::from_tcp(listener).expect("should serve").serve(app.into_make_service()).await;
+        });
+        (url, calls)
+    }
+
+    fn config(max_retries: u32) -> HttpClientConfig {
+        HttpClientConfig {
+            timeout: Duration::from_secs(2),
+            max_retries,
+            backoff_base: Duration::from_millis(1),
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_should_retry_transient_failures() {
+        let (url, calls) = serve_flaky(3).await;
+        let client = HttpClient::new(config(2)).expect("should build client");
+        let res: Value = client.get_json(url.as_str()).await.expect("should succeed on third call");
+        assert_eq!(3, res["call"].as_u64().unwrap_or_default());
+        assert_eq!(3, calls.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_should_fail_after_retries() {
+        let (url, calls) = serve_flaky(10).await;
+        let client = HttpClient::new(config(1)).expect("should build client");
+        assert!(client.get_json::<Value>(url.as_str()).await.is_err());
+        assert_eq!(2, calls.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::log::warn;
+use crate::core::events::DomainEvent;
+use crate::core::library::LibraryResult;
+use crate::gateway::subscribers::SubscriberRegistry;
+
+// SqsEvent is the payload of a Lambda function that is triggered by an SQS queue
+#[derive(Debug, Deserialize)]
+pub struct SqsEvent {
+    #[serde(rename = "Records")]
+    pub records: Vec<SqsMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SqsMessage {
+    pub message_id: String,
+    #[serde(default)]
+    pub body: String,
+}
+
+// SqsBatchResponse reports the messages that failed so that only they are delivered again, it requires
+// ReportBatchItemFailures on the event source mapping
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SqsBatchResponse {
+    pub batch_item_failures: Vec<SqsBatchItemFailure>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SqsBatchItemFailure {
+    pub item_identifier: String,
+}
+
+// SnsEvent is the payload of a Lambda function that is subscribed to an SNS topic
+#[derive(Debug, Deserialize)]
+pub struct SnsEvent {
+    #[serde(rename = "Records")]
+    pub records: Vec<SnsRecord>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SnsRecord {
+    #[serde(rename = "Sns")]
+    pub sns: SnsMessage,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SnsMessage {
+    pub message_id: String,
+    #[serde(default)]
+    pub topic_arn: String,
+    pub message: String,
+}
+
+// parses the domain event of a message, queues subscribed to an SNS topic without raw message delivery
+// receive the event wrapped in the SNS notification
+pub(crate) fn parse_event(body: &str) -> LibraryResult<DomainEvent> {
+    let value: Value = serde_json::from_str(body)?;
+    match (value.get("Type").and_then(|t| t.as_str()), value.get("Message").and_then(|m| m.as_str())) {
+        (Some("Notification"), Some(message)) => Ok(serde_json::from_str(message)?),
+        _ => Ok(serde_json::from_value(value)?),
+    }
+}
+
+// routes the events of the batch to the subscribers and returns the messages that failed, malformed messages
+// are dropped because they would fail on every delivery
+pub async fn handle_sqs_event(registry: &SubscriberRegistry, event: SqsEvent) -> SqsBatchResponse {
+    let mut res = SqsBatchResponse::default();
+    for message in event.records {
+        let domain_event = match parse_event(message.body.as_str()) {
+            Ok(domain_event) => domain_event,
+            Err(err) => {
+                warn!("dropping malformed sqs message {} due to {}", message.message_id, err);
+                continue;
+            }
+        };
+        if registry.dispatch(&domain_event).await.is_err() {
+            res.batch_item_failures.push(SqsBatchItemFailure { item_identifier: message.message_id });
+        }
+    }
+    res
+}
+
+// routes the events of the notification to the subscribers, an error lets Lambda retry the invocation
+pub async fn handle_sns_event(registry: &SubscriberRegistry, event: SnsEvent) -> LibraryResult<()> {
+    for record in event.records {
+        let domain_event = match parse_event(record.sns.message.as_str()) {
+            Ok(domain_event) => domain_event,
+            Err(err) => {
+                warn!("dropping malformed sns message {} of {} due to {}", record.sns.message_id, record.sns.topic_arn, err);
+                continue;
+            }
+        };
+        let _ = registry.dispatch(&domain_event).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use async_trait::async_trait;
+    use serde_json::json;
+    use crate::core::events::DomainEvent;
+    use crate::core::library::{LibraryError, LibraryResult};
+    use crate::gateway::lambda::{handle_sns_event, handle_sqs_event, parse_event, SnsEvent, SqsEvent};
+    use crate::gateway::subscribers::{EventSubscriber, SubscriberRegistry};
+
+    // counts events and fails events named failing
+    struct CountingSubscriber {
+        count: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl EventSubscriber for CountingSubscriber {
+        fn name(&self) -> String {
+            "counting".to_string()
+        }
+
+        fn handles(&self, _event: &DomainEvent) -> bool {
+            true
+        }
+
+        async fn handle(&self, event: &DomainEvent) -> LibraryResult<()> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            if event.name == "failing" {
+                return Err(LibraryError::runtime("subscriber failure", None));
+            }
+            Ok(())
+        }
+    }
+
+    fn event_json(name: &str) -> String {
+        let event = DomainEvent::added(name, "books", "key", &HashMap::new(), &HashMap::from([("a", 1)])).expect("build event");
+        serde_json::to_string(&event).expect("should serialize")
+    }
+
+    #[tokio::test]
+    async fn test_should_parse_raw_and_wrapped_events() {
+        let raw = event_json("book_added");
+        assert_eq!("book_added", parse_event(raw.as_str()).expect("should parse raw").name.as_str());
+        let wrapped = json!({"Type": "Notification", "MessageId": "m1", "Message": raw}).to_string();
+        assert_eq!("book_added", parse_event(wrapped.as_str()).expect("should parse wrapped").name.as_str());
+        assert!(parse_event("not json").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_report_failed_sqs_messages() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let registry = SubscriberRegistry::new().register(Box::new(CountingSubscriber { count: count.clone() }));
+        let event: SqsEvent = serde_json::from_value(json!({"Records": [
+            {"messageId": "m1", "receiptHandle": "r1", "body": event_json("book_added"), "eventSource": "aws:sqs"},
+            {"messageId": "m2", "receiptHandle": "r2", "body": event_json("failing"), "eventSource": "aws:sqs"},
+            {"messageId": "m3", "receiptHandle": "r3", "body": "{}", "eventSource": "aws:sqs"},
+        ]})).expect("should parse sqs event");
+        let res = handle_sqs_event(&registry, event).await;
+        assert_eq!(2, count.load(Ordering::SeqCst));
+        assert_eq!(vec!["m2"], res.batch_item_failures.iter().map(|f| f.item_identifier.as_str()).collect::<Vec<&str>>());
+    }
+
+    #[tokio::test]
+    async fn test_should_route_sns_notifications() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let registry = SubscriberRegistry::new().register(Box::new(CountingSubscriber { count: count.clone() }));
+        let event: SnsEvent = serde_json::from_value(json!({"Records": [
+            {"EventSource": "aws:sns", "Sns": {"MessageId": "m1", "TopicArn": "arn:aws:sns:us-east-1:1:book_added",
+                                               "Message": event_json("book_added")}},
+        ]})).expect("should parse sns event");
+        handle_sns_event(&registry, event).await.expect("should handle");
+        assert_eq!(1, count.load(Ordering::SeqCst));
+
+        let failing: SnsEvent = serde_json::from_value(json!({"Records": [
+            {"EventSource": "aws:sns", "Sns": {"MessageId": "m2", "Message": event_json("failing")}},
+        ]})).expect("should parse sns event");
+        assert!(handle_sns_event(&registry, failing).await.is_err());
+    }
+}
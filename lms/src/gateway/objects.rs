@@ -0,0 +1,261 @@
+use std::path::PathBuf;
+use std::time::Duration;
+use async_trait::async_trait;
+use aws_sdk_s3::Client;
+use aws_sdk_s3::presigning::PresigningConfig;
+use aws_sdk_s3::primitives::ByteStream;
+use crate::core::library::{LibraryError, LibraryResult};
+
+// ObjectStore keeps binary content such as cover images and party documents under keys that are recorded on the
+// owning entities
+#[async_trait]
+pub(crate) trait ObjectStore: Sync + Send {
+    async fn put(&self, key: &str, content_type: &str, content: Vec<u8>) -> LibraryResult<()>;
+    async fn delete(&self, key: &str) -> LibraryResult<()>;
+    async fn get(&self, key: &str) -> LibraryResult<Vec<u8>>;
+    // returns keys of the objects that start with the prefix in the order of the keys
+    async fn list(&self, prefix: &str) -> LibraryResult<Vec<String>>;
+    // returns a URL the object can be downloaded from without credentials until it expires
+    async fn url(&self, key: &str, expires_in: Duration) -> LibraryResult<String>;
+    // returns a URL that clients upload the object to with a PUT of the given content type until it expires
+    async fn upload_url(&self, key: &str, content_type: &str, expires_in: Duration) -> LibraryResult<String>;
+}
+
+// S3ObjectStore stores objects in a bucket and hands out pre-signed GET and PUT URLs
+pub(crate) struct S3ObjectStore {
+    client: Client,
+    bucket: String,
+}
+
+impl S3ObjectStore {
+    pub(crate) fn new(client: Client, bucket: &str) -> Self {
+        Self {
+            client,
+            bucket: bucket.to_string(),
+        }
+    }
+
+    fn presigning_config(expires_in: Duration) -> LibraryResult<PresigningConfig> {
+        PresigningConfig::expires_in(expires_in).map_err(|err| LibraryError::runtime(
+            format!("invalid expiry of object url due to {}", err).as_str(), None))
+    }
+}
+
+#[async_trait]
+impl ObjectStore for S3ObjectStore {
+    async fn put(&self, key: &str, content_type: &str, content: Vec<u8>) -> LibraryResult<()> {
+        self.client.put_object()
+            .bucket(self.bucket.as_str())
+            .key(key)
+            .content_type(content_type)
+            .body(ByteStream::from(content))
+            .send()
+            .await.map(|_| ()).map_err(|err| LibraryError::unavailable(
+            format!("failed to upload object {} due to {}", key, err).as_str(), None, true))
+    }
+
+    async fn delete(&self, key: &str) -> LibraryResult<()> {
+        self.client.delete_object()
+            .bucket(self.bucket.as_str())
+            .key(key)
+            .send()
+            .await.map(|_| ()).map_err(|err| LibraryError::unavailable(
+            format!("failed to delete object {} due to {}", key, err).as_str(), None, true))
+    }
+
+    async fn get(&self, key: &str) -> LibraryResult<Vec<u8>> {
+        let res = match self.client.get_object().bucket(self.bucket.as_str()).key(key).send().await {
+            Ok(res) => res,
+            Err(err) => {
+                let err = err.into_service_error();
+                if err.is_no_such_key() {
+                    return Err(LibraryError::not_found(format!("object {} not found", key).as_str()));
+                }
+                return Err(LibraryError::unavailable(
+                    format!("failed to download object {} due to {}", key, err).as_str(), None, true));
+            }
+        };
+        res.body.collect().await.map(|data| data.into_bytes().to_vec()).map_err(|err| LibraryError::unavailable(
+            format!("failed to read object {} due to {}", key, err).as_str(), None, true))
+    }
+
+    async fn list(&self, prefix: &str) -> LibraryResult<Vec<String>> {
+        let mut keys = vec![];
+        let mut token: Option<String> = None;
+        loop {
+            let res = self.client.list_objects_v2()
+                .bucket(self.bucket.as_str())
+                .prefix(prefix)
+                .set_continuation_token(token)
+                .send()
+                .await.map_err(|err| LibraryError::unavailable(
+                format!("failed to list objects of {} due to {}", prefix, err).as_str(), None, true))?;
+            keys.extend(res.contents().unwrap_or_default().iter().filter_map(|object| object.key().map(str::to_string)));
+            token = res.next_continuation_token().map(str::to_string);
+            if token.is_none() {
+                break;
+            }
+        }
+        Ok(keys)
+    }
+
+    async fn url(&self, key: &str, expires_in: Duration) -> LibraryResult<String> {
+        self.client.get_object()
+            .bucket(self.bucket.as_str())
+            .key(key)
+            .presigned(Self::presigning_config(expires_in)?)
+            .await.map(|req| req.uri().to_string()).map_err(|err| LibraryError::unavailable(
+            format!("failed to sign url of object {} due to {}", key, err).as_str(), None, false))
+    }
+
+    async fn upload_url(&self, key: &str, content_type: &str, expires_in: Duration) -> LibraryResult<String> {
+        self.client.put_object()
+            .bucket(self.bucket.as_str())
+            .key(key)
+            .content_type(content_type)
+            .presigned(Self::presigning_config(expires_in)?)
+            .await.map(|req| req.uri().to_string()).map_err(|err| LibraryError::unavailable(
+            format!("failed to sign upload url of object {} due to {}", key, err).as_str(), None, false))
+    }
+}
+
+// LocalObjectStore keeps objects in a directory for local runs and tests without a bucket, its URLs are file URLs
+// that do not expire
+pub(crate) struct LocalObjectStore {
+    dir: PathBuf,
+}
+
+impl LocalObjectStore {
+    pub(crate) fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+        }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(key)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for LocalObjectStore {
+    async fn put(&self, key: &str, _content_type: &str, content: Vec<u8>) -> LibraryResult<()> {
+        let path = self.path(key);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| LibraryError::runtime(
+                format!("failed to create directory of object {} due to {}", key, err).as_str(), None))?;
+        }
+        std::fs::write(path, content).map_err(|err| LibraryError::runtime(
+            format!("failed to write object {} due to {}", key, err).as_str(), None))
+    }
+
+    async fn delete(&self, key: &str) -> LibraryResult<()> {
+        match std::fs::remove_file(self.path(key)) {
+            Ok(_) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(LibraryError::runtime(format!("failed to delete object {} due to {}", key, err).as_str(), None)),
+        }
+    }
+
+    async fn get(&self, key: &str) -> LibraryResult<Vec<u8>> {
+        match std::fs::read(self.path(key)) {
+            Ok(content) => Ok(content),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound =>
+                Err(LibraryError::not_found(format!("object {} not found", key).as_str())),
+            Err(err) => Err(LibraryError::runtime(format!("failed to read object {} due to {}", key, err).as_str(), None)),
+        }
+    }
+
+    // keys are the paths of the files below the directory with / as separator like the keys of a bucket
+    async fn list(&self, prefix: &str) -> LibraryResult<Vec<String>> {
+        let mut keys = vec![];
+        let mut dirs = vec![self.dir.clone()];
+        while let Some(dir) = dirs.pop() {
+            let entries = match std::fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(LibraryError::runtime(
+                    format!("failed to list objects of {} due to {}", prefix, err).as_str(), None)),
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else if let Ok(relative) = path.strip_prefix(&self.dir) {
+                    let key = relative.components().map(|c| c.as_os_str().to_string_lossy().to_string())
+                        .collect::<Vec<String>>().join("/");
+                    if key.starts_with(prefix) {
+                        keys.push(key);
+                    }
+                }
+            }
+        }
+        keys.sort();
+        Ok(keys)
+    }
+
+    async fn url(&self, key: &str, _expires_in: Duration) -> LibraryResult<String> {
+        let path = self.path(key);
+        if !path.exists() {
+            return Err(LibraryError::not_found(format!("object {} not found", key).as_str()));
+        }
+        Ok(format!("file://{}", path.display()))
+    }
+
+    // local clients write the file themselves so only its directory is created
+    async fn upload_url(&self, key: &str, _content_type: &str, _expires_in: Duration) -> LibraryResult<String> {
+        let path = self.path(key);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| LibraryError::runtime(
+                format!("failed to create directory of object {} due to {}", key, err).as_str(), None))?;
+        }
+        Ok(format!("file://{}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use uuid::Uuid;
+    use crate::core::library::LibraryError;
+    use crate::gateway::objects::{LocalObjectStore, ObjectStore};
+
+    #[tokio::test]
+    async fn test_should_put_delete_local_objects() {
+        let dir = std::env::temp_dir().join(format!("lms-covers-{}", Uuid::new_v4()));
+        let store = LocalObjectStore::new(dir.clone());
+        store.put("covers/book1/cover.png", "image/png", vec![1, 2, 3]).await.expect("should put cover");
+        let url = store.url("covers/book1/cover.png", Duration::from_secs(60)).await.expect("should return url");
+        assert!(url.starts_with("file://") && url.ends_with("covers/book1/cover.png"));
+        store.delete("covers/book1/cover.png").await.expect("should delete cover");
+        assert!(store.url("covers/book1/cover.png", Duration::from_secs(60)).await.is_err());
+        store.delete("covers/book1/cover.png").await.expect("should ignore missing cover");
+        assert!(matches!(store.get("covers/book1/cover.png").await, Err(LibraryError::NotFound { .. })));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_should_return_local_upload_url() {
+        let dir = std::env::temp_dir().join(format!("lms-documents-{}", Uuid::new_v4()));
+        let store = LocalObjectStore::new(dir.clone());
+        let url = store.upload_url("documents/party1/id.pdf", "application/pdf", Duration::from_secs(60))
+            .await.expect("should return upload url");
+        assert!(url.starts_with("file://") && url.ends_with("documents/party1/id.pdf"));
+        assert!(dir.join("documents/party1").is_dir());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_should_get_and_list_local_objects() {
+        let dir = std::env::temp_dir().join(format!("lms-objects-{}", Uuid::new_v4()));
+        let store = LocalObjectStore::new(dir.clone());
+        store.put("events/b/2024-01-02.jsonl.gz", "application/gzip", vec![2]).await.expect("should put object");
+        store.put("events/a/2024-01-01.jsonl.gz", "application/gzip", vec![1]).await.expect("should put object");
+        store.put("covers/book1/cover.png", "image/png", vec![3]).await.expect("should put object");
+        assert_eq!(vec![1], store.get("events/a/2024-01-01.jsonl.gz").await.expect("should get object"));
+        assert_eq!(vec!["events/a/2024-01-01.jsonl.gz".to_string(), "events/b/2024-01-02.jsonl.gz".to_string()],
+                   store.list("events/").await.expect("should list objects"));
+        assert!(LocalObjectStore::new(dir.join("missing")).list("").await.expect("should list nothing").is_empty());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,387 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use async_trait::async_trait;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::gateway::http::HttpClient;
+use crate::utils::signing::{sign_hex, verify_hex};
+
+// PaymentStatus is the state of a payment kept on the record that is paid for
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum PaymentStatus {
+    RequiresPayment,
+    Processing,
+    Succeeded,
+    Failed,
+    Canceled,
+}
+
+impl PaymentStatus {
+    pub(crate) fn is_final(&self) -> bool {
+        matches!(self, PaymentStatus::Succeeded | PaymentStatus::Canceled)
+    }
+
+    // returns whether the record moves to the next status: webhooks are redelivered and arrive out of order, so a
+    // repeated status and an earlier status after the payment settled leave the record unchanged, only a settled
+    // payment that settles differently is a conflict
+    pub(crate) fn transition(&self, next: PaymentStatus) -> LibraryResult<bool> {
+        if *self == next || (self.is_final() && !next.is_final()) {
+            return Ok(false);
+        }
+        if self.is_final() {
+            return Err(LibraryError::validation(
+                format!("payment is already {} and cannot become {}", self, next).as_str(), Some("409".to_string())));
+        }
+        Ok(true)
+    }
+}
+
+impl From<String> for PaymentStatus {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Processing" => PaymentStatus::Processing,
+            "Succeeded" => PaymentStatus::Succeeded,
+            "Failed" => PaymentStatus::Failed,
+            "Canceled" => PaymentStatus::Canceled,
+            _ => PaymentStatus::RequiresPayment,
+        }
+    }
+}
+
+impl Display for PaymentStatus {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            PaymentStatus::RequiresPayment => write!(f, "RequiresPayment"),
+            PaymentStatus::Processing => write!(f, "Processing"),
+            PaymentStatus::Succeeded => write!(f, "Succeeded"),
+            PaymentStatus::Failed => write!(f, "Failed"),
+            PaymentStatus::Canceled => write!(f, "Canceled"),
+        }
+    }
+}
+
+// PaymentIntent is a payment of an amount in the smallest unit of the currency, clients complete it with the client
+// secret so that card details never reach the library
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct PaymentIntent {
+    pub intent_id: String,
+    // id of the record that is paid for
+    pub reference: String,
+    pub amount_cents: i64,
+    pub currency: String,
+    pub status: PaymentStatus,
+    pub client_secret: String,
+}
+
+// PaymentEvent is a change of a payment reported by a webhook of the provider, the event id is unique per change
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct PaymentEvent {
+    pub event_id: String,
+    pub intent: PaymentIntent,
+}
+
+// PaymentGateway takes payments through a payment provider
+#[async_trait]
+pub(crate) trait PaymentGateway: Sync + Send {
+    // creates the intent of paying the amount of the reference, creating it again for the same reference returns
+    // the same intent
+    async fn create_intent(&self, reference: &str, amount_cents: i64, currency: &str) -> LibraryResult<PaymentIntent>;
+
+    // verifies the signature of a webhook and returns the payment change it reports, events other than changes of
+    // payment intents are acknowledged without a change
+    fn parse_webhook(&self, payload: &str, signature: &str, now: i64) -> LibraryResult<Option<PaymentEvent>>;
+}
+
+// StubPaymentGateway is used when no payment provider is configured, intents are created locally and payments are
+// completed by posting webhooks signed with the webhook secret
+pub(crate) struct StubPaymentGateway {
+    webhook_secret: String,
+    tolerance_seconds: i64,
+}
+
+impl StubPaymentGateway {
+    pub(crate) fn new(webhook_secret: &str, tolerance_seconds: i64) -> Self {
+        Self {
+            webhook_secret: webhook_secret.to_string(),
+            tolerance_seconds,
+        }
+    }
+}
+
+#[async_trait]
+impl PaymentGateway for StubPaymentGateway {
+    async fn create_intent(&self, reference: &str, amount_cents: i64, currency: &str) -> LibraryResult<PaymentIntent> {
+        validate_amount(amount_cents, currency)?;
+        let digest = Sha256::digest(reference.as_bytes());
+        let intent_id = format!("pi_stub_{}", digest.iter().take(12).map(|b| format!("{:02x}", b)).collect::<String>());
+        Ok(PaymentIntent {
+            client_secret: format!("{}_secret", intent_id),
+            intent_id,
+            reference: reference.to_string(),
+            amount_cents,
+            currency: currency.to_lowercase(),
+            status: PaymentStatus::RequiresPayment,
+        })
+    }
+
+    fn parse_webhook(&self, payload: &str, signature: &str, now: i64) -> LibraryResult<Option<PaymentEvent>> {
+        verify_webhook(self.webhook_secret.as_str(), payload, signature, now, self.tolerance_seconds)?;
+        to_payment_event(payload)
+    }
+}
+
+// StripePaymentGateway talks to providers with the API of Stripe: intents are created with the secret key as bearer
+// token and the reference as idempotency key, webhooks are signed in the Stripe-Signature format
+pub(crate) struct StripePaymentGateway {
+    client: HttpClient,
+    url: String,
+    api_key: String,
+    webhook_secret: String,
+    tolerance_seconds: i64,
+}
+
+impl StripePaymentGateway {
+    pub(crate) fn new(client: HttpClient, url: &str, api_key: &str, webhook_secret: &str, tolerance_seconds: i64) -> Self {
+        Self {
+            client,
+            url: url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+            webhook_secret: webhook_secret.to_string(),
+            tolerance_seconds,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CreateIntentRequest<'a> {
+    amount: i64,
+    currency: String,
+    metadata: HashMap<&'a str, &'a str>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProviderIntent {
+    id: String,
+    status: String,
+    amount: i64,
+    currency: String,
+    #[serde(default)]
+    client_secret: Option<String>,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+impl ProviderIntent {
+    fn to_intent(&self, status: PaymentStatus) -> PaymentIntent {
+        PaymentIntent {
+            intent_id: self.id.to_string(),
+            reference: self.metadata.get("reference").cloned().unwrap_or_default(),
+            amount_cents: self.amount,
+            currency: self.currency.to_string(),
+            status,
+            client_secret: self.client_secret.clone().unwrap_or_default(),
+        }
+    }
+
+    fn status(&self) -> PaymentStatus {
+        match self.status.as_str() {
+            "processing" => PaymentStatus::Processing,
+            "succeeded" => PaymentStatus::Succeeded,
+            "canceled" => PaymentStatus::Canceled,
+            _ => PaymentStatus::RequiresPayment,
+        }
+    }
+}
+
+// objects of other events such as customers or refunds have their own shape, so the object is only read as an
+// intent once the event type is known
+#[derive(Debug, Deserialize)]
+struct ProviderEventData {
+    object: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProviderEvent {
+    id: String,
+    #[serde(rename = "type")]
+    event_type: String,
+    data: ProviderEventData,
+}
+
+#[async_trait]
+impl PaymentGateway for StripePaymentGateway {
+    async fn create_intent(&self, reference: &str, amount_cents: i64, currency: &str) -> LibraryResult<PaymentIntent> {
+        validate_amount(amount_cents, currency)?;
+        let body = CreateIntentRequest {
+            amount: amount_cents,
+            currency: currency.to_lowercase(),
+            metadata: HashMap::from([("reference", reference)]),
+        };
+        let headers = [
+            ("authorization", format!("Bearer {}", self.api_key)),
+            ("idempotency-key", format!("intent-{}", reference)),
+        ];
+        let url = format!("{}/v1/payment_intents", self.url);
+        let intent: ProviderIntent = self.client.send_with_headers(Method::POST, url.as_str(), Some(&body), &headers).await?.json()?;
+        Ok(intent.to_intent(intent.status()))
+    }
+
+    fn parse_webhook(&self, payload: &str, signature: &str, now: i64) -> LibraryResult<Option<PaymentEvent>> {
+        verify_webhook(self.webhook_secret.as_str(), payload, signature, now, self.tolerance_seconds)?;
+        to_payment_event(payload)
+    }
+}
+
+fn validate_amount(amount_cents: i64, currency: &str) -> LibraryResult<()> {
+    if amount_cents <= 0 {
+        return Err(LibraryError::validation("payment amount must be positive", Some("400".to_string())));
+    }
+    if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(LibraryError::validation(format!("invalid currency {}", currency).as_str(), Some("400".to_string())));
+    }
+    Ok(())
+}
+
+// failed payments keep the status that asks for another payment method, so the status is taken from the event type
+fn to_payment_event(payload: &str) -> LibraryResult<Option<PaymentEvent>> {
+    let event: ProviderEvent = serde_json::from_str(payload)?;
+    let status = match event.event_type.as_str() {
+        "payment_intent.succeeded" => PaymentStatus::Succeeded,
+        "payment_intent.processing" => PaymentStatus::Processing,
+        "payment_intent.payment_failed" => PaymentStatus::Failed,
+        "payment_intent.canceled" => PaymentStatus::Canceled,
+        _ => return Ok(None),
+    };
+    let intent: ProviderIntent = serde_json::from_value(event.data.object)?;
+    Ok(Some(PaymentEvent { event_id: event.id, intent: intent.to_intent(status) }))
+}
+
+// signature header of a webhook as `t={timestamp},v1={hex of HMAC-SHA256 of "{timestamp}.{payload}"}`
+pub(crate) fn sign_webhook(secret: &str, payload: &str, timestamp: i64) -> String {
+    format!("t={},v1={}", timestamp, sign_hex(secret, format!("{}.{}", timestamp, payload).as_str()))
+}
+
+// any of the v1 signatures may match so that the provider can sign with the old and the new secret while it is rolled,
+// webhooks outside the tolerance are rejected as replays
+pub(crate) fn verify_webhook(secret: &str, payload: &str, header: &str, now: i64, tolerance_seconds: i64) -> LibraryResult<()> {
+    let invalid = |reason: &str| LibraryError::access_denied(format!("invalid webhook signature, {}", reason).as_str(), Some("401".to_string()));
+    let mut timestamp = None;
+    let mut signatures = vec![];
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+            Some(("v1", value)) => signatures.push(value),
+            _ => {}
+        }
+    }
+    let timestamp = timestamp.ok_or_else(|| invalid("timestamp is missing"))?;
+    if (now - timestamp).abs() > tolerance_seconds {
+        return Err(invalid("timestamp is outside the tolerance"));
+    }
+    let message = format!("{}.{}", timestamp, payload);
+    if signatures.iter().any(|signature| verify_hex(secret, message.as_str(), signature)) {
+        return Ok(());
+    }
+    Err(invalid("no signature matches"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use axum::http::HeaderMap;
+    use axum::routing::post;
+    use axum::{Json, Router};
+    use serde_json::{json, Value};
+    use crate::core::library::LibraryError;
+    use crate::gateway::http::{HttpClient, HttpClientConfig};
+    use crate::gateway::payments::{sign_webhook, verify_webhook, PaymentGateway, PaymentStatus, StripePaymentGateway, StubPaymentGateway};
+
+    // answers with the intent of the request and echoes the idempotency key as the id
+    async fn create_intent(headers: HeaderMap, Json(body): Json<Value>) -> Json<Value> {
+        assert_eq!(Some("Bearer sk_test"), headers.get("authorization").and_then(|v| v.to_str().ok()));
+        let key = headers.get("idempotency-key").and_then(|v| v.to_str().ok()).unwrap_or_default().to_string();
+        Json(json!({
+            "id": format!("pi_{}", key),
+            "status": "requires_payment_method",
+            "amount": body["amount"],
+            "currency": body["currency"],
+            "client_secret": "pi_secret",
+            "metadata": body["metadata"],
+        }))
+    }
+
+    fn event(event_type: &str) -> String {
+        json!({
+            "id": "evt_1",
+            "type": event_type,
+            "data": {"object": {"id": "pi_1", "status": "requires_payment_method", "amount": 250, "currency": "usd",
+                                "metadata": {"reference": "fine1"}}},
+        }).to_string()
+    }
+
+    #[tokio::test]
+    async fn test_should_transition_payment_status() {
+        assert!(PaymentStatus::RequiresPayment.transition(PaymentStatus::Processing).expect("should transition"));
+        assert!(PaymentStatus::Failed.transition(PaymentStatus::Succeeded).expect("should transition"));
+        assert!(!PaymentStatus::Succeeded.transition(PaymentStatus::Succeeded).expect("should ignore redelivery"));
+        assert!(!PaymentStatus::Succeeded.transition(PaymentStatus::Processing).expect("should ignore stale event"));
+        assert!(matches!(PaymentStatus::Succeeded.transition(PaymentStatus::Canceled), Err(LibraryError::Validation { .. })));
+        assert_eq!(PaymentStatus::Failed, PaymentStatus::from(PaymentStatus::Failed.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_should_verify_webhook_signature() {
+        let payload = event("payment_intent.succeeded");
+        let header = sign_webhook("whsec", payload.as_str(), 1000);
+        assert!(verify_webhook("whsec", payload.as_str(), header.as_str(), 1100, 300).is_ok());
+        let rolled = format!("{},v1=00ff", header);
+        assert!(verify_webhook("whsec", payload.as_str(), rolled.as_str(), 1100, 300).is_ok());
+        assert!(verify_webhook("other", payload.as_str(), header.as_str(), 1100, 300).is_err());
+        assert!(verify_webhook("whsec", payload.replace("250", "1").as_str(), header.as_str(), 1100, 300).is_err());
+        assert!(verify_webhook("whsec", payload.as_str(), header.as_str(), 1400, 300).is_err());
+        assert!(verify_webhook("whsec", payload.as_str(), "v1=zz", 1000, 300).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_create_stub_intent_and_parse_webhook() {
+        let gateway = StubPaymentGateway::new("whsec", 300);
+        let intent = gateway.create_intent("fine1", 250, "USD").await.expect("should create intent");
+        assert_eq!(intent, gateway.create_intent("fine1", 250, "USD").await.expect("should create intent"));
+        assert_eq!(PaymentStatus::RequiresPayment, intent.status);
+        assert_eq!("usd", intent.currency.as_str());
+        assert!(gateway.create_intent("fine1", 0, "USD").await.is_err());
+
+        let payload = event("payment_intent.payment_failed");
+        let payment = gateway.parse_webhook(payload.as_str(), sign_webhook("whsec", payload.as_str(), 1000).as_str(), 1000)
+            .expect("should parse webhook").expect("should be a payment event");
+        assert_eq!("evt_1", payment.event_id.as_str());
+        assert_eq!("fine1", payment.intent.reference.as_str());
+        assert_eq!(PaymentStatus::Failed, payment.intent.status);
+
+        let payload = json!({"id": "evt_2", "type": "customer.created", "data": {"object": {"id": "cus_1"}}}).to_string();
+        assert_eq!(None, gateway.parse_webhook(payload.as_str(), sign_webhook("whsec", payload.as_str(), 1000).as_str(), 1000)
+            .expect("should parse webhook"));
+    }
+
+    #[tokio::test]
+    async fn test_should_create_stripe_intent() {
+        let app = Router::new().route("/v1/payment_intents", post(create_intent));
+        let listener = TcpListener::bind("127.0.0.1:0").expect("should bind");
+        let url = format!("http://{}", listener.local_addr().expect("should have address"));
+        tokio::spawn(async move {
+            let _ = axum::Server::from_tcp(listener).expect("should serve").serve(app.into_make_service()).await;
+        });
+
+        let client = HttpClient::new(HttpClientConfig::default()).expect("should build client");
+        let gateway = StripePaymentGateway::new(client, url.as_str(), "sk_test", "whsec", 300);
+        let intent = gateway.create_intent("fine1", 250, "usd").await.expect("should create intent");
+        assert_eq!("pi_intent-fine1", intent.intent_id.as_str());
+        assert_eq!("fine1", intent.reference.as_str());
+        assert_eq!(250, intent.amount_cents);
+        assert_eq!(PaymentStatus::RequiresPayment, intent.status);
+        assert_eq!("pi_secret", intent.client_secret.as_str());
+    }
+}
@@ -0,0 +1,253 @@
+use std::path::PathBuf;
+use async_trait::async_trait;
+use aws_sdk_sesv2::Client;
+use aws_sdk_sesv2::primitives::Blob;
+use aws_sdk_sesv2::types::{Body, Content, Destination, EmailContent, Message, RawMessage, Template};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde_json::Value;
+use uuid::Uuid;
+use crate::core::library::{LibraryError, LibraryResult};
+
+// base64 lines of attachments are wrapped at the line length of MIME
+const MIME_LINE_LENGTH: usize = 76;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct EmailAttachment {
+    pub(crate) file_name: String,
+    pub(crate) content_type: String,
+    pub(crate) content: Vec<u8>,
+}
+
+// Email is sent as plain text or rendered from a template of the sending account with the template data,
+// attachments are sent as a raw MIME message that cannot use templates
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Email {
+    pub(crate) to: String,
+    pub(crate) subject: String,
+    pub(crate) text: String,
+    pub(crate) template: Option<(String, Value)>,
+    pub(crate) attachments: Vec<EmailAttachment>,
+}
+
+impl Email {
+    pub(crate) fn new(to: &str, subject: &str, text: &str) -> Self {
+        Self {
+            to: to.to_string(),
+            subject: subject.to_string(),
+            text: text.to_string(),
+            template: None,
+            attachments: vec![],
+        }
+    }
+
+    pub(crate) fn with_template(mut self, name: &str, data: Value) -> Self {
+        self.template = Some((name.to_string(), data));
+        self
+    }
+
+    pub(crate) fn with_attachment(mut self, file_name: &str, content_type: &str, content: Vec<u8>) -> Self {
+        self.attachments.push(EmailAttachment {
+            file_name: file_name.to_string(),
+            content_type: content_type.to_string(),
+            content,
+        });
+        self
+    }
+
+    fn validate(&self) -> LibraryResult<()> {
+        if self.to.is_empty() {
+            return Err(LibraryError::validation("email recipient is required", Some("400".to_string())));
+        }
+        if self.template.is_some() && !self.attachments.is_empty() {
+            return Err(LibraryError::validation("templated emails cannot have attachments", Some("400".to_string())));
+        }
+        Ok(())
+    }
+}
+
+// EmailSender delivers emails such as notifications to parties and returns the id of the sent message
+#[async_trait]
+pub(crate) trait EmailSender: Sync + Send {
+    async fn send(&self, email: &Email) -> LibraryResult<String>;
+}
+
+// SESEmailSender sends emails through SES from a verified address, in sandbox mode every email goes to the sandbox
+// recipient instead of the party so that test accounts never mail real patrons
+pub(crate) struct SESEmailSender {
+    client: Client,
+    from: String,
+    sandbox_recipient: Option<String>,
+}
+
+impl SESEmailSender {
+    pub(crate) fn new(client: Client, from: &str) -> Self {
+        Self {
+            client,
+            from: from.to_string(),
+            sandbox_recipient: None,
+        }
+    }
+
+    pub(crate) fn with_sandbox_recipient(mut self, sandbox_recipient: Option<String>) -> Self {
+        self.sandbox_recipient = sandbox_recipient;
+        self
+    }
+
+    fn content(&self, email: &Email) -> EmailContent {
+        if !email.attachments.is_empty() {
+            let mime = to_mime(self.from.as_str(), email);
+            return EmailContent::builder()
+                .raw(RawMessage::builder().data(Blob::new(mime.into_bytes())).build())
+                .build();
+        }
+        if let Some((name, data)) = &email.template {
+            return EmailContent::builder()
+                .template(Template::builder().template_name(name).template_data(data.to_string()).build())
+                .build();
+        }
+        EmailContent::builder()
+            .simple(Message::builder()
+                .subject(Content::builder().data(email.subject.as_str()).charset("UTF-8").build())
+                .body(Body::builder().text(Content::builder().data(email.text.as_str()).charset("UTF-8").build()).build())
+                .build())
+            .build()
+    }
+}
+
+#[async_trait]
+impl EmailSender for SESEmailSender {
+    async fn send(&self, email: &Email) -> LibraryResult<String> {
+        email.validate()?;
+        let email = match &self.sandbox_recipient {
+            Some(recipient) => to_sandbox(email, recipient.as_str()),
+            None => email.clone(),
+        };
+        self.client.send_email()
+            .from_email_address(self.from.as_str())
+            .destination(Destination::builder().to_addresses(email.to.as_str()).build())
+            .content(self.content(&email))
+            .send()
+            .await.map(|res| res.message_id().unwrap_or_default().to_string()).map_err(|err| LibraryError::unavailable(
+            format!("failed to send email {} due to {}", email.subject, err).as_str(), None, true))
+    }
+}
+
+// LocalEmailSender writes emails as .eml files into a directory so that they can be opened in a mail client
+// during local development and tests
+pub(crate) struct LocalEmailSender {
+    dir: PathBuf,
+    from: String,
+}
+
+impl LocalEmailSender {
+    pub(crate) fn new(dir: PathBuf, from: &str) -> Self {
+        Self {
+            dir,
+            from: from.to_string(),
+        }
+    }
+
+    pub(crate) fn path(&self, message_id: &str) -> PathBuf {
+        self.dir.join(format!("{}.eml", message_id))
+    }
+}
+
+#[async_trait]
+impl EmailSender for LocalEmailSender {
+    async fn send(&self, email: &Email) -> LibraryResult<String> {
+        email.validate()?;
+        let mut email = email.clone();
+        if let Some((name, data)) = &email.template {
+            email.text = format!("template {} with {}", name, data);
+        }
+        let message_id = Uuid::new_v4().to_string();
+        std::fs::create_dir_all(&self.dir).map_err(|err| LibraryError::runtime(
+            format!("failed to create email directory due to {}", err).as_str(), None))?;
+        std::fs::write(self.path(message_id.as_str()), to_mime(self.from.as_str(), &email)).map_err(|err| LibraryError::runtime(
+            format!("failed to write email {} due to {}", message_id, err).as_str(), None))?;
+        Ok(message_id)
+    }
+}
+
+// the subject names the party the email was meant for
+fn to_sandbox(email: &Email, recipient: &str) -> Email {
+    let mut sandboxed = email.clone();
+    sandboxed.subject = format!("[sandbox for {}] {}", email.to, email.subject);
+    sandboxed.to = recipient.to_string();
+    sandboxed
+}
+
+// builds a multipart MIME message with the text as first part and a base64 encoded part per attachment
+pub(crate) fn to_mime(from: &str, email: &Email) -> String {
+    let boundary = format!("lms-{}", Uuid::new_v4().simple());
+    let mut lines = vec![
+        format!("From: {}", from),
+        format!("To: {}", email.to),
+        format!("Subject: {}", encode_header(email.subject.as_str())),
+        "MIME-Version: 1.0".to_string(),
+        format!("Content-Type: multipart/mixed; boundary=\"{}\"", boundary),
+        "".to_string(),
+        format!("--{}", boundary),
+        "Content-Type: text/plain; charset=UTF-8".to_string(),
+        "Content-Transfer-Encoding: 8bit".to_string(),
+        "".to_string(),
+        email.text.to_string(),
+    ];
+    for attachment in &email.attachments {
+        let file_name = attachment.file_name.replace('"', "");
+        lines.push(format!("--{}", boundary));
+        lines.push(format!("Content-Type: {}; name=\"{}\"", attachment.content_type, file_name));
+        lines.push(format!("Content-Disposition: attachment; filename=\"{}\"", file_name));
+        lines.push("Content-Transfer-Encoding: base64".to_string());
+        lines.push("".to_string());
+        let encoded = STANDARD.encode(&attachment.content);
+        lines.extend(encoded.as_bytes().chunks(MIME_LINE_LENGTH).map(|line| String::from_utf8_lossy(line).to_string()));
+    }
+    lines.push(format!("--{}--", boundary));
+    lines.join("\r\n")
+}
+
+// headers are ASCII, other subjects are sent as RFC 2047 encoded words
+fn encode_header(value: &str) -> String {
+    if value.is_ascii() {
+        value.to_string()
+    } else {
+        format!("=?UTF-8?B?{}?=", STANDARD.encode(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::gateway::ses::{to_mime, to_sandbox, Email, EmailSender, LocalEmailSender};
+
+    #[tokio::test]
+    async fn test_should_build_mime_with_attachments() {
+        let email = Email::new("patron@example.com", "Receipt über", "your receipt")
+            .with_attachment("receipt.pdf", "application/pdf", b"%PDF-1.7".to_vec());
+        let mime = to_mime("library@example.com", &email);
+        assert!(mime.contains("Subject: =?UTF-8?B?"));
+        assert!(mime.contains("Content-Disposition: attachment; filename=\"receipt.pdf\""));
+        assert!(mime.contains("JVBERi0xLjc="));
+        assert!(mime.ends_with("--"));
+
+        let sandboxed = to_sandbox(&email, "sandbox@example.com");
+        assert_eq!("sandbox@example.com", sandboxed.to);
+        assert_eq!("[sandbox for patron@example.com] Receipt über", sandboxed.subject);
+    }
+
+    #[tokio::test]
+    async fn test_should_write_local_emails() {
+        let sender = LocalEmailSender::new(std::env::temp_dir().join("lms-emails-test"), "library@example.com");
+        let message_id = sender.send(&Email::new("patron@example.com", "Hold ready", "pick it up")
+            .with_template("hold_ready", json!({"title": "Dune"}))).await.expect("should send email");
+        let written = std::fs::read_to_string(sender.path(message_id.as_str())).expect("should write email");
+        assert!(written.contains("template hold_ready with {\"title\":\"Dune\"}"));
+
+        let invalid = Email::new("patron@example.com", "Hold ready", "pick it up")
+            .with_template("hold_ready", json!({})).with_attachment("a.txt", "text/plain", vec![]);
+        assert!(sender.send(&invalid).await.is_err());
+        assert!(sender.send(&Email::new("", "Hold ready", "pick it up")).await.is_err());
+    }
+}
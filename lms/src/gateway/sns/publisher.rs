@@ -5,30 +5,129 @@ use aws_sdk_sns::error::SdkError;
 use aws_sdk_sns::operation::create_topic::CreateTopicError;
 use aws_sdk_sns::operation::list_topics::ListTopicsError;
 use aws_sdk_sns::operation::publish::PublishError;
+use aws_sdk_sns::operation::publish_batch::PublishBatchError;
+use aws_sdk_sns::types::{MessageAttributeValue, PublishBatchRequestEntry};
+use serde_json::Value;
 use tracing::log::info;
 use crate::core::events::DomainEvent;
 use crate::core::library::LibraryError;
 use crate::gateway::events::EventPublisher;
+use crate::gateway::topics::{topic_name, TopicConfig};
+
+// PublishBatch accepts at most 10 messages per request
+const MAX_PUBLISH_BATCH_ENTRIES: usize = 10;
+// names of FIFO topics must end with the suffix
+const FIFO_SUFFIX: &str = ".fifo";
 
 #[derive(Debug)]
-pub struct SESPublisher {
+pub struct SNSPublisher {
     client: Client,
     topics: HashMap<String, String>,
+    fifo_topics: bool,
+    config: TopicConfig,
 }
 
-impl SESPublisher {
+// previous name of the publisher, which publishes events to SNS topics and does not send emails
+#[deprecated(note = "use SNSPublisher, emails are sent by gateway::ses::EmailSender")]
+#[allow(dead_code)]
+pub type SESPublisher = SNSPublisher;
+
+impl SNSPublisher {
     pub(crate) fn new(client: Client) -> Self {
         Self {
             client,
             topics: HashMap::new(),
+            fifo_topics: false,
+            config: TopicConfig::default(),
         }
     }
+
+    pub(crate) fn with_topic_config(mut self, config: TopicConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    // creates FIFO topics so that subscribers receive the events of an aggregate in the order they were published
+    pub(crate) fn with_fifo_topics(mut self, fifo_topics: bool) -> Self {
+        self.fifo_topics = fifo_topics;
+        self
+    }
+
+    // checks that the configured topics exist when a function starts instead of failing its first publish, missing
+    // topics are created when auto_create is set, returns the ARNs of created topics
+    pub(crate) async fn ensure_topics(&mut self) -> Result<Vec<String>, LibraryError> {
+        let existing = self.get_topics().await?;
+        let mut missing = self.config.topic_arns.values().filter(|arn| !existing.contains(arn))
+            .cloned().collect::<Vec<String>>();
+        missing.sort();
+        missing.dedup();
+        if missing.is_empty() {
+            return Ok(vec![]);
+        }
+        if !self.config.auto_create {
+            return Err(LibraryError::runtime(format!("topics are missing: {}", missing.join(", ")).as_str(),
+                                             Some("missing_topics".to_string())));
+        }
+        let mut created = vec![];
+        for arn in missing {
+            let name = topic_name(arn.as_str());
+            let mut req = self.client.create_topic().name(name);
+            if is_fifo(name) {
+                req = req.attributes("FifoTopic", "true");
+            }
+            let resp = req.send().await?;
+            info!("Created missing topic with ARN: {}", resp.topic_arn().unwrap_or_default());
+            created.push(resp.topic_arn().unwrap_or_default().to_string());
+        }
+        Ok(created)
+    }
+
+    // topics created by the publisher take precedence over configured topics
+    fn topic_arn(&self, name: &str) -> Result<&String, LibraryError> {
+        self.topics.get(name).or_else(|| self.config.topic_arn(name)).ok_or_else(|| {
+            LibraryError::runtime(format!("topic is not found {}", name).as_str(), Some("missing_topics".to_string()))
+        })
+    }
+}
+
+// attributes of published messages that subscription filter policies match on, e.g. {"event_group": ["checkout"]},
+// the branch is taken from the metadata or the branch_id of the payload and left out when the event has none
+pub(crate) fn message_attributes(event: &DomainEvent) -> HashMap<String, MessageAttributeValue> {
+    let mut attributes = HashMap::from([
+        ("event_name".to_string(), string_attribute(event.name.as_str())),
+        ("event_group".to_string(), string_attribute(event.group.as_str())),
+        ("event_kind".to_string(), string_attribute(format!("{:?}", event.kind).as_str())),
+    ]);
+    let branch_id = event.metadata.get("branch_id").cloned().or_else(|| {
+        serde_json::from_str::<Value>(event.json_data.as_str()).ok()
+            .and_then(|data| data.get("branch_id").and_then(Value::as_str).map(str::to_string))
+    }).unwrap_or_default();
+    // SNS rejects attributes with empty values
+    if !branch_id.is_empty() {
+        attributes.insert("branch_id".to_string(), string_attribute(branch_id.as_str()));
+    }
+    attributes
+}
+
+fn string_attribute(value: &str) -> MessageAttributeValue {
+    MessageAttributeValue::builder().data_type("String").string_value(value).build()
+}
+
+// messages to FIFO topics need a message group, events of an aggregate share the group of its key so they are
+// delivered in order, and the event id deduplicates retried publishes
+fn is_fifo(arn: &str) -> bool {
+    arn.ends_with(FIFO_SUFFIX)
 }
 
 #[async_trait]
-impl EventPublisher for SESPublisher {
+impl EventPublisher for SNSPublisher {
     async fn create_topic(&mut self, topic: &str) -> Result<String, LibraryError> {
-        let resp = self.client.create_topic().name(topic).send().await?;
+        let resp = if self.fifo_topics {
+            self.client.create_topic().name(format!("{}{}", topic, FIFO_SUFFIX))
+                .attributes("FifoTopic", "true").send().await?
+        } else {
+            self.client.create_topic().name(topic).send().await?
+        };
         let arn = resp.topic_arn().unwrap_or_default();
         self.topics.insert(topic.to_string(), arn.to_string());
         info!("Created topic with ARN: {}", arn);
@@ -37,22 +136,62 @@ impl EventPublisher for SESPublisher {
 
     async fn get_topics(&mut self) -> Result<Vec<String>, LibraryError> {
         let mut topics = vec![];
-        let resp = self.client.list_topics().send().await?;
-        for topic in resp.topics().unwrap_or_default() {
-            topics.push(topic.topic_arn().unwrap_or_default().to_string());
+        let mut next_token: Option<String> = None;
+        loop {
+            let resp = self.client.list_topics().set_next_token(next_token).send().await?;
+            for topic in resp.topics().unwrap_or_default() {
+                topics.push(topic.topic_arn().unwrap_or_default().to_string());
+            }
+            next_token = resp.next_token().map(str::to_string);
+            if next_token.is_none() {
+                break;
+            }
         }
         Ok(topics)
     }
 
     async fn publish(&self, event: &DomainEvent) -> Result<(), LibraryError> {
-        let topic = self.topics.get(event.name.as_str());
-        if let Some(arn) = topic {
-            let json = serde_json::to_string(event)?;
-            self.client.publish().topic_arn(arn).message(json).send().await?;
-            Ok(())
-        } else {
-            Err(LibraryError::runtime(format!("topic is not found {}", event.name).as_str(), None))
+        let arn = self.topic_arn(event.name.as_str())?;
+        let json = serde_json::to_string(event)?;
+        let mut req = self.client.publish().topic_arn(arn).message(json)
+            .set_message_attributes(Some(message_attributes(event)));
+        if is_fifo(arn) {
+            req = req.message_group_id(event.key.as_str()).message_deduplication_id(event.event_id.as_str());
+        }
+        req.send().await?;
+        Ok(())
+    }
+
+    // events are grouped by their topic because a batch is published to a single topic
+    async fn publish_all(&self, events: &[DomainEvent]) -> Result<(), LibraryError> {
+        let mut by_topic: HashMap<&str, Vec<&DomainEvent>> = HashMap::new();
+        for event in events {
+            let arn = self.topic_arn(event.name.as_str())?;
+            by_topic.entry(arn.as_str()).or_default().push(event);
+        }
+        for (arn, topic_events) in by_topic {
+            for chunk in topic_events.chunks(MAX_PUBLISH_BATCH_ENTRIES) {
+                let mut req = self.client.publish_batch().topic_arn(arn);
+                for event in chunk {
+                    let mut entry = PublishBatchRequestEntry::builder()
+                        .id(event.event_id.as_str())
+                        .message(serde_json::to_string(event)?)
+                        .set_message_attributes(Some(message_attributes(event)));
+                    if is_fifo(arn) {
+                        entry = entry.message_group_id(event.key.as_str())
+                            .message_deduplication_id(event.event_id.as_str());
+                    }
+                    req = req.publish_batch_request_entries(entry.build());
+                }
+                let resp = req.send().await?;
+                let failed = resp.failed().unwrap_or_default();
+                if !failed.is_empty() {
+                    return Err(LibraryError::runtime(format!("failed to publish {} events to {}: {:?}",
+                                                             failed.len(), arn, failed).as_str(), None));
+                }
+            }
         }
+        Ok(())
     }
 }
 
@@ -74,10 +213,17 @@ impl From<SdkError<PublishError>> for LibraryError {
     }
 }
 
+impl From<SdkError<PublishBatchError>> for LibraryError {
+    fn from(err: SdkError<PublishBatchError>) -> Self {
+        LibraryError::runtime(format!("{:?}", err).as_str(), None)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
     use crate::core::events::DomainEvent;
+    use crate::gateway::sns::publisher::message_attributes;
     use crate::gateway::{factory, GatewayPublisherVia};
 
     #[tokio::test]
@@ -90,4 +236,19 @@ mod tests {
         let topics = publisher.get_topics().await.expect("should get topics");
         assert!(topics.contains(&arn));
     }
+
+    #[tokio::test]
+    async fn test_should_build_message_attributes() {
+        let data = HashMap::from([("branch_id", "main")]);
+        let event = DomainEvent::updated("book_overdue", "checkout", "key", &HashMap::new(), &data).expect("build event");
+        let attributes = message_attributes(&event);
+        assert_eq!(Some("book_overdue"), attributes["event_name"].string_value());
+        assert_eq!(Some("checkout"), attributes["event_group"].string_value());
+        assert_eq!(Some("Updated"), attributes["event_kind"].string_value());
+        assert_eq!(Some("main"), attributes["branch_id"].string_value());
+
+        let event = DomainEvent::added("test-name", "group", "key", &HashMap::new(), &HashMap::from([("a", 1)]))
+            .expect("build event");
+        assert!(!message_attributes(&event).contains_key("branch_id"));
+    }
 }
@@ -0,0 +1,177 @@
+use async_trait::async_trait;
+use reqwest::Method;
+use crate::books::dto::BookDto;
+use crate::catalog::marc::{map_record, parse_marcxml};
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::gateway::http::HttpClient;
+
+// UnionCatalogQuery is a search of the union catalog by isbn when the search term reads as an isbn or by title otherwise
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum UnionCatalogQuery {
+    Isbn(String),
+    Title(String),
+}
+
+impl UnionCatalogQuery {
+    pub(crate) fn parse(q: &str) -> LibraryResult<UnionCatalogQuery> {
+        let q = q.split_whitespace().collect::<Vec<&str>>().join(" ");
+        if q.is_empty() {
+            return Err(LibraryError::validation("search term is required", Some("400".to_string())));
+        }
+        let isbn = q.chars().filter(|c| *c != '-' && *c != ' ').collect::<String>().to_uppercase();
+        let digits = isbn.chars().filter(|c| c.is_ascii_digit()).count();
+        if (isbn.len() == 13 && digits == 13) || (isbn.len() == 10 && (digits == 10 || (digits == 9 && isbn.ends_with('X')))) {
+            return Ok(UnionCatalogQuery::Isbn(isbn));
+        }
+        Ok(UnionCatalogQuery::Title(q))
+    }
+
+    // CQL with the Bath profile isbn index and the Dublin Core title index that SRU servers commonly support, quotes
+    // and backslashes of the term are escaped
+    pub(crate) fn to_cql(&self) -> String {
+        match self {
+            UnionCatalogQuery::Isbn(isbn) => format!("bath.isbn=\"{}\"", isbn),
+            UnionCatalogQuery::Title(title) => format!("dc.title=\"{}\"", title.replace('\\', "\\\\").replace('"', "\\\"")),
+        }
+    }
+}
+
+// UnionCatalog searches an external union catalog and returns the matching records as books, the books are candidates
+// for cataloging so they carry new ids
+#[async_trait]
+pub(crate) trait UnionCatalog: Sync + Send {
+    async fn search(&self, query: &UnionCatalogQuery, max_records: usize) -> LibraryResult<Vec<BookDto>>;
+}
+
+// StubUnionCatalog is used when no union catalog is configured, its searches fail as unavailable so that federated
+// search degrades the same way as when the union catalog is down
+#[derive(Debug, Default)]
+pub(crate) struct StubUnionCatalog {}
+
+impl StubUnionCatalog {
+    pub(crate) fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl UnionCatalog for StubUnionCatalog {
+    async fn search(&self, _query: &UnionCatalogQuery, _max_records: usize) -> LibraryResult<Vec<BookDto>> {
+        Err(LibraryError::unavailable("union catalog is not configured", None, false))
+    }
+}
+
+// SruUnionCatalog sends searchRetrieve requests of SRU 1.2 asking for MARCXML records packed as XML, records that
+// cannot be mapped to a book such as records without isbn are skipped
+pub(crate) struct SruUnionCatalog {
+    client: HttpClient,
+    url: String,
+}
+
+impl SruUnionCatalog {
+    pub(crate) fn new(client: HttpClient, url: &str) -> Self {
+        Self {
+            client,
+            url: url.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl UnionCatalog for SruUnionCatalog {
+    async fn search(&self, query: &UnionCatalogQuery, max_records: usize) -> LibraryResult<Vec<BookDto>> {
+        let cql = query.to_cql();
+        let max_records = max_records.to_string();
+        let url = reqwest::Url::parse_with_params(self.url.as_str(), &[
+            ("version", "1.2"),
+            ("operation", "searchRetrieve"),
+            ("query", cql.as_str()),
+            ("maximumRecords", max_records.as_str()),
+            ("recordSchema", "marcxml"),
+            ("recordPacking", "xml"),
+        ]).map_err(|err| LibraryError::runtime(format!("invalid union catalog url {} {}", self.url, err).as_str(), None))?;
+        let res = self.client.send(Method::GET, url.as_str(), None::<&()>).await?;
+        // the record envelopes of the response share the local name of MARCXML records, only the MARC records
+        // nested in them carry fields
+        let books = parse_marcxml(res.body.as_str())?.iter()
+            .filter_map(|record| map_record(record).book.ok()).collect();
+        Ok(books)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::net::TcpListener;
+    use axum::extract::Query;
+    use axum::routing::get;
+    use axum::Router;
+    use crate::core::library::LibraryError;
+    use crate::gateway::http::{HttpClient, HttpClientConfig};
+    use crate::gateway::sru::{SruUnionCatalog, StubUnionCatalog, UnionCatalog, UnionCatalogQuery};
+
+    // answers isbn searches with a record of the isbn and a record without isbn
+    async fn search_retrieve(Query(params): Query<HashMap<String, String>>) -> String {
+        assert_eq!(Some("searchRetrieve"), params.get("operation").map(|op| op.as_str()));
+        assert_eq!(Some("marcxml"), params.get("recordSchema").map(|schema| schema.as_str()));
+        let isbn = params.get("query").and_then(|query| query.strip_prefix("bath.isbn=\""))
+            .map(|isbn| isbn.trim_end_matches('"')).unwrap_or_default().to_string();
+        format!(r#"<?xml version="1.0"?>
+            <zs:searchRetrieveResponse xmlns:zs="http://www.loc.gov/zing/srw/">
+              <zs:version>1.2</zs:version>
+              <zs:numberOfRecords>2</zs:numberOfRecords>
+              <zs:records>
+                <zs:record>
+                  <zs:recordSchema>marcxml</zs:recordSchema>
+                  <zs:recordPacking>xml</zs:recordPacking>
+                  <zs:recordData>
+                    <record xmlns="http://www.loc.gov/MARC21/slim">
+                      <datafield tag="020" ind1=" " ind2=" "><subfield code="a">{}</subfield></datafield>
+                      <datafield tag="245" ind1="1" ind2="0"><subfield code="a">Dune /</subfield></datafield>
+                    </record>
+                  </zs:recordData>
+                  <zs:recordPosition>1</zs:recordPosition>
+                </zs:record>
+                <zs:record>
+                  <zs:recordData>
+                    <record xmlns="http://www.loc.gov/MARC21/slim">
+                      <datafield tag="245" ind1="1" ind2="0"><subfield code="a">Dune</subfield></datafield>
+                    </record>
+                  </zs:recordData>
+                </zs:record>
+              </zs:records>
+            </zs:searchRetrieveResponse>"#, isbn)
+    }
+
+    #[tokio::test]
+    async fn test_should_parse_union_catalog_query() {
+        assert_eq!(UnionCatalogQuery::Isbn("9780441172719".to_string()),
+                   UnionCatalogQuery::parse(" 978-0-441-17271-9 ").expect("should parse isbn"));
+        assert_eq!(UnionCatalogQuery::Isbn("044117271X".to_string()),
+                   UnionCatalogQuery::parse("044117271x").expect("should parse isbn"));
+        let title = UnionCatalogQuery::parse("The  \"Dune\" saga").expect("should parse title");
+        assert_eq!("dc.title=\"The \\\"Dune\\\" saga\"", title.to_cql().as_str());
+        assert!(matches!(UnionCatalogQuery::parse(" "), Err(LibraryError::Validation { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_should_search_union_catalog() {
+        let app = Router::new().route("/sru", get(search_retrieve));
+        let listener = TcpListener::bind("127.0.0.1:0").expect("should bind");
+        let url = format!("http://{}/sru", listener.local_addr().expect("should have address"));
+        tokio::spawn(async move {
+            let _ = axum::Server::from_tcp(listener).expect("should serve").serve(app.into_make_service()).await;
+        });
+
+        let client = HttpClient::new(HttpClientConfig::default()).expect("should build client");
+        let union_catalog = SruUnionCatalog::new(client, url.as_str());
+        let query = UnionCatalogQuery::parse("9780441172719").expect("should parse query");
+        let books = union_catalog.search(&query, 10).await.expect("should search union catalog");
+        assert_eq!(1, books.len());
+        assert_eq!("9780441172719", books[0].isbn.as_str());
+        assert_eq!("Dune", books[0].title.as_str());
+
+        assert!(matches!(StubUnionCatalog::new().search(&query, 10).await,
+            Err(LibraryError::CurrentlyUnavailable { .. })));
+    }
+}
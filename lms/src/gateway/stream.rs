@@ -0,0 +1,114 @@
+use async_trait::async_trait;
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use tokio::sync::broadcast;
+use crate::core::events::DomainEvent;
+use crate::core::library::LibraryError;
+use crate::gateway::events::EventPublisher;
+
+// events buffered for each subscriber, slow subscribers skip the oldest events instead of holding up publishers
+const EVENT_STREAM_CAPACITY: usize = 1024;
+
+lazy_static! {
+    static ref EVENT_STREAM: broadcast::Sender<DomainEvent> = broadcast::channel(EVENT_STREAM_CAPACITY).0;
+}
+
+// returns a receiver of the events published by this process from now on
+pub(crate) fn subscribe_events() -> broadcast::Receiver<DomainEvent> {
+    EVENT_STREAM.subscribe()
+}
+
+// BroadcastPublisher forwards events to in-process subscribers such as the event stream of the standalone server
+// after they are published by the delegate, sending without subscribers is a no-op
+pub(crate) struct BroadcastPublisher {
+    delegate: Box<dyn EventPublisher>,
+}
+
+impl BroadcastPublisher {
+    pub(crate) fn new(delegate: Box<dyn EventPublisher>) -> Self {
+        Self {
+            delegate,
+        }
+    }
+}
+
+#[async_trait]
+impl EventPublisher for BroadcastPublisher {
+    async fn create_topic(&mut self, topic: &str) -> Result<String, LibraryError> {
+        self.delegate.create_topic(topic).await
+    }
+
+    async fn get_topics(&mut self) -> Result<Vec<String>, LibraryError> {
+        self.delegate.get_topics().await
+    }
+
+    async fn publish(&self, event: &DomainEvent) -> Result<(), LibraryError> {
+        self.delegate.publish(event).await?;
+        let _ = EVENT_STREAM.send(event.clone());
+        Ok(())
+    }
+
+    async fn publish_all(&self, events: &[DomainEvent]) -> Result<(), LibraryError> {
+        self.delegate.publish_all(events).await?;
+        for event in events {
+            let _ = EVENT_STREAM.send(event.clone());
+        }
+        Ok(())
+    }
+}
+
+// EventStreamFilter selects streamed events by comma separated groups and names, missing filters match all events
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct EventStreamFilter {
+    #[serde(default)]
+    pub group: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+impl EventStreamFilter {
+    pub(crate) fn matches(&self, event: &DomainEvent) -> bool {
+        let matches = |filter: &Option<String>, value: &str| filter.as_ref()
+            .map(|filter| filter.split(',').map(|f| f.trim()).any(|f| f.is_empty() || f == value))
+            .unwrap_or(true);
+        matches(&self.group, event.group.as_str()) && matches(&self.name, event.name.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::core::events::DomainEvent;
+    use crate::gateway::factory::create_publisher;
+    use crate::gateway::stream::{subscribe_events, EventStreamFilter};
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_broadcast_published_events() {
+        let store = test_store(module_path!(), "test_should_broadcast_published_events");
+        let mut receiver = subscribe_events();
+        let publisher = create_publisher(store.gateway_publisher()).await;
+        let data = HashMap::from([("a", 1)]);
+        let event = DomainEvent::added("streamed", "stream_test", "key", &HashMap::new(), &data).expect("build event");
+        publisher.publish(&event).await.expect("should publish");
+        // other tests publish concurrently so events of this test are picked by their group
+        loop {
+            let received = receiver.recv().await.expect("should receive event");
+            if received.group == "stream_test" {
+                assert_eq!(event, received);
+                break;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_should_filter_events_by_group_and_name() {
+        let data = HashMap::from([("a", 1)]);
+        let event = DomainEvent::added("book_checkout", "checkout", "key", &HashMap::new(), &data).expect("build event");
+        assert!(EventStreamFilter::default().matches(&event));
+        assert!(EventStreamFilter { group: Some("hold,checkout".to_string()), name: None }.matches(&event));
+        assert!(EventStreamFilter { group: None, name: Some("book_checkout".to_string()) }.matches(&event));
+        assert!(!EventStreamFilter { group: Some("hold".to_string()), name: None }.matches(&event));
+        assert!(!EventStreamFilter { group: Some("checkout".to_string()), name: Some("book_returned".to_string()) }.matches(&event));
+    }
+}
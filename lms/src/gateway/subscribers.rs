@@ -0,0 +1,106 @@
+use async_trait::async_trait;
+use tracing::log::warn;
+use crate::core::events::DomainEvent;
+use crate::core::events::upcasters::UPCASTERS;
+use crate::core::library::{LibraryError, LibraryResult};
+
+// EventSubscriber consumes domain events that are delivered asynchronously, e.g. by SNS or SQS triggers.
+// Events are delivered at least once so subscribers may see an event again after a failed delivery.
+#[async_trait]
+pub(crate) trait EventSubscriber: Sync + Send {
+    // name of subscriber
+    fn name(&self) -> String;
+
+    // returns true if subscriber is interested in the event
+    fn handles(&self, event: &DomainEvent) -> bool;
+
+    async fn handle(&self, event: &DomainEvent) -> LibraryResult<()>;
+}
+
+// SubscriberRegistry routes each event to all subscribers that handle it
+pub struct SubscriberRegistry {
+    subscribers: Vec<Box<dyn EventSubscriber>>,
+}
+
+impl SubscriberRegistry {
+    pub(crate) fn new() -> Self {
+        Self {
+            subscribers: vec![],
+        }
+    }
+
+    pub(crate) fn register(mut self, subscriber: Box<dyn EventSubscriber>) -> Self {
+        self.subscribers.push(subscriber);
+        self
+    }
+
+    // delivers the event to every interested subscriber and returns the number of subscribers, the event
+    // fails if any subscriber failed so that the trigger delivers it again
+    pub(crate) async fn dispatch(&self, event: &DomainEvent) -> LibraryResult<usize> {
+        // events published before their payload changed are delivered in the current shape
+        let event = &UPCASTERS.upcast(event)?;
+        let mut delivered = 0;
+        let mut failed = vec![];
+        for subscriber in self.subscribers.iter().filter(|s| s.handles(event)) {
+            match subscriber.handle(event).await {
+                Ok(_) => delivered += 1,
+                Err(err) => {
+                    warn!("subscriber {} failed to handle event {} due to {}", subscriber.name(), event.event_id, err);
+                    failed.push(subscriber.name());
+                }
+            }
+        }
+        if !failed.is_empty() {
+            return Err(LibraryError::runtime(format!("event {} failed in subscribers {}",
+                                                     event.event_id, failed.join(", ")).as_str(), None));
+        }
+        Ok(delivered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use async_trait::async_trait;
+    use crate::core::events::DomainEvent;
+    use crate::core::library::{LibraryError, LibraryResult};
+    use crate::gateway::subscribers::{EventSubscriber, SubscriberRegistry};
+
+    struct NamedSubscriber {
+        name: String,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl EventSubscriber for NamedSubscriber {
+        fn name(&self) -> String {
+            self.name.to_string()
+        }
+
+        fn handles(&self, event: &DomainEvent) -> bool {
+            event.name != "ignored"
+        }
+
+        async fn handle(&self, _event: &DomainEvent) -> LibraryResult<()> {
+            if self.fail {
+                return Err(LibraryError::runtime("subscriber failure", None));
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_should_dispatch_to_subscribers() {
+        let data = HashMap::from([("a", 1)]);
+        let event = DomainEvent::added("book_added", "books", "key", &HashMap::new(), &data).expect("build event");
+        let ignored = DomainEvent::added("ignored", "books", "key", &HashMap::new(), &data).expect("build event");
+        let registry = SubscriberRegistry::new()
+            .register(Box::new(NamedSubscriber { name: "first".to_string(), fail: false }))
+            .register(Box::new(NamedSubscriber { name: "second".to_string(), fail: false }));
+        assert_eq!(2, registry.dispatch(&event).await.expect("should dispatch"));
+        assert_eq!(0, registry.dispatch(&ignored).await.expect("should dispatch"));
+
+        let registry = registry.register(Box::new(NamedSubscriber { name: "failing".to_string(), fail: true }));
+        assert!(registry.dispatch(&event).await.is_err());
+    }
+}
@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use tracing::log::info;
+use crate::core::library::LibraryResult;
+use crate::core::repository::RepositoryStore;
+use crate::core::tasks::{Task, TaskHandler, TaskPayload};
+use crate::gateway::ddb::compaction::compact_events;
+use crate::gateway::factory::create_event_archive;
+use crate::utils::ddb::{build_db_client, ScanGuard};
+
+// ArchiveEventsTaskHandler moves old events from the events table into the event archive in the background,
+// compacting again after a failure does not duplicate archived events
+pub(crate) struct ArchiveEventsTaskHandler {
+    store: RepositoryStore,
+}
+
+impl ArchiveEventsTaskHandler {
+    pub(crate) fn new(store: RepositoryStore) -> Self {
+        Self {
+            store,
+        }
+    }
+}
+
+#[async_trait]
+impl TaskHandler for ArchiveEventsTaskHandler {
+    fn handles(&self, task: &Task) -> bool {
+        matches!(task.payload, TaskPayload::ArchiveEvents { .. })
+    }
+
+    async fn handle(&self, task: &Task) -> LibraryResult<()> {
+        if let TaskPayload::ArchiveEvents { older_than_days } = &task.payload {
+            let client = build_db_client(self.store).await;
+            let before = Utc::now().naive_utc() - Duration::days(*older_than_days);
+            let summary = compact_events(&client, self.store.table_name("events").as_str(), &create_event_archive().await,
+                                         &ScanGuard::new(self.store), before).await?;
+            info!("archived {} events into {} objects", summary.archived, summary.objects);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::repository::RepositoryStore;
+    use crate::core::tasks::{Task, TaskHandler, TaskPayload};
+    use crate::gateway::tasks::ArchiveEventsTaskHandler;
+
+    #[tokio::test]
+    async fn test_should_handle_archive_tasks() {
+        let handler = ArchiveEventsTaskHandler::new(RepositoryStore::LocalDynamoDB);
+        assert!(handler.handles(&Task::new(TaskPayload::ArchiveEvents { older_than_days: 90 })));
+        assert!(!handler.handles(&Task::new(TaskPayload::RebuildProjection { projection: "co_checkouts".to_string(), switch_over: true })));
+    }
+}
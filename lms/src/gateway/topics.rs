@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use crate::core::library::{LibraryError, LibraryResult};
+
+// event names whose topic is not configured are published to the topic of the default entry
+pub(crate) const DEFAULT_TOPIC: &str = "*";
+
+// TopicConfig maps event names to the ARNs of their topics, it is read from TOPIC_ARNS such as
+// "book_checkout=arn:aws:sns:us-east-1:123456789012:book_checkout,*=arn:aws:sns:us-east-1:123456789012:library"
+// and AUTO_CREATE_TOPICS=true creates configured topics that are missing when the publisher starts
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct TopicConfig {
+    pub(crate) topic_arns: HashMap<String, String>,
+    pub(crate) auto_create: bool,
+}
+
+impl TopicConfig {
+    pub(crate) fn new(topic_arns: HashMap<String, String>, auto_create: bool) -> Self {
+        Self {
+            topic_arns,
+            auto_create,
+        }
+    }
+
+    pub(crate) fn from_env() -> LibraryResult<Self> {
+        let topic_arns = parse_topic_arns(std::env::var("TOPIC_ARNS").unwrap_or_default().as_str())?;
+        let auto_create = std::env::var("AUTO_CREATE_TOPICS").map(|auto| auto == "true").unwrap_or(false);
+        Ok(Self::new(topic_arns, auto_create))
+    }
+
+    pub(crate) fn topic_arn(&self, name: &str) -> Option<&String> {
+        self.topic_arns.get(name).or_else(|| self.topic_arns.get(DEFAULT_TOPIC))
+    }
+}
+
+pub(crate) fn parse_topic_arns(value: &str) -> LibraryResult<HashMap<String, String>> {
+    let mut topic_arns = HashMap::new();
+    for entry in value.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+        match entry.split_once('=') {
+            Some((name, arn)) if !name.trim().is_empty() && arn.trim().starts_with("arn:") => {
+                topic_arns.insert(name.trim().to_string(), arn.trim().to_string());
+            }
+            _ => return Err(LibraryError::validation(
+                format!("topic {} is not configured as <event name>=<topic arn>", entry).as_str(),
+                Some("invalid_topic_config".to_string()))),
+        }
+    }
+    Ok(topic_arns)
+}
+
+// name of the topic is the last segment of its ARN
+pub(crate) fn topic_name(arn: &str) -> &str {
+    arn.rsplit(':').next().unwrap_or(arn)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gateway::topics::{parse_topic_arns, topic_name, TopicConfig};
+
+    #[tokio::test]
+    async fn test_should_resolve_configured_topics() {
+        let topic_arns = parse_topic_arns("book_checkout=arn:aws:sns:us-east-1:1:checkouts, *=arn:aws:sns:us-east-1:1:library")
+            .expect("should parse topics");
+        let config = TopicConfig::new(topic_arns, false);
+        assert_eq!(Some(&"arn:aws:sns:us-east-1:1:checkouts".to_string()), config.topic_arn("book_checkout"));
+        assert_eq!(Some(&"arn:aws:sns:us-east-1:1:library".to_string()), config.topic_arn("book_hold"));
+        assert_eq!("checkouts", topic_name("arn:aws:sns:us-east-1:1:checkouts"));
+        assert!(parse_topic_arns("").expect("should parse empty topics").is_empty());
+        assert!(parse_topic_arns("book_checkout").is_err());
+        assert!(parse_topic_arns("book_checkout=checkouts").is_err());
+    }
+}
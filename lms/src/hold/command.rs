@@ -1,3 +1,9 @@
 pub mod cancel_hold_book_cmd;
 pub mod checkout_hold_book_cmd;
+pub mod expire_pickups_cmd;
+pub mod extend_hold_cmd;
+pub mod find_recent_holds_cmd;
 pub mod hold_book_cmd;
+pub mod hold_books_cmd;
+pub mod hold_status_cmd;
+pub mod ready_for_pickup_cmd;
@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use crate::core::command::{Command, CommandError};
+use crate::core::ids::{BookId, PatronId};
 use crate::hold::domain::HoldService;
 use crate::hold::dto::HoldDto;
 
@@ -16,14 +17,14 @@ impl CheckoutHoldBookCommand {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct CheckoutHoldBookCommandRequest {
-    patron_id: String,
-    book_id: String,
+    patron_id: PatronId,
+    book_id: BookId,
 }
 
 impl CheckoutHoldBookCommandRequest {
-    pub fn new(patron_id: String, book_id: String) -> Self {
+    pub fn new(patron_id: PatronId, book_id: BookId) -> Self {
         Self {
             patron_id,
             book_id,
@@ -32,7 +33,7 @@ impl CheckoutHoldBookCommandRequest {
 }
 
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct CheckoutHoldBookCommandResponse {
     hold: HoldDto,
 }
@@ -48,15 +49,13 @@ impl CheckoutHoldBookCommandResponse {
 #[async_trait]
 impl Command<CheckoutHoldBookCommandRequest, CheckoutHoldBookCommandResponse> for CheckoutHoldBookCommand {
     async fn execute(&self, req: CheckoutHoldBookCommandRequest) -> Result<CheckoutHoldBookCommandResponse, CommandError> {
-        self.hold_service.checkout(req.patron_id.as_str(), req.book_id.as_str())
+        self.hold_service.checkout(&req.patron_id, &req.book_id)
             .await.map_err(CommandError::from).map(CheckoutHoldBookCommandResponse::new)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use async_once::AsyncOnce;
-    use lazy_static::lazy_static;
     use crate::books::dto::BookDto;
     use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
     use crate::catalog::factory::create_catalog_service;
@@ -70,32 +69,35 @@ mod tests {
     use crate::patrons::command::add_patron_cmd::{AddPatronCommand, AddPatronCommandRequest};
     use crate::patrons::dto::PatronDto;
     use crate::patrons::factory::create_patron_service;
+    use crate::utils::testing::test_store;
 
-    lazy_static! {
-        static ref BOOK_CMD : AsyncOnce<AddBookCommand> = AsyncOnce::new(async {
-                let svc = create_catalog_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                AddBookCommand::new(svc)
-            });
-        static ref PATRON_CMD : AsyncOnce<AddPatronCommand> = AsyncOnce::new(async {
-                let svc = create_patron_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                AddPatronCommand::new(svc)
-            });
-        static ref HOLD_CMD : AsyncOnce<HoldBookCommand> = AsyncOnce::new(async {
-                let svc = create_hold_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                HoldBookCommand::new(svc)
-            });
-        static ref CHECKOUT_HOLD_CMD : AsyncOnce<CheckoutHoldBookCommand> = AsyncOnce::new(async {
-                let svc = create_hold_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                CheckoutHoldBookCommand::new(svc)
-            });
+    async fn build_book_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_patron_cmd(store: RepositoryStore) -> AddPatronCommand {
+        let svc = create_patron_service(&Configuration::new("test"), store).await;
+        AddPatronCommand::new(svc)
+    }
+
+    async fn build_hold_cmd(store: RepositoryStore) -> HoldBookCommand {
+        let svc = create_hold_service(&Configuration::new("test"), store).await;
+        HoldBookCommand::new(svc)
+    }
+
+    async fn build_checkout_hold_cmd(store: RepositoryStore) -> CheckoutHoldBookCommand {
+        let svc = create_hold_service(&Configuration::new("test"), store).await;
+        CheckoutHoldBookCommand::new(svc)
     }
 
     #[tokio::test]
     async fn test_should_run_checkout_book() {
-        let patron_cmd: &AddPatronCommand = PATRON_CMD.get().await.clone();
-        let book_cmd: &AddBookCommand = BOOK_CMD.get().await.clone();
-        let hold_cmd: &HoldBookCommand = HOLD_CMD.get().await.clone();
-        let checkout_hold_cmd: &CheckoutHoldBookCommand = CHECKOUT_HOLD_CMD.get().await.clone();
+        let store = test_store(module_path!(), "test_should_run_checkout_book");
+        let patron_cmd = build_patron_cmd(store).await;
+        let book_cmd = build_book_cmd(store).await;
+        let hold_cmd = build_hold_cmd(store).await;
+        let checkout_hold_cmd = build_checkout_hold_cmd(store).await;
 
         let patron = PatronDto::new("email");
         let _ = patron_cmd.execute(AddPatronCommandRequest::new(patron.email.as_str())).await.expect("should add patron");
@@ -104,9 +106,9 @@ mod tests {
         let _ = book_cmd.execute(AddBookCommandRequest::new(book.isbn.as_str(), book.title.as_str()))
             .await.expect("should add book");
         let _ = hold_cmd.execute(HoldBookCommandRequest::new(
-            patron.patron_id.to_string(), book.book_id.to_string())).await.expect("should hold book");
+            patron.patron_id.as_str().into(), book.book_id.as_str().into())).await.expect("should hold book");
         let res = checkout_hold_cmd.execute(CheckoutHoldBookCommandRequest::new(
-            patron.patron_id.to_string(), book.book_id.to_string())).await.expect("should cancel book");
+            patron.patron_id.as_str().into(), book.book_id.as_str().into())).await.expect("should cancel book");
         assert_eq!(patron.patron_id, res.hold.patron_id);
         assert_eq!(book.book_id, res.hold.book_id);
     }
@@ -0,0 +1,81 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::hold::domain::HoldService;
+use crate::hold::dto::HoldDto;
+
+pub(crate) struct ExpirePickupsCommand {
+    hold_service: Box<dyn HoldService>,
+}
+
+impl ExpirePickupsCommand {
+    pub(crate) fn new(hold_service: Box<dyn HoldService>) -> Self {
+        Self {
+            hold_service,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ExpirePickupsCommandRequest {}
+
+impl ExpirePickupsCommandRequest {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ExpirePickupsCommandResponse {
+    expired: Vec<HoldDto>,
+}
+
+impl ExpirePickupsCommandResponse {
+    pub fn new(expired: Vec<HoldDto>) -> Self {
+        Self {
+            expired,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<ExpirePickupsCommandRequest, ExpirePickupsCommandResponse> for ExpirePickupsCommand {
+    async fn execute(&self, _req: ExpirePickupsCommandRequest) -> Result<ExpirePickupsCommandResponse, CommandError> {
+        self.hold_service.expire_pickups()
+            .await.map_err(CommandError::from).map(ExpirePickupsCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::ids::{BookId, PatronId};
+    use crate::core::library::HoldStatus;
+    use crate::core::repository::RepositoryStore;
+    use crate::hold::command::expire_pickups_cmd::{ExpirePickupsCommand, ExpirePickupsCommandRequest};
+    use crate::hold::domain::model::HoldEntity;
+    use crate::hold::factory::{create_hold_repository, create_hold_service};
+    use crate::utils::testing::test_store;
+
+    async fn build_sut_cmd(store: RepositoryStore) -> ExpirePickupsCommand {
+        let svc = create_hold_service(&Configuration::new("test"), store).await;
+        ExpirePickupsCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_expire_pickups() {
+        let store = test_store(module_path!(), "test_should_run_expire_pickups");
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut hold = HoldEntity::new(&BookId::new("expired_pickup_book"), &PatronId::new("expired_pickup_patron"));
+        hold.hold_status = HoldStatus::ReadyForPickup;
+        hold.pickup_by = Some(Utc::now().naive_utc() - Duration::days(1));
+        let _ = create_hold_repository(store).await.create(&hold).await.expect("should create hold");
+
+        let res = sut_cmd.execute(ExpirePickupsCommandRequest::new()).await.expect("should expire pickups");
+        let expired = res.expired.iter().find(|h| h.hold_id == hold.hold_id).expect("should expire hold");
+        assert_eq!(HoldStatus::Canceled, expired.hold_status);
+    }
+}
@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::core::ids::HoldId;
+use crate::hold::domain::HoldService;
+use crate::hold::dto::HoldDto;
+
+pub(crate) struct ExtendHoldCommand {
+    hold_service: Box<dyn HoldService>,
+}
+
+impl ExtendHoldCommand {
+    pub(crate) fn new(hold_service: Box<dyn HoldService>) -> Self {
+        Self {
+            hold_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ExtendHoldCommandRequest {
+    #[serde(default)]
+    pub hold_id: HoldId,
+    // patron holding the book or a librarian
+    requested_by: String,
+}
+
+impl ExtendHoldCommandRequest {
+    pub fn new(hold_id: &HoldId, requested_by: &str) -> Self {
+        Self {
+            hold_id: hold_id.clone(),
+            requested_by: requested_by.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ExtendHoldCommandResponse {
+    pub hold: HoldDto,
+}
+
+impl ExtendHoldCommandResponse {
+    pub fn new(hold: HoldDto) -> Self {
+        Self {
+            hold,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<ExtendHoldCommandRequest, ExtendHoldCommandResponse> for ExtendHoldCommand {
+    async fn execute(&self, req: ExtendHoldCommandRequest) -> Result<ExtendHoldCommandResponse, CommandError> {
+        self.hold_service.extend(&req.hold_id, req.requested_by.as_str())
+            .await.map_err(CommandError::from).map(ExtendHoldCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::books::domain::model::BookEntity;
+    use crate::books::factory::create_book_repository;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::ids::{BookId, PatronId};
+    use crate::core::library::{BookStatus, PartyKind};
+    use crate::core::repository::RepositoryStore;
+    use crate::hold::command::extend_hold_cmd::{ExtendHoldCommand, ExtendHoldCommandRequest};
+    use crate::hold::domain::HoldService;
+    use crate::hold::factory::create_hold_service;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn HoldService> {
+        create_hold_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> ExtendHoldCommand {
+        let svc = create_hold_service(&Configuration::new("test"), store).await;
+        ExtendHoldCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_extend_hold() {
+        let store = test_store(module_path!(), "test_should_run_extend_hold");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let patron = PartyEntity::new(PartyKind::Patron, "extend_cmd@example.com");
+        let _ = create_party_repository(store).await.create(&patron).await.expect("should create patron");
+        let book = BookEntity::new("isbn", "extend title", BookStatus::Available);
+        let _ = create_book_repository(store).await.create(&book).await.expect("should create book");
+        let hold = svc.hold(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str()), None).await.expect("should hold");
+
+        let res = sut_cmd.execute(ExtendHoldCommandRequest::new(&hold.hold_id, patron.party_id.as_str()))
+            .await.expect("should extend");
+        assert_eq!(1, res.hold.extensions);
+        assert!(res.hold.expires_at > hold.expires_at);
+    }
+}
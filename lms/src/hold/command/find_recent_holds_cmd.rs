@@ -0,0 +1,116 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::hold::domain::HoldQueryService;
+use crate::hold::dto::HoldDto;
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+// recent holds are those of the last day unless the request starts earlier
+const DEFAULT_RECENT_HOURS: i64 = 24;
+
+pub(crate) struct FindRecentHoldsCommand {
+    hold_service: Box<dyn HoldQueryService>,
+}
+
+impl FindRecentHoldsCommand {
+    pub(crate) fn new(hold_service: Box<dyn HoldQueryService>) -> Self {
+        Self {
+            hold_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindRecentHoldsCommandRequest {
+    // defaults to the branch of the configuration
+    #[serde(default)]
+    pub(crate) branch_id: String,
+    pub(crate) since: Option<DateTime<Utc>>,
+    pub(crate) until: Option<DateTime<Utc>>,
+    pub(crate) page: Option<String>,
+    pub(crate) page_size: Option<usize>,
+}
+
+impl FindRecentHoldsCommandRequest {
+    pub fn new(branch_id: &str) -> Self {
+        Self {
+            branch_id: branch_id.to_string(),
+            since: None,
+            until: None,
+            page: None,
+            page_size: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindRecentHoldsCommandResponse {
+    pub holds: Vec<HoldDto>,
+    pub next_page: Option<String>,
+}
+
+impl FindRecentHoldsCommandResponse {
+    pub fn new(holds: Vec<HoldDto>, next_page: Option<String>) -> Self {
+        Self {
+            holds,
+            next_page,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<FindRecentHoldsCommandRequest, FindRecentHoldsCommandResponse> for FindRecentHoldsCommand {
+    async fn execute(&self, req: FindRecentHoldsCommandRequest) -> Result<FindRecentHoldsCommandResponse, CommandError> {
+        let until = req.until.unwrap_or_else(Utc::now).naive_utc();
+        let since = req.since.map(|since| since.naive_utc()).unwrap_or(until - Duration::hours(DEFAULT_RECENT_HOURS));
+        self.hold_service.find_recent(req.branch_id.as_str(), since, until, req.page.as_deref(),
+                                      req.page_size.unwrap_or(DEFAULT_PAGE_SIZE))
+            .await.map_err(CommandError::from).map(|res| FindRecentHoldsCommandResponse::new(res.records, res.next_page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::books::domain::model::BookEntity;
+    use crate::books::factory::create_book_repository;
+    use crate::core::command::{Command, CommandError};
+    use crate::core::domain::Configuration;
+    use crate::core::ids::{BookId, PatronId};
+    use crate::core::library::{BookStatus, PartyKind};
+    use crate::hold::command::find_recent_holds_cmd::{FindRecentHoldsCommand, FindRecentHoldsCommandRequest};
+    use crate::hold::factory::{create_hold_query_service, create_hold_service};
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_run_find_recent_holds() {
+        let store = test_store(module_path!(), "test_should_run_find_recent_holds");
+        let config = Configuration::new("recent_holds_branch");
+        let svc = create_hold_service(&config, store).await;
+        let sut_cmd = FindRecentHoldsCommand::new(create_hold_query_service(&config, store).await);
+        let patron = PartyEntity::new(PartyKind::Patron, "recent_holds@example.com");
+        let _ = create_party_repository(store).await.create(&patron).await.expect("should create patron");
+        let book = BookEntity::new("isbn", "recent title", BookStatus::Available);
+        let _ = create_book_repository(store).await.create(&book).await.expect("should create book");
+        let hold = svc.hold(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str()), None)
+            .await.expect("should hold");
+        assert!(hold.hold_id.as_str().starts_with("recent_holds_branch_"));
+
+        let res = sut_cmd.execute(FindRecentHoldsCommandRequest::new("recent_holds_branch")).await.expect("should find holds");
+        assert_eq!(vec![hold.hold_id.clone()], res.holds.iter().map(|h| h.hold_id.clone()).collect::<Vec<_>>());
+
+        // holds of earlier times are out of range
+        let mut req = FindRecentHoldsCommandRequest::new("recent_holds_branch");
+        req.until = Some(Utc::now() - Duration::hours(1));
+        let res = sut_cmd.execute(req).await.expect("should find holds");
+        assert!(res.holds.is_empty());
+
+        let mut req = FindRecentHoldsCommandRequest::new("recent_holds_branch");
+        req.since = Some(Utc::now() + Duration::hours(1));
+        let res = sut_cmd.execute(req).await;
+        assert!(matches!(res, Err(CommandError::Validation { .. })));
+    }
+}
@@ -0,0 +1,124 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::audit::dto::StaffOverrideDto;
+use crate::core::command::{Command, CommandError};
+use crate::core::ids::{BookId, PatronId};
+use crate::core::library::{BatchResult, BatchStatus};
+use crate::hold::domain::HoldService;
+use crate::hold::dto::HoldDto;
+
+pub(crate) struct HoldBooksCommand {
+    hold_service: Box<dyn HoldService>,
+}
+
+impl HoldBooksCommand {
+    pub(crate) fn new(hold_service: Box<dyn HoldService>) -> Self {
+        Self {
+            hold_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct HoldBooksCommandRequest {
+    patron_id: PatronId,
+    book_ids: Vec<BookId>,
+    // branch where the books will be picked up, defaults to the branch of the holds
+    #[serde(default)]
+    pickup_branch_id: Option<String>,
+    // librarians can supply an override to bypass policy rejections of all books
+    #[serde(default)]
+    staff_override: Option<StaffOverrideDto>,
+}
+
+impl HoldBooksCommandRequest {
+    pub fn new(patron_id: PatronId, book_ids: Vec<BookId>) -> Self {
+        Self {
+            patron_id,
+            book_ids,
+            pickup_branch_id: None,
+            staff_override: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct HoldBooksCommandResponse {
+    #[serde(flatten)]
+    result: BatchResult<HoldDto>,
+}
+
+impl HoldBooksCommandResponse {
+    pub fn new(result: BatchResult<HoldDto>) -> Self {
+        Self {
+            result,
+        }
+    }
+
+    pub(crate) fn status(&self) -> BatchStatus {
+        self.result.status
+    }
+}
+
+#[async_trait]
+impl Command<HoldBooksCommandRequest, HoldBooksCommandResponse> for HoldBooksCommand {
+    async fn execute(&self, req: HoldBooksCommandRequest) -> Result<HoldBooksCommandResponse, CommandError> {
+        self.hold_service.hold_all(&req.patron_id, &req.book_ids, req.pickup_branch_id.as_deref(),
+                                   req.staff_override.as_ref())
+            .await.map_err(CommandError::from).map(HoldBooksCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::books::dto::BookDto;
+    use crate::catalog::command::add_book_cmd::{AddBookCommand, AddBookCommandRequest};
+    use crate::catalog::factory::create_catalog_service;
+    use crate::core::command::Command;
+    use crate::core::ids::BookId;
+    use crate::core::library::{BatchStatus, BookStatus};
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::hold::command::hold_books_cmd::{HoldBooksCommand, HoldBooksCommandRequest};
+    use crate::hold::factory::create_hold_service;
+    use crate::patrons::command::add_patron_cmd::{AddPatronCommand, AddPatronCommandRequest};
+    use crate::patrons::factory::create_patron_service;
+    use crate::utils::testing::test_store;
+
+    async fn build_book_cmd(store: RepositoryStore) -> AddBookCommand {
+        let svc = create_catalog_service(&Configuration::new("test"), store).await;
+        AddBookCommand::new(svc)
+    }
+
+    async fn build_patron_cmd(store: RepositoryStore) -> AddPatronCommand {
+        let svc = create_patron_service(&Configuration::new("test"), store).await;
+        AddPatronCommand::new(svc)
+    }
+
+    async fn build_hold_cmd(store: RepositoryStore) -> HoldBooksCommand {
+        let svc = create_hold_service(&Configuration::new("test"), store).await;
+        HoldBooksCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_hold_books() {
+        let store = test_store(module_path!(), "test_should_run_hold_books");
+        let patron_cmd = build_patron_cmd(store).await;
+        let book_cmd = build_book_cmd(store).await;
+        let hold_cmd = build_hold_cmd(store).await;
+
+        let patron = patron_cmd.execute(AddPatronCommandRequest::new("email")).await.expect("should add patron").patron;
+        let mut book_ids = vec![];
+        for isbn in ["isbn1", "isbn2"] {
+            let book = BookDto::new(isbn, "test book", BookStatus::Available);
+            let res = book_cmd.execute(AddBookCommandRequest::new(book.isbn.as_str(), book.title.as_str()))
+                .await.expect("should add book");
+            book_ids.push(BookId::from(res.book.book_id));
+        }
+        let res = hold_cmd.execute(HoldBooksCommandRequest::new(
+            patron.patron_id.as_str().into(), book_ids.clone())).await.expect("should hold books");
+        assert_eq!(BatchStatus::Succeeded, res.status());
+        assert_eq!(book_ids, res.result.successes.iter().map(|h| h.book_id.clone()).collect::<Vec<BookId>>());
+    }
+}
@@ -0,0 +1,105 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::core::ids::HoldId;
+use crate::hold::domain::HoldService;
+use crate::hold::dto::HoldStatusDto;
+
+pub(crate) struct HoldStatusCommand {
+    hold_service: Box<dyn HoldService>,
+}
+
+impl HoldStatusCommand {
+    pub(crate) fn new(hold_service: Box<dyn HoldService>) -> Self {
+        Self {
+            hold_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct HoldStatusCommandRequest {
+    pub hold_id: HoldId,
+    // patron holding the book or a librarian
+    requested_by: String,
+}
+
+impl HoldStatusCommandRequest {
+    pub fn new(hold_id: &HoldId, requested_by: &str) -> Self {
+        Self {
+            hold_id: hold_id.clone(),
+            requested_by: requested_by.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct HoldStatusCommandResponse {
+    pub status: HoldStatusDto,
+}
+
+impl HoldStatusCommandResponse {
+    pub fn new(status: HoldStatusDto) -> Self {
+        Self {
+            status,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<HoldStatusCommandRequest, HoldStatusCommandResponse> for HoldStatusCommand {
+    async fn execute(&self, req: HoldStatusCommandRequest) -> Result<HoldStatusCommandResponse, CommandError> {
+        self.hold_service.hold_status(&req.hold_id, req.requested_by.as_str())
+            .await.map_err(CommandError::from).map(HoldStatusCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::books::domain::model::BookEntity;
+    use crate::books::factory::create_book_repository;
+    use crate::core::command::{Command, CommandError};
+    use crate::core::domain::Configuration;
+    use crate::core::ids::{BookId, PatronId};
+    use crate::core::library::{BookStatus, HoldStatus, PartyKind};
+    use crate::core::repository::RepositoryStore;
+    use crate::hold::command::hold_status_cmd::{HoldStatusCommand, HoldStatusCommandRequest};
+    use crate::hold::domain::HoldService;
+    use crate::hold::factory::create_hold_service;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn HoldService> {
+        create_hold_service(&Configuration::new("test"), store).await
+    }
+
+    #[tokio::test]
+    async fn test_should_run_hold_status() {
+        let store = test_store(module_path!(), "test_should_run_hold_status");
+        let svc = build_svc(store).await;
+        let sut_cmd = HoldStatusCommand::new(build_svc(store).await);
+        let party_repo = create_party_repository(store).await;
+        let first = PartyEntity::new(PartyKind::Patron, "status_cmd_first@example.com");
+        let second = PartyEntity::new(PartyKind::Patron, "status_cmd_second@example.com");
+        let _ = party_repo.create(&first).await.expect("should create patron");
+        let _ = party_repo.create(&second).await.expect("should create patron");
+        let book = BookEntity::new("isbn", "status title", BookStatus::Available);
+        let _ = create_book_repository(store).await.create(&book).await.expect("should create book");
+        let book_id = BookId::new(book.book_id.as_str());
+        let _ = svc.hold(&PatronId::new(first.party_id.as_str()), &book_id, None).await.expect("should hold");
+        let hold = svc.hold(&PatronId::new(second.party_id.as_str()), &book_id, None).await.expect("should hold");
+
+        let res = sut_cmd.execute(HoldStatusCommandRequest::new(&hold.hold_id, second.party_id.as_str()))
+            .await.expect("should return status");
+        assert_eq!(HoldStatus::Waiting, res.status.hold_status);
+        assert_eq!(2, res.status.position);
+        assert_eq!(2, res.status.queue_length);
+        assert!(res.status.estimated_available_at.is_some());
+
+        // other patrons cannot see the hold
+        let res = sut_cmd.execute(HoldStatusCommandRequest::new(&hold.hold_id, first.party_id.as_str())).await;
+        assert!(matches!(res, Err(CommandError::Access { .. })));
+    }
+}
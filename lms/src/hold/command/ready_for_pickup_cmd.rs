@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::core::ids::HoldId;
+use crate::hold::domain::HoldService;
+use crate::hold::dto::HoldDto;
+
+pub(crate) struct ReadyForPickupCommand {
+    hold_service: Box<dyn HoldService>,
+}
+
+impl ReadyForPickupCommand {
+    pub(crate) fn new(hold_service: Box<dyn HoldService>) -> Self {
+        Self {
+            hold_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ReadyForPickupCommandRequest {
+    #[serde(default)]
+    pub hold_id: HoldId,
+}
+
+impl ReadyForPickupCommandRequest {
+    pub fn new(hold_id: &HoldId) -> Self {
+        Self {
+            hold_id: hold_id.clone(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ReadyForPickupCommandResponse {
+    pub hold: HoldDto,
+}
+
+impl ReadyForPickupCommandResponse {
+    pub fn new(hold: HoldDto) -> Self {
+        Self {
+            hold,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<ReadyForPickupCommandRequest, ReadyForPickupCommandResponse> for ReadyForPickupCommand {
+    async fn execute(&self, req: ReadyForPickupCommandRequest) -> Result<ReadyForPickupCommandResponse, CommandError> {
+        self.hold_service.ready_for_pickup(&req.hold_id)
+            .await.map_err(CommandError::from).map(ReadyForPickupCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::books::domain::model::BookEntity;
+    use crate::books::factory::create_book_repository;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::ids::{BookId, PatronId};
+    use crate::core::library::{BookStatus, HoldStatus, PartyKind};
+    use crate::core::repository::RepositoryStore;
+    use crate::hold::command::ready_for_pickup_cmd::{ReadyForPickupCommand, ReadyForPickupCommandRequest};
+    use crate::hold::domain::HoldService;
+    use crate::hold::factory::create_hold_service;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn HoldService> {
+        create_hold_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> ReadyForPickupCommand {
+        let svc = create_hold_service(&Configuration::new("test"), store).await;
+        ReadyForPickupCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_ready_for_pickup() {
+        let store = test_store(module_path!(), "test_should_run_ready_for_pickup");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let patron = PartyEntity::new(PartyKind::Patron, "pickup_cmd@example.com");
+        let _ = create_party_repository(store).await.create(&patron).await.expect("should create patron");
+        let book = BookEntity::new("isbn", "pickup title", BookStatus::Available);
+        let _ = create_book_repository(store).await.create(&book).await.expect("should create book");
+        let hold = svc.hold(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str()), Some("main")).await.expect("should hold");
+
+        let res = sut_cmd.execute(ReadyForPickupCommandRequest::new(&hold.hold_id)).await.expect("should be ready");
+        assert_eq!(HoldStatus::ReadyForPickup, res.hold.hold_status);
+        assert_eq!("main", res.hold.pickup_branch_id.as_str());
+        assert!(res.hold.pickup_by.is_some());
+        assert!(sut_cmd.execute(ReadyForPickupCommandRequest::new(&hold.hold_id)).await.is_err());
+    }
+}
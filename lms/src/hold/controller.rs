@@ -1,20 +1,38 @@
 use axum::{
-    extract::State,
+    http::StatusCode,
+    extract::{Extension, Path, Query, State},
+    middleware,
     response::Json,
+    routing::{get, post},
+    Router,
 };
 use serde_json::{Value};
-use crate::core::command::Command;
-use crate::core::controller::{AppState, json_to_server_error, ServerError};
+use crate::checkout::command::fulfill_hold_cmd::{FulfillHoldCommand, FulfillHoldCommandRequest, FulfillHoldCommandResponse};
+use crate::checkout::factory::create_checkout_service;
+use crate::core::controller::{AppState, batch_status_code, body_limit, command_bus, json_to_server_error, with_common_layers, ServerError};
+use crate::core::ids::HoldId;
+use crate::credentials::controller::authenticate;
+use crate::credentials::dto::ClaimsDto;
+use crate::gateway::command::find_events_cmd::FindEventsCommandResponse;
+use crate::gateway::controller::find_entity_events;
 use crate::hold::command::cancel_hold_book_cmd::{CancelHoldBookCommand, CancelHoldBookCommandRequest, CancelHoldBookCommandResponse};
 use crate::hold::command::checkout_hold_book_cmd::{CheckoutHoldBookCommand, CheckoutHoldBookCommandRequest, CheckoutHoldBookCommandResponse};
+use crate::hold::command::expire_pickups_cmd::{ExpirePickupsCommand, ExpirePickupsCommandRequest, ExpirePickupsCommandResponse};
+use crate::hold::command::extend_hold_cmd::{ExtendHoldCommand, ExtendHoldCommandRequest, ExtendHoldCommandResponse};
+use crate::hold::command::find_recent_holds_cmd::{FindRecentHoldsCommand, FindRecentHoldsCommandRequest, FindRecentHoldsCommandResponse};
 use crate::hold::command::hold_book_cmd::{HoldBookCommand, HoldBookCommandRequest, HoldBookCommandResponse};
+use crate::hold::command::hold_books_cmd::{HoldBooksCommand, HoldBooksCommandRequest, HoldBooksCommandResponse};
+use crate::hold::command::hold_status_cmd::{HoldStatusCommand, HoldStatusCommandRequest, HoldStatusCommandResponse};
+use crate::hold::command::ready_for_pickup_cmd::{ReadyForPickupCommand, ReadyForPickupCommandRequest, ReadyForPickupCommandResponse};
 use crate::hold::domain::HoldService;
 use crate::hold::factory;
-use crate::utils::ddb::{build_db_client, create_table};
+use crate::hold::repository::ddb_hold_repository::ACTIVE_HOLDS_TABLE;
+use crate::utils::ddb::{build_db_client, create_key_table, create_table};
 
 async fn build_service(state: AppState) -> Box<dyn HoldService> {
     let client = build_db_client(state.store).await;
-    let _ = create_table(&client, "hold", "hold_id", "hold_status", "patron_id").await;
+    let _ = create_table(&client, state.store.table_name("hold").as_str(), "hold_id", "hold_status", "patron_id").await;
+    let _ = create_key_table(&client, state.store.table_name(ACTIVE_HOLDS_TABLE).as_str(), "patron_book").await;
     factory::create_hold_service(&state.config, state.store).await
 }
 
@@ -23,16 +41,26 @@ pub(crate) async fn hold_book(
     json: Json<Value>) -> Result<Json<HoldBookCommandResponse>, ServerError> {
     let req: HoldBookCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
     let svc = build_service(state).await;
-    let res = HoldBookCommand::new(svc).execute(req).await?;
+    let res = command_bus().register(HoldBookCommand::new(svc)).dispatch(req).await?;
     Ok(Json(res))
 }
 
+// patrons hold several books at once, the response reports the outcome of each book
+pub(crate) async fn hold_books(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<(StatusCode, Json<HoldBooksCommandResponse>), ServerError> {
+    let req: HoldBooksCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res: HoldBooksCommandResponse = command_bus().register(HoldBooksCommand::new(svc)).dispatch(req).await?;
+    Ok((batch_status_code(res.status()), Json(res)))
+}
+
 pub(crate) async fn checkout_hold(
     State(state): State<AppState>,
     json: Json<Value>) -> Result<Json<CheckoutHoldBookCommandResponse>, ServerError> {
     let req: CheckoutHoldBookCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
     let svc = build_service(state).await;
-    let res = CheckoutHoldBookCommand::new(svc).execute(req).await?;
+    let res = command_bus().register(CheckoutHoldBookCommand::new(svc)).dispatch(req).await?;
     Ok(Json(res))
 }
 
@@ -41,6 +69,96 @@ pub(crate) async fn cancel_hold(
     json: Json<Value>) -> Result<Json<CancelHoldBookCommandResponse>, ServerError> {
     let req: CancelHoldBookCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
     let svc = build_service(state).await;
-    let res = CancelHoldBookCommand::new(svc).execute(req).await?;
+    let res = command_bus().register(CancelHoldBookCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn ready_for_pickup(
+    State(state): State<AppState>,
+    Path(hold_id): Path<HoldId>) -> Result<Json<ReadyForPickupCommandResponse>, ServerError> {
+    let req = ReadyForPickupCommandRequest { hold_id };
+    let svc = build_service(state).await;
+    let res = command_bus().register(ReadyForPickupCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// the desk of the pickup branch converts the hold that is ready into a checkout of its patron
+pub(crate) async fn fulfill_hold(
+    State(state): State<AppState>,
+    Path(hold_id): Path<HoldId>) -> Result<Json<FulfillHoldCommandResponse>, ServerError> {
+    let req = FulfillHoldCommandRequest::new(hold_id.as_str());
+    let svc = create_checkout_service(&state.config, state.store).await;
+    let res = command_bus().register(FulfillHoldCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn extend_hold(
+    State(state): State<AppState>,
+    Path(hold_id): Path<HoldId>,
+    json: Json<Value>) -> Result<Json<ExtendHoldCommandResponse>, ServerError> {
+    let mut req: ExtendHoldCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.hold_id = hold_id;
+    let svc = build_service(state).await;
+    let res = command_bus().register(ExtendHoldCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// patrons see the position of their hold in the queue and when the book is expected to be available
+pub(crate) async fn hold_status(
+    State(state): State<AppState>,
+    Extension(claims): Extension<ClaimsDto>,
+    Path(hold_id): Path<HoldId>) -> Result<Json<HoldStatusCommandResponse>, ServerError> {
+    let req = HoldStatusCommandRequest::new(&hold_id, claims.sub.as_str());
+    let svc = build_service(state).await;
+    let res = command_bus().register(HoldStatusCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// holds placed at a branch, most recent first, e.g. for the activity feed of the circulation desk
+pub(crate) async fn find_recent_holds(
+    State(state): State<AppState>,
+    Query(mut req): Query<FindRecentHoldsCommandRequest>) -> Result<Json<FindRecentHoldsCommandResponse>, ServerError> {
+    if req.branch_id.is_empty() {
+        req.branch_id = state.config.branch_id.to_string();
+    }
+    let svc = factory::create_hold_query_service(&state.config, state.store).await;
+    let res = command_bus().register(FindRecentHoldsCommand::new(svc)).dispatch(req).await?;
     Ok(Json(res))
 }
+
+// invoked periodically (e.g. by a scheduled rule) to cancel holds that were not picked up by the deadline
+pub(crate) async fn expire_pickups(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<Json<ExpirePickupsCommandResponse>, ServerError> {
+    let req: ExpirePickupsCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res = command_bus().register(ExpirePickupsCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// holds are published in a group per transition
+const HOLD_EVENT_GROUPS: [&str; 3] = ["book_hold", "book_hold_cancel", "book_hold_checkout"];
+
+// events of the hold from placing it to its checkout, cancellation or expiry
+pub(crate) async fn find_hold_events(
+    State(state): State<AppState>,
+    Path(id): Path<String>) -> Result<Json<FindEventsCommandResponse>, ServerError> {
+    find_entity_events(state, &HOLD_EVENT_GROUPS, id.as_str()).await
+}
+
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/hold/:id/events", get(find_hold_events))
+        .route("/hold/:id/status", get(hold_status))
+        .route("/hold/recent", get(find_recent_holds))
+        .route_layer(middleware::from_fn_with_state(state.clone(), authenticate))
+        .route("/hold", post(hold_book))
+        .route("/hold/batch", post(hold_books).layer(body_limit(state.config.max_bulk_body_bytes)))
+        .route("/hold/checkout", post(checkout_hold))
+        .route("/hold/cancel", post(cancel_hold))
+        .route("/hold/pickups/expire", post(expire_pickups))
+        .route("/hold/:id/ready", post(ready_for_pickup))
+        .route("/hold/:id/fulfill", post(fulfill_hold))
+        .route("/hold/:id/extend", post(extend_hold));
+    with_common_layers(router, state)
+}
@@ -1,17 +1,66 @@
 use async_trait::async_trait;
 use std::collections::HashMap;
-use crate::core::library::{LibraryResult, PaginatedResult};
-use crate::hold::dto::HoldDto;
+use chrono::NaiveDateTime;
+use crate::audit::dto::StaffOverrideDto;
+use crate::core::ids::{BookId, HoldId, PatronId};
+use crate::core::invariants::InvariantViolation;
+use crate::core::library::{BatchResult, HoldStatus, LibraryResult, PaginatedResult};
+use crate::hold::dto::{HoldDto, HoldStatusDto};
 
+pub mod estimate;
 pub mod model;
+pub mod policy;
+pub mod query;
 pub mod service;
 
+// read side of holds
 #[async_trait]
-pub(crate) trait HoldService: Sync + Send {
-    async fn hold(&self, patron_id: &str, book_id: &str) -> LibraryResult<HoldDto>;
-    async fn cancel(&self, patron_id: &str, book_id: &str) -> LibraryResult<HoldDto>;
-    async fn checkout(&self, patron_id: &str, book_id: &str) -> LibraryResult<HoldDto>;
+pub trait HoldQueryService: Sync + Send {
+    // returns the oldest hold that is waiting for the returned copy of the book
+    async fn find_next_hold(&self, book_id: &BookId) -> LibraryResult<Option<HoldDto>>;
+    async fn find_hold(&self, hold_id: &HoldId) -> LibraryResult<HoldDto>;
+    // returns active holds of the book in the order they are served, holds ready for pickup come first
+    async fn find_queue(&self, book_id: &BookId) -> LibraryResult<Vec<HoldDto>>;
+    // returns holds of the patron with the given status
+    async fn find_by_patron(&self, patron_id: &PatronId, status: HoldStatus) -> LibraryResult<Vec<HoldDto>>;
     async fn query_expired(&self, predicate: &HashMap<String, String>,
                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<HoldDto>>;
+    // returns holds placed at the branch within the time range, most recent first, holds placed before their ids were
+    // scoped by branch are not returned
+    async fn find_recent(&self, branch_id: &str, since: NaiveDateTime, until: NaiveDateTime,
+                         page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<HoldDto>>;
+}
+
+#[async_trait]
+pub trait HoldService: HoldQueryService {
+    // holds are queued as waiting when other patrons already hold the book
+    async fn hold(&self, patron_id: &PatronId, book_id: &BookId, pickup_branch_id: Option<&str>) -> LibraryResult<HoldDto>;
+    // librarians can override max holds, restricted books and suspended accounts, overrides are recorded in the audit log
+    async fn hold_with_override(&self, patron_id: &PatronId, book_id: &BookId, pickup_branch_id: Option<&str>,
+                                staff_override: &StaffOverrideDto) -> LibraryResult<HoldDto>;
+    // holds a stack of books for the patron, holds of the books passing the policies are saved in a single transaction
+    // and the outcome of each book is reported separately
+    async fn hold_all(&self, patron_id: &PatronId, book_ids: &[BookId], pickup_branch_id: Option<&str>,
+                      staff_override: Option<&StaffOverrideDto>) -> LibraryResult<BatchResult<HoldDto>>;
+    async fn cancel(&self, patron_id: &PatronId, book_id: &BookId) -> LibraryResult<HoldDto>;
+    async fn checkout(&self, patron_id: &PatronId, book_id: &BookId) -> LibraryResult<HoldDto>;
+    // marks the hold that is ready at the pickup branch of the service as checked out by the given checkout, it is
+    // invoked by the checkout service after the checkout of the hold is saved
+    async fn fulfill(&self, hold_id: &HoldId, checkout_id: &str) -> LibraryResult<HoldDto>;
+    // marks the hold as ready at the pickup branch and notifies the patron about the pickup deadline
+    async fn ready_for_pickup(&self, hold_id: &HoldId) -> LibraryResult<HoldDto>;
+    // pushes out the expiry of the hold, patrons can extend their own holds a limited number of times while nobody
+    // else is waiting for the book and librarians can extend any hold
+    async fn extend(&self, hold_id: &HoldId, requested_by: &str) -> LibraryResult<HoldDto>;
+    // position of the hold in the queue of the book and the estimated date the book becomes available to the patron,
+    // patrons can only see their own holds and librarians can see any hold
+    async fn hold_status(&self, hold_id: &HoldId, requested_by: &str) -> LibraryResult<HoldStatusDto>;
+    // cancels holds that were not picked up by the deadline and promotes next patrons in the queue
+    async fn expire_pickups(&self) -> LibraryResult<Vec<HoldDto>>;
+    // moves active holds of a duplicate catalog record to the record it is merged into, moved holds queue behind
+    // holds of that record and holds of patrons who already hold it are canceled, dry run only counts the holds
+    async fn reassign_book(&self, from: &BookId, to: &BookId, dry_run: bool) -> LibraryResult<usize>;
+    // scans all holds for broken invariants
+    async fn find_invariant_violations(&self, page_size: usize) -> LibraryResult<Vec<InvariantViolation>>;
 }
 
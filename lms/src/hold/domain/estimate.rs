@@ -0,0 +1,56 @@
+use std::cmp;
+use chrono::{Duration, NaiveDateTime};
+
+// AvailabilityEstimator estimates when a held book becomes available to a patron in its queue, every patron ahead
+// is expected to keep the copy for the whole loan period and overdue copies are expected back right away
+pub(crate) struct AvailabilityEstimator {
+    loan_days: i64,
+}
+
+impl AvailabilityEstimator {
+    pub(crate) fn new(loan_days: i64) -> Self {
+        Self {
+            loan_days,
+        }
+    }
+
+    // ahead is the number of holds that are served first, due dates are those of the checked out copies and copies
+    // that are not checked out are available now
+    pub(crate) fn estimate(&self, ahead: usize, due_dates: &[NaiveDateTime], copies: usize,
+                           now: NaiveDateTime) -> NaiveDateTime {
+        let mut available: Vec<NaiveDateTime> = due_dates.iter().map(|due_at| cmp::max(*due_at, now)).collect();
+        while available.len() < copies.max(1) {
+            available.push(now);
+        }
+        // the copy that comes back first goes to the next patron in the queue
+        for _ in 0..ahead {
+            if let Some(first) = available.iter_mut().min() {
+                *first += Duration::days(self.loan_days);
+            }
+        }
+        available.into_iter().min().unwrap_or(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::hold::domain::estimate::AvailabilityEstimator;
+
+    #[tokio::test]
+    async fn test_should_estimate_availability_from_due_dates_and_queue() {
+        let estimator = AvailabilityEstimator::new(14);
+        let now = Utc::now().naive_utc();
+        let due_at = now + Duration::days(5);
+        // first in the queue gets the copy when it is returned
+        assert_eq!(due_at, estimator.estimate(0, &[due_at], 1, now));
+        // every patron ahead keeps the copy for a loan period
+        assert_eq!(due_at + Duration::days(28), estimator.estimate(2, &[due_at], 1, now));
+        // overdue copies are expected back now
+        assert_eq!(now, estimator.estimate(0, &[now - Duration::days(3)], 1, now));
+        // licenses that are not in use are available now and the queue is spread over the licenses
+        assert_eq!(now, estimator.estimate(0, &[due_at], 2, now));
+        assert_eq!(due_at, estimator.estimate(1, &[due_at], 2, now));
+        assert_eq!(now + Duration::days(14), estimator.estimate(2, &[due_at], 2, now));
+    }
+}
@@ -2,24 +2,32 @@ use chrono::{Duration, NaiveDateTime, Utc};
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use crate::core::domain::Identifiable;
+use crate::core::ids::{BookId, HoldId, PatronId};
+use crate::core::invariants::Invariants;
 use crate::core::library::HoldStatus;
 use crate::utils::date::serializer;
 
 // HoldEntity abstracts the book that is on hold or waiting for on-hold
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub(crate) struct HoldEntity {
-    pub hold_id: String,
+    pub hold_id: HoldId,
     pub version: i64,
     pub branch_id: String,
-    pub book_id: String,
-    pub patron_id: String,
+    pub book_id: BookId,
+    pub patron_id: PatronId,
     pub hold_status: HoldStatus,
+    pub pickup_branch_id: String,
     #[serde(with = "serializer")]
     pub hold_at: NaiveDateTime,
     #[serde(with = "serializer")]
     pub expires_at: NaiveDateTime,
     pub canceled_at: Option<NaiveDateTime>,
     pub checked_out_at: Option<NaiveDateTime>,
+    // deadline for picking up the hold once it is ready
+    pub pickup_by: Option<NaiveDateTime>,
+    // number of times the expiry of the hold was extended
+    #[serde(default)]
+    pub extensions: i64,
     #[serde(with = "serializer")]
     pub created_at: NaiveDateTime,
     #[serde(with = "serializer")]
@@ -27,18 +35,22 @@ pub(crate) struct HoldEntity {
 }
 
 impl HoldEntity{
-    pub fn new(book_id: &str, patron_id: &str) -> Self {
+    pub fn new(book_id: &BookId, patron_id: &PatronId) -> Self {
+        let branch_id = Uuid::new_v4().to_string();
         Self {
-            hold_id: Uuid::new_v4().to_string(),
+            hold_id: HoldId::generate_in(branch_id.as_str()),
             version: 0,
-            branch_id: Uuid::new_v4().to_string(),
-            book_id: book_id.to_string(),
-            patron_id: patron_id.to_string(),
+            branch_id: branch_id.to_string(),
+            book_id: book_id.clone(),
+            patron_id: patron_id.clone(),
             hold_status: HoldStatus::OnHold,
+            pickup_branch_id: branch_id,
             hold_at: Utc::now().naive_utc(),
             expires_at: Utc::now().naive_utc() + Duration::days(15),
             canceled_at: None,
             checked_out_at: None,
+            pickup_by: None,
+            extensions: 0,
             created_at: Utc::now().naive_utc(),
             updated_at: Utc::now().naive_utc(),
         }
@@ -55,17 +67,57 @@ impl Identifiable for HoldEntity {
     }
 }
 
+impl Invariants for HoldEntity {
+    fn aggregate(&self) -> &'static str {
+        "hold"
+    }
+
+    fn aggregate_id(&self) -> String {
+        self.hold_id.to_string()
+    }
+
+    fn violated_rules(&self) -> Vec<&'static str> {
+        let mut rules = vec![];
+        let canceled = self.canceled_at.is_some() || self.hold_status == HoldStatus::Canceled;
+        let checked_out = self.checked_out_at.is_some() || self.hold_status == HoldStatus::CheckedOut;
+        if canceled && checked_out {
+            rules.push("hold cannot be both canceled and checked out");
+        }
+        if self.expires_at < self.hold_at {
+            rules.push("hold cannot expire before it was placed");
+        }
+        if self.extensions < 0 {
+            rules.push("extensions of hold cannot be negative");
+        }
+        rules
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
+    use chrono::Utc;
+    use crate::core::ids::{BookId, PatronId};
+    use crate::core::invariants::Invariants;
     use crate::core::library::HoldStatus;
     use crate::hold::domain::model::HoldEntity;
 
     #[tokio::test]
     async fn test_should_build_hold() {
-        let hold = HoldEntity::new("book1", "patron1");
+        let hold = HoldEntity::new(&BookId::new("book1"), &PatronId::new("patron1"));
         assert_eq!("book1", hold.book_id.as_str());
         assert_eq!("patron1", hold.patron_id.as_str());
         assert_eq!(HoldStatus::OnHold, hold.hold_status);
     }
+
+    #[tokio::test]
+    async fn test_should_check_hold_invariants() {
+        let mut hold = HoldEntity::new(&BookId::new("book1"), &PatronId::new("patron1"));
+        assert!(hold.violated_rules().is_empty());
+        hold.hold_status = HoldStatus::CheckedOut;
+        hold.checked_out_at = Some(Utc::now().naive_utc());
+        assert!(hold.violated_rules().is_empty());
+        hold.canceled_at = Some(Utc::now().naive_utc());
+        assert_eq!(vec!["hold cannot be both canceled and checked out"], hold.violated_rules());
+    }
 }
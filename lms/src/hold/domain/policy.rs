@@ -0,0 +1,94 @@
+use crate::core::domain::{Configuration, Identifiable};
+use crate::core::library::{LibraryError, LibraryResult, OverrideRule};
+use crate::patrons::dto::PatronDto;
+
+// HoldLimitPolicy determines how many active holds a patron can have. Patrons who repeatedly let holds expire without
+// picking them up get the reduced limit while their no-show count is at the threshold, the count is projected from
+// hold events and holds they pick up bring it back down so the reduction is lifted again
+#[derive(Debug, Clone)]
+pub(crate) struct HoldLimitPolicy {
+    max_holds: i64,
+    no_show_threshold: i64,
+    no_show_max_holds: i64,
+}
+
+impl HoldLimitPolicy {
+    pub(crate) fn new(config: &Configuration) -> Self {
+        Self {
+            max_holds: config.max_holds,
+            no_show_threshold: config.no_show_threshold,
+            no_show_max_holds: config.no_show_max_holds,
+        }
+    }
+
+    pub(crate) fn is_reduced(&self, patron: &PatronDto) -> bool {
+        self.no_show_threshold > 0 && patron.num_no_shows >= self.no_show_threshold
+    }
+
+    pub(crate) fn max_holds(&self, patron: &PatronDto) -> i64 {
+        if self.is_reduced(patron) {
+            self.no_show_max_holds.min(self.max_holds)
+        } else {
+            self.max_holds
+        }
+    }
+
+    // rule that librarians override to accept holds beyond the limit of the patron
+    pub(crate) fn override_rule(&self, patron: &PatronDto) -> OverrideRule {
+        if self.is_reduced(patron) {
+            OverrideRule::NoShows
+        } else {
+            OverrideRule::MaxHolds
+        }
+    }
+
+    // checks that the patron who already has the given number of active holds can place another one
+    pub(crate) fn check(&self, patron: &PatronDto, held: usize) -> LibraryResult<()> {
+        let max_holds = self.max_holds(patron);
+        if (held as i64) < max_holds {
+            return Ok(());
+        }
+        let message = if !self.is_reduced(patron) {
+            format!("patron {} already has {} holds", patron.id(), held)
+        } else if max_holds > 0 {
+            format!("patron {} let {} holds expire without pickup and is limited to {} holds",
+                    patron.id(), patron.num_no_shows, max_holds)
+        } else {
+            format!("patron {} let {} holds expire without pickup and needs librarian approval to hold books",
+                    patron.id(), patron.num_no_shows)
+        };
+        Err(LibraryError::not_granted(message.as_str(), Some("403".to_string())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::domain::Configuration;
+    use crate::core::library::{LibraryError, OverrideRule};
+    use crate::hold::domain::policy::HoldLimitPolicy;
+    use crate::patrons::dto::PatronDto;
+
+    #[tokio::test]
+    async fn test_should_reduce_max_holds_for_no_shows() {
+        let mut config = Configuration::new("test");
+        let policy = HoldLimitPolicy::new(&config);
+        let mut patron = PatronDto::new("no_shows@example.com");
+        assert_eq!(config.max_holds, policy.max_holds(&patron));
+        assert!(policy.check(&patron, 1).is_ok());
+        assert_eq!(OverrideRule::MaxHolds, policy.override_rule(&patron));
+
+        patron.num_no_shows = config.no_show_threshold;
+        assert_eq!(config.no_show_max_holds, policy.max_holds(&patron));
+        assert!(policy.check(&patron, 0).is_ok());
+        assert!(matches!(policy.check(&patron, 1), Err(LibraryError::NotGranted { .. })));
+        assert_eq!(OverrideRule::NoShows, policy.override_rule(&patron));
+
+        // every hold needs approval when the reduced limit is zero
+        config.no_show_max_holds = 0;
+        assert!(HoldLimitPolicy::new(&config).check(&patron, 0).is_err());
+
+        // the reduction is disabled without threshold
+        config.no_show_threshold = 0;
+        assert!(HoldLimitPolicy::new(&config).check(&patron, 1).is_ok());
+    }
+}
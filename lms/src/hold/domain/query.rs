@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use crate::core::ids::{BookId, HoldId, PatronId};
+use crate::core::library::{HoldStatus, LibraryError, LibraryResult, PaginatedResult};
+use crate::hold::domain::HoldQueryService;
+use crate::hold::dto::HoldDto;
+use crate::hold::repository::HoldRepository;
+
+pub(crate) struct HoldQueryServiceImpl {
+    hold_repository: Box<dyn HoldRepository>,
+}
+
+impl HoldQueryServiceImpl {
+    pub(crate) fn new(hold_repository: Box<dyn HoldRepository>) -> Self {
+        Self {
+            hold_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl HoldQueryService for HoldQueryServiceImpl {
+    async fn find_next_hold(&self, book_id: &BookId) -> LibraryResult<Option<HoldDto>> {
+        for status in [HoldStatus::OnHold, HoldStatus::Waiting] {
+            let holds = self.hold_repository.find_by_book(book_id, status).await?;
+            if let Some(next) = holds.iter().min_by(|a, b| a.hold_at.cmp(&b.hold_at)) {
+                return Ok(Some(HoldDto::from(next)));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn find_hold(&self, hold_id: &HoldId) -> LibraryResult<HoldDto> {
+        self.hold_repository.get(hold_id.as_str()).await.map(|hold| HoldDto::from(&hold))
+    }
+
+    async fn find_queue(&self, book_id: &BookId) -> LibraryResult<Vec<HoldDto>> {
+        let mut queue = vec![];
+        for status in [HoldStatus::ReadyForPickup, HoldStatus::OnHold, HoldStatus::Waiting] {
+            let mut holds = self.hold_repository.find_by_book(book_id, status).await?;
+            holds.sort_by(|a, b| a.hold_at.cmp(&b.hold_at));
+            queue.extend(holds.iter().map(HoldDto::from));
+        }
+        Ok(queue)
+    }
+
+    async fn find_by_patron(&self, patron_id: &PatronId, status: HoldStatus) -> LibraryResult<Vec<HoldDto>> {
+        let predicate = HashMap::from([
+            ("patron_id".to_string(), patron_id.to_string()),
+            ("hold_status".to_string(), status.to_string()),
+        ]);
+        let mut holds = vec![];
+        let mut page: Option<String> = None;
+        loop {
+            let res = self.hold_repository.query(&predicate, page.as_deref(), 100).await?;
+            holds.extend(res.records.iter().map(HoldDto::from));
+            page = res.next_page;
+            if page.is_none() {
+                break;
+            }
+        }
+        Ok(holds)
+    }
+
+    async fn query_expired(&self, predicate: &HashMap<String, String>,
+                           page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<HoldDto>> {
+        let res = self.hold_repository.query_expired(predicate, page, page_size).await?;
+        let records = res.records.iter().map(HoldDto::from).collect();
+        Ok(PaginatedResult::new(page, page_size, res.next_page, records))
+    }
+
+    async fn find_recent(&self, branch_id: &str, since: NaiveDateTime, until: NaiveDateTime,
+                         page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<HoldDto>> {
+        if since > until {
+            return Err(LibraryError::validation("recent holds must start before they end", None));
+        }
+        let res = self.hold_repository.find_recent(branch_id, since, until, page, page_size).await?;
+        let records = res.records.iter().map(HoldDto::from).collect();
+        Ok(PaginatedResult::new(page, page_size, res.next_page, records))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::domain::Configuration;
+    use crate::core::ids::BookId;
+    use crate::core::repository::RepositoryStore;
+    use crate::hold::domain::HoldQueryService;
+    use crate::hold::factory;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn HoldQueryService> {
+        factory::create_hold_query_service(&Configuration::new("test"), store).await
+    }
+
+    #[tokio::test]
+    async fn test_should_not_find_next_hold_without_holds() {
+        let store = test_store(module_path!(), "test_should_not_find_next_hold_without_holds");
+        let query_svc = sut_svc(store).await;
+        let next = query_svc.find_next_hold(&BookId::new("book_without_holds")).await.expect("should find next hold");
+        assert!(next.is_none());
+    }
+}
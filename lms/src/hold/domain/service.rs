@@ -1,96 +1,344 @@
+use std::cmp;
 use std::collections::HashMap;
 
 use async_trait::async_trait;
-use chrono::{Duration, Utc};
-use uuid::Uuid;
+use chrono::{Duration, NaiveDateTime, Utc};
 
+use crate::audit::domain::AuditService;
+use crate::audit::dto::StaffOverrideDto;
 use crate::books::domain::Book;
 use crate::catalog::domain::CatalogService;
+use crate::checkout::domain::CheckoutQueryService;
 use crate::core::domain::{Configuration, Identifiable};
 use crate::core::events::DomainEvent;
-use crate::core::library::{BookStatus, HoldStatus, LibraryError, LibraryResult, PaginatedResult};
+use crate::core::ids::{BookId, HoldId, PatronId};
+use crate::core::invariants::{find_violations, InvariantViolation};
+use crate::core::library::{validate_batch, BatchResult, BookStatus, HoldStatus, LibraryError, LibraryResult, OverrideRule, PaginatedResult, PushTopic};
 use crate::gateway::events::EventPublisher;
-use crate::hold::domain::HoldService;
+use crate::hold::domain::{HoldQueryService, HoldService};
+use crate::hold::domain::estimate::AvailabilityEstimator;
 use crate::hold::domain::model::HoldEntity;
-use crate::hold::dto::HoldDto;
+use crate::hold::domain::policy::HoldLimitPolicy;
+use crate::hold::dto::{HoldDto, HoldStatusDto};
 use crate::hold::repository::HoldRepository;
+use crate::notifications::domain::NotificationService;
 use crate::patrons::domain::PatronService;
+use crate::patrons::dto::PatronDto;
 use crate::patrons::Patron;
+use crate::reserves::domain::ReserveService;
 
 pub(crate) struct HoldServiceImpl {
     branch_id: String,
+    max_holds: i64,
+    hold_limit_policy: HoldLimitPolicy,
+    hold_pickup_days: i64,
+    max_hold_extensions: i64,
+    hold_extension_days: i64,
+    book_loan_days: i64,
+    digital_loan_days: i64,
     hold_repository: Box<dyn HoldRepository>,
     patron_service: Box<dyn PatronService>,
     catalog_service: Box<dyn CatalogService>,
+    reserve_service: Box<dyn ReserveService>,
+    audit_service: Box<dyn AuditService>,
+    notification_service: Box<dyn NotificationService>,
     events_publisher: Box<dyn EventPublisher>,
+    query_service: Box<dyn HoldQueryService>,
+    checkout_query_service: Box<dyn CheckoutQueryService>,
 }
 
 impl HoldServiceImpl {
     pub(crate) fn new(config: &Configuration, hold_repository: Box<dyn HoldRepository>,
                       patron_service: Box<dyn PatronService>, catalog_service: Box<dyn CatalogService>,
-                      events_publisher: Box<dyn EventPublisher>) -> Self {
+                      reserve_service: Box<dyn ReserveService>, audit_service: Box<dyn AuditService>,
+                      notification_service: Box<dyn NotificationService>,
+                      events_publisher: Box<dyn EventPublisher>,
+                      query_service: Box<dyn HoldQueryService>,
+                      checkout_query_service: Box<dyn CheckoutQueryService>) -> Self {
         Self {
             branch_id: config.branch_id.to_string(),
+            max_holds: config.max_holds,
+            hold_limit_policy: HoldLimitPolicy::new(config),
+            hold_pickup_days: config.hold_pickup_days,
+            max_hold_extensions: config.max_hold_extensions,
+            hold_extension_days: config.hold_extension_days,
+            book_loan_days: config.book_loan_days,
+            digital_loan_days: config.digital_loan_days,
             hold_repository,
             patron_service,
             catalog_service,
+            reserve_service,
+            audit_service,
+            notification_service,
             events_publisher,
+            query_service,
+            checkout_query_service,
         }
     }
+
+    // returns first hold of the patron for the book matching statuses in the given order
+    async fn find_patron_hold(&self, patron_id: &PatronId, book_id: &BookId,
+                              statuses: &[HoldStatus]) -> LibraryResult<Option<HoldEntity>> {
+        for status in statuses {
+            let res = self.hold_repository.query(
+                &HashMap::from([("hold_status".to_string(), status.to_string()),
+                    ("patron_id".to_string(), patron_id.to_string()),
+                    ("book_id".to_string(), book_id.to_string())]), None, 10).await?;
+            if let Some(first) = res.records.into_iter().next() {
+                return Ok(Some(first));
+            }
+        }
+        Ok(None)
+    }
+
+    // returns holds of the book with the given status in the order they were placed
+    async fn find_book_holds(&self, book_id: &BookId, status: HoldStatus) -> LibraryResult<Vec<HoldEntity>> {
+        let mut holds = self.hold_repository.find_by_book(book_id, status).await?;
+        holds.sort_by(|a, b| a.hold_at.cmp(&b.hold_at));
+        Ok(holds)
+    }
+
+    async fn mark_ready(&self, hold: &mut HoldEntity) -> LibraryResult<HoldDto> {
+        hold.hold_status = HoldStatus::ReadyForPickup;
+        hold.pickup_by = Some(Utc::now().naive_utc() + Duration::days(self.hold_pickup_days));
+        self.hold_repository.update(hold).await?;
+        let dto = HoldDto::from(&*hold);
+        let _ = self.notification_service.notify_push(
+            PushTopic::HoldReady, dto.patron_id.as_str(), "Hold ready for pickup",
+            format!("Book {} is ready for pickup at branch {} until {}", dto.book_id, dto.pickup_branch_id,
+                    hold.pickup_by.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default()).as_str()).await?;
+        let _ = self.events_publisher.publish(&DomainEvent::updated(
+            "book_hold_ready", "book_hold", dto.hold_id.as_str(), &HashMap::new(), &dto.clone())?).await?;
+        Ok(dto)
+    }
+
+    // promotes the next waiting patron once the current hold of the book is released
+    async fn promote_next(&self, book_id: &BookId, ready: bool) -> LibraryResult<Option<HoldDto>> {
+        if let Some(mut next) = self.find_book_holds(book_id, HoldStatus::Waiting).await?.into_iter().next() {
+            if ready {
+                return Ok(Some(self.mark_ready(&mut next).await?));
+            }
+            next.hold_status = HoldStatus::OnHold;
+            self.hold_repository.update(&next).await?;
+            return Ok(Some(HoldDto::from(&next)));
+        }
+        Ok(None)
+    }
+
+    // staff override allows librarians to bypass max holds, restricted books and suspended accounts
+    async fn place_hold(&self, patron_id: &PatronId, book_id: &BookId, pickup_branch_id: Option<&str>,
+                        staff_override: Option<&StaffOverrideDto>) -> LibraryResult<HoldDto> {
+        if let Some(staff_override) = staff_override {
+            self.audit_service.authorize_override(staff_override).await?;
+        }
+        let mut overridden = vec![];
+        let patron = self.find_patron(patron_id, staff_override, &mut overridden).await?;
+        let hold = self.prepare_hold(&patron, book_id, pickup_branch_id, staff_override, &mut overridden).await?;
+        let held = self.count_holds(&patron).await?;
+        self.check_max_holds(&patron, held, staff_override, &mut overridden)?;
+        self.hold_repository.create(&hold).await?;
+        let hold = HoldDto::from(&hold);
+        self.record_overrides(staff_override, &overridden, &hold).await?;
+        let _ = self.events_publisher.publish(&DomainEvent::added(
+            "book_hold", "book_hold", hold.hold_id.as_str(), &HashMap::new(), &hold.clone())?).await?;
+        Ok(hold)
+    }
+
+    // suspended patrons can only hold with a staff override
+    async fn find_patron(&self, patron_id: &PatronId, staff_override: Option<&StaffOverrideDto>,
+                         overridden: &mut Vec<OverrideRule>) -> LibraryResult<PatronDto> {
+        match self.patron_service.find_patron_in_good_standing(patron_id).await {
+            Ok(patron) => Ok(patron),
+            Err(LibraryError::NotGranted { .. }) if staff_override.is_some() => {
+                overridden.push(OverrideRule::SuspendedAccount);
+                self.patron_service.find_patron_by_id(patron_id).await
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    // validates the book against the policies and returns the hold that is not saved yet, patrons are queued
+    // behind existing holds of the book
+    async fn prepare_hold(&self, patron: &PatronDto, book_id: &BookId, pickup_branch_id: Option<&str>,
+                          staff_override: Option<&StaffOverrideDto>,
+                          overridden: &mut Vec<OverrideRule>) -> LibraryResult<HoldEntity> {
+        // the active hold lookup of the repository rejects concurrent duplicates, this check reports them early
+        if self.find_patron_hold(&PatronId::from(patron.id()), book_id,
+                                 &[HoldStatus::OnHold, HoldStatus::Waiting, HoldStatus::ReadyForPickup]).await?.is_some() {
+            return Err(LibraryError::duplicate_key(format!("patron {} already has an active hold of book {}",
+                                                           patron.id(), book_id).as_str()));
+        }
+        let book = self.catalog_service.find_book_by_id(book_id).await?;
+        if book.status() != BookStatus::Available {
+            return Err(LibraryError::validation(format!("book is not available {}",
+                                                        book.id()).as_str(), Some("400".to_string())));
+        }
+        if book.is_restricted() && patron.is_regular() {
+            if staff_override.is_none() {
+                return Err(LibraryError::validation(format!("patron {} cannot hold restricted books {}",
+                                                            patron.id(), book.id()).as_str(), Some("400".to_string())));
+            }
+            overridden.push(OverrideRule::RestrictedBook);
+        }
+        if let Some(reserve) = self.reserve_service.find_rules_for_book(book_id.as_str()).await? {
+            if !reserve.holds_allowed {
+                return Err(LibraryError::validation(format!("book {} on reserve list {} cannot be held",
+                                                            book.id(), reserve.list_name).as_str(), Some("400".to_string())));
+            }
+        }
+        let mut hold = from_patron_book(self.branch_id.as_str(),
+                                        pickup_branch_id.unwrap_or(self.branch_id.as_str()), patron, &book);
+        if !self.find_book_holds(book_id, HoldStatus::OnHold).await?.is_empty() ||
+            !self.find_book_holds(book_id, HoldStatus::ReadyForPickup).await?.is_empty() {
+            hold.hold_status = HoldStatus::Waiting;
+        }
+        Ok(hold)
+    }
+
+    async fn count_holds(&self, patron: &PatronDto) -> LibraryResult<usize> {
+        let active = self.hold_repository.query(
+            &HashMap::from([("patron_id".to_string(), patron.id())]), None, self.max_holds as usize).await?;
+        Ok(active.records.len())
+    }
+
+    fn check_max_holds(&self, patron: &PatronDto, held: usize, staff_override: Option<&StaffOverrideDto>,
+                       overridden: &mut Vec<OverrideRule>) -> LibraryResult<()> {
+        if let Err(err) = self.hold_limit_policy.check(patron, held) {
+            if staff_override.is_none() {
+                return Err(err);
+            }
+            overridden.push(self.hold_limit_policy.override_rule(patron));
+        }
+        Ok(())
+    }
+
+    async fn record_overrides(&self, staff_override: Option<&StaffOverrideDto>, overridden: &[OverrideRule],
+                              hold: &HoldDto) -> LibraryResult<()> {
+        if let Some(staff_override) = staff_override {
+            for rule in overridden {
+                let _ = self.audit_service.record_override(staff_override, *rule, hold.patron_id.as_str(),
+                                                           hold.hold_id.as_str()).await?;
+            }
+        }
+        Ok(())
+    }
 }
 
-pub(crate) fn from_patron_book(branch_id: &str, patron: &dyn Patron, book: &dyn Book) -> HoldEntity {
+pub(crate) fn from_patron_book(branch_id: &str, pickup_branch_id: &str, patron: &dyn Patron, book: &dyn Book) -> HoldEntity {
     HoldEntity {
-        hold_id: Uuid::new_v4().to_string(),
+        hold_id: HoldId::generate_in(branch_id),
         version: 0,
         branch_id: branch_id.to_string(),
-        book_id: book.id(),
-        patron_id: patron.id(),
+        book_id: BookId::from(book.id()),
+        patron_id: PatronId::from(patron.id()),
         hold_status: HoldStatus::OnHold,
+        pickup_branch_id: pickup_branch_id.to_string(),
         hold_at: Utc::now().naive_utc(),
         expires_at: Utc::now().naive_utc() + Duration::days(15),
         canceled_at: None,
         checked_out_at: None,
+        pickup_by: None,
+        extensions: 0,
         created_at: Utc::now().naive_utc(),
         updated_at: Utc::now().naive_utc(),
     }
 }
 
+// holds are fulfilled at the desk of their pickup branch once they are ready for pickup
+pub(crate) fn check_pickup(hold: &HoldDto, branch_id: &str) -> LibraryResult<()> {
+    if hold.hold_status != HoldStatus::ReadyForPickup {
+        return Err(LibraryError::validation(format!("hold {} with status {} is not ready for pickup",
+                                                    hold.hold_id, hold.hold_status).as_str(), Some("400".to_string())));
+    }
+    if hold.pickup_branch_id != branch_id {
+        return Err(LibraryError::validation(format!("hold {} is picked up at branch {} rather than {}",
+                                                    hold.hold_id, hold.pickup_branch_id, branch_id).as_str(), Some("400".to_string())));
+    }
+    Ok(())
+}
+
 #[async_trait]
 impl HoldService for HoldServiceImpl {
-    async fn hold(&self, patron_id: &str, book_id: &str) -> LibraryResult<HoldDto> {
-        let patron = self.patron_service.find_patron_by_id(patron_id).await?;
-        let book = self.catalog_service.find_book_by_id(book_id).await?;
-        if book.status() != BookStatus::Available {
-            return Err(LibraryError::validation(format!("book is not available {}",
-                                                        book.id()).as_str(), Some("400".to_string())));
+    async fn hold(&self, patron_id: &PatronId, book_id: &BookId, pickup_branch_id: Option<&str>) -> LibraryResult<HoldDto> {
+        self.place_hold(patron_id, book_id, pickup_branch_id, None).await
+    }
+
+    async fn hold_with_override(&self, patron_id: &PatronId, book_id: &BookId, pickup_branch_id: Option<&str>,
+                                staff_override: &StaffOverrideDto) -> LibraryResult<HoldDto> {
+        self.place_hold(patron_id, book_id, pickup_branch_id, Some(staff_override)).await
+    }
+
+    async fn hold_all(&self, patron_id: &PatronId, book_ids: &[BookId], pickup_branch_id: Option<&str>,
+                      staff_override: Option<&StaffOverrideDto>) -> LibraryResult<BatchResult<HoldDto>> {
+        validate_batch(book_ids.len())?;
+        if let Some(staff_override) = staff_override {
+            self.audit_service.authorize_override(staff_override).await?;
         }
-        if book.is_restricted() && patron.is_regular() {
-            return Err(LibraryError::validation(format!("patron {} cannot hold restricted books {}",
-                                                        patron.id(), book.id()).as_str(), Some("400".to_string())));
+        let mut patron_overridden = vec![];
+        let patron = self.find_patron(patron_id, staff_override, &mut patron_overridden).await?;
+        // holds accepted earlier in the batch count towards the max holds of the patron
+        let mut held = self.count_holds(&patron).await?;
+        let mut res = BatchResult::new();
+        let mut prepared = vec![];
+        for (i, book_id) in book_ids.iter().enumerate() {
+            if book_ids[..i].contains(book_id) {
+                res.failed(book_id.as_str(), &LibraryError::validation(
+                    format!("book {} is repeated in the batch", book_id).as_str(), Some("400".to_string())));
+                continue;
+            }
+            let mut overridden = patron_overridden.clone();
+            let hold = match self.prepare_hold(&patron, book_id, pickup_branch_id, staff_override, &mut overridden).await {
+                Ok(hold) => hold,
+                Err(err) => {
+                    res.failed(book_id.as_str(), &err);
+                    continue;
+                }
+            };
+            match self.check_max_holds(&patron, held, staff_override, &mut overridden) {
+                Ok(_) => {
+                    held += 1;
+                    prepared.push((hold, overridden));
+                }
+                Err(err) => res.failed(book_id.as_str(), &err),
+            }
         }
-        let hold = from_patron_book(self.branch_id.as_str(), &patron, &book);
-        self.hold_repository.create(&hold).await?;
-        let hold = HoldDto::from(&hold);
-        let _ = self.events_publisher.publish(&DomainEvent::added(
-            "book_hold", "book_hold", hold.hold_id.as_str(), &HashMap::new(), &hold.clone())?).await?;
-        Ok(hold)
+        if !prepared.is_empty() {
+            let entities = prepared.iter().map(|(hold, _)| hold.clone()).collect::<Vec<HoldEntity>>();
+            if let Err(err) = self.hold_repository.create_all(&entities).await {
+                for (hold, _) in prepared {
+                    res.failed(hold.book_id.as_str(), &err);
+                }
+            } else {
+                let mut events = vec![];
+                for (hold, overridden) in prepared {
+                    let hold = HoldDto::from(&hold);
+                    self.record_overrides(staff_override, &overridden, &hold).await?;
+                    events.push(DomainEvent::added(
+                        "book_hold", "book_hold", hold.hold_id.as_str(), &HashMap::new(), &hold.clone())?);
+                    res.succeeded(hold);
+                }
+                self.events_publisher.publish_all(&events).await?;
+            }
+        }
+        Ok(res)
     }
 
-    async fn cancel(&self, patron_id: &str, book_id: &str) -> LibraryResult<HoldDto> {
+    async fn cancel(&self, patron_id: &PatronId, book_id: &BookId) -> LibraryResult<HoldDto> {
         let patron = self.patron_service.find_patron_by_id(patron_id).await?;
         let book = self.catalog_service.find_book_by_id(book_id).await?;
-        let mut res = self.hold_repository.query(
-            &HashMap::from([("patron_id".to_string(), patron.id().to_string()),
-                ("book_id".to_string(), book.id().to_string())]), None, 10).await?;
-        let mut iter = res.records.iter_mut();
-        if let Some(first) = iter.next() {
+        if let Some(mut first) = self.find_patron_hold(
+            &PatronId::from(patron.id()), &BookId::from(book.id()),
+            &[HoldStatus::ReadyForPickup, HoldStatus::OnHold, HoldStatus::Waiting]).await? {
+            let released = first.hold_status;
             first.hold_status = HoldStatus::Canceled;
             first.canceled_at = Some(Utc::now().naive_utc());
-            self.hold_repository.update(first).await?;
-            let hold = HoldDto::from(&first.clone());
+            self.hold_repository.update(&first).await?;
+            let hold = HoldDto::from(&first);
             let _ = self.events_publisher.publish(&DomainEvent::deleted(
                 "book_hold_cancel", "book_hold_cancel", hold.hold_id.as_str(), &HashMap::new(), &hold.clone())?).await?;
+            if released != HoldStatus::Waiting {
+                let _ = self.promote_next(&BookId::from(book.id()), released == HoldStatus::ReadyForPickup).await?;
+            }
             Ok(hold)
         } else {
             Err(LibraryError::not_found(format!("book with id {} for patron {} not found",
@@ -98,18 +346,15 @@ impl HoldService for HoldServiceImpl {
         }
     }
 
-    async fn checkout(&self, patron_id: &str, book_id: &str) -> LibraryResult<HoldDto> {
-        let patron = self.patron_service.find_patron_by_id(patron_id).await?;
+    async fn checkout(&self, patron_id: &PatronId, book_id: &BookId) -> LibraryResult<HoldDto> {
+        let patron = self.patron_service.find_patron_in_good_standing(patron_id).await?;
         let book = self.catalog_service.find_book_by_id(book_id).await?;
-        let mut res = self.hold_repository.query(
-            &HashMap::from([("patron_id".to_string(), patron.id().to_string()),
-                ("book_id".to_string(), book.id().to_string())]), None, 10).await?;
-        let mut iter = res.records.iter_mut();
-        if let Some(first) = iter.next() {
+        if let Some(mut first) = self.find_patron_hold(
+            &PatronId::from(patron.id()), &BookId::from(book.id()), &[HoldStatus::ReadyForPickup, HoldStatus::OnHold]).await? {
             first.hold_status = HoldStatus::CheckedOut;
             first.checked_out_at = Some(Utc::now().naive_utc());
-            self.hold_repository.update(first).await?;
-            let hold = HoldDto::from(&first.clone());
+            self.hold_repository.update(&first).await?;
+            let hold = HoldDto::from(&first);
             let _ = self.events_publisher.publish(&DomainEvent::deleted(
                 "book_hold_checkout", "book_hold_checkout", hold.hold_id.as_str(), &HashMap::new(), &hold.clone())?).await?;
             Ok(hold)
@@ -119,27 +364,205 @@ impl HoldService for HoldServiceImpl {
         }
     }
 
+    async fn fulfill(&self, hold_id: &HoldId, checkout_id: &str) -> LibraryResult<HoldDto> {
+        let mut hold = self.hold_repository.get(hold_id.as_str()).await?;
+        check_pickup(&HoldDto::from(&hold), self.branch_id.as_str())?;
+        hold.hold_status = HoldStatus::CheckedOut;
+        hold.checked_out_at = Some(Utc::now().naive_utc());
+        // the version check of the update rejects concurrent fulfillment of the same hold at another desk
+        self.hold_repository.update(&hold).await?;
+        let hold = HoldDto::from(&hold);
+        let _ = self.events_publisher.publish(&DomainEvent::deleted(
+            "book_hold_fulfilled", "book_hold_checkout", hold.hold_id.as_str(),
+            &HashMap::from([("checkout_id".to_string(), checkout_id.to_string())]), &hold.clone())?).await?;
+        Ok(hold)
+    }
+
+    async fn ready_for_pickup(&self, hold_id: &HoldId) -> LibraryResult<HoldDto> {
+        let mut hold = self.hold_repository.get(hold_id.as_str()).await?;
+        if hold.hold_status != HoldStatus::OnHold && hold.hold_status != HoldStatus::Waiting {
+            return Err(LibraryError::validation(format!("hold {} with status {} cannot be picked up",
+                                                        hold_id, hold.hold_status).as_str(), Some("400".to_string())));
+        }
+        self.mark_ready(&mut hold).await
+    }
+
+    async fn extend(&self, hold_id: &HoldId, requested_by: &str) -> LibraryResult<HoldDto> {
+        let mut hold = self.hold_repository.get(hold_id.as_str()).await?;
+        if hold.hold_status != HoldStatus::OnHold && hold.hold_status != HoldStatus::Waiting {
+            return Err(LibraryError::validation(format!("hold {} with status {} cannot be extended",
+                                                        hold_id, hold.hold_status).as_str(), Some("400".to_string())));
+        }
+        let requester = self.patron_service.find_patron_by_id(&PatronId::new(requested_by)).await?;
+        if !requester.is_librarian() && !requester.is_admin() {
+            if requester.id() != hold.patron_id.as_str() {
+                return Err(LibraryError::not_granted(format!("patron {} cannot extend hold {}",
+                                                             requested_by, hold_id).as_str(), Some("403".to_string())));
+            }
+            if hold.extensions >= self.max_hold_extensions {
+                return Err(LibraryError::validation(format!("hold {} was already extended {} times",
+                                                            hold_id, hold.extensions).as_str(), Some("400".to_string())));
+            }
+            if self.find_book_holds(&hold.book_id, HoldStatus::Waiting).await?.iter().any(|h| h.hold_id != hold.hold_id) {
+                return Err(LibraryError::validation(format!("hold {} cannot be extended while other patrons are waiting for book {}",
+                                                            hold_id, hold.book_id).as_str(), Some("400".to_string())));
+            }
+        }
+        // extensions of expired holds start from now so that they are not expired again right away
+        hold.expires_at = cmp::max(hold.expires_at, Utc::now().naive_utc()) + Duration::days(self.hold_extension_days);
+        hold.extensions += 1;
+        self.hold_repository.update(&hold).await?;
+        let dto = HoldDto::from(&hold);
+        let _ = self.events_publisher.publish(&DomainEvent::updated(
+            "book_hold_extended", "book_hold", dto.hold_id.as_str(), &HashMap::new(), &dto.clone())?).await?;
+        Ok(dto)
+    }
+
+    async fn hold_status(&self, hold_id: &HoldId, requested_by: &str) -> LibraryResult<HoldStatusDto> {
+        let hold = HoldDto::from(&self.hold_repository.get(hold_id.as_str()).await?);
+        let requester = self.patron_service.find_patron_by_id(&PatronId::new(requested_by)).await?;
+        if !requester.is_librarian() && !requester.is_admin() && requester.id() != hold.patron_id.as_str() {
+            return Err(LibraryError::not_granted(format!("patron {} cannot see hold {}",
+                                                         requested_by, hold_id).as_str(), Some("403".to_string())));
+        }
+        let queue = self.query_service.find_queue(&hold.book_id).await?;
+        let mut status = HoldStatusDto::new(&hold, queue.len());
+        let Some(ahead) = queue.iter().position(|queued| queued.hold_id == hold.hold_id) else {
+            return Ok(status);
+        };
+        status.position = ahead + 1;
+        let now = Utc::now().naive_utc();
+        if hold.hold_status == HoldStatus::ReadyForPickup {
+            status.estimated_available_at = Some(now);
+            return Ok(status);
+        }
+        let book = self.catalog_service.find_book_by_id(&hold.book_id).await?;
+        let (copies, loan_days) = if book.book_format.is_digital() {
+            (book.license_count.max(1) as usize, self.digital_loan_days)
+        } else {
+            (1, self.book_loan_days)
+        };
+        let due_dates: Vec<_> = self.checkout_query_service.find_active_by_book(&hold.book_id).await?
+            .iter().map(|checkout| checkout.due_at).collect();
+        status.estimated_available_at = Some(AvailabilityEstimator::new(loan_days).estimate(ahead, &due_dates, copies, now));
+        Ok(status)
+    }
+
+    async fn expire_pickups(&self) -> LibraryResult<Vec<HoldDto>> {
+        let mut expired = vec![];
+        for mut hold in self.hold_repository.find_pickup_expired().await? {
+            hold.hold_status = HoldStatus::Canceled;
+            hold.canceled_at = Some(Utc::now().naive_utc());
+            self.hold_repository.update(&hold).await?;
+            let dto = HoldDto::from(&hold);
+            let _ = self.events_publisher.publish(&DomainEvent::deleted(
+                "book_hold_pickup_expired", "book_hold", dto.hold_id.as_str(), &HashMap::new(), &dto.clone())?).await?;
+            let _ = self.promote_next(&hold.book_id, true).await?;
+            expired.push(dto);
+        }
+        Ok(expired)
+    }
+
+    async fn reassign_book(&self, from: &BookId, to: &BookId, dry_run: bool) -> LibraryResult<usize> {
+        let statuses = [HoldStatus::ReadyForPickup, HoldStatus::OnHold, HoldStatus::Waiting];
+        let mut moved = vec![];
+        for status in statuses {
+            moved.extend(self.find_book_holds(from, status).await?);
+        }
+        if dry_run || moved.is_empty() {
+            return Ok(moved.len());
+        }
+        let mut kept = vec![];
+        for status in statuses {
+            kept.extend(self.find_book_holds(to, status).await?);
+        }
+        // only one hold of the copy can be on hold or ready, others wait in the order they were placed
+        let mut queued = kept.iter().any(|hold| hold.hold_status != HoldStatus::Waiting);
+        let count = moved.len();
+        for mut hold in moved {
+            if kept.iter().any(|other| other.patron_id == hold.patron_id) {
+                hold.hold_status = HoldStatus::Canceled;
+                hold.canceled_at = Some(Utc::now().naive_utc());
+                self.hold_repository.update(&hold).await?;
+                let dto = HoldDto::from(&hold);
+                let _ = self.events_publisher.publish(&DomainEvent::deleted(
+                    "book_hold_cancel", "book_hold_cancel", dto.hold_id.as_str(), &HashMap::new(), &dto.clone())?).await?;
+                continue;
+            }
+            if queued {
+                hold.hold_status = HoldStatus::Waiting;
+                hold.pickup_by = None;
+            }
+            queued = queued || hold.hold_status != HoldStatus::Waiting;
+            hold.book_id = to.clone();
+            self.hold_repository.reassign_book(&hold, from).await?;
+            let dto = HoldDto::from(&hold);
+            let _ = self.events_publisher.publish(&DomainEvent::updated(
+                "book_hold_reassigned", "book_hold", dto.hold_id.as_str(), &HashMap::new(), &dto.clone())?).await?;
+        }
+        Ok(count)
+    }
+
+    async fn find_invariant_violations(&self, page_size: usize) -> LibraryResult<Vec<InvariantViolation>> {
+        let mut violations = vec![];
+        let mut next_page: Option<String> = None;
+        loop {
+            let res = self.hold_repository.scan(next_page.as_deref(), page_size).await?;
+            violations.extend(find_violations(&res.records));
+            next_page = res.next_page;
+            if next_page.is_none() {
+                break;
+            }
+        }
+        Ok(violations)
+    }
+}
+
+#[async_trait]
+impl HoldQueryService for HoldServiceImpl {
+    async fn find_next_hold(&self, book_id: &BookId) -> LibraryResult<Option<HoldDto>> {
+        self.query_service.find_next_hold(book_id).await
+    }
+
+    async fn find_hold(&self, hold_id: &HoldId) -> LibraryResult<HoldDto> {
+        self.query_service.find_hold(hold_id).await
+    }
+
+    async fn find_queue(&self, book_id: &BookId) -> LibraryResult<Vec<HoldDto>> {
+        self.query_service.find_queue(book_id).await
+    }
+
     async fn query_expired(&self, predicate: &HashMap<String, String>,
                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<HoldDto>> {
-        let res = self.hold_repository.query_expired(predicate, page, page_size).await?;
-        let records = res.records.iter().map(HoldDto::from).collect();
-        Ok(PaginatedResult::new(page, page_size, res.next_page, records))
+        self.query_service.query_expired(predicate, page, page_size).await
+    }
+
+    async fn find_recent(&self, branch_id: &str, since: NaiveDateTime, until: NaiveDateTime,
+                         page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<HoldDto>> {
+        self.query_service.find_recent(branch_id, since, until, page, page_size).await
+    }
+
+    async fn find_by_patron(&self, patron_id: &PatronId, status: HoldStatus) -> LibraryResult<Vec<HoldDto>> {
+        self.query_service.find_by_patron(patron_id, status).await
     }
 }
 
 impl From<&HoldDto> for HoldEntity {
     fn from(other: &HoldDto) -> HoldEntity {
         HoldEntity {
-            hold_id: other.hold_id.to_string(),
+            hold_id: other.hold_id.clone(),
             version: other.version,
             branch_id: other.branch_id.to_string(),
-            book_id: other.book_id.to_string(),
-            patron_id: other.patron_id.to_string(),
+            book_id: other.book_id.clone(),
+            patron_id: other.patron_id.clone(),
             hold_status: other.hold_status,
+            pickup_branch_id: other.pickup_branch_id.to_string(),
             hold_at: other.hold_at,
             expires_at: other.expires_at,
             canceled_at: other.canceled_at,
             checked_out_at: other.checked_out_at,
+            pickup_by: other.pickup_by,
+            extensions: other.extensions,
             created_at: other.created_at,
             updated_at: other.updated_at,
         }
@@ -149,16 +572,19 @@ impl From<&HoldDto> for HoldEntity {
 impl From<&HoldEntity> for HoldDto {
     fn from(other: &HoldEntity) -> HoldDto {
         HoldDto {
-            hold_id: other.hold_id.to_string(),
+            hold_id: other.hold_id.clone(),
             version: other.version,
             branch_id: other.branch_id.to_string(),
-            book_id: other.book_id.to_string(),
-            patron_id: other.patron_id.to_string(),
+            book_id: other.book_id.clone(),
+            patron_id: other.patron_id.clone(),
             hold_status: other.hold_status,
+            pickup_branch_id: other.pickup_branch_id.to_string(),
             hold_at: other.hold_at,
             expires_at: other.expires_at,
             canceled_at: other.canceled_at,
             checked_out_at: other.checked_out_at,
+            pickup_by: other.pickup_by,
+            extensions: other.extensions,
             created_at: other.created_at,
             updated_at: other.updated_at,
         }
@@ -169,84 +595,343 @@ impl From<&HoldEntity> for HoldDto {
 mod tests {
     use std::collections::HashMap;
 
-    use async_once::AsyncOnce;
     use aws_sdk_dynamodb::Client;
-    use lazy_static::lazy_static;
+    use chrono::{Duration, Utc};
 
+    use crate::audit::dto::StaffOverrideDto;
+    use crate::audit::factory::create_audit_service;
     use crate::books::domain::model::BookEntity;
     use crate::books::factory::create_book_repository;
     use crate::books::repository::BookRepository;
     use crate::core::domain::Configuration;
-    use crate::core::library::{BookStatus, PartyKind};
+    use crate::core::ids::{BookId, PatronId};
+    use crate::core::library::{AccountStatus, BatchStatus, BookStatus, HoldStatus, LibraryError, OverrideRule, PartyKind, Role};
     use crate::core::repository::RepositoryStore;
     use crate::hold::domain::HoldService;
     use crate::hold::factory;
+    use crate::hold::factory::create_hold_repository;
+    use crate::notifications::factory::create_notification_service;
     use crate::parties::domain::model::PartyEntity;
     use crate::parties::factory::create_party_repository;
     use crate::parties::repository::PartyRepository;
-    use crate::utils::ddb::{build_db_client, create_table, delete_table};
-
-    lazy_static! {
-        static ref CLIENT: AsyncOnce<Client> = AsyncOnce::new(async {
-                build_db_client(RepositoryStore::LocalDynamoDB).await
-            });
-        static ref SUT_SVC: AsyncOnce<Box<dyn HoldService>> = AsyncOnce::new(async {
-                let _ = delete_table(&CLIENT.get().await.clone(), "hold").await;
-                let _ = create_table(&CLIENT.get().await.clone(), "hold", "hold_id", "hold_status", "patron_id").await;
-                factory::create_hold_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await
-            });
-        static ref BOOK_REPO: AsyncOnce<Box<dyn BookRepository>> = AsyncOnce::new(async {
-                let _ = delete_table(&CLIENT.get().await.clone(), "books").await;
-                let _ = create_table(&CLIENT.get().await.clone(), "books", "book_id", "book_status", "isbn").await;
-                create_book_repository(RepositoryStore::LocalDynamoDB).await
-            });
-        static ref PARTY_REPO: AsyncOnce<Box<dyn PartyRepository>> = AsyncOnce::new(async {
-                let _ = delete_table(&CLIENT.get().await.clone(), "parties").await;
-                let _ = create_table(&CLIENT.get().await.clone(), "parties", "party_id", "kind", "email").await;
-                create_party_repository(RepositoryStore::LocalDynamoDB).await
-            });
+    use crate::reserves::domain::model::{ReserveItemEntity, ReserveListEntity};
+    use crate::reserves::factory::{create_reserve_item_repository, create_reserve_list_repository};
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn client(store: RepositoryStore) -> Client {
+        build_db_client(store).await
+    }
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn HoldService> {
+        let _ = create_table(&client(store).await, store.table_name("hold").as_str(), "hold_id", "hold_status", "patron_id").await;
+        factory::create_hold_service(&Configuration::new("test"), store).await
+    }
+
+    async fn book_repo(store: RepositoryStore) -> Box<dyn BookRepository> {
+        let _ = create_table(&client(store).await, store.table_name("books").as_str(), "book_id", "book_status", "isbn").await;
+        create_book_repository(store).await
+    }
+
+    async fn party_repo(store: RepositoryStore) -> Box<dyn PartyRepository> {
+        let _ = create_table(&client(store).await, store.table_name("parties").as_str(), "party_id", "kind", "normalized_email").await;
+        create_party_repository(store).await
     }
 
     #[tokio::test]
     async fn test_should_hold_and_cancel() {
-        let hold_svc = SUT_SVC.get().await.clone();
+        let store = test_store(module_path!(), "test_should_hold_and_cancel");
+        let hold_svc = sut_svc(store).await;
 
         let patron = &PartyEntity::new(PartyKind::Patron, "email");
-        let _ = PARTY_REPO.get().await.create(&patron).await.expect("should get patron");
+        let _ = party_repo(store).await.create(&patron).await.expect("should get patron");
         let book = BookEntity::new("isbn", "title", BookStatus::Available);
-        let _ = BOOK_REPO.get().await.create(&book).await.expect("should get book");
-        let res = hold_svc.cancel(patron.party_id.as_str(), book.book_id.as_str()).await;
+        let _ = book_repo(store).await.create(&book).await.expect("should get book");
+        let res = hold_svc.cancel(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str())).await;
         assert!(res.is_err());
-        let hold = hold_svc.hold(patron.party_id.as_str(), book.book_id.as_str()).await.expect("should hold");
+        let hold = hold_svc.hold(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str()), None).await.expect("should hold");
         assert_eq!(patron.party_id, hold.patron_id);
         assert_eq!(book.book_id, hold.book_id);
-        let canceled = hold_svc.cancel(patron.party_id.as_str(), book.book_id.as_str()).await.expect("should canceled");
+        let canceled = hold_svc.cancel(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.expect("should canceled");
         assert_eq!(patron.party_id, canceled.patron_id);
         assert_eq!(book.book_id, canceled.book_id);
     }
 
+    #[tokio::test]
+    async fn test_should_not_hold_book_twice() {
+        let store = test_store(module_path!(), "test_should_not_hold_book_twice");
+        let hold_svc = sut_svc(store).await;
+
+        let patron = &PartyEntity::new(PartyKind::Patron, "hold_twice@example.com");
+        let _ = party_repo(store).await.create(&patron).await.expect("should create patron");
+        let book = BookEntity::new("isbn", "title", BookStatus::Available);
+        let _ = book_repo(store).await.create(&book).await.expect("should create book");
+        let (patron_id, book_id) = (PatronId::new(patron.party_id.as_str()), BookId::new(book.book_id.as_str()));
+        let _ = hold_svc.hold(&patron_id, &book_id, None).await.expect("should hold");
+        let res = hold_svc.hold(&patron_id, &book_id, None).await;
+        assert!(matches!(res, Err(LibraryError::DuplicateKey { .. })));
+        let res = hold_svc.hold_all(&patron_id, &[book_id.clone()], None, None).await.expect("should hold all");
+        assert_eq!(BatchStatus::Failed, res.status);
+
+        // the book can be held again after the hold is canceled
+        let _ = hold_svc.cancel(&patron_id, &book_id).await.expect("should cancel");
+        let _ = hold_svc.hold(&patron_id, &book_id, None).await.expect("should hold again");
+    }
+
+    #[tokio::test]
+    async fn test_should_extend_hold_within_limits() {
+        let store = test_store(module_path!(), "test_should_extend_hold_within_limits");
+        let hold_svc = sut_svc(store).await;
+
+        let patron = PartyEntity::new(PartyKind::Patron, "extend@example.com");
+        let other = PartyEntity::new(PartyKind::Patron, "extend_other@example.com");
+        let mut librarian = PartyEntity::new(PartyKind::Employee, "extend_librarian@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        for party in [&patron, &other, &librarian] {
+            let _ = party_repo(store).await.create(party).await.expect("should create party");
+        }
+        let mut book_ids = vec![];
+        for _ in 0..2 {
+            let book = BookEntity::new("isbn", "title", BookStatus::Available);
+            let _ = book_repo(store).await.create(&book).await.expect("should create book");
+            book_ids.push(BookId::new(book.book_id.as_str()));
+        }
+        let patron_id = PatronId::new(patron.party_id.as_str());
+        let hold = hold_svc.hold(&patron_id, &book_ids[0], None).await.expect("should hold");
+        let mut expires_at = hold.expires_at;
+        for _ in 0..Configuration::new("test").max_hold_extensions {
+            let extended = hold_svc.extend(&hold.hold_id, patron.party_id.as_str()).await.expect("should extend");
+            assert!(extended.expires_at > expires_at);
+            expires_at = extended.expires_at;
+        }
+        assert!(hold_svc.extend(&hold.hold_id, patron.party_id.as_str()).await.is_err());
+        let res = hold_svc.extend(&hold.hold_id, other.party_id.as_str()).await;
+        assert!(matches!(res, Err(LibraryError::NotGranted { .. })));
+        // librarians are not limited by the number of extensions
+        let extended = hold_svc.extend(&hold.hold_id, librarian.party_id.as_str()).await.expect("should extend");
+        assert_eq!(3, extended.extensions);
+
+        // patrons cannot extend holds while others are waiting for the book
+        let hold = hold_svc.hold(&patron_id, &book_ids[1], None).await.expect("should hold");
+        let waiting = hold_svc.hold(&PatronId::new(other.party_id.as_str()), &book_ids[1], None).await.expect("should hold");
+        assert_eq!(HoldStatus::Waiting, waiting.hold_status);
+        assert!(hold_svc.extend(&hold.hold_id, patron.party_id.as_str()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_hold_all_within_max_holds() {
+        let store = test_store(module_path!(), "test_should_hold_all_within_max_holds");
+        let hold_svc = sut_svc(store).await;
+
+        let patron = &PartyEntity::new(PartyKind::Patron, "email");
+        let _ = party_repo(store).await.create(&patron).await.expect("should get patron");
+        let mut book_ids = vec![BookId::new("missing")];
+        for i in 0..5 {
+            let book = BookEntity::new(format!("isbn{}", i).as_str(), "title", BookStatus::Available);
+            let _ = book_repo(store).await.create(&book).await.expect("should get book");
+            book_ids.push(BookId::new(book.book_id.as_str()));
+        }
+        let res = hold_svc.hold_all(&PatronId::new(patron.party_id.as_str()), &book_ids, None, None).await.expect("should hold all");
+        assert_eq!(BatchStatus::PartiallySucceeded, res.status);
+        // configuration of tests allows 4 holds so the last book of the batch is rejected
+        assert_eq!(4, res.successes.len());
+        assert_eq!(vec![book_ids[0].to_string(), book_ids[5].to_string()],
+                   res.failures.iter().map(|f| f.id.to_string()).collect::<Vec<String>>());
+        let held = &res.successes[0];
+        assert_eq!(patron.party_id, held.patron_id);
+        assert_eq!(book_ids[1], held.book_id);
+    }
+
     #[tokio::test]
     async fn test_should_hold_and_checked_out() {
-        let hold_svc = SUT_SVC.get().await.clone();
+        let store = test_store(module_path!(), "test_should_hold_and_checked_out");
+        let hold_svc = sut_svc(store).await;
 
         let patron = &PartyEntity::new(PartyKind::Patron, "email");
-        let _ = PARTY_REPO.get().await.create(&patron).await.expect("should get patron");
+        let _ = party_repo(store).await.create(&patron).await.expect("should get patron");
         let book = BookEntity::new("isbn", "title", BookStatus::Available);
-        let _ = BOOK_REPO.get().await.create(&book).await.expect("should get book");
-        let res = hold_svc.checkout(patron.party_id.as_str(), book.book_id.as_str()).await;
+        let _ = book_repo(store).await.create(&book).await.expect("should get book");
+        let res = hold_svc.checkout(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str())).await;
         assert!(res.is_err());
-        let hold = hold_svc.hold(patron.party_id.as_str(), book.book_id.as_str()).await.expect("should hold");
+        let hold = hold_svc.hold(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str()), None).await.expect("should hold");
         assert_eq!(patron.party_id, hold.patron_id);
         assert_eq!(book.book_id, hold.book_id);
-        let checked_out = hold_svc.checkout(patron.party_id.as_str(), book.book_id.as_str()).await.expect("should checked out");
+        let checked_out = hold_svc.checkout(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.expect("should checked out");
         assert_eq!(patron.party_id, checked_out.patron_id);
         assert_eq!(book.book_id, checked_out.book_id);
     }
 
+    #[tokio::test]
+    async fn test_should_not_hold_reserve_book() {
+        let store = test_store(module_path!(), "test_should_not_hold_reserve_book");
+        let hold_svc = sut_svc(store).await;
+
+        let patron = &PartyEntity::new(PartyKind::Patron, "email");
+        let _ = party_repo(store).await.create(&patron).await.expect("should get patron");
+        let book = BookEntity::new("isbn", "course text", BookStatus::Available);
+        let _ = book_repo(store).await.create(&book).await.expect("should get book");
+        let list = ReserveListEntity::new(format!("hold_{}", book.book_id).as_str(), 24);
+        let _ = create_reserve_list_repository(store).await.create(&list).await.expect("should create list");
+        let _ = create_reserve_item_repository(store).await
+            .create(&ReserveItemEntity::new(list.list_name.as_str(), book.book_id.as_str(), "librarian")).await.expect("should reserve book");
+
+        assert!(hold_svc.hold(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str()), None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_not_hold_for_banned_patron() {
+        let store = test_store(module_path!(), "test_should_not_hold_for_banned_patron");
+        let hold_svc = sut_svc(store).await;
+
+        let mut patron = PartyEntity::new(PartyKind::Patron, "banned_hold@example.com");
+        patron.account_status = AccountStatus::Banned;
+        let _ = party_repo(store).await.create(&patron).await.expect("should create patron");
+        let book = BookEntity::new("isbn", "title", BookStatus::Available);
+        let _ = book_repo(store).await.create(&book).await.expect("should create book");
+        assert!(hold_svc.hold(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str()), None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_override_max_holds() {
+        let store = test_store(module_path!(), "test_should_override_max_holds");
+        let hold_svc = sut_svc(store).await;
+
+        let patron = PartyEntity::new(PartyKind::Patron, "max_holds@example.com");
+        let mut librarian = PartyEntity::new(PartyKind::Employee, "max_holds_librarian@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        for party in [&patron, &librarian] {
+            let _ = party_repo(store).await.create(party).await.expect("should create party");
+        }
+        for _ in 0..Configuration::new("test").max_holds {
+            let book = BookEntity::new("isbn", "title", BookStatus::Available);
+            let _ = book_repo(store).await.create(&book).await.expect("should create book");
+            let _ = hold_svc.hold(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str()), None).await.expect("should hold");
+        }
+        let book = BookEntity::new("isbn", "one more title", BookStatus::Available);
+        let _ = book_repo(store).await.create(&book).await.expect("should create book");
+        assert!(hold_svc.hold(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str()), None).await.is_err());
+
+        let staff_override = StaffOverrideDto::new(librarian.party_id.as_str(), "visiting scholar");
+        let hold = hold_svc.hold_with_override(&PatronId::new(patron.party_id.as_str()), &BookId::new(book.book_id.as_str()), None, &staff_override)
+            .await.expect("should hold with override");
+        let now = Utc::now().naive_utc();
+        let res = create_audit_service(&Configuration::new("test"), store).await
+            .find_overrides(now - Duration::hours(1), now + Duration::hours(1), None, 500).await.expect("should find overrides");
+        let audit = res.records.iter().find(|a| a.subject_id == hold.hold_id).expect("should record override");
+        assert_eq!(OverrideRule::MaxHolds.to_string(), audit.details);
+        assert_eq!("visiting scholar", audit.reason.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_should_require_approval_for_holds_of_no_show_patrons() {
+        let store = test_store(module_path!(), "test_should_require_approval_for_holds_of_no_show_patrons");
+        let hold_svc = sut_svc(store).await;
+
+        let config = Configuration::new("test");
+        let mut patron = PartyEntity::new(PartyKind::Patron, "no_show_holds@example.com");
+        patron.num_no_shows = config.no_show_threshold;
+        let mut librarian = PartyEntity::new(PartyKind::Employee, "no_show_librarian@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        for party in [&patron, &librarian] {
+            let _ = party_repo(store).await.create(party).await.expect("should create party");
+        }
+        let mut books = vec![];
+        for _ in 0..config.no_show_max_holds + 1 {
+            let book = BookEntity::new("isbn", "no show title", BookStatus::Available);
+            let _ = book_repo(store).await.create(&book).await.expect("should create book");
+            books.push(BookId::new(book.book_id.as_str()));
+        }
+        let patron_id = PatronId::new(patron.party_id.as_str());
+        for book_id in &books[..config.no_show_max_holds as usize] {
+            let _ = hold_svc.hold(&patron_id, book_id, None).await.expect("should hold within reduced limit");
+        }
+        let last = books.last().expect("should have book");
+        let err = hold_svc.hold(&patron_id, last, None).await.expect_err("should exceed reduced limit");
+        assert!(matches!(err, LibraryError::NotGranted { .. }));
+
+        let staff_override = StaffOverrideDto::new(librarian.party_id.as_str(), "picked up at the desk before");
+        let hold = hold_svc.hold_with_override(&patron_id, last, None, &staff_override).await.expect("should hold with approval");
+        let now = Utc::now().naive_utc();
+        let res = create_audit_service(&config, store).await
+            .find_overrides(now - Duration::hours(1), now + Duration::hours(1), None, 500).await.expect("should find overrides");
+        let audit = res.records.iter().find(|a| a.subject_id == hold.hold_id).expect("should record override");
+        assert_eq!(OverrideRule::NoShows.to_string(), audit.details);
+    }
+
+    #[tokio::test]
+    async fn test_should_promote_waiting_hold_after_pickup_expires() {
+        let store = test_store(module_path!(), "test_should_promote_waiting_hold_after_pickup_expires");
+        let hold_svc = sut_svc(store).await;
+
+        let first = PartyEntity::new(PartyKind::Patron, "first_pickup@example.com");
+        let second = PartyEntity::new(PartyKind::Patron, "second_pickup@example.com");
+        for patron in [&first, &second] {
+            let _ = party_repo(store).await.create(patron).await.expect("should create patron");
+        }
+        let book = BookEntity::new("isbn", "popular title", BookStatus::Available);
+        let _ = book_repo(store).await.create(&book).await.expect("should create book");
+
+        let first_hold = hold_svc.hold(&PatronId::new(first.party_id.as_str()), &BookId::new(book.book_id.as_str()), Some("downtown")).await.expect("should hold");
+        assert_eq!(HoldStatus::OnHold, first_hold.hold_status);
+        assert_eq!("downtown", first_hold.pickup_branch_id.as_str());
+        let second_hold = hold_svc.hold(&PatronId::new(second.party_id.as_str()), &BookId::new(book.book_id.as_str()), None).await.expect("should hold");
+        assert_eq!(HoldStatus::Waiting, second_hold.hold_status);
+
+        let ready = hold_svc.ready_for_pickup(&first_hold.hold_id).await.expect("should be ready");
+        assert_eq!(HoldStatus::ReadyForPickup, ready.hold_status);
+        let notifications = create_notification_service(store).await
+            .find_notifications(first.party_id.as_str(), None, 10).await.expect("should find notifications");
+        assert_eq!(1, notifications.records.len());
+
+        // pickup deadline passed
+        let hold_repo = create_hold_repository(store).await;
+        let mut expired = hold_repo.get(first_hold.hold_id.as_str()).await.expect("should get hold");
+        expired.pickup_by = Some(Utc::now().naive_utc() - Duration::hours(1));
+        let _ = hold_repo.update(&expired).await.expect("should update hold");
+        let canceled = hold_svc.expire_pickups().await.expect("should expire pickups");
+        assert!(canceled.iter().any(|h| h.hold_id == first_hold.hold_id));
+
+        let promoted = hold_repo.get(second_hold.hold_id.as_str()).await.expect("should get hold");
+        assert_eq!(HoldStatus::ReadyForPickup, promoted.hold_status);
+        let _ = hold_svc.checkout(&PatronId::new(second.party_id.as_str()), &BookId::new(book.book_id.as_str())).await.expect("should checkout");
+    }
+
+    #[tokio::test]
+    async fn test_should_reassign_holds_of_merged_book() {
+        let store = test_store(module_path!(), "test_should_reassign_holds_of_merged_book");
+        let hold_svc = sut_svc(store).await;
+
+        let first = PartyEntity::new(PartyKind::Patron, "first_merge@example.com");
+        let second = PartyEntity::new(PartyKind::Patron, "second_merge@example.com");
+        for patron in [&first, &second] {
+            let _ = party_repo(store).await.create(patron).await.expect("should create patron");
+        }
+        let kept = BookEntity::new("isbn", "merged title", BookStatus::Available);
+        let duplicate = BookEntity::new("isbn", "merged title", BookStatus::Available);
+        for book in [&kept, &duplicate] {
+            let _ = book_repo(store).await.create(book).await.expect("should create book");
+        }
+        let (kept_id, duplicate_id) = (BookId::new(kept.book_id.as_str()), BookId::new(duplicate.book_id.as_str()));
+        let _ = hold_svc.hold(&PatronId::new(first.party_id.as_str()), &kept_id, None).await.expect("should hold");
+        let first_duplicate = hold_svc.hold(&PatronId::new(first.party_id.as_str()), &duplicate_id, None).await.expect("should hold");
+        let second_duplicate = hold_svc.hold(&PatronId::new(second.party_id.as_str()), &duplicate_id, None).await.expect("should hold");
+
+        assert_eq!(2, hold_svc.reassign_book(&duplicate_id, &kept_id, true).await.expect("should count holds"));
+        let hold_repo = create_hold_repository(store).await;
+        assert_eq!(duplicate_id, hold_repo.get(second_duplicate.hold_id.as_str()).await.expect("should get hold").book_id);
+
+        assert_eq!(2, hold_svc.reassign_book(&duplicate_id, &kept_id, false).await.expect("should reassign holds"));
+        let canceled = hold_repo.get(first_duplicate.hold_id.as_str()).await.expect("should get hold");
+        assert_eq!(HoldStatus::Canceled, canceled.hold_status);
+        let moved = hold_repo.get(second_duplicate.hold_id.as_str()).await.expect("should get hold");
+        assert_eq!(kept_id, moved.book_id);
+        assert_eq!(HoldStatus::Waiting, moved.hold_status);
+    }
 
     #[tokio::test]
     async fn test_should_query_expired() {
-        let hold_svc = SUT_SVC.get().await.clone();
+        let store = test_store(module_path!(), "test_should_query_expired");
+        let hold_svc = sut_svc(store).await;
 
         let res = hold_svc.query_expired(&HashMap::new(), None, 50).await.expect("should query");
         assert_eq!(0, res.records.len());
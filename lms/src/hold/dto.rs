@@ -1,57 +1,103 @@
 use chrono::{Duration, NaiveDateTime, Utc};
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use crate::core::ids::{BookId, HoldId, PatronId};
 use crate::core::library::HoldStatus;
-use crate::utils::date::serializer;
+use crate::utils::date::{serializer, Rfc3339};
 
 // HoldDto abstracts data transfer object for holding book request
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
-pub(crate) struct HoldDto {
-    pub hold_id: String,
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HoldDto {
+    pub hold_id: HoldId,
     pub version: i64,
     pub branch_id: String,
-    pub book_id: String,
-    pub patron_id: String,
+    pub book_id: BookId,
+    pub patron_id: PatronId,
     pub hold_status: HoldStatus,
+    pub pickup_branch_id: String,
     #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
     pub hold_at: NaiveDateTime,
     #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
     pub expires_at: NaiveDateTime,
     pub canceled_at: Option<NaiveDateTime>,
     pub checked_out_at: Option<NaiveDateTime>,
+    // deadline for picking up the hold once it is ready
+    pub pickup_by: Option<NaiveDateTime>,
+    // number of times the expiry of the hold was extended
+    #[serde(default)]
+    pub extensions: i64,
     #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
     pub created_at: NaiveDateTime,
     #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
     pub updated_at: NaiveDateTime,
 }
 
 impl HoldDto {
-    pub fn new(book_id: &str, patron_id: &str) -> Self {
+    pub fn new(book_id: &BookId, patron_id: &PatronId) -> Self {
+        let branch_id = Uuid::new_v4().to_string();
         Self {
-            hold_id: Uuid::new_v4().to_string(),
+            hold_id: HoldId::generate_in(branch_id.as_str()),
             version: 0,
-            branch_id: Uuid::new_v4().to_string(),
-            book_id: book_id.to_string(),
-            patron_id: patron_id.to_string(),
+            branch_id: branch_id.to_string(),
+            book_id: book_id.clone(),
+            patron_id: patron_id.clone(),
             hold_status: HoldStatus::OnHold,
+            pickup_branch_id: branch_id,
             hold_at: Utc::now().naive_utc(),
             expires_at: Utc::now().naive_utc() + Duration::days(15),
             canceled_at: None,
             checked_out_at: None,
+            pickup_by: None,
+            extensions: 0,
             created_at: Utc::now().naive_utc(),
             updated_at: Utc::now().naive_utc(),
         }
     }
 }
 
+// HoldStatusDto tells the patron where the hold is in the queue of the book and when the book is expected to be
+// available to them, other patrons in the queue are not revealed
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct HoldStatusDto {
+    pub hold_id: HoldId,
+    pub book_id: BookId,
+    pub hold_status: HoldStatus,
+    // position in the queue starting at 1, zero once the hold is checked out or canceled
+    pub position: usize,
+    pub queue_length: usize,
+    // deadline for picking up the hold once it is ready
+    pub pickup_by: Option<NaiveDateTime>,
+    pub estimated_available_at: Option<NaiveDateTime>,
+}
+
+impl HoldStatusDto {
+    pub fn new(hold: &HoldDto, queue_length: usize) -> Self {
+        Self {
+            hold_id: hold.hold_id.clone(),
+            book_id: hold.book_id.clone(),
+            hold_status: hold.hold_status,
+            position: 0,
+            queue_length,
+            pickup_by: hold.pickup_by,
+            estimated_available_at: None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::core::ids::{BookId, PatronId};
     use crate::core::library::HoldStatus;
     use crate::hold::domain::model::HoldEntity;
 
     #[tokio::test]
     async fn test_should_build_hold() {
-        let hold = HoldEntity::new("book1", "patron1");
+        let hold = HoldEntity::new(&BookId::new("book1"), &PatronId::new("patron1"));
         assert_eq!("book1", hold.book_id.as_str());
         assert_eq!("patron1", hold.patron_id.as_str());
         assert_eq!(HoldStatus::OnHold, hold.hold_status);
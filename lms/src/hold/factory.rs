@@ -1,32 +1,60 @@
+use crate::audit::factory::create_audit_service;
 use crate::catalog::factory::create_catalog_service;
+use crate::checkout::factory::create_checkout_query_service;
 use crate::core::domain::Configuration;
-use crate::hold::domain::HoldService;
+use crate::hold::domain::{HoldQueryService, HoldService};
+use crate::hold::domain::query::HoldQueryServiceImpl;
 use crate::hold::domain::service::HoldServiceImpl;
-use crate::hold::repository::ddb_hold_repository::DDBHoldRepository;
+use crate::hold::repository::ddb_hold_repository::{DDBHoldRepository, ACTIVE_HOLDS_TABLE, BRANCH_INDEX};
 use crate::hold::repository::HoldRepository;
+use crate::notifications::factory::create_notification_service;
 use crate::core::repository::RepositoryStore;
-use crate::gateway::factory::create_publisher;
 use crate::patrons::factory::create_patron_service;
-use crate::utils::ddb::{build_db_client, create_table};
+use crate::projector::factory::create_projecting_publisher;
+use crate::reserves::factory::create_reserve_service;
+use crate::utils::ddb::{build_region_client, create_index, create_key_table, create_table, ScanGuard, TableBilling};
+use crate::utils::region::ClientRole;
 
 pub(crate) async fn create_hold_repository(store: RepositoryStore) -> Box<dyn HoldRepository> {
+    create_hold_repository_for(store, ClientRole::Write).await
+}
+
+pub(crate) async fn create_hold_repository_for(store: RepositoryStore, role: ClientRole) -> Box<dyn HoldRepository> {
     match store {
         RepositoryStore::DynamoDB => {
-            let client = build_db_client(store).await;
-            Box::new(DDBHoldRepository::new(client, "hold", "hold_ndx"))
+            let client = build_region_client(store, role).await;
+            Box::new(DDBHoldRepository::new(client, "hold", "hold_ndx", ACTIVE_HOLDS_TABLE).with_scan_guard(ScanGuard::new(store)))
         }
-        RepositoryStore::LocalDynamoDB => {
-            let client = build_db_client(store).await;
-            let _ = create_table(&client, "hold", "hold_id", "hold_status", "patron_id").await;
-            Box::new(DDBHoldRepository::new(client, "hold", "hold_ndx"))
+        _ => {
+            let client = build_region_client(store, role).await;
+            let table_name = store.table_name("hold");
+            let _ = create_table(&client, table_name.as_str(), "hold_id", "hold_status", "patron_id").await;
+            let _ = create_index(&client, table_name.as_str(), BRANCH_INDEX, "branch_id", "hold_id", TableBilling::default()).await;
+            let active_table_name = store.table_name(ACTIVE_HOLDS_TABLE);
+            let _ = create_key_table(&client, active_table_name.as_str(), "patron_book").await;
+            Box::new(DDBHoldRepository::new(client, table_name.as_str(), store.table_name("hold_ndx").as_str(),
+                                            active_table_name.as_str()))
         }
     }
 }
 
-pub(crate) async fn create_hold_service(config: &Configuration, store: RepositoryStore) -> Box<dyn HoldService> {
+pub async fn create_hold_query_service(_config: &Configuration, store: RepositoryStore) -> Box<dyn HoldQueryService> {
+    let hold_repository = create_hold_repository_for(store, ClientRole::Read).await;
+    Box::new(HoldQueryServiceImpl::new(hold_repository))
+}
+
+pub async fn create_hold_service(config: &Configuration, store: RepositoryStore) -> Box<dyn HoldService> {
     let hold_repository = create_hold_repository(store).await;
     let catalog_svc = create_catalog_service(config, store).await;
     let patron_svc = create_patron_service(config, store).await;
-    let publisher = create_publisher(store.gateway_publisher()).await;
-    Box::new(HoldServiceImpl::new(config, hold_repository, patron_svc, catalog_svc, publisher))
+    let reserve_svc = create_reserve_service(config, store).await;
+    let audit_svc = create_audit_service(config, store).await;
+    let notification_svc = create_notification_service(store).await;
+    // patron counters are projected from hold events
+    let publisher = create_projecting_publisher(store).await;
+    let query_svc = create_hold_query_service(config, store).await;
+    // due dates of checkouts estimate when held books become available
+    let checkout_query_svc = create_checkout_query_service(config, store).await;
+    Box::new(HoldServiceImpl::new(config, hold_repository, patron_svc, catalog_svc, reserve_svc,
+                                  audit_svc, notification_svc, publisher, query_svc, checkout_query_svc))
 }
@@ -2,8 +2,10 @@ pub mod ddb_hold_repository;
 
 use async_trait::async_trait;
 use std::collections::HashMap;
+use chrono::NaiveDateTime;
 use crate::hold::domain::model::HoldEntity;
-use crate::core::library::{LibraryResult, PaginatedResult};
+use crate::core::ids::BookId;
+use crate::core::library::{HoldStatus, LibraryResult, PaginatedResult};
 use crate::core::repository::Repository;
 
 
@@ -11,5 +13,19 @@ use crate::core::repository::Repository;
 pub(crate) trait HoldRepository: Repository<HoldEntity> {
     async fn query_expired(&self, predicate: &HashMap::<String, String>,
                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<HoldEntity>>;
+    // returns holds of the book with the given status
+    async fn find_by_book(&self, book_id: &BookId, status: HoldStatus) -> LibraryResult<Vec<HoldEntity>>;
+    // returns holds that were ready for pickup but not picked up before the deadline
+    async fn find_pickup_expired(&self) -> LibraryResult<Vec<HoldEntity>>;
+    // creates holds of a batch together, none of them is created when one of them fails
+    async fn create_all(&self, entities: &[HoldEntity]) -> LibraryResult<usize>;
+    // moves the active hold from the book to the book of the entity along with its lookup, used when duplicate
+    // catalog records are merged
+    async fn reassign_book(&self, entity: &HoldEntity, from_book_id: &BookId) -> LibraryResult<usize>;
+    // returns holds placed at the branch within the time range, most recent first
+    async fn find_recent(&self, branch_id: &str, since: NaiveDateTime, until: NaiveDateTime,
+                         page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<HoldEntity>>;
+    // reads holds of all statuses page by page, used by the invariants job
+    async fn scan(&self, page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<HoldEntity>>;
 }
 
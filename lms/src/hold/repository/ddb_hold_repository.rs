@@ -3,70 +3,179 @@ use std::collections::HashMap;
 
 use async_trait::async_trait;
 use aws_sdk_dynamodb::Client;
-use aws_sdk_dynamodb::types::AttributeValue;
-use chrono::Utc;
+use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::operation::transact_write_items::TransactWriteItemsError;
+use aws_sdk_dynamodb::types::{AttributeValue, Delete, Put, ReturnConsumedCapacity, TransactWriteItem, Update};
+use chrono::{NaiveDateTime, Utc};
 
 use crate::hold::domain::model::HoldEntity;
+use crate::core::ids::{branch_scoped_range, BookId};
+use crate::core::invariants::assert_invariants;
 use crate::core::library::{HoldStatus, LibraryError, LibraryResult, PaginatedResult};
 use crate::core::repository::Repository;
 use crate::hold::repository::HoldRepository;
-use crate::utils::ddb::{add_filter_expr, from_ddb, opt_string_date, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, string_date, to_ddb_page};
+use crate::utils::ddb::{add_filter_expr, from_ddb, is_transaction_condition_failed, opt_string_date, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, string_date, to_ddb_page, ScanGuard};
+use crate::utils::metrics::OperationMeter;
+
+// lookup of active holds keyed by patron and book, holds and their lookup are written in one transaction so that
+// a patron cannot hold the same book twice
+pub(crate) const ACTIVE_HOLDS_TABLE: &str = "active_holds";
+// suffix of the index of holds by branch sorted by their branch scoped ids, which orders them by the time they were
+// placed
+pub(crate) const BRANCH_INDEX: &str = "branch_ndx";
+
+const UPDATE_EXPR: &str = "SET version = :version, hold_status = :hold_status, pickup_branch_id = :pickup_branch_id, hold_at = :hold_at, expires_at = :expires_at, canceled_at = :canceled_at, checked_out_at = :checked_out_at, pickup_by = :pickup_by, extensions = :extensions, updated_at = :updated_at";
+const UPDATE_CONDITION: &str = "attribute_exists(version) AND version = :old_version";
 
 #[derive(Debug)]
 pub struct DDBHoldRepository {
     client: Client,
     table_name: String,
     index_name: String,
+    branch_index_name: String,
+    active_table_name: String,
+    scan_guard: ScanGuard,
 }
 
 impl DDBHoldRepository {
-    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str, active_table_name: &str) -> Self {
         Self {
             client,
             table_name: table_name.to_string(),
             index_name: index_name.to_string(),
+            branch_index_name: format!("{}_{}", table_name, BRANCH_INDEX),
+            active_table_name: active_table_name.to_string(),
+            scan_guard: ScanGuard::default(),
         }
     }
-}
 
-#[async_trait]
-impl Repository<HoldEntity> for DDBHoldRepository {
-    async fn create(&self, entity: &HoldEntity) -> LibraryResult<usize> {
-        let table_name: &str = self.table_name.as_ref();
+    pub(crate) fn with_scan_guard(mut self, scan_guard: ScanGuard) -> Self {
+        self.scan_guard = scan_guard;
+        self
+    }
+
+    fn hold_put(&self, entity: &HoldEntity) -> LibraryResult<Put> {
         let val = serde_json::to_value(entity)?;
-        self.client
-            .put_item()
-            .table_name(table_name)
+        Ok(Put::builder()
+            .table_name(self.table_name.as_str())
             .condition_expression("attribute_not_exists(hold_id)")
             .set_item(Some(parse_item(val)?))
-            .send()
-            .await.map(|_| 1).map_err(LibraryError::from)
+            .build())
     }
 
-    async fn update(&self, entity: &HoldEntity) -> LibraryResult<usize> {
+    fn active_put(&self, entity: &HoldEntity) -> Put {
+        Put::builder()
+            .table_name(self.active_table_name.as_str())
+            .condition_expression("attribute_not_exists(patron_book)")
+            .item("patron_book", AttributeValue::S(active_key(entity)))
+            .item("hold_id", AttributeValue::S(entity.hold_id.to_string()))
+            .build()
+    }
+
+    // holds created before the lookup existed have no lookup item so a missing item is not an error
+    fn active_delete(&self, entity: &HoldEntity) -> Delete {
+        Delete::builder()
+            .table_name(self.active_table_name.as_str())
+            .key("patron_book", AttributeValue::S(active_key(entity)))
+            .condition_expression("attribute_not_exists(patron_book) OR hold_id = :hold_id")
+            .expression_attribute_values(":hold_id", AttributeValue::S(entity.hold_id.to_string()))
+            .build()
+    }
+
+    fn update_values(entity: &HoldEntity) -> HashMap<String, AttributeValue> {
         let now = Utc::now().naive_utc();
+        HashMap::from([
+            (":old_version".to_string(), AttributeValue::N(entity.version.to_string())),
+            (":version".to_string(), AttributeValue::N((entity.version + 1).to_string())),
+            (":hold_status".to_string(), AttributeValue::S(entity.hold_status.to_string())),
+            (":pickup_branch_id".to_string(), AttributeValue::S(entity.pickup_branch_id.to_string())),
+            (":hold_at".to_string(), string_date(entity.hold_at)),
+            (":expires_at".to_string(), string_date(entity.expires_at)),
+            (":canceled_at".to_string(), opt_string_date(entity.canceled_at)),
+            (":checked_out_at".to_string(), opt_string_date(entity.checked_out_at)),
+            (":pickup_by".to_string(), opt_string_date(entity.pickup_by)),
+            (":extensions".to_string(), AttributeValue::N(entity.extensions.to_string())),
+            (":updated_at".to_string(), string_date(now)),
+        ])
+    }
+
+    // the condition of a lookup at the given indexes of the transaction fails when the hold is a duplicate
+    fn duplicate_error(err: SdkError<TransactWriteItemsError>, lookup_indexes: &[usize]) -> LibraryError {
+        if lookup_indexes.iter().any(|i| is_transaction_condition_failed(&err, *i)) {
+            return LibraryError::duplicate_key("patron already has an active hold of the book");
+        }
+        LibraryError::from(err)
+    }
+
+    // reads all pages of holds with the status that match the filter
+    async fn find_all(&self, filter_expr: &str, name: &str, value: AttributeValue,
+                      status: HoldStatus) -> LibraryResult<Vec<HoldEntity>> {
         let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let mut holds = vec![];
+        let mut last_key: Option<HashMap<String, AttributeValue>> = None;
+        loop {
+            let res = self.client
+                .query()
+                .table_name(table_name)
+                .index_name(index_name)
+                .consistent_read(false)
+                .set_exclusive_start_key(last_key)
+                .key_condition_expression("hold_status = :hold_status")
+                .filter_expression(filter_expr)
+                .expression_attribute_values(":hold_status", AttributeValue::S(status.to_string()))
+                .expression_attribute_values(name, value.clone())
+                .send()
+                .await.map_err(LibraryError::from)?;
+            holds.extend(res.items.as_ref().unwrap_or(&vec![]).iter().map(HoldEntity::from));
+            last_key = res.last_evaluated_key().cloned();
+            if last_key.is_none() {
+                break;
+            }
+        }
+        Ok(holds)
+    }
+}
 
+#[async_trait]
+impl Repository<HoldEntity> for DDBHoldRepository {
+    async fn create(&self, entity: &HoldEntity) -> LibraryResult<usize> {
+        self.create_all(std::slice::from_ref(entity)).await
+    }
+
+    // the lookup is released along with the hold once it is checked out or canceled
+    async fn update(&self, entity: &HoldEntity) -> LibraryResult<usize> {
+        assert_invariants(entity);
+        let key = AttributeValue::S(entity.hold_id.to_string());
+        if entity.hold_status.is_active() {
+            return self.client
+                .update_item()
+                .table_name(self.table_name.as_str())
+                .key("hold_id", key)
+                .update_expression(UPDATE_EXPR)
+                .set_expression_attribute_values(Some(Self::update_values(entity)))
+                .condition_expression(UPDATE_CONDITION)
+                .send()
+                .await.map(|_| 1).map_err(LibraryError::from);
+        }
+        let hold = Update::builder()
+            .table_name(self.table_name.as_str())
+            .key("hold_id", key)
+            .update_expression(UPDATE_EXPR)
+            .set_expression_attribute_values(Some(Self::update_values(entity)))
+            .condition_expression(UPDATE_CONDITION)
+            .build();
         self.client
-            .update_item()
-            .table_name(table_name)
-            .key("hold_id", AttributeValue::S(entity.hold_id.clone()))
-            .update_expression("SET version = :version, hold_status = :hold_status, hold_at = :hold_at, expires_at = :expires_at, canceled_at = :canceled_at, checked_out_at = :checked_out_at, updated_at = :updated_at")
-            .expression_attribute_values(":old_version", AttributeValue::N(entity.version.to_string()))
-            .expression_attribute_values(":version", AttributeValue::N((entity.version + 1).to_string()))
-            .expression_attribute_values(":hold_status", AttributeValue::S(entity.hold_status.to_string()))
-            .expression_attribute_values(":hold_at", string_date(entity.hold_at))
-            .expression_attribute_values(":expires_at", string_date(entity.expires_at))
-            .expression_attribute_values(":canceled_at", opt_string_date(entity.canceled_at))
-            .expression_attribute_values(":checked_out_at", opt_string_date(entity.checked_out_at))
-            .expression_attribute_values(":updated_at", string_date(now))
-            .condition_expression("attribute_exists(version) AND version = :old_version")
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().update(hold).build())
+            .transact_items(TransactWriteItem::builder().delete(self.active_delete(entity)).build())
             .send()
             .await.map(|_| 1).map_err(LibraryError::from)
     }
 
     async fn get(&self, id: &str) -> LibraryResult<HoldEntity> {
         let table_name: &str = self.table_name.as_ref();
+        let meter = OperationMeter::start(table_name, "get").key("hold_id");
         self.client
             .query()
             .table_name(table_name)
@@ -79,8 +188,10 @@ impl Repository<HoldEntity> for DDBHoldRepository {
                 ":hold_id",
                 AttributeValue::S(id.to_string()),
             )
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
             .send()
             .await.map_err(LibraryError::from).and_then(|req| {
+            meter.finish(req.consumed_capacity());
             if let Some(items) = req.items {
                 if items.len() > 1 {
                     return Err(LibraryError::database(format!("too many hold for {}", id).as_str(), None, false));
@@ -98,6 +209,18 @@ impl Repository<HoldEntity> for DDBHoldRepository {
 
     async fn delete(&self, id: &str) -> LibraryResult<usize> {
         let table_name: &str = self.table_name.as_ref();
+        if let Ok(entity) = self.get(id).await {
+            let hold = Delete::builder()
+                .table_name(table_name)
+                .key("hold_id", AttributeValue::S(id.to_string()))
+                .build();
+            return self.client
+                .transact_write_items()
+                .transact_items(TransactWriteItem::builder().delete(hold).build())
+                .transact_items(TransactWriteItem::builder().delete(self.active_delete(&entity)).build())
+                .send()
+                .await.map(|_| 1).map_err(LibraryError::from);
+        }
         self.client.delete_item()
             .table_name(table_name)
             .key("hold_id", AttributeValue::S(id.to_string()))
@@ -129,6 +252,7 @@ impl Repository<HoldEntity> for DDBHoldRepository {
             key_cond.push_str(" AND patron_id = :patron_id");
             request = request.expression_attribute_values(":patron_id", AttributeValue::S(patron_id.to_string()));
         }
+        let meter = OperationMeter::start(table_name, "query").index(index_name).key(key_cond.as_str()).page_size(page_size);
         request = request.key_condition_expression(key_cond);
         let mut filter_expr = String::new();
         // then handle other filters
@@ -142,8 +266,10 @@ impl Repository<HoldEntity> for DDBHoldRepository {
             request = request.filter_expression(filter_expr);
         }
         request
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
             .send()
             .await.map_err(LibraryError::from).map(|req| {
+            meter.finish(req.consumed_capacity());
             let records = req.items.as_ref().unwrap_or(&vec![]).iter()
                 .map(HoldEntity::from).collect();
             from_ddb(page, page_size, req.last_evaluated_key(), records)
@@ -153,6 +279,24 @@ impl Repository<HoldEntity> for DDBHoldRepository {
 
 #[async_trait]
 impl HoldRepository for DDBHoldRepository {
+    async fn create_all(&self, entities: &[HoldEntity]) -> LibraryResult<usize> {
+        let mut req = self.client.transact_write_items();
+        let mut size = 0;
+        let mut lookup_indexes = vec![];
+        for entity in entities {
+            assert_invariants(entity);
+            req = req.transact_items(TransactWriteItem::builder().put(self.hold_put(entity)?).build());
+            size += 1;
+            // inactive holds do not reserve the book so they have no lookup
+            if entity.hold_status.is_active() {
+                req = req.transact_items(TransactWriteItem::builder().put(self.active_put(entity)).build());
+                lookup_indexes.push(size);
+                size += 1;
+            }
+        }
+        req.send().await.map(|_| entities.len()).map_err(|err| Self::duplicate_error(err, &lookup_indexes))
+    }
+
     async fn query_expired(&self, predicate: &HashMap<String, String>, page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<HoldEntity>> {
         let now = Utc::now().naive_utc();
         let mut new_predicate = HashMap::from([
@@ -164,21 +308,107 @@ impl HoldRepository for DDBHoldRepository {
         }
         self.query(&new_predicate, page, page_size).await
     }
+
+    async fn find_by_book(&self, book_id: &BookId, status: HoldStatus) -> LibraryResult<Vec<HoldEntity>> {
+        self.find_all("book_id = :book_id", ":book_id", AttributeValue::S(book_id.to_string()), status).await
+    }
+
+    async fn reassign_book(&self, entity: &HoldEntity, from_book_id: &BookId) -> LibraryResult<usize> {
+        assert_invariants(entity);
+        let mut values = Self::update_values(entity);
+        values.insert(":book_id".to_string(), AttributeValue::S(entity.book_id.to_string()));
+        let hold = Update::builder()
+            .table_name(self.table_name.as_str())
+            .key("hold_id", AttributeValue::S(entity.hold_id.to_string()))
+            .update_expression(format!("{}, book_id = :book_id", UPDATE_EXPR))
+            .set_expression_attribute_values(Some(values))
+            .condition_expression(UPDATE_CONDITION)
+            .build();
+        let previous = HoldEntity { book_id: from_book_id.clone(), ..entity.clone() };
+        self.client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().update(hold).build())
+            .transact_items(TransactWriteItem::builder().delete(self.active_delete(&previous)).build())
+            .transact_items(TransactWriteItem::builder().put(self.active_put(entity)).build())
+            .send()
+            .await.map(|_| 1).map_err(|err| Self::duplicate_error(err, &[2]))
+    }
+
+    async fn find_pickup_expired(&self) -> LibraryResult<Vec<HoldEntity>> {
+        let now = Utc::now().naive_utc();
+        self.find_all("pickup_by <= :pickup_by", ":pickup_by", string_date(now), HoldStatus::ReadyForPickup).await
+    }
+
+    async fn find_recent(&self, branch_id: &str, since: NaiveDateTime, until: NaiveDateTime,
+                         page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<HoldEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.branch_index_name.as_ref();
+        let key = HashMap::from([
+            ("branch_id".to_string(), branch_id.to_string()),
+        ]);
+        let (lower, upper) = branch_scoped_range(branch_id, since, until);
+        let meter = OperationMeter::start(table_name, "find_recent").index(index_name).key("branch_id").page_size(page_size);
+        self.client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .limit(cmp::min(page_size, 500) as i32)
+            .consistent_read(false)
+            .scan_index_forward(false)
+            .set_exclusive_start_key(to_ddb_page(page, &key))
+            .key_condition_expression("branch_id = :branch_id AND hold_id BETWEEN :lower AND :upper")
+            .expression_attribute_values(":branch_id", AttributeValue::S(branch_id.to_string()))
+            .expression_attribute_values(":lower", AttributeValue::S(lower))
+            .expression_attribute_values(":upper", AttributeValue::S(upper))
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            meter.finish(req.consumed_capacity());
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(HoldEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+
+    async fn scan(&self, page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<HoldEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        self.scan_guard.check(table_name)?;
+        self.client
+            .scan()
+            .table_name(table_name)
+            .consistent_read(false)
+            .set_exclusive_start_key(to_ddb_page(page, &HashMap::new()))
+            .limit(cmp::min(page_size, 500) as i32)
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(HoldEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+}
+
+fn active_key(entity: &HoldEntity) -> String {
+    format!("{}#{}", entity.patron_id, entity.book_id)
 }
 
 impl From<&HashMap<String, AttributeValue>> for HoldEntity {
     fn from(map: &HashMap<String, AttributeValue>) -> Self {
         HoldEntity {
-            hold_id: parse_string_attribute("hold_id", map).unwrap_or_else(|| String::from("")),
+            hold_id: parse_string_attribute("hold_id", map).unwrap_or_default().into(),
             version: parse_number_attribute("version", map),
             branch_id: parse_string_attribute("branch_id", map).unwrap_or_else(|| String::from("")),
-            book_id: parse_string_attribute("book_id", map).unwrap_or_else(|| String::from("")),
-            patron_id: parse_string_attribute("patron_id", map).unwrap_or_else(|| String::from("")),
+            book_id: parse_string_attribute("book_id", map).unwrap_or_default().into(),
+            patron_id: parse_string_attribute("patron_id", map).unwrap_or_default().into(),
             hold_status: HoldStatus::from(parse_string_attribute("hold_status", map).unwrap_or_else(|| HoldStatus::OnHold.to_string())),
+            pickup_branch_id: parse_string_attribute("pickup_branch_id", map)
+                .or_else(|| parse_string_attribute("branch_id", map)).unwrap_or_else(|| String::from("")),
             hold_at: parse_date_attribute("hold_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
             expires_at: parse_date_attribute("expires_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
             canceled_at: parse_date_attribute("canceled_at", map),
             checked_out_at: parse_date_attribute("checked_out_at", map),
+            pickup_by: parse_date_attribute("pickup_by", map),
+            extensions: parse_number_attribute("extensions", map),
             created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
             updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
         }
@@ -188,33 +418,33 @@ impl From<&HashMap<String, AttributeValue>> for HoldEntity {
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
-    use async_once::AsyncOnce;
 
     use aws_sdk_dynamodb::Client;
     use chrono::NaiveDateTime;
-    use lazy_static::lazy_static;
-    use crate::core::library::HoldStatus;
+    use crate::core::ids::{BookId, PatronId};
+    use crate::core::library::{HoldStatus, LibraryError};
     use crate::core::repository::{Repository, RepositoryStore};
+    use crate::hold::repository::HoldRepository;
 
     use crate::hold::domain::model::HoldEntity;
-    use crate::hold::repository::ddb_hold_repository::DDBHoldRepository;
-    use crate::utils::ddb::{build_db_client, create_table, delete_table};
+    use crate::hold::repository::ddb_hold_repository::{DDBHoldRepository, ACTIVE_HOLDS_TABLE};
+    use crate::utils::ddb::{build_db_client, create_key_table, create_table};
     use crate::utils::date::DATE_FMT;
+    use crate::utils::testing::test_store;
 
-    lazy_static! {
-        static ref CLIENT: AsyncOnce<Client> = AsyncOnce::new(async {
-                let client = build_db_client(RepositoryStore::LocalDynamoDB).await;
-                let _ = delete_table(&client, "hold").await;
-                let _ = create_table(&client, "hold", "hold_id", "hold_status", "patron_id").await;
-                client
-            });
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("hold").as_str(), "hold_id", "hold_status", "patron_id").await;
+        let _ = create_key_table(&client, store.table_name(ACTIVE_HOLDS_TABLE).as_str(), "patron_book").await;
+        client
     }
 
     #[tokio::test]
     async fn test_should_create_get_hold() {
-        let hold_repo = DDBHoldRepository::new(
-            CLIENT.get().await.clone(), "hold", "hold_ndx");
-        let hold = HoldEntity::new("book1", "patron1");
+        let store = test_store(module_path!(), "test_should_create_get_hold");
+        let hold_repo = DDBHoldRepository::new(build_client(store).await, store.table_name("hold").as_str(), store.table_name("hold_ndx").as_str(),
+            store.table_name(ACTIVE_HOLDS_TABLE).as_str());
+        let hold = HoldEntity::new(&BookId::new("book1"), &PatronId::new("patron1"));
         let size = hold_repo.create(&hold).await.expect("should create hold");
         assert_eq!(1, size);
 
@@ -224,9 +454,10 @@ mod tests {
 
     #[tokio::test]
     async fn test_should_create_update_hold() {
-        let hold_repo = DDBHoldRepository::new(
-            CLIENT.get().await.clone(), "hold", "hold_ndx");
-        let mut hold = HoldEntity::new("book2", "patron2");
+        let store = test_store(module_path!(), "test_should_create_update_hold");
+        let hold_repo = DDBHoldRepository::new(build_client(store).await, store.table_name("hold").as_str(), store.table_name("hold_ndx").as_str(),
+            store.table_name(ACTIVE_HOLDS_TABLE).as_str());
+        let mut hold = HoldEntity::new(&BookId::new("book2"), &PatronId::new("patron2"));
         let size = hold_repo.create(&hold).await.expect("should create hold");
         assert_eq!(1, size);
 
@@ -242,8 +473,9 @@ mod tests {
 
     #[tokio::test]
     async fn test_should_create_query_hold() {
-        let hold_repo = DDBHoldRepository::new(
-            CLIENT.get().await.clone(), "hold2", "hold2_ndx");
+        let store = test_store(module_path!(), "test_should_create_query_hold");
+        let hold_repo = DDBHoldRepository::new(build_client(store).await, store.table_name("hold2").as_str(), store.table_name("hold2_ndx").as_str(),
+            store.table_name(ACTIVE_HOLDS_TABLE).as_str());
         add_test_hold(&hold_repo, HoldStatus::Waiting).await;
         let mut next_page = None;
         let mut total = 0;
@@ -274,9 +506,10 @@ mod tests {
 
     #[tokio::test]
     async fn test_should_create_delete_hold() {
-        let hold_repo = DDBHoldRepository::new(
-            CLIENT.get().await.clone(), "hold", "hold_ndx");
-        let hold = HoldEntity::new("book1", "patron1");
+        let store = test_store(module_path!(), "test_should_create_delete_hold");
+        let hold_repo = DDBHoldRepository::new(build_client(store).await, store.table_name("hold").as_str(), store.table_name("hold_ndx").as_str(),
+            store.table_name(ACTIVE_HOLDS_TABLE).as_str());
+        let hold = HoldEntity::new(&BookId::new("book1"), &PatronId::new("patron1"));
         let size = hold_repo.create(&hold).await.expect("should create hold");
         assert_eq!(1, size);
 
@@ -287,9 +520,54 @@ mod tests {
         assert!(loaded.is_err());
     }
 
+    #[tokio::test]
+    async fn test_should_reject_duplicate_active_hold() {
+        let store = test_store(module_path!(), "test_should_reject_duplicate_active_hold");
+        let hold_repo = DDBHoldRepository::new(build_client(store).await, store.table_name("hold").as_str(), store.table_name("hold_ndx").as_str(),
+            store.table_name(ACTIVE_HOLDS_TABLE).as_str());
+        let mut hold = HoldEntity::new(&BookId::new("dup_book"), &PatronId::new("dup_patron"));
+        let _ = hold_repo.create(&hold).await.expect("should create hold");
+        let duplicate = HoldEntity::new(&BookId::new("dup_book"), &PatronId::new("dup_patron"));
+        let res = hold_repo.create(&duplicate).await;
+        assert!(matches!(res, Err(LibraryError::DuplicateKey { .. })));
+        // neither hold nor lookup is saved when the transaction is canceled
+        assert!(hold_repo.get(duplicate.hold_id.as_str()).await.is_err());
+        let res = hold_repo.create_all(&[HoldEntity::new(&BookId::new("other_book"), &PatronId::new("dup_patron")),
+            duplicate.clone()]).await;
+        assert!(matches!(res, Err(LibraryError::DuplicateKey { .. })));
+
+        // the book can be held again once the active hold is canceled
+        hold.hold_status = HoldStatus::Canceled;
+        let _ = hold_repo.update(&hold).await.expect("should cancel hold");
+        let _ = hold_repo.create(&duplicate).await.expect("should create hold after cancel");
+        let _ = hold_repo.delete(duplicate.hold_id.as_str()).await.expect("should delete hold");
+        let _ = hold_repo.create(&HoldEntity::new(&BookId::new("dup_book"), &PatronId::new("dup_patron")))
+            .await.expect("should create hold after delete");
+    }
+
+    #[tokio::test]
+    async fn test_should_reassign_hold_to_other_book() {
+        let store = test_store(module_path!(), "test_should_reassign_hold_to_other_book");
+        let hold_repo = DDBHoldRepository::new(build_client(store).await, store.table_name("hold").as_str(), store.table_name("hold_ndx").as_str(),
+            store.table_name(ACTIVE_HOLDS_TABLE).as_str());
+        let (from, to, patron_id) = (BookId::generate(), BookId::generate(), PatronId::generate());
+        let hold = HoldEntity::new(&from, &patron_id);
+        let _ = hold_repo.create(&hold).await.expect("should create hold");
+        let moved = HoldEntity { book_id: to.clone(), ..hold.clone() };
+        let _ = hold_repo.reassign_book(&moved, &from).await.expect("should reassign hold");
+        let loaded = hold_repo.get(hold.hold_id.as_str()).await.expect("should return hold");
+        assert_eq!(to, loaded.book_id);
+        assert_eq!(hold.version + 1, loaded.version);
+
+        // the lookup moves along so the patron can hold the previous book again but not the new one
+        let _ = hold_repo.create(&HoldEntity::new(&from, &patron_id)).await.expect("should hold previous book");
+        let res = hold_repo.create(&HoldEntity::new(&to, &patron_id)).await;
+        assert!(matches!(res, Err(LibraryError::DuplicateKey { .. })));
+    }
+
     async fn add_test_hold(hold_repo: &DDBHoldRepository, status: HoldStatus) {
         for i in 0..50 {
-            let mut hold = HoldEntity::new("book1", "patron1");
+            let mut hold = HoldEntity::new(&BookId::new(format!("book{}", i).as_str()), &PatronId::new("patron1"));
             hold.hold_status = status;
             hold.hold_at = NaiveDateTime::parse_from_str("2023-04-11T11:11:11", DATE_FMT).unwrap();
             if i % 2 == 0 {
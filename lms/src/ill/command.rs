@@ -0,0 +1,10 @@
+pub mod approve_ill_cmd;
+pub mod complete_ill_cmd;
+pub mod find_sagas_cmd;
+pub mod find_ills_cmd;
+pub mod get_ill_cmd;
+pub mod receive_ill_cmd;
+pub mod reject_ill_cmd;
+pub mod request_ill_cmd;
+pub mod return_ill_cmd;
+pub mod ship_ill_cmd;
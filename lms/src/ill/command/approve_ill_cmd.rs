@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::ill::domain::IllService;
+use crate::ill::dto::IllRequestDto;
+
+pub(crate) struct ApproveIllCommand {
+    ill_service: Box<dyn IllService>,
+}
+
+impl ApproveIllCommand {
+    pub(crate) fn new(ill_service: Box<dyn IllService>) -> Self {
+        Self {
+            ill_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ApproveIllCommandRequest {
+    #[serde(default)]
+    pub(crate) ill_id: String,
+    pub(crate) approved_by: String,
+    pub(crate) lending_library: String,
+}
+
+impl ApproveIllCommandRequest {
+    pub fn new(ill_id: &str, approved_by: &str, lending_library: &str) -> Self {
+        Self {
+            ill_id: ill_id.to_string(),
+            approved_by: approved_by.to_string(),
+            lending_library: lending_library.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ApproveIllCommandResponse {
+    pub ill: IllRequestDto,
+}
+
+impl ApproveIllCommandResponse {
+    pub fn new(ill: IllRequestDto) -> Self {
+        Self {
+            ill,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<ApproveIllCommandRequest, ApproveIllCommandResponse> for ApproveIllCommand {
+    async fn execute(&self, req: ApproveIllCommandRequest) -> Result<ApproveIllCommandResponse, CommandError> {
+        self.ill_service.approve(req.ill_id.as_str(), req.approved_by.as_str(), req.lending_library.as_str())
+            .await.map_err(CommandError::from).map(ApproveIllCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{IllStatus, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::ill::command::approve_ill_cmd::{ApproveIllCommand, ApproveIllCommandRequest};
+    use crate::ill::domain::IllService;
+    use crate::ill::dto::IllRequestDto;
+    use crate::ill::factory::create_ill_service;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn IllService> {
+        create_ill_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> ApproveIllCommand {
+        let svc = create_ill_service(&Configuration::new("test"), store).await;
+        ApproveIllCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_approve_ill() {
+        let store = test_store(module_path!(), "test_should_run_approve_ill");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "approve_ill@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let ill = svc.request(&IllRequestDto::new(librarian.party_id.as_str(), "approve_ill_isbn", "rare title")).await.expect("should request ill");
+        let res = sut_cmd.execute(ApproveIllCommandRequest::new(ill.ill_id.as_str(), librarian.party_id.as_str(), "State Library")).await.expect("should approve ill");
+        assert_eq!(IllStatus::Approved, res.ill.ill_status);
+        assert_eq!("State Library", res.ill.lending_library.as_str());
+    }
+}
@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::ill::domain::IllService;
+use crate::ill::dto::IllRequestDto;
+
+pub(crate) struct CompleteIllCommand {
+    ill_service: Box<dyn IllService>,
+}
+
+impl CompleteIllCommand {
+    pub(crate) fn new(ill_service: Box<dyn IllService>) -> Self {
+        Self {
+            ill_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CompleteIllCommandRequest {
+    pub(crate) ill_id: String,
+}
+
+impl CompleteIllCommandRequest {
+    pub fn new(ill_id: &str) -> Self {
+        Self {
+            ill_id: ill_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CompleteIllCommandResponse {
+    pub ill: IllRequestDto,
+}
+
+impl CompleteIllCommandResponse {
+    pub fn new(ill: IllRequestDto) -> Self {
+        Self {
+            ill,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<CompleteIllCommandRequest, CompleteIllCommandResponse> for CompleteIllCommand {
+    async fn execute(&self, req: CompleteIllCommandRequest) -> Result<CompleteIllCommandResponse, CommandError> {
+        self.ill_service.complete(req.ill_id.as_str())
+            .await.map_err(CommandError::from).map(CompleteIllCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{IllStatus, PartyKind, Role, ShippingStatus};
+    use crate::core::repository::RepositoryStore;
+    use crate::ill::command::complete_ill_cmd::{CompleteIllCommand, CompleteIllCommandRequest};
+    use crate::ill::domain::IllService;
+    use crate::ill::dto::IllRequestDto;
+    use crate::ill::factory::create_ill_service;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn IllService> {
+        create_ill_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> CompleteIllCommand {
+        let svc = create_ill_service(&Configuration::new("test"), store).await;
+        CompleteIllCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_complete_ill() {
+        let store = test_store(module_path!(), "test_should_run_complete_ill");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "complete_ill@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let ill = svc.request(&IllRequestDto::new(librarian.party_id.as_str(), "complete_ill_isbn", "rare title")).await.expect("should request ill");
+        let _ = svc.approve(ill.ill_id.as_str(), librarian.party_id.as_str(), "State Library").await.expect("should approve");
+        let _ = svc.receive(ill.ill_id.as_str(), Utc::now().naive_utc() + Duration::days(21)).await.expect("should receive");
+        let _ = svc.returned(ill.ill_id.as_str()).await.expect("should return");
+        let _ = svc.ship_return(ill.ill_id.as_str(), "TRACK1").await.expect("should ship");
+        let res = sut_cmd.execute(CompleteIllCommandRequest::new(ill.ill_id.as_str())).await.expect("should complete ill");
+        assert_eq!(IllStatus::Completed, res.ill.ill_status);
+        assert_eq!(ShippingStatus::Delivered, res.ill.shipping_status);
+    }
+}
@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::core::library::IllStatus;
+use crate::ill::domain::IllQueryService;
+use crate::ill::dto::IllRequestDto;
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+
+pub(crate) struct FindIllsCommand {
+    ill_service: Box<dyn IllQueryService>,
+}
+
+impl FindIllsCommand {
+    pub(crate) fn new(ill_service: Box<dyn IllQueryService>) -> Self {
+        Self {
+            ill_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindIllsCommandRequest {
+    pub(crate) status: Option<String>,
+    pub(crate) page: Option<String>,
+    pub(crate) page_size: Option<usize>,
+}
+
+impl FindIllsCommandRequest {
+    pub fn new(status: IllStatus) -> Self {
+        Self {
+            status: Some(status.to_string()),
+            page: None,
+            page_size: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindIllsCommandResponse {
+    pub ills: Vec<IllRequestDto>,
+    pub next_page: Option<String>,
+}
+
+impl FindIllsCommandResponse {
+    pub fn new(ills: Vec<IllRequestDto>, next_page: Option<String>) -> Self {
+        Self {
+            ills,
+            next_page,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<FindIllsCommandRequest, FindIllsCommandResponse> for FindIllsCommand {
+    async fn execute(&self, req: FindIllsCommandRequest) -> Result<FindIllsCommandResponse, CommandError> {
+        let status = req.status.map(IllStatus::from).unwrap_or(IllStatus::Requested);
+        self.ill_service.find_ill_by_status(status, req.page.as_deref(),
+                                            req.page_size.unwrap_or(DEFAULT_PAGE_SIZE))
+            .await.map_err(CommandError::from).map(|res| FindIllsCommandResponse::new(res.records, res.next_page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{IllStatus, PartyKind};
+    use crate::core::repository::RepositoryStore;
+    use crate::ill::command::find_ills_cmd::{FindIllsCommand, FindIllsCommandRequest};
+    use crate::ill::domain::IllService;
+    use crate::ill::dto::IllRequestDto;
+    use crate::ill::factory::{create_ill_service, create_ill_query_service};
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn IllService> {
+        create_ill_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> FindIllsCommand {
+        let svc = create_ill_query_service(&Configuration::new("test"), store).await;
+        FindIllsCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_find_ills() {
+        let store = test_store(module_path!(), "test_should_run_find_ills");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let patron = PartyEntity::new(PartyKind::Patron, "find_ills@example.com");
+        let _ = create_party_repository(store).await.create(&patron).await.expect("should create patron");
+        let ill = svc.request(&IllRequestDto::new(patron.party_id.as_str(), "find_ills_isbn", "rare title")).await.expect("should request ill");
+
+        let res = sut_cmd.execute(FindIllsCommandRequest::new(IllStatus::Requested)).await.expect("should find ills");
+        assert!(res.ills.iter().any(|i| i.ill_id == ill.ill_id));
+    }
+}
@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::ill::domain::IllQueryService;
+use crate::ill::dto::StuckWorkflowDto;
+
+const DEFAULT_PAGE_SIZE: usize = 100;
+// the only state of workflows that operators can list
+const STUCK_STATE: &str = "stuck";
+
+pub(crate) struct FindSagasCommand {
+    ill_service: Box<dyn IllQueryService>,
+    stuck_days: i64,
+}
+
+impl FindSagasCommand {
+    pub(crate) fn new(ill_service: Box<dyn IllQueryService>, stuck_days: i64) -> Self {
+        Self {
+            ill_service,
+            stuck_days,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindSagasCommandRequest {
+    pub(crate) state: Option<String>,
+    // overrides the timeout of the configuration
+    pub(crate) stuck_days: Option<i64>,
+}
+
+impl FindSagasCommandRequest {
+    pub fn new(state: &str) -> Self {
+        Self {
+            state: Some(state.to_string()),
+            stuck_days: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindSagasCommandResponse {
+    pub sagas: Vec<StuckWorkflowDto>,
+}
+
+impl FindSagasCommandResponse {
+    pub fn new(sagas: Vec<StuckWorkflowDto>) -> Self {
+        Self {
+            sagas,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<FindSagasCommandRequest, FindSagasCommandResponse> for FindSagasCommand {
+    async fn execute(&self, req: FindSagasCommandRequest) -> Result<FindSagasCommandResponse, CommandError> {
+        if req.state.as_deref().unwrap_or(STUCK_STATE) != STUCK_STATE {
+            return Err(CommandError::Validation {
+                message: format!("sagas can only be listed by state {}", STUCK_STATE),
+                reason_code: Some("400".to_string()),
+            });
+        }
+        let stuck_days = req.stuck_days.unwrap_or(self.stuck_days).max(0);
+        let stuck_before = Utc::now().naive_utc() - Duration::days(stuck_days);
+        self.ill_service.find_stuck(stuck_before, DEFAULT_PAGE_SIZE)
+            .await.map_err(CommandError::from).map(FindSagasCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::{Command, CommandError};
+    use crate::core::domain::Configuration;
+    use crate::core::library::PartyKind;
+    use crate::ill::command::find_sagas_cmd::{FindSagasCommand, FindSagasCommandRequest};
+    use crate::ill::dto::IllRequestDto;
+    use crate::ill::factory::{create_ill_query_service, create_ill_service};
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_run_find_sagas() {
+        let store = test_store(module_path!(), "test_should_run_find_sagas");
+        let config = Configuration::new("test");
+        let svc = create_ill_service(&config, store).await;
+        let sut_cmd = FindSagasCommand::new(create_ill_query_service(&config, store).await, 0);
+        let patron = PartyEntity::new(PartyKind::Patron, "find_sagas@example.com");
+        let _ = create_party_repository(store).await.create(&patron).await.expect("should create patron");
+        let ill = svc.request(&IllRequestDto::new(patron.party_id.as_str(), "sagas_isbn", "rare title")).await.expect("should request ill");
+
+        let res = sut_cmd.execute(FindSagasCommandRequest::new("stuck")).await.expect("should find sagas");
+        assert!(res.sagas.iter().any(|s| s.workflow == "ill" && s.workflow_id == ill.ill_id));
+
+        let mut req = FindSagasCommandRequest::new("stuck");
+        req.stuck_days = Some(14);
+        let res = sut_cmd.execute(req).await.expect("should find sagas");
+        assert!(res.sagas.is_empty());
+
+        let res = sut_cmd.execute(FindSagasCommandRequest::new("running")).await;
+        assert!(matches!(res, Err(CommandError::Validation { .. })));
+    }
+}
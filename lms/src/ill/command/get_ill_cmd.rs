@@ -0,0 +1,90 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::ill::domain::IllQueryService;
+use crate::ill::dto::IllRequestDto;
+
+pub(crate) struct GetIllCommand {
+    ill_service: Box<dyn IllQueryService>,
+}
+
+impl GetIllCommand {
+    pub(crate) fn new(ill_service: Box<dyn IllQueryService>) -> Self {
+        Self {
+            ill_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetIllCommandRequest {
+    pub(crate) ill_id: String,
+}
+
+impl GetIllCommandRequest {
+    pub fn new(ill_id: &str) -> Self {
+        Self {
+            ill_id: ill_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetIllCommandResponse {
+    pub ill: IllRequestDto,
+}
+
+impl GetIllCommandResponse {
+    pub fn new(ill: IllRequestDto) -> Self {
+        Self {
+            ill,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<GetIllCommandRequest, GetIllCommandResponse> for GetIllCommand {
+    async fn execute(&self, req: GetIllCommandRequest) -> Result<GetIllCommandResponse, CommandError> {
+        self.ill_service.find_ill_by_id(req.ill_id.as_str())
+            .await.map_err(CommandError::from).map(GetIllCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{IllStatus, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::ill::command::get_ill_cmd::{GetIllCommand, GetIllCommandRequest};
+    use crate::ill::domain::IllService;
+    use crate::ill::dto::IllRequestDto;
+    use crate::ill::factory::{create_ill_service, create_ill_query_service};
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn IllService> {
+        create_ill_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> GetIllCommand {
+        let svc = create_ill_query_service(&Configuration::new("test"), store).await;
+        GetIllCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_get_ill() {
+        let store = test_store(module_path!(), "test_should_run_get_ill");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "get_ill@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let ill = svc.request(&IllRequestDto::new(librarian.party_id.as_str(), "get_ill_isbn", "rare title")).await.expect("should request ill");
+        let res = sut_cmd.execute(GetIllCommandRequest::new(ill.ill_id.as_str())).await.expect("should get ill");
+        assert_eq!(ill.ill_id, res.ill.ill_id);
+        assert_eq!(IllStatus::Requested, res.ill.ill_status);
+    }
+}
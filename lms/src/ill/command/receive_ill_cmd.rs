@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::ill::domain::IllService;
+use crate::ill::dto::IllRequestDto;
+
+pub(crate) struct ReceiveIllCommand {
+    ill_service: Box<dyn IllService>,
+}
+
+impl ReceiveIllCommand {
+    pub(crate) fn new(ill_service: Box<dyn IllService>) -> Self {
+        Self {
+            ill_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ReceiveIllCommandRequest {
+    #[serde(default)]
+    pub(crate) ill_id: String,
+    pub(crate) due_at: NaiveDateTime,
+}
+
+impl ReceiveIllCommandRequest {
+    pub fn new(ill_id: &str, due_at: NaiveDateTime) -> Self {
+        Self {
+            ill_id: ill_id.to_string(),
+            due_at,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ReceiveIllCommandResponse {
+    pub ill: IllRequestDto,
+}
+
+impl ReceiveIllCommandResponse {
+    pub fn new(ill: IllRequestDto) -> Self {
+        Self {
+            ill,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<ReceiveIllCommandRequest, ReceiveIllCommandResponse> for ReceiveIllCommand {
+    async fn execute(&self, req: ReceiveIllCommandRequest) -> Result<ReceiveIllCommandResponse, CommandError> {
+        self.ill_service.receive(req.ill_id.as_str(), req.due_at)
+            .await.map_err(CommandError::from).map(ReceiveIllCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{IllStatus, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::ill::command::receive_ill_cmd::{ReceiveIllCommand, ReceiveIllCommandRequest};
+    use crate::ill::domain::IllService;
+    use crate::ill::dto::IllRequestDto;
+    use crate::ill::factory::create_ill_service;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn IllService> {
+        create_ill_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> ReceiveIllCommand {
+        let svc = create_ill_service(&Configuration::new("test"), store).await;
+        ReceiveIllCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_receive_ill() {
+        let store = test_store(module_path!(), "test_should_run_receive_ill");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "receive_ill@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let ill = svc.request(&IllRequestDto::new(librarian.party_id.as_str(), "receive_ill_isbn", "rare title")).await.expect("should request ill");
+        let _ = svc.approve(ill.ill_id.as_str(), librarian.party_id.as_str(), "State Library").await.expect("should approve");
+        let due_at = Utc::now().naive_utc() + Duration::days(21);
+        let res = sut_cmd.execute(ReceiveIllCommandRequest::new(ill.ill_id.as_str(), due_at)).await.expect("should receive ill");
+        assert_eq!(IllStatus::Received, res.ill.ill_status);
+        assert_eq!(Some(due_at), res.ill.due_at);
+    }
+}
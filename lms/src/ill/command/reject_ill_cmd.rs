@@ -0,0 +1,92 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::ill::domain::IllService;
+use crate::ill::dto::IllRequestDto;
+
+pub(crate) struct RejectIllCommand {
+    ill_service: Box<dyn IllService>,
+}
+
+impl RejectIllCommand {
+    pub(crate) fn new(ill_service: Box<dyn IllService>) -> Self {
+        Self {
+            ill_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RejectIllCommandRequest {
+    #[serde(default)]
+    pub(crate) ill_id: String,
+    pub(crate) rejected_by: String,
+}
+
+impl RejectIllCommandRequest {
+    pub fn new(ill_id: &str, rejected_by: &str) -> Self {
+        Self {
+            ill_id: ill_id.to_string(),
+            rejected_by: rejected_by.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RejectIllCommandResponse {
+    pub ill: IllRequestDto,
+}
+
+impl RejectIllCommandResponse {
+    pub fn new(ill: IllRequestDto) -> Self {
+        Self {
+            ill,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<RejectIllCommandRequest, RejectIllCommandResponse> for RejectIllCommand {
+    async fn execute(&self, req: RejectIllCommandRequest) -> Result<RejectIllCommandResponse, CommandError> {
+        self.ill_service.reject(req.ill_id.as_str(), req.rejected_by.as_str())
+            .await.map_err(CommandError::from).map(RejectIllCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{IllStatus, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::ill::command::reject_ill_cmd::{RejectIllCommand, RejectIllCommandRequest};
+    use crate::ill::domain::IllService;
+    use crate::ill::dto::IllRequestDto;
+    use crate::ill::factory::create_ill_service;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn IllService> {
+        create_ill_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> RejectIllCommand {
+        let svc = create_ill_service(&Configuration::new("test"), store).await;
+        RejectIllCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_reject_ill() {
+        let store = test_store(module_path!(), "test_should_run_reject_ill");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "reject_ill@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let ill = svc.request(&IllRequestDto::new(librarian.party_id.as_str(), "reject_ill_isbn", "rare title")).await.expect("should request ill");
+        let res = sut_cmd.execute(RejectIllCommandRequest::new(ill.ill_id.as_str(), librarian.party_id.as_str())).await.expect("should reject ill");
+        assert_eq!(IllStatus::Rejected, res.ill.ill_status);
+    }
+}
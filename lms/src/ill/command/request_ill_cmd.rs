@@ -0,0 +1,87 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::ill::domain::IllService;
+use crate::ill::dto::IllRequestDto;
+
+pub(crate) struct RequestIllCommand {
+    ill_service: Box<dyn IllService>,
+}
+
+impl RequestIllCommand {
+    pub(crate) fn new(ill_service: Box<dyn IllService>) -> Self {
+        Self {
+            ill_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RequestIllCommandRequest {
+    patron_id: String,
+    isbn: String,
+    title: String,
+}
+
+impl RequestIllCommandRequest {
+    pub fn new(patron_id: &str, isbn: &str, title: &str) -> Self {
+        Self {
+            patron_id: patron_id.to_string(),
+            isbn: isbn.to_string(),
+            title: title.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RequestIllCommandResponse {
+    pub ill: IllRequestDto,
+}
+
+impl RequestIllCommandResponse {
+    pub fn new(ill: IllRequestDto) -> Self {
+        Self {
+            ill,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<RequestIllCommandRequest, RequestIllCommandResponse> for RequestIllCommand {
+    async fn execute(&self, req: RequestIllCommandRequest) -> Result<RequestIllCommandResponse, CommandError> {
+        let ill = IllRequestDto::new(req.patron_id.as_str(), req.isbn.as_str(), req.title.as_str());
+        self.ill_service.request(&ill).await.map_err(CommandError::from).map(RequestIllCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{IllStatus, PartyKind};
+    use crate::core::repository::RepositoryStore;
+    use crate::ill::command::request_ill_cmd::{RequestIllCommand, RequestIllCommandRequest};
+    use crate::ill::factory::create_ill_service;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_sut_cmd(store: RepositoryStore) -> RequestIllCommand {
+        let svc = create_ill_service(&Configuration::new("test"), store).await;
+        RequestIllCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_request_ill() {
+        let store = test_store(module_path!(), "test_should_run_request_ill");
+        let sut_cmd = build_sut_cmd(store).await;
+        let patron = PartyEntity::new(PartyKind::Patron, "request_ill@example.com");
+        let _ = create_party_repository(store).await.create(&patron).await.expect("should create patron");
+
+        let res = sut_cmd.execute(RequestIllCommandRequest::new(patron.party_id.as_str(), "request_ill_isbn", "rare title"))
+            .await.expect("should request ill");
+        assert_eq!(IllStatus::Requested, res.ill.ill_status);
+        assert_eq!(patron.party_id, res.ill.patron_id);
+    }
+}
@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::ill::domain::IllService;
+use crate::ill::dto::IllRequestDto;
+
+pub(crate) struct ReturnIllCommand {
+    ill_service: Box<dyn IllService>,
+}
+
+impl ReturnIllCommand {
+    pub(crate) fn new(ill_service: Box<dyn IllService>) -> Self {
+        Self {
+            ill_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ReturnIllCommandRequest {
+    pub(crate) ill_id: String,
+}
+
+impl ReturnIllCommandRequest {
+    pub fn new(ill_id: &str) -> Self {
+        Self {
+            ill_id: ill_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ReturnIllCommandResponse {
+    pub ill: IllRequestDto,
+}
+
+impl ReturnIllCommandResponse {
+    pub fn new(ill: IllRequestDto) -> Self {
+        Self {
+            ill,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<ReturnIllCommandRequest, ReturnIllCommandResponse> for ReturnIllCommand {
+    async fn execute(&self, req: ReturnIllCommandRequest) -> Result<ReturnIllCommandResponse, CommandError> {
+        self.ill_service.returned(req.ill_id.as_str())
+            .await.map_err(CommandError::from).map(ReturnIllCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{IllStatus, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::ill::command::return_ill_cmd::{ReturnIllCommand, ReturnIllCommandRequest};
+    use crate::ill::domain::IllService;
+    use crate::ill::dto::IllRequestDto;
+    use crate::ill::factory::create_ill_service;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn IllService> {
+        create_ill_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> ReturnIllCommand {
+        let svc = create_ill_service(&Configuration::new("test"), store).await;
+        ReturnIllCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_return_ill() {
+        let store = test_store(module_path!(), "test_should_run_return_ill");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "return_ill@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let ill = svc.request(&IllRequestDto::new(librarian.party_id.as_str(), "return_ill_isbn", "rare title")).await.expect("should request ill");
+        let _ = svc.approve(ill.ill_id.as_str(), librarian.party_id.as_str(), "State Library").await.expect("should approve");
+        let _ = svc.receive(ill.ill_id.as_str(), Utc::now().naive_utc() + Duration::days(21)).await.expect("should receive");
+        let res = sut_cmd.execute(ReturnIllCommandRequest::new(ill.ill_id.as_str())).await.expect("should return ill");
+        assert_eq!(IllStatus::Returned, res.ill.ill_status);
+        assert!(res.ill.returned_at.is_some());
+    }
+}
@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::ill::domain::IllService;
+use crate::ill::dto::IllRequestDto;
+
+pub(crate) struct ShipIllCommand {
+    ill_service: Box<dyn IllService>,
+}
+
+impl ShipIllCommand {
+    pub(crate) fn new(ill_service: Box<dyn IllService>) -> Self {
+        Self {
+            ill_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ShipIllCommandRequest {
+    #[serde(default)]
+    pub(crate) ill_id: String,
+    pub(crate) tracking_number: String,
+}
+
+impl ShipIllCommandRequest {
+    pub fn new(ill_id: &str, tracking_number: &str) -> Self {
+        Self {
+            ill_id: ill_id.to_string(),
+            tracking_number: tracking_number.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ShipIllCommandResponse {
+    pub ill: IllRequestDto,
+}
+
+impl ShipIllCommandResponse {
+    pub fn new(ill: IllRequestDto) -> Self {
+        Self {
+            ill,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<ShipIllCommandRequest, ShipIllCommandResponse> for ShipIllCommand {
+    async fn execute(&self, req: ShipIllCommandRequest) -> Result<ShipIllCommandResponse, CommandError> {
+        self.ill_service.ship_return(req.ill_id.as_str(), req.tracking_number.as_str())
+            .await.map_err(CommandError::from).map(ShipIllCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, Role, ShippingStatus};
+    use crate::core::repository::RepositoryStore;
+    use crate::ill::command::ship_ill_cmd::{ShipIllCommand, ShipIllCommandRequest};
+    use crate::ill::domain::IllService;
+    use crate::ill::dto::IllRequestDto;
+    use crate::ill::factory::create_ill_service;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn IllService> {
+        create_ill_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> ShipIllCommand {
+        let svc = create_ill_service(&Configuration::new("test"), store).await;
+        ShipIllCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_ship_ill() {
+        let store = test_store(module_path!(), "test_should_run_ship_ill");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "ship_ill@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let ill = svc.request(&IllRequestDto::new(librarian.party_id.as_str(), "ship_ill_isbn", "rare title")).await.expect("should request ill");
+        let _ = svc.approve(ill.ill_id.as_str(), librarian.party_id.as_str(), "State Library").await.expect("should approve");
+        let _ = svc.receive(ill.ill_id.as_str(), Utc::now().naive_utc() + Duration::days(21)).await.expect("should receive");
+        let _ = svc.returned(ill.ill_id.as_str()).await.expect("should return");
+        let res = sut_cmd.execute(ShipIllCommandRequest::new(ill.ill_id.as_str(), "TRACK1")).await.expect("should ship ill");
+        assert_eq!(ShippingStatus::InTransit, res.ill.shipping_status);
+        assert_eq!("TRACK1", res.ill.tracking_number.as_str());
+    }
+}
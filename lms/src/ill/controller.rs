@@ -0,0 +1,152 @@
+use axum::{
+    extract::{Path, Query, State},
+    middleware,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use serde_json::{Value};
+use crate::core::controller::{AppState, command_bus, json_to_server_error, with_common_layers, ServerError};
+use crate::credentials::controller::authenticate;
+use crate::ill::command::approve_ill_cmd::{ApproveIllCommand, ApproveIllCommandRequest, ApproveIllCommandResponse};
+use crate::ill::command::complete_ill_cmd::{CompleteIllCommand, CompleteIllCommandRequest, CompleteIllCommandResponse};
+use crate::ill::command::find_ills_cmd::{FindIllsCommand, FindIllsCommandRequest, FindIllsCommandResponse};
+use crate::ill::command::find_sagas_cmd::{FindSagasCommand, FindSagasCommandRequest, FindSagasCommandResponse};
+use crate::ill::command::get_ill_cmd::{GetIllCommand, GetIllCommandRequest, GetIllCommandResponse};
+use crate::ill::command::receive_ill_cmd::{ReceiveIllCommand, ReceiveIllCommandRequest, ReceiveIllCommandResponse};
+use crate::ill::command::reject_ill_cmd::{RejectIllCommand, RejectIllCommandRequest, RejectIllCommandResponse};
+use crate::ill::command::request_ill_cmd::{RequestIllCommand, RequestIllCommandRequest, RequestIllCommandResponse};
+use crate::ill::command::return_ill_cmd::{ReturnIllCommand, ReturnIllCommandRequest, ReturnIllCommandResponse};
+use crate::ill::command::ship_ill_cmd::{ShipIllCommand, ShipIllCommandRequest, ShipIllCommandResponse};
+use crate::ill::domain::{IllQueryService, IllService};
+use crate::ill::factory;
+use crate::utils::ddb::{build_db_client, create_table};
+
+async fn build_service(state: AppState) -> Box<dyn IllService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_table(&client, state.store.table_name("ill_requests").as_str(), "ill_id", "ill_status", "patron_id").await;
+    factory::create_ill_service(&state.config, state.store).await
+}
+
+async fn build_query_service(state: AppState) -> Box<dyn IllQueryService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_table(&client, state.store.table_name("ill_requests").as_str(), "ill_id", "ill_status", "patron_id").await;
+    factory::create_ill_query_service(&state.config, state.store).await
+}
+
+pub(crate) async fn request_ill(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<Json<RequestIllCommandResponse>, ServerError> {
+    let req: RequestIllCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res = command_bus().register(RequestIllCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn find_ill_by_id(
+    State(state): State<AppState>,
+    Path(ill_id): Path<String>) -> Result<Json<GetIllCommandResponse>, ServerError> {
+    let req = GetIllCommandRequest { ill_id };
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(GetIllCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn find_ills(
+    State(state): State<AppState>,
+    Query(req): Query<FindIllsCommandRequest>) -> Result<Json<FindIllsCommandResponse>, ServerError> {
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(FindIllsCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// interlibrary loans are the long running workflows that wait on other libraries, operators list those that are
+// stuck in an intermediate state beyond the timeout with `?state=stuck`
+pub(crate) async fn find_sagas(
+    State(state): State<AppState>,
+    Query(req): Query<FindSagasCommandRequest>) -> Result<Json<FindSagasCommandResponse>, ServerError> {
+    let stuck_days = state.config.ill_stuck_days;
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(FindSagasCommand::new(svc, stuck_days)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// approving records the library that lends the book
+pub(crate) async fn approve_ill(
+    State(state): State<AppState>,
+    Path(ill_id): Path<String>,
+    json: Json<Value>) -> Result<Json<ApproveIllCommandResponse>, ServerError> {
+    let mut req: ApproveIllCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.ill_id = ill_id;
+    let svc = build_service(state).await;
+    let res = command_bus().register(ApproveIllCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn reject_ill(
+    State(state): State<AppState>,
+    Path(ill_id): Path<String>,
+    json: Json<Value>) -> Result<Json<RejectIllCommandResponse>, ServerError> {
+    let mut req: RejectIllCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.ill_id = ill_id;
+    let svc = build_service(state).await;
+    let res = command_bus().register(RejectIllCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// due date of the loan is set by the lending library
+pub(crate) async fn receive_ill(
+    State(state): State<AppState>,
+    Path(ill_id): Path<String>,
+    json: Json<Value>) -> Result<Json<ReceiveIllCommandResponse>, ServerError> {
+    let mut req: ReceiveIllCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.ill_id = ill_id;
+    let svc = build_service(state).await;
+    let res = command_bus().register(ReceiveIllCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn return_ill(
+    State(state): State<AppState>,
+    Path(ill_id): Path<String>) -> Result<Json<ReturnIllCommandResponse>, ServerError> {
+    let req = ReturnIllCommandRequest { ill_id };
+    let svc = build_service(state).await;
+    let res = command_bus().register(ReturnIllCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn ship_ill(
+    State(state): State<AppState>,
+    Path(ill_id): Path<String>,
+    json: Json<Value>) -> Result<Json<ShipIllCommandResponse>, ServerError> {
+    let mut req: ShipIllCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.ill_id = ill_id;
+    let svc = build_service(state).await;
+    let res = command_bus().register(ShipIllCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// completes the loan once return shipment is delivered
+pub(crate) async fn complete_ill(
+    State(state): State<AppState>,
+    Path(ill_id): Path<String>) -> Result<Json<CompleteIllCommandResponse>, ServerError> {
+    let req = CompleteIllCommandRequest { ill_id };
+    let svc = build_service(state).await;
+    let res = command_bus().register(CompleteIllCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/admin/sagas", get(find_sagas))
+        .route_layer(middleware::from_fn_with_state(state.clone(), authenticate))
+        .route("/ill", post(request_ill).get(find_ills))
+        .route("/ill/:id", get(find_ill_by_id))
+        .route("/ill/:id/approve", post(approve_ill))
+        .route("/ill/:id/reject", post(reject_ill))
+        .route("/ill/:id/receive", post(receive_ill))
+        .route("/ill/:id/return", post(return_ill))
+        .route("/ill/:id/ship", post(ship_ill))
+        .route("/ill/:id/complete", post(complete_ill));
+    with_common_layers(router, state)
+}
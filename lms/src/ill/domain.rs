@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use crate::core::library::{IllStatus, LibraryResult, PaginatedResult};
+use crate::ill::dto::{IllRequestDto, StuckWorkflowDto};
+
+pub mod model;
+pub mod query;
+pub mod service;
+
+// read side of interlibrary loans
+#[async_trait]
+pub(crate) trait IllQueryService: Sync + Send {
+    async fn find_ill_by_id(&self, ill_id: &str) -> LibraryResult<IllRequestDto>;
+    async fn find_ill_by_status(&self, status: IllStatus,
+                                page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<IllRequestDto>>;
+    // returns loans that have not moved on from requested, approved or returned since the given time, loans that
+    // are received stay with the patron until their due date and are followed up as overdue instead
+    async fn find_stuck(&self, stuck_before: NaiveDateTime, page_size: usize) -> LibraryResult<Vec<StuckWorkflowDto>>;
+}
+
+#[async_trait]
+pub(crate) trait IllService: IllQueryService {
+    // patrons can only request titles that are not in the catalog
+    async fn request(&self, ill: &IllRequestDto) -> LibraryResult<IllRequestDto>;
+    async fn approve(&self, ill_id: &str, approved_by: &str, lending_library: &str) -> LibraryResult<IllRequestDto>;
+    async fn reject(&self, ill_id: &str, rejected_by: &str) -> LibraryResult<IllRequestDto>;
+    // receives the book from lending library with the due date set by the lending library
+    async fn receive(&self, ill_id: &str, due_at: NaiveDateTime) -> LibraryResult<IllRequestDto>;
+    async fn returned(&self, ill_id: &str) -> LibraryResult<IllRequestDto>;
+    async fn ship_return(&self, ill_id: &str, tracking_number: &str) -> LibraryResult<IllRequestDto>;
+    // completes the loan once return shipment is delivered to the lending library
+    async fn complete(&self, ill_id: &str) -> LibraryResult<IllRequestDto>;
+    // compensates stuck loans, requests that no librarian approved are rejected so that the patron can request the
+    // title again and loans that wait on the lending library are alerted to staff, run daily by the admin binary
+    async fn compensate_stuck(&self, stuck_before: NaiveDateTime, page_size: usize) -> LibraryResult<Vec<StuckWorkflowDto>>;
+}
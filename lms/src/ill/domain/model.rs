@@ -0,0 +1,82 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::core::domain::Identifiable;
+use crate::core::library::{IllStatus, ShippingStatus};
+use crate::utils::date::serializer;
+
+// IllRequestEntity abstracts an interlibrary loan of a title that is borrowed from another library for a patron
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct IllRequestEntity {
+    pub ill_id: String,
+    pub version: i64,
+    pub branch_id: String,
+    pub patron_id: String,
+    pub isbn: String,
+    pub title: String,
+    pub ill_status: IllStatus,
+    pub lending_library: String,
+    pub approved_by: String,
+    // due date is set by the lending library when the book is received
+    pub due_at: Option<NaiveDateTime>,
+    pub shipping_status: ShippingStatus,
+    pub tracking_number: String,
+    pub approved_at: Option<NaiveDateTime>,
+    pub received_at: Option<NaiveDateTime>,
+    pub returned_at: Option<NaiveDateTime>,
+    pub completed_at: Option<NaiveDateTime>,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl IllRequestEntity {
+    pub fn new(patron_id: &str, isbn: &str, title: &str) -> Self {
+        Self {
+            ill_id: Uuid::new_v4().to_string(),
+            version: 0,
+            branch_id: Uuid::new_v4().to_string(),
+            patron_id: patron_id.to_string(),
+            isbn: isbn.to_string(),
+            title: title.to_string(),
+            ill_status: IllStatus::Requested,
+            lending_library: "".to_string(),
+            approved_by: "".to_string(),
+            due_at: None,
+            shipping_status: ShippingStatus::NotShipped,
+            tracking_number: "".to_string(),
+            approved_at: None,
+            received_at: None,
+            returned_at: None,
+            completed_at: None,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl Identifiable for IllRequestEntity {
+    fn id(&self) -> String {
+        self.ill_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::library::{IllStatus, ShippingStatus};
+    use crate::ill::domain::model::IllRequestEntity;
+
+    #[tokio::test]
+    async fn test_should_build_ill_request() {
+        let ill = IllRequestEntity::new("patron1", "isbn", "rare title");
+        assert_eq!("patron1", ill.patron_id.as_str());
+        assert_eq!(IllStatus::Requested, ill.ill_status);
+        assert_eq!(ShippingStatus::NotShipped, ill.shipping_status);
+        assert!(ill.due_at.is_none());
+    }
+}
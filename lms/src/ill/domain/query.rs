@@ -0,0 +1,49 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use crate::core::library::{IllStatus, LibraryResult, PaginatedResult};
+use crate::ill::domain::IllQueryService;
+use crate::ill::dto::{IllRequestDto, StuckWorkflowDto};
+use crate::ill::repository::IllRepository;
+
+pub(crate) struct IllQueryServiceImpl {
+    ill_repository: Box<dyn IllRepository>,
+}
+
+impl IllQueryServiceImpl {
+    pub(crate) fn new(ill_repository: Box<dyn IllRepository>) -> Self {
+        Self {
+            ill_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl IllQueryService for IllQueryServiceImpl {
+    async fn find_ill_by_id(&self, ill_id: &str) -> LibraryResult<IllRequestDto> {
+        self.ill_repository.get(ill_id).await.map(|ill| IllRequestDto::from(&ill))
+    }
+
+    async fn find_ill_by_status(&self, status: IllStatus,
+                                page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<IllRequestDto>> {
+        let res = self.ill_repository.find_by_status(status, page, page_size).await?;
+        let records = res.records.iter().map(IllRequestDto::from).collect();
+        Ok(PaginatedResult::new(page, page_size, res.next_page, records))
+    }
+
+    async fn find_stuck(&self, stuck_before: NaiveDateTime, page_size: usize) -> LibraryResult<Vec<StuckWorkflowDto>> {
+        let mut stuck = vec![];
+        for status in [IllStatus::Requested, IllStatus::Approved, IllStatus::Returned] {
+            let mut page: Option<String> = None;
+            loop {
+                let res = self.ill_repository.find_by_status(status, page.as_deref(), page_size).await?;
+                stuck.extend(res.records.iter().filter(|ill| ill.updated_at < stuck_before)
+                    .map(|ill| StuckWorkflowDto::from_ill(&IllRequestDto::from(ill), None)));
+                page = res.next_page;
+                if page.is_none() {
+                    break;
+                }
+            }
+        }
+        Ok(stuck)
+    }
+}
@@ -0,0 +1,354 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use tracing::warn;
+
+use crate::catalog::domain::CatalogService;
+use crate::core::domain::Configuration;
+use crate::core::events::DomainEvent;
+use crate::core::ids::PatronId;
+use crate::core::library::{IllStatus, LibraryError, LibraryResult, PaginatedResult, ShippingStatus};
+use crate::core::repository::ReadConsistency;
+use crate::gateway::events::EventPublisher;
+use crate::ill::domain::{IllQueryService, IllService};
+use crate::ill::domain::model::IllRequestEntity;
+use crate::ill::dto::{IllRequestDto, StuckWorkflowDto};
+use crate::ill::repository::IllRepository;
+use crate::patrons::domain::PatronService;
+use crate::patrons::Patron;
+
+// recorded as the librarian of requests that the watchdog rejected
+const WATCHDOG: &str = "ill_watchdog";
+
+pub(crate) struct IllServiceImpl {
+    branch_id: String,
+    ill_repository: Box<dyn IllRepository>,
+    patron_service: Box<dyn PatronService>,
+    catalog_service: Box<dyn CatalogService>,
+    events_publisher: Box<dyn EventPublisher>,
+    query_service: Box<dyn IllQueryService>,
+}
+
+impl IllServiceImpl {
+    pub(crate) fn new(config: &Configuration, ill_repository: Box<dyn IllRepository>,
+                      patron_service: Box<dyn PatronService>, catalog_service: Box<dyn CatalogService>,
+                      events_publisher: Box<dyn EventPublisher>,
+                      query_service: Box<dyn IllQueryService>) -> Self {
+        Self {
+            branch_id: config.branch_id.to_string(),
+            ill_repository,
+            patron_service,
+            catalog_service,
+            events_publisher,
+            query_service,
+        }
+    }
+
+    async fn validate_librarian(&self, patron_id: &str) -> LibraryResult<()> {
+        let patron = self.patron_service.find_patron_by_id(&PatronId::new(patron_id)).await?;
+        if !patron.is_librarian() && !patron.is_admin() {
+            return Err(LibraryError::validation(format!("patron {} cannot manage interlibrary loans",
+                                                        patron_id).as_str(), Some("400".to_string())));
+        }
+        Ok(())
+    }
+
+    async fn update(&self, ill: &mut IllRequestEntity, event_name: &str) -> LibraryResult<IllRequestDto> {
+        self.ill_repository.update(ill).await?;
+        ill.version += 1;
+        let dto = IllRequestDto::from(&*ill);
+        let _ = self.events_publisher.publish(&DomainEvent::updated(
+            event_name, "ill", dto.ill_id.as_str(), &HashMap::new(), &dto)?).await?;
+        Ok(dto)
+    }
+
+    // moves interlibrary loan to next status and publishes event of the transition
+    async fn transition(&self, ill: &mut IllRequestEntity, next: IllStatus) -> LibraryResult<IllRequestDto> {
+        if !ill.ill_status.can_transition_to(next) {
+            return Err(LibraryError::validation(format!("interlibrary loan {} cannot be moved from {} to {}",
+                                                        ill.ill_id, ill.ill_status, next).as_str(), Some("400".to_string())));
+        }
+        let now = Utc::now().naive_utc();
+        ill.ill_status = next;
+        match next {
+            IllStatus::Approved | IllStatus::Rejected => ill.approved_at = Some(now),
+            IllStatus::Received => ill.received_at = Some(now),
+            IllStatus::Returned => ill.returned_at = Some(now),
+            IllStatus::Completed => ill.completed_at = Some(now),
+            IllStatus::Requested => {}
+        }
+        self.update(ill, format!("ill_{}", next.to_string().to_lowercase()).as_str()).await
+    }
+}
+
+#[async_trait]
+impl IllService for IllServiceImpl {
+    async fn request(&self, ill: &IllRequestDto) -> LibraryResult<IllRequestDto> {
+        if ill.isbn.is_empty() || ill.title.is_empty() {
+            return Err(LibraryError::validation("isbn and title are required", Some("400".to_string())));
+        }
+        let _ = self.patron_service.find_patron_by_id(&PatronId::new(ill.patron_id.as_str())).await?;
+        if !self.catalog_service.find_book_by_isbn(ill.isbn.as_str(), &ReadConsistency::Eventual).await?.is_empty() {
+            return Err(LibraryError::validation(format!("book with isbn {} is already in the catalog",
+                                                        ill.isbn).as_str(), Some("400".to_string())));
+        }
+        let mut entity = IllRequestEntity::from(ill);
+        entity.branch_id = self.branch_id.to_string();
+        entity.ill_status = IllStatus::Requested;
+        entity.shipping_status = ShippingStatus::NotShipped;
+        self.ill_repository.create(&entity).await?;
+        let dto = IllRequestDto::from(&entity);
+        let _ = self.events_publisher.publish(&DomainEvent::added(
+            "ill_requested", "ill", dto.ill_id.as_str(), &HashMap::new(), &dto)?).await?;
+        Ok(dto)
+    }
+
+    async fn approve(&self, ill_id: &str, approved_by: &str, lending_library: &str) -> LibraryResult<IllRequestDto> {
+        if lending_library.is_empty() {
+            return Err(LibraryError::validation("lending library is required", Some("400".to_string())));
+        }
+        self.validate_librarian(approved_by).await?;
+        let mut ill = self.ill_repository.get(ill_id).await?;
+        ill.approved_by = approved_by.to_string();
+        ill.lending_library = lending_library.to_string();
+        self.transition(&mut ill, IllStatus::Approved).await
+    }
+
+    async fn reject(&self, ill_id: &str, rejected_by: &str) -> LibraryResult<IllRequestDto> {
+        self.validate_librarian(rejected_by).await?;
+        let mut ill = self.ill_repository.get(ill_id).await?;
+        ill.approved_by = rejected_by.to_string();
+        self.transition(&mut ill, IllStatus::Rejected).await
+    }
+
+    async fn receive(&self, ill_id: &str, due_at: NaiveDateTime) -> LibraryResult<IllRequestDto> {
+        if due_at <= Utc::now().naive_utc() {
+            return Err(LibraryError::validation("due date must be in future", Some("400".to_string())));
+        }
+        let mut ill = self.ill_repository.get(ill_id).await?;
+        ill.due_at = Some(due_at);
+        self.transition(&mut ill, IllStatus::Received).await
+    }
+
+    async fn returned(&self, ill_id: &str) -> LibraryResult<IllRequestDto> {
+        let mut ill = self.ill_repository.get(ill_id).await?;
+        self.transition(&mut ill, IllStatus::Returned).await
+    }
+
+    async fn ship_return(&self, ill_id: &str, tracking_number: &str) -> LibraryResult<IllRequestDto> {
+        let mut ill = self.ill_repository.get(ill_id).await?;
+        if ill.ill_status != IllStatus::Returned || ill.shipping_status != ShippingStatus::NotShipped {
+            return Err(LibraryError::validation(format!("interlibrary loan {} is not ready for return shipping",
+                                                        ill_id).as_str(), Some("400".to_string())));
+        }
+        ill.shipping_status = ShippingStatus::InTransit;
+        ill.tracking_number = tracking_number.to_string();
+        self.update(&mut ill, "ill_return_shipped").await
+    }
+
+    async fn complete(&self, ill_id: &str) -> LibraryResult<IllRequestDto> {
+        let mut ill = self.ill_repository.get(ill_id).await?;
+        if ill.shipping_status != ShippingStatus::InTransit {
+            return Err(LibraryError::validation(format!("return of interlibrary loan {} is not shipped",
+                                                        ill_id).as_str(), Some("400".to_string())));
+        }
+        ill.shipping_status = ShippingStatus::Delivered;
+        self.transition(&mut ill, IllStatus::Completed).await
+    }
+
+    async fn compensate_stuck(&self, stuck_before: NaiveDateTime, page_size: usize) -> LibraryResult<Vec<StuckWorkflowDto>> {
+        let mut compensated = vec![];
+        for stuck in self.find_stuck(stuck_before, page_size).await? {
+            if stuck.state == IllStatus::Requested.to_string() {
+                let mut ill = self.ill_repository.get(stuck.workflow_id.as_str()).await?;
+                ill.approved_by = WATCHDOG.to_string();
+                let _ = self.transition(&mut ill, IllStatus::Rejected).await?;
+                compensated.push(StuckWorkflowDto { compensation: Some("rejected".to_string()), ..stuck });
+            } else {
+                warn!(workflow = stuck.workflow.as_str(), workflow_id = stuck.workflow_id.as_str(),
+                      state = stuck.state.as_str(), stuck_since = %stuck.stuck_since, "stuck workflow");
+                compensated.push(stuck);
+            }
+        }
+        Ok(compensated)
+    }
+}
+
+#[async_trait]
+impl IllQueryService for IllServiceImpl {
+    async fn find_ill_by_id(&self, ill_id: &str) -> LibraryResult<IllRequestDto> {
+        self.query_service.find_ill_by_id(ill_id).await
+    }
+
+    async fn find_ill_by_status(&self, status: IllStatus,
+                                page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<IllRequestDto>> {
+        self.query_service.find_ill_by_status(status, page, page_size).await
+    }
+
+    async fn find_stuck(&self, stuck_before: NaiveDateTime, page_size: usize) -> LibraryResult<Vec<StuckWorkflowDto>> {
+        self.query_service.find_stuck(stuck_before, page_size).await
+    }
+}
+
+impl From<&IllRequestDto> for IllRequestEntity {
+    fn from(other: &IllRequestDto) -> IllRequestEntity {
+        IllRequestEntity {
+            ill_id: other.ill_id.to_string(),
+            version: other.version,
+            branch_id: other.branch_id.to_string(),
+            patron_id: other.patron_id.to_string(),
+            isbn: other.isbn.to_string(),
+            title: other.title.to_string(),
+            ill_status: other.ill_status,
+            lending_library: other.lending_library.to_string(),
+            approved_by: other.approved_by.to_string(),
+            due_at: other.due_at,
+            shipping_status: other.shipping_status,
+            tracking_number: other.tracking_number.to_string(),
+            approved_at: other.approved_at,
+            received_at: other.received_at,
+            returned_at: other.returned_at,
+            completed_at: other.completed_at,
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+impl From<&IllRequestEntity> for IllRequestDto {
+    fn from(other: &IllRequestEntity) -> IllRequestDto {
+        IllRequestDto {
+            ill_id: other.ill_id.to_string(),
+            version: other.version,
+            branch_id: other.branch_id.to_string(),
+            patron_id: other.patron_id.to_string(),
+            isbn: other.isbn.to_string(),
+            title: other.title.to_string(),
+            ill_status: other.ill_status,
+            lending_library: other.lending_library.to_string(),
+            approved_by: other.approved_by.to_string(),
+            due_at: other.due_at,
+            shipping_status: other.shipping_status,
+            tracking_number: other.tracking_number.to_string(),
+            approved_at: other.approved_at,
+            received_at: other.received_at,
+            returned_at: other.returned_at,
+            completed_at: other.completed_at,
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::core::domain::Configuration;
+    use crate::core::library::{IllStatus, PartyKind, Role, ShippingStatus};
+    use crate::core::repository::RepositoryStore;
+    use crate::ill::domain::IllService;
+    use crate::ill::dto::IllRequestDto;
+    use crate::ill::factory;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::parties::repository::PartyRepository;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn IllService> {
+        factory::create_ill_service(&Configuration::new("test"), store).await
+    }
+
+    async fn party_repo(store: RepositoryStore) -> Box<dyn PartyRepository> {
+        create_party_repository(store).await
+    }
+
+    async fn add_party(store: RepositoryStore, email: &str, role: Role) -> PartyEntity {
+        let mut party = PartyEntity::new(PartyKind::Patron, email);
+        party.group_roles = vec![role.to_string()];
+        let _ = party_repo(store).await.create(&party).await.expect("should create party");
+        party
+    }
+
+    #[tokio::test]
+    async fn test_should_request_approve_receive_return_ill() {
+        let store = test_store(module_path!(), "test_should_request_approve_receive_return_ill");
+        let ill_svc = sut_svc(store).await;
+        let patron = add_party(store, "ill_patron@example.com", Role::Regular).await;
+        let librarian = add_party(store, "ill_librarian@example.com", Role::Librarian).await;
+
+        let ill = ill_svc.request(&IllRequestDto::new(patron.party_id.as_str(), "ill_isbn", "rare title"))
+            .await.expect("should request ill");
+        assert_eq!(IllStatus::Requested, ill.ill_status);
+        // regular patrons cannot approve requests
+        assert!(ill_svc.approve(ill.ill_id.as_str(), patron.party_id.as_str(), "State Library").await.is_err());
+        assert!(ill_svc.receive(ill.ill_id.as_str(), Utc::now().naive_utc() + Duration::days(21)).await.is_err());
+
+        let approved = ill_svc.approve(ill.ill_id.as_str(), librarian.party_id.as_str(), "State Library").await.expect("should approve");
+        assert_eq!("State Library", approved.lending_library.as_str());
+        assert!(ill_svc.receive(ill.ill_id.as_str(), Utc::now().naive_utc() - Duration::days(1)).await.is_err());
+        let due_at = Utc::now().naive_utc() + Duration::days(21);
+        let received = ill_svc.receive(ill.ill_id.as_str(), due_at).await.expect("should receive");
+        assert_eq!(Some(due_at), received.due_at);
+
+        // return cannot be shipped before the patron returns the book
+        assert!(ill_svc.ship_return(ill.ill_id.as_str(), "TRACK1").await.is_err());
+        let _ = ill_svc.returned(ill.ill_id.as_str()).await.expect("should return");
+        assert!(ill_svc.complete(ill.ill_id.as_str()).await.is_err());
+        let shipped = ill_svc.ship_return(ill.ill_id.as_str(), "TRACK1").await.expect("should ship");
+        assert_eq!(ShippingStatus::InTransit, shipped.shipping_status);
+        let completed = ill_svc.complete(ill.ill_id.as_str()).await.expect("should complete");
+        assert_eq!(IllStatus::Completed, completed.ill_status);
+        assert_eq!(ShippingStatus::Delivered, completed.shipping_status);
+
+        let loaded = ill_svc.find_ill_by_id(ill.ill_id.as_str()).await.expect("should find ill");
+        assert_eq!("TRACK1", loaded.tracking_number.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_should_reject_ill() {
+        let store = test_store(module_path!(), "test_should_reject_ill");
+        let ill_svc = sut_svc(store).await;
+        let patron = add_party(store, "ill_patron2@example.com", Role::Regular).await;
+        let librarian = add_party(store, "ill_librarian2@example.com", Role::Librarian).await;
+
+        let ill = ill_svc.request(&IllRequestDto::new(patron.party_id.as_str(), "ill_isbn2", "rare title"))
+            .await.expect("should request ill");
+        let rejected = ill_svc.reject(ill.ill_id.as_str(), librarian.party_id.as_str()).await.expect("should reject");
+        assert_eq!(IllStatus::Rejected, rejected.ill_status);
+        assert!(ill_svc.approve(ill.ill_id.as_str(), librarian.party_id.as_str(), "State Library").await.is_err());
+        assert!(ill_svc.request(&IllRequestDto::new(patron.party_id.as_str(), "", "")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_compensate_stuck_ills() {
+        let store = test_store(module_path!(), "test_should_compensate_stuck_ills");
+        let ill_svc = sut_svc(store).await;
+        let patron = add_party(store, "ill_patron3@example.com", Role::Regular).await;
+        let librarian = add_party(store, "ill_librarian3@example.com", Role::Librarian).await;
+        let requested = ill_svc.request(&IllRequestDto::new(patron.party_id.as_str(), "ill_isbn3", "rare title"))
+            .await.expect("should request ill");
+        let approved = ill_svc.request(&IllRequestDto::new(patron.party_id.as_str(), "ill_isbn4", "rarer title"))
+            .await.expect("should request ill");
+        let _ = ill_svc.approve(approved.ill_id.as_str(), librarian.party_id.as_str(), "State Library").await.expect("should approve");
+
+        // nothing is stuck within the timeout
+        let stuck = ill_svc.find_stuck(Utc::now().naive_utc() - Duration::days(14), 10).await.expect("should find stuck");
+        assert!(stuck.is_empty());
+
+        let stuck_before = Utc::now().naive_utc() + Duration::minutes(1);
+        let compensated = ill_svc.compensate_stuck(stuck_before, 10).await.expect("should compensate");
+        assert_eq!(2, compensated.len());
+        let rejected = compensated.iter().find(|s| s.workflow_id == requested.ill_id).expect("should reject request");
+        assert_eq!(Some("rejected".to_string()), rejected.compensation);
+        assert_eq!(IllStatus::Rejected, ill_svc.find_ill_by_id(requested.ill_id.as_str()).await.expect("should find ill").ill_status);
+        let alerted = compensated.iter().find(|s| s.workflow_id == approved.ill_id).expect("should alert approved");
+        assert_eq!("Approved", alerted.state.as_str());
+        assert_eq!(None, alerted.compensation);
+
+        // rejected requests are no longer stuck
+        let stuck = ill_svc.find_stuck(stuck_before, 10).await.expect("should find stuck");
+        assert_eq!(vec![approved.ill_id.clone()], stuck.iter().map(|s| s.workflow_id.clone()).collect::<Vec<String>>());
+    }
+}
@@ -0,0 +1,107 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use uuid::Uuid;
+use crate::core::domain::Identifiable;
+use crate::core::library::{IllStatus, ShippingStatus};
+use crate::utils::date::{serializer, Rfc3339};
+
+// IllRequestDto abstracts data transfer object for interlibrary loan requests
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct IllRequestDto {
+    pub ill_id: String,
+    pub version: i64,
+    pub branch_id: String,
+    pub patron_id: String,
+    pub isbn: String,
+    pub title: String,
+    pub ill_status: IllStatus,
+    pub lending_library: String,
+    pub approved_by: String,
+    pub due_at: Option<NaiveDateTime>,
+    pub shipping_status: ShippingStatus,
+    pub tracking_number: String,
+    pub approved_at: Option<NaiveDateTime>,
+    pub received_at: Option<NaiveDateTime>,
+    pub returned_at: Option<NaiveDateTime>,
+    pub completed_at: Option<NaiveDateTime>,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl IllRequestDto {
+    pub fn new(patron_id: &str, isbn: &str, title: &str) -> Self {
+        Self {
+            ill_id: Uuid::new_v4().to_string(),
+            version: 0,
+            branch_id: Uuid::new_v4().to_string(),
+            patron_id: patron_id.to_string(),
+            isbn: isbn.to_string(),
+            title: title.to_string(),
+            ill_status: IllStatus::Requested,
+            lending_library: "".to_string(),
+            approved_by: "".to_string(),
+            due_at: None,
+            shipping_status: ShippingStatus::NotShipped,
+            tracking_number: "".to_string(),
+            approved_at: None,
+            received_at: None,
+            returned_at: None,
+            completed_at: None,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+// StuckWorkflowDto reports an interlibrary loan that stayed in an intermediate state of its workflow longer than
+// the timeout along with the compensation of the watchdog, loans without compensation are only alerted
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StuckWorkflowDto {
+    pub workflow: String,
+    pub workflow_id: String,
+    pub state: String,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub stuck_since: NaiveDateTime,
+    pub compensation: Option<String>,
+}
+
+impl StuckWorkflowDto {
+    pub(crate) fn from_ill(ill: &IllRequestDto, compensation: Option<&str>) -> Self {
+        Self {
+            workflow: "ill".to_string(),
+            workflow_id: ill.ill_id.to_string(),
+            state: ill.ill_status.to_string(),
+            stuck_since: ill.updated_at,
+            compensation: compensation.map(str::to_string),
+        }
+    }
+}
+
+impl Identifiable for IllRequestDto {
+    fn id(&self) -> String {
+        self.ill_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::library::IllStatus;
+    use crate::ill::dto::IllRequestDto;
+
+    #[tokio::test]
+    async fn test_should_build_ill_request() {
+        let ill = IllRequestDto::new("patron1", "isbn", "rare title");
+        assert_eq!("rare title", ill.title.as_str());
+        assert_eq!(IllStatus::Requested, ill.ill_status);
+    }
+}
@@ -0,0 +1,41 @@
+use crate::catalog::factory::create_catalog_service;
+use crate::core::domain::Configuration;
+use crate::core::repository::RepositoryStore;
+use crate::gateway::factory::create_publisher;
+use crate::ill::domain::{IllQueryService, IllService};
+use crate::ill::domain::query::IllQueryServiceImpl;
+use crate::ill::domain::service::IllServiceImpl;
+use crate::ill::factory;
+use crate::ill::repository::ddb_ill_repository::DDBIllRepository;
+use crate::ill::repository::IllRepository;
+use crate::patrons::factory::create_patron_service;
+use crate::utils::ddb::{build_db_client, create_table};
+
+pub(crate) async fn create_ill_repository(store: RepositoryStore) -> Box<dyn IllRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBIllRepository::new(client, "ill_requests", "ill_requests_ndx"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("ill_requests");
+            let _ = create_table(&client, table_name.as_str(), "ill_id", "ill_status", "patron_id").await;
+            Box::new(DDBIllRepository::new(client, table_name.as_str(), store.table_name("ill_requests_ndx").as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_ill_query_service(_config: &Configuration, store: RepositoryStore) -> Box<dyn IllQueryService> {
+    let ill_repo = factory::create_ill_repository(store).await;
+    Box::new(IllQueryServiceImpl::new(ill_repo))
+}
+
+pub(crate) async fn create_ill_service(config: &Configuration, store: RepositoryStore) -> Box<dyn IllService> {
+    let ill_repo = factory::create_ill_repository(store).await;
+    let patron_svc = create_patron_service(config, store).await;
+    let catalog_svc = create_catalog_service(config, store).await;
+    let publisher = create_publisher(store.gateway_publisher()).await;
+    let query_svc = create_ill_query_service(config, store).await;
+    Box::new(IllServiceImpl::new(config, ill_repo, patron_svc, catalog_svc, publisher, query_svc))
+}
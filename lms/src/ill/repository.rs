@@ -0,0 +1,14 @@
+pub mod ddb_ill_repository;
+
+use async_trait::async_trait;
+use crate::core::library::{IllStatus, LibraryResult, PaginatedResult};
+use crate::ill::domain::model::IllRequestEntity;
+
+#[async_trait]
+pub(crate) trait IllRepository: Sync + Send {
+    async fn create(&self, entity: &IllRequestEntity) -> LibraryResult<usize>;
+    async fn update(&self, entity: &IllRequestEntity) -> LibraryResult<usize>;
+    async fn get(&self, ill_id: &str) -> LibraryResult<IllRequestEntity>;
+    async fn find_by_status(&self, status: IllStatus,
+                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<IllRequestEntity>>;
+}
@@ -0,0 +1,183 @@
+use std::cmp;
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::core::library::{IllStatus, LibraryError, LibraryResult, PaginatedResult, ShippingStatus};
+use crate::ill::domain::model::IllRequestEntity;
+use crate::ill::repository::IllRepository;
+use crate::utils::ddb::{from_ddb, opt_string_date, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, string_date, to_ddb_page};
+
+#[derive(Debug)]
+pub(crate) struct DDBIllRepository {
+    client: Client,
+    table_name: String,
+    index_name: String,
+}
+
+impl DDBIllRepository {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl IllRepository for DDBIllRepository {
+    async fn create(&self, entity: &IllRequestEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(ill_id)")
+            .set_item(Some(parse_item(val)?))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn update(&self, entity: &IllRequestEntity) -> LibraryResult<usize> {
+        let now = Utc::now().naive_utc();
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .update_item()
+            .table_name(table_name)
+            .key("ill_id", AttributeValue::S(entity.ill_id.clone()))
+            .update_expression("SET version = :version, ill_status = :ill_status, lending_library = :lending_library, approved_by = :approved_by, due_at = :due_at, shipping_status = :shipping_status, tracking_number = :tracking_number, approved_at = :approved_at, received_at = :received_at, returned_at = :returned_at, completed_at = :completed_at, updated_at = :updated_at")
+            .expression_attribute_values(":old_version", AttributeValue::N(entity.version.to_string()))
+            .expression_attribute_values(":version", AttributeValue::N((entity.version + 1).to_string()))
+            .expression_attribute_values(":ill_status", AttributeValue::S(entity.ill_status.to_string()))
+            .expression_attribute_values(":lending_library", AttributeValue::S(entity.lending_library.to_string()))
+            .expression_attribute_values(":approved_by", AttributeValue::S(entity.approved_by.to_string()))
+            .expression_attribute_values(":due_at", opt_string_date(entity.due_at))
+            .expression_attribute_values(":shipping_status", AttributeValue::S(entity.shipping_status.to_string()))
+            .expression_attribute_values(":tracking_number", AttributeValue::S(entity.tracking_number.to_string()))
+            .expression_attribute_values(":approved_at", opt_string_date(entity.approved_at))
+            .expression_attribute_values(":received_at", opt_string_date(entity.received_at))
+            .expression_attribute_values(":returned_at", opt_string_date(entity.returned_at))
+            .expression_attribute_values(":completed_at", opt_string_date(entity.completed_at))
+            .expression_attribute_values(":updated_at", string_date(now))
+            .condition_expression("attribute_exists(version) AND version = :old_version")
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn get(&self, ill_id: &str) -> LibraryResult<IllRequestEntity> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .query()
+            .table_name(table_name)
+            .limit(1)
+            .consistent_read(true)
+            .key_condition_expression("ill_id = :ill_id")
+            .expression_attribute_values(":ill_id", AttributeValue::S(ill_id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).and_then(|req| {
+            if let Some(map) = req.items.as_ref().and_then(|items| items.first()) {
+                return Ok(IllRequestEntity::from(map));
+            }
+            Err(LibraryError::not_found(format!("interlibrary loan not found for {}", ill_id).as_str()))
+        })
+    }
+
+    async fn find_by_status(&self, status: IllStatus,
+                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<IllRequestEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let predicate = HashMap::from([("ill_status".to_string(), status.to_string())]);
+        self.client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .limit(cmp::min(page_size, 500) as i32)
+            .consistent_read(false)
+            .set_exclusive_start_key(to_ddb_page(page, &predicate))
+            .key_condition_expression("ill_status = :ill_status")
+            .expression_attribute_values(":ill_status", AttributeValue::S(status.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(IllRequestEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for IllRequestEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        IllRequestEntity {
+            ill_id: parse_string_attribute("ill_id", map).unwrap_or_else(|| String::from("")),
+            version: parse_number_attribute("version", map),
+            branch_id: parse_string_attribute("branch_id", map).unwrap_or_else(|| String::from("")),
+            patron_id: parse_string_attribute("patron_id", map).unwrap_or_else(|| String::from("")),
+            isbn: parse_string_attribute("isbn", map).unwrap_or_else(|| String::from("")),
+            title: parse_string_attribute("title", map).unwrap_or_else(|| String::from("")),
+            ill_status: IllStatus::from(parse_string_attribute("ill_status", map).unwrap_or_else(|| String::from(""))),
+            lending_library: parse_string_attribute("lending_library", map).unwrap_or_else(|| String::from("")),
+            approved_by: parse_string_attribute("approved_by", map).unwrap_or_else(|| String::from("")),
+            due_at: parse_date_attribute("due_at", map),
+            shipping_status: ShippingStatus::from(parse_string_attribute("shipping_status", map).unwrap_or_else(|| String::from(""))),
+            tracking_number: parse_string_attribute("tracking_number", map).unwrap_or_else(|| String::from("")),
+            approved_at: parse_date_attribute("approved_at", map),
+            received_at: parse_date_attribute("received_at", map),
+            returned_at: parse_date_attribute("returned_at", map),
+            completed_at: parse_date_attribute("completed_at", map),
+            created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+    use chrono::{Duration, Utc};
+
+    use crate::core::library::{IllStatus, ShippingStatus};
+    use crate::core::repository::RepositoryStore;
+    use crate::ill::domain::model::IllRequestEntity;
+    use crate::ill::repository::ddb_ill_repository::DDBIllRepository;
+    use crate::ill::repository::IllRepository;
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("ill_requests").as_str(), "ill_id", "ill_status", "patron_id").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_create_update_find_ill_requests() {
+        let store = test_store(module_path!(), "test_should_create_update_find_ill_requests");
+        let repo = DDBIllRepository::new(build_client(store).await, store.table_name("ill_requests").as_str(), store.table_name("ill_requests_ndx").as_str());
+        let mut ill = IllRequestEntity::new("patron1", "isbn", "rare title");
+        assert_eq!(1, repo.create(&ill).await.expect("should create ill request"));
+        assert!(repo.create(&ill).await.is_err());
+
+        ill.ill_status = IllStatus::Received;
+        ill.lending_library = "State Library".to_string();
+        ill.due_at = Some(Utc::now().naive_utc() + Duration::days(21));
+        ill.shipping_status = ShippingStatus::InTransit;
+        assert_eq!(1, repo.update(&ill).await.expect("should update ill request"));
+        // stale version should not be updated
+        assert!(repo.update(&ill).await.is_err());
+
+        let loaded = repo.get(ill.ill_id.as_str()).await.expect("should get ill request");
+        assert_eq!(1, loaded.version);
+        assert_eq!("State Library", loaded.lending_library.as_str());
+        assert_eq!(ShippingStatus::InTransit, loaded.shipping_status);
+        assert!(loaded.due_at.is_some());
+
+        let res = repo.find_by_status(IllStatus::Received, None, 10).await.expect("should find ill requests");
+        assert_eq!(1, res.records.len());
+        let res = repo.find_by_status(IllStatus::Requested, None, 10).await.expect("should find ill requests");
+        assert_eq!(0, res.records.len());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod inventory_report_cmd;
+pub mod reconcile_inventory_cmd;
+pub mod scan_items_cmd;
+pub mod start_inventory_cmd;
@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::inventory::domain::InventoryQueryService;
+use crate::inventory::dto::InventoryReportDto;
+
+const DEFAULT_PAGE_SIZE: usize = 200;
+
+pub(crate) struct InventoryReportCommand {
+    inventory_service: Box<dyn InventoryQueryService>,
+}
+
+impl InventoryReportCommand {
+    pub(crate) fn new(inventory_service: Box<dyn InventoryQueryService>) -> Self {
+        Self {
+            inventory_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct InventoryReportCommandRequest {
+    #[serde(default)]
+    pub(crate) session_id: String,
+    pub(crate) page: Option<String>,
+    pub(crate) page_size: Option<usize>,
+}
+
+impl InventoryReportCommandRequest {
+    pub fn new(session_id: &str) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            page: None,
+            page_size: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct InventoryReportCommandResponse {
+    pub report: InventoryReportDto,
+}
+
+impl InventoryReportCommandResponse {
+    pub fn new(report: InventoryReportDto) -> Self {
+        Self {
+            report,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<InventoryReportCommandRequest, InventoryReportCommandResponse> for InventoryReportCommand {
+    async fn execute(&self, req: InventoryReportCommandRequest) -> Result<InventoryReportCommandResponse, CommandError> {
+        self.inventory_service.report(req.session_id.as_str(), req.page.as_deref(),
+                                      req.page_size.unwrap_or(DEFAULT_PAGE_SIZE))
+            .await.map_err(CommandError::from).map(InventoryReportCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::inventory::command::inventory_report_cmd::{InventoryReportCommand, InventoryReportCommandRequest};
+    use crate::inventory::domain::InventoryService;
+    use crate::inventory::factory::{create_inventory_service, create_inventory_query_service};
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn InventoryService> {
+        create_inventory_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> InventoryReportCommand {
+        let svc = create_inventory_query_service(&Configuration::new("test"), store).await;
+        InventoryReportCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_inventory_report() {
+        let store = test_store(module_path!(), "test_should_run_inventory_report");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Employee, "inventory_report@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let session = svc.start_session(librarian.party_id.as_str(), Some("Report Shelf")).await.expect("should start session");
+        let _ = svc.scan(session.session_id.as_str(), "Report Shelf", &["report_unknown_copy".to_string()])
+            .await.expect("should scan");
+        let res = sut_cmd.execute(InventoryReportCommandRequest::new(session.session_id.as_str()))
+            .await.expect("should return report");
+        assert_eq!(1, res.report.unexpected.len());
+        assert!(res.report.missing.is_empty());
+    }
+}
@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::inventory::domain::InventoryService;
+use crate::inventory::dto::InventoryScanDto;
+
+const DEFAULT_PAGE_SIZE: usize = 200;
+
+pub(crate) struct ReconcileInventoryCommand {
+    inventory_service: Box<dyn InventoryService>,
+}
+
+impl ReconcileInventoryCommand {
+    pub(crate) fn new(inventory_service: Box<dyn InventoryService>) -> Self {
+        Self {
+            inventory_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ReconcileInventoryCommandRequest {
+    #[serde(default)]
+    pub(crate) session_id: String,
+    pub(crate) page: Option<String>,
+    pub(crate) page_size: Option<usize>,
+}
+
+impl ReconcileInventoryCommandRequest {
+    pub fn new(session_id: &str, page: Option<&str>) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            page: page.map(|p| p.to_string()),
+            page_size: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ReconcileInventoryCommandResponse {
+    pub missing: Vec<InventoryScanDto>,
+    pub next_page: Option<String>,
+}
+
+impl ReconcileInventoryCommandResponse {
+    pub fn new(missing: Vec<InventoryScanDto>, next_page: Option<String>) -> Self {
+        Self {
+            missing,
+            next_page,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<ReconcileInventoryCommandRequest, ReconcileInventoryCommandResponse> for ReconcileInventoryCommand {
+    async fn execute(&self, req: ReconcileInventoryCommandRequest) -> Result<ReconcileInventoryCommandResponse, CommandError> {
+        self.inventory_service.reconcile(req.session_id.as_str(), req.page.as_deref(),
+                                         req.page_size.unwrap_or(DEFAULT_PAGE_SIZE))
+            .await.map_err(CommandError::from).map(|res| ReconcileInventoryCommandResponse::new(res.records, res.next_page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::books::domain::model::BookEntity;
+    use crate::books::factory::create_book_repository;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{BookStatus, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::inventory::command::reconcile_inventory_cmd::{ReconcileInventoryCommand, ReconcileInventoryCommandRequest};
+    use crate::inventory::domain::InventoryService;
+    use crate::inventory::factory::create_inventory_service;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn InventoryService> {
+        create_inventory_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> ReconcileInventoryCommand {
+        let svc = create_inventory_service(&Configuration::new("test"), store).await;
+        ReconcileInventoryCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_reconcile_inventory() {
+        let store = test_store(module_path!(), "test_should_run_reconcile_inventory");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Employee, "reconcile_inventory@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let mut book = BookEntity::new("isbn", "unscanned", BookStatus::Available);
+        book.shelf_location = "Reconcile Shelf".to_string();
+        let _ = create_book_repository(store).await.create(&book).await.expect("should create book");
+        let session = svc.start_session(librarian.party_id.as_str(), Some("Reconcile Shelf")).await.expect("should start session");
+
+        let mut missing = vec![];
+        let mut next_page: Option<String> = None;
+        loop {
+            let res = sut_cmd.execute(ReconcileInventoryCommandRequest::new(session.session_id.as_str(), next_page.as_deref()))
+                .await.expect("should reconcile inventory");
+            missing.extend(res.missing);
+            next_page = res.next_page;
+            if next_page.is_none() {
+                break;
+            }
+        }
+        assert_eq!(1, missing.len());
+        assert_eq!(book.book_id, missing[0].book_id);
+    }
+}
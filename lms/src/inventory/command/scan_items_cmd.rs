@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::inventory::domain::InventoryService;
+use crate::inventory::dto::InventoryScanDto;
+
+pub(crate) struct ScanItemsCommand {
+    inventory_service: Box<dyn InventoryService>,
+}
+
+impl ScanItemsCommand {
+    pub(crate) fn new(inventory_service: Box<dyn InventoryService>) -> Self {
+        Self {
+            inventory_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ScanItemsCommandRequest {
+    #[serde(default)]
+    pub(crate) session_id: String,
+    pub(crate) scanned_location: String,
+    pub(crate) book_ids: Vec<String>,
+}
+
+impl ScanItemsCommandRequest {
+    pub fn new(session_id: &str, scanned_location: &str, book_ids: Vec<String>) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            scanned_location: scanned_location.to_string(),
+            book_ids,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ScanItemsCommandResponse {
+    pub scans: Vec<InventoryScanDto>,
+}
+
+impl ScanItemsCommandResponse {
+    pub fn new(scans: Vec<InventoryScanDto>) -> Self {
+        Self {
+            scans,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<ScanItemsCommandRequest, ScanItemsCommandResponse> for ScanItemsCommand {
+    async fn execute(&self, req: ScanItemsCommandRequest) -> Result<ScanItemsCommandResponse, CommandError> {
+        self.inventory_service.scan(req.session_id.as_str(), req.scanned_location.as_str(), &req.book_ids)
+            .await.map_err(CommandError::from).map(ScanItemsCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, Role, ScanResult};
+    use crate::core::repository::RepositoryStore;
+    use crate::inventory::command::scan_items_cmd::{ScanItemsCommand, ScanItemsCommandRequest};
+    use crate::inventory::domain::InventoryService;
+    use crate::inventory::factory::create_inventory_service;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn InventoryService> {
+        create_inventory_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> ScanItemsCommand {
+        let svc = create_inventory_service(&Configuration::new("test"), store).await;
+        ScanItemsCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_scan_items() {
+        let store = test_store(module_path!(), "test_should_run_scan_items");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Employee, "scan_items@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let session = svc.start_session(librarian.party_id.as_str(), None).await.expect("should start session");
+        let res = sut_cmd.execute(ScanItemsCommandRequest::new(session.session_id.as_str(), "Floor 1",
+                                                               vec!["scan_unknown_copy".to_string()]))
+            .await.expect("should scan items");
+        assert_eq!(1, res.scans.len());
+        assert_eq!(ScanResult::Unexpected, res.scans[0].scan_result);
+    }
+}
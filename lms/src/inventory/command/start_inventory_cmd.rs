@@ -0,0 +1,86 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::inventory::domain::InventoryService;
+use crate::inventory::dto::InventorySessionDto;
+
+pub(crate) struct StartInventoryCommand {
+    inventory_service: Box<dyn InventoryService>,
+}
+
+impl StartInventoryCommand {
+    pub(crate) fn new(inventory_service: Box<dyn InventoryService>) -> Self {
+        Self {
+            inventory_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct StartInventoryCommandRequest {
+    pub(crate) started_by: String,
+    #[serde(default)]
+    pub(crate) shelf_location: Option<String>,
+}
+
+impl StartInventoryCommandRequest {
+    pub fn new(started_by: &str, shelf_location: Option<&str>) -> Self {
+        Self {
+            started_by: started_by.to_string(),
+            shelf_location: shelf_location.map(|s| s.to_string()),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct StartInventoryCommandResponse {
+    pub session: InventorySessionDto,
+}
+
+impl StartInventoryCommandResponse {
+    pub fn new(session: InventorySessionDto) -> Self {
+        Self {
+            session,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<StartInventoryCommandRequest, StartInventoryCommandResponse> for StartInventoryCommand {
+    async fn execute(&self, req: StartInventoryCommandRequest) -> Result<StartInventoryCommandResponse, CommandError> {
+        self.inventory_service.start_session(req.started_by.as_str(), req.shelf_location.as_deref())
+            .await.map_err(CommandError::from).map(StartInventoryCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{InventoryStatus, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::inventory::command::start_inventory_cmd::{StartInventoryCommand, StartInventoryCommandRequest};
+    use crate::inventory::factory::create_inventory_service;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn build_sut_cmd(store: RepositoryStore) -> StartInventoryCommand {
+        let svc = create_inventory_service(&Configuration::new("test"), store).await;
+        StartInventoryCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_start_inventory() {
+        let store = test_store(module_path!(), "test_should_run_start_inventory");
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Employee, "start_inventory@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let res = sut_cmd.execute(StartInventoryCommandRequest::new(librarian.party_id.as_str(), Some("Floor 1")))
+            .await.expect("should start inventory");
+        assert_eq!(InventoryStatus::InProgress, res.session.session_status);
+        assert_eq!("Floor 1", res.session.shelf_location.as_str());
+    }
+}
@@ -0,0 +1,81 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use serde_json::{Value};
+use crate::core::controller::{AppState, command_bus, json_to_server_error, with_common_layers, ServerError};
+use crate::inventory::command::inventory_report_cmd::{InventoryReportCommand, InventoryReportCommandRequest, InventoryReportCommandResponse};
+use crate::inventory::command::reconcile_inventory_cmd::{ReconcileInventoryCommand, ReconcileInventoryCommandRequest, ReconcileInventoryCommandResponse};
+use crate::inventory::command::scan_items_cmd::{ScanItemsCommand, ScanItemsCommandRequest, ScanItemsCommandResponse};
+use crate::inventory::command::start_inventory_cmd::{StartInventoryCommand, StartInventoryCommandRequest, StartInventoryCommandResponse};
+use crate::inventory::domain::{InventoryQueryService, InventoryService};
+use crate::inventory::factory;
+use crate::utils::ddb::{build_db_client, create_key_table, create_table};
+
+async fn build_service(state: AppState) -> Box<dyn InventoryService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_key_table(&client, state.store.table_name("inventory_sessions").as_str(), "session_id").await;
+    let _ = create_table(&client, state.store.table_name("inventory_scans").as_str(), "scan_id", "session_id", "scan_result").await;
+    factory::create_inventory_service(&state.config, state.store).await
+}
+
+async fn build_query_service(state: AppState) -> Box<dyn InventoryQueryService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_key_table(&client, state.store.table_name("inventory_sessions").as_str(), "session_id").await;
+    let _ = create_table(&client, state.store.table_name("inventory_scans").as_str(), "scan_id", "session_id", "scan_result").await;
+    factory::create_inventory_query_service(&state.config, state.store).await
+}
+
+pub(crate) async fn start_inventory(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<Json<StartInventoryCommandResponse>, ServerError> {
+    let req: StartInventoryCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res = command_bus().register(StartInventoryCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// scanned barcodes can be sent in chunks while the session is in progress
+pub(crate) async fn scan_items(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+    json: Json<Value>) -> Result<Json<ScanItemsCommandResponse>, ServerError> {
+    let mut req: ScanItemsCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.session_id = session_id;
+    let svc = build_service(state).await;
+    let res = command_bus().register(ScanItemsCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// reconciliation is invoked with next_page until it is empty and then the session is completed
+pub(crate) async fn reconcile_inventory(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+    json: Json<Value>) -> Result<Json<ReconcileInventoryCommandResponse>, ServerError> {
+    let mut req: ReconcileInventoryCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.session_id = session_id;
+    let svc = build_service(state).await;
+    let res = command_bus().register(ReconcileInventoryCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn inventory_report(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+    Query(mut req): Query<InventoryReportCommandRequest>) -> Result<Json<InventoryReportCommandResponse>, ServerError> {
+    req.session_id = session_id;
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(InventoryReportCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/inventory", post(start_inventory))
+        .route("/inventory/:id/scans", post(scan_items))
+        .route("/inventory/:id/reconcile", post(reconcile_inventory))
+        .route("/inventory/:id/report", get(inventory_report));
+    with_common_layers(router, state)
+}
@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use crate::core::library::{LibraryResult, PaginatedResult};
+use crate::inventory::dto::{InventoryReportDto, InventoryScanDto, InventorySessionDto};
+
+pub mod model;
+pub mod query;
+pub mod service;
+
+// reports of inventory sessions that do not change the session
+#[async_trait]
+pub(crate) trait InventoryQueryService: Sync + Send {
+    async fn report(&self, session_id: &str, page: Option<&str>, page_size: usize) -> LibraryResult<InventoryReportDto>;
+}
+
+#[async_trait]
+pub(crate) trait InventoryService: InventoryQueryService {
+    // only librarians can start an inventory of a shelf or the whole branch when shelf is not given
+    async fn start_session(&self, started_by: &str, shelf_location: Option<&str>) -> LibraryResult<InventorySessionDto>;
+    // records a chunk of scanned copies at the location and returns their reconciliation
+    async fn scan(&self, session_id: &str, scanned_location: &str, book_ids: &[String]) -> LibraryResult<Vec<InventoryScanDto>>;
+    // compares a page of expected copies with the scans, records missing copies and completes the
+    // session after the last page
+    async fn reconcile(&self, session_id: &str,
+                       page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<InventoryScanDto>>;
+}
@@ -0,0 +1,101 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::core::domain::Identifiable;
+use crate::core::library::{InventoryStatus, ScanResult};
+use crate::utils::date::serializer;
+
+// InventorySessionEntity abstracts a shelf audit of the branch, an empty shelf location audits all shelves
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct InventorySessionEntity {
+    pub session_id: String,
+    pub version: i64,
+    pub branch_id: String,
+    pub shelf_location: String,
+    pub started_by: String,
+    pub session_status: InventoryStatus,
+    pub completed_at: Option<NaiveDateTime>,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl InventorySessionEntity {
+    pub fn new(branch_id: &str, shelf_location: &str, started_by: &str) -> Self {
+        Self {
+            session_id: Uuid::new_v4().to_string(),
+            version: 0,
+            branch_id: branch_id.to_string(),
+            shelf_location: shelf_location.to_string(),
+            started_by: started_by.to_string(),
+            session_status: InventoryStatus::InProgress,
+            completed_at: None,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl Identifiable for InventorySessionEntity {
+    fn id(&self) -> String {
+        self.session_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+// InventoryScanEntity records reconciliation of a copy within the session, scan id is derived from
+// session and book so that scanning the same copy again replaces the earlier result
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct InventoryScanEntity {
+    pub scan_id: String,
+    pub session_id: String,
+    pub book_id: String,
+    pub scanned_location: String,
+    pub expected_location: String,
+    pub scan_result: ScanResult,
+    #[serde(with = "serializer")]
+    pub scanned_at: NaiveDateTime,
+}
+
+impl InventoryScanEntity {
+    pub fn new(session_id: &str, book_id: &str, scanned_location: &str,
+               expected_location: &str, scan_result: ScanResult) -> Self {
+        Self {
+            scan_id: InventoryScanEntity::build_id(session_id, book_id),
+            session_id: session_id.to_string(),
+            book_id: book_id.to_string(),
+            scanned_location: scanned_location.to_string(),
+            expected_location: expected_location.to_string(),
+            scan_result,
+            scanned_at: Utc::now().naive_utc(),
+        }
+    }
+
+    pub fn build_id(session_id: &str, book_id: &str) -> String {
+        format!("{}_{}", session_id, book_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::library::{InventoryStatus, ScanResult};
+    use crate::inventory::domain::model::{InventoryScanEntity, InventorySessionEntity};
+
+    #[tokio::test]
+    async fn test_should_build_session() {
+        let session = InventorySessionEntity::new("branch", "Floor 2", "librarian");
+        assert_eq!(InventoryStatus::InProgress, session.session_status);
+        assert!(session.completed_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_should_build_scan() {
+        let scan = InventoryScanEntity::new("session", "book", "Floor 2", "Floor 3", ScanResult::Misplaced);
+        assert_eq!("session_book", scan.scan_id.as_str());
+        assert_eq!(ScanResult::Misplaced, scan.scan_result);
+    }
+}
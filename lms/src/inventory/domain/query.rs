@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+use crate::core::library::LibraryResult;
+use crate::inventory::domain::InventoryQueryService;
+use crate::inventory::dto::{InventoryReportDto, InventoryScanDto, InventorySessionDto};
+use crate::inventory::repository::{InventoryScanRepository, InventorySessionRepository};
+
+pub(crate) struct InventoryQueryServiceImpl {
+    session_repository: Box<dyn InventorySessionRepository>,
+    scan_repository: Box<dyn InventoryScanRepository>,
+}
+
+impl InventoryQueryServiceImpl {
+    pub(crate) fn new(session_repository: Box<dyn InventorySessionRepository>,
+                      scan_repository: Box<dyn InventoryScanRepository>) -> Self {
+        Self {
+            session_repository,
+            scan_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl InventoryQueryService for InventoryQueryServiceImpl {
+    async fn report(&self, session_id: &str, page: Option<&str>, page_size: usize) -> LibraryResult<InventoryReportDto> {
+        let session = self.session_repository.get(session_id).await?;
+        let res = self.scan_repository.find_discrepancies(session_id, page, page_size).await?;
+        let scans = res.records.iter().map(InventoryScanDto::from).collect();
+        Ok(InventoryReportDto::new(InventorySessionDto::from(&session), scans, res.next_page))
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::books::domain::Book;
+use crate::catalog::domain::CatalogService;
+use crate::core::domain::Configuration;
+use crate::core::events::DomainEvent;
+use crate::core::ids::{BookId, PatronId};
+use crate::core::library::{BookStatus, InventoryStatus, LibraryError, LibraryResult, PaginatedResult, ScanResult};
+use crate::gateway::events::EventPublisher;
+use crate::inventory::domain::{InventoryQueryService, InventoryService};
+use crate::inventory::domain::model::{InventoryScanEntity, InventorySessionEntity};
+use crate::inventory::dto::{InventoryReportDto, InventoryScanDto, InventorySessionDto};
+use crate::inventory::repository::{InventoryScanRepository, InventorySessionRepository};
+use crate::patrons::domain::PatronService;
+use crate::patrons::Patron;
+
+pub(crate) struct InventoryServiceImpl {
+    branch_id: String,
+    session_repository: Box<dyn InventorySessionRepository>,
+    scan_repository: Box<dyn InventoryScanRepository>,
+    patron_service: Box<dyn PatronService>,
+    catalog_service: Box<dyn CatalogService>,
+    events_publisher: Box<dyn EventPublisher>,
+    query_service: Box<dyn InventoryQueryService>,
+}
+
+impl InventoryServiceImpl {
+    pub(crate) fn new(config: &Configuration, session_repository: Box<dyn InventorySessionRepository>,
+                      scan_repository: Box<dyn InventoryScanRepository>,
+                      patron_service: Box<dyn PatronService>, catalog_service: Box<dyn CatalogService>,
+                      events_publisher: Box<dyn EventPublisher>,
+                      query_service: Box<dyn InventoryQueryService>) -> Self {
+        Self {
+            branch_id: config.branch_id.to_string(),
+            session_repository,
+            scan_repository,
+            patron_service,
+            catalog_service,
+            events_publisher,
+            query_service,
+        }
+    }
+
+    async fn find_open_session(&self, session_id: &str) -> LibraryResult<InventorySessionEntity> {
+        let session = self.session_repository.get(session_id).await?;
+        if session.session_status != InventoryStatus::InProgress {
+            return Err(LibraryError::validation(format!("inventory session {} is already {}",
+                                                        session_id, session.session_status).as_str(), Some("400".to_string())));
+        }
+        Ok(session)
+    }
+
+    // checked out copies are not expected on shelves and copies without location can be shelved anywhere
+    async fn classify(&self, book_id: &str, scanned_location: &str) -> LibraryResult<(ScanResult, String)> {
+        let book = match self.catalog_service.find_book_by_id(&BookId::new(book_id)).await {
+            Ok(book) => book,
+            Err(LibraryError::NotFound { .. }) => return Ok((ScanResult::Unexpected, "".to_string())),
+            Err(err) => return Err(err),
+        };
+        let result = if book.status() == BookStatus::CheckedOut {
+            ScanResult::Unexpected
+        } else if !book.shelf_location.is_empty() && book.shelf_location != scanned_location {
+            ScanResult::Misplaced
+        } else {
+            ScanResult::Found
+        };
+        Ok((result, book.shelf_location))
+    }
+}
+
+#[async_trait]
+impl InventoryService for InventoryServiceImpl {
+    async fn start_session(&self, started_by: &str, shelf_location: Option<&str>) -> LibraryResult<InventorySessionDto> {
+        let staff = self.patron_service.find_patron_by_id(&PatronId::new(started_by)).await?;
+        if !staff.is_librarian() && !staff.is_admin() {
+            return Err(LibraryError::not_granted(format!("{} cannot start inventory",
+                                                         started_by).as_str(), Some("403".to_string())));
+        }
+        let session = InventorySessionEntity::new(self.branch_id.as_str(),
+                                                  shelf_location.unwrap_or_default().trim(), started_by);
+        self.session_repository.create(&session).await?;
+        let dto = InventorySessionDto::from(&session);
+        let _ = self.events_publisher.publish(&DomainEvent::added(
+            "inventory_started", "inventory", dto.session_id.as_str(), &HashMap::new(), &dto)?).await?;
+        Ok(dto)
+    }
+
+    async fn scan(&self, session_id: &str, scanned_location: &str, book_ids: &[String]) -> LibraryResult<Vec<InventoryScanDto>> {
+        let session = self.find_open_session(session_id).await?;
+        let scanned_location = scanned_location.trim();
+        if scanned_location.is_empty() {
+            return Err(LibraryError::validation("scanned location cannot be empty", Some("400".to_string())));
+        }
+        let mut scans = vec![];
+        for book_id in book_ids {
+            let (result, expected_location) = self.classify(book_id.as_str(), scanned_location).await?;
+            let scan = InventoryScanEntity::new(session.session_id.as_str(), book_id.as_str(), scanned_location,
+                                                expected_location.as_str(), result);
+            self.scan_repository.save(&scan).await?;
+            scans.push(InventoryScanDto::from(&scan));
+        }
+        Ok(scans)
+    }
+
+    async fn reconcile(&self, session_id: &str,
+                       page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<InventoryScanDto>> {
+        let mut session = self.find_open_session(session_id).await?;
+        let shelf_location = if session.shelf_location.is_empty() { None } else { Some(session.shelf_location.as_str()) };
+        let res = self.catalog_service.find_books_by_shelf(shelf_location, page, page_size).await?;
+        let mut missing = vec![];
+        for book in res.records.iter().filter(|b| !b.format().is_digital() && b.status() != BookStatus::CheckedOut) {
+            let scan_id = InventoryScanEntity::build_id(session_id, book.book_id.as_str());
+            if self.scan_repository.find_by_id(scan_id.as_str()).await?.is_none() {
+                let scan = InventoryScanEntity::new(session_id, book.book_id.as_str(), "",
+                                                    book.shelf_location.as_str(), ScanResult::Missing);
+                self.scan_repository.save(&scan).await?;
+                missing.push(InventoryScanDto::from(&scan));
+            }
+        }
+        if res.next_page.is_none() {
+            session.session_status = InventoryStatus::Completed;
+            session.completed_at = Some(Utc::now().naive_utc());
+            self.session_repository.update(&session).await?;
+            let dto = InventorySessionDto::from(&session);
+            let _ = self.events_publisher.publish(&DomainEvent::updated(
+                "inventory_completed", "inventory", dto.session_id.as_str(), &HashMap::new(), &dto)?).await?;
+        }
+        Ok(PaginatedResult::new(page, page_size, res.next_page, missing))
+    }
+}
+
+#[async_trait]
+impl InventoryQueryService for InventoryServiceImpl {
+    async fn report(&self, session_id: &str, page: Option<&str>, page_size: usize) -> LibraryResult<InventoryReportDto> {
+        self.query_service.report(session_id, page, page_size).await
+    }
+}
+
+impl From<&InventorySessionEntity> for InventorySessionDto {
+    fn from(other: &InventorySessionEntity) -> InventorySessionDto {
+        InventorySessionDto {
+            session_id: other.session_id.to_string(),
+            version: other.version,
+            branch_id: other.branch_id.to_string(),
+            shelf_location: other.shelf_location.to_string(),
+            started_by: other.started_by.to_string(),
+            session_status: other.session_status,
+            completed_at: other.completed_at,
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+impl From<&InventoryScanEntity> for InventoryScanDto {
+    fn from(other: &InventoryScanEntity) -> InventoryScanDto {
+        InventoryScanDto {
+            scan_id: other.scan_id.to_string(),
+            session_id: other.session_id.to_string(),
+            book_id: other.book_id.to_string(),
+            scanned_location: other.scanned_location.to_string(),
+            expected_location: other.expected_location.to_string(),
+            scan_result: other.scan_result,
+            scanned_at: other.scanned_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::books::domain::model::BookEntity;
+    use crate::books::factory::create_book_repository;
+    use crate::books::repository::BookRepository;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{BookStatus, InventoryStatus, PartyKind, Role, ScanResult};
+    use crate::core::repository::RepositoryStore;
+    use crate::inventory::domain::InventoryService;
+    use crate::inventory::factory;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn InventoryService> {
+        factory::create_inventory_service(&Configuration::new("test"), store).await
+    }
+
+    async fn book_repo(store: RepositoryStore) -> Box<dyn BookRepository> {
+        create_book_repository(store).await
+    }
+
+    async fn add_shelved_book(store: RepositoryStore, title: &str, shelf_location: &str, status: BookStatus) -> BookEntity {
+        let mut book = BookEntity::new("isbn", title, status);
+        book.shelf_location = shelf_location.to_string();
+        let _ = book_repo(store).await.create(&book).await.expect("should create book");
+        book
+    }
+
+    #[tokio::test]
+    async fn test_should_audit_shelf_inventory() {
+        let store = test_store(module_path!(), "test_should_audit_shelf_inventory");
+        let inventory_svc = sut_svc(store).await;
+        let patron = PartyEntity::new(PartyKind::Patron, "inventory_patron@example.com");
+        let mut librarian = PartyEntity::new(PartyKind::Employee, "inventory_librarian@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        for party in [&patron, &librarian] {
+            let _ = create_party_repository(store).await.create(party).await.expect("should create party");
+        }
+        let shelf = "Inventory Aisle 7";
+        let found = add_shelved_book(store, "found", shelf, BookStatus::Available).await;
+        let missing = add_shelved_book(store, "missing", shelf, BookStatus::Available).await;
+        let checked_out = add_shelved_book(store, "checked out", shelf, BookStatus::CheckedOut).await;
+        let misplaced = add_shelved_book(store, "misplaced", "Inventory Aisle 8", BookStatus::Available).await;
+
+        assert!(inventory_svc.start_session(patron.party_id.as_str(), Some(shelf)).await.is_err());
+        let session = inventory_svc.start_session(librarian.party_id.as_str(), Some(shelf)).await.expect("should start session");
+        assert_eq!(InventoryStatus::InProgress, session.session_status);
+
+        let scans = inventory_svc.scan(session.session_id.as_str(), shelf,
+                                       &[found.book_id.to_string(), misplaced.book_id.to_string()]).await.expect("should scan");
+        assert_eq!(ScanResult::Found, scans[0].scan_result);
+        assert_eq!(ScanResult::Misplaced, scans[1].scan_result);
+        let scans = inventory_svc.scan(session.session_id.as_str(), shelf,
+                                       &[checked_out.book_id.to_string(), "unknown_copy".to_string()]).await.expect("should scan");
+        assert!(scans.iter().all(|s| s.scan_result == ScanResult::Unexpected));
+
+        // reconcile in small chunks until the session is completed
+        let mut next_page: Option<String> = None;
+        let mut reconciled = vec![];
+        loop {
+            let res = inventory_svc.reconcile(session.session_id.as_str(), next_page.as_deref(), 10)
+                .await.expect("should reconcile");
+            reconciled.extend(res.records);
+            next_page = res.next_page;
+            if next_page.is_none() {
+                break;
+            }
+        }
+        assert_eq!(vec![missing.book_id.to_string()], reconciled.iter().map(|s| s.book_id.to_string()).collect::<Vec<String>>());
+        assert!(inventory_svc.scan(session.session_id.as_str(), shelf, &[missing.book_id.to_string()]).await.is_err());
+
+        let report = inventory_svc.report(session.session_id.as_str(), None, 100).await.expect("should report");
+        assert_eq!(InventoryStatus::Completed, report.session.session_status);
+        assert_eq!(1, report.missing.len());
+        assert_eq!(1, report.misplaced.len());
+        assert_eq!("Inventory Aisle 8", report.misplaced[0].expected_location.as_str());
+        assert_eq!(2, report.unexpected.len());
+    }
+}
@@ -0,0 +1,66 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use crate::core::library::{InventoryStatus, ScanResult};
+use crate::utils::date::{serializer, Rfc3339};
+
+// InventorySessionDto abstracts data transfer object for shelf inventory sessions
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct InventorySessionDto {
+    pub session_id: String,
+    pub version: i64,
+    pub branch_id: String,
+    pub shelf_location: String,
+    pub started_by: String,
+    pub session_status: InventoryStatus,
+    pub completed_at: Option<NaiveDateTime>,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct InventoryScanDto {
+    pub scan_id: String,
+    pub session_id: String,
+    pub book_id: String,
+    pub scanned_location: String,
+    pub expected_location: String,
+    pub scan_result: ScanResult,
+    #[serde(with = "serializer")]
+    pub scanned_at: NaiveDateTime,
+}
+
+// InventoryReportDto groups discrepancies of a page of the session, found items are not included
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct InventoryReportDto {
+    pub session: InventorySessionDto,
+    pub missing: Vec<InventoryScanDto>,
+    pub misplaced: Vec<InventoryScanDto>,
+    pub unexpected: Vec<InventoryScanDto>,
+    pub next_page: Option<String>,
+}
+
+impl InventoryReportDto {
+    pub fn new(session: InventorySessionDto, scans: Vec<InventoryScanDto>, next_page: Option<String>) -> Self {
+        let mut report = Self {
+            session,
+            missing: vec![],
+            misplaced: vec![],
+            unexpected: vec![],
+            next_page,
+        };
+        for scan in scans {
+            match scan.scan_result {
+                ScanResult::Missing => report.missing.push(scan),
+                ScanResult::Misplaced => report.misplaced.push(scan),
+                ScanResult::Unexpected => report.unexpected.push(scan),
+                ScanResult::Found => {}
+            }
+        }
+        report
+    }
+}
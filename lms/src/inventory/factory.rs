@@ -0,0 +1,59 @@
+use crate::catalog::factory::create_catalog_service;
+use crate::core::domain::Configuration;
+use crate::core::repository::RepositoryStore;
+use crate::gateway::factory::create_publisher;
+use crate::inventory::domain::{InventoryQueryService, InventoryService};
+use crate::inventory::domain::query::InventoryQueryServiceImpl;
+use crate::inventory::domain::service::InventoryServiceImpl;
+use crate::inventory::factory;
+use crate::inventory::repository::ddb_inventory_scan_repository::DDBInventoryScanRepository;
+use crate::inventory::repository::ddb_inventory_session_repository::DDBInventorySessionRepository;
+use crate::inventory::repository::{InventoryScanRepository, InventorySessionRepository};
+use crate::patrons::factory::create_patron_service;
+use crate::utils::ddb::{build_db_client, create_key_table, create_table};
+
+pub(crate) async fn create_inventory_session_repository(store: RepositoryStore) -> Box<dyn InventorySessionRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBInventorySessionRepository::new(client, "inventory_sessions"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("inventory_sessions");
+            let _ = create_key_table(&client, table_name.as_str(), "session_id").await;
+            Box::new(DDBInventorySessionRepository::new(client, table_name.as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_inventory_scan_repository(store: RepositoryStore) -> Box<dyn InventoryScanRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBInventoryScanRepository::new(client, "inventory_scans", "inventory_scans_ndx"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("inventory_scans");
+            let _ = create_table(&client, table_name.as_str(), "scan_id", "session_id", "scan_result").await;
+            Box::new(DDBInventoryScanRepository::new(client, table_name.as_str(), store.table_name("inventory_scans_ndx").as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_inventory_query_service(_config: &Configuration, store: RepositoryStore) -> Box<dyn InventoryQueryService> {
+    let session_repo = factory::create_inventory_session_repository(store).await;
+    let scan_repo = factory::create_inventory_scan_repository(store).await;
+    Box::new(InventoryQueryServiceImpl::new(session_repo, scan_repo))
+}
+
+pub(crate) async fn create_inventory_service(config: &Configuration, store: RepositoryStore) -> Box<dyn InventoryService> {
+    let session_repo = factory::create_inventory_session_repository(store).await;
+    let scan_repo = factory::create_inventory_scan_repository(store).await;
+    let patron_svc = create_patron_service(config, store).await;
+    let catalog_svc = create_catalog_service(config, store).await;
+    let publisher = create_publisher(store.gateway_publisher()).await;
+    let query_svc = create_inventory_query_service(config, store).await;
+    Box::new(InventoryServiceImpl::new(config, session_repo, scan_repo, patron_svc, catalog_svc, publisher, query_svc))
+}
@@ -0,0 +1,23 @@
+pub mod ddb_inventory_scan_repository;
+pub mod ddb_inventory_session_repository;
+
+use async_trait::async_trait;
+use crate::core::library::{LibraryResult, PaginatedResult};
+use crate::inventory::domain::model::{InventoryScanEntity, InventorySessionEntity};
+
+#[async_trait]
+pub(crate) trait InventorySessionRepository: Sync + Send {
+    async fn create(&self, entity: &InventorySessionEntity) -> LibraryResult<usize>;
+    async fn update(&self, entity: &InventorySessionEntity) -> LibraryResult<usize>;
+    async fn get(&self, session_id: &str) -> LibraryResult<InventorySessionEntity>;
+}
+
+#[async_trait]
+pub(crate) trait InventoryScanRepository: Sync + Send {
+    // stores the scan and replaces earlier scan of the same copy within the session
+    async fn save(&self, entity: &InventoryScanEntity) -> LibraryResult<usize>;
+    async fn find_by_id(&self, scan_id: &str) -> LibraryResult<Option<InventoryScanEntity>>;
+    // returns missing, misplaced and unexpected scans of the session
+    async fn find_discrepancies(&self, session_id: &str,
+                                page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<InventoryScanEntity>>;
+}
@@ -0,0 +1,138 @@
+use std::cmp;
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult, ScanResult};
+use crate::inventory::domain::model::InventoryScanEntity;
+use crate::inventory::repository::InventoryScanRepository;
+use crate::utils::ddb::{from_ddb, parse_date_attribute, parse_item, parse_string_attribute, to_ddb_page};
+
+#[derive(Debug)]
+pub(crate) struct DDBInventoryScanRepository {
+    client: Client,
+    table_name: String,
+    index_name: String,
+}
+
+impl DDBInventoryScanRepository {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl InventoryScanRepository for DDBInventoryScanRepository {
+    async fn save(&self, entity: &InventoryScanEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .set_item(Some(parse_item(val)?))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn find_by_id(&self, scan_id: &str) -> LibraryResult<Option<InventoryScanEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .query()
+            .table_name(table_name)
+            .limit(1)
+            .consistent_read(true)
+            .key_condition_expression("scan_id = :scan_id")
+            .expression_attribute_values(":scan_id", AttributeValue::S(scan_id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            req.items.as_ref().and_then(|items| items.first()).map(InventoryScanEntity::from)
+        })
+    }
+
+    async fn find_discrepancies(&self, session_id: &str,
+                                page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<InventoryScanEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let predicate = HashMap::from([("session_id".to_string(), session_id.to_string())]);
+        self.client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .limit(cmp::min(page_size, 500) as i32)
+            .consistent_read(false)
+            .set_exclusive_start_key(to_ddb_page(page, &predicate))
+            .key_condition_expression("session_id = :session_id")
+            .filter_expression("scan_result <> :found")
+            .expression_attribute_values(":session_id", AttributeValue::S(session_id.to_string()))
+            .expression_attribute_values(":found", AttributeValue::S(ScanResult::Found.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(InventoryScanEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for InventoryScanEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        InventoryScanEntity {
+            scan_id: parse_string_attribute("scan_id", map).unwrap_or_else(|| String::from("")),
+            session_id: parse_string_attribute("session_id", map).unwrap_or_else(|| String::from("")),
+            book_id: parse_string_attribute("book_id", map).unwrap_or_else(|| String::from("")),
+            scanned_location: parse_string_attribute("scanned_location", map).unwrap_or_else(|| String::from("")),
+            expected_location: parse_string_attribute("expected_location", map).unwrap_or_else(|| String::from("")),
+            scan_result: ScanResult::from(parse_string_attribute("scan_result", map).unwrap_or_else(|| String::from(""))),
+            scanned_at: parse_date_attribute("scanned_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+
+    use crate::core::library::ScanResult;
+    use crate::core::repository::RepositoryStore;
+    use crate::inventory::domain::model::InventoryScanEntity;
+    use crate::inventory::repository::ddb_inventory_scan_repository::DDBInventoryScanRepository;
+    use crate::inventory::repository::InventoryScanRepository;
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("inventory_scans").as_str(), "scan_id", "session_id", "scan_result").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_save_find_scans() {
+        let store = test_store(module_path!(), "test_should_save_find_scans");
+        let repo = DDBInventoryScanRepository::new(build_client(store).await, store.table_name("inventory_scans").as_str(), store.table_name("inventory_scans_ndx").as_str());
+        let found = InventoryScanEntity::new("repo_session", "book1", "Floor 2", "Floor 2", ScanResult::Found);
+        let misplaced = InventoryScanEntity::new("repo_session", "book2", "Floor 2", "Floor 3", ScanResult::Misplaced);
+        let missing = InventoryScanEntity::new("repo_session", "book3", "", "Floor 2", ScanResult::Missing);
+        for scan in [&found, &misplaced, &missing] {
+            assert_eq!(1, repo.save(scan).await.expect("should save scan"));
+        }
+        let res = repo.find_discrepancies("repo_session", None, 10).await.expect("should find scans");
+        assert_eq!(2, res.records.len());
+
+        // scanning the missing copy replaces earlier result
+        let rescanned = InventoryScanEntity::new("repo_session", "book3", "Floor 2", "Floor 2", ScanResult::Found);
+        assert_eq!(1, repo.save(&rescanned).await.expect("should save scan"));
+        let loaded = repo.find_by_id(missing.scan_id.as_str()).await.expect("should find scan").expect("should exist");
+        assert_eq!(ScanResult::Found, loaded.scan_result);
+        let res = repo.find_discrepancies("repo_session", None, 10).await.expect("should find scans");
+        assert_eq!(1, res.records.len());
+        assert!(repo.find_by_id("missing").await.expect("should query scan").is_none());
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::core::library::{InventoryStatus, LibraryError, LibraryResult};
+use crate::inventory::domain::model::InventorySessionEntity;
+use crate::inventory::repository::InventorySessionRepository;
+use crate::utils::ddb::{opt_string_date, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, string_date};
+
+#[derive(Debug)]
+pub(crate) struct DDBInventorySessionRepository {
+    client: Client,
+    table_name: String,
+}
+
+impl DDBInventorySessionRepository {
+    pub(crate) fn new(client: Client, table_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl InventorySessionRepository for DDBInventorySessionRepository {
+    async fn create(&self, entity: &InventorySessionEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        let mut item = parse_item(val)?;
+        item.insert("created_at".to_string(), string_date(entity.created_at));
+        item.insert("updated_at".to_string(), string_date(entity.updated_at));
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(session_id)")
+            .set_item(Some(item))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn update(&self, entity: &InventorySessionEntity) -> LibraryResult<usize> {
+        let now = Utc::now().naive_utc();
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .update_item()
+            .table_name(table_name)
+            .key("session_id", AttributeValue::S(entity.session_id.clone()))
+            .update_expression("SET version = :version, session_status = :session_status, completed_at = :completed_at, updated_at = :updated_at")
+            .expression_attribute_values(":old_version", AttributeValue::N(entity.version.to_string()))
+            .expression_attribute_values(":version", AttributeValue::N((entity.version + 1).to_string()))
+            .expression_attribute_values(":session_status", AttributeValue::S(entity.session_status.to_string()))
+            .expression_attribute_values(":completed_at", opt_string_date(entity.completed_at))
+            .expression_attribute_values(":updated_at", string_date(now))
+            .condition_expression("attribute_exists(version) AND version = :old_version")
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn get(&self, session_id: &str) -> LibraryResult<InventorySessionEntity> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .query()
+            .table_name(table_name)
+            .limit(1)
+            .consistent_read(true)
+            .key_condition_expression("session_id = :session_id")
+            .expression_attribute_values(":session_id", AttributeValue::S(session_id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).and_then(|req| {
+            if let Some(map) = req.items.as_ref().and_then(|items| items.first()) {
+                return Ok(InventorySessionEntity::from(map));
+            }
+            Err(LibraryError::not_found(format!("inventory session not found for {}", session_id).as_str()))
+        })
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for InventorySessionEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        InventorySessionEntity {
+            session_id: parse_string_attribute("session_id", map).unwrap_or_else(|| String::from("")),
+            version: parse_number_attribute("version", map),
+            branch_id: parse_string_attribute("branch_id", map).unwrap_or_else(|| String::from("")),
+            shelf_location: parse_string_attribute("shelf_location", map).unwrap_or_else(|| String::from("")),
+            started_by: parse_string_attribute("started_by", map).unwrap_or_else(|| String::from("")),
+            session_status: InventoryStatus::from(parse_string_attribute("session_status", map).unwrap_or_else(|| String::from(""))),
+            completed_at: parse_date_attribute("completed_at", map),
+            created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+    use chrono::Utc;
+
+    use crate::core::library::InventoryStatus;
+    use crate::core::repository::RepositoryStore;
+    use crate::inventory::domain::model::InventorySessionEntity;
+    use crate::inventory::repository::ddb_inventory_session_repository::DDBInventorySessionRepository;
+    use crate::inventory::repository::InventorySessionRepository;
+    use crate::utils::ddb::{build_db_client, create_key_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_key_table(&client, store.table_name("inventory_sessions").as_str(), "session_id").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_create_update_get_sessions() {
+        let store = test_store(module_path!(), "test_should_create_update_get_sessions");
+        let repo = DDBInventorySessionRepository::new(build_client(store).await, store.table_name("inventory_sessions").as_str());
+        let mut session = InventorySessionEntity::new("branch", "Floor 2", "librarian");
+        assert_eq!(1, repo.create(&session).await.expect("should create session"));
+        assert!(repo.create(&session).await.is_err());
+
+        session.session_status = InventoryStatus::Completed;
+        session.completed_at = Some(Utc::now().naive_utc());
+        assert_eq!(1, repo.update(&session).await.expect("should update session"));
+        // stale version should not be updated
+        assert!(repo.update(&session).await.is_err());
+
+        let loaded = repo.get(session.session_id.as_str()).await.expect("should get session");
+        assert_eq!(InventoryStatus::Completed, loaded.session_status);
+        assert!(loaded.completed_at.is_some());
+        assert!(repo.get("missing").await.is_err());
+    }
+}
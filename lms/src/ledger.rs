@@ -0,0 +1,4 @@
+pub mod domain;
+pub mod dto;
+pub mod factory;
+pub mod repository;
@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use crate::core::library::LibraryResult;
+use crate::ledger::dto::{AccountBalanceDto, JournalEntryDto, LedgerCheckDto};
+
+pub mod model;
+pub mod query;
+pub mod service;
+
+// read side of the ledger
+#[async_trait]
+pub(crate) trait LedgerQueryService: Sync + Send {
+    // balances of the accounts of the branch from the entries posted within the period, sorted by account
+    async fn branch_balances(&self, branch_id: &str, from: NaiveDateTime, to: NaiveDateTime) -> LibraryResult<Vec<AccountBalanceDto>>;
+    // walks the whole journal and reports entries that do not balance
+    async fn check_integrity(&self, page_size: usize) -> LibraryResult<LedgerCheckDto>;
+}
+
+#[async_trait]
+pub(crate) trait LedgerService: LedgerQueryService {
+    // entries are immutable once posted, corrections are posted as entries in the other direction, an entry id that
+    // was already posted is rejected as a duplicate key so that retried postings are posted once
+    async fn post(&self, entry: &JournalEntryDto) -> LibraryResult<JournalEntryDto>;
+}
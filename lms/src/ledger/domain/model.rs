@@ -0,0 +1,57 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use crate::ledger::dto::{EntrySide, JournalEntryDto, PostingDto};
+use crate::utils::date::serializer;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct PostingEntity {
+    pub account: String,
+    pub side: EntrySide,
+    pub amount: i64,
+}
+
+// JournalEntryEntity is stored with its postings so that an entry is written or rejected as a whole
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct JournalEntryEntity {
+    pub entry_id: String,
+    pub branch_id: String,
+    pub reference: String,
+    pub description: String,
+    pub postings: Vec<PostingEntity>,
+    #[serde(with = "serializer")]
+    pub posted_at: NaiveDateTime,
+}
+
+impl From<&JournalEntryDto> for JournalEntryEntity {
+    fn from(other: &JournalEntryDto) -> Self {
+        Self {
+            entry_id: other.entry_id.to_string(),
+            branch_id: other.branch_id.to_string(),
+            reference: other.reference.to_string(),
+            description: other.description.to_string(),
+            postings: other.postings.iter().map(|p| PostingEntity {
+                account: p.account.to_string(),
+                side: p.side,
+                amount: p.amount,
+            }).collect(),
+            posted_at: other.posted_at,
+        }
+    }
+}
+
+impl From<&JournalEntryEntity> for JournalEntryDto {
+    fn from(other: &JournalEntryEntity) -> Self {
+        Self {
+            entry_id: other.entry_id.to_string(),
+            branch_id: other.branch_id.to_string(),
+            reference: other.reference.to_string(),
+            description: other.description.to_string(),
+            postings: other.postings.iter().map(|p| PostingDto {
+                account: p.account.to_string(),
+                side: p.side,
+                amount: p.amount,
+            }).collect(),
+            posted_at: other.posted_at,
+        }
+    }
+}
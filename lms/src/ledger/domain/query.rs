@@ -0,0 +1,98 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+use crate::core::library::LibraryResult;
+use crate::ledger::domain::LedgerQueryService;
+use crate::ledger::dto::{AccountBalanceDto, EntrySide, JournalEntryDto, LedgerCheckDto};
+use crate::ledger::repository::JournalRepository;
+
+const PAGE_SIZE: usize = 100;
+
+pub(crate) struct LedgerQueryServiceImpl {
+    journal_repository: Box<dyn JournalRepository>,
+}
+
+impl LedgerQueryServiceImpl {
+    pub(crate) fn new(journal_repository: Box<dyn JournalRepository>) -> Self {
+        Self {
+            journal_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl LedgerQueryService for LedgerQueryServiceImpl {
+    async fn branch_balances(&self, branch_id: &str, from: NaiveDateTime, to: NaiveDateTime) -> LibraryResult<Vec<AccountBalanceDto>> {
+        let mut balances: BTreeMap<String, AccountBalanceDto> = BTreeMap::new();
+        let mut next_page: Option<String> = None;
+        loop {
+            let res = self.journal_repository.find_by_branch(branch_id, from, to, next_page.as_deref(), PAGE_SIZE).await?;
+            for posting in res.records.iter().flat_map(|entry| entry.postings.iter()) {
+                let balance = balances.entry(posting.account.to_string()).or_insert_with(|| AccountBalanceDto {
+                    account: posting.account.to_string(),
+                    debits: 0,
+                    credits: 0,
+                    balance: 0,
+                });
+                match posting.side {
+                    EntrySide::Debit => balance.debits += posting.amount,
+                    EntrySide::Credit => balance.credits += posting.amount,
+                }
+                balance.balance = balance.debits - balance.credits;
+            }
+            next_page = res.next_page;
+            if next_page.is_none() {
+                break;
+            }
+        }
+        Ok(balances.into_values().collect())
+    }
+
+    async fn check_integrity(&self, page_size: usize) -> LibraryResult<LedgerCheckDto> {
+        let mut check = LedgerCheckDto::default();
+        let mut next_page: Option<String> = None;
+        loop {
+            let res = self.journal_repository.scan(next_page.as_deref(), page_size).await?;
+            for entry in res.records.iter().map(JournalEntryDto::from) {
+                check.entries += 1;
+                check.debits += entry.total(EntrySide::Debit);
+                check.credits += entry.total(EntrySide::Credit);
+                if let Err(err) = entry.validate() {
+                    check.unbalanced.push(err.to_string());
+                }
+            }
+            next_page = res.next_page;
+            if next_page.is_none() {
+                break;
+            }
+        }
+        Ok(check)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::ledger::domain::LedgerQueryService;
+    use crate::ledger::factory;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn LedgerQueryService> {
+        factory::create_ledger_query_service(&Configuration::new("test"), store).await
+    }
+
+    #[tokio::test]
+    async fn test_should_not_find_balances_of_unknown_branch() {
+        let store = test_store(module_path!(), "test_should_not_find_balances_of_unknown_branch");
+        let query_svc = sut_svc(store).await;
+        let now = Utc::now().naive_utc();
+        let balances = query_svc.branch_balances("ledger_unknown_branch", now - Duration::days(1), now)
+            .await.expect("should find balances");
+        assert!(balances.is_empty());
+    }
+}
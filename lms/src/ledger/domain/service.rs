@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+use crate::core::library::LibraryResult;
+use crate::ledger::domain::{LedgerQueryService, LedgerService};
+use crate::ledger::domain::model::JournalEntryEntity;
+use crate::ledger::dto::{AccountBalanceDto, JournalEntryDto, LedgerCheckDto};
+use crate::ledger::repository::JournalRepository;
+
+pub(crate) struct LedgerServiceImpl {
+    journal_repository: Box<dyn JournalRepository>,
+    query_service: Box<dyn LedgerQueryService>,
+}
+
+impl LedgerServiceImpl {
+    pub(crate) fn new(journal_repository: Box<dyn JournalRepository>, query_service: Box<dyn LedgerQueryService>) -> Self {
+        Self {
+            journal_repository,
+            query_service,
+        }
+    }
+}
+
+#[async_trait]
+impl LedgerService for LedgerServiceImpl {
+    async fn post(&self, entry: &JournalEntryDto) -> LibraryResult<JournalEntryDto> {
+        entry.validate()?;
+        let entity = JournalEntryEntity::from(entry);
+        self.journal_repository.create(&entity).await?;
+        Ok(JournalEntryDto::from(&entity))
+    }
+}
+
+#[async_trait]
+impl LedgerQueryService for LedgerServiceImpl {
+    async fn branch_balances(&self, branch_id: &str, from: NaiveDateTime, to: NaiveDateTime) -> LibraryResult<Vec<AccountBalanceDto>> {
+        self.query_service.branch_balances(branch_id, from, to).await
+    }
+
+    async fn check_integrity(&self, page_size: usize) -> LibraryResult<LedgerCheckDto> {
+        self.query_service.check_integrity(page_size).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::core::domain::Configuration;
+    use crate::core::library::LibraryError;
+    use crate::core::repository::RepositoryStore;
+    use crate::ledger::domain::LedgerService;
+    use crate::ledger::dto::JournalEntryDto;
+    use crate::ledger::factory;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn LedgerService> {
+        factory::create_ledger_service(&Configuration::new("test"), store).await
+    }
+
+    #[tokio::test]
+    async fn test_should_post_balanced_entries() {
+        let store = test_store(module_path!(), "test_should_post_balanced_entries");
+        let ledger_svc = sut_svc(store).await;
+        let from = Utc::now().naive_utc() - Duration::seconds(1);
+        ledger_svc.post(&JournalEntryDto::new("ledger_branch", "fine1", "fine assessed")
+            .transfer("revenue:fines", "receivable:fines", 1000)).await.expect("should post fine");
+        let payment = JournalEntryDto::new("ledger_branch", "fine1", "fine paid")
+            .with_entry_id("ledger_payment1").transfer("receivable:fines", "cash:fines", 300);
+        ledger_svc.post(&payment).await.expect("should post payment");
+        // a retried posting is rejected instead of being posted twice
+        assert!(matches!(ledger_svc.post(&payment).await, Err(LibraryError::DuplicateKey { .. })));
+        assert!(ledger_svc.post(&JournalEntryDto::new("ledger_branch", "fine2", "fine paid")
+            .debit("cash:fines", 300).credit("receivable:fines", 200)).await.is_err());
+
+        let balances = ledger_svc.branch_balances("ledger_branch", from, Utc::now().naive_utc())
+            .await.expect("should find balances");
+        let balance = |account: &str| balances.iter().find(|b| b.account == account).map(|b| b.balance);
+        assert_eq!(Some(700), balance("receivable:fines"));
+        assert_eq!(Some(300), balance("cash:fines"));
+        assert_eq!(Some(-1000), balance("revenue:fines"));
+        assert_eq!(0, balances.iter().map(|b| b.balance).sum::<i64>());
+
+        let check = ledger_svc.check_integrity(100).await.expect("should check ledger");
+        assert!(check.entries >= 2);
+        assert_eq!(check.debits, check.credits);
+        assert!(check.unbalanced.is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use crate::core::ids::branch_scoped_id;
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::utils::date::{serializer, Rfc3339};
+
+// EntrySide is the side of an account that a posting is recorded on
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub(crate) enum EntrySide {
+    Debit,
+    Credit,
+}
+
+// PostingDto records an amount in the smallest unit of the currency on one side of an account
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct PostingDto {
+    pub account: String,
+    pub side: EntrySide,
+    pub amount: i64,
+}
+
+// JournalEntryDto is a financial event of a branch recorded as postings whose debits equal their credits, the
+// reference is the id of the record that caused it such as a purchase
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct JournalEntryDto {
+    pub entry_id: String,
+    pub branch_id: String,
+    pub reference: String,
+    pub description: String,
+    pub postings: Vec<PostingDto>,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub posted_at: NaiveDateTime,
+}
+
+impl JournalEntryDto {
+    pub(crate) fn new(branch_id: &str, reference: &str, description: &str) -> Self {
+        Self {
+            entry_id: branch_scoped_id(branch_id),
+            branch_id: branch_id.to_string(),
+            reference: reference.to_string(),
+            description: description.to_string(),
+            postings: vec![],
+            posted_at: Utc::now().naive_utc(),
+        }
+    }
+
+    // entries of retried operations are keyed by the operation instead of a new id so that they are posted once
+    pub(crate) fn with_entry_id(mut self, entry_id: &str) -> Self {
+        self.entry_id = entry_id.to_string();
+        self
+    }
+
+    pub(crate) fn debit(mut self, account: &str, amount: i64) -> Self {
+        self.postings.push(PostingDto { account: account.to_string(), side: EntrySide::Debit, amount });
+        self
+    }
+
+    pub(crate) fn credit(mut self, account: &str, amount: i64) -> Self {
+        self.postings.push(PostingDto { account: account.to_string(), side: EntrySide::Credit, amount });
+        self
+    }
+
+    // a transfer of the amount from one account to another, negative amounts transfer in the other direction
+    pub(crate) fn transfer(self, from_account: &str, to_account: &str, amount: i64) -> Self {
+        if amount < 0 {
+            self.debit(from_account, -amount).credit(to_account, -amount)
+        } else {
+            self.debit(to_account, amount).credit(from_account, amount)
+        }
+    }
+
+    // sums are kept in i128 so that the check cannot be defeated by overflowing amounts
+    pub(crate) fn total(&self, side: EntrySide) -> i128 {
+        self.postings.iter().filter(|p| p.side == side).map(|p| p.amount as i128).sum()
+    }
+
+    pub(crate) fn validate(&self) -> LibraryResult<()> {
+        let invalid = |reason: &str| LibraryError::validation(
+            format!("journal entry {} {}", self.entry_id, reason).as_str(), Some("400".to_string()));
+        if self.postings.len() < 2 {
+            return Err(invalid("needs at least two postings"));
+        }
+        if let Some(posting) = self.postings.iter().find(|p| p.amount <= 0 || p.account.is_empty()) {
+            return Err(invalid(format!("has an invalid posting of {} to '{}'", posting.amount, posting.account).as_str()));
+        }
+        let (debits, credits) = (self.total(EntrySide::Debit), self.total(EntrySide::Credit));
+        if debits != credits {
+            return Err(invalid(format!("does not balance, debits {} and credits {}", debits, credits).as_str()));
+        }
+        Ok(())
+    }
+}
+
+// AccountBalanceDto sums the postings of an account, the balance is debits less credits
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct AccountBalanceDto {
+    pub account: String,
+    pub debits: i64,
+    pub credits: i64,
+    pub balance: i64,
+}
+
+// LedgerCheckDto is the result of checking the whole journal, which balances when every entry does, discrepancies
+// are records of other contexts that disagree with the balances of the ledger
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct LedgerCheckDto {
+    pub entries: usize,
+    pub debits: i128,
+    pub credits: i128,
+    pub unbalanced: Vec<String>,
+    pub discrepancies: Vec<String>,
+}
+
+impl LedgerCheckDto {
+    pub fn is_consistent(&self) -> bool {
+        self.debits == self.credits && self.unbalanced.is_empty() && self.discrepancies.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ledger::dto::{EntrySide, JournalEntryDto};
+
+    #[tokio::test]
+    async fn test_should_validate_journal_entry() {
+        let entry = JournalEntryDto::new("main", "fine1", "fine assessed").transfer("revenue:fines", "receivable:fines", 500);
+        assert!(entry.validate().is_ok());
+        assert_eq!(500, entry.total(EntrySide::Debit));
+        assert_eq!("receivable:fines", entry.postings[0].account.as_str());
+        assert!(entry.entry_id.starts_with("main_"));
+        assert_eq!("fine1", entry.with_entry_id("fine1").entry_id.as_str());
+
+        let reversed = JournalEntryDto::new("main", "fine1", "fine refunded").transfer("receivable:fines", "cash:fines", -500);
+        assert_eq!("receivable:fines", reversed.postings[0].account.as_str());
+        assert!(reversed.validate().is_ok());
+
+        assert!(JournalEntryDto::new("main", "", "").debit("cash", 100).validate().is_err());
+        assert!(JournalEntryDto::new("main", "", "").debit("cash", 100).credit("revenue", 90).validate().is_err());
+        assert!(JournalEntryDto::new("main", "", "").debit("cash", 0).credit("revenue", 0).validate().is_err());
+        assert!(JournalEntryDto::new("main", "", "").debit("cash", i64::MAX).debit("cash", i64::MAX)
+            .credit("revenue", i64::MAX).credit("revenue", i64::MAX - 1).validate().is_err());
+    }
+}
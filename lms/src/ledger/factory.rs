@@ -0,0 +1,35 @@
+use crate::core::domain::Configuration;
+use crate::core::repository::RepositoryStore;
+use crate::ledger::domain::{LedgerQueryService, LedgerService};
+use crate::ledger::domain::query::LedgerQueryServiceImpl;
+use crate::ledger::domain::service::LedgerServiceImpl;
+use crate::ledger::factory;
+use crate::ledger::repository::JournalRepository;
+use crate::ledger::repository::ddb_journal_repository::DDBJournalRepository;
+use crate::utils::ddb::{build_db_client, create_table, ScanGuard};
+
+pub(crate) async fn create_journal_repository(store: RepositoryStore) -> Box<dyn JournalRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBJournalRepository::new(client, "journal", "journal_ndx").with_scan_guard(ScanGuard::new(store)))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("journal");
+            let _ = create_table(&client, table_name.as_str(), "entry_id", "branch_id", "posted_at").await;
+            Box::new(DDBJournalRepository::new(client, table_name.as_str(), store.table_name("journal_ndx").as_str()).with_scan_guard(ScanGuard::new(store)))
+        }
+    }
+}
+
+pub(crate) async fn create_ledger_query_service(_config: &Configuration, store: RepositoryStore) -> Box<dyn LedgerQueryService> {
+    let journal_repo = factory::create_journal_repository(store).await;
+    Box::new(LedgerQueryServiceImpl::new(journal_repo))
+}
+
+pub(crate) async fn create_ledger_service(config: &Configuration, store: RepositoryStore) -> Box<dyn LedgerService> {
+    let journal_repo = factory::create_journal_repository(store).await;
+    let query_svc = create_ledger_query_service(config, store).await;
+    Box::new(LedgerServiceImpl::new(journal_repo, query_svc))
+}
@@ -0,0 +1,15 @@
+pub mod ddb_journal_repository;
+
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use crate::core::library::{LibraryResult, PaginatedResult};
+use crate::ledger::domain::model::JournalEntryEntity;
+
+#[async_trait]
+pub(crate) trait JournalRepository: Sync + Send {
+    async fn create(&self, entity: &JournalEntryEntity) -> LibraryResult<usize>;
+    // entries of the branch posted within the period, oldest first
+    async fn find_by_branch(&self, branch_id: &str, from: NaiveDateTime, to: NaiveDateTime,
+                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<JournalEntryEntity>>;
+    async fn scan(&self, page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<JournalEntryEntity>>;
+}
@@ -0,0 +1,160 @@
+use std::cmp;
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::{NaiveDateTime, Utc};
+
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult};
+use crate::ledger::domain::model::JournalEntryEntity;
+use crate::ledger::repository::JournalRepository;
+use crate::utils::ddb::{from_ddb, parse_date_attribute, parse_item, parse_json_attribute, parse_string_attribute, string_date, to_ddb_page, ScanGuard};
+
+#[derive(Debug)]
+pub(crate) struct DDBJournalRepository {
+    client: Client,
+    table_name: String,
+    index_name: String,
+    scan_guard: ScanGuard,
+}
+
+impl DDBJournalRepository {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+            scan_guard: ScanGuard::default(),
+        }
+    }
+
+    pub(crate) fn with_scan_guard(mut self, scan_guard: ScanGuard) -> Self {
+        self.scan_guard = scan_guard;
+        self
+    }
+}
+
+#[async_trait]
+impl JournalRepository for DDBJournalRepository {
+    // entries are never overwritten so that a posted entry cannot be changed after the fact, posting an entry id again
+    // is reported as a duplicate key
+    async fn create(&self, entity: &JournalEntryEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        let mut item = parse_item(val)?;
+        item.insert("posted_at".to_string(), string_date(entity.posted_at));
+        let res = self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(entry_id)")
+            .set_item(Some(item))
+            .send()
+            .await;
+        match res {
+            Ok(_) => Ok(1),
+            Err(SdkError::ServiceError(ctx)) if ctx.err().is_conditional_check_failed_exception() =>
+                Err(LibraryError::duplicate_key(format!("journal entry {} is already posted", entity.entry_id).as_str())),
+            Err(err) => Err(LibraryError::from(err)),
+        }
+    }
+
+    async fn find_by_branch(&self, branch_id: &str, from: NaiveDateTime, to: NaiveDateTime,
+                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<JournalEntryEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let predicate = HashMap::from([("branch_id".to_string(), branch_id.to_string())]);
+        self.client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .limit(cmp::min(page_size, 500) as i32)
+            .consistent_read(false)
+            .scan_index_forward(true)
+            .set_exclusive_start_key(to_ddb_page(page, &predicate))
+            .key_condition_expression("branch_id = :branch_id AND posted_at BETWEEN :from AND :to")
+            .expression_attribute_values(":branch_id", AttributeValue::S(branch_id.to_string()))
+            .expression_attribute_values(":from", string_date(from))
+            .expression_attribute_values(":to", string_date(to))
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(JournalEntryEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+
+    async fn scan(&self, page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<JournalEntryEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        self.scan_guard.check(table_name)?;
+        self.client
+            .scan()
+            .table_name(table_name)
+            .consistent_read(false)
+            .set_exclusive_start_key(to_ddb_page(page, &HashMap::new()))
+            .limit(cmp::min(page_size, 500) as i32)
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(JournalEntryEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+}
+
+// postings that cannot be read are left out, which the integrity check reports as an unbalanced entry
+impl From<&HashMap<String, AttributeValue>> for JournalEntryEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        JournalEntryEntity {
+            entry_id: parse_string_attribute("entry_id", map).unwrap_or_else(|| String::from("")),
+            branch_id: parse_string_attribute("branch_id", map).unwrap_or_else(|| String::from("")),
+            reference: parse_string_attribute("reference", map).unwrap_or_else(|| String::from("")),
+            description: parse_string_attribute("description", map).unwrap_or_else(|| String::from("")),
+            postings: parse_json_attribute("postings", map)
+                .and_then(|json| serde_json::from_str(json.as_str()).ok()).unwrap_or_default(),
+            posted_at: parse_date_attribute("posted_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+    use chrono::Duration;
+
+    use crate::core::library::LibraryError;
+    use crate::core::repository::RepositoryStore;
+    use crate::ledger::domain::model::JournalEntryEntity;
+    use crate::ledger::dto::JournalEntryDto;
+    use crate::ledger::repository::JournalRepository;
+    use crate::ledger::repository::ddb_journal_repository::DDBJournalRepository;
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("journal").as_str(), "entry_id", "branch_id", "posted_at").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_create_find_journal_entries() {
+        let store = test_store(module_path!(), "test_should_create_find_journal_entries");
+        let repo = DDBJournalRepository::new(build_client(store).await, store.table_name("journal").as_str(), store.table_name("journal_ndx").as_str());
+        let entry = JournalEntryEntity::from(&JournalEntryDto::new("journal_repo_branch", "fine1", "fine assessed")
+            .transfer("revenue:fines", "receivable:fines", 250));
+        assert_eq!(1, repo.create(&entry).await.expect("should create entry"));
+        // posted entries cannot be overwritten
+        assert!(matches!(repo.create(&entry).await, Err(LibraryError::DuplicateKey { .. })));
+
+        let res = repo.find_by_branch("journal_repo_branch", entry.posted_at - Duration::hours(1),
+                                      entry.posted_at + Duration::hours(1), None, 10).await.expect("should find entries");
+        assert_eq!(1, res.records.len());
+        assert_eq!(entry.postings, res.records[0].postings);
+        assert_eq!(entry.entry_id, res.records[0].entry_id);
+        let res = repo.find_by_branch("journal_repo_branch", entry.posted_at + Duration::hours(1),
+                                      entry.posted_at + Duration::hours(2), None, 10).await.expect("should find entries");
+        assert_eq!(0, res.records.len());
+    }
+}
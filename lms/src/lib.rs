@@ -1,9 +1,109 @@
+mod acquisitions;
+mod admin;
+mod audit;
+mod branches;
 mod checkout;
 mod core;
 mod catalog;
+mod credentials;
+mod documents;
+mod fines;
 mod gateway;
 mod hold;
+mod ill;
+mod inventory;
 mod books;
+mod ledger;
+mod ncip;
+mod notifications;
 mod parties;
 mod patrons;
+mod programs;
+mod projector;
+mod reserves;
+mod resources;
+mod serials;
 mod utils;
+mod vendors;
+
+pub mod api;
+
+// bounded contexts stay private to the crate, binaries and other applications use the items exported below
+pub use crate::core::controller::{run_lambda, AppState};
+pub use crate::core::library::{LibraryError, LibraryResult};
+pub use crate::core::repository::RepositoryStore;
+pub use crate::admin::tables::check_tables;
+pub use crate::gateway::factory::check_topics;
+pub use crate::utils::ddb::setup_tracing;
+
+// HTTP routes of each bounded context with the request context middleware applied
+pub mod routes {
+    pub use crate::acquisitions::controller::router as acquisitions;
+    pub use crate::audit::controller::router as audit;
+    pub use crate::branches::controller::router as branches;
+    pub use crate::catalog::controller::router as catalog;
+    pub use crate::checkout::controller::router as checkout;
+    pub use crate::credentials::controller::router as credentials;
+    pub use crate::documents::controller::router as documents;
+    pub use crate::fines::controller::router as fines;
+    pub use crate::hold::controller::router as hold;
+    pub use crate::ill::controller::router as ill;
+    pub use crate::inventory::controller::router as inventory;
+    pub use crate::ncip::controller::router as ncip;
+    pub use crate::patrons::controller::router as patrons;
+    pub use crate::programs::controller::router as programs;
+    pub use crate::reserves::controller::router as reserves;
+    pub use crate::resources::controller::router as resources;
+    pub use crate::serials::controller::router as serials;
+    pub use crate::vendors::controller::router as vendors;
+}
+
+// background worker of the task queue
+pub mod tasks {
+    pub use crate::core::tasks::factory::create_task_worker;
+    pub use crate::core::tasks::worker::TaskWorker;
+}
+
+// consumers of domain events delivered by SQS and SNS triggers
+pub mod events {
+    pub use crate::gateway::factory::create_subscriber_registry;
+    pub use crate::gateway::lambda::{handle_sns_event, handle_sqs_event, SnsEvent, SnsMessage, SnsRecord,
+                                     SqsBatchItemFailure, SqsBatchResponse, SqsEvent, SqsMessage};
+    pub use crate::gateway::subscribers::SubscriberRegistry;
+}
+
+// table bootstrap of the admin binary
+pub mod tables {
+    pub use crate::admin::tables::{bootstrap_tables, describe_tables, AutoScaling, BootstrapOptions, IndexSchema, IndexSpec,
+                                   KeySchema, TableDescription, TableDrift, TableReport, TableSchema, TableSpec, TABLES};
+    pub use crate::utils::ddb::TableBilling;
+}
+
+// scripted scenario of the admin binary that doubles as living documentation and smoke test
+pub mod demo {
+    pub use crate::admin::demo::{run_demo, DemoReport, DemoStep};
+}
+
+// scheduled jobs of the admin binary
+pub mod jobs {
+    pub use crate::admin::anonymize::{copy_anonymized, CopySummary};
+    pub use crate::admin::jobs::{check_invariants, check_ledger, compact_events, compensate_stuck_sagas,
+                                 email_shelf_list, export_schemas, export_shelf_list, publish_overdue_checkouts,
+                                 purge_deleted_patrons, purge_expired_documents, queue_compact_events,
+                                 queue_rebuild_projection, rebuild_projection, replay_events, send_due_soon_digests,
+                                 DigestSummary};
+    pub use crate::core::invariants::InvariantViolation;
+    pub use crate::gateway::ddb::compaction::CompactionSummary;
+    pub use crate::ill::dto::StuckWorkflowDto;
+    pub use crate::ledger::dto::LedgerCheckDto;
+    pub use crate::projector::rebuild::RebuildProgress;
+    pub use crate::utils::ddb::allow_unbounded_scans;
+}
+
+// one-command local environment of the admin binary
+pub mod dev {
+    pub use crate::admin::dev::{create_dev_tables, merged_router, seed_data, SeedSummary};
+    #[cfg(feature = "dev")]
+    pub use crate::admin::dev::DynamoDBLocal;
+    pub use crate::core::domain::Configuration;
+}
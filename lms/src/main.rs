@@ -1,5 +0,0 @@
-include!("lib.rs");
-
-#[tokio::main]
-async fn main() {
-}
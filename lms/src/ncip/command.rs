@@ -0,0 +1 @@
+pub mod process_message_cmd;
@@ -0,0 +1,129 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::ncip::domain::NcipService;
+use crate::ncip::message::{parse_ncip, NcipResponse};
+
+pub(crate) struct ProcessNcipMessageCommand {
+    ncip_service: Box<dyn NcipService>,
+}
+
+impl ProcessNcipMessageCommand {
+    pub(crate) fn new(ncip_service: Box<dyn NcipService>) -> Self {
+        Self {
+            ncip_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ProcessNcipMessageCommandRequest {
+    xml: String,
+    agent: String,
+}
+
+impl ProcessNcipMessageCommandRequest {
+    pub fn new(xml: &str, agent: &str) -> Self {
+        Self {
+            xml: xml.to_string(),
+            agent: agent.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ProcessNcipMessageCommandResponse {
+    pub xml: String,
+}
+
+impl ProcessNcipMessageCommandResponse {
+    pub fn new(xml: String) -> Self {
+        Self {
+            xml,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<ProcessNcipMessageCommandRequest, ProcessNcipMessageCommandResponse> for ProcessNcipMessageCommand {
+    // messages that cannot be read are answered with a problem like failed services so that consortium systems
+    // always receive an NCIP response
+    async fn execute(&self, req: ProcessNcipMessageCommandRequest) -> Result<ProcessNcipMessageCommandResponse, CommandError> {
+        let res = match parse_ncip(req.xml.as_str()) {
+            Ok(request) => self.ncip_service.process(&request, req.agent.as_str()).await,
+            Err(problem) => NcipResponse::Problem { service: None, problem },
+        };
+        Ok(ProcessNcipMessageCommandResponse::new(res.to_xml()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::books::domain::model::BookEntity;
+    use crate::books::factory::create_book_repository;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{BookStatus, PartyKind};
+    use crate::ncip::command::process_message_cmd::{ProcessNcipMessageCommand, ProcessNcipMessageCommandRequest};
+    use crate::ncip::factory::create_ncip_service;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    fn message(body: &str) -> String {
+        format!("<NCIPMessage xmlns=\"http://www.niso.org/2008/ncip\" version=\"2.02\">{}</NCIPMessage>", body)
+    }
+
+    async fn process(sut_cmd: &ProcessNcipMessageCommand, body: &str) -> String {
+        sut_cmd.execute(ProcessNcipMessageCommandRequest::new(message(body).as_str(), "consortium"))
+            .await.expect("should respond").xml
+    }
+
+    #[tokio::test]
+    async fn test_should_run_process_ncip_message() {
+        let store = test_store(module_path!(), "test_should_run_process_ncip_message");
+        let config = Configuration::new("test");
+        let sut_cmd = ProcessNcipMessageCommand::new(create_ncip_service(&config, store).await);
+        let patron = PartyEntity::new(PartyKind::Patron, "ncip_cmd@example.com");
+        let _ = create_party_repository(store).await.create(&patron).await.expect("should create patron");
+        let book_repo = create_book_repository(store).await;
+        let loaned = BookEntity::new("isbn", "ncip loaned", BookStatus::Available);
+        let _ = book_repo.create(&loaned).await.expect("should create book");
+        let requested = BookEntity::new("isbn", "ncip requested", BookStatus::Available);
+        let _ = book_repo.create(&requested).await.expect("should create book");
+        let user = format!("<UserId><UserIdentifierValue>{}</UserIdentifierValue></UserId>", patron.party_id);
+
+        let xml = process(&sut_cmd, format!("<LookupUser>{}</LookupUser>", user).as_str()).await;
+        assert!(xml.contains("<LookupUserResponse>"));
+        assert!(xml.contains("<ElectronicAddressData>ncip_cmd@example.com</ElectronicAddressData>"));
+
+        let xml = process(&sut_cmd, format!(
+            "<CheckOutItem>{}<ItemId><ItemIdentifierValue>{}</ItemIdentifierValue></ItemId></CheckOutItem>",
+            user, loaned.book_id).as_str()).await;
+        assert!(xml.contains("<CheckOutItemResponse>"));
+        assert!(xml.contains("<DateDue>"));
+
+        let xml = process(&sut_cmd, format!(
+            "<CheckInItem><ItemId><ItemIdentifierValue>{}</ItemIdentifierValue></ItemId></CheckInItem>",
+            loaned.book_id).as_str()).await;
+        assert!(xml.contains(format!("<CheckInItemResponse><ItemId><ItemIdentifierValue>{}</ItemIdentifierValue></ItemId>",
+                                     loaned.book_id).as_str()));
+
+        let xml = process(&sut_cmd, format!(
+            "<RequestItem>{}<ItemId><ItemIdentifierValue>{}</ItemIdentifierValue></ItemId>\
+             <RequestType>Hold</RequestType><RequestScopeType>Item</RequestScopeType></RequestItem>",
+            user, requested.book_id).as_str()).await;
+        assert!(xml.contains("<RequestItemResponse><RequestId>"));
+
+        // failures are reported as problems of the service
+        let xml = process(&sut_cmd, "<LookupUser><UserId><UserIdentifierValue>unknown-ncip-user</UserIdentifierValue></UserId></LookupUser>").await;
+        assert!(xml.contains("<LookupUserResponse><Problem><ProblemType>Unknown User</ProblemType>"));
+        let xml = process(&sut_cmd, format!(
+            "<CheckOutItem>{}<ItemId><ItemIdentifierValue>unknown-ncip-book</ItemIdentifierValue></ItemId></CheckOutItem>",
+            user).as_str()).await;
+        assert!(xml.contains("<CheckOutItemResponse><Problem><ProblemType>Unknown Item</ProblemType>"));
+
+        let res = sut_cmd.execute(ProcessNcipMessageCommandRequest::new("not xml <", "consortium")).await.expect("should respond");
+        assert!(res.xml.contains("<Problem><ProblemType>Invalid Message Syntax Error</ProblemType>"));
+    }
+}
@@ -0,0 +1,31 @@
+use axum::{
+    extract::{Extension, State},
+    http::header,
+    middleware,
+    routing::post,
+    Router,
+};
+use crate::core::controller::{AppState, command_bus, with_common_layers, ServerError};
+use crate::credentials::controller::authenticate;
+use crate::credentials::dto::ClaimsDto;
+use crate::ncip::command::process_message_cmd::{ProcessNcipMessageCommand, ProcessNcipMessageCommandRequest, ProcessNcipMessageCommandResponse};
+use crate::ncip::factory;
+
+// consortium systems post NCIP messages and receive NCIP messages, problems of the message are returned with 200
+// as NCIP expects
+pub(crate) async fn process_message(
+    State(state): State<AppState>,
+    Extension(claims): Extension<ClaimsDto>,
+    xml: String) -> Result<([(header::HeaderName, &'static str); 1], String), ServerError> {
+    let req = ProcessNcipMessageCommandRequest::new(xml.as_str(), claims.principal().as_str());
+    let svc = factory::create_ncip_service(&state.config, state.store).await;
+    let res: ProcessNcipMessageCommandResponse = command_bus().register(ProcessNcipMessageCommand::new(svc)).dispatch(req).await?;
+    Ok(([(header::CONTENT_TYPE, "application/xml; charset=utf-8")], res.xml))
+}
+
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/ncip", post(process_message))
+        .route_layer(middleware::from_fn_with_state(state.clone(), authenticate));
+    with_common_layers(router, state)
+}
@@ -0,0 +1,12 @@
+pub mod service;
+
+use async_trait::async_trait;
+use crate::ncip::message::{NcipRequest, NcipResponse};
+
+// NcipService translates NCIP messages of consortium systems into calls of the patron, hold and checkout services,
+// failures of the services are answered with NCIP problems instead of errors
+#[async_trait]
+pub(crate) trait NcipService: Sync + Send {
+    // agent is the principal of the consortium system that is recorded as the staff member of check-ins
+    async fn process(&self, request: &NcipRequest, agent: &str) -> NcipResponse;
+}
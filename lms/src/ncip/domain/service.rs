@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use crate::checkout::domain::CheckoutService;
+use crate::core::ids::{BookId, PatronId};
+use crate::core::library::LibraryError;
+use crate::hold::domain::HoldService;
+use crate::ncip::domain::NcipService;
+use crate::ncip::message::{NcipProblem, NcipRequest, NcipResponse};
+use crate::patrons::domain::PatronQueryService;
+
+pub(crate) struct NcipServiceImpl {
+    patron_service: Box<dyn PatronQueryService>,
+    hold_service: Box<dyn HoldService>,
+    checkout_service: Box<dyn CheckoutService>,
+}
+
+impl NcipServiceImpl {
+    pub(crate) fn new(patron_service: Box<dyn PatronQueryService>,
+                      hold_service: Box<dyn HoldService>,
+                      checkout_service: Box<dyn CheckoutService>) -> Self {
+        Self {
+            patron_service,
+            hold_service,
+            checkout_service,
+        }
+    }
+
+    // users are looked up before items are requested or checked out so that unknown users and unknown items are
+    // told apart in the problem
+    async fn check_user(&self, user_id: &str) -> Result<(), NcipProblem> {
+        self.patron_service.find_patron_by_id(&PatronId::new(user_id)).await
+            .map(|_| ()).map_err(|err| unknown_user(&err, user_id))
+    }
+}
+
+// problem of an item error, errors other than not found and unavailability mean the operation is not allowed
+fn item_problem(err: &LibraryError, item_id: &str, not_allowed: &str) -> NcipProblem {
+    match err {
+        LibraryError::NotFound { .. } => NcipProblem::new("Unknown Item", err.message())
+            .with_element("ItemIdentifierValue", Some(item_id)),
+        LibraryError::CurrentlyUnavailable { .. } | LibraryError::Database { .. } | LibraryError::Runtime { .. } |
+        LibraryError::Serialization { .. } => NcipProblem::new("Temporary Processing Failure", err.message()),
+        _ => NcipProblem::new(not_allowed, err.message()).with_element("ItemIdentifierValue", Some(item_id)),
+    }
+}
+
+fn unknown_user(err: &LibraryError, user_id: &str) -> NcipProblem {
+    match err {
+        LibraryError::NotFound { .. } => NcipProblem::new("Unknown User", err.message())
+            .with_element("UserIdentifierValue", Some(user_id)),
+        _ => NcipProblem::new("Temporary Processing Failure", err.message()),
+    }
+}
+
+#[async_trait]
+impl NcipService for NcipServiceImpl {
+    async fn process(&self, request: &NcipRequest, agent: &str) -> NcipResponse {
+        let res = match request {
+            NcipRequest::LookupUser { user_id } => self.patron_service.find_patron_by_id(&PatronId::new(user_id)).await
+                .map(|patron| NcipResponse::LookupUser { patron })
+                .map_err(|err| unknown_user(&err, user_id)),
+            NcipRequest::RequestItem { user_id, item_id, pickup_location } => match self.check_user(user_id).await {
+                Ok(_) => self.hold_service.hold(&PatronId::new(user_id), &BookId::new(item_id), pickup_location.as_deref()).await
+                    .map(|hold| NcipResponse::RequestItem { hold })
+                    .map_err(|err| item_problem(&err, item_id, "User Ineligible To Request This Item")),
+                Err(problem) => Err(problem),
+            },
+            NcipRequest::CheckOutItem { user_id, item_id } => match self.check_user(user_id).await {
+                Ok(_) => self.checkout_service.checkout(&PatronId::new(user_id), &BookId::new(item_id)).await
+                    .map(|checkout| NcipResponse::CheckOutItem { checkout })
+                    .map_err(|err| item_problem(&err, item_id, "User Ineligible To Check Out This Item")),
+                Err(problem) => Err(problem),
+            },
+            NcipRequest::CheckInItem { item_id } => self.checkout_service.check_in(&BookId::new(item_id), None, agent).await
+                .map(|check_in| NcipResponse::CheckInItem { checkout: check_in.checkout })
+                .map_err(|err| item_problem(&err, item_id, "Item Not Checked Out")),
+        };
+        res.unwrap_or_else(|problem| NcipResponse::Problem { service: Some(request.service()), problem })
+    }
+}
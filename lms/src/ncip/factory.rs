@@ -0,0 +1,14 @@
+use crate::checkout::factory::create_checkout_service;
+use crate::core::domain::Configuration;
+use crate::core::repository::RepositoryStore;
+use crate::hold::factory::create_hold_service;
+use crate::ncip::domain::NcipService;
+use crate::ncip::domain::service::NcipServiceImpl;
+use crate::patrons::factory::create_patron_query_service;
+
+pub(crate) async fn create_ncip_service(config: &Configuration, store: RepositoryStore) -> Box<dyn NcipService> {
+    let patron_svc = create_patron_query_service(config, store).await;
+    let hold_svc = create_hold_service(config, store).await;
+    let checkout_svc = create_checkout_service(config, store).await;
+    Box::new(NcipServiceImpl::new(patron_svc, hold_svc, checkout_svc))
+}
@@ -0,0 +1,361 @@
+use chrono::NaiveDateTime;
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::{Namespace, ResolveResult};
+use quick_xml::NsReader;
+use crate::checkout::dto::CheckoutDto;
+use crate::hold::dto::HoldDto;
+use crate::patrons::dto::PatronDto;
+
+pub(crate) const NCIP_NAMESPACE: &str = "http://www.niso.org/2008/ncip";
+pub(crate) const NCIP_VERSION: &str = "http://www.niso.org/schemas/ncip/v2_02/ncip_v2_02.xsd";
+
+// MessageSchema is the subset of the NCIP 2.02 schema of a supported message that the facade reads, each path below
+// the message element must occur exactly once and carry a value, other elements of the schema are accepted and ignored
+struct MessageSchema {
+    name: &'static str,
+    required: &'static [&'static str],
+}
+
+const MESSAGE_SCHEMAS: [MessageSchema; 4] = [
+    MessageSchema { name: "LookupUser", required: &["UserId/UserIdentifierValue"] },
+    MessageSchema {
+        name: "RequestItem",
+        required: &["UserId/UserIdentifierValue", "ItemId/ItemIdentifierValue", "RequestType", "RequestScopeType"],
+    },
+    MessageSchema { name: "CheckOutItem", required: &["UserId/UserIdentifierValue", "ItemId/ItemIdentifierValue"] },
+    MessageSchema { name: "CheckInItem", required: &["ItemId/ItemIdentifierValue"] },
+];
+
+// NcipRequest is a supported initiation message, users are identified by patron id and items by book id
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum NcipRequest {
+    LookupUser { user_id: String },
+    // only holds are requested, the pickup location is the id of the pickup branch
+    RequestItem { user_id: String, item_id: String, pickup_location: Option<String> },
+    CheckOutItem { user_id: String, item_id: String },
+    CheckInItem { item_id: String },
+}
+
+impl NcipRequest {
+    pub(crate) fn service(&self) -> &'static str {
+        match self {
+            NcipRequest::LookupUser { .. } => "LookupUser",
+            NcipRequest::RequestItem { .. } => "RequestItem",
+            NcipRequest::CheckOutItem { .. } => "CheckOutItem",
+            NcipRequest::CheckInItem { .. } => "CheckInItem",
+        }
+    }
+}
+
+// NcipProblem is reported in place of the response data, types are values of the NCIP problem type scheme
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct NcipProblem {
+    pub problem_type: String,
+    pub detail: String,
+    pub element: Option<String>,
+    pub value: Option<String>,
+}
+
+impl NcipProblem {
+    pub(crate) fn new(problem_type: &str, detail: &str) -> Self {
+        Self {
+            problem_type: problem_type.to_string(),
+            detail: detail.to_string(),
+            element: None,
+            value: None,
+        }
+    }
+
+    pub(crate) fn with_element(mut self, element: &str, value: Option<&str>) -> Self {
+        self.element = Some(element.to_string());
+        self.value = value.map(String::from);
+        self
+    }
+}
+
+// NcipResponse is the response message of a service, or a problem of the service, or a problem of a message whose
+// service is not known
+#[derive(Debug, Clone)]
+pub(crate) enum NcipResponse {
+    LookupUser { patron: PatronDto },
+    RequestItem { hold: HoldDto },
+    CheckOutItem { checkout: CheckoutDto },
+    CheckInItem { checkout: CheckoutDto },
+    Problem { service: Option<&'static str>, problem: NcipProblem },
+}
+
+impl NcipResponse {
+    pub(crate) fn to_xml(&self) -> String {
+        let (name, body) = match self {
+            NcipResponse::LookupUser { patron } => {
+                let name = element("NameInformation", element("PersonalNameInformation", element(
+                    "StructuredPersonalUserName",
+                    format!("{}{}", leaf("GivenName", patron.first_name.as_str()), leaf("Surname", patron.last_name.as_str())))));
+                let email = element("UserAddressInformation", element("ElectronicAddress", format!(
+                    "{}{}", leaf("ElectronicAddressType", "mailto"), leaf("ElectronicAddressData", patron.email.as_str()))));
+                (Some("LookupUserResponse"), format!("{}{}", user_id(patron.patron_id.as_str()),
+                                                     element("UserOptionalFields", format!("{}{}", name, email))))
+            }
+            NcipResponse::RequestItem { hold } => (Some("RequestItemResponse"), format!(
+                "{}{}{}{}{}",
+                element("RequestId", leaf("RequestIdentifierValue", hold.hold_id.as_str())),
+                item_id(hold.book_id.as_str()),
+                user_id(hold.patron_id.as_str()),
+                leaf("RequestType", "Hold"),
+                leaf("RequestScopeType", "Item"))),
+            NcipResponse::CheckOutItem { checkout } => (Some("CheckOutItemResponse"), format!(
+                "{}{}{}", item_id(checkout.book_id.as_str()), user_id(checkout.patron_id.as_str()),
+                leaf("DateDue", date_time(checkout.due_at).as_str()))),
+            NcipResponse::CheckInItem { checkout } => (Some("CheckInItemResponse"), format!(
+                "{}{}", item_id(checkout.book_id.as_str()), user_id(checkout.patron_id.as_str()))),
+            NcipResponse::Problem { service, problem } => {
+                let mut body = format!("{}{}", leaf("ProblemType", problem.problem_type.as_str()),
+                                       leaf("ProblemDetail", problem.detail.as_str()));
+                if let Some(element) = &problem.element {
+                    body.push_str(leaf("ProblemElement", element.as_str()).as_str());
+                }
+                if let Some(value) = &problem.value {
+                    body.push_str(leaf("ProblemValue", value.as_str()).as_str());
+                }
+                // problems of unknown services are reported directly in the message
+                (service.map(response_name), element("Problem", body))
+            }
+        };
+        let message = match name {
+            Some(name) => element(name, body),
+            None => body,
+        };
+        format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?><NCIPMessage xmlns=\"{}\" version=\"{}\">{}</NCIPMessage>",
+                NCIP_NAMESPACE, NCIP_VERSION, message)
+    }
+}
+
+fn response_name(service: &str) -> &'static str {
+    match service {
+        "LookupUser" => "LookupUserResponse",
+        "RequestItem" => "RequestItemResponse",
+        "CheckOutItem" => "CheckOutItemResponse",
+        _ => "CheckInItemResponse",
+    }
+}
+
+fn element(name: &str, body: String) -> String {
+    format!("<{0}>{1}</{0}>", name, body)
+}
+
+fn leaf(name: &str, value: &str) -> String {
+    format!("<{0}>{1}</{0}>", name, escape(value))
+}
+
+fn user_id(patron_id: &str) -> String {
+    element("UserId", leaf("UserIdentifierValue", patron_id))
+}
+
+fn item_id(book_id: &str) -> String {
+    element("ItemId", leaf("ItemIdentifierValue", book_id))
+}
+
+fn date_time(at: NaiveDateTime) -> String {
+    at.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+// XmlElement is an element of a parsed message with its namespace, attributes by local name and text
+#[derive(Debug, Clone, Default)]
+struct XmlElement {
+    name: String,
+    namespace: String,
+    attributes: Vec<(String, String)>,
+    text: String,
+    children: Vec<XmlElement>,
+}
+
+impl XmlElement {
+    fn new(namespace: ResolveResult, start: &BytesStart) -> Result<Self, NcipProblem> {
+        let mut attributes = vec![];
+        for attr in start.attributes() {
+            let attr = attr.map_err(syntax_problem)?;
+            let value = attr.unescape_value().map_err(syntax_problem)?.to_string();
+            attributes.push((String::from_utf8_lossy(attr.key.local_name().as_ref()).to_string(), value));
+        }
+        Ok(Self {
+            name: String::from_utf8_lossy(start.local_name().as_ref()).to_string(),
+            namespace: match namespace {
+                ResolveResult::Bound(Namespace(ns)) => String::from_utf8_lossy(ns).to_string(),
+                _ => String::new(),
+            },
+            attributes,
+            ..XmlElement::default()
+        })
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+
+    // elements at the path of local names separated by /
+    fn find(&self, path: &str) -> Vec<&XmlElement> {
+        let mut found = vec![self];
+        for name in path.split('/') {
+            found = found.iter().flat_map(|element| element.children.iter().filter(|child| child.name == name)).collect();
+        }
+        found
+    }
+
+    fn value(&self, path: &str) -> Option<String> {
+        self.find(path).first().map(|element| element.text.trim().to_string()).filter(|value| !value.is_empty())
+    }
+}
+
+// reads a message and validates it against the supported subset of the schema, messages that cannot be read are
+// reported with the problem to respond with
+pub(crate) fn parse_ncip(xml: &str) -> Result<NcipRequest, NcipProblem> {
+    let root = parse_xml(xml)?;
+    if root.name != "NCIPMessage" || root.namespace != NCIP_NAMESPACE {
+        return Err(NcipProblem::new("Invalid Message Syntax Error",
+                                    format!("root element must be NCIPMessage of namespace {}", NCIP_NAMESPACE).as_str())
+            .with_element("NCIPMessage", Some(root.name.as_str())));
+    }
+    if root.attribute("version").unwrap_or_default().trim().is_empty() {
+        return Err(NcipProblem::new("Invalid Message Syntax Error", "NCIPMessage requires a version")
+            .with_element("NCIPMessage", None));
+    }
+    let message = match root.children.as_slice() {
+        [message] => message,
+        _ => return Err(NcipProblem::new("Invalid Message Syntax Error",
+                                         "NCIPMessage must contain exactly one message").with_element("NCIPMessage", None)),
+    };
+    let schema = MESSAGE_SCHEMAS.iter().find(|schema| schema.name == message.name)
+        .ok_or_else(|| NcipProblem::new("Unsupported Service", format!("{} is not supported", message.name).as_str())
+            .with_element(message.name.as_str(), None))?;
+    for path in schema.required {
+        let element = path.rsplit('/').next().unwrap_or(path);
+        match message.find(path).as_slice() {
+            [found] if !found.text.trim().is_empty() => {}
+            [] | [_] => return Err(NcipProblem::new("Needed Data Missing",
+                                                    format!("{} requires {}", schema.name, path).as_str())
+                .with_element(element, None)),
+            _ => return Err(NcipProblem::new("Invalid Message Syntax Error",
+                                             format!("{} allows one {}", schema.name, path).as_str())
+                .with_element(element, None)),
+        }
+    }
+    let required = |path: &str| message.value(path).unwrap_or_default();
+    match schema.name {
+        "LookupUser" => Ok(NcipRequest::LookupUser { user_id: required("UserId/UserIdentifierValue") }),
+        "RequestItem" => {
+            let request_type = required("RequestType");
+            if !request_type.eq_ignore_ascii_case("Hold") {
+                return Err(NcipProblem::new("Unknown Value From Known Scheme", "only holds can be requested")
+                    .with_element("RequestType", Some(request_type.as_str())));
+            }
+            Ok(NcipRequest::RequestItem {
+                user_id: required("UserId/UserIdentifierValue"),
+                item_id: required("ItemId/ItemIdentifierValue"),
+                pickup_location: message.value("PickupLocation"),
+            })
+        }
+        "CheckOutItem" => Ok(NcipRequest::CheckOutItem {
+            user_id: required("UserId/UserIdentifierValue"),
+            item_id: required("ItemId/ItemIdentifierValue"),
+        }),
+        _ => Ok(NcipRequest::CheckInItem { item_id: required("ItemId/ItemIdentifierValue") }),
+    }
+}
+
+fn parse_xml(xml: &str) -> Result<XmlElement, NcipProblem> {
+    let mut reader = NsReader::from_str(xml);
+    reader.trim_text(true);
+    let mut stack: Vec<XmlElement> = vec![];
+    let mut root: Option<XmlElement> = None;
+    loop {
+        match reader.read_resolved_event().map_err(syntax_problem)? {
+            (ns, Event::Start(e)) => stack.push(XmlElement::new(ns, &e)?),
+            (ns, Event::Empty(e)) => {
+                let element = XmlElement::new(ns, &e)?;
+                append(&mut stack, &mut root, element);
+            }
+            (_, Event::Text(e)) => if let Some(element) = stack.last_mut() {
+                element.text.push_str(e.unescape().map_err(syntax_problem)?.as_ref());
+            },
+            (_, Event::End(_)) => if let Some(element) = stack.pop() {
+                append(&mut stack, &mut root, element);
+            },
+            (_, Event::Eof) => break,
+            _ => {}
+        }
+    }
+    if !stack.is_empty() {
+        return Err(NcipProblem::new("Invalid Message Syntax Error", "message is not closed"));
+    }
+    root.ok_or_else(|| NcipProblem::new("Invalid Message Syntax Error", "message is empty"))
+}
+
+fn append(stack: &mut [XmlElement], root: &mut Option<XmlElement>, element: XmlElement) {
+    match stack.last_mut() {
+        Some(parent) => parent.children.push(element),
+        None => *root = Some(element),
+    }
+}
+
+fn syntax_problem<E: std::fmt::Display>(err: E) -> NcipProblem {
+    NcipProblem::new("Invalid Message Syntax Error", format!("message is malformed: {}", err).as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ncip::message::{parse_ncip, NcipProblem, NcipRequest, NcipResponse};
+
+    fn message(body: &str) -> String {
+        format!(r#"<?xml version="1.0" encoding="UTF-8"?>
+            <ns1:NCIPMessage xmlns:ns1="http://www.niso.org/2008/ncip" ns1:version="http://www.niso.org/schemas/ncip/v2_02/ncip_v2_02.xsd">
+              {}
+            </ns1:NCIPMessage>"#, body)
+    }
+
+    #[tokio::test]
+    async fn test_should_parse_ncip_messages() {
+        let req = parse_ncip(message(r#"<ns1:RequestItem>
+                <ns1:InitiationHeader><ns1:FromAgencyId><ns1:AgencyId>CONSORTIUM</ns1:AgencyId></ns1:FromAgencyId></ns1:InitiationHeader>
+                <ns1:UserId><ns1:UserIdentifierValue>patron1</ns1:UserIdentifierValue></ns1:UserId>
+                <ns1:ItemId><ns1:ItemIdentifierValue>book1</ns1:ItemIdentifierValue></ns1:ItemId>
+                <ns1:RequestType>Hold</ns1:RequestType>
+                <ns1:RequestScopeType>Item</ns1:RequestScopeType>
+                <ns1:PickupLocation>branch2</ns1:PickupLocation>
+              </ns1:RequestItem>"#).as_str()).expect("should parse request item");
+        assert_eq!(NcipRequest::RequestItem {
+            user_id: "patron1".to_string(),
+            item_id: "book1".to_string(),
+            pickup_location: Some("branch2".to_string()),
+        }, req);
+
+        let req = parse_ncip(message(
+            "<ns1:CheckInItem><ns1:ItemId><ns1:ItemIdentifierValue>book&amp;1</ns1:ItemIdentifierValue></ns1:ItemId></ns1:CheckInItem>"
+        ).as_str()).expect("should parse check in item");
+        assert_eq!(NcipRequest::CheckInItem { item_id: "book&1".to_string() }, req);
+
+        let problem = parse_ncip(message("<ns1:CheckOutItem><ns1:UserId/></ns1:CheckOutItem>").as_str())
+            .expect_err("should require user and item");
+        assert_eq!("Needed Data Missing", problem.problem_type.as_str());
+        assert_eq!(Some("UserIdentifierValue"), problem.element.as_deref());
+
+        let problem = parse_ncip(message("<ns1:RenewItem/>").as_str()).expect_err("should not support renewals");
+        assert_eq!("Unsupported Service", problem.problem_type.as_str());
+        assert!(parse_ncip("<NCIPMessage version=\"2\"><LookupUser/></NCIPMessage>").is_err());
+        assert!(parse_ncip("<ns1:NCIPMessage xmlns:ns1=\"http://www.niso.org/2008/ncip\">").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_write_ncip_problems() {
+        let xml = NcipResponse::Problem {
+            service: Some("CheckOutItem"),
+            problem: NcipProblem::new("Unknown Item", "book <1> is not found").with_element("ItemIdentifierValue", Some("1")),
+        }.to_xml();
+        assert!(xml.contains("<CheckOutItemResponse><Problem><ProblemType>Unknown Item</ProblemType>\
+            <ProblemDetail>book &lt;1&gt; is not found</ProblemDetail><ProblemElement>ItemIdentifierValue</ProblemElement>\
+            <ProblemValue>1</ProblemValue></Problem></CheckOutItemResponse>"));
+
+        let xml = NcipResponse::Problem { service: None, problem: NcipProblem::new("Unsupported Service", "unknown") }.to_xml();
+        assert!(xml.contains("version=\"http://www.niso.org/schemas/ncip/v2_02/ncip_v2_02.xsd\"><Problem>"));
+    }
+}
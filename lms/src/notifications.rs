@@ -0,0 +1,8 @@
+pub mod controller;
+pub mod domain;
+pub mod dto;
+pub mod email;
+pub mod factory;
+pub mod push;
+pub mod repository;
+pub mod tasks;
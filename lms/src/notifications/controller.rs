@@ -0,0 +1,53 @@
+use axum::{
+    extract::{Extension, ws::{Message, WebSocket, WebSocketUpgrade}},
+    middleware,
+    response::Response,
+    routing::get,
+    Router,
+};
+use tokio::sync::broadcast::error::RecvError;
+use crate::core::controller::{AppState, with_common_layers};
+use crate::credentials::controller::authenticate;
+use crate::credentials::dto::ClaimsDto;
+use crate::gateway::stream::subscribe_events;
+use crate::notifications::push::to_push_message;
+
+pub(crate) async fn push_notifications(
+    Extension(claims): Extension<ClaimsDto>,
+    ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(move |socket| push_to_socket(socket, claims.sub))
+}
+
+// forwards notifications of the party until the client closes the socket, the client is not expected
+// to send anything else
+async fn push_to_socket(mut socket: WebSocket, party_id: String) {
+    let mut events = subscribe_events();
+    loop {
+        tokio::select! {
+            received = socket.recv() => match received {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+            event = events.recv() => match event {
+                Ok(event) => {
+                    let Some(message) = to_push_message(&event, party_id.as_str()) else { continue };
+                    let Ok(text) = serde_json::to_string(&message) else { continue };
+                    if socket.send(Message::Text(text)).await.is_err() {
+                        break;
+                    }
+                }
+                // notifications missed by a slow client remain available from the notifications listing
+                Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => break,
+            },
+        }
+    }
+}
+
+// pushes only see notifications requested in this process so the socket is served by the standalone server
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/notifications/ws", get(push_notifications))
+        .route_layer(middleware::from_fn_with_state(state.clone(), authenticate));
+    with_common_layers(router, state)
+}
@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+use crate::core::library::{LibraryResult, PaginatedResult, PushTopic};
+use crate::notifications::dto::NotificationDto;
+
+pub mod model;
+pub mod query;
+pub mod service;
+
+// read side of notifications
+#[async_trait]
+pub(crate) trait NotificationQueryService: Sync + Send {
+    async fn find_notifications(&self, party_id: &str,
+                                page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<NotificationDto>>;
+}
+
+#[async_trait]
+pub(crate) trait NotificationService: NotificationQueryService {
+    // records the notification for the party and publishes it for delivery to the email of the party
+    async fn notify(&self, party_id: &str, subject: &str, message: &str) -> LibraryResult<NotificationDto>;
+    // notifies the party and also pushes the notification under the topic to connected clients of the party
+    async fn notify_push(&self, topic: PushTopic, party_id: &str, subject: &str, message: &str) -> LibraryResult<NotificationDto>;
+    // notifies the party unless a notification with the same delivery key was sent before, scheduled jobs use it
+    // so that running them again does not repeat their notifications
+    async fn notify_once(&self, delivery_key: &str, topic: Option<PushTopic>, party_id: &str, subject: &str,
+                         message: &str) -> LibraryResult<Option<NotificationDto>>;
+}
@@ -0,0 +1,29 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::utils::date::serializer;
+
+// NotificationEntity keeps a message sent to a party so that it can be looked up later
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct NotificationEntity {
+    pub notification_id: String,
+    pub party_id: String,
+    pub email: String,
+    pub subject: String,
+    pub message: String,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+}
+
+impl NotificationEntity {
+    pub fn new(party_id: &str, email: &str, subject: &str, message: &str) -> Self {
+        Self {
+            notification_id: Uuid::new_v4().to_string(),
+            party_id: party_id.to_string(),
+            email: email.to_string(),
+            subject: subject.to_string(),
+            message: message.to_string(),
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use crate::core::library::{LibraryResult, PaginatedResult};
+use crate::notifications::domain::NotificationQueryService;
+use crate::notifications::dto::NotificationDto;
+use crate::notifications::repository::NotificationRepository;
+
+pub(crate) struct NotificationQueryServiceImpl {
+    notification_repository: Box<dyn NotificationRepository>,
+}
+
+impl NotificationQueryServiceImpl {
+    pub(crate) fn new(notification_repository: Box<dyn NotificationRepository>) -> Self {
+        Self {
+            notification_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationQueryService for NotificationQueryServiceImpl {
+    async fn find_notifications(&self, party_id: &str,
+                                page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<NotificationDto>> {
+        let res = self.notification_repository.find_by_party(party_id, page, page_size).await?;
+        let records = res.records.iter().map(NotificationDto::from).collect();
+        Ok(PaginatedResult::new(page, page_size, res.next_page, records))
+    }
+}
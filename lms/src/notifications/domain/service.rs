@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use crate::core::events::DomainEvent;
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult, PushTopic};
+use crate::gateway::events::EventPublisher;
+use crate::notifications::domain::model::NotificationEntity;
+use crate::notifications::domain::{NotificationQueryService, NotificationService};
+use crate::notifications::dto::NotificationDto;
+use crate::notifications::repository::NotificationRepository;
+use crate::parties::repository::PartyRepository;
+
+pub(crate) const NOTIFICATION_REQUESTED: &str = "notification_requested";
+// metadata of requested notifications that are pushed to connected clients
+pub(crate) const PUSH_TOPIC: &str = "push_topic";
+
+pub(crate) struct NotificationServiceImpl {
+    notification_repository: Box<dyn NotificationRepository>,
+    party_repository: Box<dyn PartyRepository>,
+    events_publisher: Box<dyn EventPublisher>,
+    query_service: Box<dyn NotificationQueryService>,
+}
+
+impl NotificationServiceImpl {
+    pub(crate) fn new(notification_repository: Box<dyn NotificationRepository>,
+                      party_repository: Box<dyn PartyRepository>,
+                      events_publisher: Box<dyn EventPublisher>,
+                      query_service: Box<dyn NotificationQueryService>) -> Self {
+        Self {
+            notification_repository,
+            party_repository,
+            events_publisher,
+            query_service,
+        }
+    }
+
+    async fn dispatch(&self, topic: Option<PushTopic>, party_id: &str, subject: &str, message: &str) -> LibraryResult<NotificationDto> {
+        if subject.is_empty() {
+            return Err(LibraryError::validation("notification subject is required", Some("400".to_string())));
+        }
+        let party = self.party_repository.get(party_id).await?;
+        let notification = NotificationEntity::new(party_id, party.email.as_str(), subject, message);
+        self.notification_repository.create(&notification).await?;
+        let dto = NotificationDto::from(&notification);
+        let metadata = topic.map(|topic| HashMap::from([(PUSH_TOPIC.to_string(), topic.to_string())])).unwrap_or_default();
+        // delivery is done by subscribers of the event so that sending emails does not block the request
+        let _ = self.events_publisher.publish(&DomainEvent::added(
+            NOTIFICATION_REQUESTED, "notifications", dto.notification_id.as_str(), &metadata, &dto)?).await?;
+        Ok(dto)
+    }
+}
+
+#[async_trait]
+impl NotificationService for NotificationServiceImpl {
+    async fn notify(&self, party_id: &str, subject: &str, message: &str) -> LibraryResult<NotificationDto> {
+        self.dispatch(None, party_id, subject, message).await
+    }
+
+    async fn notify_push(&self, topic: PushTopic, party_id: &str, subject: &str, message: &str) -> LibraryResult<NotificationDto> {
+        self.dispatch(Some(topic), party_id, subject, message).await
+    }
+
+    async fn notify_once(&self, delivery_key: &str, topic: Option<PushTopic>, party_id: &str, subject: &str,
+                         message: &str) -> LibraryResult<Option<NotificationDto>> {
+        if !self.notification_repository.claim_delivery(delivery_key, party_id).await? {
+            return Ok(None);
+        }
+        match self.dispatch(topic, party_id, subject, message).await {
+            Ok(dto) => Ok(Some(dto)),
+            Err(err) => {
+                let _ = self.notification_repository.release_delivery(delivery_key).await;
+                Err(err)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationQueryService for NotificationServiceImpl {
+    async fn find_notifications(&self, party_id: &str,
+                                page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<NotificationDto>> {
+        self.query_service.find_notifications(party_id, page, page_size).await
+    }
+}
+
+impl From<&NotificationEntity> for NotificationDto {
+    fn from(other: &NotificationEntity) -> NotificationDto {
+        NotificationDto {
+            notification_id: other.notification_id.to_string(),
+            party_id: other.party_id.to_string(),
+            email: other.email.to_string(),
+            subject: other.subject.to_string(),
+            message: other.message.to_string(),
+            created_at: other.created_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::core::library::PartyKind;
+    use crate::core::repository::RepositoryStore;
+    use crate::notifications::domain::NotificationService;
+    use crate::notifications::factory;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn NotificationService> {
+        factory::create_notification_service(store).await
+    }
+
+    #[tokio::test]
+    async fn test_should_notify_party() {
+        let store = test_store(module_path!(), "test_should_notify_party");
+        let notification_svc = sut_svc(store).await;
+        let patron = PartyEntity::new(PartyKind::Patron, "notify@example.com");
+        let _ = create_party_repository(store).await.create(&patron).await.expect("should create patron");
+
+        let notification = notification_svc.notify(patron.party_id.as_str(), "Story time", "Starts tomorrow")
+            .await.expect("should notify");
+        assert_eq!("notify@example.com", notification.email.as_str());
+        assert!(notification_svc.notify("unknown", "Story time", "").await.is_err());
+        assert!(notification_svc.notify(patron.party_id.as_str(), "", "").await.is_err());
+
+        let res = notification_svc.find_notifications(patron.party_id.as_str(), None, 10).await.expect("should find notifications");
+        assert_eq!(1, res.records.len());
+    }
+
+    #[tokio::test]
+    async fn test_should_notify_once_per_delivery_key() {
+        let store = test_store(module_path!(), "test_should_notify_once_per_delivery_key");
+        let notification_svc = sut_svc(store).await;
+        let patron = PartyEntity::new(PartyKind::Patron, "notify_once@example.com");
+        let _ = create_party_repository(store).await.create(&patron).await.expect("should create patron");
+        let key = format!("digest#{}", patron.party_id);
+
+        let first = notification_svc.notify_once(key.as_str(), None, patron.party_id.as_str(), "Digest", "Due soon")
+            .await.expect("should notify");
+        assert!(first.is_some());
+        let second = notification_svc.notify_once(key.as_str(), None, patron.party_id.as_str(), "Digest", "Due soon")
+            .await.expect("should skip notification");
+        assert_eq!(None, second);
+
+        // failed deliveries are released so that they are not skipped by the next run
+        assert!(notification_svc.notify_once("digest#unknown", None, "unknown", "Digest", "Due soon").await.is_err());
+        assert!(notification_svc.notify_once("digest#unknown", None, "unknown", "Digest", "Due soon").await.is_err());
+    }
+}
@@ -0,0 +1,24 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use crate::core::library::PushTopic;
+use crate::utils::date::{serializer, Rfc3339};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct NotificationDto {
+    pub notification_id: String,
+    pub party_id: String,
+    pub email: String,
+    pub subject: String,
+    pub message: String,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub created_at: NaiveDateTime,
+}
+
+// message pushed to connected clients of the party the notification was sent to
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct PushMessageDto {
+    pub topic: PushTopic,
+    pub notification: NotificationDto,
+}
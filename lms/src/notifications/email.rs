@@ -0,0 +1,83 @@
+use async_trait::async_trait;
+use serde_json::json;
+use tracing::log::info;
+use crate::core::events::DomainEvent;
+use crate::core::library::LibraryResult;
+use crate::gateway::ses::{Email, EmailSender};
+use crate::gateway::subscribers::EventSubscriber;
+use crate::notifications::domain::service::NOTIFICATION_REQUESTED;
+use crate::notifications::dto::NotificationDto;
+
+// NotificationEmailSubscriber delivers requested notifications to the email of the party, notifications are rendered
+// from the template when one is configured so that the branding of emails is maintained in the sending account
+pub(crate) struct NotificationEmailSubscriber {
+    email_sender: Box<dyn EmailSender>,
+    template: Option<String>,
+}
+
+impl NotificationEmailSubscriber {
+    pub(crate) fn new(email_sender: Box<dyn EmailSender>, template: Option<String>) -> Self {
+        Self {
+            email_sender,
+            template,
+        }
+    }
+}
+
+#[async_trait]
+impl EventSubscriber for NotificationEmailSubscriber {
+    fn name(&self) -> String {
+        "notification_email".to_string()
+    }
+
+    fn handles(&self, event: &DomainEvent) -> bool {
+        event.name == NOTIFICATION_REQUESTED
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> LibraryResult<()> {
+        let notification: NotificationDto = serde_json::from_str(event.json_data.as_str())?;
+        // parties without email such as api clients only see notifications in the app
+        if notification.email.is_empty() {
+            return Ok(());
+        }
+        let mut email = Email::new(notification.email.as_str(), notification.subject.as_str(), notification.message.as_str());
+        if let Some(template) = &self.template {
+            email = email.with_template(template, json!({
+                "subject": notification.subject,
+                "message": notification.message,
+                "party_id": notification.party_id,
+            }));
+        }
+        let message_id = self.email_sender.send(&email).await?;
+        info!("sent notification {} as email {}", notification.notification_id, message_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::core::events::DomainEvent;
+    use crate::gateway::ses::LocalEmailSender;
+    use crate::gateway::subscribers::EventSubscriber;
+    use crate::notifications::domain::model::NotificationEntity;
+    use crate::notifications::domain::service::NOTIFICATION_REQUESTED;
+    use crate::notifications::dto::NotificationDto;
+    use crate::notifications::email::NotificationEmailSubscriber;
+
+    #[tokio::test]
+    async fn test_should_email_requested_notifications() {
+        let dir = std::env::temp_dir().join(format!("lms-emails-{}", uuid::Uuid::new_v4()));
+        let subscriber = NotificationEmailSubscriber::new(
+            Box::new(LocalEmailSender::new(dir.clone(), "library@example.com")), None);
+        let dto = NotificationDto::from(&NotificationEntity::new("party1", "patron@example.com", "Hold ready", "pick it up"));
+        let event = DomainEvent::added(NOTIFICATION_REQUESTED, "notifications", dto.notification_id.as_str(),
+                                       &HashMap::new(), &dto).expect("build event");
+        assert!(subscriber.handles(&event));
+        subscriber.handle(&event).await.expect("should send email");
+
+        let emails = std::fs::read_dir(&dir).expect("should write emails").count();
+        assert_eq!(1, emails);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,54 @@
+use crate::core::repository::RepositoryStore;
+use crate::core::tasks::TaskHandler;
+use crate::gateway::factory::{create_email_sender, create_publisher};
+use crate::gateway::subscribers::EventSubscriber;
+use crate::notifications::domain::{NotificationQueryService, NotificationService};
+use crate::notifications::domain::query::NotificationQueryServiceImpl;
+use crate::notifications::domain::service::NotificationServiceImpl;
+use crate::notifications::email::NotificationEmailSubscriber;
+use crate::notifications::factory;
+use crate::notifications::repository::ddb_notification_repository::{DDBNotificationRepository, DELIVERIES_TABLE};
+use crate::notifications::repository::NotificationRepository;
+use crate::notifications::tasks::NotificationTaskHandler;
+use crate::parties::factory::create_party_repository;
+use crate::utils::ddb::{build_db_client, create_key_table, create_table};
+
+pub(crate) async fn create_notification_repository(store: RepositoryStore) -> Box<dyn NotificationRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBNotificationRepository::new(client, "notifications", "notifications_ndx", DELIVERIES_TABLE))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("notifications");
+            let _ = create_table(&client, table_name.as_str(), "notification_id", "party_id", "created_at").await;
+            let deliveries_table_name = store.table_name(DELIVERIES_TABLE);
+            let _ = create_key_table(&client, deliveries_table_name.as_str(), "delivery_key").await;
+            Box::new(DDBNotificationRepository::new(client, table_name.as_str(), store.table_name("notifications_ndx").as_str(),
+                                                    deliveries_table_name.as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_notification_query_service(store: RepositoryStore) -> Box<dyn NotificationQueryService> {
+    let notification_repo = factory::create_notification_repository(store).await;
+    Box::new(NotificationQueryServiceImpl::new(notification_repo))
+}
+
+pub(crate) async fn create_notification_service(store: RepositoryStore) -> Box<dyn NotificationService> {
+    let notification_repo = factory::create_notification_repository(store).await;
+    let party_repo = create_party_repository(store).await;
+    let publisher = create_publisher(store.gateway_publisher()).await;
+    let query_svc = create_notification_query_service(store).await;
+    Box::new(NotificationServiceImpl::new(notification_repo, party_repo, publisher, query_svc))
+}
+
+pub(crate) async fn create_notification_task_handler(store: RepositoryStore) -> Box<dyn TaskHandler> {
+    Box::new(NotificationTaskHandler::new(create_notification_service(store).await))
+}
+
+// notifications are rendered from the SES template of EMAIL_TEMPLATE when it is set
+pub(crate) async fn create_notification_email_subscriber() -> Box<dyn EventSubscriber> {
+    Box::new(NotificationEmailSubscriber::new(create_email_sender().await, std::env::var("EMAIL_TEMPLATE").ok()))
+}
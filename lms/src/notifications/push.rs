@@ -0,0 +1,43 @@
+use crate::core::events::DomainEvent;
+use crate::core::library::PushTopic;
+use crate::notifications::domain::service::{NOTIFICATION_REQUESTED, PUSH_TOPIC};
+use crate::notifications::dto::{NotificationDto, PushMessageDto};
+
+// builds the message pushed to the party from a requested notification, notifications without a push topic
+// or sent to other parties are not pushed
+pub(crate) fn to_push_message(event: &DomainEvent, party_id: &str) -> Option<PushMessageDto> {
+    if event.name != NOTIFICATION_REQUESTED {
+        return None;
+    }
+    let topic = PushTopic::try_from(event.metadata.get(PUSH_TOPIC)?.to_string()).ok()?;
+    let notification: NotificationDto = serde_json::from_str(event.json_data.as_str()).ok()?;
+    if notification.party_id != party_id {
+        return None;
+    }
+    Some(PushMessageDto { topic, notification })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::core::events::DomainEvent;
+    use crate::core::library::PushTopic;
+    use crate::notifications::domain::model::NotificationEntity;
+    use crate::notifications::domain::service::{NOTIFICATION_REQUESTED, PUSH_TOPIC};
+    use crate::notifications::dto::NotificationDto;
+    use crate::notifications::push::to_push_message;
+
+    #[tokio::test]
+    async fn test_should_push_notifications_with_topic_to_their_party() {
+        let dto = NotificationDto::from(&NotificationEntity::new("party1", "party1@example.com", "Hold ready", "Pick it up"));
+        let metadata = HashMap::from([(PUSH_TOPIC.to_string(), PushTopic::HoldReady.to_string())]);
+        let event = DomainEvent::added(NOTIFICATION_REQUESTED, "notifications", dto.notification_id.as_str(), &metadata, &dto).expect("build event");
+        let message = to_push_message(&event, "party1").expect("should push message");
+        assert_eq!(PushTopic::HoldReady, message.topic);
+        assert_eq!(dto, message.notification);
+        assert!(to_push_message(&event, "party2").is_none());
+
+        let plain = DomainEvent::added(NOTIFICATION_REQUESTED, "notifications", dto.notification_id.as_str(), &HashMap::new(), &dto).expect("build event");
+        assert!(to_push_message(&plain, "party1").is_none());
+    }
+}
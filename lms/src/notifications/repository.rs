@@ -0,0 +1,16 @@
+pub mod ddb_notification_repository;
+
+use async_trait::async_trait;
+use crate::core::library::{LibraryResult, PaginatedResult};
+use crate::notifications::domain::model::NotificationEntity;
+
+#[async_trait]
+pub(crate) trait NotificationRepository: Sync + Send {
+    async fn create(&self, entity: &NotificationEntity) -> LibraryResult<usize>;
+    async fn find_by_party(&self, party_id: &str,
+                           page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<NotificationEntity>>;
+    // claims the delivery key for the party, returns false when the key was claimed by an earlier delivery
+    async fn claim_delivery(&self, delivery_key: &str, party_id: &str) -> LibraryResult<bool>;
+    // releases the claim of a delivery that failed so that the next run can send it
+    async fn release_delivery(&self, delivery_key: &str) -> LibraryResult<()>;
+}
@@ -0,0 +1,161 @@
+use std::cmp;
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult};
+use crate::notifications::domain::model::NotificationEntity;
+use crate::notifications::repository::NotificationRepository;
+use crate::utils::ddb::{from_ddb, parse_date_attribute, parse_item, parse_string_attribute, string_date, to_ddb_page};
+
+// deliveries that must not be repeated such as daily digests are claimed in this table before they are sent
+pub(crate) const DELIVERIES_TABLE: &str = "notification_deliveries";
+
+#[derive(Debug)]
+pub(crate) struct DDBNotificationRepository {
+    client: Client,
+    table_name: String,
+    index_name: String,
+    deliveries_table_name: String,
+}
+
+impl DDBNotificationRepository {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str, deliveries_table_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+            deliveries_table_name: deliveries_table_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationRepository for DDBNotificationRepository {
+    async fn create(&self, entity: &NotificationEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        let mut item = parse_item(val)?;
+        item.insert("created_at".to_string(), string_date(entity.created_at));
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(notification_id)")
+            .set_item(Some(item))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn find_by_party(&self, party_id: &str,
+                           page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<NotificationEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let predicate = HashMap::from([("party_id".to_string(), party_id.to_string())]);
+        self.client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .limit(cmp::min(page_size, 500) as i32)
+            .consistent_read(false)
+            .scan_index_forward(false)
+            .set_exclusive_start_key(to_ddb_page(page, &predicate))
+            .key_condition_expression("party_id = :party_id")
+            .expression_attribute_values(":party_id", AttributeValue::S(party_id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(NotificationEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+
+    async fn claim_delivery(&self, delivery_key: &str, party_id: &str) -> LibraryResult<bool> {
+        let res = self.client
+            .put_item()
+            .table_name(self.deliveries_table_name.as_str())
+            .condition_expression("attribute_not_exists(delivery_key)")
+            .item("delivery_key", AttributeValue::S(delivery_key.to_string()))
+            .item("party_id", AttributeValue::S(party_id.to_string()))
+            .item("created_at", string_date(Utc::now().naive_utc()))
+            .send()
+            .await;
+        match res {
+            Ok(_) => Ok(true),
+            Err(SdkError::ServiceError(ctx)) if ctx.err().is_conditional_check_failed_exception() => Ok(false),
+            Err(err) => Err(LibraryError::from(err)),
+        }
+    }
+
+    async fn release_delivery(&self, delivery_key: &str) -> LibraryResult<()> {
+        self.client
+            .delete_item()
+            .table_name(self.deliveries_table_name.as_str())
+            .key("delivery_key", AttributeValue::S(delivery_key.to_string()))
+            .send()
+            .await.map(|_| ()).map_err(LibraryError::from)
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for NotificationEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        NotificationEntity {
+            notification_id: parse_string_attribute("notification_id", map).unwrap_or_else(|| String::from("")),
+            party_id: parse_string_attribute("party_id", map).unwrap_or_else(|| String::from("")),
+            email: parse_string_attribute("email", map).unwrap_or_else(|| String::from("")),
+            subject: parse_string_attribute("subject", map).unwrap_or_else(|| String::from("")),
+            message: parse_string_attribute("message", map).unwrap_or_else(|| String::from("")),
+            created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+
+    use crate::core::repository::RepositoryStore;
+    use crate::notifications::domain::model::NotificationEntity;
+    use crate::notifications::repository::ddb_notification_repository::{DDBNotificationRepository, DELIVERIES_TABLE};
+    use crate::notifications::repository::NotificationRepository;
+    use crate::utils::ddb::{build_db_client, create_key_table, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("notifications").as_str(), "notification_id", "party_id", "created_at").await;
+        let _ = create_key_table(&client, store.table_name(DELIVERIES_TABLE).as_str(), "delivery_key").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_create_find_notifications() {
+        let store = test_store(module_path!(), "test_should_create_find_notifications");
+        let repo = DDBNotificationRepository::new(build_client(store).await, store.table_name("notifications").as_str(), store.table_name("notifications_ndx").as_str(),
+            store.table_name(DELIVERIES_TABLE).as_str());
+        let notification = NotificationEntity::new("notified_party", "party@example.com", "Reminder", "See you soon");
+        assert_eq!(1, repo.create(&notification).await.expect("should create notification"));
+        assert!(repo.create(&notification).await.is_err());
+
+        let res = repo.find_by_party("notified_party", None, 10).await.expect("should find notifications");
+        assert_eq!(1, res.records.len());
+        assert_eq!("Reminder", res.records[0].subject.as_str());
+        assert_eq!(notification.created_at, res.records[0].created_at);
+        let res = repo.find_by_party("other_party", None, 10).await.expect("should find notifications");
+        assert_eq!(0, res.records.len());
+    }
+
+    #[tokio::test]
+    async fn test_should_claim_delivery_once() {
+        let store = test_store(module_path!(), "test_should_claim_delivery_once");
+        let repo = DDBNotificationRepository::new(build_client(store).await, store.table_name("notifications").as_str(), store.table_name("notifications_ndx").as_str(),
+            store.table_name(DELIVERIES_TABLE).as_str());
+        assert!(repo.claim_delivery("digest#claimed_party", "claimed_party").await.expect("should claim delivery"));
+        assert!(!repo.claim_delivery("digest#claimed_party", "claimed_party").await.expect("should claim delivery"));
+        repo.release_delivery("digest#claimed_party").await.expect("should release delivery");
+        assert!(repo.claim_delivery("digest#claimed_party", "claimed_party").await.expect("should claim delivery"));
+    }
+}
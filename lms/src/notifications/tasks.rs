@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+use crate::core::library::LibraryResult;
+use crate::core::tasks::{Task, TaskHandler, TaskPayload};
+use crate::notifications::domain::NotificationService;
+
+// NotificationTaskHandler delivers notifications that were deferred to the task queue
+pub(crate) struct NotificationTaskHandler {
+    notification_service: Box<dyn NotificationService>,
+}
+
+impl NotificationTaskHandler {
+    pub(crate) fn new(notification_service: Box<dyn NotificationService>) -> Self {
+        Self {
+            notification_service,
+        }
+    }
+}
+
+#[async_trait]
+impl TaskHandler for NotificationTaskHandler {
+    fn handles(&self, task: &Task) -> bool {
+        matches!(task.payload, TaskPayload::SendNotification { .. })
+    }
+
+    async fn handle(&self, task: &Task) -> LibraryResult<()> {
+        if let TaskPayload::SendNotification { party_id, subject, message } = &task.payload {
+            let _ = self.notification_service.notify(party_id, subject, message).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::library::PartyKind;
+    use crate::core::tasks::{Task, TaskPayload};
+    use crate::notifications::factory::{create_notification_query_service, create_notification_task_handler};
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_send_deferred_notification() {
+        let store = test_store(module_path!(), "test_should_send_deferred_notification");
+        let party = PartyEntity::new(PartyKind::Patron, "notification_task@example.com");
+        let _ = create_party_repository(store).await.create(&party).await.expect("should create party");
+        let handler = create_notification_task_handler(store).await;
+        let task = Task::new(TaskPayload::SendNotification {
+            party_id: party.party_id.to_string(),
+            subject: "deferred".to_string(),
+            message: "deferred message".to_string(),
+        });
+        assert!(handler.handles(&task));
+        handler.handle(&task).await.expect("should notify");
+
+        let res = create_notification_query_service(store).await
+            .find_notifications(party.party_id.as_str(), None, 10).await.expect("should find notifications");
+        assert!(res.records.iter().any(|n| n.subject == "deferred"));
+    }
+}
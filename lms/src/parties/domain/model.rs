@@ -1,10 +1,29 @@
-use chrono::{NaiveDateTime, Utc};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::core::domain::Identifiable;
-use crate::core::library::PartyKind;
+use crate::core::library::{AccountStatus, PartyKind};
 use crate::utils::date::serializer;
 
+// PartyCounter names the counters of parties that are maintained from domain events
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PartyCounter {
+    Holds,
+    Overdue,
+    // holds that expired without pickup, lowered again by holds that are picked up
+    NoShows,
+}
+
+impl PartyCounter {
+    pub(crate) fn attribute(&self) -> &'static str {
+        match self {
+            PartyCounter::Holds => "num_holds",
+            PartyCounter::Overdue => "num_overdue",
+            PartyCounter::NoShows => "num_no_shows",
+        }
+    }
+}
+
 // Party abstracts person, patron, employee, branch, organization based on https://martinfowler.com/apsupp/accountability.pdf
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub(crate) struct PartyEntity {
@@ -14,10 +33,35 @@ pub(crate) struct PartyEntity {
     pub first_name: String,
     pub last_name: String,
     pub email: String,
+    // lower case email without surrounding spaces that the email index and uniqueness of emails use
+    #[serde(default)]
+    pub normalized_email: String,
     pub under_13: bool,
     pub group_roles: Vec<String>,
     pub num_holds: i64,
     pub num_overdue: i64,
+    #[serde(default)]
+    pub num_no_shows: i64,
+    // reading history is disabled by default for privacy
+    #[serde(default)]
+    pub reading_history_enabled: bool,
+    // name of organization parties such as vendors
+    #[serde(default)]
+    pub organization_name: String,
+    // weekly opening hours of branch parties, branches are closed on days of the week without hours
+    #[serde(default)]
+    pub opening_hours: Vec<OpeningHoursEntity>,
+    // dates on which branch parties are closed such as holidays
+    #[serde(default)]
+    pub closures: Vec<ClosureEntity>,
+    // inactive parties are kept for references from existing records
+    #[serde(default = "default_active")]
+    pub active: bool,
+    #[serde(default = "default_account_status")]
+    pub account_status: AccountStatus,
+    // reason of suspension or ban shown to librarians
+    #[serde(default)]
+    pub status_reason: String,
     pub home_phone: Option<String>,
     pub cell_phone: Option<String>,
     pub work_phone: Option<String>,
@@ -36,10 +80,30 @@ pub(crate) struct AddressEntity {
     pub zip_code: String,
     pub state: String,
     pub country: String,
+    // coordinates are only known for addresses geocoded by the address validation provider
+    #[serde(default)]
+    pub latitude: Option<f64>,
+    #[serde(default)]
+    pub longitude: Option<f64>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
 
+// OpeningHours defines when a branch opens and closes on a day of the week
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub(crate) struct OpeningHoursEntity {
+    pub weekday: Weekday,
+    pub opens_at: NaiveTime,
+    pub closes_at: NaiveTime,
+}
+
+// Closure defines a date on which a branch is closed
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub(crate) struct ClosureEntity {
+    pub date: NaiveDate,
+    pub reason: String,
+}
+
 impl PartyEntity {
     pub fn new(kind: PartyKind, email: &str) -> Self {
         Self {
@@ -49,10 +113,19 @@ impl PartyEntity {
             first_name: "".to_string(),
             last_name: "".to_string(),
             email: email.to_string(),
+            normalized_email: normalize_email(email, false),
             under_13: false,
             group_roles: vec![],
             num_holds: 0,
             num_overdue: 0,
+            num_no_shows: 0,
+            reading_history_enabled: false,
+            organization_name: "".to_string(),
+            opening_hours: vec![],
+            closures: vec![],
+            active: true,
+            account_status: AccountStatus::Active,
+            status_reason: "".to_string(),
             home_phone: None,
             cell_phone: None,
             work_phone: None,
@@ -63,6 +136,29 @@ impl PartyEntity {
     }
 }
 
+// normalizes email for comparisons, stripping the plus tag also matches sub-addresses with their mailbox
+pub(crate) fn normalize_email(email: &str, strip_plus_tag: bool) -> String {
+    let email = email.trim().to_lowercase();
+    if strip_plus_tag {
+        if let Some((local, domain)) = email.split_once('@') {
+            if let Some((mailbox, _)) = local.split_once('+') {
+                if !mailbox.is_empty() {
+                    return format!("{}@{}", mailbox, domain);
+                }
+            }
+        }
+    }
+    email
+}
+
+fn default_active() -> bool {
+    true
+}
+
+fn default_account_status() -> AccountStatus {
+    AccountStatus::Active
+}
+
 impl AddressEntity {
     pub fn to_json(&self) -> String {
         serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string())
@@ -84,6 +180,8 @@ impl Default for AddressEntity {
             zip_code: "".to_string(),
             state: "".to_string(),
             country: "".to_string(),
+            latitude: None,
+            longitude: None,
             created_at: Utc::now().naive_utc(),
             updated_at: Utc::now().naive_utc(),
         }
@@ -105,12 +203,22 @@ impl Identifiable for PartyEntity {
 mod tests {
     use chrono::Utc;
     use crate::core::library::PartyKind;
-    use crate::parties::domain::model::{AddressEntity, PartyEntity};
+    use crate::parties::domain::model::{normalize_email, AddressEntity, PartyEntity};
+
+    #[tokio::test]
+    async fn test_should_normalize_email() {
+        assert_eq!("jane@example.com", normalize_email(" Jane@Example.COM ", false).as_str());
+        assert_eq!("jane+library@example.com", normalize_email("Jane+Library@example.com", false).as_str());
+        assert_eq!("jane@example.com", normalize_email("Jane+Library@example.com", true).as_str());
+        assert_eq!("+jane@example.com", normalize_email("+jane@example.com", true).as_str());
+        assert_eq!("jane", normalize_email("Jane", true).as_str());
+    }
 
     #[tokio::test]
     async fn test_should_build_party() {
-        let patron = PartyEntity::new(PartyKind::Patron, "email@org.cc");
-        assert_eq!("email@org.cc", patron.email.as_str());
+        let patron = PartyEntity::new(PartyKind::Patron, "Email@org.cc");
+        assert_eq!("Email@org.cc", patron.email.as_str());
+        assert_eq!("email@org.cc", patron.normalized_email.as_str());
         assert_eq!(PartyKind::Patron, patron.kind);
     }
 
@@ -122,11 +230,14 @@ mod tests {
             zip_code: "980101".to_string(),
             state: "WA".to_string(),
             country: "US".to_string(),
+            latitude: Some(47.6),
+            longitude: Some(-122.3),
             created_at: Utc::now().naive_utc(),
             updated_at: Utc::now().naive_utc(),
         };
         let str = address.to_json();
         let des_address = AddressEntity::from_json(str).unwrap();
         assert_eq!(address.street_address, des_address.street_address);
+        assert_eq!(address.latitude, des_address.latitude);
     }
 }
@@ -1,18 +1,24 @@
-use crate::parties::repository::ddb_party_repository::DDBPartyRepository;
+use crate::parties::repository::ddb_party_repository::{DDBPartyRepository, COUNTER_CHANGES_TABLE, EMAILS_TABLE};
 use crate::core::repository::RepositoryStore;
 use crate::parties::repository::PartyRepository;
-use crate::utils::ddb::{build_db_client, create_table};
+use crate::utils::ddb::{build_db_client, create_key_table, create_table};
 
 pub(crate) async fn create_party_repository(store: RepositoryStore) -> Box<dyn PartyRepository> {
     match store {
         RepositoryStore::DynamoDB => {
             let client = build_db_client(store).await;
-            Box::new(DDBPartyRepository::new(client, "parties", "parties_ndx"))
+            Box::new(DDBPartyRepository::new(client, "parties", "parties_ndx", EMAILS_TABLE, COUNTER_CHANGES_TABLE))
         }
-        RepositoryStore::LocalDynamoDB => {
+        _ => {
             let client = build_db_client(store).await;
-            let _ = create_table(&client, "parties", "party_id", "kind", "email").await;
-            Box::new(DDBPartyRepository::new(client, "parties", "parties_ndx"))
+            let table_name = store.table_name("parties");
+            let emails_table_name = store.table_name(EMAILS_TABLE);
+            let counter_changes_table_name = store.table_name(COUNTER_CHANGES_TABLE);
+            let _ = create_table(&client, table_name.as_str(), "party_id", "kind", "normalized_email").await;
+            let _ = create_key_table(&client, emails_table_name.as_str(), "email").await;
+            let _ = create_key_table(&client, counter_changes_table_name.as_str(), "change_key").await;
+            Box::new(DDBPartyRepository::new(client, table_name.as_str(), store.table_name("parties_ndx").as_str(),
+                                             emails_table_name.as_str(), counter_changes_table_name.as_str()))
         }
     }
 }
@@ -1,11 +1,29 @@
 pub(crate) mod ddb_party_repository;
 use async_trait::async_trait;
-use crate::core::library::LibraryResult;
+use chrono::NaiveDateTime;
+use crate::core::library::{LibraryResult, PaginatedResult};
 use crate::core::repository::Repository;
-use crate::parties::domain::model::PartyEntity;
+use crate::parties::domain::model::{PartyCounter, PartyEntity};
 
 #[async_trait]
 pub(crate) trait PartyRepository: Repository<PartyEntity> {
     async fn find_by_email(&self, email: &str) -> LibraryResult<Vec<PartyEntity>>;
+    // creates the party together with the lookup of its email in one transaction, it fails with DuplicateKey when
+    // another party already owns the email
+    async fn create_with_unique_email(&self, entity: &PartyEntity) -> LibraryResult<usize>;
+    // creates parties of a batch with their email lookups in one transaction, none of them is created when one of
+    // the emails is already owned by another party
+    async fn create_all_with_unique_emails(&self, entities: &[PartyEntity]) -> LibraryResult<usize>;
+    // moves the email lookup of the party from its old normalized email to the normalized email of the entity
+    async fn change_email(&self, entity: &PartyEntity, old_email: &str) -> LibraryResult<usize>;
+    // frees the normalized email for other parties, lookups owned by other parties are kept
+    async fn release_email(&self, party_id: &str, email: &str) -> LibraryResult<()>;
+    // returns patrons that were removed before the given time
+    async fn find_deleted(&self, before: NaiveDateTime,
+                          page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<PartyEntity>>;
+    // adds the delta to the counter of the party once per change key, the counter is left as is and false is returned
+    // when the change was applied before, the change it requires was never applied or the counter would drop below zero
+    async fn add_counter(&self, party_id: &str, counter: PartyCounter, delta: i64, change_key: &str,
+                         requires_key: Option<&str>) -> LibraryResult<bool>;
 }
 
@@ -3,29 +3,60 @@ use std::collections::HashMap;
 
 use async_trait::async_trait;
 use aws_sdk_dynamodb::Client;
-use aws_sdk_dynamodb::types::AttributeValue;
-use chrono::Utc;
+use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::operation::transact_write_items::TransactWriteItemsError;
+use aws_sdk_dynamodb::types::{AttributeValue, ConditionCheck, Delete, Put, TransactWriteItem, Update};
+use chrono::{NaiveDateTime, Utc};
 
-use crate::parties::domain::model::{AddressEntity, PartyEntity};
-use crate::core::library::{LibraryError, LibraryResult, PaginatedResult, PartyKind};
+use crate::parties::domain::model::{normalize_email, AddressEntity, PartyCounter, PartyEntity};
+use crate::core::library::{AccountStatus, LibraryError, LibraryResult, PaginatedResult, PartyKind};
 use crate::core::repository::Repository;
 use crate::parties::repository::PartyRepository;
-use crate::utils::ddb::{add_filter_expr, from_ddb, parse_bool_attribute, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, string_date, to_ddb_page};
+use crate::utils::ddb::{add_filter_expr, from_ddb, is_transaction_condition_failed, parse_bool_attribute, parse_date_attribute, parse_item, parse_json_attribute, parse_number_attribute, parse_string_attribute, string_date, to_ddb_page, CounterUpdate};
+
+// lookup table of emails to the parties that own them, it enforces uniqueness that the eventually consistent
+// index on email cannot
+pub(crate) const EMAILS_TABLE: &str = "party_emails";
+
+// changes applied to the counters of parties, events are delivered at least once so each change is recorded
+// along with the counters to apply it only once
+pub(crate) const COUNTER_CHANGES_TABLE: &str = "party_counter_changes";
 
 #[derive(Debug)]
 pub(crate) struct DDBPartyRepository {
     client: Client,
     table_name: String,
     index_name: String,
+    emails_table_name: String,
+    counter_changes_table_name: String,
 }
 
 impl DDBPartyRepository {
-    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str,
+                      emails_table_name: &str, counter_changes_table_name: &str) -> Self {
         Self {
             client,
             table_name: table_name.to_string(),
             index_name: index_name.to_string(),
+            emails_table_name: emails_table_name.to_string(),
+            counter_changes_table_name: counter_changes_table_name.to_string(),
+        }
+    }
+
+    fn email_lookup(&self, entity: &PartyEntity) -> Put {
+        Put::builder()
+            .table_name(self.emails_table_name.as_str())
+            .condition_expression("attribute_not_exists(email)")
+            .item("email", AttributeValue::S(entity.normalized_email.to_string()))
+            .item("party_id", AttributeValue::S(entity.party_id.to_string()))
+            .build()
+    }
+
+    fn email_error(err: SdkError<TransactWriteItemsError>, email: &str, lookup_index: usize) -> LibraryError {
+        if is_transaction_condition_failed(&err, lookup_index) {
+            return LibraryError::duplicate_key(format!("email {} is already registered", email).as_str());
         }
+        LibraryError::from(err)
     }
 }
 
@@ -49,21 +80,33 @@ impl Repository<PartyEntity> for DDBPartyRepository {
 
         let address = serde_json::to_string(entity.address.as_ref().unwrap_or(&AddressEntity::default()))?;
         let roles = serde_json::to_string(&entity.group_roles)?;
+        let opening_hours = serde_json::to_string(&entity.opening_hours)?;
+        let closures = serde_json::to_string(&entity.closures)?;
         self.client
             .update_item()
             .table_name(table_name)
             .key("party_id", AttributeValue::S(entity.party_id.clone()))
-            .update_expression("SET version = :version, email = :email, kind = :kind, first_name = :first, last_name = :last, address = :address, group_roles = :group_roles, num_holds = :num_holds, num_overdue = :num_overdue, updated_at = :updated_at")
+            // num_holds, num_overdue and num_no_shows are left to add_counter
+            .update_expression("SET version = :version, email = :email, normalized_email = :normalized_email, kind = :kind, first_name = :first, last_name = :last, address = :address, group_roles = :group_roles, reading_history_enabled = :reading_history_enabled, organization_name = :organization_name, opening_hours = :opening_hours, closures = :closures, active = :active, account_status = :account_status, status_reason = :status_reason, home_phone = :home_phone, cell_phone = :cell_phone, work_phone = :work_phone, updated_at = :updated_at")
             .expression_attribute_values(":old_version", AttributeValue::N(entity.version.to_string()))
             .expression_attribute_values(":version", AttributeValue::N((entity.version + 1).to_string()))
             .expression_attribute_values(":email", AttributeValue::S(entity.email.to_string()))
+            .expression_attribute_values(":normalized_email", AttributeValue::S(entity.normalized_email.to_string()))
             .expression_attribute_values(":kind", AttributeValue::S(entity.kind.to_string()))
             .expression_attribute_values(":first", AttributeValue::S(entity.first_name.to_string()))
             .expression_attribute_values(":last", AttributeValue::S(entity.last_name.to_string()))
             .expression_attribute_values(":address", AttributeValue::S(address))
             .expression_attribute_values(":group_roles", AttributeValue::S(roles))
-            .expression_attribute_values(":num_holds", AttributeValue::N(entity.num_holds.to_string()))
-            .expression_attribute_values(":num_overdue", AttributeValue::N(entity.num_overdue.to_string()))
+            .expression_attribute_values(":reading_history_enabled", AttributeValue::Bool(entity.reading_history_enabled))
+            .expression_attribute_values(":organization_name", AttributeValue::S(entity.organization_name.to_string()))
+            .expression_attribute_values(":opening_hours", AttributeValue::S(opening_hours))
+            .expression_attribute_values(":closures", AttributeValue::S(closures))
+            .expression_attribute_values(":active", AttributeValue::Bool(entity.active))
+            .expression_attribute_values(":account_status", AttributeValue::S(entity.account_status.to_string()))
+            .expression_attribute_values(":status_reason", AttributeValue::S(entity.status_reason.to_string()))
+            .expression_attribute_values(":home_phone", AttributeValue::S(entity.home_phone.clone().unwrap_or_default()))
+            .expression_attribute_values(":cell_phone", AttributeValue::S(entity.cell_phone.clone().unwrap_or_default()))
+            .expression_attribute_values(":work_phone", AttributeValue::S(entity.work_phone.clone().unwrap_or_default()))
             .expression_attribute_values(":updated_at", string_date(now))
             .condition_expression("attribute_exists(version) AND version = :old_version")
             .send()
@@ -130,9 +173,10 @@ impl Repository<PartyEntity> for DDBPartyRepository {
         let mut key_cond = String::new();
         key_cond.push_str("kind = :kind");
 
+        // emails are matched by their normalized form that the index is keyed on
         if let Some(email) = predicate.get("email") {
-            key_cond.push_str(" AND email = :email");
-            request = request.expression_attribute_values(":email", AttributeValue::S(email.to_string()));
+            key_cond.push_str(" AND normalized_email = :email");
+            request = request.expression_attribute_values(":email", AttributeValue::S(normalize_email(email, false)));
         }
         request = request.key_condition_expression(key_cond);
         let mut filter_expr = String::new();
@@ -165,13 +209,155 @@ impl PartyRepository for DDBPartyRepository {
         let res = self.query(&predicate, None, 50).await?;
         Ok(res.records)
     }
+
+    async fn create_with_unique_email(&self, entity: &PartyEntity) -> LibraryResult<usize> {
+        let val = serde_json::to_value(entity)?;
+        let party = Put::builder()
+            .table_name(self.table_name.as_str())
+            .condition_expression("attribute_not_exists(party_id)")
+            .set_item(Some(parse_item(val)?))
+            .build();
+        self.client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().put(party).build())
+            .transact_items(TransactWriteItem::builder().put(self.email_lookup(entity)).build())
+            .send()
+            .await.map(|_| 1).map_err(|err| Self::email_error(err, entity.email.as_str(), 1))
+    }
+
+    async fn create_all_with_unique_emails(&self, entities: &[PartyEntity]) -> LibraryResult<usize> {
+        if entities.is_empty() {
+            return Ok(0);
+        }
+        let mut req = self.client.transact_write_items();
+        for entity in entities {
+            let party = Put::builder()
+                .table_name(self.table_name.as_str())
+                .condition_expression("attribute_not_exists(party_id)")
+                .set_item(Some(parse_item(serde_json::to_value(entity)?)?))
+                .build();
+            req = req
+                .transact_items(TransactWriteItem::builder().put(party).build())
+                .transact_items(TransactWriteItem::builder().put(self.email_lookup(entity)).build());
+        }
+        match req.send().await {
+            Ok(_) => Ok(entities.len()),
+            Err(err) => {
+                // the lookup of each party follows the party in the transaction
+                match entities.iter().enumerate().find(|(i, _)| is_transaction_condition_failed(&err, i * 2 + 1)) {
+                    Some((i, entity)) => Err(Self::email_error(err, entity.email.as_str(), i * 2 + 1)),
+                    None => Err(LibraryError::from(err)),
+                }
+            }
+        }
+    }
+
+    async fn change_email(&self, entity: &PartyEntity, old_email: &str) -> LibraryResult<usize> {
+        if old_email == entity.normalized_email {
+            return Ok(0);
+        }
+        // the old lookup may be missing for parties created before emails were unique
+        let old = Delete::builder()
+            .table_name(self.emails_table_name.as_str())
+            .key("email", AttributeValue::S(old_email.to_string()))
+            .condition_expression("attribute_not_exists(email) OR party_id = :party_id")
+            .expression_attribute_values(":party_id", AttributeValue::S(entity.party_id.to_string()))
+            .build();
+        let party = Update::builder()
+            .table_name(self.table_name.as_str())
+            .key("party_id", AttributeValue::S(entity.party_id.to_string()))
+            .update_expression("SET email = :email, normalized_email = :normalized_email")
+            .condition_expression("attribute_exists(party_id)")
+            .expression_attribute_values(":email", AttributeValue::S(entity.email.to_string()))
+            .expression_attribute_values(":normalized_email", AttributeValue::S(entity.normalized_email.to_string()))
+            .build();
+        self.client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().put(self.email_lookup(entity)).build())
+            .transact_items(TransactWriteItem::builder().delete(old).build())
+            .transact_items(TransactWriteItem::builder().update(party).build())
+            .send()
+            .await.map(|_| 1).map_err(|err| Self::email_error(err, entity.email.as_str(), 0))
+    }
+
+    async fn release_email(&self, party_id: &str, email: &str) -> LibraryResult<()> {
+        let res = self.client.delete_item()
+            .table_name(self.emails_table_name.as_str())
+            .key("email", AttributeValue::S(email.to_string()))
+            .condition_expression("party_id = :party_id")
+            .expression_attribute_values(":party_id", AttributeValue::S(party_id.to_string()))
+            .send()
+            .await;
+        match res {
+            Ok(_) => Ok(()),
+            Err(SdkError::ServiceError(ctx)) if ctx.err().is_conditional_check_failed_exception() => Ok(()),
+            Err(err) => Err(LibraryError::from(err)),
+        }
+    }
+
+    // removed patrons are not updated anymore so updated_at is the time of their removal
+    async fn find_deleted(&self, before: NaiveDateTime,
+                          page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<PartyEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        self.client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .limit(cmp::min(page_size, 500) as i32)
+            .consistent_read(false)
+            .set_exclusive_start_key(to_ddb_page(page, &HashMap::new()))
+            .key_condition_expression("kind = :kind")
+            .filter_expression("account_status = :deleted AND updated_at < :before")
+            .expression_attribute_values(":kind", AttributeValue::S(PartyKind::Patron.to_string()))
+            .expression_attribute_values(":deleted", AttributeValue::S(AccountStatus::Deleted.to_string()))
+            .expression_attribute_values(":before", string_date(before))
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(PartyEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+
+    async fn add_counter(&self, party_id: &str, counter: PartyCounter, delta: i64, change_key: &str,
+                         requires_key: Option<&str>) -> LibraryResult<bool> {
+        let change = Put::builder()
+            .table_name(self.counter_changes_table_name.as_str())
+            .condition_expression("attribute_not_exists(change_key)")
+            .item("change_key", AttributeValue::S(change_key.to_string()))
+            .item("party_id", AttributeValue::S(party_id.to_string()))
+            .item("created_at", string_date(Utc::now().naive_utc()))
+            .build();
+        // counters are only changed by adding to them, updates of the party leave them as is
+        let party = CounterUpdate::new(self.table_name.as_str(), "party_id", party_id, counter.attribute()).to_update(delta);
+        let mut req = self.client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().put(change).build())
+            .transact_items(TransactWriteItem::builder().update(party).build());
+        if let Some(requires_key) = requires_key {
+            let required = ConditionCheck::builder()
+                .table_name(self.counter_changes_table_name.as_str())
+                .key("change_key", AttributeValue::S(requires_key.to_string()))
+                .condition_expression("attribute_exists(change_key)")
+                .build();
+            req = req.transact_items(TransactWriteItem::builder().condition_check(required).build());
+        }
+        match req.send().await {
+            Ok(_) => Ok(true),
+            Err(err) if is_transaction_condition_failed(&err, 0) || is_transaction_condition_failed(&err, 2) => Ok(false),
+            // the update fails for missing parties as well as counters that would drop below zero
+            Err(err) if is_transaction_condition_failed(&err, 1) => self.get(party_id).await.map(|_| false),
+            Err(err) => Err(LibraryError::from(err)),
+        }
+    }
 }
 
 
 impl From<&HashMap<String, AttributeValue>> for PartyEntity {
     fn from(map: &HashMap<String, AttributeValue>) -> Self {
         let roles: Vec<String> = serde_json::from_str(
-            parse_string_attribute("group_roles", map).unwrap_or_else(|| String::from("[]")).as_str()).unwrap_or_default();
+            parse_json_attribute("group_roles", map).unwrap_or_else(|| String::from("[]")).as_str()).unwrap_or_default();
         PartyEntity {
             party_id: parse_string_attribute("party_id", map).unwrap_or_else(|| String::from("")),
             version: parse_number_attribute("version", map),
@@ -179,14 +365,28 @@ impl From<&HashMap<String, AttributeValue>> for PartyEntity {
             first_name: parse_string_attribute("first_name", map).unwrap_or_else(|| String::from("")),
             last_name: parse_string_attribute("last_name", map).unwrap_or_else(|| String::from("")),
             email: parse_string_attribute("email", map).unwrap_or_else(|| String::from("")),
+            // parties saved before emails were normalized are normalized when they are read
+            normalized_email: parse_string_attribute("normalized_email", map)
+                .unwrap_or_else(|| normalize_email(parse_string_attribute("email", map).unwrap_or_default().as_str(), false)),
             under_13: parse_bool_attribute("under_13", map),
             group_roles: roles,
             num_holds: parse_number_attribute("num_holds", map),
             num_overdue: parse_number_attribute("num_overdue", map),
+            num_no_shows: parse_number_attribute("num_no_shows", map),
+            reading_history_enabled: parse_bool_attribute("reading_history_enabled", map),
+            organization_name: parse_string_attribute("organization_name", map).unwrap_or_else(|| String::from("")),
+            opening_hours: serde_json::from_str(
+                parse_json_attribute("opening_hours", map).unwrap_or_else(|| String::from("[]")).as_str()).unwrap_or_default(),
+            closures: serde_json::from_str(
+                parse_json_attribute("closures", map).unwrap_or_else(|| String::from("[]")).as_str()).unwrap_or_default(),
+            // parties created before deactivation was supported are active
+            active: !map.contains_key("active") || parse_bool_attribute("active", map),
+            account_status: AccountStatus::from(parse_string_attribute("account_status", map).unwrap_or_else(|| AccountStatus::Active.to_string())),
+            status_reason: parse_string_attribute("status_reason", map).unwrap_or_else(|| String::from("")),
             home_phone: Some(parse_string_attribute("home_phone", map).unwrap_or_else(|| String::from(""))),
             cell_phone: Some(parse_string_attribute("cell_phone", map).unwrap_or_else(|| String::from(""))),
             work_phone: Some(parse_string_attribute("work_phone", map).unwrap_or_else(|| String::from(""))),
-            address: AddressEntity::from_json(parse_string_attribute("address", map).unwrap_or_else(|| String::from("{}"))),
+            address: AddressEntity::from_json(parse_json_attribute("address", map).unwrap_or_else(|| String::from("{}"))),
             created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
             updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
         }
@@ -196,61 +396,83 @@ impl From<&HashMap<String, AttributeValue>> for PartyEntity {
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
-    use async_once::AsyncOnce;
 
     use aws_sdk_dynamodb::Client;
-    use chrono::Utc;
-    use lazy_static::lazy_static;
-    use crate::core::library::PartyKind;
+    use chrono::{Duration, Utc};
+    use crate::core::library::{AccountStatus, LibraryError, PartyKind};
     use crate::core::repository::{Repository, RepositoryStore};
 
-    use crate::parties::domain::model::{AddressEntity, PartyEntity};
-    use crate::parties::repository::ddb_party_repository::DDBPartyRepository;
-    use crate::utils::ddb::{build_db_client, create_table, delete_table};
-
-    lazy_static! {
-        static ref CLIENT: AsyncOnce<Client> = AsyncOnce::new(async {
-                let client = build_db_client(RepositoryStore::LocalDynamoDB).await;
-                let _ = delete_table(&client, "parties").await;
-                let _ = create_table(&client, "parties", "party_id", "kind", "email").await;
-                client
-            });
+    use crate::parties::domain::model::{AddressEntity, PartyCounter, PartyEntity};
+    use crate::parties::repository::PartyRepository;
+    use crate::parties::repository::ddb_party_repository::{DDBPartyRepository, COUNTER_CHANGES_TABLE, EMAILS_TABLE};
+    use crate::utils::ddb::{build_db_client, create_key_table, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("parties").as_str(), "party_id", "kind", "normalized_email").await;
+        let _ = create_key_table(&client, store.table_name(EMAILS_TABLE).as_str(), "email").await;
+        let _ = create_key_table(&client, store.table_name(COUNTER_CHANGES_TABLE).as_str(), "change_key").await;
+        client
     }
 
     #[tokio::test]
     async fn test_should_create_get_patrons() {
-        let parties_repo = DDBPartyRepository::new(
-            CLIENT.get().await.clone(), "parties", "parties_ndx");
-        let patron = PartyEntity::new(PartyKind::Patron, "email");
+        let store = test_store(module_path!(), "test_should_create_get_patrons");
+        let parties_repo = DDBPartyRepository::new(build_client(store).await, store.table_name("parties").as_str(), store.table_name("parties_ndx").as_str(),
+            store.table_name(EMAILS_TABLE).as_str(), store.table_name(COUNTER_CHANGES_TABLE).as_str());
+        let mut patron = PartyEntity::new(PartyKind::Patron, "email");
+        patron.group_roles = vec!["Librarian".to_string()];
+        patron.address = Some(AddressEntity {
+            street_address: "100 main st.".to_string(),
+            city: "Seattle".to_string(),
+            zip_code: "98101".to_string(),
+            state: "WA".to_string(),
+            country: "US".to_string(),
+            latitude: Some(47.6),
+            longitude: Some(-122.3),
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        });
         let size = parties_repo.create(&patron).await.expect("should create patron");
         assert_eq!(1, size);
 
         let loaded = parties_repo.get(patron.party_id.as_str()).await.expect("should return patron");
         assert_eq!(patron.party_id, loaded.party_id);
+        // nested values of created parties are read back
+        assert_eq!(patron.group_roles, loaded.group_roles);
+        assert_eq!(Some(47.6), loaded.address.as_ref().and_then(|a| a.latitude));
+        assert_eq!(Some("Seattle"), loaded.address.as_ref().map(|a| a.city.as_str()));
     }
 
     #[tokio::test]
     async fn test_should_create_update_patrons() {
-        let parties_repo = DDBPartyRepository::new(
-            CLIENT.get().await.clone(), "parties", "parties_ndx");
+        let store = test_store(module_path!(), "test_should_create_update_patrons");
+        let parties_repo = DDBPartyRepository::new(build_client(store).await, store.table_name("parties").as_str(), store.table_name("parties_ndx").as_str(),
+            store.table_name(EMAILS_TABLE).as_str(), store.table_name(COUNTER_CHANGES_TABLE).as_str());
         let mut patron = PartyEntity::new(PartyKind::Patron, "email");
         let size = parties_repo.create(&patron).await.expect("should create patron");
         assert_eq!(1, size);
 
         patron.first_name = "first2".to_string();
         patron.last_name = "last2".to_string();
+        patron.account_status = AccountStatus::Suspended;
+        patron.status_reason = "lost books".to_string();
         let size = parties_repo.update(&patron).await.expect("should update patron");
         assert_eq!(1, size);
 
         let loaded = parties_repo.get(patron.party_id.as_str()).await.expect("should return patron");
         assert_eq!(patron.first_name, loaded.first_name);
         assert_eq!(patron.last_name, loaded.last_name);
+        assert_eq!(AccountStatus::Suspended, loaded.account_status);
+        assert_eq!("lost books", loaded.status_reason.as_str());
     }
 
     #[tokio::test]
     async fn test_should_create_query_patrons() {
-        let parties_repo = DDBPartyRepository::new(
-            CLIENT.get().await.clone(), "parties", "parties_ndx");
+        let store = test_store(module_path!(), "test_should_create_query_patrons");
+        let parties_repo = DDBPartyRepository::new(build_client(store).await, store.table_name("parties").as_str(), store.table_name("parties_ndx").as_str(),
+            store.table_name(EMAILS_TABLE).as_str(), store.table_name(COUNTER_CHANGES_TABLE).as_str());
         add_test_patrons(&parties_repo, PartyKind::Branch).await;
         let mut next_page = None;
         let mut total = 0;
@@ -279,8 +501,9 @@ mod tests {
 
     #[tokio::test]
     async fn test_should_create_delete_patrons() {
-        let parties_repo = DDBPartyRepository::new(
-            CLIENT.get().await.clone(), "parties", "parties_ndx");
+        let store = test_store(module_path!(), "test_should_create_delete_patrons");
+        let parties_repo = DDBPartyRepository::new(build_client(store).await, store.table_name("parties").as_str(), store.table_name("parties_ndx").as_str(),
+            store.table_name(EMAILS_TABLE).as_str(), store.table_name(COUNTER_CHANGES_TABLE).as_str());
         let patron = PartyEntity::new(PartyKind::Patron, "email");
         let size = parties_repo.create(&patron).await.expect("should create patron");
         assert_eq!(1, size);
@@ -292,6 +515,36 @@ mod tests {
         assert!(loaded.is_err());
     }
 
+    #[tokio::test]
+    async fn test_should_create_with_unique_email() {
+        let store = test_store(module_path!(), "test_should_create_with_unique_email");
+        let parties_repo = DDBPartyRepository::new(build_client(store).await, store.table_name("parties").as_str(), store.table_name("parties_ndx").as_str(),
+            store.table_name(EMAILS_TABLE).as_str(), store.table_name(COUNTER_CHANGES_TABLE).as_str());
+        let mut patron = PartyEntity::new(PartyKind::Patron, "unique@example.com");
+        let size = parties_repo.create_with_unique_email(&patron).await.expect("should create patron");
+        assert_eq!(1, size);
+
+        let other = PartyEntity::new(PartyKind::Patron, " UNIQUE@example.com");
+        let res = parties_repo.create_with_unique_email(&other).await;
+        assert!(matches!(res, Err(LibraryError::DuplicateKey { .. })));
+        // neither party nor lookup is saved when the transaction is canceled
+        assert!(parties_repo.get(other.party_id.as_str()).await.is_err());
+
+        patron.email = "Changed@example.com".to_string();
+        patron.normalized_email = "changed@example.com".to_string();
+        let size = parties_repo.change_email(&patron, "unique@example.com").await.expect("should change email");
+        assert_eq!(1, size);
+        let loaded = parties_repo.get(patron.party_id.as_str()).await.expect("should return patron");
+        assert_eq!("Changed@example.com", loaded.email.as_str());
+        assert_eq!("changed@example.com", loaded.normalized_email.as_str());
+        let _ = parties_repo.create_with_unique_email(&other).await.expect("should create with released email");
+
+        // lookups owned by other parties are not released
+        parties_repo.release_email(other.party_id.as_str(), "changed@example.com").await.expect("should release email");
+        let res = parties_repo.create_with_unique_email(&PartyEntity::new(PartyKind::Patron, "changed@example.com")).await;
+        assert!(matches!(res, Err(LibraryError::DuplicateKey { .. })));
+    }
+
     async fn add_test_patrons(parties_repo: &DDBPartyRepository, kind: PartyKind) {
         for i in 0..50 {
             let mut patron = PartyEntity::new(kind, format!("email_{}", i / 10).as_str());
@@ -304,6 +557,8 @@ mod tests {
                     zip_code: "980101".to_string(),
                     state: "WA".to_string(),
                     country: "US".to_string(),
+                    latitude: None,
+                    longitude: None,
                     created_at: Utc::now().naive_utc(),
                     updated_at: Utc::now().naive_utc(),
                 })
@@ -313,4 +568,57 @@ mod tests {
             assert_eq!(1, size);
         }
     }
+
+    #[tokio::test]
+    async fn test_should_add_counters_once_per_change() {
+        let store = test_store(module_path!(), "test_should_add_counters_once_per_change");
+        let repo = DDBPartyRepository::new(build_client(store).await, store.table_name("parties").as_str(), store.table_name("parties_ndx").as_str(),
+            store.table_name(EMAILS_TABLE).as_str(), store.table_name(COUNTER_CHANGES_TABLE).as_str());
+        let party = PartyEntity::new(PartyKind::Patron, "counters@example.com");
+        let _ = repo.create(&party).await.expect("should create party");
+        let placed = format!("hold#{}", party.party_id);
+        let ended = format!("hold_ended#{}", party.party_id);
+
+        assert!(repo.add_counter(party.party_id.as_str(), PartyCounter::Holds, 1, placed.as_str(), None).await.expect("should add"));
+        assert!(!repo.add_counter(party.party_id.as_str(), PartyCounter::Holds, 1, placed.as_str(), None).await.expect("should skip"));
+        assert!(!repo.add_counter(party.party_id.as_str(), PartyCounter::Overdue, -1, "overdue_returned#none", Some("overdue#none"))
+            .await.expect("should skip"));
+        // counters do not drop below zero
+        assert!(!repo.add_counter(party.party_id.as_str(), PartyCounter::Overdue, -1, "overdue_returned#floor", None)
+            .await.expect("should skip"));
+        let loaded = repo.get(party.party_id.as_str()).await.expect("should get party");
+        assert_eq!((1, 0), (loaded.num_holds, loaded.num_overdue));
+        // updates of a party read before the change keep the counters
+        assert_eq!(1, repo.update(&party).await.expect("should update party"));
+        assert_eq!(1, repo.get(party.party_id.as_str()).await.expect("should get party").num_holds);
+
+        assert!(repo.add_counter(party.party_id.as_str(), PartyCounter::Holds, -1, ended.as_str(), Some(placed.as_str()))
+            .await.expect("should add"));
+        assert_eq!(0, repo.get(party.party_id.as_str()).await.expect("should get party").num_holds);
+        let err = repo.add_counter("missing_party", PartyCounter::Holds, 1, "hold#missing_party", None).await.expect_err("should fail");
+        assert!(matches!(err, LibraryError::NotFound { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_should_find_deleted_patrons() {
+        let store = test_store(module_path!(), "test_should_find_deleted_patrons");
+        let repo = DDBPartyRepository::new(build_client(store).await, store.table_name("parties").as_str(), store.table_name("parties_ndx").as_str(),
+            store.table_name(EMAILS_TABLE).as_str(), store.table_name(COUNTER_CHANGES_TABLE).as_str());
+        let now = Utc::now().naive_utc();
+        let mut expired = PartyEntity::new(PartyKind::Patron, "deleted_expired@example.com");
+        expired.account_status = AccountStatus::Deleted;
+        expired.updated_at = now - Duration::days(40);
+        let mut recent = PartyEntity::new(PartyKind::Patron, "deleted_recent@example.com");
+        recent.account_status = AccountStatus::Deleted;
+        let active = PartyEntity::new(PartyKind::Patron, "deleted_active@example.com");
+        for party in [&expired, &recent, &active] {
+            let _ = repo.create(party).await.expect("should create party");
+        }
+
+        let res = repo.find_deleted(now - Duration::days(30), None, 500).await.expect("should find deleted");
+        let ids: Vec<String> = res.records.iter().map(|p| p.party_id.to_string()).collect();
+        assert!(ids.contains(&expired.party_id));
+        assert!(!ids.contains(&recent.party_id));
+        assert!(!ids.contains(&active.party_id));
+    }
 }
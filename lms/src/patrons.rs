@@ -5,6 +5,8 @@ pub mod command;
 pub mod domain;
 pub mod dto;
 pub mod factory;
+pub mod import;
+pub mod calendar;
 pub mod controller;
 
 pub(crate) trait Patron: Identifiable {
@@ -1,13 +1,5 @@
-include!("../../lib.rs");
-use axum::{
-    routing::{get, post},
-    Router,
-};
-use lambda_http::{run, Error};
-use crate::utils::ddb::setup_tracing;
-use crate::core::controller::AppState;
-use crate::core::repository::RepositoryStore;
-use crate::patrons::controller::{add_patron, remove_patron, find_patron_by_id};
+use lambda_http::Error;
+use lms::{check_tables, check_topics, routes, run_lambda, setup_tracing, AppState, RepositoryStore};
 
 const DEV_MODE: bool = true;
 
@@ -22,14 +14,10 @@ async fn main() -> Result<(), Error> {
         std::env::set_var("AWS_LAMBDA_RUNTIME_API", "http://[::]:9000/.rt");
         AppState::new("dev", RepositoryStore::LocalDynamoDB)
     } else {
+        check_topics(RepositoryStore::DynamoDB).await.map_err(|err| err.to_string())?;
+        check_tables(RepositoryStore::DynamoDB).await.map_err(|err| err.to_string())?;
         AppState::new("prod", RepositoryStore::DynamoDB)
     };
 
-    let app = Router::new()
-        .route("/patrons", post(add_patron))
-        .route("/patrons/:id",
-               get(find_patron_by_id).delete(remove_patron))
-        .with_state(state);
-
-    run(app).await
+    run_lambda(routes::patrons(state)).await
 }
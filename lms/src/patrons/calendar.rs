@@ -0,0 +1,167 @@
+use chrono::NaiveDateTime;
+use crate::catalog::domain::CatalogQueryService;
+use crate::checkout::domain::CheckoutQueryService;
+use crate::core::ids::{BookId, PatronId};
+use crate::core::library::{HoldStatus, LibraryError, LibraryResult};
+use crate::hold::domain::HoldQueryService;
+use crate::utils::signing::{sign_hex, verify_hex};
+
+const ICAL_DATE_FMT: &str = "%Y%m%dT%H%M%SZ";
+// content lines longer than 75 octets are folded
+const MAX_LINE_OCTETS: usize = 75;
+
+// DueDateEvent is a deadline of the patron, either the due date of a checkout or the pickup deadline of a hold
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DueDateEvent {
+    pub uid: String,
+    pub summary: String,
+    pub description: String,
+    pub location: String,
+    pub at: NaiveDateTime,
+}
+
+// calendar apps cannot send bearer tokens so feeds are authenticated by a token in the feed URL, the token is an
+// HMAC-SHA256 signature of the patron id that stays valid until the calendar secret is rotated
+pub(crate) fn build_calendar_token(secret: &str, patron_id: &str) -> String {
+    sign_hex(secret, format!("calendar.{}", patron_id).as_str())
+}
+
+pub(crate) fn verify_calendar_token(secret: &str, patron_id: &str, token: &str) -> LibraryResult<()> {
+    if !verify_hex(secret, format!("calendar.{}", patron_id).as_str(), token) {
+        return Err(LibraryError::not_granted("invalid calendar token", Some("403".to_string())));
+    }
+    Ok(())
+}
+
+// due dates of active checkouts and pickup deadlines of holds ready for pickup, titles of removed books fall back
+// to their ids
+pub(crate) async fn due_date_events(checkout_service: &dyn CheckoutQueryService, hold_service: &dyn HoldQueryService,
+                                    catalog_service: &dyn CatalogQueryService, patron_id: &str) -> LibraryResult<Vec<DueDateEvent>> {
+    let mut events = vec![];
+    for checkout in checkout_service.find_active_by_patron(&PatronId::new(patron_id)).await? {
+        let title = find_title(catalog_service, checkout.book_id.as_str()).await?;
+        events.push(DueDateEvent {
+            uid: format!("checkout-{}@lms", checkout.checkout_id),
+            summary: format!("Due: {}", title),
+            description: format!("Return or renew {} by the due date.", title),
+            location: checkout.branch_id.to_string(),
+            at: checkout.due_at,
+        });
+    }
+    for hold in hold_service.find_by_patron(&PatronId::new(patron_id), HoldStatus::ReadyForPickup).await? {
+        let Some(pickup_by) = hold.pickup_by else {
+            continue;
+        };
+        let title = find_title(catalog_service, hold.book_id.as_str()).await?;
+        events.push(DueDateEvent {
+            uid: format!("hold-{}@lms", hold.hold_id),
+            summary: format!("Pick up: {}", title),
+            description: format!("{} is ready for pickup, the hold expires after this deadline.", title),
+            location: hold.pickup_branch_id.to_string(),
+            at: pickup_by,
+        });
+    }
+    events.sort_by(|a, b| a.at.cmp(&b.at));
+    Ok(events)
+}
+
+async fn find_title(catalog_service: &dyn CatalogQueryService, book_id: &str) -> LibraryResult<String> {
+    match catalog_service.find_book_by_id(&BookId::new(book_id)).await {
+        Ok(book) => Ok(book.title),
+        Err(LibraryError::NotFound { .. }) => Ok(book_id.to_string()),
+        Err(err) => Err(err),
+    }
+}
+
+// iCalendar of RFC 5545 with an event at each deadline and a reminder a day before it, subscribing apps are asked
+// to refresh the calendar twice a day
+pub(crate) fn to_ical(events: &[DueDateEvent], now: NaiveDateTime) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Library Management System//Due Dates//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        "X-WR-CALNAME:Library due dates".to_string(),
+        "REFRESH-INTERVAL;VALUE=DURATION:PT12H".to_string(),
+        "X-PUBLISHED-TTL:PT12H".to_string(),
+    ];
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", event.uid));
+        lines.push(format!("DTSTAMP:{}", now.format(ICAL_DATE_FMT)));
+        lines.push(format!("DTSTART:{}", event.at.format(ICAL_DATE_FMT)));
+        lines.push(format!("DTEND:{}", event.at.format(ICAL_DATE_FMT)));
+        lines.push(format!("SUMMARY:{}", escape_text(event.summary.as_str())));
+        lines.push(format!("DESCRIPTION:{}", escape_text(event.description.as_str())));
+        if !event.location.is_empty() {
+            lines.push(format!("LOCATION:{}", escape_text(event.location.as_str())));
+        }
+        lines.push("BEGIN:VALARM".to_string());
+        lines.push("ACTION:DISPLAY".to_string());
+        lines.push(format!("DESCRIPTION:{}", escape_text(event.summary.as_str())));
+        lines.push("TRIGGER:-P1D".to_string());
+        lines.push("END:VALARM".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+    lines.iter().map(|line| fold(line.as_str())).collect()
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\").replace(';', "\\;").replace(',', "\\,")
+        .replace("\r\n", "\\n").replace(['\n', '\r'], "\\n")
+}
+
+// folds the content line without splitting characters and terminates it with CRLF
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            // the leading space of the continuation line counts towards its length
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use crate::core::library::LibraryError;
+    use crate::patrons::calendar::{build_calendar_token, to_ical, verify_calendar_token, DueDateEvent};
+
+    #[tokio::test]
+    async fn test_should_sign_and_verify_calendar_tokens() {
+        let token = build_calendar_token("secret", "patron1");
+        assert!(verify_calendar_token("secret", "patron1", token.as_str()).is_ok());
+        assert!(matches!(verify_calendar_token("secret", "patron2", token.as_str()), Err(LibraryError::NotGranted { .. })));
+        assert!(matches!(verify_calendar_token("other", "patron1", token.as_str()), Err(LibraryError::NotGranted { .. })));
+        assert!(matches!(verify_calendar_token("secret", "patron1", "zz"), Err(LibraryError::NotGranted { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_should_write_ical() {
+        let at = NaiveDate::from_ymd_opt(2024, 3, 1).and_then(|d| d.and_hms_opt(17, 0, 0)).expect("should be a time");
+        let event = DueDateEvent {
+            uid: "checkout-1@lms".to_string(),
+            summary: "Due: Dune, Messiah; Children".to_string(),
+            description: "line\nbreak ".repeat(10),
+            location: "main".to_string(),
+            at,
+        };
+        let ical = to_ical(&[event], at);
+        assert!(ical.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ical.ends_with("END:VCALENDAR\r\n"));
+        assert!(ical.contains("\r\nDTSTART:20240301T170000Z\r\n"));
+        assert!(ical.contains("\r\nSUMMARY:Due: Dune\\, Messiah\\; Children\r\n"));
+        assert!(ical.contains("\r\nTRIGGER:-P1D\r\n"));
+        assert!(ical.split("\r\n").all(|line| line.len() <= 75));
+        assert!(ical.contains("\r\n "));
+    }
+}
@@ -1,4 +1,13 @@
 pub mod add_patron_cmd;
+pub mod import_patrons_cmd;
 pub mod update_patron_cmd;
 pub mod remove_patron_cmd;
-pub mod get_patron_cmd;
\ No newline at end of file
+pub mod restore_patron_cmd;
+pub mod get_patron_cmd;
+pub mod set_reading_history_cmd;
+pub mod get_reading_history_cmd;
+pub mod get_recommendations_cmd;
+pub mod set_account_status_cmd;
+pub mod register_patron_cmd;
+pub mod verify_patron_cmd;pub mod issue_calendar_token_cmd;
+pub mod get_due_dates_calendar_cmd;
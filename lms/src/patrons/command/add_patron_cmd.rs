@@ -2,6 +2,7 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use crate::patrons::dto::PatronDto;
 use crate::core::command::{Command, CommandError};
+use crate::core::library::LibraryResult;
 use crate::patrons::domain::PatronService;
 
 pub(crate) struct AddPatronCommand {
@@ -16,7 +17,7 @@ impl AddPatronCommand {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct AddPatronCommandRequest {
     pub email: String,
 }
@@ -27,13 +28,13 @@ impl AddPatronCommandRequest {
             email: email.to_string(),
         }
     }
-    pub fn build_patron(&self) -> PatronDto {
-        PatronDto::new(self.email.as_str())
+    pub fn build_patron(&self) -> LibraryResult<PatronDto> {
+        PatronDto::builder().email(self.email.as_str()).build()
     }
 }
 
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct AddPatronCommandResponse {
     pub patron: PatronDto,
 }
@@ -49,31 +50,29 @@ impl AddPatronCommandResponse {
 #[async_trait]
 impl Command<AddPatronCommandRequest, AddPatronCommandResponse> for AddPatronCommand {
     async fn execute(&self, req: AddPatronCommandRequest) -> Result<AddPatronCommandResponse, CommandError> {
-        let patron = req.build_patron();
+        let patron = req.build_patron().map_err(CommandError::from)?;
         self.patron_service.add_patron(&patron).await.map_err(CommandError::from).map(|_|AddPatronCommandResponse::new(patron))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use async_once::AsyncOnce;
-    use lazy_static::lazy_static;
+    use crate::core::repository::RepositoryStore;
     use crate::patrons::command::add_patron_cmd::{AddPatronCommand, AddPatronCommandRequest};
     use crate::patrons::factory;
     use crate::core::command::Command;
     use crate::core::domain::Configuration;
-    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
 
-    lazy_static! {
-        static ref SUT_CMD : AsyncOnce<AddPatronCommand> = AsyncOnce::new(async {
-                let svc = factory::create_patron_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                AddPatronCommand::new(svc)
-            });
+    async fn sut_cmd(store: RepositoryStore) -> AddPatronCommand {
+        let svc = factory::create_patron_service(&Configuration::new("test"), store).await;
+        AddPatronCommand::new(svc)
     }
 
     #[tokio::test]
     async fn test_should_run_add_patron() {
-        let cmd = SUT_CMD.get().await.clone();
+        let store = test_store(module_path!(), "test_should_run_add_patron");
+        let cmd = sut_cmd(store).await;
 
         let _ = cmd.execute(AddPatronCommandRequest::new("test-email")).await.expect("should add patron");
     }
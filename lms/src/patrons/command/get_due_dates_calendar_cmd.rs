@@ -0,0 +1,120 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use crate::catalog::domain::CatalogQueryService;
+use crate::checkout::domain::CheckoutQueryService;
+use crate::core::command::{Command, CommandError};
+use crate::core::ids::PatronId;
+use crate::hold::domain::HoldQueryService;
+use crate::patrons::calendar::{due_date_events, to_ical, verify_calendar_token};
+use crate::patrons::domain::PatronQueryService;
+
+pub(crate) struct GetDueDatesCalendarCommand {
+    patron_service: Box<dyn PatronQueryService>,
+    checkout_service: Box<dyn CheckoutQueryService>,
+    hold_service: Box<dyn HoldQueryService>,
+    catalog_service: Box<dyn CatalogQueryService>,
+    calendar_secret: String,
+}
+
+impl GetDueDatesCalendarCommand {
+    pub(crate) fn new(patron_service: Box<dyn PatronQueryService>,
+                      checkout_service: Box<dyn CheckoutQueryService>,
+                      hold_service: Box<dyn HoldQueryService>,
+                      catalog_service: Box<dyn CatalogQueryService>,
+                      calendar_secret: &str) -> Self {
+        Self {
+            patron_service,
+            checkout_service,
+            hold_service,
+            catalog_service,
+            calendar_secret: calendar_secret.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetDueDatesCalendarCommandRequest {
+    #[serde(default)]
+    pub patron_id: String,
+    // feed token issued to the patron
+    #[serde(default)]
+    pub token: String,
+}
+
+impl GetDueDatesCalendarCommandRequest {
+    pub fn new(patron_id: &str, token: &str) -> Self {
+        Self {
+            patron_id: patron_id.to_string(),
+            token: token.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetDueDatesCalendarCommandResponse {
+    pub ics: String,
+}
+
+impl GetDueDatesCalendarCommandResponse {
+    pub fn new(ics: String) -> Self {
+        Self {
+            ics,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<GetDueDatesCalendarCommandRequest, GetDueDatesCalendarCommandResponse> for GetDueDatesCalendarCommand {
+    async fn execute(&self, req: GetDueDatesCalendarCommandRequest) -> Result<GetDueDatesCalendarCommandResponse, CommandError> {
+        verify_calendar_token(self.calendar_secret.as_str(), req.patron_id.as_str(), req.token.as_str())
+            .map_err(CommandError::from)?;
+        // feeds of removed patrons stop working even though their tokens are still signed
+        let _ = self.patron_service.find_patron_by_id(&PatronId::new(req.patron_id.as_str())).await.map_err(CommandError::from)?;
+        let events = due_date_events(self.checkout_service.as_ref(), self.hold_service.as_ref(),
+                                     self.catalog_service.as_ref(), req.patron_id.as_str()).await.map_err(CommandError::from)?;
+        Ok(GetDueDatesCalendarCommandResponse::new(to_ical(&events, Utc::now().naive_utc())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::books::dto::BookDto;
+    use crate::catalog::factory::{create_catalog_query_service, create_catalog_service};
+    use crate::checkout::factory::{create_checkout_query_service, create_checkout_service};
+    use crate::core::command::{Command, CommandError};
+    use crate::core::domain::Configuration;
+    use crate::core::library::BookStatus;
+    use crate::hold::factory::create_hold_query_service;
+    use crate::patrons::calendar::build_calendar_token;
+    use crate::patrons::command::get_due_dates_calendar_cmd::{GetDueDatesCalendarCommand, GetDueDatesCalendarCommandRequest};
+    use crate::patrons::dto::PatronDto;
+    use crate::patrons::factory::{create_patron_query_service, create_patron_service};
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_run_get_due_dates_calendar() {
+        let config = Configuration::new("test");
+        let store = test_store(module_path!(), "test_should_run_get_due_dates_calendar");
+        let patron = PatronDto::new("calendar@example.com");
+        create_patron_service(&config, store).await.add_patron(&patron).await.expect("should add patron");
+        let book = BookDto::new("calendar_isbn", "Calendar, Due Book", BookStatus::Available);
+        let book = create_catalog_service(&config, store).await.add_book(&book).await.expect("should add book");
+        let checkout = create_checkout_service(&config, store).await
+            .checkout(&patron.patron_id, &book.book_id).await.expect("should checkout book");
+
+        let sut_cmd = GetDueDatesCalendarCommand::new(
+            create_patron_query_service(&config, store).await, create_checkout_query_service(&config, store).await,
+            create_hold_query_service(&config, store).await, create_catalog_query_service(&config, store).await,
+            config.calendar_secret.as_str());
+        let token = build_calendar_token(config.calendar_secret.as_str(), patron.patron_id.as_str());
+        let res = sut_cmd.execute(GetDueDatesCalendarCommandRequest::new(patron.patron_id.as_str(), token.as_str()))
+            .await.expect("should build calendar");
+        assert!(res.ics.contains(format!("UID:checkout-{}@lms", checkout.checkout_id).as_str()));
+        assert!(res.ics.contains("SUMMARY:Due: Calendar\\, Due Book\r\n"));
+        assert!(res.ics.contains(format!("DTSTART:{}", checkout.due_at.format("%Y%m%dT%H%M%SZ")).as_str()));
+
+        let res = sut_cmd.execute(GetDueDatesCalendarCommandRequest::new(patron.patron_id.as_str(), "forged")).await;
+        assert!(matches!(res, Err(CommandError::Access { .. })));
+    }
+}
@@ -2,21 +2,22 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use crate::patrons::dto::PatronDto;
 use crate::core::command::{Command, CommandError};
-use crate::patrons::domain::PatronService;
+use crate::core::ids::PatronId;
+use crate::patrons::domain::PatronQueryService;
 
 pub(crate) struct GetPatronCommand {
-    patron_service: Box<dyn PatronService>,
+    patron_service: Box<dyn PatronQueryService>,
 }
 
 impl GetPatronCommand {
-    pub(crate) fn new(patron_service: Box<dyn PatronService>) -> Self {
+    pub(crate) fn new(patron_service: Box<dyn PatronQueryService>) -> Self {
         Self {
             patron_service,
         }
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct GetPatronCommandRequest {
     pub patron_id: String,
 }
@@ -30,7 +31,7 @@ impl GetPatronCommandRequest {
 }
 
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct GetPatronCommandResponse {
     patron: PatronDto,
 }
@@ -46,37 +47,36 @@ impl GetPatronCommandResponse {
 #[async_trait]
 impl Command<GetPatronCommandRequest, GetPatronCommandResponse> for GetPatronCommand {
     async fn execute(&self, req: GetPatronCommandRequest) -> Result<GetPatronCommandResponse, CommandError> {
-        self.patron_service.find_patron_by_id(req.patron_id.as_str())
+        self.patron_service.find_patron_by_id(&PatronId::new(req.patron_id.as_str()))
             .await.map_err(CommandError::from).map(GetPatronCommandResponse::new)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use async_once::AsyncOnce;
-    use lazy_static::lazy_static;
+    use crate::core::repository::RepositoryStore;
     use crate::patrons::command::add_patron_cmd::{AddPatronCommand, AddPatronCommandRequest};
     use crate::patrons::command::get_patron_cmd::{GetPatronCommand, GetPatronCommandRequest};
     use crate::patrons::factory;
     use crate::core::command::Command;
     use crate::core::domain::Configuration;
-    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddPatronCommand {
+        let svc = factory::create_patron_service(&Configuration::new("test"), store).await;
+        AddPatronCommand::new(svc)
+    }
 
-    lazy_static! {
-        static ref ADD_CMD : AsyncOnce<AddPatronCommand> = AsyncOnce::new(async {
-                let svc = factory::create_patron_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                AddPatronCommand::new(svc)
-            });
-        static ref GET_CMD : AsyncOnce<GetPatronCommand> = AsyncOnce::new(async {
-                let svc = factory::create_patron_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                GetPatronCommand::new(svc)
-            });
+    async fn build_get_cmd(store: RepositoryStore) -> GetPatronCommand {
+        let svc = factory::create_patron_query_service(&Configuration::new("test"), store).await;
+        GetPatronCommand::new(svc)
     }
 
     #[tokio::test]
     async fn test_should_run_get_patron() {
-        let add_cmd = ADD_CMD.get().await.clone();
-        let get_cmd = GET_CMD.get().await.clone();
+        let store = test_store(module_path!(), "test_should_run_get_patron");
+        let add_cmd = build_add_cmd(store).await;
+        let get_cmd = build_get_cmd(store).await;
 
         let add_res = add_cmd.execute(AddPatronCommandRequest::new("email1")).await.expect("should add patron");
         let get_res = get_cmd.execute(GetPatronCommandRequest::new(add_res.patron.patron_id.to_string())).await.expect("should get patron");
@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::patrons::dto::ReadingHistoryDto;
+use crate::core::command::{Command, CommandError};
+use crate::core::ids::PatronId;
+use crate::patrons::domain::PatronQueryService;
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+
+pub(crate) struct GetReadingHistoryCommand {
+    patron_service: Box<dyn PatronQueryService>,
+}
+
+impl GetReadingHistoryCommand {
+    pub(crate) fn new(patron_service: Box<dyn PatronQueryService>) -> Self {
+        Self {
+            patron_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetReadingHistoryCommandRequest {
+    #[serde(default)]
+    pub patron_id: String,
+    pub page: Option<String>,
+    pub page_size: Option<usize>,
+}
+
+impl GetReadingHistoryCommandRequest {
+    pub fn new(patron_id: &str) -> Self {
+        Self {
+            patron_id: patron_id.to_string(),
+            page: None,
+            page_size: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetReadingHistoryCommandResponse {
+    pub history: Vec<ReadingHistoryDto>,
+    pub next_page: Option<String>,
+}
+
+impl GetReadingHistoryCommandResponse {
+    pub fn new(history: Vec<ReadingHistoryDto>, next_page: Option<String>) -> Self {
+        Self {
+            history,
+            next_page,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<GetReadingHistoryCommandRequest, GetReadingHistoryCommandResponse> for GetReadingHistoryCommand {
+    async fn execute(&self, req: GetReadingHistoryCommandRequest) -> Result<GetReadingHistoryCommandResponse, CommandError> {
+        self.patron_service.reading_history(&PatronId::new(req.patron_id.as_str()), req.page.as_deref(),
+                                            req.page_size.unwrap_or(DEFAULT_PAGE_SIZE))
+            .await.map_err(CommandError::from).map(|res| GetReadingHistoryCommandResponse::new(res.records, res.next_page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::repository::RepositoryStore;
+    use crate::patrons::command::add_patron_cmd::{AddPatronCommand, AddPatronCommandRequest};
+    use crate::patrons::command::get_reading_history_cmd::{GetReadingHistoryCommand, GetReadingHistoryCommandRequest};
+    use crate::patrons::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddPatronCommand {
+        let svc = factory::create_patron_service(&Configuration::new("test"), store).await;
+        AddPatronCommand::new(svc)
+    }
+
+    async fn build_history_cmd(store: RepositoryStore) -> GetReadingHistoryCommand {
+        let svc = factory::create_patron_query_service(&Configuration::new("test"), store).await;
+        GetReadingHistoryCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_not_get_history_without_opt_in() {
+        let store = test_store(module_path!(), "test_should_not_get_history_without_opt_in");
+        let add_cmd = build_add_cmd(store).await;
+        let history_cmd = build_history_cmd(store).await;
+
+        let add_res = add_cmd.execute(AddPatronCommandRequest::new("email1")).await.expect("should add patron");
+        let res = history_cmd.execute(GetReadingHistoryCommandRequest::new(add_res.patron.patron_id.as_str())).await;
+        assert!(res.is_err());
+    }
+}
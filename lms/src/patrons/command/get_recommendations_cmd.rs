@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::books::dto::RelatedBookDto;
+use crate::core::command::{Command, CommandError};
+use crate::core::ids::PatronId;
+use crate::patrons::domain::PatronQueryService;
+
+const DEFAULT_LIMIT: usize = 10;
+
+pub(crate) struct GetRecommendationsCommand {
+    patron_service: Box<dyn PatronQueryService>,
+}
+
+impl GetRecommendationsCommand {
+    pub(crate) fn new(patron_service: Box<dyn PatronQueryService>) -> Self {
+        Self {
+            patron_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetRecommendationsCommandRequest {
+    #[serde(default)]
+    pub patron_id: String,
+    pub limit: Option<usize>,
+}
+
+impl GetRecommendationsCommandRequest {
+    pub fn new(patron_id: &str) -> Self {
+        Self {
+            patron_id: patron_id.to_string(),
+            limit: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetRecommendationsCommandResponse {
+    pub recommendations: Vec<RelatedBookDto>,
+}
+
+impl GetRecommendationsCommandResponse {
+    pub fn new(recommendations: Vec<RelatedBookDto>) -> Self {
+        Self {
+            recommendations,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<GetRecommendationsCommandRequest, GetRecommendationsCommandResponse> for GetRecommendationsCommand {
+    async fn execute(&self, req: GetRecommendationsCommandRequest) -> Result<GetRecommendationsCommandResponse, CommandError> {
+        self.patron_service.recommendations(&PatronId::new(req.patron_id.as_str()), req.limit.unwrap_or(DEFAULT_LIMIT))
+            .await.map_err(CommandError::from).map(GetRecommendationsCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::repository::RepositoryStore;
+    use crate::patrons::command::add_patron_cmd::{AddPatronCommand, AddPatronCommandRequest};
+    use crate::patrons::command::get_recommendations_cmd::{GetRecommendationsCommand, GetRecommendationsCommandRequest};
+    use crate::patrons::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddPatronCommand {
+        let svc = factory::create_patron_service(&Configuration::new("test"), store).await;
+        AddPatronCommand::new(svc)
+    }
+
+    async fn build_recommend_cmd(store: RepositoryStore) -> GetRecommendationsCommand {
+        let svc = factory::create_patron_query_service(&Configuration::new("test"), store).await;
+        GetRecommendationsCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_not_recommend_without_opt_in() {
+        let store = test_store(module_path!(), "test_should_not_recommend_without_opt_in");
+        let add_cmd = build_add_cmd(store).await;
+        let recommend_cmd = build_recommend_cmd(store).await;
+
+        let add_res = add_cmd.execute(AddPatronCommandRequest::new("email1")).await.expect("should add patron");
+        let res = recommend_cmd.execute(GetRecommendationsCommandRequest::new(add_res.patron.patron_id.as_str()))
+            .await.expect("should return recommendations");
+        assert_eq!(0, res.recommendations.len());
+    }
+}
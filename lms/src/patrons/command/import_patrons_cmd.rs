@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::core::library::{BatchResult, BatchStatus};
+use crate::patrons::domain::PatronService;
+use crate::patrons::dto::ImportedPatronDto;
+
+pub(crate) struct ImportPatronsCommand {
+    patron_service: Box<dyn PatronService>,
+}
+
+impl ImportPatronsCommand {
+    pub(crate) fn new(patron_service: Box<dyn PatronService>) -> Self {
+        Self {
+            patron_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ImportPatronsCommandRequest {
+    csv: String,
+}
+
+impl ImportPatronsCommandRequest {
+    pub fn new(csv: &str) -> Self {
+        Self {
+            csv: csv.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ImportPatronsCommandResponse {
+    #[serde(flatten)]
+    result: BatchResult<ImportedPatronDto>,
+}
+
+impl ImportPatronsCommandResponse {
+    pub fn new(result: BatchResult<ImportedPatronDto>) -> Self {
+        Self {
+            result,
+        }
+    }
+
+    pub(crate) fn status(&self) -> BatchStatus {
+        self.result.status
+    }
+}
+
+#[async_trait]
+impl Command<ImportPatronsCommandRequest, ImportPatronsCommandResponse> for ImportPatronsCommand {
+    async fn execute(&self, req: ImportPatronsCommandRequest) -> Result<ImportPatronsCommandResponse, CommandError> {
+        self.patron_service.import_patrons(req.csv.as_str())
+            .await.map_err(CommandError::from).map(ImportPatronsCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::{Command, CommandError};
+    use crate::core::domain::Configuration;
+    use crate::core::library::BatchStatus;
+    use crate::patrons::command::add_patron_cmd::{AddPatronCommand, AddPatronCommandRequest};
+    use crate::patrons::command::import_patrons_cmd::{ImportPatronsCommand, ImportPatronsCommandRequest};
+    use crate::patrons::factory;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_run_import_patrons() {
+        let store = test_store(module_path!(), "test_should_run_import_patrons");
+        let config = Configuration::new("test");
+        let sut_cmd = ImportPatronsCommand::new(factory::create_patron_service(&config, store).await);
+        let add_cmd = AddPatronCommand::new(factory::create_patron_service(&config, store).await);
+        let _ = add_cmd.execute(AddPatronCommandRequest::new("registered_import@example.com")).await.expect("should add patron");
+
+        let csv = "email,first_name,last_name\n\
+                   first_import@example.com,Ada,Lovelace\n\
+                   Registered_Import@example.com,Bob,Smith\n\
+                   second_import@example.com,Carl,Jones\n\
+                   FIRST_IMPORT@example.com,Ada,Again\n\
+                   invalid,Dan,Brown\n";
+        let res = sut_cmd.execute(ImportPatronsCommandRequest::new(csv)).await.expect("should import patrons");
+        assert_eq!(BatchStatus::PartiallySucceeded, res.status());
+        assert_eq!(vec![2, 4], res.result.successes.iter().map(|p| p.line).collect::<Vec<usize>>());
+        assert_eq!(vec!["3", "5", "6"], res.result.failures.iter().map(|f| f.id.as_str()).collect::<Vec<&str>>());
+
+        // imported emails are registered
+        let res = sut_cmd.execute(ImportPatronsCommandRequest::new("email\nsecond_import@example.com\n"))
+            .await.expect("should import patrons");
+        assert_eq!(BatchStatus::Failed, res.status());
+
+        let res = sut_cmd.execute(ImportPatronsCommandRequest::new("name\nAda\n")).await;
+        assert!(matches!(res, Err(CommandError::Validation { .. })));
+    }
+}
@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::core::ids::PatronId;
+use crate::core::library::LibraryError;
+use crate::patrons::calendar::build_calendar_token;
+use crate::patrons::domain::PatronQueryService;
+use crate::patrons::Patron;
+
+pub(crate) struct IssueCalendarTokenCommand {
+    patron_service: Box<dyn PatronQueryService>,
+    calendar_secret: String,
+}
+
+impl IssueCalendarTokenCommand {
+    pub(crate) fn new(patron_service: Box<dyn PatronQueryService>, calendar_secret: &str) -> Self {
+        Self {
+            patron_service,
+            calendar_secret: calendar_secret.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct IssueCalendarTokenCommandRequest {
+    pub patron_id: String,
+    // party of the authenticated request, patrons get tokens of their own feeds and librarians of any patron
+    pub requested_by: String,
+}
+
+impl IssueCalendarTokenCommandRequest {
+    pub fn new(patron_id: &str, requested_by: &str) -> Self {
+        Self {
+            patron_id: patron_id.to_string(),
+            requested_by: requested_by.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct IssueCalendarTokenCommandResponse {
+    pub token: String,
+    // path of the feed that calendar apps subscribe to
+    pub feed_path: String,
+}
+
+impl IssueCalendarTokenCommandResponse {
+    pub fn new(patron_id: &str, token: String) -> Self {
+        Self {
+            feed_path: format!("/patrons/{}/due-dates.ics?token={}", patron_id, token),
+            token,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<IssueCalendarTokenCommandRequest, IssueCalendarTokenCommandResponse> for IssueCalendarTokenCommand {
+    async fn execute(&self, req: IssueCalendarTokenCommandRequest) -> Result<IssueCalendarTokenCommandResponse, CommandError> {
+        let patron = self.patron_service.find_patron_by_id(&PatronId::new(req.patron_id.as_str())).await.map_err(CommandError::from)?;
+        if req.requested_by != req.patron_id {
+            let requester = self.patron_service.find_patron_by_id(&PatronId::new(req.requested_by.as_str())).await.map_err(CommandError::from)?;
+            if !requester.is_librarian() && !requester.is_admin() {
+                return Err(CommandError::from(LibraryError::not_granted(
+                    format!("{} cannot subscribe to due dates of patron {}", req.requested_by, req.patron_id).as_str(),
+                    Some("403".to_string()))));
+            }
+        }
+        let token = build_calendar_token(self.calendar_secret.as_str(), patron.patron_id.as_str());
+        Ok(IssueCalendarTokenCommandResponse::new(patron.patron_id.as_str(), token))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::{Command, CommandError};
+    use crate::core::domain::Configuration;
+    use crate::patrons::calendar::verify_calendar_token;
+    use crate::patrons::command::issue_calendar_token_cmd::{IssueCalendarTokenCommand, IssueCalendarTokenCommandRequest};
+    use crate::patrons::dto::PatronDto;
+    use crate::patrons::factory::{create_patron_query_service, create_patron_service};
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_run_issue_calendar_token() {
+        let store = test_store(module_path!(), "test_should_run_issue_calendar_token");
+        let config = Configuration::new("test");
+        let patron_svc = create_patron_service(&config, store).await;
+        let patron = PatronDto::new("calendar_token@example.com");
+        let other = PatronDto::new("calendar_other@example.com");
+        patron_svc.add_patron(&patron).await.expect("should add patron");
+        patron_svc.add_patron(&other).await.expect("should add patron");
+
+        let sut_cmd = IssueCalendarTokenCommand::new(
+            create_patron_query_service(&config, store).await, config.calendar_secret.as_str());
+        let res = sut_cmd.execute(IssueCalendarTokenCommandRequest::new(patron.patron_id.as_str(), patron.patron_id.as_str()))
+            .await.expect("should issue token");
+        assert!(verify_calendar_token(config.calendar_secret.as_str(), patron.patron_id.as_str(), res.token.as_str()).is_ok());
+        assert_eq!(format!("/patrons/{}/due-dates.ics?token={}", patron.patron_id, res.token), res.feed_path);
+
+        // regular patrons cannot subscribe to the due dates of others
+        let res = sut_cmd.execute(IssueCalendarTokenCommandRequest::new(patron.patron_id.as_str(), other.patron_id.as_str())).await;
+        assert!(matches!(res, Err(CommandError::Access { .. })));
+    }
+}
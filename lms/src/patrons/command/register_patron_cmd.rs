@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::patrons::dto::PatronDto;
+use crate::core::command::{Command, CommandError};
+use crate::core::library::LibraryResult;
+use crate::patrons::domain::PatronService;
+
+pub(crate) struct RegisterPatronCommand {
+    patron_service: Box<dyn PatronService>,
+}
+
+impl RegisterPatronCommand {
+    pub(crate) fn new(patron_service: Box<dyn PatronService>) -> Self {
+        Self {
+            patron_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RegisterPatronCommandRequest {
+    pub email: String,
+    #[serde(default)]
+    pub first_name: String,
+    #[serde(default)]
+    pub last_name: String,
+    #[serde(default)]
+    pub under_13: bool,
+}
+
+impl RegisterPatronCommandRequest {
+    pub fn new(email: &str, first_name: &str, last_name: &str) -> Self {
+        Self {
+            email: email.to_string(),
+            first_name: first_name.to_string(),
+            last_name: last_name.to_string(),
+            under_13: false,
+        }
+    }
+    pub fn build_patron(&self) -> LibraryResult<PatronDto> {
+        PatronDto::builder()
+            .email(self.email.as_str())
+            .first_name(self.first_name.as_str())
+            .last_name(self.last_name.as_str())
+            .under_13(self.under_13)
+            .build()
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RegisterPatronCommandResponse {
+    pub patron: PatronDto,
+}
+
+impl RegisterPatronCommandResponse {
+    pub fn new(patron: PatronDto) -> Self {
+        Self {
+            patron,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<RegisterPatronCommandRequest, RegisterPatronCommandResponse> for RegisterPatronCommand {
+    async fn execute(&self, req: RegisterPatronCommandRequest) -> Result<RegisterPatronCommandResponse, CommandError> {
+        let patron = req.build_patron().map_err(CommandError::from)?;
+        self.patron_service.register_patron(&patron)
+            .await.map_err(CommandError::from).map(RegisterPatronCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::repository::RepositoryStore;
+    use crate::patrons::command::register_patron_cmd::{RegisterPatronCommand, RegisterPatronCommandRequest};
+    use crate::patrons::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::AccountStatus;
+    use crate::utils::testing::test_store;
+
+    async fn sut_cmd(store: RepositoryStore) -> RegisterPatronCommand {
+        let svc = factory::create_patron_service(&Configuration::new("test"), store).await;
+        RegisterPatronCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_register_patron() {
+        let store = test_store(module_path!(), "test_should_run_register_patron");
+        let cmd = sut_cmd(store).await;
+
+        let res = cmd.execute(RegisterPatronCommandRequest::new("register_cmd@example.com", "first", "last"))
+            .await.expect("should register patron");
+        assert_eq!(AccountStatus::Pending, res.patron.account_status);
+        assert_eq!("first", res.patron.first_name.as_str());
+    }
+}
@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use crate::core::command::{Command, CommandError};
+use crate::core::ids::PatronId;
 use crate::patrons::domain::PatronService;
 
 pub(crate) struct RemovePatronCommand {
@@ -15,7 +16,7 @@ impl RemovePatronCommand {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct RemovePatronCommandRequest {
     pub(crate) patron_id: String,
 }
@@ -29,7 +30,7 @@ impl RemovePatronCommandRequest {
 }
 
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct RemovePatronCommandResponse {}
 
 impl RemovePatronCommandResponse {
@@ -41,40 +42,39 @@ impl RemovePatronCommandResponse {
 #[async_trait]
 impl Command<RemovePatronCommandRequest, RemovePatronCommandResponse> for RemovePatronCommand {
     async fn execute(&self, req: RemovePatronCommandRequest) -> Result<RemovePatronCommandResponse, CommandError> {
-        self.patron_service.remove_patron(req.patron_id.as_str()).await
+        self.patron_service.remove_patron(&PatronId::new(req.patron_id.as_str())).await
             .map_err(CommandError::from).map(|_|RemovePatronCommandResponse::new())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use async_once::AsyncOnce;
-    use lazy_static::lazy_static;
+    use crate::core::repository::RepositoryStore;
     use crate::patrons::command::add_patron_cmd::{AddPatronCommand, AddPatronCommandRequest};
     use crate::patrons::command::remove_patron_cmd::{RemovePatronCommand, RemovePatronCommandRequest};
     use crate::patrons::factory;
     use crate::core::command::Command;
     use crate::core::domain::Configuration;
-    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddPatronCommand {
+        let svc = factory::create_patron_service(&Configuration::new("test"), store).await;
+        AddPatronCommand::new(svc)
+    }
 
-    lazy_static! {
-        static ref ADD_CMD : AsyncOnce<AddPatronCommand> = AsyncOnce::new(async {
-                let svc = factory::create_patron_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                AddPatronCommand::new(svc)
-            });
-        static ref REMOVE_CMD : AsyncOnce<RemovePatronCommand> = AsyncOnce::new(async {
-                let svc = factory::create_patron_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                RemovePatronCommand::new(svc)
-            });
+    async fn build_remove_cmd(store: RepositoryStore) -> RemovePatronCommand {
+        let svc = factory::create_patron_service(&Configuration::new("test"), store).await;
+        RemovePatronCommand::new(svc)
     }
 
     #[tokio::test]
     async fn test_should_run_remove_patron() {
-        let add_cmd = ADD_CMD.get().await.clone();
-        let remove_cmd = REMOVE_CMD.get().await.clone();
+        let store = test_store(module_path!(), "test_should_run_remove_patron");
+        let add_cmd = build_add_cmd(store).await;
+        let remove_cmd = build_remove_cmd(store).await;
 
         let res = add_cmd.execute(AddPatronCommandRequest::new("email")).await.expect("should add patron");
-        let _ = remove_cmd.execute(RemovePatronCommandRequest::new(res.patron.patron_id)).await.expect("should remove patron");
+        let _ = remove_cmd.execute(RemovePatronCommandRequest::new(res.patron.patron_id.into())).await.expect("should remove patron");
     }
 
 }
@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::core::ids::PatronId;
+use crate::patrons::domain::PatronService;
+use crate::patrons::dto::PatronDto;
+
+pub(crate) struct RestorePatronCommand {
+    patron_service: Box<dyn PatronService>,
+}
+
+impl RestorePatronCommand {
+    pub(crate) fn new(patron_service: Box<dyn PatronService>) -> Self {
+        Self {
+            patron_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RestorePatronCommandRequest {
+    pub(crate) patron_id: String,
+}
+
+impl RestorePatronCommandRequest {
+    pub fn new(patron_id: String) -> Self {
+        Self {
+            patron_id,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RestorePatronCommandResponse {
+    pub patron: PatronDto,
+}
+
+impl RestorePatronCommandResponse {
+    pub fn new(patron: PatronDto) -> Self {
+        Self {
+            patron,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<RestorePatronCommandRequest, RestorePatronCommandResponse> for RestorePatronCommand {
+    async fn execute(&self, req: RestorePatronCommandRequest) -> Result<RestorePatronCommandResponse, CommandError> {
+        self.patron_service.restore_patron(&PatronId::new(req.patron_id.as_str())).await
+            .map_err(CommandError::from).map(RestorePatronCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::patrons::command::add_patron_cmd::{AddPatronCommand, AddPatronCommandRequest};
+    use crate::patrons::command::remove_patron_cmd::{RemovePatronCommand, RemovePatronCommandRequest};
+    use crate::patrons::command::restore_patron_cmd::{RestorePatronCommand, RestorePatronCommandRequest};
+    use crate::patrons::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::AccountStatus;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_run_restore_patron() {
+        let store = test_store(module_path!(), "test_should_run_restore_patron");
+        let config = Configuration::new("test");
+        let add_cmd = AddPatronCommand::new(factory::create_patron_service(&config, store).await);
+        let remove_cmd = RemovePatronCommand::new(factory::create_patron_service(&config, store).await);
+        let restore_cmd = RestorePatronCommand::new(factory::create_patron_service(&config, store).await);
+
+        let res = add_cmd.execute(AddPatronCommandRequest::new("restore_cmd@example.com")).await.expect("should add patron");
+        let patron_id = res.patron.patron_id;
+        let _ = remove_cmd.execute(RemovePatronCommandRequest::new(patron_id.to_string())).await.expect("should remove patron");
+        let res = restore_cmd.execute(RestorePatronCommandRequest::new(patron_id.into())).await.expect("should restore patron");
+        assert_eq!(AccountStatus::Active, res.patron.account_status);
+    }
+}
@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::library::AccountStatus;
+use crate::patrons::dto::PatronDto;
+use crate::core::command::{Command, CommandError};
+use crate::core::ids::PatronId;
+use crate::patrons::domain::PatronService;
+
+pub(crate) struct SetAccountStatusCommand {
+    patron_service: Box<dyn PatronService>,
+}
+
+impl SetAccountStatusCommand {
+    pub(crate) fn new(patron_service: Box<dyn PatronService>) -> Self {
+        Self {
+            patron_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SetAccountStatusCommandRequest {
+    #[serde(default)]
+    pub patron_id: String,
+    pub changed_by: String,
+    pub account_status: AccountStatus,
+    #[serde(default)]
+    pub reason: String,
+}
+
+impl SetAccountStatusCommandRequest {
+    pub fn new(patron_id: &str, changed_by: &str, account_status: AccountStatus, reason: &str) -> Self {
+        Self {
+            patron_id: patron_id.to_string(),
+            changed_by: changed_by.to_string(),
+            account_status,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SetAccountStatusCommandResponse {
+    pub patron: PatronDto,
+}
+
+impl SetAccountStatusCommandResponse {
+    pub fn new(patron: PatronDto) -> Self {
+        Self {
+            patron,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<SetAccountStatusCommandRequest, SetAccountStatusCommandResponse> for SetAccountStatusCommand {
+    async fn execute(&self, req: SetAccountStatusCommandRequest) -> Result<SetAccountStatusCommandResponse, CommandError> {
+        self.patron_service.set_account_status(&PatronId::new(req.patron_id.as_str()), &PatronId::new(req.changed_by.as_str()),
+                                               req.account_status, req.reason.as_str())
+            .await.map_err(CommandError::from).map(SetAccountStatusCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::repository::RepositoryStore;
+    use crate::patrons::command::set_account_status_cmd::{SetAccountStatusCommand, SetAccountStatusCommandRequest};
+    use crate::patrons::domain::PatronService;
+    use crate::patrons::dto::PatronDto;
+    use crate::patrons::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{AccountStatus, Role};
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn PatronService> {
+        factory::create_patron_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_set_cmd(store: RepositoryStore) -> SetAccountStatusCommand {
+        let svc = factory::create_patron_service(&Configuration::new("test"), store).await;
+        SetAccountStatusCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_set_account_status() {
+        let store = test_store(module_path!(), "test_should_run_set_account_status");
+        let svc = build_svc(store).await;
+        let set_cmd = build_set_cmd(store).await;
+
+        let patron = PatronDto::new("status_cmd@example.com");
+        let _ = svc.add_patron(&patron).await.expect("should add patron");
+        let mut librarian = PatronDto::new("status_cmd_librarian@example.com");
+        librarian.group_roles = vec![Role::Librarian];
+        let _ = svc.add_patron(&librarian).await.expect("should add librarian");
+
+        let res = set_cmd.execute(SetAccountStatusCommandRequest::new(
+            patron.patron_id.as_str(), librarian.patron_id.as_str(), AccountStatus::Suspended, "unpaid fines"))
+            .await.expect("should suspend patron");
+        assert_eq!(AccountStatus::Suspended, res.patron.account_status);
+        assert_eq!("unpaid fines", res.patron.status_reason.as_str());
+    }
+}
@@ -0,0 +1,90 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::patrons::dto::PatronDto;
+use crate::core::command::{Command, CommandError};
+use crate::core::ids::PatronId;
+use crate::patrons::domain::PatronService;
+
+pub(crate) struct SetReadingHistoryCommand {
+    patron_service: Box<dyn PatronService>,
+}
+
+impl SetReadingHistoryCommand {
+    pub(crate) fn new(patron_service: Box<dyn PatronService>) -> Self {
+        Self {
+            patron_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SetReadingHistoryCommandRequest {
+    #[serde(default)]
+    pub patron_id: String,
+    pub enabled: bool,
+}
+
+impl SetReadingHistoryCommandRequest {
+    pub fn new(patron_id: &str, enabled: bool) -> Self {
+        Self {
+            patron_id: patron_id.to_string(),
+            enabled,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SetReadingHistoryCommandResponse {
+    pub patron: PatronDto,
+}
+
+impl SetReadingHistoryCommandResponse {
+    pub fn new(patron: PatronDto) -> Self {
+        Self {
+            patron,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<SetReadingHistoryCommandRequest, SetReadingHistoryCommandResponse> for SetReadingHistoryCommand {
+    async fn execute(&self, req: SetReadingHistoryCommandRequest) -> Result<SetReadingHistoryCommandResponse, CommandError> {
+        self.patron_service.set_reading_history(&PatronId::new(req.patron_id.as_str()), req.enabled)
+            .await.map_err(CommandError::from).map(SetReadingHistoryCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::repository::RepositoryStore;
+    use crate::patrons::command::add_patron_cmd::{AddPatronCommand, AddPatronCommandRequest};
+    use crate::patrons::command::set_reading_history_cmd::{SetReadingHistoryCommand, SetReadingHistoryCommandRequest};
+    use crate::patrons::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddPatronCommand {
+        let svc = factory::create_patron_service(&Configuration::new("test"), store).await;
+        AddPatronCommand::new(svc)
+    }
+
+    async fn build_set_cmd(store: RepositoryStore) -> SetReadingHistoryCommand {
+        let svc = factory::create_patron_service(&Configuration::new("test"), store).await;
+        SetReadingHistoryCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_set_reading_history() {
+        let store = test_store(module_path!(), "test_should_run_set_reading_history");
+        let add_cmd = build_add_cmd(store).await;
+        let set_cmd = build_set_cmd(store).await;
+
+        let add_res = add_cmd.execute(AddPatronCommandRequest::new("email1")).await.expect("should add patron");
+        assert!(!add_res.patron.reading_history_enabled);
+        let set_res = set_cmd.execute(SetReadingHistoryCommandRequest::new(add_res.patron.patron_id.as_str(), true))
+            .await.expect("should enable reading history");
+        assert!(set_res.patron.reading_history_enabled);
+    }
+}
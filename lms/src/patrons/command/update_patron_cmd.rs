@@ -1,6 +1,6 @@
 use async_trait::async_trait;
-use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use crate::core::library::LibraryResult;
 use crate::patrons::dto::PatronDto;
 use crate::core::command::{Command, CommandError};
 use crate::patrons::domain::PatronService;
@@ -17,7 +17,7 @@ impl UpdatePatronCommand {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct UpdatePatronCommandRequest {
     pub patron_id: String,
     pub email: String,
@@ -34,28 +34,13 @@ impl UpdatePatronCommandRequest {
             last_name: last_name.to_string(),
         }
     }
-    pub fn build_patron(&self) -> PatronDto {
-        PatronDto {
-            patron_id: self.patron_id.to_string(),
-            version: 0,
-            first_name: self.first_name.to_string(),
-            last_name: self.last_name.to_string(),
-            email: self.email.to_string(),
-            under_13: false,
-            group_roles: vec![],
-            num_holds: 0,
-            num_overdue: 0,
-            home_phone: None,
-            cell_phone: None,
-            work_phone: None,
-            street_address: None,
-            city: None,
-            zip_code: None,
-            state: None,
-            country: None,
-            created_at: Utc::now().naive_utc(),
-            updated_at: Utc::now().naive_utc(),
-        }
+    pub fn build_patron(&self) -> LibraryResult<PatronDto> {
+        PatronDto::builder()
+            .patron_id(self.patron_id.as_str())
+            .email(self.email.as_str())
+            .first_name(self.first_name.as_str())
+            .last_name(self.last_name.as_str())
+            .build()
     }
 }
 
@@ -76,38 +61,37 @@ impl UpdatePatronCommandResponse {
 #[async_trait]
 impl Command<UpdatePatronCommandRequest, UpdatePatronCommandResponse> for UpdatePatronCommand {
     async fn execute(&self, req: UpdatePatronCommandRequest) -> Result<UpdatePatronCommandResponse, CommandError> {
-        let patron = req.build_patron();
+        let patron = req.build_patron().map_err(CommandError::from)?;
         self.patron_service.update_patron(&patron).await.map_err(CommandError::from).map(|_| UpdatePatronCommandResponse::new(patron))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use async_once::AsyncOnce;
-    use lazy_static::lazy_static;
+    use crate::core::repository::RepositoryStore;
     use crate::patrons::dto::PatronDto;
     use crate::core::command::Command;
     use crate::core::domain::Configuration;
-    use crate::core::repository::RepositoryStore;
     use crate::patrons::command::add_patron_cmd::{AddPatronCommand, AddPatronCommandRequest};
     use crate::patrons::command::update_patron_cmd::{UpdatePatronCommand, UpdatePatronCommandRequest};
     use crate::patrons::factory;
+    use crate::utils::testing::test_store;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddPatronCommand {
+        let svc = factory::create_patron_service(&Configuration::new("test"), store).await;
+        AddPatronCommand::new(svc)
+    }
 
-    lazy_static! {
-        static ref ADD_CMD : AsyncOnce<AddPatronCommand> = AsyncOnce::new(async {
-                let svc = factory::create_patron_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                AddPatronCommand::new(svc)
-            });
-        static ref UPDATE_CMD : AsyncOnce<UpdatePatronCommand> = AsyncOnce::new(async {
-                let svc = factory::create_patron_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await;
-                UpdatePatronCommand::new(svc)
-            });
+    async fn build_update_cmd(store: RepositoryStore) -> UpdatePatronCommand {
+        let svc = factory::create_patron_service(&Configuration::new("test"), store).await;
+        UpdatePatronCommand::new(svc)
     }
 
     #[tokio::test]
     async fn test_should_run_update_patron() {
-        let add_cmd = ADD_CMD.get().await.clone();
-        let update_cmd = UPDATE_CMD.get().await.clone();
+        let store = test_store(module_path!(), "test_should_run_update_patron");
+        let add_cmd = build_add_cmd(store).await;
+        let update_cmd = build_update_cmd(store).await;
 
         let mut patron = PatronDto::new("email");
         patron.email = "old_email".to_string();
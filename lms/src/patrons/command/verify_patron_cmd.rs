@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::patrons::dto::PatronDto;
+use crate::core::command::{Command, CommandError};
+use crate::patrons::domain::PatronService;
+
+pub(crate) struct VerifyPatronCommand {
+    patron_service: Box<dyn PatronService>,
+}
+
+impl VerifyPatronCommand {
+    pub(crate) fn new(patron_service: Box<dyn PatronService>) -> Self {
+        Self {
+            patron_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct VerifyPatronCommandRequest {
+    pub token: String,
+}
+
+impl VerifyPatronCommandRequest {
+    pub fn new(token: &str) -> Self {
+        Self {
+            token: token.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct VerifyPatronCommandResponse {
+    pub patron: PatronDto,
+}
+
+impl VerifyPatronCommandResponse {
+    pub fn new(patron: PatronDto) -> Self {
+        Self {
+            patron,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<VerifyPatronCommandRequest, VerifyPatronCommandResponse> for VerifyPatronCommand {
+    async fn execute(&self, req: VerifyPatronCommandRequest) -> Result<VerifyPatronCommandResponse, CommandError> {
+        self.patron_service.verify_patron(req.token.as_str())
+            .await.map_err(CommandError::from).map(VerifyPatronCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use crate::core::repository::RepositoryStore;
+    use crate::patrons::command::register_patron_cmd::{RegisterPatronCommand, RegisterPatronCommandRequest};
+    use crate::patrons::command::verify_patron_cmd::{VerifyPatronCommand, VerifyPatronCommandRequest};
+    use crate::patrons::domain::service::build_verification_token;
+    use crate::patrons::factory;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::AccountStatus;
+    use crate::utils::testing::test_store;
+
+    async fn build_register_cmd(store: RepositoryStore) -> RegisterPatronCommand {
+        let svc = factory::create_patron_service(&Configuration::new("test"), store).await;
+        RegisterPatronCommand::new(svc)
+    }
+
+    async fn sut_cmd(store: RepositoryStore) -> VerifyPatronCommand {
+        let svc = factory::create_patron_service(&Configuration::new("test"), store).await;
+        VerifyPatronCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_verify_patron() {
+        let store = test_store(module_path!(), "test_should_run_verify_patron");
+        let register_cmd = build_register_cmd(store).await;
+        let cmd = sut_cmd(store).await;
+
+        let registered = register_cmd.execute(RegisterPatronCommandRequest::new("verify_cmd@example.com", "first", "last"))
+            .await.expect("should register patron");
+        assert!(cmd.execute(VerifyPatronCommandRequest::new("bad.token")).await.is_err());
+        let token = build_verification_token(Configuration::new("test").verification_secret.as_str(),
+                                             registered.patron.patron_id.as_str(), Utc::now().timestamp() + 3600);
+        let res = cmd.execute(VerifyPatronCommandRequest::new(token.as_str())).await.expect("should verify patron");
+        assert_eq!(AccountStatus::Active, res.patron.account_status);
+    }
+}
@@ -1,38 +1,77 @@
 use axum::{
-    extract::{Path, State},
+    http::{header, StatusCode},
+    extract::{Path, Query, State},
+    middleware,
     response::Json,
+    routing::{get, post, put},
+    Extension, Router,
 };
 use serde_json::{Value};
-use crate::core::command::Command;
-use crate::core::controller::{AppState, json_to_server_error, ServerError};
+use crate::catalog::factory::create_catalog_query_service;
+use crate::checkout::factory::create_checkout_query_service;
+use crate::core::controller::{AppState, batch_status_code, body_limit, command_bus, json_to_server_error, with_common_layers, ServerError};
+use crate::credentials::controller::authenticate;
+use crate::credentials::dto::ClaimsDto;
+use crate::hold::factory::create_hold_query_service;
 use crate::patrons::command::add_patron_cmd::{AddPatronCommand, AddPatronCommandRequest, AddPatronCommandResponse};
+use crate::patrons::command::get_due_dates_calendar_cmd::{GetDueDatesCalendarCommand, GetDueDatesCalendarCommandRequest, GetDueDatesCalendarCommandResponse};
 use crate::patrons::command::get_patron_cmd::{GetPatronCommand, GetPatronCommandRequest, GetPatronCommandResponse};
+use crate::patrons::command::get_reading_history_cmd::{GetReadingHistoryCommand, GetReadingHistoryCommandRequest, GetReadingHistoryCommandResponse};
+use crate::patrons::command::get_recommendations_cmd::{GetRecommendationsCommand, GetRecommendationsCommandRequest, GetRecommendationsCommandResponse};
+use crate::patrons::command::import_patrons_cmd::{ImportPatronsCommand, ImportPatronsCommandRequest, ImportPatronsCommandResponse};
+use crate::patrons::command::issue_calendar_token_cmd::{IssueCalendarTokenCommand, IssueCalendarTokenCommandRequest, IssueCalendarTokenCommandResponse};
+use crate::patrons::command::register_patron_cmd::{RegisterPatronCommand, RegisterPatronCommandRequest, RegisterPatronCommandResponse};
 use crate::patrons::command::remove_patron_cmd::{RemovePatronCommand, RemovePatronCommandRequest, RemovePatronCommandResponse};
-use crate::patrons::domain::PatronService;
+use crate::patrons::command::restore_patron_cmd::{RestorePatronCommand, RestorePatronCommandRequest, RestorePatronCommandResponse};
+use crate::patrons::command::set_account_status_cmd::{SetAccountStatusCommand, SetAccountStatusCommandRequest, SetAccountStatusCommandResponse};
+use crate::patrons::command::set_reading_history_cmd::{SetReadingHistoryCommand, SetReadingHistoryCommandRequest, SetReadingHistoryCommandResponse};
+use crate::patrons::command::verify_patron_cmd::{VerifyPatronCommand, VerifyPatronCommandRequest, VerifyPatronCommandResponse};
+use crate::patrons::domain::{PatronQueryService, PatronService};
 use crate::patrons::factory;
-use crate::utils::ddb::{build_db_client, create_table};
+use crate::utils::ddb::{build_db_client, create_key_table, create_table};
 
 async fn build_service(state: AppState) -> Box<dyn PatronService> {
     let client = build_db_client(state.store).await;
-    let _ = create_table(&client, "parties", "party_id", "kind", "email").await;
+    let _ = create_table(&client, state.store.table_name("parties").as_str(), "party_id", "kind", "normalized_email").await;
+    let _ = create_key_table(&client, state.store.table_name("party_emails").as_str(), "email").await;
+    let _ = create_key_table(&client, state.store.table_name("party_counter_changes").as_str(), "change_key").await;
+    let _ = create_table(&client, state.store.table_name("reading_history").as_str(), "history_id", "patron_id", "returned_at").await;
+    let _ = create_table(&client, state.store.table_name("notifications").as_str(), "notification_id", "party_id", "created_at").await;
     factory::create_patron_service(&state.config, state.store).await
 }
 
+async fn build_query_service(state: AppState) -> Box<dyn PatronQueryService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_table(&client, state.store.table_name("parties").as_str(), "party_id", "kind", "normalized_email").await;
+    let _ = create_table(&client, state.store.table_name("reading_history").as_str(), "history_id", "patron_id", "returned_at").await;
+    factory::create_patron_query_service(&state.config, state.store).await
+}
+
 pub(crate) async fn add_patron(
     State(state): State<AppState>,
     json: Json<Value>) -> Result<Json<AddPatronCommandResponse>, ServerError> {
     let req: AddPatronCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
     let svc = build_service(state).await;
-    let res = AddPatronCommand::new(svc).execute(req).await?;
+    let res = command_bus().register(AddPatronCommand::new(svc)).dispatch(req).await?;
     Ok(Json(res))
 }
 
+// patrons migrated from another library system are imported from CSV, the response reports the outcome of each row
+pub(crate) async fn import_patrons(
+    State(state): State<AppState>,
+    csv: String) -> Result<(StatusCode, Json<ImportPatronsCommandResponse>), ServerError> {
+    let req = ImportPatronsCommandRequest::new(csv.as_str());
+    let svc = build_service(state).await;
+    let res: ImportPatronsCommandResponse = command_bus().register(ImportPatronsCommand::new(svc)).dispatch(req).await?;
+    Ok((batch_status_code(res.status()), Json(res)))
+}
+
 pub(crate) async fn find_patron_by_id(
     State(state): State<AppState>,
     Path(patron_id): Path<String>) -> Result<Json<GetPatronCommandResponse>, ServerError> {
     let req = GetPatronCommandRequest { patron_id };
-    let svc = build_service(state).await;
-    let res = GetPatronCommand::new(svc).execute(req).await?;
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(GetPatronCommand::new(svc)).dispatch(req).await?;
     Ok(Json(res))
 }
 
@@ -41,6 +80,123 @@ pub(crate) async fn remove_patron(
     Path(patron_id): Path<String>) -> Result<Json<RemovePatronCommandResponse>, ServerError> {
     let req = RemovePatronCommandRequest { patron_id };
     let svc = factory::create_patron_service(&state.config, state.store).await;
-    let res = RemovePatronCommand::new(svc).execute(req).await?;
+    let res = command_bus().register(RemovePatronCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn restore_patron(
+    State(state): State<AppState>,
+    Path(patron_id): Path<String>) -> Result<Json<RestorePatronCommandResponse>, ServerError> {
+    let req = RestorePatronCommandRequest { patron_id };
+    let svc = build_service(state).await;
+    let res = command_bus().register(RestorePatronCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn set_reading_history(
+    State(state): State<AppState>,
+    Path(patron_id): Path<String>,
+    json: Json<Value>) -> Result<Json<SetReadingHistoryCommandResponse>, ServerError> {
+    let mut req: SetReadingHistoryCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.patron_id = patron_id;
+    let svc = build_service(state).await;
+    let res = command_bus().register(SetReadingHistoryCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn get_reading_history(
+    State(state): State<AppState>,
+    Path(patron_id): Path<String>,
+    Query(mut req): Query<GetReadingHistoryCommandRequest>) -> Result<Json<GetReadingHistoryCommandResponse>, ServerError> {
+    req.patron_id = patron_id;
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(GetReadingHistoryCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn get_recommendations(
+    State(state): State<AppState>,
+    Path(patron_id): Path<String>,
+    Query(mut req): Query<GetRecommendationsCommandRequest>) -> Result<Json<GetRecommendationsCommandResponse>, ServerError> {
+    req.patron_id = patron_id;
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(GetRecommendationsCommand::new(svc)).dispatch(req).await?;
     Ok(Json(res))
-}
\ No newline at end of file
+}
+
+pub(crate) async fn set_account_status(
+    State(state): State<AppState>,
+    Path(patron_id): Path<String>,
+    json: Json<Value>) -> Result<Json<SetAccountStatusCommandResponse>, ServerError> {
+    let mut req: SetAccountStatusCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.patron_id = patron_id;
+    let svc = build_service(state).await;
+    let res = command_bus().register(SetAccountStatusCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn register_patron(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<Json<RegisterPatronCommandResponse>, ServerError> {
+    let req: RegisterPatronCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res = command_bus().register(RegisterPatronCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn verify_patron(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<Json<VerifyPatronCommandResponse>, ServerError> {
+    let req: VerifyPatronCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res = command_bus().register(VerifyPatronCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// token of the due dates calendar of the patron, issued to the patron or to librarians
+pub(crate) async fn issue_calendar_token(
+    State(state): State<AppState>,
+    Extension(claims): Extension<ClaimsDto>,
+    Path(patron_id): Path<String>) -> Result<Json<IssueCalendarTokenCommandResponse>, ServerError> {
+    let req = IssueCalendarTokenCommandRequest::new(patron_id.as_str(), claims.sub.as_str());
+    let secret = state.config.calendar_secret.to_string();
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(IssueCalendarTokenCommand::new(svc, secret.as_str())).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// iCalendar feed of due dates and pickup deadlines that calendar apps subscribe to with the token of the feed URL,
+// e.g. /patrons/{id}/due-dates.ics?token={token}
+pub(crate) async fn get_due_dates_calendar(
+    State(state): State<AppState>,
+    Path(patron_id): Path<String>,
+    Query(mut req): Query<GetDueDatesCalendarCommandRequest>) -> Result<([(header::HeaderName, &'static str); 2], String), ServerError> {
+    req.patron_id = patron_id;
+    let checkout_svc = create_checkout_query_service(&state.config, state.store).await;
+    let hold_svc = create_hold_query_service(&state.config, state.store).await;
+    let catalog_svc = create_catalog_query_service(&state.config, state.store).await;
+    let secret = state.config.calendar_secret.to_string();
+    let svc = build_query_service(state).await;
+    let res: GetDueDatesCalendarCommandResponse = command_bus().register(GetDueDatesCalendarCommand::new(svc, checkout_svc, hold_svc, catalog_svc, secret.as_str()))
+        .dispatch(req).await?;
+    Ok(([(header::CONTENT_TYPE, "text/calendar; charset=utf-8"), (header::CACHE_CONTROL, "private, max-age=3600")], res.ics))
+}
+
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/patrons/:id/calendar-token", post(issue_calendar_token))
+        .route_layer(middleware::from_fn_with_state(state.clone(), authenticate))
+        .route("/patrons", post(add_patron))
+        .route("/patrons/register", post(register_patron))
+        .route("/patrons/import", post(import_patrons).layer(body_limit(state.config.max_bulk_body_bytes)))
+        .route("/patrons/verify", post(verify_patron))
+        .route("/patrons/:id",
+               get(find_patron_by_id).delete(remove_patron))
+        .route("/patrons/:id/restore", post(restore_patron))
+        .route("/patrons/:id/reading-history",
+               get(get_reading_history).put(set_reading_history))
+        .route("/patrons/:id/recommendations", get(get_recommendations))
+        .route("/patrons/:id/due-dates.ics", get(get_due_dates_calendar))
+        .route("/patrons/:id/status", put(set_account_status));
+    with_common_layers(router, state)
+}
@@ -1,14 +1,46 @@
+pub mod query;
 pub mod service;
 
 use async_trait::async_trait;
-use crate::core::library::LibraryResult;
-use crate::patrons::dto::PatronDto;
+use crate::books::dto::RelatedBookDto;
+use crate::core::ids::PatronId;
+use crate::core::library::{AccountStatus, BatchResult, LibraryResult, PaginatedResult};
+use crate::patrons::dto::{ImportedPatronDto, PatronDto, ReadingHistoryDto};
 
+// read side of patrons, find_patron_in_good_standing stays on PatronService as it may suspend the account
 #[async_trait]
-pub(crate) trait PatronService: Sync + Send {
+pub(crate) trait PatronQueryService: Sync + Send {
+    async fn find_patron_by_id(&self, id: &PatronId) -> LibraryResult<PatronDto>;
+    async fn find_patron_by_email(&self, email: &str) -> LibraryResult<Vec<PatronDto>>;
+    async fn reading_history(&self, id: &PatronId,
+                             page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<ReadingHistoryDto>>;
+    // recommends books related to reading history excluding books that were already read
+    async fn recommendations(&self, id: &PatronId, limit: usize) -> LibraryResult<Vec<RelatedBookDto>>;
+}
+
+#[async_trait]
+pub(crate) trait PatronService: PatronQueryService {
     async fn add_patron(&self, patron: &PatronDto) -> LibraryResult<()>;
-    async fn remove_patron(&self, id: &str) -> LibraryResult<()>;
+    // imports active patrons from CSV, e.g. when migrating from another library system, rows that fail validation or
+    // whose emails are already registered are reported by line and the other rows are created in batches
+    async fn import_patrons(&self, csv: &str) -> LibraryResult<BatchResult<ImportedPatronDto>>;
+    // creates pending patron and sends verification email with signed token
+    async fn register_patron(&self, patron: &PatronDto) -> LibraryResult<PatronDto>;
+    // activates pending patron with the token from verification email
+    async fn verify_patron(&self, token: &str) -> LibraryResult<PatronDto>;
+    // marks patron as deleted, the record and email are kept until the retention of removed patrons ends
+    async fn remove_patron(&self, id: &PatronId) -> LibraryResult<()>;
+    // reactivates a removed patron whose record was not purged yet
+    async fn restore_patron(&self, id: &PatronId) -> LibraryResult<PatronDto>;
     async fn update_patron(&self, patron: &PatronDto) -> LibraryResult<()>;
-    async fn find_patron_by_id(&self, id: &str) -> LibraryResult<PatronDto>;
-    async fn find_patron_by_email(&self, email: &str) -> LibraryResult<Vec<PatronDto>>;
+    // returns patron who is allowed to borrow, patrons with too many overdue items are suspended automatically
+    async fn find_patron_in_good_standing(&self, id: &PatronId) -> LibraryResult<PatronDto>;
+    // only librarians can change account status of patrons
+    async fn set_account_status(&self, id: &PatronId, changed_by: &PatronId,
+                                status: AccountStatus, reason: &str) -> LibraryResult<PatronDto>;
+    // opting out of reading history also deletes existing history of patron
+    async fn set_reading_history(&self, id: &PatronId, enabled: bool) -> LibraryResult<PatronDto>;
+    // deletes removed patrons whose retention ended along with their email and reading history, returns the number
+    // of purged patrons
+    async fn purge_deleted(&self, page_size: usize) -> LibraryResult<usize>;
 }
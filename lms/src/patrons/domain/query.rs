@@ -0,0 +1,157 @@
+use std::collections::{HashMap, HashSet};
+use async_trait::async_trait;
+use crate::books::dto::RelatedBookDto;
+use crate::catalog::domain::CatalogQueryService;
+use crate::core::domain::Configuration;
+use crate::core::ids::{BookId, PatronId};
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult, PartyKind};
+use crate::parties::domain::model::normalize_email;
+use crate::parties::repository::PartyRepository;
+use crate::patrons::domain::PatronQueryService;
+use crate::patrons::dto::{PatronDto, ReadingHistoryDto};
+use crate::projector::repository::ReadingHistoryRepository;
+
+// number of recently read books that are used for recommendations
+const MAX_SEED_BOOKS: usize = 20;
+// maximum number of history records that are checked for already read books
+const MAX_HISTORY: usize = 500;
+
+pub(crate) struct PatronQueryServiceImpl {
+    strip_email_plus_tags: bool,
+    party_repository: Box<dyn PartyRepository>,
+    history_repository: Box<dyn ReadingHistoryRepository>,
+    catalog_service: Box<dyn CatalogQueryService>,
+}
+
+impl PatronQueryServiceImpl {
+    pub(crate) fn new(config: &Configuration, party_repository: Box<dyn PartyRepository>,
+                      history_repository: Box<dyn ReadingHistoryRepository>,
+                      catalog_service: Box<dyn CatalogQueryService>) -> Self {
+        Self {
+            strip_email_plus_tags: config.strip_email_plus_tags,
+            party_repository,
+            history_repository,
+            catalog_service,
+        }
+    }
+
+    // returns recently read books first along with all books read by patron
+    async fn read_books(&self, id: &str) -> LibraryResult<(Vec<String>, HashSet<String>)> {
+        let mut recent = vec![];
+        let mut read = HashSet::new();
+        let mut next_page: Option<String> = None;
+        while read.len() < MAX_HISTORY {
+            let res = self.history_repository.find_by_patron(id, next_page.as_deref(), 100).await?;
+            for record in res.records {
+                if read.insert(record.book_id.to_string()) && recent.len() < MAX_SEED_BOOKS {
+                    recent.push(record.book_id);
+                }
+            }
+            next_page = res.next_page;
+            if next_page.is_none() {
+                break;
+            }
+        }
+        Ok((recent, read))
+    }
+}
+
+#[async_trait]
+impl PatronQueryService for PatronQueryServiceImpl {
+    async fn find_patron_by_id(&self, id: &PatronId) -> LibraryResult<PatronDto> {
+        self.party_repository.get(id.as_str()).await.map(|p| PatronDto::from(&p))
+    }
+
+    async fn find_patron_by_email(&self, email: &str) -> LibraryResult<Vec<PatronDto>> {
+        let res = self.party_repository.query(
+            &HashMap::from([("email".to_string(), normalize_email(email, self.strip_email_plus_tags)),
+                ("kind".to_string(), PartyKind::Patron.to_string())]), None, 100).await?;
+        Ok(res.records.iter().map(PatronDto::from).collect())
+    }
+
+    async fn reading_history(&self, id: &PatronId,
+                             page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<ReadingHistoryDto>> {
+        let patron = self.party_repository.get(id.as_str()).await?;
+        if !patron.reading_history_enabled {
+            return Err(LibraryError::validation(
+                format!("reading history is not enabled for {}", id).as_str(), None));
+        }
+        let res = self.history_repository.find_by_patron(id.as_str(), page, page_size).await?;
+        let records = res.records.iter().map(ReadingHistoryDto::from).collect();
+        Ok(PaginatedResult::new(page, page_size, res.next_page, records))
+    }
+
+    async fn recommendations(&self, id: &PatronId, limit: usize) -> LibraryResult<Vec<RelatedBookDto>> {
+        let patron = self.party_repository.get(id.as_str()).await?;
+        if !patron.reading_history_enabled {
+            return Ok(vec![]);
+        }
+        let (recent, read) = self.read_books(id.as_str()).await?;
+        let mut candidates: HashMap<String, RelatedBookDto> = HashMap::new();
+        for book_id in recent {
+            let related = match self.catalog_service.find_related_books(&BookId::new(book_id.as_str()), limit).await {
+                Ok(related) => related,
+                Err(LibraryError::NotFound { .. }) => continue,
+                Err(err) => return Err(err),
+            };
+            for other in related {
+                if read.contains(other.book.book_id.as_str()) {
+                    continue;
+                }
+                match candidates.get_mut(other.book.book_id.as_str()) {
+                    Some(existing) => {
+                        existing.score += other.score;
+                        existing.same_author = existing.same_author || other.same_author;
+                        existing.co_checkout_count += other.co_checkout_count;
+                        for tag in other.shared_tags {
+                            if !existing.shared_tags.contains(&tag) {
+                                existing.shared_tags.push(tag);
+                            }
+                        }
+                    }
+                    None => {
+                        candidates.insert(other.book.book_id.to_string(), other);
+                    }
+                }
+            }
+        }
+        let mut recommended: Vec<RelatedBookDto> = candidates.into_values().collect();
+        recommended.sort_by(|a, b| b.score.cmp(&a.score).then(a.book.title.cmp(&b.book.title)));
+        recommended.truncate(limit);
+        Ok(recommended)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::patrons::domain::{PatronQueryService, PatronService};
+    use crate::patrons::dto::PatronDto;
+    use crate::patrons::factory;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn PatronQueryService> {
+        factory::create_patron_query_service(&Configuration::new("test"), store).await
+    }
+
+    async fn patron_svc(store: RepositoryStore) -> Box<dyn PatronService> {
+        factory::create_patron_service(&Configuration::new("test"), store).await
+    }
+
+    #[tokio::test]
+    async fn test_should_find_patrons_and_skip_recommendations_without_history() {
+        let store = test_store(module_path!(), "test_should_find_patrons_and_skip_recommendations_without_history");
+        let query_svc = sut_svc(store).await;
+        let patron = PatronDto::new("query_patron@example.com");
+        patron_svc(store).await.add_patron(&patron).await.expect("should add patron");
+
+        let loaded = query_svc.find_patron_by_id(&patron.patron_id).await.expect("should find patron");
+        assert_eq!(patron.email, loaded.email);
+        let res = query_svc.find_patron_by_email("query_patron@example.com").await.expect("should find by email");
+        assert!(res.iter().any(|p| p.patron_id == patron.patron_id));
+        let recommended = query_svc.recommendations(&patron.patron_id, 10).await.expect("should recommend");
+        assert!(recommended.is_empty());
+        assert!(query_svc.reading_history(&patron.patron_id, None, 10).await.is_err());
+    }
+}
@@ -1,53 +1,345 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
 use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use crate::books::dto::RelatedBookDto;
 use crate::core::domain::Configuration;
-use crate::core::library::{LibraryResult, PartyKind, Role};
-use crate::parties::domain::model::{AddressEntity, PartyEntity};
+use crate::core::ids::PatronId;
+use crate::core::library::{AccountStatus, BatchResult, LibraryError, LibraryResult, PaginatedResult, PartyKind, Role, MAX_BATCH_ITEMS};
+use crate::gateway::address::{AddressValidator, PostalAddress};
+use crate::notifications::domain::NotificationService;
+use crate::parties::domain::model::{normalize_email, AddressEntity, PartyEntity};
 use crate::parties::repository::PartyRepository;
-use crate::patrons::domain::PatronService;
-use crate::patrons::dto::PatronDto;
+use crate::patrons::domain::{PatronQueryService, PatronService};
+use crate::patrons::dto::{ImportedPatronDto, PatronDto, ReadingHistoryDto};
+use crate::patrons::import::parse_patrons_csv;
+use crate::patrons::Patron;
+use crate::projector::domain::model::ReadingHistoryEntity;
+use crate::projector::repository::ReadingHistoryRepository;
+use crate::utils::signing::{sign_hex, verify_hex};
 
 pub(crate) struct PatronServiceImpl {
+    max_overdue: i64,
+    strip_email_plus_tags: bool,
+    verification_secret: String,
+    verification_token_hours: i64,
+    retention_days: i64,
     party_repository: Box<dyn PartyRepository>,
+    history_repository: Box<dyn ReadingHistoryRepository>,
+    notification_service: Box<dyn NotificationService>,
+    query_service: Box<dyn PatronQueryService>,
+    address_validator: Box<dyn AddressValidator>,
 }
 
 impl PatronServiceImpl {
-    pub(crate) fn new(_config: &Configuration, party_repository: Box<dyn PartyRepository>) -> Self {
+    pub(crate) fn new(config: &Configuration, party_repository: Box<dyn PartyRepository>,
+                      history_repository: Box<dyn ReadingHistoryRepository>,
+                      notification_service: Box<dyn NotificationService>,
+                      query_service: Box<dyn PatronQueryService>,
+                      address_validator: Box<dyn AddressValidator>) -> Self {
         PatronServiceImpl {
+            max_overdue: config.max_overdue,
+            strip_email_plus_tags: config.strip_email_plus_tags,
+            verification_secret: config.verification_secret.to_string(),
+            verification_token_hours: config.verification_token_hours,
+            retention_days: config.patron_retention_days,
             party_repository,
+            history_repository,
+            notification_service,
+            query_service,
+            address_validator,
         }
     }
+
+    fn to_party(&self, patron: &PatronDto) -> PartyEntity {
+        let mut entity = PartyEntity::from(patron);
+        entity.normalized_email = normalize_email(patron.email.as_str(), self.strip_email_plus_tags);
+        entity
+    }
+
+    // stores the normalized components and coordinates of the address returned by the validator
+    async fn to_validated_party(&self, patron: &PatronDto) -> LibraryResult<PartyEntity> {
+        let mut entity = self.to_party(patron);
+        if let Some(address) = entity.address.as_mut() {
+            let validated = self.address_validator.validate(&PostalAddress {
+                street_address: address.street_address.to_string(),
+                city: address.city.to_string(),
+                zip_code: address.zip_code.to_string(),
+                state: address.state.to_string(),
+                country: address.country.to_string(),
+                latitude: address.latitude,
+                longitude: address.longitude,
+            }).await?;
+            address.street_address = validated.street_address;
+            address.city = validated.city;
+            address.zip_code = validated.zip_code;
+            address.state = validated.state;
+            address.country = validated.country;
+            address.latitude = validated.latitude;
+            address.longitude = validated.longitude;
+        }
+        Ok(entity)
+    }
 }
 
 #[async_trait]
 impl PatronService for PatronServiceImpl {
     async fn add_patron(&self, patron: &PatronDto) -> LibraryResult<()> {
-        self.party_repository.create(&PartyEntity::from(patron)).await.map(|_| ())
+        let entity = self.to_validated_party(patron).await?;
+        self.party_repository.create_with_unique_email(&entity).await.map(|_| ())
+    }
+
+    async fn import_patrons(&self, csv: &str) -> LibraryResult<BatchResult<ImportedPatronDto>> {
+        let mut res = BatchResult::new();
+        let mut emails = HashSet::new();
+        let mut lines = vec![];
+        let mut entities = vec![];
+        for row in parse_patrons_csv(csv)? {
+            let entity = match row.patron {
+                Ok(patron) => self.to_validated_party(&patron).await,
+                Err(err) => Err(err),
+            };
+            let entity = match entity {
+                Ok(entity) => entity,
+                Err(err) => {
+                    res.failed(row.line.to_string().as_str(), &err);
+                    continue;
+                }
+            };
+            if !emails.insert(entity.normalized_email.to_string()) {
+                res.failed(row.line.to_string().as_str(), &LibraryError::duplicate_key(
+                    format!("email {} is repeated in the import", entity.email).as_str()));
+                continue;
+            }
+            // a registered email would fail the batch of the row, so it is reported before writing
+            if !self.party_repository.find_by_email(entity.normalized_email.as_str()).await?.is_empty() {
+                res.failed(row.line.to_string().as_str(), &LibraryError::duplicate_key(
+                    format!("email {} is already registered", entity.email).as_str()));
+                continue;
+            }
+            lines.push(row.line);
+            entities.push(entity);
+        }
+        for (lines, entities) in lines.chunks(MAX_BATCH_ITEMS).zip(entities.chunks(MAX_BATCH_ITEMS)) {
+            if self.party_repository.create_all_with_unique_emails(entities).await.is_ok() {
+                for (line, entity) in lines.iter().zip(entities) {
+                    res.succeeded(ImportedPatronDto::new(*line, entity.party_id.as_str(), entity.email.as_str()));
+                }
+                continue;
+            }
+            // emails registered since they were checked fail the whole batch, its rows are created one by one so
+            // that only those rows are reported
+            for (line, entity) in lines.iter().zip(entities) {
+                match self.party_repository.create_with_unique_email(entity).await {
+                    Ok(_) => res.succeeded(ImportedPatronDto::new(*line, entity.party_id.as_str(), entity.email.as_str())),
+                    Err(err) => res.failed(line.to_string().as_str(), &err),
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    async fn register_patron(&self, patron: &PatronDto) -> LibraryResult<PatronDto> {
+        if patron.email.is_empty() || !patron.email.contains('@') {
+            return Err(LibraryError::validation(
+                format!("invalid email {}", patron.email).as_str(), Some("400".to_string())));
+        }
+        let mut entity = self.to_validated_party(patron).await?;
+        // self-registered patrons cannot grant themselves roles or carry over counters
+        entity.group_roles = vec![];
+        entity.num_holds = 0;
+        entity.num_overdue = 0;
+        entity.num_no_shows = 0;
+        entity.account_status = AccountStatus::Pending;
+        entity.status_reason = "email is not verified".to_string();
+        let _ = self.party_repository.create_with_unique_email(&entity).await?;
+
+        let expires_at = Utc::now() + Duration::hours(self.verification_token_hours);
+        let token = build_verification_token(
+            self.verification_secret.as_str(), entity.party_id.as_str(), expires_at.timestamp());
+        let _ = self.notification_service.notify(
+            entity.party_id.as_str(), "Verify your library account",
+            format!("Use verification token {} to activate your account before {}.", token, expires_at).as_str()).await?;
+        self.find_patron_by_id(&PatronId::new(entity.party_id.as_str())).await
+    }
+
+    async fn verify_patron(&self, token: &str) -> LibraryResult<PatronDto> {
+        let (id, expires_at) = parse_verification_token(self.verification_secret.as_str(), token)?;
+        if expires_at < Utc::now().timestamp() {
+            return Err(LibraryError::validation("verification token is expired", Some("400".to_string())));
+        }
+        let mut patron = self.party_repository.get(id.as_str()).await?;
+        match patron.account_status {
+            AccountStatus::Pending => {
+                patron.account_status = AccountStatus::Active;
+                patron.status_reason = "".to_string();
+                let _ = self.party_repository.update(&patron).await?;
+            }
+            // verifying again is a no-op
+            AccountStatus::Active => {}
+            status => {
+                return Err(LibraryError::not_granted(format!("account of patron {} is {}",
+                                                             id, status).as_str(), Some("403".to_string())));
+            }
+        }
+        self.find_patron_by_id(&PatronId::new(id.as_str())).await
+    }
+
+    async fn remove_patron(&self, id: &PatronId) -> LibraryResult<()> {
+        let mut patron = self.party_repository.get(id.as_str()).await?;
+        // removing again would extend the retention
+        if patron.account_status == AccountStatus::Deleted {
+            return Ok(());
+        }
+        patron.account_status = AccountStatus::Deleted;
+        patron.status_reason = "removed".to_string();
+        self.party_repository.update(&patron).await.map(|_| ())
     }
 
-    async fn remove_patron(&self, id: &str) -> LibraryResult<()> {
-        self.party_repository.delete(id).await.map(|_| ())
+    async fn restore_patron(&self, id: &PatronId) -> LibraryResult<PatronDto> {
+        let mut patron = self.party_repository.get(id.as_str()).await?;
+        if patron.account_status != AccountStatus::Deleted {
+            return Err(LibraryError::validation(format!("patron {} is not deleted", id).as_str(),
+                                                Some("400".to_string())));
+        }
+        patron.account_status = AccountStatus::Active;
+        patron.status_reason = "".to_string();
+        let _ = self.party_repository.update(&patron).await?;
+        self.find_patron_by_id(id).await
     }
 
     async fn update_patron(&self, patron: &PatronDto) -> LibraryResult<()> {
-        self.party_repository.update(&PartyEntity::from(patron)).await.map(|_| ())
+        let mut entity = self.to_validated_party(patron).await?;
+        // account status can only be changed by librarians
+        let existing = self.party_repository.get(patron.patron_id.as_str()).await?;
+        // updates would restart the retention of removed patrons
+        if existing.account_status == AccountStatus::Deleted {
+            return Err(LibraryError::not_found(format!("patron {} is deleted", patron.patron_id).as_str()));
+        }
+        entity.account_status = existing.account_status;
+        entity.status_reason = existing.status_reason;
+        let _ = self.party_repository.change_email(&entity, existing.normalized_email.as_str()).await?;
+        self.party_repository.update(&entity).await.map(|_| ())
     }
 
-    async fn find_patron_by_id(&self, id: &str) -> LibraryResult<PatronDto> {
-        self.party_repository.get(id).await.map(|p| PatronDto::from(&p))
+    async fn find_patron_in_good_standing(&self, id: &PatronId) -> LibraryResult<PatronDto> {
+        let mut patron = self.party_repository.get(id.as_str()).await?;
+        if patron.account_status == AccountStatus::Active && patron.num_overdue > self.max_overdue {
+            patron.account_status = AccountStatus::Suspended;
+            patron.status_reason = format!("{} overdue items exceed limit of {}", patron.num_overdue, self.max_overdue);
+            let _ = self.party_repository.update(&patron).await?;
+        }
+        if patron.account_status != AccountStatus::Active {
+            return Err(LibraryError::not_granted(format!("account of patron {} is {}",
+                                                         id, patron.account_status).as_str(), Some("403".to_string())));
+        }
+        Ok(PatronDto::from(&patron))
     }
+
+    async fn set_account_status(&self, id: &PatronId, changed_by: &PatronId,
+                                status: AccountStatus, reason: &str) -> LibraryResult<PatronDto> {
+        let librarian = self.find_patron_by_id(changed_by).await?;
+        if !librarian.is_librarian() && !librarian.is_admin() {
+            return Err(LibraryError::not_granted(format!("patron {} cannot change account status",
+                                                         changed_by).as_str(), Some("403".to_string())));
+        }
+        let mut patron = self.party_repository.get(id.as_str()).await?;
+        if patron.account_status != status || patron.status_reason != reason {
+            patron.account_status = status;
+            patron.status_reason = reason.to_string();
+            let _ = self.party_repository.update(&patron).await?;
+        }
+        self.find_patron_by_id(id).await
+    }
+
+    async fn set_reading_history(&self, id: &PatronId, enabled: bool) -> LibraryResult<PatronDto> {
+        let mut patron = self.party_repository.get(id.as_str()).await?;
+        if patron.reading_history_enabled != enabled {
+            patron.reading_history_enabled = enabled;
+            let _ = self.party_repository.update(&patron).await?;
+        }
+        if !enabled {
+            let _ = self.history_repository.delete_by_patron(id.as_str()).await?;
+        }
+        self.find_patron_by_id(id).await
+    }
+
+    async fn purge_deleted(&self, page_size: usize) -> LibraryResult<usize> {
+        let before = Utc::now().naive_utc() - Duration::days(self.retention_days);
+        let mut purged = 0;
+        let mut page: Option<String> = None;
+        loop {
+            let res = self.party_repository.find_deleted(before, page.as_deref(), page_size).await?;
+            for patron in &res.records {
+                let _ = self.history_repository.delete_by_patron(patron.party_id.as_str()).await?;
+                let _ = self.party_repository.delete(patron.party_id.as_str()).await?;
+                self.party_repository.release_email(patron.party_id.as_str(), patron.normalized_email.as_str()).await?;
+                purged += 1;
+            }
+            page = res.next_page;
+            if page.is_none() {
+                break;
+            }
+        }
+        Ok(purged)
+    }
+}
+
+#[async_trait]
+impl PatronQueryService for PatronServiceImpl {
+    async fn find_patron_by_id(&self, id: &PatronId) -> LibraryResult<PatronDto> {
+        self.query_service.find_patron_by_id(id).await
+    }
+
     async fn find_patron_by_email(&self, email: &str) -> LibraryResult<Vec<PatronDto>> {
-        let res = self.party_repository.query(
-            &HashMap::from([("email".to_string(), email.to_string()),
-                ("kind".to_string(), PartyKind::Patron.to_string())]), None, 100).await?;
-        Ok(res.records.iter().map(PatronDto::from).collect())
+        self.query_service.find_patron_by_email(email).await
+    }
+
+    async fn reading_history(&self, id: &PatronId,
+                             page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<ReadingHistoryDto>> {
+        self.query_service.reading_history(id, page, page_size).await
+    }
+
+    async fn recommendations(&self, id: &PatronId, limit: usize) -> LibraryResult<Vec<RelatedBookDto>> {
+        self.query_service.recommendations(id, limit).await
+    }
+}
+
+// builds token of patron-id, expiration epoch and HMAC-SHA256 signature of both
+pub(crate) fn build_verification_token(secret: &str, id: &str, expires_at: i64) -> String {
+    let payload = format!("{}.{}", id, expires_at);
+    let signature = sign_hex(secret, payload.as_str());
+    format!("{}.{}", payload, signature)
+}
+
+// returns patron-id and expiration epoch of a token with valid signature
+fn parse_verification_token(secret: &str, token: &str) -> LibraryResult<(String, i64)> {
+    let invalid = || LibraryError::not_granted("invalid verification token", Some("403".to_string()));
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != 3 {
+        return Err(invalid());
+    }
+    let expires_at: i64 = parts[1].parse().map_err(|_| invalid())?;
+    if !verify_hex(secret, format!("{}.{}", parts[0], parts[1]).as_str(), parts[2]) {
+        return Err(invalid());
+    }
+    Ok((parts[0].to_string(), expires_at))
+}
+
+impl From<&ReadingHistoryEntity> for ReadingHistoryDto {
+    fn from(other: &ReadingHistoryEntity) -> Self {
+        Self {
+            checkout_id: other.history_id.to_string(),
+            book_id: other.book_id.to_string(),
+            checkout_at: other.checkout_at,
+            returned_at: other.returned_at,
+        }
     }
 }
 
 impl From<&PartyEntity> for PatronDto {
     fn from(other: &PartyEntity) -> Self {
         let mut patron = Self {
-            patron_id: other.party_id.to_string(),
+            patron_id: PatronId::new(other.party_id.as_str()),
             version: other.version,
             first_name: other.first_name.to_string(),
             last_name: other.last_name.to_string(),
@@ -56,6 +348,10 @@ impl From<&PartyEntity> for PatronDto {
             group_roles: other.group_roles.iter().map(|r| Role::from(r.to_string())).collect(),
             num_holds: other.num_holds,
             num_overdue: other.num_overdue,
+            num_no_shows: other.num_no_shows,
+            reading_history_enabled: other.reading_history_enabled,
+            account_status: other.account_status,
+            status_reason: other.status_reason.to_string(),
             home_phone: other.home_phone.clone(),
             cell_phone: other.cell_phone.clone(),
             work_phone: other.work_phone.clone(),
@@ -64,6 +360,8 @@ impl From<&PartyEntity> for PatronDto {
             zip_code: None,
             state: None,
             country: None,
+            latitude: None,
+            longitude: None,
             created_at: other.created_at,
             updated_at: other.updated_at,
         };
@@ -73,6 +371,8 @@ impl From<&PartyEntity> for PatronDto {
             patron.zip_code = Some(address.zip_code.to_string());
             patron.state = Some(address.state.to_string());
             patron.country = Some(address.country.to_string());
+            patron.latitude = address.latitude;
+            patron.longitude = address.longitude;
         }
         patron
     }
@@ -87,10 +387,19 @@ impl From<&PatronDto> for PartyEntity {
             first_name: other.first_name.to_string(),
             last_name: other.last_name.to_string(),
             email: other.email.to_string(),
+            normalized_email: normalize_email(other.email.as_str(), false),
             under_13: other.under_13,
             group_roles: other.group_roles.iter().map(|r| r.to_string()).collect(),
             num_holds: other.num_holds,
             num_overdue: other.num_overdue,
+            num_no_shows: other.num_no_shows,
+            reading_history_enabled: other.reading_history_enabled,
+            organization_name: "".to_string(),
+            opening_hours: vec![],
+            closures: vec![],
+            active: true,
+            account_status: other.account_status,
+            status_reason: other.status_reason.to_string(),
             home_phone: other.home_phone.clone(),
             cell_phone: other.cell_phone.clone(),
             work_phone: other.work_phone.clone(),
@@ -106,6 +415,8 @@ impl From<&PatronDto> for PartyEntity {
                 zip_code: zip_code.to_string(),
                 state: state.to_string(),
                 country: country.to_string(),
+                latitude: other.latitude,
+                longitude: other.longitude,
                 created_at: other.created_at,
                 updated_at: other.updated_at,
             });
@@ -117,37 +428,102 @@ impl From<&PatronDto> for PartyEntity {
 
 #[cfg(test)]
 mod tests {
-    use async_once::AsyncOnce;
-    use lazy_static::lazy_static;
+    use chrono::Utc;
     use crate::core::domain::Configuration;
+    use crate::core::library::{AccountStatus, LibraryError, Role};
     use crate::core::repository::RepositoryStore;
     use crate::patrons::domain::PatronService;
+    use crate::patrons::domain::service::build_verification_token;
     use crate::patrons::dto::PatronDto;
     use crate::patrons::factory;
-    use crate::utils::ddb::{build_db_client, delete_table};
+    use crate::utils::testing::test_store;
 
-    lazy_static! {
-        static ref SUT_SVC: AsyncOnce<Box<dyn PatronService>> = AsyncOnce::new(async {
-                let client = build_db_client(RepositoryStore::LocalDynamoDB).await;
-                let _ = delete_table(&client, "parties").await;
-                factory::create_patron_service(&Configuration::new("test"), RepositoryStore::LocalDynamoDB).await
-            });
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn PatronService> {
+        factory::create_patron_service(&Configuration::new("test"), store).await
     }
 
     #[tokio::test]
     async fn test_should_add_patron() {
-        let patron_svc = SUT_SVC.get().await.clone();
+        let store = test_store(module_path!(), "test_should_add_patron");
+        let patron_svc = sut_svc(store).await;
 
         let patron = PatronDto::new("email");
         let _ = patron_svc.add_patron(&patron).await.expect("should add parton");
 
-        let loaded = patron_svc.find_patron_by_id(patron.patron_id.as_str()).await.expect("should return patron");
+        let loaded = patron_svc.find_patron_by_id(&patron.patron_id).await.expect("should return patron");
         assert_eq!(patron.patron_id, loaded.patron_id);
     }
 
+    #[tokio::test]
+    async fn test_should_not_add_patron_with_duplicate_email() {
+        let store = test_store(module_path!(), "test_should_not_add_patron_with_duplicate_email");
+        let patron_svc = sut_svc(store).await;
+
+        let patron = PatronDto::new("unique@example.com");
+        let _ = patron_svc.add_patron(&patron).await.expect("should add patron");
+        let res = patron_svc.add_patron(&PatronDto::new("Unique@example.com")).await;
+        assert!(matches!(res, Err(LibraryError::DuplicateKey { .. })));
+        assert_eq!(1, patron_svc.find_patron_by_email(patron.email.as_str()).await.expect("should find patron").len());
+
+        // email stays reserved for removed patrons until they are purged
+        let _ = patron_svc.remove_patron(&patron.patron_id).await.expect("should remove patron");
+        let res = patron_svc.add_patron(&PatronDto::new("unique@example.com")).await;
+        assert!(matches!(res, Err(LibraryError::DuplicateKey { .. })));
+        let mut config = Configuration::new("test");
+        config.patron_retention_days = 0;
+        let purge_svc = factory::create_patron_service(&config, store).await;
+        assert!(purge_svc.purge_deleted(100).await.expect("should purge patrons") >= 1);
+        let _ = patron_svc.add_patron(&PatronDto::new("unique@example.com")).await.expect("should add patron");
+    }
+
+    #[tokio::test]
+    async fn test_should_not_update_patron_to_duplicate_email() {
+        let store = test_store(module_path!(), "test_should_not_update_patron_to_duplicate_email");
+        let patron_svc = sut_svc(store).await;
+
+        let first = PatronDto::new("first@example.com");
+        let _ = patron_svc.add_patron(&first).await.expect("should add patron");
+        let mut second = PatronDto::new("second@example.com");
+        let _ = patron_svc.add_patron(&second).await.expect("should add patron");
+
+        second.email = first.email.to_string();
+        let res = patron_svc.update_patron(&second).await;
+        assert!(matches!(res, Err(LibraryError::DuplicateKey { .. })));
+
+        // old email is released when the email changes
+        second.email = "third@example.com".to_string();
+        let _ = patron_svc.update_patron(&second).await.expect("should update patron");
+        let _ = patron_svc.add_patron(&PatronDto::new("second@example.com")).await.expect("should add patron");
+    }
+
+    #[tokio::test]
+    async fn test_should_normalize_patron_address() {
+        let store = test_store(module_path!(), "test_should_normalize_patron_address");
+        let patron_svc = sut_svc(store).await;
+
+        let mut patron = PatronDto::new("address@example.com");
+        patron.street_address = Some(" 100  Main St. ".to_string());
+        patron.city = Some("Seattle".to_string());
+        patron.zip_code = Some("98101".to_string());
+        patron.state = Some("wa".to_string());
+        patron.country = Some("us".to_string());
+        let _ = patron_svc.add_patron(&patron).await.expect("should add patron");
+
+        let loaded = patron_svc.find_patron_by_id(&patron.patron_id).await.expect("should return patron");
+        assert_eq!(Some("100 Main St.".to_string()), loaded.street_address);
+        assert_eq!(Some("WA".to_string()), loaded.state);
+        assert_eq!(Some("US".to_string()), loaded.country);
+
+        // incomplete addresses are rejected on update
+        patron.city = Some("".to_string());
+        let res = patron_svc.update_patron(&patron).await;
+        assert!(matches!(res, Err(LibraryError::Validation { .. })));
+    }
+
     #[tokio::test]
     async fn test_should_update_patron() {
-        let patron_svc = SUT_SVC.get().await.clone();
+        let store = test_store(module_path!(), "test_should_update_patron");
+        let patron_svc = sut_svc(store).await;
 
         let mut patron = PatronDto::new("email");
         let _ = patron_svc.add_patron(&patron).await.expect("should add patron");
@@ -156,7 +532,7 @@ mod tests {
         patron.first_name = "new_first".to_string();
         let _ = patron_svc.update_patron(&patron).await.expect("should update patron");
 
-        let loaded = patron_svc.find_patron_by_id(patron.patron_id.as_str()).await.expect("should return patron");
+        let loaded = patron_svc.find_patron_by_id(&patron.patron_id).await.expect("should return patron");
         assert_eq!(patron.email, loaded.email);
         assert_eq!(patron.first_name, loaded.first_name);
     }
@@ -164,7 +540,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_should_find_by_email() {
-        let patron_svc = SUT_SVC.get().await.clone();
+        let store = test_store(module_path!(), "test_should_find_by_email");
+        let patron_svc = sut_svc(store).await;
 
         let patron = PatronDto::new("email.xyz");
         let _ = patron_svc.add_patron(&patron).await.expect("should add patron");
@@ -172,16 +549,160 @@ mod tests {
         assert_eq!(1, res.len());
     }
 
+    #[tokio::test]
+    async fn test_should_find_by_email_regardless_of_case() {
+        let store = test_store(module_path!(), "test_should_find_by_email_regardless_of_case");
+        let patron_svc = sut_svc(store).await;
+
+        let patron = PatronDto::new("Reader@Example.com");
+        let _ = patron_svc.add_patron(&patron).await.expect("should add patron");
+        let res = patron_svc.find_patron_by_email(" reader@example.COM").await.expect("should return patron");
+        assert_eq!(1, res.len());
+        assert_eq!("Reader@Example.com", res[0].email.as_str());
+        // plus tags are kept unless configured otherwise
+        let res = patron_svc.find_patron_by_email("reader+library@example.com").await.expect("should return patrons");
+        assert_eq!(0, res.len());
+    }
+
+    #[tokio::test]
+    async fn test_should_strip_plus_tags_when_configured() {
+        let store = test_store(module_path!(), "test_should_strip_plus_tags_when_configured");
+        let mut config = Configuration::new("test");
+        config.strip_email_plus_tags = true;
+        let patron_svc = factory::create_patron_service(&config, store).await;
+
+        let patron = PatronDto::new("jane+library@example.com");
+        let _ = patron_svc.add_patron(&patron).await.expect("should add patron");
+        let res = patron_svc.find_patron_by_email("Jane@example.com").await.expect("should return patron");
+        assert_eq!(1, res.len());
+        let res = patron_svc.add_patron(&PatronDto::new("jane+books@example.com")).await;
+        assert!(matches!(res, Err(LibraryError::DuplicateKey { .. })));
+    }
+
     #[tokio::test]
     async fn test_should_remove_patron() {
-        let patron_svc = SUT_SVC.get().await.clone();
+        let store = test_store(module_path!(), "test_should_remove_patron");
+        let patron_svc = sut_svc(store).await;
 
         let patron = PatronDto::new("email");
         let _ = patron_svc.add_patron(&patron).await.expect("should add patron");
 
-        let _ = patron_svc.remove_patron(patron.patron_id.as_str()).await.expect("should remove patron");
+        let _ = patron_svc.remove_patron(&patron.patron_id).await.expect("should remove patron");
+
+        let loaded = patron_svc.find_patron_by_id(&patron.patron_id).await.expect("should keep removed patron");
+        assert_eq!(AccountStatus::Deleted, loaded.account_status);
+        assert!(patron_svc.find_patron_in_good_standing(&patron.patron_id).await.is_err());
+        // retention of removed patrons has not ended
+        let _ = patron_svc.purge_deleted(100).await.expect("should purge patrons");
+        assert!(patron_svc.find_patron_by_id(&patron.patron_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_should_restore_removed_patron() {
+        let store = test_store(module_path!(), "test_should_restore_removed_patron");
+        let patron_svc = sut_svc(store).await;
+
+        let patron = PatronDto::new("restore@example.com");
+        let _ = patron_svc.add_patron(&patron).await.expect("should add patron");
+        let res = patron_svc.restore_patron(&patron.patron_id).await;
+        assert!(matches!(res, Err(LibraryError::Validation { .. })));
+
+        let _ = patron_svc.remove_patron(&patron.patron_id).await.expect("should remove patron");
+        let restored = patron_svc.restore_patron(&patron.patron_id).await.expect("should restore patron");
+        assert_eq!(AccountStatus::Active, restored.account_status);
+        assert_eq!("restore@example.com", restored.email);
+    }
+
+    #[tokio::test]
+    async fn test_should_opt_in_and_out_reading_history() {
+        let store = test_store(module_path!(), "test_should_opt_in_and_out_reading_history");
+        let patron_svc = sut_svc(store).await;
+
+        let patron = PatronDto::new("reader@example.com");
+        let _ = patron_svc.add_patron(&patron).await.expect("should add patron");
+        assert!(patron_svc.reading_history(&patron.patron_id, None, 10).await.is_err());
+        let recommended = patron_svc.recommendations(&patron.patron_id, 10).await.expect("should recommend");
+        assert_eq!(0, recommended.len());
+
+        let loaded = patron_svc.set_reading_history(&patron.patron_id, true).await.expect("should opt in");
+        assert!(loaded.reading_history_enabled);
+        let res = patron_svc.reading_history(&patron.patron_id, None, 10).await.expect("should return history");
+        assert_eq!(0, res.records.len());
+
+        let loaded = patron_svc.set_reading_history(&patron.patron_id, false).await.expect("should opt out");
+        assert!(!loaded.reading_history_enabled);
+    }
+
+    #[tokio::test]
+    async fn test_should_change_account_status() {
+        let store = test_store(module_path!(), "test_should_change_account_status");
+        let patron_svc = sut_svc(store).await;
+
+        let patron = PatronDto::new("suspended@example.com");
+        let _ = patron_svc.add_patron(&patron).await.expect("should add patron");
+        let mut librarian = PatronDto::new("status_librarian@example.com");
+        librarian.group_roles = vec![Role::Librarian];
+        let _ = patron_svc.add_patron(&librarian).await.expect("should add librarian");
+
+        // regular patrons cannot change account status
+        assert!(patron_svc.set_account_status(&patron.patron_id, &patron.patron_id,
+                                              AccountStatus::Active, "").await.is_err());
+        let loaded = patron_svc.set_account_status(&patron.patron_id, &librarian.patron_id,
+                                                   AccountStatus::Banned, "damaged books").await.expect("should ban");
+        assert_eq!(AccountStatus::Banned, loaded.account_status);
+        assert_eq!("damaged books", loaded.status_reason.as_str());
+        assert!(patron_svc.find_patron_in_good_standing(&patron.patron_id).await.is_err());
+
+        // updating profile should not reinstate the account
+        let mut reinstated = loaded.clone();
+        reinstated.account_status = AccountStatus::Active;
+        let _ = patron_svc.update_patron(&reinstated).await.expect("should update patron");
+        assert!(patron_svc.find_patron_in_good_standing(&patron.patron_id).await.is_err());
+
+        let _ = patron_svc.set_account_status(&patron.patron_id, &librarian.patron_id,
+                                              AccountStatus::Active, "").await.expect("should reinstate");
+        let _ = patron_svc.find_patron_in_good_standing(&patron.patron_id).await.expect("should be in good standing");
+    }
+
+    #[tokio::test]
+    async fn test_should_suspend_patron_with_overdue_items() {
+        let store = test_store(module_path!(), "test_should_suspend_patron_with_overdue_items");
+        let patron_svc = sut_svc(store).await;
+
+        let mut patron = PatronDto::new("overdue@example.com");
+        patron.num_overdue = Configuration::new("test").max_overdue + 1;
+        let _ = patron_svc.add_patron(&patron).await.expect("should add patron");
+        assert!(patron_svc.find_patron_in_good_standing(&patron.patron_id).await.is_err());
+        let loaded = patron_svc.find_patron_by_id(&patron.patron_id).await.expect("should return patron");
+        assert_eq!(AccountStatus::Suspended, loaded.account_status);
+    }
+
+    #[tokio::test]
+    async fn test_should_register_and_verify_patron() {
+        let store = test_store(module_path!(), "test_should_register_and_verify_patron");
+        let patron_svc = sut_svc(store).await;
+        let config = Configuration::new("test");
+
+        let mut patron = PatronDto::new("register@example.com");
+        patron.group_roles = vec![Role::Admin];
+        let registered = patron_svc.register_patron(&patron).await.expect("should register patron");
+        assert_eq!(AccountStatus::Pending, registered.account_status);
+        assert!(registered.group_roles.is_empty());
+        // unverified patrons cannot hold or checkout
+        assert!(patron_svc.find_patron_in_good_standing(&registered.patron_id).await.is_err());
+        // same email cannot be registered twice
+        let res = patron_svc.register_patron(&PatronDto::new("register@example.com")).await;
+        assert!(matches!(res, Err(LibraryError::DuplicateKey { .. })));
+
+        let expires_at = Utc::now().timestamp() + 3600;
+        let forged = build_verification_token("wrong secret", registered.patron_id.as_str(), expires_at);
+        assert!(patron_svc.verify_patron(forged.as_str()).await.is_err());
+        let expired = build_verification_token(config.verification_secret.as_str(), registered.patron_id.as_str(), expires_at - 7200);
+        assert!(patron_svc.verify_patron(expired.as_str()).await.is_err());
 
-        let loaded = patron_svc.find_patron_by_id(patron.patron_id.as_str()).await;
-        assert!(loaded.is_err());
+        let token = build_verification_token(config.verification_secret.as_str(), registered.patron_id.as_str(), expires_at);
+        let verified = patron_svc.verify_patron(token.as_str()).await.expect("should verify patron");
+        assert_eq!(AccountStatus::Active, verified.account_status);
+        let _ = patron_svc.find_patron_in_good_standing(&registered.patron_id).await.expect("should be in good standing");
     }
 }
@@ -1,15 +1,16 @@
 use chrono::{NaiveDateTime, Utc};
-use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use crate::core::domain::Identifiable;
-use crate::core::library::Role;
+use crate::core::ids::PatronId;
+use crate::core::library::{AccountStatus, LibraryError, LibraryResult, Role};
 use crate::patrons::Patron;
+use crate::utils::date::serializer;
 
 
 // Patron abstracts library member.
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub(crate) struct PatronDto {
-    pub patron_id: String,
+    pub patron_id: PatronId,
     pub version: i64,
     pub first_name: String,
     pub last_name: String,
@@ -18,6 +19,14 @@ pub(crate) struct PatronDto {
     pub group_roles: Vec<Role>,
     pub num_holds: i64,
     pub num_overdue: i64,
+    #[serde(default)]
+    pub num_no_shows: i64,
+    #[serde(default)]
+    pub reading_history_enabled: bool,
+    #[serde(default = "default_account_status")]
+    pub account_status: AccountStatus,
+    #[serde(default)]
+    pub status_reason: String,
     pub home_phone: Option<String>,
     pub cell_phone: Option<String>,
     pub work_phone: Option<String>,
@@ -26,22 +35,82 @@ pub(crate) struct PatronDto {
     pub zip_code: Option<String>,
     pub state: Option<String>,
     pub country: Option<String>,
+    #[serde(default)]
+    pub latitude: Option<f64>,
+    #[serde(default)]
+    pub longitude: Option<f64>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
 
 impl PatronDto {
+    pub(crate) fn builder() -> PatronDtoBuilder {
+        PatronDtoBuilder::default()
+    }
+
+    // test fixture of an active patron with the email
+    #[cfg(test)]
     pub(crate) fn new(email: &str) -> Self {
-        Self {
-            patron_id: Uuid::new_v4().to_string(),
+        PatronDto::builder().email(email).build().expect("should build patron")
+    }
+}
+
+// PatronDtoBuilder builds a patron from the required email, a new id is generated unless the id of an existing
+// patron is given
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PatronDtoBuilder {
+    patron_id: Option<String>,
+    email: String,
+    first_name: String,
+    last_name: String,
+    under_13: bool,
+}
+
+impl PatronDtoBuilder {
+    pub(crate) fn patron_id(mut self, patron_id: &str) -> Self {
+        self.patron_id = Some(patron_id.to_string());
+        self
+    }
+
+    pub(crate) fn email(mut self, email: &str) -> Self {
+        self.email = email.trim().to_string();
+        self
+    }
+
+    pub(crate) fn first_name(mut self, first_name: &str) -> Self {
+        self.first_name = first_name.trim().to_string();
+        self
+    }
+
+    pub(crate) fn last_name(mut self, last_name: &str) -> Self {
+        self.last_name = last_name.trim().to_string();
+        self
+    }
+
+    pub(crate) fn under_13(mut self, under_13: bool) -> Self {
+        self.under_13 = under_13;
+        self
+    }
+
+    pub(crate) fn build(self) -> LibraryResult<PatronDto> {
+        if self.email.is_empty() {
+            return Err(LibraryError::validation("email is required", Some("400".to_string())));
+        }
+        let now = Utc::now().naive_utc();
+        Ok(PatronDto {
+            patron_id: self.patron_id.map(PatronId::from).unwrap_or_else(PatronId::generate),
             version: 0,
-            first_name: "".to_string(),
-            last_name: "".to_string(),
-            email: email.to_string(),
-            under_13: false,
+            first_name: self.first_name,
+            last_name: self.last_name,
+            email: self.email,
+            under_13: self.under_13,
             group_roles: vec![],
             num_holds: 0,
             num_overdue: 0,
+            num_no_shows: 0,
+            reading_history_enabled: false,
+            account_status: AccountStatus::Active,
+            status_reason: String::new(),
             home_phone: None,
             cell_phone: None,
             work_phone: None,
@@ -50,12 +119,47 @@ impl PatronDto {
             zip_code: None,
             state: None,
             country: None,
-            created_at: Utc::now().naive_utc(),
-            updated_at: Utc::now().naive_utc(),
+            latitude: None,
+            longitude: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+}
+
+// ImportedPatronDto reports a patron created by an import along with the line of its row in the CSV
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct ImportedPatronDto {
+    pub line: usize,
+    pub patron_id: String,
+    pub email: String,
+}
+
+impl ImportedPatronDto {
+    pub fn new(line: usize, patron_id: &str, email: &str) -> Self {
+        Self {
+            line,
+            patron_id: patron_id.to_string(),
+            email: email.to_string(),
         }
     }
 }
 
+fn default_account_status() -> AccountStatus {
+    AccountStatus::Active
+}
+
+// ReadingHistoryDto is a book returned by patron who opted in for reading history
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct ReadingHistoryDto {
+    pub checkout_id: String,
+    pub book_id: String,
+    #[serde(with = "serializer")]
+    pub checkout_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub returned_at: NaiveDateTime,
+}
+
 impl Identifiable for PatronDto {
     fn id(&self) -> String {
         self.patron_id.to_string()
@@ -109,6 +213,18 @@ mod tests {
         assert!(!patron.is_librarian());
     }
 
+    #[tokio::test]
+    async fn test_should_build_patron_with_builder() {
+        let patron = PatronDto::builder().email(" jane@org.cc ").first_name("Jane").last_name("Doe")
+            .build().expect("should build patron");
+        assert_eq!("jane@org.cc", patron.email.as_str());
+        assert_eq!("Jane", patron.first_name.as_str());
+        assert!(patron.is_regular());
+        assert!(PatronDto::builder().first_name("Jane").build().is_err());
+        let existing = PatronDto::builder().patron_id("patron1").email("jane@org.cc").build().expect("should build patron");
+        assert_eq!("patron1", existing.patron_id.as_str());
+    }
+
     #[tokio::test]
     async fn test_should_format_roles() {
         let roles = vec![
@@ -1,10 +1,26 @@
+use crate::catalog::factory::create_catalog_query_service;
 use crate::core::domain::Configuration;
 use crate::parties::factory;
 use crate::core::repository::RepositoryStore;
-use crate::patrons::domain::PatronService;
+use crate::gateway::factory::create_address_validator;
+use crate::notifications::factory::create_notification_service;
+use crate::patrons::domain::{PatronQueryService, PatronService};
+use crate::patrons::domain::query::PatronQueryServiceImpl;
 use crate::patrons::domain::service::PatronServiceImpl;
+use crate::projector::factory::create_reading_history_repository;
+
+pub(crate) async fn create_patron_query_service(config: &Configuration, store: RepositoryStore) -> Box<dyn PatronQueryService> {
+    let party_repo = factory::create_party_repository(store).await;
+    let history_repo = create_reading_history_repository(store).await;
+    let catalog_svc = create_catalog_query_service(config, store).await;
+    Box::new(PatronQueryServiceImpl::new(config, party_repo, history_repo, catalog_svc))
+}
 
 pub(crate) async fn create_patron_service(config: &Configuration, store: RepositoryStore) -> Box<dyn PatronService> {
     let party_repo = factory::create_party_repository(store).await;
-    Box::new(PatronServiceImpl::new(config, party_repo))
+    let history_repo = create_reading_history_repository(store).await;
+    let notification_svc = create_notification_service(store).await;
+    let query_svc = create_patron_query_service(config, store).await;
+    let address_validator = create_address_validator(config);
+    Box::new(PatronServiceImpl::new(config, party_repo, history_repo, notification_svc, query_svc, address_validator))
 }
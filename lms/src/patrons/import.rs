@@ -0,0 +1,148 @@
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::patrons::dto::PatronDto;
+
+// imports are limited so that validating and writing all rows fits into a single request
+pub(crate) const MAX_IMPORT_ROWS: usize = 1000;
+
+// PatronImportRow is a data row of the CSV with the line it starts on, rows that cannot be read as a patron carry
+// the error that is reported for their line
+#[derive(Debug)]
+pub(crate) struct PatronImportRow {
+    pub line: usize,
+    pub patron: LibraryResult<PatronDto>,
+}
+
+// reads patrons from CSV with a header line, email is the only required column and the optional columns are
+// first_name, last_name, under_13, home_phone, cell_phone, work_phone, street_address, city, zip_code, state and
+// country, other columns of exports of the previous system are ignored
+pub(crate) fn parse_patrons_csv(csv: &str) -> LibraryResult<Vec<PatronImportRow>> {
+    let mut records = parse_records(csv.trim_start_matches('\u{feff}'))?.into_iter();
+    let header = records.next().map(|(_, header)| header)
+        .ok_or_else(|| LibraryError::validation("patron import has no header line", Some("400".to_string())))?
+        .iter().map(|column| column.trim().to_lowercase()).collect::<Vec<String>>();
+    if !header.iter().any(|column| column == "email") {
+        return Err(LibraryError::validation("patron import must have an email column", Some("400".to_string())));
+    }
+    let rows = records.map(|(line, fields)| PatronImportRow { line, patron: to_patron(&header, &fields) })
+        .collect::<Vec<PatronImportRow>>();
+    if rows.is_empty() || rows.len() > MAX_IMPORT_ROWS {
+        return Err(LibraryError::validation(format!("patron import must have between 1 and {} rows but had {}",
+                                                    MAX_IMPORT_ROWS, rows.len()).as_str(), Some("400".to_string())));
+    }
+    Ok(rows)
+}
+
+fn to_patron(header: &[String], fields: &[String]) -> LibraryResult<PatronDto> {
+    if fields.len() != header.len() {
+        return Err(LibraryError::validation(format!("row has {} fields but the header has {}",
+                                                    fields.len(), header.len()).as_str(), Some("400".to_string())));
+    }
+    let value = |name: &str| header.iter().position(|column| column == name)
+        .map(|i| fields[i].trim()).filter(|value| !value.is_empty());
+    let email = value("email").unwrap_or_default();
+    if !email.contains('@') {
+        return Err(LibraryError::validation(format!("invalid email {}", email).as_str(), Some("400".to_string())));
+    }
+    let under_13 = match value("under_13").map(|value| value.to_lowercase()).as_deref() {
+        None | Some("false") | Some("no") | Some("n") | Some("0") => false,
+        Some("true") | Some("yes") | Some("y") | Some("1") => true,
+        Some(other) => return Err(LibraryError::validation(format!("invalid under_13 {}", other).as_str(),
+                                                           Some("400".to_string()))),
+    };
+    let mut patron = PatronDto::builder()
+        .email(email)
+        .first_name(value("first_name").unwrap_or_default())
+        .last_name(value("last_name").unwrap_or_default())
+        .under_13(under_13)
+        .build()?;
+    patron.home_phone = value("home_phone").map(String::from);
+    patron.cell_phone = value("cell_phone").map(String::from);
+    patron.work_phone = value("work_phone").map(String::from);
+    patron.street_address = value("street_address").map(String::from);
+    patron.city = value("city").map(String::from);
+    patron.zip_code = value("zip_code").map(String::from);
+    patron.state = value("state").map(String::from);
+    patron.country = value("country").map(String::from);
+    Ok(patron)
+}
+
+// splits CSV into records with the line they start on, quoted fields may contain commas, doubled quotes and line
+// breaks, and blank lines are skipped
+fn parse_records(csv: &str) -> LibraryResult<Vec<(usize, Vec<String>)>> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut start = 1;
+    let mut chars = csv.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => quoted = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push((start, std::mem::take(&mut record)));
+                line += 1;
+                start = line;
+            }
+            _ => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(LibraryError::validation(format!("quoted field of line {} is not closed", start).as_str(),
+                                            Some("400".to_string())));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push((start, record));
+    }
+    Ok(records.into_iter().filter(|(_, record)| !(record.len() == 1 && record[0].trim().is_empty())).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::library::LibraryError;
+    use crate::patrons::import::parse_patrons_csv;
+
+    #[tokio::test]
+    async fn test_should_parse_patrons_csv() {
+        let csv = "\u{feff}Email,First_Name,Last_Name,under_13,legacy_id\r\n\
+                   ada@example.com,Ada,\"Lovelace, Countess\",no,17\r\n\
+                   \r\n\
+                   bob@example.com,\"Bob \"\"The\"\"\nBuilder\",Smith,maybe,18\n\
+                   not-an-email,Carl,Jones,,19\n\
+                   dan@example.com,Dan\n";
+        let rows = parse_patrons_csv(csv).expect("should parse csv");
+        assert_eq!(vec![2, 4, 6, 7], rows.iter().map(|row| row.line).collect::<Vec<usize>>());
+
+        let ada = rows[0].patron.as_ref().expect("should read patron");
+        assert_eq!("ada@example.com", ada.email.as_str());
+        assert_eq!("Lovelace, Countess", ada.last_name.as_str());
+        assert!(!ada.under_13);
+        assert!(matches!(rows[1].patron, Err(LibraryError::Validation { .. })));
+        assert!(matches!(rows[2].patron, Err(LibraryError::Validation { .. })));
+        assert!(matches!(rows[3].patron, Err(LibraryError::Validation { .. })));
+
+        assert!(parse_patrons_csv("first_name\nAda\n").is_err());
+        assert!(parse_patrons_csv("email\n").is_err());
+        assert!(parse_patrons_csv("email\n\"ada@example.com\n").is_err());
+    }
+}
@@ -0,0 +1,6 @@
+pub mod command;
+pub mod domain;
+pub mod dto;
+pub mod factory;
+pub mod repository;
+pub mod controller;
@@ -0,0 +1,8 @@
+pub mod add_program_cmd;
+pub mod cancel_registration_cmd;
+pub mod find_programs_cmd;
+pub mod get_program_cmd;
+pub mod register_program_cmd;
+pub mod remove_program_cmd;
+pub mod send_reminders_cmd;
+pub mod update_program_cmd;
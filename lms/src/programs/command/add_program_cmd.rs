@@ -0,0 +1,111 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::programs::domain::ProgramService;
+use crate::programs::dto::ProgramDto;
+
+pub(crate) struct AddProgramCommand {
+    program_service: Box<dyn ProgramService>,
+}
+
+impl AddProgramCommand {
+    pub(crate) fn new(program_service: Box<dyn ProgramService>) -> Self {
+        Self {
+            program_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AddProgramCommandRequest {
+    pub(crate) title: String,
+    #[serde(default)]
+    pub(crate) description: String,
+    pub(crate) capacity: i64,
+    pub(crate) created_by: String,
+    pub(crate) starts_at: NaiveDateTime,
+    pub(crate) ends_at: NaiveDateTime,
+}
+
+impl AddProgramCommandRequest {
+    pub fn new(title: &str, capacity: i64, created_by: &str, starts_at: NaiveDateTime, ends_at: NaiveDateTime) -> Self {
+        Self {
+            title: title.to_string(),
+            description: "".to_string(),
+            capacity,
+            created_by: created_by.to_string(),
+            starts_at,
+            ends_at,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AddProgramCommandResponse {
+    pub program: ProgramDto,
+}
+
+impl AddProgramCommandResponse {
+    pub fn new(program: ProgramDto) -> Self {
+        Self {
+            program,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<AddProgramCommandRequest, AddProgramCommandResponse> for AddProgramCommand {
+    async fn execute(&self, req: AddProgramCommandRequest) -> Result<AddProgramCommandResponse, CommandError> {
+        let mut program = ProgramDto::new(req.title.as_str(), req.capacity, req.starts_at, req.ends_at);
+        program.description = req.description;
+        program.created_by = req.created_by;
+        self.program_service.add_program(&program)
+            .await.map_err(CommandError::from).map(AddProgramCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::programs::command::add_program_cmd::{AddProgramCommand, AddProgramCommandRequest};
+    use crate::programs::domain::ProgramService;
+    use crate::programs::dto::ProgramDto;
+    use crate::programs::factory::create_program_service;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn ProgramService> {
+        create_program_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> AddProgramCommand {
+        let svc = create_program_service(&Configuration::new("test"), store).await;
+        AddProgramCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_add_program() {
+        let store = test_store(module_path!(), "test_should_run_add_program");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "add_program@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let starts_at = Utc::now().naive_utc() + Duration::hours(24);
+        let mut program = ProgramDto::new("Program", 10, starts_at, starts_at + Duration::hours(1));
+        program.created_by = librarian.party_id.to_string();
+        let program = svc.add_program(&program).await.expect("should add program");
+        let res = sut_cmd.execute(AddProgramCommandRequest::new("Book club", 12, librarian.party_id.as_str(),
+                                                               starts_at, starts_at + Duration::hours(2)))
+            .await.expect("should add program");
+        assert_eq!("Book club", res.program.title.as_str());
+        assert_ne!(program.program_id, res.program.program_id);
+    }
+}
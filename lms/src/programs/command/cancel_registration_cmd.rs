@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::programs::domain::ProgramService;
+use crate::programs::dto::RegistrationDto;
+
+pub(crate) struct CancelRegistrationCommand {
+    program_service: Box<dyn ProgramService>,
+}
+
+impl CancelRegistrationCommand {
+    pub(crate) fn new(program_service: Box<dyn ProgramService>) -> Self {
+        Self {
+            program_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CancelRegistrationCommandRequest {
+    pub(crate) program_id: String,
+    pub(crate) patron_id: String,
+}
+
+impl CancelRegistrationCommandRequest {
+    pub fn new(program_id: &str, patron_id: &str) -> Self {
+        Self {
+            program_id: program_id.to_string(),
+            patron_id: patron_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CancelRegistrationCommandResponse {
+    pub registration: RegistrationDto,
+}
+
+impl CancelRegistrationCommandResponse {
+    pub fn new(registration: RegistrationDto) -> Self {
+        Self {
+            registration,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<CancelRegistrationCommandRequest, CancelRegistrationCommandResponse> for CancelRegistrationCommand {
+    async fn execute(&self, req: CancelRegistrationCommandRequest) -> Result<CancelRegistrationCommandResponse, CommandError> {
+        self.program_service.cancel_registration(req.program_id.as_str(), req.patron_id.as_str())
+            .await.map_err(CommandError::from).map(CancelRegistrationCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, RegistrationStatus, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::programs::command::cancel_registration_cmd::{CancelRegistrationCommand, CancelRegistrationCommandRequest};
+    use crate::programs::domain::ProgramService;
+    use crate::programs::dto::ProgramDto;
+    use crate::programs::factory::create_program_service;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn ProgramService> {
+        create_program_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> CancelRegistrationCommand {
+        let svc = create_program_service(&Configuration::new("test"), store).await;
+        CancelRegistrationCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_cancel_registration() {
+        let store = test_store(module_path!(), "test_should_run_cancel_registration");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "cancel_registration@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let starts_at = Utc::now().naive_utc() + Duration::hours(24);
+        let mut program = ProgramDto::new("Chess club", 10, starts_at, starts_at + Duration::hours(1));
+        program.created_by = librarian.party_id.to_string();
+        let program = svc.add_program(&program).await.expect("should add program");
+        let _ = svc.register(program.program_id.as_str(), librarian.party_id.as_str()).await.expect("should register");
+        let res = sut_cmd.execute(CancelRegistrationCommandRequest::new(program.program_id.as_str(), librarian.party_id.as_str()))
+            .await.expect("should cancel registration");
+        assert_eq!(RegistrationStatus::Canceled, res.registration.registration_status);
+    }
+}
@@ -0,0 +1,111 @@
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::programs::domain::ProgramQueryService;
+use crate::programs::dto::ProgramDto;
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+const DEFAULT_PROGRAM_DAYS: i64 = 30;
+
+pub(crate) struct FindProgramsCommand {
+    program_service: Box<dyn ProgramQueryService>,
+}
+
+impl FindProgramsCommand {
+    pub(crate) fn new(program_service: Box<dyn ProgramQueryService>) -> Self {
+        Self {
+            program_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindProgramsCommandRequest {
+    pub(crate) from: Option<NaiveDateTime>,
+    pub(crate) to: Option<NaiveDateTime>,
+    pub(crate) page: Option<String>,
+    pub(crate) page_size: Option<usize>,
+}
+
+impl FindProgramsCommandRequest {
+    pub fn new(from: NaiveDateTime, to: NaiveDateTime) -> Self {
+        Self {
+            from: Some(from),
+            to: Some(to),
+            page: None,
+            page_size: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindProgramsCommandResponse {
+    pub programs: Vec<ProgramDto>,
+    pub next_page: Option<String>,
+}
+
+impl FindProgramsCommandResponse {
+    pub fn new(programs: Vec<ProgramDto>, next_page: Option<String>) -> Self {
+        Self {
+            programs,
+            next_page,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<FindProgramsCommandRequest, FindProgramsCommandResponse> for FindProgramsCommand {
+    async fn execute(&self, req: FindProgramsCommandRequest) -> Result<FindProgramsCommandResponse, CommandError> {
+        // programs default to the ones starting within next month
+        let from = req.from.unwrap_or_else(|| Utc::now().naive_utc());
+        let to = req.to.unwrap_or(from + Duration::days(DEFAULT_PROGRAM_DAYS));
+        self.program_service.find_programs(from, to, req.page.as_deref(),
+                                           req.page_size.unwrap_or(DEFAULT_PAGE_SIZE))
+            .await.map_err(CommandError::from).map(|res| FindProgramsCommandResponse::new(res.records, res.next_page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::programs::command::find_programs_cmd::{FindProgramsCommand, FindProgramsCommandRequest};
+    use crate::programs::domain::ProgramService;
+    use crate::programs::dto::ProgramDto;
+    use crate::programs::factory::{create_program_service, create_program_query_service};
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn ProgramService> {
+        create_program_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> FindProgramsCommand {
+        let svc = create_program_query_service(&Configuration::new("test"), store).await;
+        FindProgramsCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_find_programs() {
+        let store = test_store(module_path!(), "test_should_run_find_programs");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "find_programs@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let starts_at = Utc::now().naive_utc() + Duration::days(3);
+        let mut program = ProgramDto::new("Poetry reading", 10, starts_at, starts_at + Duration::hours(1));
+        program.created_by = librarian.party_id.to_string();
+        let program = svc.add_program(&program).await.expect("should add program");
+
+        let res = sut_cmd.execute(FindProgramsCommandRequest::new(starts_at - Duration::hours(1), starts_at + Duration::hours(1)))
+            .await.expect("should find programs");
+        assert!(res.programs.iter().any(|p| p.program_id == program.program_id));
+    }
+}
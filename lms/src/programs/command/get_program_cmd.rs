@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::programs::domain::ProgramQueryService;
+use crate::programs::dto::{ProgramDto, RegistrationDto};
+
+pub(crate) struct GetProgramCommand {
+    program_service: Box<dyn ProgramQueryService>,
+}
+
+impl GetProgramCommand {
+    pub(crate) fn new(program_service: Box<dyn ProgramQueryService>) -> Self {
+        Self {
+            program_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetProgramCommandRequest {
+    pub(crate) program_id: String,
+}
+
+impl GetProgramCommandRequest {
+    pub fn new(program_id: &str) -> Self {
+        Self {
+            program_id: program_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetProgramCommandResponse {
+    pub program: ProgramDto,
+    pub registrations: Vec<RegistrationDto>,
+}
+
+impl GetProgramCommandResponse {
+    pub fn new(program: ProgramDto, registrations: Vec<RegistrationDto>) -> Self {
+        Self {
+            program,
+            registrations,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<GetProgramCommandRequest, GetProgramCommandResponse> for GetProgramCommand {
+    async fn execute(&self, req: GetProgramCommandRequest) -> Result<GetProgramCommandResponse, CommandError> {
+        let program = self.program_service.find_program_by_id(req.program_id.as_str())
+            .await.map_err(CommandError::from)?;
+        self.program_service.find_registrations(req.program_id.as_str())
+            .await.map_err(CommandError::from).map(|registrations| GetProgramCommandResponse::new(program, registrations))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::programs::command::get_program_cmd::{GetProgramCommand, GetProgramCommandRequest};
+    use crate::programs::domain::ProgramService;
+    use crate::programs::dto::ProgramDto;
+    use crate::programs::factory::{create_program_service, create_program_query_service};
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn ProgramService> {
+        create_program_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> GetProgramCommand {
+        let svc = create_program_query_service(&Configuration::new("test"), store).await;
+        GetProgramCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_get_program() {
+        let store = test_store(module_path!(), "test_should_run_get_program");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "get_program@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let starts_at = Utc::now().naive_utc() + Duration::hours(24);
+        let mut program = ProgramDto::new("Story time", 10, starts_at, starts_at + Duration::hours(1));
+        program.created_by = librarian.party_id.to_string();
+        let program = svc.add_program(&program).await.expect("should add program");
+        let _ = svc.register(program.program_id.as_str(), librarian.party_id.as_str()).await.expect("should register");
+        let res = sut_cmd.execute(GetProgramCommandRequest::new(program.program_id.as_str()))
+            .await.expect("should get program");
+        assert_eq!(program.program_id, res.program.program_id);
+        assert_eq!(1, res.registrations.len());
+    }
+}
@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::programs::domain::ProgramService;
+use crate::programs::dto::RegistrationDto;
+
+pub(crate) struct RegisterProgramCommand {
+    program_service: Box<dyn ProgramService>,
+}
+
+impl RegisterProgramCommand {
+    pub(crate) fn new(program_service: Box<dyn ProgramService>) -> Self {
+        Self {
+            program_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RegisterProgramCommandRequest {
+    #[serde(default)]
+    pub(crate) program_id: String,
+    pub(crate) patron_id: String,
+}
+
+impl RegisterProgramCommandRequest {
+    pub fn new(program_id: &str, patron_id: &str) -> Self {
+        Self {
+            program_id: program_id.to_string(),
+            patron_id: patron_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RegisterProgramCommandResponse {
+    pub registration: RegistrationDto,
+}
+
+impl RegisterProgramCommandResponse {
+    pub fn new(registration: RegistrationDto) -> Self {
+        Self {
+            registration,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<RegisterProgramCommandRequest, RegisterProgramCommandResponse> for RegisterProgramCommand {
+    async fn execute(&self, req: RegisterProgramCommandRequest) -> Result<RegisterProgramCommandResponse, CommandError> {
+        self.program_service.register(req.program_id.as_str(), req.patron_id.as_str())
+            .await.map_err(CommandError::from).map(RegisterProgramCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, RegistrationStatus, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::programs::command::register_program_cmd::{RegisterProgramCommand, RegisterProgramCommandRequest};
+    use crate::programs::domain::ProgramService;
+    use crate::programs::dto::ProgramDto;
+    use crate::programs::factory::create_program_service;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn ProgramService> {
+        create_program_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> RegisterProgramCommand {
+        let svc = create_program_service(&Configuration::new("test"), store).await;
+        RegisterProgramCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_register_program() {
+        let store = test_store(module_path!(), "test_should_run_register_program");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "register_program@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let starts_at = Utc::now().naive_utc() + Duration::hours(24);
+        let mut program = ProgramDto::new("Craft hour", 10, starts_at, starts_at + Duration::hours(1));
+        program.created_by = librarian.party_id.to_string();
+        let program = svc.add_program(&program).await.expect("should add program");
+        let res = sut_cmd.execute(RegisterProgramCommandRequest::new(program.program_id.as_str(), librarian.party_id.as_str()))
+            .await.expect("should register");
+        assert_eq!(RegistrationStatus::Registered, res.registration.registration_status);
+    }
+}
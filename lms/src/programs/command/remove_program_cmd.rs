@@ -0,0 +1,92 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::programs::domain::ProgramService;
+
+pub(crate) struct RemoveProgramCommand {
+    program_service: Box<dyn ProgramService>,
+}
+
+impl RemoveProgramCommand {
+    pub(crate) fn new(program_service: Box<dyn ProgramService>) -> Self {
+        Self {
+            program_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RemoveProgramCommandRequest {
+    #[serde(default)]
+    pub(crate) program_id: String,
+    pub(crate) removed_by: String,
+}
+
+impl RemoveProgramCommandRequest {
+    pub fn new(program_id: &str, removed_by: &str) -> Self {
+        Self {
+            program_id: program_id.to_string(),
+            removed_by: removed_by.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RemoveProgramCommandResponse {}
+
+impl RemoveProgramCommandResponse {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl Command<RemoveProgramCommandRequest, RemoveProgramCommandResponse> for RemoveProgramCommand {
+    async fn execute(&self, req: RemoveProgramCommandRequest) -> Result<RemoveProgramCommandResponse, CommandError> {
+        self.program_service.remove_program(req.program_id.as_str(), req.removed_by.as_str())
+            .await.map_err(CommandError::from).map(|_| RemoveProgramCommandResponse::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::programs::command::remove_program_cmd::{RemoveProgramCommand, RemoveProgramCommandRequest};
+    use crate::programs::domain::ProgramService;
+    use crate::programs::dto::ProgramDto;
+    use crate::programs::factory::create_program_service;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn ProgramService> {
+        create_program_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> RemoveProgramCommand {
+        let svc = create_program_service(&Configuration::new("test"), store).await;
+        RemoveProgramCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_remove_program() {
+        let store = test_store(module_path!(), "test_should_run_remove_program");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "remove_program@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let starts_at = Utc::now().naive_utc() + Duration::hours(24);
+        let mut program = ProgramDto::new("Movie night", 10, starts_at, starts_at + Duration::hours(1));
+        program.created_by = librarian.party_id.to_string();
+        let program = svc.add_program(&program).await.expect("should add program");
+        let _ = sut_cmd.execute(RemoveProgramCommandRequest::new(program.program_id.as_str(), librarian.party_id.as_str()))
+            .await.expect("should remove program");
+        assert!(svc.find_program_by_id(program.program_id.as_str()).await.is_err());
+    }
+}
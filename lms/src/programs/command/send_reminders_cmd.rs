@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::programs::domain::ProgramService;
+
+const DEFAULT_REMINDER_HOURS: i64 = 24;
+
+pub(crate) struct SendRemindersCommand {
+    program_service: Box<dyn ProgramService>,
+}
+
+impl SendRemindersCommand {
+    pub(crate) fn new(program_service: Box<dyn ProgramService>) -> Self {
+        Self {
+            program_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SendRemindersCommandRequest {
+    pub(crate) within_hours: Option<i64>,
+}
+
+impl SendRemindersCommandRequest {
+    pub fn new(within_hours: i64) -> Self {
+        Self {
+            within_hours: Some(within_hours),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SendRemindersCommandResponse {
+    pub reminders: usize,
+}
+
+impl SendRemindersCommandResponse {
+    pub fn new(reminders: usize) -> Self {
+        Self {
+            reminders,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<SendRemindersCommandRequest, SendRemindersCommandResponse> for SendRemindersCommand {
+    async fn execute(&self, req: SendRemindersCommandRequest) -> Result<SendRemindersCommandResponse, CommandError> {
+        self.program_service.send_reminders(req.within_hours.unwrap_or(DEFAULT_REMINDER_HOURS))
+            .await.map_err(CommandError::from).map(SendRemindersCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::programs::command::send_reminders_cmd::{SendRemindersCommand, SendRemindersCommandRequest};
+    use crate::programs::domain::ProgramService;
+    use crate::programs::dto::ProgramDto;
+    use crate::programs::factory::create_program_service;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn ProgramService> {
+        create_program_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> SendRemindersCommand {
+        let svc = create_program_service(&Configuration::new("test"), store).await;
+        SendRemindersCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_send_reminders() {
+        let store = test_store(module_path!(), "test_should_run_send_reminders");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "send_reminders@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let starts_at = Utc::now().naive_utc() + Duration::hours(1);
+        let mut program = ProgramDto::new("Author talk", 10, starts_at, starts_at + Duration::hours(1));
+        program.created_by = librarian.party_id.to_string();
+        let program = svc.add_program(&program).await.expect("should add program");
+        let _ = svc.register(program.program_id.as_str(), librarian.party_id.as_str()).await.expect("should register");
+        let res = sut_cmd.execute(SendRemindersCommandRequest::new(2)).await.expect("should send reminders");
+        assert!(res.reminders >= 1);
+    }
+}
@@ -0,0 +1,116 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::programs::domain::ProgramService;
+use crate::programs::dto::ProgramDto;
+
+pub(crate) struct UpdateProgramCommand {
+    program_service: Box<dyn ProgramService>,
+}
+
+impl UpdateProgramCommand {
+    pub(crate) fn new(program_service: Box<dyn ProgramService>) -> Self {
+        Self {
+            program_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct UpdateProgramCommandRequest {
+    #[serde(default)]
+    pub(crate) program_id: String,
+    pub(crate) updated_by: String,
+    pub(crate) title: String,
+    #[serde(default)]
+    pub(crate) description: String,
+    pub(crate) capacity: i64,
+    pub(crate) starts_at: NaiveDateTime,
+    pub(crate) ends_at: NaiveDateTime,
+}
+
+impl UpdateProgramCommandRequest {
+    pub fn new(program: &ProgramDto, updated_by: &str) -> Self {
+        Self {
+            program_id: program.program_id.to_string(),
+            updated_by: updated_by.to_string(),
+            title: program.title.to_string(),
+            description: program.description.to_string(),
+            capacity: program.capacity,
+            starts_at: program.starts_at,
+            ends_at: program.ends_at,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct UpdateProgramCommandResponse {
+    pub program: ProgramDto,
+}
+
+impl UpdateProgramCommandResponse {
+    pub fn new(program: ProgramDto) -> Self {
+        Self {
+            program,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<UpdateProgramCommandRequest, UpdateProgramCommandResponse> for UpdateProgramCommand {
+    async fn execute(&self, req: UpdateProgramCommandRequest) -> Result<UpdateProgramCommandResponse, CommandError> {
+        let mut program = ProgramDto::new(req.title.as_str(), req.capacity, req.starts_at, req.ends_at);
+        program.program_id = req.program_id;
+        program.description = req.description;
+        self.program_service.update_program(req.updated_by.as_str(), &program)
+            .await.map_err(CommandError::from).map(UpdateProgramCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::programs::command::update_program_cmd::{UpdateProgramCommand, UpdateProgramCommandRequest};
+    use crate::programs::domain::ProgramService;
+    use crate::programs::dto::ProgramDto;
+    use crate::programs::factory::create_program_service;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn ProgramService> {
+        create_program_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> UpdateProgramCommand {
+        let svc = create_program_service(&Configuration::new("test"), store).await;
+        UpdateProgramCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_update_program() {
+        let store = test_store(module_path!(), "test_should_run_update_program");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "update_program@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let starts_at = Utc::now().naive_utc() + Duration::hours(24);
+        let mut program = ProgramDto::new("Coding workshop", 10, starts_at, starts_at + Duration::hours(1));
+        program.created_by = librarian.party_id.to_string();
+        let program = svc.add_program(&program).await.expect("should add program");
+        let mut changed = program.clone();
+        changed.capacity = 20;
+        changed.description = "bring your own laptop".to_string();
+        let res = sut_cmd.execute(UpdateProgramCommandRequest::new(&changed, librarian.party_id.as_str()))
+            .await.expect("should update program");
+        assert_eq!(20, res.program.capacity);
+        assert_eq!("bring your own laptop", res.program.description.as_str());
+    }
+}
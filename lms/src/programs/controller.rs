@@ -0,0 +1,123 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::{delete, get, post},
+    Router,
+};
+use serde_json::{Value};
+use crate::core::controller::{AppState, command_bus, json_to_server_error, with_common_layers, ServerError};
+use crate::programs::command::add_program_cmd::{AddProgramCommand, AddProgramCommandRequest, AddProgramCommandResponse};
+use crate::programs::command::cancel_registration_cmd::{CancelRegistrationCommand, CancelRegistrationCommandRequest, CancelRegistrationCommandResponse};
+use crate::programs::command::find_programs_cmd::{FindProgramsCommand, FindProgramsCommandRequest, FindProgramsCommandResponse};
+use crate::programs::command::get_program_cmd::{GetProgramCommand, GetProgramCommandRequest, GetProgramCommandResponse};
+use crate::programs::command::register_program_cmd::{RegisterProgramCommand, RegisterProgramCommandRequest, RegisterProgramCommandResponse};
+use crate::programs::command::remove_program_cmd::{RemoveProgramCommand, RemoveProgramCommandRequest, RemoveProgramCommandResponse};
+use crate::programs::command::send_reminders_cmd::{SendRemindersCommand, SendRemindersCommandRequest, SendRemindersCommandResponse};
+use crate::programs::command::update_program_cmd::{UpdateProgramCommand, UpdateProgramCommandRequest, UpdateProgramCommandResponse};
+use crate::programs::domain::{ProgramQueryService, ProgramService};
+use crate::programs::factory;
+use crate::utils::ddb::{build_db_client, create_table};
+
+async fn build_service(state: AppState) -> Box<dyn ProgramService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_table(&client, state.store.table_name("programs").as_str(), "program_id", "branch_id", "starts_at").await;
+    let _ = create_table(&client, state.store.table_name("program_registrations").as_str(), "registration_id", "program_id", "created_at").await;
+    let _ = create_table(&client, state.store.table_name("notifications").as_str(), "notification_id", "party_id", "created_at").await;
+    factory::create_program_service(&state.config, state.store).await
+}
+
+async fn build_query_service(state: AppState) -> Box<dyn ProgramQueryService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_table(&client, state.store.table_name("programs").as_str(), "program_id", "branch_id", "starts_at").await;
+    let _ = create_table(&client, state.store.table_name("program_registrations").as_str(), "registration_id", "program_id", "created_at").await;
+    let _ = create_table(&client, state.store.table_name("notifications").as_str(), "notification_id", "party_id", "created_at").await;
+    factory::create_program_query_service(&state.config, state.store).await
+}
+
+pub(crate) async fn add_program(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<Json<AddProgramCommandResponse>, ServerError> {
+    let req: AddProgramCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res = command_bus().register(AddProgramCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn find_programs(
+    State(state): State<AppState>,
+    Query(req): Query<FindProgramsCommandRequest>) -> Result<Json<FindProgramsCommandResponse>, ServerError> {
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(FindProgramsCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn find_program_by_id(
+    State(state): State<AppState>,
+    Path(program_id): Path<String>) -> Result<Json<GetProgramCommandResponse>, ServerError> {
+    let req = GetProgramCommandRequest { program_id };
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(GetProgramCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn update_program(
+    State(state): State<AppState>,
+    Path(program_id): Path<String>,
+    json: Json<Value>) -> Result<Json<UpdateProgramCommandResponse>, ServerError> {
+    let mut req: UpdateProgramCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.program_id = program_id;
+    let svc = build_service(state).await;
+    let res = command_bus().register(UpdateProgramCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// registered and waitlisted patrons are notified when a program is removed
+pub(crate) async fn remove_program(
+    State(state): State<AppState>,
+    Path(program_id): Path<String>,
+    Query(mut req): Query<RemoveProgramCommandRequest>) -> Result<Json<RemoveProgramCommandResponse>, ServerError> {
+    req.program_id = program_id;
+    let svc = build_service(state).await;
+    let res = command_bus().register(RemoveProgramCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn register_program(
+    State(state): State<AppState>,
+    Path(program_id): Path<String>,
+    json: Json<Value>) -> Result<Json<RegisterProgramCommandResponse>, ServerError> {
+    let mut req: RegisterProgramCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.program_id = program_id;
+    let svc = build_service(state).await;
+    let res = command_bus().register(RegisterProgramCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn cancel_registration(
+    State(state): State<AppState>,
+    Path((program_id, patron_id)): Path<(String, String)>) -> Result<Json<CancelRegistrationCommandResponse>, ServerError> {
+    let req = CancelRegistrationCommandRequest { program_id, patron_id };
+    let svc = build_service(state).await;
+    let res = command_bus().register(CancelRegistrationCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// reminders are meant to be triggered by a scheduled job
+pub(crate) async fn send_reminders(
+    State(state): State<AppState>,
+    Query(req): Query<SendRemindersCommandRequest>) -> Result<Json<SendRemindersCommandResponse>, ServerError> {
+    let svc = build_service(state).await;
+    let res = command_bus().register(SendRemindersCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/programs", post(add_program).get(find_programs))
+        .route("/programs/reminders", post(send_reminders))
+        .route("/programs/:id",
+               get(find_program_by_id).put(update_program).delete(remove_program))
+        .route("/programs/:id/registrations", post(register_program))
+        .route("/programs/:id/registrations/:patron_id", delete(cancel_registration));
+    with_common_layers(router, state)
+}
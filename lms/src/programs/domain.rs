@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use crate::core::library::{LibraryResult, PaginatedResult};
+use crate::programs::dto::{ProgramDto, RegistrationDto};
+
+pub mod model;
+pub mod query;
+pub mod service;
+
+// read side of programs and their registrations
+#[async_trait]
+pub(crate) trait ProgramQueryService: Sync + Send {
+    async fn find_program_by_id(&self, program_id: &str) -> LibraryResult<ProgramDto>;
+    async fn find_programs(&self, from: NaiveDateTime, to: NaiveDateTime,
+                           page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<ProgramDto>>;
+    async fn find_registrations(&self, program_id: &str) -> LibraryResult<Vec<RegistrationDto>>;
+}
+
+#[async_trait]
+pub(crate) trait ProgramService: ProgramQueryService {
+    // only librarians can create, update or delete programs
+    async fn add_program(&self, program: &ProgramDto) -> LibraryResult<ProgramDto>;
+    async fn update_program(&self, updated_by: &str, program: &ProgramDto) -> LibraryResult<ProgramDto>;
+    async fn remove_program(&self, program_id: &str, removed_by: &str) -> LibraryResult<()>;
+    // registers the patron or adds the patron to the waitlist when the program is full
+    async fn register(&self, program_id: &str, patron_id: &str) -> LibraryResult<RegistrationDto>;
+    // cancels the registration and promotes the next patron on the waitlist
+    async fn cancel_registration(&self, program_id: &str, patron_id: &str) -> LibraryResult<RegistrationDto>;
+    // notifies registered patrons of programs starting within given hours and returns number of reminders sent
+    async fn send_reminders(&self, within_hours: i64) -> LibraryResult<usize>;
+}
@@ -0,0 +1,117 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::core::domain::Identifiable;
+use crate::core::library::RegistrationStatus;
+use crate::utils::date::serializer;
+
+// ProgramEntity abstracts a library event such as story time or workshop with limited seats
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct ProgramEntity {
+    pub program_id: String,
+    pub version: i64,
+    pub branch_id: String,
+    pub title: String,
+    pub description: String,
+    pub capacity: i64,
+    pub created_by: String,
+    #[serde(with = "serializer")]
+    pub starts_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub ends_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl ProgramEntity {
+    pub fn new(title: &str, capacity: i64, starts_at: NaiveDateTime, ends_at: NaiveDateTime) -> Self {
+        Self {
+            program_id: Uuid::new_v4().to_string(),
+            version: 0,
+            branch_id: "".to_string(),
+            title: title.to_string(),
+            description: "".to_string(),
+            capacity,
+            created_by: "".to_string(),
+            starts_at,
+            ends_at,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl Identifiable for ProgramEntity {
+    fn id(&self) -> String {
+        self.program_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+// RegistrationEntity records a patron signing up for a program, registrations beyond capacity are waitlisted
+// in order of their creation
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct RegistrationEntity {
+    pub registration_id: String,
+    pub version: i64,
+    pub program_id: String,
+    pub patron_id: String,
+    pub registration_status: RegistrationStatus,
+    pub reminded_at: Option<NaiveDateTime>,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl RegistrationEntity {
+    pub fn new(program_id: &str, patron_id: &str, registration_status: RegistrationStatus) -> Self {
+        Self {
+            registration_id: Uuid::new_v4().to_string(),
+            version: 0,
+            program_id: program_id.to_string(),
+            patron_id: patron_id.to_string(),
+            registration_status,
+            reminded_at: None,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl Identifiable for RegistrationEntity {
+    fn id(&self) -> String {
+        self.registration_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::library::RegistrationStatus;
+    use crate::programs::domain::model::{ProgramEntity, RegistrationEntity};
+
+    #[tokio::test]
+    async fn test_should_build_program() {
+        let starts_at = Utc::now().naive_utc() + Duration::days(1);
+        let program = ProgramEntity::new("Story time", 20, starts_at, starts_at + Duration::hours(1));
+        assert_eq!("Story time", program.title.as_str());
+        assert_eq!(20, program.capacity);
+    }
+
+    #[tokio::test]
+    async fn test_should_build_registration() {
+        let registration = RegistrationEntity::new("program1", "patron1", RegistrationStatus::Waitlisted);
+        assert_eq!(RegistrationStatus::Waitlisted, registration.registration_status);
+        assert!(registration.reminded_at.is_none());
+    }
+}
@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use crate::core::domain::Configuration;
+use crate::core::library::{LibraryResult, PaginatedResult};
+use crate::programs::domain::ProgramQueryService;
+use crate::programs::dto::{ProgramDto, RegistrationDto};
+use crate::programs::repository::{ProgramRepository, RegistrationRepository};
+
+pub(crate) struct ProgramQueryServiceImpl {
+    branch_id: String,
+    program_repository: Box<dyn ProgramRepository>,
+    registration_repository: Box<dyn RegistrationRepository>,
+}
+
+impl ProgramQueryServiceImpl {
+    pub(crate) fn new(config: &Configuration, program_repository: Box<dyn ProgramRepository>,
+                      registration_repository: Box<dyn RegistrationRepository>) -> Self {
+        Self {
+            branch_id: config.branch_id.to_string(),
+            program_repository,
+            registration_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl ProgramQueryService for ProgramQueryServiceImpl {
+    async fn find_program_by_id(&self, program_id: &str) -> LibraryResult<ProgramDto> {
+        self.program_repository.get(program_id).await.map(|program| ProgramDto::from(&program))
+    }
+
+    async fn find_programs(&self, from: NaiveDateTime, to: NaiveDateTime,
+                           page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<ProgramDto>> {
+        let res = self.program_repository.find_by_branch(self.branch_id.as_str(), from, to, page, page_size).await?;
+        let records = res.records.iter().map(ProgramDto::from).collect();
+        Ok(PaginatedResult::new(page, page_size, res.next_page, records))
+    }
+
+    async fn find_registrations(&self, program_id: &str) -> LibraryResult<Vec<RegistrationDto>> {
+        let registrations = self.registration_repository.find_by_program(program_id).await?;
+        Ok(registrations.iter().map(RegistrationDto::from).collect())
+    }
+}
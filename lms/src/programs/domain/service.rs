@@ -0,0 +1,388 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime, Utc};
+
+use crate::core::domain::Configuration;
+use crate::core::events::DomainEvent;
+use crate::core::ids::PatronId;
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult, RegistrationStatus};
+use crate::gateway::events::EventPublisher;
+use crate::notifications::domain::NotificationService;
+use crate::patrons::domain::PatronService;
+use crate::patrons::Patron;
+use crate::programs::domain::model::{ProgramEntity, RegistrationEntity};
+use crate::programs::domain::{ProgramQueryService, ProgramService};
+use crate::programs::dto::{ProgramDto, RegistrationDto};
+use crate::programs::repository::{ProgramRepository, RegistrationRepository};
+
+const REMINDER_PAGE_SIZE: usize = 100;
+
+pub(crate) struct ProgramServiceImpl {
+    branch_id: String,
+    program_repository: Box<dyn ProgramRepository>,
+    registration_repository: Box<dyn RegistrationRepository>,
+    patron_service: Box<dyn PatronService>,
+    notification_service: Box<dyn NotificationService>,
+    events_publisher: Box<dyn EventPublisher>,
+    query_service: Box<dyn ProgramQueryService>,
+}
+
+impl ProgramServiceImpl {
+    pub(crate) fn new(config: &Configuration, program_repository: Box<dyn ProgramRepository>,
+                      registration_repository: Box<dyn RegistrationRepository>,
+                      patron_service: Box<dyn PatronService>, notification_service: Box<dyn NotificationService>,
+                      events_publisher: Box<dyn EventPublisher>,
+                      query_service: Box<dyn ProgramQueryService>) -> Self {
+        Self {
+            branch_id: config.branch_id.to_string(),
+            program_repository,
+            registration_repository,
+            patron_service,
+            notification_service,
+            events_publisher,
+            query_service,
+        }
+    }
+
+    async fn validate_librarian(&self, patron_id: &str) -> LibraryResult<()> {
+        let patron = self.patron_service.find_patron_by_id(&PatronId::new(patron_id)).await?;
+        if !patron.is_librarian() && !patron.is_admin() {
+            return Err(LibraryError::validation(format!("patron {} cannot manage programs",
+                                                        patron_id).as_str(), Some("400".to_string())));
+        }
+        Ok(())
+    }
+
+    fn validate_program(program: &ProgramDto) -> LibraryResult<()> {
+        if program.title.is_empty() || program.capacity <= 0 {
+            return Err(LibraryError::validation("program title and positive capacity are required", Some("400".to_string())));
+        }
+        if program.ends_at <= program.starts_at {
+            return Err(LibraryError::validation("program must end after it starts", Some("400".to_string())));
+        }
+        Ok(())
+    }
+
+    async fn update_registration(&self, registration: &mut RegistrationEntity, event_name: &str) -> LibraryResult<RegistrationDto> {
+        self.registration_repository.update(registration).await?;
+        registration.version += 1;
+        let dto = RegistrationDto::from(&*registration);
+        let _ = self.events_publisher.publish(&DomainEvent::updated(
+            event_name, "programs", dto.registration_id.as_str(), &HashMap::new(), &dto)?).await?;
+        Ok(dto)
+    }
+
+    // moves waitlisted patrons in order of registration into open seats of the program
+    async fn promote_waitlist(&self, program: &ProgramEntity) -> LibraryResult<()> {
+        let registrations = self.registration_repository.find_by_program(program.program_id.as_str()).await?;
+        let registered = registrations.iter()
+            .filter(|r| r.registration_status == RegistrationStatus::Registered).count() as i64;
+        let open_seats = (program.capacity - registered).max(0) as usize;
+        for mut next in registrations.into_iter()
+            .filter(|r| r.registration_status == RegistrationStatus::Waitlisted).take(open_seats) {
+            next.registration_status = RegistrationStatus::Registered;
+            let _ = self.update_registration(&mut next, "program_waitlist_promoted").await?;
+            let _ = self.notification_service.notify(
+                next.patron_id.as_str(), format!("You are registered for {}", program.title).as_str(),
+                format!("A seat opened up for {} starting at {}", program.title, program.starts_at).as_str()).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ProgramService for ProgramServiceImpl {
+    async fn add_program(&self, program: &ProgramDto) -> LibraryResult<ProgramDto> {
+        Self::validate_program(program)?;
+        if program.starts_at <= Utc::now().naive_utc() {
+            return Err(LibraryError::validation("program must start in future", Some("400".to_string())));
+        }
+        self.validate_librarian(program.created_by.as_str()).await?;
+        let mut entity = ProgramEntity::from(program);
+        entity.branch_id = self.branch_id.to_string();
+        self.program_repository.create(&entity).await?;
+        let dto = ProgramDto::from(&entity);
+        let _ = self.events_publisher.publish(&DomainEvent::added(
+            "program_added", "programs", dto.program_id.as_str(), &HashMap::new(), &dto)?).await?;
+        Ok(dto)
+    }
+
+    async fn update_program(&self, updated_by: &str, program: &ProgramDto) -> LibraryResult<ProgramDto> {
+        Self::validate_program(program)?;
+        self.validate_librarian(updated_by).await?;
+        let mut entity = self.program_repository.get(program.program_id.as_str()).await?;
+        entity.title = program.title.to_string();
+        entity.description = program.description.to_string();
+        entity.capacity = program.capacity;
+        entity.starts_at = program.starts_at;
+        entity.ends_at = program.ends_at;
+        self.program_repository.update(&entity).await?;
+        entity.version += 1;
+        // increased capacity opens seats for the waitlist
+        self.promote_waitlist(&entity).await?;
+        let dto = ProgramDto::from(&entity);
+        let _ = self.events_publisher.publish(&DomainEvent::updated(
+            "program_updated", "programs", dto.program_id.as_str(), &HashMap::new(), &dto)?).await?;
+        Ok(dto)
+    }
+
+    async fn remove_program(&self, program_id: &str, removed_by: &str) -> LibraryResult<()> {
+        self.validate_librarian(removed_by).await?;
+        let program = self.program_repository.get(program_id).await?;
+        for mut registration in self.registration_repository.find_by_program(program_id).await? {
+            if registration.registration_status == RegistrationStatus::Canceled {
+                continue;
+            }
+            registration.registration_status = RegistrationStatus::Canceled;
+            let _ = self.update_registration(&mut registration, "program_registration_canceled").await?;
+            let _ = self.notification_service.notify(
+                registration.patron_id.as_str(), format!("{} is canceled", program.title).as_str(),
+                format!("{} starting at {} has been canceled", program.title, program.starts_at).as_str()).await?;
+        }
+        self.program_repository.delete(program_id).await?;
+        let dto = ProgramDto::from(&program);
+        let _ = self.events_publisher.publish(&DomainEvent::deleted(
+            "program_removed", "programs", dto.program_id.as_str(), &HashMap::new(), &dto)?).await?;
+        Ok(())
+    }
+
+    async fn register(&self, program_id: &str, patron_id: &str) -> LibraryResult<RegistrationDto> {
+        let _ = self.patron_service.find_patron_by_id(&PatronId::new(patron_id)).await?;
+        let program = self.program_repository.get(program_id).await?;
+        if program.starts_at <= Utc::now().naive_utc() {
+            return Err(LibraryError::validation(format!("program {} has already started",
+                                                        program_id).as_str(), Some("400".to_string())));
+        }
+        let registrations = self.registration_repository.find_by_program(program_id).await?;
+        if registrations.iter().any(|r| r.patron_id == patron_id && r.registration_status != RegistrationStatus::Canceled) {
+            return Err(LibraryError::duplicate_key(format!("patron {} is already registered for {}",
+                                                           patron_id, program_id).as_str()));
+        }
+        let registered = registrations.iter()
+            .filter(|r| r.registration_status == RegistrationStatus::Registered).count() as i64;
+        let status = if registered < program.capacity { RegistrationStatus::Registered } else { RegistrationStatus::Waitlisted };
+        let registration = RegistrationEntity::new(program_id, patron_id, status);
+        self.registration_repository.create(&registration).await?;
+        let dto = RegistrationDto::from(&registration);
+        let _ = self.events_publisher.publish(&DomainEvent::added(
+            format!("program_{}", status.to_string().to_lowercase()).as_str(), "programs",
+            dto.registration_id.as_str(), &HashMap::new(), &dto)?).await?;
+        Ok(dto)
+    }
+
+    async fn cancel_registration(&self, program_id: &str, patron_id: &str) -> LibraryResult<RegistrationDto> {
+        let program = self.program_repository.get(program_id).await?;
+        let mut registration = self.registration_repository.find_by_program(program_id).await?.into_iter()
+            .find(|r| r.patron_id == patron_id && r.registration_status != RegistrationStatus::Canceled)
+            .ok_or_else(|| LibraryError::not_found(format!("registration not found for {} in {}",
+                                                           patron_id, program_id).as_str()))?;
+        let was_registered = registration.registration_status == RegistrationStatus::Registered;
+        registration.registration_status = RegistrationStatus::Canceled;
+        let dto = self.update_registration(&mut registration, "program_registration_canceled").await?;
+        if was_registered {
+            self.promote_waitlist(&program).await?;
+        }
+        Ok(dto)
+    }
+
+    async fn send_reminders(&self, within_hours: i64) -> LibraryResult<usize> {
+        if within_hours <= 0 {
+            return Err(LibraryError::validation("reminder window must be positive", Some("400".to_string())));
+        }
+        let now = Utc::now().naive_utc();
+        let mut sent = 0;
+        let mut next_page: Option<String> = None;
+        loop {
+            let res = self.program_repository.find_by_branch(
+                self.branch_id.as_str(), now, now + Duration::hours(within_hours),
+                next_page.as_deref(), REMINDER_PAGE_SIZE).await?;
+            for program in &res.records {
+                for mut registration in self.registration_repository.find_by_program(program.program_id.as_str()).await? {
+                    // reminders are sent only once so that the job can run repeatedly
+                    if registration.registration_status != RegistrationStatus::Registered || registration.reminded_at.is_some() {
+                        continue;
+                    }
+                    let _ = self.notification_service.notify(
+                        registration.patron_id.as_str(), format!("Reminder: {}", program.title).as_str(),
+                        format!("{} starts at {}", program.title, program.starts_at).as_str()).await?;
+                    registration.reminded_at = Some(Utc::now().naive_utc());
+                    let _ = self.update_registration(&mut registration, "program_reminded").await?;
+                    sent += 1;
+                }
+            }
+            next_page = res.next_page;
+            if next_page.is_none() {
+                break;
+            }
+        }
+        Ok(sent)
+    }
+}
+
+#[async_trait]
+impl ProgramQueryService for ProgramServiceImpl {
+    async fn find_program_by_id(&self, program_id: &str) -> LibraryResult<ProgramDto> {
+        self.query_service.find_program_by_id(program_id).await
+    }
+
+    async fn find_programs(&self, from: NaiveDateTime, to: NaiveDateTime,
+                           page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<ProgramDto>> {
+        self.query_service.find_programs(from, to, page, page_size).await
+    }
+
+    async fn find_registrations(&self, program_id: &str) -> LibraryResult<Vec<RegistrationDto>> {
+        self.query_service.find_registrations(program_id).await
+    }
+}
+
+impl From<&ProgramDto> for ProgramEntity {
+    fn from(other: &ProgramDto) -> ProgramEntity {
+        ProgramEntity {
+            program_id: other.program_id.to_string(),
+            version: other.version,
+            branch_id: other.branch_id.to_string(),
+            title: other.title.to_string(),
+            description: other.description.to_string(),
+            capacity: other.capacity,
+            created_by: other.created_by.to_string(),
+            starts_at: other.starts_at,
+            ends_at: other.ends_at,
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+impl From<&ProgramEntity> for ProgramDto {
+    fn from(other: &ProgramEntity) -> ProgramDto {
+        ProgramDto {
+            program_id: other.program_id.to_string(),
+            version: other.version,
+            branch_id: other.branch_id.to_string(),
+            title: other.title.to_string(),
+            description: other.description.to_string(),
+            capacity: other.capacity,
+            created_by: other.created_by.to_string(),
+            starts_at: other.starts_at,
+            ends_at: other.ends_at,
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+impl From<&RegistrationEntity> for RegistrationDto {
+    fn from(other: &RegistrationEntity) -> RegistrationDto {
+        RegistrationDto {
+            registration_id: other.registration_id.to_string(),
+            version: other.version,
+            program_id: other.program_id.to_string(),
+            patron_id: other.patron_id.to_string(),
+            registration_status: other.registration_status,
+            reminded_at: other.reminded_at,
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, RegistrationStatus, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::notifications::domain::NotificationService;
+    use crate::notifications::factory::create_notification_service;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::parties::repository::PartyRepository;
+    use crate::programs::domain::ProgramService;
+    use crate::programs::dto::ProgramDto;
+    use crate::programs::factory;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn ProgramService> {
+        factory::create_program_service(&Configuration::new("test"), store).await
+    }
+
+    async fn party_repo(store: RepositoryStore) -> Box<dyn PartyRepository> {
+        create_party_repository(store).await
+    }
+
+    async fn notification_svc(store: RepositoryStore) -> Box<dyn NotificationService> {
+        create_notification_service(store).await
+    }
+
+    async fn add_party(store: RepositoryStore, email: &str, role: Role) -> PartyEntity {
+        let mut party = PartyEntity::new(PartyKind::Patron, email);
+        party.group_roles = vec![role.to_string()];
+        let _ = party_repo(store).await.create(&party).await.expect("should create party");
+        party
+    }
+
+    fn new_program(title: &str, capacity: i64, start_hours: i64, created_by: &str) -> ProgramDto {
+        let starts_at = Utc::now().naive_utc() + Duration::hours(start_hours);
+        let mut program = ProgramDto::new(title, capacity, starts_at, starts_at + Duration::hours(1));
+        program.created_by = created_by.to_string();
+        program
+    }
+
+    #[tokio::test]
+    async fn test_should_register_waitlist_and_promote() {
+        let store = test_store(module_path!(), "test_should_register_waitlist_and_promote");
+        let program_svc = sut_svc(store).await;
+        let librarian = add_party(store, "program_librarian@example.com", Role::Librarian).await;
+        let first = add_party(store, "program_patron1@example.com", Role::Regular).await;
+        let second = add_party(store, "program_patron2@example.com", Role::Regular).await;
+        // regular patrons cannot add programs
+        assert!(program_svc.add_program(&new_program("Story time", 1, 48, first.party_id.as_str())).await.is_err());
+        let program = program_svc.add_program(&new_program("Story time", 1, 48, librarian.party_id.as_str()))
+            .await.expect("should add program");
+
+        let registration = program_svc.register(program.program_id.as_str(), first.party_id.as_str()).await.expect("should register");
+        assert_eq!(RegistrationStatus::Registered, registration.registration_status);
+        assert!(program_svc.register(program.program_id.as_str(), first.party_id.as_str()).await.is_err());
+        let waitlisted = program_svc.register(program.program_id.as_str(), second.party_id.as_str()).await.expect("should waitlist");
+        assert_eq!(RegistrationStatus::Waitlisted, waitlisted.registration_status);
+
+        let _ = program_svc.cancel_registration(program.program_id.as_str(), first.party_id.as_str()).await.expect("should cancel");
+        let registrations = program_svc.find_registrations(program.program_id.as_str()).await.expect("should find registrations");
+        let promoted = registrations.iter().find(|r| r.patron_id == second.party_id).expect("should find promoted");
+        assert_eq!(RegistrationStatus::Registered, promoted.registration_status);
+        let notifications = notification_svc(store).await.find_notifications(second.party_id.as_str(), None, 10)
+            .await.expect("should find notifications");
+        assert_eq!(1, notifications.records.len());
+    }
+
+    #[tokio::test]
+    async fn test_should_update_remove_and_remind_programs() {
+        let store = test_store(module_path!(), "test_should_update_remove_and_remind_programs");
+        let program_svc = sut_svc(store).await;
+        let librarian = add_party(store, "program_librarian2@example.com", Role::Librarian).await;
+        let patron = add_party(store, "program_patron3@example.com", Role::Regular).await;
+        let mut program = program_svc.add_program(&new_program("Workshop", 5, 2, librarian.party_id.as_str()))
+            .await.expect("should add program");
+        program.description = "Learn to code".to_string();
+        program.capacity = 10;
+        let updated = program_svc.update_program(librarian.party_id.as_str(), &program).await.expect("should update program");
+        assert_eq!(10, updated.capacity);
+        assert!(program_svc.update_program(patron.party_id.as_str(), &program).await.is_err());
+
+        let _ = program_svc.register(program.program_id.as_str(), patron.party_id.as_str()).await.expect("should register");
+        assert!(program_svc.send_reminders(0).await.is_err());
+        assert!(program_svc.send_reminders(3).await.expect("should send reminders") >= 1);
+        let registrations = program_svc.find_registrations(program.program_id.as_str()).await.expect("should find registrations");
+        assert!(registrations[0].reminded_at.is_some());
+
+        program_svc.remove_program(program.program_id.as_str(), librarian.party_id.as_str()).await.expect("should remove program");
+        assert!(program_svc.find_program_by_id(program.program_id.as_str()).await.is_err());
+        let registrations = program_svc.find_registrations(program.program_id.as_str()).await.expect("should find registrations");
+        assert_eq!(RegistrationStatus::Canceled, registrations[0].registration_status);
+        let notifications = notification_svc(store).await.find_notifications(patron.party_id.as_str(), None, 10)
+            .await.expect("should find notifications");
+        assert_eq!(2, notifications.records.len());
+    }
+}
@@ -0,0 +1,89 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use uuid::Uuid;
+use crate::core::domain::Identifiable;
+use crate::core::library::RegistrationStatus;
+use crate::utils::date::{serializer, Rfc3339};
+
+// ProgramDto abstracts data transfer object for library programs
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct ProgramDto {
+    pub program_id: String,
+    pub version: i64,
+    pub branch_id: String,
+    pub title: String,
+    pub description: String,
+    pub capacity: i64,
+    pub created_by: String,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub starts_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub ends_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl ProgramDto {
+    pub fn new(title: &str, capacity: i64, starts_at: NaiveDateTime, ends_at: NaiveDateTime) -> Self {
+        Self {
+            program_id: Uuid::new_v4().to_string(),
+            version: 0,
+            branch_id: "".to_string(),
+            title: title.to_string(),
+            description: "".to_string(),
+            capacity,
+            created_by: "".to_string(),
+            starts_at,
+            ends_at,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl Identifiable for ProgramDto {
+    fn id(&self) -> String {
+        self.program_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct RegistrationDto {
+    pub registration_id: String,
+    pub version: i64,
+    pub program_id: String,
+    pub patron_id: String,
+    pub registration_status: RegistrationStatus,
+    pub reminded_at: Option<NaiveDateTime>,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub updated_at: NaiveDateTime,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::programs::dto::ProgramDto;
+
+    #[tokio::test]
+    async fn test_should_build_program() {
+        let starts_at = Utc::now().naive_utc() + Duration::days(1);
+        let program = ProgramDto::new("Workshop", 10, starts_at, starts_at + Duration::hours(2));
+        assert_eq!("Workshop", program.title.as_str());
+        assert_eq!(10, program.capacity);
+    }
+}
@@ -0,0 +1,59 @@
+use crate::core::domain::Configuration;
+use crate::core::repository::RepositoryStore;
+use crate::gateway::factory::create_publisher;
+use crate::notifications::factory::create_notification_service;
+use crate::patrons::factory::create_patron_service;
+use crate::programs::domain::{ProgramQueryService, ProgramService};
+use crate::programs::domain::query::ProgramQueryServiceImpl;
+use crate::programs::domain::service::ProgramServiceImpl;
+use crate::programs::factory;
+use crate::programs::repository::ddb_program_repository::DDBProgramRepository;
+use crate::programs::repository::ddb_registration_repository::DDBRegistrationRepository;
+use crate::programs::repository::{ProgramRepository, RegistrationRepository};
+use crate::utils::ddb::{build_db_client, create_table};
+
+pub(crate) async fn create_program_repository(store: RepositoryStore) -> Box<dyn ProgramRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBProgramRepository::new(client, "programs", "programs_ndx"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("programs");
+            let _ = create_table(&client, table_name.as_str(), "program_id", "branch_id", "starts_at").await;
+            Box::new(DDBProgramRepository::new(client, table_name.as_str(), store.table_name("programs_ndx").as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_registration_repository(store: RepositoryStore) -> Box<dyn RegistrationRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBRegistrationRepository::new(client, "program_registrations", "program_registrations_ndx"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("program_registrations");
+            let _ = create_table(&client, table_name.as_str(), "registration_id", "program_id", "created_at").await;
+            Box::new(DDBRegistrationRepository::new(client, table_name.as_str(), store.table_name("program_registrations_ndx").as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_program_query_service(config: &Configuration, store: RepositoryStore) -> Box<dyn ProgramQueryService> {
+    let program_repo = factory::create_program_repository(store).await;
+    let registration_repo = factory::create_registration_repository(store).await;
+    Box::new(ProgramQueryServiceImpl::new(config, program_repo, registration_repo))
+}
+
+pub(crate) async fn create_program_service(config: &Configuration, store: RepositoryStore) -> Box<dyn ProgramService> {
+    let program_repo = factory::create_program_repository(store).await;
+    let registration_repo = factory::create_registration_repository(store).await;
+    let patron_svc = create_patron_service(config, store).await;
+    let notification_svc = create_notification_service(store).await;
+    let publisher = create_publisher(store.gateway_publisher()).await;
+    let query_svc = create_program_query_service(config, store).await;
+    Box::new(ProgramServiceImpl::new(config, program_repo, registration_repo, patron_svc, notification_svc, publisher, query_svc))
+}
@@ -0,0 +1,26 @@
+pub mod ddb_program_repository;
+pub mod ddb_registration_repository;
+
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use crate::core::library::{LibraryResult, PaginatedResult};
+use crate::programs::domain::model::{ProgramEntity, RegistrationEntity};
+
+#[async_trait]
+pub(crate) trait ProgramRepository: Sync + Send {
+    async fn create(&self, entity: &ProgramEntity) -> LibraryResult<usize>;
+    async fn update(&self, entity: &ProgramEntity) -> LibraryResult<usize>;
+    async fn get(&self, program_id: &str) -> LibraryResult<ProgramEntity>;
+    async fn delete(&self, program_id: &str) -> LibraryResult<usize>;
+    // returns programs of the branch starting within the time window ordered by start time
+    async fn find_by_branch(&self, branch_id: &str, from: NaiveDateTime, to: NaiveDateTime,
+                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<ProgramEntity>>;
+}
+
+#[async_trait]
+pub(crate) trait RegistrationRepository: Sync + Send {
+    async fn create(&self, entity: &RegistrationEntity) -> LibraryResult<usize>;
+    async fn update(&self, entity: &RegistrationEntity) -> LibraryResult<usize>;
+    // returns all registrations of the program ordered by registration time
+    async fn find_by_program(&self, program_id: &str) -> LibraryResult<Vec<RegistrationEntity>>;
+}
@@ -0,0 +1,196 @@
+use std::cmp;
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::{NaiveDateTime, Utc};
+
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult};
+use crate::programs::domain::model::ProgramEntity;
+use crate::programs::repository::ProgramRepository;
+use crate::utils::ddb::{from_ddb, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, string_date, to_ddb_page};
+
+#[derive(Debug)]
+pub(crate) struct DDBProgramRepository {
+    client: Client,
+    table_name: String,
+    index_name: String,
+}
+
+impl DDBProgramRepository {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl ProgramRepository for DDBProgramRepository {
+    async fn create(&self, entity: &ProgramEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        let mut item = parse_item(val)?;
+        // start time is stored in the same format as query bounds so that upcoming programs can be queried
+        item.insert("starts_at".to_string(), string_date(entity.starts_at));
+        item.insert("ends_at".to_string(), string_date(entity.ends_at));
+        item.insert("created_at".to_string(), string_date(entity.created_at));
+        item.insert("updated_at".to_string(), string_date(entity.updated_at));
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(program_id)")
+            .set_item(Some(item))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn update(&self, entity: &ProgramEntity) -> LibraryResult<usize> {
+        let now = Utc::now().naive_utc();
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .update_item()
+            .table_name(table_name)
+            .key("program_id", AttributeValue::S(entity.program_id.clone()))
+            .update_expression("SET version = :version, title = :title, description = :description, capacity = :capacity, starts_at = :starts_at, ends_at = :ends_at, updated_at = :updated_at")
+            .expression_attribute_values(":old_version", AttributeValue::N(entity.version.to_string()))
+            .expression_attribute_values(":version", AttributeValue::N((entity.version + 1).to_string()))
+            .expression_attribute_values(":title", AttributeValue::S(entity.title.to_string()))
+            .expression_attribute_values(":description", AttributeValue::S(entity.description.to_string()))
+            .expression_attribute_values(":capacity", AttributeValue::N(entity.capacity.to_string()))
+            .expression_attribute_values(":starts_at", string_date(entity.starts_at))
+            .expression_attribute_values(":ends_at", string_date(entity.ends_at))
+            .expression_attribute_values(":updated_at", string_date(now))
+            .condition_expression("attribute_exists(version) AND version = :old_version")
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn get(&self, program_id: &str) -> LibraryResult<ProgramEntity> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .query()
+            .table_name(table_name)
+            .limit(1)
+            .consistent_read(true)
+            .key_condition_expression("program_id = :program_id")
+            .expression_attribute_values(":program_id", AttributeValue::S(program_id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).and_then(|req| {
+            if let Some(map) = req.items.as_ref().and_then(|items| items.first()) {
+                return Ok(ProgramEntity::from(map));
+            }
+            Err(LibraryError::not_found(format!("program not found for {}", program_id).as_str()))
+        })
+    }
+
+    async fn delete(&self, program_id: &str) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client.delete_item()
+            .table_name(table_name)
+            .key("program_id", AttributeValue::S(program_id.to_string()))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn find_by_branch(&self, branch_id: &str, from: NaiveDateTime, to: NaiveDateTime,
+                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<ProgramEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let predicate = HashMap::from([("branch_id".to_string(), branch_id.to_string())]);
+        self.client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .limit(cmp::min(page_size, 500) as i32)
+            .consistent_read(false)
+            .set_exclusive_start_key(to_ddb_page(page, &predicate))
+            .key_condition_expression("branch_id = :branch_id AND starts_at BETWEEN :from AND :to")
+            .expression_attribute_values(":branch_id", AttributeValue::S(branch_id.to_string()))
+            .expression_attribute_values(":from", string_date(from))
+            .expression_attribute_values(":to", string_date(to))
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(ProgramEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for ProgramEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        ProgramEntity {
+            program_id: parse_string_attribute("program_id", map).unwrap_or_else(|| String::from("")),
+            version: parse_number_attribute("version", map),
+            branch_id: parse_string_attribute("branch_id", map).unwrap_or_else(|| String::from("")),
+            title: parse_string_attribute("title", map).unwrap_or_else(|| String::from("")),
+            description: parse_string_attribute("description", map).unwrap_or_else(|| String::from("")),
+            capacity: parse_number_attribute("capacity", map),
+            created_by: parse_string_attribute("created_by", map).unwrap_or_else(|| String::from("")),
+            starts_at: parse_date_attribute("starts_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            ends_at: parse_date_attribute("ends_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+    use chrono::NaiveDateTime;
+
+    use crate::core::repository::RepositoryStore;
+    use crate::programs::domain::model::ProgramEntity;
+    use crate::programs::repository::ddb_program_repository::DDBProgramRepository;
+    use crate::programs::repository::ProgramRepository;
+    use crate::utils::date::DATE_FMT;
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("programs").as_str(), "program_id", "branch_id", "starts_at").await;
+        client
+    }
+
+    fn at(day: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(format!("2023-06-{}T10:00:00", day).as_str(), DATE_FMT).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_should_create_update_find_delete_programs() {
+        let store = test_store(module_path!(), "test_should_create_update_find_delete_programs");
+        let repo = DDBProgramRepository::new(build_client(store).await, store.table_name("programs").as_str(), store.table_name("programs_ndx").as_str());
+        let mut story_time = ProgramEntity::new("Story time", 20, at("01"), at("01"));
+        story_time.branch_id = "program_branch".to_string();
+        assert_eq!(1, repo.create(&story_time).await.expect("should create program"));
+        assert!(repo.create(&story_time).await.is_err());
+        let mut workshop = ProgramEntity::new("Workshop", 10, at("15"), at("15"));
+        workshop.branch_id = "program_branch".to_string();
+        assert_eq!(1, repo.create(&workshop).await.expect("should create program"));
+
+        story_time.capacity = 25;
+        story_time.title = "Story time for toddlers".to_string();
+        assert_eq!(1, repo.update(&story_time).await.expect("should update program"));
+        // stale version should not be updated
+        assert!(repo.update(&story_time).await.is_err());
+        let loaded = repo.get(story_time.program_id.as_str()).await.expect("should get program");
+        assert_eq!(1, loaded.version);
+        assert_eq!(25, loaded.capacity);
+        assert_eq!(at("01"), loaded.starts_at);
+
+        let res = repo.find_by_branch("program_branch", at("01"), at("10"), None, 10).await.expect("should find programs");
+        assert_eq!(1, res.records.len());
+        let res = repo.find_by_branch("program_branch", at("01"), at("30"), None, 10).await.expect("should find programs");
+        assert_eq!(2, res.records.len());
+        assert_eq!("Workshop", res.records[1].title.as_str());
+
+        assert_eq!(1, repo.delete(workshop.program_id.as_str()).await.expect("should delete program"));
+        assert!(repo.get(workshop.program_id.as_str()).await.is_err());
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::core::library::{LibraryError, LibraryResult, RegistrationStatus};
+use crate::programs::domain::model::RegistrationEntity;
+use crate::programs::repository::RegistrationRepository;
+use crate::utils::ddb::{opt_string_date, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, string_date};
+
+#[derive(Debug)]
+pub(crate) struct DDBRegistrationRepository {
+    client: Client,
+    table_name: String,
+    index_name: String,
+}
+
+impl DDBRegistrationRepository {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl RegistrationRepository for DDBRegistrationRepository {
+    async fn create(&self, entity: &RegistrationEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        let mut item = parse_item(val)?;
+        // registration time is the sort key for the waitlist so it is stored in sortable format
+        item.insert("created_at".to_string(), string_date(entity.created_at));
+        item.insert("updated_at".to_string(), string_date(entity.updated_at));
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(registration_id)")
+            .set_item(Some(item))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn update(&self, entity: &RegistrationEntity) -> LibraryResult<usize> {
+        let now = Utc::now().naive_utc();
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .update_item()
+            .table_name(table_name)
+            .key("registration_id", AttributeValue::S(entity.registration_id.clone()))
+            .update_expression("SET version = :version, registration_status = :registration_status, reminded_at = :reminded_at, updated_at = :updated_at")
+            .expression_attribute_values(":old_version", AttributeValue::N(entity.version.to_string()))
+            .expression_attribute_values(":version", AttributeValue::N((entity.version + 1).to_string()))
+            .expression_attribute_values(":registration_status", AttributeValue::S(entity.registration_status.to_string()))
+            .expression_attribute_values(":reminded_at", opt_string_date(entity.reminded_at))
+            .expression_attribute_values(":updated_at", string_date(now))
+            .condition_expression("attribute_exists(version) AND version = :old_version")
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn find_by_program(&self, program_id: &str) -> LibraryResult<Vec<RegistrationEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let mut registrations = vec![];
+        let mut last_key: Option<HashMap<String, AttributeValue>> = None;
+        loop {
+            let res = self.client
+                .query()
+                .table_name(table_name)
+                .index_name(index_name)
+                .consistent_read(false)
+                .set_exclusive_start_key(last_key)
+                .key_condition_expression("program_id = :program_id")
+                .expression_attribute_values(":program_id", AttributeValue::S(program_id.to_string()))
+                .send()
+                .await.map_err(LibraryError::from)?;
+            registrations.extend(res.items.as_ref().unwrap_or(&vec![]).iter().map(RegistrationEntity::from));
+            last_key = res.last_evaluated_key().cloned();
+            if last_key.is_none() {
+                break;
+            }
+        }
+        Ok(registrations)
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for RegistrationEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        RegistrationEntity {
+            registration_id: parse_string_attribute("registration_id", map).unwrap_or_else(|| String::from("")),
+            version: parse_number_attribute("version", map),
+            program_id: parse_string_attribute("program_id", map).unwrap_or_else(|| String::from("")),
+            patron_id: parse_string_attribute("patron_id", map).unwrap_or_else(|| String::from("")),
+            registration_status: RegistrationStatus::from(parse_string_attribute("registration_status", map).unwrap_or_else(|| String::from(""))),
+            reminded_at: parse_date_attribute("reminded_at", map),
+            created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+    use chrono::Utc;
+
+    use crate::core::library::RegistrationStatus;
+    use crate::core::repository::RepositoryStore;
+    use crate::programs::domain::model::RegistrationEntity;
+    use crate::programs::repository::ddb_registration_repository::DDBRegistrationRepository;
+    use crate::programs::repository::RegistrationRepository;
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("program_registrations").as_str(), "registration_id", "program_id", "created_at").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_create_update_find_registrations() {
+        let store = test_store(module_path!(), "test_should_create_update_find_registrations");
+        let repo = DDBRegistrationRepository::new(build_client(store).await, store.table_name("program_registrations").as_str(), store.table_name("program_registrations_ndx").as_str());
+        let mut first = RegistrationEntity::new("registered_program", "patron1", RegistrationStatus::Registered);
+        assert_eq!(1, repo.create(&first).await.expect("should create registration"));
+        assert!(repo.create(&first).await.is_err());
+        let second = RegistrationEntity::new("registered_program", "patron2", RegistrationStatus::Waitlisted);
+        assert_eq!(1, repo.create(&second).await.expect("should create registration"));
+
+        first.registration_status = RegistrationStatus::Canceled;
+        first.reminded_at = Some(Utc::now().naive_utc());
+        assert_eq!(1, repo.update(&first).await.expect("should update registration"));
+        // stale version should not be updated
+        assert!(repo.update(&first).await.is_err());
+
+        let res = repo.find_by_program("registered_program").await.expect("should find registrations");
+        assert_eq!(2, res.len());
+        assert_eq!("patron1", res[0].patron_id.as_str());
+        assert_eq!(RegistrationStatus::Canceled, res[0].registration_status);
+        assert!(res[0].reminded_at.is_some());
+        assert_eq!(RegistrationStatus::Waitlisted, res[1].registration_status);
+    }
+}
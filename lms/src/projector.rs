@@ -0,0 +1,6 @@
+pub mod domain;
+pub mod factory;
+pub mod publisher;
+pub mod rebuild;
+pub mod repository;
+pub mod tasks;
@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use crate::core::events::DomainEvent;
+use crate::core::library::LibraryResult;
+use crate::gateway::subscribers::EventSubscriber;
+
+pub mod co_checkout;
+pub mod model;
+pub mod patron_counters;
+pub mod popularity;
+pub mod reading_history;
+
+// Projector builds read-side projections from domain events
+#[async_trait]
+pub(crate) trait Projector: Sync + Send {
+    // name of projection
+    fn name(&self) -> String;
+
+    // returns true if projector is interested in the event
+    fn handles(&self, event: &DomainEvent) -> bool;
+
+    // applies the event to the projection
+    async fn project(&self, event: &DomainEvent) -> LibraryResult<()>;
+}
+
+// projectors also subscribe to events delivered by SNS and SQS triggers so that projections can be built
+// by event consumers instead of the publishing service
+#[async_trait]
+impl EventSubscriber for Box<dyn Projector> {
+    fn name(&self) -> String {
+        (**self).name()
+    }
+
+    fn handles(&self, event: &DomainEvent) -> bool {
+        (**self).handles(event)
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> LibraryResult<()> {
+        self.project(event).await
+    }
+}
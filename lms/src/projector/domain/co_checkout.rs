@@ -0,0 +1,131 @@
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+
+use crate::checkout::dto::CheckoutDto;
+use crate::checkout::repository::CheckoutRepository;
+use crate::core::events::{DomainEvent, DomainEventType};
+use crate::core::library::{CheckoutStatus, LibraryResult};
+use crate::projector::domain::Projector;
+use crate::projector::repository::CoCheckoutRepository;
+
+const MAX_HISTORY: usize = 200;
+
+// CoCheckoutProjector counts books that are checked out by same patrons so that
+// frequently co-checked-out titles can be recommended as related books.
+pub(crate) struct CoCheckoutProjector {
+    checkout_repository: Box<dyn CheckoutRepository>,
+    co_checkout_repository: Box<dyn CoCheckoutRepository>,
+}
+
+impl CoCheckoutProjector {
+    pub(crate) fn new(checkout_repository: Box<dyn CheckoutRepository>,
+                      co_checkout_repository: Box<dyn CoCheckoutRepository>) -> Self {
+        Self {
+            checkout_repository,
+            co_checkout_repository,
+        }
+    }
+
+    // returns other books that were checked out by the patron
+    async fn patron_history(&self, checkout: &CheckoutDto) -> LibraryResult<Option<HashSet<String>>> {
+        let mut book_ids = HashSet::new();
+        for status in [CheckoutStatus::CheckedOut, CheckoutStatus::Returned] {
+            let predicate = HashMap::from([
+                ("checkout_status".to_string(), status.to_string()),
+                ("patron_id".to_string(), checkout.patron_id.to_string()),
+            ]);
+            let mut next_page: Option<String> = None;
+            loop {
+                let res = self.checkout_repository.query(&predicate, next_page.as_deref(), MAX_HISTORY).await?;
+                for record in res.records {
+                    if record.checkout_id == checkout.checkout_id {
+                        continue;
+                    }
+                    if record.book_id == checkout.book_id {
+                        // pairs of book were already counted when patron checked it out before
+                        return Ok(None);
+                    }
+                    book_ids.insert(record.book_id);
+                }
+                next_page = res.next_page;
+                if next_page.is_none() || book_ids.len() >= MAX_HISTORY {
+                    break;
+                }
+            }
+        }
+        Ok(Some(book_ids))
+    }
+}
+
+#[async_trait]
+impl Projector for CoCheckoutProjector {
+    fn name(&self) -> String {
+        "co_checkouts".to_string()
+    }
+
+    fn handles(&self, event: &DomainEvent) -> bool {
+        event.name == "book_checkout" && event.kind == DomainEventType::Added
+    }
+
+    async fn project(&self, event: &DomainEvent) -> LibraryResult<()> {
+        let checkout: CheckoutDto = serde_json::from_str(event.json_data.as_str())?;
+        if let Some(book_ids) = self.patron_history(&checkout).await? {
+            for other_book_id in book_ids {
+                let _ = self.co_checkout_repository.increment(
+                    checkout.book_id.as_str(), other_book_id.as_str(), 1).await?;
+                let _ = self.co_checkout_repository.increment(
+                    other_book_id.as_str(), checkout.book_id.as_str(), 1).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::checkout::domain::model::CheckoutEntity;
+    use crate::checkout::dto::CheckoutDto;
+    use crate::checkout::factory::create_checkout_repository;
+    use crate::checkout::repository::CheckoutRepository;
+    use crate::core::events::DomainEvent;
+    use crate::core::repository::RepositoryStore;
+    use crate::projector::domain::co_checkout::CoCheckoutProjector;
+    use crate::projector::domain::Projector;
+    use crate::projector::factory::create_co_checkout_repository;
+    use crate::projector::repository::CoCheckoutRepository;
+    use crate::utils::testing::test_store;
+
+    async fn checkout_repo(store: RepositoryStore) -> Box<dyn CheckoutRepository> {
+        create_checkout_repository(store).await
+    }
+
+    async fn sut_projector(store: RepositoryStore) -> CoCheckoutProjector {
+        CoCheckoutProjector::new(create_checkout_repository(store).await,
+                                 create_co_checkout_repository(store).await)
+    }
+
+    async fn co_checkout_repo(store: RepositoryStore) -> Box<dyn CoCheckoutRepository> {
+        create_co_checkout_repository(store).await
+    }
+
+    #[tokio::test]
+    async fn test_should_project_co_checkouts() {
+        let store = test_store(module_path!(), "test_should_project_co_checkouts");
+        let projector = sut_projector(store).await;
+        let first = CheckoutEntity::new("projected_book1", "projected_patron");
+        let second = CheckoutEntity::new("projected_book2", "projected_patron");
+        for checkout in [&first, &second] {
+            let _ = checkout_repo(store).await.create(checkout).await.expect("should create checkout");
+            let event = DomainEvent::added("book_checkout", "checkout", checkout.checkout_id.as_str(),
+                                           &HashMap::new(), &CheckoutDto::from(checkout)).expect("should build event");
+            assert!(projector.handles(&event));
+            projector.project(&event).await.expect("should project");
+        }
+        let related = co_checkout_repo(store).await.find_related("projected_book1", 10).await.expect("should find related");
+        assert_eq!(1, related.len());
+        assert_eq!("projected_book2", related[0].related_book_id.as_str());
+    }
+}
@@ -0,0 +1,210 @@
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::utils::date::serializer;
+
+// CoCheckoutEntity counts how many patrons checked out both book and related book
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct CoCheckoutEntity {
+    pub pair_id: String,
+    pub book_id: String,
+    pub related_book_id: String,
+    pub checkout_count: i64,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl CoCheckoutEntity {
+    pub fn new(book_id: &str, related_book_id: &str, checkout_count: i64) -> Self {
+        Self {
+            pair_id: CoCheckoutEntity::to_pair_id(book_id, related_book_id),
+            book_id: book_id.to_string(),
+            related_book_id: related_book_id.to_string(),
+            checkout_count,
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+
+    pub fn to_pair_id(book_id: &str, related_book_id: &str) -> String {
+        format!("{}:{}", book_id, related_book_id)
+    }
+}
+
+// ReadingHistoryEntity records a book returned by a patron who opted in for reading history
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct ReadingHistoryEntity {
+    pub history_id: String,
+    pub patron_id: String,
+    pub book_id: String,
+    #[serde(with = "serializer")]
+    pub checkout_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub returned_at: NaiveDateTime,
+}
+
+impl ReadingHistoryEntity {
+    // history_id is same as checkout_id so that replaying a return event is idempotent
+    pub fn new(checkout_id: &str, patron_id: &str, book_id: &str,
+               checkout_at: NaiveDateTime, returned_at: NaiveDateTime) -> Self {
+        Self {
+            history_id: checkout_id.to_string(),
+            patron_id: patron_id.to_string(),
+            book_id: book_id.to_string(),
+            checkout_at,
+            returned_at,
+        }
+    }
+}
+
+// ProjectionTableEntity points a projection to the table that serves it and tracks the rebuild of its next table,
+// checkpoints are the last replayed event so that a rebuild resumes after it
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct ProjectionTableEntity {
+    pub projection: String,
+    pub active_table: String,
+    // table that was replaced by the last switch-over, kept until it is deleted by an operator
+    pub previous_table: String,
+    // table that is being rebuilt, empty when no rebuild is in progress
+    pub rebuild_table: String,
+    pub checkpoint_event_id: String,
+    pub checkpoint_at: Option<NaiveDateTime>,
+    pub processed: i64,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl ProjectionTableEntity {
+    pub fn new(projection: &str, active_table: &str) -> Self {
+        Self {
+            projection: projection.to_string(),
+            active_table: active_table.to_string(),
+            previous_table: "".to_string(),
+            rebuild_table: "".to_string(),
+            checkpoint_event_id: "".to_string(),
+            checkpoint_at: None,
+            processed: 0,
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+
+    // events are replayed in the order of their creation and ids
+    pub fn is_after_checkpoint(&self, event_id: &str, created_at: NaiveDateTime) -> bool {
+        (Some(created_at), event_id) > (self.checkpoint_at, self.checkpoint_event_id.as_str())
+    }
+}
+
+// windows of popularity scores with the half-life of their decay in days
+pub(crate) const POPULARITY_WINDOWS: [(&str, i64); 3] = [("1d", 1), ("7d", 7), ("30d", 30)];
+
+// PopularityEntity keeps the decay-weighted activity of a book within a window. Weights are decayed forward from a
+// fixed epoch and stored as log2 so that adding activity never rewrites older scores and the score does not overflow,
+// the score at a given time is derived from log_score by subtracting the half-lives elapsed since the epoch.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct PopularityEntity {
+    pub score_id: String,
+    pub window: String,
+    pub book_id: String,
+    pub log_score: f64,
+    pub version: i64,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl PopularityEntity {
+    pub fn new(window: &str, book_id: &str) -> Self {
+        Self {
+            score_id: PopularityEntity::to_score_id(window, book_id),
+            window: window.to_string(),
+            book_id: book_id.to_string(),
+            log_score: f64::NEG_INFINITY,
+            version: 0,
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+
+    pub fn to_score_id(window: &str, book_id: &str) -> String {
+        format!("{}#{}", window, book_id)
+    }
+
+    // returns half-life of the window or None for unknown windows
+    pub fn half_life_days(window: &str) -> Option<i64> {
+        POPULARITY_WINDOWS.iter().find(|(name, _)| *name == window).map(|(_, days)| *days)
+    }
+
+    // returns log2 of the weight of activity at the given time decayed forward from the epoch
+    pub fn log_weight(weight: f64, at: NaiveDateTime, half_life_days: i64) -> f64 {
+        weight.log2() + Self::half_lives_since_epoch(at, half_life_days)
+    }
+
+    // adds activity to the score, i.e. log2(2^log_score + 2^log_weight) without leaving the log domain
+    pub fn add(&mut self, log_weight: f64) {
+        let (max, min) = if self.log_score > log_weight { (self.log_score, log_weight) } else { (log_weight, self.log_score) };
+        self.log_score = max + (1.0 + (min - max).exp2()).log2();
+    }
+
+    // returns the score decayed to the given time
+    pub fn score_at(&self, now: NaiveDateTime) -> f64 {
+        let half_life_days = Self::half_life_days(self.window.as_str()).unwrap_or(1);
+        (self.log_score - Self::half_lives_since_epoch(now, half_life_days)).exp2()
+    }
+
+    // sort key of the score index, scores are zero-padded so that strings sort in the order of scores
+    pub fn score_key(&self) -> String {
+        format!("{:015.6}", self.log_score.max(0.0))
+    }
+
+    fn half_lives_since_epoch(at: NaiveDateTime, half_life_days: i64) -> f64 {
+        let epoch = NaiveDate::from_ymd_opt(2023, 1, 1).and_then(|date| date.and_hms_opt(0, 0, 0)).expect("should build epoch");
+        (at - epoch).num_seconds() as f64 / (half_life_days * 86400) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::projector::domain::model::{CoCheckoutEntity, PopularityEntity, ProjectionTableEntity, ReadingHistoryEntity};
+
+    #[tokio::test]
+    async fn test_should_build_co_checkout() {
+        let co_checkout = CoCheckoutEntity::new("book1", "book2", 1);
+        assert_eq!("book1:book2", co_checkout.pair_id.as_str());
+        assert_eq!(1, co_checkout.checkout_count);
+    }
+
+    #[tokio::test]
+    async fn test_should_build_reading_history() {
+        let history = ReadingHistoryEntity::new("checkout1", "patron1", "book1",
+                                                Utc::now().naive_utc(), Utc::now().naive_utc());
+        assert_eq!("checkout1", history.history_id.as_str());
+        assert_eq!("patron1", history.patron_id.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_should_compare_checkpoint() {
+        let mut table = ProjectionTableEntity::new("co_checkouts", "co_checkouts");
+        let now = Utc::now().naive_utc();
+        assert!(table.is_after_checkpoint("event1", now));
+        table.checkpoint_at = Some(now);
+        table.checkpoint_event_id = "event2".to_string();
+        assert!(!table.is_after_checkpoint("event1", now));
+        assert!(!table.is_after_checkpoint("event2", now));
+        assert!(table.is_after_checkpoint("event3", now));
+    }
+
+    #[tokio::test]
+    async fn test_should_decay_popularity() {
+        let now = Utc::now().naive_utc();
+        let mut popularity = PopularityEntity::new("7d", "book1");
+        assert_eq!(Some(7), PopularityEntity::half_life_days("7d"));
+        assert_eq!(None, PopularityEntity::half_life_days("2w"));
+        popularity.add(PopularityEntity::log_weight(1.0, now, 7));
+        assert!((popularity.score_at(now) - 1.0).abs() < 1e-6);
+        // activity a half-life ago counts half
+        popularity.add(PopularityEntity::log_weight(1.0, now - Duration::days(7), 7));
+        assert!((popularity.score_at(now) - 1.5).abs() < 1e-6);
+        assert!((popularity.score_at(now + Duration::days(7)) - 0.75).abs() < 1e-6);
+
+        let mut other = PopularityEntity::new("7d", "book2");
+        other.add(PopularityEntity::log_weight(2.0, now, 7));
+        assert!(other.score_key() > popularity.score_key());
+    }
+}
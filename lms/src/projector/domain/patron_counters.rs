@@ -0,0 +1,180 @@
+use async_trait::async_trait;
+
+use crate::checkout::dto::CheckoutDto;
+use crate::core::events::DomainEvent;
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::hold::dto::HoldDto;
+use crate::parties::domain::model::PartyCounter;
+use crate::parties::repository::PartyRepository;
+use crate::projector::domain::Projector;
+
+// events of holds that are no longer counted, the hold was canceled, checked out, fulfilled at the desk or not picked
+// up in time
+const HOLD_ENDED_EVENTS: [&str; 4] = ["book_hold_cancel", "book_hold_checkout", "book_hold_fulfilled",
+    "book_hold_pickup_expired"];
+
+// PatronCountersProjector maintains num_holds, num_overdue and num_no_shows of patrons from hold and checkout events. Changes are
+// keyed by the hold or checkout so that redelivered events and overdue checkouts that are published again by later
+// runs are counted once, and a hold or checkout is only decremented after it was counted.
+pub(crate) struct PatronCountersProjector {
+    party_repository: Box<dyn PartyRepository>,
+}
+
+impl PatronCountersProjector {
+    pub(crate) fn new(party_repository: Box<dyn PartyRepository>) -> Self {
+        Self {
+            party_repository,
+        }
+    }
+
+    async fn add_counter(&self, party_id: &str, counter: PartyCounter, delta: i64, change_key: String,
+                         requires_key: Option<String>) -> LibraryResult<()> {
+        match self.party_repository.add_counter(party_id, counter, delta, change_key.as_str(), requires_key.as_deref()).await {
+            Ok(_) => Ok(()),
+            // patrons that were removed have no counters to maintain
+            Err(LibraryError::NotFound { .. }) => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[async_trait]
+impl Projector for PatronCountersProjector {
+    fn name(&self) -> String {
+        "patron_counters".to_string()
+    }
+
+    fn handles(&self, event: &DomainEvent) -> bool {
+        event.name == "book_hold" || HOLD_ENDED_EVENTS.contains(&event.name.as_str()) ||
+            event.name == "book_overdue" || event.name == "book_returned"
+    }
+
+    async fn project(&self, event: &DomainEvent) -> LibraryResult<()> {
+        match event.name.as_str() {
+            "book_hold" => {
+                let hold: HoldDto = serde_json::from_str(event.json_data.as_str())?;
+                self.add_counter(hold.patron_id.as_str(), PartyCounter::Holds, 1, format!("hold#{}", hold.hold_id), None).await
+            }
+            "book_overdue" => {
+                let checkout: CheckoutDto = serde_json::from_str(event.json_data.as_str())?;
+                self.add_counter(checkout.patron_id.as_str(), PartyCounter::Overdue, 1,
+                                 format!("overdue#{}", checkout.checkout_id), None).await
+            }
+            "book_returned" => {
+                let checkout: CheckoutDto = serde_json::from_str(event.json_data.as_str())?;
+                self.add_counter(checkout.patron_id.as_str(), PartyCounter::Overdue, -1,
+                                 format!("overdue_returned#{}", checkout.checkout_id),
+                                 Some(format!("overdue#{}", checkout.checkout_id))).await
+            }
+            _ => {
+                let hold: HoldDto = serde_json::from_str(event.json_data.as_str())?;
+                self.add_counter(hold.patron_id.as_str(), PartyCounter::Holds, -1,
+                                 format!("hold_ended#{}", hold.hold_id), Some(format!("hold#{}", hold.hold_id))).await?;
+                match event.name.as_str() {
+                    "book_hold_pickup_expired" => self.add_counter(hold.patron_id.as_str(), PartyCounter::NoShows, 1,
+                                                                   format!("no_show#{}", hold.hold_id), None).await,
+                    "book_hold_cancel" => Ok(()),
+                    // each hold that is picked up forgives an earlier no-show, the counter does not drop below zero
+                    _ => self.add_counter(hold.patron_id.as_str(), PartyCounter::NoShows, -1,
+                                          format!("picked_up#{}", hold.hold_id), None).await,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::checkout::dto::CheckoutDto;
+    use crate::core::events::DomainEvent;
+    use crate::core::ids::{BookId, PatronId};
+    use crate::core::library::PartyKind;
+    use crate::core::repository::RepositoryStore;
+    use crate::hold::dto::HoldDto;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::parties::repository::PartyRepository;
+    use crate::projector::domain::patron_counters::PatronCountersProjector;
+    use crate::projector::domain::Projector;
+    use crate::utils::testing::test_store;
+
+    async fn party_repo(store: RepositoryStore) -> Box<dyn PartyRepository> {
+        create_party_repository(store).await
+    }
+
+    async fn counters(store: RepositoryStore, party_id: &str) -> (i64, i64) {
+        let party = party_repo(store).await.get(party_id).await.expect("should get party");
+        (party.num_holds, party.num_overdue)
+    }
+
+    async fn no_shows(store: RepositoryStore, party_id: &str) -> i64 {
+        party_repo(store).await.get(party_id).await.expect("should get party").num_no_shows
+    }
+
+    #[tokio::test]
+    async fn test_should_maintain_patron_counters() {
+        let store = test_store(module_path!(), "test_should_maintain_patron_counters");
+        let projector = PatronCountersProjector::new(party_repo(store).await);
+        let patron = PartyEntity::new(PartyKind::Patron, "counted@example.com");
+        let _ = party_repo(store).await.create(&patron).await.expect("should create patron");
+
+        let hold = HoldDto::new(&BookId::new("counted_book"), &PatronId::new(patron.party_id.as_str()));
+        let placed = DomainEvent::added("book_hold", "book_hold", hold.hold_id.as_str(), &HashMap::new(), &hold)
+            .expect("should build event");
+        assert!(projector.handles(&placed));
+        // redelivered events are counted once
+        projector.project(&placed).await.expect("should project");
+        projector.project(&placed).await.expect("should project");
+        assert_eq!((1, 0), counters(store, patron.party_id.as_str()).await);
+
+        let checkout = CheckoutDto::new("counted_book", patron.party_id.as_str());
+        let returned = DomainEvent::deleted("book_returned", "checkout", checkout.checkout_id.as_str(), &HashMap::new(), &checkout)
+            .expect("should build event");
+        // returns of checkouts that were never overdue leave the overdue counter as is
+        projector.project(&returned).await.expect("should project");
+        assert_eq!((1, 0), counters(store, patron.party_id.as_str()).await);
+
+        let overdue = DomainEvent::updated("book_overdue", "checkout", checkout.checkout_id.as_str(), &HashMap::new(), &checkout)
+            .expect("should build event");
+        projector.project(&overdue).await.expect("should project");
+        projector.project(&overdue).await.expect("should project");
+        assert_eq!((1, 1), counters(store, patron.party_id.as_str()).await);
+
+        let canceled = DomainEvent::deleted("book_hold_cancel", "book_hold_cancel", hold.hold_id.as_str(), &HashMap::new(), &hold)
+            .expect("should build event");
+        assert!(projector.handles(&canceled));
+        projector.project(&canceled).await.expect("should project");
+        let returned = DomainEvent::deleted("book_returned", "checkout", checkout.checkout_id.as_str(), &HashMap::new(), &checkout)
+            .expect("should build event");
+        projector.project(&returned).await.expect("should project");
+        assert_eq!((0, 0), counters(store, patron.party_id.as_str()).await);
+    }
+
+    #[tokio::test]
+    async fn test_should_count_no_shows() {
+        let store = test_store(module_path!(), "test_should_count_no_shows");
+        let projector = PatronCountersProjector::new(party_repo(store).await);
+        let patron = PartyEntity::new(PartyKind::Patron, "no_show@example.com");
+        let _ = party_repo(store).await.create(&patron).await.expect("should create patron");
+
+        let mut holds = vec![];
+        for name in ["book_hold_pickup_expired", "book_hold_pickup_expired", "book_hold_checkout"] {
+            let hold = HoldDto::new(&BookId::new("no_show_book"), &PatronId::new(patron.party_id.as_str()));
+            let placed = DomainEvent::added("book_hold", "book_hold", hold.hold_id.as_str(), &HashMap::new(), &hold)
+                .expect("should build event");
+            projector.project(&placed).await.expect("should project");
+            holds.push((name, hold));
+        }
+        for (name, hold) in &holds {
+            let ended = DomainEvent::deleted(name, "book_hold", hold.hold_id.as_str(), &HashMap::new(), hold)
+                .expect("should build event");
+            // redelivered events are counted once
+            projector.project(&ended).await.expect("should project");
+            projector.project(&ended).await.expect("should project");
+        }
+        assert_eq!(1, no_shows(store, patron.party_id.as_str()).await);
+        assert_eq!((0, 0), counters(store, patron.party_id.as_str()).await);
+    }
+}
@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+use crate::checkout::dto::CheckoutDto;
+use crate::core::events::{DomainEvent, DomainEventType};
+use crate::core::library::LibraryResult;
+use crate::hold::dto::HoldDto;
+use crate::projector::domain::model::{PopularityEntity, POPULARITY_WINDOWS};
+use crate::projector::domain::Projector;
+use crate::projector::repository::PopularityRepository;
+
+// holds signal demand that could not be served so they weigh more than checkouts
+const CHECKOUT_WEIGHT: f64 = 1.0;
+const HOLD_WEIGHT: f64 = 2.0;
+
+// PopularityProjector scores books by their recent checkouts and holds for every window so that trending books
+// can be listed without counting activity at query time.
+pub(crate) struct PopularityProjector {
+    popularity_repository: Box<dyn PopularityRepository>,
+}
+
+impl PopularityProjector {
+    pub(crate) fn new(popularity_repository: Box<dyn PopularityRepository>) -> Self {
+        Self {
+            popularity_repository,
+        }
+    }
+
+    async fn add(&self, book_id: &str, weight: f64, at: NaiveDateTime) -> LibraryResult<()> {
+        for (window, half_life_days) in POPULARITY_WINDOWS {
+            let _ = self.popularity_repository.add(
+                window, book_id, PopularityEntity::log_weight(weight, at, half_life_days)).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Projector for PopularityProjector {
+    fn name(&self) -> String {
+        "book_popularity".to_string()
+    }
+
+    fn handles(&self, event: &DomainEvent) -> bool {
+        (event.name == "book_checkout" || event.name == "book_hold") && event.kind == DomainEventType::Added
+    }
+
+    async fn project(&self, event: &DomainEvent) -> LibraryResult<()> {
+        if event.name == "book_hold" {
+            let hold: HoldDto = serde_json::from_str(event.json_data.as_str())?;
+            self.add(hold.book_id.as_str(), HOLD_WEIGHT, hold.hold_at).await
+        } else {
+            let checkout: CheckoutDto = serde_json::from_str(event.json_data.as_str())?;
+            self.add(checkout.book_id.as_str(), CHECKOUT_WEIGHT, checkout.checkout_at).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::checkout::domain::model::CheckoutEntity;
+    use crate::checkout::dto::CheckoutDto;
+    use crate::core::events::DomainEvent;
+    use crate::core::ids::{BookId, PatronId};
+    use crate::hold::dto::HoldDto;
+    use crate::projector::domain::popularity::PopularityProjector;
+    use crate::projector::domain::Projector;
+    use crate::projector::factory::create_popularity_repository;
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_project_popularity() {
+        let store = test_store(module_path!(), "test_should_project_popularity");
+        let projector = PopularityProjector::new(create_popularity_repository(store).await);
+        let book_id = uuid::Uuid::new_v4().to_string();
+        let checkout = CheckoutDto::from(&CheckoutEntity::new(book_id.as_str(), "popular_patron"));
+        let hold = HoldDto::new(&BookId::new(book_id.as_str()), &PatronId::new("popular_patron"));
+        let events = vec![
+            DomainEvent::added("book_checkout", "checkout", checkout.checkout_id.as_str(), &HashMap::new(), &checkout)
+                .expect("should build event"),
+            DomainEvent::added("book_hold", "book_hold", hold.hold_id.as_str(), &HashMap::new(), &hold)
+                .expect("should build event"),
+        ];
+        for event in &events {
+            assert!(projector.handles(event));
+            projector.project(event).await.expect("should project");
+        }
+        let top = create_popularity_repository(store).await
+            .find_top("1d", 1000).await.expect("should find top");
+        let popularity = top.iter().find(|p| p.book_id == book_id).expect("should score book");
+        assert_eq!(2, popularity.version);
+    }
+}
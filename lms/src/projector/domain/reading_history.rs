@@ -0,0 +1,103 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::checkout::dto::CheckoutDto;
+use crate::core::events::DomainEvent;
+use crate::core::library::LibraryResult;
+use crate::parties::repository::PartyRepository;
+use crate::projector::domain::model::ReadingHistoryEntity;
+use crate::projector::domain::Projector;
+use crate::projector::repository::ReadingHistoryRepository;
+
+// ReadingHistoryProjector records returned books for patrons who opted in for reading history.
+pub(crate) struct ReadingHistoryProjector {
+    party_repository: Box<dyn PartyRepository>,
+    history_repository: Box<dyn ReadingHistoryRepository>,
+}
+
+impl ReadingHistoryProjector {
+    pub(crate) fn new(party_repository: Box<dyn PartyRepository>,
+                      history_repository: Box<dyn ReadingHistoryRepository>) -> Self {
+        Self {
+            party_repository,
+            history_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl Projector for ReadingHistoryProjector {
+    fn name(&self) -> String {
+        "reading_history".to_string()
+    }
+
+    fn handles(&self, event: &DomainEvent) -> bool {
+        event.name == "book_returned"
+    }
+
+    async fn project(&self, event: &DomainEvent) -> LibraryResult<()> {
+        let checkout: CheckoutDto = serde_json::from_str(event.json_data.as_str())?;
+        let patron = self.party_repository.get(checkout.patron_id.as_str()).await?;
+        if !patron.reading_history_enabled {
+            return Ok(());
+        }
+        let history = ReadingHistoryEntity::new(
+            checkout.checkout_id.as_str(), checkout.patron_id.as_str(), checkout.book_id.as_str(),
+            checkout.checkout_at, checkout.returned_at.unwrap_or_else(|| Utc::now().naive_utc()));
+        let _ = self.history_repository.record(&history).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::checkout::dto::CheckoutDto;
+    use crate::core::events::DomainEvent;
+    use crate::core::library::PartyKind;
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::parties::repository::PartyRepository;
+    use crate::projector::domain::Projector;
+    use crate::projector::domain::reading_history::ReadingHistoryProjector;
+    use crate::projector::factory::create_reading_history_repository;
+    use crate::projector::repository::ReadingHistoryRepository;
+    use crate::utils::testing::test_store;
+
+    async fn party_repo(store: RepositoryStore) -> Box<dyn PartyRepository> {
+        create_party_repository(store).await
+    }
+
+    async fn history_repo(store: RepositoryStore) -> Box<dyn ReadingHistoryRepository> {
+        create_reading_history_repository(store).await
+    }
+
+    async fn sut_projector(store: RepositoryStore) -> ReadingHistoryProjector {
+        ReadingHistoryProjector::new(create_party_repository(store).await,
+                                     create_reading_history_repository(store).await)
+    }
+
+    #[tokio::test]
+    async fn test_should_project_only_opted_in_patrons() {
+        let store = test_store(module_path!(), "test_should_project_only_opted_in_patrons");
+        let projector = sut_projector(store).await;
+        let mut opted_in = PartyEntity::new(PartyKind::Patron, "opted_in@example.com");
+        opted_in.reading_history_enabled = true;
+        let opted_out = PartyEntity::new(PartyKind::Patron, "opted_out@example.com");
+        for patron in [&opted_in, &opted_out] {
+            let _ = party_repo(store).await.create(patron).await.expect("should create patron");
+            let mut checkout = CheckoutDto::new("history_book", patron.party_id.as_str());
+            checkout.returned_at = Some(checkout.checkout_at);
+            let event = DomainEvent::deleted("book_returned", "checkout", checkout.checkout_id.as_str(),
+                                             &HashMap::new(), &checkout).expect("should build event");
+            assert!(projector.handles(&event));
+            projector.project(&event).await.expect("should project");
+        }
+        let res = history_repo(store).await.find_by_patron(opted_in.party_id.as_str(), None, 10).await.expect("should find history");
+        assert_eq!(1, res.records.len());
+        let res = history_repo(store).await.find_by_patron(opted_out.party_id.as_str(), None, 10).await.expect("should find history");
+        assert_eq!(0, res.records.len());
+    }
+}
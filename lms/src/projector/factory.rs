@@ -0,0 +1,139 @@
+use tracing::log::warn;
+use crate::checkout::factory::create_checkout_repository;
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::core::repository::RepositoryStore;
+use crate::core::tasks::factory::create_task_queue;
+use crate::core::tasks::TaskHandler;
+use crate::gateway::events::EventPublisher;
+use crate::gateway::factory::create_publisher;
+use crate::parties::factory::create_party_repository;
+use crate::projector::domain::co_checkout::CoCheckoutProjector;
+use crate::projector::domain::patron_counters::PatronCountersProjector;
+use crate::projector::domain::popularity::PopularityProjector;
+use crate::projector::domain::Projector;
+use crate::projector::domain::reading_history::ReadingHistoryProjector;
+use crate::projector::publisher::ProjectingPublisher;
+use crate::projector::repository::{CoCheckoutRepository, PopularityRepository, ProjectionTableRepository, ReadingHistoryRepository};
+use crate::projector::repository::ddb_co_checkout_repository::DDBCoCheckoutRepository;
+use crate::projector::repository::ddb_popularity_repository::DDBPopularityRepository;
+use crate::projector::repository::ddb_projection_table_repository::DDBProjectionTableRepository;
+use crate::projector::repository::ddb_reading_history_repository::DDBReadingHistoryRepository;
+use crate::projector::tasks::{ProjectionTaskHandler, RebuildProjectionTaskHandler};
+use crate::utils::ddb::{build_db_client, create_key_table, create_table};
+
+// key schema of the tables of projections that can be rebuilt into a new table by replaying events
+const REBUILDABLE_PROJECTIONS: [(&str, &str, &str, &str); 2] = [
+    ("co_checkouts", "pair_id", "book_id", "related_book_id"),
+    ("reading_history", "history_id", "patron_id", "returned_at"),
+];
+
+pub(crate) async fn create_projection_table_repository(store: RepositoryStore) -> Box<dyn ProjectionTableRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBProjectionTableRepository::new(client, "projection_tables"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("projection_tables");
+            let _ = create_key_table(&client, table_name.as_str(), "projection").await;
+            Box::new(DDBProjectionTableRepository::new(client, table_name.as_str()))
+        }
+    }
+}
+
+// resolves the table that serves the projection, projections that were never rebuilt use the table of their name
+async fn active_table(store: RepositoryStore, projection: &str) -> String {
+    match create_projection_table_repository(store).await.get(projection).await {
+        Ok(table) => table.active_table,
+        Err(LibraryError::NotFound { .. }) => projection.to_string(),
+        Err(err) => {
+            warn!("failed to resolve table of {} projection due to {}", projection, err);
+            projection.to_string()
+        }
+    }
+}
+
+// creates the table of a projection that can be rebuilt along with its <table>_ndx index, projection tables keep the
+// names of tables without the prefix of the store
+pub(crate) async fn create_projection_table(store: RepositoryStore, projection: &str, table_name: &str) -> LibraryResult<()> {
+    let (_, pk, gsi_pk, gsi_sk) = REBUILDABLE_PROJECTIONS.iter().find(|(name, ..)| *name == projection)
+        .ok_or_else(|| LibraryError::validation(format!("projection {} cannot be rebuilt", projection).as_str(),
+                                                Some("400".to_string())))?;
+    create_table(&build_db_client(store).await, store.table_name(table_name).as_str(), pk, gsi_pk, gsi_sk).await
+}
+
+pub(crate) async fn create_co_checkout_repository(store: RepositoryStore) -> Box<dyn CoCheckoutRepository> {
+    create_co_checkout_repository_in(store, active_table(store, "co_checkouts").await.as_str()).await
+}
+
+async fn create_co_checkout_repository_in(store: RepositoryStore, table_name: &str) -> Box<dyn CoCheckoutRepository> {
+    if store != RepositoryStore::DynamoDB {
+        let _ = create_projection_table(store, "co_checkouts", table_name).await;
+    }
+    let table_name = store.table_name(table_name);
+    let index_name = format!("{}_ndx", table_name);
+    Box::new(DDBCoCheckoutRepository::new(build_db_client(store).await, table_name.as_str(), index_name.as_str()))
+}
+
+pub(crate) async fn create_reading_history_repository(store: RepositoryStore) -> Box<dyn ReadingHistoryRepository> {
+    create_reading_history_repository_in(store, active_table(store, "reading_history").await.as_str()).await
+}
+
+async fn create_reading_history_repository_in(store: RepositoryStore, table_name: &str) -> Box<dyn ReadingHistoryRepository> {
+    if store != RepositoryStore::DynamoDB {
+        let _ = create_projection_table(store, "reading_history", table_name).await;
+    }
+    let table_name = store.table_name(table_name);
+    let index_name = format!("{}_ndx", table_name);
+    Box::new(DDBReadingHistoryRepository::new(build_db_client(store).await, table_name.as_str(), index_name.as_str()))
+}
+
+pub(crate) async fn create_popularity_repository(store: RepositoryStore) -> Box<dyn PopularityRepository> {
+    let client = build_db_client(store).await;
+    let table_name = store.table_name("book_popularity");
+    if store != RepositoryStore::DynamoDB {
+        let _ = create_table(&client, table_name.as_str(), "score_id", "score_window", "score_key").await;
+    }
+    Box::new(DDBPopularityRepository::new(client, table_name.as_str(), store.table_name("book_popularity_ndx").as_str()))
+}
+
+pub(crate) async fn create_projectors(store: RepositoryStore) -> Vec<Box<dyn Projector>> {
+    vec![
+        Box::new(CoCheckoutProjector::new(create_checkout_repository(store).await,
+                                          create_co_checkout_repository(store).await)),
+        Box::new(ReadingHistoryProjector::new(create_party_repository(store).await,
+                                              create_reading_history_repository(store).await)),
+        Box::new(PatronCountersProjector::new(create_party_repository(store).await)),
+        Box::new(PopularityProjector::new(create_popularity_repository(store).await)),
+    ]
+}
+
+// creates the projector of a projection that writes to the given table instead of its active table, e.g. to
+// rebuild the projection into a new table
+pub(crate) async fn create_projector_in(store: RepositoryStore, projection: &str,
+                                        table_name: &str) -> LibraryResult<Box<dyn Projector>> {
+    match projection {
+        "co_checkouts" => Ok(Box::new(CoCheckoutProjector::new(create_checkout_repository(store).await,
+                                                               create_co_checkout_repository_in(store, table_name).await))),
+        "reading_history" => Ok(Box::new(ReadingHistoryProjector::new(create_party_repository(store).await,
+                                                                      create_reading_history_repository_in(store, table_name).await))),
+        _ => Err(LibraryError::validation(format!("projection {} cannot be rebuilt", projection).as_str(),
+                                          Some("400".to_string()))),
+    }
+}
+
+// creates publisher that updates read-side projections after publishing events
+pub(crate) async fn create_projecting_publisher(store: RepositoryStore) -> Box<dyn EventPublisher> {
+    let publisher = create_publisher(store.gateway_publisher()).await;
+    let task_queue = create_task_queue(store.task_queue()).await;
+    Box::new(ProjectingPublisher::new(publisher, create_projectors(store).await, task_queue))
+}
+
+pub(crate) async fn create_projection_task_handler(store: RepositoryStore) -> Box<dyn TaskHandler> {
+    Box::new(ProjectionTaskHandler::new(create_projectors(store).await))
+}
+
+pub(crate) async fn create_rebuild_projection_task_handler(store: RepositoryStore) -> Box<dyn TaskHandler> {
+    Box::new(RebuildProjectionTaskHandler::new(store))
+}
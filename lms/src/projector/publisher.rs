@@ -0,0 +1,142 @@
+use async_trait::async_trait;
+use chrono::Duration;
+use tracing::log::warn;
+use crate::core::events::DomainEvent;
+use crate::core::library::LibraryError;
+use crate::core::tasks::{Task, TaskPayload, TaskQueue};
+use crate::gateway::events::EventPublisher;
+use crate::projector::domain::Projector;
+
+// ProjectingPublisher publishes events and then applies them to the registered projectors.
+// Projections are eventually consistent so a failure of projector does not fail the publish,
+// the event is queued for the projector instead so that the task worker applies it later.
+pub(crate) struct ProjectingPublisher {
+    delegate: Box<dyn EventPublisher>,
+    projectors: Vec<Box<dyn Projector>>,
+    task_queue: Box<dyn TaskQueue>,
+}
+
+impl ProjectingPublisher {
+    pub(crate) fn new(delegate: Box<dyn EventPublisher>, projectors: Vec<Box<dyn Projector>>,
+                      task_queue: Box<dyn TaskQueue>) -> Self {
+        Self {
+            delegate,
+            projectors,
+            task_queue,
+        }
+    }
+
+    async fn project(&self, event: &DomainEvent) {
+        for projector in &self.projectors {
+            if projector.handles(event) {
+                if let Err(err) = projector.project(event).await {
+                    warn!("failed to project event {} to {} due to {}", event.event_id, projector.name(), err);
+                    let task = Task::new(TaskPayload::ProjectEvent { projector: projector.name(), event: event.clone() });
+                    if let Err(err) = self.task_queue.enqueue(&task, Duration::zero()).await {
+                        warn!("failed to queue event {} for {} due to {}", event.event_id, projector.name(), err);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl EventPublisher for ProjectingPublisher {
+    async fn create_topic(&mut self, topic: &str) -> Result<String, LibraryError> {
+        self.delegate.create_topic(topic).await
+    }
+
+    async fn get_topics(&mut self) -> Result<Vec<String>, LibraryError> {
+        self.delegate.get_topics().await
+    }
+
+    async fn publish(&self, event: &DomainEvent) -> Result<(), LibraryError> {
+        self.delegate.publish(event).await?;
+        self.project(event).await;
+        Ok(())
+    }
+
+    async fn publish_all(&self, events: &[DomainEvent]) -> Result<(), LibraryError> {
+        self.delegate.publish_all(events).await?;
+        for event in events {
+            self.project(event).await;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use async_trait::async_trait;
+    use crate::core::events::DomainEvent;
+    use crate::core::library::{LibraryError, LibraryResult};
+    use crate::core::tasks::memory::MemoryTaskQueue;
+    use crate::core::tasks::{TaskPayload, TaskQueue};
+    use crate::gateway::factory::create_publisher;
+    use crate::gateway::events::EventPublisher;
+    use crate::projector::domain::Projector;
+    use crate::projector::publisher::ProjectingPublisher;
+    use crate::utils::testing::test_store;
+
+    struct CountingProjector {
+        count: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Projector for CountingProjector {
+        fn name(&self) -> String {
+            "counting".to_string()
+        }
+
+        fn handles(&self, event: &DomainEvent) -> bool {
+            event.name == "counted"
+        }
+
+        async fn project(&self, _event: &DomainEvent) -> LibraryResult<()> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            Err(LibraryError::runtime("projection failure should be ignored", None))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_should_publish_and_project() {
+        let store = test_store(module_path!(), "test_should_publish_and_project");
+        let count = Arc::new(AtomicUsize::new(0));
+        let queue = MemoryTaskQueue::new();
+        let publisher = ProjectingPublisher::new(
+            create_publisher(store.gateway_publisher()).await,
+            vec![Box::new(CountingProjector { count: count.clone() })], Box::new(queue.clone()));
+        let data = HashMap::from([("a", 1)]);
+        let counted = DomainEvent::added("counted", "group", "key", &HashMap::new(), &data).expect("build event");
+        let ignored = DomainEvent::added("ignored", "group", "key", &HashMap::new(), &data).expect("build event");
+        publisher.publish(&counted).await.expect("should publish");
+        publisher.publish(&ignored).await.expect("should publish");
+        assert_eq!(1, count.load(Ordering::SeqCst));
+
+        // failed projection is queued for the worker
+        let tasks = queue.receive(10).await.expect("should receive");
+        assert_eq!(1, tasks.len());
+        assert!(matches!(&tasks[0].payload, TaskPayload::ProjectEvent { projector, event } if projector == "counting" && event.name == "counted"));
+    }
+
+    #[tokio::test]
+    async fn test_should_publish_all_and_project_each() {
+        let store = test_store(module_path!(), "test_should_publish_all_and_project_each");
+        let count = Arc::new(AtomicUsize::new(0));
+        let publisher = ProjectingPublisher::new(
+            create_publisher(store.gateway_publisher()).await,
+            vec![Box::new(CountingProjector { count: count.clone() })], Box::new(MemoryTaskQueue::new()));
+        let data = HashMap::from([("a", 1)]);
+        let events = vec![
+            DomainEvent::added("counted", "group", "key1", &HashMap::new(), &data).expect("build event"),
+            DomainEvent::added("counted", "group", "key2", &HashMap::new(), &data).expect("build event"),
+            DomainEvent::added("ignored", "group", "key3", &HashMap::new(), &data).expect("build event"),
+        ];
+        publisher.publish_all(&events).await.expect("should publish all");
+        assert_eq!(2, count.load(Ordering::SeqCst));
+    }
+}
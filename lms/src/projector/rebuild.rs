@@ -0,0 +1,170 @@
+use chrono::Utc;
+use tracing::log::warn;
+use crate::core::events::upcasters::UPCASTERS;
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::core::repository::RepositoryStore;
+use crate::gateway::events::EventLog;
+use crate::gateway::factory::create_event_log;
+use crate::projector::domain::model::ProjectionTableEntity;
+use crate::projector::factory::{create_projection_table, create_projection_table_repository, create_projector_in};
+use crate::projector::repository::ProjectionTableRepository;
+
+// events between progress reports and between checkpoints of events that the projector does not handle
+const PROGRESS_INTERVAL: i64 = 100;
+
+// RebuildProgress reports replayed events of a projection rebuild
+#[derive(Debug, Clone)]
+pub struct RebuildProgress {
+    pub projection: String,
+    pub table: String,
+    pub processed: i64,
+    // events of the current pass that are not replayed yet
+    pub pending: usize,
+    // events of this run that the projector failed to project
+    pub failed: usize,
+    pub switched: bool,
+}
+
+impl RebuildProgress {
+    fn new(table: &ProjectionTableEntity, pending: usize, failed: usize) -> Self {
+        Self {
+            projection: table.projection.to_string(),
+            table: table.rebuild_table.to_string(),
+            processed: table.processed,
+            pending,
+            failed,
+            switched: false,
+        }
+    }
+}
+
+// replays the events table and the event archive into a new table of the projection, the rebuild checkpoints the last replayed event so
+// that a rebuild that was interrupted resumes after it when it is run again. Events that were published while a pass
+// replayed are picked up by the next pass until none are left, then the new table replaces the active table unless
+// switch_over is false, in which case a later run catches up and switches.
+pub(crate) async fn rebuild_projection(store: RepositoryStore, projection: &str, switch_over: bool,
+                                       progress: &(dyn Fn(&RebuildProgress) + Sync)) -> LibraryResult<RebuildProgress> {
+    let table_repository = create_projection_table_repository(store).await;
+    let mut table = resume_or_start(store, table_repository.as_ref(), projection).await?;
+    let projector = create_projector_in(store, projection, table.rebuild_table.as_str()).await?;
+    let event_log: Box<dyn EventLog> = create_event_log(store).await;
+    let mut failed = 0;
+    loop {
+        let events = event_log.find_events(None).await?.into_iter()
+            .filter(|event| table.is_after_checkpoint(event.event_id.as_str(), event.created_at))
+            .collect::<Vec<_>>();
+        if events.is_empty() {
+            break;
+        }
+        for (i, event) in events.iter().enumerate() {
+            let event = UPCASTERS.upcast(event)?;
+            let handled = projector.handles(&event);
+            // like live projections, an event that cannot be projected does not stop the others
+            if handled {
+                if let Err(err) = projector.project(&event).await {
+                    warn!("failed to project event {} to {} due to {}", event.event_id, projection, err);
+                    failed += 1;
+                }
+            }
+            table.checkpoint_event_id = event.event_id.to_string();
+            table.checkpoint_at = Some(event.created_at);
+            table.processed += 1;
+            // projected events are checkpointed right away so that a resumed rebuild does not count them twice
+            if handled || table.processed % PROGRESS_INTERVAL == 0 {
+                let _ = table_repository.checkpoint(&table).await?;
+            }
+            if table.processed % PROGRESS_INTERVAL == 0 {
+                progress(&RebuildProgress::new(&table, events.len() - i - 1, failed));
+            }
+        }
+        let _ = table_repository.checkpoint(&table).await?;
+    }
+
+    let mut res = RebuildProgress::new(&table, 0, failed);
+    if switch_over {
+        let _ = table_repository.switch_over(&table).await?;
+        res.switched = true;
+    }
+    progress(&res);
+    Ok(res)
+}
+
+async fn resume_or_start(store: RepositoryStore, table_repository: &dyn ProjectionTableRepository,
+                         projection: &str) -> LibraryResult<ProjectionTableEntity> {
+    let mut table = match table_repository.get(projection).await {
+        Ok(table) if !table.rebuild_table.is_empty() => return Ok(table),
+        Ok(table) => ProjectionTableEntity {
+            previous_table: table.previous_table,
+            ..ProjectionTableEntity::new(projection, table.active_table.as_str())
+        },
+        Err(LibraryError::NotFound { .. }) => ProjectionTableEntity::new(projection, projection),
+        Err(err) => return Err(err),
+    };
+    table.rebuild_table = format!("{}_{}", projection, Utc::now().format("%Y%m%d%H%M%S"));
+    create_projection_table(store, projection, table.rebuild_table.as_str()).await?;
+    let _ = table_repository.start_rebuild(&table).await?;
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use crate::checkout::dto::CheckoutDto;
+    use crate::core::events::DomainEvent;
+    use crate::core::library::PartyKind;
+    use crate::gateway::ddb::publisher::DDBPublisher;
+    use crate::gateway::events::EventPublisher;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::projector::factory::create_projection_table_repository;
+    use crate::projector::rebuild::rebuild_projection;
+    use crate::projector::repository::ddb_reading_history_repository::DDBReadingHistoryRepository;
+    use crate::projector::repository::ReadingHistoryRepository;
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    #[tokio::test]
+    async fn test_should_rebuild_projection_and_resume_from_checkpoint() {
+        let store = test_store(module_path!(), "test_should_rebuild_projection_and_resume_from_checkpoint");
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("events").as_str(), "event_id", "group", "key").await;
+        let mut patron = PartyEntity::new(PartyKind::Patron, "rebuilt@example.com");
+        patron.reading_history_enabled = true;
+        let _ = create_party_repository(store).await.create(&patron).await.expect("should create patron");
+        let publisher = DDBPublisher::new(client.clone(), store.table_name("events").as_str(), store.table_name("events_ndx").as_str());
+        let return_book = |book_id: &str| {
+            let mut checkout = CheckoutDto::new(book_id, patron.party_id.as_str());
+            checkout.returned_at = Some(checkout.checkout_at);
+            DomainEvent::deleted("book_returned", "checkout", checkout.checkout_id.as_str(),
+                                 &HashMap::new(), &checkout).expect("should build event")
+        };
+        publisher.publish(&return_book("rebuilt_book1")).await.expect("should publish");
+
+        // the rebuilt table is left inactive so that tests of the live projection are not affected
+        let reports = Mutex::new(vec![]);
+        let res = rebuild_projection(store, "reading_history", false,
+                                     &|progress| reports.lock().unwrap().push(progress.clone()))
+            .await.expect("should rebuild");
+        assert!(!res.switched);
+        assert!(!reports.lock().unwrap().is_empty());
+        let rebuilt = DDBReadingHistoryRepository::new(client.clone(), res.table.as_str(), format!("{}_ndx", res.table).as_str());
+        let history = rebuilt.find_by_patron(patron.party_id.as_str(), None, 10).await.expect("should find history");
+        assert_eq!(1, history.records.len());
+
+        // a later run resumes the same table and replays only events after the checkpoint
+        publisher.publish(&return_book("rebuilt_book2")).await.expect("should publish");
+        let resumed = rebuild_projection(store, "reading_history", false, &|_| {})
+            .await.expect("should resume");
+        assert_eq!(res.table, resumed.table);
+        assert!(resumed.processed > res.processed);
+        let history = rebuilt.find_by_patron(patron.party_id.as_str(), None, 10).await.expect("should find history");
+        assert_eq!(2, history.records.len());
+        let table = create_projection_table_repository(store).await
+            .get("reading_history").await.expect("should get projection table");
+        assert_eq!(res.table, table.rebuild_table);
+
+        assert!(rebuild_projection(store, "patron_counters", false, &|_| {}).await.is_err());
+    }
+}
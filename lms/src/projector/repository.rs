@@ -0,0 +1,54 @@
+pub mod ddb_co_checkout_repository;
+pub mod ddb_popularity_repository;
+pub mod ddb_projection_table_repository;
+pub mod ddb_reading_history_repository;
+
+use async_trait::async_trait;
+use crate::core::library::{LibraryResult, PaginatedResult};
+use crate::projector::domain::model::{CoCheckoutEntity, PopularityEntity, ProjectionTableEntity, ReadingHistoryEntity};
+
+#[async_trait]
+pub(crate) trait CoCheckoutRepository: Sync + Send {
+    // adds delta to number of co-checkouts of book and related book and returns new count
+    async fn increment(&self, book_id: &str, related_book_id: &str, delta: i64) -> LibraryResult<i64>;
+
+    // returns most frequently co-checked-out books in descending order of count
+    async fn find_related(&self, book_id: &str, limit: usize) -> LibraryResult<Vec<CoCheckoutEntity>>;
+}
+
+#[async_trait]
+pub(crate) trait PopularityRepository: Sync + Send {
+    // adds activity with the log2 of its decayed weight to the score of book in the window and returns new score
+    async fn add(&self, window: &str, book_id: &str, log_weight: f64) -> LibraryResult<PopularityEntity>;
+
+    // returns most popular books of the window in descending order of score
+    async fn find_top(&self, window: &str, limit: usize) -> LibraryResult<Vec<PopularityEntity>>;
+}
+
+#[async_trait]
+pub(crate) trait ReadingHistoryRepository: Sync + Send {
+    // records returned book, replacing existing record for the same checkout
+    async fn record(&self, entity: &ReadingHistoryEntity) -> LibraryResult<usize>;
+
+    // returns reading history of patron with recently returned books first
+    async fn find_by_patron(&self, patron_id: &str,
+                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<ReadingHistoryEntity>>;
+
+    // deletes all reading history of patron
+    async fn delete_by_patron(&self, patron_id: &str) -> LibraryResult<usize>;
+}
+
+#[async_trait]
+pub(crate) trait ProjectionTableRepository: Sync + Send {
+    // returns the table of projection
+    async fn get(&self, projection: &str) -> LibraryResult<ProjectionTableEntity>;
+
+    // records the start of a rebuild, which fails while another rebuild of the projection is in progress
+    async fn start_rebuild(&self, entity: &ProjectionTableEntity) -> LibraryResult<usize>;
+
+    // records the last replayed event of the rebuild
+    async fn checkpoint(&self, entity: &ProjectionTableEntity) -> LibraryResult<usize>;
+
+    // makes the rebuilt table the active table of projection in a single conditional write
+    async fn switch_over(&self, entity: &ProjectionTableEntity) -> LibraryResult<usize>;
+}
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::projector::domain::model::CoCheckoutEntity;
+use crate::projector::repository::CoCheckoutRepository;
+use crate::utils::ddb::{increment_attribute, parse_date_attribute, parse_number_attribute, parse_string_attribute, string_date, CounterUpdate};
+
+#[derive(Debug)]
+pub(crate) struct DDBCoCheckoutRepository {
+    client: Client,
+    table_name: String,
+    index_name: String,
+}
+
+impl DDBCoCheckoutRepository {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl CoCheckoutRepository for DDBCoCheckoutRepository {
+    async fn increment(&self, book_id: &str, related_book_id: &str, delta: i64) -> LibraryResult<i64> {
+        let counter = CounterUpdate::new(self.table_name.as_str(), "pair_id",
+                                         CoCheckoutEntity::to_pair_id(book_id, related_book_id).as_str(), "checkout_count")
+            .upsert()
+            .set("book_id", AttributeValue::S(book_id.to_string()))
+            .set("related_book_id", AttributeValue::S(related_book_id.to_string()))
+            .set("updated_at", string_date(Utc::now().naive_utc()));
+        increment_attribute(&self.client, &counter, delta).await
+    }
+
+    async fn find_related(&self, book_id: &str, limit: usize) -> LibraryResult<Vec<CoCheckoutEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let mut related = vec![];
+        let mut next_page: Option<HashMap<String, AttributeValue>> = None;
+        // the number of related books is bounded by checkouts of patrons so we read all pairs and sort them
+        for _i in 0..10 {
+            let res = self.client
+                .query()
+                .table_name(table_name)
+                .index_name(index_name)
+                .consistent_read(false)
+                .key_condition_expression("book_id = :book_id")
+                .expression_attribute_values(":book_id", AttributeValue::S(book_id.to_string()))
+                .set_exclusive_start_key(next_page)
+                .limit(500)
+                .send()
+                .await.map_err(LibraryError::from)?;
+            for item in res.items().unwrap_or_default() {
+                let co_checkout = CoCheckoutEntity::from(item);
+                if co_checkout.checkout_count > 0 {
+                    related.push(co_checkout);
+                }
+            }
+            next_page = res.last_evaluated_key().cloned();
+            if next_page.is_none() {
+                break;
+            }
+        }
+        related.sort_by(|a, b| b.checkout_count.cmp(&a.checkout_count));
+        related.truncate(limit);
+        Ok(related)
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for CoCheckoutEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        CoCheckoutEntity {
+            pair_id: parse_string_attribute("pair_id", map).unwrap_or_else(|| String::from("")),
+            book_id: parse_string_attribute("book_id", map).unwrap_or_else(|| String::from("")),
+            related_book_id: parse_string_attribute("related_book_id", map).unwrap_or_else(|| String::from("")),
+            checkout_count: parse_number_attribute("checkout_count", map),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+
+    use crate::core::repository::RepositoryStore;
+    use crate::projector::repository::CoCheckoutRepository;
+    use crate::projector::repository::ddb_co_checkout_repository::DDBCoCheckoutRepository;
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("co_checkouts").as_str(), "pair_id", "book_id", "related_book_id").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_increment_and_find_related() {
+        let store = test_store(module_path!(), "test_should_increment_and_find_related");
+        let repo = DDBCoCheckoutRepository::new(build_client(store).await, store.table_name("co_checkouts").as_str(), store.table_name("co_checkouts_ndx").as_str());
+        assert_eq!(1, repo.increment("book1", "book2", 1).await.expect("should increment"));
+        assert_eq!(2, repo.increment("book1", "book2", 1).await.expect("should increment"));
+        assert_eq!(1, repo.increment("book1", "book3", 1).await.expect("should increment"));
+
+        let related = repo.find_related("book1", 10).await.expect("should find related");
+        assert_eq!(2, related.len());
+        assert_eq!("book2", related[0].related_book_id.as_str());
+        assert_eq!(2, related[0].checkout_count);
+
+        let related = repo.find_related("book1", 1).await.expect("should find related");
+        assert_eq!(1, related.len());
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::projector::domain::model::PopularityEntity;
+use crate::projector::repository::PopularityRepository;
+use crate::utils::ddb::{parse_date_attribute, parse_number_attribute, parse_string_attribute, string_date};
+
+// concurrent updates of the same score are retried a few times before giving up
+const MAX_UPDATE_ATTEMPTS: usize = 5;
+
+// DDBPopularityRepository keeps one item per window and book, the index on window and score_key serves the most
+// popular books of a window without scanning
+#[derive(Debug)]
+pub(crate) struct DDBPopularityRepository {
+    client: Client,
+    table_name: String,
+    index_name: String,
+}
+
+impl DDBPopularityRepository {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+        }
+    }
+
+    async fn get(&self, window: &str, book_id: &str) -> LibraryResult<PopularityEntity> {
+        let table_name: &str = self.table_name.as_ref();
+        let score_id = PopularityEntity::to_score_id(window, book_id);
+        let res = self.client
+            .query()
+            .table_name(table_name)
+            .limit(1)
+            .consistent_read(true)
+            .key_condition_expression("score_id = :score_id")
+            .expression_attribute_values(":score_id", AttributeValue::S(score_id))
+            .send()
+            .await.map_err(LibraryError::from)?;
+        Ok(res.items().unwrap_or_default().first().map(PopularityEntity::from)
+            .unwrap_or_else(|| PopularityEntity::new(window, book_id)))
+    }
+
+    // saves the score unless it was updated since it was read, returns false on conflicts
+    async fn save(&self, entity: &PopularityEntity) -> LibraryResult<bool> {
+        let table_name: &str = self.table_name.as_ref();
+        let res = self.client
+            .put_item()
+            .table_name(table_name)
+            .item("score_id", AttributeValue::S(entity.score_id.to_string()))
+            .item("score_window", AttributeValue::S(entity.window.to_string()))
+            .item("book_id", AttributeValue::S(entity.book_id.to_string()))
+            .item("log_score", AttributeValue::N(entity.log_score.to_string()))
+            .item("score_key", AttributeValue::S(entity.score_key()))
+            .item("version", AttributeValue::N((entity.version + 1).to_string()))
+            .item("updated_at", string_date(Utc::now().naive_utc()))
+            .condition_expression("attribute_not_exists(score_id) OR version = :version")
+            .expression_attribute_values(":version", AttributeValue::N(entity.version.to_string()))
+            .send()
+            .await;
+        match res {
+            Ok(_) => Ok(true),
+            Err(SdkError::ServiceError(ctx)) if ctx.err().is_conditional_check_failed_exception() => Ok(false),
+            Err(err) => Err(LibraryError::from(err)),
+        }
+    }
+}
+
+#[async_trait]
+impl PopularityRepository for DDBPopularityRepository {
+    async fn add(&self, window: &str, book_id: &str, log_weight: f64) -> LibraryResult<PopularityEntity> {
+        for _ in 0..MAX_UPDATE_ATTEMPTS {
+            let mut entity = self.get(window, book_id).await?;
+            entity.add(log_weight);
+            if self.save(&entity).await? {
+                entity.version += 1;
+                return Ok(entity);
+            }
+        }
+        Err(LibraryError::database(format!("score of {} in {} was updated concurrently", book_id, window).as_str(),
+                                   None, true))
+    }
+
+    async fn find_top(&self, window: &str, limit: usize) -> LibraryResult<Vec<PopularityEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let res = self.client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .consistent_read(false)
+            .key_condition_expression("score_window = :score_window")
+            .expression_attribute_values(":score_window", AttributeValue::S(window.to_string()))
+            .scan_index_forward(false)
+            .limit(limit as i32)
+            .send()
+            .await.map_err(LibraryError::from)?;
+        Ok(res.items().unwrap_or_default().iter().map(PopularityEntity::from).collect())
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for PopularityEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        let log_score = match map.get("log_score") {
+            Some(AttributeValue::N(n)) => n.parse::<f64>().unwrap_or(f64::NEG_INFINITY),
+            _ => f64::NEG_INFINITY,
+        };
+        PopularityEntity {
+            score_id: parse_string_attribute("score_id", map).unwrap_or_else(|| String::from("")),
+            window: parse_string_attribute("score_window", map).unwrap_or_else(|| String::from("")),
+            book_id: parse_string_attribute("book_id", map).unwrap_or_else(|| String::from("")),
+            log_score,
+            version: parse_number_attribute("version", map),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+    use chrono::{Duration, Utc};
+
+    use crate::core::repository::RepositoryStore;
+    use crate::projector::domain::model::PopularityEntity;
+    use crate::projector::repository::PopularityRepository;
+    use crate::projector::repository::ddb_popularity_repository::DDBPopularityRepository;
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("book_popularity").as_str(), "score_id", "score_window", "score_key").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_add_and_find_top() {
+        let store = test_store(module_path!(), "test_should_add_and_find_top");
+        let repo = DDBPopularityRepository::new(build_client(store).await, store.table_name("book_popularity").as_str(), store.table_name("book_popularity_ndx").as_str());
+        let window = format!("test{}", uuid::Uuid::new_v4());
+        let now = Utc::now().naive_utc();
+        let _ = repo.add(window.as_str(), "book1", PopularityEntity::log_weight(1.0, now - Duration::days(7), 7))
+            .await.expect("should add");
+        let score = repo.add(window.as_str(), "book1", PopularityEntity::log_weight(1.0, now, 7))
+            .await.expect("should add");
+        assert_eq!(2, score.version);
+        let _ = repo.add(window.as_str(), "book2", PopularityEntity::log_weight(1.0, now, 7))
+            .await.expect("should add");
+
+        let top = repo.find_top(window.as_str(), 10).await.expect("should find top");
+        assert_eq!(vec!["book1", "book2"], top.iter().map(|p| p.book_id.as_str()).collect::<Vec<&str>>());
+        let top = repo.find_top(window.as_str(), 1).await.expect("should find top");
+        assert_eq!(1, top.len());
+    }
+}
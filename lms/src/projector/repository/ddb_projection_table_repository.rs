@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::projector::domain::model::ProjectionTableEntity;
+use crate::projector::repository::ProjectionTableRepository;
+use crate::utils::ddb::{is_conditional_check_failed, opt_string_date, parse_date_attribute, parse_number_attribute, parse_string_attribute, string_date};
+
+// DDBProjectionTableRepository keeps one item per projection keyed by its name
+#[derive(Debug)]
+pub(crate) struct DDBProjectionTableRepository {
+    client: Client,
+    table_name: String,
+}
+
+impl DDBProjectionTableRepository {
+    pub(crate) fn new(client: Client, table_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+        }
+    }
+
+    fn rebuild_conflict(entity: &ProjectionTableEntity) -> LibraryError {
+        LibraryError::validation(format!("rebuild of {} into {} is no longer in progress",
+                                         entity.projection, entity.rebuild_table).as_str(), Some("409".to_string()))
+    }
+}
+
+#[async_trait]
+impl ProjectionTableRepository for DDBProjectionTableRepository {
+    async fn get(&self, projection: &str) -> LibraryResult<ProjectionTableEntity> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .query()
+            .table_name(table_name)
+            .limit(1)
+            .consistent_read(true)
+            .key_condition_expression("projection = :projection")
+            .expression_attribute_values(":projection", AttributeValue::S(projection.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).and_then(|req| {
+            if let Some(map) = req.items.as_ref().and_then(|items| items.first()) {
+                return Ok(ProjectionTableEntity::from(map));
+            }
+            Err(LibraryError::not_found(format!("projection table not found for {}", projection).as_str()))
+        })
+    }
+
+    async fn start_rebuild(&self, entity: &ProjectionTableEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let res = self.client
+            .put_item()
+            .table_name(table_name)
+            .item("projection", AttributeValue::S(entity.projection.to_string()))
+            .item("active_table", AttributeValue::S(entity.active_table.to_string()))
+            .item("previous_table", AttributeValue::S(entity.previous_table.to_string()))
+            .item("rebuild_table", AttributeValue::S(entity.rebuild_table.to_string()))
+            .item("checkpoint_event_id", AttributeValue::S(entity.checkpoint_event_id.to_string()))
+            .item("checkpoint_at", opt_string_date(entity.checkpoint_at))
+            .item("processed", AttributeValue::N(entity.processed.to_string()))
+            .item("updated_at", string_date(Utc::now().naive_utc()))
+            .condition_expression("attribute_not_exists(projection) OR rebuild_table = :none")
+            .expression_attribute_values(":none", AttributeValue::S("".to_string()))
+            .send()
+            .await;
+        match res {
+            Ok(_) => Ok(1),
+            Err(SdkError::ServiceError(ctx)) if ctx.err().is_conditional_check_failed_exception() => Err(
+                LibraryError::validation(format!("{} is already being rebuilt", entity.projection).as_str(),
+                                         Some("409".to_string()))),
+            Err(err) => Err(LibraryError::from(err)),
+        }
+    }
+
+    async fn checkpoint(&self, entity: &ProjectionTableEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .update_item()
+            .table_name(table_name)
+            .key("projection", AttributeValue::S(entity.projection.to_string()))
+            .update_expression("SET checkpoint_event_id = :checkpoint_event_id, checkpoint_at = :checkpoint_at, processed = :processed, updated_at = :updated_at")
+            .expression_attribute_values(":checkpoint_event_id", AttributeValue::S(entity.checkpoint_event_id.to_string()))
+            .expression_attribute_values(":checkpoint_at", opt_string_date(entity.checkpoint_at))
+            .expression_attribute_values(":processed", AttributeValue::N(entity.processed.to_string()))
+            .expression_attribute_values(":rebuild_table", AttributeValue::S(entity.rebuild_table.to_string()))
+            .expression_attribute_values(":updated_at", string_date(Utc::now().naive_utc()))
+            .condition_expression("rebuild_table = :rebuild_table")
+            .send()
+            .await.map(|_| 1).map_err(|err| {
+            if is_conditional_check_failed(&err) {
+                Self::rebuild_conflict(entity)
+            } else {
+                LibraryError::from(err)
+            }
+        })
+    }
+
+    async fn switch_over(&self, entity: &ProjectionTableEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .update_item()
+            .table_name(table_name)
+            .key("projection", AttributeValue::S(entity.projection.to_string()))
+            .update_expression("SET active_table = :rebuild_table, previous_table = :active_table, rebuild_table = :none, updated_at = :updated_at")
+            .expression_attribute_values(":rebuild_table", AttributeValue::S(entity.rebuild_table.to_string()))
+            .expression_attribute_values(":active_table", AttributeValue::S(entity.active_table.to_string()))
+            .expression_attribute_values(":none", AttributeValue::S("".to_string()))
+            .expression_attribute_values(":updated_at", string_date(Utc::now().naive_utc()))
+            .condition_expression("rebuild_table = :rebuild_table AND active_table = :active_table")
+            .send()
+            .await.map(|_| 1).map_err(|err| {
+            if is_conditional_check_failed(&err) {
+                Self::rebuild_conflict(entity)
+            } else {
+                LibraryError::from(err)
+            }
+        })
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for ProjectionTableEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        ProjectionTableEntity {
+            projection: parse_string_attribute("projection", map).unwrap_or_else(|| String::from("")),
+            active_table: parse_string_attribute("active_table", map).unwrap_or_else(|| String::from("")),
+            previous_table: parse_string_attribute("previous_table", map).unwrap_or_else(|| String::from("")),
+            rebuild_table: parse_string_attribute("rebuild_table", map).unwrap_or_else(|| String::from("")),
+            checkpoint_event_id: parse_string_attribute("checkpoint_event_id", map).unwrap_or_else(|| String::from("")),
+            checkpoint_at: parse_date_attribute("checkpoint_at", map),
+            processed: parse_number_attribute("processed", map),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use crate::core::repository::RepositoryStore;
+    use crate::projector::domain::model::ProjectionTableEntity;
+    use crate::projector::repository::ddb_projection_table_repository::DDBProjectionTableRepository;
+    use crate::projector::repository::ProjectionTableRepository;
+    use crate::utils::ddb::{build_db_client, create_key_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_key_table(&client, store.table_name("projection_tables").as_str(), "projection").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_checkpoint_and_switch_over_rebuild() {
+        let store = test_store(module_path!(), "test_should_checkpoint_and_switch_over_rebuild");
+        let repo = DDBProjectionTableRepository::new(build_client(store).await, store.table_name("projection_tables").as_str());
+        let projection = format!("projection_{}", Uuid::new_v4());
+        assert!(repo.get(projection.as_str()).await.is_err());
+
+        let mut table = ProjectionTableEntity::new(projection.as_str(), "projected");
+        table.rebuild_table = "projected_v2".to_string();
+        assert_eq!(1, repo.start_rebuild(&table).await.expect("should start rebuild"));
+        // only one rebuild of a projection runs at a time
+        assert!(repo.start_rebuild(&table).await.is_err());
+
+        table.checkpoint_event_id = "event1".to_string();
+        table.checkpoint_at = Some(Utc::now().naive_utc());
+        table.processed = 1;
+        assert_eq!(1, repo.checkpoint(&table).await.expect("should checkpoint"));
+        let loaded = repo.get(projection.as_str()).await.expect("should get projection table");
+        assert_eq!("event1", loaded.checkpoint_event_id.as_str());
+        assert_eq!(1, loaded.processed);
+        assert_eq!("projected", loaded.active_table.as_str());
+
+        assert_eq!(1, repo.switch_over(&table).await.expect("should switch over"));
+        let loaded = repo.get(projection.as_str()).await.expect("should get projection table");
+        assert_eq!("projected_v2", loaded.active_table.as_str());
+        assert_eq!("projected", loaded.previous_table.as_str());
+        assert_eq!("", loaded.rebuild_table.as_str());
+        // a switched rebuild can neither checkpoint nor switch again
+        assert!(repo.checkpoint(&table).await.is_err());
+        assert!(repo.switch_over(&table).await.is_err());
+    }
+}
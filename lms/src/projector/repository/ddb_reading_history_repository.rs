@@ -0,0 +1,142 @@
+use std::cmp;
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult};
+use crate::projector::domain::model::ReadingHistoryEntity;
+use crate::projector::repository::ReadingHistoryRepository;
+use crate::utils::ddb::{from_ddb, parse_date_attribute, parse_item, parse_string_attribute, to_ddb_page};
+
+#[derive(Debug)]
+pub(crate) struct DDBReadingHistoryRepository {
+    client: Client,
+    table_name: String,
+    index_name: String,
+}
+
+impl DDBReadingHistoryRepository {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl ReadingHistoryRepository for DDBReadingHistoryRepository {
+    async fn record(&self, entity: &ReadingHistoryEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .set_item(Some(parse_item(val)?))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn find_by_patron(&self, patron_id: &str,
+                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<ReadingHistoryEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let predicate = HashMap::from([("patron_id".to_string(), patron_id.to_string())]);
+        self.client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .limit(cmp::min(page_size, 500) as i32)
+            .consistent_read(false)
+            .scan_index_forward(false)
+            .set_exclusive_start_key(to_ddb_page(page, &predicate))
+            .key_condition_expression("patron_id = :patron_id")
+            .expression_attribute_values(":patron_id", AttributeValue::S(patron_id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(ReadingHistoryEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+
+    async fn delete_by_patron(&self, patron_id: &str) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let mut deleted = 0;
+        let mut next_page: Option<String> = None;
+        loop {
+            let res = self.find_by_patron(patron_id, next_page.as_deref(), 100).await?;
+            for record in &res.records {
+                deleted += self.client.delete_item()
+                    .table_name(table_name)
+                    .key("history_id", AttributeValue::S(record.history_id.to_string()))
+                    .send()
+                    .await.map(|_| 1).map_err(LibraryError::from)?;
+            }
+            next_page = res.next_page;
+            if next_page.is_none() {
+                break;
+            }
+        }
+        Ok(deleted)
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for ReadingHistoryEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        ReadingHistoryEntity {
+            history_id: parse_string_attribute("history_id", map).unwrap_or_else(|| String::from("")),
+            patron_id: parse_string_attribute("patron_id", map).unwrap_or_else(|| String::from("")),
+            book_id: parse_string_attribute("book_id", map).unwrap_or_else(|| String::from("")),
+            checkout_at: parse_date_attribute("checkout_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            returned_at: parse_date_attribute("returned_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+    use chrono::NaiveDateTime;
+
+    use crate::core::repository::RepositoryStore;
+    use crate::projector::domain::model::ReadingHistoryEntity;
+    use crate::projector::repository::ddb_reading_history_repository::DDBReadingHistoryRepository;
+    use crate::projector::repository::ReadingHistoryRepository;
+    use crate::utils::date::DATE_FMT;
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("reading_history").as_str(), "history_id", "patron_id", "returned_at").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_record_find_delete_history() {
+        let store = test_store(module_path!(), "test_should_record_find_delete_history");
+        let repo = DDBReadingHistoryRepository::new(build_client(store).await, store.table_name("reading_history").as_str(), store.table_name("reading_history_ndx").as_str());
+        let checkout_at = NaiveDateTime::parse_from_str("2023-04-11T11:11:11", DATE_FMT).unwrap();
+        let first = ReadingHistoryEntity::new("history_checkout1", "history_patron", "book1", checkout_at,
+                                              NaiveDateTime::parse_from_str("2023-04-20T11:11:11", DATE_FMT).unwrap());
+        let second = ReadingHistoryEntity::new("history_checkout2", "history_patron", "book2", checkout_at,
+                                               NaiveDateTime::parse_from_str("2023-05-20T11:11:11", DATE_FMT).unwrap());
+        assert_eq!(1, repo.record(&first).await.expect("should record history"));
+        assert_eq!(1, repo.record(&second).await.expect("should record history"));
+        // recording same checkout again should not duplicate history
+        assert_eq!(1, repo.record(&second).await.expect("should record history"));
+
+        let res = repo.find_by_patron("history_patron", None, 10).await.expect("should find history");
+        assert_eq!(2, res.records.len());
+        assert_eq!("book2", res.records[0].book_id.as_str());
+
+        assert_eq!(2, repo.delete_by_patron("history_patron").await.expect("should delete history"));
+        let res = repo.find_by_patron("history_patron", None, 10).await.expect("should find history");
+        assert_eq!(0, res.records.len());
+    }
+}
@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+use tracing::log::info;
+use crate::core::events::upcasters::UPCASTERS;
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::core::repository::RepositoryStore;
+use crate::core::tasks::{Task, TaskHandler, TaskPayload};
+use crate::projector::domain::Projector;
+use crate::projector::rebuild::rebuild_projection;
+
+// ProjectionTaskHandler applies queued events to the projector named by the task, e.g. to catch up a projection
+// after its projector failed while the event was published
+pub(crate) struct ProjectionTaskHandler {
+    projectors: Vec<Box<dyn Projector>>,
+}
+
+impl ProjectionTaskHandler {
+    pub(crate) fn new(projectors: Vec<Box<dyn Projector>>) -> Self {
+        Self {
+            projectors,
+        }
+    }
+}
+
+#[async_trait]
+impl TaskHandler for ProjectionTaskHandler {
+    fn handles(&self, task: &Task) -> bool {
+        matches!(task.payload, TaskPayload::ProjectEvent { .. })
+    }
+
+    async fn handle(&self, task: &Task) -> LibraryResult<()> {
+        if let TaskPayload::ProjectEvent { projector, event } = &task.payload {
+            let projector = self.projectors.iter().find(|p| p.name() == *projector)
+                .ok_or_else(|| LibraryError::validation(format!("unknown projector {}", projector).as_str(), None))?;
+            // the event may have been queued before its payload changed
+            projector.project(&UPCASTERS.upcast(event)?).await?;
+        }
+        Ok(())
+    }
+}
+
+// RebuildProjectionTaskHandler rebuilds a projection into a new table in the background, a task that fails midway
+// resumes from the checkpoint of the rebuild when it is retried
+pub(crate) struct RebuildProjectionTaskHandler {
+    store: RepositoryStore,
+}
+
+impl RebuildProjectionTaskHandler {
+    pub(crate) fn new(store: RepositoryStore) -> Self {
+        Self {
+            store,
+        }
+    }
+}
+
+#[async_trait]
+impl TaskHandler for RebuildProjectionTaskHandler {
+    fn handles(&self, task: &Task) -> bool {
+        matches!(task.payload, TaskPayload::RebuildProjection { .. })
+    }
+
+    async fn handle(&self, task: &Task) -> LibraryResult<()> {
+        if let TaskPayload::RebuildProjection { projection, switch_over } = &task.payload {
+            let res = rebuild_projection(self.store, projection, *switch_over, &|progress| {
+                info!("rebuilding {} into {} processed {} events", progress.projection, progress.table, progress.processed);
+            }).await?;
+            info!("rebuilt {} into {} from {} events with {} failures, switched: {}",
+                res.projection, res.table, res.processed, res.failed, res.switched);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use async_trait::async_trait;
+    use crate::core::events::DomainEvent;
+    use crate::core::library::LibraryResult;
+    use crate::core::repository::RepositoryStore;
+    use crate::core::tasks::{Task, TaskHandler, TaskPayload};
+    use crate::projector::domain::Projector;
+    use crate::projector::tasks::{ProjectionTaskHandler, RebuildProjectionTaskHandler};
+
+    struct CountingProjector {
+        count: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Projector for CountingProjector {
+        fn name(&self) -> String {
+            "counting".to_string()
+        }
+
+        fn handles(&self, _event: &DomainEvent) -> bool {
+            true
+        }
+
+        async fn project(&self, _event: &DomainEvent) -> LibraryResult<()> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_should_project_queued_event() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let handler = ProjectionTaskHandler::new(vec![Box::new(CountingProjector { count: count.clone() })]);
+        let event = DomainEvent::added("counted", "group", "key", &HashMap::new(), &HashMap::from([("a", 1)])).expect("build event");
+
+        let task = Task::new(TaskPayload::ProjectEvent { projector: "counting".to_string(), event: event.clone() });
+        assert!(handler.handles(&task));
+        handler.handle(&task).await.expect("should project");
+        assert_eq!(1, count.load(Ordering::SeqCst));
+
+        let unknown = Task::new(TaskPayload::ProjectEvent { projector: "unknown".to_string(), event });
+        assert!(handler.handle(&unknown).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_should_handle_rebuild_tasks() {
+        let handler = RebuildProjectionTaskHandler::new(RepositoryStore::LocalDynamoDB);
+        assert!(handler.handles(&Task::new(TaskPayload::RebuildProjection { projection: "co_checkouts".to_string(), switch_over: true })));
+        assert!(!handler.handles(&Task::new(TaskPayload::ArchiveEvents { older_than_days: 90 })));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod add_reserve_book_cmd;
+pub mod create_reserve_list_cmd;
+pub mod get_reserve_list_cmd;
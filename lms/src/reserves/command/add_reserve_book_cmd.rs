@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::reserves::domain::ReserveService;
+use crate::reserves::dto::ReserveItemDto;
+
+pub(crate) struct AddReserveBookCommand {
+    reserve_service: Box<dyn ReserveService>,
+}
+
+impl AddReserveBookCommand {
+    pub(crate) fn new(reserve_service: Box<dyn ReserveService>) -> Self {
+        Self {
+            reserve_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AddReserveBookCommandRequest {
+    #[serde(default)]
+    pub(crate) list_name: String,
+    pub(crate) added_by: String,
+    pub(crate) book_id: String,
+}
+
+impl AddReserveBookCommandRequest {
+    pub fn new(list_name: &str, added_by: &str, book_id: &str) -> Self {
+        Self {
+            list_name: list_name.to_string(),
+            added_by: added_by.to_string(),
+            book_id: book_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AddReserveBookCommandResponse {
+    pub reserve: ReserveItemDto,
+}
+
+impl AddReserveBookCommandResponse {
+    pub fn new(reserve: ReserveItemDto) -> Self {
+        Self {
+            reserve,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<AddReserveBookCommandRequest, AddReserveBookCommandResponse> for AddReserveBookCommand {
+    async fn execute(&self, req: AddReserveBookCommandRequest) -> Result<AddReserveBookCommandResponse, CommandError> {
+        self.reserve_service.add_book(req.added_by.as_str(), req.list_name.as_str(), req.book_id.as_str())
+            .await.map_err(CommandError::from).map(AddReserveBookCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+    use crate::books::domain::model::BookEntity;
+    use crate::books::factory::create_book_repository;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{BookStatus, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::reserves::command::add_reserve_book_cmd::{AddReserveBookCommand, AddReserveBookCommandRequest};
+    use crate::reserves::domain::model::ReserveListEntity;
+    use crate::reserves::factory;
+    use crate::utils::testing::test_store;
+
+    async fn sut_cmd(store: RepositoryStore) -> AddReserveBookCommand {
+        let svc = factory::create_reserve_service(&Configuration::new("test"), store).await;
+        AddReserveBookCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_add_reserve_book() {
+        let store = test_store(module_path!(), "test_should_run_add_reserve_book");
+        let cmd = sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "add_reserve@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+        let book = BookEntity::new("isbn", "course text", BookStatus::Available);
+        let _ = create_book_repository(store).await.create(&book).await.expect("should create book");
+        let list = ReserveListEntity::new(format!("PHYS101_{}", Uuid::new_v4()).as_str(), 2);
+        let _ = factory::create_reserve_list_repository(store).await.create(&list).await.expect("should create list");
+
+        let res = cmd.execute(AddReserveBookCommandRequest::new(list.list_name.as_str(), librarian.party_id.as_str(), book.book_id.as_str()))
+            .await.expect("should add reserve book");
+        assert_eq!(book.book_id, res.reserve.book_id);
+        assert_eq!(list.list_name, res.reserve.list_name);
+    }
+}
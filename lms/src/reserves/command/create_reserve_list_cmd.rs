@@ -0,0 +1,102 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::reserves::domain::ReserveService;
+use crate::reserves::dto::ReserveListDto;
+
+pub(crate) struct CreateReserveListCommand {
+    reserve_service: Box<dyn ReserveService>,
+}
+
+impl CreateReserveListCommand {
+    pub(crate) fn new(reserve_service: Box<dyn ReserveService>) -> Self {
+        Self {
+            reserve_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CreateReserveListCommandRequest {
+    list_name: String,
+    created_by: String,
+    loan_hours: i64,
+    #[serde(default)]
+    renewals_allowed: bool,
+    #[serde(default)]
+    holds_allowed: bool,
+}
+
+impl CreateReserveListCommandRequest {
+    pub fn new(list_name: &str, created_by: &str, loan_hours: i64) -> Self {
+        Self {
+            list_name: list_name.to_string(),
+            created_by: created_by.to_string(),
+            loan_hours,
+            renewals_allowed: false,
+            holds_allowed: false,
+        }
+    }
+    pub fn build_list(&self) -> ReserveListDto {
+        let mut list = ReserveListDto::new(self.list_name.as_str(), self.loan_hours, self.created_by.as_str());
+        list.renewals_allowed = self.renewals_allowed;
+        list.holds_allowed = self.holds_allowed;
+        list
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CreateReserveListCommandResponse {
+    pub list: ReserveListDto,
+}
+
+impl CreateReserveListCommandResponse {
+    pub fn new(list: ReserveListDto) -> Self {
+        Self {
+            list,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<CreateReserveListCommandRequest, CreateReserveListCommandResponse> for CreateReserveListCommand {
+    async fn execute(&self, req: CreateReserveListCommandRequest) -> Result<CreateReserveListCommandResponse, CommandError> {
+        self.reserve_service.create_list(&req.build_list())
+            .await.map_err(CommandError::from).map(CreateReserveListCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::reserves::command::create_reserve_list_cmd::{CreateReserveListCommand, CreateReserveListCommandRequest};
+    use crate::reserves::factory;
+    use crate::utils::testing::test_store;
+
+    async fn sut_cmd(store: RepositoryStore) -> CreateReserveListCommand {
+        let svc = factory::create_reserve_service(&Configuration::new("test"), store).await;
+        CreateReserveListCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_create_reserve_list() {
+        let store = test_store(module_path!(), "test_should_run_create_reserve_list");
+        let cmd = sut_cmd(store).await;
+        let mut librarian = PartyEntity::new(PartyKind::Patron, "create_reserve@example.com");
+        librarian.group_roles = vec![Role::Librarian.to_string()];
+        let _ = create_party_repository(store).await.create(&librarian).await.expect("should create librarian");
+
+        let name = format!("MATH200_{}", Uuid::new_v4());
+        let res = cmd.execute(CreateReserveListCommandRequest::new(name.as_str(), librarian.party_id.as_str(), 24))
+            .await.expect("should create reserve list");
+        assert_eq!(name, res.list.list_name);
+        assert_eq!(24, res.list.loan_hours);
+    }
+}
@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::books::dto::BookDto;
+use crate::core::command::{Command, CommandError};
+use crate::reserves::domain::ReserveQueryService;
+use crate::reserves::dto::ReserveListDto;
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+
+pub(crate) struct GetReserveListCommand {
+    reserve_service: Box<dyn ReserveQueryService>,
+}
+
+impl GetReserveListCommand {
+    pub(crate) fn new(reserve_service: Box<dyn ReserveQueryService>) -> Self {
+        Self {
+            reserve_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetReserveListCommandRequest {
+    #[serde(default)]
+    pub(crate) list_name: String,
+    pub(crate) page: Option<String>,
+    pub(crate) page_size: Option<usize>,
+}
+
+impl GetReserveListCommandRequest {
+    pub fn new(list_name: &str) -> Self {
+        Self {
+            list_name: list_name.to_string(),
+            page: None,
+            page_size: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetReserveListCommandResponse {
+    pub list: ReserveListDto,
+    pub books: Vec<BookDto>,
+    pub next_page: Option<String>,
+}
+
+impl GetReserveListCommandResponse {
+    pub fn new(list: ReserveListDto, books: Vec<BookDto>, next_page: Option<String>) -> Self {
+        Self {
+            list,
+            books,
+            next_page,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<GetReserveListCommandRequest, GetReserveListCommandResponse> for GetReserveListCommand {
+    async fn execute(&self, req: GetReserveListCommandRequest) -> Result<GetReserveListCommandResponse, CommandError> {
+        let list = self.reserve_service.find_list(req.list_name.as_str()).await.map_err(CommandError::from)?;
+        self.reserve_service.find_books(req.list_name.as_str(), req.page.as_deref(),
+                                        req.page_size.unwrap_or(DEFAULT_PAGE_SIZE))
+            .await.map_err(CommandError::from).map(|res| GetReserveListCommandResponse::new(list, res.records, res.next_page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+    use crate::books::domain::model::BookEntity;
+    use crate::books::factory::create_book_repository;
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::BookStatus;
+    use crate::core::repository::RepositoryStore;
+    use crate::reserves::command::get_reserve_list_cmd::{GetReserveListCommand, GetReserveListCommandRequest};
+    use crate::reserves::domain::model::{ReserveItemEntity, ReserveListEntity};
+    use crate::reserves::factory;
+    use crate::utils::testing::test_store;
+
+    async fn sut_cmd(store: RepositoryStore) -> GetReserveListCommand {
+        let svc = factory::create_reserve_query_service(&Configuration::new("test"), store).await;
+        GetReserveListCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_get_reserve_list() {
+        let store = test_store(module_path!(), "test_should_run_get_reserve_list");
+        let cmd = sut_cmd(store).await;
+        let book = BookEntity::new("isbn", "course text", BookStatus::Available);
+        let _ = create_book_repository(store).await.create(&book).await.expect("should create book");
+        let list = ReserveListEntity::new(format!("ECON101_{}", Uuid::new_v4()).as_str(), 24);
+        let _ = factory::create_reserve_list_repository(store).await.create(&list).await.expect("should create list");
+        let _ = factory::create_reserve_item_repository(store).await
+            .create(&ReserveItemEntity::new(list.list_name.as_str(), book.book_id.as_str(), "librarian")).await.expect("should reserve book");
+
+        let res = cmd.execute(GetReserveListCommandRequest::new(list.list_name.as_str())).await.expect("should get reserve list");
+        assert_eq!(24, res.list.loan_hours);
+        assert_eq!(1, res.books.len());
+        assert_eq!(book.book_id, res.books[0].book_id);
+        assert!(cmd.execute(GetReserveListCommandRequest::new("unknown_list")).await.is_err());
+    }
+}
@@ -0,0 +1,67 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use serde_json::{Value};
+use crate::core::controller::{AppState, command_bus, json_to_server_error, with_common_layers, ServerError};
+use crate::reserves::command::add_reserve_book_cmd::{AddReserveBookCommand, AddReserveBookCommandRequest, AddReserveBookCommandResponse};
+use crate::reserves::command::create_reserve_list_cmd::{CreateReserveListCommand, CreateReserveListCommandRequest, CreateReserveListCommandResponse};
+use crate::reserves::command::get_reserve_list_cmd::{GetReserveListCommand, GetReserveListCommandRequest, GetReserveListCommandResponse};
+use crate::reserves::domain::{ReserveQueryService, ReserveService};
+use crate::reserves::factory;
+use crate::utils::ddb::{build_db_client, create_key_table, create_table};
+
+async fn build_service(state: AppState) -> Box<dyn ReserveService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_key_table(&client, state.store.table_name("reserve_lists").as_str(), "list_name").await;
+    let _ = create_table(&client, state.store.table_name("reserve_items").as_str(), "book_id", "list_name", "added_at").await;
+    factory::create_reserve_service(&state.config, state.store).await
+}
+
+async fn build_query_service(state: AppState) -> Box<dyn ReserveQueryService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_key_table(&client, state.store.table_name("reserve_lists").as_str(), "list_name").await;
+    let _ = create_table(&client, state.store.table_name("reserve_items").as_str(), "book_id", "list_name", "added_at").await;
+    factory::create_reserve_query_service(&state.config, state.store).await
+}
+
+pub(crate) async fn create_reserve_list(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<Json<CreateReserveListCommandResponse>, ServerError> {
+    let req: CreateReserveListCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res = command_bus().register(CreateReserveListCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// returns loan rules of the reserve list along with its books
+pub(crate) async fn find_reserve_list(
+    State(state): State<AppState>,
+    Path(list_name): Path<String>,
+    Query(mut req): Query<GetReserveListCommandRequest>) -> Result<Json<GetReserveListCommandResponse>, ServerError> {
+    req.list_name = list_name;
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(GetReserveListCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn add_reserve_book(
+    State(state): State<AppState>,
+    Path(list_name): Path<String>,
+    json: Json<Value>) -> Result<Json<AddReserveBookCommandResponse>, ServerError> {
+    let mut req: AddReserveBookCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.list_name = list_name;
+    let svc = build_service(state).await;
+    let res = command_bus().register(AddReserveBookCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/reserves", post(create_reserve_list))
+        .route("/reserves/:list", get(find_reserve_list))
+        .route("/reserves/:list/books", post(add_reserve_book));
+    with_common_layers(router, state)
+}
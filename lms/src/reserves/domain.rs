@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use crate::books::dto::BookDto;
+use crate::core::library::{LibraryResult, PaginatedResult};
+use crate::reserves::dto::{ReserveItemDto, ReserveListDto};
+
+pub mod model;
+pub mod query;
+pub mod service;
+
+// read side of course reserves including loan rules that checkout and holds apply
+#[async_trait]
+pub(crate) trait ReserveQueryService: Sync + Send {
+    async fn find_list(&self, list_name: &str) -> LibraryResult<ReserveListDto>;
+    async fn find_books(&self, list_name: &str,
+                        page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookDto>>;
+    // returns loan rules of the reserve list that the book is attached to
+    async fn find_rules_for_book(&self, book_id: &str) -> LibraryResult<Option<ReserveListDto>>;
+}
+
+#[async_trait]
+pub(crate) trait ReserveService: ReserveQueryService {
+    // only librarians and admins can create reserve lists and attach books to them
+    async fn create_list(&self, list: &ReserveListDto) -> LibraryResult<ReserveListDto>;
+    async fn add_book(&self, added_by: &str, list_name: &str, book_id: &str) -> LibraryResult<ReserveItemDto>;
+}
@@ -0,0 +1,85 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::core::domain::Identifiable;
+use crate::utils::date::serializer;
+
+// ReserveListEntity abstracts a named course reserve or short-loan collection along with its loan rules
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct ReserveListEntity {
+    pub list_name: String,
+    pub version: i64,
+    pub branch_id: String,
+    // loan period of books on the list in hours, e.g. 2 hours or 24 hours
+    pub loan_hours: i64,
+    pub renewals_allowed: bool,
+    pub holds_allowed: bool,
+    pub created_by: String,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl ReserveListEntity {
+    pub fn new(list_name: &str, loan_hours: i64) -> Self {
+        Self {
+            list_name: list_name.to_string(),
+            version: 0,
+            branch_id: "".to_string(),
+            loan_hours,
+            renewals_allowed: false,
+            holds_allowed: false,
+            created_by: "".to_string(),
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl Identifiable for ReserveListEntity {
+    fn id(&self) -> String {
+        self.list_name.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+// ReserveItemEntity attaches a book to a reserve list, a book can only be on one reserve list at a time
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct ReserveItemEntity {
+    pub book_id: String,
+    pub list_name: String,
+    pub added_by: String,
+    #[serde(with = "serializer")]
+    pub added_at: NaiveDateTime,
+}
+
+impl ReserveItemEntity {
+    pub fn new(list_name: &str, book_id: &str, added_by: &str) -> Self {
+        Self {
+            book_id: book_id.to_string(),
+            list_name: list_name.to_string(),
+            added_by: added_by.to_string(),
+            added_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::reserves::domain::model::{ReserveItemEntity, ReserveListEntity};
+
+    #[tokio::test]
+    async fn test_should_build_reserve_list() {
+        let list = ReserveListEntity::new("CS101", 2);
+        assert_eq!("CS101", list.list_name.as_str());
+        assert_eq!(2, list.loan_hours);
+        assert!(!list.renewals_allowed);
+        assert!(!list.holds_allowed);
+        let item = ReserveItemEntity::new("CS101", "book1", "librarian1");
+        assert_eq!("CS101", item.list_name.as_str());
+        assert_eq!("book1", item.book_id.as_str());
+    }
+}
@@ -0,0 +1,74 @@
+use async_trait::async_trait;
+use crate::books::dto::BookDto;
+use crate::catalog::domain::CatalogQueryService;
+use crate::core::ids::BookId;
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult};
+use crate::reserves::domain::ReserveQueryService;
+use crate::reserves::dto::ReserveListDto;
+use crate::reserves::repository::{ReserveItemRepository, ReserveListRepository};
+
+pub(crate) struct ReserveQueryServiceImpl {
+    list_repository: Box<dyn ReserveListRepository>,
+    item_repository: Box<dyn ReserveItemRepository>,
+    catalog_service: Box<dyn CatalogQueryService>,
+}
+
+impl ReserveQueryServiceImpl {
+    pub(crate) fn new(list_repository: Box<dyn ReserveListRepository>,
+                      item_repository: Box<dyn ReserveItemRepository>,
+                      catalog_service: Box<dyn CatalogQueryService>) -> Self {
+        Self {
+            list_repository,
+            item_repository,
+            catalog_service,
+        }
+    }
+}
+
+#[async_trait]
+impl ReserveQueryService for ReserveQueryServiceImpl {
+    async fn find_list(&self, list_name: &str) -> LibraryResult<ReserveListDto> {
+        self.list_repository.get(list_name).await.map(|l| ReserveListDto::from(&l))
+    }
+
+    async fn find_books(&self, list_name: &str,
+                        page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookDto>> {
+        let res = self.item_repository.find_by_list(list_name, page, page_size).await?;
+        let mut books = vec![];
+        for item in &res.records {
+            books.push(self.catalog_service.find_book_by_id(&BookId::new(item.book_id.as_str())).await?);
+        }
+        Ok(PaginatedResult::new(page, page_size, res.next_page, books))
+    }
+
+    async fn find_rules_for_book(&self, book_id: &str) -> LibraryResult<Option<ReserveListDto>> {
+        let item = match self.item_repository.get(book_id).await {
+            Ok(item) => item,
+            Err(LibraryError::NotFound { .. }) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        self.find_list(item.list_name.as_str()).await.map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::reserves::domain::ReserveQueryService;
+    use crate::reserves::factory;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn ReserveQueryService> {
+        factory::create_reserve_query_service(&Configuration::new("test"), store).await
+    }
+
+    #[tokio::test]
+    async fn test_should_not_find_rules_for_book_without_reserve() {
+        let store = test_store(module_path!(), "test_should_not_find_rules_for_book_without_reserve");
+        let query_svc = sut_svc(store).await;
+        let rules = query_svc.find_rules_for_book("book_without_reserve").await.expect("should find rules");
+        assert!(rules.is_none());
+        assert!(query_svc.find_list("unknown_reserve_list").await.is_err());
+    }
+}
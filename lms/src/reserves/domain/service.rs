@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::books::dto::BookDto;
+use crate::catalog::domain::CatalogService;
+use crate::core::domain::Configuration;
+use crate::core::events::DomainEvent;
+use crate::core::ids::{BookId, PatronId};
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult};
+use crate::gateway::events::EventPublisher;
+use crate::patrons::domain::PatronService;
+use crate::patrons::Patron;
+use crate::reserves::domain::model::{ReserveItemEntity, ReserveListEntity};
+use crate::reserves::domain::{ReserveQueryService, ReserveService};
+use crate::reserves::dto::{ReserveItemDto, ReserveListDto};
+use crate::reserves::repository::{ReserveItemRepository, ReserveListRepository};
+
+pub(crate) struct ReserveServiceImpl {
+    branch_id: String,
+    list_repository: Box<dyn ReserveListRepository>,
+    item_repository: Box<dyn ReserveItemRepository>,
+    patron_service: Box<dyn PatronService>,
+    catalog_service: Box<dyn CatalogService>,
+    events_publisher: Box<dyn EventPublisher>,
+    query_service: Box<dyn ReserveQueryService>,
+}
+
+impl ReserveServiceImpl {
+    pub(crate) fn new(config: &Configuration, list_repository: Box<dyn ReserveListRepository>,
+                      item_repository: Box<dyn ReserveItemRepository>,
+                      patron_service: Box<dyn PatronService>, catalog_service: Box<dyn CatalogService>,
+                      events_publisher: Box<dyn EventPublisher>,
+                      query_service: Box<dyn ReserveQueryService>) -> Self {
+        Self {
+            branch_id: config.branch_id.to_string(),
+            list_repository,
+            item_repository,
+            patron_service,
+            catalog_service,
+            events_publisher,
+            query_service,
+        }
+    }
+
+    async fn validate_librarian(&self, patron_id: &str) -> LibraryResult<()> {
+        let patron = self.patron_service.find_patron_by_id(&PatronId::new(patron_id)).await?;
+        if !patron.is_librarian() && !patron.is_admin() {
+            return Err(LibraryError::validation(format!("patron {} cannot manage reserves",
+                                                        patron_id).as_str(), Some("400".to_string())));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ReserveService for ReserveServiceImpl {
+    async fn create_list(&self, list: &ReserveListDto) -> LibraryResult<ReserveListDto> {
+        if list.list_name.is_empty() {
+            return Err(LibraryError::validation("reserve list name is required", Some("400".to_string())));
+        }
+        if list.loan_hours <= 0 {
+            return Err(LibraryError::validation("loan hours must be positive", Some("400".to_string())));
+        }
+        self.validate_librarian(list.created_by.as_str()).await?;
+        let mut entity = ReserveListEntity::from(list);
+        entity.branch_id = self.branch_id.to_string();
+        entity.created_at = Utc::now().naive_utc();
+        entity.updated_at = Utc::now().naive_utc();
+        self.list_repository.create(&entity).await?;
+        let list = ReserveListDto::from(&entity);
+        let _ = self.events_publisher.publish(&DomainEvent::added(
+            "reserve_list_created", "reserves", list.list_name.as_str(), &HashMap::new(), &list)?).await?;
+        Ok(list)
+    }
+
+    async fn add_book(&self, added_by: &str, list_name: &str, book_id: &str) -> LibraryResult<ReserveItemDto> {
+        self.validate_librarian(added_by).await?;
+        let _ = self.list_repository.get(list_name).await?;
+        let _ = self.catalog_service.find_book_by_id(&BookId::new(book_id)).await?;
+        let entity = ReserveItemEntity::new(list_name, book_id, added_by);
+        if let Err(err) = self.item_repository.create(&entity).await {
+            return match self.item_repository.get(book_id).await {
+                Ok(existing) => Err(LibraryError::validation(format!("book {} is already on reserve list {}",
+                                                                     book_id, existing.list_name).as_str(), Some("400".to_string()))),
+                Err(_) => Err(err),
+            };
+        }
+        let item = ReserveItemDto::from(&entity);
+        let _ = self.events_publisher.publish(&DomainEvent::added(
+            "book_reserved", "reserves", book_id, &HashMap::new(), &item)?).await?;
+        Ok(item)
+    }
+}
+
+#[async_trait]
+impl ReserveQueryService for ReserveServiceImpl {
+    async fn find_list(&self, list_name: &str) -> LibraryResult<ReserveListDto> {
+        self.query_service.find_list(list_name).await
+    }
+
+    async fn find_books(&self, list_name: &str,
+                        page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<BookDto>> {
+        self.query_service.find_books(list_name, page, page_size).await
+    }
+
+    async fn find_rules_for_book(&self, book_id: &str) -> LibraryResult<Option<ReserveListDto>> {
+        self.query_service.find_rules_for_book(book_id).await
+    }
+}
+
+impl From<&ReserveListDto> for ReserveListEntity {
+    fn from(other: &ReserveListDto) -> ReserveListEntity {
+        ReserveListEntity {
+            list_name: other.list_name.to_string(),
+            version: other.version,
+            branch_id: other.branch_id.to_string(),
+            loan_hours: other.loan_hours,
+            renewals_allowed: other.renewals_allowed,
+            holds_allowed: other.holds_allowed,
+            created_by: other.created_by.to_string(),
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+impl From<&ReserveListEntity> for ReserveListDto {
+    fn from(other: &ReserveListEntity) -> ReserveListDto {
+        ReserveListDto {
+            list_name: other.list_name.to_string(),
+            version: other.version,
+            branch_id: other.branch_id.to_string(),
+            loan_hours: other.loan_hours,
+            renewals_allowed: other.renewals_allowed,
+            holds_allowed: other.holds_allowed,
+            created_by: other.created_by.to_string(),
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+impl From<&ReserveItemEntity> for ReserveItemDto {
+    fn from(other: &ReserveItemEntity) -> ReserveItemDto {
+        ReserveItemDto {
+            book_id: other.book_id.to_string(),
+            list_name: other.list_name.to_string(),
+            added_by: other.added_by.to_string(),
+            added_at: other.added_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::books::domain::model::BookEntity;
+    use crate::books::factory::create_book_repository;
+    use crate::books::repository::BookRepository;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{BookStatus, PartyKind, Role};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::parties::repository::PartyRepository;
+    use crate::reserves::domain::ReserveService;
+    use crate::reserves::dto::ReserveListDto;
+    use crate::reserves::factory;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn ReserveService> {
+        factory::create_reserve_service(&Configuration::new("test"), store).await
+    }
+
+    async fn party_repo(store: RepositoryStore) -> Box<dyn PartyRepository> {
+        create_party_repository(store).await
+    }
+
+    async fn book_repo(store: RepositoryStore) -> Box<dyn BookRepository> {
+        create_book_repository(store).await
+    }
+
+    async fn add_party(store: RepositoryStore, email: &str, role: Role) -> PartyEntity {
+        let mut party = PartyEntity::new(PartyKind::Patron, email);
+        party.group_roles = vec![role.to_string()];
+        let _ = party_repo(store).await.create(&party).await.expect("should create party");
+        party
+    }
+
+    #[tokio::test]
+    async fn test_should_create_list_and_add_books() {
+        let store = test_store(module_path!(), "test_should_create_list_and_add_books");
+        let reserve_svc = sut_svc(store).await;
+        let librarian = add_party(store, "reserve_librarian@example.com", Role::Librarian).await;
+        let book = BookEntity::new("reserve_isbn", "course text", BookStatus::Available);
+        let _ = book_repo(store).await.create(&book).await.expect("should create book");
+
+        // list names are unique so that tests can be rerun against same tables
+        let name = format!("CS101_{}", Uuid::new_v4());
+        let list = ReserveListDto::new(name.as_str(), 2, librarian.party_id.as_str());
+        let list = reserve_svc.create_list(&list).await.expect("should create list");
+        assert_eq!("test", list.branch_id.as_str());
+        assert!(reserve_svc.find_rules_for_book(book.book_id.as_str()).await.expect("should find rules").is_none());
+
+        let item = reserve_svc.add_book(librarian.party_id.as_str(), name.as_str(), book.book_id.as_str()).await.expect("should add book");
+        assert_eq!(name.as_str(), item.list_name.as_str());
+        // book cannot be reserved twice
+        assert!(reserve_svc.add_book(librarian.party_id.as_str(), name.as_str(), book.book_id.as_str()).await.is_err());
+
+        let rules = reserve_svc.find_rules_for_book(book.book_id.as_str()).await.expect("should find rules").expect("should be on reserve");
+        assert_eq!(2, rules.loan_hours);
+        let books = reserve_svc.find_books(name.as_str(), None, 10).await.expect("should find books");
+        assert_eq!(1, books.records.len());
+        assert_eq!(book.book_id, books.records[0].book_id);
+    }
+
+    #[tokio::test]
+    async fn test_should_not_manage_reserves_by_regular_patron() {
+        let store = test_store(module_path!(), "test_should_not_manage_reserves_by_regular_patron");
+        let reserve_svc = sut_svc(store).await;
+        let patron = add_party(store, "reserve_regular@example.com", Role::Regular).await;
+        let librarian = add_party(store, "reserve_librarian2@example.com", Role::Librarian).await;
+        let name = format!("ART100_{}", Uuid::new_v4());
+
+        assert!(reserve_svc.create_list(&ReserveListDto::new(name.as_str(), 24, patron.party_id.as_str())).await.is_err());
+        assert!(reserve_svc.create_list(&ReserveListDto::new(name.as_str(), 0, librarian.party_id.as_str())).await.is_err());
+        let _ = reserve_svc.create_list(&ReserveListDto::new(name.as_str(), 24, librarian.party_id.as_str())).await.expect("should create list");
+        assert!(reserve_svc.add_book(patron.party_id.as_str(), name.as_str(), "book1").await.is_err());
+    }
+}
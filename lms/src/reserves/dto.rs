@@ -0,0 +1,72 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use crate::core::domain::Identifiable;
+use crate::utils::date::{serializer, Rfc3339};
+
+// ReserveListDto abstracts data transfer object for course reserve list and its loan rules
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct ReserveListDto {
+    pub list_name: String,
+    pub version: i64,
+    pub branch_id: String,
+    pub loan_hours: i64,
+    pub renewals_allowed: bool,
+    pub holds_allowed: bool,
+    pub created_by: String,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl ReserveListDto {
+    pub fn new(list_name: &str, loan_hours: i64, created_by: &str) -> Self {
+        Self {
+            list_name: list_name.to_string(),
+            version: 0,
+            branch_id: "".to_string(),
+            loan_hours,
+            renewals_allowed: false,
+            holds_allowed: false,
+            created_by: created_by.to_string(),
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl Identifiable for ReserveListDto {
+    fn id(&self) -> String {
+        self.list_name.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct ReserveItemDto {
+    pub book_id: String,
+    pub list_name: String,
+    pub added_by: String,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub added_at: NaiveDateTime,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::reserves::dto::ReserveListDto;
+
+    #[tokio::test]
+    async fn test_should_build_reserve_list() {
+        let list = ReserveListDto::new("CS101", 24, "librarian1");
+        assert_eq!("CS101", list.list_name.as_str());
+        assert_eq!(24, list.loan_hours);
+        assert_eq!("librarian1", list.created_by.as_str());
+    }
+}
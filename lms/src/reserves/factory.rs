@@ -0,0 +1,60 @@
+use crate::catalog::factory::{create_catalog_query_service, create_catalog_service};
+use crate::core::domain::Configuration;
+use crate::core::repository::RepositoryStore;
+use crate::gateway::factory::create_publisher;
+use crate::patrons::factory::create_patron_service;
+use crate::reserves::domain::{ReserveQueryService, ReserveService};
+use crate::reserves::domain::query::ReserveQueryServiceImpl;
+use crate::reserves::domain::service::ReserveServiceImpl;
+use crate::reserves::factory;
+use crate::reserves::repository::{ReserveItemRepository, ReserveListRepository};
+use crate::reserves::repository::ddb_reserve_item_repository::DDBReserveItemRepository;
+use crate::reserves::repository::ddb_reserve_list_repository::DDBReserveListRepository;
+use crate::utils::ddb::{build_db_client, create_key_table, create_table};
+
+pub(crate) async fn create_reserve_list_repository(store: RepositoryStore) -> Box<dyn ReserveListRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBReserveListRepository::new(client, "reserve_lists"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("reserve_lists");
+            let _ = create_key_table(&client, table_name.as_str(), "list_name").await;
+            Box::new(DDBReserveListRepository::new(client, table_name.as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_reserve_item_repository(store: RepositoryStore) -> Box<dyn ReserveItemRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBReserveItemRepository::new(client, "reserve_items", "reserve_items_ndx"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("reserve_items");
+            let _ = create_table(&client, table_name.as_str(), "book_id", "list_name", "added_at").await;
+            Box::new(DDBReserveItemRepository::new(client, table_name.as_str(), store.table_name("reserve_items_ndx").as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_reserve_query_service(config: &Configuration, store: RepositoryStore) -> Box<dyn ReserveQueryService> {
+    let list_repo = factory::create_reserve_list_repository(store).await;
+    let item_repo = factory::create_reserve_item_repository(store).await;
+    let catalog_svc = create_catalog_query_service(config, store).await;
+    Box::new(ReserveQueryServiceImpl::new(list_repo, item_repo, catalog_svc))
+}
+
+pub(crate) async fn create_reserve_service(config: &Configuration, store: RepositoryStore) -> Box<dyn ReserveService> {
+    let list_repo = factory::create_reserve_list_repository(store).await;
+    let item_repo = factory::create_reserve_item_repository(store).await;
+    let patron_svc = create_patron_service(config, store).await;
+    let catalog_svc = create_catalog_service(config, store).await;
+    let publisher = create_publisher(store.gateway_publisher()).await;
+    let query_svc = create_reserve_query_service(config, store).await;
+    Box::new(ReserveServiceImpl::new(config, list_repo, item_repo, patron_svc, catalog_svc, publisher, query_svc))
+}
@@ -0,0 +1,20 @@
+pub mod ddb_reserve_item_repository;
+pub mod ddb_reserve_list_repository;
+
+use async_trait::async_trait;
+use crate::core::library::{LibraryResult, PaginatedResult};
+use crate::reserves::domain::model::{ReserveItemEntity, ReserveListEntity};
+
+#[async_trait]
+pub(crate) trait ReserveListRepository: Sync + Send {
+    async fn create(&self, entity: &ReserveListEntity) -> LibraryResult<usize>;
+    async fn get(&self, list_name: &str) -> LibraryResult<ReserveListEntity>;
+}
+
+#[async_trait]
+pub(crate) trait ReserveItemRepository: Sync + Send {
+    async fn create(&self, entity: &ReserveItemEntity) -> LibraryResult<usize>;
+    async fn get(&self, book_id: &str) -> LibraryResult<ReserveItemEntity>;
+    async fn find_by_list(&self, list_name: &str,
+                          page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<ReserveItemEntity>>;
+}
@@ -0,0 +1,131 @@
+use std::cmp;
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult};
+use crate::reserves::domain::model::ReserveItemEntity;
+use crate::reserves::repository::ReserveItemRepository;
+use crate::utils::ddb::{from_ddb, parse_date_attribute, parse_item, parse_string_attribute, to_ddb_page};
+
+#[derive(Debug)]
+pub(crate) struct DDBReserveItemRepository {
+    client: Client,
+    table_name: String,
+    index_name: String,
+}
+
+impl DDBReserveItemRepository {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl ReserveItemRepository for DDBReserveItemRepository {
+    async fn create(&self, entity: &ReserveItemEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(book_id)")
+            .set_item(Some(parse_item(val)?))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn get(&self, book_id: &str) -> LibraryResult<ReserveItemEntity> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .query()
+            .table_name(table_name)
+            .limit(1)
+            .consistent_read(true)
+            .key_condition_expression("book_id = :book_id")
+            .expression_attribute_values(":book_id", AttributeValue::S(book_id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).and_then(|req| {
+            if let Some(map) = req.items.as_ref().and_then(|items| items.first()) {
+                return Ok(ReserveItemEntity::from(map));
+            }
+            Err(LibraryError::not_found(format!("reserve not found for {}", book_id).as_str()))
+        })
+    }
+
+    async fn find_by_list(&self, list_name: &str,
+                          page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<ReserveItemEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let predicate = HashMap::from([("list_name".to_string(), list_name.to_string())]);
+        self.client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .limit(cmp::min(page_size, 500) as i32)
+            .consistent_read(false)
+            .set_exclusive_start_key(to_ddb_page(page, &predicate))
+            .key_condition_expression("list_name = :list_name")
+            .expression_attribute_values(":list_name", AttributeValue::S(list_name.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(ReserveItemEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for ReserveItemEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        ReserveItemEntity {
+            book_id: parse_string_attribute("book_id", map).unwrap_or_else(|| String::from("")),
+            list_name: parse_string_attribute("list_name", map).unwrap_or_else(|| String::from("")),
+            added_by: parse_string_attribute("added_by", map).unwrap_or_else(|| String::from("")),
+            added_at: parse_date_attribute("added_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+
+    use crate::core::repository::RepositoryStore;
+    use crate::reserves::domain::model::ReserveItemEntity;
+    use crate::reserves::repository::ddb_reserve_item_repository::DDBReserveItemRepository;
+    use crate::reserves::repository::ReserveItemRepository;
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("reserve_items").as_str(), "book_id", "list_name", "added_at").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_create_find_reserve_items() {
+        let store = test_store(module_path!(), "test_should_create_find_reserve_items");
+        let repo = DDBReserveItemRepository::new(build_client(store).await, store.table_name("reserve_items").as_str(), store.table_name("reserve_items_ndx").as_str());
+        let first = ReserveItemEntity::new("BIO110", "reserve_book1", "librarian1");
+        let second = ReserveItemEntity::new("BIO110", "reserve_book2", "librarian1");
+        assert_eq!(1, repo.create(&first).await.expect("should create reserve item"));
+        assert_eq!(1, repo.create(&second).await.expect("should create reserve item"));
+        // a book cannot be attached to another list
+        assert!(repo.create(&ReserveItemEntity::new("CHEM100", "reserve_book1", "librarian1")).await.is_err());
+
+        let loaded = repo.get("reserve_book1").await.expect("should get reserve item");
+        assert_eq!("BIO110", loaded.list_name.as_str());
+        assert!(repo.get("unknown").await.is_err());
+        let res = repo.find_by_list("BIO110", None, 10).await.expect("should find reserve items");
+        assert_eq!(2, res.records.len());
+    }
+}
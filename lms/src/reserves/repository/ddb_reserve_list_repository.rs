@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::core::library::{LibraryError, LibraryResult};
+use crate::reserves::domain::model::ReserveListEntity;
+use crate::reserves::repository::ReserveListRepository;
+use crate::utils::ddb::{parse_bool_attribute, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute};
+
+#[derive(Debug)]
+pub(crate) struct DDBReserveListRepository {
+    client: Client,
+    table_name: String,
+}
+
+impl DDBReserveListRepository {
+    pub(crate) fn new(client: Client, table_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl ReserveListRepository for DDBReserveListRepository {
+    async fn create(&self, entity: &ReserveListEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(list_name)")
+            .set_item(Some(parse_item(val)?))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn get(&self, list_name: &str) -> LibraryResult<ReserveListEntity> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .query()
+            .table_name(table_name)
+            .limit(1)
+            .consistent_read(true)
+            .key_condition_expression("list_name = :list_name")
+            .expression_attribute_values(":list_name", AttributeValue::S(list_name.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).and_then(|req| {
+            if let Some(map) = req.items.as_ref().and_then(|items| items.first()) {
+                return Ok(ReserveListEntity::from(map));
+            }
+            Err(LibraryError::not_found(format!("reserve list not found for {}", list_name).as_str()))
+        })
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for ReserveListEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        ReserveListEntity {
+            list_name: parse_string_attribute("list_name", map).unwrap_or_else(|| String::from("")),
+            version: parse_number_attribute("version", map),
+            branch_id: parse_string_attribute("branch_id", map).unwrap_or_else(|| String::from("")),
+            loan_hours: parse_number_attribute("loan_hours", map),
+            renewals_allowed: parse_bool_attribute("renewals_allowed", map),
+            holds_allowed: parse_bool_attribute("holds_allowed", map),
+            created_by: parse_string_attribute("created_by", map).unwrap_or_else(|| String::from("")),
+            created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+
+    use crate::core::repository::RepositoryStore;
+    use crate::reserves::domain::model::ReserveListEntity;
+    use crate::reserves::repository::ddb_reserve_list_repository::DDBReserveListRepository;
+    use crate::reserves::repository::ReserveListRepository;
+    use crate::utils::ddb::{build_db_client, create_key_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_key_table(&client, store.table_name("reserve_lists").as_str(), "list_name").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_create_get_reserve_list() {
+        let store = test_store(module_path!(), "test_should_create_get_reserve_list");
+        let repo = DDBReserveListRepository::new(build_client(store).await, store.table_name("reserve_lists").as_str());
+        let mut list = ReserveListEntity::new("HIST200", 24);
+        list.holds_allowed = true;
+        assert_eq!(1, repo.create(&list).await.expect("should create reserve list"));
+        assert!(repo.create(&list).await.is_err());
+
+        let loaded = repo.get("HIST200").await.expect("should get reserve list");
+        assert_eq!(24, loaded.loan_hours);
+        assert!(loaded.holds_allowed);
+        assert!(!loaded.renewals_allowed);
+        assert!(repo.get("unknown").await.is_err());
+    }
+}
@@ -0,0 +1,5 @@
+pub mod add_resource_cmd;
+pub mod book_resource_cmd;
+pub mod cancel_booking_cmd;
+pub mod find_resources_cmd;
+pub mod get_calendar_cmd;
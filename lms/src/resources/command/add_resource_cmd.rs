@@ -0,0 +1,86 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::core::library::ResourceKind;
+use crate::resources::domain::ResourceService;
+use crate::resources::dto::ResourceDto;
+
+pub(crate) struct AddResourceCommand {
+    resource_service: Box<dyn ResourceService>,
+}
+
+impl AddResourceCommand {
+    pub(crate) fn new(resource_service: Box<dyn ResourceService>) -> Self {
+        Self {
+            resource_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AddResourceCommandRequest {
+    pub(crate) name: String,
+    pub(crate) resource_kind: ResourceKind,
+    #[serde(default)]
+    pub(crate) capacity: i64,
+}
+
+impl AddResourceCommandRequest {
+    pub fn new(name: &str, resource_kind: ResourceKind, capacity: i64) -> Self {
+        Self {
+            name: name.to_string(),
+            resource_kind,
+            capacity,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AddResourceCommandResponse {
+    pub resource: ResourceDto,
+}
+
+impl AddResourceCommandResponse {
+    pub fn new(resource: ResourceDto) -> Self {
+        Self {
+            resource,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<AddResourceCommandRequest, AddResourceCommandResponse> for AddResourceCommand {
+    async fn execute(&self, req: AddResourceCommandRequest) -> Result<AddResourceCommandResponse, CommandError> {
+        let mut resource = ResourceDto::new(req.name.as_str(), req.resource_kind);
+        resource.capacity = req.capacity;
+        self.resource_service.add_resource(&resource)
+            .await.map_err(CommandError::from).map(AddResourceCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::ResourceKind;
+    use crate::core::repository::RepositoryStore;
+    use crate::resources::command::add_resource_cmd::{AddResourceCommand, AddResourceCommandRequest};
+    use crate::resources::factory::create_resource_service;
+    use crate::utils::testing::test_store;
+
+    async fn build_sut_cmd(store: RepositoryStore) -> AddResourceCommand {
+        let svc = create_resource_service(&Configuration::new("test"), store).await;
+        AddResourceCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_add_resource() {
+        let store = test_store(module_path!(), "test_should_run_add_resource");
+        let sut_cmd = build_sut_cmd(store).await;
+        let res = sut_cmd.execute(AddResourceCommandRequest::new("Board Room", ResourceKind::Room, 12))
+            .await.expect("should add resource");
+        assert_eq!("Board Room", res.resource.name.as_str());
+        assert_eq!(12, res.resource.capacity);
+    }
+}
@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::resources::domain::ResourceService;
+use crate::resources::dto::BookingDto;
+
+pub(crate) struct BookResourceCommand {
+    resource_service: Box<dyn ResourceService>,
+}
+
+impl BookResourceCommand {
+    pub(crate) fn new(resource_service: Box<dyn ResourceService>) -> Self {
+        Self {
+            resource_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct BookResourceCommandRequest {
+    #[serde(default)]
+    pub(crate) resource_id: String,
+    pub(crate) party_id: String,
+    pub(crate) starts_at: NaiveDateTime,
+    pub(crate) ends_at: NaiveDateTime,
+}
+
+impl BookResourceCommandRequest {
+    pub fn new(resource_id: &str, party_id: &str, starts_at: NaiveDateTime, ends_at: NaiveDateTime) -> Self {
+        Self {
+            resource_id: resource_id.to_string(),
+            party_id: party_id.to_string(),
+            starts_at,
+            ends_at,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct BookResourceCommandResponse {
+    pub booking: BookingDto,
+}
+
+impl BookResourceCommandResponse {
+    pub fn new(booking: BookingDto) -> Self {
+        Self {
+            booking,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<BookResourceCommandRequest, BookResourceCommandResponse> for BookResourceCommand {
+    async fn execute(&self, req: BookResourceCommandRequest) -> Result<BookResourceCommandResponse, CommandError> {
+        self.resource_service.book(req.resource_id.as_str(), req.party_id.as_str(), req.starts_at, req.ends_at)
+            .await.map_err(CommandError::from).map(BookResourceCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{BookingStatus, PartyKind, ResourceKind};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::resources::command::book_resource_cmd::{BookResourceCommand, BookResourceCommandRequest};
+    use crate::resources::domain::ResourceService;
+    use crate::resources::dto::ResourceDto;
+    use crate::resources::factory::create_resource_service;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn ResourceService> {
+        create_resource_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> BookResourceCommand {
+        let svc = create_resource_service(&Configuration::new("test"), store).await;
+        BookResourceCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_book_resource() {
+        let store = test_store(module_path!(), "test_should_run_book_resource");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let patron = PartyEntity::new(PartyKind::Patron, "book_resource@example.com");
+        let _ = create_party_repository(store).await.create(&patron).await.expect("should create patron");
+        let room = svc.add_resource(&ResourceDto::new("Study Room", ResourceKind::Room)).await.expect("should add resource");
+        let starts_at = Utc::now().naive_utc() + Duration::days(1);
+        let res = sut_cmd.execute(BookResourceCommandRequest::new(room.resource_id.as_str(), patron.party_id.as_str(),
+                                                                  starts_at, starts_at + Duration::hours(2)))
+            .await.expect("should book resource");
+        assert_eq!(BookingStatus::Reserved, res.booking.booking_status);
+    }
+}
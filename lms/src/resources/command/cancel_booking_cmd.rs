@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::resources::domain::ResourceService;
+use crate::resources::dto::BookingDto;
+
+pub(crate) struct CancelBookingCommand {
+    resource_service: Box<dyn ResourceService>,
+}
+
+impl CancelBookingCommand {
+    pub(crate) fn new(resource_service: Box<dyn ResourceService>) -> Self {
+        Self {
+            resource_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CancelBookingCommandRequest {
+    #[serde(default)]
+    pub(crate) booking_id: String,
+    pub(crate) party_id: String,
+}
+
+impl CancelBookingCommandRequest {
+    pub fn new(booking_id: &str, party_id: &str) -> Self {
+        Self {
+            booking_id: booking_id.to_string(),
+            party_id: party_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CancelBookingCommandResponse {
+    pub booking: BookingDto,
+}
+
+impl CancelBookingCommandResponse {
+    pub fn new(booking: BookingDto) -> Self {
+        Self {
+            booking,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<CancelBookingCommandRequest, CancelBookingCommandResponse> for CancelBookingCommand {
+    async fn execute(&self, req: CancelBookingCommandRequest) -> Result<CancelBookingCommandResponse, CommandError> {
+        self.resource_service.cancel(req.booking_id.as_str(), req.party_id.as_str())
+            .await.map_err(CommandError::from).map(CancelBookingCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{BookingStatus, PartyKind, ResourceKind};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::resources::command::cancel_booking_cmd::{CancelBookingCommand, CancelBookingCommandRequest};
+    use crate::resources::domain::ResourceService;
+    use crate::resources::dto::ResourceDto;
+    use crate::resources::factory::create_resource_service;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn ResourceService> {
+        create_resource_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> CancelBookingCommand {
+        let svc = create_resource_service(&Configuration::new("test"), store).await;
+        CancelBookingCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_cancel_booking() {
+        let store = test_store(module_path!(), "test_should_run_cancel_booking");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let patron = PartyEntity::new(PartyKind::Patron, "cancel_booking@example.com");
+        let _ = create_party_repository(store).await.create(&patron).await.expect("should create patron");
+        let laptop = svc.add_resource(&ResourceDto::new("Laptop", ResourceKind::Equipment)).await.expect("should add resource");
+        let starts_at = Utc::now().naive_utc() + Duration::days(1);
+        let booking = svc.book(laptop.resource_id.as_str(), patron.party_id.as_str(),
+                               starts_at, starts_at + Duration::hours(4)).await.expect("should book");
+        let res = sut_cmd.execute(CancelBookingCommandRequest::new(booking.booking_id.as_str(), patron.party_id.as_str()))
+            .await.expect("should cancel booking");
+        assert_eq!(BookingStatus::Canceled, res.booking.booking_status);
+    }
+}
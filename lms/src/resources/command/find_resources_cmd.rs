@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::core::library::ResourceKind;
+use crate::resources::domain::ResourceQueryService;
+use crate::resources::dto::ResourceDto;
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+
+pub(crate) struct FindResourcesCommand {
+    resource_service: Box<dyn ResourceQueryService>,
+}
+
+impl FindResourcesCommand {
+    pub(crate) fn new(resource_service: Box<dyn ResourceQueryService>) -> Self {
+        Self {
+            resource_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindResourcesCommandRequest {
+    pub(crate) kind: Option<String>,
+    pub(crate) page: Option<String>,
+    pub(crate) page_size: Option<usize>,
+}
+
+impl FindResourcesCommandRequest {
+    pub fn new(kind: ResourceKind) -> Self {
+        Self {
+            kind: Some(kind.to_string()),
+            page: None,
+            page_size: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindResourcesCommandResponse {
+    pub resources: Vec<ResourceDto>,
+    pub next_page: Option<String>,
+}
+
+impl FindResourcesCommandResponse {
+    pub fn new(resources: Vec<ResourceDto>, next_page: Option<String>) -> Self {
+        Self {
+            resources,
+            next_page,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<FindResourcesCommandRequest, FindResourcesCommandResponse> for FindResourcesCommand {
+    async fn execute(&self, req: FindResourcesCommandRequest) -> Result<FindResourcesCommandResponse, CommandError> {
+        let kind = req.kind.map(ResourceKind::from).unwrap_or(ResourceKind::Room);
+        self.resource_service.find_resources(kind, req.page.as_deref(),
+                                             req.page_size.unwrap_or(DEFAULT_PAGE_SIZE))
+            .await.map_err(CommandError::from).map(|res| FindResourcesCommandResponse::new(res.records, res.next_page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::ResourceKind;
+    use crate::core::repository::RepositoryStore;
+    use crate::resources::command::find_resources_cmd::{FindResourcesCommand, FindResourcesCommandRequest};
+    use crate::resources::domain::ResourceService;
+    use crate::resources::dto::ResourceDto;
+    use crate::resources::factory::{create_resource_service, create_resource_query_service};
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn ResourceService> {
+        create_resource_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> FindResourcesCommand {
+        let svc = create_resource_query_service(&Configuration::new("test"), store).await;
+        FindResourcesCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_find_resources() {
+        let store = test_store(module_path!(), "test_should_run_find_resources");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let projector = svc.add_resource(&ResourceDto::new("Projector", ResourceKind::Equipment)).await.expect("should add resource");
+
+        let res = sut_cmd.execute(FindResourcesCommandRequest::new(ResourceKind::Equipment)).await.expect("should find resources");
+        assert!(res.resources.iter().any(|r| r.resource_id == projector.resource_id));
+    }
+}
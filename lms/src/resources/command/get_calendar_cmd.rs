@@ -0,0 +1,111 @@
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::resources::domain::ResourceQueryService;
+use crate::resources::dto::{BookingDto, ResourceDto};
+
+const DEFAULT_CALENDAR_DAYS: i64 = 7;
+
+pub(crate) struct GetCalendarCommand {
+    resource_service: Box<dyn ResourceQueryService>,
+}
+
+impl GetCalendarCommand {
+    pub(crate) fn new(resource_service: Box<dyn ResourceQueryService>) -> Self {
+        Self {
+            resource_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetCalendarCommandRequest {
+    #[serde(default)]
+    pub(crate) resource_id: String,
+    pub(crate) from: Option<NaiveDateTime>,
+    pub(crate) to: Option<NaiveDateTime>,
+}
+
+impl GetCalendarCommandRequest {
+    pub fn new(resource_id: &str, from: NaiveDateTime, to: NaiveDateTime) -> Self {
+        Self {
+            resource_id: resource_id.to_string(),
+            from: Some(from),
+            to: Some(to),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetCalendarCommandResponse {
+    pub resource: ResourceDto,
+    pub bookings: Vec<BookingDto>,
+}
+
+impl GetCalendarCommandResponse {
+    pub fn new(resource: ResourceDto, bookings: Vec<BookingDto>) -> Self {
+        Self {
+            resource,
+            bookings,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<GetCalendarCommandRequest, GetCalendarCommandResponse> for GetCalendarCommand {
+    async fn execute(&self, req: GetCalendarCommandRequest) -> Result<GetCalendarCommandResponse, CommandError> {
+        // calendar defaults to the upcoming week
+        let from = req.from.unwrap_or_else(|| Utc::now().naive_utc());
+        let to = req.to.unwrap_or(from + Duration::days(DEFAULT_CALENDAR_DAYS));
+        let resource = self.resource_service.find_resource_by_id(req.resource_id.as_str())
+            .await.map_err(CommandError::from)?;
+        self.resource_service.calendar(req.resource_id.as_str(), from, to)
+            .await.map_err(CommandError::from).map(|bookings| GetCalendarCommandResponse::new(resource, bookings))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{PartyKind, ResourceKind};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::resources::command::get_calendar_cmd::{GetCalendarCommand, GetCalendarCommandRequest};
+    use crate::resources::domain::ResourceService;
+    use crate::resources::dto::ResourceDto;
+    use crate::resources::factory::{create_resource_service, create_resource_query_service};
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn ResourceService> {
+        create_resource_service(&Configuration::new("test"), store).await
+    }
+
+    async fn build_sut_cmd(store: RepositoryStore) -> GetCalendarCommand {
+        let svc = create_resource_query_service(&Configuration::new("test"), store).await;
+        GetCalendarCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_get_calendar() {
+        let store = test_store(module_path!(), "test_should_run_get_calendar");
+        let svc = build_svc(store).await;
+        let sut_cmd = build_sut_cmd(store).await;
+        let patron = PartyEntity::new(PartyKind::Patron, "get_calendar@example.com");
+        let _ = create_party_repository(store).await.create(&patron).await.expect("should create patron");
+        let room = svc.add_resource(&ResourceDto::new("Quiet Room", ResourceKind::Room)).await.expect("should add resource");
+        let starts_at = Utc::now().naive_utc() + Duration::days(1);
+        let booking = svc.book(room.resource_id.as_str(), patron.party_id.as_str(),
+                               starts_at, starts_at + Duration::hours(1)).await.expect("should book");
+
+        let res = sut_cmd.execute(GetCalendarCommandRequest::new(room.resource_id.as_str(), starts_at - Duration::days(1),
+                                                                 starts_at + Duration::days(1)))
+            .await.expect("should get calendar");
+        assert_eq!(1, res.bookings.len());
+        assert_eq!(booking.booking_id, res.bookings[0].booking_id);
+    }
+}
@@ -0,0 +1,89 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::post,
+    Router,
+};
+use serde_json::{Value};
+use crate::core::controller::{AppState, command_bus, json_to_server_error, with_common_layers, ServerError};
+use crate::resources::command::add_resource_cmd::{AddResourceCommand, AddResourceCommandRequest, AddResourceCommandResponse};
+use crate::resources::command::book_resource_cmd::{BookResourceCommand, BookResourceCommandRequest, BookResourceCommandResponse};
+use crate::resources::command::cancel_booking_cmd::{CancelBookingCommand, CancelBookingCommandRequest, CancelBookingCommandResponse};
+use crate::resources::command::find_resources_cmd::{FindResourcesCommand, FindResourcesCommandRequest, FindResourcesCommandResponse};
+use crate::resources::command::get_calendar_cmd::{GetCalendarCommand, GetCalendarCommandRequest, GetCalendarCommandResponse};
+use crate::resources::domain::{ResourceQueryService, ResourceService};
+use crate::resources::factory;
+use crate::utils::ddb::{build_db_client, create_table};
+
+async fn build_service(state: AppState) -> Box<dyn ResourceService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_table(&client, state.store.table_name("resources").as_str(), "resource_id", "resource_kind", "name").await;
+    let _ = create_table(&client, state.store.table_name("resource_bookings").as_str(), "booking_id", "resource_id", "starts_at").await;
+    factory::create_resource_service(&state.config, state.store).await
+}
+
+async fn build_query_service(state: AppState) -> Box<dyn ResourceQueryService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_table(&client, state.store.table_name("resources").as_str(), "resource_id", "resource_kind", "name").await;
+    let _ = create_table(&client, state.store.table_name("resource_bookings").as_str(), "booking_id", "resource_id", "starts_at").await;
+    factory::create_resource_query_service(&state.config, state.store).await
+}
+
+pub(crate) async fn add_resource(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<Json<AddResourceCommandResponse>, ServerError> {
+    let req: AddResourceCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res = command_bus().register(AddResourceCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn find_resources(
+    State(state): State<AppState>,
+    Query(req): Query<FindResourcesCommandRequest>) -> Result<Json<FindResourcesCommandResponse>, ServerError> {
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(FindResourcesCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// booking fails with a conflict if the time slot overlaps an existing booking
+pub(crate) async fn book_resource(
+    State(state): State<AppState>,
+    Path(resource_id): Path<String>,
+    json: Json<Value>) -> Result<Json<BookResourceCommandResponse>, ServerError> {
+    let mut req: BookResourceCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.resource_id = resource_id;
+    let svc = build_service(state).await;
+    let res = command_bus().register(BookResourceCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// calendar accepts optional from and to query parameters
+pub(crate) async fn get_calendar(
+    State(state): State<AppState>,
+    Path(resource_id): Path<String>,
+    Query(mut req): Query<GetCalendarCommandRequest>) -> Result<Json<GetCalendarCommandResponse>, ServerError> {
+    req.resource_id = resource_id;
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(GetCalendarCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn cancel_booking(
+    State(state): State<AppState>,
+    Path((_resource_id, booking_id)): Path<(String, String)>,
+    json: Json<Value>) -> Result<Json<CancelBookingCommandResponse>, ServerError> {
+    let mut req: CancelBookingCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.booking_id = booking_id;
+    let svc = build_service(state).await;
+    let res = command_bus().register(CancelBookingCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/resources", post(add_resource).get(find_resources))
+        .route("/resources/:id/bookings", post(book_resource).get(get_calendar))
+        .route("/resources/:id/bookings/:booking_id/cancel", post(cancel_booking));
+    with_common_layers(router, state)
+}
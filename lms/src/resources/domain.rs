@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use crate::core::library::{LibraryResult, PaginatedResult, ResourceKind};
+use crate::resources::dto::{BookingDto, ResourceDto};
+
+pub mod model;
+pub mod query;
+pub mod service;
+
+// read side of bookable resources and their calendars
+#[async_trait]
+pub(crate) trait ResourceQueryService: Sync + Send {
+    async fn find_resource_by_id(&self, resource_id: &str) -> LibraryResult<ResourceDto>;
+    async fn find_resources(&self, kind: ResourceKind,
+                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<ResourceDto>>;
+    // returns active bookings of the resource within the time window ordered by start time
+    async fn calendar(&self, resource_id: &str,
+                      from: NaiveDateTime, to: NaiveDateTime) -> LibraryResult<Vec<BookingDto>>;
+}
+
+#[async_trait]
+pub(crate) trait ResourceService: ResourceQueryService {
+    async fn add_resource(&self, resource: &ResourceDto) -> LibraryResult<ResourceDto>;
+    // reserves the resource for the party unless the time slot conflicts with another booking
+    async fn book(&self, resource_id: &str, party_id: &str,
+                  starts_at: NaiveDateTime, ends_at: NaiveDateTime) -> LibraryResult<BookingDto>;
+    async fn cancel(&self, booking_id: &str, party_id: &str) -> LibraryResult<BookingDto>;
+}
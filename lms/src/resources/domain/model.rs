@@ -0,0 +1,127 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::core::domain::Identifiable;
+use crate::core::library::{BookingStatus, ResourceKind};
+use crate::utils::date::serializer;
+
+// ResourceEntity abstracts a lendable resource such as meeting room or laptop
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct ResourceEntity {
+    pub resource_id: String,
+    pub version: i64,
+    pub branch_id: String,
+    pub name: String,
+    pub resource_kind: ResourceKind,
+    // number of seats for rooms
+    pub capacity: i64,
+    pub active: bool,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl ResourceEntity {
+    pub fn new(name: &str, resource_kind: ResourceKind) -> Self {
+        Self {
+            resource_id: Uuid::new_v4().to_string(),
+            version: 0,
+            branch_id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            resource_kind,
+            capacity: 0,
+            active: true,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl Identifiable for ResourceEntity {
+    fn id(&self) -> String {
+        self.resource_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+// BookingEntity abstracts reservation of a resource by a party for a time slot
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct BookingEntity {
+    pub booking_id: String,
+    pub version: i64,
+    pub resource_id: String,
+    pub party_id: String,
+    pub booking_status: BookingStatus,
+    #[serde(with = "serializer")]
+    pub starts_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub ends_at: NaiveDateTime,
+    pub canceled_at: Option<NaiveDateTime>,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl BookingEntity {
+    pub fn new(resource_id: &str, party_id: &str, starts_at: NaiveDateTime, ends_at: NaiveDateTime) -> Self {
+        Self {
+            booking_id: Uuid::new_v4().to_string(),
+            version: 0,
+            resource_id: resource_id.to_string(),
+            party_id: party_id.to_string(),
+            booking_status: BookingStatus::Reserved,
+            starts_at,
+            ends_at,
+            canceled_at: None,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+
+    // time slots are half-open so that a booking can start when the previous one ends
+    pub fn overlaps(&self, starts_at: NaiveDateTime, ends_at: NaiveDateTime) -> bool {
+        self.booking_status == BookingStatus::Reserved && self.starts_at < ends_at && starts_at < self.ends_at
+    }
+}
+
+impl Identifiable for BookingEntity {
+    fn id(&self) -> String {
+        self.booking_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::library::{BookingStatus, ResourceKind};
+    use crate::resources::domain::model::{BookingEntity, ResourceEntity};
+
+    #[tokio::test]
+    async fn test_should_build_resource() {
+        let resource = ResourceEntity::new("Room A", ResourceKind::Room);
+        assert_eq!("Room A", resource.name.as_str());
+        assert_eq!(ResourceKind::Room, resource.resource_kind);
+        assert!(resource.active);
+    }
+
+    #[tokio::test]
+    async fn test_should_detect_overlapping_bookings() {
+        let now = Utc::now().naive_utc();
+        let mut booking = BookingEntity::new("room1", "patron1", now, now + Duration::hours(2));
+        assert!(booking.overlaps(now + Duration::hours(1), now + Duration::hours(3)));
+        assert!(booking.overlaps(now - Duration::hours(1), now + Duration::minutes(1)));
+        assert!(!booking.overlaps(now + Duration::hours(2), now + Duration::hours(3)));
+        assert!(!booking.overlaps(now - Duration::hours(1), now));
+        booking.booking_status = BookingStatus::Canceled;
+        assert!(!booking.overlaps(now + Duration::hours(1), now + Duration::hours(3)));
+    }
+}
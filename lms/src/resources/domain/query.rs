@@ -0,0 +1,47 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult, ResourceKind};
+use crate::resources::domain::ResourceQueryService;
+use crate::resources::dto::{BookingDto, ResourceDto};
+use crate::resources::repository::{BookingRepository, ResourceRepository};
+
+pub(crate) struct ResourceQueryServiceImpl {
+    resource_repository: Box<dyn ResourceRepository>,
+    booking_repository: Box<dyn BookingRepository>,
+}
+
+impl ResourceQueryServiceImpl {
+    pub(crate) fn new(resource_repository: Box<dyn ResourceRepository>,
+                      booking_repository: Box<dyn BookingRepository>) -> Self {
+        Self {
+            resource_repository,
+            booking_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl ResourceQueryService for ResourceQueryServiceImpl {
+    async fn find_resource_by_id(&self, resource_id: &str) -> LibraryResult<ResourceDto> {
+        self.resource_repository.get(resource_id).await.map(|resource| ResourceDto::from(&resource))
+    }
+
+    async fn find_resources(&self, kind: ResourceKind,
+                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<ResourceDto>> {
+        let res = self.resource_repository.find_by_kind(kind, page, page_size).await?;
+        let records = res.records.iter().map(ResourceDto::from).collect();
+        Ok(PaginatedResult::new(page, page_size, res.next_page, records))
+    }
+
+    async fn calendar(&self, resource_id: &str,
+                      from: NaiveDateTime, to: NaiveDateTime) -> LibraryResult<Vec<BookingDto>> {
+        if to <= from {
+            return Err(LibraryError::validation("calendar window must end after it starts", Some("400".to_string())));
+        }
+        let _ = self.resource_repository.get(resource_id).await?;
+        let bookings = self.booking_repository.find_overlapping(resource_id, from, to).await?;
+        Ok(bookings.iter()
+            .filter(|booking| booking.overlaps(from, to))
+            .map(BookingDto::from).collect())
+    }
+}
@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+
+use crate::core::domain::Configuration;
+use crate::core::events::DomainEvent;
+use crate::core::library::{BookingStatus, LibraryError, LibraryResult, PaginatedResult, PartyKind, ResourceKind};
+use crate::gateway::events::EventPublisher;
+use crate::parties::repository::PartyRepository;
+use crate::resources::domain::model::{BookingEntity, ResourceEntity};
+use crate::resources::domain::{ResourceQueryService, ResourceService};
+use crate::resources::dto::{BookingDto, ResourceDto};
+use crate::resources::repository::{BookingRepository, ResourceRepository};
+
+pub(crate) struct ResourceServiceImpl {
+    branch_id: String,
+    resource_repository: Box<dyn ResourceRepository>,
+    booking_repository: Box<dyn BookingRepository>,
+    party_repository: Box<dyn PartyRepository>,
+    events_publisher: Box<dyn EventPublisher>,
+    query_service: Box<dyn ResourceQueryService>,
+}
+
+impl ResourceServiceImpl {
+    pub(crate) fn new(config: &Configuration, resource_repository: Box<dyn ResourceRepository>,
+                      booking_repository: Box<dyn BookingRepository>, party_repository: Box<dyn PartyRepository>,
+                      events_publisher: Box<dyn EventPublisher>,
+                      query_service: Box<dyn ResourceQueryService>) -> Self {
+        Self {
+            branch_id: config.branch_id.to_string(),
+            resource_repository,
+            booking_repository,
+            party_repository,
+            events_publisher,
+            query_service,
+        }
+    }
+
+    // borrowers can be any active person in the party model such as patrons or employees
+    async fn validate_borrower(&self, party_id: &str) -> LibraryResult<()> {
+        let party = self.party_repository.get(party_id).await?;
+        if !party.active || party.kind == PartyKind::Organization {
+            return Err(LibraryError::validation(format!("party {} cannot book resources",
+                                                        party_id).as_str(), Some("400".to_string())));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ResourceService for ResourceServiceImpl {
+    async fn add_resource(&self, resource: &ResourceDto) -> LibraryResult<ResourceDto> {
+        if resource.name.is_empty() || resource.capacity < 0 {
+            return Err(LibraryError::validation("resource name and non-negative capacity are required",
+                                                Some("400".to_string())));
+        }
+        let mut entity = ResourceEntity::from(resource);
+        entity.branch_id = self.branch_id.to_string();
+        self.resource_repository.create(&entity).await?;
+        let dto = ResourceDto::from(&entity);
+        let _ = self.events_publisher.publish(&DomainEvent::added(
+            "resource_added", "resources", dto.resource_id.as_str(), &HashMap::new(), &dto)?).await?;
+        Ok(dto)
+    }
+
+    async fn book(&self, resource_id: &str, party_id: &str,
+                  starts_at: NaiveDateTime, ends_at: NaiveDateTime) -> LibraryResult<BookingDto> {
+        if ends_at <= starts_at {
+            return Err(LibraryError::validation("booking must end after it starts", Some("400".to_string())));
+        }
+        if starts_at < Utc::now().naive_utc() {
+            return Err(LibraryError::validation("booking must start in future", Some("400".to_string())));
+        }
+        let resource = self.resource_repository.get(resource_id).await?;
+        if !resource.active {
+            return Err(LibraryError::validation(format!("resource {} is not available for booking",
+                                                        resource_id).as_str(), Some("400".to_string())));
+        }
+        self.validate_borrower(party_id).await?;
+        let existing = self.booking_repository.find_overlapping(resource_id, starts_at, ends_at).await?;
+        if let Some(conflict) = existing.iter().find(|booking| booking.overlaps(starts_at, ends_at)) {
+            return Err(LibraryError::validation(format!("resource {} is already booked from {} to {}",
+                                                        resource_id, conflict.starts_at, conflict.ends_at).as_str(), Some("409".to_string())));
+        }
+        let booking = BookingEntity::new(resource_id, party_id, starts_at, ends_at);
+        self.booking_repository.create(&booking).await?;
+        let dto = BookingDto::from(&booking);
+        let _ = self.events_publisher.publish(&DomainEvent::added(
+            "resource_booked", "resources", dto.booking_id.as_str(), &HashMap::new(), &dto)?).await?;
+        Ok(dto)
+    }
+
+    async fn cancel(&self, booking_id: &str, party_id: &str) -> LibraryResult<BookingDto> {
+        let mut booking = self.booking_repository.get(booking_id).await?;
+        if booking.party_id != party_id {
+            return Err(LibraryError::validation(format!("booking {} does not belong to {}",
+                                                        booking_id, party_id).as_str(), Some("400".to_string())));
+        }
+        if booking.booking_status != BookingStatus::Reserved {
+            return Err(LibraryError::validation(format!("booking {} is already canceled",
+                                                        booking_id).as_str(), Some("400".to_string())));
+        }
+        booking.booking_status = BookingStatus::Canceled;
+        booking.canceled_at = Some(Utc::now().naive_utc());
+        self.booking_repository.update(&booking).await?;
+        booking.version += 1;
+        let dto = BookingDto::from(&booking);
+        let _ = self.events_publisher.publish(&DomainEvent::updated(
+            "booking_canceled", "resources", dto.booking_id.as_str(), &HashMap::new(), &dto)?).await?;
+        Ok(dto)
+    }
+}
+
+#[async_trait]
+impl ResourceQueryService for ResourceServiceImpl {
+    async fn find_resource_by_id(&self, resource_id: &str) -> LibraryResult<ResourceDto> {
+        self.query_service.find_resource_by_id(resource_id).await
+    }
+
+    async fn find_resources(&self, kind: ResourceKind,
+                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<ResourceDto>> {
+        self.query_service.find_resources(kind, page, page_size).await
+    }
+
+    async fn calendar(&self, resource_id: &str,
+                      from: NaiveDateTime, to: NaiveDateTime) -> LibraryResult<Vec<BookingDto>> {
+        self.query_service.calendar(resource_id, from, to).await
+    }
+}
+
+impl From<&ResourceDto> for ResourceEntity {
+    fn from(other: &ResourceDto) -> ResourceEntity {
+        ResourceEntity {
+            resource_id: other.resource_id.to_string(),
+            version: other.version,
+            branch_id: other.branch_id.to_string(),
+            name: other.name.to_string(),
+            resource_kind: other.resource_kind,
+            capacity: other.capacity,
+            active: other.active,
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+impl From<&ResourceEntity> for ResourceDto {
+    fn from(other: &ResourceEntity) -> ResourceDto {
+        ResourceDto {
+            resource_id: other.resource_id.to_string(),
+            version: other.version,
+            branch_id: other.branch_id.to_string(),
+            name: other.name.to_string(),
+            resource_kind: other.resource_kind,
+            capacity: other.capacity,
+            active: other.active,
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+impl From<&BookingEntity> for BookingDto {
+    fn from(other: &BookingEntity) -> BookingDto {
+        BookingDto {
+            booking_id: other.booking_id.to_string(),
+            version: other.version,
+            resource_id: other.resource_id.to_string(),
+            party_id: other.party_id.to_string(),
+            booking_status: other.booking_status,
+            starts_at: other.starts_at,
+            ends_at: other.ends_at,
+            canceled_at: other.canceled_at,
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDateTime, Timelike, Utc};
+
+    use crate::core::domain::Configuration;
+    use crate::core::library::{BookingStatus, PartyKind, ResourceKind};
+    use crate::core::repository::RepositoryStore;
+    use crate::parties::domain::model::PartyEntity;
+    use crate::parties::factory::create_party_repository;
+    use crate::parties::repository::PartyRepository;
+    use crate::resources::domain::ResourceService;
+    use crate::resources::dto::ResourceDto;
+    use crate::resources::factory;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn ResourceService> {
+        factory::create_resource_service(&Configuration::new("test"), store).await
+    }
+
+    async fn party_repo(store: RepositoryStore) -> Box<dyn PartyRepository> {
+        create_party_repository(store).await
+    }
+
+    async fn add_party(store: RepositoryStore, kind: PartyKind, email: &str) -> PartyEntity {
+        let party = PartyEntity::new(kind, email);
+        let _ = party_repo(store).await.create(&party).await.expect("should create party");
+        party
+    }
+
+    fn tomorrow_at(hour: u32) -> NaiveDateTime {
+        (Utc::now().naive_utc() + Duration::days(1)).with_hour(hour).unwrap()
+            .with_minute(0).unwrap().with_second(0).unwrap().with_nanosecond(0).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_should_book_cancel_and_query_calendar() {
+        let store = test_store(module_path!(), "test_should_book_cancel_and_query_calendar");
+        let resource_svc = sut_svc(store).await;
+        let patron = add_party(store, PartyKind::Patron, "room_patron@example.com").await;
+        let employee = add_party(store, PartyKind::Employee, "room_employee@example.com").await;
+        let mut room = ResourceDto::new("Meeting Room", ResourceKind::Room);
+        room.capacity = 8;
+        let room = resource_svc.add_resource(&room).await.expect("should add resource");
+
+        let booking = resource_svc.book(room.resource_id.as_str(), patron.party_id.as_str(),
+                                        tomorrow_at(9), tomorrow_at(11)).await.expect("should book");
+        assert_eq!(BookingStatus::Reserved, booking.booking_status);
+        // overlapping time slot should be rejected
+        assert!(resource_svc.book(room.resource_id.as_str(), employee.party_id.as_str(),
+                                  tomorrow_at(10), tomorrow_at(12)).await.is_err());
+        // adjacent time slot should be accepted
+        let _ = resource_svc.book(room.resource_id.as_str(), employee.party_id.as_str(),
+                                  tomorrow_at(11), tomorrow_at(12)).await.expect("should book");
+
+        let calendar = resource_svc.calendar(room.resource_id.as_str(), tomorrow_at(8), tomorrow_at(18))
+            .await.expect("should return calendar");
+        assert_eq!(2, calendar.len());
+        assert_eq!(tomorrow_at(9), calendar[0].starts_at);
+
+        // only the borrower can cancel the booking
+        assert!(resource_svc.cancel(booking.booking_id.as_str(), employee.party_id.as_str()).await.is_err());
+        let canceled = resource_svc.cancel(booking.booking_id.as_str(), patron.party_id.as_str()).await.expect("should cancel");
+        assert_eq!(BookingStatus::Canceled, canceled.booking_status);
+        let _ = resource_svc.book(room.resource_id.as_str(), employee.party_id.as_str(),
+                                  tomorrow_at(10), tomorrow_at(11)).await.expect("should book canceled slot");
+        let calendar = resource_svc.calendar(room.resource_id.as_str(), tomorrow_at(8), tomorrow_at(18))
+            .await.expect("should return calendar");
+        assert_eq!(2, calendar.len());
+        assert!(calendar.iter().all(|b| b.party_id == employee.party_id));
+    }
+
+    #[tokio::test]
+    async fn test_should_not_book_invalid_slot_or_borrower() {
+        let store = test_store(module_path!(), "test_should_not_book_invalid_slot_or_borrower");
+        let resource_svc = sut_svc(store).await;
+        let patron = add_party(store, PartyKind::Patron, "laptop_patron@example.com").await;
+        let vendor = add_party(store, PartyKind::Organization, "laptop_vendor@example.com").await;
+        let laptop = resource_svc.add_resource(&ResourceDto::new("Laptop", ResourceKind::Equipment))
+            .await.expect("should add resource");
+
+        assert!(resource_svc.book(laptop.resource_id.as_str(), patron.party_id.as_str(),
+                                  tomorrow_at(11), tomorrow_at(9)).await.is_err());
+        assert!(resource_svc.book(laptop.resource_id.as_str(), vendor.party_id.as_str(),
+                                  tomorrow_at(9), tomorrow_at(11)).await.is_err());
+        assert!(resource_svc.book("unknown", patron.party_id.as_str(),
+                                  tomorrow_at(9), tomorrow_at(11)).await.is_err());
+        assert!(resource_svc.add_resource(&ResourceDto::new("", ResourceKind::Room)).await.is_err());
+
+        let res = resource_svc.find_resources(ResourceKind::Equipment, None, 100).await.expect("should find resources");
+        assert!(res.records.iter().any(|r| r.resource_id == laptop.resource_id));
+    }
+}
@@ -0,0 +1,86 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use uuid::Uuid;
+use crate::core::domain::Identifiable;
+use crate::core::library::{BookingStatus, ResourceKind};
+use crate::utils::date::{serializer, Rfc3339};
+
+// ResourceDto abstracts data transfer object for lendable rooms and equipment
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct ResourceDto {
+    pub resource_id: String,
+    pub version: i64,
+    pub branch_id: String,
+    pub name: String,
+    pub resource_kind: ResourceKind,
+    pub capacity: i64,
+    pub active: bool,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl ResourceDto {
+    pub fn new(name: &str, resource_kind: ResourceKind) -> Self {
+        Self {
+            resource_id: Uuid::new_v4().to_string(),
+            version: 0,
+            branch_id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            resource_kind,
+            capacity: 0,
+            active: true,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl Identifiable for ResourceDto {
+    fn id(&self) -> String {
+        self.resource_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct BookingDto {
+    pub booking_id: String,
+    pub version: i64,
+    pub resource_id: String,
+    pub party_id: String,
+    pub booking_status: BookingStatus,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub starts_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub ends_at: NaiveDateTime,
+    pub canceled_at: Option<NaiveDateTime>,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    #[schemars(with = "Rfc3339")]
+    pub updated_at: NaiveDateTime,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::library::ResourceKind;
+    use crate::resources::dto::ResourceDto;
+
+    #[tokio::test]
+    async fn test_should_build_resource() {
+        let resource = ResourceDto::new("Laptop 1", ResourceKind::Equipment);
+        assert_eq!("Laptop 1", resource.name.as_str());
+        assert_eq!(ResourceKind::Equipment, resource.resource_kind);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::core::domain::Configuration;
+use crate::core::repository::RepositoryStore;
+use crate::gateway::factory::create_publisher;
+use crate::parties::factory::create_party_repository;
+use crate::resources::domain::{ResourceQueryService, ResourceService};
+use crate::resources::domain::query::ResourceQueryServiceImpl;
+use crate::resources::domain::service::ResourceServiceImpl;
+use crate::resources::factory;
+use crate::resources::repository::ddb_booking_repository::DDBBookingRepository;
+use crate::resources::repository::ddb_resource_repository::DDBResourceRepository;
+use crate::resources::repository::{BookingRepository, ResourceRepository};
+use crate::utils::ddb::{build_db_client, create_table};
+
+pub(crate) async fn create_resource_repository(store: RepositoryStore) -> Box<dyn ResourceRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBResourceRepository::new(client, "resources", "resources_ndx"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("resources");
+            let _ = create_table(&client, table_name.as_str(), "resource_id", "resource_kind", "name").await;
+            Box::new(DDBResourceRepository::new(client, table_name.as_str(), store.table_name("resources_ndx").as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_booking_repository(store: RepositoryStore) -> Box<dyn BookingRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBBookingRepository::new(client, "resource_bookings", "resource_bookings_ndx"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("resource_bookings");
+            let _ = create_table(&client, table_name.as_str(), "booking_id", "resource_id", "starts_at").await;
+            Box::new(DDBBookingRepository::new(client, table_name.as_str(), store.table_name("resource_bookings_ndx").as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_resource_query_service(_config: &Configuration, store: RepositoryStore) -> Box<dyn ResourceQueryService> {
+    let resource_repo = factory::create_resource_repository(store).await;
+    let booking_repo = factory::create_booking_repository(store).await;
+    Box::new(ResourceQueryServiceImpl::new(resource_repo, booking_repo))
+}
+
+pub(crate) async fn create_resource_service(config: &Configuration, store: RepositoryStore) -> Box<dyn ResourceService> {
+    let resource_repo = factory::create_resource_repository(store).await;
+    let booking_repo = factory::create_booking_repository(store).await;
+    let party_repo = create_party_repository(store).await;
+    let publisher = create_publisher(store.gateway_publisher()).await;
+    let query_svc = create_resource_query_service(config, store).await;
+    Box::new(ResourceServiceImpl::new(config, resource_repo, booking_repo, party_repo, publisher, query_svc))
+}
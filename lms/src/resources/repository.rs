@@ -0,0 +1,25 @@
+pub mod ddb_booking_repository;
+pub mod ddb_resource_repository;
+
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use crate::core::library::{LibraryResult, PaginatedResult, ResourceKind};
+use crate::resources::domain::model::{BookingEntity, ResourceEntity};
+
+#[async_trait]
+pub(crate) trait ResourceRepository: Sync + Send {
+    async fn create(&self, entity: &ResourceEntity) -> LibraryResult<usize>;
+    async fn get(&self, resource_id: &str) -> LibraryResult<ResourceEntity>;
+    async fn find_by_kind(&self, kind: ResourceKind,
+                          page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<ResourceEntity>>;
+}
+
+#[async_trait]
+pub(crate) trait BookingRepository: Sync + Send {
+    async fn create(&self, entity: &BookingEntity) -> LibraryResult<usize>;
+    async fn update(&self, entity: &BookingEntity) -> LibraryResult<usize>;
+    async fn get(&self, booking_id: &str) -> LibraryResult<BookingEntity>;
+    // returns bookings of the resource that start before `to` and end after `from` ordered by start time
+    async fn find_overlapping(&self, resource_id: &str,
+                              from: NaiveDateTime, to: NaiveDateTime) -> LibraryResult<Vec<BookingEntity>>;
+}
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::{NaiveDateTime, Utc};
+
+use crate::core::library::{BookingStatus, LibraryError, LibraryResult};
+use crate::resources::domain::model::BookingEntity;
+use crate::resources::repository::BookingRepository;
+use crate::utils::ddb::{opt_string_date, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, string_date};
+
+#[derive(Debug)]
+pub(crate) struct DDBBookingRepository {
+    client: Client,
+    table_name: String,
+    index_name: String,
+}
+
+impl DDBBookingRepository {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl BookingRepository for DDBBookingRepository {
+    async fn create(&self, entity: &BookingEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        let mut item = parse_item(val)?;
+        // time slots are stored in the same format as query bounds so that they can be compared
+        item.insert("starts_at".to_string(), string_date(entity.starts_at));
+        item.insert("ends_at".to_string(), string_date(entity.ends_at));
+        item.insert("created_at".to_string(), string_date(entity.created_at));
+        item.insert("updated_at".to_string(), string_date(entity.updated_at));
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(booking_id)")
+            .set_item(Some(item))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn update(&self, entity: &BookingEntity) -> LibraryResult<usize> {
+        let now = Utc::now().naive_utc();
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .update_item()
+            .table_name(table_name)
+            .key("booking_id", AttributeValue::S(entity.booking_id.clone()))
+            .update_expression("SET version = :version, booking_status = :booking_status, canceled_at = :canceled_at, updated_at = :updated_at")
+            .expression_attribute_values(":old_version", AttributeValue::N(entity.version.to_string()))
+            .expression_attribute_values(":version", AttributeValue::N((entity.version + 1).to_string()))
+            .expression_attribute_values(":booking_status", AttributeValue::S(entity.booking_status.to_string()))
+            .expression_attribute_values(":canceled_at", opt_string_date(entity.canceled_at))
+            .expression_attribute_values(":updated_at", string_date(now))
+            .condition_expression("attribute_exists(version) AND version = :old_version")
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn get(&self, booking_id: &str) -> LibraryResult<BookingEntity> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .query()
+            .table_name(table_name)
+            .limit(1)
+            .consistent_read(true)
+            .key_condition_expression("booking_id = :booking_id")
+            .expression_attribute_values(":booking_id", AttributeValue::S(booking_id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).and_then(|req| {
+            if let Some(map) = req.items.as_ref().and_then(|items| items.first()) {
+                return Ok(BookingEntity::from(map));
+            }
+            Err(LibraryError::not_found(format!("booking not found for {}", booking_id).as_str()))
+        })
+    }
+
+    async fn find_overlapping(&self, resource_id: &str,
+                              from: NaiveDateTime, to: NaiveDateTime) -> LibraryResult<Vec<BookingEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let mut bookings = vec![];
+        let mut last_key: Option<HashMap<String, AttributeValue>> = None;
+        loop {
+            let res = self.client
+                .query()
+                .table_name(table_name)
+                .index_name(index_name)
+                .consistent_read(false)
+                .set_exclusive_start_key(last_key)
+                .key_condition_expression("resource_id = :resource_id AND starts_at < :to")
+                .filter_expression("ends_at > :from")
+                .expression_attribute_values(":resource_id", AttributeValue::S(resource_id.to_string()))
+                .expression_attribute_values(":to", string_date(to))
+                .expression_attribute_values(":from", string_date(from))
+                .send()
+                .await.map_err(LibraryError::from)?;
+            bookings.extend(res.items.as_ref().unwrap_or(&vec![]).iter().map(BookingEntity::from));
+            last_key = res.last_evaluated_key().cloned();
+            if last_key.is_none() {
+                break;
+            }
+        }
+        Ok(bookings)
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for BookingEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        BookingEntity {
+            booking_id: parse_string_attribute("booking_id", map).unwrap_or_else(|| String::from("")),
+            version: parse_number_attribute("version", map),
+            resource_id: parse_string_attribute("resource_id", map).unwrap_or_else(|| String::from("")),
+            party_id: parse_string_attribute("party_id", map).unwrap_or_else(|| String::from("")),
+            booking_status: BookingStatus::from(parse_string_attribute("booking_status", map).unwrap_or_else(|| String::from(""))),
+            starts_at: parse_date_attribute("starts_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            ends_at: parse_date_attribute("ends_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            canceled_at: parse_date_attribute("canceled_at", map),
+            created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+    use chrono::NaiveDateTime;
+
+    use crate::core::library::BookingStatus;
+    use crate::core::repository::RepositoryStore;
+    use crate::resources::domain::model::BookingEntity;
+    use crate::resources::repository::ddb_booking_repository::DDBBookingRepository;
+    use crate::resources::repository::BookingRepository;
+    use crate::utils::date::DATE_FMT;
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("resource_bookings").as_str(), "booking_id", "resource_id", "starts_at").await;
+        client
+    }
+
+    fn at(hour: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(format!("2023-06-01T{}:00:00", hour).as_str(), DATE_FMT).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_should_create_update_find_overlapping_bookings() {
+        let store = test_store(module_path!(), "test_should_create_update_find_overlapping_bookings");
+        let repo = DDBBookingRepository::new(build_client(store).await, store.table_name("resource_bookings").as_str(), store.table_name("resource_bookings_ndx").as_str());
+        let mut morning = BookingEntity::new("room1", "patron1", at("09"), at("11"));
+        assert_eq!(1, repo.create(&morning).await.expect("should create booking"));
+        assert!(repo.create(&morning).await.is_err());
+        let afternoon = BookingEntity::new("room1", "patron2", at("13"), at("15"));
+        assert_eq!(1, repo.create(&afternoon).await.expect("should create booking"));
+
+        let res = repo.find_overlapping("room1", at("10"), at("14")).await.expect("should find bookings");
+        assert_eq!(2, res.len());
+        assert_eq!(at("09"), res[0].starts_at);
+        let res = repo.find_overlapping("room1", at("11"), at("13")).await.expect("should find bookings");
+        assert_eq!(0, res.len());
+        let res = repo.find_overlapping("room2", at("08"), at("18")).await.expect("should find bookings");
+        assert_eq!(0, res.len());
+
+        morning.booking_status = BookingStatus::Canceled;
+        morning.canceled_at = Some(at("08"));
+        assert_eq!(1, repo.update(&morning).await.expect("should update booking"));
+        // stale version should not be updated
+        assert!(repo.update(&morning).await.is_err());
+        let loaded = repo.get(morning.booking_id.as_str()).await.expect("should get booking");
+        assert_eq!(1, loaded.version);
+        assert_eq!(BookingStatus::Canceled, loaded.booking_status);
+        assert_eq!(at("11"), loaded.ends_at);
+    }
+}
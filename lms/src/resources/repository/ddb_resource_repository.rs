@@ -0,0 +1,141 @@
+use std::cmp;
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult, ResourceKind};
+use crate::resources::domain::model::ResourceEntity;
+use crate::resources::repository::ResourceRepository;
+use crate::utils::ddb::{from_ddb, parse_bool_attribute, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, to_ddb_page};
+
+#[derive(Debug)]
+pub(crate) struct DDBResourceRepository {
+    client: Client,
+    table_name: String,
+    index_name: String,
+}
+
+impl DDBResourceRepository {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl ResourceRepository for DDBResourceRepository {
+    async fn create(&self, entity: &ResourceEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(resource_id)")
+            .set_item(Some(parse_item(val)?))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn get(&self, resource_id: &str) -> LibraryResult<ResourceEntity> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .query()
+            .table_name(table_name)
+            .limit(1)
+            .consistent_read(true)
+            .key_condition_expression("resource_id = :resource_id")
+            .expression_attribute_values(":resource_id", AttributeValue::S(resource_id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).and_then(|req| {
+            if let Some(map) = req.items.as_ref().and_then(|items| items.first()) {
+                return Ok(ResourceEntity::from(map));
+            }
+            Err(LibraryError::not_found(format!("resource not found for {}", resource_id).as_str()))
+        })
+    }
+
+    async fn find_by_kind(&self, kind: ResourceKind,
+                          page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<ResourceEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let predicate = HashMap::from([("resource_kind".to_string(), kind.to_string())]);
+        self.client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .limit(cmp::min(page_size, 500) as i32)
+            .consistent_read(false)
+            .set_exclusive_start_key(to_ddb_page(page, &predicate))
+            .key_condition_expression("resource_kind = :resource_kind")
+            .expression_attribute_values(":resource_kind", AttributeValue::S(kind.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(ResourceEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for ResourceEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        ResourceEntity {
+            resource_id: parse_string_attribute("resource_id", map).unwrap_or_else(|| String::from("")),
+            version: parse_number_attribute("version", map),
+            branch_id: parse_string_attribute("branch_id", map).unwrap_or_else(|| String::from("")),
+            name: parse_string_attribute("name", map).unwrap_or_else(|| String::from("")),
+            resource_kind: ResourceKind::from(parse_string_attribute("resource_kind", map).unwrap_or_else(|| String::from(""))),
+            capacity: parse_number_attribute("capacity", map),
+            active: parse_bool_attribute("active", map),
+            created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+
+    use crate::core::library::ResourceKind;
+    use crate::core::repository::RepositoryStore;
+    use crate::resources::domain::model::ResourceEntity;
+    use crate::resources::repository::ddb_resource_repository::DDBResourceRepository;
+    use crate::resources::repository::ResourceRepository;
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("resources").as_str(), "resource_id", "resource_kind", "name").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_create_get_find_resources() {
+        let store = test_store(module_path!(), "test_should_create_get_find_resources");
+        let repo = DDBResourceRepository::new(build_client(store).await, store.table_name("resources").as_str(), store.table_name("resources_ndx").as_str());
+        let mut room = ResourceEntity::new("Study Room", ResourceKind::Room);
+        room.capacity = 6;
+        assert_eq!(1, repo.create(&room).await.expect("should create resource"));
+        assert!(repo.create(&room).await.is_err());
+        let laptop = ResourceEntity::new("Laptop", ResourceKind::Equipment);
+        assert_eq!(1, repo.create(&laptop).await.expect("should create resource"));
+
+        let loaded = repo.get(room.resource_id.as_str()).await.expect("should get resource");
+        assert_eq!("Study Room", loaded.name.as_str());
+        assert_eq!(6, loaded.capacity);
+        assert!(loaded.active);
+
+        let res = repo.find_by_kind(ResourceKind::Room, None, 10).await.expect("should find resources");
+        assert_eq!(1, res.records.len());
+        let res = repo.find_by_kind(ResourceKind::Equipment, None, 10).await.expect("should find resources");
+        assert_eq!(1, res.records.len());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod add_serial_cmd;
+pub mod check_in_issue_cmd;
+pub mod claim_issues_cmd;
+pub mod get_holdings_cmd;
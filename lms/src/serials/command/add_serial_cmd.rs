@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::core::library::SerialFrequency;
+use crate::serials::domain::SerialService;
+use crate::serials::dto::SerialDto;
+
+pub(crate) struct AddSerialCommand {
+    serial_service: Box<dyn SerialService>,
+}
+
+impl AddSerialCommand {
+    pub(crate) fn new(serial_service: Box<dyn SerialService>) -> Self {
+        Self {
+            serial_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AddSerialCommandRequest {
+    issn: String,
+    title: String,
+    frequency: SerialFrequency,
+    first_issue_at: Option<NaiveDateTime>,
+    scheduled_issues: Option<i64>,
+    claim_after_days: Option<i64>,
+}
+
+impl AddSerialCommandRequest {
+    pub fn new(issn: &str, title: &str, frequency: SerialFrequency) -> Self {
+        Self {
+            issn: issn.to_string(),
+            title: title.to_string(),
+            frequency,
+            first_issue_at: None,
+            scheduled_issues: None,
+            claim_after_days: None,
+        }
+    }
+    pub fn build_serial(&self) -> SerialDto {
+        let mut serial = SerialDto::new(self.issn.as_str(), self.title.as_str(), self.frequency);
+        serial.first_issue_at = self.first_issue_at.unwrap_or_else(|| Utc::now().naive_utc());
+        if let Some(scheduled_issues) = self.scheduled_issues {
+            serial.scheduled_issues = scheduled_issues;
+        }
+        if let Some(claim_after_days) = self.claim_after_days {
+            serial.claim_after_days = claim_after_days;
+        }
+        serial
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AddSerialCommandResponse {
+    pub serial: SerialDto,
+}
+
+impl AddSerialCommandResponse {
+    pub fn new(serial: SerialDto) -> Self {
+        Self {
+            serial,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<AddSerialCommandRequest, AddSerialCommandResponse> for AddSerialCommand {
+    async fn execute(&self, req: AddSerialCommandRequest) -> Result<AddSerialCommandResponse, CommandError> {
+        let serial = req.build_serial();
+        self.serial_service.add_serial(&serial).await.map_err(CommandError::from).map(AddSerialCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::SerialFrequency;
+    use crate::core::repository::RepositoryStore;
+    use crate::serials::command::add_serial_cmd::{AddSerialCommand, AddSerialCommandRequest};
+    use crate::serials::factory;
+    use crate::utils::testing::test_store;
+
+    async fn sut_cmd(store: RepositoryStore) -> AddSerialCommand {
+        let svc = factory::create_serial_service(&Configuration::new("test"), store).await;
+        AddSerialCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_add_serial() {
+        let store = test_store(module_path!(), "test_should_run_add_serial");
+        let cmd = sut_cmd(store).await;
+
+        let res = cmd.execute(AddSerialCommandRequest::new("1111-2222", "quarterly", SerialFrequency::Quarterly)).await.expect("should add serial");
+        assert_eq!(12, res.serial.scheduled_issues);
+    }
+}
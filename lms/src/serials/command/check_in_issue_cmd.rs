@@ -0,0 +1,86 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::serials::domain::SerialService;
+use crate::serials::dto::IssueDto;
+
+pub(crate) struct CheckInIssueCommand {
+    serial_service: Box<dyn SerialService>,
+}
+
+impl CheckInIssueCommand {
+    pub(crate) fn new(serial_service: Box<dyn SerialService>) -> Self {
+        Self {
+            serial_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CheckInIssueCommandRequest {
+    pub(crate) serial_id: String,
+    pub(crate) issue_number: i64,
+}
+
+impl CheckInIssueCommandRequest {
+    pub fn new(serial_id: &str, issue_number: i64) -> Self {
+        Self {
+            serial_id: serial_id.to_string(),
+            issue_number,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CheckInIssueCommandResponse {
+    pub issue: IssueDto,
+}
+
+impl CheckInIssueCommandResponse {
+    pub fn new(issue: IssueDto) -> Self {
+        Self {
+            issue,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<CheckInIssueCommandRequest, CheckInIssueCommandResponse> for CheckInIssueCommand {
+    async fn execute(&self, req: CheckInIssueCommandRequest) -> Result<CheckInIssueCommandResponse, CommandError> {
+        self.serial_service.check_in(req.serial_id.as_str(), req.issue_number)
+            .await.map_err(CommandError::from).map(CheckInIssueCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{IssueStatus, SerialFrequency};
+    use crate::core::repository::RepositoryStore;
+    use crate::serials::command::check_in_issue_cmd::{CheckInIssueCommand, CheckInIssueCommandRequest};
+    use crate::serials::domain::SerialService;
+    use crate::serials::dto::SerialDto;
+    use crate::serials::factory;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn SerialService> {
+        factory::create_serial_service(&Configuration::new("test"), store).await
+    }
+
+    async fn sut_cmd(store: RepositoryStore) -> CheckInIssueCommand {
+        let svc = factory::create_serial_service(&Configuration::new("test"), store).await;
+        CheckInIssueCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_check_in_issue() {
+        let store = test_store(module_path!(), "test_should_run_check_in_issue");
+        let cmd = sut_cmd(store).await;
+        let serial = build_svc(store).await.add_serial(&SerialDto::new("3333-4444", "check in", SerialFrequency::Monthly)).await.expect("should add serial");
+
+        let res = cmd.execute(CheckInIssueCommandRequest::new(serial.serial_id.as_str(), 1)).await.expect("should check in");
+        assert_eq!(IssueStatus::Received, res.issue.issue_status);
+    }
+}
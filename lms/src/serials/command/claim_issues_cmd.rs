@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::serials::domain::SerialService;
+use crate::serials::dto::IssueDto;
+
+pub(crate) struct ClaimIssuesCommand {
+    serial_service: Box<dyn SerialService>,
+}
+
+impl ClaimIssuesCommand {
+    pub(crate) fn new(serial_service: Box<dyn SerialService>) -> Self {
+        Self {
+            serial_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ClaimIssuesCommandRequest {
+    pub(crate) serial_id: String,
+}
+
+impl ClaimIssuesCommandRequest {
+    pub fn new(serial_id: &str) -> Self {
+        Self {
+            serial_id: serial_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ClaimIssuesCommandResponse {
+    pub claimed: Vec<IssueDto>,
+}
+
+impl ClaimIssuesCommandResponse {
+    pub fn new(claimed: Vec<IssueDto>) -> Self {
+        Self {
+            claimed,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<ClaimIssuesCommandRequest, ClaimIssuesCommandResponse> for ClaimIssuesCommand {
+    async fn execute(&self, req: ClaimIssuesCommandRequest) -> Result<ClaimIssuesCommandResponse, CommandError> {
+        self.serial_service.claim_missing(req.serial_id.as_str())
+            .await.map_err(CommandError::from).map(ClaimIssuesCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::{IssueStatus, SerialFrequency};
+    use crate::core::repository::RepositoryStore;
+    use crate::serials::command::claim_issues_cmd::{ClaimIssuesCommand, ClaimIssuesCommandRequest};
+    use crate::serials::domain::SerialService;
+    use crate::serials::dto::SerialDto;
+    use crate::serials::factory;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn SerialService> {
+        factory::create_serial_service(&Configuration::new("test"), store).await
+    }
+
+    async fn sut_cmd(store: RepositoryStore) -> ClaimIssuesCommand {
+        let svc = factory::create_serial_service(&Configuration::new("test"), store).await;
+        ClaimIssuesCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_claim_issues() {
+        let store = test_store(module_path!(), "test_should_run_claim_issues");
+        let cmd = sut_cmd(store).await;
+        let mut serial = SerialDto::new("5555-6666", "claims", SerialFrequency::Annual);
+        serial.first_issue_at = Utc::now().naive_utc() - Duration::days(380);
+        serial.scheduled_issues = 2;
+        let serial = build_svc(store).await.add_serial(&serial).await.expect("should add serial");
+
+        let res = cmd.execute(ClaimIssuesCommandRequest::new(serial.serial_id.as_str())).await.expect("should claim");
+        assert_eq!(1, res.claimed.len());
+        assert_eq!(IssueStatus::Claimed, res.claimed[0].issue_status);
+    }
+}
@@ -0,0 +1,84 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::serials::domain::SerialQueryService;
+use crate::serials::dto::HoldingsDto;
+
+pub(crate) struct GetHoldingsCommand {
+    serial_service: Box<dyn SerialQueryService>,
+}
+
+impl GetHoldingsCommand {
+    pub(crate) fn new(serial_service: Box<dyn SerialQueryService>) -> Self {
+        Self {
+            serial_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetHoldingsCommandRequest {
+    pub(crate) serial_id: String,
+}
+
+impl GetHoldingsCommandRequest {
+    pub fn new(serial_id: &str) -> Self {
+        Self {
+            serial_id: serial_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetHoldingsCommandResponse {
+    pub holdings: HoldingsDto,
+}
+
+impl GetHoldingsCommandResponse {
+    pub fn new(holdings: HoldingsDto) -> Self {
+        Self {
+            holdings,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<GetHoldingsCommandRequest, GetHoldingsCommandResponse> for GetHoldingsCommand {
+    async fn execute(&self, req: GetHoldingsCommandRequest) -> Result<GetHoldingsCommandResponse, CommandError> {
+        self.serial_service.holdings(req.serial_id.as_str())
+            .await.map_err(CommandError::from).map(GetHoldingsCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::library::SerialFrequency;
+    use crate::core::repository::RepositoryStore;
+    use crate::serials::command::get_holdings_cmd::{GetHoldingsCommand, GetHoldingsCommandRequest};
+    use crate::serials::domain::SerialService;
+    use crate::serials::dto::SerialDto;
+    use crate::serials::factory;
+    use crate::utils::testing::test_store;
+
+    async fn build_svc(store: RepositoryStore) -> Box<dyn SerialService> {
+        factory::create_serial_service(&Configuration::new("test"), store).await
+    }
+
+    async fn sut_cmd(store: RepositoryStore) -> GetHoldingsCommand {
+        let svc = factory::create_serial_query_service(&Configuration::new("test"), store).await;
+        GetHoldingsCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_get_holdings() {
+        let store = test_store(module_path!(), "test_should_run_get_holdings");
+        let cmd = sut_cmd(store).await;
+        let serial = build_svc(store).await.add_serial(&SerialDto::new("7777-8888", "holdings", SerialFrequency::Biweekly)).await.expect("should add serial");
+
+        let res = cmd.execute(GetHoldingsCommandRequest::new(serial.serial_id.as_str())).await.expect("should get holdings");
+        assert_eq!(serial.scheduled_issues as usize, res.holdings.expected);
+    }
+}
@@ -0,0 +1,77 @@
+use axum::{
+    extract::{Path, State},
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use serde_json::{Value};
+use crate::core::controller::{AppState, command_bus, json_to_server_error, with_common_layers, ServerError};
+use crate::serials::command::add_serial_cmd::{AddSerialCommand, AddSerialCommandRequest, AddSerialCommandResponse};
+use crate::serials::command::check_in_issue_cmd::{CheckInIssueCommand, CheckInIssueCommandRequest, CheckInIssueCommandResponse};
+use crate::serials::command::claim_issues_cmd::{ClaimIssuesCommand, ClaimIssuesCommandRequest, ClaimIssuesCommandResponse};
+use crate::serials::command::get_holdings_cmd::{GetHoldingsCommand, GetHoldingsCommandRequest, GetHoldingsCommandResponse};
+use crate::serials::domain::{SerialQueryService, SerialService};
+use crate::serials::factory;
+use crate::utils::ddb::{build_db_client, create_key_table, create_table};
+
+async fn build_service(state: AppState) -> Box<dyn SerialService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_key_table(&client, state.store.table_name("serials").as_str(), "serial_id").await;
+    let _ = create_table(&client, state.store.table_name("serial_issues").as_str(), "issue_id", "serial_id", "expected_at").await;
+    let _ = create_table(&client, state.store.table_name("books").as_str(), "book_id", "book_status", "isbn").await;
+    factory::create_serial_service(&state.config, state.store).await
+}
+
+async fn build_query_service(state: AppState) -> Box<dyn SerialQueryService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_key_table(&client, state.store.table_name("serials").as_str(), "serial_id").await;
+    let _ = create_table(&client, state.store.table_name("serial_issues").as_str(), "issue_id", "serial_id", "expected_at").await;
+    let _ = create_table(&client, state.store.table_name("books").as_str(), "book_id", "book_status", "isbn").await;
+    factory::create_serial_query_service(&state.config, state.store).await
+}
+
+pub(crate) async fn add_serial(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<Json<AddSerialCommandResponse>, ServerError> {
+    let req: AddSerialCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res = command_bus().register(AddSerialCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn find_holdings(
+    State(state): State<AppState>,
+    Path(serial_id): Path<String>) -> Result<Json<GetHoldingsCommandResponse>, ServerError> {
+    let req = GetHoldingsCommandRequest { serial_id };
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(GetHoldingsCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn check_in_issue(
+    State(state): State<AppState>,
+    Path((serial_id, issue_number)): Path<(String, i64)>) -> Result<Json<CheckInIssueCommandResponse>, ServerError> {
+    let req = CheckInIssueCommandRequest { serial_id, issue_number };
+    let svc = build_service(state).await;
+    let res = command_bus().register(CheckInIssueCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+// claims all issues of the serial that are overdue beyond its claim period
+pub(crate) async fn claim_issues(
+    State(state): State<AppState>,
+    Path(serial_id): Path<String>) -> Result<Json<ClaimIssuesCommandResponse>, ServerError> {
+    let req = ClaimIssuesCommandRequest { serial_id };
+    let svc = build_service(state).await;
+    let res = command_bus().register(ClaimIssuesCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/serials", post(add_serial))
+        .route("/serials/:id", get(find_holdings))
+        .route("/serials/:id/issues/:number/checkin", post(check_in_issue))
+        .route("/serials/:id/claims", post(claim_issues));
+    with_common_layers(router, state)
+}
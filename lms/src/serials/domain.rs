@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+use crate::core::library::LibraryResult;
+use crate::serials::dto::{HoldingsDto, IssueDto, SerialDto};
+
+pub mod model;
+pub mod query;
+pub mod service;
+
+// read side of serials
+#[async_trait]
+pub(crate) trait SerialQueryService: Sync + Send {
+    async fn holdings(&self, serial_id: &str) -> LibraryResult<HoldingsDto>;
+}
+
+#[async_trait]
+pub(crate) trait SerialService: SerialQueryService {
+    // adds title record of the serial to the catalog along with its expected issues
+    async fn add_serial(&self, serial: &SerialDto) -> LibraryResult<SerialDto>;
+    async fn check_in(&self, serial_id: &str, issue_number: i64) -> LibraryResult<IssueDto>;
+    // claims issues that were not received within claim period of their expected date
+    async fn claim_missing(&self, serial_id: &str) -> LibraryResult<Vec<IssueDto>>;
+}
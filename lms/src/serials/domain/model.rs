@@ -0,0 +1,118 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::core::domain::Identifiable;
+use crate::core::library::{IssueStatus, SerialFrequency};
+use crate::utils::date::serializer;
+
+// SerialEntity abstracts magazine or journal, serial_id is the book_id of its title record in the catalog
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct SerialEntity {
+    pub serial_id: String,
+    pub version: i64,
+    pub issn: String,
+    pub title: String,
+    pub frequency: SerialFrequency,
+    #[serde(with = "serializer")]
+    pub first_issue_at: NaiveDateTime,
+    pub scheduled_issues: i64,
+    pub claim_after_days: i64,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl SerialEntity {
+    pub fn new(issn: &str, title: &str, frequency: SerialFrequency) -> Self {
+        Self {
+            serial_id: Uuid::new_v4().to_string(),
+            version: 0,
+            issn: issn.to_string(),
+            title: title.to_string(),
+            frequency,
+            first_issue_at: Utc::now().naive_utc(),
+            scheduled_issues: 12,
+            claim_after_days: 30,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl Identifiable for SerialEntity {
+    fn id(&self) -> String {
+        self.serial_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+// IssueEntity abstracts an expected issue of a serial
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct IssueEntity {
+    pub issue_id: String,
+    pub version: i64,
+    pub serial_id: String,
+    pub issue_number: i64,
+    pub issue_status: IssueStatus,
+    #[serde(with = "serializer")]
+    pub expected_at: NaiveDateTime,
+    pub received_at: Option<NaiveDateTime>,
+    pub claimed_at: Option<NaiveDateTime>,
+    pub claim_count: i64,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl IssueEntity {
+    pub fn new(serial_id: &str, issue_number: i64, expected_at: NaiveDateTime) -> Self {
+        Self {
+            issue_id: IssueEntity::build_id(serial_id, issue_number),
+            version: 0,
+            serial_id: serial_id.to_string(),
+            issue_number,
+            issue_status: IssueStatus::Expected,
+            expected_at,
+            received_at: None,
+            claimed_at: None,
+            claim_count: 0,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+
+    pub fn build_id(serial_id: &str, issue_number: i64) -> String {
+        format!("{}_{}", serial_id, issue_number)
+    }
+}
+
+impl Identifiable for IssueEntity {
+    fn id(&self) -> String {
+        self.issue_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use crate::core::library::{IssueStatus, SerialFrequency};
+    use crate::serials::domain::model::{IssueEntity, SerialEntity};
+
+    #[tokio::test]
+    async fn test_should_build_serial_and_issue() {
+        let serial = SerialEntity::new("1234-5678", "journal", SerialFrequency::Monthly);
+        assert_eq!("journal", serial.title.as_str());
+        let issue = IssueEntity::new(serial.serial_id.as_str(), 3, Utc::now().naive_utc());
+        assert_eq!(format!("{}_3", serial.serial_id), issue.issue_id);
+        assert_eq!(IssueStatus::Expected, issue.issue_status);
+    }
+}
@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use crate::core::library::{IssueStatus, LibraryResult};
+use crate::serials::domain::SerialQueryService;
+use crate::serials::dto::{HoldingsDto, IssueDto, SerialDto};
+use crate::serials::repository::{IssueRepository, SerialRepository};
+
+pub(crate) struct SerialQueryServiceImpl {
+    serial_repository: Box<dyn SerialRepository>,
+    issue_repository: Box<dyn IssueRepository>,
+}
+
+impl SerialQueryServiceImpl {
+    pub(crate) fn new(serial_repository: Box<dyn SerialRepository>,
+                      issue_repository: Box<dyn IssueRepository>) -> Self {
+        Self {
+            serial_repository,
+            issue_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl SerialQueryService for SerialQueryServiceImpl {
+    async fn holdings(&self, serial_id: &str) -> LibraryResult<HoldingsDto> {
+        let serial = self.serial_repository.get(serial_id).await?;
+        let mut issues: Vec<IssueDto> = vec![];
+        let mut next_page: Option<String> = None;
+        loop {
+            let res = self.issue_repository.find_by_serial(serial_id, next_page.as_deref(), 200).await?;
+            issues.extend(res.records.iter().map(IssueDto::from));
+            next_page = res.next_page;
+            if next_page.is_none() {
+                break;
+            }
+        }
+        let count = |status: IssueStatus| issues.iter().filter(|i| i.issue_status == status).count();
+        Ok(HoldingsDto {
+            serial: SerialDto::from(&serial),
+            received: count(IssueStatus::Received),
+            expected: count(IssueStatus::Expected),
+            claimed: count(IssueStatus::Claimed),
+            issues,
+        })
+    }
+}
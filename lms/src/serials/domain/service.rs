@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+
+use crate::books::dto::BookDto;
+use crate::catalog::domain::CatalogService;
+use crate::core::domain::Configuration;
+use crate::core::events::DomainEvent;
+use crate::core::library::{BookFormat, BookStatus, IssueStatus, LibraryError, LibraryResult};
+use crate::gateway::events::EventPublisher;
+use crate::serials::domain::model::{IssueEntity, SerialEntity};
+use crate::serials::domain::{SerialQueryService, SerialService};
+use crate::serials::dto::{HoldingsDto, IssueDto, SerialDto};
+use crate::serials::repository::{IssueRepository, SerialRepository};
+
+// maximum number of issues that are scheduled at once
+const MAX_SCHEDULED_ISSUES: i64 = 104;
+
+pub(crate) struct SerialServiceImpl {
+    serial_repository: Box<dyn SerialRepository>,
+    issue_repository: Box<dyn IssueRepository>,
+    catalog_service: Box<dyn CatalogService>,
+    events_publisher: Box<dyn EventPublisher>,
+    query_service: Box<dyn SerialQueryService>,
+}
+
+impl SerialServiceImpl {
+    pub(crate) fn new(_config: &Configuration, serial_repository: Box<dyn SerialRepository>,
+                      issue_repository: Box<dyn IssueRepository>, catalog_service: Box<dyn CatalogService>,
+                      events_publisher: Box<dyn EventPublisher>,
+                      query_service: Box<dyn SerialQueryService>) -> Self {
+        Self {
+            serial_repository,
+            issue_repository,
+            catalog_service,
+            events_publisher,
+            query_service,
+        }
+    }
+
+    async fn all_issues(&self, serial_id: &str) -> LibraryResult<Vec<IssueEntity>> {
+        let mut issues = vec![];
+        let mut next_page: Option<String> = None;
+        loop {
+            let res = self.issue_repository.find_by_serial(serial_id, next_page.as_deref(), 200).await?;
+            issues.extend(res.records);
+            next_page = res.next_page;
+            if next_page.is_none() {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+
+    async fn publish_issue(&self, name: &str, issue: &IssueDto) -> LibraryResult<()> {
+        let _ = self.events_publisher.publish(&DomainEvent::updated(
+            name, "serials", issue.issue_id.as_str(), &HashMap::new(), issue)?).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SerialService for SerialServiceImpl {
+    async fn add_serial(&self, serial: &SerialDto) -> LibraryResult<SerialDto> {
+        if serial.title.is_empty() {
+            return Err(LibraryError::validation("serial title is required", Some("400".to_string())));
+        }
+        if serial.scheduled_issues <= 0 || serial.scheduled_issues > MAX_SCHEDULED_ISSUES {
+            return Err(LibraryError::validation(format!("scheduled issues must be between 1 and {}",
+                                                        MAX_SCHEDULED_ISSUES).as_str(), Some("400".to_string())));
+        }
+        if serial.claim_after_days < 0 {
+            return Err(LibraryError::validation("claim period cannot be negative", Some("400".to_string())));
+        }
+        let title = BookDto::builder().isbn(serial.issn.as_str()).title(serial.title.as_str())
+            .book_status(BookStatus::Available).book_format(BookFormat::Serial).build()?;
+        let title = self.catalog_service.add_book(&title).await?;
+
+        let mut entity = SerialEntity::from(serial);
+        entity.serial_id = title.book_id.to_string();
+        self.serial_repository.create(&entity).await?;
+        for i in 0..entity.scheduled_issues {
+            let expected_at = entity.frequency.expected_at(entity.first_issue_at, i as u32);
+            let _ = self.issue_repository.create(&IssueEntity::new(entity.serial_id.as_str(), i + 1, expected_at)).await?;
+        }
+        let serial = SerialDto::from(&entity);
+        let _ = self.events_publisher.publish(&DomainEvent::added(
+            "serial_added", "serials", serial.serial_id.as_str(), &HashMap::new(), &serial)?).await?;
+        Ok(serial)
+    }
+
+    async fn check_in(&self, serial_id: &str, issue_number: i64) -> LibraryResult<IssueDto> {
+        let mut issue = self.issue_repository.get(IssueEntity::build_id(serial_id, issue_number).as_str()).await?;
+        if issue.issue_status == IssueStatus::Received {
+            return Err(LibraryError::validation(format!("issue {} of {} is already checked in",
+                                                        issue_number, serial_id).as_str(), Some("400".to_string())));
+        }
+        issue.issue_status = IssueStatus::Received;
+        issue.received_at = Some(Utc::now().naive_utc());
+        self.issue_repository.update(&issue).await?;
+        let issue = IssueDto::from(&issue);
+        self.publish_issue("issue_checked_in", &issue).await?;
+        Ok(issue)
+    }
+
+    async fn claim_missing(&self, serial_id: &str) -> LibraryResult<Vec<IssueDto>> {
+        let serial = self.serial_repository.get(serial_id).await?;
+        let now = Utc::now().naive_utc();
+        let claim_after = Duration::days(serial.claim_after_days);
+        let mut claimed = vec![];
+        for mut issue in self.all_issues(serial_id).await? {
+            // issues that are still missing after claim period of previous claim are claimed again
+            let due = match issue.issue_status {
+                IssueStatus::Expected => issue.expected_at + claim_after < now,
+                IssueStatus::Claimed => issue.claimed_at.map(|at| at + claim_after < now).unwrap_or(true),
+                IssueStatus::Received => false,
+            };
+            if !due {
+                continue;
+            }
+            issue.issue_status = IssueStatus::Claimed;
+            issue.claimed_at = Some(now);
+            issue.claim_count += 1;
+            self.issue_repository.update(&issue).await?;
+            let issue = IssueDto::from(&issue);
+            self.publish_issue("issue_claimed", &issue).await?;
+            claimed.push(issue);
+        }
+        Ok(claimed)
+    }
+}
+
+#[async_trait]
+impl SerialQueryService for SerialServiceImpl {
+    async fn holdings(&self, serial_id: &str) -> LibraryResult<HoldingsDto> {
+        self.query_service.holdings(serial_id).await
+    }
+}
+
+impl From<&SerialDto> for SerialEntity {
+    fn from(other: &SerialDto) -> SerialEntity {
+        SerialEntity {
+            serial_id: other.serial_id.to_string(),
+            version: other.version,
+            issn: other.issn.to_string(),
+            title: other.title.to_string(),
+            frequency: other.frequency,
+            first_issue_at: other.first_issue_at,
+            scheduled_issues: other.scheduled_issues,
+            claim_after_days: other.claim_after_days,
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+impl From<&SerialEntity> for SerialDto {
+    fn from(other: &SerialEntity) -> SerialDto {
+        SerialDto {
+            serial_id: other.serial_id.to_string(),
+            version: other.version,
+            issn: other.issn.to_string(),
+            title: other.title.to_string(),
+            frequency: other.frequency,
+            first_issue_at: other.first_issue_at,
+            scheduled_issues: other.scheduled_issues,
+            claim_after_days: other.claim_after_days,
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        }
+    }
+}
+
+impl From<&IssueEntity> for IssueDto {
+    fn from(other: &IssueEntity) -> IssueDto {
+        IssueDto {
+            issue_id: other.issue_id.to_string(),
+            serial_id: other.serial_id.to_string(),
+            issue_number: other.issue_number,
+            issue_status: other.issue_status,
+            expected_at: other.expected_at,
+            received_at: other.received_at,
+            claimed_at: other.claimed_at,
+            claim_count: other.claim_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::catalog::domain::CatalogService;
+    use crate::catalog::factory::create_catalog_service;
+    use crate::core::domain::Configuration;
+    use crate::core::ids::BookId;
+    use crate::core::library::{BookFormat, IssueStatus, SerialFrequency};
+    use crate::core::repository::RepositoryStore;
+    use crate::serials::domain::SerialService;
+    use crate::serials::dto::SerialDto;
+    use crate::serials::factory;
+    use crate::utils::testing::test_store;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn SerialService> {
+        factory::create_serial_service(&Configuration::new("test"), store).await
+    }
+
+    async fn catalog_svc(store: RepositoryStore) -> Box<dyn CatalogService> {
+        create_catalog_service(&Configuration::new("test"), store).await
+    }
+
+    #[tokio::test]
+    async fn test_should_add_serial_with_schedule() {
+        let store = test_store(module_path!(), "test_should_add_serial_with_schedule");
+        let serial_svc = sut_svc(store).await;
+        let mut serial = SerialDto::new("1234-5678", "monthly journal", SerialFrequency::Monthly);
+        serial.scheduled_issues = 3;
+        let serial = serial_svc.add_serial(&serial).await.expect("should add serial");
+
+        let title = catalog_svc(store).await.find_book_by_id(&BookId::new(serial.serial_id.as_str())).await.expect("should catalog title");
+        assert_eq!(BookFormat::Serial, title.book_format);
+        let holdings = serial_svc.holdings(serial.serial_id.as_str()).await.expect("should return holdings");
+        assert_eq!(3, holdings.expected);
+        assert_eq!(vec![1, 2, 3], holdings.issues.iter().map(|i| i.issue_number).collect::<Vec<i64>>());
+    }
+
+    #[tokio::test]
+    async fn test_should_check_in_and_claim_issues() {
+        let store = test_store(module_path!(), "test_should_check_in_and_claim_issues");
+        let serial_svc = sut_svc(store).await;
+        let mut serial = SerialDto::new("8765-4321", "weekly magazine", SerialFrequency::Weekly);
+        serial.scheduled_issues = 5;
+        serial.claim_after_days = 7;
+        serial.first_issue_at = Utc::now().naive_utc() - Duration::days(30);
+        let serial = serial_svc.add_serial(&serial).await.expect("should add serial");
+
+        let issue = serial_svc.check_in(serial.serial_id.as_str(), 1).await.expect("should check in");
+        assert_eq!(IssueStatus::Received, issue.issue_status);
+        assert!(serial_svc.check_in(serial.serial_id.as_str(), 1).await.is_err());
+
+        // issues expected more than a week ago are missing except issue 1 that was received
+        let claimed = serial_svc.claim_missing(serial.serial_id.as_str()).await.expect("should claim");
+        assert_eq!(vec![2, 3, 4], claimed.iter().map(|i| i.issue_number).collect::<Vec<i64>>());
+        // claimed issues are not claimed again before claim period
+        let claimed = serial_svc.claim_missing(serial.serial_id.as_str()).await.expect("should claim");
+        assert_eq!(0, claimed.len());
+
+        // claimed issues can be checked in when they arrive
+        let _ = serial_svc.check_in(serial.serial_id.as_str(), 2).await.expect("should check in claimed");
+        let holdings = serial_svc.holdings(serial.serial_id.as_str()).await.expect("should return holdings");
+        assert_eq!(2, holdings.received);
+        assert_eq!(2, holdings.claimed);
+        assert_eq!(1, holdings.expected);
+    }
+}
@@ -0,0 +1,87 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::core::domain::Identifiable;
+use crate::core::library::{IssueStatus, SerialFrequency};
+use crate::utils::date::serializer;
+
+// SerialDto abstracts data transfer object for magazines and journals
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct SerialDto {
+    pub serial_id: String,
+    pub version: i64,
+    pub issn: String,
+    pub title: String,
+    pub frequency: SerialFrequency,
+    #[serde(with = "serializer")]
+    pub first_issue_at: NaiveDateTime,
+    pub scheduled_issues: i64,
+    pub claim_after_days: i64,
+    #[serde(with = "serializer")]
+    pub created_at: NaiveDateTime,
+    #[serde(with = "serializer")]
+    pub updated_at: NaiveDateTime,
+}
+
+impl SerialDto {
+    pub fn new(issn: &str, title: &str, frequency: SerialFrequency) -> Self {
+        Self {
+            serial_id: Uuid::new_v4().to_string(),
+            version: 0,
+            issn: issn.to_string(),
+            title: title.to_string(),
+            frequency,
+            first_issue_at: Utc::now().naive_utc(),
+            scheduled_issues: 12,
+            claim_after_days: 30,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl Identifiable for SerialDto {
+    fn id(&self) -> String {
+        self.serial_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct IssueDto {
+    pub issue_id: String,
+    pub serial_id: String,
+    pub issue_number: i64,
+    pub issue_status: IssueStatus,
+    #[serde(with = "serializer")]
+    pub expected_at: NaiveDateTime,
+    pub received_at: Option<NaiveDateTime>,
+    pub claimed_at: Option<NaiveDateTime>,
+    pub claim_count: i64,
+}
+
+// HoldingsDto summarizes received, expected and claimed issues of a serial
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct HoldingsDto {
+    pub serial: SerialDto,
+    pub received: usize,
+    pub expected: usize,
+    pub claimed: usize,
+    pub issues: Vec<IssueDto>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::library::SerialFrequency;
+    use crate::serials::dto::SerialDto;
+
+    #[tokio::test]
+    async fn test_should_build_serial() {
+        let serial = SerialDto::new("1234-5678", "journal", SerialFrequency::Weekly);
+        assert_eq!("1234-5678", serial.issn.as_str());
+        assert_eq!(SerialFrequency::Weekly, serial.frequency);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::catalog::factory::create_catalog_service;
+use crate::core::domain::Configuration;
+use crate::core::repository::RepositoryStore;
+use crate::gateway::factory::create_publisher;
+use crate::serials::domain::{SerialQueryService, SerialService};
+use crate::serials::domain::query::SerialQueryServiceImpl;
+use crate::serials::domain::service::SerialServiceImpl;
+use crate::serials::factory;
+use crate::serials::repository::{IssueRepository, SerialRepository};
+use crate::serials::repository::ddb_issue_repository::DDBIssueRepository;
+use crate::serials::repository::ddb_serial_repository::DDBSerialRepository;
+use crate::utils::ddb::{build_db_client, create_key_table, create_table};
+
+pub(crate) async fn create_serial_repository(store: RepositoryStore) -> Box<dyn SerialRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBSerialRepository::new(client, "serials"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("serials");
+            let _ = create_key_table(&client, table_name.as_str(), "serial_id").await;
+            Box::new(DDBSerialRepository::new(client, table_name.as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_issue_repository(store: RepositoryStore) -> Box<dyn IssueRepository> {
+    match store {
+        RepositoryStore::DynamoDB => {
+            let client = build_db_client(store).await;
+            Box::new(DDBIssueRepository::new(client, "serial_issues", "serial_issues_ndx"))
+        }
+        _ => {
+            let client = build_db_client(store).await;
+            let table_name = store.table_name("serial_issues");
+            let _ = create_table(&client, table_name.as_str(), "issue_id", "serial_id", "expected_at").await;
+            Box::new(DDBIssueRepository::new(client, table_name.as_str(), store.table_name("serial_issues_ndx").as_str()))
+        }
+    }
+}
+
+pub(crate) async fn create_serial_query_service(_config: &Configuration, store: RepositoryStore) -> Box<dyn SerialQueryService> {
+    let serial_repo = factory::create_serial_repository(store).await;
+    let issue_repo = factory::create_issue_repository(store).await;
+    Box::new(SerialQueryServiceImpl::new(serial_repo, issue_repo))
+}
+
+pub(crate) async fn create_serial_service(config: &Configuration, store: RepositoryStore) -> Box<dyn SerialService> {
+    let serial_repo = factory::create_serial_repository(store).await;
+    let issue_repo = factory::create_issue_repository(store).await;
+    let catalog_svc = create_catalog_service(config, store).await;
+    let publisher = create_publisher(store.gateway_publisher()).await;
+    let query_svc = create_serial_query_service(config, store).await;
+    Box::new(SerialServiceImpl::new(config, serial_repo, issue_repo, catalog_svc, publisher, query_svc))
+}
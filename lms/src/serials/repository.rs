@@ -0,0 +1,22 @@
+pub mod ddb_issue_repository;
+pub mod ddb_serial_repository;
+
+use async_trait::async_trait;
+use crate::core::library::{LibraryResult, PaginatedResult};
+use crate::serials::domain::model::{IssueEntity, SerialEntity};
+
+#[async_trait]
+pub(crate) trait SerialRepository: Sync + Send {
+    async fn create(&self, entity: &SerialEntity) -> LibraryResult<usize>;
+    async fn get(&self, serial_id: &str) -> LibraryResult<SerialEntity>;
+}
+
+#[async_trait]
+pub(crate) trait IssueRepository: Sync + Send {
+    async fn create(&self, entity: &IssueEntity) -> LibraryResult<usize>;
+    async fn update(&self, entity: &IssueEntity) -> LibraryResult<usize>;
+    async fn get(&self, issue_id: &str) -> LibraryResult<IssueEntity>;
+    // returns issues of serial ordered by expected date
+    async fn find_by_serial(&self, serial_id: &str,
+                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<IssueEntity>>;
+}
@@ -0,0 +1,164 @@
+use std::cmp;
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::core::library::{IssueStatus, LibraryError, LibraryResult, PaginatedResult};
+use crate::serials::domain::model::IssueEntity;
+use crate::serials::repository::IssueRepository;
+use crate::utils::ddb::{from_ddb, opt_string_date, parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute, string_date, to_ddb_page};
+
+#[derive(Debug)]
+pub(crate) struct DDBIssueRepository {
+    client: Client,
+    table_name: String,
+    index_name: String,
+}
+
+impl DDBIssueRepository {
+    pub(crate) fn new(client: Client, table_name: &str, index_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            index_name: index_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl IssueRepository for DDBIssueRepository {
+    async fn create(&self, entity: &IssueEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(issue_id)")
+            .set_item(Some(parse_item(val)?))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn update(&self, entity: &IssueEntity) -> LibraryResult<usize> {
+        let now = Utc::now().naive_utc();
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .update_item()
+            .table_name(table_name)
+            .key("issue_id", AttributeValue::S(entity.issue_id.clone()))
+            .update_expression("SET version = :version, issue_status = :issue_status, received_at = :received_at, claimed_at = :claimed_at, claim_count = :claim_count, updated_at = :updated_at")
+            .expression_attribute_values(":old_version", AttributeValue::N(entity.version.to_string()))
+            .expression_attribute_values(":version", AttributeValue::N((entity.version + 1).to_string()))
+            .expression_attribute_values(":issue_status", AttributeValue::S(entity.issue_status.to_string()))
+            .expression_attribute_values(":received_at", opt_string_date(entity.received_at))
+            .expression_attribute_values(":claimed_at", opt_string_date(entity.claimed_at))
+            .expression_attribute_values(":claim_count", AttributeValue::N(entity.claim_count.to_string()))
+            .expression_attribute_values(":updated_at", string_date(now))
+            .condition_expression("attribute_exists(version) AND version = :old_version")
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn get(&self, issue_id: &str) -> LibraryResult<IssueEntity> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .query()
+            .table_name(table_name)
+            .limit(1)
+            .consistent_read(true)
+            .key_condition_expression("issue_id = :issue_id")
+            .expression_attribute_values(":issue_id", AttributeValue::S(issue_id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).and_then(|req| {
+            if let Some(map) = req.items.as_ref().and_then(|items| items.first()) {
+                return Ok(IssueEntity::from(map));
+            }
+            Err(LibraryError::not_found(format!("issue not found for {}", issue_id).as_str()))
+        })
+    }
+
+    async fn find_by_serial(&self, serial_id: &str,
+                            page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<IssueEntity>> {
+        let table_name: &str = self.table_name.as_ref();
+        let index_name: &str = self.index_name.as_ref();
+        let predicate = HashMap::from([("serial_id".to_string(), serial_id.to_string())]);
+        self.client
+            .query()
+            .table_name(table_name)
+            .index_name(index_name)
+            .limit(cmp::min(page_size, 500) as i32)
+            .consistent_read(false)
+            .set_exclusive_start_key(to_ddb_page(page, &predicate))
+            .key_condition_expression("serial_id = :serial_id")
+            .expression_attribute_values(":serial_id", AttributeValue::S(serial_id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).map(|req| {
+            let records = req.items.as_ref().unwrap_or(&vec![]).iter()
+                .map(IssueEntity::from).collect();
+            from_ddb(page, page_size, req.last_evaluated_key(), records)
+        })
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for IssueEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        IssueEntity {
+            issue_id: parse_string_attribute("issue_id", map).unwrap_or_else(|| String::from("")),
+            version: parse_number_attribute("version", map),
+            serial_id: parse_string_attribute("serial_id", map).unwrap_or_else(|| String::from("")),
+            issue_number: parse_number_attribute("issue_number", map),
+            issue_status: IssueStatus::from(parse_string_attribute("issue_status", map).unwrap_or_else(|| String::from(""))),
+            expected_at: parse_date_attribute("expected_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            received_at: parse_date_attribute("received_at", map),
+            claimed_at: parse_date_attribute("claimed_at", map),
+            claim_count: parse_number_attribute("claim_count", map),
+            created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+    use chrono::{Duration, Utc};
+
+    use crate::core::library::IssueStatus;
+    use crate::core::repository::RepositoryStore;
+    use crate::serials::domain::model::IssueEntity;
+    use crate::serials::repository::ddb_issue_repository::DDBIssueRepository;
+    use crate::serials::repository::IssueRepository;
+    use crate::utils::ddb::{build_db_client, create_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_table(&client, store.table_name("serial_issues").as_str(), "issue_id", "serial_id", "expected_at").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_create_update_find_issues() {
+        let store = test_store(module_path!(), "test_should_create_update_find_issues");
+        let repo = DDBIssueRepository::new(build_client(store).await, store.table_name("serial_issues").as_str(), store.table_name("serial_issues_ndx").as_str());
+        let now = Utc::now().naive_utc();
+        for i in (1..4).rev() {
+            let issue = IssueEntity::new("issue_serial", i, now + Duration::days(i * 7));
+            assert_eq!(1, repo.create(&issue).await.expect("should create issue"));
+        }
+
+        let mut issue = repo.get(IssueEntity::build_id("issue_serial", 2).as_str()).await.expect("should get issue");
+        issue.issue_status = IssueStatus::Received;
+        issue.received_at = Some(now);
+        assert_eq!(1, repo.update(&issue).await.expect("should update issue"));
+        // stale version cannot be updated
+        assert!(repo.update(&issue).await.is_err());
+
+        let res = repo.find_by_serial("issue_serial", None, 10).await.expect("should find issues");
+        assert_eq!(vec![1, 2, 3], res.records.iter().map(|i| i.issue_number).collect::<Vec<i64>>());
+        assert_eq!(IssueStatus::Received, res.records[1].issue_status);
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::Client;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::Utc;
+
+use crate::core::library::{LibraryError, LibraryResult, SerialFrequency};
+use crate::serials::domain::model::SerialEntity;
+use crate::serials::repository::SerialRepository;
+use crate::utils::ddb::{parse_date_attribute, parse_item, parse_number_attribute, parse_string_attribute};
+
+#[derive(Debug)]
+pub(crate) struct DDBSerialRepository {
+    client: Client,
+    table_name: String,
+}
+
+impl DDBSerialRepository {
+    pub(crate) fn new(client: Client, table_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl SerialRepository for DDBSerialRepository {
+    async fn create(&self, entity: &SerialEntity) -> LibraryResult<usize> {
+        let table_name: &str = self.table_name.as_ref();
+        let val = serde_json::to_value(entity)?;
+        self.client
+            .put_item()
+            .table_name(table_name)
+            .condition_expression("attribute_not_exists(serial_id)")
+            .set_item(Some(parse_item(val)?))
+            .send()
+            .await.map(|_| 1).map_err(LibraryError::from)
+    }
+
+    async fn get(&self, serial_id: &str) -> LibraryResult<SerialEntity> {
+        let table_name: &str = self.table_name.as_ref();
+        self.client
+            .query()
+            .table_name(table_name)
+            .limit(1)
+            .consistent_read(true)
+            .key_condition_expression("serial_id = :serial_id")
+            .expression_attribute_values(":serial_id", AttributeValue::S(serial_id.to_string()))
+            .send()
+            .await.map_err(LibraryError::from).and_then(|req| {
+            if let Some(map) = req.items.as_ref().and_then(|items| items.first()) {
+                return Ok(SerialEntity::from(map));
+            }
+            Err(LibraryError::not_found(format!("serial not found for {}", serial_id).as_str()))
+        })
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for SerialEntity {
+    fn from(map: &HashMap<String, AttributeValue>) -> Self {
+        SerialEntity {
+            serial_id: parse_string_attribute("serial_id", map).unwrap_or_else(|| String::from("")),
+            version: parse_number_attribute("version", map),
+            issn: parse_string_attribute("issn", map).unwrap_or_else(|| String::from("")),
+            title: parse_string_attribute("title", map).unwrap_or_else(|| String::from("")),
+            frequency: SerialFrequency::from(parse_string_attribute("frequency", map).unwrap_or_else(|| String::from(""))),
+            first_issue_at: parse_date_attribute("first_issue_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            scheduled_issues: parse_number_attribute("scheduled_issues", map),
+            claim_after_days: parse_number_attribute("claim_after_days", map),
+            created_at: parse_date_attribute("created_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+            updated_at: parse_date_attribute("updated_at", map).unwrap_or_else(|| Utc::now().naive_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_dynamodb::Client;
+
+    use crate::core::library::SerialFrequency;
+    use crate::core::repository::RepositoryStore;
+    use crate::serials::domain::model::SerialEntity;
+    use crate::serials::repository::ddb_serial_repository::DDBSerialRepository;
+    use crate::serials::repository::SerialRepository;
+    use crate::utils::ddb::{build_db_client, create_key_table};
+    use crate::utils::testing::test_store;
+
+    async fn build_client(store: RepositoryStore) -> Client {
+        let client = build_db_client(store).await;
+        let _ = create_key_table(&client, store.table_name("serials").as_str(), "serial_id").await;
+        client
+    }
+
+    #[tokio::test]
+    async fn test_should_create_get_serial() {
+        let store = test_store(module_path!(), "test_should_create_get_serial");
+        let repo = DDBSerialRepository::new(build_client(store).await, store.table_name("serials").as_str());
+        let serial = SerialEntity::new("1234-5678", "journal", SerialFrequency::Quarterly);
+        assert_eq!(1, repo.create(&serial).await.expect("should create serial"));
+        assert!(repo.create(&serial).await.is_err());
+
+        let loaded = repo.get(serial.serial_id.as_str()).await.expect("should get serial");
+        assert_eq!(serial.title, loaded.title);
+        assert_eq!(SerialFrequency::Quarterly, loaded.frequency);
+        assert!(repo.get("unknown").await.is_err());
+    }
+}
@@ -1,2 +1,7 @@
 pub mod ddb;
 pub mod date;
+pub mod metrics;
+pub mod region;
+pub(crate) mod signing;
+#[cfg(test)]
+pub(crate) mod testing;
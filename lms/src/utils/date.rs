@@ -1,5 +1,8 @@
 pub const DATE_FMT: &str = "%Y-%m-%dT%H:%M:%S%.f";
 
+// json schemas describe fields written by serializer as RFC 3339 date-time strings
+pub type Rfc3339 = chrono::DateTime<chrono::Utc>;
+
 pub mod serializer {
     use chrono::{DateTime, NaiveDateTime, Utc};
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -10,8 +13,12 @@ pub mod serializer {
         time_to_json(*time).serialize(serializer)
     }
 
+    // accepts the RFC 3339 format written by serialize as well as DATE_FMT without offset
     pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveDateTime, D::Error> {
         let str_time: String = Deserialize::deserialize(deserializer)?;
+        if let Ok(time) = DateTime::parse_from_rfc3339(&str_time) {
+            return Ok(time.naive_utc());
+        }
         let time = NaiveDateTime::parse_from_str(&str_time, DATE_FMT).map_err(D::Error::custom)?;
         Ok(time)
     }
@@ -1,42 +1,103 @@
-use std::collections::HashMap;
-use std::time::Duration;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 use aws_sdk_dynamodb::Client;
 use aws_sdk_dynamodb::config::{Credentials, Region};
 use aws_sdk_dynamodb::endpoint::{DefaultResolver, Params};
 use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::operation::batch_write_item::BatchWriteItemError;
 use aws_sdk_dynamodb::operation::delete_item::DeleteItemError;
 use aws_sdk_dynamodb::operation::execute_statement::ExecuteStatementError;
 use aws_sdk_dynamodb::operation::put_item::PutItemError;
 use aws_sdk_dynamodb::operation::query::QueryError;
 use aws_sdk_dynamodb::operation::scan::ScanError;
+use aws_sdk_dynamodb::operation::transact_write_items::TransactWriteItemsError;
 use aws_sdk_dynamodb::operation::update_item::UpdateItemError;
-use aws_sdk_dynamodb::types::{AttributeDefinition, AttributeValue, GlobalSecondaryIndex, KeySchemaElement, KeyType, Projection, ProjectionType, ProvisionedThroughput, ScalarAttributeType, TableStatus};
+use aws_sdk_dynamodb::types::{AttributeDefinition, BillingMode, AttributeValue, CreateGlobalSecondaryIndexAction, GlobalSecondaryIndex, GlobalSecondaryIndexUpdate, IndexStatus, KeySchemaElement, KeyType, Projection, ProjectionType, ProvisionedThroughput, Put, ReturnValue, ScalarAttributeType, TableStatus, Tag, TimeToLiveSpecification, TimeToLiveStatus, TransactWriteItem, Update};
 use chrono::NaiveDateTime;
 use serde_json::Value;
+use tracing::log::warn;
 use crate::core::library::{LibraryError, LibraryResult, PaginatedResult};
 use crate::core::repository::RepositoryStore;
 use crate::utils::date::DATE_FMT;
+use crate::utils::region::{ClientRole, RegionSettings, HEALTH_CHECK_TIMEOUT, PRIMARY_HEALTH};
+
+// TableBilling selects the capacity mode of created tables, local and test tables use the provisioned default
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TableBilling {
+    // on-demand (PAY_PER_REQUEST) capacity, tables and indexes are billed per request without throughput
+    OnDemand,
+    Provisioned { read_capacity: i64, write_capacity: i64 },
+}
+
+impl Default for TableBilling {
+    fn default() -> Self {
+        TableBilling::Provisioned { read_capacity: 10, write_capacity: 10 }
+    }
+}
+
+impl TableBilling {
+    fn billing_mode(&self) -> BillingMode {
+        match self {
+            TableBilling::OnDemand => BillingMode::PayPerRequest,
+            TableBilling::Provisioned { .. } => BillingMode::Provisioned,
+        }
+    }
+
+    fn throughput(&self) -> Option<ProvisionedThroughput> {
+        match self {
+            TableBilling::OnDemand => None,
+            TableBilling::Provisioned { read_capacity, write_capacity } => Some(
+                ProvisionedThroughput::builder()
+                    .read_capacity_units(*read_capacity)
+                    .write_capacity_units(*write_capacity)
+                    .build()),
+        }
+    }
+}
+
+impl Display for TableBilling {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            TableBilling::OnDemand => write!(f, "on-demand"),
+            TableBilling::Provisioned { read_capacity, write_capacity } => {
+                write!(f, "provisioned {}/{} RCU/WCU", read_capacity, write_capacity)
+            }
+        }
+    }
+}
+
+// TableSettings defines settings of created tables other than their key schema
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct TableSettings {
+    pub billing: TableBilling,
+    // rejects DeleteTable requests until the protection is turned off
+    pub deletion_protection: bool,
+    pub tags: BTreeMap<String, String>,
+}
 
 pub(crate) async fn create_table(client: &Client,
                                  table_name: &str, pk: &str,
                                  gsi_pk: &str, gsi_sk: &str) -> LibraryResult<()> {
-    let gsi = GlobalSecondaryIndex::builder()
-        .index_name(format!("{}_ndx", table_name))
-        .key_schema(KeySchemaElement::builder()
-            .attribute_name(gsi_pk)
-            .key_type(KeyType::Hash).build())
-        .key_schema(KeySchemaElement::builder()
-            .attribute_name(gsi_sk)
-            .key_type(KeyType::Range).build())
-        .projection(Projection::builder().projection_type(ProjectionType::All).build())
-        .provisioned_throughput(
-            ProvisionedThroughput::builder().read_capacity_units(10).write_capacity_units(10).build())
-        .build();
+    create_table_with_settings(client, table_name, pk, Some((gsi_pk, gsi_sk)), &TableSettings::default()).await
+}
 
-    match client
+pub(crate) async fn create_key_table(client: &Client, table_name: &str, pk: &str) -> LibraryResult<()> {
+    create_table_with_settings(client, table_name, pk, None, &TableSettings::default()).await
+}
+
+// creates a table with string partition key and an optional global secondary index named <table>_ndx
+pub(crate) async fn create_table_with_settings(client: &Client,
+                                              table_name: &str, pk: &str, gsi_keys: Option<(&str, &str)>,
+                                              settings: &TableSettings) -> LibraryResult<()> {
+    let billing = settings.billing;
+    let mut req = client
         .create_table()
         .table_name(table_name)
-        .global_secondary_indexes(gsi)
+        .billing_mode(billing.billing_mode())
+        .deletion_protection_enabled(settings.deletion_protection)
         .key_schema(
             KeySchemaElement::builder()
                 .attribute_name(pk)
@@ -49,27 +110,40 @@ pub(crate) async fn create_table(client: &Client,
                 .attribute_type(ScalarAttributeType::S)
                 .build(),
         )
-        .attribute_definitions(
-            AttributeDefinition::builder()
+        .set_provisioned_throughput(billing.throughput());
+    for (key, value) in &settings.tags {
+        req = req.tags(Tag::builder().key(key).value(value).build());
+    }
+
+    if let Some((gsi_pk, gsi_sk)) = gsi_keys {
+        let gsi = GlobalSecondaryIndex::builder()
+            .index_name(format!("{}_ndx", table_name))
+            .key_schema(KeySchemaElement::builder()
                 .attribute_name(gsi_pk)
-                .attribute_type(ScalarAttributeType::S)
-                .build(),
-        )
-        .attribute_definitions(
-            AttributeDefinition::builder()
+                .key_type(KeyType::Hash).build())
+            .key_schema(KeySchemaElement::builder()
                 .attribute_name(gsi_sk)
-                .attribute_type(ScalarAttributeType::S)
-                .build(),
-        )
-        .provisioned_throughput(
-            ProvisionedThroughput::builder()
-                .read_capacity_units(10)
-                .write_capacity_units(10)
-                .build(),
-        )
-        .send()
-        .await
-    {
+                .key_type(KeyType::Range).build())
+            .projection(Projection::builder().projection_type(ProjectionType::All).build())
+            .set_provisioned_throughput(billing.throughput())
+            .build();
+        req = req
+            .global_secondary_indexes(gsi)
+            .attribute_definitions(
+                AttributeDefinition::builder()
+                    .attribute_name(gsi_pk)
+                    .attribute_type(ScalarAttributeType::S)
+                    .build(),
+            )
+            .attribute_definitions(
+                AttributeDefinition::builder()
+                    .attribute_name(gsi_sk)
+                    .attribute_type(ScalarAttributeType::S)
+                    .build(),
+            );
+    }
+
+    match req.send().await {
         Ok(_k) => {
             wait_until_table_status_is_not(client, table_name, TableStatus::Creating).await;
             Ok(())
@@ -81,22 +155,70 @@ pub(crate) async fn create_table(client: &Client,
     }
 }
 
-pub(crate) async fn delete_table(client: &Client, table_name: &str) -> LibraryResult<()> {
-    match client.delete_table().table_name(table_name).send().await {
-        Ok(_k) => {
-            wait_until_table_status_is_not(client, table_name, TableStatus::Deleting).await;
-            Ok(())
+// adds a global secondary index named <table>_<suffix> to an existing table and waits until it can be queried,
+// indexes that exist already are left as is so tables created before the index get it when they are bootstrapped again
+pub(crate) async fn create_index(client: &Client, table_name: &str, suffix: &str,
+                                 gsi_pk: &str, gsi_sk: &str, billing: TableBilling) -> LibraryResult<()> {
+    let index_name = format!("{}_{}", table_name, suffix);
+    if describe_index_status(client, table_name, index_name.as_str()).await?.is_none() {
+        let gsi = CreateGlobalSecondaryIndexAction::builder()
+            .index_name(index_name.as_str())
+            .key_schema(KeySchemaElement::builder()
+                .attribute_name(gsi_pk)
+                .key_type(KeyType::Hash).build())
+            .key_schema(KeySchemaElement::builder()
+                .attribute_name(gsi_sk)
+                .key_type(KeyType::Range).build())
+            .projection(Projection::builder().projection_type(ProjectionType::All).build())
+            .set_provisioned_throughput(billing.throughput())
+            .build();
+        let res = client
+            .update_table()
+            .table_name(table_name)
+            .attribute_definitions(AttributeDefinition::builder()
+                .attribute_name(gsi_pk)
+                .attribute_type(ScalarAttributeType::S).build())
+            .attribute_definitions(AttributeDefinition::builder()
+                .attribute_name(gsi_sk)
+                .attribute_type(ScalarAttributeType::S).build())
+            .global_secondary_index_updates(GlobalSecondaryIndexUpdate::builder().create(gsi).build())
+            .send()
+            .await;
+        // another repository may have started creating the same index in the meantime
+        if let Err(err) = res {
+            if describe_index_status(client, table_name, index_name.as_str()).await?.is_none() {
+                return Err(LibraryError::database_or_unavailable(format!("failed to create {} index due to {}",
+                                                                         index_name, err).as_str(), None, false));
+            }
         }
-        Err(err) => {
-            Err(LibraryError::database_or_unavailable(format!("failed to delete {} table due to {}",
-                                                              table_name, err).as_str(), None, false))
+    }
+    for _i in 0..30 {
+        if describe_index_status(client, table_name, index_name.as_str()).await? == Some(IndexStatus::Active) {
+            return Ok(());
         }
+        tokio::time::sleep(Duration::from_secs(1)).await;
     }
+    Err(LibraryError::unavailable(format!("{} index is not active yet", index_name).as_str(), None, true))
+}
+
+async fn describe_index_status(client: &Client, table_name: &str, index_name: &str) -> LibraryResult<Option<IndexStatus>> {
+    let out = client
+        .describe_table()
+        .table_name(table_name)
+        .send()
+        .await.map_err(|err| LibraryError::database_or_unavailable(
+        format!("failed to describe {} table due to {}", table_name, err).as_str(), None, true))?;
+    Ok(out.table()
+        .and_then(|table| table.global_secondary_indexes())
+        .unwrap_or_default()
+        .iter()
+        .find(|index| index.index_name() == Some(index_name))
+        .map(|index| index.index_status().cloned().unwrap_or(IndexStatus::Creating)))
 }
 
 async fn wait_until_table_status_is_not(client: &Client, table_name: &str, other_status: TableStatus) {
     for _i in 0..30 {
-        match describe_table(client, table_name).await {
+        match describe_table_status(client, table_name).await {
             Ok(status) => {
                 if status != other_status {
                     return;
@@ -108,7 +230,30 @@ async fn wait_until_table_status_is_not(client: &Client, table_name: &str, other
     }
 }
 
-async fn describe_table(client: &Client, table_name: &str) -> LibraryResult<TableStatus> {
+pub(crate) async fn describe_table(client: &Client, table_name: &str) -> LibraryResult<TableStatus> {
+    describe_table_status(client, table_name).await
+}
+
+// lets DynamoDB delete items once the epoch seconds of the attribute passed, deletion lags behind by up to a few days
+// so readers still compare the attribute, tables that expire items already are left as is
+pub(crate) async fn enable_time_to_live(client: &Client, table_name: &str, attribute: &str) -> LibraryResult<()> {
+    let out = client.describe_time_to_live().table_name(table_name).send().await
+        .map_err(|err| LibraryError::database_or_unavailable(
+            format!("failed to describe time to live of {} table due to {}", table_name, err).as_str(), None, true))?;
+    let status = out.time_to_live_description().and_then(|ttl| ttl.time_to_live_status());
+    if matches!(status, Some(TimeToLiveStatus::Enabled) | Some(TimeToLiveStatus::Enabling)) {
+        return Ok(());
+    }
+    client.update_time_to_live()
+        .table_name(table_name)
+        .time_to_live_specification(TimeToLiveSpecification::builder().attribute_name(attribute).enabled(true).build())
+        .send().await
+        .map_err(|err| LibraryError::database_or_unavailable(
+            format!("failed to enable time to live of {} table due to {}", table_name, err).as_str(), None, false))?;
+    Ok(())
+}
+
+async fn describe_table_status(client: &Client, table_name: &str) -> LibraryResult<TableStatus> {
     match client
         .describe_table()
         .table_name(table_name)
@@ -138,6 +283,11 @@ pub(crate) fn parse_item(value: Value) -> Result<HashMap<String, AttributeValue>
     }
 }
 
+// reverses parse_item for items that are read back into json
+pub(crate) fn parse_json_item(item: &HashMap<String, AttributeValue>) -> Value {
+    item_to_value(&AttributeValue::M(item.clone()))
+}
+
 pub(crate) fn parse_string_attribute(name: &str, map: &HashMap<String, AttributeValue>) -> Option<String> {
     if let Some(AttributeValue::S(str)) = map.get(name) {
         return Some(str.clone());
@@ -145,6 +295,35 @@ pub(crate) fn parse_string_attribute(name: &str, map: &HashMap<String, Attribute
     None
 }
 
+// nested values are stored as json strings by updates but as maps and lists by items created from json, the json
+// text is returned for both
+pub(crate) fn parse_json_attribute(name: &str, map: &HashMap<String, AttributeValue>) -> Option<String> {
+    match map.get(name) {
+        Some(AttributeValue::S(str)) => Some(str.clone()),
+        Some(value @ (AttributeValue::M(_) | AttributeValue::L(_))) => serde_json::to_string(&item_to_value(value)).ok(),
+        _ => None,
+    }
+}
+
+// string sets are stored as SS but items created from json are stored as list of strings
+pub(crate) fn parse_string_set_attribute(name: &str, map: &HashMap<String, AttributeValue>) -> Vec<String> {
+    match map.get(name) {
+        Some(AttributeValue::Ss(values)) => values.clone(),
+        Some(AttributeValue::L(values)) => values.iter()
+            .filter_map(|v| v.as_s().ok().map(|s| s.to_string())).collect(),
+        _ => vec![],
+    }
+}
+
+// DynamoDB does not allow empty sets so None is returned for empty values
+pub(crate) fn string_set(values: &[String]) -> Option<AttributeValue> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(AttributeValue::Ss(values.to_vec()))
+    }
+}
+
 pub(crate) fn parse_bool_attribute(name: &str, map: &HashMap<String, AttributeValue>) -> bool {
     if let Some(AttributeValue::Bool(b)) = map.get(name) {
         return *b;
@@ -234,6 +413,17 @@ pub(crate) fn from_ddb<T>(page: Option<&str>, page_size: usize,
 }
 
 
+fn item_to_value(item: &AttributeValue) -> Value {
+    match item {
+        AttributeValue::Bool(b) => Value::Bool(*b),
+        AttributeValue::N(n) => serde_json::from_str(n).unwrap_or(Value::Null),
+        AttributeValue::S(s) => Value::String(s.clone()),
+        AttributeValue::L(l) => Value::Array(l.iter().map(item_to_value).collect()),
+        AttributeValue::M(m) => Value::Object(m.iter().map(|(k, v)| (k.clone(), item_to_value(v))).collect()),
+        _ => Value::Null,
+    }
+}
+
 fn value_to_item(value: Value) -> AttributeValue {
     match value {
         Value::Null => AttributeValue::Null(true),
@@ -247,16 +437,39 @@ fn value_to_item(value: Value) -> AttributeValue {
     }
 }
 
-// helper method to build db-client with tracing enabled
+// overrides endpoint of DynamoDB Local, which is resolved to http://localhost:8000 otherwise
+pub(crate) const LOCAL_ENDPOINT_ENV: &str = "LMS_DYNAMODB_ENDPOINT";
+
+// helper method to build db-client with tracing enabled, clients of repositories write so they use the primary region
 pub(crate) async fn build_db_client(store: RepositoryStore) -> Client {
+    build_region_client(store, ClientRole::Write).await
+}
+
+// builds a client of the region that serves the role, reads use the secondary region of global tables while the
+// primary region fails its health check
+pub(crate) async fn build_region_client(store: RepositoryStore, role: ClientRole) -> Client {
     match store {
         RepositoryStore::DynamoDB => {
-            //Get config from environment.
-            let config = aws_config::load_from_env().await;
-            //Create the DynamoDB client.
-            Client::new(&config)
+            let settings = RegionSettings::from_env();
+            let primary = build_aws_client(settings.primary.as_deref()).await;
+            if role == ClientRole::Write || !settings.has_secondary() || is_healthy(&primary).await {
+                return primary;
+            }
+            let secondary = settings.region_for(role, false);
+            warn!("primary region {:?} is unhealthy, reading from {:?}", settings.primary, secondary);
+            build_aws_client(secondary.as_deref()).await
         }
-        RepositoryStore::LocalDynamoDB => {
+        _ => {
+            // DynamoDB Local started on another port such as the container of the dev environment or tests
+            let endpoint = std::env::var(LOCAL_ENDPOINT_ENV).ok();
+            if let Some(endpoint) = endpoint {
+                let dynamodb_local_config = aws_sdk_dynamodb::Config::builder()
+                    .region(Region::new("local"))
+                    .credentials_provider(
+                        Credentials::new("AKIDLOCALSTACK", "localstacksecret", None, None, "faked"))
+                    .endpoint_url(endpoint).build();
+                return Client::from_conf(dynamodb_local_config);
+            }
             // See https://docs.aws.amazon.com/sdk-for-rust/latest/dg/dynamodb-local.html
             let _params = Params::builder()
                 .region("local".to_string())
@@ -275,14 +488,105 @@ pub(crate) async fn build_db_client(store: RepositoryStore) -> Client {
     }
 }
 
-// helper method to build ses-client with tracing enabled
-pub async fn build_ses_client() -> aws_sdk_sns::Client {
+// client with the config from the environment, the region of the environment is used without region
+async fn build_aws_client(region: Option<&str>) -> Client {
+    let mut loader = aws_config::from_env();
+    if let Some(region) = region {
+        loader = loader.region(Region::new(region.to_string()));
+    }
+    Client::new(&loader.load().await)
+}
+
+// probes the primary region with a cheap request unless it was probed recently
+async fn is_healthy(client: &Client) -> bool {
+    let now = Instant::now();
+    if let Some(healthy) = PRIMARY_HEALTH.cached(now) {
+        return healthy;
+    }
+    let healthy = matches!(tokio::time::timeout(HEALTH_CHECK_TIMEOUT, client.list_tables().limit(1).send()).await,
+                           Ok(Ok(_)));
+    PRIMARY_HEALTH.record(healthy, now);
+    healthy
+}
+
+// pages of 1MB that a repository may scan in production, repositories are built for each request so that this
+// bounds the capacity a single request can consume
+pub(crate) const MAX_SCAN_PAGES: usize = 10;
+
+static UNBOUNDED_SCANS: AtomicBool = AtomicBool::new(false);
+
+// lifts the limit of scans in production, only the admin binary calls it for jobs that walk whole tables
+pub fn allow_unbounded_scans() {
+    UNBOUNDED_SCANS.store(true, Ordering::SeqCst);
+}
+
+// ScanGuard counts the scanned pages of a repository and rejects scans beyond its limit before they are sent
+#[derive(Debug, Default)]
+pub(crate) struct ScanGuard {
+    max_pages: Option<usize>,
+    pages: AtomicUsize,
+}
+
+impl ScanGuard {
+    pub(crate) fn new(store: RepositoryStore) -> Self {
+        match store {
+            RepositoryStore::DynamoDB if !UNBOUNDED_SCANS.load(Ordering::SeqCst) => Self::limited(MAX_SCAN_PAGES),
+            _ => Self::default(),
+        }
+    }
+
+    pub(crate) fn limited(max_pages: usize) -> Self {
+        Self {
+            max_pages: Some(max_pages),
+            pages: AtomicUsize::new(0),
+        }
+    }
+
+    // called before each page of a scan
+    pub(crate) fn check(&self, table_name: &str) -> LibraryResult<()> {
+        let pages = self.pages.fetch_add(1, Ordering::SeqCst) + 1;
+        match self.max_pages {
+            Some(max_pages) if pages > max_pages => Err(LibraryError::unavailable(
+                format!("scan of {} exceeded {} pages, unbounded scans are only allowed to admin jobs",
+                        table_name, max_pages).as_str(), Some("scan_limit".to_string()), false)),
+            _ => Ok(()),
+        }
+    }
+}
+
+// helper method to build sns-client for publishing domain events
+pub async fn build_sns_client() -> aws_sdk_sns::Client {
     //Get config from environment.
     let config = aws_config::load_from_env().await;
-    //Create the SES client.
+    //Create the SNS client.
     aws_sdk_sns::Client::new(&config)
 }
 
+// the previous name returned the SNS client as well, emails are sent with the client of build_email_client
+#[deprecated(note = "use build_sns_client")]
+#[allow(dead_code)]
+pub async fn build_ses_client() -> aws_sdk_sns::Client {
+    build_sns_client().await
+}
+
+// helper method to build sesv2-client for sending notification emails
+pub async fn build_email_client() -> aws_sdk_sesv2::Client {
+    let config = aws_config::load_from_env().await;
+    aws_sdk_sesv2::Client::new(&config)
+}
+
+// helper method to build s3-client for book covers and party documents
+pub async fn build_s3_client() -> aws_sdk_s3::Client {
+    let config = aws_config::load_from_env().await;
+    aws_sdk_s3::Client::new(&config)
+}
+
+// helper method to build sqs-client for the task queue
+pub async fn build_sqs_client() -> aws_sdk_sqs::Client {
+    let config = aws_config::load_from_env().await;
+    aws_sdk_sqs::Client::new(&config)
+}
+
 // required to enable CloudWatch error logging by the runtime
 pub fn setup_tracing() {
     tracing_subscriber::fmt()
@@ -341,6 +645,211 @@ impl From<SdkError<ExecuteStatementError>> for LibraryError {
     }
 }
 
+impl From<SdkError<TransactWriteItemsError>> for LibraryError {
+    fn from(err: SdkError<TransactWriteItemsError>) -> Self {
+        let (retryable, reason) = retryable_sdk_error(&err);
+        LibraryError::database_or_unavailable(format!("{:?}", err).as_str(), reason, retryable)
+    }
+}
+
+impl From<SdkError<BatchWriteItemError>> for LibraryError {
+    fn from(err: SdkError<BatchWriteItemError>) -> Self {
+        let (retryable, reason) = retryable_sdk_error(&err);
+        LibraryError::database_or_unavailable(format!("{:?}", err).as_str(), reason, retryable)
+    }
+}
+
+// puts new items in a single transaction, none of the items is added when one of them already exists
+pub(crate) async fn transact_put_items(client: &Client, table_name: &str, key: &str,
+                                       items: Vec<Value>) -> LibraryResult<usize> {
+    let size = items.len();
+    let mut req = client.transact_write_items();
+    for item in items {
+        let put = Put::builder()
+            .table_name(table_name)
+            .condition_expression(format!("attribute_not_exists({})", key))
+            .set_item(Some(parse_item(item)?))
+            .build();
+        req = req.transact_items(TransactWriteItem::builder().put(put).build());
+    }
+    req.send().await.map(|_| size).map_err(LibraryError::from)
+}
+
+// returns true if the transaction was canceled because the condition of its item at the index did not match
+pub(crate) fn is_transaction_condition_failed(err: &SdkError<TransactWriteItemsError>, index: usize) -> bool {
+    if let SdkError::ServiceError(ctx) = err {
+        if let TransactWriteItemsError::TransactionCanceledException(canceled) = ctx.err() {
+            return canceled.cancellation_reasons().and_then(|reasons| reasons.get(index))
+                .and_then(|reason| reason.code()) == Some("ConditionalCheckFailed");
+        }
+    }
+    false
+}
+
+// returns true if update was rejected because its condition expression did not match
+pub(crate) fn is_conditional_check_failed(err: &SdkError<UpdateItemError>) -> bool {
+    if let SdkError::ServiceError(ctx) = err {
+        return ctx.err().is_conditional_check_failed_exception();
+    }
+    false
+}
+
+// CounterUpdate describes an atomic ADD to a numeric attribute of an item so that concurrent changes are not lost,
+// decrements are conditioned on the attribute staying at or above zero
+#[derive(Debug, Clone)]
+pub(crate) struct CounterUpdate {
+    table_name: String,
+    key_name: String,
+    key_value: String,
+    attribute: String,
+    // the first increment creates the item instead of requiring an existing item
+    upsert: bool,
+    set_values: Vec<(String, AttributeValue)>,
+    condition: Option<String>,
+    condition_values: HashMap<String, AttributeValue>,
+    conflict: Option<String>,
+}
+
+impl CounterUpdate {
+    // table name is qualified by the caller like the table names of the repositories
+    pub(crate) fn new(table_name: &str, key_name: &str, key_value: &str, attribute: &str) -> Self {
+        Self {
+            table_name: table_name.to_string(),
+            key_name: key_name.to_string(),
+            key_value: key_value.to_string(),
+            attribute: attribute.to_string(),
+            upsert: false,
+            set_values: vec![],
+            condition: None,
+            condition_values: HashMap::new(),
+            conflict: None,
+        }
+    }
+
+    pub(crate) fn upsert(mut self) -> Self {
+        self.upsert = true;
+        self
+    }
+
+    // sets other attributes of the item along with the counter, e.g. updated_at
+    pub(crate) fn set(mut self, name: &str, value: AttributeValue) -> Self {
+        self.set_values.push((name.to_string(), value));
+        self
+    }
+
+    // adds a condition besides the existence of the item and the floor at zero, e.g. an upper bound of the counter
+    pub(crate) fn condition(mut self, condition: &str, values: HashMap<String, AttributeValue>) -> Self {
+        self.condition = Some(condition.to_string());
+        self.condition_values = values;
+        self
+    }
+
+    // message of the validation error returned when a condition of the counter does not hold
+    pub(crate) fn conflict(mut self, conflict: &str) -> Self {
+        self.conflict = Some(conflict.to_string());
+        self
+    }
+
+    fn update_expression(&self) -> String {
+        let add = "ADD #counter :counter_delta";
+        if self.set_values.is_empty() {
+            return add.to_string();
+        }
+        let set = self.set_values.iter().map(|(name, _)| format!("#set_{} = :set_{}", name, name))
+            .collect::<Vec<String>>().join(", ");
+        format!("SET {} {}", set, add)
+    }
+
+    fn condition_expression(&self, delta: i64) -> Option<String> {
+        let mut conditions = vec![];
+        if !self.upsert {
+            conditions.push("attribute_exists(#counter_key)".to_string());
+        }
+        if delta < 0 {
+            conditions.push("#counter >= :counter_floor".to_string());
+        }
+        if let Some(condition) = &self.condition {
+            conditions.push(format!("({})", condition));
+        }
+        if conditions.is_empty() {
+            None
+        } else {
+            Some(conditions.join(" AND "))
+        }
+    }
+
+    fn names(&self) -> HashMap<String, String> {
+        let mut names = HashMap::from([("#counter".to_string(), self.attribute.to_string())]);
+        if !self.upsert {
+            names.insert("#counter_key".to_string(), self.key_name.to_string());
+        }
+        for (name, _) in &self.set_values {
+            names.insert(format!("#set_{}", name), name.to_string());
+        }
+        names
+    }
+
+    fn values(&self, delta: i64) -> HashMap<String, AttributeValue> {
+        let mut values = self.condition_values.clone();
+        values.insert(":counter_delta".to_string(), AttributeValue::N(delta.to_string()));
+        if delta < 0 {
+            values.insert(":counter_floor".to_string(), AttributeValue::N((-delta).to_string()));
+        }
+        for (name, value) in &self.set_values {
+            values.insert(format!(":set_{}", name), value.clone());
+        }
+        values
+    }
+
+    fn conflict_error(&self, delta: i64) -> LibraryError {
+        let message = self.conflict.clone().unwrap_or_else(|| format!(
+            "{} of {} cannot be changed by {}", self.attribute, self.key_value, delta));
+        LibraryError::validation(message.as_str(), Some("400".to_string()))
+    }
+
+    // update of the counter for transactions that change the counter together with other items
+    pub(crate) fn to_update(&self, delta: i64) -> Update {
+        Update::builder()
+            .table_name(self.table_name.as_str())
+            .key(self.key_name.as_str(), AttributeValue::S(self.key_value.to_string()))
+            .update_expression(self.update_expression())
+            .set_condition_expression(self.condition_expression(delta))
+            .set_expression_attribute_names(Some(self.names()))
+            .set_expression_attribute_values(Some(self.values(delta)))
+            .build()
+    }
+}
+
+// adds delta to the counter and returns its new value, a validation error is returned when the item does not exist
+// or a condition of the counter does not hold
+pub(crate) async fn increment_attribute(client: &Client, counter: &CounterUpdate, delta: i64) -> LibraryResult<i64> {
+    client
+        .update_item()
+        .table_name(counter.table_name.as_str())
+        .key(counter.key_name.as_str(), AttributeValue::S(counter.key_value.to_string()))
+        .update_expression(counter.update_expression())
+        .set_condition_expression(counter.condition_expression(delta))
+        .set_expression_attribute_names(Some(counter.names()))
+        .set_expression_attribute_values(Some(counter.values(delta)))
+        .return_values(ReturnValue::UpdatedNew)
+        .send()
+        .await.map_err(|err| {
+        if is_conditional_check_failed(&err) {
+            counter.conflict_error(delta)
+        } else {
+            LibraryError::from(err)
+        }
+    }).map(|res| {
+        res.attributes().map(|attrs| parse_number_attribute(counter.attribute.as_str(), attrs)).unwrap_or(0)
+    })
+}
+
+// subtracts delta from the counter and returns its new value, the counter is left as is and a validation error is
+// returned when it would drop below zero
+pub(crate) async fn decrement_attribute(client: &Client, counter: &CounterUpdate, delta: i64) -> LibraryResult<i64> {
+    increment_attribute(client, counter, -delta).await
+}
+
 fn retryable_sdk_error<T>(err: &SdkError<T>) -> (bool, Option<String>) {
     match err {
         SdkError::ConstructionFailure(_) => { (false, Some("ConstructionFailure".to_string())) }
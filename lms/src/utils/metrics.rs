@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use aws_sdk_dynamodb::types::ConsumedCapacity;
+use lazy_static::lazy_static;
+use tracing::{debug, warn};
+
+// operations of repositories that take longer than the threshold are logged as slow, it can be lowered to find
+// queries that miss an index or raised for tables that are known to be slow
+pub(crate) const SLOW_OPERATION_MILLIS_ENV: &str = "LMS_SLOW_OPERATION_MILLIS";
+const DEFAULT_SLOW_OPERATION_MILLIS: u64 = 250;
+
+lazy_static! {
+    // repositories of all requests of the lambda instance record into the same metrics
+    pub(crate) static ref REPOSITORY_METRICS: RepositoryMetrics = RepositoryMetrics::new(slow_threshold_from_env());
+}
+
+fn slow_threshold_from_env() -> Duration {
+    let millis = std::env::var(SLOW_OPERATION_MILLIS_ENV).ok()
+        .and_then(|millis| millis.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_SLOW_OPERATION_MILLIS);
+    Duration::from_millis(millis)
+}
+
+// OperationStats of an operation of a table
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct OperationStats {
+    pub operations: u64,
+    pub slow_operations: u64,
+    pub total_millis: u128,
+    pub consumed_capacity: f64,
+}
+
+// RepositoryMetrics counts operations, slow operations, latency and consumed capacity of each operation of a table in
+// memory of the lambda instance, like the command metrics of the command bus
+#[derive(Debug)]
+pub(crate) struct RepositoryMetrics {
+    slow_threshold: Duration,
+    stats: Mutex<HashMap<String, OperationStats>>,
+}
+
+impl RepositoryMetrics {
+    pub(crate) fn new(slow_threshold: Duration) -> Self {
+        Self {
+            slow_threshold,
+            stats: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn stats(&self, table: &str, operation: &str) -> OperationStats {
+        let stats = self.stats.lock().unwrap_or_else(|err| err.into_inner());
+        stats.get(format!("{}#{}", table, operation).as_str()).copied().unwrap_or_default()
+    }
+
+    // records the operation and returns true when it was slow
+    pub(crate) fn record(&self, meter: &OperationMeter, elapsed: Duration, capacity: Option<f64>) -> bool {
+        let slow = elapsed >= self.slow_threshold;
+        {
+            let mut stats = self.stats.lock().unwrap_or_else(|err| err.into_inner());
+            let entry = stats.entry(format!("{}#{}", meter.table, meter.operation)).or_default();
+            entry.operations += 1;
+            entry.total_millis += elapsed.as_millis();
+            entry.consumed_capacity += capacity.unwrap_or_default();
+            if slow {
+                entry.slow_operations += 1;
+            }
+        }
+        // running totals of the operation are logged with every operation like the command metrics of the command bus
+        let stats = self.stats(meter.table.as_str(), meter.operation);
+        debug!(table = meter.table.as_str(), operation = meter.operation, operations = stats.operations,
+               slow_operations = stats.slow_operations, total_millis = stats.total_millis as u64,
+               consumed_capacity = stats.consumed_capacity, "repository metrics");
+        if slow {
+            warn!(table = meter.table.as_str(), operation = meter.operation, index = meter.index.as_deref().unwrap_or(""),
+                  key = meter.key.as_deref().unwrap_or(""), page_size = meter.page_size.unwrap_or_default(),
+                  consumed_capacity = capacity.unwrap_or_default(), elapsed_millis = elapsed.as_millis() as u64,
+                  threshold_millis = self.slow_threshold.as_millis() as u64, "slow repository operation");
+        }
+        slow
+    }
+}
+
+// OperationMeter times one request of a repository, it is started before the request is sent and finished with the
+// consumed capacity of the response, which requests return when they ask for ReturnConsumedCapacity::Total
+#[derive(Debug)]
+pub(crate) struct OperationMeter {
+    table: String,
+    operation: &'static str,
+    index: Option<String>,
+    key: Option<String>,
+    page_size: Option<usize>,
+    started_at: Instant,
+}
+
+impl OperationMeter {
+    pub(crate) fn start(table: &str, operation: &'static str) -> Self {
+        Self {
+            table: table.to_string(),
+            operation,
+            index: None,
+            key: None,
+            page_size: None,
+            started_at: Instant::now(),
+        }
+    }
+
+    pub(crate) fn index(mut self, index: &str) -> Self {
+        self.index = Some(index.to_string());
+        self
+    }
+
+    // key condition or key of the request, values are left out as they may identify patrons
+    pub(crate) fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.to_string());
+        self
+    }
+
+    pub(crate) fn page_size(mut self, page_size: usize) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    pub(crate) fn finish(&self, consumed: Option<&ConsumedCapacity>) {
+        let capacity = consumed.and_then(|consumed| consumed.capacity_units());
+        REPOSITORY_METRICS.record(self, self.started_at.elapsed(), capacity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::utils::metrics::{OperationMeter, RepositoryMetrics};
+
+    #[tokio::test]
+    async fn test_should_count_slow_operations() {
+        let metrics = RepositoryMetrics::new(Duration::from_millis(100));
+        let meter = OperationMeter::start("books", "query").index("books_ndx").key("book_status").page_size(50);
+        assert!(!metrics.record(&meter, Duration::from_millis(20), Some(0.5)));
+        assert!(metrics.record(&meter, Duration::from_millis(150), Some(2.0)));
+        assert!(!metrics.record(&OperationMeter::start("books", "get"), Duration::from_millis(5), None));
+
+        let stats = metrics.stats("books", "query");
+        assert_eq!(2, stats.operations);
+        assert_eq!(1, stats.slow_operations);
+        assert_eq!(170, stats.total_millis);
+        assert_eq!(2.5, stats.consumed_capacity);
+        assert_eq!(1, metrics.stats("books", "get").operations);
+        assert_eq!(0, metrics.stats("hold", "query").operations);
+    }
+}
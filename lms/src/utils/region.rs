@@ -0,0 +1,103 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use lazy_static::lazy_static;
+
+// regions of the tables, the primary region defaults to the region of the environment and the secondary region holds
+// replicas of the global tables
+pub(crate) const PRIMARY_REGION_ENV: &str = "LMS_PRIMARY_REGION";
+pub(crate) const SECONDARY_REGION_ENV: &str = "LMS_SECONDARY_REGION";
+
+// the primary region is probed at most once per interval by a process, lambdas keep the result across requests
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+pub(crate) const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+lazy_static! {
+    pub(crate) static ref PRIMARY_HEALTH: RegionHealth = RegionHealth::default();
+}
+
+// ClientRole selects the region that serves the requests of a client
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ClientRole {
+    // reads fail over to the secondary region while the primary region is unhealthy
+    Read,
+    // writes are fenced to the primary region so that replicas are never written concurrently in both regions, they
+    // fail while the primary region is down rather than diverge
+    Write,
+}
+
+// RegionSettings of an active-passive deployment, deployments without secondary region serve reads and writes from
+// the primary region
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct RegionSettings {
+    pub primary: Option<String>,
+    pub secondary: Option<String>,
+}
+
+impl RegionSettings {
+    pub(crate) fn from_env() -> Self {
+        let region = |name: &str| std::env::var(name).ok().filter(|region| !region.trim().is_empty());
+        Self {
+            primary: region(PRIMARY_REGION_ENV),
+            secondary: region(SECONDARY_REGION_ENV),
+        }
+    }
+
+    pub(crate) fn has_secondary(&self) -> bool {
+        self.secondary.is_some() && self.secondary != self.primary
+    }
+
+    // returns the region of a client with the role, None is the region of the environment
+    pub(crate) fn region_for(&self, role: ClientRole, primary_healthy: bool) -> Option<String> {
+        match role {
+            ClientRole::Read if !primary_healthy && self.has_secondary() => self.secondary.clone(),
+            _ => self.primary.clone(),
+        }
+    }
+}
+
+// RegionHealth caches the last health check of a region
+#[derive(Debug, Default)]
+pub(crate) struct RegionHealth {
+    last_check: Mutex<Option<(Instant, bool)>>,
+}
+
+impl RegionHealth {
+    // returns the health of the last check unless it is older than the interval
+    pub(crate) fn cached(&self, now: Instant) -> Option<bool> {
+        let last_check = self.last_check.lock().expect("region health lock");
+        last_check.filter(|(checked_at, _)| now.duration_since(*checked_at) < HEALTH_CHECK_INTERVAL)
+            .map(|(_, healthy)| healthy)
+    }
+
+    pub(crate) fn record(&self, healthy: bool, now: Instant) {
+        *self.last_check.lock().expect("region health lock") = Some((now, healthy));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+    use crate::utils::region::{ClientRole, RegionHealth, RegionSettings};
+
+    #[tokio::test]
+    async fn test_should_fence_writes_and_fail_over_reads() {
+        let settings = RegionSettings { primary: Some("us-east-1".to_string()), secondary: Some("us-west-2".to_string()) };
+        assert_eq!(Some("us-east-1".to_string()), settings.region_for(ClientRole::Read, true));
+        assert_eq!(Some("us-west-2".to_string()), settings.region_for(ClientRole::Read, false));
+        assert_eq!(Some("us-east-1".to_string()), settings.region_for(ClientRole::Write, false));
+
+        // reads stay in the primary region without secondary region
+        let settings = RegionSettings { primary: None, secondary: None };
+        assert_eq!(None, settings.region_for(ClientRole::Read, false));
+    }
+
+    #[tokio::test]
+    async fn test_should_cache_region_health() {
+        let health = RegionHealth::default();
+        let now = Instant::now();
+        assert_eq!(None, health.cached(now));
+        health.record(false, now);
+        assert_eq!(Some(false), health.cached(now + Duration::from_secs(10)));
+        assert_eq!(None, health.cached(now + Duration::from_secs(31)));
+    }
+}
@@ -0,0 +1,48 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+// HMAC-SHA256 of the message, tokens, webhook signatures and pseudonyms are all derived from it
+pub(crate) fn hmac_sha256(secret: &str, message: &str) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(message.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+// lowercase hex of the HMAC-SHA256 signature of the message
+pub(crate) fn sign_hex(secret: &str, message: &str) -> String {
+    hmac_sha256(secret, message).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// checks the hex signature of the message in constant time, signatures that are not hex never match
+pub(crate) fn verify_hex(secret: &str, message: &str, signature: &str) -> bool {
+    if !signature.len().is_multiple_of(2) || !signature.is_ascii() {
+        return false;
+    }
+    let Ok(bytes) = (0..signature.len()).step_by(2)
+        .map(|i| u8::from_str_radix(&signature[i..i + 2], 16)).collect::<Result<Vec<u8>, _>>() else {
+        return false;
+    };
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(message.as_bytes());
+    mac.verify_slice(bytes.as_slice()).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::signing::{sign_hex, verify_hex};
+
+    #[tokio::test]
+    async fn test_should_sign_and_verify() {
+        let signature = sign_hex("secret", "message");
+        assert_eq!(64, signature.len());
+        assert!(verify_hex("secret", "message", signature.as_str()));
+        assert!(verify_hex("secret", "message", signature.to_uppercase().as_str()));
+        assert!(!verify_hex("other", "message", signature.as_str()));
+        assert!(!verify_hex("secret", "other", signature.as_str()));
+        assert!(!verify_hex("secret", "message", &signature[1..]));
+        assert!(!verify_hex("secret", "message", "zz"));
+        assert!(!verify_hex("secret", "message", "é"));
+    }
+}
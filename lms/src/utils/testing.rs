@@ -0,0 +1,101 @@
+use std::process::Command;
+use std::sync::Mutex;
+use lazy_static::lazy_static;
+use testcontainers::clients::Cli;
+use uuid::Uuid;
+use crate::admin::dev::DynamoDBLocal;
+use crate::core::repository::{RepositoryStore, TablePrefix};
+use crate::utils::ddb::{build_db_client, LOCAL_ENDPOINT_ENV};
+
+// shared test support: a single DynamoDB Local container serves all tests of the test binary and every test
+// gets its own tables, so tests can run in parallel without seeing records of other tests or of previous runs
+lazy_static! {
+    static ref ENDPOINT: String = start_dynamodb_local();
+    // distinguishes tables of separate runs when tests use an endpoint that outlives them
+    static ref RUN_ID: String = format!("t{}", &Uuid::new_v4().simple().to_string()[..8]);
+}
+
+// container started for the tests, statics are not dropped so it is removed when the test binary exits
+static CONTAINER_ID: Mutex<Option<String>> = Mutex::new(None);
+
+// an endpoint given by LMS_DYNAMODB_ENDPOINT such as the docker-compose setup is used instead of a container,
+// otherwise the container sets LMS_DYNAMODB_ENDPOINT for the clients of the stores of tests
+fn start_dynamodb_local() -> String {
+    if let Ok(endpoint) = std::env::var(LOCAL_ENDPOINT_ENV) {
+        return endpoint;
+    }
+    let docker: &'static Cli = Box::leak(Box::new(Cli::default()));
+    let ddb = DynamoDBLocal::start(docker);
+    *CONTAINER_ID.lock().unwrap_or_else(|err| err.into_inner()) = Some(ddb.container_id().to_string());
+    let endpoint = ddb.endpoint.clone();
+    std::mem::forget(ddb);
+    endpoint
+}
+
+#[ctor::dtor]
+fn cleanup_dynamodb_local() {
+    if let Some(id) = CONTAINER_ID.lock().unwrap_or_else(|err| err.into_inner()).take() {
+        let _ = Command::new("docker").args(["rm", "--force", "--volumes", id.as_str()]).output();
+        return;
+    }
+    if std::env::var(LOCAL_ENDPOINT_ENV).is_err() {
+        return;
+    }
+    // the runtimes of the tests are gone by now so the tables of the run are deleted on a runtime of their own
+    if let Ok(runtime) = tokio::runtime::Builder::new_current_thread().enable_all().build() {
+        runtime.block_on(async {
+            let client = build_db_client(RepositoryStore::LocalDynamoDB).await;
+            let prefix = format!("{}_", RUN_ID.as_str());
+            let mut start: Option<String> = None;
+            loop {
+                let res = match client.list_tables().set_exclusive_start_table_name(start).send().await {
+                    Ok(res) => res,
+                    Err(_) => return,
+                };
+                for table in res.table_names().unwrap_or_default().iter().filter(|table| table.starts_with(prefix.as_str())) {
+                    let _ = client.delete_table().table_name(table).send().await;
+                }
+                start = res.last_evaluated_table_name().map(|table| table.to_string());
+                if start.is_none() {
+                    break;
+                }
+            }
+        });
+    }
+}
+
+// tests name their tables after their module and function such as catalog::domain::service::tests and
+// test_should_add_book, which becomes catalog.domain.service.test_should_add_book
+fn test_prefix(module: &str, test: &str) -> String {
+    let module = module.trim_start_matches("lms::").trim_end_matches("::tests").replace("::", ".");
+    format!("{}_{}.{}", RUN_ID.as_str(), module, test)
+}
+
+// store of a test, its clients use the endpoint of DynamoDB Local that is started on first use. Tests pass
+// module_path!() and their name, so the tables do not depend on the thread or task that runs the test
+pub(crate) fn test_store(module: &str, test: &str) -> RepositoryStore {
+    let _ = ENDPOINT.as_str();
+    RepositoryStore::PrefixedLocalDynamoDB(TablePrefix::new(test_prefix(module, test).as_str()).expect("should build table prefix"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::repository::{RepositoryStore, TablePrefix};
+    use crate::utils::testing::test_prefix;
+
+    #[tokio::test]
+    async fn test_should_prefix_tables_with_test() {
+        let prefix = test_prefix(module_path!(), "test_should_prefix_tables_with_test");
+        assert!(prefix.starts_with('t'));
+        assert!(prefix.ends_with("_utils.testing.test_should_prefix_tables_with_test"));
+        assert_eq!(prefix, test_prefix(module_path!(), "test_should_prefix_tables_with_test"));
+        let store = RepositoryStore::PrefixedLocalDynamoDB(TablePrefix::new(prefix.as_str()).expect("should build table prefix"));
+        assert_eq!(format!("{}_books", prefix), store.table_name("books"));
+    }
+
+    #[tokio::test]
+    async fn test_should_not_share_tables_between_tests() {
+        assert_ne!(test_prefix(module_path!(), "test_a"), test_prefix(module_path!(), "test_b"));
+        assert_ne!(test_prefix("lms::catalog::tests", "test_a"), test_prefix("lms::patrons::tests", "test_a"));
+    }
+}
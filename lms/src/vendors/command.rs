@@ -0,0 +1,4 @@
+pub mod add_vendor_cmd;
+pub mod find_vendors_cmd;
+pub mod get_vendor_cmd;
+pub mod update_vendor_cmd;
@@ -0,0 +1,106 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::vendors::domain::VendorService;
+use crate::vendors::dto::VendorDto;
+
+pub(crate) struct AddVendorCommand {
+    vendor_service: Box<dyn VendorService>,
+}
+
+impl AddVendorCommand {
+    pub(crate) fn new(vendor_service: Box<dyn VendorService>) -> Self {
+        Self {
+            vendor_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AddVendorCommandRequest {
+    pub name: String,
+    pub email: String,
+    pub contact_first_name: Option<String>,
+    pub contact_last_name: Option<String>,
+    pub work_phone: Option<String>,
+    pub street_address: Option<String>,
+    pub city: Option<String>,
+    pub zip_code: Option<String>,
+    pub state: Option<String>,
+    pub country: Option<String>,
+}
+
+impl AddVendorCommandRequest {
+    pub fn new(name: &str, email: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            email: email.to_string(),
+            contact_first_name: None,
+            contact_last_name: None,
+            work_phone: None,
+            street_address: None,
+            city: None,
+            zip_code: None,
+            state: None,
+            country: None,
+        }
+    }
+    pub fn build_vendor(&self) -> VendorDto {
+        let mut vendor = VendorDto::new(self.name.as_str(), self.email.as_str());
+        vendor.contact_first_name = self.contact_first_name.clone().unwrap_or_default();
+        vendor.contact_last_name = self.contact_last_name.clone().unwrap_or_default();
+        vendor.work_phone = self.work_phone.clone();
+        vendor.street_address = self.street_address.clone();
+        vendor.city = self.city.clone();
+        vendor.zip_code = self.zip_code.clone();
+        vendor.state = self.state.clone();
+        vendor.country = self.country.clone();
+        vendor
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AddVendorCommandResponse {
+    pub vendor: VendorDto,
+}
+
+impl AddVendorCommandResponse {
+    pub fn new(vendor: VendorDto) -> Self {
+        Self {
+            vendor,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<AddVendorCommandRequest, AddVendorCommandResponse> for AddVendorCommand {
+    async fn execute(&self, req: AddVendorCommandRequest) -> Result<AddVendorCommandResponse, CommandError> {
+        let vendor = req.build_vendor();
+        self.vendor_service.add_vendor(&vendor).await.map_err(CommandError::from).map(AddVendorCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+    use crate::vendors::command::add_vendor_cmd::{AddVendorCommand, AddVendorCommandRequest};
+    use crate::vendors::factory;
+
+    async fn sut_cmd(store: RepositoryStore) -> AddVendorCommand {
+        let svc = factory::create_vendor_service(&Configuration::new("test"), store).await;
+        AddVendorCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_add_vendor() {
+        let store = test_store(module_path!(), "test_should_run_add_vendor");
+        let cmd = sut_cmd(store).await;
+
+        let res = cmd.execute(AddVendorCommandRequest::new("Books Inc", "add@books.cc")).await.expect("should add vendor");
+        assert!(res.vendor.active);
+    }
+}
@@ -0,0 +1,90 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::vendors::domain::VendorQueryService;
+use crate::vendors::dto::VendorDto;
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+
+pub(crate) struct FindVendorsCommand {
+    vendor_service: Box<dyn VendorQueryService>,
+}
+
+impl FindVendorsCommand {
+    pub(crate) fn new(vendor_service: Box<dyn VendorQueryService>) -> Self {
+        Self {
+            vendor_service,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct FindVendorsCommandRequest {
+    pub(crate) page: Option<String>,
+    pub(crate) page_size: Option<usize>,
+}
+
+impl FindVendorsCommandRequest {
+    pub fn new() -> Self {
+        Self {
+            page: None,
+            page_size: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FindVendorsCommandResponse {
+    pub vendors: Vec<VendorDto>,
+    pub next_page: Option<String>,
+}
+
+impl FindVendorsCommandResponse {
+    pub fn new(vendors: Vec<VendorDto>, next_page: Option<String>) -> Self {
+        Self {
+            vendors,
+            next_page,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<FindVendorsCommandRequest, FindVendorsCommandResponse> for FindVendorsCommand {
+    async fn execute(&self, req: FindVendorsCommandRequest) -> Result<FindVendorsCommandResponse, CommandError> {
+        self.vendor_service.find_vendors(req.page.as_deref(), req.page_size.unwrap_or(DEFAULT_PAGE_SIZE))
+            .await.map_err(CommandError::from).map(|res| FindVendorsCommandResponse::new(res.records, res.next_page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+    use crate::vendors::command::add_vendor_cmd::{AddVendorCommand, AddVendorCommandRequest};
+    use crate::vendors::command::find_vendors_cmd::{FindVendorsCommand, FindVendorsCommandRequest};
+    use crate::vendors::factory;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddVendorCommand {
+        let svc = factory::create_vendor_service(&Configuration::new("test"), store).await;
+        AddVendorCommand::new(svc)
+    }
+
+    async fn build_find_cmd(store: RepositoryStore) -> FindVendorsCommand {
+        let svc = factory::create_vendor_query_service(&Configuration::new("test"), store).await;
+        FindVendorsCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_find_vendors() {
+        let store = test_store(module_path!(), "test_should_run_find_vendors");
+        let add_cmd = build_add_cmd(store).await;
+        let find_cmd = build_find_cmd(store).await;
+
+        let _ = add_cmd.execute(AddVendorCommandRequest::new("Books Inc", "find@books.cc")).await.expect("should add vendor");
+        let res = find_cmd.execute(FindVendorsCommandRequest::new()).await.expect("should find vendors");
+        assert!(!res.vendors.is_empty());
+    }
+}
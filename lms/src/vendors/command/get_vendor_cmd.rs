@@ -0,0 +1,84 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::vendors::domain::VendorQueryService;
+use crate::vendors::dto::VendorDto;
+
+pub(crate) struct GetVendorCommand {
+    vendor_service: Box<dyn VendorQueryService>,
+}
+
+impl GetVendorCommand {
+    pub(crate) fn new(vendor_service: Box<dyn VendorQueryService>) -> Self {
+        Self {
+            vendor_service,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetVendorCommandRequest {
+    pub(crate) vendor_id: String,
+}
+
+impl GetVendorCommandRequest {
+    pub fn new(vendor_id: &str) -> Self {
+        Self {
+            vendor_id: vendor_id.to_string(),
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GetVendorCommandResponse {
+    pub vendor: VendorDto,
+}
+
+impl GetVendorCommandResponse {
+    pub fn new(vendor: VendorDto) -> Self {
+        Self {
+            vendor,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<GetVendorCommandRequest, GetVendorCommandResponse> for GetVendorCommand {
+    async fn execute(&self, req: GetVendorCommandRequest) -> Result<GetVendorCommandResponse, CommandError> {
+        self.vendor_service.find_vendor_by_id(req.vendor_id.as_str())
+            .await.map_err(CommandError::from).map(GetVendorCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+    use crate::vendors::command::add_vendor_cmd::{AddVendorCommand, AddVendorCommandRequest};
+    use crate::vendors::command::get_vendor_cmd::{GetVendorCommand, GetVendorCommandRequest};
+    use crate::vendors::factory;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddVendorCommand {
+        let svc = factory::create_vendor_service(&Configuration::new("test"), store).await;
+        AddVendorCommand::new(svc)
+    }
+
+    async fn build_get_cmd(store: RepositoryStore) -> GetVendorCommand {
+        let svc = factory::create_vendor_query_service(&Configuration::new("test"), store).await;
+        GetVendorCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_get_vendor() {
+        let store = test_store(module_path!(), "test_should_run_get_vendor");
+        let add_cmd = build_add_cmd(store).await;
+        let get_cmd = build_get_cmd(store).await;
+
+        let res = add_cmd.execute(AddVendorCommandRequest::new("Books Inc", "get@books.cc")).await.expect("should add vendor");
+        let loaded = get_cmd.execute(GetVendorCommandRequest::new(res.vendor.vendor_id.as_str())).await.expect("should get vendor");
+        assert_eq!(res.vendor.email, loaded.vendor.email);
+    }
+}
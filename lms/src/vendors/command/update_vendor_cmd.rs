@@ -0,0 +1,122 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::command::{Command, CommandError};
+use crate::vendors::domain::VendorService;
+use crate::vendors::dto::VendorDto;
+
+pub(crate) struct UpdateVendorCommand {
+    vendor_service: Box<dyn VendorService>,
+}
+
+impl UpdateVendorCommand {
+    pub(crate) fn new(vendor_service: Box<dyn VendorService>) -> Self {
+        Self {
+            vendor_service,
+        }
+    }
+}
+
+// only given fields are changed, setting active to false deactivates the vendor
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct UpdateVendorCommandRequest {
+    #[serde(default)]
+    pub vendor_id: String,
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub contact_first_name: Option<String>,
+    pub contact_last_name: Option<String>,
+    pub work_phone: Option<String>,
+    pub active: Option<bool>,
+}
+
+impl UpdateVendorCommandRequest {
+    pub fn new(vendor_id: &str) -> Self {
+        Self {
+            vendor_id: vendor_id.to_string(),
+            name: None,
+            email: None,
+            contact_first_name: None,
+            contact_last_name: None,
+            work_phone: None,
+            active: None,
+        }
+    }
+    pub fn apply(&self, vendor: &mut VendorDto) {
+        if let Some(name) = &self.name {
+            vendor.name = name.to_string();
+        }
+        if let Some(email) = &self.email {
+            vendor.email = email.to_string();
+        }
+        if let Some(first_name) = &self.contact_first_name {
+            vendor.contact_first_name = first_name.to_string();
+        }
+        if let Some(last_name) = &self.contact_last_name {
+            vendor.contact_last_name = last_name.to_string();
+        }
+        if self.work_phone.is_some() {
+            vendor.work_phone = self.work_phone.clone();
+        }
+        if let Some(active) = self.active {
+            vendor.active = active;
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct UpdateVendorCommandResponse {
+    pub vendor: VendorDto,
+}
+
+impl UpdateVendorCommandResponse {
+    pub fn new(vendor: VendorDto) -> Self {
+        Self {
+            vendor,
+        }
+    }
+}
+
+#[async_trait]
+impl Command<UpdateVendorCommandRequest, UpdateVendorCommandResponse> for UpdateVendorCommand {
+    async fn execute(&self, req: UpdateVendorCommandRequest) -> Result<UpdateVendorCommandResponse, CommandError> {
+        let mut vendor = self.vendor_service.find_vendor_by_id(req.vendor_id.as_str()).await.map_err(CommandError::from)?;
+        req.apply(&mut vendor);
+        self.vendor_service.update_vendor(&vendor).await.map_err(CommandError::from).map(UpdateVendorCommandResponse::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::command::Command;
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+    use crate::vendors::command::add_vendor_cmd::{AddVendorCommand, AddVendorCommandRequest};
+    use crate::vendors::command::update_vendor_cmd::{UpdateVendorCommand, UpdateVendorCommandRequest};
+    use crate::vendors::factory;
+
+    async fn build_add_cmd(store: RepositoryStore) -> AddVendorCommand {
+        let svc = factory::create_vendor_service(&Configuration::new("test"), store).await;
+        AddVendorCommand::new(svc)
+    }
+
+    async fn build_update_cmd(store: RepositoryStore) -> UpdateVendorCommand {
+        let svc = factory::create_vendor_service(&Configuration::new("test"), store).await;
+        UpdateVendorCommand::new(svc)
+    }
+
+    #[tokio::test]
+    async fn test_should_run_update_vendor() {
+        let store = test_store(module_path!(), "test_should_run_update_vendor");
+        let add_cmd = build_add_cmd(store).await;
+        let update_cmd = build_update_cmd(store).await;
+
+        let res = add_cmd.execute(AddVendorCommandRequest::new("Books Inc", "update@books.cc")).await.expect("should add vendor");
+        let mut req = UpdateVendorCommandRequest::new(res.vendor.vendor_id.as_str());
+        req.active = Some(false);
+        let updated = update_cmd.execute(req).await.expect("should update vendor");
+        assert!(!updated.vendor.active);
+        assert_eq!("Books Inc", updated.vendor.name.as_str());
+    }
+}
@@ -0,0 +1,72 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use serde_json::{Value};
+use crate::core::controller::{AppState, command_bus, json_to_server_error, with_common_layers, ServerError};
+use crate::utils::ddb::{build_db_client, create_table};
+use crate::vendors::command::add_vendor_cmd::{AddVendorCommand, AddVendorCommandRequest, AddVendorCommandResponse};
+use crate::vendors::command::find_vendors_cmd::{FindVendorsCommand, FindVendorsCommandRequest, FindVendorsCommandResponse};
+use crate::vendors::command::get_vendor_cmd::{GetVendorCommand, GetVendorCommandRequest, GetVendorCommandResponse};
+use crate::vendors::command::update_vendor_cmd::{UpdateVendorCommand, UpdateVendorCommandRequest, UpdateVendorCommandResponse};
+use crate::vendors::domain::{VendorQueryService, VendorService};
+use crate::vendors::factory;
+
+async fn build_service(state: AppState) -> Box<dyn VendorService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_table(&client, state.store.table_name("parties").as_str(), "party_id", "kind", "normalized_email").await;
+    factory::create_vendor_service(&state.config, state.store).await
+}
+
+async fn build_query_service(state: AppState) -> Box<dyn VendorQueryService> {
+    let client = build_db_client(state.store).await;
+    let _ = create_table(&client, state.store.table_name("parties").as_str(), "party_id", "kind", "normalized_email").await;
+    factory::create_vendor_query_service(&state.config, state.store).await
+}
+
+pub(crate) async fn add_vendor(
+    State(state): State<AppState>,
+    json: Json<Value>) -> Result<Json<AddVendorCommandResponse>, ServerError> {
+    let req: AddVendorCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    let svc = build_service(state).await;
+    let res = command_bus().register(AddVendorCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn update_vendor(
+    State(state): State<AppState>,
+    Path(vendor_id): Path<String>,
+    json: Json<Value>) -> Result<Json<UpdateVendorCommandResponse>, ServerError> {
+    let mut req: UpdateVendorCommandRequest = serde_json::from_value(json.0).map_err(json_to_server_error)?;
+    req.vendor_id = vendor_id;
+    let svc = build_service(state).await;
+    let res = command_bus().register(UpdateVendorCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn find_vendor_by_id(
+    State(state): State<AppState>,
+    Path(vendor_id): Path<String>) -> Result<Json<GetVendorCommandResponse>, ServerError> {
+    let req = GetVendorCommandRequest { vendor_id };
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(GetVendorCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub(crate) async fn find_vendors(
+    State(state): State<AppState>,
+    Query(req): Query<FindVendorsCommandRequest>) -> Result<Json<FindVendorsCommandResponse>, ServerError> {
+    let svc = build_query_service(state).await;
+    let res = command_bus().register(FindVendorsCommand::new(svc)).dispatch(req).await?;
+    Ok(Json(res))
+}
+
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/vendors", post(add_vendor).get(find_vendors))
+        .route("/vendors/:id",
+               get(find_vendor_by_id).put(update_vendor));
+    with_common_layers(router, state)
+}
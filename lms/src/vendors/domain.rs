@@ -0,0 +1,19 @@
+pub mod query;
+pub mod service;
+
+use async_trait::async_trait;
+use crate::core::library::{LibraryResult, PaginatedResult};
+use crate::vendors::dto::VendorDto;
+
+// read-only lookups of vendors used by queries and by other contexts
+#[async_trait]
+pub(crate) trait VendorQueryService: Sync + Send {
+    async fn find_vendor_by_id(&self, id: &str) -> LibraryResult<VendorDto>;
+    async fn find_vendors(&self, page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<VendorDto>>;
+}
+
+#[async_trait]
+pub(crate) trait VendorService: VendorQueryService {
+    async fn add_vendor(&self, vendor: &VendorDto) -> LibraryResult<VendorDto>;
+    async fn update_vendor(&self, vendor: &VendorDto) -> LibraryResult<VendorDto>;
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use async_trait::async_trait;
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult, PartyKind};
+use crate::parties::repository::PartyRepository;
+use crate::vendors::domain::VendorQueryService;
+use crate::vendors::dto::VendorDto;
+
+pub(crate) struct VendorQueryServiceImpl {
+    party_repository: Box<dyn PartyRepository>,
+}
+
+impl VendorQueryServiceImpl {
+    pub(crate) fn new(party_repository: Box<dyn PartyRepository>) -> Self {
+        Self {
+            party_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl VendorQueryService for VendorQueryServiceImpl {
+    async fn find_vendor_by_id(&self, id: &str) -> LibraryResult<VendorDto> {
+        let party = self.party_repository.get(id).await?;
+        if party.kind != PartyKind::Organization {
+            return Err(LibraryError::not_found(format!("vendor not found for {}", id).as_str()));
+        }
+        Ok(VendorDto::from(&party))
+    }
+
+    async fn find_vendors(&self, page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<VendorDto>> {
+        let res = self.party_repository.query(
+            &HashMap::from([("kind".to_string(), PartyKind::Organization.to_string())]), page, page_size).await?;
+        let records = res.records.iter().map(VendorDto::from).collect();
+        Ok(PaginatedResult::new(page, page_size, res.next_page, records))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::utils::testing::test_store;
+    use crate::vendors::domain::{VendorQueryService, VendorService};
+    use crate::vendors::dto::VendorDto;
+    use crate::vendors::factory;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn VendorQueryService> {
+        factory::create_vendor_query_service(&Configuration::new("test"), store).await
+    }
+
+    async fn vendor_svc(store: RepositoryStore) -> Box<dyn VendorService> {
+        factory::create_vendor_service(&Configuration::new("test"), store).await
+    }
+
+    #[tokio::test]
+    async fn test_should_find_vendors() {
+        let store = test_store(module_path!(), "test_should_find_vendors");
+        let query_svc = sut_svc(store).await;
+        let vendor = VendorDto::new("Query Books", "query@books.cc");
+        let _ = vendor_svc(store).await.add_vendor(&vendor).await.expect("should add vendor");
+
+        let loaded = query_svc.find_vendor_by_id(vendor.vendor_id.as_str()).await.expect("should find vendor");
+        assert_eq!(vendor.name, loaded.name);
+        let res = query_svc.find_vendors(None, 500).await.expect("should find vendors");
+        assert!(res.records.iter().any(|v| v.vendor_id == vendor.vendor_id));
+    }
+}
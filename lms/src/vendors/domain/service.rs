@@ -0,0 +1,173 @@
+use async_trait::async_trait;
+use crate::core::domain::Configuration;
+use crate::core::library::{LibraryError, LibraryResult, PaginatedResult, PartyKind};
+use crate::parties::domain::model::{AddressEntity, PartyEntity};
+use crate::parties::repository::PartyRepository;
+use crate::vendors::domain::{VendorQueryService, VendorService};
+use crate::vendors::dto::VendorDto;
+
+pub(crate) struct VendorServiceImpl {
+    party_repository: Box<dyn PartyRepository>,
+    query_service: Box<dyn VendorQueryService>,
+}
+
+impl VendorServiceImpl {
+    pub(crate) fn new(_config: &Configuration, party_repository: Box<dyn PartyRepository>,
+                      query_service: Box<dyn VendorQueryService>) -> Self {
+        VendorServiceImpl {
+            party_repository,
+            query_service,
+        }
+    }
+
+    fn validate(vendor: &VendorDto) -> LibraryResult<()> {
+        if vendor.name.is_empty() || vendor.email.is_empty() {
+            return Err(LibraryError::validation("vendor name and email are required", Some("400".to_string())));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl VendorService for VendorServiceImpl {
+    async fn add_vendor(&self, vendor: &VendorDto) -> LibraryResult<VendorDto> {
+        VendorServiceImpl::validate(vendor)?;
+        self.party_repository.create(&PartyEntity::from(vendor)).await?;
+        self.find_vendor_by_id(vendor.vendor_id.as_str()).await
+    }
+
+    async fn update_vendor(&self, vendor: &VendorDto) -> LibraryResult<VendorDto> {
+        VendorServiceImpl::validate(vendor)?;
+        let existing = self.find_vendor_by_id(vendor.vendor_id.as_str()).await?;
+        let mut entity = PartyEntity::from(vendor);
+        entity.version = existing.version;
+        self.party_repository.update(&entity).await?;
+        self.find_vendor_by_id(vendor.vendor_id.as_str()).await
+    }
+}
+
+#[async_trait]
+impl VendorQueryService for VendorServiceImpl {
+    async fn find_vendor_by_id(&self, id: &str) -> LibraryResult<VendorDto> {
+        self.query_service.find_vendor_by_id(id).await
+    }
+
+    async fn find_vendors(&self, page: Option<&str>, page_size: usize) -> LibraryResult<PaginatedResult<VendorDto>> {
+        self.query_service.find_vendors(page, page_size).await
+    }
+}
+
+impl From<&PartyEntity> for VendorDto {
+    fn from(other: &PartyEntity) -> Self {
+        let mut vendor = Self {
+            vendor_id: other.party_id.to_string(),
+            version: other.version,
+            name: other.organization_name.to_string(),
+            email: other.email.to_string(),
+            contact_first_name: other.first_name.to_string(),
+            contact_last_name: other.last_name.to_string(),
+            work_phone: other.work_phone.clone(),
+            street_address: None,
+            city: None,
+            zip_code: None,
+            state: None,
+            country: None,
+            active: other.active,
+            created_at: other.created_at,
+            updated_at: other.updated_at,
+        };
+        if let Some(address) = &other.address {
+            vendor.street_address = Some(address.street_address.to_string());
+            vendor.city = Some(address.city.to_string());
+            vendor.zip_code = Some(address.zip_code.to_string());
+            vendor.state = Some(address.state.to_string());
+            vendor.country = Some(address.country.to_string());
+        }
+        vendor
+    }
+}
+
+impl From<&VendorDto> for PartyEntity {
+    fn from(other: &VendorDto) -> Self {
+        let mut vendor = PartyEntity::new(PartyKind::Organization, other.email.as_str());
+        vendor.party_id = other.vendor_id.to_string();
+        vendor.version = other.version;
+        vendor.organization_name = other.name.to_string();
+        vendor.first_name = other.contact_first_name.to_string();
+        vendor.last_name = other.contact_last_name.to_string();
+        vendor.work_phone = other.work_phone.clone();
+        vendor.active = other.active;
+        vendor.created_at = other.created_at;
+        vendor.updated_at = other.updated_at;
+        if let (Some(street_address), Some(city), Some(zip_code), Some(state), Some(country)) =
+        (&other.street_address, &other.city, &other.zip_code, &other.state, &other.country) {
+            vendor.address = Some(AddressEntity {
+                street_address: street_address.to_string(),
+                city: city.to_string(),
+                zip_code: zip_code.to_string(),
+                state: state.to_string(),
+                country: country.to_string(),
+                latitude: None,
+                longitude: None,
+                created_at: other.created_at,
+                updated_at: other.updated_at,
+            });
+        }
+        vendor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::domain::Configuration;
+    use crate::core::repository::RepositoryStore;
+    use crate::patrons::domain::PatronService;
+    use crate::patrons::dto::PatronDto;
+    use crate::patrons::factory::create_patron_service;
+    use crate::utils::testing::test_store;
+    use crate::vendors::domain::VendorService;
+    use crate::vendors::dto::VendorDto;
+    use crate::vendors::factory;
+
+    async fn sut_svc(store: RepositoryStore) -> Box<dyn VendorService> {
+        factory::create_vendor_service(&Configuration::new("test"), store).await
+    }
+
+    async fn patron_svc(store: RepositoryStore) -> Box<dyn PatronService> {
+        create_patron_service(&Configuration::new("test"), store).await
+    }
+
+    #[tokio::test]
+    async fn test_should_add_update_vendor() {
+        let store = test_store(module_path!(), "test_should_add_update_vendor");
+        let vendor_svc = sut_svc(store).await;
+
+        let mut vendor = VendorDto::new("Books Inc", "orders@books.cc");
+        vendor.work_phone = Some("555-1000".to_string());
+        let added = vendor_svc.add_vendor(&vendor).await.expect("should add vendor");
+        assert_eq!(vendor.name, added.name);
+        assert!(added.active);
+
+        vendor.contact_first_name = "Jane".to_string();
+        vendor.active = false;
+        let updated = vendor_svc.update_vendor(&vendor).await.expect("should update vendor");
+        assert_eq!("Jane", updated.contact_first_name.as_str());
+        assert_eq!(Some("555-1000".to_string()), updated.work_phone);
+        assert!(!updated.active);
+        // updates are applied against latest version
+        let _ = vendor_svc.update_vendor(&vendor).await.expect("should update vendor again");
+
+        let res = vendor_svc.find_vendors(None, 500).await.expect("should find vendors");
+        assert!(res.records.iter().any(|v| v.vendor_id == vendor.vendor_id));
+    }
+
+    #[tokio::test]
+    async fn test_should_not_find_patron_as_vendor() {
+        let store = test_store(module_path!(), "test_should_not_find_patron_as_vendor");
+        let vendor_svc = sut_svc(store).await;
+        let patron = PatronDto::new("not_vendor@example.com");
+        let _ = patron_svc(store).await.add_patron(&patron).await.expect("should add patron");
+        assert!(vendor_svc.find_vendor_by_id(patron.patron_id.as_str()).await.is_err());
+        assert!(vendor_svc.add_vendor(&VendorDto::new("", "no_name@books.cc")).await.is_err());
+    }
+}
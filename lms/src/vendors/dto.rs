@@ -0,0 +1,69 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::core::domain::Identifiable;
+
+// Vendor abstracts organization supplying books to the library.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct VendorDto {
+    pub vendor_id: String,
+    pub version: i64,
+    pub name: String,
+    pub email: String,
+    pub contact_first_name: String,
+    pub contact_last_name: String,
+    pub work_phone: Option<String>,
+    pub street_address: Option<String>,
+    pub city: Option<String>,
+    pub zip_code: Option<String>,
+    pub state: Option<String>,
+    pub country: Option<String>,
+    pub active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl VendorDto {
+    pub(crate) fn new(name: &str, email: &str) -> Self {
+        Self {
+            vendor_id: Uuid::new_v4().to_string(),
+            version: 0,
+            name: name.to_string(),
+            email: email.to_string(),
+            contact_first_name: "".to_string(),
+            contact_last_name: "".to_string(),
+            work_phone: None,
+            street_address: None,
+            city: None,
+            zip_code: None,
+            state: None,
+            country: None,
+            active: true,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl Identifiable for VendorDto {
+    fn id(&self) -> String {
+        self.vendor_id.to_string()
+    }
+
+    fn version(&self) -> i64 {
+        self.version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::vendors::dto::VendorDto;
+
+    #[tokio::test]
+    async fn test_should_build_vendor() {
+        let vendor = VendorDto::new("Books Inc", "orders@books.cc");
+        assert_eq!("Books Inc", vendor.name.as_str());
+        assert_eq!("orders@books.cc", vendor.email.as_str());
+        assert!(vendor.active);
+    }
+}
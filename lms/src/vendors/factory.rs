@@ -0,0 +1,17 @@
+use crate::core::domain::Configuration;
+use crate::core::repository::RepositoryStore;
+use crate::parties::factory;
+use crate::vendors::domain::{VendorQueryService, VendorService};
+use crate::vendors::domain::query::VendorQueryServiceImpl;
+use crate::vendors::domain::service::VendorServiceImpl;
+
+pub(crate) async fn create_vendor_query_service(_config: &Configuration, store: RepositoryStore) -> Box<dyn VendorQueryService> {
+    let party_repo = factory::create_party_repository(store).await;
+    Box::new(VendorQueryServiceImpl::new(party_repo))
+}
+
+pub(crate) async fn create_vendor_service(config: &Configuration, store: RepositoryStore) -> Box<dyn VendorService> {
+    let party_repo = factory::create_party_repository(store).await;
+    let query_svc = create_vendor_query_service(config, store).await;
+    Box::new(VendorServiceImpl::new(config, party_repo, query_svc))
+}
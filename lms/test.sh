@@ -1 +1 @@
-cargo test -- --test-threads=1
+cargo test